@@ -0,0 +1,84 @@
+# dcg message catalog: English (bundled default).
+#
+# Syntax is a subset of Project Fluent (https://projectfluent.org):
+# `id = value`, indented continuation lines, `.attribute = value`,
+# `{ $variable }` placeables, and `{ $n -> [one] ... *[other] ... }` plural
+# selectors. Any message missing from a translated catalog falls back to this
+# file, so translations can be partial.
+#
+# Pack rules are translated with `rule-<pack id, dots as dashes>-<pattern>`
+# messages carrying `.reason`, `.explanation`, and `.recovery` attributes, e.g.
+#
+#   rule-core-git-reset-hard =
+#       .reason = git reset --hard destroys uncommitted changes.
+
+## Hook deny message (permissionDecisionReason / agent-facing text)
+
+denial-title = BLOCKED by dcg
+denial-short = BLOCKED by dcg: { $reason }
+denial-explain-tip = Tip: dcg explain "{ $command }"
+denial-reason = Reason: { $reason }
+denial-explanation-label = Explanation:
+denial-rule = Rule: { $rule }
+denial-pack = Pack: { $pack }
+denial-command = Command: { $command }
+denial-ask-user = If this operation is truly needed, ask the user for explicit permission and have them run the command manually.
+
+explanation-missing-rule = Matched destructive pattern { $rule }. No additional explanation is available yet. See pack documentation for details.
+explanation-missing-pack = Matched destructive pack { $pack }. No additional explanation is available yet. See pack documentation for details.
+explanation-missing = Matched a destructive pattern. No additional explanation is available yet. See pack documentation for details.
+
+allowlist-expired-note = Note: the { $layer } allowlist entry for this rule expired ({ $expiry }) and is no longer honored. Re-add it with a new --expires date, or remove it with `dcg allowlist prune`.
+
+## Terminal denial box
+
+box-header = BLOCKED: Destructive Command Detected
+box-header-short = COMMAND BLOCKED
+box-command = Command:
+box-explanation = Explanation:
+box-alternatives = Safe alternatives:
+box-allow-once = To allow once:
+
+footer-learn-more = Learn more:
+footer-false-positive = False positive? File an issue:
+
+## Warn mode
+
+warning-header = dcg WARNING (allowed by policy):
+warning-explanation = Explanation:
+warning-rule = Rule:
+warning-pack = Pack:
+warning-command = Command:
+warning-recovery = If this goes wrong:
+warning-informational = No hook JSON deny was emitted; this warning is informational.
+
+reminder-label = Reminder:
+reminder-expired-allowlist =
+    { $count ->
+        [one] { $count } allowlist entry has expired and no longer applies; run `dcg allowlist prune` to clean up
+       *[other] { $count } allowlist entries have expired and no longer apply; run `dcg allowlist prune` to clean up
+    }
+
+## Stop hook session summary
+
+session-summary =
+    { $total ->
+        [one] dcg: 1 command checked this session ({ $denied } denied, { $warned } warned, { $failed } failed)
+       *[other] dcg: { $total } commands checked this session ({ $denied } denied, { $warned } warned, { $failed } failed)
+    }
+
+## Contextual suggestions
+
+suggest-git-stash = Consider using 'git stash' first to save your changes.
+suggest-git-clean-dry-run = Use 'git clean -n' first to preview what would be deleted.
+suggest-force-with-lease = Consider using '--force-with-lease' for safer force pushing.
+suggest-rm-verify = Verify the path carefully before running rm -rf manually.
+suggest-db-backup = Consider backing up the database/table before dropping.
+suggest-kubectl-dry-run = Use 'kubectl delete --dry-run=client' to preview changes first.
+suggest-docker-df = Use 'docker system df' to see what would be affected.
+suggest-terraform-plan = Use 'terraform plan -destroy' to preview changes first.
+
+## dcg test
+
+test-would-block = WOULD BE BLOCKED
+test-would-allow = WOULD BE ALLOWED
@@ -0,0 +1,264 @@
+//! Hook adapters for OpenAI Codex CLI and Gemini CLI.
+//!
+//! Both agents share the core evaluator; only hook payload parsing and denial
+//! serialization differ from the Claude Code protocol in [`crate::hook`].
+//!
+//! - **Gemini CLI** (`BeforeTool` hook): the payload carries
+//!   `hook_event_name: "BeforeTool"`, `tool_name: "run_shell_command"`, and
+//!   `tool_input.command`. A denial is `{"decision": "deny", "reason": ...,
+//!   "systemMessage": ...}`, where `reason` goes back to the model and
+//!   `systemMessage` is shown to the user.
+//! - **Codex CLI**: the shell tool passes the command as an argv array
+//!   (`{"command": ["bash", "-lc", "git reset --hard"], "cwd": ...}`). A denial
+//!   is `{"decision": "deny", "reason": ...}`.
+//!
+//! Both formats carry the same `ruleId`/`packId`/`severity`/allow-once metadata
+//! as the Claude-compatible output.
+
+use crate::hook::{DenyDetails, HookInput, Remediation};
+use serde::Serialize;
+use std::borrow::Cow;
+
+/// Gemini CLI hook event that runs before a tool call.
+const GEMINI_BEFORE_TOOL_EVENT: &str = "BeforeTool";
+
+/// Shells whose `-c` script is unwrapped from a Codex argv array.
+const ARGV_SHELLS: &[&str] = &["bash", "sh", "zsh", "dash", "ksh"];
+
+/// Metadata shared by every adapter's denial payload.
+#[derive(Debug, Serialize)]
+pub struct DenialDetails {
+    /// Short allow-once code (if a pending exception was recorded).
+    #[serde(rename = "allowOnceCode", skip_serializing_if = "Option::is_none")]
+    pub allow_once_code: Option<String>,
+
+    /// Full hash for allow-once disambiguation (if available).
+    #[serde(rename = "allowOnceFullHash", skip_serializing_if = "Option::is_none")]
+    pub allow_once_full_hash: Option<String>,
+
+    /// Stable rule identifier (e.g., "core.git:reset-hard").
+    #[serde(rename = "ruleId", skip_serializing_if = "Option::is_none")]
+    pub rule_id: Option<String>,
+
+    /// Pack identifier that matched (e.g., "core.git").
+    #[serde(rename = "packId", skip_serializing_if = "Option::is_none")]
+    pub pack_id: Option<String>,
+
+    /// Severity level of the matched pattern.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub severity: Option<crate::packs::Severity>,
+
+    /// Confidence score for this match (0.0-1.0).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f64>,
+
+    /// Safe variant of the blocked command, templated against its arguments.
+    #[serde(
+        rename = "suggestedAlternative",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub suggested_alternative: Option<String>,
+
+    /// Remediation suggestions for the blocked command.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remediation: Option<Remediation>,
+
+    /// Version of the structured `details` payload (only with `structured_deny`).
+    #[serde(rename = "schemaVersion", skip_serializing_if = "Option::is_none")]
+    pub schema_version: Option<u32>,
+
+    /// Structured match details (only with `structured_deny`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<DenyDetails>,
+}
+
+/// Gemini CLI denial output for `BeforeTool` hooks.
+#[derive(Debug, Serialize)]
+pub struct GeminiHookOutput<'a> {
+    /// Hook decision (`deny`).
+    pub decision: &'static str,
+
+    /// Explanation returned to the model.
+    pub reason: Cow<'a, str>,
+
+    /// Short message shown to the user.
+    #[serde(rename = "systemMessage")]
+    pub system_message: Cow<'a, str>,
+
+    /// Rule, pack, and allow-once metadata.
+    #[serde(flatten)]
+    pub details: DenialDetails,
+}
+
+/// Codex CLI denial output.
+#[derive(Debug, Serialize)]
+pub struct CodexHookOutput<'a> {
+    /// Hook decision (`deny`).
+    pub decision: &'static str,
+
+    /// Explanation returned to the model.
+    pub reason: Cow<'a, str>,
+
+    /// Rule, pack, and allow-once metadata.
+    #[serde(flatten)]
+    pub details: DenialDetails,
+}
+
+/// Returns `true` for Gemini CLI `BeforeTool` payloads.
+#[must_use]
+pub fn is_gemini_payload(input: &HookInput) -> bool {
+    input
+        .hook_event_name
+        .as_deref()
+        .is_some_and(|name| name.eq_ignore_ascii_case(GEMINI_BEFORE_TOOL_EVENT))
+}
+
+/// Extract the shell command from a Codex payload.
+///
+/// Codex passes argv arrays, either at the top level (`command`) or inside
+/// `tool_input.command`. Returns `None` when neither is an array, so other
+/// protocols are tried.
+#[must_use]
+pub fn codex_command(input: &HookInput) -> Option<String> {
+    let argv = input
+        .command
+        .as_ref()
+        .and_then(serde_json::Value::as_array)
+        .or_else(|| {
+            input
+                .tool_input
+                .as_ref()
+                .and_then(|tool_input| tool_input.command.as_ref())
+                .and_then(serde_json::Value::as_array)
+        })?;
+
+    let args: Vec<&str> = argv
+        .iter()
+        .map(serde_json::Value::as_str)
+        .collect::<Option<_>>()?;
+    command_from_argv(&args)
+}
+
+/// Turn an argv array into the command string dcg evaluates.
+///
+/// `["bash", "-lc", "<script>"]` (and other shells with `-c`) yields the script
+/// itself; anything else is joined with shell quoting so arguments containing
+/// spaces stay intact.
+#[must_use]
+pub fn command_from_argv(args: &[&str]) -> Option<String> {
+    let (program, rest) = args.split_first()?;
+
+    let shell = program.rsplit('/').next().unwrap_or(program);
+    if ARGV_SHELLS.contains(&shell) {
+        if let [flag, script] = rest {
+            if flag.starts_with('-') && flag.ends_with('c') && !script.is_empty() {
+                return Some((*script).to_string());
+            }
+        }
+    }
+
+    let joined = args
+        .iter()
+        .map(|arg| quote_arg(arg))
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!joined.trim().is_empty()).then_some(joined)
+}
+
+/// Quote a single argument for a POSIX shell when it needs it.
+fn quote_arg(arg: &str) -> Cow<'_, str> {
+    let is_plain = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=:,+@%^".contains(c));
+    if is_plain {
+        Cow::Borrowed(arg)
+    } else {
+        Cow::Owned(format!("'{}'", arg.replace('\'', r"'\''")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(json: &str) -> HookInput {
+        serde_json::from_str(json).expect("hook input must parse")
+    }
+
+    #[test]
+    fn test_command_from_argv_unwraps_shell_script() {
+        assert_eq!(
+            command_from_argv(&["bash", "-lc", "git reset --hard && ls"]).as_deref(),
+            Some("git reset --hard && ls")
+        );
+        assert_eq!(
+            command_from_argv(&["/bin/sh", "-c", "rm -rf build"]).as_deref(),
+            Some("rm -rf build")
+        );
+    }
+
+    #[test]
+    fn test_command_from_argv_quotes_plain_argv() {
+        assert_eq!(
+            command_from_argv(&["git", "commit", "-m", "it's done"]).as_deref(),
+            Some(r"git commit -m 'it'\''s done'")
+        );
+        assert_eq!(
+            command_from_argv(&["rm", "-rf", "/"]).as_deref(),
+            Some("rm -rf /")
+        );
+        assert_eq!(command_from_argv(&[]), None);
+    }
+
+    #[test]
+    fn test_codex_command_from_payload() {
+        let input = parse(r#"{"command":["bash","-lc","git push --force"],"cwd":"/repo"}"#);
+        assert_eq!(codex_command(&input).as_deref(), Some("git push --force"));
+
+        let input =
+            parse(r#"{"tool_name":"shell","tool_input":{"command":["git","clean","-fdx"]}}"#);
+        assert_eq!(codex_command(&input).as_deref(), Some("git clean -fdx"));
+
+        let input = parse(r#"{"tool_name":"Bash","tool_input":{"command":"ls"}}"#);
+        assert_eq!(codex_command(&input), None);
+    }
+
+    #[test]
+    fn test_gemini_payload_detection() {
+        let input = parse(
+            r#"{"hook_event_name":"BeforeTool","tool_name":"run_shell_command","tool_input":{"command":"ls"}}"#,
+        );
+        assert!(is_gemini_payload(&input));
+
+        let input = parse(r#"{"hook_event_name":"PreToolUse","tool_name":"Bash"}"#);
+        assert!(!is_gemini_payload(&input));
+    }
+
+    #[test]
+    fn test_gemini_output_shape() {
+        let output = GeminiHookOutput {
+            decision: "deny",
+            reason: Cow::Borrowed("BLOCKED by dcg\n\nReason: destructive"),
+            system_message: Cow::Borrowed("BLOCKED by dcg: destructive"),
+            details: DenialDetails {
+                allow_once_code: Some("ab12".to_string()),
+                allow_once_full_hash: None,
+                rule_id: Some("core.git:reset-hard".to_string()),
+                pack_id: Some("core.git".to_string()),
+                severity: None,
+                confidence: None,
+                suggested_alternative: None,
+                remediation: None,
+                schema_version: None,
+                details: None,
+            },
+        };
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["decision"], "deny");
+        assert_eq!(json["systemMessage"], "BLOCKED by dcg: destructive");
+        assert_eq!(json["ruleId"], "core.git:reset-hard");
+        assert_eq!(json["allowOnceCode"], "ab12");
+        assert!(json.get("details").is_none());
+    }
+}
@@ -17,6 +17,7 @@
 //! - Continue: `CONTINUE_SESSION_ID` env var
 //! - Codex CLI: `CODEX_CLI=1` env var
 //! - Gemini CLI: `GEMINI_CLI=1` env var
+//! - Cursor: `CURSOR_AGENT=1` env var
 //!
 //! # Usage
 //!
@@ -53,6 +54,8 @@ pub enum Agent {
     CodexCli,
     /// Google Gemini CLI.
     GeminiCli,
+    /// Cursor editor agent.
+    Cursor,
     /// A custom agent specified by name.
     Custom(String),
     /// Unknown or undetected agent.
@@ -73,6 +76,7 @@ impl Agent {
             Self::Continue => "continue",
             Self::CodexCli => "codex-cli",
             Self::GeminiCli => "gemini-cli",
+            Self::Cursor => "cursor",
             Self::Custom(name) => name,
             Self::Unknown => "unknown",
         }
@@ -89,6 +93,7 @@ impl Agent {
                 | Self::Continue
                 | Self::CodexCli
                 | Self::GeminiCli
+                | Self::Cursor
         )
     }
 
@@ -107,6 +112,7 @@ impl Agent {
     /// - `"continue"` -> `Continue`
     /// - `"codex"`, `"codex-cli"`, `"codex_cli"` -> `CodexCli`
     /// - `"gemini"`, `"gemini-cli"`, `"gemini_cli"` -> `GeminiCli`
+    /// - `"cursor"` -> `Cursor`
     /// - `"unknown"` -> `Unknown`
     /// - Any other value -> `Custom(value)`
     #[must_use]
@@ -119,6 +125,7 @@ impl Agent {
             "continue" => Self::Continue,
             "codexcli" | "codex" => Self::CodexCli,
             "geminicli" | "gemini" => Self::GeminiCli,
+            "cursor" => Self::Cursor,
             "unknown" => Self::Unknown,
             _ => Self::Custom(name.to_string()),
         }
@@ -134,6 +141,7 @@ impl fmt::Display for Agent {
             Self::Continue => write!(f, "Continue"),
             Self::CodexCli => write!(f, "Codex CLI"),
             Self::GeminiCli => write!(f, "Gemini CLI"),
+            Self::Cursor => write!(f, "Cursor"),
             Self::Custom(name) => write!(f, "{name}"),
             Self::Unknown => write!(f, "Unknown"),
         }
@@ -363,6 +371,15 @@ fn detect_from_environment() -> Option<DetectionResult> {
         ));
     }
 
+    // Cursor detection
+    if std::env::var("CURSOR_AGENT").is_ok() {
+        return Some(DetectionResult::new(
+            Agent::Cursor,
+            DetectionMethod::Environment,
+            Some("CURSOR_AGENT".to_string()),
+        ));
+    }
+
     None
 }
 
@@ -458,6 +475,7 @@ mod tests {
         assert_eq!(Agent::Continue.config_key(), "continue");
         assert_eq!(Agent::CodexCli.config_key(), "codex-cli");
         assert_eq!(Agent::GeminiCli.config_key(), "gemini-cli");
+        assert_eq!(Agent::Cursor.config_key(), "cursor");
         assert_eq!(Agent::Unknown.config_key(), "unknown");
         assert_eq!(
             Agent::Custom("my-agent".to_string()).config_key(),
@@ -474,6 +492,7 @@ mod tests {
         assert_eq!(Agent::from_name("continue"), Agent::Continue);
         assert_eq!(Agent::from_name("codex-cli"), Agent::CodexCli);
         assert_eq!(Agent::from_name("gemini-cli"), Agent::GeminiCli);
+        assert_eq!(Agent::from_name("cursor"), Agent::Cursor);
         assert_eq!(Agent::from_name("unknown"), Agent::Unknown);
 
         // Variations
@@ -501,6 +520,7 @@ mod tests {
         assert_eq!(format!("{}", Agent::Continue), "Continue");
         assert_eq!(format!("{}", Agent::CodexCli), "Codex CLI");
         assert_eq!(format!("{}", Agent::GeminiCli), "Gemini CLI");
+        assert_eq!(format!("{}", Agent::Cursor), "Cursor");
         assert_eq!(format!("{}", Agent::Unknown), "Unknown");
         assert_eq!(
             format!("{}", Agent::Custom("MyAgent".to_string())),
@@ -567,6 +587,7 @@ mod env_tests {
         "CONTINUE_SESSION_ID",
         "CODEX_CLI",
         "GEMINI_CLI",
+        "CURSOR_AGENT",
     ];
 
     fn with_env_var<F, R>(key: &str, value: &str, f: F) -> R
@@ -693,6 +714,15 @@ mod env_tests {
         });
     }
 
+    #[test]
+    fn test_detect_cursor_env() {
+        with_env_var("CURSOR_AGENT", "1", || {
+            let result = detect_agent_with_details();
+            assert_eq!(result.agent, Agent::Cursor);
+            assert_eq!(result.method, DetectionMethod::Environment);
+        });
+    }
+
     #[test]
     fn test_detect_unknown_no_env() {
         // Acquire lock to prevent race conditions with parallel tests
@@ -0,0 +1,529 @@
+//! Shell alias and function expansion.
+//!
+//! Agents often run commands through the user's interactive shell, where
+//! `alias grf='git reset --hard'` makes `grf` a destructive command that no
+//! pack pattern recognizes. This module loads alias and function definitions
+//! from a snapshot file (`alias -p` / `typeset -f` output) and expands them at
+//! command positions before evaluation.
+//!
+//! # Design Principles
+//!
+//! - **Shell-like**: Only unquoted words in command position are expanded, an
+//!   alias never re-expands itself, and a value ending in a blank makes the
+//!   next word eligible too (as in bash).
+//! - **Bounded**: Nested expansion stops after [`MAX_EXPANSION_DEPTH`] levels.
+//! - **Fail-open**: Missing or unreadable files simply contribute nothing.
+
+use crate::config::AliasesConfig;
+use regex::Regex;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// Default snapshot file name inside the dcg config directory.
+pub const DEFAULT_ALIAS_SNAPSHOT_FILENAME: &str = "aliases.sh";
+
+/// Maximum nesting of alias/function expansion.
+pub const MAX_EXPANSION_DEPTH: usize = 8;
+
+/// Reserved words after which the next word is again in command position.
+const COMMAND_POSITION_KEYWORDS: &[&str] = &[
+    "!", "{", "if", "then", "elif", "else", "while", "until", "do", "time",
+];
+
+/// Default alias snapshot path (`<config dir>/dcg/aliases.sh`).
+#[must_use]
+pub fn default_snapshot_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("dcg").join(DEFAULT_ALIAS_SNAPSHOT_FILENAME))
+}
+
+/// Alias and function definitions used to expand commands before matching.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AliasTable {
+    aliases: HashMap<String, String>,
+    functions: HashMap<String, String>,
+}
+
+impl AliasTable {
+    /// Load definitions from the configured files.
+    ///
+    /// When `files` is empty the default snapshot is used. Returns `None` when
+    /// expansion is disabled or no definitions were found.
+    #[must_use]
+    pub fn load(config: &AliasesConfig) -> Option<Self> {
+        if !config.enabled {
+            return None;
+        }
+
+        let paths: Vec<PathBuf> = if config.files.is_empty() {
+            default_snapshot_path().into_iter().collect()
+        } else {
+            config
+                .files
+                .iter()
+                .filter_map(|file| crate::config::resolve_config_path_value(file, None))
+                .collect()
+        };
+
+        let mut table = Self::default();
+        for path in &paths {
+            table.load_file(path);
+        }
+        (!table.is_empty()).then_some(table)
+    }
+
+    /// Merge definitions from one file, ignoring I/O errors.
+    pub fn load_file(&mut self, path: &Path) {
+        if let Ok(content) = std::fs::read_to_string(path) {
+            self.extend(Self::parse(&content));
+        }
+    }
+
+    /// Parse `alias -p`, zsh `alias`, and `typeset -f` style definitions.
+    #[must_use]
+    pub fn parse(content: &str) -> Self {
+        let mut table = Self::default();
+        let mut function_ranges: Vec<Range<usize>> = Vec::new();
+
+        for caps in FUNCTION_HEADER.captures_iter(content) {
+            let (Some(header), Some(name)) = (caps.get(0), caps.get(1).or_else(|| caps.get(2)))
+            else {
+                continue;
+            };
+            if function_ranges
+                .iter()
+                .any(|range| range.contains(&header.start()))
+            {
+                continue;
+            }
+            let open = header.end() - 1;
+            let Some(close) = matching_brace(content.as_bytes(), open) else {
+                continue;
+            };
+            let body = content[open + 1..close]
+                .lines()
+                .map(|line| line.trim().trim_end_matches(';').trim_end())
+                .filter(|line| !line.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n");
+            function_ranges.push(header.start()..close + 1);
+            table.functions.insert(name.as_str().to_string(), body);
+        }
+
+        let mut offset = 0;
+        for line in content.split_inclusive('\n') {
+            let start = offset;
+            offset += line.len();
+            if function_ranges.iter().any(|range| range.contains(&start)) {
+                continue;
+            }
+            if let Some((name, value)) = parse_alias_line(line.trim()) {
+                table.aliases.insert(name, value);
+            }
+        }
+
+        table
+    }
+
+    /// Add definitions from `other`, replacing existing names.
+    pub fn extend(&mut self, other: Self) {
+        self.aliases.extend(other.aliases);
+        self.functions.extend(other.functions);
+    }
+
+    /// Whether the table has no definitions.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.aliases.is_empty() && self.functions.is_empty()
+    }
+
+    /// Number of aliases loaded.
+    #[must_use]
+    pub fn alias_count(&self) -> usize {
+        self.aliases.len()
+    }
+
+    /// Number of shell functions loaded.
+    #[must_use]
+    pub fn function_count(&self) -> usize {
+        self.functions.len()
+    }
+
+    /// Aliases sorted by name.
+    #[must_use]
+    pub fn aliases(&self) -> Vec<(&str, &str)> {
+        sorted_entries(&self.aliases)
+    }
+
+    /// Shell functions sorted by name (bodies are newline-separated).
+    #[must_use]
+    pub fn functions(&self) -> Vec<(&str, &str)> {
+        sorted_entries(&self.functions)
+    }
+
+    /// Expand aliases and functions in command position.
+    ///
+    /// Returns the command unchanged (borrowed) when nothing was expanded.
+    #[must_use]
+    pub fn expand<'a>(&self, command: &'a str) -> Cow<'a, str> {
+        let mut active = Vec::new();
+        self.expand_inner(command, &mut active, 0)
+            .map_or(Cow::Borrowed(command), Cow::Owned)
+    }
+
+    fn expand_inner(
+        &self,
+        command: &str,
+        active: &mut Vec<String>,
+        depth: usize,
+    ) -> Option<String> {
+        if depth >= MAX_EXPANSION_DEPTH {
+            return None;
+        }
+
+        let bytes = command.as_bytes();
+        let mut out = String::with_capacity(command.len());
+        let mut changed = false;
+        let mut copied_to = 0;
+        let mut command_position = true;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            match bytes[i] {
+                b' ' | b'\t' => {
+                    i += 1;
+                    continue;
+                }
+                b';' | b'&' | b'|' | b'\n' | b'(' | b'`' => {
+                    command_position = true;
+                    i += 1;
+                    continue;
+                }
+                b')' => {
+                    i += 1;
+                    continue;
+                }
+                _ => {}
+            }
+
+            let end = word_end(bytes, i);
+            let word = &command[i..end];
+
+            if !command_position {
+                i = end;
+                continue;
+            }
+
+            if is_assignment(word) || COMMAND_POSITION_KEYWORDS.contains(&word) {
+                i = end;
+                continue;
+            }
+
+            command_position = false;
+            if active.iter().any(|name| name == word) {
+                i = end;
+                continue;
+            }
+
+            if let Some(value) = self.aliases.get(word) {
+                active.push(word.to_string());
+                let expanded = self
+                    .expand_inner(value, active, depth + 1)
+                    .unwrap_or_else(|| value.clone());
+                active.pop();
+
+                out.push_str(&command[copied_to..i]);
+                out.push_str(expanded.trim_end());
+                copied_to = end;
+                changed = true;
+                command_position = value.ends_with([' ', '\t']);
+                i = end;
+                continue;
+            }
+
+            if let Some(body) = self.functions.get(word) {
+                let seg_end = segment_end(bytes, end);
+                let args = command[end..seg_end].trim_end();
+                let args_end = end + args.len();
+                let args = args.trim_start();
+                let body = substitute_positional(body, args);
+
+                active.push(word.to_string());
+                let expanded = self.expand_inner(&body, active, depth + 1).unwrap_or(body);
+                active.pop();
+
+                out.push_str(&command[copied_to..i]);
+                out.push_str(&expanded);
+                copied_to = args_end;
+                changed = true;
+                i = seg_end;
+                continue;
+            }
+
+            i = end;
+        }
+
+        if !changed {
+            return None;
+        }
+        out.push_str(&command[copied_to..]);
+        Some(out)
+    }
+}
+
+fn sorted_entries(map: &HashMap<String, String>) -> Vec<(&str, &str)> {
+    let mut entries: Vec<_> = map.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+    entries.sort_unstable();
+    entries
+}
+
+/// Find the end of the shell word starting at `start`, honoring quotes and
+/// backslash escapes.
+fn word_end(bytes: &[u8], start: usize) -> usize {
+    let mut i = start;
+    let mut quote: Option<u8> = None;
+    while i < bytes.len() {
+        let b = bytes[i];
+        match (quote, b) {
+            (Some(b'"') | None, b'\\') => i += 1,
+            (Some(q), _) if b == q => quote = None,
+            (Some(_), _) => {}
+            (None, b'\'' | b'"') => quote = Some(b),
+            (None, b' ' | b'\t' | b'\n' | b';' | b'&' | b'|' | b'(' | b')' | b'`') => break,
+            (None, _) => {}
+        }
+        i += 1;
+    }
+    i.min(bytes.len())
+}
+
+/// Find the end of the simple command starting at `start` (the next unquoted
+/// separator, or the end of input).
+fn segment_end(bytes: &[u8], start: usize) -> usize {
+    let mut i = start;
+    while i < bytes.len() {
+        match bytes[i] {
+            b';' | b'&' | b'|' | b'\n' | b')' | b'`' => return i,
+            b' ' | b'\t' | b'(' => i += 1,
+            _ => i = word_end(bytes, i),
+        }
+    }
+    bytes.len()
+}
+
+fn is_assignment(word: &str) -> bool {
+    word.split_once('=').is_some_and(|(name, _)| {
+        !name.is_empty()
+            && !name.starts_with(|c: char| c.is_ascii_digit())
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
+fn is_name(word: &str) -> bool {
+    !word.is_empty()
+        && !word.starts_with('-')
+        && word
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | ':' | '+' | '@'))
+}
+
+/// Parse `alias name='value'`, `alias -- name=value`, or zsh `name=value`.
+fn parse_alias_line(line: &str) -> Option<(String, String)> {
+    let (rest, bare) = line.strip_prefix("alias ").map_or((line, true), |rest| {
+        let rest = rest.trim_start();
+        (rest.strip_prefix("-- ").unwrap_or(rest).trim_start(), false)
+    });
+    let (name, raw_value) = rest.split_once('=')?;
+    // zsh prints bare `name=value` and quotes values containing blanks, so an
+    // unquoted bare value with blanks is some other shell line, not an alias.
+    let unquoted_blanks =
+        !raw_value.starts_with(['\'', '"']) && raw_value.contains(char::is_whitespace);
+    if !is_name(name) || (bare && unquoted_blanks) {
+        return None;
+    }
+    let value = unquote(raw_value);
+    (!value.trim().is_empty()).then(|| (name.to_string(), value))
+}
+
+/// Remove shell quoting (`'...'`, `"..."`, `\x`, and bash's `'\''` idiom).
+fn unquote(raw: &str) -> String {
+    let mut out = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => out.extend(chars.by_ref().take_while(|&c| c != '\'')),
+            '"' => {
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' => {
+                            if let Some(next) = chars.next() {
+                                if !matches!(next, '"' | '\\' | '$' | '`') {
+                                    out.push('\\');
+                                }
+                                out.push(next);
+                            }
+                        }
+                        _ => out.push(c),
+                    }
+                }
+            }
+            '\\' => out.extend(chars.next()),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Function headers: `name() {`, `name () \n{` (`typeset -f`), `function name {`.
+static FUNCTION_HEADER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?m)^[ \t]*(?:function[ \t]+([A-Za-z_][\w.:+@-]*)[ \t]*(?:\([ \t]*\))?|([A-Za-z_][\w.:+@-]*)[ \t]*\([ \t]*\))\s*\{",
+    )
+    .expect("function header regex compiles")
+});
+
+/// Find the `}` matching the `{` at `open`, skipping quoted text.
+fn matching_brace(bytes: &[u8], open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut i = open;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' => depth += 1,
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            b'\'' | b'"' => {
+                i = word_end(bytes, i).saturating_sub(1);
+            }
+            b'\\' => i += 1,
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Substitute positional parameters in a function body with call arguments.
+fn substitute_positional(body: &str, args: &str) -> String {
+    let words: Vec<&str> = args.split_whitespace().collect();
+    let mut out = body
+        .replace("\"$@\"", args)
+        .replace("\"$*\"", args)
+        .replace("$@", args)
+        .replace("$*", args);
+    for n in (1..=9).rev() {
+        let value = words.get(n - 1).copied().unwrap_or("");
+        out = out
+            .replace(&format!("\"${{{n}}}\""), value)
+            .replace(&format!("\"${n}\""), value)
+            .replace(&format!("${{{n}}}"), value)
+            .replace(&format!("${n}"), value);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(content: &str) -> AliasTable {
+        AliasTable::parse(content)
+    }
+
+    #[test]
+    fn parses_bash_alias_p_output() {
+        let t =
+            table("alias grf='git reset --hard'\nalias ll='ls -la'\nalias q='echo '\\''hi'\\'''\n");
+        assert_eq!(t.alias_count(), 3);
+        assert_eq!(t.expand("grf HEAD~1"), "git reset --hard HEAD~1");
+        assert_eq!(t.expand("q"), "echo 'hi'");
+    }
+
+    #[test]
+    fn parses_zsh_alias_output() {
+        let t = table("gpf='git push --force'\nnuke=rm\n");
+        assert_eq!(t.expand("gpf origin main"), "git push --force origin main");
+        assert_eq!(t.expand("nuke -rf /"), "rm -rf /");
+    }
+
+    #[test]
+    fn expands_in_every_command_position() {
+        let t = table("alias grf='git reset --hard'\n");
+        assert_eq!(
+            t.expand("cd repo && grf; echo done | FOO=1 grf"),
+            "cd repo && git reset --hard; echo done | FOO=1 git reset --hard"
+        );
+        assert_eq!(
+            t.expand("if true; then grf; fi"),
+            "if true; then git reset --hard; fi"
+        );
+        assert_eq!(t.expand("echo $(grf)"), "echo $(git reset --hard)");
+    }
+
+    #[test]
+    fn leaves_arguments_and_quoted_words_alone() {
+        let t = table("alias grf='git reset --hard'\n");
+        assert!(matches!(t.expand("echo grf"), Cow::Borrowed(_)));
+        assert!(matches!(t.expand("'grf'"), Cow::Borrowed(_)));
+        assert!(matches!(t.expand("\\grf"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn self_referencing_alias_terminates() {
+        let t = table("alias ls='ls --color=auto'\nalias rm='rm -i'\nalias del='rm'\n");
+        assert_eq!(t.expand("ls"), "ls --color=auto");
+        assert_eq!(t.expand("del -rf x"), "rm -i -rf x");
+    }
+
+    #[test]
+    fn trailing_space_expands_next_word() {
+        let t = table("alias sudo='sudo '\nalias grf='git reset --hard'\n");
+        assert_eq!(t.expand("sudo grf"), "sudo git reset --hard");
+    }
+
+    #[test]
+    fn parses_typeset_f_functions() {
+        let t = table("nuke () \n{ \n    rm -rf \"$1\";\n    echo gone\n}\nalias ll='ls -la'\n");
+        assert_eq!(t.function_count(), 1);
+        assert_eq!(t.alias_count(), 1);
+        assert_eq!(
+            t.expand("nuke build && ll"),
+            "rm -rf build\necho gone && ls -la"
+        );
+    }
+
+    #[test]
+    fn parses_one_line_functions() {
+        let t = table("wipe() { git clean -fdx \"$@\"; }\nfunction drop { git stash clear; }\n");
+        assert_eq!(t.expand("wipe -e .env"), "git clean -fdx -e .env");
+        assert_eq!(t.expand("drop"), "git stash clear");
+    }
+
+    #[test]
+    fn disabled_config_loads_nothing() {
+        let config = AliasesConfig {
+            enabled: false,
+            ..AliasesConfig::default()
+        };
+        assert!(AliasTable::load(&config).is_none());
+    }
+
+    #[test]
+    fn loads_configured_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("aliases.sh");
+        std::fs::write(&path, "alias gpf='git push --force'\n").unwrap();
+        let config = AliasesConfig {
+            enabled: true,
+            files: vec![path.to_string_lossy().to_string()],
+        };
+        let t = AliasTable::load(&config).expect("aliases loaded");
+        assert_eq!(t.expand("gpf"), "git push --force");
+    }
+}
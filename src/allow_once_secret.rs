@@ -0,0 +1,365 @@
+//! Secret key for allow-once short codes.
+//!
+//! A pending exception's `full_hash` (and the short code derived from it) is
+//! an HMAC-SHA256 of the denied command keyed with this secret. `dcg
+//! allow-once <code>` recomputes the hash and ignores records that do not
+//! verify, so a record appended to `pending_exceptions.jsonl` by hand (or by
+//! an agent) cannot be redeemed.
+//!
+//! The secret is resolved in this order:
+//!
+//! 1. `DCG_ALLOW_ONCE_SECRET`, when set and non-empty.
+//! 2. The OS keychain (macOS Keychain, Windows Credential Manager, Secret
+//!    Service on Linux) when dcg is built with the `keyring` feature.
+//! 3. `allow_once.secret` in the dcg config directory, created with mode 0600.
+//!
+//! When none exists, a random secret is generated on first use and stored in
+//! the keychain, or in the file when no keychain is reachable.
+
+use std::env;
+use std::fmt::Write as FmtWrite;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use rand::RngCore;
+
+use crate::config::resolve_config_path_value;
+use crate::pending_exceptions::ENV_ALLOW_ONCE_SECRET;
+
+/// Environment override for the fallback secret file path.
+pub const ENV_ALLOW_ONCE_SECRET_PATH: &str = "DCG_ALLOW_ONCE_SECRET_PATH";
+
+const SECRET_FILE: &str = "allow_once.secret";
+const KEYRING_SERVICE: &str = "dcg";
+const KEYRING_USER: &str = "allow-once-secret";
+
+/// Process-wide secret used when hashing pending exceptions.
+static CURRENT: OnceLock<Option<String>> = OnceLock::new();
+
+/// Where the secret was loaded from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecretSource {
+    Env,
+    Keyring,
+    File(PathBuf),
+}
+
+impl SecretSource {
+    #[must_use]
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Env => format!("${ENV_ALLOW_ONCE_SECRET}"),
+            Self::Keyring => format!("OS keychain ({KEYRING_SERVICE}/{KEYRING_USER})"),
+            Self::File(path) => path.display().to_string(),
+        }
+    }
+}
+
+/// A resolved secret and where it lives.
+#[derive(Clone)]
+pub struct AllowOnceSecret {
+    value: String,
+    source: SecretSource,
+}
+
+impl std::fmt::Debug for AllowOnceSecret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AllowOnceSecret")
+            .field("value", &"<redacted>")
+            .field("source", &self.source)
+            .finish()
+    }
+}
+
+impl AllowOnceSecret {
+    #[must_use]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    #[must_use]
+    pub const fn source(&self) -> &SecretSource {
+        &self.source
+    }
+}
+
+/// Resolve the default fallback file path (env override or ~/.config/dcg/..).
+#[must_use]
+pub fn default_path(cwd: Option<&Path>) -> PathBuf {
+    if let Ok(value) = env::var(ENV_ALLOW_ONCE_SECRET_PATH) {
+        if let Some(path) = resolve_config_path_value(&value, cwd) {
+            return path;
+        }
+    }
+
+    // Check XDG-style path first (~/.config/dcg/), then platform-native
+    if let Some(xdg_dir) = dirs::home_dir().map(|h| h.join(".config").join("dcg")) {
+        if xdg_dir.exists() {
+            return xdg_dir.join(SECRET_FILE);
+        }
+    }
+
+    let base =
+        dirs::config_dir().unwrap_or_else(|| dirs::home_dir().unwrap_or_default().join(".config"));
+    base.join("dcg").join(SECRET_FILE)
+}
+
+/// Secret for hashing pending exceptions in this process, resolved once.
+///
+/// Returns `None` when no secret can be loaded or created; codes then fall
+/// back to plain SHA-256. Unit tests always get `None` so they never touch the
+/// real keychain or config directory.
+#[must_use]
+pub fn current() -> Option<&'static str> {
+    CURRENT
+        .get_or_init(|| {
+            if cfg!(test) {
+                return None;
+            }
+            resolve(&default_path(None), true)
+                .ok()
+                .map(|secret| secret.value)
+        })
+        .as_deref()
+}
+
+/// Load the secret, generating and storing one if none exists.
+///
+/// `use_keyring` is ignored when dcg is built without the `keyring` feature.
+///
+/// # Errors
+///
+/// Returns an I/O error if the fallback file cannot be read or created.
+pub fn resolve(file: &Path, use_keyring: bool) -> io::Result<AllowOnceSecret> {
+    if let Some(value) = env_secret() {
+        return Ok(AllowOnceSecret {
+            value,
+            source: SecretSource::Env,
+        });
+    }
+
+    let keyring_available = if use_keyring {
+        match os_keyring::load() {
+            Ok(Some(value)) => {
+                return Ok(AllowOnceSecret {
+                    value,
+                    source: SecretSource::Keyring,
+                });
+            }
+            Ok(None) => true,
+            Err(_) => false,
+        }
+    } else {
+        false
+    };
+
+    if let Some(value) = read_secret_file(file)? {
+        return Ok(AllowOnceSecret {
+            value,
+            source: SecretSource::File(file.to_path_buf()),
+        });
+    }
+
+    let value = generate();
+    if keyring_available && os_keyring::store(&value).is_ok() {
+        // Another process may have stored one concurrently; the keychain wins.
+        if let Ok(Some(stored)) = os_keyring::load() {
+            return Ok(AllowOnceSecret {
+                value: stored,
+                source: SecretSource::Keyring,
+            });
+        }
+    }
+
+    match write_secret_file(file, &value, true) {
+        Ok(()) => Ok(AllowOnceSecret {
+            value,
+            source: SecretSource::File(file.to_path_buf()),
+        }),
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+            let value = read_secret_file(file)?.ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "allow-once secret file is empty",
+                )
+            })?;
+            Ok(AllowOnceSecret {
+                value,
+                source: SecretSource::File(file.to_path_buf()),
+            })
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Replace the stored secret with a new random one, in the same place.
+///
+/// Pending codes issued under the old secret stop verifying. Active
+/// allow-once entries are not affected.
+///
+/// # Errors
+///
+/// Returns an error when the secret comes from `DCG_ALLOW_ONCE_SECRET`, or
+/// when the keychain or file cannot be written.
+pub fn rotate(file: &Path, use_keyring: bool) -> io::Result<AllowOnceSecret> {
+    let existing = resolve(file, use_keyring)?;
+    let value = generate();
+    match existing.source {
+        SecretSource::Env => {
+            return Err(io::Error::other(format!(
+                "the secret comes from ${ENV_ALLOW_ONCE_SECRET}; change it there"
+            )));
+        }
+        SecretSource::Keyring => os_keyring::store(&value).map_err(io::Error::other)?,
+        SecretSource::File(ref path) => write_secret_file(path, &value, false)?,
+    }
+    Ok(AllowOnceSecret {
+        value,
+        source: existing.source,
+    })
+}
+
+fn env_secret() -> Option<String> {
+    env::var(ENV_ALLOW_ONCE_SECRET)
+        .ok()
+        .filter(|value| !value.is_empty())
+}
+
+fn generate() -> String {
+    let mut bytes = [0u8; 32];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        let _ = write!(hex, "{byte:02x}");
+    }
+    hex
+}
+
+fn read_secret_file(path: &Path) -> io::Result<Option<String>> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => {
+            let value = content.trim();
+            Ok((!value.is_empty()).then(|| value.to_string()))
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Write the secret with owner-only permissions.
+///
+/// With `create_new`, fails with `AlreadyExists` instead of overwriting;
+/// otherwise replaces the file atomically.
+fn write_secret_file(path: &Path, value: &str, create_new: bool) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let target = if create_new {
+        path.to_path_buf()
+    } else {
+        path.with_extension("secret.tmp")
+    };
+
+    let mut options = OpenOptions::new();
+    options.write(true);
+    if create_new {
+        options.create_new(true);
+    } else {
+        options.create(true).truncate(true);
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&target)?;
+    file.write_all(value.as_bytes())?;
+    file.write_all(b"\n")?;
+    file.sync_all()?;
+
+    if !create_new {
+        std::fs::rename(&target, path)?;
+    }
+    Ok(())
+}
+
+#[cfg(all(feature = "keyring", not(test)))]
+mod os_keyring {
+    use super::{KEYRING_SERVICE, KEYRING_USER};
+
+    fn entry() -> Result<keyring::Entry, String> {
+        keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER).map_err(|e| e.to_string())
+    }
+
+    /// `Ok(None)` when the keychain works but holds no secret yet.
+    pub fn load() -> Result<Option<String>, String> {
+        match entry()?.get_password() {
+            Ok(value) => Ok(Some(value)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    pub fn store(value: &str) -> Result<(), String> {
+        entry()?.set_password(value).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(not(all(feature = "keyring", not(test))))]
+mod os_keyring {
+    pub fn load() -> Result<Option<String>, String> {
+        Err("dcg was built without keychain support".to_string())
+    }
+
+    pub fn store(_value: &str) -> Result<(), String> {
+        Err("dcg was built without keychain support".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generates_file_secret_once_and_reuses_it() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("dcg").join(SECRET_FILE);
+
+        let first = resolve(&path, false).unwrap();
+        assert_eq!(first.source(), &SecretSource::File(path.clone()));
+        assert_eq!(first.value().len(), 64);
+
+        let second = resolve(&path, false).unwrap();
+        assert_eq!(first.value(), second.value());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+
+    #[test]
+    fn rotate_replaces_file_secret() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(SECRET_FILE);
+
+        let before = resolve(&path, false).unwrap();
+        let rotated = rotate(&path, false).unwrap();
+        assert_ne!(before.value(), rotated.value());
+        assert_eq!(resolve(&path, false).unwrap().value(), rotated.value());
+        assert!(!path.with_extension("secret.tmp").exists());
+    }
+
+    #[test]
+    fn debug_output_redacts_value() {
+        let secret = AllowOnceSecret {
+            value: "abc123".to_string(),
+            source: SecretSource::Env,
+        };
+        assert!(!format!("{secret:?}").contains("abc123"));
+    }
+}
@@ -5,6 +5,9 @@
 //! - User: `~/.config/dcg/allowlist.toml`
 //! - System: `/etc/dcg/allowlist.toml` (optional)
 //!
+//! The system layer can be restricted to signed bundles; see
+//! [`crate::allowlist_bundle`].
+//!
 //! Test override:
 //! - `DCG_ALLOWLIST_SYSTEM_PATH` can override the system allowlist path
 //!   (useful for hermetic E2E tests).
@@ -12,7 +15,7 @@
 //! Design goals:
 //! - Strongly-typed model (`AllowEntry`, `AllowSelector`)
 //! - Robust parsing: invalid TOML or invalid entries must not crash the hook
-//! - Explicit, testable layering precedence (project > user > system)
+//! - Explicit, testable layering precedence (project > user > system > remote)
 
 use std::collections::HashMap;
 use std::fs;
@@ -24,6 +27,8 @@ pub enum AllowlistLayer {
     Project,
     User,
     System,
+    /// Allowlist from the synced team policy bundle (`dcg sync`).
+    Remote,
 }
 
 impl AllowlistLayer {
@@ -33,6 +38,7 @@ impl AllowlistLayer {
             Self::Project => "project",
             Self::User => "user",
             Self::System => "system",
+            Self::Remote => "remote",
         }
     }
 }
@@ -165,7 +171,7 @@ pub struct LoadedAllowlistLayer {
     pub file: AllowlistFile,
 }
 
-/// All allowlist layers, ordered by precedence (project > user > system).
+/// All allowlist layers, ordered by precedence (project > user > system > remote).
 #[derive(Debug, Clone, Default)]
 pub struct LayeredAllowlist {
     pub layers: Vec<LoadedAllowlistLayer>,
@@ -385,6 +391,151 @@ impl LayeredAllowlist {
         }
         None
     }
+
+    // =========================================================================
+    // Expiry auditing
+    // =========================================================================
+
+    /// All entries across layers whose `expires_at` / `ttl` has passed.
+    ///
+    /// Expired entries are never honored during matching; this is used to
+    /// report them (`dcg allowlist list/validate/prune`, hook reminders).
+    #[must_use]
+    pub fn expired_entries(&self) -> Vec<ExpiredAllowEntry<'_>> {
+        self.layers
+            .iter()
+            .flat_map(|layer| {
+                layer
+                    .file
+                    .entries
+                    .iter()
+                    .filter(|entry| is_expired(entry))
+                    .map(|entry| ExpiredAllowEntry {
+                        layer: layer.layer,
+                        path: &layer.path,
+                        entry,
+                    })
+            })
+            .collect()
+    }
+
+    /// Find an expired rule entry that would otherwise have matched
+    /// `(pack_id, pattern_name)` at `cwd`.
+    ///
+    /// Used on denials so a lapsed allowlist entry is reported instead of
+    /// being skipped silently.
+    #[must_use]
+    pub fn match_expired_rule_at_path(
+        &self,
+        pack_id: &str,
+        pattern_name: &str,
+        cwd: Option<&Path>,
+    ) -> Option<ExpiredAllowEntry<'_>> {
+        self.expired_entries().into_iter().find(|expired| {
+            let AllowSelector::Rule(rule_id) = &expired.entry.selector else {
+                return false;
+            };
+            pack_id != "*"
+                && rule_id.pack_id == pack_id
+                && (rule_id.pattern_name == pattern_name || rule_id.pattern_name == "*")
+                && cwd.is_none_or(|cwd| entry_path_matches(expired.entry, &cwd.to_string_lossy()))
+        })
+    }
+}
+
+/// An expired allowlist entry together with where it was loaded from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExpiredAllowEntry<'a> {
+    pub layer: AllowlistLayer,
+    pub path: &'a Path,
+    pub entry: &'a AllowEntry,
+}
+
+impl ExpiredAllowEntry<'_> {
+    /// Human-readable expiry description (`expires_at` value or `ttl ... from added_at`).
+    #[must_use]
+    pub fn expiry(&self) -> String {
+        match (&self.entry.expires_at, &self.entry.ttl) {
+            (Some(expires_at), _) => expires_at.clone(),
+            (None, Some(ttl)) => format!(
+                "ttl {ttl} from {}",
+                self.entry.added_at.as_deref().unwrap_or("<unknown>")
+            ),
+            (None, None) => "unknown".to_string(),
+        }
+    }
+}
+
+/// Remove expired `[[allow]]` entries from allowlist TOML, preserving formatting.
+///
+/// Returns the rewritten document and the entries that were removed. Entries
+/// that fail to parse are kept so `dcg allowlist validate` can still report them.
+///
+/// # Errors
+///
+/// Returns an error if `content` is not valid TOML.
+pub fn prune_expired_toml(content: &str) -> Result<(String, Vec<AllowEntry>), String> {
+    let value: toml::Value = toml::from_str(content).map_err(|e| format!("invalid TOML: {e}"))?;
+    let mut doc: toml_edit::DocumentMut =
+        content.parse().map_err(|e| format!("invalid TOML: {e}"))?;
+    let Some(arr) = doc
+        .get_mut("allow")
+        .and_then(toml_edit::Item::as_array_of_tables_mut)
+    else {
+        return Ok((content.to_string(), Vec::new()));
+    };
+
+    let expired: Vec<(usize, AllowEntry)> = value
+        .get("allow")
+        .and_then(toml::Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, item)| {
+                    let entry = parse_allow_entry(item.as_table()?).ok()?;
+                    is_expired(&entry).then_some((idx, entry))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // Comments above a removed table (such as a file header) move to the next
+    // surviving table, or to the end of the document when none is left.
+    let mut orphaned = String::new();
+    for (idx, _) in expired.iter().rev() {
+        let prefix = arr
+            .get(*idx)
+            .and_then(|table| table.decor().prefix())
+            .and_then(toml_edit::RawString::as_str)
+            .unwrap_or_default()
+            .to_string();
+        arr.remove(*idx);
+        if prefix.trim().is_empty() {
+            continue;
+        }
+        if let Some(next) = arr.get_mut(*idx) {
+            let existing = next
+                .decor()
+                .prefix()
+                .and_then(toml_edit::RawString::as_str)
+                .unwrap_or_default();
+            let merged = format!("{prefix}{existing}");
+            next.decor_mut().set_prefix(merged);
+        } else {
+            orphaned.insert_str(0, &prefix);
+        }
+    }
+    if !orphaned.is_empty() {
+        let trailing = doc.trailing().as_str().unwrap_or_default();
+        let merged = format!("{trailing}{orphaned}");
+        doc.set_trailing(merged);
+    }
+
+    Ok((
+        doc.to_string(),
+        expired.into_iter().map(|(_, entry)| entry).collect(),
+    ))
 }
 
 /// A successful allowlist match (borrowed view).
@@ -914,7 +1065,18 @@ pub fn load_default_allowlists() -> LayeredAllowlist {
         },
     );
 
-    LayeredAllowlist::load_from_paths(project, user, system)
+    let mut allowlists = LayeredAllowlist::load_from_paths(project, user, system);
+
+    // The synced team policy bundle is itself an allowlist file; it was
+    // signature-checked by `dcg sync` before being cached.
+    if let Some(path) = crate::remote::cached_policy_path() {
+        allowlists.layers.push(LoadedAllowlistLayer {
+            layer: AllowlistLayer::Remote,
+            file: load_allowlist_file(AllowlistLayer::Remote, &path),
+            path,
+        });
+    }
+    allowlists
 }
 
 fn find_repo_root(start: &Path) -> Option<PathBuf> {
@@ -951,6 +1113,22 @@ fn load_allowlist_file(layer: AllowlistLayer, path: &Path) -> AllowlistFile {
         }
     };
 
+    if layer == AllowlistLayer::System {
+        // A trusted signing key next to the system allowlist means only a
+        // correctly signed bundle is honored.
+        if let Err(message) = crate::allowlist_bundle::verify_system_allowlist(path, &content) {
+            return AllowlistFile {
+                entries: Vec::new(),
+                errors: vec![AllowlistError {
+                    layer,
+                    path: path.to_path_buf(),
+                    entry_index: None,
+                    message: format!("system allowlist ignored: {message}"),
+                }],
+            };
+        }
+    }
+
     parse_allowlist_toml(layer, path, &content)
 }
 
@@ -2040,4 +2218,70 @@ mod tests {
         assert_eq!(file.errors.len(), 1);
         assert!(file.errors[0].message.contains("invalid"));
     }
+
+    const EXPIRY_TOML: &str = r#"
+# team allowlist
+[[allow]]
+rule = "core.git:reset-hard"
+reason = "expired"
+expires_at = "2020-01-01T00:00:00Z"
+
+[[allow]]
+rule = "core.git:clean-force"
+reason = "still valid"
+expires_at = "2099-01-01"
+
+[[allow]]
+exact_command = "rm -rf ./build"
+reason = "lapsed ttl"
+added_at = "2020-01-01T00:00:00Z"
+ttl = "1h"
+"#;
+
+    #[test]
+    fn expired_entries_are_reported_per_layer() {
+        let allowlist = LayeredAllowlist {
+            layers: vec![LoadedAllowlistLayer {
+                layer: AllowlistLayer::User,
+                path: PathBuf::from("/tmp/allowlist.toml"),
+                file: parse_allowlist_toml(AllowlistLayer::User, Path::new("dummy"), EXPIRY_TOML),
+            }],
+        };
+
+        let expired = allowlist.expired_entries();
+        assert_eq!(expired.len(), 2);
+        assert_eq!(expired[0].layer, AllowlistLayer::User);
+        assert_eq!(expired[0].expiry(), "2020-01-01T00:00:00Z");
+        assert_eq!(expired[1].expiry(), "ttl 1h from 2020-01-01T00:00:00Z");
+
+        // The expired rule is not honored, but it is found for reporting.
+        assert!(allowlist.match_rule("core.git", "reset-hard").is_none());
+        let hit = allowlist
+            .match_expired_rule_at_path("core.git", "reset-hard", None)
+            .expect("expired entry should be reported");
+        assert_eq!(hit.entry.reason, "expired");
+        assert!(
+            allowlist
+                .match_expired_rule_at_path("core.git", "clean-force", None)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn prune_expired_toml_keeps_valid_entries_and_comments() {
+        let (pruned, removed) = prune_expired_toml(EXPIRY_TOML).unwrap();
+        assert_eq!(removed.len(), 2);
+        assert!(pruned.contains("# team allowlist"));
+        assert!(pruned.contains("core.git:clean-force"));
+        assert!(!pruned.contains("core.git:reset-hard"));
+        assert!(!pruned.contains("rm -rf ./build"));
+
+        let reparsed = parse_allowlist_toml(AllowlistLayer::Project, Path::new("dummy"), &pruned);
+        assert_eq!(reparsed.entries.len(), 1);
+        assert!(reparsed.errors.is_empty());
+
+        let (unchanged, removed) = prune_expired_toml(&pruned).unwrap();
+        assert!(removed.is_empty());
+        assert_eq!(unchanged, pruned);
+    }
 }
@@ -0,0 +1,283 @@
+//! Signed allowlist bundles.
+//!
+//! `dcg allowlist export` merges the allowlist layers into a single TOML file
+//! (the bundle) that is itself a valid allowlist, so it can be installed
+//! unchanged as `/etc/dcg/allowlist.toml`. Signing it with an Ed25519 key
+//! writes a detached signature next to it (`<bundle>.sig`, base64).
+//!
+//! A security team distributes the bundle, its signature, and the public key.
+//! Once the public key is installed as `allowlist-signing.pub` next to the
+//! system allowlist, dcg only honors the system layer when its signature
+//! verifies; an unsigned or tampered system allowlist is ignored and reported
+//! as an allowlist error.
+
+use std::path::{Path, PathBuf};
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use rand::RngCore;
+use toml_edit::{Array, ArrayOfTables, DocumentMut, Item, Table, value};
+
+use crate::allowlist::{AllowlistLayer, LoadedAllowlistLayer};
+
+/// Bundle format version written to `[bundle] format`.
+pub const BUNDLE_FORMAT_VERSION: i64 = 1;
+
+/// Trusted public key file, looked up next to the system allowlist.
+pub const TRUSTED_KEY_FILE: &str = "allowlist-signing.pub";
+
+/// Detached signature path for a bundle or allowlist file (`<path>.sig`).
+#[must_use]
+pub fn signature_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".sig");
+    path.with_file_name(name)
+}
+
+/// Trusted public key path for a system allowlist at `system_path`.
+#[must_use]
+pub fn trusted_key_path(system_path: &Path) -> PathBuf {
+    system_path.with_file_name(TRUSTED_KEY_FILE)
+}
+
+/// Generate a signing key, returning `(secret_key, public_key)` as base64.
+#[must_use]
+pub fn generate_keypair() -> (String, String) {
+    let mut seed = [0u8; 32];
+    rand::rngs::OsRng.fill_bytes(&mut seed);
+    let key = SigningKey::from_bytes(&seed);
+    (
+        BASE64.encode(key.to_bytes()),
+        BASE64.encode(key.verifying_key().to_bytes()),
+    )
+}
+
+/// Parse a base64 secret key (as written by `dcg allowlist keygen`).
+///
+/// # Errors
+///
+/// Returns an error if the text is not base64 for a 32-byte key.
+pub fn parse_signing_key(text: &str) -> Result<SigningKey, String> {
+    let bytes: [u8; 32] = decode_fixed(text, "signing key")?;
+    Ok(SigningKey::from_bytes(&bytes))
+}
+
+/// Parse a base64 public key.
+///
+/// # Errors
+///
+/// Returns an error if the text is not base64 for a valid Ed25519 public key.
+pub fn parse_public_key(text: &str) -> Result<VerifyingKey, String> {
+    let bytes: [u8; 32] = decode_fixed(text, "public key")?;
+    VerifyingKey::from_bytes(&bytes).map_err(|e| format!("invalid public key: {e}"))
+}
+
+/// Base64 public key for a signing key.
+#[must_use]
+pub fn public_key_string(key: &VerifyingKey) -> String {
+    BASE64.encode(key.to_bytes())
+}
+
+/// Sign `content`, returning the base64 detached signature.
+#[must_use]
+pub fn sign(content: &[u8], key: &SigningKey) -> String {
+    BASE64.encode(key.sign(content).to_bytes())
+}
+
+/// Verify a base64 detached signature over `content`.
+///
+/// # Errors
+///
+/// Returns an error if the signature is malformed or does not match.
+pub fn verify(content: &[u8], signature: &str, key: &VerifyingKey) -> Result<(), String> {
+    let bytes: [u8; 64] = decode_fixed(signature, "signature")?;
+    key.verify_strict(content, &Signature::from_bytes(&bytes))
+        .map_err(|_| "signature does not match the allowlist contents".to_string())
+}
+
+/// Check the system allowlist at `path` against the trusted key next to it.
+///
+/// Returns `Ok(())` when no trusted key is installed (signing not enforced)
+/// or when `<path>.sig` verifies.
+///
+/// # Errors
+///
+/// Returns a description of the failure when a trusted key is installed and
+/// the signature is missing, unreadable, or does not verify.
+pub fn verify_system_allowlist(path: &Path, content: &str) -> Result<(), String> {
+    let key_path = trusted_key_path(path);
+    if !key_path.exists() {
+        return Ok(());
+    }
+    let key_text = std::fs::read_to_string(&key_path)
+        .map_err(|e| format!("failed to read {}: {e}", key_path.display()))?;
+    let key = parse_public_key(&key_text)?;
+
+    let sig_path = signature_path(path);
+    let signature = std::fs::read_to_string(&sig_path).map_err(|_| {
+        format!(
+            "{} is signed-only (trusted key {}) but {} is missing",
+            path.display(),
+            key_path.display(),
+            sig_path.display()
+        )
+    })?;
+    verify(content.as_bytes(), &signature, &key)
+}
+
+/// Merge allowlist layers into a single bundle document.
+///
+/// Entries keep their precedence order (project, then user, then system) so
+/// the first match in the bundle is the one the layered lookup would pick.
+/// Each entry's fields are copied unchanged and annotated with
+/// `source_layer`.
+///
+/// # Errors
+///
+/// Returns an error if a layer file exists but cannot be read or parsed.
+pub fn export_bundle(layers: &[&LoadedAllowlistLayer]) -> Result<String, String> {
+    let mut entries = ArrayOfTables::new();
+    let mut exported_layers = Array::new();
+
+    for layer in layers {
+        if !layer.path.exists() {
+            continue;
+        }
+        let content = std::fs::read_to_string(&layer.path)
+            .map_err(|e| format!("failed to read {}: {e}", layer.path.display()))?;
+        let doc: DocumentMut = content
+            .parse()
+            .map_err(|e| format!("invalid TOML in {}: {e}", layer.path.display()))?;
+        let Some(allow) = doc.get("allow").and_then(Item::as_array_of_tables) else {
+            continue;
+        };
+        for entry in allow {
+            // Rebuild rather than clone so the source document's table
+            // positions do not leak into the bundle's ordering.
+            let mut merged = Table::new();
+            for (key, item) in entry {
+                merged.insert(key, item.clone());
+            }
+            merged.insert("source_layer", value(layer.layer.label()));
+            entries.push(merged);
+        }
+        exported_layers.push(layer.layer.label());
+    }
+
+    let mut bundle = Table::new();
+    bundle.insert("format", value(BUNDLE_FORMAT_VERSION));
+    bundle.insert("created_at", value(chrono::Utc::now().to_rfc3339()));
+    bundle.insert("layers", value(exported_layers));
+
+    let mut doc = DocumentMut::new();
+    doc.insert("bundle", Item::Table(bundle));
+    doc.insert("allow", Item::ArrayOfTables(entries));
+    Ok(doc.to_string())
+}
+
+/// Layers included in an export, honoring the `--project/--user/--system`
+/// selection (none selected means all layers).
+#[must_use]
+pub fn select_layers<'a>(
+    layers: &'a [LoadedAllowlistLayer],
+    selected: &[AllowlistLayer],
+) -> Vec<&'a LoadedAllowlistLayer> {
+    layers
+        .iter()
+        .filter(|l| selected.is_empty() || selected.contains(&l.layer))
+        .collect()
+}
+
+fn decode_fixed<const N: usize>(text: &str, what: &str) -> Result<[u8; N], String> {
+    let bytes = BASE64
+        .decode(text.trim())
+        .map_err(|e| format!("invalid {what}: {e}"))?;
+    bytes.try_into().map_err(|bytes: Vec<u8>| {
+        format!("invalid {what}: expected {N} bytes, got {}", bytes.len())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::allowlist::{AllowlistFile, parse_allowlist_toml};
+
+    fn layer(layer: AllowlistLayer, path: &Path, content: &str) -> LoadedAllowlistLayer {
+        std::fs::write(path, content).unwrap();
+        LoadedAllowlistLayer {
+            layer,
+            path: path.to_path_buf(),
+            file: AllowlistFile::default(),
+        }
+    }
+
+    #[test]
+    fn sign_and_verify_roundtrip() {
+        let (secret, public) = generate_keypair();
+        let signing = parse_signing_key(&secret).unwrap();
+        let verifying = parse_public_key(&public).unwrap();
+        assert_eq!(public_key_string(&signing.verifying_key()), public);
+
+        let signature = sign(b"[[allow]]", &signing);
+        assert!(verify(b"[[allow]]", &signature, &verifying).is_ok());
+        assert!(verify(b"[[allow]] ", &signature, &verifying).is_err());
+        assert!(verify(b"[[allow]]", "not base64!", &verifying).is_err());
+    }
+
+    #[test]
+    fn export_merges_layers_in_precedence_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = layer(
+            AllowlistLayer::Project,
+            &dir.path().join("project.toml"),
+            "[[allow]]\nrule = \"core.git:reset-hard\"\nreason = \"project\"\n",
+        );
+        let system = layer(
+            AllowlistLayer::System,
+            &dir.path().join("system.toml"),
+            "[[allow]]\nexact_command = \"rm -rf build\"\nreason = \"org\"\n",
+        );
+        let missing = LoadedAllowlistLayer {
+            layer: AllowlistLayer::User,
+            path: dir.path().join("missing.toml"),
+            file: AllowlistFile::default(),
+        };
+
+        let bundle = export_bundle(&[&project, &missing, &system]).unwrap();
+        let parsed = parse_allowlist_toml(AllowlistLayer::System, Path::new("b"), &bundle);
+        assert!(parsed.errors.is_empty(), "{:?}", parsed.errors);
+        assert_eq!(parsed.entries.len(), 2);
+        assert_eq!(parsed.entries[0].reason, "project");
+        let raw: toml::Value = toml::from_str(&bundle).unwrap();
+        assert_eq!(
+            raw["bundle"]["format"].as_integer(),
+            Some(BUNDLE_FORMAT_VERSION)
+        );
+        assert_eq!(
+            raw["bundle"]["layers"],
+            toml::Value::Array(vec!["project".into(), "system".into()])
+        );
+        assert_eq!(raw["allow"][1]["source_layer"].as_str(), Some("system"));
+    }
+
+    #[test]
+    fn system_allowlist_requires_signature_once_key_is_trusted() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("allowlist.toml");
+        let content = "[[allow]]\nrule = \"core.git:reset-hard\"\nreason = \"org\"\n";
+
+        // No trusted key: nothing to enforce.
+        assert!(verify_system_allowlist(&path, content).is_ok());
+
+        let (secret, public) = generate_keypair();
+        std::fs::write(trusted_key_path(&path), &public).unwrap();
+        let err = verify_system_allowlist(&path, content).unwrap_err();
+        assert!(err.contains("missing"), "{err}");
+
+        let signing = parse_signing_key(&secret).unwrap();
+        std::fs::write(signature_path(&path), sign(content.as_bytes(), &signing)).unwrap();
+        assert!(verify_system_allowlist(&path, content).is_ok());
+        assert!(verify_system_allowlist(&path, &content.replace("org", "me")).is_err());
+    }
+}
@@ -0,0 +1,155 @@
+//! Rate-based anomaly detection on denied commands.
+//!
+//! An agent that keeps retrying destructive commands after being denied is
+//! "fighting the guard". When `[anomaly]` is enabled and the agent has been
+//! denied `deny_threshold` times within `window_secs` in the same session (or,
+//! when the hook payload has no session id, the same working directory),
+//! rules that would only warn are escalated to deny, and the denial carries a
+//! notice asking the agent to stop and hand the decision back to the user.
+//!
+//! Denials are counted from the history database, so nothing is detected
+//! unless `[history]` is enabled.
+
+use chrono::{DateTime, TimeDelta, Utc};
+
+use crate::config::AnomalyConfig;
+use crate::history::{HistoryDb, Outcome};
+use crate::packs::DecisionMode;
+
+/// An agent that is over the denial threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnomalyStatus {
+    /// Denials recorded within the window, not counting the current command.
+    pub recent_denials: u64,
+    /// Window length in seconds.
+    pub window_secs: u64,
+}
+
+impl AnomalyStatus {
+    /// Escalate a decision: `Warn` becomes `Deny`; `Deny` and `Log` are unchanged.
+    #[must_use]
+    pub const fn escalate(&self, mode: DecisionMode) -> DecisionMode {
+        match mode {
+            DecisionMode::Warn => DecisionMode::Deny,
+            other => other,
+        }
+    }
+
+    /// Notice appended to denials while the anomaly is active.
+    #[must_use]
+    pub fn notice(&self) -> String {
+        format!(
+            "Agent appears to be fighting the guard: {} destructive commands were denied in \
+             the last {}s. Stop retrying variations of blocked commands and ask the user how \
+             to proceed. Until then, rules that normally only warn are denied.",
+            self.recent_denials, self.window_secs
+        )
+    }
+}
+
+/// Check whether an agent has been denied often enough to be treated as anomalous.
+///
+/// Returns `None` when detection is disabled, the query fails (fail-open), or
+/// the agent is under the threshold.
+#[must_use]
+pub fn detect(
+    db: &HistoryDb,
+    config: &AnomalyConfig,
+    agent_type: &str,
+    working_dir: &str,
+    session_id: Option<&str>,
+    now: DateTime<Utc>,
+) -> Option<AnomalyStatus> {
+    if !config.is_active() {
+        return None;
+    }
+
+    let window = i64::try_from(config.window_secs)
+        .ok()
+        .and_then(TimeDelta::try_seconds)?;
+    let since = now.checked_sub_signed(window)?;
+    let recent_denials = db
+        .count_recent_outcomes(Outcome::Deny, agent_type, working_dir, session_id, since)
+        .ok()?;
+
+    (recent_denials >= u64::from(config.deny_threshold)).then_some(AnomalyStatus {
+        recent_denials,
+        window_secs: config.window_secs,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::CommandEntry;
+
+    fn log_denials(db: &HistoryDb, now: DateTime<Utc>, seconds_ago: &[i64]) {
+        for &secs in seconds_ago {
+            db.log_command(&CommandEntry {
+                timestamp: now - TimeDelta::seconds(secs),
+                agent_type: "claude_code".to_string(),
+                working_dir: "/repo".to_string(),
+                command: "git reset --hard".to_string(),
+                outcome: Outcome::Deny,
+                ..Default::default()
+            })
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn detects_denials_over_threshold_within_window() {
+        let db = HistoryDb::open_in_memory().unwrap();
+        let now = Utc::now();
+        let config = AnomalyConfig {
+            enabled: true,
+            deny_threshold: 3,
+            window_secs: 60,
+        };
+
+        // Two recent denials plus one outside the window: under threshold.
+        log_denials(&db, now, &[5, 10, 300]);
+        assert_eq!(
+            detect(&db, &config, "claude_code", "/repo", None, now),
+            None
+        );
+
+        log_denials(&db, now, &[1]);
+        let status = detect(&db, &config, "claude_code", "/repo", None, now).expect("anomalous");
+        assert_eq!(status.recent_denials, 3);
+        assert!(status.notice().contains("fighting the guard"));
+
+        // Scoped to the agent's working directory.
+        assert_eq!(
+            detect(&db, &config, "claude_code", "/other", None, now),
+            None
+        );
+    }
+
+    #[test]
+    fn disabled_config_never_detects() {
+        let db = HistoryDb::open_in_memory().unwrap();
+        let now = Utc::now();
+        log_denials(&db, now, &[1, 2, 3, 4, 5, 6]);
+
+        let config = AnomalyConfig {
+            enabled: false,
+            ..AnomalyConfig::default()
+        };
+        assert_eq!(
+            detect(&db, &config, "claude_code", "/repo", None, now),
+            None
+        );
+    }
+
+    #[test]
+    fn escalates_only_warn() {
+        let status = AnomalyStatus {
+            recent_denials: 5,
+            window_secs: 120,
+        };
+        assert_eq!(status.escalate(DecisionMode::Warn), DecisionMode::Deny);
+        assert_eq!(status.escalate(DecisionMode::Deny), DecisionMode::Deny);
+        assert_eq!(status.escalate(DecisionMode::Log), DecisionMode::Log);
+    }
+}
@@ -0,0 +1,222 @@
+//! Ansible ad-hoc command introspection.
+//!
+//! `ansible all -m shell -a "rm -rf /var/lib/app"` runs the `-a` argument as
+//! a shell command on every targeted host. This module finds ad-hoc
+//! invocations whose module executes a command and returns the command text,
+//! so the evaluator can check it through the normal pipeline:
+//!
+//! ```text
+//! ansible all -m shell -a 'rm -rf /srv'         -> rm -rf /srv
+//! ansible web -a 'systemctl stop nginx'         -> systemctl stop nginx (default module: command)
+//! ansible db -m ansible.builtin.raw -a 'reboot' -> reboot
+//! ```
+//!
+//! Modules that do not run a command (`file`, `copy`, `apt`, ...) are left
+//! to the `infrastructure.ansible` pack patterns.
+
+use crate::scripts::{command_segments, is_env_assignment, shell_words};
+
+/// Modules whose free-form argument is a command line.
+const COMMAND_MODULES: &[&str] = &["shell", "command", "raw", "win_shell", "win_command"];
+
+/// Options that take a separate value, so the value is not read as the host
+/// pattern.
+const VALUE_OPTIONS: &[&str] = &[
+    "-i",
+    "--inventory",
+    "-l",
+    "--limit",
+    "-u",
+    "--user",
+    "-f",
+    "--forks",
+    "-e",
+    "--extra-vars",
+    "-c",
+    "--connection",
+    "-T",
+    "--timeout",
+    "-M",
+    "--module-path",
+    "-B",
+    "--background",
+    "-P",
+    "--poll",
+    "-t",
+    "--tree",
+    "--become-user",
+    "--become-method",
+    "--private-key",
+    "--key-file",
+    "--vault-id",
+    "--vault-password-file",
+];
+
+/// Module ansible runs when `-a` is given without `-m`.
+const DEFAULT_MODULE: &str = "command";
+
+/// Most invocations returned for a single command.
+const MAX_INVOCATIONS: usize = 8;
+
+/// One ad-hoc command that ansible would run on remote hosts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdhocInvocation {
+    /// Host pattern (`all`, `web`, `db:&prod`), empty when none was given.
+    pub hosts: String,
+    /// Module name as written (`shell`, `ansible.builtin.command`).
+    pub module: String,
+    /// The unquoted `-a` argument.
+    pub args: String,
+    /// Byte offset of the `-a` argument in the command.
+    pub start: usize,
+    /// Byte offset one past the `-a` argument.
+    pub end: usize,
+}
+
+impl AdhocInvocation {
+    /// Short preview of the remote command for denial messages.
+    #[must_use]
+    pub fn preview(&self) -> String {
+        const MAX_PREVIEW: usize = 80;
+        let args = self.args.trim().replace('\n', "; ");
+        if args.chars().count() <= MAX_PREVIEW {
+            return args;
+        }
+        let truncated: String = args.chars().take(MAX_PREVIEW).collect();
+        format!("{truncated}...")
+    }
+}
+
+/// Cheap pre-check: does the command mention the `ansible` program at all?
+#[must_use]
+pub fn mentions_ansible(command: &str) -> bool {
+    command
+        .split(|c: char| c.is_whitespace() || matches!(c, ';' | '&' | '|' | '(' | ')'))
+        .any(|word| word.rsplit('/').next() == Some("ansible"))
+}
+
+/// Find the ad-hoc commands `command` would run through command modules.
+#[must_use]
+pub fn extract_adhoc_invocations(command: &str) -> Vec<AdhocInvocation> {
+    let mut invocations = Vec::new();
+    if !mentions_ansible(command) {
+        return invocations;
+    }
+    for (start, segment) in command_segments(command) {
+        let words = shell_words(segment);
+        let mut words = words
+            .iter()
+            .skip_while(|word| word.text == "sudo" || is_env_assignment(&word.text));
+        let Some(program) = words.next() else {
+            continue;
+        };
+        if program.text.rsplit('/').next() != Some("ansible") {
+            continue;
+        }
+
+        let mut hosts = None;
+        let mut module = None;
+        let mut args = None;
+        while let Some(word) = words.next() {
+            let text = word.text.as_str();
+            if let Some(value) = text.strip_prefix("--module-name=") {
+                module = Some(value.to_string());
+            } else if let Some(value) = text.strip_prefix("--args=") {
+                args = Some((value.to_string(), word.start, word.end));
+            } else if text == "-m" || text == "--module-name" {
+                module = words.next().map(|w| w.text.clone());
+            } else if text == "-a" || text == "--args" {
+                args = words.next().map(|w| (w.text.clone(), w.start, w.end));
+            } else if let Some(value) = text.strip_prefix("-m").filter(|v| !v.is_empty()) {
+                module = Some(value.to_string());
+            } else if let Some(value) = text.strip_prefix("-a").filter(|v| !v.is_empty()) {
+                args = Some((value.to_string(), word.start, word.end));
+            } else if VALUE_OPTIONS.contains(&text) {
+                words.next();
+            } else if hosts.is_none() && !text.starts_with('-') {
+                hosts = Some(text.to_string());
+            }
+        }
+
+        let Some((args, arg_start, arg_end)) = args else {
+            continue;
+        };
+        let module = module.unwrap_or_else(|| DEFAULT_MODULE.to_string());
+        if !runs_command(&module) || args.trim().is_empty() {
+            continue;
+        }
+        invocations.push(AdhocInvocation {
+            hosts: hosts.unwrap_or_default(),
+            module,
+            args,
+            start: start + arg_start,
+            end: start + arg_end,
+        });
+        if invocations.len() == MAX_INVOCATIONS {
+            break;
+        }
+    }
+    invocations
+}
+
+/// `shell`, `ansible.builtin.shell`, `ansible.windows.win_shell`, ...
+fn runs_command(module: &str) -> bool {
+    let name = module.rsplit('.').next().unwrap_or(module);
+    COMMAND_MODULES.contains(&name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args_of(command: &str) -> Vec<String> {
+        extract_adhoc_invocations(command)
+            .into_iter()
+            .map(|inv| inv.args)
+            .collect()
+    }
+
+    #[test]
+    fn extracts_shell_and_command_modules() {
+        let invocations = extract_adhoc_invocations(r#"ansible all -m shell -a "rm -rf /srv""#);
+        assert_eq!(invocations.len(), 1);
+        assert_eq!(invocations[0].hosts, "all");
+        assert_eq!(invocations[0].module, "shell");
+        assert_eq!(invocations[0].args, "rm -rf /srv");
+
+        let invocations = extract_adhoc_invocations("ansible -i hosts.ini web -a uptime");
+        assert_eq!(invocations[0].hosts, "web");
+        assert_eq!(invocations[0].module, "command");
+
+        assert_eq!(
+            args_of("ansible -i hosts.ini web -a 'systemctl stop nginx'"),
+            vec!["systemctl stop nginx"]
+        );
+        assert_eq!(
+            args_of("ansible db --module-name=ansible.builtin.raw --args='reboot now'"),
+            vec!["reboot now"]
+        );
+        assert_eq!(
+            args_of("sudo ansible all -mcommand -a uptime"),
+            vec!["uptime"]
+        );
+    }
+
+    #[test]
+    fn span_covers_the_quoted_argument() {
+        let command = r#"echo start; ansible all -m shell -a "rm -rf /srv" -f 20"#;
+        let invocation = &extract_adhoc_invocations(command)[0];
+        assert_eq!(
+            &command[invocation.start..invocation.end],
+            r#""rm -rf /srv""#
+        );
+    }
+
+    #[test]
+    fn ignores_non_command_modules_and_other_programs() {
+        assert!(args_of("ansible all -m file -a 'path=/etc state=absent'").is_empty());
+        assert!(args_of("ansible all -m ping").is_empty());
+        assert!(args_of("ansible-playbook site.yml -e 'x=1'").is_empty());
+        assert!(args_of("echo 'ansible all -a reboot'").is_empty());
+    }
+}
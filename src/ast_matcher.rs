@@ -234,11 +234,28 @@ impl AstMatcher {
     /// - Timeout
     ///
     /// All errors are non-fatal; callers should fail-open (allow the command).
-    #[allow(clippy::cast_possible_truncation)] // Timeout values are always small
     pub fn find_matches(
         &self,
         code: &str,
         language: ScriptLanguage,
+    ) -> Result<Vec<PatternMatch>, MatchError> {
+        self.find_matches_for_command(code, language, None)
+    }
+
+    /// Find pattern matches, using the surrounding shell command for context.
+    ///
+    /// Only manifests use the context: a `kind: Namespace` document is
+    /// destructive when the command is `kubectl delete -f -`, not `apply`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`AstMatcher::find_matches`].
+    #[allow(clippy::cast_possible_truncation)] // Timeout values are always small
+    pub fn find_matches_for_command(
+        &self,
+        code: &str,
+        language: ScriptLanguage,
+        command: Option<&str>,
     ) -> Result<Vec<PatternMatch>, MatchError> {
         let start_time = Instant::now();
         let budget_ms = self.timeout.as_millis() as u64;
@@ -254,6 +271,20 @@ impl AstMatcher {
             return find_matches_perl(code, start_time, self.timeout, budget_ms);
         }
 
+        // SQL has no ast-grep grammar either; scan statement by statement.
+        if language == ScriptLanguage::Sql {
+            return find_matches_sql(code, start_time, self.timeout, budget_ms);
+        }
+
+        // Declarative config is scanned for the settings that remove safeguards.
+        if language == ScriptLanguage::Hcl {
+            return find_matches_hcl(code, start_time, self.timeout, budget_ms);
+        }
+        if language == ScriptLanguage::Yaml {
+            let deletes = command.is_some_and(|cmd| KUBECTL_DELETE_STDIN.is_match(cmd));
+            return find_matches_yaml(code, deletes, start_time, self.timeout, budget_ms);
+        }
+
         // Check language support FIRST (before patterns, so we report unsupported properly)
         let Some(ast_lang) = script_language_to_ast_lang(language) else {
             return Err(MatchError::UnsupportedLanguage(language));
@@ -358,7 +389,11 @@ const fn script_language_to_ast_lang(lang: ScriptLanguage) -> Option<SupportLang
         ScriptLanguage::Bash => Some(SupportLang::Bash),
         ScriptLanguage::Go => Some(SupportLang::Go),
         ScriptLanguage::Php => Some(SupportLang::Php),
-        ScriptLanguage::Perl | ScriptLanguage::Unknown => None,
+        ScriptLanguage::Perl
+        | ScriptLanguage::Sql
+        | ScriptLanguage::Hcl
+        | ScriptLanguage::Yaml
+        | ScriptLanguage::Unknown => None,
     }
 }
 
@@ -378,6 +413,12 @@ static JS_RECURSIVE_TRUE: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r"(?m)\brecursive\s*:\s*true\b").expect("js recursive:true regex compiles")
 });
 
+static JS_MONGO_EMPTY_FILTER: LazyLock<Regex> = LazyLock::new(|| {
+    // Matches: deleteMany({}) / remove({}), optionally followed by options
+    Regex::new(r"\b(?:deleteMany|remove)\s*\(\s*\{\s*\}\s*[,)]")
+        .expect("js mongo empty filter regex compiles")
+});
+
 static JS_EXEC_SYNC_LITERAL: LazyLock<Regex> = LazyLock::new(|| {
     // Matches: execSync("...") / execSync('...')
     Regex::new(r#"(?m)\bexecSync\b\s*\(\s*(?:"(?P<dq>[^"\n]*)"|'(?P<sq>[^'\n]*)')"#)
@@ -502,6 +543,13 @@ fn refine_javascript_match(meta: &CompiledPattern, matched_text: &str) -> Option
         });
     }
 
+    // Only an empty filter deletes everything; deleteMany({ status: "expired" }) is routine.
+    if rule_id == "heredoc.javascript.mongo_delete_all"
+        && !JS_MONGO_EMPTY_FILTER.is_match(matched_text)
+    {
+        return None;
+    }
+
     if rule_id.starts_with("heredoc.javascript.fs_")
         || rule_id.starts_with("heredoc.javascript.fspromises_")
     {
@@ -1178,6 +1226,475 @@ fn mask_perl_comments(code: &str) -> std::borrow::Cow<'_, str> {
     String::from_utf8(out).map_or(std::borrow::Cow::Borrowed(code), std::borrow::Cow::Owned)
 }
 
+// ============================================================================
+// SQL statement scanner
+// ============================================================================
+
+static SQL_DROP: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^DROP\s+(?:TEMP(?:ORARY)?\s+)?(?P<object>TABLE|DATABASE|SCHEMA)\b")
+        .expect("sql drop regex compiles")
+});
+
+static SQL_TRUNCATE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)^TRUNCATE\b").expect("sql truncate regex compiles"));
+
+static SQL_DELETE_FROM: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)^DELETE\s+FROM\b").expect("sql delete regex compiles"));
+
+static SQL_WHERE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)\bWHERE\b").expect("sql where regex compiles"));
+
+/// sqlite3 dot-commands that replace or overwrite database files.
+static SQLITE_DOT_COMMAND: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^[ \t]*\.(?P<cmd>open|restore|shell|system)\b(?P<args>[^\n]*)")
+        .expect("sqlite dot-command regex compiles")
+});
+
+#[derive(Clone, Copy)]
+enum SqlMaskState {
+    Normal,
+    LineComment,
+    BlockComment,
+    Literal,
+}
+
+/// Scan SQL fed to a client (`psql <<EOF`, `mysql -e "..."`).
+///
+/// Statements are split on `;` after comments, string literals, psql
+/// meta-commands, and sqlite3 dot-commands are masked, so a `DROP TABLE`
+/// inside a comment or a string value does not match. Dot-commands are
+/// scanned separately, remembering the file the last `.open` selected.
+fn find_matches_sql(
+    code: &str,
+    start_time: Instant,
+    timeout: Duration,
+    budget_ms: u64,
+) -> Result<Vec<PatternMatch>, MatchError> {
+    let newline_positions: Vec<usize> = memchr_iter(b'\n', code.as_bytes()).collect();
+    let masked = mask_sql_comments_and_literals(code);
+
+    let mut matches = Vec::new();
+    let mut offset = 0usize;
+    for statement in masked.split(';') {
+        perl_check_timeout(start_time, timeout, budget_ms)?;
+        let statement_offset = offset;
+        offset += statement.len() + 1;
+
+        let trimmed = statement.trim_start();
+        let body = trimmed.trim_end();
+        if body.is_empty() {
+            continue;
+        }
+        let start = statement_offset + (statement.len() - trimmed.len());
+        let end = start + body.len();
+
+        let (rule_id, reason, severity, suggestion) = if let Some(caps) = SQL_DROP.captures(body) {
+            match caps["object"].to_ascii_uppercase().as_str() {
+                "DATABASE" => (
+                    "heredoc.sql.drop_database",
+                    "DROP DATABASE permanently deletes the database and all its data",
+                    Severity::Critical,
+                    "Take a backup (pg_dump / mysqldump) before dropping",
+                ),
+                "SCHEMA" => (
+                    "heredoc.sql.drop_schema",
+                    "DROP SCHEMA deletes every object in the schema",
+                    Severity::Critical,
+                    "Take a backup (pg_dump / mysqldump) before dropping",
+                ),
+                _ => (
+                    "heredoc.sql.drop_table",
+                    "DROP TABLE permanently deletes the table and its data",
+                    Severity::High,
+                    "Rename the table or back it up before dropping",
+                ),
+            }
+        } else if SQL_TRUNCATE.is_match(body) {
+            (
+                "heredoc.sql.truncate",
+                "TRUNCATE deletes every row in the table",
+                Severity::High,
+                "Use DELETE ... WHERE inside a transaction to remove specific rows",
+            )
+        } else if SQL_DELETE_FROM.is_match(body) && !SQL_WHERE.is_match(body) {
+            (
+                "heredoc.sql.delete_without_where",
+                "DELETE without WHERE deletes every row in the table",
+                Severity::High,
+                "Add a WHERE clause, or run it inside BEGIN/ROLLBACK first",
+            )
+        } else {
+            continue;
+        };
+
+        push_regex_match(
+            &mut matches,
+            code,
+            &newline_positions,
+            rule_id,
+            reason,
+            severity,
+            Some(suggestion.to_string()),
+            start,
+            end,
+        );
+    }
+
+    find_sqlite_dot_commands(
+        code,
+        &newline_positions,
+        &mut matches,
+        start_time,
+        timeout,
+        budget_ms,
+    )?;
+
+    Ok(matches)
+}
+
+/// Scan sqlite3 dot-commands (`.open --new`, `.restore`, `.shell rm ...`).
+fn find_sqlite_dot_commands(
+    code: &str,
+    newline_positions: &[usize],
+    matches: &mut Vec<PatternMatch>,
+    start_time: Instant,
+    timeout: Duration,
+    budget_ms: u64,
+) -> Result<(), MatchError> {
+    let mut opened: Option<&str> = None;
+    for caps in SQLITE_DOT_COMMAND.captures_iter(code) {
+        perl_check_timeout(start_time, timeout, budget_ms)?;
+        let whole = caps.get(0).map_or(0..0, |m| m.range());
+        let args = caps.name("args").map_or("", |m| m.as_str().trim());
+
+        let (rule_id, reason, severity, suggestion) = match &caps["cmd"] {
+            "open" => {
+                let replaces = args
+                    .split_whitespace()
+                    .any(|word| matches!(word, "--new" | "-new"));
+                opened = args
+                    .split_whitespace()
+                    .rfind(|word| !word.starts_with('-'))
+                    .map(|file| file.trim_matches(['\'', '"']));
+                if !replaces {
+                    continue;
+                }
+                (
+                    "heredoc.sql.sqlite_open_new".to_string(),
+                    format!(
+                        ".open --new replaces {} with an empty database",
+                        opened.unwrap_or("the database file")
+                    ),
+                    Severity::High,
+                    "Open the file without --new, or .backup it first",
+                )
+            }
+            "restore" => (
+                "heredoc.sql.sqlite_restore".to_string(),
+                format!(
+                    ".restore overwrites {} with the contents of a backup file",
+                    opened.unwrap_or("the open database")
+                ),
+                Severity::High,
+                "Run .backup to a new file before restoring over the database",
+            ),
+            _ => {
+                let Some(hit) = detect_shell_payload(args) else {
+                    continue;
+                };
+                (
+                    format!("heredoc.sql.sqlite_shell.{}", hit.rule_suffix),
+                    hit.reason.to_string(),
+                    hit.severity,
+                    hit.suggestion
+                        .unwrap_or("Run the shell command outside sqlite3 after review"),
+                )
+            }
+        };
+
+        push_regex_match(
+            matches,
+            code,
+            newline_positions,
+            &rule_id,
+            &reason,
+            severity,
+            Some(suggestion.to_string()),
+            whole.start,
+            whole.end,
+        );
+    }
+    Ok(())
+}
+
+/// Blank out comments (`--`, `/* */`, `#` at line start), string literal
+/// contents, psql meta-command lines (`\c db`), and sqlite3 dot-command lines
+/// (`.open app.db`), keeping byte offsets.
+fn mask_sql_comments_and_literals(code: &str) -> std::borrow::Cow<'_, str> {
+    let bytes = code.as_bytes();
+    if !bytes
+        .iter()
+        .any(|b| matches!(b, b'-' | b'/' | b'#' | b'.' | b'\'' | b'\\'))
+    {
+        return std::borrow::Cow::Borrowed(code);
+    }
+
+    let mut out = bytes.to_vec();
+    let mut state = SqlMaskState::Normal;
+    let mut at_line_start = true;
+    let mut i = 0usize;
+
+    while i < out.len() {
+        let b = out[i];
+        match state {
+            SqlMaskState::Normal => {
+                let next = out.get(i + 1).copied();
+                if b == b'-' && next == Some(b'-') {
+                    state = SqlMaskState::LineComment;
+                    continue;
+                }
+                if b == b'/' && next == Some(b'*') {
+                    out[i] = b' ';
+                    out[i + 1] = b' ';
+                    i += 2;
+                    state = SqlMaskState::BlockComment;
+                    continue;
+                }
+                if at_line_start && matches!(b, b'#' | b'\\' | b'.') {
+                    state = SqlMaskState::LineComment;
+                    continue;
+                }
+                if b == b'\'' {
+                    state = SqlMaskState::Literal;
+                }
+                if b == b'\n' {
+                    at_line_start = true;
+                } else if !b.is_ascii_whitespace() {
+                    at_line_start = false;
+                }
+                i += 1;
+            }
+            SqlMaskState::LineComment => {
+                if b == b'\n' {
+                    state = SqlMaskState::Normal;
+                    at_line_start = true;
+                } else {
+                    out[i] = b' ';
+                }
+                i += 1;
+            }
+            SqlMaskState::BlockComment => {
+                if b == b'*' && out.get(i + 1) == Some(&b'/') {
+                    out[i] = b' ';
+                    out[i + 1] = b' ';
+                    i += 2;
+                    state = SqlMaskState::Normal;
+                    continue;
+                }
+                if b != b'\n' {
+                    out[i] = b' ';
+                }
+                i += 1;
+            }
+            SqlMaskState::Literal => {
+                if b == b'\\' && out.get(i + 1).is_some_and(|n| n.is_ascii() && *n != b'\n') {
+                    out[i] = b' ';
+                    out[i + 1] = b' ';
+                    i += 2;
+                    continue;
+                }
+                if b == b'\'' {
+                    // '' is an escaped quote inside the literal
+                    if out.get(i + 1) == Some(&b'\'') {
+                        out[i] = b' ';
+                        out[i + 1] = b' ';
+                        i += 2;
+                        continue;
+                    }
+                    state = SqlMaskState::Normal;
+                    at_line_start = false;
+                } else if b != b'\n' {
+                    out[i] = b' ';
+                }
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8(out).map_or(std::borrow::Cow::Borrowed(code), std::borrow::Cow::Owned)
+}
+
+// ============================================================================
+// Terraform/HCL and Kubernetes manifest scanners
+// ============================================================================
+
+static HCL_PREVENT_DESTROY_FALSE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^[ \t]*prevent_destroy[ \t]*=[ \t]*false\b")
+        .expect("hcl prevent_destroy regex compiles")
+});
+
+static HCL_WHEN_DESTROY: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?m)^[ \t]*when[ \t]*=[ \t]*"?destroy"?[ \t]*$"#)
+        .expect("hcl when = destroy regex compiles")
+});
+
+static YAML_FINALIZERS_CLEARED: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^[ \t]*finalizers:[ \t]*(?:\[[ \t]*\]|null|~)[ \t]*$")
+        .expect("yaml finalizers regex compiles")
+});
+
+static YAML_KIND_NAMESPACE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?m)^kind:[ \t]*["']?Namespace["']?[ \t]*$"#)
+        .expect("yaml namespace kind regex compiles")
+});
+
+/// `kubectl [flags] delete ... -f -`: the manifest on stdin is deleted, not applied.
+static KUBECTL_DELETE_STDIN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"\bkubectl\b[^|;&\n]*\sdelete\b[^|;&\n]*\s(?:-f[ \t]*|--filename[ \t=][ \t]*)-(?:\s|$)",
+    )
+    .expect("kubectl delete stdin regex compiles")
+});
+
+/// Scan Terraform configuration written from a heredoc (`cat > main.tf <<EOF`).
+fn find_matches_hcl(
+    code: &str,
+    start_time: Instant,
+    timeout: Duration,
+    budget_ms: u64,
+) -> Result<Vec<PatternMatch>, MatchError> {
+    let newline_positions: Vec<usize> = memchr_iter(b'\n', code.as_bytes()).collect();
+    let masked = mask_line_comments(code, &["#", "//"]);
+    let haystack = masked.as_ref();
+
+    let mut matches = Vec::new();
+    for m in HCL_PREVENT_DESTROY_FALSE.find_iter(haystack) {
+        perl_check_timeout(start_time, timeout, budget_ms)?;
+        push_regex_match(
+            &mut matches,
+            code,
+            &newline_positions,
+            "heredoc.hcl.prevent_destroy_disabled",
+            "prevent_destroy = false lets terraform destroy the protected resource",
+            Severity::High,
+            Some("Leave prevent_destroy = true and remove the resource deliberately".to_string()),
+            m.start(),
+            m.end(),
+        );
+    }
+    for m in HCL_WHEN_DESTROY.find_iter(haystack) {
+        perl_check_timeout(start_time, timeout, budget_ms)?;
+        push_regex_match(
+            &mut matches,
+            code,
+            &newline_positions,
+            "heredoc.hcl.destroy_provisioner",
+            "Destroy-time provisioner runs commands whenever the resource is destroyed",
+            Severity::High,
+            Some("Review the provisioner's command; run cleanup explicitly instead".to_string()),
+            m.start(),
+            m.end(),
+        );
+    }
+
+    Ok(matches)
+}
+
+/// Scan Kubernetes manifests fed to kubectl (`kubectl apply -f - <<EOF`).
+///
+/// `deletes` is true when the command deletes what it reads from stdin.
+fn find_matches_yaml(
+    code: &str,
+    deletes: bool,
+    start_time: Instant,
+    timeout: Duration,
+    budget_ms: u64,
+) -> Result<Vec<PatternMatch>, MatchError> {
+    let newline_positions: Vec<usize> = memchr_iter(b'\n', code.as_bytes()).collect();
+    let masked = mask_line_comments(code, &["#"]);
+    let haystack = masked.as_ref();
+
+    let mut matches = Vec::new();
+    for m in YAML_FINALIZERS_CLEARED.find_iter(haystack) {
+        perl_check_timeout(start_time, timeout, budget_ms)?;
+        push_regex_match(
+            &mut matches,
+            code,
+            &newline_positions,
+            "heredoc.yaml.finalizers_removed",
+            "Clearing finalizers lets the object be deleted without its cleanup running",
+            Severity::High,
+            Some("Fix whatever is blocking the finalizer instead of removing it".to_string()),
+            m.start(),
+            m.end(),
+        );
+    }
+    if deletes {
+        for m in YAML_KIND_NAMESPACE.find_iter(haystack) {
+            perl_check_timeout(start_time, timeout, budget_ms)?;
+            push_regex_match(
+                &mut matches,
+                code,
+                &newline_positions,
+                "heredoc.yaml.namespace_delete",
+                "Deleting a Namespace deletes every resource inside it",
+                Severity::Critical,
+                Some("Delete the specific resources instead of the namespace".to_string()),
+                m.start(),
+                m.end(),
+            );
+        }
+    }
+
+    Ok(matches)
+}
+
+/// Blank out the rest of a line after any comment marker outside a
+/// double-quoted string, keeping byte offsets.
+fn mask_line_comments<'a>(code: &'a str, markers: &[&str]) -> std::borrow::Cow<'a, str> {
+    if !markers.iter().any(|marker| code.contains(marker)) {
+        return std::borrow::Cow::Borrowed(code);
+    }
+
+    let mut out = String::with_capacity(code.len());
+    for line in code.split_inclusive('\n') {
+        let mut in_string = false;
+        let mut escaped = false;
+        let mut comment_at = None;
+        for (idx, ch) in line.char_indices() {
+            if in_string {
+                if escaped {
+                    escaped = false;
+                } else if ch == '\\' {
+                    escaped = true;
+                } else if ch == '"' {
+                    in_string = false;
+                }
+                continue;
+            }
+            if ch == '"' {
+                in_string = true;
+            } else if markers.iter().any(|marker| line[idx..].starts_with(marker)) {
+                comment_at = Some(idx);
+                break;
+            }
+        }
+
+        match comment_at {
+            Some(idx) => {
+                out.push_str(&line[..idx]);
+                let rest = &line[idx..];
+                let body = rest.trim_end_matches('\n');
+                out.extend(std::iter::repeat_n(' ', body.len()));
+                out.push_str(&rest[body.len()..]);
+            }
+            None => out.push_str(line),
+        }
+    }
+
+    std::borrow::Cow::Owned(out)
+}
+
 fn string_literal_from_caps<'t>(caps: &regex::Captures<'t>) -> Option<&'t str> {
     caps.name("dq")
         .or_else(|| caps.name("sq"))
@@ -1667,6 +2184,42 @@ fn default_patterns() -> HashMap<ScriptLanguage, Vec<CompiledPattern>> {
                 Severity::Medium, // warn-only unless catastrophic literal target (refined at match time)
                 Some("Verify target path carefully before running".to_string()),
             ),
+            // MongoDB shell (mongosh --eval, mongosh <<EOF)
+            CompiledPattern::new(
+                "$DB.dropDatabase($$$)".to_string(),
+                "heredoc.javascript.mongo_drop_database".to_string(),
+                "dropDatabase() permanently deletes the entire database".to_string(),
+                Severity::Critical,
+                Some("Back up with mongodump before dropping".to_string()),
+            ),
+            CompiledPattern::new(
+                "db.$COLL.drop($$$)".to_string(),
+                "heredoc.javascript.mongo_drop_collection".to_string(),
+                "drop() permanently deletes the collection and its indexes".to_string(),
+                Severity::High,
+                Some("Export the collection with mongoexport before dropping".to_string()),
+            ),
+            CompiledPattern::new(
+                "db.getCollection($$$).drop($$$)".to_string(),
+                "heredoc.javascript.mongo_drop_collection".to_string(),
+                "drop() permanently deletes the collection and its indexes".to_string(),
+                Severity::High,
+                Some("Export the collection with mongoexport before dropping".to_string()),
+            ),
+            CompiledPattern::new(
+                "$COLL.deleteMany($$$)".to_string(),
+                "heredoc.javascript.mongo_delete_all".to_string(),
+                "deleteMany({}) with an empty filter deletes every document".to_string(),
+                Severity::High, // only reported for an empty filter (refined at match time)
+                Some("Add filter criteria; preview with countDocuments(filter)".to_string()),
+            ),
+            CompiledPattern::new(
+                "$COLL.remove($$$)".to_string(),
+                "heredoc.javascript.mongo_delete_all".to_string(),
+                "remove({}) with an empty filter deletes every document".to_string(),
+                Severity::High, // only reported for an empty filter (refined at match time)
+                Some("Add filter criteria; preview with countDocuments(filter)".to_string()),
+            ),
         ],
     );
 
@@ -2265,6 +2818,49 @@ mod tests {
             );
         }
 
+        #[test]
+        fn mongo_shell_destructive_calls_block() {
+            let ast_matcher = AstMatcher::new();
+            let cases = [
+                (
+                    "db.dropDatabase()",
+                    "heredoc.javascript.mongo_drop_database",
+                ),
+                (
+                    "db.getSiblingDB('prod').dropDatabase()",
+                    "heredoc.javascript.mongo_drop_database",
+                ),
+                (
+                    "db.users.drop()",
+                    "heredoc.javascript.mongo_drop_collection",
+                ),
+                (
+                    "db.getCollection('users').drop()",
+                    "heredoc.javascript.mongo_drop_collection",
+                ),
+                (
+                    "db.orders.deleteMany({ })",
+                    "heredoc.javascript.mongo_delete_all",
+                ),
+                (
+                    "db.orders.remove({})",
+                    "heredoc.javascript.mongo_delete_all",
+                ),
+            ];
+
+            for (code, rule_id) in cases {
+                let matches = ast_matcher
+                    .find_matches(code, ScriptLanguage::JavaScript)
+                    .unwrap();
+                assert!(
+                    matches
+                        .iter()
+                        .any(|m| m.rule_id == rule_id && m.severity.blocks_by_default()),
+                    "{code} should block as {rule_id}, got {matches:?}"
+                );
+            }
+        }
+
         #[test]
         fn fs_rmsync_path_traversal_escapes_tmp_blocks() {
             // Path traversal from /tmp to /etc should be detected as catastrophic
@@ -2346,6 +2942,22 @@ mod tests {
             assert!(matches.is_empty());
         }
 
+        #[test]
+        fn mongo_shell_filtered_and_quoted_calls_do_not_match() {
+            let ast_matcher = AstMatcher::new();
+            for code in [
+                "db.orders.deleteMany({ status: 'expired' })",
+                "db.sessions.remove({ expiresAt: { $lt: new Date() } })",
+                "print('db.dropDatabase()')",
+                "db.users.find({ note: 'drop() later' })",
+            ] {
+                let matches = ast_matcher
+                    .find_matches(code, ScriptLanguage::JavaScript)
+                    .unwrap();
+                assert!(matches.is_empty(), "{code} should not match: {matches:?}");
+            }
+        }
+
         #[test]
         fn fs_rmsync_tmp_dotdot_in_filename_does_not_block() {
             // Filenames with consecutive dots are NOT path traversal
@@ -2512,6 +3124,210 @@ mod tests {
         }
     }
 
+    mod sql_positive_fixtures {
+        use super::*;
+
+        fn sql_rule_ids(code: &str) -> Vec<String> {
+            AstMatcher::new()
+                .find_matches(code, ScriptLanguage::Sql)
+                .expect("sql scanner should run")
+                .into_iter()
+                .map(|m| m.rule_id)
+                .collect()
+        }
+
+        #[test]
+        fn sql_drop_table_blocks() {
+            let ast_matcher = AstMatcher::new();
+            let code = "BEGIN;\nDROP TABLE users;\nCOMMIT;\n";
+
+            let matches = ast_matcher
+                .find_matches(code, ScriptLanguage::Sql)
+                .expect("sql scanner should run");
+            assert_eq!(matches.len(), 1);
+            assert_eq!(matches[0].rule_id, "heredoc.sql.drop_table");
+            assert_eq!(matches[0].line_number, 2);
+            assert_eq!(matches[0].matched_text_preview, "DROP TABLE users");
+            assert!(matches[0].severity.blocks_by_default());
+        }
+
+        #[test]
+        fn sql_drop_database_is_critical() {
+            let ast_matcher = AstMatcher::new();
+            let matches = ast_matcher
+                .find_matches("drop database if exists prod", ScriptLanguage::Sql)
+                .expect("sql scanner should run");
+            assert_eq!(matches[0].rule_id, "heredoc.sql.drop_database");
+            assert_eq!(matches[0].severity, Severity::Critical);
+            assert_eq!(
+                sql_rule_ids("DROP SCHEMA public CASCADE;"),
+                vec!["heredoc.sql.drop_schema"]
+            );
+        }
+
+        #[test]
+        fn sql_truncate_and_unscoped_delete_block() {
+            assert_eq!(
+                sql_rule_ids("TRUNCATE TABLE sessions;\nDELETE FROM audit_log;"),
+                vec!["heredoc.sql.truncate", "heredoc.sql.delete_without_where"]
+            );
+        }
+
+        #[test]
+        fn sql_statement_after_psql_meta_command_blocks() {
+            assert_eq!(
+                sql_rule_ids("\\c production\nDROP TABLE orders;"),
+                vec!["heredoc.sql.drop_table"]
+            );
+        }
+
+        #[test]
+        fn sqlite_dot_commands_after_open_block() {
+            assert_eq!(
+                sql_rule_ids(".open prod.db\nDROP TABLE orders;"),
+                vec!["heredoc.sql.drop_table"]
+            );
+
+            let matches = AstMatcher::new()
+                .find_matches(
+                    ".open /srv/app/prod.db\n.restore backup.db\n",
+                    ScriptLanguage::Sql,
+                )
+                .expect("sql scanner should run");
+            assert_eq!(matches.len(), 1);
+            assert_eq!(matches[0].rule_id, "heredoc.sql.sqlite_restore");
+            assert_eq!(matches[0].line_number, 2);
+            assert!(matches[0].reason.contains("/srv/app/prod.db"));
+            assert!(matches[0].severity.blocks_by_default());
+
+            assert_eq!(
+                sql_rule_ids(".open --new app.db\n.tables"),
+                vec!["heredoc.sql.sqlite_open_new"]
+            );
+            assert!(
+                sql_rule_ids(".shell rm -rf /")
+                    .iter()
+                    .all(|id| id.starts_with("heredoc.sql.sqlite_shell."))
+            );
+            assert_eq!(sql_rule_ids(".shell rm -rf /").len(), 1);
+        }
+    }
+
+    mod sql_negative_fixtures {
+        use super::*;
+
+        fn sql_matches(code: &str) -> Vec<PatternMatch> {
+            AstMatcher::new()
+                .find_matches(code, ScriptLanguage::Sql)
+                .expect("sql scanner should run")
+        }
+
+        #[test]
+        fn sql_scoped_delete_does_not_match() {
+            assert!(sql_matches("DELETE FROM sessions WHERE expires_at < now();").is_empty());
+        }
+
+        #[test]
+        fn sql_reads_and_creates_do_not_match() {
+            assert!(
+                sql_matches("SELECT * FROM users;\nCREATE TABLE t (id int);\nDROP INDEX idx;")
+                    .is_empty()
+            );
+        }
+
+        #[test]
+        fn sql_comments_do_not_match() {
+            let code = "-- DROP TABLE users;\n/* TRUNCATE orders; */\n# DELETE FROM t;\nSELECT 1;";
+            assert!(
+                sql_matches(code).is_empty(),
+                "commented-out statements are not executed"
+            );
+        }
+
+        #[test]
+        fn sqlite_read_only_dot_commands_do_not_match() {
+            let code =
+                ".open app.db\n.headers on\n.tables\n.shell ls -la\nSELECT count(*) FROM users;";
+            assert!(sql_matches(code).is_empty());
+        }
+
+        #[test]
+        fn sql_string_literals_do_not_match() {
+            let code = "INSERT INTO notes (body) VALUES ('it''s fine; DROP TABLE users');";
+            assert!(
+                sql_matches(code).is_empty(),
+                "string values are data, not statements"
+            );
+        }
+    }
+
+    mod manifest_fixtures {
+        use super::*;
+
+        fn rule_ids(code: &str, language: ScriptLanguage, command: &str) -> Vec<String> {
+            AstMatcher::new()
+                .find_matches_for_command(code, language, Some(command))
+                .expect("manifest scanner should run")
+                .into_iter()
+                .map(|m| m.rule_id)
+                .collect()
+        }
+
+        #[test]
+        fn hcl_prevent_destroy_flip_blocks() {
+            let code = "resource \"aws_db_instance\" \"main\" {\n  lifecycle {\n    prevent_destroy = false\n  }\n}\n";
+            let matches = AstMatcher::new()
+                .find_matches(code, ScriptLanguage::Hcl)
+                .expect("hcl scanner should run");
+            assert_eq!(matches.len(), 1);
+            assert_eq!(matches[0].rule_id, "heredoc.hcl.prevent_destroy_disabled");
+            assert_eq!(matches[0].line_number, 3);
+            assert!(matches[0].severity.blocks_by_default());
+        }
+
+        #[test]
+        fn hcl_destroy_provisioner_blocks() {
+            let code = "provisioner \"local-exec\" {\n  when    = destroy\n  command = \"./cleanup.sh\"\n}\n";
+            assert_eq!(
+                rule_ids(code, ScriptLanguage::Hcl, "cat > main.tf <<EOF"),
+                vec!["heredoc.hcl.destroy_provisioner"]
+            );
+        }
+
+        #[test]
+        fn hcl_protection_and_comments_do_not_match() {
+            let code = "lifecycle {\n  prevent_destroy = true\n  # prevent_destroy = false\n}\n";
+            assert!(rule_ids(code, ScriptLanguage::Hcl, "cat > main.tf <<EOF").is_empty());
+        }
+
+        #[test]
+        fn yaml_cleared_finalizers_block() {
+            let code =
+                "apiVersion: v1\nkind: Namespace\nmetadata:\n  name: team-a\n  finalizers: []\n";
+            assert_eq!(
+                rule_ids(code, ScriptLanguage::Yaml, "kubectl apply -f - <<EOF"),
+                vec!["heredoc.yaml.finalizers_removed"]
+            );
+        }
+
+        #[test]
+        fn yaml_namespace_blocks_only_when_deleted() {
+            let code = "apiVersion: v1\nkind: Namespace\nmetadata:\n  name: team-a\n";
+            assert!(rule_ids(code, ScriptLanguage::Yaml, "kubectl apply -f - <<EOF").is_empty());
+            assert_eq!(
+                rule_ids(code, ScriptLanguage::Yaml, "kubectl delete -f - <<EOF"),
+                vec!["heredoc.yaml.namespace_delete"]
+            );
+            assert!(
+                AstMatcher::new()
+                    .find_matches(code, ScriptLanguage::Yaml)
+                    .expect("yaml scanner should run")
+                    .is_empty(),
+                "without a command there is no delete context"
+            );
+        }
+    }
+
     #[test]
     fn match_includes_line_number() {
         let ast_matcher = AstMatcher::new();
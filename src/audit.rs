@@ -0,0 +1,404 @@
+//! Tamper-evident audit trail of configuration changes.
+//!
+//! Changes that dcg CLI commands make to config files, allowlists, and
+//! allow-once entries are appended to `audit.jsonl`, one JSON record per
+//! line. Each record carries the SHA-256 hash of the record before it, so
+//! editing, reordering, or deleting a line breaks the chain. `dcg audit verify`
+//! walks the chain and reports the first break.
+//!
+//! The chain cannot detect records cut off the end of the file; compare the
+//! record count or last hash from `dcg audit verify --json` against a copy
+//! kept elsewhere to catch that.
+
+use chrono::Utc;
+use fs2::FileExt;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt::Write as FmtWrite;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use crate::config::resolve_config_path_value;
+
+/// Environment variable to override the audit log path.
+pub const ENV_AUDIT_LOG_PATH: &str = "DCG_AUDIT_LOG_PATH";
+
+/// Audit log filename in the dcg config directory.
+pub const AUDIT_LOG_FILE: &str = "audit.jsonl";
+
+/// `prev_hash` of the first record.
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// One audited change.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AuditRecord {
+    /// Position in the chain, starting at 1.
+    pub seq: u64,
+    pub timestamp: String,
+    /// What changed, e.g. `allowlist.add_rule` or `packs.disable`.
+    pub action: String,
+    /// File that was changed.
+    pub target: String,
+    /// Action-specific fields (rule id, command, layer, ...).
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+    pub details: serde_json::Value,
+    /// OS user that ran the command.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    pub prev_hash: String,
+    pub hash: String,
+}
+
+impl AuditRecord {
+    /// Hash of every field except `hash` itself.
+    #[must_use]
+    pub fn compute_hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.seq.to_string().as_bytes());
+        for field in [
+            self.timestamp.as_str(),
+            self.action.as_str(),
+            self.target.as_str(),
+            &self.details.to_string(),
+            self.user.as_deref().unwrap_or(""),
+            self.prev_hash.as_str(),
+        ] {
+            hasher.update([0]);
+            hasher.update(field.as_bytes());
+        }
+        let digest = hasher.finalize();
+        let mut hex = String::with_capacity(digest.len() * 2);
+        for byte in digest {
+            let _ = write!(hex, "{byte:02x}");
+        }
+        hex
+    }
+}
+
+/// Why verification stopped.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ChainBreak {
+    /// The line is not a valid record.
+    Unparseable { line: usize },
+    /// `seq` is not one more than the previous record's.
+    Sequence {
+        line: usize,
+        expected: u64,
+        found: u64,
+    },
+    /// `prev_hash` does not match the previous record's hash.
+    Link { line: usize, seq: u64 },
+    /// The record was modified after it was written.
+    Hash { line: usize, seq: u64 },
+}
+
+impl std::fmt::Display for ChainBreak {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unparseable { line } => write!(f, "line {line}: not a valid audit record"),
+            Self::Sequence {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {line}: expected seq {expected}, found {found} (records missing or reordered)"
+            ),
+            Self::Link { line, seq } => write!(
+                f,
+                "line {line} (seq {seq}): prev_hash does not match the previous record"
+            ),
+            Self::Hash { line, seq } => {
+                write!(f, "line {line} (seq {seq}): record contents were modified")
+            }
+        }
+    }
+}
+
+/// Result of walking the chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VerifyReport {
+    /// Records checked before the first break (all of them when intact).
+    pub records: u64,
+    /// Hash of the last intact record.
+    pub last_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub broken: Option<ChainBreak>,
+}
+
+impl VerifyReport {
+    #[must_use]
+    pub const fn is_intact(&self) -> bool {
+        self.broken.is_none()
+    }
+}
+
+/// Append-only audit log file.
+#[derive(Debug, Clone)]
+pub struct AuditLog {
+    path: PathBuf,
+}
+
+impl AuditLog {
+    #[must_use]
+    pub const fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Resolve the default path (env override or ~/.config/dcg/..).
+    #[must_use]
+    pub fn default_path(cwd: Option<&Path>) -> PathBuf {
+        if let Ok(value) = std::env::var(ENV_AUDIT_LOG_PATH) {
+            if let Some(path) = resolve_config_path_value(&value, cwd) {
+                return path;
+            }
+        }
+
+        // Check XDG-style path first (~/.config/dcg/), then platform-native
+        let xdg_base = dirs::home_dir().map(|h| h.join(".config"));
+        if let Some(base) = xdg_base {
+            if base.join("dcg").exists() {
+                return base.join("dcg").join(AUDIT_LOG_FILE);
+            }
+        }
+
+        let base = dirs::config_dir()
+            .unwrap_or_else(|| dirs::home_dir().unwrap_or_default().join(".config"));
+        base.join("dcg").join(AUDIT_LOG_FILE)
+    }
+
+    /// Append a record for `action` on `target` and return it.
+    ///
+    /// # Errors
+    ///
+    /// Returns any I/O error from opening, locking, reading, or writing the log.
+    pub fn append(
+        &self,
+        action: &str,
+        target: &Path,
+        details: serde_json::Value,
+    ) -> io::Result<AuditRecord> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(&self.path)?;
+        file.lock_exclusive()?;
+
+        let (seq, prev_hash) = last_link(&mut file)?;
+        let mut record = AuditRecord {
+            seq: seq + 1,
+            timestamp: Utc::now().to_rfc3339(),
+            action: action.to_string(),
+            target: target.display().to_string(),
+            details,
+            user: std::env::var("USER")
+                .or_else(|_| std::env::var("USERNAME"))
+                .ok(),
+            prev_hash,
+            hash: String::new(),
+        };
+        record.hash = record.compute_hash();
+
+        let mut line = serde_json::to_string(&record).map_err(io::Error::other)?;
+        line.push('\n');
+        file.write_all(line.as_bytes())?;
+        file.sync_all()?;
+        Ok(record)
+    }
+
+    /// Walk the chain from the first record and report the first break.
+    ///
+    /// A missing log verifies as an empty, intact chain.
+    ///
+    /// # Errors
+    ///
+    /// Returns any I/O error other than the log not existing.
+    pub fn verify(&self) -> io::Result<VerifyReport> {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                return Ok(VerifyReport {
+                    records: 0,
+                    last_hash: None,
+                    broken: None,
+                });
+            }
+            Err(e) => return Err(e),
+        };
+
+        let mut report = VerifyReport {
+            records: 0,
+            last_hash: None,
+            broken: None,
+        };
+        for (index, line) in BufReader::new(file).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let line_no = index + 1;
+            let broken = match serde_json::from_str::<AuditRecord>(&line) {
+                Err(_) => Some(ChainBreak::Unparseable { line: line_no }),
+                Ok(record) if record.seq != report.records + 1 => Some(ChainBreak::Sequence {
+                    line: line_no,
+                    expected: report.records + 1,
+                    found: record.seq,
+                }),
+                Ok(record)
+                    if record.prev_hash != report.last_hash.as_deref().unwrap_or(GENESIS_HASH) =>
+                {
+                    Some(ChainBreak::Link {
+                        line: line_no,
+                        seq: record.seq,
+                    })
+                }
+                Ok(record) if record.hash != record.compute_hash() => Some(ChainBreak::Hash {
+                    line: line_no,
+                    seq: record.seq,
+                }),
+                Ok(record) => {
+                    report.records = record.seq;
+                    report.last_hash = Some(record.hash);
+                    None
+                }
+            };
+            if broken.is_some() {
+                report.broken = broken;
+                break;
+            }
+        }
+        Ok(report)
+    }
+}
+
+/// Sequence number and hash of the last record in `file`.
+fn last_link(file: &mut File) -> io::Result<(u64, String)> {
+    file.seek(SeekFrom::Start(0))?;
+    let mut content = String::new();
+    file.read_to_string(&mut content)?;
+    let last = content.lines().rev().find(|line| !line.trim().is_empty());
+    match last {
+        None => Ok((0, GENESIS_HASH.to_string())),
+        Some(line) => {
+            let record: AuditRecord = serde_json::from_str(line).map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("last audit record is unreadable: {e}"),
+                )
+            })?;
+            Ok((record.seq, record.hash))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn log_with_records(count: usize) -> (tempfile::TempDir, AuditLog) {
+        let dir = tempfile::tempdir().unwrap();
+        let log = AuditLog::new(dir.path().join(AUDIT_LOG_FILE));
+        for i in 0..count {
+            log.append(
+                "allowlist.add_rule",
+                Path::new("/home/u/.config/dcg/allowlist.toml"),
+                json!({ "rule": format!("core.git:rule-{i}") }),
+            )
+            .unwrap();
+        }
+        (dir, log)
+    }
+
+    fn rewrite_lines(log: &AuditLog, edit: impl FnOnce(&mut Vec<String>)) {
+        let content = std::fs::read_to_string(log.path()).unwrap();
+        let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+        edit(&mut lines);
+        std::fs::write(log.path(), lines.join("\n") + "\n").unwrap();
+    }
+
+    #[test]
+    fn appended_records_form_an_intact_chain() {
+        let (_dir, log) = log_with_records(3);
+        let report = log.verify().unwrap();
+        assert!(report.is_intact());
+        assert_eq!(report.records, 3);
+
+        let next = log
+            .append("packs.disable", Path::new("/repo/.dcg.toml"), json!(null))
+            .unwrap();
+        assert_eq!(next.seq, 4);
+        assert_eq!(report.last_hash.as_deref(), Some(next.prev_hash.as_str()));
+    }
+
+    #[test]
+    fn missing_log_is_empty_and_intact() {
+        let dir = tempfile::tempdir().unwrap();
+        let report = AuditLog::new(dir.path().join("none.jsonl"))
+            .verify()
+            .unwrap();
+        assert!(report.is_intact());
+        assert_eq!(report.records, 0);
+    }
+
+    #[test]
+    fn detects_modified_record() {
+        let (_dir, log) = log_with_records(3);
+        rewrite_lines(&log, |lines| {
+            lines[1] = lines[1].replace("rule-1", "rule-X");
+        });
+        let report = log.verify().unwrap();
+        assert_eq!(report.broken, Some(ChainBreak::Hash { line: 2, seq: 2 }));
+        assert_eq!(report.records, 1);
+    }
+
+    #[test]
+    fn detects_deleted_and_reordered_records() {
+        let (_dir, log) = log_with_records(3);
+        rewrite_lines(&log, |lines| {
+            lines.remove(1);
+        });
+        assert_eq!(
+            log.verify().unwrap().broken,
+            Some(ChainBreak::Sequence {
+                line: 2,
+                expected: 2,
+                found: 3
+            })
+        );
+
+        let (_dir, log) = log_with_records(2);
+        rewrite_lines(&log, |lines| lines.swap(0, 1));
+        assert!(matches!(
+            log.verify().unwrap().broken,
+            Some(ChainBreak::Sequence { line: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn detects_recomputed_record_with_stale_link() {
+        let (_dir, log) = log_with_records(2);
+        // Rewriting a record and fixing its own hash still breaks the next link.
+        rewrite_lines(&log, |lines| {
+            let mut record: AuditRecord = serde_json::from_str(&lines[0]).unwrap();
+            record.details = json!({ "rule": "core.git:*" });
+            record.hash = record.compute_hash();
+            lines[0] = serde_json::to_string(&record).unwrap();
+        });
+        assert_eq!(
+            log.verify().unwrap().broken,
+            Some(ChainBreak::Link { line: 2, seq: 2 })
+        );
+    }
+}
@@ -0,0 +1,342 @@
+//! Latency benchmark for `dcg bench`.
+//!
+//! Times the evaluation pipeline against a small built-in corpus using the
+//! caller's configuration, so enabled packs, external packs, and overrides
+//! are all part of the measurement:
+//!
+//! | Stage | What is timed |
+//! |-------|---------------|
+//! | `cold_start` | Spawning `dcg` in hook mode for one command (process start, config load, evaluation) |
+//! | `quick_reject` | In-process evaluation of commands no pack keyword matches |
+//! | `pack_eval` | In-process evaluation of commands that reach pattern matching |
+//! | `heredoc` | In-process evaluation of heredocs and inline scripts |
+//!
+//! Each stage's p99 is compared against the `[bench]` thresholds in the
+//! config; `dcg bench` exits non-zero when any stage is over budget.
+
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::allowlist::LayeredAllowlist;
+use crate::config::{BenchConfig, Config};
+use crate::evaluator::evaluate_command;
+use crate::packs::REGISTRY;
+
+/// Command used for cold-start samples.
+const COLD_START_COMMAND: &str = "git status";
+
+/// Commands no built-in pack keyword matches.
+const QUICK_REJECT_CORPUS: &[&str] = &[
+    "ls -la",
+    "cargo build --release",
+    "npm install",
+    "echo hello world",
+    "cat README.md",
+    "make test",
+    "python3 scripts/report.py --verbose",
+    "grep -rn TODO src",
+];
+
+/// Commands that reach pack pattern matching (safe and destructive).
+const PACK_EVAL_CORPUS: &[&str] = &[
+    "git status",
+    "git log --oneline -20",
+    "git reset --hard HEAD~1",
+    "git push --force origin main",
+    "git clean -fdx",
+    "rm -rf ./build",
+    "rm -rf /",
+    "docker system prune -af",
+    "kubectl delete namespace production",
+];
+
+/// Commands that go through heredoc and inline-script analysis.
+const HEREDOC_CORPUS: &[&str] = &[
+    "python3 - <<'EOF'\nimport shutil\nshutil.rmtree('/var/lib/app')\nEOF",
+    "bash <<EOF\nrm -rf /tmp/cache\nEOF",
+    "cat > notes.txt <<EOF\nrm -rf / is dangerous\nEOF",
+    "node -e \"require('fs').rmSync('/srv/data', { recursive: true })\"",
+    "ruby -e 'require \"fileutils\"; FileUtils.rm_rf(\"/opt/app\")'",
+    "sh -c 'git reset --hard origin/main'",
+];
+
+/// A benchmarked stage of the pipeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Stage {
+    /// Fresh `dcg` process in hook mode.
+    ColdStart,
+    /// Keyword gating rejects the command.
+    QuickReject,
+    /// Full pack evaluation.
+    PackEval,
+    /// Heredoc / inline-script pipeline.
+    Heredoc,
+}
+
+impl Stage {
+    /// Stages measured in-process, in report order.
+    pub const IN_PROCESS: [Self; 3] = [Self::QuickReject, Self::PackEval, Self::Heredoc];
+
+    /// Stable name used in output.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::ColdStart => "cold_start",
+            Self::QuickReject => "quick_reject",
+            Self::PackEval => "pack_eval",
+            Self::Heredoc => "heredoc",
+        }
+    }
+
+    /// Built-in commands for this stage.
+    #[must_use]
+    pub const fn corpus(self) -> &'static [&'static str] {
+        match self {
+            Self::ColdStart => &[COLD_START_COMMAND],
+            Self::QuickReject => QUICK_REJECT_CORPUS,
+            Self::PackEval => PACK_EVAL_CORPUS,
+            Self::Heredoc => HEREDOC_CORPUS,
+        }
+    }
+
+    /// p99 threshold for this stage from `[bench]`.
+    #[must_use]
+    pub const fn threshold(self, config: &BenchConfig) -> Duration {
+        match self {
+            Self::ColdStart => Duration::from_millis(config.cold_start_p99_ms),
+            Self::QuickReject => Duration::from_micros(config.quick_reject_p99_us),
+            Self::PackEval => Duration::from_micros(config.pack_eval_p99_us),
+            Self::Heredoc => Duration::from_millis(config.heredoc_p99_ms),
+        }
+    }
+}
+
+/// Latency distribution for one stage, in microseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct LatencyStats {
+    /// Number of samples.
+    pub samples: usize,
+    /// Arithmetic mean.
+    pub mean_us: u64,
+    /// Median.
+    pub p50_us: u64,
+    /// 95th percentile.
+    pub p95_us: u64,
+    /// 99th percentile.
+    pub p99_us: u64,
+    /// Slowest sample.
+    pub max_us: u64,
+}
+
+impl LatencyStats {
+    /// Summarize samples (nearest-rank percentiles).
+    #[must_use]
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let mut sorted: Vec<u64> = samples.iter().map(|d| duration_us(*d)).collect();
+        sorted.sort_unstable();
+        let total: u64 = sorted.iter().fold(0, |acc, us| acc.saturating_add(*us));
+        let count = sorted.len() as u64;
+        Self {
+            samples: sorted.len(),
+            mean_us: total.checked_div(count).unwrap_or(0),
+            p50_us: percentile(&sorted, 50),
+            p95_us: percentile(&sorted, 95),
+            p99_us: percentile(&sorted, 99),
+            max_us: sorted.last().copied().unwrap_or(0),
+        }
+    }
+}
+
+fn duration_us(duration: Duration) -> u64 {
+    u64::try_from(duration.as_micros()).unwrap_or(u64::MAX)
+}
+
+fn percentile(sorted: &[u64], pct: usize) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = (sorted.len() * pct).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+/// Result for one stage, with its threshold.
+#[derive(Debug, Clone, Serialize)]
+pub struct StageReport {
+    /// Which stage.
+    pub stage: Stage,
+    /// Measured latency.
+    #[serde(flatten)]
+    pub stats: LatencyStats,
+    /// Configured p99 threshold.
+    pub threshold_us: u64,
+    /// Whether p99 is within the threshold.
+    pub passed: bool,
+}
+
+/// Full `dcg bench` report.
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchReport {
+    /// Iterations over each in-process corpus.
+    pub iterations: usize,
+    /// One entry per measured stage.
+    pub stages: Vec<StageReport>,
+    /// Whether every stage is within its threshold.
+    pub passed: bool,
+}
+
+impl BenchReport {
+    /// Compare measured stages against the `[bench]` thresholds.
+    #[must_use]
+    pub fn new(
+        config: &BenchConfig,
+        iterations: usize,
+        measured: Vec<(Stage, LatencyStats)>,
+    ) -> Self {
+        let stages: Vec<StageReport> = measured
+            .into_iter()
+            .map(|(stage, stats)| {
+                let threshold_us = duration_us(stage.threshold(config));
+                StageReport {
+                    stage,
+                    stats,
+                    threshold_us,
+                    passed: stats.p99_us <= threshold_us,
+                }
+            })
+            .collect();
+        let passed = stages.iter().all(|stage| stage.passed);
+        Self {
+            iterations,
+            stages,
+            passed,
+        }
+    }
+}
+
+/// Time the in-process stages, `iterations` passes over each corpus.
+///
+/// One untimed pass runs first so lazily compiled patterns don't count
+/// against the first stage; process-level cold cost is what
+/// [`cold_start`] measures.
+#[must_use]
+pub fn run_in_process(
+    config: &Config,
+    allowlists: &LayeredAllowlist,
+    iterations: usize,
+) -> Vec<(Stage, LatencyStats)> {
+    let enabled = config.enabled_pack_ids();
+    let keywords = REGISTRY.collect_enabled_keywords(&enabled);
+    let compiled = config.compile_overrides();
+
+    for stage in Stage::IN_PROCESS {
+        for command in stage.corpus() {
+            let _ = evaluate_command(command, config, &keywords, &compiled, allowlists);
+        }
+    }
+
+    Stage::IN_PROCESS
+        .into_iter()
+        .map(|stage| {
+            let mut samples = Vec::with_capacity(iterations * stage.corpus().len());
+            for _ in 0..iterations {
+                for command in stage.corpus() {
+                    let start = Instant::now();
+                    let _ = evaluate_command(command, config, &keywords, &compiled, allowlists);
+                    samples.push(start.elapsed());
+                }
+            }
+            (stage, LatencyStats::from_samples(&samples))
+        })
+        .collect()
+}
+
+/// Time `iterations` fresh hook-mode runs of `exe`.
+///
+/// History logging and update checks are disabled in the child so samples
+/// don't write to the history database or touch the network.
+///
+/// # Errors
+///
+/// Returns an error if the process cannot be spawned or fed its input.
+pub fn cold_start(exe: &Path, iterations: usize) -> std::io::Result<LatencyStats> {
+    let input = serde_json::json!({
+        "tool_name": "Bash",
+        "tool_input": { "command": COLD_START_COMMAND },
+    })
+    .to_string();
+
+    let mut samples = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let mut child = Command::new(exe)
+            .env("DCG_HISTORY_ENABLED", "0")
+            .env("DCG_NO_UPDATE_CHECK", "1")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(input.as_bytes())?;
+        }
+        child.wait()?;
+        samples.push(start.elapsed());
+    }
+    Ok(LatencyStats::from_samples(&samples))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_use_nearest_rank_percentiles() {
+        let samples: Vec<Duration> = (1..=100).map(Duration::from_micros).collect();
+        let stats = LatencyStats::from_samples(&samples);
+
+        assert_eq!(stats.samples, 100);
+        assert_eq!(stats.p50_us, 50);
+        assert_eq!(stats.p95_us, 95);
+        assert_eq!(stats.p99_us, 99);
+        assert_eq!(stats.max_us, 100);
+        assert_eq!(stats.mean_us, 50);
+        assert_eq!(LatencyStats::from_samples(&[]).p99_us, 0);
+    }
+
+    #[test]
+    fn report_fails_stage_over_threshold() {
+        let config = BenchConfig {
+            quick_reject_p99_us: 10,
+            ..BenchConfig::default()
+        };
+        let fast = LatencyStats::from_samples(&[Duration::from_micros(5)]);
+        let slow = LatencyStats::from_samples(&[Duration::from_micros(11)]);
+
+        let report = BenchReport::new(
+            &config,
+            1,
+            vec![(Stage::QuickReject, fast), (Stage::PackEval, slow)],
+        );
+        assert!(report.passed);
+
+        let report = BenchReport::new(&config, 1, vec![(Stage::QuickReject, slow)]);
+        assert!(!report.passed);
+        assert_eq!(report.stages[0].threshold_us, 10);
+    }
+
+    #[test]
+    fn in_process_stages_cover_every_corpus() {
+        let config = Config::default();
+        let measured = run_in_process(&config, &LayeredAllowlist::default(), 2);
+
+        let stages: Vec<Stage> = measured.iter().map(|(stage, _)| *stage).collect();
+        assert_eq!(stages, Stage::IN_PROCESS);
+        for (stage, stats) in measured {
+            assert_eq!(stats.samples, stage.corpus().len() * 2);
+        }
+    }
+}
@@ -11,8 +11,9 @@ use inquire::{Select, Text};
 use crate::agent::{DetectionMethod, detect_agent_with_details};
 use crate::config::Config;
 use crate::evaluator::{
-    DEFAULT_WINDOW_WIDTH, EvaluationDecision, EvaluationResult, MatchSource,
-    evaluate_command_with_pack_order, evaluate_command_with_pack_order_deadline_at_path,
+    BatchEvaluator, DEFAULT_WINDOW_WIDTH, EvaluationDecision, EvaluationResult, MatchSource,
+    evaluate_command, evaluate_command_with_pack_order,
+    evaluate_command_with_pack_order_deadline_at_path,
 };
 use crate::exit_codes::EXIT_DENIED;
 use crate::highlight::{HighlightSpan, format_highlighted_command, should_use_color};
@@ -161,6 +162,11 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub robot: bool,
 
+    /// Agent whose hook protocol to use (e.g. `cursor`, `copilot`, `claude-code`).
+    /// When omitted, the protocol is detected from the hook payload shape.
+    #[arg(long, global = true, env = "DCG_AGENT", value_name = "NAME")]
+    pub agent: Option<String>,
+
     /// Subcommand to run (omit to run in hook mode)
     #[command(subcommand)]
     pub command: Option<Command>,
@@ -244,16 +250,26 @@ pub enum Command {
     #[command(name = "allow-once")]
     AllowOnce(AllowOnceCommand),
 
-    /// Install the hook into Claude Code settings
-    #[command(name = "install")]
+    /// Promote repeatedly allow-onced rules into the project allowlist
+    ///
+    /// Lists rules (or exact commands) approved with `dcg allow-once` at
+    /// least `[trust] promote_after` times in the current repository and,
+    /// after confirmation, adds each to the project allowlist.
+    #[command(name = "promote")]
+    Promote(PromoteCommand),
+
+    /// Install the hook into an agent's settings (Claude Code by default;
+    /// `--agent cursor|codex` for others)
+    #[command(name = "install", visible_alias = "install-hook")]
     Install {
         /// Force overwrite existing hook configuration
         #[arg(long)]
         force: bool,
     },
 
-    /// Remove the hook from Claude Code settings
-    #[command(name = "uninstall")]
+    /// Remove the hook from an agent's settings (Claude Code by default;
+    /// `--agent cursor|codex` for others)
+    #[command(name = "uninstall", visible_alias = "uninstall-hook")]
     Uninstall {
         /// Also remove configuration files
         #[arg(long)]
@@ -265,6 +281,9 @@ pub enum Command {
     Update(UpdateCommand),
 
     /// Generate shell completion scripts
+    ///
+    /// Pack ID arguments (`--pack`, `--with-packs`, `dcg pack info`, ...)
+    /// complete to the built-in pack IDs and categories.
     #[command(name = "completions")]
     Completions {
         /// Shell to generate completions for
@@ -272,6 +291,17 @@ pub enum Command {
         shell: CompletionShell,
     },
 
+    /// Generate man pages
+    ///
+    /// Without `--output-dir`, prints dcg(1) to stdout. With it, writes
+    /// dcg.1 plus one page per subcommand (dcg-history-export.1, ...).
+    #[command(name = "man")]
+    Man {
+        /// Directory to write one page per command into
+        #[arg(long, short = 'o', value_name = "DIR")]
+        output_dir: Option<std::path::PathBuf>,
+    },
+
     /// List all available packs and their status
     #[command(name = "packs")]
     ListPacks {
@@ -290,6 +320,9 @@ pub enum Command {
             env = "DCG_FORMAT"
         )]
         format: PacksFormat,
+
+        #[command(subcommand)]
+        action: Option<PacksAction>,
     },
 
     /// Pack management commands (info, validate)
@@ -299,11 +332,24 @@ pub enum Command {
         action: PackAction,
     },
 
+    /// Pack authoring tools (test patterns against examples and corpus)
+    #[command(name = "pattern")]
+    Pattern {
+        #[command(subcommand)]
+        action: PatternAction,
+    },
+
     /// Test a command against enabled packs
     #[command(name = "test")]
     TestCommand {
         /// Command to test
-        command: String,
+        #[arg(required_unless_present = "stdin_batch")]
+        command: Option<String>,
+
+        /// Read commands from stdin (one per line, or a JSON array of strings)
+        /// and print a JSON array of results
+        #[arg(long, conflicts_with_all = ["command", "explain"])]
+        stdin_batch: bool,
 
         /// Use a specific config file (overrides default config discovery)
         #[arg(long, short = 'c', value_name = "PATH")]
@@ -352,7 +398,11 @@ pub enum Command {
         heredoc_languages: Option<Vec<String>>,
     },
 
-    /// Generate a sample configuration file
+    /// Set up dcg: pick ecosystems, write config, install the hook, smoke test
+    ///
+    /// In an interactive terminal this runs a setup wizard. Otherwise (or with
+    /// `--output`, or when `DCG_NON_INTERACTIVE` / `CI` is set) it prints or
+    /// writes the commented sample configuration.
     #[command(name = "init")]
     Init {
         /// Output path (defaults to stdout)
@@ -366,7 +416,25 @@ pub enum Command {
 
     /// Show current configuration
     #[command(name = "config")]
-    ShowConfig,
+    ShowConfig {
+        /// Optional action (e.g. `effective` to print the merged config)
+        #[command(subcommand)]
+        action: Option<ConfigAction>,
+    },
+
+    /// Inspect the decision-mode policy
+    #[command(name = "policy")]
+    Policy {
+        #[command(subcommand)]
+        action: PolicyAction,
+    },
+
+    /// Manage the shell alias/function snapshot expanded before matching
+    #[command(name = "aliases")]
+    Aliases {
+        #[command(subcommand)]
+        action: AliasesAction,
+    },
 
     /// Scan files for destructive commands (CI/pre-commit integration)
     ///
@@ -376,6 +444,17 @@ pub enum Command {
     #[command(name = "scan")]
     Scan(ScanCommand),
 
+    /// Install git hooks that check pushes and rebases made outside the agent
+    ///
+    /// The pre-push and pre-rebase hooks translate force pushes, remote branch
+    /// deletions, and rebases of published commits into the equivalent
+    /// command and evaluate it with the same packs, allowlists, and policy.
+    #[command(name = "git-hook")]
+    GitHook {
+        #[command(subcommand)]
+        action: GitHookAction,
+    },
+
     /// Simulate policy evaluation on command logs (replay/dry-run)
     ///
     /// Parses a file containing commands (one per line) and evaluates each
@@ -422,6 +501,14 @@ pub enum Command {
     #[command(name = "corpus")]
     Corpus(CorpusCommand),
 
+    /// Measure evaluation latency against the `[bench]` thresholds
+    ///
+    /// Times cold start, quick reject, full pack evaluation, and the heredoc
+    /// pipeline over a built-in corpus with the current config and packs.
+    /// Exits non-zero if any stage's p99 exceeds its configured threshold.
+    #[command(name = "bench")]
+    Bench(BenchCommand),
+
     /// Show local statistics from the log file
     ///
     /// Displays aggregated statistics about blocked commands, allows,
@@ -444,6 +531,37 @@ pub enum Command {
     #[command(name = "suggest-allowlist")]
     SuggestAllowlist(SuggestAllowlistCommand),
 
+    /// Recommend packs to enable based on command history
+    ///
+    /// Replays commands that were allowed against every pack that is not
+    /// enabled and recommends the packs that would have blocked some of
+    /// them, with the measured number of commands each would have blocked.
+    #[command(name = "recommend")]
+    Recommend(RecommendCommand),
+
+    /// Review recent denies interactively
+    ///
+    /// Opens a terminal UI listing unreviewed denies from the history
+    /// database. Approve a deny (allow-once or allowlist entry), ignore it,
+    /// or mark it as a true positive; verdicts feed `dcg history analyze`.
+    #[command(name = "review")]
+    Review(ReviewCommand),
+
+    /// Live view of recent evaluations
+    ///
+    /// Refreshes from the history database every second and shows the deny
+    /// rate, latency percentiles, active sessions, and the latest
+    /// evaluations over a recent window. Requires history to be enabled.
+    #[command(name = "top")]
+    Top(TopCommand),
+
+    /// Inspect the hash-chained audit log of config and allowlist changes
+    #[command(name = "audit")]
+    Audit {
+        #[command(subcommand)]
+        action: AuditAction,
+    },
+
     /// Developer tools for pack development and testing
     #[command(name = "dev")]
     Dev {
@@ -461,6 +579,9 @@ pub enum Command {
     /// - `scan_file`: Scan a file or directory for destructive commands
     /// - `explain_pattern`: Explain a dcg rule by `rule_id`
     ///
+    /// Config and allowlist changes are picked up without a restart; use
+    /// `dcg reload` to force one.
+    ///
     /// Example agent configuration (Claude Code):
     /// ```json
     /// {
@@ -471,6 +592,68 @@ pub enum Command {
     /// ```
     #[command(name = "mcp-server")]
     McpServer,
+
+    /// Tell running dcg servers to reload config and allowlists
+    #[command(name = "reload")]
+    Reload,
+
+    /// Run a background evaluator that hook invocations forward to
+    ///
+    /// The daemon keeps the compiled packs, config, and allowlists warm and
+    /// listens on a Unix socket (`$DCG_DAEMON_SOCKET`, default
+    /// `$XDG_RUNTIME_DIR/dcg/daemon.sock`). Hook mode sends each command there
+    /// and evaluates in-process when no daemon answers.
+    #[command(name = "daemon")]
+    Daemon {
+        #[command(subcommand)]
+        action: DaemonAction,
+    },
+
+    /// Compile enabled packs' regexes now and report the cost
+    ///
+    /// Packs are normally compiled on their first keyword hit, so most hook
+    /// invocations never compile the packs they don't touch. This builds every
+    /// enabled pack up front, reports per-pack compile time, and lists patterns
+    /// that fail to compile. `dcg mcp-server` does the same in the background
+    /// at startup.
+    #[command(name = "warmup")]
+    Warmup {
+        /// Warm every registered pack, not just the enabled ones
+        #[arg(long)]
+        all: bool,
+
+        /// Output JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Inspect or clear the evaluation cache and quick-allow filter (`[cache]`)
+    #[command(name = "cache")]
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+
+    /// Send or test webhook notifications for denials (`[notifications]`)
+    #[command(name = "notify")]
+    Notify {
+        #[command(subcommand)]
+        action: NotifyAction,
+    },
+
+    /// Fetch and verify the team policy bundle (`[remote]`)
+    #[command(name = "sync")]
+    Sync {
+        /// Print nothing on success (used by the hook's background refresh)
+        #[arg(long, short = 'q')]
+        quiet: bool,
+        /// Show what is cached instead of fetching
+        #[arg(long, conflicts_with = "quiet")]
+        status: bool,
+        /// Output JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 /// `dcg hook` command arguments.
@@ -529,6 +712,10 @@ pub struct BatchHookOutput {
 /// `dcg corpus` command arguments.
 #[derive(Args, Debug)]
 pub struct CorpusCommand {
+    /// Record or replay a corpus of real commands instead of running the test corpus
+    #[command(subcommand)]
+    pub action: Option<CorpusAction>,
+
     /// Path to corpus directory (default: tests/corpus)
     #[arg(long, short = 'd', default_value = "tests/corpus")]
     pub dir: std::path::PathBuf,
@@ -564,6 +751,53 @@ pub struct CorpusCommand {
     pub summary_only: bool,
 }
 
+/// `dcg corpus` subcommands for recorded decision corpora.
+#[derive(Subcommand, Debug)]
+pub enum CorpusAction {
+    /// Snapshot commands and the decisions dcg makes for them into a corpus file
+    ///
+    /// Commands come from the history database (every evaluated command) or
+    /// from --input. Each is evaluated under the current config and packs.
+    #[command(name = "record")]
+    Record {
+        /// Corpus file to write
+        #[arg(long, short = 'o')]
+        output: std::path::PathBuf,
+
+        /// Read commands from a file (one per line, or hook JSON) instead of history
+        #[arg(long, short = 'i')]
+        input: Option<std::path::PathBuf>,
+
+        /// History look back period (e.g., "30d", "7d", "24h")
+        #[arg(long, default_value = "30d")]
+        since: String,
+
+        /// Maximum number of history rows to read (most recent first)
+        #[arg(long, default_value = "10000")]
+        limit: usize,
+    },
+
+    /// Re-evaluate a recorded corpus and report changed decisions
+    ///
+    /// Exits non-zero if any command is now denied, allowed, or matched by a
+    /// different rule than when it was recorded.
+    #[command(name = "replay")]
+    Replay {
+        /// Corpus file written by `dcg corpus record`
+        file: std::path::PathBuf,
+
+        /// Output format
+        #[arg(
+            long,
+            short = 'f',
+            value_enum,
+            default_value = "pretty",
+            env = "DCG_FORMAT"
+        )]
+        format: CorpusFormat,
+    },
+}
+
 /// Output format for corpus command.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
 pub enum CorpusFormat {
@@ -576,6 +810,28 @@ pub enum CorpusFormat {
     Pretty,
 }
 
+/// `dcg bench` command arguments.
+#[derive(Args, Debug)]
+pub struct BenchCommand {
+    /// Passes over each in-process corpus
+    #[arg(long, short = 'n', default_value = "200")]
+    pub iterations: usize,
+
+    /// Hook-mode process spawns for the cold-start stage (0 to skip)
+    #[arg(long, default_value = "20")]
+    pub cold_start_iterations: usize,
+
+    /// Output format
+    #[arg(
+        long,
+        short = 'f',
+        value_enum,
+        default_value = "json",
+        env = "DCG_FORMAT"
+    )]
+    pub format: CorpusFormat,
+}
+
 /// `dcg stats` command arguments.
 #[derive(Args, Debug)]
 pub struct StatsCommand {
@@ -604,6 +860,14 @@ pub struct StatsCommand {
     #[arg(long, short = 'r')]
     pub rules: bool,
 
+    /// Show a dashboard from the history database
+    ///
+    /// Per-pack hit counts, top blocked patterns, daily deny/warn/allow
+    /// ratios, median evaluation latency, and an estimate of false positives
+    /// (denies that were allowed again shortly afterwards).
+    #[arg(long, conflicts_with = "rules")]
+    pub dashboard: bool,
+
     /// Limit number of rules to display (default: 20)
     #[arg(long, short = 'n', default_value = "20")]
     pub limit: usize,
@@ -691,6 +955,9 @@ pub struct TestOutput {
     /// Detected agent information
     #[serde(skip_serializing_if = "Option::is_none")]
     pub agent: Option<AgentInfo>,
+    /// Fingerprint of the config, allowlists, and packs the decision was made
+    /// under; compare with `config_fingerprint` in history entries
+    pub config_fingerprint: String,
 }
 
 /// Allowlist override information in test output
@@ -785,7 +1052,79 @@ pub struct SuggestAllowlistCommand {
     pub undo: Option<u32>,
 }
 
-/// Output format for suggest-allowlist command.
+/// `dcg recommend` command arguments.
+#[derive(Args, Debug)]
+pub struct RecommendCommand {
+    /// Look back period (e.g., "30d", "7d", "24h")
+    #[arg(long, default_value = "30d")]
+    pub since: String,
+
+    /// Minimum number of commands a pack would have blocked to be recommended
+    #[arg(long, default_value = "1")]
+    pub min_blocks: u64,
+
+    /// Maximum number of allowed commands to replay (most recent first)
+    #[arg(long, default_value = "5000")]
+    pub limit: usize,
+
+    /// Output format (text, json)
+    #[arg(
+        long,
+        short = 'f',
+        value_enum,
+        default_value = "text",
+        env = "DCG_FORMAT"
+    )]
+    pub format: SuggestFormat,
+}
+
+/// `dcg review` command arguments.
+#[derive(Args, Debug)]
+pub struct ReviewCommand {
+    /// Only review denies newer than this (e.g., "7d", "24h", or a date)
+    #[arg(long, default_value = "7d")]
+    pub since: String,
+
+    /// Maximum number of denies to load
+    #[arg(long, default_value = "200")]
+    pub limit: usize,
+}
+
+/// `dcg top` command arguments.
+#[derive(Args, Debug)]
+pub struct TopCommand {
+    /// Window the counts and percentiles cover (e.g., "5m", "1h")
+    #[arg(long, default_value = "5m")]
+    pub window: String,
+
+    /// Seconds between refreshes
+    #[arg(long, default_value = "1", value_parser = clap::value_parser!(u64).range(1..))]
+    pub interval: u64,
+
+    /// Maximum active sessions to list
+    #[arg(long, default_value = "10")]
+    pub sessions: usize,
+
+    /// Maximum recent evaluations to list
+    #[arg(long, default_value = "50")]
+    pub limit: usize,
+
+    /// Print one snapshot and exit instead of opening the live view
+    #[arg(long)]
+    pub once: bool,
+
+    /// Output format for --once (text, json)
+    #[arg(
+        long,
+        short = 'f',
+        value_enum,
+        default_value = "text",
+        env = "DCG_FORMAT"
+    )]
+    pub format: SuggestFormat,
+}
+
+/// Output format for suggest-allowlist and recommend commands.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
 pub enum SuggestFormat {
     /// Human-readable colored output
@@ -835,11 +1174,228 @@ pub enum ExportFormat {
     Jsonl,
     /// Comma-separated values
     Csv,
+    /// Apache Parquet (columnar; requires the `parquet` feature)
+    Parquet,
+}
+
+/// `dcg config` subcommands.
+#[derive(Subcommand, Debug)]
+pub enum ConfigAction {
+    /// Print the merged configuration and the layers it was built from
+    ///
+    /// Layers are applied system → user → project (`.dcg.toml` at the repo
+    /// root) → `DCG_CONFIG` → environment variables; later layers win.
+    Effective {
+        /// Output format
+        #[arg(long, short = 'f', value_enum, default_value = "toml")]
+        format: ConfigEffectiveFormat,
+    },
+}
+
+/// `dcg policy` subcommands.
+#[derive(Subcommand, Debug)]
+pub enum PolicyAction {
+    /// Show the effective severity/mode matrix for enabled packs
+    ///
+    /// Lists `[policy.severity_overrides]` (with validation problems) and,
+    /// for each rule whose severity or mode differs from its default, the
+    /// remapped severity and the mode the policy resolves to right now.
+    Show {
+        /// Output format
+        #[arg(long, short = 'f', value_enum, default_value = "pretty")]
+        format: PolicyShowFormat,
+
+        /// Include rules that still use their default severity and mode
+        #[arg(long)]
+        all: bool,
+    },
+}
+
+/// `dcg cache` subcommands.
+#[derive(Subcommand, Debug)]
+pub enum CacheAction {
+    /// Show the cache location and how many entries are valid for the current config
+    Status {
+        /// Output JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Remove every cached decision
+    Clear,
+}
+
+/// `dcg daemon` subcommands.
+#[derive(Subcommand, Debug)]
+pub enum DaemonAction {
+    /// Serve evaluation requests in the foreground until stopped
+    Start,
+    /// Report whether a daemon is listening and what it has served
+    Status {
+        /// Output JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Ask the running daemon to exit
+    Stop,
+}
+
+/// `dcg notify` subcommands.
+#[derive(Subcommand, Debug)]
+pub enum NotifyAction {
+    /// Send a sample denial to every configured webhook and report the result
+    Test,
+    /// Deliver queued denials (started automatically by the hook)
+    #[command(hide = true)]
+    Flush,
+}
+
+/// Output format for `dcg policy show`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum PolicyShowFormat {
+    /// Human-readable table
+    #[default]
+    Pretty,
+    /// JSON object with `overrides`, `issues`, and `rules` keys
+    Json,
+}
+
+/// `dcg aliases` subcommands.
+#[derive(Subcommand, Debug)]
+pub enum AliasesAction {
+    /// Capture aliases and functions from an interactive shell
+    ///
+    /// Runs `<shell> -ic 'alias; typeset -f'` and writes the output to the
+    /// snapshot file that `[aliases]` reads by default.
+    Snapshot {
+        /// Shell to query (defaults to $SHELL, then bash)
+        #[arg(long)]
+        shell: Option<String>,
+
+        /// Write the snapshot here instead of the default location
+        #[arg(long, short = 'o', value_name = "PATH")]
+        output: Option<std::path::PathBuf>,
+    },
+
+    /// List the aliases and functions that will be expanded
+    List,
+}
+
+/// `dcg audit` subcommands.
+#[derive(Subcommand, Debug)]
+pub enum AuditAction {
+    /// Check the audit log hash chain for edited, reordered, or deleted records
+    Verify {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// `dcg git-hook` subcommands.
+#[derive(Subcommand, Debug)]
+pub enum GitHookAction {
+    /// Write pre-push and pre-rebase hooks into the current repository
+    Install,
+
+    /// Remove hooks written by `dcg git-hook install`
+    Uninstall,
+
+    /// Entry point for the pre-push hook (reads ref updates from stdin)
+    #[command(name = "pre-push", hide = true)]
+    PrePush {
+        /// Remote name
+        remote: String,
+        /// Remote URL
+        url: Option<String>,
+    },
+
+    /// Entry point for the pre-rebase hook
+    #[command(name = "pre-rebase", hide = true)]
+    PreRebase {
+        /// Upstream the series is being rebased onto
+        upstream: String,
+        /// Branch being rebased (empty for the current branch)
+        branch: Option<String>,
+    },
+}
+
+/// Output format for `dcg config effective`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ConfigEffectiveFormat {
+    /// TOML, with the contributing layers listed as leading comments
+    #[default]
+    Toml,
+    /// JSON object with `sources` and `config` keys
+    Json,
+}
+
+/// Output format for `dcg history search`.
+#[derive(Debug, Clone, Copy, Default, clap::ValueEnum)]
+pub enum HistorySearchFormat {
+    /// Human-readable table
+    #[default]
+    Table,
+    /// JSON array of matching commands
+    Json,
+    /// Comma-separated values
+    Csv,
 }
 
 /// History subcommand actions
 #[derive(Subcommand, Debug, Clone)]
 pub enum HistoryAction {
+    /// Full-text search over recorded commands
+    #[command(name = "search")]
+    Search {
+        /// Search terms matched against the command text (omit to list recent commands)
+        #[arg(value_name = "QUERY")]
+        query: Option<String>,
+
+        /// Filter by outcome (allow, deny, warn, bypass)
+        #[arg(long, value_name = "OUTCOME")]
+        outcome: Option<String>,
+
+        /// Filter by pack ID or category (e.g., core.git, database)
+        #[arg(long, value_name = "PACK")]
+        pack: Option<String>,
+
+        /// Only commands newer than this (e.g., 7d, 12h, or an ISO 8601 timestamp)
+        #[arg(long, value_name = "WHEN")]
+        since: Option<String>,
+
+        /// Only commands run in this directory or below it
+        #[arg(long, value_name = "PATH")]
+        cwd: Option<String>,
+
+        /// Only commands from this agent session
+        #[arg(long, value_name = "ID")]
+        session: Option<String>,
+
+        /// Maximum number of results
+        #[arg(long, short = 'n', default_value = "50")]
+        limit: usize,
+
+        /// Output format
+        #[arg(long, short = 'f', value_enum, default_value = "table")]
+        format: HistorySearchFormat,
+    },
+
+    /// Summarize recorded commands per agent session
+    #[command(name = "sessions")]
+    Sessions {
+        /// Time period in days (default: 7)
+        #[arg(long, short = 'd', default_value = "7")]
+        days: u64,
+
+        /// Maximum number of sessions
+        #[arg(long, short = 'n', default_value = "20")]
+        limit: usize,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
     /// Show history stats and summaries
     #[command(name = "stats")]
     Stats {
@@ -872,6 +1428,18 @@ pub enum HistoryAction {
         yes: bool,
     },
 
+    /// Apply `[history.retention]` limits now and reclaim disk space
+    #[command(name = "vacuum")]
+    Vacuum {
+        /// Only reclaim space; skip the retention pass
+        #[arg(long)]
+        no_compact: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
     /// Export command history to a file
     #[command(name = "export")]
     Export {
@@ -887,19 +1455,24 @@ pub enum HistoryAction {
         #[arg(long, value_name = "OUTCOME")]
         outcome: Option<String>,
 
-        /// Include only commands since this date/time (ISO 8601)
-        #[arg(long, value_name = "DATETIME")]
+        /// Include only commands since this time (7d, 12h, 2026-01-01, or RFC 3339)
+        #[arg(long, value_name = "WHEN")]
         since: Option<String>,
 
-        /// Include only commands until this date/time (ISO 8601)
-        #[arg(long, value_name = "DATETIME")]
+        /// Include only commands before this time (7d, 12h, 2026-01-01, or RFC 3339)
+        #[arg(long, value_name = "WHEN")]
         until: Option<String>,
 
+        /// Filter rows by KEY=VALUE (repeatable; keys: outcome, pack, rule,
+        /// agent, cwd, session, host, command)
+        #[arg(long = "filter", value_name = "KEY=VALUE")]
+        filters: Vec<String>,
+
         /// Maximum number of records to export
         #[arg(long, value_name = "N")]
         limit: Option<usize>,
 
-        /// Compress output with gzip
+        /// Compress output with gzip (not valid with parquet)
         #[arg(long)]
         compress: bool,
     },
@@ -1243,6 +1816,10 @@ pub struct SimulateCommand {
     #[arg(long, short = 'f', default_value = "-")]
     pub file: String,
 
+    /// Replay a bash/zsh history file or exported Claude transcript (JSONL)
+    #[arg(long, value_name = "FILE", conflicts_with = "file")]
+    pub from_history: Option<String>,
+
     /// Maximum number of lines to process
     #[arg(long)]
     pub max_lines: Option<usize>,
@@ -1409,22 +1986,101 @@ pub enum AllowlistAction {
         #[arg(long)]
         strict: bool,
     },
-}
 
-/// Subcommands for managing allow-once entries.
-#[derive(Subcommand, Debug, Clone)]
-pub enum AllowOnceAction {
-    /// List pending codes and active allow-once entries (redacted by default)
-    #[command(name = "list")]
-    List,
+    /// Remove expired entries (past `expires_at` or `ttl`)
+    #[command(name = "prune")]
+    Prune {
+        /// Prune project allowlist only
+        #[arg(long, conflicts_with = "user")]
+        project: bool,
 
-    /// Clear expired entries and optionally wipe stores
-    #[command(name = "clear")]
-    Clear(AllowOnceClearArgs),
+        /// Prune user allowlist only
+        #[arg(long, conflicts_with = "project")]
+        user: bool,
 
-    /// Revoke a pending code or active allow-once entry
-    #[command(name = "revoke")]
-    Revoke(AllowOnceRevokeArgs),
+        /// Show what would be removed without writing
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Export the layered allowlist as a single (optionally signed) bundle
+    #[command(name = "export")]
+    Export {
+        /// Bundle file to write
+        #[arg(long, short = 'o')]
+        output: std::path::PathBuf,
+
+        /// Ed25519 key from `dcg allowlist keygen`; writes `<output>.sig`
+        #[arg(long, value_name = "FILE")]
+        sign_key: Option<std::path::PathBuf>,
+
+        /// Include the project allowlist (default: all layers)
+        #[arg(long)]
+        project: bool,
+
+        /// Include the user allowlist (default: all layers)
+        #[arg(long)]
+        user: bool,
+
+        /// Include the system allowlist (default: all layers)
+        #[arg(long)]
+        system: bool,
+    },
+
+    /// Verify a signed bundle and install it as an allowlist layer
+    ///
+    /// Installs into the system allowlist by default, together with the
+    /// public key, so dcg only honors the system layer while its signature
+    /// verifies.
+    #[command(name = "import")]
+    Import {
+        /// Bundle file produced by `dcg allowlist export --sign-key`
+        bundle: std::path::PathBuf,
+
+        /// Public key (base64, or a file containing it)
+        #[arg(long, value_name = "KEY")]
+        public_key: String,
+
+        /// Detached signature (default: `<bundle>.sig`)
+        #[arg(long, value_name = "FILE")]
+        signature: Option<std::path::PathBuf>,
+
+        /// Install into the project allowlist instead
+        #[arg(long, conflicts_with = "user")]
+        project: bool,
+
+        /// Install into the user allowlist instead
+        #[arg(long, conflicts_with = "project")]
+        user: bool,
+    },
+
+    /// Generate an Ed25519 key pair for signing allowlist bundles
+    #[command(name = "keygen")]
+    Keygen {
+        /// Secret key file to write (the public key goes to `<output>.pub`)
+        #[arg(long, short = 'o')]
+        output: std::path::PathBuf,
+    },
+}
+
+/// Subcommands for managing allow-once entries.
+#[derive(Subcommand, Debug, Clone)]
+pub enum AllowOnceAction {
+    /// List pending codes and active allow-once entries (redacted by default)
+    #[command(name = "list")]
+    List,
+
+    /// Clear expired entries and optionally wipe stores
+    #[command(name = "clear")]
+    Clear(AllowOnceClearArgs),
+
+    /// Revoke a pending code or active allow-once entry
+    #[command(name = "revoke")]
+    Revoke(AllowOnceRevokeArgs),
+
+    /// Replace the HMAC secret behind short codes (invalidates pending codes)
+    #[command(name = "rotate-secret")]
+    RotateSecret,
 }
 
 #[derive(Args, Debug, Clone)]
@@ -1448,6 +2104,26 @@ pub struct AllowOnceRevokeArgs {
     pub target: String,
 }
 
+/// `dcg promote` command arguments.
+#[derive(Args, Debug)]
+pub struct PromoteCommand {
+    /// Approvals needed before a rule is offered (default: `[trust] promote_after`)
+    #[arg(long)]
+    pub min_approvals: Option<u32>,
+
+    /// Promote every candidate without prompting
+    #[arg(long, short = 'y')]
+    pub yes: bool,
+
+    /// List candidates without changing the allowlist
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Output JSON (implies --dry-run unless --yes is given)
+    #[arg(long)]
+    pub json: bool,
+}
+
 /// Allow-once command arguments.
 ///
 /// - `dcg allow-once <CODE>` (legacy shorthand for applying an allow-once code)
@@ -1488,6 +2164,10 @@ pub struct AllowOnceCommand {
     #[arg(long)]
     pub force: bool,
 
+    /// Only allow within the agent session the command was denied in (apply-only)
+    #[arg(long)]
+    pub session: bool,
+
     /// Select a specific entry when multiple match the code (1-based) (apply-only)
     #[arg(long, value_name = "N", conflicts_with = "hash")]
     pub pick: Option<usize>,
@@ -1559,6 +2239,62 @@ pub enum PackAction {
     },
 }
 
+/// `dcg packs` subcommands.
+#[derive(Subcommand, Debug)]
+pub enum PacksAction {
+    /// Enable a pack or category in the config file
+    ///
+    /// Adds the ID to `[packs] enabled` and removes it from `disabled`.
+    /// Comments and formatting in the file are preserved.
+    #[command(name = "enable")]
+    Enable {
+        /// Pack ID or category (e.g., "database.postgresql", "kubernetes")
+        pack_id: String,
+
+        /// Edit `.dcg.toml` at the repository root instead of the user config
+        #[arg(long)]
+        project: bool,
+    },
+
+    /// Disable a pack or category in the config file
+    ///
+    /// Removes the ID from `[packs] enabled` and adds it to `disabled`, so
+    /// packs enabled by other config layers are turned off as well.
+    #[command(name = "disable")]
+    Disable {
+        /// Pack ID or category (e.g., "database.postgresql", "kubernetes")
+        pack_id: String,
+
+        /// Edit `.dcg.toml` at the repository root instead of the user config
+        #[arg(long)]
+        project: bool,
+    },
+}
+
+/// Pattern subcommand actions
+#[derive(Subcommand, Debug)]
+pub enum PatternAction {
+    /// Run an external pack against its examples and the built-in corpus
+    ///
+    /// Reports:
+    /// - False negatives: `examples.positive` commands the pack allows
+    /// - False positives: `examples.negative` or corpus commands the pack blocks
+    /// - Destructive patterns no positive example exercises
+    #[command(name = "test")]
+    Test {
+        /// Path to pack YAML file
+        file_path: String,
+
+        /// Skip the embedded false-positive corpus
+        #[arg(long)]
+        no_corpus: bool,
+
+        /// Output format
+        #[arg(long, short = 'f', value_enum, default_value_t = PackValidateFormat::Pretty, env = "DCG_FORMAT")]
+        format: PackValidateFormat,
+    },
+}
+
 /// Output format for pack validate command
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
 pub enum PackValidateFormat {
@@ -1651,7 +2387,12 @@ fn maybe_show_update_notice(cli: &Cli, config: &Config, verbosity: Verbosity) {
     }
 
     if let Some(
-        Command::Update(_) | Command::Hook(_) | Command::Completions { .. } | Command::McpServer,
+        Command::Update(_)
+        | Command::Hook(_)
+        | Command::Completions { .. }
+        | Command::Man { .. }
+        | Command::McpServer
+        | Command::Daemon { .. },
     ) = cli.command
     {
         // Skip update notices for update/hook/completion/server flows.
@@ -1682,6 +2423,7 @@ fn maybe_show_update_notice(cli: &Cli, config: &Config, verbosity: Verbosity) {
 #[allow(clippy::too_many_lines)]
 pub fn run_command(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
     let config = Config::load();
+    crate::i18n::init(&config.output);
     let verbosity = Verbosity::from_cli(&cli);
     maybe_show_update_notice(&cli, &config, verbosity);
 
@@ -1693,10 +2435,10 @@ pub fn run_command(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
             run_hook_command(&config, &cmd)?;
         }
         Some(Command::Install { force }) => {
-            install_hook(force)?;
+            install_hook(HookTarget::from_agent(cli.agent.as_deref())?, force)?;
         }
         Some(Command::Uninstall { purge }) => {
-            uninstall_hook(purge)?;
+            uninstall_hook(HookTarget::from_agent(cli.agent.as_deref())?, purge)?;
         }
         Some(Command::Update(update)) => {
             self_update(update)?;
@@ -1704,7 +2446,24 @@ pub fn run_command(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
         Some(Command::Completions { shell }) => {
             write_completions(shell)?;
         }
-        Some(Command::ListPacks { enabled, format }) => {
+        Some(Command::Man { output_dir }) => {
+            write_man_pages(output_dir.as_deref())?;
+        }
+        Some(Command::ListPacks {
+            action: Some(action),
+            ..
+        }) => {
+            let (pack_id, enable, project) = match action {
+                PacksAction::Enable { pack_id, project } => (pack_id, true, project),
+                PacksAction::Disable { pack_id, project } => (pack_id, false, project),
+            };
+            toggle_pack(&config, &pack_id, enable, project)?;
+        }
+        Some(Command::ListPacks {
+            enabled,
+            format,
+            action: None,
+        }) => {
             // Robot mode forces JSON output
             let robot_mode = cli.robot || std::env::var("DCG_ROBOT").is_ok();
             let effective_format = if robot_mode {
@@ -1728,8 +2487,16 @@ pub fn run_command(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
         Some(Command::Pack { action }) => {
             handle_pack_command(&config, action)?;
         }
+        Some(Command::Pattern { action }) => match action {
+            PatternAction::Test {
+                file_path,
+                no_corpus,
+                format,
+            } => pattern_test(&file_path, !no_corpus, format)?,
+        },
         Some(Command::TestCommand {
             command,
+            stdin_batch,
             config: config_path,
             with_packs,
             explain,
@@ -1754,7 +2521,21 @@ pub fn run_command(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
                 config.clone()
             };
 
-            if explain {
+            let command = command.unwrap_or_default();
+            if stdin_batch {
+                let any_blocked = test_command_batch(
+                    &effective_config,
+                    with_packs,
+                    robot_mode,
+                    heredoc_scan,
+                    no_heredoc_scan,
+                    heredoc_timeout_ms,
+                    heredoc_languages,
+                )?;
+                if any_blocked {
+                    std::process::exit(EXIT_DENIED);
+                }
+            } else if explain {
                 // Delegate to explain handler for detailed trace output
                 // Convert TestFormat to ExplainFormat for explain mode
                 let explain_format = match effective_format {
@@ -1785,11 +2566,27 @@ pub fn run_command(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
         Some(Command::Init { output, force }) => {
             init_config(output, force)?;
         }
-        Some(Command::ShowConfig) => {
+        Some(Command::ShowConfig { action: None }) => {
             if !verbosity.quiet {
                 show_config(&config);
             }
         }
+        Some(Command::ShowConfig {
+            action: Some(ConfigAction::Effective { format }),
+        }) => {
+            show_effective_config(format)?;
+        }
+        Some(Command::Policy {
+            action: PolicyAction::Show { format, all },
+        }) => {
+            show_policy(&config, format, all)?;
+        }
+        Some(Command::Aliases { action }) => {
+            handle_aliases_command(&config, action)?;
+        }
+        Some(Command::GitHook { action }) => {
+            handle_git_hook(&config, action)?;
+        }
         Some(Command::Allowlist { action }) => {
             handle_allowlist_command(action)?;
         }
@@ -1848,6 +2645,9 @@ pub fn run_command(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
         Some(Command::AllowOnce(cmd)) => {
             handle_allow_once_command(&config, &cmd)?;
         }
+        Some(Command::Promote(cmd)) => {
+            handle_promote_command(&config, &cmd)?;
+        }
         Some(Command::Scan(scan)) => {
             handle_scan_command(&config, scan, verbosity)?;
         }
@@ -1874,6 +2674,9 @@ pub fn run_command(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
         Some(Command::Corpus(corpus)) => {
             handle_corpus_command(&config, &corpus)?;
         }
+        Some(Command::Bench(bench)) => {
+            handle_bench_command(&config, &bench)?;
+        }
         Some(Command::Stats(stats)) => {
             handle_stats_command(&config, &stats, verbosity.quiet)?;
         }
@@ -1883,10 +2686,51 @@ pub fn run_command(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
         Some(Command::SuggestAllowlist(cmd)) => {
             handle_suggest_allowlist_command(&config, &cmd)?;
         }
+        Some(Command::Recommend(cmd)) => {
+            handle_recommend_command(&config, &cmd)?;
+        }
+        Some(Command::Review(cmd)) => {
+            handle_review_command(&config, &cmd)?;
+        }
+        Some(Command::Top(cmd)) => {
+            handle_top_command(&config, &cmd)?;
+        }
+        Some(Command::Audit { action }) => {
+            handle_audit_command(&action)?;
+        }
         Some(Command::Dev { action }) => {
             handle_dev_command(&config, action, verbosity)?;
         }
+        Some(Command::Reload) => {
+            let trigger = crate::reload::request_reload()?;
+            println!("Reload requested (touched {}).", trigger.display());
+            println!("Running `dcg mcp-server` processes will pick up the new configuration.");
+        }
+        Some(Command::Daemon { action }) => {
+            handle_daemon(&config, action)?;
+        }
+        Some(Command::Warmup { all, json }) => {
+            handle_warmup(&config, all, json)?;
+        }
+        Some(Command::Cache { action }) => {
+            handle_cache(&config, action)?;
+        }
+        Some(Command::Notify { action }) => {
+            handle_notify(&config, &action)?;
+        }
+        Some(Command::Sync {
+            quiet,
+            status,
+            json,
+        }) => {
+            handle_sync(&config, quiet, status, json)?;
+        }
         Some(Command::McpServer) => {
+            // Compile enabled packs off the request path so the first check is fast.
+            let enabled = REGISTRY.expand_enabled_ordered(&config.enabled_pack_ids());
+            std::thread::spawn(move || {
+                let _ = REGISTRY.warm_up(&enabled);
+            });
             crate::mcp::run_mcp_server()?;
         }
         None => {
@@ -1899,122 +2743,512 @@ pub fn run_command(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-fn write_completions(shell: CompletionShell) -> Result<(), Box<dyn std::error::Error>> {
-    use std::io::{self, Write};
-
-    let mut cmd = Cli::command();
-    let bin_name = cmd.get_name().to_string();
-    let mut stdout = io::stdout();
-    generate(shell.as_shell(), &mut cmd, &bin_name, &mut stdout);
-    stdout.flush()?;
-    Ok(())
-}
-
-// ============================================================================
-// Hook Command (dcg hook --batch)
-// ============================================================================
-
-/// Run the hook command with optional batch processing.
-#[allow(clippy::too_many_lines)]
-fn run_hook_command(config: &Config, cmd: &HookCommand) -> Result<(), Box<dyn std::error::Error>> {
-    use std::io::{self, BufRead, Write};
-
-    // If not batch mode and not parallel, fall through to normal hook mode
-    if !cmd.batch && !cmd.parallel {
-        // Delegate to main.rs hook mode by returning an error
-        // that main.rs will catch and handle
-        return Err("Hook mode without --batch; delegating to main.rs".into());
-    }
+/// Handle `dcg warmup`.
+fn handle_warmup(config: &Config, all: bool, json: bool) -> Result<(), Box<dyn std::error::Error>> {
+    use colored::Colorize;
 
-    // Parallel implies batch
-    let workers = if cmd.workers == 0 {
-        std::thread::available_parallelism()
-            .map(std::num::NonZeroUsize::get)
-            .unwrap_or(4)
+    let pack_ids: Vec<String> = if all {
+        REGISTRY
+            .all_pack_ids()
+            .into_iter()
+            .map(str::to_string)
+            .collect()
     } else {
-        cmd.workers
+        REGISTRY.expand_enabled_ordered(&config.enabled_pack_ids())
     };
 
-    // Load configuration for evaluation
-    let compiled_overrides = config.overrides.compile();
-    let allowlists = crate::load_default_allowlists();
-    let heredoc_settings = config.heredoc_settings();
-    let enabled_packs = config.enabled_pack_ids();
-    let enabled_keywords = REGISTRY.collect_enabled_keywords(&enabled_packs);
-    let ordered_packs = REGISTRY.expand_enabled_ordered(&enabled_packs);
-    let keyword_index = REGISTRY.build_enabled_keyword_index(&ordered_packs);
-
-    // TODO: External pack loading is not yet implemented.
-    // When ExternalPackLoader is implemented, load custom YAML packs here.
+    let start = std::time::Instant::now();
+    let mut results = REGISTRY.warm_up(&pack_ids);
+    let total = start.elapsed();
+    results.sort_by_key(|r| std::cmp::Reverse(r.duration));
+    let pattern_count: usize = results.iter().map(|r| r.pattern_count).sum();
+    let error_count: usize = results.iter().map(|r| r.errors.len()).sum();
 
-    let stdin = io::stdin();
-    let stdout = io::stdout();
-    let mut stdout_lock = stdout.lock();
+    if json {
+        let output = serde_json::json!({
+            "packs": results.len(),
+            "patterns": pattern_count,
+            "total_us": u64::try_from(total.as_micros()).unwrap_or(u64::MAX),
+            "errors": error_count,
+            "results": results
+                .iter()
+                .map(|r| serde_json::json!({
+                    "pack_id": r.id,
+                    "patterns": r.pattern_count,
+                    "duration_us": u64::try_from(r.duration.as_micros()).unwrap_or(u64::MAX),
+                    "errors": r.errors,
+                }))
+                .collect::<Vec<_>>(),
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
 
-    if cmd.parallel && workers > 1 {
-        // Parallel processing: collect all lines, process in parallel, output in order
-        let lines: Vec<(usize, String)> = stdin
-            .lock()
-            .lines()
-            .enumerate()
-            .filter_map(|(idx, line)| line.ok().map(|l| (idx, l)))
-            .collect();
+    println!(
+        "{} {} packs, {} patterns in {:.1}ms",
+        "Compiled".bold(),
+        results.len(),
+        pattern_count,
+        total.as_secs_f64() * 1000.0
+    );
+    for result in &results {
+        println!(
+            "  {:<36} {:>4} patterns {:>8.2}ms",
+            result.id,
+            result.pattern_count,
+            result.duration.as_secs_f64() * 1000.0
+        );
+        for error in &result.errors {
+            println!("    {} {error}", "!".yellow());
+        }
+    }
+    if error_count > 0 {
+        println!(
+            "{} {error_count} pattern(s) failed to compile and will never match.",
+            "Warning:".yellow().bold()
+        );
+    }
+    Ok(())
+}
 
-        // Process in parallel using rayon
-        #[cfg(feature = "rayon")]
-        {
-            use rayon::prelude::*;
+/// Handle `dcg cache`.
+fn handle_daemon(config: &Config, action: DaemonAction) -> Result<(), Box<dyn std::error::Error>> {
+    use crate::daemon::{Request, Response};
+    use colored::Colorize;
 
-            let results: Vec<BatchHookOutput> = lines
-                .into_par_iter()
-                .map(|(index, line)| {
-                    evaluate_batch_line(
-                        index,
-                        &line,
-                        &enabled_keywords,
-                        &ordered_packs,
-                        keyword_index.as_ref(),
-                        &compiled_overrides,
-                        &allowlists,
-                        &heredoc_settings,
-                        cmd.continue_on_error,
-                    )
-                })
-                .collect();
+    const CLIENT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
 
-            // Sort by index and output
-            let mut sorted = results;
-            sorted.sort_by_key(|r| r.index);
+    let socket = crate::daemon::socket_path()
+        .ok_or("could not determine the daemon socket path (set DCG_DAEMON_SOCKET)")?;
 
-            for result in sorted {
-                let json = serde_json::to_string(&result)?;
-                writeln!(stdout_lock, "{json}")?;
+    match action {
+        DaemonAction::Start => {
+            let enabled = REGISTRY.expand_enabled_ordered(&config.enabled_pack_ids());
+            let _ = REGISTRY.warm_up(&enabled);
+            eprintln!("dcg daemon listening on {}", socket.display());
+            crate::daemon::serve(&socket)?;
+        }
+        DaemonAction::Status { json } => {
+            let status = match crate::daemon::send(&socket, &Request::Ping, CLIENT_TIMEOUT) {
+                Ok(Response::Pong(status)) => Some(status),
+                _ => None,
+            };
+            if json {
+                let output = serde_json::json!({
+                    "socket": socket,
+                    "running": status.is_some(),
+                    "status": status,
+                });
+                println!("{}", serde_json::to_string_pretty(&output)?);
+                return Ok(());
             }
+            let Some(status) = status else {
+                println!("{} {}", "dcg daemon:".bold(), "not running".yellow());
+                println!("  Socket: {}", socket.display());
+                return Ok(());
+            };
+            println!("{} {}", "dcg daemon:".bold(), "running".green());
+            println!("  Socket:    {}", socket.display());
+            println!("  PID:       {} (v{})", status.pid, status.version);
+            println!("  Started:   {}", status.started_at);
+            println!(
+                "  Requests:  {} evaluated, {} handed back to the hook",
+                status.evaluated, status.fallbacks
+            );
+            println!("  Projects:  {}", status.projects);
         }
-
-        // Fallback to sequential if rayon is not available
-        #[cfg(not(feature = "rayon"))]
-        {
-            for (index, line) in lines {
-                let result = evaluate_batch_line(
-                    index,
-                    &line,
-                    &enabled_keywords,
-                    &ordered_packs,
-                    keyword_index.as_ref(),
-                    &compiled_overrides,
-                    &allowlists,
-                    &heredoc_settings,
-                    cmd.continue_on_error,
-                );
-                let json = serde_json::to_string(&result)?;
-                writeln!(stdout_lock, "{json}")?;
+        DaemonAction::Stop => {
+            match crate::daemon::send(&socket, &Request::Shutdown, CLIENT_TIMEOUT) {
+                Ok(Response::Stopping) => println!("dcg daemon stopped."),
+                _ => println!("No dcg daemon is listening on {}.", socket.display()),
             }
         }
-    } else {
-        // Sequential processing: stream input to output
-        for (index, line) in stdin.lock().lines().enumerate() {
-            let line = match line {
+    }
+    Ok(())
+}
+
+fn handle_cache(config: &Config, action: CacheAction) -> Result<(), Box<dyn std::error::Error>> {
+    use colored::Colorize;
+
+    let fingerprint =
+        crate::fingerprint::ConfigFingerprint::new(config, &load_default_allowlists());
+    let cache = crate::eval_cache::EvalCache::for_config(config, &fingerprint)
+        .ok_or("could not determine the evaluation cache location")?;
+    let quick = crate::quick_allow::QuickAllowFilter::for_config(config, &fingerprint)
+        .ok_or("could not determine the quick-allow filter location")?;
+
+    match action {
+        CacheAction::Status { json } => {
+            let status = cache.status();
+            let quick_status = quick.status();
+            if json {
+                let output = serde_json::json!({
+                    "enabled": config.cache.is_active(),
+                    "max_entries": config.cache.max_entries,
+                    "ttl_secs": config.cache.ttl_secs,
+                    "config_fingerprint": fingerprint,
+                    "status": status,
+                    "quick_allow": {
+                        "enabled": config.cache.quick_allow,
+                        "status": quick_status,
+                    },
+                });
+                println!("{}", serde_json::to_string_pretty(&output)?);
+                return Ok(());
+            }
+            let state = if config.cache.is_active() {
+                "enabled".green()
+            } else {
+                "disabled".yellow()
+            };
+            println!("{} {}", "Config fingerprint:".bold(), fingerprint.short());
+            println!("{} {state}", "Evaluation cache:".bold());
+            println!("  Path:    {}", status.path.display());
+            println!(
+                "  Entries: {} live, {} stale (max {}, ttl {}s)",
+                status.live_entries,
+                status.stale_entries,
+                config.cache.max_entries,
+                config.cache.ttl_secs
+            );
+            if !status.fingerprint_matches && status.stale_entries > 0 {
+                println!("  Config changed since entries were recorded; they will be discarded.");
+            }
+            let quick_state = if config.cache.quick_allow {
+                "enabled".green()
+            } else {
+                "disabled".yellow()
+            };
+            println!("{} {quick_state}", "Quick-allow filter:".bold());
+            println!("  Path:    {}", quick_status.path.display());
+            if quick_status.fingerprint_matches {
+                println!(
+                    "  Entries: {} quick, {} seen once (reset at {} quick)",
+                    quick_status.quick,
+                    quick_status.seen,
+                    crate::quick_allow::QUICK_CAPACITY
+                );
+            } else if quick_status.path.exists() {
+                println!("  Config changed since entries were recorded; they will be discarded.");
+            }
+        }
+        CacheAction::Clear => {
+            let removed = cache.clear()?;
+            let quick_removed = quick.clear()?;
+            println!(
+                "Removed {removed} cached decision(s) and {quick_removed} quick-allow command(s)."
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Handle `dcg notify`.
+fn handle_notify(config: &Config, action: &NotifyAction) -> Result<(), Box<dyn std::error::Error>> {
+    use colored::Colorize;
+
+    let notifications = &config.notifications;
+    match action {
+        NotifyAction::Flush => {
+            crate::notifications::flush(notifications)?;
+        }
+        NotifyAction::Test => {
+            if notifications.webhooks.is_empty() {
+                return Err("no webhooks configured under [[notifications.webhooks]]".into());
+            }
+            if !notifications.enabled {
+                println!(
+                    "{} [notifications] enabled = false; denials will not be sent.",
+                    "Note:".yellow()
+                );
+            }
+            let cwd = std::env::current_dir().ok();
+            let event = crate::notifications::DenyEvent::new(
+                "dcg notify test",
+                Some("dcg"),
+                Some("notify-test"),
+                Some(crate::packs::Severity::High),
+                "Test notification from `dcg notify test`",
+                cwd.as_deref(),
+                &config.logging.redaction,
+            );
+            let mut failures = 0;
+            for webhook in &notifications.webhooks {
+                // Only the scheme and host; the path often carries a secret token.
+                let shown = webhook
+                    .url
+                    .splitn(4, '/')
+                    .take(3)
+                    .collect::<Vec<_>>()
+                    .join("/");
+                match crate::notifications::send(
+                    webhook,
+                    std::slice::from_ref(&event),
+                    notifications.timeout_ms,
+                ) {
+                    Ok(()) => println!("  {} {shown} ({:?})", "✓".green(), webhook.format),
+                    Err(e) => {
+                        failures += 1;
+                        println!("  {} {shown} ({:?}): {e}", "✗".red(), webhook.format);
+                    }
+                }
+            }
+            if failures > 0 {
+                return Err(format!("{failures} webhook(s) failed").into());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Handle `dcg sync`.
+fn handle_sync(
+    config: &Config,
+    quiet: bool,
+    status: bool,
+    json: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use colored::Colorize;
+
+    let remote = &config.remote;
+    if status {
+        let meta = crate::remote::status().unwrap_or_default();
+        if json {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&serde_json::json!({
+                    "enabled": remote.enabled,
+                    "url": remote.url,
+                    "cached": meta,
+                    "policy_path": crate::remote::cached_policy_path(),
+                }))?
+            );
+            return Ok(());
+        }
+        println!(
+            "Remote policy: {}",
+            if remote.is_active() {
+                "enabled".green()
+            } else {
+                "disabled".yellow()
+            }
+        );
+        if let Some(url) = &remote.url {
+            println!("  URL:          {url}");
+        }
+        match crate::remote::cached_policy_path() {
+            Some(path) => println!("  Cached:       {}", path.display()),
+            None => println!("  Cached:       {}", "none (run `dcg sync`)".dimmed()),
+        }
+        if let Some(name) = &meta.name {
+            println!("  Bundle:       {name}");
+        }
+        let format_time = |ts: Option<i64>| {
+            ts.and_then(|ts| chrono::DateTime::from_timestamp(ts, 0))
+                .map_or_else(|| "never".to_string(), |time| time.to_rfc3339())
+        };
+        println!("  Last fetched: {}", format_time(meta.fetched_at));
+        println!("  Last attempt: {}", format_time(meta.last_attempt));
+        return Ok(());
+    }
+
+    if !remote.enabled {
+        return Err("remote policy sync is disabled (set [remote] enabled = true)".into());
+    }
+    let outcome = crate::remote::sync(remote)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&outcome)?);
+    } else if !quiet {
+        match outcome {
+            crate::remote::SyncOutcome::NotModified => {
+                println!("{} Policy bundle is up to date.", "✓".green());
+            }
+            crate::remote::SyncOutcome::Updated {
+                name,
+                allow_entries,
+                custom_packs,
+            } => {
+                println!(
+                    "{} Installed policy bundle{} ({allow_entries} allowlist entries, {custom_packs} custom packs).",
+                    "✓".green(),
+                    name.map(|name| format!(" '{name}'")).unwrap_or_default()
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_completions(shell: CompletionShell) -> Result<(), Box<dyn std::error::Error>> {
+    use std::io::{self, Write};
+
+    let mut cmd = completion_command();
+    let bin_name = cmd.get_name().to_string();
+    let mut stdout = io::stdout();
+    generate(shell.as_shell(), &mut cmd, &bin_name, &mut stdout);
+    stdout.flush()?;
+    Ok(())
+}
+
+/// Argument IDs that take a pack ID or category.
+const PACK_ID_ARGS: &[&str] = &["pack", "pack_id", "with_packs"];
+
+/// The CLI definition with pack ID arguments restricted to known pack IDs and
+/// categories, so shells complete them. Only used for generating completions:
+/// the real parser still accepts external pack IDs.
+fn completion_command() -> clap::Command {
+    let mut values: Vec<&'static str> = REGISTRY.all_pack_ids();
+    let categories: Vec<&'static str> = values
+        .iter()
+        .filter_map(|id| id.split_once('.').map(|(category, _)| category))
+        .collect();
+    values.extend(categories);
+    values.sort_unstable();
+    values.dedup();
+    with_pack_id_values(Cli::command(), &values)
+}
+
+fn with_pack_id_values(cmd: clap::Command, values: &[&'static str]) -> clap::Command {
+    let pack_args: Vec<clap::Id> = cmd
+        .get_arguments()
+        .filter(|arg| PACK_ID_ARGS.contains(&arg.get_id().as_str()))
+        .map(|arg| arg.get_id().clone())
+        .collect();
+    let mut cmd = cmd;
+    for id in pack_args {
+        cmd = cmd.mut_arg(id, |arg| {
+            // `dev benchmark` defaults to "all".
+            let all = arg.get_default_values().iter().any(|value| value == "all");
+            arg.value_parser(clap::builder::PossibleValuesParser::new(
+                values.iter().copied().chain(all.then_some("all")),
+            ))
+        });
+    }
+    cmd.mut_subcommands(|sub| with_pack_id_values(sub, values))
+}
+
+fn write_man_pages(output_dir: Option<&std::path::Path>) -> Result<(), Box<dyn std::error::Error>> {
+    use std::io::{self, Write};
+
+    let cmd = Cli::command();
+    if let Some(dir) = output_dir {
+        std::fs::create_dir_all(dir)?;
+        clap_mangen::generate_to(cmd, dir)?;
+        eprintln!("Wrote man pages to {}", dir.display());
+        return Ok(());
+    }
+
+    let mut stdout = io::stdout();
+    clap_mangen::Man::new(cmd).render(&mut stdout)?;
+    stdout.flush()?;
+    Ok(())
+}
+
+// ============================================================================
+// Hook Command (dcg hook --batch)
+// ============================================================================
+
+/// Run the hook command with optional batch processing.
+#[allow(clippy::too_many_lines)]
+fn run_hook_command(config: &Config, cmd: &HookCommand) -> Result<(), Box<dyn std::error::Error>> {
+    use std::io::{self, BufRead, Write};
+
+    // If not batch mode and not parallel, fall through to normal hook mode
+    if !cmd.batch && !cmd.parallel {
+        // Delegate to main.rs hook mode by returning an error
+        // that main.rs will catch and handle
+        return Err("Hook mode without --batch; delegating to main.rs".into());
+    }
+
+    // Parallel implies batch
+    let workers = if cmd.workers == 0 {
+        std::thread::available_parallelism()
+            .map(std::num::NonZeroUsize::get)
+            .unwrap_or(4)
+    } else {
+        cmd.workers
+    };
+
+    // Load configuration for evaluation
+    let compiled_overrides = config.compile_overrides();
+    let allowlists = crate::load_default_allowlists();
+    let heredoc_settings = config.heredoc_settings();
+    let enabled_packs = config.enabled_pack_ids();
+    let enabled_keywords = REGISTRY.collect_enabled_keywords(&enabled_packs);
+    let ordered_packs = REGISTRY.expand_enabled_ordered(&enabled_packs);
+    let keyword_index = REGISTRY.build_enabled_keyword_index(&ordered_packs);
+
+    // TODO: External pack loading is not yet implemented.
+    // When ExternalPackLoader is implemented, load custom YAML packs here.
+
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    let mut stdout_lock = stdout.lock();
+
+    if cmd.parallel && workers > 1 {
+        // Parallel processing: collect all lines, process in parallel, output in order
+        let lines: Vec<(usize, String)> = stdin
+            .lock()
+            .lines()
+            .enumerate()
+            .filter_map(|(idx, line)| line.ok().map(|l| (idx, l)))
+            .collect();
+
+        // Process in parallel using rayon
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+
+            let results: Vec<BatchHookOutput> = lines
+                .into_par_iter()
+                .map(|(index, line)| {
+                    evaluate_batch_line(
+                        index,
+                        &line,
+                        &enabled_keywords,
+                        &ordered_packs,
+                        keyword_index.as_ref(),
+                        &compiled_overrides,
+                        &allowlists,
+                        &heredoc_settings,
+                        cmd.continue_on_error,
+                    )
+                })
+                .collect();
+
+            // Sort by index and output
+            let mut sorted = results;
+            sorted.sort_by_key(|r| r.index);
+
+            for result in sorted {
+                let json = serde_json::to_string(&result)?;
+                writeln!(stdout_lock, "{json}")?;
+            }
+        }
+
+        // Fallback to sequential if rayon is not available
+        #[cfg(not(feature = "rayon"))]
+        {
+            for (index, line) in lines {
+                let result = evaluate_batch_line(
+                    index,
+                    &line,
+                    &enabled_keywords,
+                    &ordered_packs,
+                    keyword_index.as_ref(),
+                    &compiled_overrides,
+                    &allowlists,
+                    &heredoc_settings,
+                    cmd.continue_on_error,
+                );
+                let json = serde_json::to_string(&result)?;
+                writeln!(stdout_lock, "{json}")?;
+            }
+        }
+    } else {
+        // Sequential processing: stream input to output
+        for (index, line) in stdin.lock().lines().enumerate() {
+            let line = match line {
                 Ok(l) => l,
                 Err(e) => {
                     if cmd.continue_on_error {
@@ -2146,15 +3380,211 @@ fn evaluate_batch_line(
                         (rule_id, info.pack_id.clone())
                     });
 
-            BatchHookOutput {
-                index,
-                decision: "deny",
-                rule_id,
-                pack_id,
-                error: None,
-            }
-        }
+            BatchHookOutput {
+                index,
+                decision: "deny",
+                rule_id,
+                pack_id,
+                error: None,
+            }
+        }
+    }
+}
+
+/// Whether `pack_id` names a built-in pack, a built-in category, or a loaded external pack.
+fn is_known_pack_id<'a>(pack_id: &str, mut external_ids: impl Iterator<Item = &'a String>) -> bool {
+    REGISTRY.get_entry(pack_id).is_some()
+        || REGISTRY
+            .all_categories()
+            .iter()
+            .any(|c| c.as_str() == pack_id)
+        || external_ids.any(|id| id == pack_id)
+}
+
+/// The `[packs] <key>` array in `doc`, created if missing.
+fn packs_array<'a>(
+    doc: &'a mut toml_edit::DocumentMut,
+    key: &str,
+) -> Result<&'a mut toml_edit::Array, String> {
+    doc.entry("packs")
+        .or_insert(toml_edit::table())
+        .as_table_like_mut()
+        .ok_or("`packs` is not a table")?
+        .entry(key)
+        .or_insert(toml_edit::value(toml_edit::Array::new()))
+        .as_array_mut()
+        .ok_or_else(|| format!("`packs.{key}` is not an array"))
+}
+
+/// Move `pack_id` into `[packs] enabled` (or `disabled`) and out of the other list.
+///
+/// Returns whether the document changed.
+fn set_pack_enabled(
+    doc: &mut toml_edit::DocumentMut,
+    pack_id: &str,
+    enable: bool,
+) -> Result<bool, String> {
+    let (add_to, remove_from) = if enable {
+        ("enabled", "disabled")
+    } else {
+        ("disabled", "enabled")
+    };
+    let mut changed = false;
+
+    let remove = packs_array(doc, remove_from)?;
+    let before = remove.len();
+    remove.retain(|value| value.as_str() != Some(pack_id));
+    changed |= remove.len() != before;
+
+    let add = packs_array(doc, add_to)?;
+    if !add.iter().any(|value| value.as_str() == Some(pack_id)) {
+        add.push(pack_id);
+        changed = true;
+    }
+    Ok(changed)
+}
+
+/// Config file `dcg packs enable/disable` edits.
+fn pack_toggle_config_path(
+    project: bool,
+) -> Result<std::path::PathBuf, Box<dyn std::error::Error>> {
+    if project {
+        let cwd = std::env::current_dir()?;
+        let root = crate::config::find_repo_root(&cwd, crate::config::REPO_ROOT_SEARCH_MAX_HOPS)
+            .ok_or("--project requires running inside a git repository")?;
+        return Ok(root.join(".dcg.toml"));
+    }
+    if let Some(path) = std::env::var(crate::config::ENV_CONFIG_PATH)
+        .ok()
+        .and_then(|value| crate::config::resolve_config_path_value(&value, None))
+    {
+        return Ok(path);
+    }
+    Ok(config_path())
+}
+
+/// Enabled pack IDs after category expansion.
+fn expanded_pack_ids(config: &Config) -> Vec<String> {
+    REGISTRY.expand_enabled_ordered(&config.enabled_pack_ids())
+}
+
+/// Distinct pack keywords for `config`.
+fn enabled_keyword_count(config: &Config) -> usize {
+    REGISTRY
+        .collect_enabled_keywords(&config.enabled_pack_ids())
+        .into_iter()
+        .collect::<std::collections::HashSet<_>>()
+        .len()
+}
+
+/// Median in-process evaluation time per bench stage.
+fn pack_toggle_latency(config: &Config) -> Vec<(crate::bench::Stage, u64)> {
+    const ITERATIONS: usize = 20;
+    crate::bench::run_in_process(config, &load_default_allowlists(), ITERATIONS)
+        .into_iter()
+        .filter(|(stage, _)| {
+            matches!(
+                stage,
+                crate::bench::Stage::QuickReject | crate::bench::Stage::PackEval
+            )
+        })
+        .map(|(stage, stats)| (stage, stats.p50_us))
+        .collect()
+}
+
+/// `dcg packs enable|disable <id>`.
+fn toggle_pack(
+    config: &Config,
+    pack_id: &str,
+    enable: bool,
+    project: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use colored::Colorize;
+
+    let external = load_external_packs(&config.packs.expand_custom_paths());
+    if !is_known_pack_id(pack_id, external.pack_ids()) {
+        return Err(format!("Unknown pack: {pack_id} (run `dcg packs` to list packs)").into());
+    }
+
+    let path = pack_toggle_config_path(project)?;
+    let existing = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(format!("Failed to read {}: {e}", path.display()).into()),
+    };
+    let mut doc: toml_edit::DocumentMut = existing
+        .parse()
+        .map_err(|e| format!("Failed to parse {}: {e}", path.display()))?;
+
+    let verb = if enable { "enabled" } else { "disabled" };
+    if !set_pack_enabled(&mut doc, pack_id, enable)? {
+        println!("{pack_id} is already {verb} in {}", path.display());
+        return Ok(());
+    }
+
+    let keywords_before = enabled_keyword_count(config);
+    let latency_before = pack_toggle_latency(config);
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    crate::remote::write_atomic(&path, &doc.to_string())?;
+    record_audit(
+        if enable {
+            "packs.enable"
+        } else {
+            "packs.disable"
+        },
+        &path,
+        serde_json::json!({ "pack": pack_id }),
+    );
+    println!(
+        "{} {pack_id} in {}",
+        if enable { "Enabled" } else { "Disabled" }.green(),
+        path.display()
+    );
+
+    let after = Config::load();
+    let prefix = format!("{pack_id}.");
+    let still_on: Vec<String> = expanded_pack_ids(&after)
+        .into_iter()
+        .filter(|id| id == pack_id || id.starts_with(&prefix))
+        .collect();
+    if !enable && !still_on.is_empty() {
+        let reason = if pack_id == "core" || pack_id.starts_with("core.") {
+            "core packs are always enabled"
+        } else {
+            "enabled by a project override or another config layer"
+        };
+        println!(
+            "{} still enabled ({reason}): {}",
+            "Warning:".yellow(),
+            still_on.join(", ")
+        );
+    }
+    if enable && still_on.is_empty() {
+        println!(
+            "{} {pack_id} is still off; a `disabled` entry in another config layer or \
+             a parent category overrides it",
+            "Warning:".yellow()
+        );
+    }
+
+    let keywords_after = enabled_keyword_count(&after);
+    println!(
+        "Keywords: {keywords_before} -> {keywords_after} ({:+})",
+        i64::try_from(keywords_after).unwrap_or(i64::MAX)
+            - i64::try_from(keywords_before).unwrap_or(i64::MAX)
+    );
+    for ((stage, before), (_, after_us)) in
+        latency_before.into_iter().zip(pack_toggle_latency(&after))
+    {
+        println!(
+            "Median {}: {before}us -> {after_us}us",
+            stage.as_str().replace('_', " ")
+        );
     }
+    Ok(())
 }
 
 /// List all packs and their status
@@ -2857,6 +4287,100 @@ struct PackEngineSummary {
     linear_percentage: f64,
 }
 
+// ============================================================================
+// Pattern Commands (dcg pattern test)
+// ============================================================================
+
+/// Run an external pack against its examples and the embedded corpus
+fn pattern_test(
+    file_path: &str,
+    include_corpus: bool,
+    format: PackValidateFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use crate::pattern_test::ExampleKind;
+    use colored::Colorize;
+
+    let pack = crate::packs::external::parse_pack_file(std::path::Path::new(file_path))?;
+    let report = crate::pattern_test::run(&pack, include_corpus);
+
+    match format {
+        PackValidateFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        }
+        PackValidateFormat::Pretty => {
+            println!("{}", "Pattern Test Report".bold().cyan());
+            println!();
+            println!("File: {file_path}");
+            println!("Pack: {}", report.pack_id);
+            println!();
+            println!(
+                "  {} positive examples",
+                report.count(ExampleKind::Positive).to_string().cyan()
+            );
+            println!(
+                "  {} negative examples",
+                report.count(ExampleKind::Negative).to_string().cyan()
+            );
+            if include_corpus {
+                println!(
+                    "  {} corpus commands",
+                    report.count(ExampleKind::Corpus).to_string().cyan()
+                );
+            }
+
+            let false_negatives: Vec<_> = report.false_negatives().collect();
+            if !false_negatives.is_empty() {
+                println!();
+                println!("{}", "False negatives (should block):".bold().red());
+                for result in false_negatives {
+                    println!("  {} {}", "✗".red(), result.command);
+                }
+            }
+
+            let false_positives: Vec<_> = report.false_positives().collect();
+            if !false_positives.is_empty() {
+                println!();
+                println!("{}", "False positives (should allow):".bold().red());
+                for result in false_positives {
+                    let source = result.corpus.map_or_else(
+                        || "examples.negative".to_string(),
+                        |name| format!("corpus/{name}"),
+                    );
+                    println!("  {} {}", "✗".red(), result.command);
+                    println!(
+                        "    {}",
+                        format!(
+                            "→ matched {} ({source})",
+                            result.matched_pattern.as_deref().unwrap_or("unnamed")
+                        )
+                        .dimmed()
+                    );
+                }
+            }
+
+            if !report.untested_patterns.is_empty() {
+                println!();
+                println!("{}", "Untested patterns:".bold().yellow());
+                for name in &report.untested_patterns {
+                    println!("  {} {name} has no matching positive example", "⚠".yellow());
+                }
+            }
+
+            println!();
+            if report.passed() {
+                println!("{}", "✓ All examples behaved as expected.".bold().green());
+            } else {
+                println!("{}", "✗ Pattern test failed.".bold().red());
+            }
+        }
+    }
+
+    if !report.passed() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum InteractiveDecision {
     Block,
@@ -2990,9 +4514,20 @@ fn resolve_mode_for_cli(
     let pack = info.pack_id.as_deref();
     let pattern = info.pattern_name.as_deref();
 
+    let severity =
+        crate::kube_context::adjust_severity(&config.kube_context, command, pack, info.severity)
+            .map_or(info.severity, |adjustment| Some(adjustment.to));
+    let cwd = std::env::current_dir().ok();
+    let severity = crate::protected_paths::escalate_in_workspace(
+        &config.protection.workspaces,
+        cwd.as_deref(),
+        severity,
+    )
+    .map_or(severity, |escalation| Some(escalation.to));
+
     let mut mode = match info.source {
         MatchSource::Pack | MatchSource::HeredocAst => {
-            config.policy().resolve_mode(pack, pattern, info.severity)
+            config.policy().resolve_mode(pack, pattern, severity)
         }
         MatchSource::ConfigOverride | MatchSource::LegacyPattern => DecisionMode::Deny,
     };
@@ -3025,6 +4560,230 @@ fn resolve_mode_for_cli(
     Some(mode)
 }
 
+/// Apply `dcg test` flags (`--with-packs`, heredoc overrides) on top of `config`.
+fn test_effective_config(
+    config: &Config,
+    extra_packs: Option<Vec<String>>,
+    heredoc_scan: bool,
+    no_heredoc_scan: bool,
+    heredoc_timeout_ms: Option<u64>,
+    heredoc_languages: Option<Vec<String>>,
+) -> Config {
+    let mut effective_config = extra_packs.map_or_else(
+        || config.clone(),
+        |packs| {
+            let mut modified = config.clone();
+            modified.packs.enabled.extend(packs);
+            modified
+        },
+    );
+
+    // CLI overrides for heredoc scanning (higher priority than env/config file).
+    if heredoc_scan {
+        effective_config.heredoc.enabled = Some(true);
+    }
+    if no_heredoc_scan {
+        effective_config.heredoc.enabled = Some(false);
+    }
+    if let Some(timeout_ms) = heredoc_timeout_ms {
+        effective_config.heredoc.timeout_ms = Some(timeout_ms);
+    }
+    if let Some(langs) = heredoc_languages {
+        effective_config.heredoc.languages = Some(langs);
+    }
+
+    effective_config
+}
+
+/// Describe the detected agent for JSON test output.
+fn test_agent_info(config: &Config, detection: &crate::agent::DetectionResult) -> AgentInfo {
+    let trust_level = config.trust_level_for_agent(&detection.agent);
+    AgentInfo {
+        detected: detection.agent.config_key().to_string(),
+        trust_level: format!("{:?}", trust_level).to_lowercase(),
+        detection_method: match detection.method {
+            DetectionMethod::Environment => "environment_variable".to_string(),
+            DetectionMethod::Explicit => "explicit".to_string(),
+            DetectionMethod::Process => "process".to_string(),
+            DetectionMethod::None => "none".to_string(),
+        },
+    }
+}
+
+/// Build the JSON output record for one evaluated command.
+#[allow(clippy::too_many_lines)]
+fn build_test_output(
+    command: &str,
+    result: &EvaluationResult,
+    robot_mode: bool,
+    agent_info: &AgentInfo,
+    fingerprint: &crate::fingerprint::ConfigFingerprint,
+) -> TestOutput {
+    match result.decision {
+        EvaluationDecision::Allow => {
+            let allowlist = result
+                .allowlist_override
+                .as_ref()
+                .map(|info| AllowlistOverrideInfo {
+                    layer: info.layer.label().to_string(),
+                    reason: info.reason.clone(),
+                });
+            TestOutput {
+                schema_version: TEST_OUTPUT_SCHEMA_VERSION,
+                dcg_version: env!("CARGO_PKG_VERSION").to_string(),
+                robot_mode,
+                command: command.to_string(),
+                decision: "allow".to_string(),
+                rule_id: None,
+                pack_id: None,
+                pattern_name: None,
+                reason: None,
+                explanation: None,
+                source: None,
+                matched_span: None,
+                severity: None,
+                allowlist,
+                agent: Some(agent_info.clone()),
+                config_fingerprint: fingerprint.to_string(),
+            }
+        }
+        EvaluationDecision::Deny => {
+            let (
+                pack_id,
+                pattern_name,
+                reason,
+                explanation,
+                source_str,
+                matched_span,
+                rule_id,
+                severity,
+            ) = result.pattern_info.as_ref().map_or(
+                (None, None, None, None, None, None, None, None),
+                |info| {
+                    let source_str = match info.source {
+                        MatchSource::ConfigOverride => "config_override",
+                        MatchSource::LegacyPattern => "legacy_pattern",
+                        MatchSource::Pack => "pack",
+                        MatchSource::HeredocAst => "heredoc_ast",
+                    };
+                    let rule_id = info
+                        .pack_id
+                        .as_ref()
+                        .and_then(|p| info.pattern_name.as_ref().map(|n| format!("{p}:{n}")));
+                    let severity_str = info.severity.map(|s| match s {
+                        PackSeverity::Critical => "critical",
+                        PackSeverity::High => "high",
+                        PackSeverity::Medium => "medium",
+                        PackSeverity::Low => "low",
+                    });
+                    (
+                        info.pack_id.clone(),
+                        info.pattern_name.clone(),
+                        Some(info.reason.clone()),
+                        info.explanation.clone(),
+                        Some(source_str.to_string()),
+                        info.matched_span.as_ref().map(|s| (s.start, s.end)),
+                        rule_id,
+                        severity_str.map(std::string::ToString::to_string),
+                    )
+                },
+            );
+            TestOutput {
+                schema_version: TEST_OUTPUT_SCHEMA_VERSION,
+                dcg_version: env!("CARGO_PKG_VERSION").to_string(),
+                robot_mode,
+                command: command.to_string(),
+                decision: "deny".to_string(),
+                rule_id,
+                pack_id,
+                pattern_name,
+                reason,
+                explanation,
+                source: source_str,
+                matched_span,
+                severity,
+                allowlist: None,
+                agent: Some(agent_info.clone()),
+                config_fingerprint: fingerprint.to_string(),
+            }
+        }
+    }
+}
+
+/// Parse `dcg test --stdin-batch` input into commands.
+///
+/// Input starting with `[` is read as a JSON array of strings; anything else is
+/// one command per line, with blank lines skipped.
+fn parse_batch_input(input: &str) -> Result<Vec<String>, String> {
+    let trimmed = input.trim_start();
+    if trimmed.starts_with('[') {
+        return serde_json::from_str(trimmed)
+            .map_err(|e| format!("invalid JSON array of commands: {e}"));
+    }
+    Ok(input
+        .lines()
+        .map(|line| line.trim_end_matches('\r'))
+        .filter(|line| !line.trim().is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Evaluate every command on stdin and print a JSON array of test results.
+///
+/// The pack order, keyword index, overrides, and allowlists are built once and
+/// reused for each command. Returns `true` if any command would be blocked.
+fn test_command_batch(
+    config: &Config,
+    extra_packs: Option<Vec<String>>,
+    robot_mode: bool,
+    heredoc_scan: bool,
+    no_heredoc_scan: bool,
+    heredoc_timeout_ms: Option<u64>,
+    heredoc_languages: Option<Vec<String>>,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    use std::io::Read;
+
+    let mut input = String::new();
+    std::io::stdin().read_to_string(&mut input)?;
+    let commands = parse_batch_input(&input)?;
+
+    let effective_config = test_effective_config(
+        config,
+        extra_packs,
+        heredoc_scan,
+        no_heredoc_scan,
+        heredoc_timeout_ms,
+        heredoc_languages,
+    );
+    let allowlists = load_default_allowlists();
+    let evaluator = BatchEvaluator::new(&effective_config, &allowlists);
+    let fingerprint = crate::fingerprint::ConfigFingerprint::new(&effective_config, &allowlists);
+    let alias_table = crate::aliases::AliasTable::load(&effective_config.aliases);
+    let agent_info = test_agent_info(&effective_config, &detect_agent_with_details());
+
+    let mut any_blocked = false;
+    let outputs: Vec<TestOutput> = commands
+        .iter()
+        .map(|raw| {
+            let command = alias_table
+                .as_ref()
+                .map_or(std::borrow::Cow::Borrowed(raw.as_str()), |table| {
+                    table.expand(raw)
+                });
+            let command = crate::env_expand::expand_if_enabled(
+                command,
+                effective_config.general.expand_variables,
+            );
+            let result = evaluator.evaluate(&command);
+            any_blocked |= result.decision == EvaluationDecision::Deny;
+            build_test_output(&command, &result, robot_mode, &agent_info, &fingerprint)
+        })
+        .collect();
+
+    println!("{}", serde_json::to_string_pretty(&outputs)?);
+    Ok(any_blocked)
+}
+
 /// Test a command against the configured packs using the shared evaluator.
 ///
 /// This ensures parity with hook mode by using the same evaluation logic:
@@ -3059,190 +4818,51 @@ fn test_command(
         return false; // Explain mode doesn't track blocked status
     }
 
-    // Build effective config with extra packs if specified
-    let mut effective_config = extra_packs.map_or_else(
-        || config.clone(),
-        |packs| {
-            let mut modified = config.clone();
-            modified.packs.enabled.extend(packs);
-            modified
-        },
+    let effective_config = test_effective_config(
+        config,
+        extra_packs,
+        heredoc_scan,
+        no_heredoc_scan,
+        heredoc_timeout_ms,
+        heredoc_languages,
     );
 
-    // CLI overrides for heredoc scanning (higher priority than env/config file).
-    if heredoc_scan {
-        effective_config.heredoc.enabled = Some(true);
-    }
-    if no_heredoc_scan {
-        effective_config.heredoc.enabled = Some(false);
-    }
-    if let Some(timeout_ms) = heredoc_timeout_ms {
-        effective_config.heredoc.timeout_ms = Some(timeout_ms);
-    }
-    if let Some(langs) = heredoc_languages {
-        effective_config.heredoc.languages = Some(langs);
-    }
-
-    // Get enabled packs and collect keywords for quick rejection
-    let mut enabled_packs = effective_config.enabled_pack_ids();
-    let mut enabled_keywords = REGISTRY.collect_enabled_keywords(&enabled_packs);
-    let heredoc_settings = effective_config.heredoc_settings();
-
-    // Compile overrides once (not per-command)
-    let compiled_overrides = effective_config.overrides.compile();
-
     // Load allowlists (project/user/system) for parity with hook mode.
     // This is a small file read and only affects decisions when a rule matches.
     let allowlists = load_default_allowlists();
 
-    // Load external packs from custom_paths (glob + tilde expansion).
-    let external_paths = effective_config.packs.expand_custom_paths();
-    let external_store = load_external_packs(&external_paths);
-
-    // Auto-enable external packs and merge their keywords.
-    for id in external_store.pack_ids() {
-        enabled_packs.insert(id.clone());
-    }
-    enabled_keywords.extend(external_store.keywords().iter().copied());
-
-    // Build ordered pack list AFTER external packs are loaded so they're included.
-    let mut ordered_packs = REGISTRY.expand_enabled_ordered(&enabled_packs);
-    for id in external_store.pack_ids() {
-        if !ordered_packs.contains(id) {
-            ordered_packs.push(id.clone());
-        }
-    }
-    // Disable keyword index when external packs are present (not covered by index).
-    let keyword_index = if external_store.pack_ids().next().is_some() {
-        None
-    } else {
-        REGISTRY.build_enabled_keyword_index(&ordered_packs)
-    };
+    // Pack order, keyword index, overrides, and external packs are resolved once.
+    let evaluator = BatchEvaluator::new(&effective_config, &allowlists);
+    let fingerprint = crate::fingerprint::ConfigFingerprint::new(&effective_config, &allowlists);
 
     // Detect the current AI coding agent for agent-specific profiles
     let detection = detect_agent_with_details();
-    let trust_level = effective_config.trust_level_for_agent(&detection.agent);
-    let agent_info = AgentInfo {
-        detected: detection.agent.config_key().to_string(),
-        trust_level: format!("{:?}", trust_level).to_lowercase(),
-        detection_method: match detection.method {
-            DetectionMethod::Environment => "environment_variable".to_string(),
-            DetectionMethod::Explicit => "explicit".to_string(),
-            DetectionMethod::Process => "process".to_string(),
-            DetectionMethod::None => "none".to_string(),
-        },
-    };
+    let agent_info = test_agent_info(&effective_config, &detection);
+
+    // Expand shell aliases/functions and variables like hook mode; report the
+    // expanded form so matched spans line up with what was evaluated.
+    let alias_table = crate::aliases::AliasTable::load(&effective_config.aliases);
+    let expanded = alias_table
+        .as_ref()
+        .map_or(std::borrow::Cow::Borrowed(command), |table| {
+            table.expand(command)
+        });
+    let expanded =
+        crate::env_expand::expand_if_enabled(expanded, effective_config.general.expand_variables);
+    let command = expanded.as_ref();
 
     // Use shared evaluator for consistent behavior with hook mode
     let start = Instant::now();
-    let result = evaluate_command_with_pack_order_deadline_at_path(
-        command,
-        &enabled_keywords,
-        &ordered_packs,
-        keyword_index.as_ref(),
-        &compiled_overrides,
-        &allowlists,
-        &heredoc_settings,
-        None, // allow_once_audit
-        None, // project_path
-        None, // deadline
-    );
+    let result = evaluator.evaluate(command);
 
     // NOTE: External packs from custom_paths are now checked in evaluate_command()
-    // alongside built-in packs, so no separate fallback check is needed here.
-
-    let elapsed = start.elapsed();
-
-    // Handle JSON output
-    if format == TestFormat::Json {
-        let output = match result.decision {
-            EvaluationDecision::Allow => {
-                let allowlist =
-                    result
-                        .allowlist_override
-                        .as_ref()
-                        .map(|info| AllowlistOverrideInfo {
-                            layer: info.layer.label().to_string(),
-                            reason: info.reason.clone(),
-                        });
-                TestOutput {
-                    schema_version: TEST_OUTPUT_SCHEMA_VERSION,
-                    dcg_version: env!("CARGO_PKG_VERSION").to_string(),
-                    robot_mode,
-                    command: command.to_string(),
-                    decision: "allow".to_string(),
-                    rule_id: None,
-                    pack_id: None,
-                    pattern_name: None,
-                    reason: None,
-                    explanation: None,
-                    source: None,
-                    matched_span: None,
-                    severity: None,
-                    allowlist,
-                    agent: Some(agent_info.clone()),
-                }
-            }
-            EvaluationDecision::Deny => {
-                let (
-                    pack_id,
-                    pattern_name,
-                    reason,
-                    explanation,
-                    source_str,
-                    matched_span,
-                    rule_id,
-                    severity,
-                ) = result.pattern_info.as_ref().map_or(
-                    (None, None, None, None, None, None, None, None),
-                    |info| {
-                        let source_str = match info.source {
-                            MatchSource::ConfigOverride => "config_override",
-                            MatchSource::LegacyPattern => "legacy_pattern",
-                            MatchSource::Pack => "pack",
-                            MatchSource::HeredocAst => "heredoc_ast",
-                        };
-                        let rule_id = info
-                            .pack_id
-                            .as_ref()
-                            .and_then(|p| info.pattern_name.as_ref().map(|n| format!("{p}:{n}")));
-                        let severity_str = info.severity.map(|s| match s {
-                            PackSeverity::Critical => "critical",
-                            PackSeverity::High => "high",
-                            PackSeverity::Medium => "medium",
-                            PackSeverity::Low => "low",
-                        });
-                        (
-                            info.pack_id.clone(),
-                            info.pattern_name.clone(),
-                            Some(info.reason.clone()),
-                            info.explanation.clone(),
-                            Some(source_str.to_string()),
-                            info.matched_span.as_ref().map(|s| (s.start, s.end)),
-                            rule_id,
-                            severity_str.map(std::string::ToString::to_string),
-                        )
-                    },
-                );
-                TestOutput {
-                    schema_version: TEST_OUTPUT_SCHEMA_VERSION,
-                    dcg_version: env!("CARGO_PKG_VERSION").to_string(),
-                    robot_mode,
-                    command: command.to_string(),
-                    decision: "deny".to_string(),
-                    rule_id,
-                    pack_id,
-                    pattern_name,
-                    reason,
-                    explanation,
-                    source: source_str,
-                    matched_span,
-                    severity,
-                    allowlist: None,
-                    agent: Some(agent_info.clone()),
-                }
-            }
-        };
+    // alongside built-in packs, so no separate fallback check is needed here.
+
+    let elapsed = start.elapsed();
+
+    // Handle JSON output
+    if format == TestFormat::Json {
+        let output = build_test_output(command, &result, robot_mode, &agent_info, &fingerprint);
         println!("{}", serde_json::to_string_pretty(&output).unwrap());
         return result.decision == EvaluationDecision::Deny;
     }
@@ -3310,8 +4930,13 @@ fn test_command(
                 if let Some(ref pattern_name) = info.pattern_name {
                     println!("Pattern: {pattern_name}");
                 }
-                println!("Reason: {}", info.reason);
-                if let Some(ref explanation) = info.explanation {
+                let localizer = crate::i18n::localizer();
+                let pack_id = info.pack_id.as_deref();
+                let pattern_name = info.pattern_name.as_deref();
+                let reason = localizer.rule_reason(pack_id, pattern_name);
+                println!("Reason: {}", reason.as_deref().unwrap_or(&info.reason));
+                let explanation = localizer.rule_explanation(pack_id, pattern_name);
+                if let Some(explanation) = explanation.as_deref().or(info.explanation.as_deref()) {
                     println!("Explanation: {explanation}");
                 }
                 let source = match info.source {
@@ -3453,6 +5078,7 @@ fn test_command(
 
     if verbosity.is_verbose() {
         println!("Elapsed: {:.2}ms", elapsed.as_secs_f64() * 1000.0);
+        println!("Config fingerprint: {}", fingerprint.short());
         println!("Agent: {}", detection.agent);
         println!("Trust level: {}", agent_info.trust_level);
         if let Some(ref info) = result.pattern_info {
@@ -3502,6 +5128,10 @@ fn test_command(
 
 /// Generate a sample configuration file
 fn init_config(output: Option<String>, force: bool) -> Result<(), Box<dyn std::error::Error>> {
+    if output.is_none() && should_run_init_wizard() {
+        return run_init_wizard(force);
+    }
+
     let sample = Config::generate_sample_config();
 
     match output {
@@ -3519,6 +5149,7 @@ fn init_config(output: Option<String>, force: bool) -> Result<(), Box<dyn std::e
             }
 
             std::fs::write(path, sample)?;
+            record_audit("config.init", path, serde_json::Value::Null);
             println!("Configuration written to: {}", path.display());
         }
         None => {
@@ -3529,6 +5160,231 @@ fn init_config(output: Option<String>, force: bool) -> Result<(), Box<dyn std::e
     Ok(())
 }
 
+/// Ecosystems offered by the `dcg init` wizard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InitEcosystem {
+    Git,
+    Containers,
+    Kubernetes,
+    Databases,
+    Cloud,
+    Infrastructure,
+}
+
+impl InitEcosystem {
+    const ALL: [Self; 6] = [
+        Self::Git,
+        Self::Containers,
+        Self::Kubernetes,
+        Self::Databases,
+        Self::Cloud,
+        Self::Infrastructure,
+    ];
+
+    /// Packs to enable; categories expand to every sub-pack. Git is covered by
+    /// the always-on core packs.
+    const fn packs(self) -> &'static [&'static str] {
+        match self {
+            Self::Git => &[],
+            Self::Containers => &["containers"],
+            Self::Kubernetes => &["kubernetes"],
+            Self::Databases => &["database"],
+            Self::Cloud => &["cloud"],
+            Self::Infrastructure => &["infrastructure"],
+        }
+    }
+
+    /// A command the smoke test expects to be blocked once the ecosystem is enabled.
+    const fn smoke_command(self) -> &'static str {
+        match self {
+            Self::Git => "git reset --hard HEAD~1",
+            Self::Containers => "docker system prune -af",
+            Self::Kubernetes => "kubectl delete namespace production",
+            Self::Databases => "dropdb production",
+            Self::Cloud => "aws ec2 terminate-instances --instance-ids i-0123456789abcdef0",
+            Self::Infrastructure => "terraform destroy",
+        }
+    }
+}
+
+impl std::fmt::Display for InitEcosystem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Git => "Git (core protections are always on)",
+            Self::Containers => "Containers (Docker, Compose, Podman)",
+            Self::Kubernetes => "Kubernetes (kubectl, Helm, Kustomize)",
+            Self::Databases => "Databases (PostgreSQL, MySQL, MongoDB, Redis, ...)",
+            Self::Cloud => "Cloud providers (AWS, GCP, Azure, CloudFormation)",
+            Self::Infrastructure => "Infrastructure as code (Terraform, Pulumi, Ansible)",
+        })
+    }
+}
+
+/// Whether `dcg init` should prompt instead of printing the sample config.
+fn should_run_init_wizard() -> bool {
+    if std::env::var("DCG_NON_INTERACTIVE").is_ok() || std::env::var("CI").is_ok() {
+        return false;
+    }
+    std::io::stdin().is_terminal() && std::io::stdout().is_terminal()
+}
+
+/// Pack IDs enabled for the chosen ecosystems.
+fn init_wizard_packs(ecosystems: &[InitEcosystem], strict_git: bool) -> Vec<String> {
+    let mut packs: Vec<String> = ecosystems
+        .iter()
+        .flat_map(|e| e.packs().iter().map(|p| (*p).to_string()))
+        .collect();
+    if strict_git {
+        packs.push("strict_git".to_string());
+    }
+    packs
+}
+
+/// Render the commented sample config with `packs.enabled` set to `packs`.
+fn render_init_config(packs: &[String]) -> Result<String, Box<dyn std::error::Error>> {
+    let mut doc: toml_edit::DocumentMut = Config::generate_sample_config().parse()?;
+    let enabled: toml_edit::Array = packs.iter().map(String::as_str).collect();
+    doc["packs"]["enabled"] = toml_edit::value(enabled);
+    Ok(doc.to_string())
+}
+
+/// Commands the post-setup smoke test runs, with whether each should be blocked.
+fn init_smoke_cases(ecosystems: &[InitEcosystem]) -> Vec<(&'static str, bool)> {
+    let mut cases = vec![
+        ("git status", false),
+        (InitEcosystem::Git.smoke_command(), true),
+    ];
+    cases.extend(
+        ecosystems
+            .iter()
+            .filter(|e| **e != InitEcosystem::Git)
+            .map(|e| (e.smoke_command(), true)),
+    );
+    cases
+}
+
+/// Interactive first-run setup for `dcg init`.
+#[allow(clippy::too_many_lines)]
+fn run_init_wizard(force: bool) -> Result<(), Box<dyn std::error::Error>> {
+    use colored::Colorize;
+    use inquire::{Confirm, MultiSelect};
+
+    let console = crate::output::console();
+    println!("{}", "Welcome to dcg".green().bold());
+    println!("dcg blocks destructive shell commands before your coding agent runs them.");
+    println!();
+
+    // Step 1: ecosystems -> packs
+    console.rule(Some("1/4 Ecosystems"));
+    let ecosystems = MultiSelect::new(
+        "Which ecosystems do you work with?",
+        InitEcosystem::ALL.to_vec(),
+    )
+    .with_default(&[0])
+    .with_help_message("Space to toggle, Enter to confirm")
+    .prompt()?;
+    let strict_git = ecosystems.contains(&InitEcosystem::Git)
+        && Confirm::new("Also block every force push, rebase, and history rewrite (strict_git)?")
+            .with_default(false)
+            .prompt()?;
+    let packs = init_wizard_packs(&ecosystems, strict_git);
+
+    // Step 2: config file
+    console.rule(Some("2/4 Configuration"));
+    let path = config_path();
+    let write = !path.exists()
+        || force
+        || Confirm::new(&format!(
+            "{} already exists. Replace it? (the old file is kept as a backup)",
+            path.display()
+        ))
+        .with_default(false)
+        .prompt()?;
+    if write {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let backup = write_settings_with_backup(&path, &render_init_config(&packs)?)?;
+        record_audit("config.init", &path, serde_json::json!({ "packs": packs }));
+        println!("{} {}", "Configuration written to:".green(), path.display());
+        if let Some(backup) = backup {
+            println!("Previous config saved to: {}", backup.display());
+        }
+        if packs.is_empty() {
+            println!("Enabled packs: core only");
+        } else {
+            println!("Enabled packs: core, {}", packs.join(", "));
+        }
+    } else {
+        println!("Keeping existing configuration at {}", path.display());
+    }
+
+    // Step 3: agent hooks
+    console.rule(Some("3/4 Agent hooks"));
+    let targets = [
+        HookTarget::ClaudeCode,
+        HookTarget::Cursor,
+        HookTarget::Codex,
+    ];
+    let labels: Vec<&str> = targets.iter().map(|t| t.display_name()).collect();
+    // Preselect agents whose config directory already exists.
+    let defaults: Vec<usize> = targets
+        .iter()
+        .enumerate()
+        .filter(|(_, t)| {
+            t.settings_path()
+                .parent()
+                .is_some_and(std::path::Path::exists)
+        })
+        .map(|(i, _)| i)
+        .collect();
+    let selected = MultiSelect::new("Install the dcg hook for:", labels)
+        .with_default(&defaults)
+        .with_help_message("Space to toggle, Enter to confirm (none to skip)")
+        .raw_prompt()?;
+    for option in selected {
+        if let Err(e) = install_hook(targets[option.index], force) {
+            println!(
+                "{} {}: {e}",
+                "Could not install hook for".red(),
+                targets[option.index].display_name()
+            );
+        }
+    }
+
+    // Step 4: smoke test against the config that will actually be loaded
+    console.rule(Some("4/4 Smoke test"));
+    let config = Config::load();
+    let enabled = config.enabled_pack_ids();
+    let keywords = REGISTRY.collect_enabled_keywords(&enabled);
+    let compiled = config.compile_overrides();
+    let allowlists = load_default_allowlists();
+    let mut failures = 0;
+    for (command, expect_block) in init_smoke_cases(&ecosystems) {
+        let result = evaluate_command(command, &config, &keywords, &compiled, &allowlists);
+        let blocked = result.is_denied();
+        let verdict = if blocked { "blocked" } else { "allowed" };
+        if blocked == expect_block {
+            println!("  {} {verdict:<8} {command}", "✓".green());
+        } else {
+            failures += 1;
+            println!("  {} {verdict:<8} {command}", "✗".red());
+        }
+    }
+    println!();
+    if failures == 0 {
+        println!("{}", "dcg is ready.".green().bold());
+    } else {
+        println!(
+            "{} {failures} smoke test(s) did not behave as expected. Run `dcg doctor` and \
+             `dcg config effective` to see which settings are in effect.",
+            "Warning:".yellow().bold()
+        );
+    }
+    println!("Try `dcg explain \"<command>\"` to see why a command is blocked or allowed.");
+    Ok(())
+}
+
 /// Show the current configuration
 fn show_config(config: &Config) {
     println!("Current configuration:");
@@ -3558,60 +5414,440 @@ fn show_config(config: &Config) {
             } else {
                 println!("  - DCG_CONFIG: {} (missing)", path.display());
             }
-        } else {
-            println!("  - DCG_CONFIG: (set but empty)");
+        } else {
+            println!("  - DCG_CONFIG: (set but empty)");
+        }
+    }
+    println!();
+    println!("General:");
+    println!("  Color: {}", config.general.color);
+    println!("  Verbose: {}", config.general.verbose);
+    println!("  Log file: {:?}", config.general.log_file);
+    println!();
+    println!("Fail mode:");
+    for class in [
+        crate::config::FailureClass::Timeout,
+        crate::config::FailureClass::ParseError,
+        crate::config::FailureClass::Io,
+        crate::config::FailureClass::SizeLimit,
+        crate::config::FailureClass::Telemetry,
+    ] {
+        let mode = match config.policy.fail_mode.mode_for(class) {
+            crate::config::FailMode::Open => "open",
+            crate::config::FailMode::Closed => "closed",
+        };
+        println!("  {}: {mode}", class.label());
+    }
+    println!();
+    println!("Enabled packs:");
+    for pack in config.enabled_pack_ids() {
+        println!("  - {pack}");
+    }
+    println!();
+    println!("Disabled packs:");
+    for pack in &config.packs.disabled {
+        println!("  - {pack}");
+    }
+    println!();
+
+    let heredoc = config.heredoc_settings();
+    println!("Heredoc scanning:");
+    println!("  Enabled: {}", heredoc.enabled);
+    println!("  Timeout (ms): {}", heredoc.limits.timeout_ms);
+    println!("  Max body bytes: {}", heredoc.limits.max_body_bytes);
+    println!("  Max body lines: {}", heredoc.limits.max_body_lines);
+    println!("  Max heredocs: {}", heredoc.limits.max_heredocs);
+    println!(
+        "  Fail-open on parse error: {}",
+        heredoc.fallback_on_parse_error
+    );
+    println!("  Fail-open on timeout: {}", heredoc.fallback_on_timeout);
+
+    let lang_label = |lang: crate::heredoc::ScriptLanguage| -> &'static str {
+        match lang {
+            crate::heredoc::ScriptLanguage::Bash => "bash",
+            crate::heredoc::ScriptLanguage::Go => "go",
+            crate::heredoc::ScriptLanguage::Php => "php",
+            crate::heredoc::ScriptLanguage::Python => "python",
+            crate::heredoc::ScriptLanguage::Ruby => "ruby",
+            crate::heredoc::ScriptLanguage::Perl => "perl",
+            crate::heredoc::ScriptLanguage::JavaScript => "javascript",
+            crate::heredoc::ScriptLanguage::TypeScript => "typescript",
+            crate::heredoc::ScriptLanguage::Sql => "sql",
+            crate::heredoc::ScriptLanguage::Hcl => "hcl",
+            crate::heredoc::ScriptLanguage::Yaml => "yaml",
+            crate::heredoc::ScriptLanguage::Unknown => "unknown",
+        }
+    };
+
+    if let Some(langs) = &heredoc.allowed_languages {
+        let langs = langs.iter().copied().map(lang_label).collect::<Vec<_>>();
+        println!("  Languages: {}", langs.join(","));
+    } else {
+        println!("  Languages: all");
+    }
+}
+
+/// Print the merged configuration along with the layers that produced it.
+fn show_effective_config(format: ConfigEffectiveFormat) -> Result<(), Box<dyn std::error::Error>> {
+    let (config, sources) = Config::load_with_sources();
+
+    match format {
+        ConfigEffectiveFormat::Json => {
+            let output = serde_json::json!({
+                "sources": sources,
+                "config": config,
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        ConfigEffectiveFormat::Toml => {
+            println!("# Effective dcg configuration");
+            println!("# Layers (lowest → highest priority):");
+            if sources.is_empty() {
+                println!("#   (none - built-in defaults only)");
+            }
+            for source in &sources {
+                match &source.path {
+                    Some(path) => println!("#   - {}: {}", source.layer, path.display()),
+                    None => println!("#   - {}: {}", source.layer, source.env_vars.join(", ")),
+                }
+                if !source.sections.is_empty() {
+                    println!("#       sections: {}", source.sections.join(", "));
+                }
+                if !source.packs_enabled.is_empty() {
+                    println!("#       packs enabled: {}", source.packs_enabled.join(", "));
+                }
+                if !source.packs_disabled.is_empty() {
+                    println!(
+                        "#       packs disabled: {}",
+                        source.packs_disabled.join(", ")
+                    );
+                }
+            }
+            println!();
+            print!("{}", toml::to_string_pretty(&config)?);
+        }
+    }
+
+    Ok(())
+}
+
+/// One row of the `dcg policy show` matrix.
+#[derive(Debug, serde::Serialize)]
+struct PolicyRuleRow {
+    rule_id: String,
+    severity: &'static str,
+    effective_severity: &'static str,
+    mode: &'static str,
+}
+
+/// Print severity overrides and the resulting per-rule policy matrix.
+fn show_policy(
+    config: &Config,
+    format: PolicyShowFormat,
+    all: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use colored::Colorize;
+
+    let policy = config.policy();
+    let issues = policy.validate_severity_overrides();
+    let mut overrides: Vec<(&String, &String)> = policy.severity_overrides.iter().collect();
+    overrides.sort();
+
+    let mut rows = Vec::new();
+    for pack_id in REGISTRY.expand_enabled_ordered(&config.enabled_pack_ids()) {
+        let Some(pack) = REGISTRY.get(&pack_id) else {
+            continue;
+        };
+        for pattern in &pack.destructive_patterns {
+            let Some(name) = pattern.name else {
+                continue;
+            };
+            let effective = policy
+                .effective_severity(Some(&pack_id), Some(name), Some(pattern.severity))
+                .unwrap_or(pattern.severity);
+            let mode = policy.resolve_mode(Some(&pack_id), Some(name), Some(pattern.severity));
+            if !all && effective == pattern.severity && mode == pattern.severity.default_mode() {
+                continue;
+            }
+            rows.push(PolicyRuleRow {
+                rule_id: format!("{pack_id}:{name}"),
+                severity: pattern.severity.label(),
+                effective_severity: effective.label(),
+                mode: mode.label(),
+            });
+        }
+    }
+
+    match format {
+        PolicyShowFormat::Json => {
+            let output = serde_json::json!({
+                "default_mode": policy.default_mode,
+                "observe_until": policy.observe_until,
+                "overrides": overrides
+                    .iter()
+                    .map(|(key, value)| serde_json::json!({ "key": key, "severity": value }))
+                    .collect::<Vec<_>>(),
+                "issues": issues,
+                "rules": rows,
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        PolicyShowFormat::Pretty => {
+            println!("{}", "Decision-mode policy".bold());
+            println!(
+                "  default_mode: {}",
+                policy
+                    .default_mode
+                    .map_or("(severity defaults)", |mode| mode
+                        .to_decision_mode()
+                        .label())
+            );
+            println!();
+
+            println!("{}", "Severity overrides:".bold());
+            if overrides.is_empty() {
+                println!("  (none)");
+            }
+            for (key, value) in &overrides {
+                println!("  {key} = {value}");
+            }
+            for issue in &issues {
+                println!("  {} {issue}", "!".yellow());
+            }
+            println!();
+
+            println!("{}", "Rules:".bold());
+            if rows.is_empty() {
+                println!("  (all enabled rules use their default severity and mode)");
+            }
+            for row in &rows {
+                println!(
+                    "  {:<48} {:>8} -> {:<8} {}",
+                    row.rule_id, row.severity, row.effective_severity, row.mode
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn handle_aliases_command(
+    config: &Config,
+    action: AliasesAction,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use crate::aliases::{AliasTable, default_snapshot_path};
+
+    match action {
+        AliasesAction::Snapshot { shell, output } => {
+            let shell = shell
+                .or_else(|| std::env::var("SHELL").ok())
+                .unwrap_or_else(|| "bash".to_string());
+            let path = output
+                .or_else(default_snapshot_path)
+                .ok_or("could not determine the dcg config directory; pass --output")?;
+
+            let captured = std::process::Command::new(&shell)
+                .args(["-ic", "alias; typeset -f"])
+                .stdin(std::process::Stdio::null())
+                .stderr(std::process::Stdio::null())
+                .output()?;
+            if !captured.status.success() {
+                return Err(format!("{shell} exited with {}", captured.status).into());
+            }
+
+            let content = String::from_utf8_lossy(&captured.stdout);
+            let table = AliasTable::parse(&content);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, content.as_bytes())?;
+            println!(
+                "Wrote {} aliases and {} functions to {}",
+                table.alias_count(),
+                table.function_count(),
+                path.display()
+            );
+            if !config.aliases.enabled {
+                println!("Note: alias expansion is disabled ([aliases] enabled = false).");
+            }
+        }
+        AliasesAction::List => {
+            let Some(table) = AliasTable::load(&config.aliases) else {
+                if config.aliases.enabled {
+                    println!("No aliases or functions loaded. Run `dcg aliases snapshot` first.");
+                } else {
+                    println!("Alias expansion is disabled ([aliases] enabled = false).");
+                }
+                return Ok(());
+            };
+            for (name, value) in table.aliases() {
+                println!("alias {name}='{value}'");
+            }
+            for (name, body) in table.functions() {
+                println!("{name}() {{ {} }}", body.replace('\n', "; "));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn handle_git_hook(
+    config: &Config,
+    action: GitHookAction,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let cwd = std::env::current_dir()?;
+    match action {
+        GitHookAction::Install => {
+            for path in install_git_hooks_at(&cwd)? {
+                eprintln!("Installed git hook: {}", path.display());
+            }
+        }
+        GitHookAction::Uninstall => {
+            let removed = uninstall_git_hooks_at(&cwd)?;
+            if removed.is_empty() {
+                eprintln!("No dcg git hooks found (nothing to remove).");
+            }
+            for path in removed {
+                eprintln!("Removed git hook: {}", path.display());
+            }
+        }
+        GitHookAction::PrePush { remote, url: _ } => {
+            use std::io::Read;
+
+            let mut input = String::new();
+            std::io::stdin().read_to_string(&mut input)?;
+            let implied: Vec<_> = crate::git_hook::parse_pre_push_input(&input)
+                .iter()
+                .filter_map(|update| {
+                    let effect = crate::git_hook::classify_push(&cwd, update);
+                    crate::git_hook::implied_push_command(&remote, update, effect)
+                })
+                .collect();
+            if !check_git_hook_commands(config, "push", &implied) {
+                std::process::exit(EXIT_DENIED);
+            }
+        }
+        GitHookAction::PreRebase { upstream, branch } => {
+            let implied: Vec<_> =
+                crate::git_hook::implied_rebase_command(&cwd, &upstream, branch.as_deref())
+                    .into_iter()
+                    .collect();
+            if !check_git_hook_commands(config, "rebase", &implied) {
+                std::process::exit(EXIT_DENIED);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Evaluate the commands a git operation implies. Returns false if any is denied.
+fn check_git_hook_commands(
+    config: &Config,
+    operation: &str,
+    implied: &[crate::git_hook::ImpliedCommand],
+) -> bool {
+    if implied.is_empty() {
+        return true;
+    }
+
+    let allowlists = load_default_allowlists();
+    let evaluator = BatchEvaluator::new(config, &allowlists);
+    let mut allowed = true;
+    for item in implied {
+        let result = evaluator.evaluate(&item.command);
+        if result.decision != EvaluationDecision::Deny {
+            continue;
+        }
+        let Some(info) = result.pattern_info.as_ref() else {
+            continue;
+        };
+        let rule = info
+            .pack_id
+            .as_deref()
+            .zip(info.pattern_name.as_deref())
+            .map(|(pack, pattern)| format!(" [{pack}:{pattern}]"))
+            .unwrap_or_default();
+        match resolve_mode_for_cli(config, &item.command, &result).unwrap_or(DecisionMode::Deny) {
+            DecisionMode::Deny => {
+                allowed = false;
+                eprintln!("dcg blocked this {operation}: {}", item.description);
+                eprintln!("  Evaluated as: {}", item.command);
+                eprintln!("  Reason: {}{rule}", info.reason);
+            }
+            DecisionMode::Warn => {
+                eprintln!("dcg warning: {}", item.description);
+                eprintln!("  Evaluated as: {}", item.command);
+                eprintln!("  Reason: {}{rule}", info.reason);
+            }
+            DecisionMode::Log => {}
         }
     }
-    println!();
-    println!("General:");
-    println!("  Color: {}", config.general.color);
-    println!("  Verbose: {}", config.general.verbose);
-    println!("  Log file: {:?}", config.general.log_file);
-    println!();
-    println!("Enabled packs:");
-    for pack in config.enabled_pack_ids() {
-        println!("  - {pack}");
+    if !allowed {
+        eprintln!("Bypass once (unsafe): git {operation} --no-verify");
     }
-    println!();
-    println!("Disabled packs:");
-    for pack in &config.packs.disabled {
-        println!("  - {pack}");
+    allowed
+}
+
+fn install_git_hooks_at(
+    cwd: &std::path::Path,
+) -> Result<Vec<std::path::PathBuf>, Box<dyn std::error::Error>> {
+    ensure_git_repo(cwd)?;
+
+    // Check every hook first so a refusal leaves nothing half-installed.
+    let mut paths = Vec::new();
+    for name in crate::git_hook::MANAGED_HOOKS {
+        let hook_path = crate::git_hook::hook_path(cwd, name)?;
+        if hook_path.exists() && !crate::git_hook::is_managed_hook(&std::fs::read(&hook_path)?) {
+            return Err(format!(
+                "Refusing to overwrite existing {name} hook at {}\n\n\
+This hook does not appear to have been installed by dcg.\n\n\
+Manual integration: add this line to your existing hook:\n\
+  dcg git-hook {name} \"$@\"",
+                hook_path.display()
+            )
+            .into());
+        }
+        paths.push((name, hook_path));
     }
-    println!();
 
-    let heredoc = config.heredoc_settings();
-    println!("Heredoc scanning:");
-    println!("  Enabled: {}", heredoc.enabled);
-    println!("  Timeout (ms): {}", heredoc.limits.timeout_ms);
-    println!("  Max body bytes: {}", heredoc.limits.max_body_bytes);
-    println!("  Max body lines: {}", heredoc.limits.max_body_lines);
-    println!("  Max heredocs: {}", heredoc.limits.max_heredocs);
-    println!(
-        "  Fail-open on parse error: {}",
-        heredoc.fallback_on_parse_error
-    );
-    println!("  Fail-open on timeout: {}", heredoc.fallback_on_timeout);
+    let mut installed = Vec::new();
+    for (name, hook_path) in paths {
+        if let Some(parent) = hook_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&hook_path, crate::git_hook::hook_script(name))?;
 
-    let lang_label = |lang: crate::heredoc::ScriptLanguage| -> &'static str {
-        match lang {
-            crate::heredoc::ScriptLanguage::Bash => "bash",
-            crate::heredoc::ScriptLanguage::Go => "go",
-            crate::heredoc::ScriptLanguage::Php => "php",
-            crate::heredoc::ScriptLanguage::Python => "python",
-            crate::heredoc::ScriptLanguage::Ruby => "ruby",
-            crate::heredoc::ScriptLanguage::Perl => "perl",
-            crate::heredoc::ScriptLanguage::JavaScript => "javascript",
-            crate::heredoc::ScriptLanguage::TypeScript => "typescript",
-            crate::heredoc::ScriptLanguage::Unknown => "unknown",
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            let mut perms = std::fs::metadata(&hook_path)?.permissions();
+            perms.set_mode(0o755);
+            std::fs::set_permissions(&hook_path, perms)?;
         }
-    };
+        installed.push(hook_path);
+    }
 
-    if let Some(langs) = &heredoc.allowed_languages {
-        let langs = langs.iter().copied().map(lang_label).collect::<Vec<_>>();
-        println!("  Languages: {}", langs.join(","));
-    } else {
-        println!("  Languages: all");
+    Ok(installed)
+}
+
+fn uninstall_git_hooks_at(
+    cwd: &std::path::Path,
+) -> Result<Vec<std::path::PathBuf>, Box<dyn std::error::Error>> {
+    ensure_git_repo(cwd)?;
+
+    let mut removed = Vec::new();
+    for name in crate::git_hook::MANAGED_HOOKS {
+        let hook_path = crate::git_hook::hook_path(cwd, name)?;
+        if hook_path.exists() && crate::git_hook::is_managed_hook(&std::fs::read(&hook_path)?) {
+            std::fs::remove_file(&hook_path)?;
+            removed.push(hook_path);
+        }
     }
+    Ok(removed)
 }
 
 const DCG_SCAN_PRE_COMMIT_SENTINEL: &str = "# dcg:scan-pre-commit";
@@ -3924,13 +6160,14 @@ fn handle_simulate_command(
 ) -> Result<(), Box<dyn std::error::Error>> {
     use crate::simulate::{
         SimulateLimits, SimulateOutputConfig, SimulationConfig, format_json_output,
-        format_pretty_output, run_simulation_from_reader,
+        format_pretty_output, run_simulation_from_history, run_simulation_from_reader,
     };
     use std::fs::File;
     use std::io::{self, BufReader};
 
     let SimulateCommand {
         file,
+        from_history,
         max_lines,
         max_bytes,
         max_command_bytes,
@@ -3947,11 +6184,12 @@ fn handle_simulate_command(
         max_command_bytes: Some(max_command_bytes),
     };
 
-    // Open input (file or stdin)
-    let reader: Box<dyn io::Read> = if file == "-" {
+    // Open input (history file, regular file, or stdin)
+    let input_path = from_history.as_deref().unwrap_or(&file);
+    let reader: Box<dyn io::Read> = if input_path == "-" {
         Box::new(io::stdin())
     } else {
-        Box::new(BufReader::new(File::open(&file)?))
+        Box::new(BufReader::new(File::open(input_path)?))
     };
 
     let sim_config = SimulationConfig::default();
@@ -3964,13 +6202,18 @@ fn handle_simulate_command(
         }
         if verbosity.is_trace() {
             eprintln!(
-                "Simulate input: file={file}, max_lines={max_lines:?}, max_bytes={max_bytes:?}, top={top}, truncate={truncate}, redact={redact:?}"
+                "Simulate input: file={input_path}, from_history={}, max_lines={max_lines:?}, max_bytes={max_bytes:?}, top={top}, truncate={truncate}, redact={redact:?}",
+                from_history.is_some()
             );
         }
     }
 
     // Run simulation with evaluation loop
-    let result = run_simulation_from_reader(reader, limits, config, sim_config, strict)?;
+    let result = if from_history.is_some() {
+        run_simulation_from_history(reader, limits, config, sim_config, strict)?
+    } else {
+        run_simulation_from_reader(reader, limits, config, sim_config, strict)?
+    };
 
     // Build output configuration
     let output_config = SimulateOutputConfig {
@@ -4731,8 +6974,8 @@ fn truncate_for_markdown(s: &str, max_len: usize) -> String {
 
 /// Handle the `dcg explain` subcommand.
 ///
-/// Shows a detailed decision trace for why a command would be allowed or denied.
-/// Currently wraps the evaluator result; full tracing integration is future work.
+/// Shows a detailed decision trace for why a command would be allowed or denied:
+/// each pipeline stage, the candidate packs, and the matched span in context.
 #[allow(clippy::needless_pass_by_value)] // Value consumed from CLI args
 fn handle_explain(
     config: &Config,
@@ -4758,14 +7001,14 @@ fn handle_explain(
     let ordered_packs = REGISTRY.expand_enabled_ordered(&enabled_packs);
     let keyword_index = REGISTRY.build_enabled_keyword_index(&ordered_packs);
     let heredoc_settings = effective_config.heredoc_settings();
-    let compiled_overrides = effective_config.overrides.compile();
+    let compiled_overrides = effective_config.compile_overrides();
     let allowlists = crate::LayeredAllowlist::default();
 
     // Start tracing
     let mut collector = TraceCollector::new(command);
 
-    // Evaluate with timing
-    collector.begin_step();
+    // Evaluate with timing; the decision always comes from the real evaluator.
+    let eval_start = std::time::Instant::now();
     let result = evaluate_command_with_pack_order(
         command,
         &enabled_keywords,
@@ -4775,13 +7018,24 @@ fn handle_explain(
         &allowlists,
         &heredoc_settings,
     );
-    collector.end_step(
+    let eval_duration_us = u64::try_from(eval_start.elapsed().as_micros()).unwrap_or(u64::MAX);
+
+    // Then replay the individual stages so the trace shows how far it got.
+    crate::evaluator::trace_pipeline_stages(
+        &mut collector,
+        command,
+        &enabled_keywords,
+        &ordered_packs,
+        &compiled_overrides,
+        &heredoc_settings,
+        &result,
+    );
+    collector.record_step(
         "full_evaluation",
-        TraceDetails::KeywordGating {
-            quick_rejected: result.decision == EvaluationDecision::Allow
-                && result.pattern_info.is_none(),
-            keywords_checked: enabled_keywords.iter().map(|s| (*s).to_string()).collect(),
-            first_match: result.pattern_info.as_ref().and_then(|p| p.pack_id.clone()),
+        eval_duration_us,
+        TraceDetails::PolicyDecision {
+            decision: result.decision,
+            allowlisted: result.allowlist_override.is_some(),
         },
     );
     collector.set_budget_skip(result.skipped_due_to_budget);
@@ -4876,6 +7130,9 @@ fn explain_rich(trace: &crate::trace::ExplainTrace) {
 
     if has_normalized || has_sanitized {
         con.print(&format!("├─ [cyan]Input:[/]      {}", trace.command));
+        if let Some(markers) = trace.sanitization_markers() {
+            con.print(&format!("│              [yellow]{markers}[/]"));
+        }
         if has_normalized {
             let branch = if has_sanitized { "├─" } else { "└─" };
             con.print(&format!(
@@ -4934,6 +7191,11 @@ fn explain_rich(trace: &crate::trace::ExplainTrace) {
                 con.print(&format!("   [dim]{line}[/]"));
             }
         }
+        if let Some(highlight) = trace.match_highlight(false) {
+            con.print("");
+            con.print(&format!("   {}", highlight.command_line));
+            con.print(&format!("   [red bold]{}[/]", highlight.caret_line));
+        }
         con.print("");
     }
 
@@ -5001,10 +7263,13 @@ fn explain_rich(trace: &crate::trace::ExplainTrace) {
                 TraceDetails::KeywordGating {
                     quick_rejected,
                     first_match,
+                    keywords_hit,
                     ..
                 } => {
                     if *quick_rejected {
                         "[green]quick pass[/]".to_string()
+                    } else if keywords_hit.len() > 1 {
+                        format!("matched: {}", keywords_hit.join(", "))
                     } else if let Some(kw) = first_match {
                         format!("matched: {kw}")
                     } else {
@@ -5402,7 +7667,7 @@ fn run_single_corpus_test(
     let enabled_keywords = REGISTRY.collect_enabled_keywords(&enabled_packs);
     let ordered_packs = REGISTRY.expand_enabled_ordered(&enabled_packs);
     let keyword_index = REGISTRY.build_enabled_keyword_index(&ordered_packs);
-    let compiled_overrides = effective_config.overrides.compile();
+    let compiled_overrides = effective_config.compile_overrides();
     let allowlists = crate::LayeredAllowlist::default();
     let heredoc_settings = effective_config.heredoc_settings();
 
@@ -5490,12 +7755,79 @@ fn run_single_corpus_test(
 }
 
 /// Handle the `dcg corpus` command.
+/// Run `dcg bench` and exit non-zero when a stage is over budget.
+fn handle_bench_command(
+    config: &Config,
+    cmd: &BenchCommand,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use crate::bench::{BenchReport, Stage};
+    use colored::Colorize;
+
+    let allowlists = load_default_allowlists();
+    let mut measured = Vec::new();
+    if cmd.cold_start_iterations > 0 {
+        let exe = std::env::current_exe()?;
+        measured.push((
+            Stage::ColdStart,
+            crate::bench::cold_start(&exe, cmd.cold_start_iterations)?,
+        ));
+    }
+    measured.extend(crate::bench::run_in_process(
+        config,
+        &allowlists,
+        cmd.iterations,
+    ));
+    let report = BenchReport::new(&config.bench, cmd.iterations, measured);
+
+    match cmd.format {
+        CorpusFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        }
+        CorpusFormat::Pretty => {
+            println!("{}", "dcg bench".bold().cyan());
+            println!();
+            println!(
+                "{:<14} {:>8} {:>10} {:>10} {:>10} {:>12}",
+                "Stage", "Samples", "p50 (µs)", "p99 (µs)", "max (µs)", "Budget (µs)"
+            );
+            println!("{}", "-".repeat(69));
+            for stage in &report.stages {
+                let p99 = format!("{:>10}", stage.stats.p99_us);
+                println!(
+                    "{:<14} {:>8} {:>10} {} {:>10} {:>12}",
+                    stage.stage.as_str(),
+                    stage.stats.samples,
+                    stage.stats.p50_us,
+                    if stage.passed { p99.green() } else { p99.red() },
+                    stage.stats.max_us,
+                    stage.threshold_us
+                );
+            }
+            println!();
+            if report.passed {
+                println!("{}", "✓ All stages within budget.".bold().green());
+            } else {
+                println!("{}", "✗ p99 latency over budget.".bold().red());
+            }
+        }
+    }
+
+    if !report.passed {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
 fn handle_corpus_command(
     config: &Config,
     cmd: &CorpusCommand,
 ) -> Result<(), Box<dyn std::error::Error>> {
     use colored::Colorize;
 
+    if let Some(action) = &cmd.action {
+        return handle_corpus_action(config, action);
+    }
+
     // Run corpus tests
     let mut output = run_corpus(config, &cmd.dir, cmd.category.as_deref());
 
@@ -5552,6 +7884,130 @@ fn handle_corpus_command(
     Ok(())
 }
 
+/// Handle `dcg corpus record` and `dcg corpus replay`.
+fn handle_corpus_action(
+    config: &Config,
+    action: &CorpusAction,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use crate::replay::{ChangeKind, DecisionRecorder, RecordedCorpus};
+    use colored::Colorize;
+
+    let allowlists = load_default_allowlists();
+    let recorder = DecisionRecorder::new(config, &allowlists);
+
+    match action {
+        CorpusAction::Record {
+            output,
+            input,
+            since,
+            limit,
+        } => {
+            let commands: Vec<String> = if let Some(path) = input {
+                let file = std::fs::File::open(path)?;
+                let parser = crate::simulate::SimulateParser::new(
+                    file,
+                    crate::simulate::SimulateLimits::default(),
+                );
+                let (parsed, _stats) = parser.collect_commands()?;
+                parsed.into_iter().map(|c| c.command).collect()
+            } else {
+                let db =
+                    HistoryDb::open(config.history.expanded_database_path()).map_err(|err| {
+                        match err {
+                            crate::history::HistoryError::Disabled => {
+                                "history is disabled; enable it or pass --input".to_string()
+                            }
+                            err => format!("error opening history database: {err}"),
+                        }
+                    })?;
+                let options = ExportOptions {
+                    since: Some(Utc::now() - parse_duration_string(since)?),
+                    limit: Some(*limit),
+                    ..Default::default()
+                };
+                // Oldest first, so the corpus reads in the order commands ran.
+                db.query_commands_for_export(&options)?
+                    .into_iter()
+                    .rev()
+                    .map(|entry| entry.command)
+                    .collect()
+            };
+
+            let corpus = recorder.record(&commands);
+            std::fs::write(output, serde_json::to_string_pretty(&corpus)?)?;
+            let denied = corpus.entries.iter().filter(|e| e.is_denied()).count();
+            println!(
+                "Recorded {} commands ({} denied) to {} (config {})",
+                corpus.entries.len(),
+                denied,
+                output.display(),
+                recorder.fingerprint().short()
+            );
+        }
+        CorpusAction::Replay { file, format } => {
+            let content = std::fs::read_to_string(file)?;
+            let corpus: RecordedCorpus = serde_json::from_str(&content)?;
+            let report = recorder.replay(&corpus);
+
+            match format {
+                CorpusFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+                CorpusFormat::Pretty => {
+                    println!(
+                        "Replayed {} commands recorded {} (dcg {})",
+                        report.total, corpus.recorded_at, corpus.binary_version
+                    );
+                    match (report.fingerprint_matches, &corpus.config_fingerprint) {
+                        (Some(true), _) => {
+                            println!("{}", "Config unchanged since recording.".dimmed());
+                        }
+                        (Some(false), Some(recorded)) => println!(
+                            "Config changed since recording ({} -> {}).",
+                            recorded.short(),
+                            recorder.fingerprint().short()
+                        ),
+                        _ => {}
+                    }
+                    for change in &report.changes {
+                        let label = match change.kind {
+                            ChangeKind::NewlyDenied => change.kind.label().red(),
+                            ChangeKind::NewlyAllowed => change.kind.label().yellow(),
+                            ChangeKind::RuleChanged => change.kind.label().cyan(),
+                        };
+                        println!("  {label}: {}", change.command);
+                        println!(
+                            "     {} {} -> {} {}",
+                            change.recorded.decision,
+                            change.recorded.rule_id.as_deref().unwrap_or("-").dimmed(),
+                            change.current.decision,
+                            change.current.rule_id.as_deref().unwrap_or("-").dimmed()
+                        );
+                    }
+                    if report.changes.is_empty() {
+                        println!("{}", "No decision changes.".green().bold());
+                    } else {
+                        println!(
+                            "{} unchanged, {} newly denied, {} newly allowed, {} rule changed",
+                            report.unchanged,
+                            report.count(ChangeKind::NewlyDenied),
+                            report.count(ChangeKind::NewlyAllowed),
+                            report.count(ChangeKind::RuleChanged)
+                        );
+                    }
+                }
+            }
+
+            if !report.changes.is_empty() {
+                return Err(format!(
+                    "{} decision changes from recorded corpus",
+                    report.changes.len()
+                )
+                .into());
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Handle the `dcg stats` command.
 #[allow(clippy::option_if_let_else)]
 fn handle_stats_command(
@@ -5569,6 +8025,9 @@ fn handle_stats_command(
     if cmd.rules {
         return handle_stats_rules(config, cmd);
     }
+    if cmd.dashboard {
+        return handle_stats_dashboard(config, cmd);
+    }
 
     // Determine log file path
     let log_path = if let Some(ref path) = cmd.file {
@@ -5696,24 +8155,163 @@ fn handle_stats_rules(
         return Ok(());
     }
 
-    // Format and print output
-    match cmd.format {
-        StatsFormat::Pretty => {
-            #[cfg(feature = "rich-output")]
-            {
-                format_rule_metrics_rich(&metrics, cmd.days);
-            }
-            #[cfg(not(feature = "rich-output"))]
-            {
-                print!("{}", format_rule_metrics_pretty(&metrics, cmd.days));
-            }
-        }
-        StatsFormat::Json => {
-            print!("{}", format_rule_metrics_json(&metrics, cmd.days)?);
-        }
+    // Format and print output
+    match cmd.format {
+        StatsFormat::Pretty => {
+            #[cfg(feature = "rich-output")]
+            {
+                format_rule_metrics_rich(&metrics, cmd.days);
+            }
+            #[cfg(not(feature = "rich-output"))]
+            {
+                print!("{}", format_rule_metrics_pretty(&metrics, cmd.days));
+            }
+        }
+        StatsFormat::Json => {
+            print!("{}", format_rule_metrics_json(&metrics, cmd.days)?);
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle the `dcg stats --dashboard` command.
+fn handle_stats_dashboard(
+    config: &Config,
+    cmd: &StatsCommand,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use crate::history::HistoryDb;
+
+    let db = match HistoryDb::open(config.history.expanded_database_path()) {
+        Ok(db) => db,
+        Err(err) => {
+            if matches!(cmd.format, StatsFormat::Json) {
+                println!("{{}}");
+                return Ok(());
+            }
+            if matches!(err, crate::history::HistoryError::Disabled) {
+                println!("History is disabled. Enable it in config to use the stats dashboard.");
+                println!();
+                println!("To enable history, add to your config (~/.config/dcg/config.toml):");
+                println!();
+                println!("  [history]");
+                println!("  enabled = true");
+                return Ok(());
+            }
+            println!("Error opening history database: {err}");
+            return Ok(());
+        }
+    };
+
+    let dashboard = db.compute_dashboard(cmd.days, cmd.limit)?;
+
+    match cmd.format {
+        StatsFormat::Pretty => print!("{}", format_stats_dashboard_pretty(&dashboard)),
+        StatsFormat::Json => println!("{}", serde_json::to_string_pretty(&dashboard)?),
+    }
+
+    Ok(())
+}
+
+/// Format the stats dashboard for the terminal.
+fn format_stats_dashboard_pretty(dashboard: &crate::history::DashboardStats) -> String {
+    use crate::history::{DashboardHitStat, FALSE_POSITIVE_WINDOW_HOURS};
+    use crate::output::{StatsRow, StatsTable, auto_theme};
+    use std::fmt::Write;
+
+    #[allow(clippy::cast_precision_loss)]
+    fn percent(part: u64, total: u64) -> f64 {
+        if total == 0 {
+            0.0
+        } else {
+            part as f64 / total as f64 * 100.0
+        }
+    }
+
+    fn rows(stats: &[DashboardHitStat]) -> Vec<StatsRow> {
+        stats
+            .iter()
+            .map(|stat| StatsRow {
+                name: stat.name.clone(),
+                hits: stat.hits,
+                allowed: stat.allowed,
+                denied: stat.denied,
+                noise_pct: (stat.denied > 0)
+                    .then(|| percent(stat.likely_false_positives, stat.denied)),
+            })
+            .collect()
+    }
+
+    let mut output = String::new();
+    let _ = writeln!(
+        output,
+        "dcg dashboard (last {} days, {} commands)",
+        dashboard.period_days, dashboard.total_commands
+    );
+    let _ = writeln!(output);
+
+    if dashboard.total_commands == 0 {
+        let _ = writeln!(output, "No commands recorded in this period.");
+        return output;
+    }
+
+    let outcomes = &dashboard.outcomes;
+    let _ = writeln!(
+        output,
+        "Outcomes: {} allowed, {} warned, {} denied, {} bypassed",
+        outcomes.allowed, outcomes.warned, outcomes.denied, outcomes.bypassed
+    );
+    let _ = writeln!(
+        output,
+        "Latency:  median {}us, p95 {}us, max {}us",
+        dashboard.median_eval_us, dashboard.performance.p95_us, dashboard.performance.max_us
+    );
+    let fp = &dashboard.false_positives;
+    let _ = writeln!(
+        output,
+        "Likely false positives: {} of {} denies ({:.1}%) were allowed again within {}h",
+        fp.likely_false_positives,
+        fp.denied,
+        fp.rate * 100.0,
+        FALSE_POSITIVE_WINDOW_HOURS
+    );
+    let _ = writeln!(output);
+
+    let theme = auto_theme();
+    let _ = writeln!(
+        output,
+        "{}",
+        StatsTable::new(rows(&dashboard.packs))
+            .with_theme(&theme)
+            .with_title("Packs (Noise% = likely false positives per deny)")
+            .render()
+    );
+    let _ = writeln!(output);
+    let _ = writeln!(
+        output,
+        "{}",
+        StatsTable::new(rows(&dashboard.top_blocked))
+            .with_theme(&theme)
+            .with_title("Top blocked patterns")
+            .render()
+    );
+    let _ = writeln!(output);
+
+    let _ = writeln!(output, "Daily outcomes (allow / warn / deny):");
+    for day in &dashboard.daily {
+        let total = day.allowed + day.warned + day.denied;
+        let _ = writeln!(
+            output,
+            "  {}  {:>6} / {:>5} / {:>5}   deny {:>5.1}%",
+            day.day,
+            day.allowed,
+            day.warned,
+            day.denied,
+            percent(day.denied, total)
+        );
     }
 
-    Ok(())
+    output
 }
 
 /// Format rule metrics as a pretty table.
@@ -6063,6 +8661,349 @@ fn format_rule_metrics_json(
     Ok(serde_json::to_string_pretty(&output)?)
 }
 
+/// Handle the `dcg review` command.
+///
+/// Runs the review UI, then applies the decisions: approvals create an
+/// allow-once or user allowlist entry, and every verdict is recorded in the
+/// history database.
+fn handle_review_command(
+    config: &Config,
+    cmd: &ReviewCommand,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use crate::review::{ReviewAction, ReviewEntry};
+    use colored::Colorize;
+
+    if !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
+        return Err("dcg review needs an interactive terminal.".into());
+    }
+
+    let since = parse_history_since(&cmd.since)?;
+    let db = HistoryDb::open(config.history.expanded_database_path())?;
+    let items = db.unreviewed_denies(Some(since), cmd.limit)?;
+    if items.is_empty() {
+        println!("No unreviewed denies since {}.", cmd.since);
+        return Ok(());
+    }
+
+    // Re-evaluate without allowlists to recover the reason and matched span.
+    let enabled = config.enabled_pack_ids();
+    let keywords = REGISTRY.collect_enabled_keywords(&enabled);
+    let compiled = config.compile_overrides();
+    let allowlists = crate::LayeredAllowlist::default();
+    let entries = items
+        .into_iter()
+        .map(|item| {
+            let result = evaluate_command(
+                &item.entry.command,
+                config,
+                &keywords,
+                &compiled,
+                &allowlists,
+            );
+            let (reason, span) = result
+                .pattern_info
+                .map_or((None, None), |info| (Some(info.reason), info.matched_span));
+            ReviewEntry::new(item, reason, span)
+        })
+        .collect();
+
+    let decisions = crate::review::run(entries)?;
+    if decisions.is_empty() {
+        println!("No decisions made.");
+        return Ok(());
+    }
+
+    let now = Utc::now();
+    let (mut approved, mut ignored, mut confirmed) = (0, 0, 0);
+    for decision in &decisions {
+        let command = &decision.item.entry;
+        match decision.action {
+            ReviewAction::AllowOnce => {
+                let reason = decision
+                    .reason
+                    .as_deref()
+                    .unwrap_or("Approved in dcg review");
+                let path = review_allow_once(config, command, reason, now)?;
+                println!(
+                    "{} Allow-once entry for {} ({})",
+                    "✓".green(),
+                    command.command.cyan(),
+                    path.display()
+                );
+                approved += 1;
+            }
+            ReviewAction::Allowlist => {
+                allowlist_add_command(
+                    &command.command,
+                    "Approved in dcg review",
+                    AllowlistLayer::User,
+                    None,
+                )?;
+                approved += 1;
+            }
+            ReviewAction::Ignore => ignored += 1,
+            ReviewAction::TruePositive => confirmed += 1,
+        }
+        db.record_review(decision.item.id, decision.action.verdict(), now)?;
+    }
+
+    println!(
+        "Recorded {} review verdicts: {approved} approved, {ignored} ignored, {confirmed} true positive.",
+        decisions.len()
+    );
+    Ok(())
+}
+
+/// Handle the `dcg top` command.
+///
+/// Opens the live view, or with `--once` prints a single snapshot of the
+/// same data.
+fn handle_top_command(config: &Config, cmd: &TopCommand) -> Result<(), Box<dyn std::error::Error>> {
+    use crate::top::TopOptions;
+    use colored::Colorize;
+
+    let window = crate::allowlist::parse_duration(&cmd.window)
+        .map_err(|e| format!("Invalid --window '{}': {e}", cmd.window))?;
+    let options = TopOptions {
+        window,
+        window_label: cmd.window.clone(),
+        interval: std::time::Duration::from_secs(cmd.interval),
+        session_limit: cmd.sessions,
+        recent_limit: cmd.limit,
+    };
+    let db = HistoryDb::open(config.history.expanded_database_path())?;
+
+    if !cmd.once {
+        if !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
+            return Err(
+                "dcg top needs an interactive terminal (use --once for a snapshot).".into(),
+            );
+        }
+        crate::top::run(&db, &options)?;
+        return Ok(());
+    }
+
+    let snapshot = crate::top::snapshot(&db, &options)?;
+    if cmd.format == SuggestFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&snapshot)?);
+        return Ok(());
+    }
+
+    println!(
+        "{} over the last {}: {} evaluations, deny rate {:.1}% ({} denied, {} warned, {} bypassed)",
+        "dcg top".bold(),
+        cmd.window,
+        snapshot.total,
+        snapshot.deny_rate * 100.0,
+        snapshot.outcomes.denied,
+        snapshot.outcomes.warned,
+        snapshot.outcomes.bypassed
+    );
+    println!(
+        "Latency: p50 {}  p95 {}  p99 {}  max {}",
+        crate::trace::format_duration(snapshot.latency.p50_us),
+        crate::trace::format_duration(snapshot.latency.p95_us),
+        crate::trace::format_duration(snapshot.latency.p99_us),
+        crate::trace::format_duration(snapshot.latency.max_us)
+    );
+    println!();
+    println!("{}", "Active sessions:".bold());
+    if snapshot.active_sessions.is_empty() {
+        println!("  (none)");
+    }
+    for session in &snapshot.active_sessions {
+        println!(
+            "  {} {} ({} commands, {} denied)",
+            session.session_id.cyan(),
+            session.agent_type,
+            session.total,
+            session.denied
+        );
+    }
+    println!();
+    println!("{}", "Recent evaluations:".bold());
+    if snapshot.recent.is_empty() {
+        println!("  (none)");
+    }
+    for entry in &snapshot.recent {
+        let outcome = match entry.outcome {
+            Outcome::Allow => "allow".green(),
+            Outcome::Deny => "deny".red(),
+            Outcome::Warn => "warn".yellow(),
+            Outcome::Bypass => "bypass".magenta(),
+        };
+        println!(
+            "  {} {:<6} {:>7}  {}",
+            entry
+                .timestamp
+                .with_timezone(&chrono::Local)
+                .format("%H:%M:%S"),
+            outcome,
+            crate::trace::format_duration(entry.eval_duration_us),
+            entry.command
+        );
+    }
+    Ok(())
+}
+
+/// Write a single-use allow-once entry for a reviewed deny, scoped to its
+/// repository (or working directory), and return the store path.
+fn review_allow_once(
+    config: &Config,
+    command: &crate::history::CommandEntry,
+    reason: &str,
+    now: chrono::DateTime<Utc>,
+) -> Result<std::path::PathBuf, Box<dyn std::error::Error>> {
+    let cwd = std::path::PathBuf::from(&command.working_dir);
+    let pending = PendingExceptionRecord::new(
+        now,
+        &command.working_dir,
+        &command.command,
+        reason,
+        &config.logging.redaction,
+        true,
+        Some("review".to_string()),
+    );
+    let repo_root = crate::config::find_repo_root(&cwd, crate::config::REPO_ROOT_SEARCH_MAX_HOPS);
+    let (scope_kind, scope_path) = repo_root.map_or_else(
+        || (AllowOnceScopeKind::Cwd, cwd.clone()),
+        |root| (AllowOnceScopeKind::Project, root),
+    );
+    let entry = AllowOnceEntry::from_pending(
+        &pending,
+        now,
+        scope_kind,
+        &scope_path.to_string_lossy(),
+        true,
+        false,
+        &config.logging.redaction,
+    );
+
+    let path = AllowOnceStore::default_path(Some(&cwd));
+    AllowOnceStore::new(path.clone()).add_entry(&entry, now)?;
+    record_trust(config, &entry, &cwd);
+    record_audit(
+        "allow_once.create",
+        &path,
+        serde_json::json!({
+            "code": pending.short_code,
+            "command": pending.command_redacted,
+            "scope_path": entry.scope_path,
+            "single_use": true,
+            "source": "review",
+        }),
+    );
+    Ok(path)
+}
+
+/// Record an allow-once approval in the trust ledger (best-effort). Returns
+/// whether the approval's rule or command is now eligible for `dcg promote`.
+fn record_trust(config: &Config, entry: &AllowOnceEntry, cwd: &std::path::Path) -> bool {
+    use crate::trust_ledger::{TrustLedger, TrustRecord, candidates};
+
+    if !config.trust.ledger {
+        return false;
+    }
+    let ledger = TrustLedger::new(TrustLedger::default_path(Some(cwd)));
+    let record = TrustRecord::from_allow_once(entry);
+    if let Err(e) = ledger.record(&record) {
+        eprintln!("Warning: Failed to write trust ledger: {e}");
+        return false;
+    }
+    let records = ledger.load().unwrap_or_default();
+    candidates(&records, &record.repo, config.trust.promote_after as usize)
+        .iter()
+        .any(|candidate| candidate.covers(&record))
+}
+
+/// Handle the `dcg promote` command.
+fn handle_promote_command(
+    config: &Config,
+    cmd: &PromoteCommand,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use crate::trust_ledger::{PromotionSelector, TrustLedger, candidates};
+    use colored::Colorize;
+    use std::io::{self, Write};
+
+    let cwd = std::env::current_dir().unwrap_or_default();
+    let repo = find_repo_root_from_cwd().unwrap_or_else(|| cwd.clone());
+    let repo_str = repo.to_string_lossy().to_string();
+    let ledger = TrustLedger::new(TrustLedger::default_path(Some(&cwd)));
+    let min_approvals = cmd.min_approvals.unwrap_or(config.trust.promote_after);
+    let found = candidates(&ledger.load()?, &repo_str, min_approvals as usize);
+    let dry_run = cmd.dry_run || (cmd.json && !cmd.yes);
+
+    if cmd.json {
+        let output = serde_json::json!({
+            "repo": repo_str,
+            "min_approvals": min_approvals,
+            "dry_run": dry_run,
+            "candidates": found,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else if found.is_empty() {
+        println!(
+            "No rules approved {min_approvals} or more times in {repo_str}. Ledger: {}",
+            ledger.path().display()
+        );
+        if !config.trust.ledger {
+            println!("  Note: [trust] ledger = false; new approvals are not being recorded.");
+        }
+        return Ok(());
+    }
+
+    for candidate in &found {
+        let (label, value) = match &candidate.selector {
+            PromotionSelector::Rule(rule) => ("rule", rule.as_str()),
+            PromotionSelector::ExactCommand(command) => ("exact command", command.as_str()),
+        };
+        if !cmd.json {
+            println!(
+                "\n{} {} ({} approvals, last {})",
+                label.bold(),
+                value.cyan(),
+                candidate.approvals,
+                candidate.last_approved_at
+            );
+            for command in &candidate.commands {
+                println!("  {command}");
+            }
+        }
+        if dry_run {
+            continue;
+        }
+        if !cmd.yes {
+            print!("Add to the project allowlist? [y/N]: ");
+            io::stdout().flush()?;
+            let mut response = String::new();
+            io::stdin().read_line(&mut response)?;
+            let response = response.trim().to_lowercase();
+            if response != "y" && response != "yes" {
+                continue;
+            }
+        }
+
+        let reason = format!(
+            "Promoted from trust ledger ({} allow-once approvals)",
+            candidate.approvals
+        );
+        match &candidate.selector {
+            PromotionSelector::Rule(rule) => {
+                allowlist_add_rule(rule, &reason, AllowlistLayer::Project, None, &[])?;
+            }
+            PromotionSelector::ExactCommand(command) => {
+                allowlist_add_command(command, &reason, AllowlistLayer::Project, None)?;
+            }
+        }
+        ledger.remove_promoted(candidate)?;
+    }
+
+    if dry_run && !cmd.json {
+        println!("\nDry-run: allowlist not changed.");
+    }
+    Ok(())
+}
+
 /// Handle the `dcg suggest-allowlist` command.
 /// Parse a duration string like "30d", "7d", "24h", "1w" into a chrono Duration.
 fn parse_duration_string(s: &str) -> Result<chrono::Duration, String> {
@@ -6135,8 +9076,7 @@ fn handle_suggest_allowlist_command(
     let options = ExportOptions {
         outcome_filter: Some(Outcome::Deny),
         since: Some(since_time),
-        until: None,
-        limit: None,
+        ..Default::default()
     };
 
     let entries = db.query_commands_for_export(&options)?;
@@ -6159,8 +9099,7 @@ fn handle_suggest_allowlist_command(
     let bypass_options = ExportOptions {
         outcome_filter: Some(Outcome::Bypass),
         since: Some(since_time),
-        until: None,
-        limit: None,
+        ..Default::default()
     };
     let bypass_entries = db
         .query_commands_for_export(&bypass_options)
@@ -6246,6 +9185,98 @@ fn handle_suggest_allowlist_command(
     Ok(())
 }
 
+/// Handle the `dcg recommend` command.
+///
+/// Replays allowed commands from history against disabled packs.
+fn handle_recommend_command(
+    config: &Config,
+    cmd: &RecommendCommand,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use colored::Colorize;
+
+    let duration = parse_duration_string(&cmd.since)?;
+    let since_time = Utc::now() - duration;
+    let json = matches!(cmd.format, SuggestFormat::Json);
+
+    let db = match HistoryDb::open(config.history.expanded_database_path()) {
+        Ok(db) => db,
+        Err(err) => {
+            if json {
+                println!("[]");
+                return Ok(());
+            }
+            if matches!(err, crate::history::HistoryError::Disabled) {
+                println!("History is disabled. Enable it in config to use recommend.");
+                return Ok(());
+            }
+            println!("Error opening history database: {err}");
+            println!();
+            println!("Run 'dcg history stats' to check database status.");
+            return Ok(());
+        }
+    };
+
+    let options = ExportOptions {
+        outcome_filter: Some(Outcome::Allow),
+        since: Some(since_time),
+        limit: Some(cmd.limit),
+        ..Default::default()
+    };
+    let entries = db.query_commands_for_export(&options)?;
+    let allowlists = load_default_allowlists();
+    let recommendations =
+        crate::recommend::recommend_packs(&entries, config, &allowlists, cmd.min_blocks);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&recommendations)?);
+        return Ok(());
+    }
+
+    println!("\n{}", "═══ Pack Recommendations ═══".bright_cyan().bold());
+    println!(
+        "Period: {} | Allowed commands replayed: {}\n",
+        cmd.since,
+        entries.len().to_string().yellow()
+    );
+    if recommendations.is_empty() {
+        println!(
+            "{}",
+            "No disabled pack would have blocked any allowed command.".dimmed()
+        );
+        return Ok(());
+    }
+    for rec in &recommendations {
+        let pack_id = rec.related_pattern.as_deref().unwrap_or("unknown");
+        let priority_indicator = match rec.priority {
+            8..=10 => "🔴",
+            5..=7 => "🟡",
+            _ => "🟢",
+        };
+        println!(
+            "  {priority_indicator} {} would have blocked {} of {} allowed commands mentioning its keywords",
+            pack_id.bold(),
+            rec.would_block_count.unwrap_or(0).to_string().red(),
+            rec.keyword_matches.unwrap_or(0)
+        );
+        for example in &rec.examples {
+            let rule = example.pattern_name.as_deref().unwrap_or("unknown");
+            println!(
+                "     └─ {} {}",
+                example.command,
+                format!("({rule})").dimmed()
+            );
+        }
+        if let Some(action) = &rec.suggested_action {
+            println!("     {} {}", "Inspect:".dimmed(), action);
+        }
+    }
+    println!();
+    println!("Enable a pack in ~/.config/dcg/config.toml:");
+    println!("  [packs]");
+    println!("  enabled = [\"<pack-id>\"]");
+    Ok(())
+}
+
 /// Output suggestions as JSON.
 fn output_suggestions_json(
     suggestions: &[AllowlistSuggestion],
@@ -6587,6 +9618,31 @@ fn handle_history_command(
     };
 
     match action {
+        HistoryAction::Search {
+            query,
+            outcome,
+            pack,
+            since,
+            cwd,
+            session,
+            limit,
+            format,
+        } => {
+            history_search(
+                &db,
+                query.as_deref().unwrap_or(""),
+                outcome,
+                pack,
+                since,
+                cwd,
+                session,
+                limit,
+                format,
+            )?;
+        }
+        HistoryAction::Sessions { days, limit, json } => {
+            history_sessions(&db, days, limit, json)?;
+        }
         HistoryAction::Stats { days, trends, json } => {
             history_stats(&db, days, trends, json)?;
         }
@@ -6597,16 +9653,22 @@ fn handle_history_command(
         } => {
             history_prune(&db, older_than_days, dry_run, yes)?;
         }
+        HistoryAction::Vacuum { no_compact, json } => {
+            history_vacuum(config, &db, no_compact, json)?;
+        }
         HistoryAction::Export {
             output,
             format,
             outcome,
             since,
             until,
+            filters,
             limit,
             compress,
         } => {
-            history_export(&db, output, format, outcome, since, until, limit, compress)?;
+            history_export(
+                &db, output, format, outcome, since, until, &filters, limit, compress,
+            )?;
         }
         HistoryAction::Analyze {
             days,
@@ -6628,6 +9690,95 @@ fn handle_history_command(
     Ok(())
 }
 
+/// Parse a `--since` value: a relative age (`7d`, `12h`), an RFC 3339
+/// timestamp, or a `YYYY-MM-DD` date (start of day UTC).
+fn parse_history_since(value: &str) -> Result<chrono::DateTime<Utc>, String> {
+    let trimmed = value.trim();
+    if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(trimmed) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(trimmed, "%Y-%m-%d") {
+        if let Some(dt) = date.and_hms_opt(0, 0, 0) {
+            return Ok(dt.and_utc());
+        }
+    }
+    crate::allowlist::parse_duration(trimmed)
+        .map(|age| Utc::now() - age)
+        .map_err(|_| {
+            format!("Invalid --since value: {value} (use e.g. 7d, 12h, 2026-01-01, or RFC 3339)")
+        })
+}
+
+#[allow(clippy::too_many_arguments)]
+fn history_search(
+    db: &HistoryDb,
+    query: &str,
+    outcome: Option<String>,
+    pack: Option<String>,
+    since: Option<String>,
+    cwd: Option<String>,
+    session: Option<String>,
+    limit: usize,
+    format: HistorySearchFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use crate::history::SearchOptions;
+    use crate::output::{HistoryRow, HistoryTable, auto_theme};
+
+    let outcome_filter = outcome
+        .as_deref()
+        .map(|o| Outcome::parse(o).ok_or_else(|| format!("Invalid outcome: {o}")))
+        .transpose()?;
+    let since = since.as_deref().map(parse_history_since).transpose()?;
+    let working_dir = cwd.map(|dir| {
+        crate::config::resolve_config_path_value(&dir, std::env::current_dir().ok().as_deref())
+            .map_or(dir, |path| path.to_string_lossy().into_owned())
+    });
+
+    let options = SearchOptions {
+        outcome_filter,
+        pack_id: pack,
+        since,
+        working_dir,
+        session_id: session,
+        limit: Some(limit),
+    };
+    let entries = db.search_commands(query, &options)?;
+
+    match format {
+        HistorySearchFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&entries)?);
+        }
+        HistorySearchFormat::Csv => {
+            let stdout = std::io::stdout();
+            let mut writer = stdout.lock();
+            HistoryDb::write_csv(&mut writer, &entries)?;
+        }
+        HistorySearchFormat::Table => {
+            let rows = entries
+                .iter()
+                .map(|entry| HistoryRow {
+                    timestamp: entry
+                        .timestamp
+                        .with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M:%S")
+                        .to_string(),
+                    outcome: entry.outcome.as_str().to_string(),
+                    rule: entry.get_rule_id().or_else(|| entry.pack_id.clone()),
+                    working_dir: entry.working_dir.clone(),
+                    command: entry.command.clone(),
+                })
+                .collect();
+            println!(
+                "{}",
+                HistoryTable::new(rows).with_theme(&auto_theme()).render()
+            );
+            eprintln!("{} result(s)", entries.len());
+        }
+    }
+
+    Ok(())
+}
+
 fn history_stats(
     db: &HistoryDb,
     days: u64,
@@ -6651,6 +9802,52 @@ fn history_stats(
     Ok(())
 }
 
+fn history_sessions(
+    db: &HistoryDb,
+    days: u64,
+    limit: usize,
+    json: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let days = i64::try_from(days).unwrap_or(i64::MAX);
+    let since = chrono::Duration::try_days(days)
+        .and_then(|period| Utc::now().checked_sub_signed(period))
+        .unwrap_or(chrono::DateTime::<Utc>::MIN_UTC);
+    let sessions = db.session_stats(since, limit)?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&sessions)?);
+        return Ok(());
+    }
+
+    if sessions.is_empty() {
+        println!("No sessions recorded in the last {days} days.");
+        return Ok(());
+    }
+    println!(
+        "{:<38} {:<12} {:>6} {:>6} {:>6} {:>6}  LAST SEEN",
+        "SESSION", "AGENT", "TOTAL", "DENY", "WARN", "FAIL"
+    );
+    for session in &sessions {
+        println!(
+            "{:<38} {:<12} {:>6} {:>6} {:>6} {:>6}  {}",
+            session.session_id,
+            session.agent_type,
+            session.total,
+            session.denied,
+            session.warned,
+            session.failed,
+            session.last_seen.format("%Y-%m-%d %H:%M:%S")
+        );
+        if let Some(transcript) = session.transcript_path.as_deref() {
+            println!("    transcript: {transcript}");
+        }
+    }
+    println!();
+    println!("Filter with: dcg history search --session <ID>");
+
+    Ok(())
+}
+
 fn history_prune(
     db: &HistoryDb,
     older_than_days: u64,
@@ -6676,6 +9873,52 @@ fn history_prune(
     Ok(())
 }
 
+fn history_vacuum(
+    config: &Config,
+    db: &HistoryDb,
+    no_compact: bool,
+    json: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let size_before = db.file_size()?;
+    let report = if no_compact {
+        crate::history::CompactionReport::default()
+    } else {
+        let report = db.compact(&config.history.retention.policy())?;
+        db.record_prune_timestamp()?;
+        report
+    };
+    db.vacuum()?;
+    db.checkpoint_truncate()?;
+    let size_after = db.file_size()?;
+
+    if json {
+        let output = serde_json::json!({
+            "compaction": report,
+            "remaining_rows": db.count_commands()?,
+            "size_before_bytes": size_before,
+            "size_after_bytes": size_after,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    if !no_compact {
+        println!(
+            "Pruned {} entries ({} by age, {} by row limit, {} by size limit)",
+            report.total(),
+            report.pruned_by_age,
+            report.pruned_by_rows,
+            report.pruned_by_size
+        );
+    }
+    println!(
+        "Database size: {} -> {}",
+        format_size(size_before),
+        format_size(size_after)
+    );
+    Ok(())
+}
+
 #[allow(clippy::too_many_arguments)]
 #[allow(clippy::needless_pass_by_value)]
 fn history_export(
@@ -6685,10 +9928,10 @@ fn history_export(
     outcome: Option<String>,
     since: Option<String>,
     until: Option<String>,
+    filters: &[String],
     limit: Option<usize>,
     compress: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    use chrono::DateTime;
     use flate2::Compression;
     use flate2::write::GzEncoder;
     use std::fs::File;
@@ -6701,30 +9944,40 @@ fn history_export(
         .transpose()?;
 
     // Parse date/time filters
-    let since_dt = since
-        .as_deref()
-        .map(|s| {
-            DateTime::parse_from_rfc3339(s)
-                .map(|dt| dt.with_timezone(&chrono::Utc))
-                .map_err(|_| format!("Invalid since datetime: {s} (use ISO 8601 format)"))
-        })
-        .transpose()?;
-
+    let since_dt = since.as_deref().map(parse_history_since).transpose()?;
     let until_dt = until
         .as_deref()
-        .map(|s| {
-            DateTime::parse_from_rfc3339(s)
-                .map(|dt| dt.with_timezone(&chrono::Utc))
-                .map_err(|_| format!("Invalid until datetime: {s} (use ISO 8601 format)"))
-        })
+        .map(|s| parse_history_since(s).map_err(|e| e.replace("--since", "--until")))
         .transpose()?;
 
-    let options = ExportOptions {
+    let mut options = ExportOptions {
         outcome_filter,
         since: since_dt,
         until: until_dt,
         limit,
+        ..Default::default()
     };
+    for filter in filters {
+        options.apply_filter(filter)?;
+    }
+
+    if matches!(format, ExportFormat::Parquet) {
+        if compress {
+            return Err("--compress is not supported with --format parquet \
+                        (Parquet output is already compressed)"
+                .into());
+        }
+        let count = if let Some(path) = &output_path {
+            export_parquet(db, BufWriter::new(File::create(path)?), &options)?
+        } else {
+            export_parquet(db, BufWriter::new(io::stdout()), &options)?
+        };
+        match output_path {
+            Some(path) => eprintln!("Exported {count} records to {path}"),
+            None => eprintln!("Exported {count} records"),
+        }
+        return Ok(());
+    }
 
     // Create output writer
     let count: usize;
@@ -6762,10 +10015,31 @@ fn export_to_writer<W: std::io::Write>(
         ExportFormat::Json => db.export_json(writer, options)?,
         ExportFormat::Jsonl => db.export_jsonl(writer, options)?,
         ExportFormat::Csv => db.export_csv(writer, options)?,
+        ExportFormat::Parquet => return Err("parquet is written by export_parquet".into()),
     };
     Ok(count)
 }
 
+#[cfg(feature = "parquet")]
+fn export_parquet<W: std::io::Write + Send>(
+    db: &HistoryDb,
+    writer: W,
+    options: &ExportOptions,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    Ok(db.export_parquet(writer, options)?)
+}
+
+#[cfg(not(feature = "parquet"))]
+fn export_parquet<W: std::io::Write + Send>(
+    _db: &HistoryDb,
+    _writer: W,
+    _options: &ExportOptions,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    Err("this dcg build does not include Parquet support; \
+         rebuild with `--features parquet` or use --format jsonl"
+        .into())
+}
+
 #[allow(clippy::fn_params_excessive_bools, clippy::too_many_lines)]
 fn history_analyze(
     db: &HistoryDb,
@@ -6845,6 +10119,12 @@ fn history_analyze(
                     p.bypassed_count,
                     p.total_triggers
                 );
+                if p.approved_count > 0 || p.confirmed_count > 0 {
+                    println!(
+                        "     └─ reviewed: {} approved, {} true positive",
+                        p.approved_count, p.confirmed_count
+                    );
+                }
             }
             println!();
         }
@@ -7042,7 +10322,7 @@ fn history_backup(
 
 /// Format a byte size in human-readable format.
 #[allow(clippy::cast_precision_loss)]
-fn format_size(bytes: u64) -> String {
+pub(crate) fn format_size(bytes: u64) -> String {
     const KB: u64 = 1024;
     const MB: u64 = KB * 1024;
     const GB: u64 = MB * 1024;
@@ -7368,7 +10648,7 @@ fn doctor_pretty(fix: bool) {
         issues += 1;
         if fix {
             println!("  Attempting to register hook...");
-            if install_hook(false).is_ok() {
+            if install_hook(HookTarget::ClaudeCode, false).is_ok() {
                 println!("  {}", "Fixed!".green());
                 fixed += 1;
             } else {
@@ -7428,6 +10708,7 @@ fn doctor_pretty(fix: bool) {
                     }
                     match std::fs::write(&config_path, Config::generate_sample_config()) {
                         Ok(()) => {
+                            record_audit("config.init", &config_path, serde_json::Value::Null);
                             println!("  {} Created: {}", "Fixed!".green(), config_path.display());
                             fixed += 1;
                         }
@@ -7559,6 +10840,33 @@ fn doctor_pretty(fix: bool) {
         println!("{}", "OK".green());
     }
 
+    // Check 7b: Severity overrides reference real packs/rules
+    print!("Checking severity overrides... ");
+    let severity_issues = config.policy().validate_severity_overrides();
+    if severity_issues.is_empty() {
+        println!("{}", "OK".green());
+    } else {
+        println!("{}", "WARNING".yellow());
+        for issue in &severity_issues {
+            println!("  {issue}");
+        }
+        println!("  → Invalid entries are ignored; see 'dcg policy show'");
+    }
+
+    // Check 7c: Deny templates only use known placeholders
+    print!("Checking deny templates... ");
+    let unknown_placeholders = config.output.deny_templates.unknown_placeholders();
+    if unknown_placeholders.is_empty() {
+        println!("{}", "OK".green());
+    } else {
+        println!("{}", "WARNING".yellow());
+        println!(
+            "  Unknown placeholders: {{{}}}",
+            unknown_placeholders.join("}, {")
+        );
+        println!("  → They are printed as written; see [output.deny_templates] in the docs");
+    }
+
     // Check 8: Allowlist discovery + validation
     print!("Checking allowlist entries... ");
     let allowlist_diag = diagnose_allowlists();
@@ -8011,6 +11319,60 @@ fn collect_doctor_report(fix: bool) -> DoctorReport {
         fixed: false,
     });
 
+    // Check 7b: Severity overrides reference real packs/rules
+    let severity_issues = config.policy().validate_severity_overrides();
+    let (status, message, remediation) = if severity_issues.is_empty() {
+        (
+            DoctorCheckStatus::Ok,
+            format!(
+                "{} severity override(s) valid",
+                config.policy().severity_overrides.len()
+            ),
+            None,
+        )
+    } else {
+        (
+            DoctorCheckStatus::Warning,
+            format!("Invalid severity overrides: {}", severity_issues.join("; ")),
+            Some("Invalid entries are ignored; see 'dcg policy show'".to_string()),
+        )
+    };
+    checks.push(DoctorCheck {
+        id: "severity_overrides",
+        name: "Severity overrides",
+        status,
+        message,
+        remediation,
+        fixed: false,
+    });
+
+    // Check 7c: Deny templates only use known placeholders
+    let unknown_placeholders = config.output.deny_templates.unknown_placeholders();
+    let (status, message, remediation) = if unknown_placeholders.is_empty() {
+        (
+            DoctorCheckStatus::Ok,
+            "Deny templates valid".to_string(),
+            None,
+        )
+    } else {
+        (
+            DoctorCheckStatus::Warning,
+            format!(
+                "Unknown deny template placeholders: {{{}}}",
+                unknown_placeholders.join("}, {")
+            ),
+            Some("Unknown placeholders are printed as written".to_string()),
+        )
+    };
+    checks.push(DoctorCheck {
+        id: "deny_templates",
+        name: "Deny templates",
+        status,
+        message,
+        remediation,
+        fixed: false,
+    });
+
     // Check 8: Allowlist discovery + validation
     let allowlist_diag = diagnose_allowlists();
     let (status, message, remediation) = if allowlist_diag.total_errors > 0 {
@@ -8095,190 +11457,467 @@ fn install_hook_silent(force: bool) -> Result<bool, Box<dyn std::error::Error>>
         if let Some(parent) = settings_path.parent() {
             std::fs::create_dir_all(parent)?;
         }
-        serde_json::json!({})
-    };
+        serde_json::json!({})
+    };
+
+    let changed = install_dcg_hook_into_settings(&mut settings, force)?;
+    if changed {
+        let content = serde_json::to_string_pretty(&settings)?;
+        std::fs::write(&settings_path, content)?;
+    }
+    Ok(changed)
+}
+
+/// Create the default config file at the standard path.
+fn write_default_config() -> Result<std::path::PathBuf, Box<dyn std::error::Error>> {
+    let config_path = config_path();
+    if let Some(parent) = config_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&config_path, Config::generate_sample_config())?;
+    record_audit("config.init", &config_path, serde_json::Value::Null);
+    Ok(config_path)
+}
+
+/// Install the dcg hook entry into an in-memory Claude settings JSON value.
+///
+/// Returns `Ok(true)` when a new hook entry was added, `Ok(false)` when an
+/// existing hook was detected and `force == false`.
+///
+/// # Errors
+///
+/// Returns an error if the settings JSON is not in the expected format:
+/// - root must be an object
+/// - `hooks` must be an object (if present)
+/// - `hooks.PreToolUse` must be an array (if present)
+fn install_dcg_hook_into_settings(
+    settings: &mut serde_json::Value,
+    force: bool,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    // Build the hook configuration.
+    let hook_config = serde_json::json!({
+        "matcher": "Bash",
+        "hooks": [{
+            "type": "command",
+            "command": "dcg"
+        }]
+    });
+
+    let settings_obj = settings
+        .as_object_mut()
+        .ok_or("Invalid settings format (expected JSON object)")?;
+
+    let hooks_value = settings_obj
+        .entry("hooks")
+        .or_insert_with(|| serde_json::json!({}));
+
+    let hooks_obj = hooks_value
+        .as_object_mut()
+        .ok_or("Invalid hooks format (expected JSON object)")?;
+
+    let pre_tool_use_value = hooks_obj
+        .entry("PreToolUse")
+        .or_insert_with(|| serde_json::json!([]));
+
+    let pre_tool_use = pre_tool_use_value
+        .as_array_mut()
+        .ok_or("Invalid PreToolUse hooks format (expected JSON array)")?;
+
+    let already_installed = pre_tool_use.iter().any(is_dcg_hook_entry);
+    if already_installed && !force {
+        return Ok(false);
+    }
+
+    if force {
+        pre_tool_use.retain(|h| !is_dcg_hook_entry(h));
+    }
+
+    pre_tool_use.push(hook_config);
+    Ok(true)
+}
+
+/// Remove the dcg hook entry from an in-memory Claude settings JSON value.
+///
+/// Returns `Ok(true)` when at least one entry was removed, `Ok(false)` when no
+/// dcg hook entry existed.
+///
+/// # Errors
+///
+/// Returns an error if `hooks.PreToolUse` exists but is not an array.
+fn uninstall_dcg_hook_from_settings(
+    settings: &mut serde_json::Value,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let Some(hooks) = settings.get_mut("hooks") else {
+        return Ok(false);
+    };
+    let Some(pre_tool_use) = hooks.get_mut("PreToolUse") else {
+        return Ok(false);
+    };
+
+    let Some(arr) = pre_tool_use.as_array_mut() else {
+        return Err("Invalid PreToolUse hooks format (expected JSON array)".into());
+    };
+
+    let before = arr.len();
+    arr.retain(|h| !is_dcg_hook_entry(h));
+    Ok(arr.len() < before)
+}
+
+/// Agent whose settings file `dcg install` / `dcg uninstall` edits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HookTarget {
+    /// `~/.claude/settings.json` (`hooks.PreToolUse`).
+    ClaudeCode,
+    /// `~/.cursor/hooks.json` (`hooks.beforeShellExecution`).
+    Cursor,
+    /// `~/.codex/config.toml` (`[[hooks.pre_exec]]`).
+    Codex,
+}
+
+impl HookTarget {
+    /// Resolve the `--agent` flag; Claude Code when omitted.
+    fn from_agent(agent: Option<&str>) -> Result<Self, String> {
+        let Some(agent) = agent else {
+            return Ok(Self::ClaudeCode);
+        };
+        match crate::agent::Agent::from_name(agent) {
+            crate::agent::Agent::ClaudeCode => Ok(Self::ClaudeCode),
+            crate::agent::Agent::Cursor => Ok(Self::Cursor),
+            crate::agent::Agent::CodexCli => Ok(Self::Codex),
+            _ => Err(format!(
+                "Hook installation is not supported for agent '{agent}' \
+                 (supported: claude-code, cursor, codex)"
+            )),
+        }
+    }
+
+    const fn display_name(self) -> &'static str {
+        match self {
+            Self::ClaudeCode => "Claude Code",
+            Self::Cursor => "Cursor",
+            Self::Codex => "Codex CLI",
+        }
+    }
+
+    fn settings_path(self) -> std::path::PathBuf {
+        match self {
+            Self::ClaudeCode => claude_settings_path(),
+            Self::Cursor => dirs::home_dir()
+                .unwrap_or_default()
+                .join(".cursor")
+                .join("hooks.json"),
+            Self::Codex => dirs::home_dir()
+                .unwrap_or_default()
+                .join(".codex")
+                .join("config.toml"),
+        }
+    }
+
+    /// Add the dcg hook to `existing` settings (or an empty file).
+    ///
+    /// Returns the new file content, or `None` if dcg is already installed and
+    /// `force` is not set.
+    fn install_into(
+        self,
+        existing: Option<&str>,
+        force: bool,
+    ) -> Result<Option<String>, Box<dyn std::error::Error>> {
+        if self == Self::Codex {
+            let mut doc: toml_edit::DocumentMut = existing.unwrap_or_default().parse()?;
+            let changed = install_dcg_hook_into_codex_config(&mut doc, force)?;
+            return Ok(changed.then(|| doc.to_string()));
+        }
+
+        let mut settings: serde_json::Value = match existing {
+            Some(content) => serde_json::from_str(content)?,
+            None => serde_json::json!({}),
+        };
+        let changed = if self == Self::Cursor {
+            install_dcg_hook_into_cursor_hooks(&mut settings, force)?
+        } else {
+            install_dcg_hook_into_settings(&mut settings, force)?
+        };
+        Ok(if changed {
+            Some(serde_json::to_string_pretty(&settings)?)
+        } else {
+            None
+        })
+    }
+
+    /// Remove dcg hook entries from `existing` settings.
+    ///
+    /// Returns the new file content, or `None` if no dcg hook was present.
+    fn uninstall_from(self, existing: &str) -> Result<Option<String>, Box<dyn std::error::Error>> {
+        if self == Self::Codex {
+            let mut doc: toml_edit::DocumentMut = existing.parse()?;
+            let removed = uninstall_dcg_hook_from_codex_config(&mut doc)?;
+            return Ok(removed.then(|| doc.to_string()));
+        }
 
-    let changed = install_dcg_hook_into_settings(&mut settings, force)?;
-    if changed {
-        let content = serde_json::to_string_pretty(&settings)?;
-        std::fs::write(&settings_path, content)?;
+        let mut settings: serde_json::Value = serde_json::from_str(existing)?;
+        let removed = if self == Self::Cursor {
+            uninstall_dcg_hook_from_cursor_hooks(&mut settings)?
+        } else {
+            uninstall_dcg_hook_from_settings(&mut settings)?
+        };
+        Ok(if removed {
+            Some(serde_json::to_string_pretty(&settings)?)
+        } else {
+            None
+        })
     }
-    Ok(changed)
 }
 
-/// Create the default config file at the standard path.
-fn write_default_config() -> Result<std::path::PathBuf, Box<dyn std::error::Error>> {
-    let config_path = config_path();
-    if let Some(parent) = config_path.parent() {
-        std::fs::create_dir_all(parent)?;
-    }
-    std::fs::write(&config_path, Config::generate_sample_config())?;
-    Ok(config_path)
+/// Returns `true` if a hook command line invokes dcg (e.g. `dcg --agent cursor`).
+fn is_dcg_invocation(command_line: &str) -> bool {
+    command_line
+        .split_whitespace()
+        .next()
+        .is_some_and(is_dcg_command)
 }
 
-/// Install the dcg hook entry into an in-memory Claude settings JSON value.
-///
-/// Returns `Ok(true)` when a new hook entry was added, `Ok(false)` when an
-/// existing hook was detected and `force == false`.
+fn is_dcg_cursor_hook_entry(entry: &serde_json::Value) -> bool {
+    entry
+        .get("command")
+        .and_then(|c| c.as_str())
+        .is_some_and(is_dcg_invocation)
+}
+
+/// Install the dcg hook entry into an in-memory Cursor `hooks.json` value.
 ///
 /// # Errors
 ///
-/// Returns an error if the settings JSON is not in the expected format:
-/// - root must be an object
-/// - `hooks` must be an object (if present)
-/// - `hooks.PreToolUse` must be an array (if present)
-fn install_dcg_hook_into_settings(
+/// Returns an error if the root or `hooks` is not an object, or if
+/// `hooks.beforeShellExecution` is not an array.
+fn install_dcg_hook_into_cursor_hooks(
     settings: &mut serde_json::Value,
     force: bool,
 ) -> Result<bool, Box<dyn std::error::Error>> {
-    // Build the hook configuration.
-    let hook_config = serde_json::json!({
-        "matcher": "Bash",
-        "hooks": [{
-            "type": "command",
-            "command": "dcg"
-        }]
-    });
-
     let settings_obj = settings
         .as_object_mut()
-        .ok_or("Invalid settings format (expected JSON object)")?;
+        .ok_or("Invalid hooks.json format (expected JSON object)")?;
 
-    let hooks_value = settings_obj
-        .entry("hooks")
-        .or_insert_with(|| serde_json::json!({}));
+    settings_obj
+        .entry("version")
+        .or_insert_with(|| serde_json::json!(1));
 
-    let hooks_obj = hooks_value
+    let hooks_obj = settings_obj
+        .entry("hooks")
+        .or_insert_with(|| serde_json::json!({}))
         .as_object_mut()
         .ok_or("Invalid hooks format (expected JSON object)")?;
 
-    let pre_tool_use_value = hooks_obj
-        .entry("PreToolUse")
-        .or_insert_with(|| serde_json::json!([]));
-
-    let pre_tool_use = pre_tool_use_value
+    let before_shell = hooks_obj
+        .entry("beforeShellExecution")
+        .or_insert_with(|| serde_json::json!([]))
         .as_array_mut()
-        .ok_or("Invalid PreToolUse hooks format (expected JSON array)")?;
+        .ok_or("Invalid beforeShellExecution hooks format (expected JSON array)")?;
 
-    let already_installed = pre_tool_use.iter().any(is_dcg_hook_entry);
+    let already_installed = before_shell.iter().any(is_dcg_cursor_hook_entry);
     if already_installed && !force {
         return Ok(false);
     }
 
     if force {
-        pre_tool_use.retain(|h| !is_dcg_hook_entry(h));
+        before_shell.retain(|h| !is_dcg_cursor_hook_entry(h));
     }
 
-    pre_tool_use.push(hook_config);
+    before_shell.push(serde_json::json!({ "command": "dcg --agent cursor" }));
     Ok(true)
 }
 
-/// Remove the dcg hook entry from an in-memory Claude settings JSON value.
-///
-/// Returns `Ok(true)` when at least one entry was removed, `Ok(false)` when no
-/// dcg hook entry existed.
+/// Remove dcg hook entries from an in-memory Cursor `hooks.json` value.
+fn uninstall_dcg_hook_from_cursor_hooks(
+    settings: &mut serde_json::Value,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let Some(before_shell) = settings
+        .get_mut("hooks")
+        .and_then(|hooks| hooks.get_mut("beforeShellExecution"))
+    else {
+        return Ok(false);
+    };
+
+    let Some(arr) = before_shell.as_array_mut() else {
+        return Err("Invalid beforeShellExecution hooks format (expected JSON array)".into());
+    };
+
+    let before = arr.len();
+    arr.retain(|h| !is_dcg_cursor_hook_entry(h));
+    Ok(arr.len() < before)
+}
+
+fn is_dcg_codex_hook_entry(entry: &toml_edit::Table) -> bool {
+    entry
+        .get("command")
+        .and_then(toml_edit::Item::as_array)
+        .and_then(|argv| argv.get(0))
+        .and_then(toml_edit::Value::as_str)
+        .is_some_and(is_dcg_command)
+}
+
+/// Install the dcg hook entry into a Codex `config.toml` document.
 ///
 /// # Errors
 ///
-/// Returns an error if `hooks.PreToolUse` exists but is not an array.
-fn uninstall_dcg_hook_from_settings(
-    settings: &mut serde_json::Value,
+/// Returns an error if `hooks` is not a table or `hooks.pre_exec` is not an
+/// array of tables.
+fn install_dcg_hook_into_codex_config(
+    doc: &mut toml_edit::DocumentMut,
+    force: bool,
 ) -> Result<bool, Box<dyn std::error::Error>> {
-    let Some(hooks) = settings.get_mut("hooks") else {
+    let hooks = doc
+        .entry("hooks")
+        .or_insert_with(|| {
+            let mut table = toml_edit::Table::new();
+            table.set_implicit(true);
+            toml_edit::Item::Table(table)
+        })
+        .as_table_mut()
+        .ok_or("Invalid hooks format (expected TOML table)")?;
+
+    let pre_exec = hooks
+        .entry("pre_exec")
+        .or_insert_with(|| toml_edit::Item::ArrayOfTables(toml_edit::ArrayOfTables::new()))
+        .as_array_of_tables_mut()
+        .ok_or("Invalid hooks.pre_exec format (expected array of tables)")?;
+
+    let already_installed = pre_exec.iter().any(is_dcg_codex_hook_entry);
+    if already_installed && !force {
         return Ok(false);
-    };
-    let Some(pre_tool_use) = hooks.get_mut("PreToolUse") else {
+    }
+
+    if force {
+        pre_exec.retain(|entry| !is_dcg_codex_hook_entry(entry));
+    }
+
+    let mut entry = toml_edit::Table::new();
+    entry.insert(
+        "command",
+        toml_edit::value(toml_edit::Array::from_iter(["dcg", "--agent", "codex"])),
+    );
+    pre_exec.push(entry);
+    Ok(true)
+}
+
+/// Remove dcg hook entries from a Codex `config.toml` document.
+fn uninstall_dcg_hook_from_codex_config(
+    doc: &mut toml_edit::DocumentMut,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let Some(pre_exec) = doc
+        .get_mut("hooks")
+        .and_then(|hooks| hooks.get_mut("pre_exec"))
+    else {
         return Ok(false);
     };
 
-    let Some(arr) = pre_tool_use.as_array_mut() else {
-        return Err("Invalid PreToolUse hooks format (expected JSON array)".into());
+    let Some(entries) = pre_exec.as_array_of_tables_mut() else {
+        return Err("Invalid hooks.pre_exec format (expected array of tables)".into());
     };
 
-    let before = arr.len();
-    arr.retain(|h| !is_dcg_hook_entry(h));
-    Ok(arr.len() < before)
+    let before = entries.len();
+    entries.retain(|entry| !is_dcg_codex_hook_entry(entry));
+    Ok(entries.len() < before)
+}
+
+/// Write agent settings, first copying any existing file to `<name>.dcg-backup`.
+///
+/// Returns the backup path when one was written.
+fn write_settings_with_backup(
+    path: &std::path::Path,
+    content: &str,
+) -> std::io::Result<Option<std::path::PathBuf>> {
+    let backup = if path.exists() {
+        let mut name = path.file_name().unwrap_or_default().to_os_string();
+        name.push(".dcg-backup");
+        let backup = path.with_file_name(name);
+        std::fs::copy(path, &backup)?;
+        Some(backup)
+    } else {
+        None
+    };
+    std::fs::write(path, content)?;
+    Ok(backup)
 }
 
-/// Install the dcg hook entry into Claude Code settings.
+/// Install the dcg hook entry into the target agent's settings.
 ///
-/// This is a wrapper around `install_dcg_hook_into_settings` that handles the
-/// file I/O and error reporting.
+/// This is a wrapper around [`HookTarget::install_into`] that handles the
+/// file I/O (including a backup of the previous file) and error reporting.
 ///
 /// # Errors
 ///
 /// Returns an error if the settings file cannot be read, parsed, or written.
-fn install_hook(force: bool) -> Result<(), Box<dyn std::error::Error>> {
+fn install_hook(target: HookTarget, force: bool) -> Result<(), Box<dyn std::error::Error>> {
     use colored::Colorize;
 
-    let settings_path = claude_settings_path();
+    let settings_path = target.settings_path();
 
-    // Read existing settings or create new
-    let mut settings: serde_json::Value = if settings_path.exists() {
-        let content = std::fs::read_to_string(&settings_path)?;
-        serde_json::from_str(&content)?
+    // Read existing settings or create the parent directory for a new file
+    let existing = if settings_path.exists() {
+        Some(std::fs::read_to_string(&settings_path)?)
     } else {
-        // Create parent directory if needed
         if let Some(parent) = settings_path.parent() {
             std::fs::create_dir_all(parent)?;
         }
-        serde_json::json!({})
+        None
     };
 
-    let changed = install_dcg_hook_into_settings(&mut settings, force)?;
-    if !changed {
+    let Some(content) = target.install_into(existing.as_deref(), force)? else {
         println!("{}", "Hook already installed!".yellow());
         println!("Use --force to reinstall");
         return Ok(());
-    }
+    };
 
-    // Write back
-    let content = serde_json::to_string_pretty(&settings)?;
-    std::fs::write(&settings_path, content)?;
+    let backup = write_settings_with_backup(&settings_path, &content)?;
 
     println!("{}", "Hook installed successfully!".green().bold());
     println!("Settings updated: {}", settings_path.display());
+    if let Some(backup) = backup {
+        println!("Previous settings saved to: {}", backup.display());
+    }
     println!();
     println!(
         "{}",
-        "Restart Claude Code for the changes to take effect.".yellow()
+        format!(
+            "Restart {} for the changes to take effect.",
+            target.display_name()
+        )
+        .yellow()
     );
 
     Ok(())
 }
 
-/// Remove the dcg hook entry from Claude Code settings.
+/// Remove the dcg hook entry from the target agent's settings.
 ///
-/// This is a wrapper around `uninstall_dcg_hook_from_settings` that handles the
-/// file I/O and error reporting.
+/// This is a wrapper around [`HookTarget::uninstall_from`] that handles the
+/// file I/O (including a backup of the previous file) and error reporting.
 ///
 /// # Errors
 ///
 /// Returns an error if the settings file cannot be read, parsed, or written.
-fn uninstall_hook(purge: bool) -> Result<(), Box<dyn std::error::Error>> {
+fn uninstall_hook(target: HookTarget, purge: bool) -> Result<(), Box<dyn std::error::Error>> {
     use colored::Colorize;
 
-    let settings_path = claude_settings_path();
-
-    if !settings_path.exists() {
-        println!("{}", "No Claude Code settings found.".yellow());
-        return Ok(());
-    }
-
-    // Read existing settings
-    let content = std::fs::read_to_string(&settings_path)?;
-    let mut settings: serde_json::Value = serde_json::from_str(&content)?;
-
-    // Remove dcg hooks (fail if settings structure is unexpected).
-    let removed = uninstall_dcg_hook_from_settings(&mut settings)?;
+    let settings_path = target.settings_path();
 
-    if removed {
-        // Write back
-        let content = serde_json::to_string_pretty(&settings)?;
-        std::fs::write(&settings_path, content)?;
-        println!("{}", "Hook removed successfully!".green().bold());
+    if settings_path.exists() {
+        // Remove dcg hooks (fail if settings structure is unexpected).
+        let existing = std::fs::read_to_string(&settings_path)?;
+        if let Some(content) = target.uninstall_from(&existing)? {
+            write_settings_with_backup(&settings_path, &content)?;
+            println!("{}", "Hook removed successfully!".green().bold());
+        } else {
+            println!("{}", "No dcg hook found in settings.".yellow());
+        }
     } else {
-        println!("{}", "No dcg hook found in settings.".yellow());
+        println!(
+            "{}",
+            format!("No {} settings found.", target.display_name()).yellow()
+        );
+        if !purge {
+            return Ok(());
+        }
     }
 
     // Purge config files if requested
@@ -8293,7 +11932,11 @@ fn uninstall_hook(purge: bool) -> Result<(), Box<dyn std::error::Error>> {
     println!();
     println!(
         "{}",
-        "Restart Claude Code for the changes to take effect.".yellow()
+        format!(
+            "Restart {} for the changes to take effect.",
+            target.display_name()
+        )
+        .yellow()
     );
 
     Ok(())
@@ -8545,6 +12188,62 @@ fn claude_settings_path() -> std::path::PathBuf {
         .join("settings.json")
 }
 
+/// Handle `dcg audit` subcommands.
+fn handle_audit_command(action: &AuditAction) -> Result<(), Box<dyn std::error::Error>> {
+    use colored::Colorize;
+
+    match action {
+        AuditAction::Verify { json } => {
+            let cwd = std::env::current_dir().ok();
+            let log =
+                crate::audit::AuditLog::new(crate::audit::AuditLog::default_path(cwd.as_deref()));
+            let report = log.verify()?;
+
+            if *json {
+                let mut output = serde_json::to_value(&report)?;
+                output["path"] = serde_json::json!(log.path().display().to_string());
+                output["intact"] = serde_json::json!(report.is_intact());
+                println!("{}", serde_json::to_string_pretty(&output)?);
+            } else if report.is_intact() {
+                println!(
+                    "{} {} records, chain intact ({})",
+                    "✓".green(),
+                    report.records,
+                    log.path().display()
+                );
+                if let Some(hash) = &report.last_hash {
+                    println!("  Last hash: {hash}");
+                }
+            }
+
+            if let Some(broken) = &report.broken {
+                return Err(format!(
+                    "audit log {} is broken after {} intact records: {broken}",
+                    log.path().display(),
+                    report.records
+                )
+                .into());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Append a record to the audit log, warning instead of failing the command.
+fn record_audit(action: &str, target: &std::path::Path, details: serde_json::Value) {
+    use colored::Colorize;
+
+    let cwd = std::env::current_dir().ok();
+    let log = crate::audit::AuditLog::new(crate::audit::AuditLog::default_path(cwd.as_deref()));
+    if let Err(e) = log.append(action, target, details) {
+        eprintln!(
+            "{} failed to write audit log {}: {e}",
+            "Warning:".yellow(),
+            log.path().display()
+        );
+    }
+}
+
 /// Get the path to dcg config directory.
 ///
 /// Prefers `$XDG_CONFIG_HOME/dcg/`, then XDG-style `~/.config/dcg/` if it exists,
@@ -8901,7 +12600,7 @@ fn run_smoke_test() -> bool {
     let enabled_keywords = REGISTRY.collect_enabled_keywords(&enabled_packs);
     let ordered_packs = REGISTRY.expand_enabled_ordered(&enabled_packs);
     let keyword_index = REGISTRY.build_enabled_keyword_index(&ordered_packs);
-    let compiled_overrides = config.overrides.compile();
+    let compiled_overrides = config.compile_overrides();
     let allowlists = crate::LayeredAllowlist::default();
     let heredoc_settings = config.heredoc_settings();
 
@@ -8964,8 +12663,11 @@ fn diagnose_allowlists() -> AllowlistDiagnostics {
 
     // Check each layer
     for loaded in &allowlist.layers {
-        // Skip system layer in doctor (less common)
-        if loaded.layer == AllowlistLayer::System {
+        // Skip system and remote layers in doctor (managed outside the repo)
+        if matches!(
+            loaded.layer,
+            AllowlistLayer::System | AllowlistLayer::Remote
+        ) {
             continue;
         }
 
@@ -8979,7 +12681,7 @@ fn diagnose_allowlists() -> AllowlistDiagnostics {
                 }
             }
             AllowlistLayer::User => config_dir().join("allowlist.toml"),
-            AllowlistLayer::System => continue,
+            AllowlistLayer::System | AllowlistLayer::Remote => continue,
         };
 
         if !path.exists() {
@@ -9078,6 +12780,9 @@ fn allowlist_path_for_layer(layer: AllowlistLayer) -> std::path::PathBuf {
         }
         AllowlistLayer::User => config_dir().join("allowlist.toml"),
         AllowlistLayer::System => std::path::PathBuf::from("/etc/dcg/allowlist.toml"),
+        AllowlistLayer::Remote => crate::remote::remote_dir()
+            .unwrap_or_default()
+            .join(crate::remote::POLICY_FILE),
     }
 }
 
@@ -9127,7 +12832,187 @@ fn handle_allowlist_command(action: AllowlistAction) -> Result<(), Box<dyn std::
         } => {
             allowlist_validate(project, user, strict)?;
         }
+        AllowlistAction::Prune {
+            project,
+            user,
+            dry_run,
+        } => {
+            allowlist_prune(project, user, dry_run)?;
+        }
+        AllowlistAction::Export {
+            output,
+            sign_key,
+            project,
+            user,
+            system,
+        } => {
+            let selected: Vec<AllowlistLayer> = [
+                (project, AllowlistLayer::Project),
+                (user, AllowlistLayer::User),
+                (system, AllowlistLayer::System),
+            ]
+            .into_iter()
+            .filter_map(|(enabled, layer)| enabled.then_some(layer))
+            .collect();
+            allowlist_export(&output, sign_key.as_deref(), &selected)?;
+        }
+        AllowlistAction::Import {
+            bundle,
+            public_key,
+            signature,
+            project,
+            user,
+        } => {
+            let layer = if project {
+                AllowlistLayer::Project
+            } else if user {
+                AllowlistLayer::User
+            } else {
+                AllowlistLayer::System
+            };
+            allowlist_import(&bundle, &public_key, signature.as_deref(), layer)?;
+        }
+        AllowlistAction::Keygen { output } => {
+            allowlist_keygen(&output)?;
+        }
+    }
+    Ok(())
+}
+
+/// Write the merged allowlist bundle, signing it when a key is given.
+fn allowlist_export(
+    output: &std::path::Path,
+    sign_key: Option<&std::path::Path>,
+    selected: &[AllowlistLayer],
+) -> Result<(), Box<dyn std::error::Error>> {
+    use crate::allowlist_bundle::{
+        export_bundle, parse_signing_key, select_layers, sign, signature_path,
+    };
+    use colored::Colorize;
+
+    let allowlists = load_default_allowlists();
+    let bundle = export_bundle(&select_layers(&allowlists.layers, selected))?;
+    std::fs::write(output, &bundle)?;
+    println!(
+        "{} Wrote allowlist bundle to {}",
+        "✓".green(),
+        output.display()
+    );
+
+    if let Some(key_path) = sign_key {
+        let key = parse_signing_key(&std::fs::read_to_string(key_path)?)?;
+        let sig_path = signature_path(output);
+        std::fs::write(&sig_path, sign(bundle.as_bytes(), &key))?;
+        println!("{} Wrote signature to {}", "✓".green(), sig_path.display());
+    }
+    Ok(())
+}
+
+/// Verify a signed bundle and install it as the given allowlist layer.
+fn allowlist_import(
+    bundle_path: &std::path::Path,
+    public_key: &str,
+    signature: Option<&std::path::Path>,
+    layer: AllowlistLayer,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use crate::allowlist_bundle::{
+        parse_public_key, public_key_string, signature_path, trusted_key_path, verify,
+    };
+    use colored::Colorize;
+
+    let key_text = if std::path::Path::new(public_key).is_file() {
+        std::fs::read_to_string(public_key)?
+    } else {
+        public_key.to_string()
+    };
+    let key = parse_public_key(&key_text)?;
+
+    let bundle = std::fs::read_to_string(bundle_path)?;
+    let sig_source =
+        signature.map_or_else(|| signature_path(bundle_path), std::path::Path::to_path_buf);
+    let sig = std::fs::read_to_string(&sig_source)
+        .map_err(|e| format!("failed to read signature {}: {e}", sig_source.display()))?;
+    verify(bundle.as_bytes(), &sig, &key)?;
+
+    let parsed = crate::allowlist::parse_allowlist_toml(layer, bundle_path, &bundle);
+    if let Some(err) = parsed.errors.first() {
+        return Err(format!("bundle has invalid entries: {}", err.message).into());
+    }
+
+    let path = allowlist_path_for_layer(layer);
+    let key_path = trusted_key_path(&path);
+    if layer == AllowlistLayer::System && key_path.exists() {
+        let trusted = parse_public_key(&std::fs::read_to_string(&key_path)?)?;
+        if trusted != key {
+            return Err(format!(
+                "bundle is signed by a different key than the trusted key in {}; \
+                 remove that file to rotate keys",
+                key_path.display()
+            )
+            .into());
+        }
     }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    if let Some(backup) = write_settings_with_backup(&path, &bundle)? {
+        println!("  Previous allowlist saved to {}", backup.display());
+    }
+    std::fs::write(signature_path(&path), sig.trim())?;
+    if layer == AllowlistLayer::System {
+        std::fs::write(&key_path, public_key_string(&key))?;
+    }
+    record_audit(
+        "allowlist.import",
+        &path,
+        serde_json::json!({
+            "bundle": bundle_path.display().to_string(),
+            "entries": parsed.entries.len(),
+            "layer": layer.label(),
+        }),
+    );
+
+    println!(
+        "{} Verified and installed {} entries into the {} allowlist",
+        "✓".green(),
+        parsed.entries.len(),
+        layer.label()
+    );
+    println!("  File: {}", path.display());
+    if layer == AllowlistLayer::System {
+        println!("  Trusted key: {}", key_path.display());
+    }
+    Ok(())
+}
+
+/// Generate a bundle signing key pair.
+fn allowlist_keygen(output: &std::path::Path) -> Result<(), Box<dyn std::error::Error>> {
+    use colored::Colorize;
+
+    if output.exists() {
+        return Err(format!("{} already exists", output.display()).into());
+    }
+    let (secret, public) = crate::allowlist_bundle::generate_keypair();
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(output)?;
+    std::io::Write::write_all(&mut file, secret.as_bytes())?;
+
+    let mut pub_name = output.file_name().unwrap_or_default().to_os_string();
+    pub_name.push(".pub");
+    let pub_path = output.with_file_name(pub_name);
+    std::fs::write(&pub_path, &public)?;
+
+    println!("{} Secret key: {}", "✓".green(), output.display());
+    println!("{} Public key: {}", "✓".green(), pub_path.display());
+    println!("  {public}");
     Ok(())
 }
 
@@ -9143,6 +13028,7 @@ fn handle_allow_once_command(
             AllowOnceAction::List => return handle_allow_once_list(config, cmd),
             AllowOnceAction::Clear(args) => return handle_allow_once_clear(config, cmd, args),
             AllowOnceAction::Revoke(args) => return handle_allow_once_revoke(config, cmd, args),
+            AllowOnceAction::RotateSecret => return handle_allow_once_rotate_secret(config, cmd),
         }
     }
 
@@ -9157,9 +13043,11 @@ fn handle_allow_once_command(
 
     let (matches, _maintenance) = pending_store.lookup_by_code(code, now)?;
     if matches.is_empty() {
-        return Err(
-            format!("No pending exception found for code '{code}'. It may be expired.").into(),
-        );
+        return Err(format!(
+            "No pending exception found for code '{code}'. It may be expired, \
+             or it was issued before the allow-once secret was rotated."
+        )
+        .into());
     }
 
     let selected = select_pending_entry(&matches, cmd)?;
@@ -9187,7 +13075,7 @@ fn handle_allow_once_command(
     );
     let scope_path_str = scope_path.to_string_lossy().to_string();
 
-    let entry = AllowOnceEntry::from_pending(
+    let mut entry = AllowOnceEntry::from_pending(
         selected,
         now,
         scope_kind,
@@ -9196,6 +13084,15 @@ fn handle_allow_once_command(
         cmd.force && is_config_block,
         &config.logging.redaction,
     );
+    if cmd.session {
+        let Some(session_id) = selected.session_id.clone() else {
+            return Err(
+                "This denial was not recorded with an agent session id; --session needs one."
+                    .into(),
+            );
+        };
+        entry.session_id = Some(session_id);
+    }
 
     if cmd.json {
         let output = serde_json::json!({
@@ -9206,6 +13103,7 @@ fn handle_allow_once_command(
             "force": entry.force_allow_config,
             "scope_kind": format!("{scope_kind:?}").to_lowercase(),
             "scope_path": scope_path_str,
+            "session_id": entry.session_id,
             "command": if cmd.show_raw { selected.command_raw.clone() } else { selected.command_redacted.clone() },
             "cwd": selected.cwd.clone(),
             "expires_at": entry.expires_at,
@@ -9225,6 +13123,9 @@ fn handle_allow_once_command(
         println!("  CWD: {}", selected.cwd);
         println!("  Expires: {}", entry.expires_at);
         println!("  Scope: {scope_kind:?} ({scope_path_str})");
+        if let Some(session_id) = entry.session_id.as_deref() {
+            println!("  Session: {session_id}");
+        }
         if cmd.single_use {
             println!("  Mode: single-use");
         } else {
@@ -9262,16 +13163,52 @@ fn handle_allow_once_command(
     let allow_once_path = AllowOnceStore::default_path(Some(&selected_cwd));
     let allow_once_store = AllowOnceStore::new(allow_once_path.clone());
     let _maintenance = allow_once_store.add_entry(&entry, now)?;
+    record_audit(
+        "allow_once.create",
+        &allow_once_path,
+        serde_json::json!({
+            "code": code,
+            "command": selected.command_redacted,
+            "scope_path": scope_path_str,
+            "single_use": cmd.single_use,
+            "force": entry.force_allow_config,
+        }),
+    );
+    // Confirming an annotated command confirms its `# dcg:allow` suppression.
+    if let Some(suppression) = crate::suppression::InlineSuppression::parse(&selected.command_raw)
+        .filter(|_| config.suppressions.inline)
+        .filter(|suppression| {
+            selected
+                .reason
+                .starts_with(&format!("{} - ", suppression.rule))
+        })
+    {
+        if let Err(e) = suppression.record(
+            &selected.command_raw,
+            Some(&selected_cwd),
+            true,
+            &config.logging.redaction,
+        ) {
+            eprintln!("Warning: Failed to write audit log: {e}");
+        }
+    }
 
     // Remove the pending exception so it doesn't show up in lists anymore.
     // This is best-effort (if it fails, the allowed command still works).
     if let Err(e) = pending_store.remove_by_full_hash(&selected.full_hash, now) {
         eprintln!("Warning: Failed to remove pending exception: {e}");
     }
+    let promotable = record_trust(config, &entry, &selected_cwd);
 
     if !cmd.json {
         println!("✓ Allow-once entry created");
         println!("  File: {}", allow_once_path.display());
+        if promotable {
+            println!(
+                "  Approved {} or more times in this repository; run `dcg promote` to allowlist it.",
+                config.trust.promote_after
+            );
+        }
     }
 
     Ok(())
@@ -9332,8 +13269,13 @@ fn handle_allow_once_list(
             } else {
                 entry.command_redacted.as_str()
             };
+            let session = entry
+                .session_id
+                .as_deref()
+                .map(|id| format!(" (session {id})"))
+                .unwrap_or_default();
             println!(
-                "  - {} [{}] {}",
+                "  - {} [{}] {}{session}",
                 entry.source_short_code,
                 &entry.source_full_hash[..8.min(entry.source_full_hash.len())],
                 cmd_display
@@ -9475,6 +13417,13 @@ fn handle_allow_once_clear(
         let (_active, maintenance) = allow_once_store.load_active(now)?;
         (0, maintenance)
     };
+    if allow_once_wiped > 0 {
+        record_audit(
+            "allow_once.clear",
+            allow_once_store.path(),
+            serde_json::json!({ "removed": allow_once_wiped }),
+        );
+    }
 
     if let Some(log_file) = config.general.log_file.as_deref() {
         let _ = crate::pending_exceptions::log_allow_once_action(
@@ -9551,6 +13500,17 @@ fn handle_allow_once_revoke(
         pending_store.remove_by_full_hash(&full_hash, now)?;
     let (allow_once_removed, allow_once_maintenance) =
         allow_once_store.remove_by_source_full_hash(&full_hash, now)?;
+    if allow_once_removed > 0 {
+        record_audit(
+            "allow_once.revoke",
+            allow_once_store.path(),
+            serde_json::json!({
+                "target": &args.target,
+                "full_hash": full_hash,
+                "removed": allow_once_removed,
+            }),
+        );
+    }
 
     if let Some(log_file) = config.general.log_file.as_deref() {
         let _ = crate::pending_exceptions::log_allow_once_action(
@@ -9581,6 +13541,68 @@ fn handle_allow_once_revoke(
     Ok(())
 }
 
+fn handle_allow_once_rotate_secret(
+    config: &Config,
+    cmd: &AllowOnceCommand,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use std::io::{self, Write};
+
+    if cmd.json && !cmd.yes {
+        return Err("JSON output requires --yes to avoid interactive prompts.".into());
+    }
+
+    let now = Utc::now();
+    let cwd = std::env::current_dir().unwrap_or_default();
+    let pending_store = PendingExceptionStore::new(PendingExceptionStore::default_path(Some(&cwd)));
+    let (pending, _) = pending_store.preview_active(now)?;
+    let invalidated = pending.iter().filter(|r| r.hash_verifies()).count();
+
+    if !cmd.json && !cmd.yes {
+        println!("Allow-once secret rotation:");
+        println!("  pending_codes_invalidated={invalidated}");
+        print!("Proceed? [y/N]: ");
+        io::stdout().flush()?;
+        let mut response = String::new();
+        io::stdin().read_line(&mut response)?;
+        let response = response.trim().to_lowercase();
+        if response != "y" && response != "yes" {
+            return Err("Aborted.".into());
+        }
+    }
+
+    let secret_path = crate::allow_once_secret::default_path(Some(&cwd));
+    let secret = crate::allow_once_secret::rotate(&secret_path, true)?;
+    let stored_in = secret.source().describe();
+    record_audit(
+        "allow_once.rotate_secret",
+        std::path::Path::new(&stored_in),
+        serde_json::json!({ "pending_invalidated": invalidated }),
+    );
+
+    if let Some(log_file) = config.general.log_file.as_deref() {
+        let _ = crate::pending_exceptions::log_allow_once_action(
+            log_file,
+            "rotate-secret",
+            &format!("stored_in={stored_in}, pending_invalidated={invalidated}"),
+        );
+    }
+
+    if cmd.json {
+        let output = serde_json::json!({
+            "status": "ok",
+            "stored_in": stored_in,
+            "pending_invalidated": invalidated,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    println!("✓ Rotated allow-once secret");
+    println!("  Stored in: {stored_in}");
+    println!("  Pending codes invalidated: {invalidated}");
+    Ok(())
+}
+
 fn resolve_allow_once_revoke_target(
     target: &str,
     pending: &[PendingExceptionRecord],
@@ -9723,6 +13745,17 @@ fn allowlist_add_rule(
 
     // Write back
     write_allowlist(&path, &doc)?;
+    record_audit(
+        "allowlist.add_rule",
+        &path,
+        serde_json::json!({
+            "rule": rule_id,
+            "reason": reason,
+            "layer": layer.label(),
+            "expires": expires,
+            "conditions": conditions,
+        }),
+    );
 
     println!(
         "{} Added {} to {} allowlist",
@@ -9768,6 +13801,16 @@ fn allowlist_add_command(
 
     // Write back
     write_allowlist(&path, &doc)?;
+    record_audit(
+        "allowlist.add_command",
+        &path,
+        serde_json::json!({
+            "command": command,
+            "reason": reason,
+            "layer": layer.label(),
+            "expires": expires,
+        }),
+    );
 
     println!(
         "{} Added exact command to {} allowlist",
@@ -9888,6 +13931,7 @@ fn allowlist_list(
                         "added_by": entry.added_by,
                         "added_at": entry.added_at,
                         "expires_at": entry.expires_at,
+                        "expired": crate::allowlist::is_expired(entry),
                     })
                 })
                 .collect();
@@ -9934,6 +13978,11 @@ fn allowlist_remove(
     }
 
     write_allowlist(&path, &doc)?;
+    record_audit(
+        "allowlist.remove",
+        &path,
+        serde_json::json!({ "rule": rule_id, "layer": layer.label() }),
+    );
 
     println!(
         "{} Removed {} from {} allowlist",
@@ -9958,7 +14007,12 @@ fn allowlist_validate(
     } else if user_only {
         vec![AllowlistLayer::User]
     } else {
-        vec![AllowlistLayer::Project, AllowlistLayer::User]
+        vec![
+            AllowlistLayer::Project,
+            AllowlistLayer::User,
+            AllowlistLayer::System,
+            AllowlistLayer::Remote,
+        ]
     };
 
     let mut errors = 0;
@@ -9988,17 +14042,18 @@ fn allowlist_validate(
 
             // Check entries
             for (idx, entry) in loaded.file.entries.iter().enumerate() {
-                // Check for expired entries
-                if let Some(expires_at) = &entry.expires_at {
-                    if is_expired(expires_at) {
-                        println!(
-                            "  {} Entry {} is expired ({})",
-                            "WARNING:".yellow(),
-                            idx + 1,
-                            expires_at
-                        );
-                        warnings += 1;
-                    }
+                // Check for expired entries (absolute `expires_at` or elapsed `ttl`)
+                if crate::allowlist::is_expired(entry) {
+                    let expiry = entry.expires_at.clone().unwrap_or_else(|| {
+                        format!("ttl {}", entry.ttl.as_deref().unwrap_or("elapsed"))
+                    });
+                    println!(
+                        "  {} Entry {} is expired ({}); remove with `dcg allowlist prune`",
+                        "WARNING:".yellow(),
+                        idx + 1,
+                        expiry
+                    );
+                    warnings += 1;
                 }
 
                 // Check for risky regex patterns without acknowledgement
@@ -10054,6 +14109,89 @@ fn allowlist_validate(
     }
 }
 
+/// Remove expired entries from the project and/or user allowlist files.
+///
+/// The system allowlist is left alone; it is managed via signed bundles.
+fn allowlist_prune(
+    project_only: bool,
+    user_only: bool,
+    dry_run: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use colored::Colorize;
+
+    let layers: Vec<AllowlistLayer> = if project_only {
+        vec![AllowlistLayer::Project]
+    } else if user_only {
+        vec![AllowlistLayer::User]
+    } else {
+        vec![AllowlistLayer::Project, AllowlistLayer::User]
+    };
+
+    let mut total = 0;
+    for layer in layers {
+        let path = allowlist_path_for_layer(layer);
+        if !path.exists() {
+            continue;
+        }
+
+        let content = std::fs::read_to_string(&path)?;
+        let (pruned, removed) = crate::allowlist::prune_expired_toml(&content)
+            .map_err(|e| format!("{}: {e}", path.display()))?;
+        if removed.is_empty() {
+            continue;
+        }
+
+        println!("{} allowlist: {}", layer.label().bold(), path.display());
+        for entry in &removed {
+            let value = match &entry.selector {
+                AllowSelector::Rule(rule_id) => rule_id.to_string(),
+                AllowSelector::ExactCommand(value)
+                | AllowSelector::CommandPrefix(value)
+                | AllowSelector::RegexPattern(value) => value.clone(),
+            };
+            println!(
+                "  {} {} {} (expired: {})",
+                "-".red(),
+                entry.selector.kind_label(),
+                value,
+                entry.expires_at.as_deref().unwrap_or("ttl elapsed")
+            );
+        }
+        total += removed.len();
+
+        if !dry_run {
+            let doc: toml_edit::DocumentMut = pruned.parse()?;
+            write_allowlist(&path, &doc)?;
+            record_audit(
+                "allowlist.prune",
+                &path,
+                serde_json::json!({ "removed": removed.len(), "layer": layer.label() }),
+            );
+        }
+    }
+
+    if total == 0 {
+        println!("{}", "No expired allowlist entries.".green());
+    } else if dry_run {
+        println!(
+            "{total} expired entr{} would be removed (dry run).",
+            plural_y(total)
+        );
+    } else {
+        println!(
+            "{} Removed {total} expired entr{}.",
+            "✓".green(),
+            plural_y(total)
+        );
+    }
+
+    Ok(())
+}
+
+const fn plural_y(count: usize) -> &'static str {
+    if count == 1 { "y" } else { "ies" }
+}
+
 // ============================================================================
 // TOML manipulation helpers (using toml_edit for stable formatting)
 // ============================================================================
@@ -10305,6 +14443,16 @@ fn allowlist_add_pattern(
 
     // Write atomically (temp file + rename to prevent corruption)
     write_allowlist(&path, &doc)?;
+    record_audit(
+        "allowlist.add_pattern",
+        &path,
+        serde_json::json!({
+            "pattern": pattern,
+            "reason": reason,
+            "layer": layer.label(),
+            "risk_level": risk_level,
+        }),
+    );
 
     Ok(path)
 }
@@ -10350,7 +14498,7 @@ fn check_pattern_conflicts(pattern: &str, config: &Config) -> PatternConflictChe
     // Check for conflicts with block overrides
     // We compile the pattern and see if any of the block patterns would match
     // the same space. This is a heuristic check.
-    let compiled_overrides = config.overrides.compile();
+    let compiled_overrides = config.compile_overrides();
     if compiled_overrides.block.is_empty() {
         return result;
     }
@@ -10444,6 +14592,11 @@ fn handle_suggest_allowlist_undo(minutes: u32) -> Result<(), Box<dyn std::error:
         let removed = remove_auto_suggested_entries(&mut doc, cutoff);
         if removed > 0 {
             write_allowlist(&path, &doc)?;
+            record_audit(
+                "allowlist.undo_suggestions",
+                &path,
+                serde_json::json!({ "removed": removed, "layer": layer.label() }),
+            );
             println!(
                 "{} Removed {} auto-suggested pattern(s) from {} allowlist ({})",
                 "✓".green(),
@@ -11298,12 +15451,106 @@ mod tests {
         assert!(err.to_string().contains("PreToolUse"));
     }
 
+    #[test]
+    fn hook_target_from_agent() {
+        assert_eq!(HookTarget::from_agent(None), Ok(HookTarget::ClaudeCode));
+        assert_eq!(
+            HookTarget::from_agent(Some("claude-code")),
+            Ok(HookTarget::ClaudeCode)
+        );
+        assert_eq!(
+            HookTarget::from_agent(Some("cursor")),
+            Ok(HookTarget::Cursor)
+        );
+        assert_eq!(HookTarget::from_agent(Some("codex")), Ok(HookTarget::Codex));
+        assert!(HookTarget::from_agent(Some("aider")).is_err());
+    }
+
+    #[test]
+    fn cursor_hooks_install_uninstall_roundtrip() {
+        let mut settings = serde_json::json!({
+            "version": 1,
+            "hooks": { "beforeShellExecution": [{ "command": "./audit.sh" }] }
+        });
+
+        assert!(install_dcg_hook_into_cursor_hooks(&mut settings, false).unwrap());
+        assert!(
+            !install_dcg_hook_into_cursor_hooks(&mut settings, false).unwrap(),
+            "second install should be a no-op"
+        );
+        let hooks = settings["hooks"]["beforeShellExecution"]
+            .as_array()
+            .unwrap();
+        assert_eq!(hooks.len(), 2);
+        assert_eq!(hooks[1]["command"], "dcg --agent cursor");
+
+        assert!(uninstall_dcg_hook_from_cursor_hooks(&mut settings).unwrap());
+        let hooks = settings["hooks"]["beforeShellExecution"]
+            .as_array()
+            .unwrap();
+        assert_eq!(hooks.len(), 1, "should retain non-dcg hook");
+        assert_eq!(hooks[0]["command"], "./audit.sh");
+        assert!(!uninstall_dcg_hook_from_cursor_hooks(&mut settings).unwrap());
+    }
+
+    #[test]
+    fn codex_config_install_uninstall_roundtrip() {
+        let mut doc: toml_edit::DocumentMut = "model = \"o4-mini\"\n".parse().unwrap();
+
+        assert!(install_dcg_hook_into_codex_config(&mut doc, false).unwrap());
+        assert!(
+            !install_dcg_hook_into_codex_config(&mut doc, false).unwrap(),
+            "second install should be a no-op"
+        );
+        assert!(install_dcg_hook_into_codex_config(&mut doc, true).unwrap());
+        let rendered = doc.to_string();
+        assert!(rendered.contains("model = \"o4-mini\""));
+        assert_eq!(rendered.matches("[[hooks.pre_exec]]").count(), 1);
+        assert!(rendered.contains(r#"command = ["dcg", "--agent", "codex"]"#));
+
+        assert!(uninstall_dcg_hook_from_codex_config(&mut doc).unwrap());
+        assert!(!doc.to_string().contains("dcg"));
+        assert!(!uninstall_dcg_hook_from_codex_config(&mut doc).unwrap());
+    }
+
+    #[test]
+    fn write_settings_with_backup_keeps_previous_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("hooks.json");
+
+        assert_eq!(write_settings_with_backup(&path, "{}").unwrap(), None);
+        let backup = write_settings_with_backup(&path, "{\"version\":1}")
+            .unwrap()
+            .expect("backup of existing file");
+        assert_eq!(backup, dir.path().join("hooks.json.dcg-backup"));
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), "{}");
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "{\"version\":1}");
+    }
+
     #[test]
     fn test_cli_parse_no_args() {
         let cli = Cli::parse_from(["dcg"]);
         assert!(cli.command.is_none());
     }
 
+    #[test]
+    fn test_cli_parse_reload() {
+        let cli = Cli::parse_from(["dcg", "reload"]);
+        assert!(matches!(cli.command, Some(Command::Reload)));
+    }
+
+    #[test]
+    fn test_cli_parse_stats_dashboard() {
+        let cli = Cli::parse_from(["dcg", "stats", "--dashboard", "-d", "7"]);
+        let Some(Command::Stats(stats)) = cli.command else {
+            panic!("expected stats command");
+        };
+        assert!(stats.dashboard);
+        assert_eq!(stats.days, 7);
+
+        assert!(Cli::try_parse_from(["dcg", "stats", "--dashboard", "--rules"]).is_err());
+    }
+
     #[test]
     fn test_cli_parse_packs() {
         let cli = Cli::parse_from(["dcg", "packs"]);
@@ -11318,6 +15565,88 @@ mod tests {
         assert_eq!(cli.verbose, 1); // Global verbose flag should be set
     }
 
+    #[test]
+    fn test_cli_parse_packs_enable_disable() {
+        let cli = Cli::parse_from(["dcg", "packs", "enable", "database.postgresql"]);
+        if let Some(Command::ListPacks {
+            action: Some(PacksAction::Enable { pack_id, project }),
+            ..
+        }) = cli.command
+        {
+            assert_eq!(pack_id, "database.postgresql");
+            assert!(!project);
+        } else {
+            unreachable!("Expected packs enable command");
+        }
+
+        let cli = Cli::parse_from(["dcg", "packs", "disable", "kubernetes", "--project"]);
+        assert!(matches!(
+            cli.command,
+            Some(Command::ListPacks {
+                action: Some(PacksAction::Disable { project: true, .. }),
+                ..
+            })
+        ));
+
+        let cli = Cli::parse_from(["dcg", "packs", "--enabled"]);
+        assert!(matches!(
+            cli.command,
+            Some(Command::ListPacks {
+                enabled: true,
+                action: None,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_set_pack_enabled_preserves_comments() {
+        let mut doc: toml_edit::DocumentMut = r#"# team config
+[packs]
+# databases we run
+enabled = ["database.mysql"]
+disabled = ["kubernetes"]
+
+[general]
+verbose = false  # keep quiet
+"#
+        .parse()
+        .unwrap();
+
+        assert!(set_pack_enabled(&mut doc, "kubernetes", true).unwrap());
+        assert!(!set_pack_enabled(&mut doc, "kubernetes", true).unwrap());
+        assert!(set_pack_enabled(&mut doc, "database.mysql", false).unwrap());
+
+        let out = doc.to_string();
+        assert!(out.contains("# team config"));
+        assert!(out.contains("# databases we run"));
+        assert!(out.contains("verbose = false  # keep quiet"));
+
+        let config: Config = toml::from_str(&out).unwrap();
+        assert_eq!(config.packs.enabled, vec!["kubernetes"]);
+        assert_eq!(config.packs.disabled, vec!["database.mysql"]);
+    }
+
+    #[test]
+    fn test_set_pack_enabled_creates_packs_table() {
+        let mut doc = toml_edit::DocumentMut::new();
+        assert!(set_pack_enabled(&mut doc, "containers.docker", true).unwrap());
+        let config: Config = toml::from_str(&doc.to_string()).unwrap();
+        assert_eq!(config.packs.enabled, vec!["containers.docker"]);
+
+        let mut doc: toml_edit::DocumentMut = "packs = 3\n".parse().unwrap();
+        assert!(set_pack_enabled(&mut doc, "containers.docker", true).is_err());
+    }
+
+    #[test]
+    fn test_is_known_pack_id() {
+        let external = ["acme.deploy".to_string()];
+        assert!(is_known_pack_id("core.git", external.iter()));
+        assert!(is_known_pack_id("database", external.iter()));
+        assert!(is_known_pack_id("acme.deploy", external.iter()));
+        assert!(!is_known_pack_id("database.nosuch", external.iter()));
+    }
+
     #[test]
     fn test_cli_parse_pack_info() {
         let cli = Cli::parse_from(["dcg", "pack", "info", "core.git"]);
@@ -11331,16 +15660,54 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_parse_bench() {
+        let cli = Cli::parse_from(["dcg", "bench", "-n", "50", "--cold-start-iterations", "0"]);
+        let Some(Command::Bench(bench)) = cli.command else {
+            unreachable!("Expected Bench command");
+        };
+        assert_eq!(bench.iterations, 50);
+        assert_eq!(bench.cold_start_iterations, 0);
+        assert_eq!(bench.format, CorpusFormat::Json);
+    }
+
+    #[test]
+    fn test_cli_parse_pattern_test() {
+        let cli = Cli::parse_from(["dcg", "pattern", "test", "my.yaml", "--no-corpus"]);
+        if let Some(Command::Pattern {
+            action:
+                PatternAction::Test {
+                    file_path,
+                    no_corpus,
+                    format,
+                },
+        }) = cli.command
+        {
+            assert_eq!(file_path, "my.yaml");
+            assert!(no_corpus);
+            assert_eq!(format, PackValidateFormat::Pretty);
+        } else {
+            unreachable!("Expected Pattern Test command");
+        }
+    }
+
     #[test]
     fn test_cli_parse_test() {
         let cli = Cli::parse_from(["dcg", "test", "git reset --hard"]);
         if let Some(Command::TestCommand { command, .. }) = cli.command {
-            assert_eq!(command, "git reset --hard");
+            assert_eq!(command.as_deref(), Some("git reset --hard"));
         } else {
             unreachable!("Expected TestCommand command");
         }
     }
 
+    #[test]
+    fn test_cli_parse_agent_flag() {
+        let cli = Cli::parse_from(["dcg", "--agent", "cursor"]);
+        assert_eq!(cli.agent.as_deref(), Some("cursor"));
+        assert!(cli.command.is_none());
+    }
+
     #[test]
     fn test_cli_parse_init() {
         let cli = Cli::parse_from(["dcg", "init"]);
@@ -11527,9 +15894,66 @@ mod tests {
             action: AllowlistAction::List { format, .. },
         }) = cli.command
         {
-            assert_eq!(format, AllowlistOutputFormat::Json);
+            assert_eq!(format, AllowlistOutputFormat::Json);
+        } else {
+            unreachable!("Expected Allowlist List command");
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_allowlist_export_import() {
+        let cli = Cli::parse_from([
+            "dcg",
+            "allowlist",
+            "export",
+            "-o",
+            "org.toml",
+            "--sign-key",
+            "org.key",
+            "--system",
+        ]);
+        if let Some(Command::Allowlist {
+            action:
+                AllowlistAction::Export {
+                    output,
+                    sign_key,
+                    system,
+                    project,
+                    ..
+                },
+        }) = cli.command
+        {
+            assert_eq!(output, std::path::PathBuf::from("org.toml"));
+            assert_eq!(sign_key, Some(std::path::PathBuf::from("org.key")));
+            assert!(system);
+            assert!(!project);
+        } else {
+            unreachable!("Expected Allowlist Export command");
+        }
+
+        let cli = Cli::parse_from([
+            "dcg",
+            "allowlist",
+            "import",
+            "org.toml",
+            "--public-key",
+            "org.key.pub",
+        ]);
+        if let Some(Command::Allowlist {
+            action:
+                AllowlistAction::Import {
+                    bundle,
+                    signature,
+                    user,
+                    ..
+                },
+        }) = cli.command
+        {
+            assert_eq!(bundle, std::path::PathBuf::from("org.toml"));
+            assert_eq!(signature, None);
+            assert!(!user);
         } else {
-            unreachable!("Expected Allowlist List command");
+            unreachable!("Expected Allowlist Import command");
         }
     }
 
@@ -11546,6 +15970,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_parse_allowlist_prune() {
+        let cli = Cli::parse_from(["dcg", "allowlist", "prune", "--user", "--dry-run"]);
+        if let Some(Command::Allowlist {
+            action:
+                AllowlistAction::Prune {
+                    project,
+                    user,
+                    dry_run,
+                },
+        }) = cli.command
+        {
+            assert!(!project);
+            assert!(user);
+            assert!(dry_run);
+        } else {
+            unreachable!("Expected Allowlist Prune command");
+        }
+    }
+
     #[test]
     fn test_cli_parse_allowlist_add_command() {
         let cli = Cli::parse_from([
@@ -11615,6 +16059,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_parse_allow_once_rotate_secret() {
+        let cli = Cli::parse_from(["dcg", "allow-once", "rotate-secret", "--yes"]);
+        if let Some(Command::AllowOnce(cmd)) = cli.command {
+            assert!(cmd.yes);
+            assert!(matches!(cmd.action, Some(AllowOnceAction::RotateSecret)));
+        } else {
+            unreachable!("Expected AllowOnce rotate-secret command");
+        }
+    }
+
     #[test]
     fn test_allowlist_toml_helpers() {
         // Test building a rule entry
@@ -12398,6 +16853,75 @@ exclude = ["target/**"]
         assert_eq!(after, existing, "should not modify unknown hook");
     }
 
+    #[test]
+    fn test_cli_parse_git_hook_pre_push() {
+        let cli = Cli::try_parse_from(["dcg", "git-hook", "pre-push", "origin", "git@x:y.git"])
+            .expect("parse");
+        if let Some(Command::GitHook {
+            action: GitHookAction::PrePush { remote, url },
+        }) = cli.command
+        {
+            assert_eq!(remote, "origin");
+            assert_eq!(url.as_deref(), Some("git@x:y.git"));
+        } else {
+            unreachable!("Expected GitHook pre-push command");
+        }
+    }
+
+    #[test]
+    fn git_hook_install_uninstall_roundtrip() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        init_temp_git_repo(tmp.path());
+
+        let installed = install_git_hooks_at(tmp.path()).expect("install");
+        assert_eq!(installed.len(), crate::git_hook::MANAGED_HOOKS.len());
+        for path in &installed {
+            let contents = std::fs::read(path).expect("read hook");
+            assert!(crate::git_hook::is_managed_hook(&contents));
+        }
+        assert_eq!(
+            install_git_hooks_at(tmp.path()).expect("install again"),
+            installed
+        );
+
+        let removed = uninstall_git_hooks_at(tmp.path()).expect("uninstall");
+        assert_eq!(removed, installed);
+        assert!(
+            uninstall_git_hooks_at(tmp.path())
+                .expect("uninstall again")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn git_hook_install_refuses_to_overwrite_unknown_hook() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        init_temp_git_repo(tmp.path());
+
+        let hook_path = git_resolve_path(tmp.path(), "hooks/pre-rebase").expect("hook path");
+        let existing = "#!/usr/bin/env bash\necho hi\n";
+        std::fs::write(&hook_path, existing).expect("write existing hook");
+
+        let err = install_git_hooks_at(tmp.path()).expect_err("should refuse");
+        assert!(err.to_string().contains("Refusing to overwrite"));
+        let pre_push = git_resolve_path(tmp.path(), "hooks/pre-push").expect("hook path");
+        assert!(!pre_push.exists(), "nothing should be half-installed");
+
+        let after = std::fs::read_to_string(&hook_path).expect("read hook after");
+        assert_eq!(after, existing, "should not modify unknown hook");
+    }
+
+    #[test]
+    fn git_hook_force_push_is_denied_by_core_git() {
+        let config = Config::default();
+        let implied = [crate::git_hook::ImpliedCommand {
+            description: "non-fast-forward update of main on origin".to_string(),
+            command: "git push --force origin main:main".to_string(),
+        }];
+        assert!(!check_git_hook_commands(&config, "push", &implied));
+        assert!(check_git_hook_commands(&config, "push", &[]));
+    }
+
     #[test]
     fn test_cli_parse_history_stats() {
         let cli = Cli::try_parse_from([
@@ -12417,6 +16941,487 @@ exclude = ["target/**"]
         }
     }
 
+    #[test]
+    fn test_cli_parse_corpus_record_and_replay() {
+        let cli =
+            Cli::try_parse_from(["dcg", "corpus", "record", "-o", "corpus.json"]).expect("parse");
+        let Some(Command::Corpus(corpus)) = cli.command else {
+            unreachable!("Expected Corpus command");
+        };
+        let Some(CorpusAction::Record { output, since, .. }) = corpus.action else {
+            unreachable!("Expected record action");
+        };
+        assert_eq!(output, std::path::PathBuf::from("corpus.json"));
+        assert_eq!(since, "30d");
+
+        let cli = Cli::try_parse_from(["dcg", "corpus", "replay", "corpus.json", "-f", "json"])
+            .expect("parse");
+        let Some(Command::Corpus(corpus)) = cli.command else {
+            unreachable!("Expected Corpus command");
+        };
+        assert!(matches!(
+            corpus.action,
+            Some(CorpusAction::Replay {
+                format: CorpusFormat::Json,
+                ..
+            })
+        ));
+
+        // Plain `dcg corpus` still runs the test corpus.
+        let cli = Cli::try_parse_from(["dcg", "corpus", "-d", "tests/corpus"]).expect("parse");
+        let Some(Command::Corpus(corpus)) = cli.command else {
+            unreachable!("Expected Corpus command");
+        };
+        assert!(corpus.action.is_none());
+    }
+
+    #[test]
+    fn test_cli_parse_recommend() {
+        let cli = Cli::try_parse_from(["dcg", "recommend", "--since", "7d", "--min-blocks", "3"])
+            .expect("parse");
+        let Some(Command::Recommend(recommend)) = cli.command else {
+            unreachable!("Expected Recommend command");
+        };
+        assert_eq!(recommend.since, "7d");
+        assert_eq!(recommend.min_blocks, 3);
+        assert_eq!(recommend.limit, 5000);
+    }
+
+    #[test]
+    fn test_cli_parse_promote() {
+        let cli = Cli::try_parse_from(["dcg", "promote", "--min-approvals", "2", "--dry-run"])
+            .expect("parse");
+        let Some(Command::Promote(promote)) = cli.command else {
+            unreachable!("Expected Promote command");
+        };
+        assert_eq!(promote.min_approvals, Some(2));
+        assert!(promote.dry_run);
+        assert!(!promote.yes);
+    }
+
+    #[test]
+    fn test_cli_parse_review() {
+        let cli = Cli::try_parse_from(["dcg", "review", "--since", "24h", "--limit", "50"])
+            .expect("parse");
+        let Some(Command::Review(review)) = cli.command else {
+            unreachable!("Expected Review command");
+        };
+        assert_eq!(review.since, "24h");
+        assert_eq!(review.limit, 50);
+
+        let cli = Cli::try_parse_from(["dcg", "review"]).expect("parse");
+        let Some(Command::Review(review)) = cli.command else {
+            unreachable!("Expected Review command");
+        };
+        assert_eq!(review.since, "7d");
+        assert_eq!(review.limit, 200);
+    }
+
+    #[test]
+    fn test_cli_parse_top() {
+        let cli = Cli::try_parse_from(["dcg", "top"]).expect("parse");
+        let Some(Command::Top(top)) = cli.command else {
+            unreachable!("Expected Top command");
+        };
+        assert_eq!(top.window, "5m");
+        assert_eq!(top.interval, 1);
+        assert!(!top.once);
+
+        let cli = Cli::try_parse_from(["dcg", "top", "--window", "1h", "--once", "-f", "json"])
+            .expect("parse");
+        let Some(Command::Top(top)) = cli.command else {
+            unreachable!("Expected Top command");
+        };
+        assert_eq!(top.window, "1h");
+        assert!(top.once);
+        assert_eq!(top.format, SuggestFormat::Json);
+
+        assert!(Cli::try_parse_from(["dcg", "top", "--interval", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_completion_command_offers_pack_ids() {
+        let cmd = completion_command();
+        let pack_values = |path: &[&str], arg: &str| -> Vec<String> {
+            let mut current = &cmd;
+            for name in path {
+                current = current.find_subcommand(name).expect("subcommand exists");
+            }
+            current
+                .get_arguments()
+                .find(|a| a.get_id() == arg)
+                .expect("argument exists")
+                .get_possible_values()
+                .iter()
+                .map(|value| value.get_name().to_string())
+                .collect()
+        };
+
+        let info = pack_values(&["pack", "info"], "pack_id");
+        assert!(info.contains(&"core.git".to_string()));
+        assert!(info.contains(&"database".to_string()));
+        assert!(!info.contains(&"all".to_string()));
+
+        let search = pack_values(&["history", "search"], "pack");
+        assert!(search.contains(&"core".to_string()));
+
+        let test = pack_values(&["test"], "with_packs");
+        assert!(test.contains(&"containers.docker".to_string()));
+
+        let mut bash = Vec::new();
+        generate(
+            clap_complete::Shell::Bash,
+            &mut completion_command(),
+            "dcg",
+            &mut bash,
+        );
+        assert!(String::from_utf8(bash).unwrap().contains("core.git"));
+
+        // The real parser still accepts pack IDs it does not know about.
+        Cli::try_parse_from(["dcg", "pack", "info", "my.external"]).expect("parse");
+    }
+
+    #[test]
+    fn test_cli_parse_man() {
+        let cli = Cli::try_parse_from(["dcg", "man", "-o", "target/man"]).expect("parse");
+        if let Some(Command::Man { output_dir }) = cli.command {
+            assert_eq!(output_dir, Some(std::path::PathBuf::from("target/man")));
+        } else {
+            unreachable!("Expected Man command");
+        }
+    }
+
+    #[test]
+    fn test_write_man_pages_to_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        write_man_pages(Some(dir.path())).unwrap();
+        let page = std::fs::read_to_string(dir.path().join("dcg.1")).unwrap();
+        assert!(page.contains(".TH dcg"));
+        assert!(dir.path().join("dcg-history-export.1").exists());
+    }
+
+    #[test]
+    fn test_cli_parse_history_vacuum() {
+        let cli = Cli::try_parse_from(["dcg", "history", "vacuum", "--no-compact"]).expect("parse");
+        if let Some(Command::History {
+            action: HistoryAction::Vacuum { no_compact, json },
+        }) = cli.command
+        {
+            assert!(no_compact);
+            assert!(!json);
+        } else {
+            unreachable!("Expected History vacuum command");
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_history_export_filters() {
+        let cli = Cli::try_parse_from([
+            "dcg",
+            "history",
+            "export",
+            "--format",
+            "parquet",
+            "--since",
+            "7d",
+            "--filter",
+            "pack=core",
+            "--filter",
+            "outcome=deny",
+        ])
+        .expect("parse");
+        if let Some(Command::History {
+            action:
+                HistoryAction::Export {
+                    format,
+                    since,
+                    filters,
+                    ..
+                },
+        }) = cli.command
+        {
+            assert!(matches!(format, ExportFormat::Parquet));
+            assert_eq!(since.as_deref(), Some("7d"));
+            assert_eq!(filters, vec!["pack=core", "outcome=deny"]);
+        } else {
+            unreachable!("Expected History export command");
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_config_effective() {
+        let cli = Cli::try_parse_from(["dcg", "config"]).expect("parse");
+        assert!(matches!(
+            cli.command,
+            Some(Command::ShowConfig { action: None })
+        ));
+
+        let cli =
+            Cli::try_parse_from(["dcg", "config", "effective", "--format", "json"]).expect("parse");
+        assert!(matches!(
+            cli.command,
+            Some(Command::ShowConfig {
+                action: Some(ConfigAction::Effective {
+                    format: ConfigEffectiveFormat::Json
+                })
+            })
+        ));
+    }
+
+    #[test]
+    fn init_wizard_maps_ecosystems_to_packs() {
+        let packs = init_wizard_packs(
+            &[
+                InitEcosystem::Git,
+                InitEcosystem::Kubernetes,
+                InitEcosystem::Cloud,
+            ],
+            true,
+        );
+        assert_eq!(packs, vec!["kubernetes", "cloud", "strict_git"]);
+        assert!(init_wizard_packs(&[InitEcosystem::Git], false).is_empty());
+    }
+
+    #[test]
+    fn init_wizard_config_enables_selected_packs() {
+        let packs = vec!["containers".to_string(), "database".to_string()];
+        let rendered = render_init_config(&packs).expect("render");
+        assert!(rendered.contains("PACK CONFIGURATION"), "comments are kept");
+
+        let config: Config = toml::from_str(&rendered).expect("rendered config parses");
+        assert_eq!(config.packs.enabled, packs);
+        // Categories expand to their sub-packs the way the evaluator sees them.
+        let expanded = REGISTRY.expand_enabled_ordered(&config.enabled_pack_ids());
+        assert!(expanded.iter().any(|id| id == "containers.docker"));
+        assert!(expanded.iter().any(|id| id == "database.postgresql"));
+        assert!(!expanded.iter().any(|id| id == "kubernetes.kubectl"));
+    }
+
+    #[test]
+    fn init_wizard_smoke_cases_block_with_selected_packs() {
+        let ecosystems = InitEcosystem::ALL;
+        let mut config = Config::default();
+        config.packs.enabled = init_wizard_packs(&ecosystems, false);
+        let enabled = config.enabled_pack_ids();
+        let keywords = REGISTRY.collect_enabled_keywords(&enabled);
+        let compiled = config.compile_overrides();
+        let allowlists = crate::allowlist::LayeredAllowlist::default();
+
+        for (command, expect_block) in init_smoke_cases(&ecosystems) {
+            let result = evaluate_command(command, &config, &keywords, &compiled, &allowlists);
+            assert_eq!(result.is_denied(), expect_block, "{command}");
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_cache() {
+        let cli = Cli::try_parse_from(["dcg", "cache", "status", "--json"]).expect("parse");
+        assert!(matches!(
+            cli.command,
+            Some(Command::Cache {
+                action: CacheAction::Status { json: true }
+            })
+        ));
+
+        let cli = Cli::try_parse_from(["dcg", "cache", "clear"]).expect("parse");
+        assert!(matches!(
+            cli.command,
+            Some(Command::Cache {
+                action: CacheAction::Clear
+            })
+        ));
+    }
+
+    #[test]
+    fn test_cli_parse_notify() {
+        let cli = Cli::try_parse_from(["dcg", "notify", "test"]).expect("parse");
+        assert!(matches!(
+            cli.command,
+            Some(Command::Notify {
+                action: NotifyAction::Test
+            })
+        ));
+
+        let cli = Cli::try_parse_from(["dcg", "notify", "flush"]).expect("parse");
+        assert!(matches!(
+            cli.command,
+            Some(Command::Notify {
+                action: NotifyAction::Flush
+            })
+        ));
+    }
+
+    #[test]
+    fn test_cli_parse_sync() {
+        let cli = Cli::try_parse_from(["dcg", "sync"]).expect("parse");
+        assert!(matches!(
+            cli.command,
+            Some(Command::Sync {
+                quiet: false,
+                status: false,
+                json: false
+            })
+        ));
+
+        let cli = Cli::try_parse_from(["dcg", "sync", "--quiet"]).expect("parse");
+        assert!(matches!(
+            cli.command,
+            Some(Command::Sync { quiet: true, .. })
+        ));
+
+        let cli = Cli::try_parse_from(["dcg", "sync", "--status", "--json"]).expect("parse");
+        assert!(matches!(
+            cli.command,
+            Some(Command::Sync {
+                status: true,
+                json: true,
+                ..
+            })
+        ));
+
+        assert!(Cli::try_parse_from(["dcg", "sync", "--status", "--quiet"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_warmup() {
+        let cli = Cli::try_parse_from(["dcg", "warmup"]).expect("parse");
+        assert!(matches!(
+            cli.command,
+            Some(Command::Warmup {
+                all: false,
+                json: false
+            })
+        ));
+
+        let cli = Cli::try_parse_from(["dcg", "warmup", "--all", "--json"]).expect("parse");
+        assert!(matches!(
+            cli.command,
+            Some(Command::Warmup {
+                all: true,
+                json: true
+            })
+        ));
+    }
+
+    #[test]
+    fn test_cli_parse_policy_show() {
+        let cli = Cli::try_parse_from(["dcg", "policy", "show"]).expect("parse");
+        assert!(matches!(
+            cli.command,
+            Some(Command::Policy {
+                action: PolicyAction::Show {
+                    format: PolicyShowFormat::Pretty,
+                    all: false
+                }
+            })
+        ));
+
+        let cli = Cli::try_parse_from(["dcg", "policy", "show", "--format", "json", "--all"])
+            .expect("parse");
+        assert!(matches!(
+            cli.command,
+            Some(Command::Policy {
+                action: PolicyAction::Show {
+                    format: PolicyShowFormat::Json,
+                    all: true
+                }
+            })
+        ));
+    }
+
+    #[test]
+    fn test_cli_parse_aliases() {
+        let cli =
+            Cli::try_parse_from(["dcg", "aliases", "snapshot", "--shell", "zsh", "-o", "a.sh"])
+                .expect("parse");
+        if let Some(Command::Aliases {
+            action: AliasesAction::Snapshot { shell, output },
+        }) = cli.command
+        {
+            assert_eq!(shell.as_deref(), Some("zsh"));
+            assert_eq!(output, Some(std::path::PathBuf::from("a.sh")));
+        } else {
+            unreachable!("Expected aliases snapshot command");
+        }
+
+        let cli = Cli::try_parse_from(["dcg", "aliases", "list"]).expect("parse");
+        assert!(matches!(
+            cli.command,
+            Some(Command::Aliases {
+                action: AliasesAction::List
+            })
+        ));
+    }
+
+    #[test]
+    fn test_cli_parse_audit_verify() {
+        let cli = Cli::try_parse_from(["dcg", "audit", "verify", "--json"]).expect("parse");
+        assert!(matches!(
+            cli.command,
+            Some(Command::Audit {
+                action: AuditAction::Verify { json: true }
+            })
+        ));
+    }
+
+    #[test]
+    fn test_cli_parse_history_search() {
+        let cli = Cli::try_parse_from([
+            "dcg",
+            "history",
+            "search",
+            "reset hard",
+            "--outcome",
+            "deny",
+            "--pack",
+            "core.git",
+            "--since",
+            "7d",
+            "--cwd",
+            "/work",
+            "--session",
+            "abc-123",
+            "--format",
+            "csv",
+        ])
+        .expect("parse");
+        if let Some(Command::History {
+            action:
+                HistoryAction::Search {
+                    query,
+                    outcome,
+                    pack,
+                    since,
+                    cwd,
+                    session,
+                    limit,
+                    format,
+                },
+        }) = cli.command
+        {
+            assert_eq!(query.as_deref(), Some("reset hard"));
+            assert_eq!(outcome.as_deref(), Some("deny"));
+            assert_eq!(pack.as_deref(), Some("core.git"));
+            assert_eq!(since.as_deref(), Some("7d"));
+            assert_eq!(cwd.as_deref(), Some("/work"));
+            assert_eq!(session.as_deref(), Some("abc-123"));
+            assert_eq!(limit, 50);
+            assert!(matches!(format, HistorySearchFormat::Csv));
+        } else {
+            unreachable!("Expected History search action");
+        }
+    }
+
+    #[test]
+    fn test_parse_history_since_accepts_relative_and_absolute() {
+        let week_ago = parse_history_since("7d").expect("relative");
+        assert!(week_ago < Utc::now() - chrono::Duration::days(6));
+        let date = parse_history_since("2026-01-01").expect("date");
+        assert_eq!(date.to_rfc3339(), "2026-01-01T00:00:00+00:00");
+        assert!(parse_history_since("2026-01-01T12:00:00Z").is_ok());
+        assert!(parse_history_since("yesterday").is_err());
+    }
+
     #[test]
     fn test_cli_parse_explain() {
         let cli = Cli::try_parse_from(["dcg", "explain", "git reset --hard"]).expect("parse");
@@ -12461,7 +17466,7 @@ exclude = ["target/**"]
             ..
         }) = cli.command
         {
-            assert_eq!(command, "git reset --hard");
+            assert_eq!(command.as_deref(), Some("git reset --hard"));
             assert!(explain);
             assert_eq!(format, TestFormat::Pretty); // default format
         } else {
@@ -12477,13 +17482,44 @@ exclude = ["target/**"]
             command, format, ..
         }) = cli.command
         {
-            assert_eq!(command, "rm -rf /tmp");
+            assert_eq!(command.as_deref(), Some("rm -rf /tmp"));
             assert_eq!(format, TestFormat::Json);
         } else {
             unreachable!("Expected TestCommand");
         }
     }
 
+    #[test]
+    fn test_cli_parse_test_stdin_batch() {
+        let cli = Cli::try_parse_from(["dcg", "test", "--stdin-batch"]).expect("parse");
+        if let Some(Command::TestCommand {
+            command,
+            stdin_batch,
+            ..
+        }) = cli.command
+        {
+            assert!(command.is_none());
+            assert!(stdin_batch);
+        } else {
+            unreachable!("Expected TestCommand");
+        }
+
+        // A command is required unless --stdin-batch is given, and not both.
+        assert!(Cli::try_parse_from(["dcg", "test"]).is_err());
+        assert!(Cli::try_parse_from(["dcg", "test", "--stdin-batch", "git status"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_batch_input() {
+        let lines = parse_batch_input("git status\n\nrm -rf /\r\n").expect("lines");
+        assert_eq!(lines, vec!["git status", "rm -rf /"]);
+
+        let array = parse_batch_input(r#" ["git reset --hard", "ls -la"]"#).expect("array");
+        assert_eq!(array, vec!["git reset --hard", "ls -la"]);
+
+        assert!(parse_batch_input("[not json").is_err());
+    }
+
     #[test]
     fn test_cli_parse_test_without_explain_flag() {
         let cli = Cli::try_parse_from(["dcg", "test", "git status"]).expect("parse");
@@ -12494,7 +17530,7 @@ exclude = ["target/**"]
             ..
         }) = cli.command
         {
-            assert_eq!(command, "git status");
+            assert_eq!(command.as_deref(), Some("git status"));
             assert!(!explain);
             assert_eq!(format, TestFormat::Pretty); // default
         } else {
@@ -13092,6 +18128,7 @@ exclude = ["target/**"]
             json: true,
             single_use: false,
             force: false,
+            session: false,
             pick: Some(2),
             hash: None,
         };
@@ -13108,6 +18145,7 @@ exclude = ["target/**"]
             json: true,
             single_use: false,
             force: false,
+            session: false,
             pick: None,
             hash: Some(b.full_hash.clone()),
         };
@@ -13151,6 +18189,7 @@ exclude = ["target/**"]
             json: true,
             single_use: false,
             force: false,
+            session: false,
             pick: Some(3),
             hash: None,
         };
@@ -9,9 +9,9 @@
 //! 5. Compiled defaults (lowest priority)
 
 use crate::interactive::{InteractiveConfig, VerificationMethod};
-use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -59,6 +59,18 @@ pub struct Config {
     /// Custom overrides.
     pub overrides: OverridesConfig,
 
+    /// Filesystem protection (protected paths).
+    pub protection: ProtectionConfig,
+
+    /// Shell alias/function expansion before matching.
+    pub aliases: AliasesConfig,
+
+    /// Installer domains trusted for `curl ... | sh` style downloads.
+    pub trusted_installers: TrustedInstallersConfig,
+
+    /// Wrapper commands (`ssh`, custom wrappers) resolved before evaluation.
+    pub wrappers: WrappersConfig,
+
     /// Heredoc/inline-script scanning configuration.
     pub heredoc: HeredocConfig,
 
@@ -71,12 +83,39 @@ pub struct Config {
     /// Command history configuration.
     pub history: HistoryConfig,
 
+    /// Rate-based anomaly detection on denied commands.
+    pub anomaly: AnomalyConfig,
+
+    /// Per-session risk scoring.
+    pub risk: RiskConfig,
+
+    /// Webhook notifications for denied commands.
+    pub notifications: NotificationsConfig,
+
+    /// Central policy bundle sync (`dcg sync`).
+    pub remote: RemoteConfig,
+
+    /// Persistent cache of clean allow decisions.
+    pub cache: EvalCacheConfig,
+
     /// Interactive prompt configuration.
     pub interactive: InteractiveConfig,
 
     /// Git branch-aware strictness configuration.
     pub git_awareness: GitAwarenessConfig,
 
+    /// Kubernetes context-aware severity.
+    pub kube_context: KubeContextConfig,
+
+    /// Inline `# dcg:allow` suppression comments.
+    pub suppressions: SuppressionsConfig,
+
+    /// Trust ledger of allow-once approvals (`dcg promote`).
+    pub trust: TrustConfig,
+
+    /// Latency thresholds for `dcg bench`.
+    pub bench: BenchConfig,
+
     /// Agent-specific profiles configuration.
     #[serde(default)]
     pub agents: AgentsConfig,
@@ -86,6 +125,82 @@ pub struct Config {
     pub projects: std::collections::HashMap<String, ProjectConfig>,
 }
 
+/// One configuration layer that contributed to the effective config.
+///
+/// Returned by [`Config::load_with_sources`] in merge order (lowest precedence
+/// first) so `dcg config effective` can explain where a setting came from.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigSource {
+    /// Layer name: `system`, `user`, `remote`, `project`, `explicit`, or `env`.
+    pub layer: &'static str,
+    /// File the layer was read from (absent for the env layer).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    /// Top-level sections present in the layer (e.g. `packs`, `policy`).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub sections: Vec<&'static str>,
+    /// Packs this layer adds to `packs.enabled`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub packs_enabled: Vec<String>,
+    /// Packs this layer adds to `packs.disabled`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub packs_disabled: Vec<String>,
+    /// `DCG_*` environment variables that were set (env layer only).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub env_vars: Vec<String>,
+}
+
+impl ConfigSource {
+    fn from_layer(layer: &'static str, path: PathBuf, config: &ConfigLayer) -> Self {
+        let present = [
+            ("general", config.general.is_some()),
+            ("output", config.output.is_some()),
+            ("theme", config.theme.is_some()),
+            ("packs", config.packs.is_some()),
+            ("policy", config.policy.is_some()),
+            ("overrides", config.overrides.is_some()),
+            ("protection", config.protection.is_some()),
+            ("aliases", config.aliases.is_some()),
+            ("trusted_installers", config.trusted_installers.is_some()),
+            ("wrappers", config.wrappers.is_some()),
+            ("heredoc", config.heredoc.is_some()),
+            ("confidence", config.confidence.is_some()),
+            ("logging", config.logging.is_some()),
+            ("history", config.history.is_some()),
+            ("anomaly", config.anomaly.is_some()),
+            ("risk", config.risk.is_some()),
+            ("notifications", config.notifications.is_some()),
+            ("remote", config.remote.is_some()),
+            ("cache", config.cache.is_some()),
+            ("interactive", config.interactive.is_some()),
+            ("git_awareness", config.git_awareness.is_some()),
+            ("kube_context", config.kube_context.is_some()),
+            ("suppressions", config.suppressions.is_some()),
+            ("trust", config.trust.is_some()),
+            ("bench", config.bench.is_some()),
+            ("agents", config.agents.is_some()),
+            ("projects", config.projects.is_some()),
+        ];
+        let (packs_enabled, packs_disabled) = config
+            .packs
+            .as_ref()
+            .map(|packs| (packs.enabled.clone(), packs.disabled.clone()))
+            .unwrap_or_default();
+
+        Self {
+            layer,
+            path: Some(path),
+            sections: present
+                .into_iter()
+                .filter_map(|(name, set)| set.then_some(name))
+                .collect(),
+            packs_enabled,
+            packs_disabled,
+            env_vars: Vec::new(),
+        }
+    }
+}
+
 // -----------------------------------------------------------------------------
 // Config file layering (presence-aware)
 // -----------------------------------------------------------------------------
@@ -110,12 +225,25 @@ struct ConfigLayer {
     packs: Option<PacksConfig>,
     policy: Option<PolicyConfig>,
     overrides: Option<OverridesConfig>,
+    protection: Option<ProtectionConfigLayer>,
+    aliases: Option<AliasesConfigLayer>,
+    trusted_installers: Option<TrustedInstallersConfigLayer>,
+    wrappers: Option<WrappersConfigLayer>,
     heredoc: Option<HeredocConfig>,
     confidence: Option<ConfidenceConfigLayer>,
     logging: Option<LoggingConfigLayer>,
     history: Option<HistoryConfigLayer>,
+    anomaly: Option<AnomalyConfigLayer>,
+    risk: Option<RiskConfigLayer>,
+    notifications: Option<NotificationsConfigLayer>,
+    remote: Option<RemoteConfigLayer>,
+    cache: Option<EvalCacheConfigLayer>,
     interactive: Option<InteractiveConfigLayer>,
     git_awareness: Option<GitAwarenessConfigLayer>,
+    kube_context: Option<KubeContextConfigLayer>,
+    suppressions: Option<SuppressionsConfigLayer>,
+    trust: Option<TrustConfigLayer>,
+    bench: Option<BenchConfigLayer>,
     agents: Option<AgentsConfig>,
     projects: Option<std::collections::HashMap<String, ProjectConfig>>,
 }
@@ -126,17 +254,23 @@ struct GeneralConfigLayer {
     log_file: Option<String>,
     verbose: Option<bool>,
     check_updates: Option<bool>,
+    expand_variables: Option<bool>,
     hook_timeout_ms: Option<u64>,
     max_hook_input_bytes: Option<usize>,
     max_command_bytes: Option<usize>,
     max_findings_per_command: Option<usize>,
+    expired_allowlist_reminder: Option<usize>,
 }
 
-#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
 struct OutputConfigLayer {
     highlight_enabled: Option<bool>,
     explanations_enabled: Option<bool>,
     high_contrast: Option<bool>,
+    structured_deny: Option<bool>,
+    locale: Option<String>,
+    locale_dir: Option<String>,
+    deny_templates: Option<DenyTemplatesConfig>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -153,6 +287,9 @@ struct LoggingConfigLayer {
     format: Option<crate::logging::LogFormat>,
     redaction: Option<RedactionConfigLayer>,
     events: Option<LogEventFilterLayer>,
+    json: Option<JsonSinkConfigLayer>,
+    syslog: Option<SyslogConfigLayer>,
+    otel: Option<OtelConfigLayer>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -162,6 +299,62 @@ struct HistoryConfigLayer {
     retention_days: Option<u32>,
     max_size_mb: Option<u32>,
     database_path: Option<String>,
+    retention: Option<HistoryRetentionConfigLayer>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+struct HistoryRetentionConfigLayer {
+    enabled: Option<bool>,
+    max_rows: Option<u64>,
+    max_age_days: Option<u32>,
+    max_db_size_mb: Option<u64>,
+    compaction_interval_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+struct AnomalyConfigLayer {
+    enabled: Option<bool>,
+    deny_threshold: Option<u32>,
+    window_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+struct RiskConfigLayer {
+    enabled: Option<bool>,
+    threshold: Option<u64>,
+    deny_points: Option<u64>,
+    warn_points: Option<u64>,
+    idle_reset_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct NotificationsConfigLayer {
+    enabled: Option<bool>,
+    min_severity: Option<StrictnessLevel>,
+    webhooks: Option<Vec<WebhookConfig>>,
+    batch_window_ms: Option<u64>,
+    timeout_ms: Option<u64>,
+    failure_threshold: Option<u32>,
+    cooldown_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct RemoteConfigLayer {
+    enabled: Option<bool>,
+    url: Option<String>,
+    public_key: Option<String>,
+    signature_url: Option<String>,
+    interval_secs: Option<u64>,
+    timeout_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct EvalCacheConfigLayer {
+    enabled: Option<bool>,
+    max_entries: Option<usize>,
+    ttl_secs: Option<u64>,
+    quick_allow: Option<bool>,
+    path: Option<String>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -190,6 +383,39 @@ struct LogEventFilterLayer {
     allow: Option<bool>,
 }
 
+#[derive(Debug, Clone, Default, Deserialize)]
+struct SyslogConfigLayer {
+    enabled: Option<bool>,
+    target: Option<crate::logging::SyslogTarget>,
+    transport: Option<crate::logging::SyslogTransport>,
+    address: Option<String>,
+    facility: Option<String>,
+    app_name: Option<String>,
+    deny: Option<bool>,
+    warn: Option<bool>,
+    severities: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct OtelConfigLayer {
+    enabled: Option<bool>,
+    endpoint: Option<String>,
+    service_name: Option<String>,
+    timeout_ms: Option<u64>,
+    traces: Option<bool>,
+    metrics: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct JsonSinkConfigLayer {
+    enabled: Option<bool>,
+    path: Option<String>,
+    max_size_mb: Option<u64>,
+    max_age_hours: Option<u64>,
+    max_files: Option<usize>,
+    compress: Option<bool>,
+}
+
 #[derive(Debug, Clone, Copy, Default, Deserialize)]
 struct ConfidenceConfigLayer {
     enabled: Option<bool>,
@@ -209,6 +435,67 @@ struct GitAwarenessConfigLayer {
     warn_if_not_git: Option<bool>,
 }
 
+#[derive(Debug, Clone, Default, Deserialize)]
+struct KubeContextConfigLayer {
+    enabled: Option<bool>,
+    production_contexts: Option<Vec<String>>,
+    development_contexts: Option<Vec<String>>,
+    cache_ttl_secs: Option<u64>,
+    timeout_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+struct SuppressionsConfigLayer {
+    inline: Option<bool>,
+    require_confirmation: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+struct TrustConfigLayer {
+    ledger: Option<bool>,
+    promote_after: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+struct BenchConfigLayer {
+    cold_start_p99_ms: Option<u64>,
+    quick_reject_p99_us: Option<u64>,
+    pack_eval_p99_us: Option<u64>,
+    heredoc_p99_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct ProtectionConfigLayer {
+    paths: Option<ProtectedPathsConfig>,
+    workspaces: Option<ProtectedWorkspacesConfig>,
+    rm_preview: Option<RmPreviewConfigLayer>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+struct RmPreviewConfigLayer {
+    enabled: Option<bool>,
+    max_entries: Option<usize>,
+    timeout_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct AliasesConfigLayer {
+    enabled: Option<bool>,
+    files: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct TrustedInstallersConfigLayer {
+    domains: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct WrappersConfigLayer {
+    ssh: Option<bool>,
+    extra: Option<Vec<String>>,
+    production_hosts: Option<Vec<String>>,
+}
+
 fn expand_tilde_path(value: &str) -> (PathBuf, bool) {
     if value == "~" {
         if let Some(home) = dirs::home_dir() {
@@ -299,6 +586,9 @@ pub struct HeredocConfig {
     /// - typescript: typescript, ts
     /// - php: php
     /// - go: go, golang
+    /// - sql: sql
+    /// - hcl: hcl, terraform, tf
+    /// - yaml: yaml, yml
     /// - unknown: unknown
     ///
     /// Special value "all" scans all languages (the default if omitted).
@@ -520,6 +810,9 @@ impl HeredocConfig {
                     "typescript" | "ts" => Some(crate::heredoc::ScriptLanguage::TypeScript),
                     "php" => Some(crate::heredoc::ScriptLanguage::Php),
                     "go" | "golang" => Some(crate::heredoc::ScriptLanguage::Go),
+                    "sql" => Some(crate::heredoc::ScriptLanguage::Sql),
+                    "hcl" | "terraform" | "tf" => Some(crate::heredoc::ScriptLanguage::Hcl),
+                    "yaml" | "yml" => Some(crate::heredoc::ScriptLanguage::Yaml),
                     "unknown" => Some(crate::heredoc::ScriptLanguage::Unknown),
                     _ => None,
                 };
@@ -726,7 +1019,7 @@ fn pattern_matches(
 /// An empty or whitespace-only filter matches all languages (same as `language: None`).
 fn language_filter_matches(filter: &str, language: crate::heredoc::ScriptLanguage) -> bool {
     use crate::heredoc::ScriptLanguage::{
-        Bash, Go, JavaScript, Perl, Php, Python, Ruby, TypeScript, Unknown,
+        Bash, Go, Hcl, JavaScript, Perl, Php, Python, Ruby, Sql, TypeScript, Unknown, Yaml,
     };
     let filter_lower = filter.trim().to_ascii_lowercase();
 
@@ -744,6 +1037,9 @@ fn language_filter_matches(filter: &str, language: crate::heredoc::ScriptLanguag
         TypeScript => matches!(filter_lower.as_str(), "typescript" | "ts"),
         Php => matches!(filter_lower.as_str(), "php"),
         Go => matches!(filter_lower.as_str(), "go" | "golang"),
+        Sql => filter_lower == "sql",
+        Hcl => matches!(filter_lower.as_str(), "hcl" | "terraform" | "tf"),
+        Yaml => matches!(filter_lower.as_str(), "yaml" | "yml"),
         Unknown => filter_lower == "unknown",
     }
 }
@@ -806,12 +1102,23 @@ pub struct GeneralConfig {
     /// Default: true. Disable with `DCG_NO_UPDATE_CHECK` (any non-empty value)
     /// or `check_updates` = false.
     pub check_updates: bool,
+
+    /// Resolve `$VAR` / `${VAR}` references from the hook's environment (and
+    /// assignments earlier in the command) before pattern matching.
+    /// Default: true.
+    pub expand_variables: bool,
+
+    /// Denial output reminds you to run `dcg allowlist prune` once more than
+    /// this many allowlist entries have expired.
+    /// Default: 0 (remind on any expired entry).
+    pub expired_allowlist_reminder: Option<usize>,
 }
 
 /// Default limits for input size (used when not configured).
 pub const DEFAULT_MAX_HOOK_INPUT_BYTES: usize = 256 * 1024; // 256 KiB
 pub const DEFAULT_MAX_COMMAND_BYTES: usize = 64 * 1024; // 64 KiB
 pub const DEFAULT_MAX_FINDINGS_PER_COMMAND: usize = 100;
+pub const DEFAULT_EXPIRED_ALLOWLIST_REMINDER: usize = 0;
 
 impl Default for GeneralConfig {
     fn default() -> Self {
@@ -824,6 +1131,8 @@ impl Default for GeneralConfig {
             max_command_bytes: None,
             max_findings_per_command: None,
             check_updates: true,
+            expand_variables: true,
+            expired_allowlist_reminder: None,
         }
     }
 }
@@ -848,6 +1157,13 @@ impl GeneralConfig {
         self.max_findings_per_command
             .unwrap_or(DEFAULT_MAX_FINDINGS_PER_COMMAND)
     }
+
+    /// Get the expired-allowlist reminder threshold (with default fallback).
+    #[must_use]
+    pub fn expired_allowlist_reminder(&self) -> usize {
+        self.expired_allowlist_reminder
+            .unwrap_or(DEFAULT_EXPIRED_ALLOWLIST_REMINDER)
+    }
 }
 
 /// Output display configuration.
@@ -870,6 +1186,150 @@ pub struct OutputConfig {
     /// Uses ASCII borders and a black/white palette for accessibility.
     /// Default: false
     pub high_contrast: Option<bool>,
+
+    /// Add `schemaVersion` and a structured `details` object (pack, pattern,
+    /// severity, span, suggestion) to the hook's deny JSON.
+    /// Default: false
+    pub structured_deny: Option<bool>,
+
+    /// Locale for denial text and CLI messages (e.g. "de", "pt-BR").
+    /// Default: `DCG_LOCALE`, then `LC_ALL` / `LC_MESSAGES` / `LANG`, then "en"
+    pub locale: Option<String>,
+
+    /// Extra directory searched for `<locale>.ftl` message catalogs, ahead of
+    /// `~/.config/dcg/locales`.
+    pub locale_dir: Option<String>,
+
+    /// Custom deny messages for agents, per pack or severity (`[output.deny_templates]`).
+    #[serde(skip_serializing_if = "DenyTemplatesConfig::is_empty")]
+    pub deny_templates: DenyTemplatesConfig,
+}
+
+/// Deny message templates (`[output.deny_templates]`).
+///
+/// A template replaces the text the agent receives for a denial (the
+/// `permissionDecisionReason` and its equivalents). The most specific one
+/// applies: exact pack id, then pack category (`"database"` for
+/// `database.postgresql`), then severity, then `default`. Placeholders are
+/// listed in [`DENY_TEMPLATE_PLACEHOLDERS`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DenyTemplatesConfig {
+    pub default: Option<String>,
+    pub critical: Option<String>,
+    pub high: Option<String>,
+    pub medium: Option<String>,
+    pub low: Option<String>,
+    /// Templates keyed by pack id or category.
+    #[serde(skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub packs: std::collections::BTreeMap<String, String>,
+}
+
+/// Placeholders a deny template may use; `{{` and `}}` are literal braces.
+pub const DENY_TEMPLATE_PLACEHOLDERS: &[&str] = &[
+    "reason",
+    "command",
+    "pack",
+    "pattern",
+    "rule",
+    "severity",
+    "explanation",
+    "suggestion",
+    "allow_once_code",
+];
+
+impl DenyTemplatesConfig {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Template for a denial by `pack` at `severity`, if one is configured.
+    #[must_use]
+    pub fn template_for(
+        &self,
+        pack: Option<&str>,
+        severity: Option<crate::packs::Severity>,
+    ) -> Option<&str> {
+        let by_pack = pack.and_then(|pack| {
+            self.packs.get(pack).or_else(|| {
+                pack.split_once('.')
+                    .and_then(|(category, _)| self.packs.get(category))
+            })
+        });
+        let by_severity = severity.and_then(|severity| match severity {
+            crate::packs::Severity::Critical => self.critical.as_ref(),
+            crate::packs::Severity::High => self.high.as_ref(),
+            crate::packs::Severity::Medium => self.medium.as_ref(),
+            crate::packs::Severity::Low => self.low.as_ref(),
+        });
+        by_pack
+            .or(by_severity)
+            .or(self.default.as_ref())
+            .map(String::as_str)
+    }
+
+    /// Placeholders used in any template that dcg does not know.
+    #[must_use]
+    pub fn unknown_placeholders(&self) -> Vec<String> {
+        let mut unknown: Vec<String> = [
+            &self.default,
+            &self.critical,
+            &self.high,
+            &self.medium,
+            &self.low,
+        ]
+        .into_iter()
+        .flatten()
+        .chain(self.packs.values())
+        .flat_map(|template| template_placeholders(template))
+        .filter(|name| !DENY_TEMPLATE_PLACEHOLDERS.contains(&name.as_str()))
+        .collect();
+        unknown.sort();
+        unknown.dedup();
+        unknown
+    }
+
+    /// Later layers replace individual templates.
+    fn merge(&mut self, other: Self) {
+        for (slot, value) in [
+            (&mut self.default, other.default),
+            (&mut self.critical, other.critical),
+            (&mut self.high, other.high),
+            (&mut self.medium, other.medium),
+            (&mut self.low, other.low),
+        ] {
+            if value.is_some() {
+                *slot = value;
+            }
+        }
+        self.packs.extend(other.packs);
+    }
+}
+
+/// Names inside single braces (`{reason}`), skipping `{{` escapes and text
+/// that is not a name (`{ "json": 1 }`).
+fn template_placeholders(template: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        let after = &rest[open + 1..];
+        if let Some(escaped) = after.strip_prefix('{') {
+            rest = escaped;
+            continue;
+        }
+        let Some(close) = after.find('}') else {
+            break;
+        };
+        let name = &after[..close];
+        if !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            names.push(name.to_string());
+            rest = &after[close + 1..];
+        } else {
+            rest = after;
+        }
+    }
+    names
 }
 
 impl OutputConfig {
@@ -890,6 +1350,21 @@ impl OutputConfig {
     pub fn high_contrast_enabled(&self) -> bool {
         self.high_contrast.unwrap_or(false)
     }
+
+    /// Check if structured deny output is enabled (default: false).
+    #[must_use]
+    pub fn structured_deny_enabled(&self) -> bool {
+        self.structured_deny.unwrap_or(false)
+    }
+
+    /// Catalog directory from `locale_dir`, with `~` expanded.
+    #[must_use]
+    pub fn locale_dir(&self) -> Option<PathBuf> {
+        self.locale_dir
+            .as_deref()
+            .filter(|dir| !dir.trim().is_empty())
+            .map(|dir| expand_tilde_path(dir).0)
+    }
 }
 
 /// Theme configuration for rich terminal output.
@@ -1038,63 +1513,669 @@ pub struct PolicyConfig {
     /// Takes precedence over pack-level and global overrides.
     #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
     pub rules: std::collections::HashMap<String, PolicyMode>,
+
+    /// Per-pack and per-rule severity remapping.
+    ///
+    /// Key is a `pack_id` or `rule_id`; value is `critical`, `high`, `medium`,
+    /// or `low`. The remapped severity is what windows and severity-based
+    /// defaults see. Rule keys win over pack keys, and (as with `packs`) only
+    /// a rule key can lower a Critical rule.
+    ///
+    /// Example in TOML:
+    /// ```toml
+    /// [policy.severity_overrides]
+    /// "containers.docker:image-prune" = "low"
+    /// "kubernetes.helm" = "medium"
+    /// ```
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub severity_overrides: std::collections::HashMap<String, String>,
+
+    /// Time-of-day escalation windows (first matching window wins).
+    ///
+    /// Example in TOML (deny High during business hours, warn otherwise):
+    /// ```toml
+    /// [[policy.windows]]
+    /// severities = ["high"]
+    /// hours = "09:00-18:00"
+    /// days = ["mon", "tue", "wed", "thu", "fri"]
+    /// mode = "deny"
+    /// otherwise = "warn"
+    /// ```
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub windows: Vec<PolicyWindow>,
+
+    /// Deployment freeze windows.
+    ///
+    /// While a freeze is active, every matched rule is escalated to the freeze
+    /// mode (default: deny). Freezes only ever make decisions stricter.
+    ///
+    /// Example in TOML:
+    /// ```toml
+    /// [[policy.freezes]]
+    /// name = "year-end freeze"
+    /// start = "2026-12-20"
+    /// end = "2027-01-05"
+    /// ```
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub freezes: Vec<PolicyFreeze>,
+
+    /// What happens when dcg cannot finish evaluating a command.
+    ///
+    /// Unset means fail open everywhere. A single mode applies to every
+    /// failure class except `telemetry`; a table sets classes individually.
+    ///
+    /// Example in TOML:
+    /// ```toml
+    /// [policy]
+    /// fail_mode = "closed"
+    ///
+    /// # or, per failure class:
+    /// [policy.fail_mode]
+    /// default = "closed"
+    /// parse_error = "open"
+    /// ```
+    #[serde(default, skip_serializing_if = "FailModeConfig::is_empty")]
+    pub fail_mode: FailModeConfig,
+
+    /// External policy endpoint consulted for matches at selected severities.
+    ///
+    /// Example in TOML:
+    /// ```toml
+    /// [policy.external]
+    /// enabled = true
+    /// url = "https://opa.internal:8181/v1/data/dcg/decision"
+    /// severities = ["medium", "high"]
+    /// timeout_ms = 250
+    /// fail_mode = "closed"
+    /// ```
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external: Option<ExternalPolicyConfig>,
+
+    /// Rego policies that override mode resolution (needs the `rego` feature).
+    ///
+    /// Example in TOML:
+    /// ```toml
+    /// [policy.rego]
+    /// enabled = true
+    /// paths = ["~/.config/dcg/policy.rego"]
+    /// query = "data.dcg.decision"
+    /// ```
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rego: Option<RegoPolicyConfig>,
 }
 
-/// Policy mode for overriding default decision behavior.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+/// Whether a failure to evaluate allows (open) or blocks (closed) the command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
-pub enum PolicyMode {
-    /// Block the command (output JSON deny, print warning).
-    Deny,
-    /// Warn but allow (print warning to stderr, no JSON deny).
-    Warn,
-    /// Log only (silent allow, record for history).
-    Log,
+pub enum FailMode {
+    /// Allow the command and warn (the historical behavior).
+    #[default]
+    Open,
+    /// Deny the command with a reason naming the failure.
+    Closed,
 }
 
-impl PolicyMode {
-    /// Convert to the internal `DecisionMode`.
+/// Kinds of evaluation failure that `[policy.fail_mode]` configures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureClass {
+    /// The hook deadline ran out before or during evaluation, including
+    /// heredoc extraction timeouts.
+    Timeout,
+    /// Hook input JSON or heredoc content could not be parsed.
+    ParseError,
+    /// Hook input could not be read from stdin.
+    Io,
+    /// Hook input or the command exceeded its size limit.
+    SizeLimit,
+    /// The history database could not be opened.
+    Telemetry,
+}
+
+impl FailureClass {
+    /// Config key for this class in `[policy.fail_mode]`.
     #[must_use]
-    pub const fn to_decision_mode(self) -> crate::packs::DecisionMode {
+    pub const fn label(self) -> &'static str {
         match self {
-            Self::Deny => crate::packs::DecisionMode::Deny,
-            Self::Warn => crate::packs::DecisionMode::Warn,
-            Self::Log => crate::packs::DecisionMode::Log,
+            Self::Timeout => "timeout",
+            Self::ParseError => "parse_error",
+            Self::Io => "io",
+            Self::SizeLimit => "size_limit",
+            Self::Telemetry => "telemetry",
         }
     }
 }
 
-impl PolicyConfig {
-    /// Resolve the effective decision mode for a given rule.
-    ///
-    /// Priority (highest to lowest):
-    /// 1. Rule-specific override (via `rules["pack_id:pattern_name"]`)
-    /// 2. Pack-specific override (via `packs["pack_id"]`)
-    /// 3. Global default (`default_mode`)
-    /// 4. Severity-based default (from pattern's severity)
+/// Per-failure-class fail modes (`[policy.fail_mode]`).
+///
+/// Accepts either a bare mode (`fail_mode = "closed"`), which sets `default`,
+/// or a table of classes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "FailModeRepr")]
+pub struct FailModeConfig {
+    /// Mode for every class not set below (except `telemetry`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<FailMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<FailMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parse_error: Option<FailMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub io: Option<FailMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size_limit: Option<FailMode>,
+    /// Only closed when set explicitly: a history database hiccup should not
+    /// block every command just because `default` is closed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub telemetry: Option<FailMode>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum FailModeRepr {
+    Mode(FailMode),
+    Table {
+        #[serde(default)]
+        default: Option<FailMode>,
+        #[serde(default)]
+        timeout: Option<FailMode>,
+        #[serde(default)]
+        parse_error: Option<FailMode>,
+        #[serde(default)]
+        io: Option<FailMode>,
+        #[serde(default)]
+        size_limit: Option<FailMode>,
+        #[serde(default)]
+        telemetry: Option<FailMode>,
+    },
+}
+
+impl From<FailModeRepr> for FailModeConfig {
+    fn from(repr: FailModeRepr) -> Self {
+        match repr {
+            FailModeRepr::Mode(mode) => Self {
+                default: Some(mode),
+                ..Self::default()
+            },
+            FailModeRepr::Table {
+                default,
+                timeout,
+                parse_error,
+                io,
+                size_limit,
+                telemetry,
+            } => Self {
+                default,
+                timeout,
+                parse_error,
+                io,
+                size_limit,
+                telemetry,
+            },
+        }
+    }
+}
+
+impl FailModeConfig {
+    /// Effective mode for a failure class.
     #[must_use]
-    pub fn resolve_mode(
-        &self,
-        pack_id: Option<&str>,
-        pattern_name: Option<&str>,
-        severity: Option<crate::packs::Severity>,
-    ) -> crate::packs::DecisionMode {
-        self.resolve_mode_at(Utc::now(), pack_id, pattern_name, severity)
+    pub fn mode_for(&self, class: FailureClass) -> FailMode {
+        let explicit = match class {
+            FailureClass::Timeout => self.timeout,
+            FailureClass::ParseError => self.parse_error,
+            FailureClass::Io => self.io,
+            FailureClass::SizeLimit => self.size_limit,
+            FailureClass::Telemetry => return self.telemetry.unwrap_or_default(),
+        };
+        explicit.or(self.default).unwrap_or_default()
     }
 
+    /// True if a failure of this class should deny the command.
     #[must_use]
-    pub fn resolve_mode_at(
+    pub fn is_closed(&self, class: FailureClass) -> bool {
+        self.mode_for(class) == FailMode::Closed
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.default.is_none()
+            && self.timeout.is_none()
+            && self.parse_error.is_none()
+            && self.io.is_none()
+            && self.size_limit.is_none()
+            && self.telemetry.is_none()
+    }
+
+    /// Overlay the classes set in `other` (a higher-priority layer).
+    fn merge(&mut self, other: Self) {
+        self.default = other.default.or(self.default);
+        self.timeout = other.timeout.or(self.timeout);
+        self.parse_error = other.parse_error.or(self.parse_error);
+        self.io = other.io.or(self.io);
+        self.size_limit = other.size_limit.or(self.size_limit);
+        self.telemetry = other.telemetry.or(self.telemetry);
+    }
+}
+
+/// A recurring time-of-day policy window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyWindow {
+    /// Optional label (shown in diagnostics).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// Severities this window applies to (`critical`, `high`, `medium`, `low`).
+    /// Empty means all severities.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub severities: Vec<String>,
+
+    /// Time-of-day range `HH:MM-HH:MM` (may wrap midnight). Unset means all day.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hours: Option<String>,
+
+    /// Days of the week (`mon`..`sun`). Empty means every day.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub days: Vec<String>,
+
+    /// Mode while inside the window.
+    pub mode: PolicyMode,
+
+    /// Mode while outside the window. When unset, resolution falls through.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub otherwise: Option<PolicyMode>,
+
+    /// Clock used for `hours`/`days`: `"local"` (default) or `"utc"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+}
+
+impl PolicyWindow {
+    /// Resolve this window's mode at `now` for a rule of the given severity.
+    ///
+    /// Returns `None` when the window does not apply (severity mismatch,
+    /// outside the window with no `otherwise`, or unparseable `hours`).
+    #[must_use]
+    pub fn mode_at(
         &self,
         now: DateTime<Utc>,
-        pack_id: Option<&str>,
-        pattern_name: Option<&str>,
         severity: Option<crate::packs::Severity>,
-    ) -> crate::packs::DecisionMode {
-        // 1. Rule-specific override
-        if let (Some(pack), Some(pattern)) = (pack_id, pattern_name) {
-            let rule_id = format!("{pack}:{pattern}");
-            if let Some(mode) = self.rules.get(&rule_id) {
-                return mode.to_decision_mode();
+    ) -> Option<PolicyMode> {
+        if !self.severities.is_empty() {
+            let label = severity?.label();
+            if !self
+                .severities
+                .iter()
+                .any(|s| s.trim().eq_ignore_ascii_case(label))
+            {
+                return None;
+            }
+        }
+
+        let local = if self
+            .timezone
+            .as_deref()
+            .is_some_and(|tz| tz.trim().eq_ignore_ascii_case("utc"))
+        {
+            now.naive_utc()
+        } else {
+            now.with_timezone(&chrono::Local).naive_local()
+        };
+
+        let in_days = self.days.is_empty()
+            || self.days.iter().any(|d| {
+                chrono::Weekday::from_str(d.trim()).is_ok_and(|day| day == local.weekday())
+            });
+        let in_hours = match self.hours.as_deref() {
+            None => true,
+            Some(range) => {
+                let (start, end) = parse_hours_range(range)?;
+                let time = local.time();
+                if start <= end {
+                    start <= time && time < end
+                } else {
+                    time >= start || time < end
+                }
+            }
+        };
+
+        if in_days && in_hours {
+            Some(self.mode)
+        } else {
+            self.otherwise
+        }
+    }
+}
+
+/// A one-off deployment freeze window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyFreeze {
+    /// Optional label (shown in diagnostics).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// Freeze start (RFC 3339, ISO 8601 as UTC, or `YYYY-MM-DD` = start of day UTC).
+    pub start: String,
+
+    /// Freeze end (RFC 3339, ISO 8601 as UTC, or `YYYY-MM-DD` = end of day UTC).
+    pub end: String,
+
+    /// Mode enforced during the freeze (default: deny).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<PolicyMode>,
+}
+
+impl PolicyFreeze {
+    /// Returns true if `now` falls inside this freeze.
+    ///
+    /// Freezes with unparseable bounds are treated as inactive.
+    #[must_use]
+    pub fn is_active_at(&self, now: DateTime<Utc>) -> bool {
+        let start = NaiveDate::parse_from_str(self.start.trim(), "%Y-%m-%d")
+            .ok()
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .map(|dt| dt.and_utc())
+            .or_else(|| parse_timestamp_as_utc(&self.start));
+        let end = parse_timestamp_as_utc(&self.end);
+        matches!((start, end), (Some(start), Some(end)) if start <= now && now <= end)
+    }
+}
+
+/// External policy endpoint (`[policy.external]`).
+///
+/// When a pack rule matches at one of `severities`, the hook POSTs the
+/// command, the matched rule, and dcg's own decision to `url` and applies the
+/// decision in the response (`allow`, `warn`, `deny`, or `defer` to keep
+/// dcg's). The request is abandoned after `timeout_ms`; an unreachable or
+/// malformed endpoint allows the command with a warning (`fail_mode = "open"`)
+/// or denies it (`"closed"`). Explicit `[overrides] block` entries are never
+/// sent. Project configs cannot set this section.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExternalPolicyConfig {
+    /// Consult the endpoint.
+    pub enabled: bool,
+    /// `http://` or `https://` URL the query is POSTed to.
+    pub url: String,
+    /// Severities sent to the endpoint (`critical`, `high`, `medium`, `low`).
+    pub severities: Vec<String>,
+    /// Hard limit for the whole request, in milliseconds.
+    pub timeout_ms: u64,
+    /// Decision when the endpoint fails or times out.
+    pub fail_mode: FailMode,
+    /// Environment variable holding a bearer token for the endpoint.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_env: Option<String>,
+}
+
+impl ExternalPolicyConfig {
+    /// Default request timeout (milliseconds).
+    pub const DEFAULT_TIMEOUT_MS: u64 = 250;
+
+    /// Whether the endpoint should be consulted at all.
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        self.enabled && !self.url.trim().is_empty()
+    }
+
+    /// Whether a match of this severity is sent to the endpoint.
+    #[must_use]
+    pub fn applies_to(&self, severity: Option<crate::packs::Severity>) -> bool {
+        let label = severity.unwrap_or(crate::packs::Severity::High).label();
+        self.severities
+            .iter()
+            .any(|s| s.trim().eq_ignore_ascii_case(label))
+    }
+}
+
+impl Default for ExternalPolicyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: String::new(),
+            severities: vec!["medium".to_string(), "high".to_string()],
+            timeout_ms: Self::DEFAULT_TIMEOUT_MS,
+            fail_mode: FailMode::Open,
+            token_env: None,
+        }
+    }
+}
+
+/// Embedded Rego policy (`[policy.rego]`).
+///
+/// For every pack match, `query` is evaluated against the loaded policy files
+/// with the match as `input` (command, cwd, pack, pattern, severity, dcg's
+/// mode, environment). A defined result (`"deny"`, `"warn"`, `"log"`,
+/// `"allow"`, or `{decision, reason}`) replaces the built-in mode; an
+/// undefined result keeps it. With no `paths`, dcg loads `policy.rego` from
+/// the user config directory and `.dcg/policy.rego` from the repository root.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RegoPolicyConfig {
+    /// Evaluate the policies.
+    pub enabled: bool,
+    /// Policy files (`~` expands; relative paths resolve against the cwd).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<String>,
+    /// Rule evaluated for each match.
+    pub query: String,
+}
+
+impl RegoPolicyConfig {
+    /// Default rule queried for a decision.
+    pub const DEFAULT_QUERY: &'static str = "data.dcg.decision";
+    /// File name looked up when `paths` is empty.
+    pub const DEFAULT_FILE_NAME: &'static str = "policy.rego";
+
+    /// Existing policy files to load for a hook running in `cwd`.
+    #[must_use]
+    pub fn policy_files(&self, cwd: Option<&Path>) -> Vec<PathBuf> {
+        let candidates: Vec<PathBuf> = if self.paths.is_empty() {
+            let user = Config::user_config_path()
+                .and_then(|path| path.parent().map(|dir| dir.join(Self::DEFAULT_FILE_NAME)));
+            let project = cwd
+                .and_then(|dir| find_repo_root(dir, REPO_ROOT_SEARCH_MAX_HOPS))
+                .map(|root| root.join(".dcg").join(Self::DEFAULT_FILE_NAME));
+            user.into_iter().chain(project).collect()
+        } else {
+            self.paths
+                .iter()
+                .filter_map(|path| resolve_config_path_value(path, cwd))
+                .collect()
+        };
+        candidates
+            .into_iter()
+            .filter(|path| path.is_file())
+            .collect()
+    }
+}
+
+impl Default for RegoPolicyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            paths: Vec::new(),
+            query: Self::DEFAULT_QUERY.to_string(),
+        }
+    }
+}
+
+/// Parse an `HH:MM-HH:MM` range.
+fn parse_hours_range(range: &str) -> Option<(chrono::NaiveTime, chrono::NaiveTime)> {
+    let (start, end) = range.split_once('-')?;
+    let start = chrono::NaiveTime::parse_from_str(start.trim(), "%H:%M").ok()?;
+    let end = chrono::NaiveTime::parse_from_str(end.trim(), "%H:%M").ok()?;
+    Some((start, end))
+}
+
+/// Ordering used when a freeze escalates a mode: Log < Warn < Deny.
+const fn mode_strictness(mode: crate::packs::DecisionMode) -> u8 {
+    match mode {
+        crate::packs::DecisionMode::Log => 0,
+        crate::packs::DecisionMode::Warn => 1,
+        crate::packs::DecisionMode::Deny => 2,
+    }
+}
+
+/// Policy mode for overriding default decision behavior.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PolicyMode {
+    /// Block the command (output JSON deny, print warning).
+    Deny,
+    /// Warn but allow (print warning to stderr, no JSON deny).
+    Warn,
+    /// Log only (silent allow, record for history).
+    Log,
+}
+
+impl PolicyMode {
+    /// Convert to the internal `DecisionMode`.
+    #[must_use]
+    pub const fn to_decision_mode(self) -> crate::packs::DecisionMode {
+        match self {
+            Self::Deny => crate::packs::DecisionMode::Deny,
+            Self::Warn => crate::packs::DecisionMode::Warn,
+            Self::Log => crate::packs::DecisionMode::Log,
+        }
+    }
+}
+
+impl PolicyConfig {
+    /// Resolve the effective decision mode for a given rule.
+    ///
+    /// The pattern's severity is first remapped through `severity_overrides`.
+    ///
+    /// Priority (highest to lowest):
+    /// 1. Rule-specific override (via `rules["pack_id:pattern_name"]`)
+    /// 2. Pack-specific override (via `packs["pack_id"]`)
+    /// 3. Time-of-day windows (`windows`)
+    /// 4. Global default (`default_mode`)
+    /// 5. Severity-based default (from pattern's severity)
+    ///
+    /// An active deployment freeze (`freezes`) then escalates the result.
+    #[must_use]
+    pub fn resolve_mode(
+        &self,
+        pack_id: Option<&str>,
+        pattern_name: Option<&str>,
+        severity: Option<crate::packs::Severity>,
+    ) -> crate::packs::DecisionMode {
+        self.resolve_mode_at(Utc::now(), pack_id, pattern_name, severity)
+    }
+
+    #[must_use]
+    pub fn resolve_mode_at(
+        &self,
+        now: DateTime<Utc>,
+        pack_id: Option<&str>,
+        pattern_name: Option<&str>,
+        severity: Option<crate::packs::Severity>,
+    ) -> crate::packs::DecisionMode {
+        let severity = self.effective_severity(pack_id, pattern_name, severity);
+        let mode = self.resolve_base_mode_at(now, pack_id, pattern_name, severity);
+
+        // Deployment freezes only ever escalate.
+        self.freezes
+            .iter()
+            .filter(|freeze| freeze.is_active_at(now))
+            .map(|freeze| freeze.mode.unwrap_or(PolicyMode::Deny).to_decision_mode())
+            .fold(mode, |acc, frozen| {
+                if mode_strictness(frozen) > mode_strictness(acc) {
+                    frozen
+                } else {
+                    acc
+                }
+            })
+    }
+
+    /// Apply `severity_overrides` to a rule's severity.
+    ///
+    /// A `rule_id` key wins over a `pack_id` key. Pack keys leave Critical
+    /// rules alone; values that are not a severity label are ignored (see
+    /// [`Self::validate_severity_overrides`]).
+    #[must_use]
+    pub fn effective_severity(
+        &self,
+        pack_id: Option<&str>,
+        pattern_name: Option<&str>,
+        severity: Option<crate::packs::Severity>,
+    ) -> Option<crate::packs::Severity> {
+        let lookup = |key: &str| {
+            self.severity_overrides
+                .get(key)
+                .and_then(|value| crate::packs::Severity::from_label(value))
+        };
+
+        let rule_override = pack_id
+            .zip(pattern_name)
+            .and_then(|(pack, pattern)| lookup(&format!("{pack}:{pattern}")));
+        if rule_override.is_some() {
+            return rule_override;
+        }
+        if matches!(severity, Some(crate::packs::Severity::Critical)) {
+            return severity;
+        }
+        pack_id.and_then(lookup).or(severity)
+    }
+
+    /// Check `severity_overrides` against the built-in pack registry.
+    ///
+    /// Returns one message per entry with an unknown severity, pack, or
+    /// pattern, sorted by key.
+    #[must_use]
+    pub fn validate_severity_overrides(&self) -> Vec<String> {
+        let mut keys: Vec<&String> = self.severity_overrides.keys().collect();
+        keys.sort();
+
+        let mut issues = Vec::new();
+        for key in keys {
+            let value = &self.severity_overrides[key];
+            if crate::packs::Severity::from_label(value).is_none() {
+                issues.push(format!(
+                    "{key}: unknown severity \"{value}\" (expected critical, high, medium, or low)"
+                ));
+            }
+
+            let (pack_id, pattern_name) = match key.split_once(':') {
+                Some((pack, pattern)) => (pack, Some(pattern)),
+                None => (key.as_str(), None),
+            };
+            let Some(pack) = crate::packs::REGISTRY.get(pack_id) else {
+                issues.push(format!("{key}: unknown pack \"{pack_id}\""));
+                continue;
+            };
+            let unknown_pattern = pattern_name.filter(|name| {
+                !pack
+                    .destructive_patterns
+                    .iter()
+                    .any(|p| p.name == Some(*name))
+            });
+            if let Some(pattern_name) = unknown_pattern {
+                issues.push(format!(
+                    "{key}: pack \"{pack_id}\" has no destructive pattern \"{pattern_name}\""
+                ));
+            }
+        }
+        issues
+    }
+
+    /// Returns the first freeze active at `now`, if any.
+    #[must_use]
+    pub fn active_freeze_at(&self, now: DateTime<Utc>) -> Option<&PolicyFreeze> {
+        self.freezes.iter().find(|freeze| freeze.is_active_at(now))
+    }
+
+    fn resolve_base_mode_at(
+        &self,
+        now: DateTime<Utc>,
+        pack_id: Option<&str>,
+        pattern_name: Option<&str>,
+        severity: Option<crate::packs::Severity>,
+    ) -> crate::packs::DecisionMode {
+        // 1. Rule-specific override
+        if let (Some(pack), Some(pattern)) = (pack_id, pattern_name) {
+            let rule_id = format!("{pack}:{pattern}");
+            if let Some(mode) = self.rules.get(&rule_id) {
+                return mode.to_decision_mode();
             }
         }
 
@@ -1111,7 +2192,16 @@ impl PolicyConfig {
             }
         }
 
-        // 3. Global default (optionally gated by observe_until)
+        // 3. Time-of-day windows
+        if let Some(mode) = self
+            .windows
+            .iter()
+            .find_map(|window| window.mode_at(now, severity))
+        {
+            return mode.to_decision_mode();
+        }
+
+        // 4. Global default (optionally gated by observe_until)
         let effective_default_mode = self
             .observe_until
             .as_ref()
@@ -1128,7 +2218,7 @@ impl PolicyConfig {
             return mode.to_decision_mode();
         }
 
-        // 4. Severity-based default
+        // 5. Severity-based default
         severity.map_or(crate::packs::DecisionMode::Deny, |s| s.default_mode())
     }
 }
@@ -1167,57 +2257,259 @@ pub struct OverridesConfig {
     pub allowlist_rules: Option<Vec<AllowlistRule>>,
 }
 
-/// An extended allowlist rule with optional path conditions.
-///
-/// This supports context-aware allowlisting where rules can be scoped
-/// to specific directories or path patterns. Rules can also have expiration
-/// settings (mutually exclusive: only one of `expires`, `ttl`, `ttl_seconds`,
-/// or `session` should be set).
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AllowlistRule {
-    /// The pattern to allow (regex supported).
-    pub pattern: String,
+/// Filesystem protection configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProtectionConfig {
+    /// Protected path globs checked against resolved `rm`/`mv`/`truncate`/`dd`/`sqlite3` targets.
+    pub paths: ProtectedPathsConfig,
+    /// Checkouts where log-only matches are raised to warnings.
+    pub workspaces: ProtectedWorkspacesConfig,
+    /// Summary of what a denied `rm` would delete.
+    pub rm_preview: RmPreviewConfig,
+}
 
-    /// Optional path patterns where this rule applies.
-    ///
-    /// If `None` or empty, the rule applies globally.
-    /// Supports glob patterns like `/home/*/projects/*`.
-    #[serde(default)]
-    pub paths: Option<Vec<String>>,
+/// Protected path patterns (`[protection.paths]`).
+///
+/// Example in TOML:
+/// ```toml
+/// [protection.paths]
+/// patterns = ["~/projects/prod-*", "/etc/**"]
+/// ```
+///
+/// Patterns must be absolute after `~` and `$VAR` expansion. Targets are
+/// resolved against the working directory, `..` is normalized, and symlinks
+/// are followed before matching, so `rm -rf ../../etc` is caught too. A
+/// `sqlite3` database operand counts as a target unless the session is
+/// read-only.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProtectedPathsConfig {
+    /// Glob patterns for protected paths.
+    pub patterns: Vec<String>,
+}
 
-    /// Optional comment explaining the rule.
-    #[serde(default)]
-    pub comment: Option<String>,
+/// Protected workspaces (`[protection.workspaces]`).
+///
+/// Example in TOML:
+/// ```toml
+/// [protection.workspaces]
+/// patterns = ["~/work/monorepo", "/srv/build/*"]
+/// ```
+///
+/// When the working directory is inside one of these directories, Low
+/// severity matches (logged by default, such as `cargo clean`) are raised to
+/// Medium, which the default policy warns about. Patterns follow the
+/// `[protection.paths]` rules.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProtectedWorkspacesConfig {
+    /// Glob patterns for protected workspace directories.
+    pub patterns: Vec<String>,
+}
 
-    /// Optional expiration timestamp (ISO 8601 format).
-    ///
-    /// After this time, the rule is no longer active.
-    /// Example: "2024-06-01T00:00:00Z"
-    ///
-    /// Mutually exclusive with `ttl`, `ttl_seconds`, and `session`.
-    #[serde(default)]
-    pub expires: Option<String>,
+/// "What would this delete" preview for denied `rm` (`[protection.rm_preview]`).
+///
+/// Example in TOML:
+/// ```toml
+/// [protection.rm_preview]
+/// enabled = true
+/// max_entries = 10000
+/// timeout_ms = 100
+/// ```
+///
+/// When enabled, a denied `rm` has its targets walked (read-only) and the deny
+/// reason gains a line such as `would delete 1204 files (38.20 MB); src has
+/// 3 uncommitted changes in git`. The walk stops after `max_entries` entries or
+/// `timeout_ms`, whichever comes first.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RmPreviewConfig {
+    /// Append the preview to `rm` deny reasons (default: false).
+    pub enabled: bool,
+    /// Maximum filesystem entries to visit.
+    pub max_entries: usize,
+    /// Wall-clock budget for the walk and git checks, in milliseconds.
+    pub timeout_ms: u64,
+}
 
-    /// Optional time-to-live duration as a human-readable string.
-    ///
-    /// Supported formats:
-    /// - Minutes: "30m", "30min", "30 minutes"
-    /// - Hours: "4h", "4hr", "4 hours"
-    /// - Days: "7d", "7 days"
-    /// - Weeks: "1w", "1 week"
-    ///
-    /// TTL is computed from `created_at` timestamp.
-    /// Mutually exclusive with `expires`, `ttl_seconds`, and `session`.
-    #[serde(default)]
-    pub ttl: Option<String>,
+impl Default for RmPreviewConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_entries: 10_000,
+            timeout_ms: 100,
+        }
+    }
+}
 
-    /// Optional time-to-live duration in seconds.
-    ///
-    /// Alternative to `ttl` for programmatic use.
-    /// TTL is computed from `created_at` timestamp.
-    /// Mutually exclusive with `expires`, `ttl`, and `session`.
-    #[serde(default)]
-    pub ttl_seconds: Option<u64>,
+/// Shell alias and function expansion (`[aliases]`).
+///
+/// Example in TOML:
+/// ```toml
+/// [aliases]
+/// enabled = true
+/// files = ["~/.config/dcg/aliases.sh"]
+/// ```
+///
+/// Files hold `alias -p` / `typeset -f` output (see `dcg aliases snapshot`).
+/// When `files` is empty the snapshot in the dcg config directory is used.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AliasesConfig {
+    /// Expand aliases and shell functions in command position before matching.
+    pub enabled: bool,
+    /// Definition files to load, in order (later files win on conflicts).
+    pub files: Vec<String>,
+}
+
+impl Default for AliasesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            files: Vec::new(),
+        }
+    }
+}
+
+/// Installer domains exempt from `core.pipe_shell` (`[trusted_installers]`).
+///
+/// Example in TOML:
+/// ```toml
+/// [trusted_installers]
+/// domains = ["sh.rustup.rs", "get.example.com"]
+/// ```
+///
+/// A pipe-to-shell command is allowed only when it has a single download and
+/// every URL in it is `https://` on one of these domains (or a subdomain).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TrustedInstallersConfig {
+    /// Trusted hostnames. Later config layers add to this list.
+    pub domains: Vec<String>,
+}
+
+impl Default for TrustedInstallersConfig {
+    fn default() -> Self {
+        Self {
+            domains: DEFAULT_TRUSTED_INSTALLER_DOMAINS
+                .iter()
+                .map(|domain| (*domain).to_string())
+                .collect(),
+        }
+    }
+}
+
+/// Official installer endpoints trusted out of the box.
+const DEFAULT_TRUSTED_INSTALLER_DOMAINS: &[&str] = &[
+    "sh.rustup.rs",
+    "astral.sh",
+    "bun.sh",
+    "deno.land",
+    "get.pnpm.io",
+];
+
+/// Wrapper commands resolved before evaluation (`[wrappers]`).
+///
+/// Example in TOML:
+/// ```toml
+/// [wrappers]
+/// ssh = true
+/// extra = ["chronic", "unbuffer"]
+/// production_hosts = ["prod-*", "*.prod.example.com"]
+/// ```
+///
+/// `sudo`, `doas`, `env`, `nice`, `ionice`, `timeout`, `nohup`, `stdbuf`, and
+/// `xargs` are always unwrapped; see [`crate::normalize::resolve_wrapper_chain`].
+/// Matches in commands run over `ssh` on a production host are raised one
+/// severity level (see [`crate::remote_exec`]).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WrappersConfig {
+    /// Evaluate the remote command of `ssh host '<cmd>'`.
+    pub ssh: bool,
+    /// More commands that run their arguments as a command. Leading `-`
+    /// options are skipped. Later config layers add to this list.
+    pub extra: Vec<String>,
+    /// `ssh` target hosts (`*` wildcards, case-insensitive) whose matches
+    /// are raised one severity level.
+    pub production_hosts: Vec<String>,
+}
+
+impl Default for WrappersConfig {
+    fn default() -> Self {
+        Self {
+            ssh: true,
+            extra: Vec::new(),
+            production_hosts: Vec::new(),
+        }
+    }
+}
+
+impl WrappersConfig {
+    /// Cheap pre-check: could `command` contain a wrapper only
+    /// [`crate::normalize::resolve_wrapper_chain`] sees through?
+    #[must_use]
+    pub fn may_wrap(&self, command: &str) -> bool {
+        (self.ssh && command.contains("ssh"))
+            || self
+                .extra
+                .iter()
+                .any(|name| command.contains(name.as_str()))
+    }
+}
+
+/// An extended allowlist rule with optional path conditions.
+///
+/// This supports context-aware allowlisting where rules can be scoped
+/// to specific directories or path patterns. Rules can also have expiration
+/// settings (mutually exclusive: only one of `expires`, `ttl`, `ttl_seconds`,
+/// or `session` should be set).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AllowlistRule {
+    /// The pattern to allow (regex supported).
+    pub pattern: String,
+
+    /// Optional path patterns where this rule applies.
+    ///
+    /// If `None` or empty, the rule applies globally.
+    /// Supports glob patterns like `/home/*/projects/*`.
+    #[serde(default)]
+    pub paths: Option<Vec<String>>,
+
+    /// Optional comment explaining the rule.
+    #[serde(default)]
+    pub comment: Option<String>,
+
+    /// Optional expiration timestamp (ISO 8601 format).
+    ///
+    /// After this time, the rule is no longer active.
+    /// Example: "2024-06-01T00:00:00Z"
+    ///
+    /// Mutually exclusive with `ttl`, `ttl_seconds`, and `session`.
+    #[serde(default)]
+    pub expires: Option<String>,
+
+    /// Optional time-to-live duration as a human-readable string.
+    ///
+    /// Supported formats:
+    /// - Minutes: "30m", "30min", "30 minutes"
+    /// - Hours: "4h", "4hr", "4 hours"
+    /// - Days: "7d", "7 days"
+    /// - Weeks: "1w", "1 week"
+    ///
+    /// TTL is computed from `created_at` timestamp.
+    /// Mutually exclusive with `expires`, `ttl_seconds`, and `session`.
+    #[serde(default)]
+    pub ttl: Option<String>,
+
+    /// Optional time-to-live duration in seconds.
+    ///
+    /// Alternative to `ttl` for programmatic use.
+    /// TTL is computed from `created_at` timestamp.
+    /// Mutually exclusive with `expires`, `ttl`, and `session`.
+    #[serde(default)]
+    pub ttl_seconds: Option<u64>,
 
     /// Whether this is a session-only rule.
     ///
@@ -1462,6 +2754,24 @@ impl AllowlistRule {
 }
 
 /// An allow override - patterns that should be permitted.
+///
+/// Besides regexes, commands can be matched literally:
+///
+/// ```toml
+/// [[overrides.allow]]
+/// exact = "npm run build"
+///
+/// [[overrides.allow]]
+/// prefix = "cargo test"        # "cargo test --release", not "cargo testing"
+///
+/// [[overrides.allow]]
+/// glob = "rm -rf ./target/*"
+/// when = "CI=true"
+/// ```
+///
+/// Prefix and glob rules never match a command that chains or substitutes
+/// another command (`;`, `&`, `|`, backticks, `$(`, newlines) unless the rule
+/// itself contains that operator.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum AllowOverride {
@@ -1473,6 +2783,24 @@ pub enum AllowOverride {
         /// Optional condition (e.g., "CI=true").
         when: Option<String>,
     },
+    /// The whole command, ignoring surrounding whitespace.
+    Exact {
+        exact: String,
+        /// Optional condition (e.g., "CI=true").
+        when: Option<String>,
+    },
+    /// Commands starting with this text at a word boundary.
+    Prefix {
+        prefix: String,
+        /// Optional condition (e.g., "CI=true").
+        when: Option<String>,
+    },
+    /// Shell-style glob (`*`, `?`, `[...]`) over the whole command.
+    Glob {
+        glob: String,
+        /// Optional condition (e.g., "CI=true").
+        when: Option<String>,
+    },
 }
 
 impl AllowOverride {
@@ -1482,28 +2810,29 @@ impl AllowOverride {
         match self {
             Self::Simple(p) => p,
             Self::Conditional { pattern, .. } => pattern,
+            Self::Exact { exact, .. } => exact,
+            Self::Prefix { prefix, .. } => prefix,
+            Self::Glob { glob, .. } => glob,
         }
     }
 
-    /// Check if the condition is met (if any).
+    /// The `when` condition, if any.
     #[must_use]
-    pub fn condition_met(&self) -> bool {
+    pub fn when(&self) -> Option<&str> {
         match self {
-            Self::Simple(_) | Self::Conditional { when: None, .. } => true,
-            Self::Conditional {
-                when: Some(condition),
-                ..
-            } => {
-                // Parse condition like "CI=true"
-                if let Some((var, expected)) = condition.split_once('=') {
-                    env::var(var).map(|v| v == expected).unwrap_or(false)
-                } else {
-                    // Just check if the env var is set
-                    env::var(condition).is_ok()
-                }
-            }
+            Self::Simple(_) => None,
+            Self::Conditional { when, .. }
+            | Self::Exact { when, .. }
+            | Self::Prefix { when, .. }
+            | Self::Glob { when, .. } => when.as_deref(),
         }
     }
+
+    /// Check if the condition is met (if any).
+    #[must_use]
+    pub fn condition_met(&self) -> bool {
+        ConditionCheck::from_when(self.when()).is_met()
+    }
 }
 
 /// A block override - additional patterns to block.
@@ -1549,16 +2878,14 @@ pub struct HistoryConfig {
     pub enabled: bool,
     /// Redaction mode for stored commands.
     pub redaction_mode: HistoryRedactionMode,
-    /// Retention window in days.
+    /// Retention window in days (legacy alias for `retention.max_age_days`).
     pub retention_days: u32,
-    /// Maximum database size in megabytes.
+    /// Maximum database size in megabytes (legacy alias for `retention.max_db_size_mb`).
     pub max_size_mb: u32,
     /// Optional database file path override.
     pub database_path: Option<String>,
-    /// Enable automatic pruning of old entries.
-    pub auto_prune: bool,
-    /// Interval in hours between automatic prune checks.
-    pub prune_check_interval_hours: u32,
+    /// Row, age, and size limits enforced by background compaction.
+    pub retention: HistoryRetentionConfig,
     /// Batch size for write operations (improves performance).
     pub batch_size: u32,
     /// Flush interval in milliseconds for batched writes.
@@ -1572,8 +2899,6 @@ impl HistoryConfig {
     pub const DEFAULT_MAX_SIZE_MB: u32 = 500;
     /// Maximum allowed retention window (days).
     pub const MAX_RETENTION_DAYS: u32 = 3650;
-    /// Default interval between automatic prune checks (hours).
-    pub const DEFAULT_PRUNE_CHECK_INTERVAL_HOURS: u32 = 24;
     /// Default batch size for write operations.
     pub const DEFAULT_BATCH_SIZE: u32 = 50;
     /// Default flush interval for batched writes (ms).
@@ -1625,128 +2950,531 @@ impl Default for HistoryConfig {
             retention_days: Self::DEFAULT_RETENTION_DAYS,
             max_size_mb: Self::DEFAULT_MAX_SIZE_MB,
             database_path: None,
-            auto_prune: false,
-            prune_check_interval_hours: Self::DEFAULT_PRUNE_CHECK_INTERVAL_HOURS,
+            retention: HistoryRetentionConfig::default(),
             batch_size: Self::DEFAULT_BATCH_SIZE,
             batch_flush_interval_ms: Self::DEFAULT_BATCH_FLUSH_INTERVAL_MS,
         }
     }
 }
 
-// ============================================================================
-// Git Branch-Aware Strictness Configuration
-// ============================================================================
-
-/// Strictness level that determines which severity levels are blocked.
+/// History retention limits (`[history.retention]`).
 ///
-/// This controls the sensitivity of pattern matching based on context,
-/// such as which git branch you're on.
+/// Example in TOML:
+/// ```toml
+/// [history.retention]
+/// max_rows = 1000000
+/// max_age_days = 90
+/// max_db_size_mb = 500
+/// ```
 ///
-/// # Example Configuration (TOML)
+/// The history writer runs a compaction pass at most once per
+/// `compaction_interval_secs` (tracked in the database, so it holds across
+/// hook processes). A limit of 0 disables it. `dcg history vacuum` runs the
+/// same pass on demand and then reclaims the freed space.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HistoryRetentionConfig {
+    /// Run compaction from the history writer thread.
+    pub enabled: bool,
+    /// Keep at most this many command entries.
+    pub max_rows: u64,
+    /// Delete entries older than this many days.
+    pub max_age_days: u32,
+    /// Keep the live database size under this many megabytes.
+    pub max_db_size_mb: u64,
+    /// Minimum seconds between background compaction passes.
+    pub compaction_interval_secs: u64,
+}
+
+impl HistoryRetentionConfig {
+    /// Default row limit.
+    pub const DEFAULT_MAX_ROWS: u64 = 1_000_000;
+    /// Default interval between compaction passes (seconds).
+    pub const DEFAULT_COMPACTION_INTERVAL_SECS: u64 = 3600;
+
+    /// The limits as a [`crate::history::RetentionPolicy`].
+    #[must_use]
+    pub fn policy(&self) -> crate::history::RetentionPolicy {
+        crate::history::RetentionPolicy {
+            max_rows: self.max_rows,
+            max_age_days: u64::from(self.max_age_days),
+            max_db_size_bytes: self.max_db_size_mb.saturating_mul(1024 * 1024),
+        }
+    }
+}
+
+impl Default for HistoryRetentionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_rows: Self::DEFAULT_MAX_ROWS,
+            max_age_days: HistoryConfig::DEFAULT_RETENTION_DAYS,
+            max_db_size_mb: u64::from(HistoryConfig::DEFAULT_MAX_SIZE_MB),
+            compaction_interval_secs: Self::DEFAULT_COMPACTION_INTERVAL_SECS,
+        }
+    }
+}
+
+/// Rate-based anomaly detection (`[anomaly]`).
+///
+/// When an agent collects `deny_threshold` denials within `window_secs`, it is
+/// treated as fighting the guard: rules that would only warn are escalated to
+/// deny, and denials carry a notice telling the agent to stop retrying.
+/// Denials are counted from the history database, so `[history]` must be
+/// enabled for this to have any effect.
 ///
+/// Example in TOML:
 /// ```toml
-/// [git_awareness]
+/// [anomaly]
 /// enabled = true
-/// protected_branches = ["main", "master", "production", "release/*"]
-/// protected_strictness = "all"
-/// relaxed_branches = ["feature/*", "experiment/*", "sandbox/*"]
-/// relaxed_strictness = "critical"
+/// deny_threshold = 5
+/// window_secs = 120
 /// ```
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
-pub enum StrictnessLevel {
-    /// Only block Critical severity patterns.
-    /// Most permissive - allows High, Medium, and Low to pass.
-    Critical,
-
-    /// Block Critical and High severity patterns.
-    /// This is the default behavior.
-    #[default]
-    High,
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AnomalyConfig {
+    /// Enable anomaly detection (requires history).
+    pub enabled: bool,
+    /// Denials within the window at which the agent is considered anomalous.
+    pub deny_threshold: u32,
+    /// Length of the sliding window in seconds.
+    pub window_secs: u64,
+}
 
-    /// Block Critical, High, and Medium severity patterns.
-    Medium,
+impl AnomalyConfig {
+    /// Default number of denials that triggers escalation.
+    pub const DEFAULT_DENY_THRESHOLD: u32 = 5;
+    /// Default sliding window (seconds).
+    pub const DEFAULT_WINDOW_SECS: u64 = 120;
 
-    /// Block all severity levels including Low.
-    /// Most restrictive - recommended for protected branches.
-    All,
+    /// Whether the detector should run at all.
+    #[must_use]
+    pub const fn is_active(&self) -> bool {
+        self.enabled && self.deny_threshold > 0 && self.window_secs > 0
+    }
 }
 
-impl StrictnessLevel {
-    /// Returns `true` if the given severity should be blocked at this strictness level.
-    #[must_use]
-    pub const fn should_block(&self, severity: crate::packs::Severity) -> bool {
-        use crate::packs::Severity;
-        match self {
-            Self::Critical => matches!(severity, Severity::Critical),
-            Self::High => matches!(severity, Severity::Critical | Severity::High),
-            Self::Medium => {
-                matches!(
-                    severity,
-                    Severity::Critical | Severity::High | Severity::Medium
-                )
-            }
-            Self::All => true,
+impl Default for AnomalyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            deny_threshold: Self::DEFAULT_DENY_THRESHOLD,
+            window_secs: Self::DEFAULT_WINDOW_SECS,
         }
     }
+}
 
-    /// Parse a strictness level from a string.
+/// Per-session risk scoring (`[risk]`).
+///
+/// Every command that matches a rule adds points to a running score for the
+/// agent session (or, without a session id, the working directory): denied
+/// commands add `deny_points`, commands that were only warned about or
+/// logged add `warn_points`, and critical rules count double. Once the score
+/// reaches `threshold`, rules that would only warn are denied for the rest of
+/// the session. A score idle for `idle_reset_secs` starts over. Scores are
+/// kept in the history database, so `[history]` must be enabled.
+///
+/// Example in TOML:
+/// ```toml
+/// [risk]
+/// enabled = true
+/// threshold = 100
+/// deny_points = 20
+/// warn_points = 5
+/// idle_reset_secs = 3600
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RiskConfig {
+    /// Enable risk scoring (requires history).
+    pub enabled: bool,
+    /// Score at which warn-mode rules are escalated to deny.
+    pub threshold: u64,
+    /// Points added for a denied command.
+    pub deny_points: u64,
+    /// Points added for a command that matched a rule but was allowed.
+    pub warn_points: u64,
+    /// Seconds without a scored command after which the score resets (0 = never).
+    pub idle_reset_secs: u64,
+}
+
+impl RiskConfig {
+    /// Default escalation threshold.
+    pub const DEFAULT_THRESHOLD: u64 = 100;
+    /// Default points for a denied command.
+    pub const DEFAULT_DENY_POINTS: u64 = 20;
+    /// Default points for an allowed-but-risky command.
+    pub const DEFAULT_WARN_POINTS: u64 = 5;
+    /// Default idle reset (seconds).
+    pub const DEFAULT_IDLE_RESET_SECS: u64 = 3600;
+
+    /// Whether scoring should run at all.
     #[must_use]
-    pub fn from_str_case_insensitive(s: &str) -> Option<Self> {
-        match s.to_ascii_lowercase().as_str() {
-            "critical" => Some(Self::Critical),
-            "high" => Some(Self::High),
-            "medium" => Some(Self::Medium),
-            "all" => Some(Self::All),
-            _ => None,
-        }
+    pub const fn is_active(&self) -> bool {
+        self.enabled && self.threshold > 0
     }
 }
 
-impl std::fmt::Display for StrictnessLevel {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::Critical => write!(f, "critical"),
-            Self::High => write!(f, "high"),
-            Self::Medium => write!(f, "medium"),
-            Self::All => write!(f, "all"),
+impl Default for RiskConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            threshold: Self::DEFAULT_THRESHOLD,
+            deny_points: Self::DEFAULT_DENY_POINTS,
+            warn_points: Self::DEFAULT_WARN_POINTS,
+            idle_reset_secs: Self::DEFAULT_IDLE_RESET_SECS,
         }
     }
 }
 
-/// Git branch-aware strictness configuration.
-///
-/// This allows different strictness levels based on the current git branch,
-/// providing more protection on important branches and more freedom on
-/// experimental branches.
+/// Webhook notifications for denied commands (`[notifications]`).
 ///
-/// # Example Configuration (TOML)
+/// Denials at or above `min_severity` are queued by the hook and posted by a
+/// detached `dcg notify flush` process, so the hook never waits on the
+/// network. Events arriving within `batch_window_ms` are sent as one message.
+/// After `failure_threshold` consecutive failures a webhook is skipped for
+/// `cooldown_secs` (circuit breaker). Commands are redacted with the
+/// `[logging.redaction]` settings.
 ///
+/// Example in TOML:
 /// ```toml
-/// [git_awareness]
+/// [notifications]
 /// enabled = true
+/// min_severity = "critical"
 ///
-/// # Protected branches get extra scrutiny
-/// protected_branches = ["main", "master", "production", "release/*"]
-/// protected_strictness = "all"  # Block everything including Low severity
-///
-/// # Feature branches get more freedom
-/// relaxed_branches = ["feature/*", "experiment/*", "sandbox/*"]
-/// relaxed_strictness = "critical"  # Only block Critical severity
-///
-/// # Packs to disable on relaxed branches
-/// relaxed_disabled_packs = []
-///
-/// # Default strictness when not matching any pattern
-/// default_strictness = "high"  # Block Critical and High (normal behavior)
-///
-/// # Show branch context in output
-/// show_branch_in_output = true
+/// [[notifications.webhooks]]
+/// url = "https://hooks.slack.com/services/T000/B000/XXXX"
+/// format = "slack"
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(default)]
-pub struct GitAwarenessConfig {
-    /// Enable git branch-aware strictness.
+pub struct NotificationsConfig {
+    /// Enable webhook notifications.
+    pub enabled: bool,
+    /// Lowest severity that triggers a notification.
+    pub min_severity: StrictnessLevel,
+    /// Webhooks to notify.
+    pub webhooks: Vec<WebhookConfig>,
+    /// How long the flusher waits to batch events (milliseconds).
+    pub batch_window_ms: u64,
+    /// Connect/read/write timeout per webhook request (milliseconds).
+    pub timeout_ms: u64,
+    /// Consecutive failures before a webhook's circuit opens.
+    pub failure_threshold: u32,
+    /// How long an open circuit skips its webhook (seconds).
+    pub cooldown_secs: u64,
+}
+
+impl NotificationsConfig {
+    /// Default batching window (milliseconds).
+    pub const DEFAULT_BATCH_WINDOW_MS: u64 = 2000;
+    /// Default per-request timeout (milliseconds).
+    pub const DEFAULT_TIMEOUT_MS: u64 = 5000;
+    /// Default consecutive failures before the circuit opens.
+    pub const DEFAULT_FAILURE_THRESHOLD: u32 = 3;
+    /// Default circuit-open duration (seconds).
+    pub const DEFAULT_COOLDOWN_SECS: u64 = 300;
+
+    /// Whether notifications should be queued at all.
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        self.enabled && !self.webhooks.is_empty()
+    }
+}
+
+impl Default for NotificationsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_severity: StrictnessLevel::High,
+            webhooks: Vec::new(),
+            batch_window_ms: Self::DEFAULT_BATCH_WINDOW_MS,
+            timeout_ms: Self::DEFAULT_TIMEOUT_MS,
+            failure_threshold: Self::DEFAULT_FAILURE_THRESHOLD,
+            cooldown_secs: Self::DEFAULT_COOLDOWN_SECS,
+        }
+    }
+}
+
+/// A webhook target in `[[notifications.webhooks]]`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    /// `http://` or `https://` endpoint.
+    pub url: String,
+    /// Payload shape expected by the endpoint.
+    #[serde(default)]
+    pub format: WebhookFormat,
+}
+
+/// Payload format for a webhook.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookFormat {
+    /// `{"source": "dcg", "events": [...]}` with one object per denial.
+    #[default]
+    Json,
+    /// Slack incoming webhook (`{"text": ...}`).
+    Slack,
+    /// Discord webhook (`{"content": ...}`).
+    Discord,
+}
+
+/// Central policy bundle sync (`[remote]`).
+///
+/// `dcg sync` downloads a signed policy bundle from `url` and caches it
+/// locally; the hook starts a background sync once the cache is older than
+/// `interval_secs`. A bundle is only accepted when its detached signature
+/// (`signature_url`, default `<url>.sig`) verifies against `public_key`. The
+/// cached bundle's packs and overrides are applied above the user config
+/// (project and explicit configs still win), its allowlist becomes the
+/// lowest-precedence `remote` allowlist layer, and its custom packs are
+/// loaded like `packs.custom_paths`.
+///
+/// Example in TOML:
+/// ```toml
+/// [remote]
+/// enabled = true
+/// url = "https://policy.example.com/dcg/policy.toml"
+/// public_key = "base64 Ed25519 public key"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RemoteConfig {
+    /// Enable policy sync and apply the cached bundle.
+    pub enabled: bool,
+    /// `https://` URL of the policy bundle.
+    pub url: Option<String>,
+    /// Base64 Ed25519 public key the bundle must be signed with.
+    pub public_key: Option<String>,
+    /// URL of the detached signature (defaults to `<url>.sig`).
+    pub signature_url: Option<String>,
+    /// How often the hook refreshes the bundle in the background (seconds).
+    pub interval_secs: u64,
+    /// Connect/read/write timeout per request (milliseconds).
+    pub timeout_ms: u64,
+}
+
+impl RemoteConfig {
+    /// Default refresh interval (seconds).
+    pub const DEFAULT_INTERVAL_SECS: u64 = 3600;
+    /// Default per-request timeout (milliseconds).
+    pub const DEFAULT_TIMEOUT_MS: u64 = 10_000;
+
+    /// Whether sync is enabled and configured with a URL and key.
+    #[must_use]
+    pub const fn is_active(&self) -> bool {
+        self.enabled && self.url.is_some() && self.public_key.is_some()
+    }
+
+    /// Signature URL: `signature_url`, or `<url>.sig`.
+    #[must_use]
+    pub fn signature_url(&self) -> Option<String> {
+        self.signature_url
+            .clone()
+            .or_else(|| self.url.as_ref().map(|url| format!("{url}.sig")))
+    }
+}
+
+impl Default for RemoteConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: None,
+            public_key: None,
+            signature_url: None,
+            interval_secs: Self::DEFAULT_INTERVAL_SECS,
+            timeout_ms: Self::DEFAULT_TIMEOUT_MS,
+        }
+    }
+}
+
+/// Persistent evaluation cache (`[cache]`).
+///
+/// Agents often re-run the same command several times in a row. With the cache
+/// enabled, a command that evaluated to a clean allow (no rule matched) is
+/// remembered for `ttl_secs`, keyed by the command and working directory, and
+/// later hook invocations skip evaluation for it. Denials, warnings, and allows
+/// that came from an allowlist or allow-once entry are never cached. Entries
+/// are dropped whenever the effective configuration, enabled packs, or dcg
+/// version changes.
+///
+/// `quick_allow` additionally keeps a Bloom filter of commands that were a
+/// clean allow at least twice, consulted before this cache; see
+/// [`crate::quick_allow`].
+///
+/// Example in TOML:
+/// ```toml
+/// [cache]
+/// enabled = true
+/// max_entries = 1000
+/// ttl_secs = 3600
+/// quick_allow = true
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EvalCacheConfig {
+    /// Enable the cache (opt-in).
+    pub enabled: bool,
+    /// Maximum number of cached commands; least recently used entries are evicted.
+    pub max_entries: usize,
+    /// How long an entry stays valid, in seconds.
+    pub ttl_secs: u64,
+    /// Skip evaluation for repeated clean allows found in the quick-allow filter (opt-in).
+    pub quick_allow: bool,
+    /// Optional cache file path override.
+    pub path: Option<String>,
+}
+
+impl EvalCacheConfig {
+    /// Default maximum number of cached commands.
+    pub const DEFAULT_MAX_ENTRIES: usize = 1000;
+    /// Default entry lifetime (seconds).
+    pub const DEFAULT_TTL_SECS: u64 = 3600;
+
+    /// Whether the cache should be consulted at all.
+    #[must_use]
+    pub const fn is_active(&self) -> bool {
+        self.enabled && self.max_entries > 0 && self.ttl_secs > 0
+    }
+    /// Expand the configured cache path, if set.
+    #[must_use]
+    pub fn expanded_path(&self) -> Option<PathBuf> {
+        let raw = self.path.as_ref()?;
+        let trimmed = raw.trim();
+        if trimmed.is_empty() {
+            return None;
+        }
+        let (path, _tilde_expanded) = expand_tilde_path(trimmed);
+        Some(path)
+    }
+}
+
+impl Default for EvalCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_entries: Self::DEFAULT_MAX_ENTRIES,
+            ttl_secs: Self::DEFAULT_TTL_SECS,
+            quick_allow: false,
+            path: None,
+        }
+    }
+}
+
+// ============================================================================
+// Git Branch-Aware Strictness Configuration
+// ============================================================================
+
+/// Strictness level that determines which severity levels are blocked.
+///
+/// This controls the sensitivity of pattern matching based on context,
+/// such as which git branch you're on.
+///
+/// # Example Configuration (TOML)
+///
+/// ```toml
+/// [git_awareness]
+/// enabled = true
+/// protected_branches = ["main", "master", "production", "release/*"]
+/// protected_strictness = "all"
+/// relaxed_branches = ["feature/*", "experiment/*", "sandbox/*"]
+/// relaxed_strictness = "critical"
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StrictnessLevel {
+    /// Only block Critical severity patterns.
+    /// Most permissive - allows High, Medium, and Low to pass.
+    Critical,
+
+    /// Block Critical and High severity patterns.
+    /// This is the default behavior.
+    #[default]
+    High,
+
+    /// Block Critical, High, and Medium severity patterns.
+    Medium,
+
+    /// Block all severity levels including Low.
+    /// Most restrictive - recommended for protected branches.
+    All,
+}
+
+impl StrictnessLevel {
+    /// Returns `true` if the given severity should be blocked at this strictness level.
+    #[must_use]
+    pub const fn should_block(&self, severity: crate::packs::Severity) -> bool {
+        use crate::packs::Severity;
+        match self {
+            Self::Critical => matches!(severity, Severity::Critical),
+            Self::High => matches!(severity, Severity::Critical | Severity::High),
+            Self::Medium => {
+                matches!(
+                    severity,
+                    Severity::Critical | Severity::High | Severity::Medium
+                )
+            }
+            Self::All => true,
+        }
+    }
+
+    /// Parse a strictness level from a string.
+    #[must_use]
+    pub fn from_str_case_insensitive(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "critical" => Some(Self::Critical),
+            "high" => Some(Self::High),
+            "medium" => Some(Self::Medium),
+            "all" => Some(Self::All),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for StrictnessLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Critical => write!(f, "critical"),
+            Self::High => write!(f, "high"),
+            Self::Medium => write!(f, "medium"),
+            Self::All => write!(f, "all"),
+        }
+    }
+}
+
+/// Git branch-aware strictness configuration.
+///
+/// This allows different strictness levels based on the current git branch,
+/// providing more protection on important branches and more freedom on
+/// experimental branches.
+///
+/// # Example Configuration (TOML)
+///
+/// ```toml
+/// [git_awareness]
+/// enabled = true
+///
+/// # Protected branches get extra scrutiny
+/// protected_branches = ["main", "master", "production", "release/*"]
+/// protected_strictness = "all"  # Block everything including Low severity
+///
+/// # Feature branches get more freedom
+/// relaxed_branches = ["feature/*", "experiment/*", "sandbox/*"]
+/// relaxed_strictness = "critical"  # Only block Critical severity
+///
+/// # Packs to disable on relaxed branches
+/// relaxed_disabled_packs = []
+///
+/// # Default strictness when not matching any pattern
+/// default_strictness = "high"  # Block Critical and High (normal behavior)
+///
+/// # Show branch context in output
+/// show_branch_in_output = true
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GitAwarenessConfig {
+    /// Enable git branch-aware strictness.
     /// When disabled, normal strictness levels apply everywhere.
     pub enabled: bool,
 
@@ -1920,6 +3648,167 @@ impl GitAwarenessConfig {
     }
 }
 
+/// Kubernetes context-aware severity (`[kube_context]`).
+///
+/// For matches from `kubernetes.*` packs, the target context is taken from
+/// `--context`/`--kube-context` in the command, or from
+/// `kubectl config current-context` (honoring `--kubeconfig`), cached for
+/// `cache_ttl_secs`. Contexts matching `production_contexts` raise the
+/// severity one level (Medium becomes High, High becomes Critical);
+/// contexts matching `development_contexts` lower High to Medium and Medium
+/// to Low. Critical matches are never lowered. Patterns support `*`
+/// wildcards.
+///
+/// Example in TOML:
+/// ```toml
+/// [kube_context]
+/// enabled = true
+/// production_contexts = ["*prod*", "arn:aws:eks:*:cluster/live-*"]
+/// development_contexts = ["kind-*", "minikube"]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KubeContextConfig {
+    /// Adjust severity based on the Kubernetes context.
+    pub enabled: bool,
+    /// Context name patterns treated as production (severity raised).
+    pub production_contexts: Vec<String>,
+    /// Context name patterns treated as disposable (severity lowered).
+    pub development_contexts: Vec<String>,
+    /// How long a `kubectl config current-context` lookup is reused (seconds).
+    pub cache_ttl_secs: u64,
+    /// Time allowed for `kubectl config current-context` (milliseconds).
+    pub timeout_ms: u64,
+}
+
+impl KubeContextConfig {
+    /// Default lookup cache lifetime (seconds).
+    pub const DEFAULT_CACHE_TTL_SECS: u64 = 60;
+    /// Default `kubectl` lookup timeout (milliseconds).
+    pub const DEFAULT_TIMEOUT_MS: u64 = 1000;
+}
+
+impl Default for KubeContextConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            production_contexts: vec!["*prod*".to_string()],
+            development_contexts: vec![
+                "kind-*".to_string(),
+                "minikube".to_string(),
+                "docker-desktop".to_string(),
+                "k3d-*".to_string(),
+                "rancher-desktop".to_string(),
+            ],
+            cache_ttl_secs: Self::DEFAULT_CACHE_TTL_SECS,
+            timeout_ms: Self::DEFAULT_TIMEOUT_MS,
+        }
+    }
+}
+
+/// Inline suppression comments (`[suppressions]`).
+///
+/// With `inline = true`, a command ending in
+/// `# dcg:allow rule=core.git:reset-hard reason="..."` may clear the one rule
+/// it names. See [`crate::suppression`].
+///
+/// Example in TOML:
+/// ```toml
+/// [suppressions]
+/// inline = true
+/// require_confirmation = true
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SuppressionsConfig {
+    /// Honor `# dcg:allow` annotations.
+    pub inline: bool,
+    /// Keep annotated commands denied until a human confirms them with
+    /// `dcg allow-once`. When false, the match is downgraded to a warning.
+    pub require_confirmation: bool,
+}
+
+impl Default for SuppressionsConfig {
+    fn default() -> Self {
+        Self {
+            inline: false,
+            require_confirmation: true,
+        }
+    }
+}
+
+/// Trust ledger of allow-once approvals (`[trust]`).
+///
+/// Every `dcg allow-once` approval is recorded per repository. Once a rule
+/// (or, for commands without a rule, the exact command) has been approved
+/// `promote_after` times in a repository, `dcg promote` offers to add it to
+/// the project allowlist. See [`crate::trust_ledger`].
+///
+/// Example in TOML:
+/// ```toml
+/// [trust]
+/// ledger = true
+/// promote_after = 3
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TrustConfig {
+    /// Record allow-once approvals in the ledger.
+    pub ledger: bool,
+    /// Approvals in one repository before `dcg promote` offers a promotion.
+    pub promote_after: u32,
+}
+
+impl TrustConfig {
+    /// Default approvals before promotion is offered.
+    pub const DEFAULT_PROMOTE_AFTER: u32 = 3;
+}
+
+impl Default for TrustConfig {
+    fn default() -> Self {
+        Self {
+            ledger: true,
+            promote_after: Self::DEFAULT_PROMOTE_AFTER,
+        }
+    }
+}
+
+/// Latency thresholds for `dcg bench` (`[bench]`).
+///
+/// `dcg bench` exits non-zero when a stage's p99 exceeds its threshold.
+/// Defaults are the panic thresholds from [`crate::perf`], with cold start
+/// bounded by the hook evaluation budget.
+///
+/// Example in TOML:
+/// ```toml
+/// [bench]
+/// cold_start_p99_ms = 50
+/// pack_eval_p99_us = 500
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BenchConfig {
+    /// Fresh hook-mode process, end to end (milliseconds).
+    pub cold_start_p99_ms: u64,
+    /// Commands rejected by keyword gating (microseconds).
+    pub quick_reject_p99_us: u64,
+    /// Commands that reach pack pattern matching (microseconds).
+    pub pack_eval_p99_us: u64,
+    /// Heredoc and inline-script analysis (milliseconds).
+    pub heredoc_p99_ms: u64,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            cold_start_p99_ms: crate::perf::HOOK_EVALUATION_BUDGET_MS,
+            quick_reject_p99_us: 50,
+            pack_eval_p99_us: 1000,
+            heredoc_p99_ms: 20,
+        }
+    }
+}
+
 // ============================================================================
 // Agent-Specific Profiles (Epic 9)
 // ============================================================================
@@ -2054,9 +3943,25 @@ enum ConditionCheck {
 }
 
 impl ConditionCheck {
-    /// Check if the condition is met.
-    fn is_met(&self) -> bool {
-        match self {
+    /// Parse a `when` clause: `VAR=value` or just `VAR` (set to anything).
+    fn from_when(when: Option<&str>) -> Self {
+        match when {
+            None => Self::Always,
+            Some(condition) => match condition.split_once('=') {
+                Some((var, expected)) => Self::EnvEquals {
+                    var: var.to_string(),
+                    expected: expected.to_string(),
+                },
+                None => Self::EnvSet {
+                    var: condition.to_string(),
+                },
+            },
+        }
+    }
+
+    /// Check if the condition is met.
+    fn is_met(&self) -> bool {
+        match self {
             Self::Always => true,
             Self::EnvEquals { var, expected } => {
                 std::env::var(var).map(|v| v == *expected).unwrap_or(false)
@@ -2103,6 +4008,125 @@ impl CompiledBlockOverride {
     }
 }
 
+/// Shell operators that chain, background, or substitute another command.
+const SHELL_CONTROL_OPERATORS: &[&str] = &[";", "&", "|", "`", "\n", "$(", "<(", ">("];
+
+/// Whether `text` uses a shell control operator that `rule` does not contain.
+fn introduces_shell_control(text: &str, rule: &str) -> bool {
+    SHELL_CONTROL_OPERATORS
+        .iter()
+        .any(|op| text.contains(op) && !rule.contains(op))
+}
+
+/// Non-regex allow overrides: exact commands, prefixes, and globs.
+///
+/// Exact commands are a hash lookup and all prefixes are matched with one
+/// Aho-Corasick pass, so large lists of simple rules stay cheap.
+#[derive(Debug, Default)]
+pub struct LiteralAllowMatcher {
+    exact: HashMap<String, Vec<ConditionCheck>>,
+    prefixes: Vec<(String, ConditionCheck)>,
+    prefix_automaton: Option<aho_corasick::AhoCorasick>,
+    globs: Vec<(glob::Pattern, ConditionCheck)>,
+}
+
+impl LiteralAllowMatcher {
+    /// Number of compiled rules.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.exact.values().map(Vec::len).sum::<usize>() + self.prefixes.len() + self.globs.len()
+    }
+
+    /// Whether there are no rules.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Whether any rule matches `command` with its condition met.
+    #[must_use]
+    pub fn matches(&self, command: &str) -> bool {
+        let command = command.trim();
+        if self
+            .exact
+            .get(command)
+            .is_some_and(|conditions| conditions.iter().any(ConditionCheck::is_met))
+        {
+            return true;
+        }
+
+        if let Some(automaton) = self.prefix_automaton.as_ref() {
+            for found in automaton.find_overlapping_iter(command) {
+                if found.start() != 0 {
+                    continue;
+                }
+                let (prefix, condition) = &self.prefixes[found.pattern().as_usize()];
+                let rest = &command[found.end()..];
+                let at_boundary = rest.is_empty()
+                    || rest.starts_with(char::is_whitespace)
+                    || prefix.ends_with(|c: char| c.is_whitespace() || c == '/' || c == '=');
+                if at_boundary && !introduces_shell_control(rest, prefix) && condition.is_met() {
+                    return true;
+                }
+            }
+        }
+
+        self.globs.iter().any(|(pattern, condition)| {
+            pattern.matches(command)
+                && !introduces_shell_control(command, pattern.as_str())
+                && condition.is_met()
+        })
+    }
+}
+
+/// Collects literal rules; [`LiteralAllowBuilder::build`] compiles the prefix automaton.
+#[derive(Default)]
+struct LiteralAllowBuilder {
+    matcher: LiteralAllowMatcher,
+}
+
+impl LiteralAllowBuilder {
+    fn non_empty(text: &str) -> Result<&str, String> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Err("empty command".to_string());
+        }
+        Ok(text)
+    }
+
+    fn exact(&mut self, command: &str, condition: ConditionCheck) -> Result<(), String> {
+        let command = Self::non_empty(command)?;
+        self.matcher
+            .exact
+            .entry(command.to_string())
+            .or_default()
+            .push(condition);
+        Ok(())
+    }
+
+    fn prefix(&mut self, prefix: &str, condition: ConditionCheck) -> Result<(), String> {
+        // Keep a trailing space ("git -C ") so it still marks a word boundary.
+        let trimmed = prefix.trim_start();
+        Self::non_empty(trimmed)?;
+        self.matcher.prefixes.push((trimmed.to_string(), condition));
+        Ok(())
+    }
+
+    fn glob(&mut self, glob: &str, condition: ConditionCheck) -> Result<(), String> {
+        let pattern = glob::Pattern::new(Self::non_empty(glob)?).map_err(|e| e.to_string())?;
+        self.matcher.globs.push((pattern, condition));
+        Ok(())
+    }
+
+    fn build(mut self) -> LiteralAllowMatcher {
+        if !self.matcher.prefixes.is_empty() {
+            self.matcher.prefix_automaton =
+                aho_corasick::AhoCorasick::new(self.matcher.prefixes.iter().map(|(p, _)| p)).ok();
+        }
+        self.matcher
+    }
+}
+
 /// Compiled overrides - runtime representation with precompiled regexes.
 ///
 /// This struct is created once per config load and reused for all command
@@ -2111,8 +4135,16 @@ impl CompiledBlockOverride {
 pub struct CompiledOverrides {
     /// Compiled allow overrides.
     pub allow: Vec<CompiledAllowOverride>,
+    /// Exact, prefix, and glob allow overrides.
+    pub literal_allow: LiteralAllowMatcher,
     /// Compiled block overrides.
     pub block: Vec<CompiledBlockOverride>,
+    /// Compiled `[protection.paths]` patterns.
+    pub protected_paths: crate::protected_paths::ProtectedPathMatcher,
+    /// `[trusted_installers]` domains exempt from `core.pipe_shell`.
+    pub trusted_installers: Vec<String>,
+    /// `[wrappers]` settings for resolving `ssh` and custom wrappers.
+    pub wrappers: WrappersConfig,
     /// Patterns that failed to compile (for diagnostics).
     pub invalid_patterns: Vec<InvalidPattern>,
 }
@@ -2140,7 +4172,7 @@ impl CompiledOverrides {
     #[inline]
     #[must_use]
     pub fn check_allow(&self, command: &str) -> bool {
-        self.allow.iter().any(|o| o.matches(command))
+        self.literal_allow.matches(command) || self.allow.iter().any(|o| o.matches(command))
     }
 
     /// Check block overrides. Returns the reason if command should be blocked.
@@ -2150,6 +4182,19 @@ impl CompiledOverrides {
         self.block.iter().find_map(|o| o.matches(command))
     }
 
+    /// Check protected paths. Returns the reason if a `rm`/`mv`/`truncate`/`dd`
+    /// target (or a writable `sqlite3` database) resolves into a protected path.
+    #[must_use]
+    pub fn check_protected_paths(&self, command: &str) -> Option<String> {
+        if self.protected_paths.is_empty() {
+            return None;
+        }
+        let cwd = env::current_dir().ok();
+        self.protected_paths
+            .check(command, cwd.as_deref())
+            .map(|hit| hit.reason())
+    }
+
     /// Check if there are any invalid patterns.
     #[must_use]
     pub fn has_invalid_patterns(&self) -> bool {
@@ -2167,32 +4212,31 @@ impl OverridesConfig {
         let mut compiled = CompiledOverrides::default();
 
         // Compile allow overrides
+        let mut literal = LiteralAllowBuilder::default();
         for allow in &self.allow {
+            let condition = || ConditionCheck::from_when(allow.when());
+            let added = match allow {
+                AllowOverride::Simple(_) | AllowOverride::Conditional { .. } => None,
+                AllowOverride::Exact { exact, .. } => Some(literal.exact(exact, condition())),
+                AllowOverride::Prefix { prefix, .. } => Some(literal.prefix(prefix, condition())),
+                AllowOverride::Glob { glob, .. } => Some(literal.glob(glob, condition())),
+            };
+            if let Some(added) = added {
+                if let Err(error) = added {
+                    compiled.invalid_patterns.push(InvalidPattern {
+                        pattern: allow.pattern().to_string(),
+                        error,
+                        kind: PatternKind::Allow,
+                    });
+                }
+                continue;
+            }
             match CompiledRegex::new(allow.pattern()) {
                 Ok(regex) => {
-                    let condition = match allow {
-                        AllowOverride::Simple(_)
-                        | AllowOverride::Conditional { when: None, .. } => ConditionCheck::Always,
-                        AllowOverride::Conditional {
-                            when: Some(condition),
-                            ..
-                        } => {
-                            if let Some((var, expected)) = condition.split_once('=') {
-                                ConditionCheck::EnvEquals {
-                                    var: var.to_string(),
-                                    expected: expected.to_string(),
-                                }
-                            } else {
-                                ConditionCheck::EnvSet {
-                                    var: condition.clone(),
-                                }
-                            }
-                        }
-                    };
                     compiled.allow.push(CompiledAllowOverride {
                         regex,
                         pattern: allow.pattern().to_string(),
-                        condition,
+                        condition: condition(),
                     });
                 }
                 Err(e) => {
@@ -2287,6 +4331,7 @@ impl OverridesConfig {
             }
         }
 
+        compiled.literal_allow = literal.build();
         compiled
     }
 
@@ -2381,31 +4426,65 @@ pub struct ProjectConfig {
 }
 
 impl Config {
+    /// Compile overrides and protected paths into their runtime representation.
+    ///
+    /// Prefer this over `overrides.compile()` when evaluating commands so that
+    /// `[protection.paths]` is enforced.
+    #[must_use]
+    pub fn compile_overrides(&self) -> CompiledOverrides {
+        let mut compiled = self.overrides.compile();
+        let (protected_paths, invalid) =
+            crate::protected_paths::ProtectedPathMatcher::compile(&self.protection.paths.patterns);
+        compiled.protected_paths = protected_paths;
+        compiled
+            .trusted_installers
+            .clone_from(&self.trusted_installers.domains);
+        compiled.wrappers.clone_from(&self.wrappers);
+        compiled
+            .invalid_patterns
+            .extend(invalid.into_iter().map(|(pattern, error)| InvalidPattern {
+                pattern,
+                error,
+                kind: PatternKind::Block,
+            }));
+        compiled
+    }
+
     /// Load configuration from all sources, merging them in priority order.
     ///
     /// Priority (highest to lowest):
     /// 1. Environment variables (settings overrides)
     /// 2. Explicit config file (`DCG_CONFIG=/path/to/config.toml`)
     /// 3. Project config (`.dcg.toml` in repo root)
-    /// 4. User config (`$XDG_CONFIG_HOME/dcg/config.toml`, `~/.config/dcg/config.toml`,
+    /// 4. Synced team policy (`[remote]`; only its `packs` and `overrides`)
+    /// 5. User config (`$XDG_CONFIG_HOME/dcg/config.toml`, `~/.config/dcg/config.toml`,
     ///    or platform-native config dir)
-    /// 5. System config (`/etc/dcg/config.toml`)
-    /// 6. Compiled defaults
+    /// 6. System config (`/etc/dcg/config.toml`)
+    /// 7. Compiled defaults
     #[must_use]
     pub fn load() -> Self {
+        Self::load_with_sources().0
+    }
+
+    /// Load configuration like [`Config::load`], also reporting which layers
+    /// contributed (in merge order, lowest precedence first).
+    #[must_use]
+    pub fn load_with_sources() -> (Self, Vec<ConfigSource>) {
         // Start with truly empty defaults - packs must be explicitly enabled.
         // generate_default() is for sample configs shown to users, not runtime defaults.
         let mut config = Self::default();
+        let mut sources = Vec::new();
         let cwd = env::current_dir().ok();
 
         // Optional explicit config path override (highest-priority file config).
         let explicit_layer = env::var(ENV_CONFIG_PATH)
             .ok()
             .and_then(|value| resolve_config_path_value(&value, cwd.as_deref()))
-            .and_then(|path| Self::load_layer_from_file(&path));
+            .and_then(|path| Self::load_layer_from_file(&path).map(|layer| (path, layer)));
 
         // Load system config (lowest priority of file configs)
-        if let Some(system_config) = Self::load_system_config_layer() {
+        if let Some((path, system_config)) = Self::load_system_config_layer() {
+            sources.push(ConfigSource::from_layer("system", path, &system_config));
             config.merge_layer(system_config);
         }
 
@@ -2415,25 +4494,63 @@ impl Config {
         // user-level config and skip loading the default user config path to
         // reduce layering confusion.
         if explicit_layer.is_none() {
-            if let Some(user_config) = Self::load_user_config_layer() {
+            if let Some((path, user_config)) = Self::load_user_config_layer() {
+                sources.push(ConfigSource::from_layer("user", path, &user_config));
                 config.merge_layer(user_config);
             }
         }
 
+        // Apply the synced team policy bundle, if remote sync is enabled by
+        // the system or user config.
+        if config.remote.enabled {
+            if let Some((path, remote_config)) = Self::load_remote_config_layer() {
+                sources.push(ConfigSource::from_layer("remote", path, &remote_config));
+                config.merge_layer(remote_config);
+            }
+            if let Some(packs_glob) = crate::remote::custom_packs_glob() {
+                config.packs.custom_paths.push(packs_glob);
+            }
+        }
+
         // Load project config (if in a git repo)
-        if let Some(project_config) = Self::load_project_config_layer_from(cwd.as_deref()) {
+        if let Some((path, mut project_config)) =
+            Self::load_project_config_layer_from(cwd.as_deref())
+        {
+            // A repository must not be able to repoint the team policy source
+            // or the external policy endpoint.
+            project_config.remote = None;
+            if let Some(policy) = project_config.policy.as_mut() {
+                policy.external = None;
+            }
+            sources.push(ConfigSource::from_layer("project", path, &project_config));
             config.merge_layer(project_config);
         }
 
         // Apply explicit config last among file configs (if present and valid).
-        if let Some(explicit_layer) = explicit_layer {
+        if let Some((path, explicit_layer)) = explicit_layer {
+            sources.push(ConfigSource::from_layer("explicit", path, &explicit_layer));
             config.merge_layer(explicit_layer);
         }
 
         // Apply environment variable overrides (highest priority)
+        let mut env_vars: Vec<String> = env::vars()
+            .map(|(key, _)| key)
+            .filter(|key| key.starts_with(&format!("{ENV_PREFIX}_")) && key != ENV_CONFIG_PATH)
+            .collect();
+        env_vars.sort();
+        if !env_vars.is_empty() {
+            sources.push(ConfigSource {
+                layer: "env",
+                path: None,
+                sections: Vec::new(),
+                packs_enabled: Vec::new(),
+                packs_disabled: Vec::new(),
+                env_vars,
+            });
+        }
         config.apply_env_overrides();
 
-        config
+        (config, sources)
     }
 
     /// Load a configuration *layer* from a specific file.
@@ -2476,9 +4593,28 @@ impl Config {
     }
 
     /// Load system-wide configuration.
-    fn load_system_config_layer() -> Option<ConfigLayer> {
+    fn load_system_config_layer() -> Option<(PathBuf, ConfigLayer)> {
         let path = PathBuf::from("/etc/dcg").join(CONFIG_FILE_NAME);
-        Self::load_layer_from_file(&path)
+        Self::load_layer_from_file(&path).map(|layer| (path, layer))
+    }
+
+    /// Load the cached remote policy bundle, keeping only the sections a
+    /// bundle may set (`packs` without local paths, and `overrides`).
+    fn load_remote_config_layer() -> Option<(PathBuf, ConfigLayer)> {
+        let path = crate::remote::cached_policy_path()?;
+        let layer = Self::load_layer_from_file(&path)?;
+        let packs = layer.packs.map(|packs| PacksConfig {
+            custom_paths: Vec::new(),
+            ..packs
+        });
+        Some((
+            path,
+            ConfigLayer {
+                packs,
+                overrides: layer.overrides,
+                ..ConfigLayer::default()
+            },
+        ))
     }
 
     /// Load user configuration.
@@ -2486,14 +4622,14 @@ impl Config {
     /// Checks XDG_CONFIG_HOME, XDG-style (`~/.config/dcg/`), and platform-native paths.
     /// This ensures users can use `~/.config/dcg/config.toml` on all platforms,
     /// including macOS where `dirs::config_dir()` returns `~/Library/Application Support`.
-    fn load_user_config_layer() -> Option<ConfigLayer> {
+    fn load_user_config_layer() -> Option<(PathBuf, ConfigLayer)> {
         // First try XDG_CONFIG_HOME (if set)
         if let Ok(xdg_home) = env::var("XDG_CONFIG_HOME") {
             if let Some(xdg_home) = resolve_config_path_value(&xdg_home, None) {
                 let xdg_path = xdg_home.join("dcg").join(CONFIG_FILE_NAME);
                 if xdg_path.exists() {
                     if let Some(layer) = Self::load_layer_from_file(&xdg_path) {
-                        return Some(layer);
+                        return Some((xdg_path, layer));
                     }
                 }
             }
@@ -2505,7 +4641,7 @@ impl Config {
             let xdg_path = home.join(".config").join("dcg").join(CONFIG_FILE_NAME);
             if xdg_path.exists() {
                 if let Some(layer) = Self::load_layer_from_file(&xdg_path) {
-                    return Some(layer);
+                    return Some((xdg_path, layer));
                 }
             }
         }
@@ -2513,18 +4649,18 @@ impl Config {
         // Fall back to platform-native path (e.g., ~/Library/Application Support/dcg/ on macOS)
         let config_dir = dirs::config_dir()?;
         let path = config_dir.join("dcg").join(CONFIG_FILE_NAME);
-        Self::load_layer_from_file(&path)
+        Self::load_layer_from_file(&path).map(|layer| (path, layer))
     }
 
     /// Load project-level configuration (`.dcg.toml` in repo root).
-    fn load_project_config_layer_from(start_dir: Option<&Path>) -> Option<ConfigLayer> {
+    fn load_project_config_layer_from(start_dir: Option<&Path>) -> Option<(PathBuf, ConfigLayer)> {
         let start_dir = start_dir?;
         let repo_root = find_repo_root(start_dir, REPO_ROOT_SEARCH_MAX_HOPS)?;
         let config_path = repo_root.join(PROJECT_CONFIG_NAME);
         if !config_path.exists() {
             return None;
         }
-        Self::load_layer_from_file(&config_path)
+        Self::load_layer_from_file(&config_path).map(|layer| (config_path, layer))
     }
 
     /// Merge another config layer into this one (other takes priority when set).
@@ -2553,6 +4689,22 @@ impl Config {
             self.merge_overrides_layer(overrides);
         }
 
+        if let Some(protection) = other.protection {
+            self.merge_protection_layer(protection);
+        }
+
+        if let Some(aliases) = other.aliases {
+            self.merge_aliases_layer(aliases);
+        }
+
+        if let Some(trusted_installers) = other.trusted_installers {
+            self.merge_trusted_installers_layer(trusted_installers);
+        }
+
+        if let Some(wrappers) = other.wrappers {
+            self.merge_wrappers_layer(wrappers);
+        }
+
         if let Some(heredoc) = other.heredoc {
             self.merge_heredoc_layer(heredoc);
         }
@@ -2569,6 +4721,26 @@ impl Config {
             self.merge_history_layer(history);
         }
 
+        if let Some(anomaly) = other.anomaly {
+            self.merge_anomaly_layer(anomaly);
+        }
+
+        if let Some(risk) = other.risk {
+            self.merge_risk_layer(risk);
+        }
+
+        if let Some(notifications) = other.notifications {
+            self.merge_notifications_layer(notifications);
+        }
+
+        if let Some(remote) = other.remote {
+            self.merge_remote_layer(remote);
+        }
+
+        if let Some(cache) = other.cache {
+            self.merge_cache_layer(cache);
+        }
+
         if let Some(interactive) = other.interactive {
             self.merge_interactive_layer(interactive);
         }
@@ -2577,6 +4749,22 @@ impl Config {
             self.merge_git_awareness_layer(git_awareness);
         }
 
+        if let Some(kube_context) = other.kube_context {
+            self.merge_kube_context_layer(kube_context);
+        }
+
+        if let Some(suppressions) = other.suppressions {
+            self.merge_suppressions_layer(suppressions);
+        }
+
+        if let Some(trust) = other.trust {
+            self.merge_trust_layer(trust);
+        }
+
+        if let Some(bench) = other.bench {
+            self.merge_bench_layer(bench);
+        }
+
         if let Some(agents) = other.agents {
             self.merge_agents_layer(agents);
         }
@@ -2612,9 +4800,15 @@ impl Config {
         if let Some(check_updates) = general.check_updates {
             self.general.check_updates = check_updates;
         }
+        if let Some(expand_variables) = general.expand_variables {
+            self.general.expand_variables = expand_variables;
+        }
+        if let Some(threshold) = general.expired_allowlist_reminder {
+            self.general.expired_allowlist_reminder = Some(threshold);
+        }
     }
 
-    const fn merge_output_layer(&mut self, output: OutputConfigLayer) {
+    fn merge_output_layer(&mut self, output: OutputConfigLayer) {
         if let Some(highlight_enabled) = output.highlight_enabled {
             self.output.highlight_enabled = Some(highlight_enabled);
         }
@@ -2624,6 +4818,18 @@ impl Config {
         if let Some(high_contrast) = output.high_contrast {
             self.output.high_contrast = Some(high_contrast);
         }
+        if let Some(structured_deny) = output.structured_deny {
+            self.output.structured_deny = Some(structured_deny);
+        }
+        if let Some(locale) = output.locale {
+            self.output.locale = Some(locale);
+        }
+        if let Some(locale_dir) = output.locale_dir {
+            self.output.locale_dir = Some(locale_dir);
+        }
+        if let Some(deny_templates) = output.deny_templates {
+            self.output.deny_templates.merge(deny_templates);
+        }
     }
 
     fn merge_theme_layer(&mut self, theme: ThemeConfigLayer) {
@@ -2653,6 +4859,18 @@ impl Config {
         }
         self.policy.packs.extend(policy.packs);
         self.policy.rules.extend(policy.rules);
+        self.policy
+            .severity_overrides
+            .extend(policy.severity_overrides);
+        self.policy.windows.extend(policy.windows);
+        self.policy.freezes.extend(policy.freezes);
+        self.policy.fail_mode.merge(policy.fail_mode);
+        if policy.external.is_some() {
+            self.policy.external = policy.external;
+        }
+        if policy.rego.is_some() {
+            self.policy.rego = policy.rego;
+        }
     }
 
     fn merge_overrides_layer(&mut self, overrides: OverridesConfig) {
@@ -2660,6 +4878,49 @@ impl Config {
         self.overrides.block.extend(overrides.block);
     }
 
+    fn merge_protection_layer(&mut self, protection: ProtectionConfigLayer) {
+        // Protected paths are additive: a project can protect more, never less.
+        if let Some(paths) = protection.paths {
+            self.protection.paths.patterns.extend(paths.patterns);
+        }
+        if let Some(workspaces) = protection.workspaces {
+            self.protection
+                .workspaces
+                .patterns
+                .extend(workspaces.patterns);
+        }
+        if let Some(rm_preview) = protection.rm_preview {
+            if let Some(enabled) = rm_preview.enabled {
+                self.protection.rm_preview.enabled = enabled;
+            }
+            if let Some(max_entries) = rm_preview.max_entries {
+                self.protection.rm_preview.max_entries = max_entries;
+            }
+            if let Some(timeout_ms) = rm_preview.timeout_ms {
+                self.protection.rm_preview.timeout_ms = timeout_ms;
+            }
+        }
+    }
+
+    fn merge_aliases_layer(&mut self, aliases: AliasesConfigLayer) {
+        if let Some(enabled) = aliases.enabled {
+            self.aliases.enabled = enabled;
+        }
+        if let Some(files) = aliases.files {
+            self.aliases.files.extend(files);
+        }
+    }
+
+    fn merge_trusted_installers_layer(&mut self, trusted: TrustedInstallersConfigLayer) {
+        if let Some(domains) = trusted.domains {
+            for domain in domains {
+                if !self.trusted_installers.domains.contains(&domain) {
+                    self.trusted_installers.domains.push(domain);
+                }
+            }
+        }
+    }
+
     fn merge_heredoc_layer(&mut self, heredoc: HeredocConfig) {
         if heredoc.enabled.is_some() {
             self.heredoc.enabled = heredoc.enabled;
@@ -2741,6 +5002,77 @@ impl Config {
                 self.logging.events.allow = allow;
             }
         }
+        if let Some(json) = logging.json {
+            if let Some(enabled) = json.enabled {
+                self.logging.json.enabled = enabled;
+            }
+            if let Some(path) = json.path {
+                self.logging.json.path = Some(path);
+            }
+            if let Some(max_size_mb) = json.max_size_mb {
+                self.logging.json.max_size_mb = max_size_mb;
+            }
+            if let Some(max_age_hours) = json.max_age_hours {
+                self.logging.json.max_age_hours = max_age_hours;
+            }
+            if let Some(max_files) = json.max_files {
+                self.logging.json.max_files = max_files;
+            }
+            if let Some(compress) = json.compress {
+                self.logging.json.compress = compress;
+            }
+        }
+        if let Some(syslog) = logging.syslog {
+            let target = &mut self.logging.syslog;
+            if let Some(enabled) = syslog.enabled {
+                target.enabled = enabled;
+            }
+            if let Some(kind) = syslog.target {
+                target.target = kind;
+            }
+            if let Some(transport) = syslog.transport {
+                target.transport = transport;
+            }
+            if let Some(address) = syslog.address {
+                target.address = address;
+            }
+            if let Some(facility) = syslog.facility {
+                target.facility = facility;
+            }
+            if let Some(app_name) = syslog.app_name {
+                target.app_name = app_name;
+            }
+            if let Some(deny) = syslog.deny {
+                target.deny = deny;
+            }
+            if let Some(warn) = syslog.warn {
+                target.warn = warn;
+            }
+            if let Some(severities) = syslog.severities {
+                target.severities = severities;
+            }
+        }
+        if let Some(otel) = logging.otel {
+            let target = &mut self.logging.otel;
+            if let Some(enabled) = otel.enabled {
+                target.enabled = enabled;
+            }
+            if let Some(endpoint) = otel.endpoint {
+                target.endpoint = endpoint;
+            }
+            if let Some(service_name) = otel.service_name {
+                target.service_name = service_name;
+            }
+            if let Some(timeout_ms) = otel.timeout_ms {
+                target.timeout_ms = timeout_ms;
+            }
+            if let Some(traces) = otel.traces {
+                target.traces = traces;
+            }
+            if let Some(metrics) = otel.metrics {
+                target.metrics = metrics;
+            }
+        }
     }
 
     fn merge_history_layer(&mut self, history: HistoryConfigLayer) {
@@ -2752,13 +5084,126 @@ impl Config {
         }
         if let Some(retention_days) = history.retention_days {
             self.history.retention_days = retention_days;
+            self.history.retention.max_age_days = retention_days;
         }
         if let Some(max_size_mb) = history.max_size_mb {
             self.history.max_size_mb = max_size_mb;
+            self.history.retention.max_db_size_mb = u64::from(max_size_mb);
         }
         if let Some(database_path) = history.database_path {
             self.history.database_path = Some(database_path);
         }
+        if let Some(retention) = history.retention {
+            let target = &mut self.history.retention;
+            if let Some(enabled) = retention.enabled {
+                target.enabled = enabled;
+            }
+            if let Some(max_rows) = retention.max_rows {
+                target.max_rows = max_rows;
+            }
+            if let Some(max_age_days) = retention.max_age_days {
+                target.max_age_days = max_age_days;
+            }
+            if let Some(max_db_size_mb) = retention.max_db_size_mb {
+                target.max_db_size_mb = max_db_size_mb;
+            }
+            if let Some(compaction_interval_secs) = retention.compaction_interval_secs {
+                target.compaction_interval_secs = compaction_interval_secs;
+            }
+        }
+    }
+
+    fn merge_notifications_layer(&mut self, notifications: NotificationsConfigLayer) {
+        if let Some(enabled) = notifications.enabled {
+            self.notifications.enabled = enabled;
+        }
+        if let Some(min_severity) = notifications.min_severity {
+            self.notifications.min_severity = min_severity;
+        }
+        if let Some(webhooks) = notifications.webhooks {
+            self.notifications.webhooks = webhooks;
+        }
+        if let Some(batch_window_ms) = notifications.batch_window_ms {
+            self.notifications.batch_window_ms = batch_window_ms;
+        }
+        if let Some(timeout_ms) = notifications.timeout_ms {
+            self.notifications.timeout_ms = timeout_ms;
+        }
+        if let Some(failure_threshold) = notifications.failure_threshold {
+            self.notifications.failure_threshold = failure_threshold;
+        }
+        if let Some(cooldown_secs) = notifications.cooldown_secs {
+            self.notifications.cooldown_secs = cooldown_secs;
+        }
+    }
+
+    fn merge_remote_layer(&mut self, remote: RemoteConfigLayer) {
+        if let Some(enabled) = remote.enabled {
+            self.remote.enabled = enabled;
+        }
+        if let Some(url) = remote.url {
+            self.remote.url = Some(url);
+        }
+        if let Some(public_key) = remote.public_key {
+            self.remote.public_key = Some(public_key);
+        }
+        if let Some(signature_url) = remote.signature_url {
+            self.remote.signature_url = Some(signature_url);
+        }
+        if let Some(interval_secs) = remote.interval_secs {
+            self.remote.interval_secs = interval_secs;
+        }
+        if let Some(timeout_ms) = remote.timeout_ms {
+            self.remote.timeout_ms = timeout_ms;
+        }
+    }
+
+    const fn merge_anomaly_layer(&mut self, anomaly: AnomalyConfigLayer) {
+        if let Some(enabled) = anomaly.enabled {
+            self.anomaly.enabled = enabled;
+        }
+        if let Some(deny_threshold) = anomaly.deny_threshold {
+            self.anomaly.deny_threshold = deny_threshold;
+        }
+        if let Some(window_secs) = anomaly.window_secs {
+            self.anomaly.window_secs = window_secs;
+        }
+    }
+
+    const fn merge_risk_layer(&mut self, risk: RiskConfigLayer) {
+        if let Some(enabled) = risk.enabled {
+            self.risk.enabled = enabled;
+        }
+        if let Some(threshold) = risk.threshold {
+            self.risk.threshold = threshold;
+        }
+        if let Some(deny_points) = risk.deny_points {
+            self.risk.deny_points = deny_points;
+        }
+        if let Some(warn_points) = risk.warn_points {
+            self.risk.warn_points = warn_points;
+        }
+        if let Some(idle_reset_secs) = risk.idle_reset_secs {
+            self.risk.idle_reset_secs = idle_reset_secs;
+        }
+    }
+
+    fn merge_cache_layer(&mut self, cache: EvalCacheConfigLayer) {
+        if let Some(enabled) = cache.enabled {
+            self.cache.enabled = enabled;
+        }
+        if let Some(max_entries) = cache.max_entries {
+            self.cache.max_entries = max_entries;
+        }
+        if let Some(ttl_secs) = cache.ttl_secs {
+            self.cache.ttl_secs = ttl_secs;
+        }
+        if let Some(quick_allow) = cache.quick_allow {
+            self.cache.quick_allow = quick_allow;
+        }
+        if let Some(path) = cache.path {
+            self.cache.path = Some(path);
+        }
     }
 
     fn merge_interactive_layer(&mut self, interactive: InteractiveConfigLayer) {
@@ -2812,6 +5257,57 @@ impl Config {
         }
     }
 
+    fn merge_kube_context_layer(&mut self, kube_context: KubeContextConfigLayer) {
+        if let Some(enabled) = kube_context.enabled {
+            self.kube_context.enabled = enabled;
+        }
+        if let Some(production_contexts) = kube_context.production_contexts {
+            self.kube_context.production_contexts = production_contexts;
+        }
+        if let Some(development_contexts) = kube_context.development_contexts {
+            self.kube_context.development_contexts = development_contexts;
+        }
+        if let Some(cache_ttl_secs) = kube_context.cache_ttl_secs {
+            self.kube_context.cache_ttl_secs = cache_ttl_secs;
+        }
+        if let Some(timeout_ms) = kube_context.timeout_ms {
+            self.kube_context.timeout_ms = timeout_ms;
+        }
+    }
+
+    const fn merge_suppressions_layer(&mut self, suppressions: SuppressionsConfigLayer) {
+        if let Some(inline) = suppressions.inline {
+            self.suppressions.inline = inline;
+        }
+        if let Some(require_confirmation) = suppressions.require_confirmation {
+            self.suppressions.require_confirmation = require_confirmation;
+        }
+    }
+
+    const fn merge_trust_layer(&mut self, trust: TrustConfigLayer) {
+        if let Some(ledger) = trust.ledger {
+            self.trust.ledger = ledger;
+        }
+        if let Some(promote_after) = trust.promote_after {
+            self.trust.promote_after = promote_after;
+        }
+    }
+
+    const fn merge_bench_layer(&mut self, bench: BenchConfigLayer) {
+        if let Some(cold_start_p99_ms) = bench.cold_start_p99_ms {
+            self.bench.cold_start_p99_ms = cold_start_p99_ms;
+        }
+        if let Some(quick_reject_p99_us) = bench.quick_reject_p99_us {
+            self.bench.quick_reject_p99_us = quick_reject_p99_us;
+        }
+        if let Some(pack_eval_p99_us) = bench.pack_eval_p99_us {
+            self.bench.pack_eval_p99_us = pack_eval_p99_us;
+        }
+        if let Some(heredoc_p99_ms) = bench.heredoc_p99_ms {
+            self.bench.heredoc_p99_ms = heredoc_p99_ms;
+        }
+    }
+
     fn merge_agents_layer(&mut self, agents: AgentsConfig) {
         // Merge default profile
         self.agents.default = agents.default;
@@ -2886,6 +5382,12 @@ impl Config {
             self.output.high_contrast = Some(parsed);
         }
 
+        // DCG_STRUCTURED_DENY=1
+        if let Some(structured_deny) = get_env("DCG_STRUCTURED_DENY") {
+            let parsed = parse_env_bool(&structured_deny).unwrap_or(true);
+            self.output.structured_deny = Some(parsed);
+        }
+
         // -----------------------------------------------------------------
         // Heredoc scanning (env overrides)
         // -----------------------------------------------------------------
@@ -2934,6 +5436,15 @@ impl Config {
             self.policy.observe_until = ObserveUntil::parse(&observe_until);
         }
 
+        // DCG_POLICY_FAIL_MODE=open|closed (sets the default for all classes)
+        if let Some(mode) = get_env(&format!("{ENV_PREFIX}_POLICY_FAIL_MODE")) {
+            match mode.trim().to_ascii_lowercase().as_str() {
+                "open" => self.policy.fail_mode.default = Some(FailMode::Open),
+                "closed" => self.policy.fail_mode.default = Some(FailMode::Closed),
+                _ => {}
+            }
+        }
+
         // -----------------------------------------------------------------
         // History config (env overrides)
         // -----------------------------------------------------------------
@@ -3108,7 +5619,15 @@ impl Config {
     /// Get effective heredoc scanning settings for evaluation.
     #[must_use]
     pub fn heredoc_settings(&self) -> HeredocSettings {
-        self.heredoc.settings()
+        let mut settings = self.heredoc.settings();
+        // A closed fail mode is at least as strict as turning the fallback off.
+        if self.policy.fail_mode.is_closed(FailureClass::Timeout) {
+            settings.fallback_on_timeout = false;
+        }
+        if self.policy.fail_mode.is_closed(FailureClass::ParseError) {
+            settings.fallback_on_parse_error = false;
+        }
+        settings
     }
 
     /// Get the path to the user config file (creates dir if needed).
@@ -3160,6 +5679,22 @@ impl Config {
     }
 
     /// Generate a default configuration with common packs enabled.
+    fn merge_wrappers_layer(&mut self, wrappers: WrappersConfigLayer) {
+        if let Some(ssh) = wrappers.ssh {
+            self.wrappers.ssh = ssh;
+        }
+        if let Some(extra) = wrappers.extra {
+            for name in extra {
+                if !self.wrappers.extra.contains(&name) {
+                    self.wrappers.extra.push(name);
+                }
+            }
+        }
+        if let Some(production_hosts) = wrappers.production_hosts {
+            self.wrappers.production_hosts = production_hosts;
+        }
+    }
+
     #[must_use]
     pub fn generate_default() -> Self {
         Self {
@@ -3177,11 +5712,24 @@ impl Config {
             },
             policy: PolicyConfig::default(),
             overrides: OverridesConfig::default(),
+            protection: ProtectionConfig::default(),
+            aliases: AliasesConfig::default(),
+            trusted_installers: TrustedInstallersConfig::default(),
+            wrappers: WrappersConfig::default(),
             heredoc: HeredocConfig::default(),
             confidence: ConfidenceConfig::default(),
             logging: crate::logging::LoggingConfig::default(),
             history: HistoryConfig::default(),
+            anomaly: AnomalyConfig::default(),
+            risk: RiskConfig::default(),
+            notifications: NotificationsConfig::default(),
+            remote: RemoteConfig::default(),
+            cache: EvalCacheConfig::default(),
             git_awareness: GitAwarenessConfig::default(),
+            kube_context: KubeContextConfig::default(),
+            suppressions: SuppressionsConfig::default(),
+            trust: TrustConfig::default(),
+            bench: BenchConfig::default(),
             agents: AgentsConfig::default(),
             projects: std::collections::HashMap::new(),
             interactive: crate::interactive::InteractiveConfig::default(),
@@ -3208,9 +5756,17 @@ verbose = false
 # Check for updates in the background (shows a notice if available)
 # check_updates = true
 
+# Resolve $VAR / ${VAR} from the environment before matching, so
+# `DIR=/; rm -rf $DIR` is judged as `rm -rf /`
+# expand_variables = true
+
 # Hook evaluation budget override (milliseconds)
 # hook_timeout_ms = 200
 
+# Remind about `dcg allowlist prune` in denial output once more than this
+# many allowlist entries have expired
+# expired_allowlist_reminder = 0
+
 #─────────────────────────────────────────────────────────────
 # OUTPUT CONFIGURATION
 #─────────────────────────────────────────────────────────────
@@ -3227,6 +5783,18 @@ verbose = false
 # High-contrast mode (ASCII borders + black/white palette).
 # high_contrast = false
 
+# Add `schemaVersion` and a structured `details` object (packId, patternName,
+# severity, span, suggestion) to the hook's deny JSON, so tools don't have to
+# parse permissionDecisionReason.
+# structured_deny = false
+
+# Locale for denial text and CLI messages. Defaults to DCG_LOCALE, then
+# LC_ALL / LC_MESSAGES / LANG, then "en". Translations are Fluent-style
+# `<locale>.ftl` catalogs in ~/.config/dcg/locales (or locale_dir); messages
+# missing from a catalog fall back to English.
+# locale = "de"
+# locale_dir = "~/.config/dcg/locales"
+
 #─────────────────────────────────────────────────────────────
 # THEME CONFIGURATION
 #─────────────────────────────────────────────────────────────
@@ -3256,23 +5824,45 @@ verbose = false
 #   database.mongodb      - MongoDB destructive commands
 #   database.redis        - Redis FLUSH commands
 #   database.sqlite       - SQLite destructive commands
+#   database.cassandra    - Cassandra/ScyllaDB cqlsh and nodetool commands
+#   database.migrations   - prisma migrate reset, rails db:drop, flyway clean
+#   kv.etcd               - etcdctl del --prefix, compact, member remove
+#   kv.consul             - consul kv delete -recurse, raft remove-peer
 #   containers.docker     - Docker destructive commands
 #   containers.compose    - Docker Compose destructive commands
 #   containers.podman     - Podman destructive commands
 #   kubernetes.kubectl    - kubectl delete commands
 #   kubernetes.helm       - Helm uninstall commands
 #   kubernetes.kustomize  - Kustomize delete commands
+#   kubernetes.argocd     - argocd app delete, proj delete, repo rm
+#   kubernetes.flux       - flux uninstall, delete --silent, suspend on prod
 #   cloud.aws             - AWS CLI destructive commands
 #   cloud.gcp             - GCP CLI destructive commands
 #   cloud.azure           - Azure CLI destructive commands
+#   cloud.cloudformation  - CloudFormation delete-stack, cdk destroy
+#   paas.fly              - fly apps/volumes destroy
+#   paas.heroku           - heroku apps:destroy, pg:reset
+#   paas.netlify          - netlify sites:delete, env:unset
+#   paas.railway          - railway down, railway delete
+#   paas.render           - render services delete
+#   paas.vercel           - vercel remove, project rm, env rm
 #   infrastructure.terraform - Terraform destroy commands
-#   infrastructure.ansible   - Ansible state=absent patterns
-#   infrastructure.pulumi    - Pulumi destroy commands
+#   infrastructure.ansible   - Ad-hoc shell/file state=absent, safety extra-vars
+#   infrastructure.pulumi    - Pulumi destroy, stack rm --force, secrets provider changes
+#   infrastructure.cdktf     - cdktf destroy, deploy --auto-approve
 #   system.disk           - Disk operations (dd, mkfs, fdisk)
+#   system.network        - iptables -F, nft flush ruleset, ufw disable, route del default
 #   system.permissions    - Dangerous permission changes
+#   system.scheduling     - crontab -r, rm /etc/cron.d/*, disabling cron, launchctl unload
 #   system.services       - Service management commands
+#   system.sessions       - tmux kill-server, kill-session -a, screen -X quit
+#   system.users          - userdel -r, passwd -d, /etc/sudoers edits, ~/.ssh deletion
+#   system.virtualization - machinectl, virsh, VBoxManage, multipass, vagrant
 #   strict_git            - Extra paranoid git protections
 #   package_managers      - npm unpublish, cargo yank, etc.
+#   package_managers.cargo - cargo publish/yank, rustup self uninstall, cargo clean
+#   package_managers.homebrew - brew uninstall --force/--zap, autoremove, untap
+#   package_managers.nix  - nix-collect-garbage -d, nix store delete, rollbacks
 
 enabled = [
     "database.postgresql",
@@ -3331,6 +5921,29 @@ custom_paths = [
 #
 # Safety: Critical rules are only loosened via explicit per-rule overrides.
 
+[policy.severity_overrides]
+# Remap the severity of a pack or rule (critical/high/medium/low).
+# Windows and severity defaults then use the remapped severity.
+# Inspect the result with `dcg policy show`.
+# Examples:
+# "containers.docker:image-prune" = "low"
+# "kubernetes.helm" = "medium"
+
+# Time-of-day windows (first match wins; times are local unless timezone = "utc").
+# Example: deny High during business hours, warn otherwise.
+# [[policy.windows]]
+# severities = ["high"]
+# hours = "09:00-18:00"
+# days = ["mon", "tue", "wed", "thu", "fri"]
+# mode = "deny"
+# otherwise = "warn"
+
+# Deployment freezes escalate every matched rule (default mode: deny).
+# [[policy.freezes]]
+# name = "year-end freeze"
+# start = "2026-12-20"
+# end = "2027-01-05"
+
 #─────────────────────────────────────────────────────────────
 # CUSTOM OVERRIDES
 #─────────────────────────────────────────────────────────────
@@ -3359,12 +5972,79 @@ block = [
 ]
 
 #─────────────────────────────────────────────────────────────
-# HEREDOC / INLINE SCRIPT SCANNING
+# PROTECTED PATHS
 #─────────────────────────────────────────────────────────────
 
-[heredoc]
-# Enable scanning for heredocs and inline scripts (python -c, bash -c, etc.).
-enabled = true
+[protection.paths]
+# Deny rm/mv/truncate/dd when a target resolves into one of these globs.
+# Targets are resolved against the working directory, `..` is normalized,
+# $VARS are expanded, and symlinks are followed before matching.
+# sqlite3 sessions on a protected database are denied unless -readonly.
+patterns = [
+    # "~/projects/prod-*",
+    # "/etc/**",
+    # "/srv/app/*.db",
+]
+
+[protection.workspaces]
+# Inside these directories, log-only (Low severity) matches such as
+# `cargo clean` are raised to Medium and warn instead.
+patterns = [
+    # "~/work/monorepo",
+]
+
+[protection.rm_preview]
+# When an `rm` is denied, walk its targets (read-only) and add file count, total
+# size, and uncommitted git changes to the deny reason.
+enabled = false
+max_entries = 10000
+timeout_ms = 100
+
+#─────────────────────────────────────────────────────────────
+# SHELL ALIASES
+#─────────────────────────────────────────────────────────────
+
+[aliases]
+# Expand shell aliases/functions (e.g. `alias grf='git reset --hard'`) before
+# matching. Refresh the snapshot with `dcg aliases snapshot`.
+enabled = true
+
+# Definition files (`alias -p` / `typeset -f` output). Empty = the snapshot in
+# the dcg config directory.
+files = []
+
+#─────────────────────────────────────────────────────────────
+# TRUSTED INSTALLERS
+#─────────────────────────────────────────────────────────────
+
+[trusted_installers]
+# `curl ... | sh`, `wget -qO- ... | bash` and `iex (irm ...)` are blocked by
+# core.pipe_shell unless every URL is https:// on one of these domains (or a
+# subdomain). Entries here are added to the built-in list.
+domains = ["sh.rustup.rs", "astral.sh", "bun.sh", "deno.land", "get.pnpm.io"]
+
+#─────────────────────────────────────────────────────────────
+# WRAPPER COMMANDS
+#─────────────────────────────────────────────────────────────
+
+[wrappers]
+# sudo, doas, env, nice, ionice, timeout, nohup, stdbuf and xargs are always
+# unwrapped so the command they run is what gets checked.
+# Check the remote command of `ssh host '<cmd>'` as well.
+ssh = true
+# More commands that run their arguments as a command (e.g. "chronic").
+extra = []
+# Raise the severity of matches in commands run over ssh on these hosts by
+# one level (high -> critical). `*` wildcards, case-insensitive.
+production_hosts = []
+
+#─────────────────────────────────────────────────────────────
+# HEREDOC / INLINE SCRIPT SCANNING
+#─────────────────────────────────────────────────────────────
+
+[heredoc]
+# Enable scanning for heredocs and inline scripts (python -c, bash -c, etc.).
+enabled = true
 
 # Extraction timeout budget (milliseconds). Parsing/matching has its own budget.
 timeout_ms = 50
@@ -3381,6 +6061,41 @@ max_heredocs = 10
 fallback_on_parse_error = true
 fallback_on_timeout = true
 
+#─────────────────────────────────────────────────────────────
+# STRUCTURED LOGGING
+#─────────────────────────────────────────────────────────────
+
+# Newline-delimited JSON decision log for SIEM ingestion (opt-in).
+# Uses the [logging.events] filter and [logging.redaction] settings.
+# [logging.json]
+# enabled = true
+# path = "~/.config/dcg/decisions.ndjson"
+# max_size_mb = 10      # rotate when the active file would exceed this size
+# max_age_hours = 24    # rotate when the first entry is older than this
+# max_files = 7         # rotated segments to keep
+# compress = true       # gzip rotated segments
+
+# Forward deny/warn events to syslog (RFC 5424) or journald (opt-in).
+# [logging.syslog]
+# enabled = true
+# target = "syslog"              # "syslog" | "journald"
+# transport = "udp"              # "udp" | "tcp" (syslog only)
+# address = "127.0.0.1:514"
+# facility = "local0"
+# deny = true
+# warn = true
+# severities = ["critical", "high"]   # empty = all severities
+
+# Export evaluation spans and per-pack decision counters over OTLP/HTTP
+# (opt-in; needs a build with the `otel` feature).
+# [logging.otel]
+# enabled = true
+# endpoint = "http://localhost:4318"   # OTEL_EXPORTER_OTLP_ENDPOINT overrides
+# service_name = "dcg"
+# timeout_ms = 500
+# traces = true
+# metrics = true
+
 #─────────────────────────────────────────────────────────────
 # HISTORY
 #─────────────────────────────────────────────────────────────
@@ -3392,13 +6107,149 @@ enabled = false
 # Redaction mode for stored commands: "pattern" | "full" | "none"
 redaction_mode = "pattern"
 
-# Retention window and database size limits.
-retention_days = 90
-max_size_mb = 500
-
 # Optional database path override.
 # database_path = "~/.config/dcg/history.db"
 
+[history.retention]
+# Background compaction in the history writer (at most once per interval).
+# Reclaim disk space afterwards with `dcg history vacuum`. 0 = no limit.
+enabled = true
+max_rows = 1000000
+max_age_days = 90
+max_db_size_mb = 500
+compaction_interval_secs = 3600
+
+#─────────────────────────────────────────────────────────────
+# ANOMALY DETECTION
+#─────────────────────────────────────────────────────────────
+
+[anomaly]
+# When an agent is denied `deny_threshold` times within `window_secs`, treat
+# it as fighting the guard: warn-mode rules are escalated to deny and denials
+# tell the agent to stop retrying. Counts come from history (needs
+# [history] enabled = true).
+enabled = true
+deny_threshold = 5
+window_secs = 120
+
+#─────────────────────────────────────────────────────────────
+# SESSION RISK SCORING
+#─────────────────────────────────────────────────────────────
+
+[risk]
+# Keep a running risk score per agent session. Denied commands add
+# `deny_points`, warned or logged matches add `warn_points` (critical rules
+# count double). At `threshold`, warn-mode rules are escalated to deny.
+# Scores live in history (needs [history] enabled = true).
+enabled = false
+threshold = 100
+deny_points = 20
+warn_points = 5
+idle_reset_secs = 3600
+
+#─────────────────────────────────────────────────────────────
+# NOTIFICATIONS
+#─────────────────────────────────────────────────────────────
+
+[notifications]
+# Post denials to webhooks. The hook only queues the event; a background
+# `dcg notify flush` batches and sends it, so hook latency is unaffected.
+# Commands are redacted per [logging.redaction]. Test with `dcg notify test`.
+enabled = false
+min_severity = "high"      # "critical" | "high" | "medium" | "all"
+batch_window_ms = 2000
+timeout_ms = 5000
+# After this many consecutive failures a webhook is skipped for cooldown_secs.
+failure_threshold = 3
+cooldown_secs = 300
+
+# [[notifications.webhooks]]
+# url = "https://hooks.slack.com/services/T000/B000/XXXX"
+# format = "slack"          # "json" | "slack" | "discord"
+
+#─────────────────────────────────────────────────────────────
+# REMOTE POLICY
+#─────────────────────────────────────────────────────────────
+
+[remote]
+# Fetch a signed team policy bundle (packs, overrides, allowlist, custom
+# packs) with `dcg sync`. The hook refreshes it in the background every
+# interval_secs; bundles whose signature does not verify are rejected.
+enabled = false
+# url = "https://policy.example.com/dcg/policy.toml"
+# public_key = "base64 Ed25519 public key"
+# signature_url = "https://policy.example.com/dcg/policy.toml.sig"
+interval_secs = 3600
+timeout_ms = 10000
+
+#─────────────────────────────────────────────────────────────
+# EVALUATION CACHE
+#─────────────────────────────────────────────────────────────
+
+[cache]
+# Remember commands that evaluated to a clean allow so identical re-runs in
+# the same directory skip evaluation. Denials and allowlisted commands are
+# never cached; any config, pack, or version change invalidates the cache.
+enabled = false
+max_entries = 1000
+ttl_secs = 3600
+
+# Keep a compact Bloom filter of commands that were a clean allow at least
+# twice and skip evaluation for them without reading the cache file.
+quick_allow = false
+
+# Optional cache file path override.
+# path = "~/.cache/dcg/eval_cache.json"
+
+#─────────────────────────────────────────────────────────────
+# KUBERNETES CONTEXT
+#─────────────────────────────────────────────────────────────
+
+[kube_context]
+# Adjust kubernetes.* severities by target context (--context, or the
+# cached `kubectl config current-context`). Production contexts raise
+# severity one level; development contexts lower High/Medium one level.
+enabled = false
+production_contexts = ["*prod*"]
+development_contexts = ["kind-*", "minikube", "docker-desktop", "k3d-*", "rancher-desktop"]
+cache_ttl_secs = 60
+timeout_ms = 1000
+
+#─────────────────────────────────────────────────────────────
+# INLINE SUPPRESSIONS
+#─────────────────────────────────────────────────────────────
+
+[suppressions]
+# Honor `# dcg:allow rule=<pack>:<pattern> reason="..."` comments at the end
+# of a command. The annotation only clears the rule it names, for that one
+# command, and every use is written to the audit log.
+inline = false
+# Keep the command denied until a human confirms it with `dcg allow-once`.
+# Critical rules always need confirmation.
+require_confirmation = true
+
+#─────────────────────────────────────────────────────────────
+# TRUST LEDGER
+#─────────────────────────────────────────────────────────────
+
+[trust]
+# Record every `dcg allow-once` approval per repository.
+ledger = true
+# After this many approvals of the same rule in a repository, `dcg promote`
+# offers to add it to the project allowlist.
+promote_after = 3
+
+#─────────────────────────────────────────────────────────────
+# BENCHMARK THRESHOLDS
+#─────────────────────────────────────────────────────────────
+
+[bench]
+# `dcg bench` exits non-zero when a stage's p99 latency exceeds these.
+cold_start_p99_ms = 200
+quick_reject_p99_us = 50
+pack_eval_p99_us = 1000
+heredoc_p99_ms = 20
+
 #─────────────────────────────────────────────────────────────
 # PROJECT-SPECIFIC OVERRIDES
 #─────────────────────────────────────────────────────────────
@@ -3632,6 +6483,39 @@ database_path = "/tmp/dcg-history.db"
         );
     }
 
+    #[test]
+    fn test_config_merge_layer_history_retention() {
+        let mut config = Config::default();
+        assert!(config.history.retention.enabled);
+        assert_eq!(
+            config.history.retention.max_rows,
+            HistoryRetentionConfig::DEFAULT_MAX_ROWS
+        );
+
+        let user: ConfigLayer = toml::from_str(
+            r"
+[history]
+retention_days = 30
+",
+        )
+        .expect("layer parses");
+        let project: ConfigLayer = toml::from_str(
+            r"
+[history.retention]
+max_rows = 5000
+max_db_size_mb = 0
+",
+        )
+        .expect("layer parses");
+        config.merge_layer(user);
+        config.merge_layer(project);
+
+        let policy = config.history.retention.policy();
+        assert_eq!(policy.max_age_days, 30, "legacy key maps onto retention");
+        assert_eq!(policy.max_rows, 5000);
+        assert_eq!(policy.max_db_size_bytes, 0);
+    }
+
     #[test]
     fn test_history_redaction_mode_parsing() {
         assert_eq!(
@@ -3743,6 +6627,10 @@ database_path = "/tmp/dcg-history.db"
             highlight_enabled: Some(false),
             explanations_enabled: Some(false),
             high_contrast: Some(false),
+            structured_deny: None,
+            locale: None,
+            locale_dir: None,
+            deny_templates: DenyTemplatesConfig::default(),
         };
         assert!(
             !config.highlight_enabled(),
@@ -3761,6 +6649,10 @@ database_path = "/tmp/dcg-history.db"
             highlight_enabled: Some(true),
             explanations_enabled: Some(true),
             high_contrast: Some(false),
+            structured_deny: None,
+            locale: None,
+            locale_dir: None,
+            deny_templates: DenyTemplatesConfig::default(),
         };
         assert!(config.highlight_enabled());
         assert!(config.explanations_enabled());
@@ -3773,6 +6665,10 @@ database_path = "/tmp/dcg-history.db"
             highlight_enabled: Some(true),
             explanations_enabled: Some(false),
             high_contrast: Some(false),
+            structured_deny: None,
+            locale: None,
+            locale_dir: None,
+            deny_templates: DenyTemplatesConfig::default(),
         };
         assert!(
             config1.highlight_enabled(),
@@ -3787,6 +6683,10 @@ database_path = "/tmp/dcg-history.db"
             highlight_enabled: Some(false),
             explanations_enabled: Some(true),
             high_contrast: Some(false),
+            structured_deny: None,
+            locale: None,
+            locale_dir: None,
+            deny_templates: DenyTemplatesConfig::default(),
         };
         assert!(
             !config2.highlight_enabled(),
@@ -3821,6 +6721,16 @@ use_color = false
         assert!(config.output.high_contrast_enabled());
     }
 
+    #[test]
+    fn test_env_structured_deny_override() {
+        let mut config = Config::default();
+        assert!(!config.output.structured_deny_enabled());
+        let env_map: std::collections::HashMap<&str, &str> =
+            std::collections::HashMap::from([("DCG_STRUCTURED_DENY", "1")]);
+        config.apply_env_overrides_from(|key| env_map.get(key).map(|v| (*v).to_string()));
+        assert!(config.output.structured_deny_enabled());
+    }
+
     #[test]
     fn test_output_config_from_toml_both_disabled() {
         let input = r"
@@ -3942,6 +6852,81 @@ explanations_enabled = true
         );
     }
 
+    #[test]
+    fn test_deny_templates_most_specific_wins() {
+        use crate::packs::Severity;
+
+        let config: Config = toml::from_str(
+            r#"
+[output.deny_templates]
+default = "Blocked: {reason}"
+critical = "CRITICAL: {reason} See https://wiki.example.com/runbooks/dcg"
+[output.deny_templates.packs]
+"core.git" = "Git: {reason} (try: {suggestion})"
+database = "DB change blocked. Ask #dba. {allow_once_code}"
+"#,
+        )
+        .expect("config parses");
+        let templates = &config.output.deny_templates;
+
+        assert_eq!(
+            templates.template_for(Some("core.git"), Some(Severity::Critical)),
+            Some("Git: {reason} (try: {suggestion})")
+        );
+        assert_eq!(
+            templates.template_for(Some("database.postgresql"), Some(Severity::High)),
+            Some("DB change blocked. Ask #dba. {allow_once_code}")
+        );
+        assert_eq!(
+            templates.template_for(Some("core.filesystem"), Some(Severity::Critical)),
+            Some("CRITICAL: {reason} See https://wiki.example.com/runbooks/dcg")
+        );
+        assert_eq!(
+            templates.template_for(Some("core.filesystem"), Some(Severity::High)),
+            Some("Blocked: {reason}")
+        );
+        assert!(templates.unknown_placeholders().is_empty());
+        assert_eq!(
+            Config::default()
+                .output
+                .deny_templates
+                .template_for(Some("core.git"), Some(Severity::High)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_deny_templates_layer_merge_and_unknown_placeholders() {
+        let mut base = Config::default();
+        base.output.deny_templates.default = Some("Blocked: {reason}".to_string());
+        base.output
+            .deny_templates
+            .packs
+            .insert("core.git".to_string(), "Git: {reason}".to_string());
+
+        let layer: ConfigLayer = toml::from_str(
+            r#"
+[output.deny_templates]
+high = "{reason} {{not a placeholder}} {runbook}"
+[output.deny_templates.packs]
+"core.git" = "Project git policy: {reason}"
+"#,
+        )
+        .expect("layer parses");
+        base.merge_layer(layer);
+
+        let templates = &base.output.deny_templates;
+        assert_eq!(templates.default.as_deref(), Some("Blocked: {reason}"));
+        assert_eq!(
+            templates.packs.get("core.git").map(String::as_str),
+            Some("Project git policy: {reason}")
+        );
+        assert_eq!(
+            templates.unknown_placeholders(),
+            vec!["runbook".to_string()]
+        );
+    }
+
     #[test]
     fn test_output_config_mixed_toml_scenarios() {
         // Test various mixed scenarios
@@ -4058,215 +7043,669 @@ enabled = ["database.postgresql"]
             "Safe command should still be allowed with disabled output toggles"
         );
 
-        // Both results should have the same decision
-        assert_eq!(
-            std::mem::discriminant(&result_default.decision),
-            std::mem::discriminant(&result_disabled.decision),
-            "Output config should not affect allow decision"
-        );
+        // Both results should have the same decision
+        assert_eq!(
+            std::mem::discriminant(&result_default.decision),
+            std::mem::discriminant(&result_disabled.decision),
+            "Output config should not affect allow decision"
+        );
+    }
+
+    #[test]
+    fn test_output_config_does_not_affect_deny_decision() {
+        // Test that output config toggles do NOT affect the evaluator's deny decision.
+        use crate::allowlist::LayeredAllowlist;
+        use crate::evaluator::{EvaluationDecision, evaluate_command};
+        use crate::packs::REGISTRY;
+
+        // Dangerous command: "git reset --hard HEAD" should always be denied
+        let command = "git reset --hard HEAD";
+
+        // Config with defaults (both true)
+        let config_default = Config::default();
+        let enabled_packs = config_default.enabled_pack_ids();
+        let keywords = REGISTRY.collect_enabled_keywords(&enabled_packs);
+        let keyword_refs: Vec<&str> = keywords.iter().map(|s| &**s).collect();
+        let overrides_default = config_default.overrides.compile();
+        let allowlists = LayeredAllowlist::default();
+
+        let result_default = evaluate_command(
+            command,
+            &config_default,
+            &keyword_refs,
+            &overrides_default,
+            &allowlists,
+        );
+        assert!(
+            matches!(result_default.decision, EvaluationDecision::Deny),
+            "Destructive command should be denied with default config"
+        );
+
+        // Config with both toggles disabled
+        let mut config_disabled = Config::default();
+        config_disabled.output.highlight_enabled = Some(false);
+        config_disabled.output.explanations_enabled = Some(false);
+        let overrides_disabled = config_disabled.overrides.compile();
+
+        let result_disabled = evaluate_command(
+            command,
+            &config_disabled,
+            &keyword_refs,
+            &overrides_disabled,
+            &allowlists,
+        );
+        assert!(
+            matches!(result_disabled.decision, EvaluationDecision::Deny),
+            "Destructive command should still be denied with disabled output toggles"
+        );
+
+        // Both results should have the same decision AND same pattern info
+        assert_eq!(
+            std::mem::discriminant(&result_default.decision),
+            std::mem::discriminant(&result_disabled.decision),
+            "Output config should not affect deny decision"
+        );
+
+        // Pattern info should also be the same
+        assert_eq!(
+            result_default.pattern_info.as_ref().map(|p| &p.reason),
+            result_disabled.pattern_info.as_ref().map(|p| &p.reason),
+            "Pattern info reason should be identical regardless of output config"
+        );
+    }
+
+    #[test]
+    fn test_output_config_toggles_are_purely_cosmetic() {
+        // Comprehensive test: verify output toggles have zero effect on evaluation
+        use crate::allowlist::LayeredAllowlist;
+        use crate::evaluator::{EvaluationDecision, evaluate_command};
+        use crate::packs::REGISTRY;
+
+        let test_cases = [
+            ("echo hello", EvaluationDecision::Allow),      // Safe
+            ("git status", EvaluationDecision::Allow),      // Safe git command
+            ("git reset --hard", EvaluationDecision::Deny), // Destructive
+            ("rm -rf /", EvaluationDecision::Deny),         // Destructive
+        ];
+
+        let toggle_combinations = [
+            (Some(true), Some(true)),
+            (Some(true), Some(false)),
+            (Some(false), Some(true)),
+            (Some(false), Some(false)),
+            (None, None), // Defaults
+        ];
+
+        let allowlists = LayeredAllowlist::default();
+
+        for (command, expected_decision) in &test_cases {
+            let mut results = Vec::new();
+
+            for (highlight, explanations) in &toggle_combinations {
+                let mut config = Config::default();
+                config.output.highlight_enabled = *highlight;
+                config.output.explanations_enabled = *explanations;
+
+                let enabled_packs = config.enabled_pack_ids();
+                let keywords = REGISTRY.collect_enabled_keywords(&enabled_packs);
+                let keyword_refs: Vec<&str> = keywords.iter().map(|s| &**s).collect();
+                let overrides = config.overrides.compile();
+
+                let result =
+                    evaluate_command(command, &config, &keyword_refs, &overrides, &allowlists);
+                results.push(result.decision);
+
+                // Each result should match the expected decision
+                assert_eq!(
+                    std::mem::discriminant(&result.decision),
+                    std::mem::discriminant(expected_decision),
+                    "Command '{command}' with toggles ({highlight:?}, {explanations:?}) should have expected decision"
+                );
+            }
+
+            // All results for this command should be identical
+            let first = &results[0];
+            for (i, result) in results.iter().enumerate().skip(1) {
+                assert_eq!(
+                    std::mem::discriminant(first),
+                    std::mem::discriminant(result),
+                    "Command '{command}': result {i} differs from result 0"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_config_merge() {
+        let mut base = Config::default();
+        let layer: ConfigLayer = toml::from_str(
+            r#"
+[packs]
+enabled = ["database.postgresql"]
+"#,
+        )
+        .expect("layer parses");
+        base.merge_layer(layer);
+        assert!(
+            base.packs
+                .enabled
+                .contains(&"database.postgresql".to_string())
+        );
+    }
+
+    #[test]
+    fn test_config_merge_merges_heredoc_allowlist() {
+        let mut base = Config::default();
+        base.heredoc.allowlist = Some(HeredocAllowlistConfig {
+            commands: vec!["cmd1".to_string()],
+            ..Default::default()
+        });
+
+        let other = ConfigLayer {
+            heredoc: Some(HeredocConfig {
+                allowlist: Some(HeredocAllowlistConfig {
+                    commands: vec!["cmd2".to_string()],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        base.merge_layer(other);
+
+        let allowlist = base.heredoc.allowlist.as_ref().expect("allowlist merged");
+        assert!(allowlist.commands.contains(&"cmd1".to_string()));
+        assert!(allowlist.commands.contains(&"cmd2".to_string()));
+    }
+
+    #[test]
+    fn test_config_merge_layer_general_verbose_can_be_disabled() {
+        let mut config = Config::default();
+        config.general.verbose = true;
+
+        let layer: ConfigLayer = toml::from_str(
+            r"
+[general]
+verbose = false
+",
+        )
+        .expect("layer parses");
+        config.merge_layer(layer);
+
+        assert!(!config.general.verbose);
+    }
+
+    #[test]
+    fn test_config_merge_layer_general_missing_fields_do_not_override() {
+        let mut config = Config::default();
+        config.general.verbose = true;
+
+        let layer: ConfigLayer = toml::from_str(
+            r#"
+[general]
+color = "never"
+"#,
+        )
+        .expect("layer parses");
+        config.merge_layer(layer);
+
+        assert!(config.general.verbose);
+        assert_eq!(config.general.color, "never");
+    }
+
+    #[test]
+    fn test_config_merge_layer_logging_json_sink() {
+        let mut config = Config::default();
+        let layer: ConfigLayer = toml::from_str(
+            r#"
+[logging.json]
+enabled = true
+path = "/var/log/dcg/decisions.ndjson"
+max_size_mb = 50
+compress = false
+"#,
+        )
+        .expect("layer parses");
+        config.merge_layer(layer);
+
+        assert!(config.logging.json.enabled);
+        assert_eq!(
+            config.logging.json.path.as_deref(),
+            Some("/var/log/dcg/decisions.ndjson")
+        );
+        assert_eq!(config.logging.json.max_size_mb, 50);
+        assert!(!config.logging.json.compress);
+        // Unset fields keep their defaults.
+        assert_eq!(config.logging.json.max_age_hours, 24);
+        assert_eq!(config.logging.json.max_files, 7);
+    }
+
+    #[test]
+    fn test_config_merge_layer_logging_syslog() {
+        let mut config = Config::default();
+        let layer: ConfigLayer = toml::from_str(
+            r#"
+[logging.syslog]
+enabled = true
+target = "journald"
+warn = false
+severities = ["critical", "high"]
+"#,
+        )
+        .expect("layer parses");
+        config.merge_layer(layer);
+
+        let syslog = &config.logging.syslog;
+        assert!(syslog.enabled);
+        assert_eq!(syslog.target, crate::logging::SyslogTarget::Journald);
+        assert!(syslog.deny);
+        assert!(!syslog.warn);
+        assert_eq!(syslog.severities, vec!["critical", "high"]);
+        assert_eq!(syslog.address, "127.0.0.1:514");
+    }
+
+    #[test]
+    fn test_config_merge_layer_logging_otel() {
+        let mut config = Config::default();
+        assert!(!config.logging.otel.enabled);
+
+        let layer: ConfigLayer = toml::from_str(
+            r#"
+[logging.otel]
+enabled = true
+endpoint = "http://collector:4318"
+traces = false
+"#,
+        )
+        .expect("layer parses");
+        config.merge_layer(layer);
+
+        let otel = &config.logging.otel;
+        assert!(otel.enabled);
+        assert_eq!(otel.endpoint, "http://collector:4318");
+        assert!(!otel.traces);
+        assert!(otel.metrics);
+        assert_eq!(otel.service_name, "dcg");
+    }
+
+    #[test]
+    fn test_config_merge_layer_aliases() {
+        let mut config = Config::default();
+        assert!(config.aliases.enabled);
+
+        let user: ConfigLayer = toml::from_str(
+            r#"
+[aliases]
+files = ["~/.bash_aliases"]
+"#,
+        )
+        .expect("layer parses");
+        let project: ConfigLayer = toml::from_str(
+            r#"
+[aliases]
+enabled = false
+files = ["scripts/aliases.sh"]
+"#,
+        )
+        .expect("layer parses");
+        config.merge_layer(user);
+        config.merge_layer(project);
+
+        assert!(!config.aliases.enabled);
+        assert_eq!(
+            config.aliases.files,
+            vec!["~/.bash_aliases", "scripts/aliases.sh"]
+        );
+    }
+
+    #[test]
+    fn test_config_merge_layer_trusted_installers() {
+        let mut config = Config::default();
+        assert!(
+            config
+                .trusted_installers
+                .domains
+                .contains(&"sh.rustup.rs".to_string())
+        );
+
+        let project: ConfigLayer = toml::from_str(
+            r#"
+[trusted_installers]
+domains = ["get.example.com", "sh.rustup.rs"]
+"#,
+        )
+        .expect("layer parses");
+        config.merge_layer(project);
+
+        let domains = &config.trusted_installers.domains;
+        assert!(domains.contains(&"get.example.com".to_string()));
+        assert_eq!(
+            domains
+                .iter()
+                .filter(|d| d.as_str() == "sh.rustup.rs")
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_config_merge_layer_anomaly() {
+        let mut config = Config::default();
+        assert!(config.anomaly.is_active());
+
+        let user: ConfigLayer = toml::from_str(
+            r"
+[anomaly]
+deny_threshold = 3
+window_secs = 60
+",
+        )
+        .expect("layer parses");
+        let project: ConfigLayer = toml::from_str(
+            r"
+[anomaly]
+window_secs = 300
+",
+        )
+        .expect("layer parses");
+        config.merge_layer(user);
+        config.merge_layer(project);
+
+        assert_eq!(config.anomaly.deny_threshold, 3);
+        assert_eq!(config.anomaly.window_secs, 300);
+
+        config.anomaly.deny_threshold = 0;
+        assert!(!config.anomaly.is_active());
+    }
+
+    #[test]
+    fn test_config_merge_layer_risk() {
+        let mut config = Config::default();
+        assert!(!config.risk.is_active());
+
+        let layer: ConfigLayer = toml::from_str(
+            r"
+[risk]
+enabled = true
+threshold = 50
+warn_points = 2
+",
+        )
+        .expect("layer parses");
+        config.merge_layer(layer);
+
+        assert!(config.risk.is_active());
+        assert_eq!(config.risk.threshold, 50);
+        assert_eq!(config.risk.deny_points, RiskConfig::DEFAULT_DENY_POINTS);
+        assert_eq!(config.risk.warn_points, 2);
     }
 
     #[test]
-    fn test_output_config_does_not_affect_deny_decision() {
-        // Test that output config toggles do NOT affect the evaluator's deny decision.
-        use crate::allowlist::LayeredAllowlist;
-        use crate::evaluator::{EvaluationDecision, evaluate_command};
-        use crate::packs::REGISTRY;
+    fn test_config_merge_layer_notifications() {
+        let mut config = Config::default();
+        assert!(!config.notifications.is_active());
 
-        // Dangerous command: "git reset --hard HEAD" should always be denied
-        let command = "git reset --hard HEAD";
+        let user: ConfigLayer = toml::from_str(
+            r#"
+[notifications]
+enabled = true
+min_severity = "critical"
 
-        // Config with defaults (both true)
-        let config_default = Config::default();
-        let enabled_packs = config_default.enabled_pack_ids();
-        let keywords = REGISTRY.collect_enabled_keywords(&enabled_packs);
-        let keyword_refs: Vec<&str> = keywords.iter().map(|s| &**s).collect();
-        let overrides_default = config_default.overrides.compile();
-        let allowlists = LayeredAllowlist::default();
+[[notifications.webhooks]]
+url = "https://hooks.slack.com/services/T/B/X"
+format = "slack"
+"#,
+        )
+        .expect("layer parses");
+        let project: ConfigLayer = toml::from_str(
+            r"
+[notifications]
+cooldown_secs = 60
+",
+        )
+        .expect("layer parses");
+        config.merge_layer(user);
+        config.merge_layer(project);
 
-        let result_default = evaluate_command(
-            command,
-            &config_default,
-            &keyword_refs,
-            &overrides_default,
-            &allowlists,
+        assert!(config.notifications.is_active());
+        assert_eq!(config.notifications.min_severity, StrictnessLevel::Critical);
+        assert_eq!(config.notifications.webhooks.len(), 1);
+        assert_eq!(
+            config.notifications.webhooks[0].format,
+            WebhookFormat::Slack
         );
-        assert!(
-            matches!(result_default.decision, EvaluationDecision::Deny),
-            "Destructive command should be denied with default config"
+        assert_eq!(config.notifications.cooldown_secs, 60);
+        assert_eq!(
+            config.notifications.batch_window_ms,
+            NotificationsConfig::DEFAULT_BATCH_WINDOW_MS
         );
+    }
 
-        // Config with both toggles disabled
-        let mut config_disabled = Config::default();
-        config_disabled.output.highlight_enabled = Some(false);
-        config_disabled.output.explanations_enabled = Some(false);
-        let overrides_disabled = config_disabled.overrides.compile();
+    #[test]
+    fn test_config_merge_layer_remote() {
+        let mut config = Config::default();
+        assert!(!config.remote.is_active());
 
-        let result_disabled = evaluate_command(
-            command,
-            &config_disabled,
-            &keyword_refs,
-            &overrides_disabled,
-            &allowlists,
-        );
-        assert!(
-            matches!(result_disabled.decision, EvaluationDecision::Deny),
-            "Destructive command should still be denied with disabled output toggles"
-        );
+        let system: ConfigLayer = toml::from_str(
+            r#"
+[remote]
+enabled = true
+url = "https://policy.example.com/policy.toml"
+public_key = "AAAA"
+"#,
+        )
+        .expect("layer parses");
+        let user: ConfigLayer = toml::from_str(
+            r"
+[remote]
+interval_secs = 600
+",
+        )
+        .expect("layer parses");
+        config.merge_layer(system);
+        config.merge_layer(user);
 
-        // Both results should have the same decision AND same pattern info
+        assert!(config.remote.is_active());
+        assert_eq!(config.remote.interval_secs, 600);
+        assert_eq!(config.remote.timeout_ms, RemoteConfig::DEFAULT_TIMEOUT_MS);
         assert_eq!(
-            std::mem::discriminant(&result_default.decision),
-            std::mem::discriminant(&result_disabled.decision),
-            "Output config should not affect deny decision"
+            config.remote.signature_url().as_deref(),
+            Some("https://policy.example.com/policy.toml.sig")
         );
+    }
 
-        // Pattern info should also be the same
+    #[test]
+    fn test_config_merge_layer_kube_context() {
+        let mut config = Config::default();
+        assert!(!config.kube_context.enabled);
+
+        let layer: ConfigLayer = toml::from_str(
+            r#"
+[kube_context]
+enabled = true
+production_contexts = ["live-*"]
+cache_ttl_secs = 5
+"#,
+        )
+        .expect("layer parses");
+        config.merge_layer(layer);
+
+        assert!(config.kube_context.enabled);
+        assert_eq!(config.kube_context.production_contexts, ["live-*"]);
+        assert!(
+            config
+                .kube_context
+                .development_contexts
+                .contains(&"minikube".to_string())
+        );
+        assert_eq!(config.kube_context.cache_ttl_secs, 5);
         assert_eq!(
-            result_default.pattern_info.as_ref().map(|p| &p.reason),
-            result_disabled.pattern_info.as_ref().map(|p| &p.reason),
-            "Pattern info reason should be identical regardless of output config"
+            config.kube_context.timeout_ms,
+            KubeContextConfig::DEFAULT_TIMEOUT_MS
         );
     }
 
     #[test]
-    fn test_output_config_toggles_are_purely_cosmetic() {
-        // Comprehensive test: verify output toggles have zero effect on evaluation
-        use crate::allowlist::LayeredAllowlist;
-        use crate::evaluator::{EvaluationDecision, evaluate_command};
-        use crate::packs::REGISTRY;
-
-        let test_cases = [
-            ("echo hello", EvaluationDecision::Allow),      // Safe
-            ("git status", EvaluationDecision::Allow),      // Safe git command
-            ("git reset --hard", EvaluationDecision::Deny), // Destructive
-            ("rm -rf /", EvaluationDecision::Deny),         // Destructive
-        ];
+    fn test_config_merge_layer_wrappers() {
+        let mut config = Config::default();
+        assert!(config.wrappers.ssh);
+        config.wrappers.extra.push("chronic".to_string());
 
-        let toggle_combinations = [
-            (Some(true), Some(true)),
-            (Some(true), Some(false)),
-            (Some(false), Some(true)),
-            (Some(false), Some(false)),
-            (None, None), // Defaults
-        ];
+        let layer: ConfigLayer = toml::from_str(
+            r#"
+[wrappers]
+extra = ["unbuffer", "chronic"]
+production_hosts = ["prod-*"]
+"#,
+        )
+        .expect("layer parses");
+        config.merge_layer(layer);
 
-        let allowlists = LayeredAllowlist::default();
+        assert!(config.wrappers.ssh);
+        assert_eq!(config.wrappers.extra, ["chronic", "unbuffer"]);
+        assert_eq!(config.wrappers.production_hosts, ["prod-*"]);
+    }
 
-        for (command, expected_decision) in &test_cases {
-            let mut results = Vec::new();
+    #[test]
+    fn test_config_merge_layer_suppressions() {
+        let mut config = Config::default();
+        assert!(!config.suppressions.inline);
+        assert!(config.suppressions.require_confirmation);
 
-            for (highlight, explanations) in &toggle_combinations {
-                let mut config = Config::default();
-                config.output.highlight_enabled = *highlight;
-                config.output.explanations_enabled = *explanations;
+        let layer: ConfigLayer = toml::from_str(
+            r"
+[suppressions]
+inline = true
+",
+        )
+        .expect("layer parses");
+        config.merge_layer(layer);
 
-                let enabled_packs = config.enabled_pack_ids();
-                let keywords = REGISTRY.collect_enabled_keywords(&enabled_packs);
-                let keyword_refs: Vec<&str> = keywords.iter().map(|s| &**s).collect();
-                let overrides = config.overrides.compile();
+        assert!(config.suppressions.inline);
+        assert!(config.suppressions.require_confirmation);
+    }
 
-                let result =
-                    evaluate_command(command, &config, &keyword_refs, &overrides, &allowlists);
-                results.push(result.decision);
+    #[test]
+    fn test_config_merge_layer_trust() {
+        let mut config = Config::default();
+        assert!(config.trust.ledger);
+        assert_eq!(
+            config.trust.promote_after,
+            TrustConfig::DEFAULT_PROMOTE_AFTER
+        );
 
-                // Each result should match the expected decision
-                assert_eq!(
-                    std::mem::discriminant(&result.decision),
-                    std::mem::discriminant(expected_decision),
-                    "Command '{command}' with toggles ({highlight:?}, {explanations:?}) should have expected decision"
-                );
-            }
+        let layer: ConfigLayer = toml::from_str(
+            r"
+[trust]
+promote_after = 5
+",
+        )
+        .expect("layer parses");
+        config.merge_layer(layer);
 
-            // All results for this command should be identical
-            let first = &results[0];
-            for (i, result) in results.iter().enumerate().skip(1) {
-                assert_eq!(
-                    std::mem::discriminant(first),
-                    std::mem::discriminant(result),
-                    "Command '{command}': result {i} differs from result 0"
-                );
-            }
-        }
+        assert!(config.trust.ledger);
+        assert_eq!(config.trust.promote_after, 5);
     }
 
     #[test]
-    fn test_config_merge() {
-        let mut base = Config::default();
+    fn test_config_merge_layer_bench() {
+        let mut config = Config::default();
+        assert_eq!(config.bench, BenchConfig::default());
+
         let layer: ConfigLayer = toml::from_str(
-            r#"
-[packs]
-enabled = ["database.postgresql"]
-"#,
+            r"
+[bench]
+cold_start_p99_ms = 50
+pack_eval_p99_us = 400
+",
         )
         .expect("layer parses");
-        base.merge_layer(layer);
-        assert!(
-            base.packs
-                .enabled
-                .contains(&"database.postgresql".to_string())
+        config.merge_layer(layer);
+
+        assert_eq!(config.bench.cold_start_p99_ms, 50);
+        assert_eq!(config.bench.pack_eval_p99_us, 400);
+        assert_eq!(
+            config.bench.quick_reject_p99_us,
+            BenchConfig::default().quick_reject_p99_us
         );
     }
 
     #[test]
-    fn test_config_merge_merges_heredoc_allowlist() {
-        let mut base = Config::default();
-        base.heredoc.allowlist = Some(HeredocAllowlistConfig {
-            commands: vec!["cmd1".to_string()],
-            ..Default::default()
-        });
-
-        let other = ConfigLayer {
-            heredoc: Some(HeredocConfig {
-                allowlist: Some(HeredocAllowlistConfig {
-                    commands: vec!["cmd2".to_string()],
-                    ..Default::default()
-                }),
-                ..Default::default()
-            }),
-            ..Default::default()
-        };
+    fn test_config_merge_layer_output_locale() {
+        let mut config = Config::default();
+        assert!(config.output.locale.is_none());
+        assert!(config.output.locale_dir().is_none());
 
-        base.merge_layer(other);
+        let layer: ConfigLayer = toml::from_str(
+            r#"
+[output]
+locale = "de"
+locale_dir = "/srv/dcg/locales"
+"#,
+        )
+        .expect("layer parses");
+        config.merge_layer(layer);
 
-        let allowlist = base.heredoc.allowlist.as_ref().expect("allowlist merged");
-        assert!(allowlist.commands.contains(&"cmd1".to_string()));
-        assert!(allowlist.commands.contains(&"cmd2".to_string()));
+        assert_eq!(config.output.locale.as_deref(), Some("de"));
+        assert_eq!(
+            config.output.locale_dir(),
+            Some(PathBuf::from("/srv/dcg/locales"))
+        );
+        assert!(config.output.highlight_enabled());
     }
 
     #[test]
-    fn test_config_merge_layer_general_verbose_can_be_disabled() {
+    fn test_config_merge_layer_cache() {
         let mut config = Config::default();
-        config.general.verbose = true;
+        assert!(!config.cache.is_active());
+        assert!(!config.cache.quick_allow);
 
         let layer: ConfigLayer = toml::from_str(
-            r"
-[general]
-verbose = false
-",
+            r#"
+[cache]
+enabled = true
+ttl_secs = 600
+quick_allow = true
+path = "~/dcg-cache.json"
+"#,
         )
         .expect("layer parses");
         config.merge_layer(layer);
 
-        assert!(!config.general.verbose);
+        assert!(config.cache.is_active());
+        assert_eq!(config.cache.ttl_secs, 600);
+        assert!(config.cache.quick_allow);
+        assert_eq!(
+            config.cache.max_entries,
+            EvalCacheConfig::DEFAULT_MAX_ENTRIES
+        );
+        assert!(
+            config
+                .cache
+                .expanded_path()
+                .is_some_and(|p| p.ends_with("dcg-cache.json"))
+        );
+
+        config.cache.max_entries = 0;
+        assert!(!config.cache.is_active());
     }
 
     #[test]
-    fn test_config_merge_layer_general_missing_fields_do_not_override() {
+    fn test_config_merge_layer_expired_allowlist_reminder() {
         let mut config = Config::default();
-        config.general.verbose = true;
+        assert_eq!(config.general.expired_allowlist_reminder(), 0);
 
         let layer: ConfigLayer = toml::from_str(
-            r#"
+            r"
 [general]
-color = "never"
-"#,
+expired_allowlist_reminder = 5
+",
         )
         .expect("layer parses");
         config.merge_layer(layer);
 
-        assert!(config.general.verbose);
-        assert_eq!(config.general.color, "never");
+        assert_eq!(config.general.expired_allowlist_reminder(), 5);
     }
 
     #[test]
@@ -4413,11 +7852,60 @@ allow = false
         let temp = tempfile::tempdir().expect("tempdir");
         let repo_root = temp.path().join("repo");
         std::fs::create_dir_all(repo_root.join(".git")).expect("create .git");
-        let deep = repo_root.join("a/b/c");
+        let deep = repo_root.join("a/b/c");
+        std::fs::create_dir_all(&deep).expect("create deep dir");
+
+        // With a hop limit of 1, we shouldn't reach the repo root from a/b/c.
+        assert!(find_repo_root(&deep, 1).is_none());
+    }
+
+    #[test]
+    fn test_project_layer_merges_over_user_layer_with_sources() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let repo_root = temp.path().join("repo");
+        std::fs::create_dir_all(repo_root.join(".git")).expect("create .git");
+        std::fs::write(
+            repo_root.join(PROJECT_CONFIG_NAME),
+            r#"
+[packs]
+enabled = ["database.postgresql"]
+disabled = ["kubernetes.helm"]
+
+[policy]
+default_mode = "warn"
+"#,
+        )
+        .expect("write project config");
+        let deep = repo_root.join("src/nested");
         std::fs::create_dir_all(&deep).expect("create deep dir");
 
-        // With a hop limit of 1, we shouldn't reach the repo root from a/b/c.
-        assert!(find_repo_root(&deep, 1).is_none());
+        let mut config = Config::default();
+        let user: ConfigLayer = toml::from_str(
+            r#"
+[packs]
+enabled = ["kubernetes"]
+
+[policy]
+default_mode = "deny"
+"#,
+        )
+        .expect("user layer parses");
+        config.merge_layer(user);
+
+        let (path, project) =
+            Config::load_project_config_layer_from(Some(&deep)).expect("project layer found");
+        assert_eq!(path, repo_root.join(PROJECT_CONFIG_NAME));
+
+        let source = ConfigSource::from_layer("project", path, &project);
+        assert_eq!(source.sections, vec!["packs", "policy"]);
+        assert_eq!(source.packs_enabled, vec!["database.postgresql"]);
+        assert_eq!(source.packs_disabled, vec!["kubernetes.helm"]);
+
+        config.merge_layer(project);
+        let enabled = config.packs.enabled_pack_ids();
+        assert!(enabled.contains("kubernetes"));
+        assert!(enabled.contains("database.postgresql"));
+        assert_eq!(config.policy.default_mode, Some(PolicyMode::Warn));
     }
 
     // ========================================================================
@@ -4528,6 +8016,128 @@ allow = false
         assert!(!compiled.check_allow("kubectl delete namespace production"));
     }
 
+    #[test]
+    fn test_literal_allow_overrides_parse_from_toml() {
+        let config: Config = toml::from_str(
+            r#"
+[[overrides.allow]]
+exact = "npm run build"
+
+[[overrides.allow]]
+prefix = "cargo test"
+
+[[overrides.allow]]
+glob = "rm -rf ./target/*"
+when = "CI=true"
+"#,
+        )
+        .expect("config parses");
+
+        let allow = &config.overrides.allow;
+        assert!(
+            matches!(&allow[0], AllowOverride::Exact { exact, when: None } if exact == "npm run build")
+        );
+        assert!(
+            matches!(&allow[1], AllowOverride::Prefix { prefix, .. } if prefix == "cargo test")
+        );
+        assert!(matches!(&allow[2], AllowOverride::Glob { .. }));
+        assert_eq!(allow[2].when(), Some("CI=true"));
+    }
+
+    #[test]
+    fn test_compile_literal_allow_overrides() {
+        let overrides = OverridesConfig {
+            allow: vec![
+                AllowOverride::Exact {
+                    exact: "  git reset --hard HEAD  ".to_string(),
+                    when: None,
+                },
+                AllowOverride::Prefix {
+                    prefix: "cargo test".to_string(),
+                    when: None,
+                },
+                AllowOverride::Glob {
+                    glob: "rm -rf ./target/*".to_string(),
+                    when: None,
+                },
+            ],
+            block: vec![],
+            ..Default::default()
+        };
+        let compiled = overrides.compile();
+
+        assert!(compiled.invalid_patterns.is_empty());
+        assert!(compiled.allow.is_empty());
+        assert_eq!(compiled.literal_allow.len(), 3);
+
+        assert!(compiled.check_allow("git reset --hard HEAD"));
+        assert!(!compiled.check_allow("git reset --hard HEAD~1"));
+
+        assert!(compiled.check_allow("cargo test"));
+        assert!(compiled.check_allow("cargo test --release"));
+        assert!(!compiled.check_allow("cargo testing"));
+
+        assert!(compiled.check_allow("rm -rf ./target/debug"));
+        assert!(!compiled.check_allow("rm -rf ./src"));
+    }
+
+    #[test]
+    fn test_literal_allow_overrides_reject_chained_commands() {
+        let overrides = OverridesConfig {
+            allow: vec![
+                AllowOverride::Prefix {
+                    prefix: "cargo test".to_string(),
+                    when: None,
+                },
+                AllowOverride::Glob {
+                    glob: "rm -rf ./target/*".to_string(),
+                    when: None,
+                },
+            ],
+            block: vec![],
+            ..Default::default()
+        };
+        let compiled = overrides.compile();
+
+        assert!(!compiled.check_allow("cargo test; rm -rf /"));
+        assert!(!compiled.check_allow("cargo test && rm -rf /"));
+        assert!(!compiled.check_allow("cargo test $(rm -rf /)"));
+        assert!(!compiled.check_allow("rm -rf ./target/x | sh"));
+    }
+
+    #[test]
+    fn test_literal_allow_override_condition_and_errors() {
+        let overrides = OverridesConfig {
+            allow: vec![
+                AllowOverride::Prefix {
+                    prefix: "terraform destroy".to_string(),
+                    when: Some("DCG_TEST_LITERAL_ALLOW_UNSET_VAR".to_string()),
+                },
+                AllowOverride::Exact {
+                    exact: "   ".to_string(),
+                    when: None,
+                },
+                AllowOverride::Glob {
+                    glob: "rm [".to_string(),
+                    when: None,
+                },
+            ],
+            block: vec![],
+            ..Default::default()
+        };
+        let compiled = overrides.compile();
+
+        assert!(!compiled.check_allow("terraform destroy -auto-approve"));
+        assert_eq!(compiled.literal_allow.len(), 1);
+        assert_eq!(compiled.invalid_patterns.len(), 2);
+        assert!(
+            compiled
+                .invalid_patterns
+                .iter()
+                .all(|p| p.kind == PatternKind::Allow)
+        );
+    }
+
     #[test]
     fn test_compiled_overrides_engine_selection_lookahead_vs_linear() {
         let overrides = OverridesConfig {
@@ -4983,6 +8593,7 @@ allow = false
                 "core.git:reset-hard".to_string(),
                 PolicyMode::Log,
             )]),
+            ..Default::default()
         };
 
         // Rule-specific override should win
@@ -5177,6 +8788,7 @@ allow = false
                     "core.git:reset-hard".to_string(),
                     PolicyMode::Log,
                 )]),
+                ..Default::default()
             }),
             ..Default::default()
         };
@@ -5294,6 +8906,395 @@ allow = false
         assert_eq!(mode, crate::packs::DecisionMode::Deny);
     }
 
+    fn utc(ts: &str) -> DateTime<Utc> {
+        chrono::DateTime::parse_from_rfc3339(ts)
+            .expect("valid timestamp")
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_policy_window_escalates_high_during_business_hours() {
+        let policy: PolicyConfig = toml::from_str(
+            r#"
+            [[windows]]
+            severities = ["high"]
+            hours = "09:00-18:00"
+            days = ["mon", "tue", "wed", "thu", "fri"]
+            timezone = "utc"
+            mode = "deny"
+            otherwise = "warn"
+            "#,
+        )
+        .expect("valid policy");
+        let high = Some(crate::packs::Severity::High);
+
+        // Wednesday 10:00 UTC: inside the window.
+        let mode =
+            policy.resolve_mode_at(utc("2026-01-07T10:00:00Z"), Some("core.git"), None, high);
+        assert_eq!(mode, crate::packs::DecisionMode::Deny);
+
+        // Wednesday 20:00 UTC and Saturday 10:00 UTC: outside.
+        let mode =
+            policy.resolve_mode_at(utc("2026-01-07T20:00:00Z"), Some("core.git"), None, high);
+        assert_eq!(mode, crate::packs::DecisionMode::Warn);
+        let mode =
+            policy.resolve_mode_at(utc("2026-01-10T10:00:00Z"), Some("core.git"), None, high);
+        assert_eq!(mode, crate::packs::DecisionMode::Warn);
+
+        // Critical is never loosened and other severities are untouched.
+        let mode = policy.resolve_mode_at(
+            utc("2026-01-07T20:00:00Z"),
+            Some("core.git"),
+            None,
+            Some(crate::packs::Severity::Critical),
+        );
+        assert_eq!(mode, crate::packs::DecisionMode::Deny);
+        let mode = policy.resolve_mode_at(
+            utc("2026-01-07T10:00:00Z"),
+            Some("core.git"),
+            None,
+            Some(crate::packs::Severity::Medium),
+        );
+        assert_eq!(mode, crate::packs::DecisionMode::Warn);
+    }
+
+    #[test]
+    fn test_policy_window_hours_wrap_midnight() {
+        let window = PolicyWindow {
+            name: None,
+            severities: vec![],
+            hours: Some("22:00-06:00".to_string()),
+            days: vec![],
+            mode: PolicyMode::Deny,
+            otherwise: None,
+            timezone: Some("utc".to_string()),
+        };
+        assert_eq!(
+            window.mode_at(utc("2026-01-07T23:30:00Z"), None),
+            Some(PolicyMode::Deny)
+        );
+        assert_eq!(
+            window.mode_at(utc("2026-01-07T05:59:00Z"), None),
+            Some(PolicyMode::Deny)
+        );
+        assert_eq!(window.mode_at(utc("2026-01-07T12:00:00Z"), None), None);
+    }
+
+    #[test]
+    fn test_policy_freeze_escalates_but_never_loosens() {
+        let policy = PolicyConfig {
+            packs: std::collections::HashMap::from([("core.git".to_string(), PolicyMode::Log)]),
+            freezes: vec![PolicyFreeze {
+                name: Some("release".to_string()),
+                start: "2026-12-20".to_string(),
+                end: "2027-01-05".to_string(),
+                mode: None,
+            }],
+            ..Default::default()
+        };
+        let medium = Some(crate::packs::Severity::Medium);
+
+        let during = utc("2026-12-20T00:30:00Z");
+        assert!(policy.active_freeze_at(during).is_some());
+        let mode = policy.resolve_mode_at(during, Some("core.git"), None, medium);
+        assert_eq!(mode, crate::packs::DecisionMode::Deny);
+
+        let after = utc("2027-01-06T00:00:00Z");
+        assert!(policy.active_freeze_at(after).is_none());
+        let mode = policy.resolve_mode_at(after, Some("core.git"), None, medium);
+        assert_eq!(mode, crate::packs::DecisionMode::Log);
+
+        // A "warn" freeze does not loosen a deny.
+        let policy = PolicyConfig {
+            freezes: vec![PolicyFreeze {
+                name: None,
+                start: "2026-12-20".to_string(),
+                end: "2027-01-05".to_string(),
+                mode: Some(PolicyMode::Warn),
+            }],
+            ..Default::default()
+        };
+        let mode = policy.resolve_mode_at(during, None, None, Some(crate::packs::Severity::High));
+        assert_eq!(mode, crate::packs::DecisionMode::Deny);
+    }
+
+    #[test]
+    fn test_policy_windows_and_freezes_merge_across_layers() {
+        let mut config = Config::default();
+        let layer: ConfigLayer = toml::from_str(
+            r#"
+            [[policy.windows]]
+            hours = "09:00-18:00"
+            mode = "deny"
+
+            [[policy.freezes]]
+            start = "2026-12-20"
+            end = "2027-01-05"
+            "#,
+        )
+        .expect("valid layer");
+        config.merge_layer(layer.clone());
+        config.merge_layer(layer);
+        assert_eq!(config.policy.windows.len(), 2);
+        assert_eq!(config.policy.freezes.len(), 2);
+    }
+
+    #[test]
+    fn test_policy_fail_mode_string_and_table() {
+        let config: Config = toml::from_str(
+            r#"
+            [policy]
+            fail_mode = "closed"
+            "#,
+        )
+        .expect("valid config");
+        let fail_mode = &config.policy.fail_mode;
+        assert!(fail_mode.is_closed(FailureClass::Timeout));
+        assert!(fail_mode.is_closed(FailureClass::Io));
+        assert!(!fail_mode.is_closed(FailureClass::Telemetry));
+        assert!(!config.heredoc_settings().fallback_on_timeout);
+
+        let config: Config = toml::from_str(
+            r#"
+            [policy.fail_mode]
+            timeout = "closed"
+            telemetry = "open"
+            "#,
+        )
+        .expect("valid config");
+        let fail_mode = &config.policy.fail_mode;
+        assert!(fail_mode.is_closed(FailureClass::Timeout));
+        assert!(!fail_mode.is_closed(FailureClass::ParseError));
+        assert!(config.heredoc_settings().fallback_on_parse_error);
+
+        assert_eq!(
+            Config::default()
+                .policy
+                .fail_mode
+                .mode_for(FailureClass::Timeout),
+            FailMode::Open
+        );
+    }
+
+    #[test]
+    fn test_policy_fail_mode_merges_per_class() {
+        let mut config = Config::default();
+        let user: ConfigLayer = toml::from_str(
+            r#"
+            [policy]
+            fail_mode = "closed"
+            "#,
+        )
+        .expect("valid layer");
+        let project: ConfigLayer = toml::from_str(
+            r#"
+            [policy.fail_mode]
+            parse_error = "open"
+            "#,
+        )
+        .expect("valid layer");
+        config.merge_layer(user);
+        config.merge_layer(project);
+        assert!(config.policy.fail_mode.is_closed(FailureClass::Timeout));
+        assert!(!config.policy.fail_mode.is_closed(FailureClass::ParseError));
+
+        let env_map: std::collections::HashMap<&str, &str> =
+            std::collections::HashMap::from([("DCG_POLICY_FAIL_MODE", "open")]);
+        config.apply_env_overrides_from(|key| env_map.get(key).map(|v| (*v).to_string()));
+        assert!(!config.policy.fail_mode.is_closed(FailureClass::Timeout));
+    }
+
+    #[test]
+    fn test_policy_external_parses_and_merges() {
+        let mut config = Config::default();
+        assert!(config.policy.external.is_none());
+
+        let user: ConfigLayer = toml::from_str(
+            r#"
+            [policy.external]
+            enabled = true
+            url = "https://opa.internal/v1/data/dcg/decision"
+            severities = ["high"]
+            fail_mode = "closed"
+            "#,
+        )
+        .expect("valid layer");
+        config.merge_layer(user);
+        let external = config.policy.external.as_ref().expect("external policy");
+        assert!(external.is_active());
+        assert!(external.applies_to(Some(crate::packs::Severity::High)));
+        assert!(!external.applies_to(Some(crate::packs::Severity::Medium)));
+        assert_eq!(external.fail_mode, FailMode::Closed);
+        assert_eq!(
+            external.timeout_ms,
+            ExternalPolicyConfig::DEFAULT_TIMEOUT_MS
+        );
+
+        // A layer without the section keeps the endpoint.
+        let other: ConfigLayer = toml::from_str(
+            r#"
+            [policy]
+            default_mode = "warn"
+            "#,
+        )
+        .expect("valid layer");
+        config.merge_layer(other);
+        assert!(config.policy.external.is_some());
+    }
+
+    #[test]
+    fn test_policy_rego_files_resolve_configured_paths() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::write(dir.path().join("team.rego"), "package dcg\n").expect("write");
+
+        let config: Config = toml::from_str(
+            r#"
+            [policy.rego]
+            enabled = true
+            paths = ["team.rego", "missing.rego"]
+            "#,
+        )
+        .expect("valid config");
+        let rego = config.policy.rego.as_ref().expect("rego policy");
+        assert_eq!(rego.query, RegoPolicyConfig::DEFAULT_QUERY);
+        assert_eq!(
+            rego.policy_files(Some(dir.path())),
+            vec![dir.path().join("team.rego")]
+        );
+    }
+
+    #[test]
+    fn test_policy_severity_override_remaps_rule_and_pack() {
+        let policy = PolicyConfig {
+            severity_overrides: std::collections::HashMap::from([
+                (
+                    "containers.docker:image-prune".to_string(),
+                    "low".to_string(),
+                ),
+                ("containers.docker".to_string(), "High".to_string()),
+            ]),
+            ..Default::default()
+        };
+
+        // Rule key wins over pack key: Medium -> Low -> Log
+        let mode = policy.resolve_mode(
+            Some("containers.docker"),
+            Some("image-prune"),
+            Some(crate::packs::Severity::Medium),
+        );
+        assert_eq!(mode, crate::packs::DecisionMode::Log);
+
+        // Pack key applies to other rules: Medium -> High -> Deny
+        assert_eq!(
+            policy.effective_severity(
+                Some("containers.docker"),
+                Some("container-prune"),
+                Some(crate::packs::Severity::Medium),
+            ),
+            Some(crate::packs::Severity::High)
+        );
+        let mode = policy.resolve_mode(
+            Some("containers.docker"),
+            Some("container-prune"),
+            Some(crate::packs::Severity::Medium),
+        );
+        assert_eq!(mode, crate::packs::DecisionMode::Deny);
+    }
+
+    #[test]
+    fn test_policy_severity_override_critical_requires_rule_key() {
+        let policy = PolicyConfig {
+            severity_overrides: std::collections::HashMap::from([
+                ("core.git".to_string(), "low".to_string()),
+                ("core.git:clean-force".to_string(), "medium".to_string()),
+            ]),
+            ..Default::default()
+        };
+
+        // Pack key cannot lower a Critical rule
+        let mode = policy.resolve_mode(
+            Some("core.git"),
+            Some("reset-hard"),
+            Some(crate::packs::Severity::Critical),
+        );
+        assert_eq!(mode, crate::packs::DecisionMode::Deny);
+
+        // Rule key can, and the lowered severity is no longer locked to Deny
+        assert_eq!(
+            policy.effective_severity(
+                Some("core.git"),
+                Some("clean-force"),
+                Some(crate::packs::Severity::Critical),
+            ),
+            Some(crate::packs::Severity::Medium)
+        );
+        let mode = policy.resolve_mode(
+            Some("core.git"),
+            Some("clean-force"),
+            Some(crate::packs::Severity::Critical),
+        );
+        assert_eq!(mode, crate::packs::DecisionMode::Warn);
+    }
+
+    #[test]
+    fn test_policy_severity_override_validation() {
+        let policy = PolicyConfig {
+            severity_overrides: std::collections::HashMap::from([
+                (
+                    "containers.docker:image-prune".to_string(),
+                    "low".to_string(),
+                ),
+                (
+                    "containers.docker:no-such-rule".to_string(),
+                    "low".to_string(),
+                ),
+                ("core.git".to_string(), "severe".to_string()),
+                ("no.such.pack".to_string(), "high".to_string()),
+            ]),
+            ..Default::default()
+        };
+
+        // Invalid values are ignored at resolution time
+        assert_eq!(
+            policy.effective_severity(
+                Some("core.git"),
+                Some("branch-force-delete"),
+                Some(crate::packs::Severity::High),
+            ),
+            Some(crate::packs::Severity::High)
+        );
+
+        let issues = policy.validate_severity_overrides();
+        assert_eq!(issues.len(), 3, "{issues:?}");
+        assert!(issues[0].contains("no destructive pattern \"no-such-rule\""));
+        assert!(issues[1].contains("unknown severity \"severe\""));
+        assert!(issues[2].contains("unknown pack \"no.such.pack\""));
+    }
+
+    #[test]
+    fn test_policy_severity_overrides_merge_across_layers() {
+        let mut config = Config::default();
+        let base: ConfigLayer = toml::from_str(
+            r#"
+            [policy.severity_overrides]
+            "containers.docker:image-prune" = "low"
+            "core.git" = "medium"
+            "#,
+        )
+        .expect("valid layer");
+        let project: ConfigLayer = toml::from_str(
+            r#"
+            [policy.severity_overrides]
+            "core.git" = "high"
+            "#,
+        )
+        .expect("valid layer");
+        config.merge_layer(base);
+        config.merge_layer(project);
+        assert_eq!(config.policy.severity_overrides.len(), 2);
+        assert_eq!(config.policy.severity_overrides["core.git"], "high");
+    }
+
     // ========================================================================
     // Heredoc allowlist tests (git_safety_guard-cpal)
     // ========================================================================
@@ -667,7 +667,21 @@ impl ContextClassifier {
                 .unwrap_or(base_name);
 
             // Skip wrappers that might precede the interpreter
-            if matches!(base_name, "sudo" | "time" | "nohup" | "env" | "command") {
+            if matches!(
+                base_name,
+                "sudo"
+                    | "doas"
+                    | "time"
+                    | "nohup"
+                    | "setsid"
+                    | "nice"
+                    | "ionice"
+                    | "timeout"
+                    | "stdbuf"
+                    | "xargs"
+                    | "env"
+                    | "command"
+            ) {
                 continue;
             }
 
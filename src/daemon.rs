@@ -0,0 +1,629 @@
+//! Background evaluation daemon (`dcg daemon`).
+//!
+//! Every hook invocation is a fresh process: it loads the config layers and
+//! allowlists, compiles the overrides, and compiles the regexes of whichever
+//! packs the command reaches. `dcg daemon` keeps all of that warm and answers
+//! evaluation requests over a Unix socket. Hook mode forwards the (alias- and
+//! variable-expanded) command there and evaluates in-process when no daemon
+//! answers, so a missing or stuck daemon only costs the fallback.
+//!
+//! The protocol is one JSON line in each direction per connection. An
+//! evaluation request carries the hook's working directory, agent session,
+//! remaining deadline, and the [`ConfigFingerprint`] of its merged
+//! configuration and allowlists. The daemon keeps evaluation state per
+//! working directory and rebuilds it when the fingerprint changes, which
+//! includes any edit to an allowlist file. When it still cannot reproduce the
+//! hook's configuration (a different environment, or external packs, which are
+//! cached process-wide) it answers `fallback` and the hook evaluates itself.
+//! Policy resolution, output, history, and notifications stay in the hook.
+//!
+//! Requests are served one at a time, and the daemon switches its working
+//! directory to the request's so cwd-relative checks (allow-once entries,
+//! protected paths, project scripts) see what the hook would.
+
+use std::collections::HashMap;
+use std::env;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::allowlist::AllowlistLayer;
+use crate::config::Config;
+use crate::evaluator::{
+    AllowlistOverride, EvaluationDecision, EvaluationResult, MatchSource, MatchSpan, PatternMatch,
+    evaluate_command_with_pack_order_deadline_at_path,
+};
+use crate::fingerprint::ConfigFingerprint;
+use crate::packs::{DecisionMode, PatternSuggestion, REGISTRY, Severity};
+use crate::perf::Deadline;
+use crate::scan::ScanEvalContext;
+
+/// Environment override for the socket path. Empty disables forwarding.
+pub const ENV_DAEMON_SOCKET: &str = "DCG_DAEMON_SOCKET";
+
+const SOCKET_FILE: &str = "daemon.sock";
+const PROTOCOL_VERSION: u32 = 1;
+/// Working directories with cached state; the cache is cleared past this.
+const MAX_PROJECTS: usize = 32;
+/// Largest request line accepted (the command plus a few short fields).
+const MAX_REQUEST_BYTES: u64 = 4 * 1024 * 1024;
+/// How long a client may take to send its request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(1);
+/// Shortest wait for an answer, even when the hook deadline is nearly spent.
+const MIN_CLIENT_TIMEOUT: Duration = Duration::from_millis(1);
+
+/// Socket the daemon listens on: `$DCG_DAEMON_SOCKET`, else `dcg/daemon.sock`
+/// in the runtime directory (or the cache directory where there is none).
+#[must_use]
+pub fn socket_path() -> Option<PathBuf> {
+    if let Ok(value) = env::var(ENV_DAEMON_SOCKET) {
+        let trimmed = value.trim();
+        return (!trimmed.is_empty()).then(|| PathBuf::from(trimmed));
+    }
+    dirs::runtime_dir()
+        .or_else(dirs::cache_dir)
+        .map(|dir| dir.join("dcg").join(SOCKET_FILE))
+}
+
+/// A request line.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Request {
+    Ping,
+    Evaluate(EvaluateRequest),
+    Shutdown,
+}
+
+/// Evaluate one command on behalf of a hook invocation.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EvaluateRequest {
+    pub v: u32,
+    /// Fingerprint of the hook's config and allowlists.
+    pub fingerprint: ConfigFingerprint,
+    pub cwd: PathBuf,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    /// Remaining hook deadline in microseconds.
+    pub budget_us: u64,
+    pub command: String,
+}
+
+/// A response line.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Response {
+    Pong(DaemonStatus),
+    Result(Box<WireResult>),
+    /// The hook should evaluate in-process.
+    Fallback {
+        reason: String,
+    },
+    Stopping,
+}
+
+/// What `dcg daemon status` reports.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DaemonStatus {
+    pub pid: u32,
+    pub version: String,
+    pub started_at: String,
+    /// Evaluations answered with a result.
+    pub evaluated: u64,
+    /// Evaluations handed back to the hook.
+    pub fallbacks: u64,
+    /// Working directories with cached state.
+    pub projects: usize,
+}
+
+/// [`EvaluationResult`] on the wire.
+///
+/// Suggestions and recovery hints are `'static` pack data, so only whether
+/// they were present is sent; the client looks them up in the registry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WireResult {
+    deny: bool,
+    #[serde(default)]
+    skipped_due_to_budget: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    effective_mode: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pattern: Option<WireMatch>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    allowlist: Option<WireAllowlist>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct WireMatch {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pack_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pattern_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    severity: Option<String>,
+    reason: String,
+    source: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    span: Option<(usize, usize)>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    preview: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    explanation: Option<String>,
+    #[serde(default)]
+    suggestions: bool,
+    #[serde(default)]
+    recovery_hint: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct WireAllowlist {
+    layer: String,
+    reason: String,
+    matched: WireMatch,
+}
+
+impl From<&EvaluationResult> for WireResult {
+    fn from(result: &EvaluationResult) -> Self {
+        Self {
+            deny: result.decision == EvaluationDecision::Deny,
+            skipped_due_to_budget: result.skipped_due_to_budget,
+            effective_mode: result.effective_mode.map(|mode| mode.label().to_string()),
+            pattern: result.pattern_info.as_ref().map(WireMatch::from),
+            allowlist: result
+                .allowlist_override
+                .as_ref()
+                .map(|override_| WireAllowlist {
+                    layer: override_.layer.label().to_string(),
+                    reason: override_.reason.clone(),
+                    matched: WireMatch::from(&override_.matched),
+                }),
+        }
+    }
+}
+
+impl WireResult {
+    /// Rebuild the evaluator result; `None` if any field does not parse.
+    #[must_use]
+    pub fn into_result(self) -> Option<EvaluationResult> {
+        let effective_mode = match self.effective_mode.as_deref() {
+            None => None,
+            Some("deny") => Some(DecisionMode::Deny),
+            Some("warn") => Some(DecisionMode::Warn),
+            Some("log") => Some(DecisionMode::Log),
+            Some(_) => return None,
+        };
+        let pattern_info = match self.pattern {
+            Some(pattern) => Some(pattern.into_match()?),
+            None => None,
+        };
+        let allowlist_override = match self.allowlist {
+            Some(allowlist) => Some(AllowlistOverride {
+                layer: match allowlist.layer.as_str() {
+                    "project" => AllowlistLayer::Project,
+                    "user" => AllowlistLayer::User,
+                    "system" => AllowlistLayer::System,
+                    "remote" => AllowlistLayer::Remote,
+                    _ => return None,
+                },
+                reason: allowlist.reason,
+                matched: allowlist.matched.into_match()?,
+            }),
+            None => None,
+        };
+
+        let mut result = if self.skipped_due_to_budget {
+            EvaluationResult::allowed_due_to_budget()
+        } else {
+            EvaluationResult::allowed()
+        };
+        if self.deny {
+            result.decision = EvaluationDecision::Deny;
+        }
+        result.effective_mode = effective_mode;
+        result.pattern_info = pattern_info;
+        result.allowlist_override = allowlist_override;
+        Some(result)
+    }
+}
+
+impl From<&PatternMatch> for WireMatch {
+    fn from(matched: &PatternMatch) -> Self {
+        Self {
+            pack_id: matched.pack_id.clone(),
+            pattern_name: matched.pattern_name.clone(),
+            severity: matched.severity.map(|s| s.label().to_string()),
+            reason: matched.reason.clone(),
+            source: source_label(matched.source).to_string(),
+            span: matched.matched_span.map(|span| (span.start, span.end)),
+            preview: matched.matched_text_preview.clone(),
+            explanation: matched.explanation.clone(),
+            suggestions: !matched.suggestions.is_empty(),
+            recovery_hint: matched.recovery_hint.is_some(),
+        }
+    }
+}
+
+impl WireMatch {
+    fn into_match(self) -> Option<PatternMatch> {
+        let severity = match self.severity.as_deref() {
+            Some(label) => Some(Severity::from_label(label)?),
+            None => None,
+        };
+        let source = parse_source(&self.source)?;
+        let (suggestions, recovery_hint) =
+            pattern_statics(self.pack_id.as_deref(), self.pattern_name.as_deref());
+        Some(PatternMatch {
+            pack_id: self.pack_id,
+            pattern_name: self.pattern_name,
+            severity,
+            reason: self.reason,
+            source,
+            matched_span: self.span.map(|(start, end)| MatchSpan { start, end }),
+            matched_text_preview: self.preview,
+            explanation: self.explanation,
+            suggestions: if self.suggestions { suggestions } else { &[] },
+            recovery_hint: recovery_hint.filter(|_| self.recovery_hint),
+        })
+    }
+}
+
+const fn source_label(source: MatchSource) -> &'static str {
+    match source {
+        MatchSource::ConfigOverride => "config_override",
+        MatchSource::LegacyPattern => "legacy_pattern",
+        MatchSource::Pack => "pack",
+        MatchSource::HeredocAst => "heredoc_ast",
+    }
+}
+
+fn parse_source(label: &str) -> Option<MatchSource> {
+    match label {
+        "config_override" => Some(MatchSource::ConfigOverride),
+        "legacy_pattern" => Some(MatchSource::LegacyPattern),
+        "pack" => Some(MatchSource::Pack),
+        "heredoc_ast" => Some(MatchSource::HeredocAst),
+        _ => None,
+    }
+}
+
+/// Suggestions and recovery hint of a built-in pack pattern.
+fn pattern_statics(
+    pack_id: Option<&str>,
+    pattern_name: Option<&str>,
+) -> (&'static [PatternSuggestion], Option<&'static str>) {
+    pack_id
+        .zip(pattern_name)
+        .and_then(|(pack_id, name)| {
+            REGISTRY
+                .get(pack_id)?
+                .destructive_patterns
+                .iter()
+                .find(|p| p.name == Some(name))
+        })
+        .map_or((&[][..], None), |p| (p.suggestions, p.recovery_hint))
+}
+
+/// Ask a running daemon to evaluate `command` for this hook invocation.
+///
+/// Returns `None` when there is no daemon, it does not answer within the
+/// deadline, or it hands the command back; the caller then evaluates
+/// in-process.
+#[must_use]
+pub fn evaluate_remote(
+    config: &Config,
+    fingerprint: &ConfigFingerprint,
+    command: &str,
+    cwd: &Path,
+    session_id: Option<&str>,
+    deadline: &Deadline,
+) -> Option<EvaluationResult> {
+    // External packs are cached process-wide, so the daemon cannot load the
+    // hook's set; skip the round trip.
+    if !config.packs.custom_paths.is_empty() {
+        return None;
+    }
+    let socket = socket_path().filter(|path| path.exists())?;
+    let remaining = deadline.remaining()?;
+    let request = Request::Evaluate(EvaluateRequest {
+        v: PROTOCOL_VERSION,
+        fingerprint: fingerprint.clone(),
+        cwd: cwd.to_path_buf(),
+        session_id: session_id.map(str::to_string),
+        budget_us: u64::try_from(remaining.as_micros()).unwrap_or(u64::MAX),
+        command: command.to_string(),
+    });
+    match send(&socket, &request, remaining.max(MIN_CLIENT_TIMEOUT)) {
+        Ok(Response::Result(wire)) => wire.into_result(),
+        _ => None,
+    }
+}
+
+/// Send one request and wait up to `timeout` for the response.
+///
+/// # Errors
+///
+/// Returns an error if the socket cannot be reached, times out, or answers
+/// with something that is not a response line.
+#[cfg(unix)]
+pub fn send(socket: &Path, request: &Request, timeout: Duration) -> io::Result<Response> {
+    use std::os::unix::net::UnixStream;
+
+    let stream = UnixStream::connect(socket)?;
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(timeout))?;
+
+    let mut line = serde_json::to_vec(request)?;
+    line.push(b'\n');
+    (&stream).write_all(&line)?;
+
+    let mut response = String::new();
+    BufReader::new(&stream).read_line(&mut response)?;
+    Ok(serde_json::from_str(&response)?)
+}
+
+#[cfg(not(unix))]
+pub fn send(_socket: &Path, _request: &Request, _timeout: Duration) -> io::Result<Response> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "dcg daemon requires Unix domain sockets",
+    ))
+}
+
+/// Evaluation state for one working directory.
+struct ProjectState {
+    fingerprint: ConfigFingerprint,
+    config: Config,
+    ctx: ScanEvalContext,
+}
+
+impl ProjectState {
+    /// Load config and allowlists for the current working directory.
+    fn load() -> Self {
+        let config = Config::load();
+        let ctx = ScanEvalContext::from_config(&config);
+        Self {
+            fingerprint: ConfigFingerprint::new(&config, &ctx.allowlists),
+            config,
+            ctx,
+        }
+    }
+}
+
+/// Daemon state shared across requests.
+struct Daemon {
+    projects: HashMap<PathBuf, ProjectState>,
+    started_at: String,
+    evaluated: u64,
+    fallbacks: u64,
+}
+
+impl Daemon {
+    fn new() -> Self {
+        Self {
+            projects: HashMap::new(),
+            started_at: chrono::Utc::now().to_rfc3339(),
+            evaluated: 0,
+            fallbacks: 0,
+        }
+    }
+
+    fn status(&self) -> DaemonStatus {
+        DaemonStatus {
+            pid: std::process::id(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            started_at: self.started_at.clone(),
+            evaluated: self.evaluated,
+            fallbacks: self.fallbacks,
+            projects: self.projects.len(),
+        }
+    }
+
+    fn handle(&mut self, request: Request) -> Response {
+        match request {
+            Request::Ping => Response::Pong(self.status()),
+            Request::Shutdown => Response::Stopping,
+            Request::Evaluate(request) => {
+                let response = self.evaluate(&request);
+                if matches!(response, Response::Result(_)) {
+                    self.evaluated += 1;
+                } else {
+                    self.fallbacks += 1;
+                }
+                response
+            }
+        }
+    }
+
+    fn evaluate(&mut self, request: &EvaluateRequest) -> Response {
+        let fallback = |reason: &str| Response::Fallback {
+            reason: reason.to_string(),
+        };
+        if request.v != PROTOCOL_VERSION {
+            return fallback("unsupported protocol version");
+        }
+        if env::set_current_dir(&request.cwd).is_err() {
+            return fallback("working directory is not accessible to the daemon");
+        }
+
+        if !self
+            .projects
+            .get(&request.cwd)
+            .is_some_and(|state| state.fingerprint == request.fingerprint)
+        {
+            if self.projects.len() >= MAX_PROJECTS {
+                self.projects.clear();
+            }
+            self.projects
+                .insert(request.cwd.clone(), ProjectState::load());
+        }
+        let Some(state) = self.projects.get(&request.cwd) else {
+            return fallback("no evaluation state");
+        };
+        if state.fingerprint != request.fingerprint {
+            return fallback("configuration differs from the hook's (environment overrides?)");
+        }
+        if !state.config.packs.custom_paths.is_empty() {
+            return fallback("external packs are evaluated in the hook");
+        }
+
+        crate::pending_exceptions::replace_hook_session_id(request.session_id.as_deref());
+        let deadline = Deadline::new(Duration::from_micros(request.budget_us));
+        let result = evaluate_command_with_pack_order_deadline_at_path(
+            &request.command,
+            &state.ctx.enabled_keywords,
+            &state.ctx.ordered_packs,
+            state.ctx.keyword_index.as_ref(),
+            &state.ctx.compiled_overrides,
+            &state.ctx.allowlists,
+            &state.ctx.heredoc_settings,
+            None,
+            None,
+            Some(&deadline),
+        );
+        Response::Result(Box::new(WireResult::from(&result)))
+    }
+}
+
+/// Listen on `socket` and serve requests until a `shutdown` request.
+///
+/// # Errors
+///
+/// Returns an error if another daemon is already listening on `socket` or
+/// the socket cannot be created.
+#[cfg(unix)]
+pub fn serve(socket: &Path) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    use std::os::unix::net::{UnixListener, UnixStream};
+
+    if UnixStream::connect(socket).is_ok() {
+        return Err(io::Error::new(
+            io::ErrorKind::AddrInUse,
+            format!("a dcg daemon is already listening on {}", socket.display()),
+        ));
+    }
+    if let Some(dir) = socket.parent() {
+        std::fs::create_dir_all(dir)?;
+        std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o700))?;
+    }
+    // Left behind by a daemon that did not shut down cleanly.
+    let _ = std::fs::remove_file(socket);
+    let listener = UnixListener::bind(socket)?;
+    std::fs::set_permissions(socket, std::fs::Permissions::from_mode(0o600))?;
+
+    let mut daemon = Daemon::new();
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else { continue };
+        if stream.set_read_timeout(Some(REQUEST_TIMEOUT)).is_err() {
+            continue;
+        }
+        let mut line = String::new();
+        if BufReader::new((&stream).take(MAX_REQUEST_BYTES))
+            .read_line(&mut line)
+            .is_err()
+        {
+            continue;
+        }
+        let response = match serde_json::from_str::<Request>(&line) {
+            Ok(request) => daemon.handle(request),
+            Err(e) => Response::Fallback {
+                reason: format!("invalid request: {e}"),
+            },
+        };
+        if let Ok(mut out) = serde_json::to_vec(&response) {
+            out.push(b'\n');
+            let _ = (&stream).write_all(&out);
+        }
+        if matches!(response, Response::Stopping) {
+            break;
+        }
+    }
+
+    let _ = std::fs::remove_file(socket);
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn serve(_socket: &Path) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "dcg daemon requires Unix domain sockets",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wire_result_round_trips_pack_denial() {
+        let original = EvaluationResult::denied_by_pack_pattern(
+            "core.git",
+            "reset-hard",
+            "git reset --hard destroys uncommitted changes.",
+            Some("explanation"),
+            Severity::Critical,
+            &[],
+        );
+        let line = serde_json::to_string(&Response::Result(Box::new(WireResult::from(&original))))
+            .unwrap();
+        let Response::Result(wire) = serde_json::from_str(&line).unwrap() else {
+            panic!("expected a result");
+        };
+        let result = wire.into_result().expect("parses");
+
+        assert!(result.is_denied());
+        let info = result.pattern_info.expect("pattern info");
+        assert_eq!(info.pack_id.as_deref(), Some("core.git"));
+        assert_eq!(info.pattern_name.as_deref(), Some("reset-hard"));
+        assert_eq!(info.severity, Some(Severity::Critical));
+        assert_eq!(info.source, MatchSource::Pack);
+        assert_eq!(result.effective_mode, original.effective_mode);
+    }
+
+    #[test]
+    fn wire_result_keeps_budget_skip() {
+        let wire = WireResult::from(&EvaluationResult::allowed_due_to_budget());
+        let result = wire.into_result().expect("parses");
+        assert!(result.is_allowed());
+        assert!(result.skipped_due_to_budget);
+    }
+
+    #[test]
+    fn unknown_labels_fall_back() {
+        let wire: WireResult = serde_json::from_str(
+            r#"{"deny":true,"pattern":{"reason":"x","source":"something-new"}}"#,
+        )
+        .unwrap();
+        assert!(wire.into_result().is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn ping_and_shutdown_over_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join(SOCKET_FILE);
+        let server_socket = socket.clone();
+        let server = std::thread::spawn(move || serve(&server_socket));
+
+        let timeout = Duration::from_secs(2);
+        let mut status = None;
+        for _ in 0..200 {
+            if let Ok(Response::Pong(pong)) = send(&socket, &Request::Ping, timeout) {
+                status = Some(pong);
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        let status = status.expect("daemon answers ping");
+        assert_eq!(status.pid, std::process::id());
+        assert_eq!(status.evaluated, 0);
+
+        assert!(matches!(
+            send(&socket, &Request::Shutdown, timeout),
+            Ok(Response::Stopping)
+        ));
+        server.join().unwrap().unwrap();
+        assert!(!socket.exists());
+    }
+}
@@ -0,0 +1,502 @@
+//! Encoded-command detection.
+//!
+//! Agents (and prompt-injected instructions) sometimes hide a command behind
+//! an encoding so no pattern ever sees it:
+//!
+//! ```text
+//! echo cm0gLXJmIC8= | base64 -d | sh
+//! eval "$(printf '\x72\x6d\x20\x2d\x72\x66\x20\x2f')"
+//! python3 -c "exec(base64.b64decode('aW1wb3J0IHNodXRpbA=='))"
+//! ```
+//!
+//! This module finds such payloads, decodes them, and reports what would
+//! actually run (and in which language) so the evaluator can check the
+//! decoded content through the normal pipeline.
+//!
+//! # Design Principles
+//!
+//! - **Executed payloads only**: A decoded payload is reported only when it
+//!   is piped into a shell or interpreter, substituted into `eval`/`sh -c`,
+//!   or passed to `exec`/`eval`/`system` inside an inline script. Decoding
+//!   to a file or the terminal is left alone.
+//! - **Bounded**: Encoded literals longer than [`MAX_ENCODED_LEN`] are
+//!   skipped and at most [`MAX_PAYLOADS`] payloads are returned per command.
+//!   Decoding always shrinks the text, so re-evaluating a payload that itself
+//!   contains an encoded payload terminates.
+//! - **Fail-open**: Anything that does not decode to UTF-8 is ignored.
+
+use std::sync::LazyLock;
+
+use base64::Engine;
+use base64::engine::general_purpose::{STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD};
+use regex::{Captures, Regex};
+
+use crate::heredoc::ScriptLanguage;
+
+/// Longest encoded literal that is decoded.
+pub const MAX_ENCODED_LEN: usize = 64 * 1024;
+
+/// Most payloads returned for a single command.
+pub const MAX_PAYLOADS: usize = 16;
+
+/// Substrings that must appear before any regex is run.
+const TRIGGERS: &[&str] = &[
+    "base64",
+    "b64decode",
+    "decode64",
+    "xxd",
+    "fromhex",
+    "unhexlify",
+    "atob",
+    "Buffer.from",
+];
+
+/// Shell-side decoder commands.
+const DECODER: &str = r"base64\s+(?:-d|-D|--decode)\b|openssl\s+(?:enc\s+)?-?base64\s+-d\b|xxd\s+(?:-r\s+-p|-p\s+-r|-rp|-pr|-r\s+-ps|-ps\s+-r)\b";
+
+/// Characters allowed in an encoded literal.
+const DATA: &str = r"[A-Za-z0-9+/=_-]{8,}";
+
+/// `echo <data> | base64 -d` / `printf '%s' <data> | xxd -r -p`.
+static ECHO_INTO_DECODER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r#"\b(?:echo|printf)(?:\s+-[a-zA-Z]+)*(?:\s+(?:'%s'|"%s"))?\s+['"]?(?P<data>{DATA})['"]?\s*\|\s*(?P<decoder>{DECODER})"#
+    ))
+    .expect("echo decoder regex compiles")
+});
+
+/// `base64 -d <<< <data>`.
+static HERESTRING_DECODER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r#"(?P<decoder>{DECODER})\s*<<<\s*['"]?(?P<data>{DATA})['"]?"#
+    ))
+    .expect("herestring decoder regex compiles")
+});
+
+/// `printf '\x72\x6d...'` / `echo -e "\x72..."`.
+static PRINTF_ESCAPES: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"\b(?:printf|echo\s+-e)\s+(?:--\s+)?(?:'(?P<sq>[^']*)'|"(?P<dq>[^"]*)")"#)
+        .expect("printf escapes regex compiles")
+});
+
+/// `exec(base64.b64decode('...'))`, `system(decode_base64("..."))`,
+/// `eval(Buffer.from('...', 'base64').toString())`, ...
+static INTERPRETER_CALL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"(?P<sink>\bexec|\beval|\bsystem|\bsubprocess\.(?:run|call|check_call|check_output|Popen)|\bexecSync|\bspawnSync)\s*\(?\s*(?P<decode>base64\.b64decode|base64\.decodebytes|b64decode|Base64\.strict_decode64|Base64\.decode64|decode_base64|atob|Buffer\.from|bytes\.fromhex|binascii\.unhexlify|unhexlify)\s*\(\s*b?['"](?P<data>[A-Za-z0-9+/=_-]+)['"](?:\s*,\s*['"](?P<enc>[\w-]+)['"])?"#,
+    )
+    .expect("interpreter call regex compiles")
+});
+
+/// What follows a decoder: `| sh`, `| sudo bash`, `| python3 -`, ...
+static PIPE_SINK: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^[^|;&)`]*\|\s*(?:sudo\s+(?:-\S+\s+)*)?(?:env\s+)?(?P<sink>[\w./-]+)")
+        .expect("pipe sink regex compiles")
+});
+
+/// What precedes a `$(...)`: `eval`, `source`, `bash -c`, `python3 -c`, ...
+static SUBSTITUTION_SINK: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?:^|[\s;&|(])(?:(?P<eval>eval|source|\.)|(?P<sink>[\w./-]+)\s+-[ce])\s*["']?$"#)
+        .expect("substitution sink regex compiles")
+});
+
+/// How a payload was encoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Base64,
+    Hex,
+    PrintfEscapes,
+}
+
+impl Encoding {
+    /// Human-readable name used in deny reasons.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Base64 => "base64",
+            Self::Hex => "hex",
+            Self::PrintfEscapes => "printf-escaped",
+        }
+    }
+}
+
+/// A decoded payload that the command would execute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodedPayload {
+    /// How the payload was encoded.
+    pub encoding: Encoding,
+    /// Language the decoded text runs as ([`ScriptLanguage::Bash`] for shells).
+    pub language: ScriptLanguage,
+    /// The decoded text.
+    pub decoded: String,
+    /// Byte offset of the encoded literal in the command.
+    pub start: usize,
+    /// Byte offset one past the encoded literal.
+    pub end: usize,
+}
+
+impl EncodedPayload {
+    /// First line of the decoded text, shortened for deny reasons.
+    #[must_use]
+    pub fn preview(&self) -> String {
+        const MAX_CHARS: usize = 60;
+        let line = self
+            .decoded
+            .lines()
+            .find(|l| !l.trim().is_empty())
+            .unwrap_or("");
+        let line = line.trim();
+        if line.chars().count() > MAX_CHARS {
+            let truncated: String = line.chars().take(MAX_CHARS).collect();
+            format!("{truncated}…")
+        } else {
+            line.to_string()
+        }
+    }
+}
+
+/// Find and decode the encoded payloads that `command` would execute.
+#[must_use]
+pub fn extract_encoded_payloads(command: &str) -> Vec<EncodedPayload> {
+    if !TRIGGERS.iter().any(|t| command.contains(t)) && !has_byte_escape(command) {
+        return Vec::new();
+    }
+
+    let mut payloads = Vec::new();
+
+    for caps in ECHO_INTO_DECODER
+        .captures_iter(command)
+        .chain(HERESTRING_DECODER.captures_iter(command))
+    {
+        let (Some(whole), Some(data)) = (caps.get(0), caps.name("data")) else {
+            continue;
+        };
+        let encoding = if caps["decoder"].starts_with("xxd") {
+            Encoding::Hex
+        } else {
+            Encoding::Base64
+        };
+        let Some(language) = shell_sink(command, whole.start(), whole.end()) else {
+            continue;
+        };
+        push_decoded(&mut payloads, encoding, language, data);
+    }
+
+    for caps in PRINTF_ESCAPES.captures_iter(command) {
+        let (Some(whole), Some(data)) = (caps.get(0), caps.name("sq").or_else(|| caps.name("dq")))
+        else {
+            continue;
+        };
+        if !has_byte_escape(data.as_str()) {
+            continue;
+        }
+        let Some(language) = shell_sink(command, whole.start(), whole.end()) else {
+            continue;
+        };
+        push_decoded(&mut payloads, Encoding::PrintfEscapes, language, data);
+    }
+
+    for caps in INTERPRETER_CALL.captures_iter(command) {
+        let Some(data) = caps.name("data") else {
+            continue;
+        };
+        let (encoding, language) = interpreter_call_kind(&caps);
+        push_decoded(&mut payloads, encoding, language, data);
+    }
+
+    payloads.sort_by_key(|p| p.start);
+    payloads.dedup_by(|a, b| a.start == b.start);
+    payloads.truncate(MAX_PAYLOADS);
+    payloads
+}
+
+fn push_decoded(
+    payloads: &mut Vec<EncodedPayload>,
+    encoding: Encoding,
+    language: ScriptLanguage,
+    data: regex::Match<'_>,
+) {
+    if data.len() > MAX_ENCODED_LEN {
+        return;
+    }
+    let bytes = match encoding {
+        Encoding::Base64 => decode_base64(data.as_str()),
+        Encoding::Hex => decode_hex(data.as_str()),
+        Encoding::PrintfEscapes => Some(decode_printf_escapes(data.as_str())),
+    };
+    let Some(decoded) = bytes.and_then(|b| String::from_utf8(b).ok()) else {
+        return;
+    };
+    if decoded.trim().is_empty() {
+        return;
+    }
+    payloads.push(EncodedPayload {
+        encoding,
+        language,
+        decoded,
+        start: data.start(),
+        end: data.end(),
+    });
+}
+
+/// Language a shell-side decoder's output runs as, if it runs at all.
+///
+/// The decoder spans `start..end`; it runs when piped into a shell or
+/// interpreter, or when it sits inside `$(...)`, backticks, or `<(...)`
+/// handed to `eval`/`source`/`sh -c`.
+fn shell_sink(command: &str, start: usize, end: usize) -> Option<ScriptLanguage> {
+    if let Some(caps) = PIPE_SINK.captures(&command[end..]) {
+        return sink_language(&caps["sink"]);
+    }
+
+    let prefix = command[..start].trim_end();
+    let prefix = prefix
+        .strip_suffix("$(")
+        .or_else(|| prefix.strip_suffix('`'))
+        .or_else(|| prefix.strip_suffix("<("))?
+        .trim_end();
+    let caps = SUBSTITUTION_SINK.captures(prefix)?;
+    if caps.name("eval").is_some() {
+        return Some(ScriptLanguage::Bash);
+    }
+    sink_language(&caps["sink"])
+}
+
+fn sink_language(word: &str) -> Option<ScriptLanguage> {
+    let base = word.rsplit('/').next().unwrap_or(word);
+    match base {
+        "sh" | "bash" | "zsh" | "dash" | "ksh" | "fish" | "source" | "." | "eval" => {
+            Some(ScriptLanguage::Bash)
+        }
+        _ => Some(ScriptLanguage::from_command(base)).filter(|l| *l != ScriptLanguage::Unknown),
+    }
+}
+
+fn interpreter_call_kind(caps: &Captures<'_>) -> (Encoding, ScriptLanguage) {
+    let decode = &caps["decode"];
+    let encoding = match decode {
+        "bytes.fromhex" | "binascii.unhexlify" | "unhexlify" => Encoding::Hex,
+        "Buffer.from" if caps.name("enc").is_some_and(|e| e.as_str() == "hex") => Encoding::Hex,
+        _ => Encoding::Base64,
+    };
+    let script_language = match decode {
+        "Base64.strict_decode64" | "Base64.decode64" => ScriptLanguage::Ruby,
+        "decode_base64" => ScriptLanguage::Perl,
+        "atob" | "Buffer.from" => ScriptLanguage::JavaScript,
+        _ => ScriptLanguage::Python,
+    };
+    let sink = &caps["sink"];
+    // Perl's and Ruby's `exec` replace the process with a shell command;
+    // Python's runs Python source.
+    let runs_shell = sink != "eval"
+        && (sink != "exec"
+            || matches!(script_language, ScriptLanguage::Perl | ScriptLanguage::Ruby));
+    let language = if runs_shell {
+        ScriptLanguage::Bash
+    } else {
+        script_language
+    };
+    (encoding, language)
+}
+
+fn decode_base64(data: &str) -> Option<Vec<u8>> {
+    [STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD]
+        .iter()
+        .find_map(|engine| engine.decode(data).ok())
+}
+
+fn decode_hex(data: &str) -> Option<Vec<u8>> {
+    if data.len() % 2 != 0 {
+        return None;
+    }
+    (0..data.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(data.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Whether `data` contains a `\xHH` or octal escape.
+fn has_byte_escape(data: &str) -> bool {
+    data.as_bytes()
+        .windows(2)
+        .any(|w| w[0] == b'\\' && (w[1] == b'x' || w[1].is_ascii_digit()))
+}
+
+/// Decode the escapes `printf` (and `echo -e`) interpret.
+fn decode_printf_escapes(data: &str) -> Vec<u8> {
+    let bytes = data.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+        if b == b'%' && bytes.get(i + 1) == Some(&b'%') {
+            out.push(b'%');
+            i += 2;
+            continue;
+        }
+        if b != b'\\' || i + 1 >= bytes.len() {
+            out.push(b);
+            i += 1;
+            continue;
+        }
+        let next = bytes[i + 1];
+        i += 2;
+        match next {
+            b'x' => {
+                let digits = take_digits(&bytes[i..], 2, 16);
+                if digits == 0 {
+                    out.extend_from_slice(b"\\x");
+                } else {
+                    let hex = std::str::from_utf8(&bytes[i..i + digits]).unwrap_or("");
+                    out.push(u8::from_str_radix(hex, 16).unwrap_or(b'?'));
+                    i += digits;
+                }
+            }
+            b'0'..=b'7' => {
+                // `\0NNN` (echo -e) and `\NNN` (printf).
+                let (skip, max) = if next == b'0' {
+                    (0, 3)
+                } else {
+                    (usize::from(next - b'0'), 2)
+                };
+                let digits = take_digits(&bytes[i..], max, 8);
+                let mut value = skip;
+                for &d in &bytes[i..i + digits] {
+                    value = value * 8 + usize::from(d - b'0');
+                }
+                out.push(u8::try_from(value).unwrap_or(b'?'));
+                i += digits;
+            }
+            b'n' => out.push(b'\n'),
+            b't' => out.push(b'\t'),
+            b'r' => out.push(b'\r'),
+            b'\\' => out.push(b'\\'),
+            other => {
+                out.push(b'\\');
+                out.push(other);
+            }
+        }
+    }
+    out
+}
+
+fn take_digits(bytes: &[u8], max: usize, radix: u32) -> usize {
+    bytes
+        .iter()
+        .take(max)
+        .take_while(|b| char::from(**b).is_digit(radix))
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decoded(command: &str) -> Vec<(Encoding, ScriptLanguage, String)> {
+        extract_encoded_payloads(command)
+            .into_iter()
+            .map(|p| (p.encoding, p.language, p.decoded))
+            .collect()
+    }
+
+    #[test]
+    fn base64_piped_to_shell() {
+        // "rm -rf /"
+        assert_eq!(
+            decoded("echo cm0gLXJmIC8= | base64 -d | sh"),
+            vec![(
+                Encoding::Base64,
+                ScriptLanguage::Bash,
+                "rm -rf /".to_string()
+            )]
+        );
+        assert_eq!(
+            decoded("base64 --decode <<< 'cm0gLXJmIC8=' | sudo bash"),
+            vec![(
+                Encoding::Base64,
+                ScriptLanguage::Bash,
+                "rm -rf /".to_string()
+            )]
+        );
+        assert_eq!(
+            decoded("echo 726d202d7266202f | xxd -r -p | bash"),
+            vec![(Encoding::Hex, ScriptLanguage::Bash, "rm -rf /".to_string())]
+        );
+    }
+
+    #[test]
+    fn substitutions_into_eval_and_shell_c() {
+        assert_eq!(
+            decoded(r#"eval "$(printf '\x72\x6d\x20\x2d\x72\x66\x20\x2f')""#),
+            vec![(
+                Encoding::PrintfEscapes,
+                ScriptLanguage::Bash,
+                "rm -rf /".to_string()
+            )]
+        );
+        assert_eq!(
+            decoded(r#"bash -c "$(echo cm0gLXJmIC8= | base64 -d)""#),
+            vec![(
+                Encoding::Base64,
+                ScriptLanguage::Bash,
+                "rm -rf /".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn interpreter_exec_calls() {
+        // "import shutil; shutil.rmtree('/')"
+        let py = "python3 -c \"import base64; exec(base64.b64decode('aW1wb3J0IHNodXRpbDsgc2h1dGlsLnJtdHJlZSgnLycp'))\"";
+        assert_eq!(
+            decoded(py),
+            vec![(
+                Encoding::Base64,
+                ScriptLanguage::Python,
+                "import shutil; shutil.rmtree('/')".to_string()
+            )]
+        );
+        let perl = "perl -MMIME::Base64 -e 'system(decode_base64(\"cm0gLXJmIC8=\"))'";
+        assert_eq!(
+            decoded(perl),
+            vec![(
+                Encoding::Base64,
+                ScriptLanguage::Bash,
+                "rm -rf /".to_string()
+            )]
+        );
+        let node = "node -e \"require('child_process').execSync(Buffer.from('726d202d7266202f', 'hex').toString())\"";
+        assert_eq!(
+            decoded(node),
+            vec![(Encoding::Hex, ScriptLanguage::Bash, "rm -rf /".to_string())]
+        );
+    }
+
+    #[test]
+    fn decoding_without_execution_is_ignored() {
+        assert!(decoded("echo cm0gLXJmIC8= | base64 -d > out.txt").is_empty());
+        assert!(decoded("echo cm0gLXJmIC8= | base64 -d | less").is_empty());
+        assert!(decoded(r"printf '\x72\x6d\n'").is_empty());
+        assert!(decoded("echo hello | base64 | sh").is_empty());
+        // Not valid UTF-8 once decoded.
+        assert!(decoded("echo //////////// | base64 -d | sh").is_empty());
+    }
+
+    #[test]
+    fn oversized_literals_are_skipped() {
+        let data = "A".repeat(MAX_ENCODED_LEN + 4);
+        assert!(decoded(&format!("echo {data} | base64 -d | sh")).is_empty());
+    }
+
+    #[test]
+    fn preview_is_first_line_truncated() {
+        let payload = EncodedPayload {
+            encoding: Encoding::Base64,
+            language: ScriptLanguage::Bash,
+            decoded: format!("\n{}\nsecond", "x".repeat(100)),
+            start: 0,
+            end: 0,
+        };
+        assert_eq!(payload.preview().chars().count(), 61);
+    }
+}
@@ -0,0 +1,290 @@
+//! Environment-variable expansion before pattern matching.
+//!
+//! Path-based safe patterns (e.g. `rm -rf` under `/tmp` or a build directory)
+//! only see the literal command text, so `rm -rf $PROJECT_DIR/..` or
+//! `DIR=/; rm -rf $DIR` would be judged by what the variable *looks* like
+//! rather than what it holds. This pass resolves simple `$VAR` / `${VAR}`
+//! references from the hook's environment, and from assignments earlier in
+//! the same command, before evaluation.
+//!
+//! # Design Principles
+//!
+//! - **Shell-like**: Nothing inside single quotes is expanded; `NAME=value`
+//!   words in command position (optionally after `export`/`local`/...) shadow
+//!   the environment for the rest of the command.
+//! - **Conservative**: Unset variables, positional and special parameters, and
+//!   `${VAR:-default}`-style forms are left literally. So are values containing
+//!   shell metacharacters, which would otherwise create command boundaries
+//!   that do not exist in the real command.
+//! - **Bounded**: Values longer than [`MAX_VALUE_LEN`] are not substituted.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// Longest variable value that is substituted.
+pub const MAX_VALUE_LEN: usize = 4096;
+
+/// Builtins whose `NAME=value` arguments are assignments.
+const DECLARATION_BUILTINS: &[&str] = &["export", "local", "declare", "readonly", "typeset"];
+
+/// Characters that would change the command structure if substituted.
+const UNSAFE_VALUE_CHARS: &[char] = &[
+    ';', '&', '|', '<', '>', '(', ')', '`', '$', '\'', '"', '\\', '\n', '\r',
+];
+
+/// Expand variables in `command` when `enabled`, using the process environment.
+#[must_use]
+pub fn expand_if_enabled(command: Cow<'_, str>, enabled: bool) -> Cow<'_, str> {
+    if !enabled {
+        return command;
+    }
+    expand_variables(&command, &|key| std::env::var(key).ok()).map_or(command, Cow::Owned)
+}
+
+/// Expand `$VAR` / `${VAR}` references in `command`.
+///
+/// Variables are looked up in assignments made earlier in the command first,
+/// then through `get_env`. Returns `None` when nothing was expanded.
+#[must_use]
+pub fn expand_variables(command: &str, get_env: &dyn Fn(&str) -> Option<String>) -> Option<String> {
+    if !command.contains('$') {
+        return None;
+    }
+
+    let chars: Vec<char> = command.chars().collect();
+    let mut out = String::with_capacity(command.len());
+    let mut assigned: HashMap<String, String> = HashMap::new();
+    let mut changed = false;
+
+    let mut in_single = false;
+    let mut in_double = false;
+    let mut command_position = true;
+    let mut after_declaration = false;
+    let mut in_word = false;
+    let mut word = String::new();
+    let mut assignment: Option<(String, String)> = None;
+
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let quoted = in_single || in_double;
+
+        // End of an unquoted word.
+        if !quoted && (c.is_whitespace() || is_separator(c)) {
+            if in_word {
+                if let Some((name, value)) = assignment.take() {
+                    assigned.insert(name, value);
+                } else if DECLARATION_BUILTINS.contains(&word.as_str()) {
+                    after_declaration = true;
+                } else if !(after_declaration && word.starts_with('-')) {
+                    command_position = false;
+                    after_declaration = false;
+                }
+                word.clear();
+                in_word = false;
+            }
+            if is_separator(c) {
+                command_position = true;
+                after_declaration = false;
+            }
+            out.push(c);
+            i += 1;
+            continue;
+        }
+
+        // Start of a word in command position: `NAME=value`?
+        let assignment_len = (!in_word && !quoted && (command_position || after_declaration))
+            .then(|| assignment_name_len(&chars[i..]))
+            .flatten();
+        if let Some(name_len) = assignment_len {
+            let name: String = chars[i..i + name_len].iter().collect();
+            out.push_str(&name);
+            out.push('=');
+            assignment = Some((name, String::new()));
+            in_word = true;
+            i += name_len + 1;
+            continue;
+        }
+        in_word = true;
+
+        match c {
+            '\'' if !in_double => {
+                in_single = !in_single;
+                out.push(c);
+            }
+            '"' if !in_single => {
+                in_double = !in_double;
+                out.push(c);
+            }
+            '\\' if !in_single && i + 1 < chars.len() => {
+                out.push(c);
+                out.push(chars[i + 1]);
+                push_word_char(&mut assignment, &mut word, chars[i + 1]);
+                i += 1;
+            }
+            '$' if !in_single => {
+                let (name, consumed) = variable_reference(&chars[i + 1..]);
+                let value = name.and_then(|name| {
+                    assigned
+                        .get(&name)
+                        .cloned()
+                        .or_else(|| get_env(&name))
+                        .filter(|value| is_inert(value))
+                });
+                if let Some(value) = value {
+                    out.push_str(&value);
+                    if let Some((_, assigned_value)) = assignment.as_mut() {
+                        assigned_value.push_str(&value);
+                    } else {
+                        word.push_str(&value);
+                    }
+                    changed = true;
+                } else {
+                    // Keep the reference literally; the assignment value is
+                    // unknown, so do not record it.
+                    let literal: String = chars[i..=i + consumed].iter().collect();
+                    out.push_str(&literal);
+                    if let Some((name, _)) = assignment.take() {
+                        assigned.remove(&name);
+                    }
+                    word.push_str(&literal);
+                }
+                i += consumed;
+            }
+            c => {
+                out.push(c);
+                push_word_char(&mut assignment, &mut word, c);
+            }
+        }
+        i += 1;
+    }
+
+    changed.then_some(out)
+}
+
+fn push_word_char(assignment: &mut Option<(String, String)>, word: &mut String, c: char) {
+    if let Some((_, value)) = assignment.as_mut() {
+        value.push(c);
+    } else {
+        word.push(c);
+    }
+}
+
+const fn is_separator(c: char) -> bool {
+    matches!(c, ';' | '&' | '|' | '(' | ')' | '\n')
+}
+
+/// Length of `NAME` when `chars` starts with `NAME=`.
+fn assignment_name_len(chars: &[char]) -> Option<usize> {
+    let first = chars.first()?;
+    if !(first.is_ascii_alphabetic() || *first == '_') {
+        return None;
+    }
+    let len = chars
+        .iter()
+        .take_while(|c| c.is_ascii_alphanumeric() || **c == '_')
+        .count();
+    (chars.get(len) == Some(&'=')).then_some(len)
+}
+
+/// Parse the variable name after a `$`.
+///
+/// Returns the name (if the reference is a simple `$NAME` or `${NAME}`) and
+/// how many chars after the `$` belong to the reference.
+fn variable_reference(rest: &[char]) -> (Option<String>, usize) {
+    let is_name = |c: &char| c.is_ascii_alphanumeric() || *c == '_';
+    if rest.first() == Some(&'{') {
+        if let Some(end) = rest.iter().position(|c| *c == '}') {
+            let name: String = rest[1..end].iter().collect();
+            let simple = name.chars().next().is_some_and(|c| !c.is_ascii_digit())
+                && name.chars().all(|c| is_name(&c));
+            return (simple.then_some(name), end + 1);
+        }
+        return (None, 0);
+    }
+    let name: String = rest.iter().take_while(|c| is_name(c)).collect();
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        return (None, 0);
+    }
+    let len = name.chars().count();
+    (Some(name), len)
+}
+
+fn is_inert(value: &str) -> bool {
+    value.len() <= MAX_VALUE_LEN && !value.contains(UNSAFE_VALUE_CHARS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(key: &str) -> Option<String> {
+        match key {
+            "PROJECT_DIR" => Some("/home/dev/app".to_string()),
+            "SHELL_SNIPPET" => Some("x; rm -rf /".to_string()),
+            _ => None,
+        }
+    }
+
+    fn expand(command: &str) -> String {
+        expand_variables(command, &env).unwrap_or_else(|| command.to_string())
+    }
+
+    #[test]
+    fn expands_environment_references() {
+        assert_eq!(expand("rm -rf $PROJECT_DIR/.."), "rm -rf /home/dev/app/..");
+        assert_eq!(
+            expand("rm -rf \"${PROJECT_DIR}/build\""),
+            "rm -rf \"/home/dev/app/build\""
+        );
+        assert_eq!(
+            expand("DIR=/tmp/x; rm -rf ${DIR}/build"),
+            "DIR=/tmp/x; rm -rf /tmp/x/build"
+        );
+        assert_eq!(
+            expand("echo ${PROJECT_DIR:-/tmp}}"),
+            "echo ${PROJECT_DIR:-/tmp}}"
+        );
+    }
+
+    #[test]
+    fn earlier_assignments_shadow_the_environment() {
+        assert_eq!(expand("DIR=/; rm -rf $DIR"), "DIR=/; rm -rf /");
+        assert_eq!(
+            expand("export PROJECT_DIR=/ && rm -rf \"$PROJECT_DIR\""),
+            "export PROJECT_DIR=/ && rm -rf \"/\""
+        );
+        assert_eq!(
+            expand("A=/srv B=$A; rm -rf $B"),
+            "A=/srv B=/srv; rm -rf /srv"
+        );
+    }
+
+    #[test]
+    fn leaves_unresolvable_references_alone() {
+        assert_eq!(expand_variables("rm -rf '$PROJECT_DIR'", &env), None);
+        assert_eq!(expand_variables("rm -rf $UNSET/build", &env), None);
+        assert_eq!(expand_variables("rm -rf ${PROJECT_DIR:-/tmp}", &env), None);
+        assert_eq!(expand_variables("echo $1 $@ $?", &env), None);
+        assert_eq!(expand_variables("echo $(pwd)", &env), None);
+        // A value with metacharacters would invent a second command.
+        assert_eq!(expand_variables("echo $SHELL_SNIPPET", &env), None);
+        // An assignment from an unknown value must not be recorded.
+        assert_eq!(expand_variables("D=$(pwd); rm -rf $D", &env), None);
+    }
+
+    #[test]
+    fn non_assignment_arguments_are_not_assignments() {
+        // `X=/` here is an argument to echo, not an assignment.
+        assert_eq!(expand_variables("echo X=/; rm -rf $X", &env), None);
+    }
+
+    #[test]
+    fn disabled_returns_input_unchanged() {
+        let command = Cow::Borrowed("DIR=/; rm -rf $DIR");
+        assert!(matches!(
+            expand_if_enabled(command, false),
+            Cow::Borrowed("DIR=/; rm -rf $DIR")
+        ));
+    }
+}
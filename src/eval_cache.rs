@@ -0,0 +1,416 @@
+//! Persistent cache of clean allow decisions.
+//!
+//! Every hook invocation is a fresh process, and agents often re-run the same
+//! command several times (`cargo test`, `git status`, ...). When `[cache]` is
+//! enabled, a command that evaluated to a clean allow (no rule matched, no
+//! allowlist or allow-once involved, evaluation not cut short by the deadline)
+//! is recorded here, keyed by a hash of the command and working directory.
+//! Later invocations that hit the cache skip evaluation entirely.
+//!
+//! Only clean allows are cached because they are the only decisions that do
+//! not depend on mutable state outside the configuration: allowlists and
+//! allow-once entries can only turn a deny into an allow, and branch awareness
+//! and policy modes only apply once a rule has matched. Everything that *can*
+//! change a clean allow (config layers, enabled packs, external pack files, the
+//! dcg version) is folded into the [`ConfigFingerprint`] stored with the cache;
+//! a fingerprint mismatch discards every entry. The fingerprint also covers the
+//! allowlist files, so editing an allowlist starts a fresh cache as well.
+//!
+//! The cache is a small JSON file guarded by an exclusive file lock. All
+//! failures are treated as a miss so the cache can never block a command.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use fs2::FileExt;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::config::{Config, EvalCacheConfig};
+use crate::evaluator::{EvaluationDecision, EvaluationResult};
+use crate::fingerprint::ConfigFingerprint;
+
+/// Environment variable overriding the cache file location.
+pub const ENV_EVAL_CACHE_PATH: &str = "DCG_EVAL_CACHE_PATH";
+
+const EVAL_CACHE_FILE: &str = "eval_cache.json";
+const SCHEMA_VERSION: u32 = 1;
+/// Hits only rewrite the file when the entry's recency is older than this,
+/// so a burst of identical commands does not turn every hit into a write.
+const TOUCH_INTERVAL_SECS: i64 = 60;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheFile {
+    schema_version: u32,
+    fingerprint: String,
+    entries: Vec<CacheEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    key: String,
+    inserted_at: i64,
+    last_used: i64,
+}
+
+/// Summary of the on-disk cache for `dcg cache status`.
+#[derive(Debug, Clone, Serialize)]
+pub struct EvalCacheStatus {
+    /// Cache file location.
+    pub path: PathBuf,
+    /// Entries that are still valid for the current configuration.
+    pub live_entries: usize,
+    /// Entries that are expired or were recorded under a different configuration.
+    pub stale_entries: usize,
+    /// Whether the stored fingerprint matches the current configuration.
+    pub fingerprint_matches: bool,
+}
+
+/// File-backed cache of commands known to evaluate to a clean allow.
+#[derive(Debug, Clone)]
+pub struct EvalCache {
+    path: PathBuf,
+    fingerprint: String,
+    max_entries: usize,
+    ttl_secs: i64,
+}
+
+impl EvalCache {
+    /// Create a cache handle.
+    #[must_use]
+    pub fn new(path: PathBuf, fingerprint: String, config: &EvalCacheConfig) -> Self {
+        Self {
+            path,
+            fingerprint,
+            max_entries: config.max_entries,
+            ttl_secs: i64::try_from(config.ttl_secs).unwrap_or(i64::MAX),
+        }
+    }
+
+    /// Cache handle for the effective configuration, whether or not `[cache]`
+    /// is enabled. Returns `None` when no cache location can be determined.
+    #[must_use]
+    pub fn for_config(config: &Config, fingerprint: &ConfigFingerprint) -> Option<Self> {
+        let path = Self::default_path(&config.cache)?;
+        Some(Self::new(path, fingerprint.to_string(), &config.cache))
+    }
+
+    /// Resolve the cache file: `DCG_EVAL_CACHE_PATH`, then `[cache] path`,
+    /// then `<cache dir>/dcg/eval_cache.json`.
+    #[must_use]
+    pub fn default_path(config: &EvalCacheConfig) -> Option<PathBuf> {
+        if let Ok(path) = std::env::var(ENV_EVAL_CACHE_PATH) {
+            return Some(PathBuf::from(path));
+        }
+        config
+            .expanded_path()
+            .or_else(|| dirs::cache_dir().map(|dir| dir.join("dcg").join(EVAL_CACHE_FILE)))
+    }
+
+    /// Cache file location.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Whether `command` run in `cwd` is a cached clean allow.
+    ///
+    /// Refreshes the entry's recency on a hit. Any I/O or parse error is a miss.
+    #[must_use]
+    pub fn lookup(&self, command: &str, cwd: &str) -> bool {
+        let key = cache_key(command, cwd);
+        self.with_locked(|cache, now| {
+            let Some(entry) = cache.entries.iter_mut().find(|e| e.key == key) else {
+                return (false, false);
+            };
+            let dirty = now - entry.last_used >= TOUCH_INTERVAL_SECS;
+            if dirty {
+                entry.last_used = now;
+            }
+            (true, dirty)
+        })
+        .unwrap_or(false)
+    }
+
+    /// Remember that `command` run in `cwd` evaluated to a clean allow.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache file cannot be opened or written.
+    pub fn record_allow(&self, command: &str, cwd: &str) -> io::Result<()> {
+        let key = cache_key(command, cwd);
+        self.with_locked(|cache, now| {
+            if let Some(entry) = cache.entries.iter_mut().find(|e| e.key == key) {
+                entry.last_used = now;
+            } else {
+                cache.entries.push(CacheEntry {
+                    key,
+                    inserted_at: now,
+                    last_used: now,
+                });
+            }
+            if cache.entries.len() > self.max_entries {
+                cache
+                    .entries
+                    .sort_by_key(|e| std::cmp::Reverse(e.last_used));
+                cache.entries.truncate(self.max_entries);
+            }
+            ((), true)
+        })
+    }
+
+    /// Remove every entry. Returns how many were removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache file cannot be opened or written.
+    pub fn clear(&self) -> io::Result<usize> {
+        let mut file = open_locked(&self.path)?;
+        let removed = read_cache_file(&mut file).entries.len();
+        write_cache_file(
+            &mut file,
+            &CacheFile {
+                schema_version: SCHEMA_VERSION,
+                fingerprint: self.fingerprint.clone(),
+                entries: Vec::new(),
+            },
+        )?;
+        Ok(removed)
+    }
+
+    /// Inspect the cache without modifying it.
+    #[must_use]
+    pub fn status(&self) -> EvalCacheStatus {
+        let cache = File::open(&self.path)
+            .map(|mut file| read_cache_file(&mut file))
+            .unwrap_or_default();
+        let fingerprint_matches =
+            cache.schema_version == SCHEMA_VERSION && cache.fingerprint == self.fingerprint;
+        let now = Utc::now().timestamp();
+        let live_entries = if fingerprint_matches {
+            cache
+                .entries
+                .iter()
+                .filter(|e| !self.is_expired(e, now))
+                .count()
+        } else {
+            0
+        };
+        EvalCacheStatus {
+            path: self.path.clone(),
+            live_entries,
+            stale_entries: cache.entries.len() - live_entries,
+            fingerprint_matches,
+        }
+    }
+
+    const fn is_expired(&self, entry: &CacheEntry, now: i64) -> bool {
+        now.saturating_sub(entry.inserted_at) >= self.ttl_secs
+    }
+
+    /// Load the cache under an exclusive lock, drop stale entries, run `f`,
+    /// and write the file back if anything changed.
+    fn with_locked<T>(&self, f: impl FnOnce(&mut CacheFile, i64) -> (T, bool)) -> io::Result<T> {
+        let mut file = open_locked(&self.path)?;
+        let mut cache = read_cache_file(&mut file);
+        let now = Utc::now().timestamp();
+
+        let stale = cache.schema_version != SCHEMA_VERSION || cache.fingerprint != self.fingerprint;
+        if stale {
+            cache = CacheFile {
+                schema_version: SCHEMA_VERSION,
+                fingerprint: self.fingerprint.clone(),
+                entries: Vec::new(),
+            };
+        }
+        let mut dirty = stale;
+        let before = cache.entries.len();
+        cache.entries.retain(|e| !self.is_expired(e, now));
+        dirty |= cache.entries.len() != before;
+
+        let (value, changed) = f(&mut cache, now);
+        if dirty || changed {
+            write_cache_file(&mut file, &cache)?;
+        }
+        Ok(value)
+    }
+}
+
+/// Whether an evaluation result may be cached: a clean allow with no rule,
+/// allowlist, or deadline involvement.
+#[must_use]
+pub const fn is_cacheable(result: &EvaluationResult) -> bool {
+    matches!(result.decision, EvaluationDecision::Allow)
+        && result.pattern_info.is_none()
+        && result.allowlist_override.is_none()
+        && result.effective_mode.is_none()
+        && !result.skipped_due_to_budget
+}
+
+/// Cache key for a command in a working directory.
+#[must_use]
+pub fn cache_key(command: &str, cwd: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(cwd.as_bytes());
+    hasher.update([0]);
+    hasher.update(command.as_bytes());
+    hex_digest(hasher)
+}
+
+pub(crate) fn hex_digest(hasher: Sha256) -> String {
+    use std::fmt::Write as _;
+
+    hasher
+        .finalize()
+        .iter()
+        .fold(String::with_capacity(64), |mut out, byte| {
+            let _ = write!(out, "{byte:02x}");
+            out
+        })
+}
+
+fn open_locked(path: &Path) -> io::Result<File> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let file = OpenOptions::new()
+        .create(true)
+        .read(true)
+        .write(true)
+        .truncate(false)
+        .open(path)?;
+    file.lock_exclusive()?;
+    Ok(file)
+}
+
+/// Read the cache file; a missing, empty, or corrupt file reads as empty.
+fn read_cache_file(file: &mut File) -> CacheFile {
+    let mut contents = String::new();
+    if file.read_to_string(&mut contents).is_err() {
+        return CacheFile::default();
+    }
+    serde_json::from_str(&contents).unwrap_or_default()
+}
+
+fn write_cache_file(file: &mut File, cache: &CacheFile) -> io::Result<()> {
+    let json = serde_json::to_vec(cache).map_err(io::Error::other)?;
+    file.set_len(0)?;
+    file.seek(SeekFrom::Start(0))?;
+    file.write_all(&json)?;
+    file.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn make_cache(dir: &TempDir, fingerprint: &str) -> EvalCache {
+        let config = EvalCacheConfig {
+            enabled: true,
+            max_entries: 2,
+            ..EvalCacheConfig::default()
+        };
+        EvalCache::new(
+            dir.path().join(EVAL_CACHE_FILE),
+            fingerprint.to_string(),
+            &config,
+        )
+    }
+
+    #[test]
+    fn records_and_looks_up_allows() {
+        let dir = TempDir::new().unwrap();
+        let cache = make_cache(&dir, "fp");
+
+        assert!(!cache.lookup("cargo test", "/repo"));
+        cache.record_allow("cargo test", "/repo").unwrap();
+        assert!(cache.lookup("cargo test", "/repo"));
+        assert!(
+            !cache.lookup("cargo test", "/other"),
+            "working directory is part of the key"
+        );
+    }
+
+    #[test]
+    fn fingerprint_change_invalidates_entries() {
+        let dir = TempDir::new().unwrap();
+        make_cache(&dir, "old")
+            .record_allow("ls -la", "/repo")
+            .unwrap();
+
+        let cache = make_cache(&dir, "new");
+        assert!(!cache.status().fingerprint_matches);
+        assert!(!cache.lookup("ls -la", "/repo"));
+        assert_eq!(cache.status().stale_entries, 0, "mismatch discards entries");
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let dir = TempDir::new().unwrap();
+        let cache = make_cache(&dir, "fp");
+
+        cache.record_allow("a", "/repo").unwrap();
+        cache.record_allow("b", "/repo").unwrap();
+        // Force "a" to look older than "b" so it is evicted first.
+        cache
+            .with_locked(|file, now| {
+                for entry in &mut file.entries {
+                    if entry.key == cache_key("a", "/repo") {
+                        entry.last_used = now - 10;
+                    }
+                }
+                ((), true)
+            })
+            .unwrap();
+        cache.record_allow("c", "/repo").unwrap();
+
+        assert!(!cache.lookup("a", "/repo"));
+        assert!(cache.lookup("b", "/repo"));
+        assert!(cache.lookup("c", "/repo"));
+    }
+
+    #[test]
+    fn expired_entries_miss() {
+        let dir = TempDir::new().unwrap();
+        let config = EvalCacheConfig {
+            enabled: true,
+            ttl_secs: 1,
+            ..EvalCacheConfig::default()
+        };
+        let cache = EvalCache::new(dir.path().join(EVAL_CACHE_FILE), "fp".into(), &config);
+        cache.record_allow("git status", "/repo").unwrap();
+        cache
+            .with_locked(|file, now| {
+                file.entries[0].inserted_at = now - 5;
+                ((), true)
+            })
+            .unwrap();
+
+        assert!(!cache.lookup("git status", "/repo"));
+    }
+
+    #[test]
+    fn corrupt_file_reads_as_empty() {
+        let dir = TempDir::new().unwrap();
+        let cache = make_cache(&dir, "fp");
+        std::fs::write(cache.path(), "not json").unwrap();
+
+        assert!(!cache.lookup("ls", "/repo"));
+        cache.record_allow("ls", "/repo").unwrap();
+        assert!(cache.lookup("ls", "/repo"));
+        assert_eq!(cache.clear().unwrap(), 1);
+        assert_eq!(cache.status().live_entries, 0);
+    }
+
+    #[test]
+    fn only_clean_allows_are_cacheable() {
+        assert!(is_cacheable(&EvaluationResult::allowed()));
+        assert!(!is_cacheable(&EvaluationResult::allowed_due_to_budget()));
+        assert!(!is_cacheable(&EvaluationResult::denied_by_config(
+            "blocked".to_string()
+        )));
+    }
+}
@@ -52,7 +52,8 @@ use crate::heredoc::{
 };
 use crate::normalize::{PATH_NORMALIZER, QUOTED_PATH_NORMALIZER, strip_wrapper_prefixes};
 use crate::packs::{
-    PatternSuggestion, REGISTRY, pack_aware_quick_reject, pack_aware_quick_reject_with_normalized,
+    PatternSuggestion, REGISTRY, load_external_packs, pack_aware_quick_reject,
+    pack_aware_quick_reject_with_normalized,
 };
 use crate::pending_exceptions::AllowOnceStore;
 use crate::perf::Deadline;
@@ -426,6 +427,8 @@ pub struct PatternMatch {
     pub explanation: Option<String>,
     /// Safer alternative commands suggested for this pattern.
     pub suggestions: &'static [PatternSuggestion],
+    /// How to recover if the command runs anyway (shown in warn mode).
+    pub recovery_hint: Option<&'static str>,
 }
 
 /// Information about an allowlist override (DENY -> ALLOW).
@@ -537,6 +540,7 @@ impl EvaluationResult {
                 matched_text_preview: None,
                 explanation: None,
                 suggestions: &[],
+                recovery_hint: None,
             }),
             allowlist_override: None,
             effective_mode: Some(crate::packs::DecisionMode::Deny),
@@ -561,6 +565,7 @@ impl EvaluationResult {
                 matched_text_preview: None,
                 explanation: None,
                 suggestions: &[],
+                recovery_hint: None,
             }),
             allowlist_override: None,
             effective_mode: Some(crate::packs::DecisionMode::Deny),
@@ -586,6 +591,7 @@ impl EvaluationResult {
                 matched_text_preview: Some(preview),
                 explanation: None,
                 suggestions: &[],
+                recovery_hint: None,
             }),
             allowlist_override: None,
             effective_mode: Some(crate::packs::DecisionMode::Deny),
@@ -610,6 +616,7 @@ impl EvaluationResult {
                 matched_text_preview: None,
                 explanation: explanation.map(str::to_string),
                 suggestions: &[],
+                recovery_hint: None,
             }),
             allowlist_override: None,
             effective_mode: Some(crate::packs::DecisionMode::Deny),
@@ -641,6 +648,7 @@ impl EvaluationResult {
                 matched_text_preview: Some(preview),
                 explanation: explanation.map(str::to_string),
                 suggestions: &[],
+                recovery_hint: None,
             }),
             allowlist_override: None,
             effective_mode: Some(crate::packs::DecisionMode::Deny),
@@ -672,6 +680,7 @@ impl EvaluationResult {
                 matched_text_preview: None,
                 explanation: explanation.map(str::to_string),
                 suggestions,
+                recovery_hint: None,
             }),
             allowlist_override: None,
             effective_mode: Some(severity.default_mode()),
@@ -706,6 +715,7 @@ impl EvaluationResult {
                 matched_text_preview: Some(preview),
                 explanation: explanation.map(str::to_string),
                 suggestions,
+                recovery_hint: None,
             }),
             allowlist_override: None,
             effective_mode: Some(severity.default_mode()),
@@ -714,6 +724,16 @@ impl EvaluationResult {
         }
     }
 
+    /// Attach the matched pattern's recovery hint.
+    #[inline]
+    #[must_use]
+    pub fn with_recovery_hint(mut self, recovery_hint: Option<&'static str>) -> Self {
+        if let Some(info) = self.pattern_info.as_mut() {
+            info.recovery_hint = recovery_hint;
+        }
+        self
+    }
+
     /// Create an "allowed" result due to allowlist override.
     #[must_use]
     pub const fn allowed_by_allowlist(
@@ -909,7 +929,7 @@ pub fn evaluate_detailed_with_allowlists(
     let ordered_packs = REGISTRY.expand_enabled_ordered(&enabled_packs);
     let keyword_index = REGISTRY.build_enabled_keyword_index(&ordered_packs);
     let heredoc_settings = config.heredoc_settings();
-    let compiled_overrides = config.overrides.compile();
+    let compiled_overrides = config.compile_overrides();
 
     // Track quick-reject status
     let quick_rejected = pack_aware_quick_reject(command, &enabled_keywords);
@@ -1044,7 +1064,13 @@ fn allow_once_match(
 ) -> Option<crate::pending_exceptions::AllowOnceEntry> {
     let cwd = std::env::current_dir().ok()?;
     let store = AllowOnceStore::new(AllowOnceStore::default_path(Some(&cwd)));
-    match store.match_command(command, &cwd, Utc::now(), allow_once_audit) {
+    match store.match_command(
+        command,
+        &cwd,
+        crate::pending_exceptions::hook_session_id().as_deref(),
+        Utc::now(),
+        allow_once_audit,
+    ) {
         Ok(Some(entry)) => Some(entry),
         _ => None,
     }
@@ -1057,7 +1083,13 @@ fn allow_once_match_force_config(
 ) -> Option<crate::pending_exceptions::AllowOnceEntry> {
     let cwd = std::env::current_dir().ok()?;
     let store = AllowOnceStore::new(AllowOnceStore::default_path(Some(&cwd)));
-    match store.match_command_force_config(command, &cwd, Utc::now(), allow_once_audit) {
+    match store.match_command_force_config(
+        command,
+        &cwd,
+        crate::pending_exceptions::hook_session_id().as_deref(),
+        Utc::now(),
+        allow_once_audit,
+    ) {
         Ok(Some(entry)) => Some(entry),
         _ => None,
     }
@@ -1093,6 +1125,97 @@ pub fn evaluate_command_with_deadline(
     )
 }
 
+/// Precomputed evaluation state for checking many commands against one config.
+///
+/// Pack expansion, keyword collection, the keyword index, override compilation,
+/// and external pack loading happen once in [`BatchEvaluator::new`]; every call
+/// to [`BatchEvaluator::evaluate`] then goes straight to the hook-mode hot path.
+/// Use this (or [`evaluate_commands`]) for scripted audits instead of spawning
+/// `dcg test` per command.
+pub struct BatchEvaluator<'a> {
+    enabled_keywords: Vec<&'static str>,
+    ordered_packs: Vec<String>,
+    keyword_index: Option<crate::packs::EnabledKeywordIndex>,
+    compiled_overrides: crate::config::CompiledOverrides,
+    heredoc_settings: crate::config::HeredocSettings,
+    allowlists: &'a LayeredAllowlist,
+}
+
+impl<'a> BatchEvaluator<'a> {
+    /// Build the shared evaluation state for `config`.
+    ///
+    /// External packs from `packs.custom_paths` are loaded and appended to the
+    /// pack order, matching `dcg test`.
+    #[must_use]
+    pub fn new(config: &Config, allowlists: &'a LayeredAllowlist) -> Self {
+        let mut enabled_packs: HashSet<String> = config.enabled_pack_ids();
+        let mut enabled_keywords = REGISTRY.collect_enabled_keywords(&enabled_packs);
+
+        let external_store = load_external_packs(&config.packs.expand_custom_paths());
+        for id in external_store.pack_ids() {
+            enabled_packs.insert(id.clone());
+        }
+        enabled_keywords.extend(external_store.keywords().iter().copied());
+
+        let mut ordered_packs = REGISTRY.expand_enabled_ordered(&enabled_packs);
+        for id in external_store.pack_ids() {
+            if !ordered_packs.contains(id) {
+                ordered_packs.push(id.clone());
+            }
+        }
+        // The keyword index only covers built-in packs.
+        let keyword_index = if external_store.pack_ids().next().is_some() {
+            None
+        } else {
+            REGISTRY.build_enabled_keyword_index(&ordered_packs)
+        };
+
+        Self {
+            enabled_keywords,
+            ordered_packs,
+            keyword_index,
+            compiled_overrides: config.compile_overrides(),
+            heredoc_settings: config.heredoc_settings(),
+            allowlists,
+        }
+    }
+
+    /// Evaluate a single command using the precomputed state.
+    #[must_use]
+    pub fn evaluate(&self, command: &str) -> EvaluationResult {
+        evaluate_command_with_pack_order_deadline_at_path(
+            command,
+            &self.enabled_keywords,
+            &self.ordered_packs,
+            self.keyword_index.as_ref(),
+            &self.compiled_overrides,
+            self.allowlists,
+            &self.heredoc_settings,
+            None,
+            None,
+            None,
+        )
+    }
+}
+
+/// Evaluate several commands against the same configuration.
+///
+/// Equivalent to calling [`evaluate_command`] for each command, but the pack
+/// order, keyword index, and compiled overrides are built only once. Results
+/// are returned in input order.
+#[must_use]
+pub fn evaluate_commands(
+    commands: &[&str],
+    config: &Config,
+    allowlists: &LayeredAllowlist,
+) -> Vec<EvaluationResult> {
+    let batch = BatchEvaluator::new(config, allowlists);
+    commands
+        .iter()
+        .map(|command| batch.evaluate(command))
+        .collect()
+}
+
 /// Evaluate a command using a precomputed pack order.
 ///
 /// This is the hot-path optimized variant for hook mode: callers can compute the
@@ -1128,6 +1251,176 @@ pub fn evaluate_command_with_pack_order(
     )
 }
 
+/// Record the stages of [`evaluate_command_with_pack_order`] into an explain trace.
+///
+/// Each stage is re-run on its own (override check, heredoc extraction,
+/// sanitization, normalization, keyword gating, pack candidate selection) so
+/// `dcg explain` can show how far a command got. `result` is the decision the
+/// full evaluator already produced; it only fills in which pack matched.
+pub fn trace_pipeline_stages(
+    collector: &mut crate::trace::TraceCollector,
+    command: &str,
+    enabled_keywords: &[&str],
+    ordered_packs: &[String],
+    compiled_overrides: &crate::config::CompiledOverrides,
+    heredoc_settings: &crate::config::HeredocSettings,
+    result: &EvaluationResult,
+) {
+    use crate::trace::{PackSummary, TraceDetails};
+
+    collector.begin_step();
+    let block_reason = compiled_overrides.check_block(command);
+    collector.end_step(
+        "config_override",
+        TraceDetails::ConfigOverride {
+            allow_matched: compiled_overrides.check_allow(command),
+            block_matched: block_reason.is_some(),
+            reason: block_reason.map(str::to_string),
+        },
+    );
+
+    collector.begin_step();
+    let triggered = heredoc_settings.enabled && check_triggers(command) == TriggerResult::Triggered;
+    let extracted = if triggered {
+        match extract_content(command, &heredoc_settings.limits) {
+            ExtractionResult::Extracted(contents)
+            | ExtractionResult::Partial {
+                extracted: contents,
+                ..
+            } => contents,
+            _ => Vec::new(),
+        }
+    } else {
+        Vec::new()
+    };
+    let mut languages: Vec<String> = Vec::new();
+    for content in &extracted {
+        let name = content.language.name();
+        if !languages.iter().any(|l| l == name) {
+            languages.push(name.to_string());
+        }
+    }
+    collector.end_step(
+        "heredoc",
+        TraceDetails::HeredocDetection {
+            triggered,
+            scripts_extracted: extracted.len(),
+            languages,
+        },
+    );
+
+    collector.begin_step();
+    let sanitized = sanitize_for_pattern_matching(command);
+    let spans_masked = count_masked_spans(command, &sanitized);
+    if spans_masked > 0 {
+        collector.set_sanitized(&sanitized);
+    }
+    collector.end_step(
+        "sanitize",
+        TraceDetails::Sanitization {
+            was_modified: spans_masked > 0,
+            spans_masked,
+        },
+    );
+
+    collector.begin_step();
+    let stripped = strip_wrapper_prefixes(&sanitized);
+    let stripped_prefix = (!stripped.stripped_wrappers.is_empty()).then(|| {
+        stripped
+            .stripped_wrappers
+            .iter()
+            .map(|w| w.stripped_text.as_str())
+            .collect::<String>()
+    });
+    let normalized = crate::normalize::normalize_command(&sanitized);
+    if normalized.as_ref() != command {
+        collector.set_normalized(&normalized);
+    }
+    collector.end_step(
+        "normalize",
+        TraceDetails::Normalization {
+            was_modified: normalized.as_ref() != sanitized.as_ref(),
+            stripped_prefix,
+        },
+    );
+
+    // Mirrors Step 4: quick-reject on the raw command, then on the sanitized one.
+    collector.begin_step();
+    let quick_rejected = pack_aware_quick_reject(command, enabled_keywords)
+        || (sanitized.as_ref() != command && pack_aware_quick_reject(&sanitized, enabled_keywords));
+    let mut keywords_hit: Vec<String> = Vec::new();
+    if !quick_rejected {
+        for keyword in enabled_keywords {
+            if normalized.contains(keyword) && !keywords_hit.iter().any(|k| k == keyword) {
+                keywords_hit.push((*keyword).to_string());
+            }
+        }
+    }
+    collector.end_step(
+        "keyword_gating",
+        TraceDetails::KeywordGating {
+            quick_rejected,
+            keywords_checked: enabled_keywords.iter().map(|k| (*k).to_string()).collect(),
+            first_match: keywords_hit.first().cloned(),
+            keywords_hit,
+        },
+    );
+
+    collector.begin_step();
+    let command_for_packs = crate::heredoc::mask_non_executing_heredocs(&normalized);
+    let external_store = crate::packs::get_external_packs();
+    let mut evaluated = Vec::new();
+    let mut skipped = Vec::new();
+    for pack_id in ordered_packs {
+        let candidate = !quick_rejected
+            && REGISTRY.get_entry(pack_id).map_or_else(
+                || {
+                    external_store
+                        .and_then(|store| store.get(pack_id))
+                        .is_some_and(|pack| pack.might_match(&command_for_packs))
+                },
+                |entry| entry.might_match(&command_for_packs),
+            );
+        if candidate {
+            evaluated.push(pack_id.clone());
+        } else {
+            skipped.push(pack_id.clone());
+        }
+    }
+    let pack_match = result
+        .pattern_info
+        .as_ref()
+        .filter(|info| info.source == MatchSource::Pack);
+    collector.end_step(
+        "pack_candidates",
+        TraceDetails::PackEvaluation {
+            packs_evaluated: evaluated.clone(),
+            packs_skipped: skipped.clone(),
+            matched_pack: pack_match.and_then(|info| info.pack_id.clone()),
+            matched_pattern: pack_match.and_then(|info| info.pattern_name.clone()),
+        },
+    );
+    collector.set_pack_summary(PackSummary {
+        enabled_count: ordered_packs.len(),
+        evaluated,
+        skipped,
+    });
+}
+
+/// Count contiguous byte runs that sanitization replaced with spaces.
+fn count_masked_spans(original: &str, sanitized: &str) -> usize {
+    let mut spans = 0;
+    let mut in_span = false;
+    for (a, b) in original.bytes().zip(sanitized.bytes()) {
+        let masked = a != b;
+        if masked && !in_span {
+            spans += 1;
+        }
+        in_span = masked;
+    }
+    spans
+}
+
 /// Evaluate a command using a precomputed pack order and an optional project path.
 #[must_use]
 #[allow(clippy::too_many_arguments)]
@@ -1239,6 +1532,14 @@ pub fn evaluate_command_with_pack_order_deadline_at_path(
         return EvaluationResult::denied_by_config(reason.to_string());
     }
 
+    // Step 1.55: Check protected paths (rm/mv/truncate/dd targets resolved on disk).
+    if let Some(reason) = compiled_overrides.check_protected_paths(command) {
+        if allow_once_match_force_config(command, allow_once_audit).is_some() {
+            return EvaluationResult::allowed();
+        }
+        return EvaluationResult::denied_by_config(reason);
+    }
+
     // Step 1.6: Check allow-once overrides.
     if allow_once_match(command, allow_once_audit).is_some() {
         return EvaluationResult::allowed();
@@ -1252,6 +1553,9 @@ pub fn evaluate_command_with_pack_order_deadline_at_path(
     let mut precomputed_sanitized = None;
     let mut heredoc_allowlist_hit: Option<(PatternMatch, AllowlistLayer, String)> = None;
 
+    // Package scripts resolve relative to the caller's working directory, not
+    // the heredoc allowlist project scope.
+    let script_cwd = project_path;
     let project_path = resolve_project_path(heredoc_settings, project_path);
     let project_path = project_path.as_deref();
 
@@ -1260,6 +1564,7 @@ pub fn evaluate_command_with_pack_order_deadline_at_path(
             return EvaluationResult::allowed_due_to_budget();
         }
 
+        let _span = tracing::info_span!("dcg.heredoc").entered();
         if check_triggers(command) == TriggerResult::Triggered {
             let sanitized = sanitize_for_pattern_matching(command);
             let sanitized_str = sanitized.as_ref();
@@ -1295,8 +1600,58 @@ pub fn evaluate_command_with_pack_order_deadline_at_path(
         return EvaluationResult::allowed_due_to_budget();
     }
 
+    // Step 3.5: Encoded payloads (base64/hex/printf escapes that are decoded and executed).
+    // Runs before quick rejection because the decoded command's keywords are not visible.
+    let context = HeredocEvaluationContext {
+        allowlists,
+        heredoc_settings,
+        project_path,
+        deadline,
+        enabled_keywords,
+        ordered_packs,
+        keyword_index,
+        compiled_overrides,
+        allow_once_audit,
+    };
+    if let Some(blocked) = evaluate_encoded_payloads(command, &context) {
+        return blocked;
+    }
+
+    // Step 3.6: Project scripts (`npm run x` / `make x` / `just x` run whatever
+    // package.json, the Makefile, or the justfile says). Also before quick
+    // rejection: the script body's keywords are not visible.
+    if let Some(blocked) = evaluate_project_scripts(command, &context, script_cwd) {
+        return blocked;
+    }
+
+    // Step 3.7: Ansible ad-hoc commands (`ansible all -m shell -a "..."`) run
+    // their `-a` argument on every targeted host.
+    if let Some(blocked) = evaluate_ansible_adhoc(command, &context) {
+        return blocked;
+    }
+
+    // Step 3.8: Wrapper chains (`ssh host '<cmd>'`, `[wrappers] extra`) run a
+    // command that is quoted or hidden behind the wrapper.
+    if let Some(blocked) = evaluate_wrapped_commands(command, &context) {
+        return blocked;
+    }
+
+    // Step 3.9: Detached sessions (`tmux send-keys`, `tmux new -d '<cmd>'`,
+    // `screen -X stuff`) run their payload after this command has returned.
+    if let Some(blocked) = evaluate_session_payloads(command, &context) {
+        return blocked;
+    }
+
+    if deadline_exceeded(deadline) {
+        return EvaluationResult::allowed_due_to_budget();
+    }
+
     // Step 4: Quick rejection - if no relevant keywords, allow immediately
-    if pack_aware_quick_reject(command, enabled_keywords) {
+    let quick_rejected = {
+        let _span = tracing::info_span!("dcg.quick_reject").entered();
+        pack_aware_quick_reject(command, enabled_keywords)
+    };
+    if quick_rejected {
         if let Some((matched, layer, reason)) = heredoc_allowlist_hit {
             return EvaluationResult::allowed_by_allowlist(matched, layer, reason);
         }
@@ -1314,12 +1669,17 @@ pub fn evaluate_command_with_pack_order_deadline_at_path(
     // Also normalize the command here (Step 6) and reuse for pack evaluation.
     // pack_aware_quick_reject_with_normalized returns both the quick-reject decision
     // and the normalized command, avoiding duplicate normalization.
-    let sanitized = precomputed_sanitized.unwrap_or_else(|| sanitize_for_pattern_matching(command));
+    let sanitized = {
+        let _span = tracing::info_span!("dcg.sanitize").entered();
+        precomputed_sanitized.unwrap_or_else(|| sanitize_for_pattern_matching(command))
+    };
     let command_for_match = sanitized.as_ref();
 
     // Use the optimized version that returns both decision and normalized form.
-    let (quick_reject, normalized) =
-        pack_aware_quick_reject_with_normalized(command_for_match, enabled_keywords);
+    let (quick_reject, normalized) = {
+        let _span = tracing::info_span!("dcg.quick_reject", sanitized = true).entered();
+        pack_aware_quick_reject_with_normalized(command_for_match, enabled_keywords)
+    };
     if matches!(sanitized, std::borrow::Cow::Owned(_)) && quick_reject {
         if let Some((matched, layer, reason)) = heredoc_allowlist_hit {
             return EvaluationResult::allowed_by_allowlist(matched, layer, reason);
@@ -1349,17 +1709,21 @@ pub fn evaluate_command_with_pack_order_deadline_at_path(
     let masked = crate::heredoc::mask_non_executing_heredocs(&normalized);
     let command_for_packs = masked.as_ref();
 
-    let result = evaluate_packs_with_allowlists(
-        command_for_packs,
-        &normalized,
-        command_for_match,
-        command,
-        ordered_packs,
-        allowlists,
-        keyword_index,
-        None,
-        project_path,
-    );
+    let result = {
+        let _span = tracing::info_span!("dcg.pack_loop", packs = ordered_packs.len()).entered();
+        evaluate_packs_with_allowlists(
+            command_for_packs,
+            &normalized,
+            command_for_match,
+            command,
+            ordered_packs,
+            allowlists,
+            &compiled_overrides.trusted_installers,
+            keyword_index,
+            deadline,
+            project_path,
+        )
+    };
     if result.allowlist_override.is_none() {
         if let Some((matched, layer, reason)) = heredoc_allowlist_hit {
             return EvaluationResult::allowed_by_allowlist(matched, layer, reason);
@@ -1378,6 +1742,7 @@ fn evaluate_packs_with_allowlists(
     original_command: &str,
     ordered_packs: &[String],
     allowlists: &LayeredAllowlist,
+    trusted_installers: &[String],
     keyword_index: Option<&crate::packs::EnabledKeywordIndex>,
     deadline: Option<&Deadline>,
     project_path: Option<&Path>,
@@ -1462,30 +1827,52 @@ fn evaluate_packs_with_allowlists(
     // 2. Check destructive patterns - if match, block (unless allowlisted)
     //
     // The rm_parse optimization for core.filesystem is handled inline.
+    //
+    // With little time left on the deadline, the Critical patterns of every
+    // candidate pack run first and everything else in a second pass, so a
+    // budget fail-open partway through is least likely to miss the worst
+    // commands. Otherwise there is a single pass in pack tier order.
+    let critical_first = remaining_below(deadline, &crate::perf::CRITICAL_FIRST);
+    let passes: &[bool] = if critical_first {
+        &[true, false]
+    } else {
+        &[false]
+    };
+    let mut gates: Vec<Option<PackGate<'_>>> = Vec::new();
+    gates.resize_with(candidate_packs.len(), || None);
     let mut first_allowlist_hit: Option<(PatternMatch, AllowlistLayer, String)> = None;
 
-    for &(pack_id, pack) in &candidate_packs {
-        if deadline_exceeded(deadline) || remaining_below(deadline, &crate::perf::PATTERN_MATCH) {
-            return EvaluationResult::allowed_due_to_budget();
-        }
+    for &critical_pass in passes {
+        for (slot, &(pack_id, pack)) in candidate_packs.iter().enumerate() {
+            if deadline_exceeded(deadline) || remaining_below(deadline, &crate::perf::PATTERN_MATCH)
+            {
+                return EvaluationResult::allowed_due_to_budget();
+            }
 
-        // Check safe patterns for this pack first.
-        // If a safe pattern matches, skip this pack's destructive patterns only.
-        // This prevents compound command bypass where one pack's safe pattern
-        // would whitelist destructive commands from other packs.
-        if pack_id == "core.filesystem" {
-            // core.filesystem uses rm_parse for more accurate safe pattern detection
-            match rm_parse.as_ref() {
-                Some(crate::packs::core::filesystem::RmParseDecision::Allow) => {
-                    continue; // Safe pattern match - skip this pack
-                }
-                Some(crate::packs::core::filesystem::RmParseDecision::NoMatch) | None => {
-                    // rm_parse didn't find rm command or wasn't computed, check safe patterns as fallback
-                    if pack.matches_safe(command_for_packs) {
+            // Check safe patterns for this pack first (once, on the first pass that
+            // reaches it). If a safe pattern matches, skip this pack's destructive
+            // patterns only. This prevents compound command bypass where one pack's
+            // safe pattern would whitelist destructive commands from other packs.
+            let gate = gates[slot].get_or_insert_with(|| {
+                pack_gate(
+                    pack_id,
+                    pack,
+                    command_for_packs,
+                    rm_parse.as_ref(),
+                    trusted_installers,
+                )
+            });
+            let candidates = match gate {
+                PackGate::Skip => continue,
+                PackGate::Evaluate(candidates) => candidates.as_ref(),
+                PackGate::RmDeny(hit) => {
+                    let hit = *hit;
+                    // Reported in the pass for its severity.
+                    if critical_first
+                        && critical_pass != (hit.severity == crate::packs::Severity::Critical)
+                    {
                         continue;
                     }
-                }
-                Some(crate::packs::core::filesystem::RmParseDecision::Deny(hit)) => {
                     if let Some(allow_hit) =
                         allowlists.match_rule_at_path(pack_id, hit.pattern_name, project_path)
                     {
@@ -1515,11 +1902,13 @@ fn evaluate_packs_with_allowlists(
                                     matched_text_preview: preview,
                                     explanation: None,
                                     suggestions: &[],
+                                    recovery_hint: None,
                                 },
                                 allow_hit.layer,
                                 allow_hit.entry.reason.clone(),
                             ));
                         }
+                        gates[slot] = Some(PackGate::Skip);
                         continue;
                     }
 
@@ -1552,99 +1941,118 @@ fn evaluate_packs_with_allowlists(
                         &[], // fast_match path doesn't have suggestions
                     );
                 }
-            }
-        } else {
-            // Non-core.filesystem packs: check safe patterns before destructive
-            if pack.matches_safe(command_for_packs) {
-                continue; // Safe pattern match - skip this pack's destructive patterns
-            }
-        }
+            };
 
-        for pattern in &pack.destructive_patterns {
-            if deadline_exceeded(deadline) || remaining_below(deadline, &crate::perf::PATTERN_MATCH)
+            // The Critical pass runs only the pack's Critical patterns (collected at
+            // registration); the pass after it runs the rest.
+            let critical = critical_pass.then_some(pack.critical_patterns.iter().copied());
+            let all = (!critical_pass).then_some(0..pack.destructive_patterns.len());
+            for index in critical
+                .into_iter()
+                .flatten()
+                .chain(all.into_iter().flatten())
             {
-                return EvaluationResult::allowed_due_to_budget();
-            }
+                if critical_first
+                    && !critical_pass
+                    && pack.critical_patterns.binary_search(&index).is_ok()
+                {
+                    continue;
+                }
+                if candidates.is_some_and(|c| !c.may_match(index)) {
+                    continue;
+                }
+                let pattern = &pack.destructive_patterns[index];
+                if deadline_exceeded(deadline)
+                    || remaining_below(deadline, &crate::perf::PATTERN_MATCH)
+                {
+                    return EvaluationResult::allowed_due_to_budget();
+                }
 
-            // All severity levels are now evaluated. The policy layer in main.rs
-            // determines whether to deny, warn, or log based on severity and config.
+                // All severity levels are now evaluated. The policy layer in main.rs
+                // determines whether to deny, warn, or log based on severity and config.
 
-            let matched_span = pattern
-                .regex
-                .find(command_for_packs)
-                .map(|(start, end)| MatchSpan { start, end });
-            let Some(span) = matched_span else {
-                continue;
-            };
+                let matched_span = pattern
+                    .regex
+                    .find(command_for_packs)
+                    .map(|(start, end)| MatchSpan { start, end });
+                let Some(span) = matched_span else {
+                    continue;
+                };
 
-            let reason = pattern.reason;
-            let mapped_span = map_span_with_offset(span, normalized_offset, original_len);
-            let preview = mapped_span
-                .as_ref()
-                .map(|span| extract_match_preview(original_command, span))
-                .or_else(|| Some(extract_match_preview(command_for_packs, &span)));
+                let reason = pattern.reason;
+                let mapped_span = map_span_with_offset(span, normalized_offset, original_len);
+                let preview = mapped_span
+                    .as_ref()
+                    .map(|span| extract_match_preview(original_command, span))
+                    .or_else(|| Some(extract_match_preview(command_for_packs, &span)));
+
+                // Allowlist check: only applies when we have a stable match identity (named pattern).
+                if let Some(pattern_name) = pattern.name {
+                    if let Some(hit) =
+                        allowlists.match_rule_at_path(pack_id, pattern_name, project_path)
+                    {
+                        if first_allowlist_hit.is_none() {
+                            first_allowlist_hit = Some((
+                                PatternMatch {
+                                    pack_id: Some(pack_id.clone()),
+                                    pattern_name: Some(pattern_name.to_string()),
+                                    severity: Some(pattern.severity),
+                                    reason: reason.to_string(),
+                                    source: MatchSource::Pack,
+                                    matched_span: mapped_span,
+                                    matched_text_preview: preview,
+                                    explanation: pattern.explanation.map(str::to_string),
+                                    suggestions: pattern.suggestions,
+                                    recovery_hint: pattern.recovery_hint,
+                                },
+                                hit.layer,
+                                hit.entry.reason.clone(),
+                            ));
+                        }
 
-            // Allowlist check: only applies when we have a stable match identity (named pattern).
-            if let Some(pattern_name) = pattern.name {
-                if let Some(hit) =
-                    allowlists.match_rule_at_path(pack_id, pattern_name, project_path)
-                {
-                    if first_allowlist_hit.is_none() {
-                        first_allowlist_hit = Some((
-                            PatternMatch {
-                                pack_id: Some(pack_id.clone()),
-                                pattern_name: Some(pattern_name.to_string()),
-                                severity: Some(pattern.severity),
-                                reason: reason.to_string(),
-                                source: MatchSource::Pack,
-                                matched_span: mapped_span,
-                                matched_text_preview: preview,
-                                explanation: pattern.explanation.map(str::to_string),
-                                suggestions: pattern.suggestions,
-                            },
-                            hit.layer,
-                            hit.entry.reason.clone(),
-                        ));
+                        // Bypass only this rule and keep evaluating other rules/packs.
+                        continue;
                     }
 
-                    // Bypass only this rule and keep evaluating other rules/packs.
-                    continue;
-                }
+                    if let Some(mapped_span) = mapped_span {
+                        return EvaluationResult::denied_by_pack_pattern_with_span(
+                            pack_id,
+                            pattern_name,
+                            reason,
+                            pattern.explanation,
+                            pattern.severity,
+                            pattern.suggestions,
+                            original_command,
+                            mapped_span,
+                        )
+                        .with_recovery_hint(pattern.recovery_hint);
+                    }
 
-                if let Some(mapped_span) = mapped_span {
-                    return EvaluationResult::denied_by_pack_pattern_with_span(
+                    return EvaluationResult::denied_by_pack_pattern(
                         pack_id,
                         pattern_name,
                         reason,
                         pattern.explanation,
                         pattern.severity,
                         pattern.suggestions,
+                    )
+                    .with_recovery_hint(pattern.recovery_hint);
+                }
+
+                if let Some(mapped_span) = mapped_span {
+                    return EvaluationResult::denied_by_pack_with_span(
+                        pack_id,
+                        reason,
+                        pattern.explanation,
                         original_command,
                         mapped_span,
-                    );
+                    )
+                    .with_recovery_hint(pattern.recovery_hint);
                 }
 
-                return EvaluationResult::denied_by_pack_pattern(
-                    pack_id,
-                    pattern_name,
-                    reason,
-                    pattern.explanation,
-                    pattern.severity,
-                    pattern.suggestions,
-                );
-            }
-
-            if let Some(mapped_span) = mapped_span {
-                return EvaluationResult::denied_by_pack_with_span(
-                    pack_id,
-                    reason,
-                    pattern.explanation,
-                    original_command,
-                    mapped_span,
-                );
+                return EvaluationResult::denied_by_pack(pack_id, reason, pattern.explanation)
+                    .with_recovery_hint(pattern.recovery_hint);
             }
-
-            return EvaluationResult::denied_by_pack(pack_id, reason, pattern.explanation);
         }
     }
 
@@ -1655,6 +2063,53 @@ fn evaluate_packs_with_allowlists(
     EvaluationResult::allowed()
 }
 
+/// Result of a pack's safe-pattern check, kept across evaluation passes.
+enum PackGate<'a> {
+    /// A safe pattern (or the rm parser) allowed the command; skip the pack.
+    Skip,
+    /// The rm parser denied the command (core.filesystem only).
+    RmDeny(&'a crate::packs::core::filesystem::RmParseMatch),
+    /// Check destructive patterns, narrowed by the pack's `RegexSet` prefilter.
+    Evaluate(Option<crate::packs::regex_engine::PrefilterCandidates<'a>>),
+}
+
+fn pack_gate<'a>(
+    pack_id: &str,
+    pack: &'a crate::packs::Pack,
+    command: &str,
+    rm_parse: Option<&'a crate::packs::core::filesystem::RmParseDecision>,
+    trusted_installers: &[String],
+) -> PackGate<'a> {
+    use crate::packs::core::filesystem::RmParseDecision;
+
+    // core.filesystem uses rm_parse for more accurate safe pattern detection;
+    // when it finds no rm command, the safe patterns are the fallback.
+    if pack_id == "core.filesystem" {
+        match rm_parse {
+            Some(RmParseDecision::Allow) => return PackGate::Skip,
+            Some(RmParseDecision::Deny(hit)) => return PackGate::RmDeny(hit),
+            Some(RmParseDecision::NoMatch) | None => {}
+        }
+    }
+
+    if pack.matches_safe(command) {
+        return PackGate::Skip;
+    }
+    // core.pipe_shell: downloads from [trusted_installers] act as a safe pattern
+    if pack_id == "core.pipe_shell"
+        && crate::packs::core::pipe_shell::is_trusted_download(command, trusted_installers)
+    {
+        return PackGate::Skip;
+    }
+
+    // One RegexSet pass narrows the destructive patterns worth running individually.
+    let candidates = pack.destructive_candidates(command);
+    if candidates.as_ref().is_some_and(|c| !c.any()) {
+        return PackGate::Skip;
+    }
+    PackGate::Evaluate(candidates)
+}
+
 /// Evaluate a command with legacy pattern support using precompiled overrides.
 ///
 /// This version includes legacy `SAFE_PATTERNS` and `DESTRUCTIVE_PATTERNS` checking.
@@ -1713,6 +2168,17 @@ where
         return EvaluationResult::denied_by_config(reason.to_string());
     }
 
+    // Step 2.1: Check protected paths
+    if let Some(reason) = compiled_overrides.check_protected_paths(command) {
+        if allow_once
+            .as_ref()
+            .is_some_and(|entry| entry.force_allow_config)
+        {
+            return EvaluationResult::allowed();
+        }
+        return EvaluationResult::denied_by_config(reason);
+    }
+
     if allow_once.is_some() {
         return EvaluationResult::allowed();
     }
@@ -1757,6 +2223,42 @@ where
         }
     }
 
+    // Step 3.5: Encoded payloads (see `evaluate_command_with_pack_order_deadline_at_path`).
+    let context = HeredocEvaluationContext {
+        allowlists,
+        heredoc_settings: &heredoc_settings,
+        project_path,
+        deadline: None,
+        enabled_keywords,
+        ordered_packs: &ordered_packs,
+        keyword_index: keyword_index.as_ref(),
+        compiled_overrides,
+        allow_once_audit: None,
+    };
+    if let Some(blocked) = evaluate_encoded_payloads(command, &context) {
+        return blocked;
+    }
+
+    // Step 3.6: Project scripts (see `evaluate_command_with_pack_order_deadline_at_path`).
+    if let Some(blocked) = evaluate_project_scripts(command, &context, None) {
+        return blocked;
+    }
+
+    // Step 3.7: Ansible ad-hoc commands (see `evaluate_command_with_pack_order_deadline_at_path`).
+    if let Some(blocked) = evaluate_ansible_adhoc(command, &context) {
+        return blocked;
+    }
+
+    // Step 3.8: Wrapper chains (see `evaluate_command_with_pack_order_deadline_at_path`).
+    if let Some(blocked) = evaluate_wrapped_commands(command, &context) {
+        return blocked;
+    }
+
+    // Step 3.9: Detached sessions (see `evaluate_command_with_pack_order_deadline_at_path`).
+    if let Some(blocked) = evaluate_session_payloads(command, &context) {
+        return blocked;
+    }
+
     // Step 4: Quick rejection - if no relevant keywords, allow immediately
     if pack_aware_quick_reject(command, enabled_keywords) {
         if let Some((matched, layer, reason)) = heredoc_allowlist_hit {
@@ -1818,6 +2320,7 @@ where
         command,
         &ordered_packs,
         allowlists,
+        &compiled_overrides.trusted_installers,
         keyword_index.as_ref(),
         None,
         None, // project_path: legacy function, path-aware allowlisting unavailable
@@ -1995,10 +2498,13 @@ fn evaluate_heredoc(
         // Commands like `cat`, `tee`, `grep`, etc. just output the heredoc content
         // as data - they don't execute it as code. This prevents false positives
         // where documentation text containing dangerous command examples is blocked.
-        if content
-            .target_command
-            .as_ref()
-            .is_some_and(|cmd| crate::heredoc::is_non_executing_heredoc_command(cmd))
+        // HCL is the exception: Terraform never reads it from stdin, so it only takes
+        // effect once `cat`/`tee` writes it to a .tf file.
+        if content.language != crate::heredoc::ScriptLanguage::Hcl
+            && content
+                .target_command
+                .as_ref()
+                .is_some_and(|cmd| crate::heredoc::is_non_executing_heredoc_command(cmd))
         {
             tracing::trace!(
                 target_command = ?content.target_command,
@@ -2072,7 +2578,11 @@ fn evaluate_heredoc(
             }
         }
 
-        let matches = match DEFAULT_MATCHER.find_matches(&content.content, content.language) {
+        let matches = match DEFAULT_MATCHER.find_matches_for_command(
+            &content.content,
+            content.language,
+            Some(command),
+        ) {
             Ok(matches) => matches,
             Err(err) => {
                 let is_timeout = matches!(err, crate::ast_matcher::MatchError::Timeout { .. });
@@ -2119,6 +2629,7 @@ fn evaluate_heredoc(
                             matched_text_preview: Some(m.matched_text_preview),
                             explanation: None,
                             suggestions: &[],
+                            recovery_hint: None,
                         },
                         hit.layer,
                         hit.entry.reason.clone(),
@@ -2141,6 +2652,7 @@ fn evaluate_heredoc(
                     matched_text_preview: Some(m.matched_text_preview),
                     explanation: None,
                     suggestions: &[],
+                    recovery_hint: None,
                 }),
                 allowlist_override: None,
                 effective_mode: Some(crate::packs::DecisionMode::Deny),
@@ -2159,14 +2671,423 @@ fn evaluate_heredoc(
     None
 }
 
-#[allow(dead_code)]
-fn check_fallback_patterns(command: &str) -> Option<EvaluationResult> {
-    // List of critical destructive patterns to check when AST analysis is skipped (e.g. oversized input).
-    // These patterns must be robust to whitespace variations where applicable.
-    static FALLBACK_PATTERNS: LazyLock<RegexSet> = LazyLock::new(|| {
-        RegexSet::new([
-            r"shutil\.rmtree",
-            r"os\.remove",
+/// Decode base64/hex/printf-escaped payloads that the command executes and
+/// evaluate what would actually run.
+///
+/// Shell payloads go back through the full evaluator (so packs, overrides,
+/// heredocs, and nested encodings all apply); script payloads go through the
+/// AST matcher for their language. Denials are reported against the encoded
+/// literal in the original command.
+fn evaluate_encoded_payloads(
+    command: &str,
+    context: &HeredocEvaluationContext<'_>,
+) -> Option<EvaluationResult> {
+    let mut payloads = crate::encoded::extract_encoded_payloads(command);
+    if payloads.is_empty() {
+        return None;
+    }
+    // Payloads inside commit messages, grep patterns, etc. are data. Masking
+    // keeps byte offsets, so spans still point into `command`.
+    let sanitized = sanitize_for_pattern_matching(command);
+    if matches!(sanitized, std::borrow::Cow::Owned(_)) {
+        payloads = crate::encoded::extract_encoded_payloads(&sanitized);
+    }
+
+    for payload in payloads {
+        if deadline_exceeded(context.deadline) {
+            return Some(EvaluationResult::allowed_due_to_budget());
+        }
+
+        let denied = if payload.language == crate::heredoc::ScriptLanguage::Bash {
+            let result = evaluate_command_with_pack_order_deadline_at_path(
+                &payload.decoded,
+                context.enabled_keywords,
+                context.ordered_packs,
+                context.keyword_index,
+                context.compiled_overrides,
+                context.allowlists,
+                context.heredoc_settings,
+                context.allow_once_audit,
+                context.project_path,
+                context.deadline,
+            );
+            if !result.is_denied() {
+                continue;
+            }
+            result
+        } else {
+            let Some(result) = evaluate_encoded_script(&payload, context) else {
+                continue;
+            };
+            result
+        };
+
+        let Some(mut info) = denied.pattern_info else {
+            return Some(denied);
+        };
+        info.reason = format!(
+            "Encoded command blocked: {} ({} payload decodes to: {})",
+            info.reason,
+            payload.encoding.label(),
+            payload.preview()
+        );
+        let span = MatchSpan {
+            start: payload.start,
+            end: payload.end,
+        };
+        info.matched_text_preview = Some(extract_match_preview(command, &span));
+        info.matched_span = Some(span);
+        return Some(EvaluationResult {
+            pattern_info: Some(info),
+            ..denied
+        });
+    }
+    None
+}
+
+/// Nesting limit for scripts that invoke other scripts (`"a": "npm run b"`,
+/// recipes that call `make`).
+const MAX_SCRIPT_DEPTH: u8 = 4;
+
+thread_local! {
+    static SCRIPT_DEPTH: std::cell::Cell<u8> = const { std::cell::Cell::new(0) };
+}
+
+/// Resolve `npm run <script>` (and yarn/pnpm), `make <target>`, and
+/// `just <recipe>` to the bodies defined in the project files and evaluate
+/// each body through the full pipeline.
+///
+/// Denials are reported against the invoking command segment. Scripts that
+/// invoke other scripts are followed up to [`MAX_SCRIPT_DEPTH`] levels.
+fn evaluate_project_scripts(
+    command: &str,
+    context: &HeredocEvaluationContext<'_>,
+    cwd: Option<&Path>,
+) -> Option<EvaluationResult> {
+    if !crate::scripts::mentions_script_runner(command) {
+        return None;
+    }
+    let depth = SCRIPT_DEPTH.with(std::cell::Cell::get);
+    if depth >= MAX_SCRIPT_DEPTH {
+        return None;
+    }
+    let cwd = cwd.map_or_else(
+        || std::env::current_dir().ok(),
+        |dir| Some(dir.to_path_buf()),
+    )?;
+    // Script names inside commit messages, grep patterns, etc. are data.
+    let sanitized = sanitize_for_pattern_matching(command);
+    let invocations = crate::scripts::extract_script_invocations(&sanitized, &cwd);
+
+    for invocation in invocations {
+        if deadline_exceeded(context.deadline) {
+            return Some(EvaluationResult::allowed_due_to_budget());
+        }
+        SCRIPT_DEPTH.with(|cell| cell.set(depth + 1));
+        let result = evaluate_command_with_pack_order_deadline_at_path(
+            &invocation.body,
+            context.enabled_keywords,
+            context.ordered_packs,
+            context.keyword_index,
+            context.compiled_overrides,
+            context.allowlists,
+            context.heredoc_settings,
+            context.allow_once_audit,
+            Some(&invocation.dir),
+            context.deadline,
+        );
+        SCRIPT_DEPTH.with(|cell| cell.set(depth));
+        if !result.is_denied() {
+            continue;
+        }
+
+        let Some(mut info) = result.pattern_info else {
+            return Some(result);
+        };
+        info.reason = format!(
+            "{} script '{}' blocked: {} (script runs: {})",
+            invocation.runner,
+            invocation.name,
+            info.reason,
+            invocation.preview()
+        );
+        let span = MatchSpan {
+            start: invocation.start,
+            end: invocation.end,
+        };
+        info.matched_text_preview = Some(extract_match_preview(command, &span));
+        info.matched_span = Some(span);
+        return Some(EvaluationResult {
+            pattern_info: Some(info),
+            ..result
+        });
+    }
+    None
+}
+
+/// Evaluate the `-a` argument of `ansible` ad-hoc commands that use a command
+/// module (`shell`, `command`, `raw`) through the full pipeline.
+///
+/// Only runs when `infrastructure.ansible` is enabled. Denials are reported
+/// against the `-a` argument in the original command.
+fn evaluate_ansible_adhoc(
+    command: &str,
+    context: &HeredocEvaluationContext<'_>,
+) -> Option<EvaluationResult> {
+    if !crate::ansible::mentions_ansible(command)
+        || !context
+            .ordered_packs
+            .iter()
+            .any(|id| id == "infrastructure.ansible")
+    {
+        return None;
+    }
+    // Only invocations still visible after masking data (commit messages,
+    // grep patterns) run; masking keeps byte offsets.
+    let sanitized = sanitize_for_pattern_matching(command);
+    let visible = crate::ansible::extract_adhoc_invocations(&sanitized);
+
+    for invocation in crate::ansible::extract_adhoc_invocations(command) {
+        if !visible.iter().any(|v| v.start == invocation.start) {
+            continue;
+        }
+        if deadline_exceeded(context.deadline) {
+            return Some(EvaluationResult::allowed_due_to_budget());
+        }
+        let result = evaluate_command_with_pack_order_deadline_at_path(
+            &invocation.args,
+            context.enabled_keywords,
+            context.ordered_packs,
+            context.keyword_index,
+            context.compiled_overrides,
+            context.allowlists,
+            context.heredoc_settings,
+            context.allow_once_audit,
+            context.project_path,
+            context.deadline,
+        );
+        if !result.is_denied() {
+            continue;
+        }
+
+        let Some(mut info) = result.pattern_info else {
+            return Some(result);
+        };
+        let hosts = if invocation.hosts.is_empty() {
+            "the targeted hosts"
+        } else {
+            invocation.hosts.as_str()
+        };
+        info.reason = format!(
+            "Ansible ad-hoc command blocked: {} (module {} runs on {hosts}: {})",
+            info.reason,
+            invocation.module,
+            invocation.preview()
+        );
+        let span = MatchSpan {
+            start: invocation.start,
+            end: invocation.end,
+        };
+        info.matched_text_preview = Some(extract_match_preview(command, &span));
+        info.matched_span = Some(span);
+        return Some(EvaluationResult {
+            pattern_info: Some(info),
+            ..result
+        });
+    }
+    None
+}
+
+/// Evaluate the innermost command of each wrapper chain that plain prefix
+/// stripping does not see through (`ssh host '<cmd>'`, `[wrappers] extra`).
+///
+/// For `ssh`, the denial names the remote host(s) the command would run on.
+fn evaluate_wrapped_commands(
+    command: &str,
+    context: &HeredocEvaluationContext<'_>,
+) -> Option<EvaluationResult> {
+    let wrappers = &context.compiled_overrides.wrappers;
+    if !wrappers.may_wrap(command) {
+        return None;
+    }
+    // Data (commit messages, grep patterns, heredoc text for `cat`) is masked
+    // first so it is never mistaken for a wrapper invocation.
+    let sanitized = sanitize_for_pattern_matching(command);
+    let visible = crate::heredoc::mask_non_executing_heredocs(&sanitized);
+
+    for (start, segment) in crate::scripts::command_segments(&visible) {
+        let chain = crate::normalize::resolve_wrapper_chain(segment, wrappers);
+        if !chain.needs_inner_evaluation() {
+            continue;
+        }
+        if deadline_exceeded(context.deadline) {
+            return Some(EvaluationResult::allowed_due_to_budget());
+        }
+        let result = evaluate_command_with_pack_order_deadline_at_path(
+            &chain.command,
+            context.enabled_keywords,
+            context.ordered_packs,
+            context.keyword_index,
+            context.compiled_overrides,
+            context.allowlists,
+            context.heredoc_settings,
+            context.allow_once_audit,
+            context.project_path,
+            context.deadline,
+        );
+        if !result.is_denied() {
+            continue;
+        }
+
+        let Some(mut info) = result.pattern_info else {
+            return Some(result);
+        };
+        if !chain.remote_hosts.is_empty() {
+            info.reason = format!(
+                "Remote command on {} blocked: {}",
+                chain.remote_hosts.join(" -> "),
+                info.reason
+            );
+        }
+        let span = MatchSpan {
+            start,
+            end: start + segment.trim_end().len(),
+        };
+        if command.get(span.start..span.end) == Some(segment.trim_end()) {
+            info.matched_text_preview = Some(extract_match_preview(command, &span));
+            info.matched_span = Some(span);
+        } else {
+            info.matched_span = None;
+        }
+        return Some(EvaluationResult {
+            pattern_info: Some(info),
+            ..result
+        });
+    }
+    None
+}
+
+/// Evaluate the commands tmux and screen would run in a session
+/// (`send-keys`, `new-session`, `split-window`, `run-shell`, `-X stuff`,
+/// `screen -dm cmd`) through the full pipeline.
+///
+/// Denials name the session and are reported against the payload words in
+/// the original command.
+fn evaluate_session_payloads(
+    command: &str,
+    context: &HeredocEvaluationContext<'_>,
+) -> Option<EvaluationResult> {
+    if !crate::sessions::mentions_session_tool(command) {
+        return None;
+    }
+    // Only invocations still visible after masking data (commit messages,
+    // grep patterns) run; masking keeps byte offsets.
+    let sanitized = sanitize_for_pattern_matching(command);
+    let visible = crate::sessions::extract_session_payloads(&sanitized);
+
+    for payload in crate::sessions::extract_session_payloads(command) {
+        if !visible.iter().any(|v| v.start == payload.start) {
+            continue;
+        }
+        if deadline_exceeded(context.deadline) {
+            return Some(EvaluationResult::allowed_due_to_budget());
+        }
+        let result = evaluate_command_with_pack_order_deadline_at_path(
+            &payload.command,
+            context.enabled_keywords,
+            context.ordered_packs,
+            context.keyword_index,
+            context.compiled_overrides,
+            context.allowlists,
+            context.heredoc_settings,
+            context.allow_once_audit,
+            context.project_path,
+            context.deadline,
+        );
+        if !result.is_denied() {
+            continue;
+        }
+
+        let Some(mut info) = result.pattern_info else {
+            return Some(result);
+        };
+        let target = payload
+            .target
+            .as_deref()
+            .map_or_else(|| "the current session".to_string(), |t| format!("'{t}'"));
+        info.reason = format!(
+            "{} session command blocked: {} ({} on {target}: {})",
+            payload.tool,
+            info.reason,
+            payload.via,
+            payload.preview()
+        );
+        let span = MatchSpan {
+            start: payload.start,
+            end: payload.end,
+        };
+        info.matched_text_preview = Some(extract_match_preview(command, &span));
+        info.matched_span = Some(span);
+        return Some(EvaluationResult {
+            pattern_info: Some(info),
+            ..result
+        });
+    }
+    None
+}
+
+/// AST-match a decoded script payload, returning the first non-allowlisted
+/// blocking match.
+fn evaluate_encoded_script(
+    payload: &crate::encoded::EncodedPayload,
+    context: &HeredocEvaluationContext<'_>,
+) -> Option<EvaluationResult> {
+    let matches = DEFAULT_MATCHER
+        .find_matches(&payload.decoded, payload.language)
+        .ok()?;
+    let m = matches.into_iter().find(|m| {
+        if !m.severity.blocks_by_default() {
+            return false;
+        }
+        let (pack_id, pattern_name) = split_ast_rule_id(&m.rule_id);
+        context
+            .allowlists
+            .match_rule(&pack_id, &pattern_name)
+            .is_none()
+    })?;
+    let (pack_id, pattern_name) = split_ast_rule_id(&m.rule_id);
+    Some(EvaluationResult {
+        decision: EvaluationDecision::Deny,
+        pattern_info: Some(PatternMatch {
+            reason: format!(
+                "{} (rule {pack_id}:{pattern_name}, matched: {})",
+                m.reason, m.matched_text_preview
+            ),
+            pack_id: Some(pack_id),
+            pattern_name: Some(pattern_name),
+            severity: Some(ast_severity_to_pack_severity(m.severity)),
+            source: MatchSource::HeredocAst,
+            matched_span: None,
+            matched_text_preview: Some(m.matched_text_preview),
+            explanation: None,
+            suggestions: &[],
+            recovery_hint: None,
+        }),
+        allowlist_override: None,
+        effective_mode: Some(crate::packs::DecisionMode::Deny),
+        skipped_due_to_budget: false,
+        branch_context: None,
+    })
+}
+
+#[allow(dead_code)]
+fn check_fallback_patterns(command: &str) -> Option<EvaluationResult> {
+    // List of critical destructive patterns to check when AST analysis is skipped (e.g. oversized input).
+    // These patterns must be robust to whitespace variations where applicable.
+    static FALLBACK_PATTERNS: LazyLock<RegexSet> = LazyLock::new(|| {
+        RegexSet::new([
+            r"shutil\.rmtree",
+            r"os\.remove",
             r"os\.rmdir",
             r"os\.unlink",
             r"fs\.rmSync",
@@ -2219,17 +3140,7 @@ fn format_heredoc_denial_reason(
     pack_id: &str,
     pattern_name: &str,
 ) -> String {
-    let lang = match extracted.language {
-        crate::heredoc::ScriptLanguage::Bash => "bash",
-        crate::heredoc::ScriptLanguage::Go => "go",
-        crate::heredoc::ScriptLanguage::Python => "python",
-        crate::heredoc::ScriptLanguage::Ruby => "ruby",
-        crate::heredoc::ScriptLanguage::Perl => "perl",
-        crate::heredoc::ScriptLanguage::JavaScript => "javascript",
-        crate::heredoc::ScriptLanguage::TypeScript => "typescript",
-        crate::heredoc::ScriptLanguage::Php => "php",
-        crate::heredoc::ScriptLanguage::Unknown => "unknown",
-    };
+    let lang = extracted.language.name();
 
     format!(
         "Embedded {lang} code blocked: {} (rule {pack_id}:{pattern_name}, line {}, matched: {})",
@@ -2607,6 +3518,27 @@ mod tests {
         assert!(result.is_allowed());
     }
 
+    #[test]
+    fn test_evaluate_commands_matches_single_evaluation() {
+        let config = default_config();
+        let compiled = config.compile_overrides();
+        let allowlists = default_allowlists();
+        let enabled_keywords = REGISTRY.collect_enabled_keywords(&config.enabled_pack_ids());
+        let commands = ["git status", "git reset --hard", "ls -la", "rm -rf /"];
+
+        let batch = evaluate_commands(&commands, &config, &allowlists);
+        assert_eq!(batch.len(), commands.len());
+        for (command, result) in commands.iter().zip(&batch) {
+            let single =
+                evaluate_command(command, &config, &enabled_keywords, &compiled, &allowlists);
+            assert_eq!(result.decision, single.decision, "{command}");
+            assert_eq!(result.pack_id(), single.pack_id(), "{command}");
+        }
+        assert!(batch[0].is_allowed());
+        assert!(batch[1].is_denied());
+        assert!(batch[3].is_denied());
+    }
+
     #[test]
     fn test_result_helper_methods() {
         let allowed = EvaluationResult::allowed();
@@ -2664,6 +3596,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_pack_match_carries_recovery_hint() {
+        let config = default_config();
+        let compiled = default_compiled_overrides();
+        let allowlists = default_allowlists();
+        let result = evaluate_command(
+            "git branch -D feature",
+            &config,
+            &["git"],
+            &compiled,
+            &allowlists,
+        );
+        assert!(result.is_denied());
+        let info = result.pattern_info.expect("deny must include pattern info");
+        assert_eq!(info.pattern_name.as_deref(), Some("branch-force-delete"));
+        assert!(
+            info.recovery_hint
+                .is_some_and(|hint| hint.contains("git reflog"))
+        );
+    }
+
     #[test]
     fn test_quick_reject_skips_patterns() {
         let config = default_config();
@@ -2790,6 +3743,97 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sql_heredocs_and_inline_statements_are_evaluated() {
+        let config = default_config();
+        let compiled = default_compiled_overrides();
+        let allowlists = default_allowlists();
+
+        // The database packs are not enabled; the SQL scanner still sees the statement.
+        let cmd = "psql -d app <<'SQL'\nBEGIN;\nDROP TABLE users;\nCOMMIT;\nSQL";
+        let result = evaluate_command(cmd, &config, &["kubectl"], &compiled, &allowlists);
+        assert!(result.is_denied());
+        let info = result.pattern_info.expect("deny must include pattern info");
+        assert_eq!(info.source, MatchSource::HeredocAst);
+        assert_eq!(info.pack_id.as_deref(), Some("heredoc.sql"));
+        assert_eq!(info.pattern_name.as_deref(), Some("drop_table"));
+
+        let cmd = r#"mysql -u root shop -e "DELETE FROM orders""#;
+        let result = evaluate_command(cmd, &config, &["kubectl"], &compiled, &allowlists);
+        assert!(result.is_denied());
+
+        let cmd = r#"mysql -u root shop -e "DELETE FROM orders WHERE id = 7""#;
+        let result = evaluate_command(cmd, &config, &["kubectl"], &compiled, &allowlists);
+        assert!(result.is_allowed());
+
+        let cmd = r#"sqlite3 -bail app.db "DELETE FROM users""#;
+        let result = evaluate_command(cmd, &config, &["kubectl"], &compiled, &allowlists);
+        assert!(result.is_denied());
+
+        let cmd = "sqlite3 <<'SQL'\n.open data/prod.db\n.restore nightly.bak\nSQL";
+        let result = evaluate_command(cmd, &config, &["kubectl"], &compiled, &allowlists);
+        assert!(result.is_denied());
+        let info = result.pattern_info.expect("deny must include pattern info");
+        assert_eq!(info.pattern_name.as_deref(), Some("sqlite_restore"));
+    }
+
+    #[test]
+    fn mongo_shell_eval_payloads_are_evaluated() {
+        let config = default_config();
+        let compiled = default_compiled_overrides();
+        let allowlists = default_allowlists();
+
+        // The mongodb pack is not enabled; the JavaScript analyzer still sees the payload.
+        let cmd = r#"mongosh "mongodb://db/app" --eval "db.users.find(); db.dropDatabase()""#;
+        let result = evaluate_command(cmd, &config, &["kubectl"], &compiled, &allowlists);
+        assert!(result.is_denied());
+        let info = result.pattern_info.expect("deny must include pattern info");
+        assert_eq!(info.source, MatchSource::HeredocAst);
+        assert_eq!(info.pack_id.as_deref(), Some("heredoc.javascript"));
+        assert_eq!(info.pattern_name.as_deref(), Some("mongo_drop_database"));
+
+        let cmd = "mongosh app --eval 'db.sessions.deleteMany({})'";
+        let result = evaluate_command(cmd, &config, &["kubectl"], &compiled, &allowlists);
+        assert!(result.is_denied());
+
+        let cmd = "mongosh app --eval 'db.sessions.deleteMany({ expired: true })'";
+        let result = evaluate_command(cmd, &config, &["kubectl"], &compiled, &allowlists);
+        assert!(result.is_allowed());
+    }
+
+    #[test]
+    fn terraform_and_kubernetes_heredocs_are_evaluated() {
+        let config = default_config();
+        let compiled = default_compiled_overrides();
+        let allowlists = default_allowlists();
+
+        // Written to a file by tee, but still scanned: terraform reads it from there.
+        let cmd = "tee main.tf <<'EOF'\nresource \"aws_db_instance\" \"main\" {\n  lifecycle {\n    prevent_destroy = false\n  }\n}\nEOF";
+        let result = evaluate_command(cmd, &config, &["kubectl"], &compiled, &allowlists);
+        assert!(result.is_denied());
+        let info = result.pattern_info.expect("deny must include pattern info");
+        assert_eq!(info.pack_id.as_deref(), Some("heredoc.hcl"));
+        assert_eq!(
+            info.pattern_name.as_deref(),
+            Some("prevent_destroy_disabled")
+        );
+
+        let namespace = "apiVersion: v1\nkind: Namespace\nmetadata:\n  name: payments\nEOF";
+        let cmd = format!("kubectl delete -f - <<EOF\n{namespace}");
+        let result = evaluate_command(&cmd, &config, &["kubectl"], &compiled, &allowlists);
+        assert!(result.is_denied());
+        let info = result.pattern_info.expect("deny must include pattern info");
+        assert_eq!(info.pattern_name.as_deref(), Some("namespace_delete"));
+
+        let cmd = format!("kubectl apply -f - <<EOF\n{namespace}");
+        let result = evaluate_command(&cmd, &config, &["kubectl"], &compiled, &allowlists);
+        assert!(result.is_allowed());
+
+        let cmd = "cat <<EOF | kubectl apply -f -\napiVersion: v1\nkind: Namespace\nmetadata:\n  name: payments\n  finalizers: null\nEOF";
+        let result = evaluate_command(cmd, &config, &["kubectl"], &compiled, &allowlists);
+        assert!(result.is_denied());
+    }
+
     #[test]
     fn heredoc_commands_with_non_blocking_matches_are_allowed() {
         let config = default_config();
@@ -2893,6 +3937,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn protected_paths_deny_resolved_targets() {
+        let mut config = default_config();
+        config.protection.paths.patterns = vec!["/etc/**".to_string()];
+
+        let compiled = config.compile_overrides();
+        let allowlists = default_allowlists();
+
+        let result = evaluate_command(
+            "truncate -s 0 /tmp/../etc/hosts",
+            &config,
+            &["kubectl"],
+            &compiled,
+            &allowlists,
+        );
+        assert!(
+            result.is_denied(),
+            "target resolving into /etc must be denied"
+        );
+        let info = result.pattern_info.expect("pattern info");
+        assert_eq!(info.source, MatchSource::ConfigOverride);
+        assert!(info.reason.contains("[protection.paths]"));
+
+        let result = evaluate_command(
+            "truncate -s 0 /tmp/app.log",
+            &config,
+            &["kubectl"],
+            &compiled,
+            &allowlists,
+        );
+        assert!(result.is_allowed());
+    }
+
     #[test]
     fn heredoc_content_allowlist_project_scope_does_not_match_other_projects() {
         let mut config = default_config();
@@ -2934,6 +4011,288 @@ mod tests {
         assert!(result.is_allowed());
     }
 
+    #[test]
+    fn encoded_shell_payloads_are_decoded_and_evaluated() {
+        let config = default_config();
+        let compiled = default_compiled_overrides();
+        let allowlists = default_allowlists();
+        let enabled_keywords = REGISTRY.collect_enabled_keywords(&config.enabled_pack_ids());
+
+        // Both decode to `rm -rf /`; neither mentions `rm` literally.
+        for cmd in [
+            "echo cm0gLXJmIC8= | base64 -d | sh",
+            r#"eval "$(printf '\x72\x6d\x20\x2d\x72\x66\x20\x2f')""#,
+        ] {
+            let result = evaluate_command(cmd, &config, &enabled_keywords, &compiled, &allowlists);
+            assert!(result.is_denied(), "{cmd}");
+            let info = result.pattern_info.expect("deny must include pattern info");
+            assert!(
+                info.reason.starts_with("Encoded command blocked:"),
+                "{}",
+                info.reason
+            );
+            assert!(info.reason.contains("rm -rf /"), "{}", info.reason);
+            let span = info.matched_span.expect("span of the encoded literal");
+            assert!(span.end <= cmd.len());
+        }
+
+        // "ls -la"
+        let result = evaluate_command(
+            "echo bHMgLWxh | base64 -d | sh",
+            &config,
+            &enabled_keywords,
+            &compiled,
+            &allowlists,
+        );
+        assert!(result.is_allowed());
+    }
+
+    #[test]
+    fn encoded_script_payloads_use_the_ast_matcher() {
+        let config = default_config();
+        let compiled = default_compiled_overrides();
+        let allowlists = default_allowlists();
+
+        // "import shutil; shutil.rmtree('/')"
+        let cmd = "python3 -c \"import base64; exec(base64.b64decode('aW1wb3J0IHNodXRpbDsgc2h1dGlsLnJtdHJlZSgnLycp'))\"";
+        let result = evaluate_command(cmd, &config, &["kubectl"], &compiled, &allowlists);
+        assert!(result.is_denied());
+        let info = result.pattern_info.expect("deny must include pattern info");
+        assert_eq!(info.source, MatchSource::HeredocAst);
+        assert!(info.reason.contains("base64 payload"), "{}", info.reason);
+    }
+
+    #[test]
+    fn encoded_payloads_inside_safe_string_arguments_are_data() {
+        let config = default_config();
+        let compiled = default_compiled_overrides();
+        let allowlists = default_allowlists();
+
+        let cmd = r#"git commit -m "detect: echo cm0gLXJmIC8= | base64 -d | sh""#;
+        let result = evaluate_command(cmd, &config, &["git"], &compiled, &allowlists);
+        assert!(result.is_allowed());
+    }
+
+    #[test]
+    fn project_scripts_are_resolved_and_evaluated() {
+        let config = default_config();
+        let compiled = default_compiled_overrides();
+        let allowlists = default_allowlists();
+        let enabled_keywords = REGISTRY.collect_enabled_keywords(&config.enabled_pack_ids());
+        let ordered_packs = REGISTRY.expand_enabled_ordered(&config.enabled_pack_ids());
+        let heredoc_settings = config.heredoc_settings();
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("package.json"),
+            r#"{"scripts": {"build": "tsc", "reset": "git reset --hard", "loop": "npm run loop", "nested": "npm run reset"}}"#,
+        )
+        .unwrap();
+        let evaluate = |cmd: &str| {
+            evaluate_command_with_pack_order_at_path(
+                cmd,
+                &enabled_keywords,
+                &ordered_packs,
+                None,
+                &compiled,
+                &allowlists,
+                &heredoc_settings,
+                Some(dir.path()),
+            )
+        };
+
+        let result = evaluate("npm run reset");
+        assert!(result.is_denied());
+        let info = result.pattern_info.expect("deny must include pattern info");
+        assert!(
+            info.reason.starts_with("npm script 'reset' blocked:"),
+            "{}",
+            info.reason
+        );
+        assert_eq!(info.pack_id.as_deref(), Some("core.git"));
+        assert_eq!(info.matched_span, Some(MatchSpan { start: 0, end: 13 }));
+
+        assert!(evaluate("npm run nested").is_denied());
+        assert!(evaluate("npm run build").is_allowed());
+        assert!(evaluate("npm run loop").is_allowed());
+        assert!(evaluate(r#"git commit -m "npm run reset""#).is_allowed());
+
+        std::fs::write(
+            dir.path().join("Makefile"),
+            "OUT = build\n\nall:\n\tcargo build\n\nclean: wipe\n\trm -rf $(OUT)\n\nwipe:\n\t@git clean -fdx\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("justfile"),
+            "test:\n    cargo test\n\nrelease: test\n    git push --force origin main\n",
+        )
+        .unwrap();
+
+        let result = evaluate("make clean");
+        assert!(result.is_denied());
+        let info = result.pattern_info.expect("deny must include pattern info");
+        assert!(
+            info.reason.starts_with("make script 'wipe' blocked:"),
+            "{}",
+            info.reason
+        );
+        assert!(evaluate("make").is_allowed());
+        assert!(evaluate("just release").is_denied());
+        assert!(evaluate("just test").is_allowed());
+    }
+
+    #[test]
+    fn ansible_adhoc_arguments_are_evaluated_as_commands() {
+        let config = default_config();
+        let compiled = default_compiled_overrides();
+        let allowlists = default_allowlists();
+        let heredoc_settings = config.heredoc_settings();
+        let mut enabled = config.enabled_pack_ids();
+        enabled.insert("infrastructure.ansible".to_string());
+        let enabled_keywords = REGISTRY.collect_enabled_keywords(&enabled);
+        let ordered_packs = REGISTRY.expand_enabled_ordered(&enabled);
+        let evaluate = |cmd: &str, ordered_packs: &[String]| {
+            evaluate_command_with_pack_order_at_path(
+                cmd,
+                &enabled_keywords,
+                ordered_packs,
+                None,
+                &compiled,
+                &allowlists,
+                &heredoc_settings,
+                None,
+            )
+        };
+
+        let cmd = r#"ansible web -m shell -a "git reset --hard" -f 20"#;
+        let result = evaluate(cmd, &ordered_packs);
+        assert!(result.is_denied());
+        let info = result.pattern_info.expect("deny must include pattern info");
+        assert!(
+            info.reason.starts_with("Ansible ad-hoc command blocked:"),
+            "{}",
+            info.reason
+        );
+        assert!(info.reason.contains("runs on web"), "{}", info.reason);
+        assert_eq!(info.pack_id.as_deref(), Some("core.git"));
+        let span = info.matched_span.expect("span of the -a argument");
+        assert_eq!(&cmd[span.start..span.end], r#""git reset --hard""#);
+
+        assert!(evaluate("ansible all -a uptime", &ordered_packs).is_allowed());
+        assert!(
+            evaluate(
+                r#"git commit -m "ansible all -a 'git reset --hard'""#,
+                &ordered_packs
+            )
+            .is_allowed()
+        );
+
+        // Without the ansible pack the argument is not unwrapped.
+        let core_only = REGISTRY.expand_enabled_ordered(&config.enabled_pack_ids());
+        let result = evaluate(cmd, &core_only);
+        let reason = result.reason().unwrap_or_default();
+        assert!(
+            !reason.starts_with("Ansible ad-hoc command blocked:"),
+            "{reason}"
+        );
+    }
+
+    #[test]
+    fn ssh_remote_commands_are_evaluated_with_host_context() {
+        let config = default_config();
+        let allowlists = default_allowlists();
+        let heredoc_settings = config.heredoc_settings();
+        let enabled = config.enabled_pack_ids();
+        let enabled_keywords = REGISTRY.collect_enabled_keywords(&enabled);
+        let ordered_packs = REGISTRY.expand_enabled_ordered(&enabled);
+        let evaluate = |cmd: &str, compiled: &crate::config::CompiledOverrides| {
+            evaluate_command_with_pack_order_at_path(
+                cmd,
+                &enabled_keywords,
+                &ordered_packs,
+                None,
+                compiled,
+                &allowlists,
+                &heredoc_settings,
+                None,
+            )
+        };
+        let compiled = default_compiled_overrides();
+
+        let cmd = r#"echo deploying; ssh -p 2222 deploy@web1 "cd /srv/app && git reset --hard""#;
+        let result = evaluate(cmd, &compiled);
+        assert!(result.is_denied());
+        let info = result.pattern_info.expect("deny must include pattern info");
+        assert!(
+            info.reason.starts_with("Remote command on web1 blocked:"),
+            "{}",
+            info.reason
+        );
+        assert_eq!(info.pack_id.as_deref(), Some("core.git"));
+        let span = info.matched_span.expect("span of the ssh segment");
+        assert_eq!(
+            &cmd[span.start..span.end],
+            r#"ssh -p 2222 deploy@web1 "cd /srv/app && git reset --hard""#
+        );
+
+        assert!(evaluate("ssh web1 'git status && uptime'", &compiled).is_allowed());
+        assert!(evaluate(r#"git commit -m "ssh web1 'git reset --hard'""#, &compiled).is_allowed());
+
+        // Custom wrappers from [wrappers] extra are unwrapped too.
+        let mut compiled = default_compiled_overrides();
+        compiled.wrappers.extra.push("chronic".to_string());
+        assert!(evaluate("chronic -v git reset --hard", &compiled).is_denied());
+    }
+
+    #[test]
+    fn session_payloads_are_evaluated_as_commands() {
+        let config = default_config();
+        let compiled = default_compiled_overrides();
+        let allowlists = default_allowlists();
+        let heredoc_settings = config.heredoc_settings();
+        let enabled = config.enabled_pack_ids();
+        let enabled_keywords = REGISTRY.collect_enabled_keywords(&enabled);
+        let ordered_packs = REGISTRY.expand_enabled_ordered(&enabled);
+        let evaluate = |cmd: &str| {
+            evaluate_command_with_pack_order_at_path(
+                cmd,
+                &enabled_keywords,
+                &ordered_packs,
+                None,
+                &compiled,
+                &allowlists,
+                &heredoc_settings,
+                None,
+            )
+        };
+
+        let cmd = "tmux send-keys -t work 'git reset' Space --hard Enter";
+        let result = evaluate(cmd);
+        assert!(result.is_denied());
+        let info = result.pattern_info.expect("deny must include pattern info");
+        assert!(
+            info.reason.starts_with("tmux session command blocked:"),
+            "{}",
+            info.reason
+        );
+        assert!(
+            info.reason.contains("send-keys on 'work'"),
+            "{}",
+            info.reason
+        );
+        assert_eq!(info.pack_id.as_deref(), Some("core.git"));
+        let span = info.matched_span.expect("span of the typed keys");
+        assert_eq!(&cmd[span.start..span.end], "'git reset' Space --hard Enter");
+
+        assert!(evaluate("nohup screen -dmS job git reset --hard &").is_denied());
+        assert!(evaluate(r"screen -S work -X stuff 'git clean -fdx\n'").is_denied());
+        assert!(evaluate("tmux new-session -d -s build 'cargo build'").is_allowed());
+        assert!(evaluate("tmux send-keys -t work C-c").is_allowed());
+        assert!(
+            evaluate(r#"git commit -m "tmux send-keys 'git reset --hard' Enter""#).is_allowed()
+        );
+    }
+
     #[test]
     fn test_evaluation_decision_equality() {
         assert_eq!(EvaluationDecision::Allow, EvaluationDecision::Allow);
@@ -3425,6 +4784,60 @@ mod tests {
             );
         }
 
+        /// A tight deadline checks Critical patterns of all packs before
+        /// lower severities of earlier packs.
+        #[test]
+        fn tight_deadline_checks_critical_patterns_first() {
+            let compiled_overrides = default_compiled_overrides();
+            let allowlists = default_allowlists();
+            let heredoc_settings = test_heredoc_settings();
+            let enabled_keywords: Vec<&str> = vec!["git", "rm"];
+            let ordered_packs: Vec<String> =
+                vec!["core.git".to_string(), "core.filesystem".to_string()];
+            let keyword_index = crate::packs::REGISTRY.build_enabled_keyword_index(&ordered_packs);
+            let command = "git branch -D feature && rm -rf /";
+
+            let evaluate = |deadline: Option<&Deadline>| {
+                evaluate_command_with_pack_order_deadline(
+                    command,
+                    &enabled_keywords,
+                    &ordered_packs,
+                    keyword_index.as_ref(),
+                    &compiled_overrides,
+                    &allowlists,
+                    &heredoc_settings,
+                    None,
+                    deadline,
+                )
+            };
+
+            // With ample budget there is a single pass in pack tier order, so
+            // core.git's Medium pattern is checked (and matches) first.
+            let relaxed = evaluate(Some(&Deadline::frozen(Duration::from_secs(10))));
+            let info = relaxed.pattern_info.as_ref().expect("denied");
+            assert_eq!(info.pack_id.as_deref(), Some("core.git"));
+            assert_eq!(info.severity, Some(crate::packs::Severity::Medium));
+
+            // Between the PATTERN_MATCH and CRITICAL_FIRST thresholds the
+            // Critical patterns of every pack are checked before core.git's
+            // Medium ones, so core.filesystem's Critical pattern reports first.
+            let tight = Deadline::frozen(
+                (crate::perf::PATTERN_MATCH.panic + crate::perf::CRITICAL_FIRST.panic) / 2,
+            );
+            let result = evaluate(Some(&tight));
+            assert!(result.is_denied(), "critical pass should still deny");
+            assert!(!result.skipped_due_to_budget);
+            let info = result.pattern_info.as_ref().expect("denied");
+            assert_eq!(info.pack_id.as_deref(), Some("core.filesystem"));
+            assert_eq!(info.severity, Some(crate::packs::Severity::Critical));
+
+            // Below PATTERN_MATCH no pattern is checked at all.
+            let exhausted = Deadline::frozen(crate::perf::PATTERN_MATCH.panic);
+            let result = evaluate(Some(&exhausted));
+            assert!(result.skipped_due_to_budget);
+            assert!(result.pattern_info.is_none());
+        }
+
         /// Safe commands should be allowed even with tight deadline.
         #[test]
         fn safe_command_with_deadline() {
@@ -3659,6 +5072,93 @@ mod tests {
         );
     }
 
+    #[test]
+    fn trace_pipeline_stages_records_each_stage() {
+        use crate::trace::{TraceCollector, TraceDetails};
+
+        let config = default_config();
+        let enabled_packs = config.enabled_pack_ids();
+        let enabled_keywords = REGISTRY.collect_enabled_keywords(&enabled_packs);
+        let ordered_packs = REGISTRY.expand_enabled_ordered(&enabled_packs);
+        let heredoc_settings = config.heredoc_settings();
+        let compiled_overrides = config.compile_overrides();
+        let command = "sudo git reset --hard";
+
+        let result = evaluate_command_with_pack_order(
+            command,
+            &enabled_keywords,
+            &ordered_packs,
+            None,
+            &compiled_overrides,
+            &LayeredAllowlist::default(),
+            &heredoc_settings,
+        );
+        let mut collector = TraceCollector::new(command);
+        trace_pipeline_stages(
+            &mut collector,
+            command,
+            &enabled_keywords,
+            &ordered_packs,
+            &compiled_overrides,
+            &heredoc_settings,
+            &result,
+        );
+        let trace = collector.finish(result.decision);
+
+        let names: Vec<&str> = trace.steps.iter().map(|s| s.name).collect();
+        assert_eq!(
+            names,
+            [
+                "config_override",
+                "heredoc",
+                "sanitize",
+                "normalize",
+                "keyword_gating",
+                "pack_candidates"
+            ]
+        );
+        assert_eq!(
+            trace.normalized_command.as_deref(),
+            Some("git reset --hard")
+        );
+
+        let Some(TraceDetails::KeywordGating {
+            quick_rejected,
+            keywords_hit,
+            ..
+        }) = trace.find_step("keyword_gating").map(|s| &s.details)
+        else {
+            panic!("missing keyword_gating step");
+        };
+        assert!(!quick_rejected);
+        assert!(keywords_hit.iter().any(|k| k == "git"));
+
+        let Some(TraceDetails::PackEvaluation {
+            packs_evaluated,
+            matched_pack,
+            ..
+        }) = trace.find_step("pack_candidates").map(|s| &s.details)
+        else {
+            panic!("missing pack_candidates step");
+        };
+        assert!(packs_evaluated.iter().any(|p| p == "core.git"));
+        assert_eq!(matched_pack.as_deref(), Some("core.git"));
+        assert_eq!(
+            trace.pack_summary.as_ref().unwrap().enabled_count,
+            ordered_packs.len()
+        );
+    }
+
+    #[test]
+    fn trace_pipeline_stages_counts_masked_spans() {
+        assert_eq!(count_masked_spans("git status", "git status"), 0);
+        assert_eq!(
+            count_masked_spans("git commit -m 'wipe'", "git commit -m       "),
+            1
+        );
+        assert_eq!(count_masked_spans("rg 'a' -e 'b'", "rg     -e    "), 2);
+    }
+
     // =========================================================================
     // UTF-8 Safe Windowing Tests (git_safety_guard-jpfm.2)
     // =========================================================================
@@ -3799,6 +5299,7 @@ mod tests {
                     matched_text_preview: None,
                     explanation: None,
                     suggestions: &[],
+                    recovery_hint: None,
                 }),
                 allowlist_override: None,
                 effective_mode: Some(crate::packs::DecisionMode::Deny),
@@ -3946,6 +5447,7 @@ mod tests {
                     matched_text_preview: None,
                     explanation: None,
                     suggestions: &[],
+                    recovery_hint: None,
                 }),
                 allowlist_override: None,
                 branch_context: None,
@@ -0,0 +1,411 @@
+//! External policy endpoint (`[policy.external]`).
+//!
+//! For pack matches at the configured severities, the hook asks an external
+//! HTTP service (an OPA server, or anything speaking the same small JSON
+//! contract) for the final decision. The request body wraps the query in
+//! `input`, as OPA's data API expects:
+//!
+//! ```json
+//! {
+//!   "input": {
+//!     "command": "kubectl delete deployment web",
+//!     "cwd": "/srv/app",
+//!     "rule_id": "kubernetes.kubectl:delete-workload",
+//!     "severity": "high",
+//!     "reason": "Deleting a workload takes it offline",
+//!     "dcg_decision": "deny",
+//!     "session_id": "abc123",
+//!     "host": "build-1"
+//!   }
+//! }
+//! ```
+//!
+//! The response is `{"decision": "allow" | "warn" | "deny" | "defer",
+//! "reason": "..."}`, optionally wrapped in OPA's `{"result": ...}`. A bare
+//! boolean result is read as allow (`true`) or deny (`false`). `defer` keeps
+//! dcg's own decision.
+//!
+//! The whole request runs under a hard timeout. Any failure (connection,
+//! non-2xx status, malformed body, timeout) resolves through the configured
+//! fail mode, so an outage never leaves the hook waiting.
+
+use std::sync::mpsc;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::config::{ExternalPolicyConfig, FailMode};
+use crate::logging::RedactionConfig;
+use crate::packs::{DecisionMode, Severity};
+
+/// Query sent to the endpoint (serialized under `input`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PolicyQuery {
+    /// Command text after redaction.
+    pub command: String,
+    /// Working directory of the hook.
+    pub cwd: String,
+    /// Matched rule (`pack_id:pattern_name`), or the pack alone.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rule_id: Option<String>,
+    /// Effective severity label.
+    pub severity: String,
+    /// Why the rule matched.
+    pub reason: String,
+    /// What dcg would decide on its own (`deny`, `warn`, or `allow`).
+    pub dcg_decision: String,
+    /// Agent session id, if the hook payload had one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    /// Host the hook ran on.
+    pub host: String,
+}
+
+impl PolicyQuery {
+    /// Build a query for a match, redacting the command.
+    #[allow(clippy::too_many_arguments)]
+    #[must_use]
+    pub fn new(
+        command: &str,
+        cwd: &str,
+        pack_id: Option<&str>,
+        pattern_name: Option<&str>,
+        severity: Option<Severity>,
+        reason: &str,
+        mode: DecisionMode,
+        session_id: Option<&str>,
+        redaction: &RedactionConfig,
+    ) -> Self {
+        let rule_id = match (pack_id, pattern_name) {
+            (Some(pack), Some(pattern)) => Some(format!("{pack}:{pattern}")),
+            (Some(pack), None) => Some(pack.to_string()),
+            _ => None,
+        };
+        Self {
+            command: crate::logging::redact_command(command, redaction),
+            cwd: cwd.to_string(),
+            rule_id,
+            severity: severity.unwrap_or(Severity::High).label().to_string(),
+            reason: reason.to_string(),
+            dcg_decision: mode_label(mode).to_string(),
+            session_id: session_id.map(str::to_string),
+            host: crate::logging::local_hostname(),
+        }
+    }
+}
+
+/// What the endpoint decided, or how its failure was resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExternalDecision {
+    /// The endpoint returned a decision.
+    Decided {
+        mode: DecisionMode,
+        reason: Option<String>,
+    },
+    /// The endpoint deferred to dcg's own decision.
+    Deferred,
+    /// The endpoint could not be consulted; `mode` comes from `fail_mode`.
+    Failed { mode: DecisionMode, error: String },
+}
+
+impl ExternalDecision {
+    /// Apply the decision to dcg's own mode.
+    #[must_use]
+    pub const fn apply(&self, mode: DecisionMode) -> DecisionMode {
+        match self {
+            Self::Decided { mode, .. } | Self::Failed { mode, .. } => *mode,
+            Self::Deferred => mode,
+        }
+    }
+
+    /// Notice appended to the deny or warn message, if any.
+    #[must_use]
+    pub fn notice(&self) -> Option<String> {
+        match self {
+            Self::Decided {
+                mode,
+                reason: Some(reason),
+            } => Some(format!(
+                "External policy decision ({}): {reason}",
+                mode_label(*mode)
+            )),
+            Self::Decided { .. } | Self::Deferred => None,
+            Self::Failed {
+                mode: DecisionMode::Deny,
+                error,
+            } => Some(format!(
+                "The external policy endpoint could not be reached ({error}) and \
+                 [policy.external] fail_mode is closed."
+            )),
+            Self::Failed { error, .. } => Some(format!(
+                "The external policy endpoint could not be reached ({error}); \
+                 allowed because [policy.external] fail_mode is open."
+            )),
+        }
+    }
+}
+
+/// Ask the endpoint for a decision, resolving failures through `fail_mode`.
+#[must_use]
+pub fn consult(config: &ExternalPolicyConfig, query: &PolicyQuery) -> ExternalDecision {
+    let timeout = Duration::from_millis(config.timeout_ms.max(1));
+    let token = config
+        .token_env
+        .as_deref()
+        .and_then(|name| std::env::var(name).ok())
+        .filter(|token| !token.trim().is_empty());
+    match request(&config.url, query, token, timeout) {
+        Ok(decision) => decision,
+        Err(error) => ExternalDecision::Failed {
+            mode: match config.fail_mode {
+                FailMode::Open => DecisionMode::Warn,
+                FailMode::Closed => DecisionMode::Deny,
+            },
+            error,
+        },
+    }
+}
+
+/// POST the query on a worker thread so the timeout covers DNS, connect,
+/// and the full response; a hung request is abandoned with the thread.
+fn request(
+    url: &str,
+    query: &PolicyQuery,
+    token: Option<String>,
+    timeout: Duration,
+) -> Result<ExternalDecision, String> {
+    let body = serde_json::json!({ "input": query }).to_string();
+    let url = url.to_string();
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let auth = token.map(|token| format!("Bearer {}", token.trim()));
+        let mut headers = vec![("Content-Type", "application/json")];
+        if let Some(auth) = auth.as_deref() {
+            headers.push(("Authorization", auth));
+        }
+        let result = crate::http::request("POST", &url, &headers, Some(body.as_bytes()), timeout);
+        let _ = tx.send(result);
+    });
+    let response = match rx.recv_timeout(timeout) {
+        Ok(Ok(response)) => response,
+        Ok(Err(e)) => return Err(e.to_string()),
+        Err(_) => return Err(format!("timed out after {}ms", timeout.as_millis())),
+    };
+    if !response.is_success() {
+        return Err(format!("HTTP {}", response.status));
+    }
+    parse_response(&response.body)
+}
+
+/// Parse a response body into a decision.
+///
+/// # Errors
+///
+/// Returns a description of the problem if the body is not a recognized
+/// decision.
+pub fn parse_response(body: &[u8]) -> Result<ExternalDecision, String> {
+    let value: serde_json::Value =
+        serde_json::from_slice(body).map_err(|e| format!("invalid JSON response: {e}"))?;
+    decision_from_json(value.get("result").unwrap_or(&value))
+}
+
+/// Read a decision document: a boolean, a decision string, or an object with
+/// `decision` and an optional `reason`. Shared with `[policy.rego]`.
+///
+/// # Errors
+///
+/// Returns a description of the problem if `value` is not a recognized
+/// decision.
+pub fn decision_from_json(value: &serde_json::Value) -> Result<ExternalDecision, String> {
+    let (decision, reason) = match value {
+        serde_json::Value::Bool(true) => ("allow", None),
+        serde_json::Value::Bool(false) => ("deny", None),
+        serde_json::Value::String(decision) => (decision.as_str(), None),
+        serde_json::Value::Object(fields) => (
+            fields
+                .get("decision")
+                .and_then(serde_json::Value::as_str)
+                .ok_or("response has no \"decision\"")?,
+            fields
+                .get("reason")
+                .and_then(serde_json::Value::as_str)
+                .map(str::trim)
+                .filter(|reason| !reason.is_empty())
+                .map(str::to_string),
+        ),
+        _ => return Err("response has no decision".to_string()),
+    };
+    let mode = match decision.trim().to_ascii_lowercase().as_str() {
+        "allow" | "log" => DecisionMode::Log,
+        "warn" => DecisionMode::Warn,
+        "deny" => DecisionMode::Deny,
+        "defer" => return Ok(ExternalDecision::Deferred),
+        other => return Err(format!("unknown decision \"{other}\"")),
+    };
+    Ok(ExternalDecision::Decided { mode, reason })
+}
+
+const fn mode_label(mode: DecisionMode) -> &'static str {
+    match mode {
+        DecisionMode::Deny => "deny",
+        DecisionMode::Warn => "warn",
+        DecisionMode::Log => "allow",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    fn query() -> PolicyQuery {
+        PolicyQuery::new(
+            "kubectl delete deployment web",
+            "/srv/app",
+            Some("kubernetes.kubectl"),
+            Some("delete-workload"),
+            Some(Severity::High),
+            "Deleting a workload takes it offline",
+            DecisionMode::Deny,
+            Some("abc123"),
+            &RedactionConfig::default(),
+        )
+    }
+
+    /// Serve one request with `response` and return the request text.
+    fn serve_once(response: &'static str) -> (String, std::thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!(
+            "http://{}/v1/data/dcg/decision",
+            listener.local_addr().unwrap()
+        );
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = vec![0u8; 8192];
+            let n = stream.read(&mut buf).unwrap();
+            let body = response;
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}",
+                body.len()
+            );
+            String::from_utf8_lossy(&buf[..n]).into_owned()
+        });
+        (url, handle)
+    }
+
+    fn config(url: String, fail_mode: FailMode) -> ExternalPolicyConfig {
+        ExternalPolicyConfig {
+            enabled: true,
+            url,
+            timeout_ms: 2_000,
+            fail_mode,
+            ..ExternalPolicyConfig::default()
+        }
+    }
+
+    #[test]
+    fn parses_plain_and_opa_responses() {
+        assert_eq!(
+            parse_response(br#"{"decision": "allow", "reason": "approved change window"}"#),
+            Ok(ExternalDecision::Decided {
+                mode: DecisionMode::Log,
+                reason: Some("approved change window".to_string()),
+            })
+        );
+        assert_eq!(
+            parse_response(br#"{"result": {"decision": "warn"}}"#),
+            Ok(ExternalDecision::Decided {
+                mode: DecisionMode::Warn,
+                reason: None,
+            })
+        );
+        assert_eq!(
+            parse_response(br#"{"result": false}"#),
+            Ok(ExternalDecision::Decided {
+                mode: DecisionMode::Deny,
+                reason: None,
+            })
+        );
+        assert_eq!(
+            parse_response(br#"{"result": "defer"}"#),
+            Ok(ExternalDecision::Deferred)
+        );
+        assert!(parse_response(br#"{"decision": "maybe"}"#).is_err());
+        assert!(parse_response(b"{}").is_err());
+        assert!(parse_response(b"not json").is_err());
+    }
+
+    #[test]
+    fn consult_posts_query_and_applies_decision() {
+        let (url, server) = serve_once(r#"{"result": {"decision": "allow", "reason": "ok"}}"#);
+        let decision = consult(&config(url, FailMode::Closed), &query());
+        assert_eq!(decision.apply(DecisionMode::Deny), DecisionMode::Log);
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /v1/data/dcg/decision HTTP/1.1"));
+        let body: serde_json::Value =
+            serde_json::from_str(request.split("\r\n\r\n").nth(1).unwrap()).unwrap();
+        assert_eq!(
+            body["input"]["rule_id"],
+            "kubernetes.kubectl:delete-workload"
+        );
+        assert_eq!(body["input"]["severity"], "high");
+        assert_eq!(body["input"]["dcg_decision"], "deny");
+        assert_eq!(body["input"]["session_id"], "abc123");
+    }
+
+    #[test]
+    fn unreachable_endpoint_follows_fail_mode() {
+        // Bind and drop to get a port with nothing listening.
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let url = format!("http://127.0.0.1:{port}/");
+
+        let open = consult(&config(url.clone(), FailMode::Open), &query());
+        assert!(matches!(open, ExternalDecision::Failed { .. }));
+        assert_eq!(open.apply(DecisionMode::Deny), DecisionMode::Warn);
+        assert!(open.notice().unwrap().contains("fail_mode is open"));
+
+        let closed = consult(&config(url, FailMode::Closed), &query());
+        assert_eq!(closed.apply(DecisionMode::Warn), DecisionMode::Deny);
+        assert!(closed.notice().unwrap().contains("fail_mode is closed"));
+    }
+
+    #[test]
+    fn slow_endpoint_times_out() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let _server = std::thread::spawn(move || {
+            let (_stream, _) = listener.accept().unwrap();
+            std::thread::sleep(Duration::from_secs(2));
+        });
+        let mut config = config(url, FailMode::Closed);
+        config.timeout_ms = 100;
+
+        let started = std::time::Instant::now();
+        let decision = consult(&config, &query());
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert!(matches!(
+            decision,
+            ExternalDecision::Failed {
+                mode: DecisionMode::Deny,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn severities_select_matches() {
+        let config = ExternalPolicyConfig::default();
+        assert!(config.applies_to(Some(Severity::Medium)));
+        assert!(config.applies_to(Some(Severity::High)));
+        assert!(!config.applies_to(Some(Severity::Critical)));
+        assert!(!config.applies_to(Some(Severity::Low)));
+        assert!(!config.is_active());
+    }
+}
@@ -0,0 +1,156 @@
+//! Fingerprint of the configuration a decision was made under.
+//!
+//! A [`ConfigFingerprint`] hashes everything besides the command itself that
+//! decides how dcg evaluates it: the dcg version (which fixes the built-in
+//! packs), the effective configuration (all merged layers, including enabled
+//! packs and overrides), the allowlist layers, and the external pack files.
+//!
+//! Hook mode computes it once per invocation and shares it: it keys the
+//! evaluation cache and the quick-allow filter, is sent to `dcg daemon`, and is
+//! stored with every history entry. `dcg test` reports it and `dcg corpus
+//! record` stores it in the corpus, so an old decision can be checked against
+//! the configuration in effect today before it is trusted.
+//!
+//! Allowlist and external pack files are covered by path, size, and
+//! modification time rather than contents, so computing the fingerprint never
+//! reads them again.
+
+use std::fmt;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::allowlist::LayeredAllowlist;
+use crate::config::Config;
+use crate::eval_cache::hex_digest;
+
+/// Length of [`ConfigFingerprint::short`].
+const SHORT_LEN: usize = 12;
+
+/// Hex-encoded SHA-256 of the configuration, allowlists, and packs in effect.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ConfigFingerprint(String);
+
+impl ConfigFingerprint {
+    /// Fingerprint `config` together with the allowlists loaded for it.
+    #[must_use]
+    pub fn new(config: &Config, allowlists: &LayeredAllowlist) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(env!("CARGO_PKG_VERSION").as_bytes());
+        hasher.update([0]);
+        // Round-trip through `Value` so map keys serialize in sorted order.
+        let config_json = serde_json::to_value(config)
+            .map(|value| value.to_string())
+            .unwrap_or_default();
+        hasher.update(config_json.as_bytes());
+        for layer in &allowlists.layers {
+            hasher.update([0]);
+            hasher.update(layer.layer.label().as_bytes());
+            hash_file(&mut hasher, &layer.path);
+        }
+        for path in config.packs.expand_custom_paths() {
+            hasher.update([0]);
+            hasher.update(b"pack");
+            hash_file(&mut hasher, Path::new(&path));
+        }
+        Self(hex_digest(hasher))
+    }
+
+    /// Full hex digest.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Leading hex digits, for human-readable output.
+    #[must_use]
+    pub fn short(&self) -> &str {
+        self.0.get(..SHORT_LEN).unwrap_or(&self.0)
+    }
+}
+
+impl fmt::Display for ConfigFingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Hash a file's path, size, and modification time (just the path when it is missing).
+fn hash_file(hasher: &mut Sha256, path: &Path) {
+    hasher.update(path.to_string_lossy().as_bytes());
+    if let Ok(meta) = std::fs::metadata(path) {
+        hasher.update(meta.len().to_le_bytes());
+        let modified = meta
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map_or(0, |d| d.as_nanos());
+        hasher.update(modified.to_le_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::allowlist::{AllowlistFile, AllowlistLayer, LoadedAllowlistLayer};
+    use tempfile::TempDir;
+
+    #[test]
+    fn fingerprint_tracks_config_and_packs() {
+        let config = Config::default();
+        let allowlists = LayeredAllowlist::default();
+        let base = ConfigFingerprint::new(&config, &allowlists);
+        assert_eq!(base, ConfigFingerprint::new(&config, &allowlists));
+        assert_eq!(base.as_str().len(), 64);
+
+        let mut changed = config.clone();
+        changed.overrides.block.push(crate::config::BlockOverride {
+            pattern: "make deploy".to_string(),
+            reason: "no deploys".to_string(),
+        });
+        assert_ne!(base, ConfigFingerprint::new(&changed, &allowlists));
+
+        let mut more_packs = config;
+        more_packs
+            .packs
+            .enabled
+            .push("containers.docker".to_string());
+        assert_ne!(base, ConfigFingerprint::new(&more_packs, &allowlists));
+    }
+
+    #[test]
+    fn fingerprint_tracks_allowlist_files() {
+        let dir = TempDir::new().expect("tempdir");
+        let path = dir.path().join("allowlist.toml");
+        let config = Config::default();
+        let allowlists = LayeredAllowlist {
+            layers: vec![LoadedAllowlistLayer {
+                layer: AllowlistLayer::Project,
+                path: path.clone(),
+                file: AllowlistFile::default(),
+            }],
+        };
+        let missing = ConfigFingerprint::new(&config, &allowlists);
+        assert_ne!(
+            missing,
+            ConfigFingerprint::new(&config, &LayeredAllowlist::default())
+        );
+
+        std::fs::write(&path, "[[allow]]\nrule = \"core.git:reset-hard\"\n").expect("write");
+        let written = ConfigFingerprint::new(&config, &allowlists);
+        assert_ne!(missing, written);
+        assert_eq!(written, ConfigFingerprint::new(&config, &allowlists));
+    }
+
+    #[test]
+    fn fingerprint_serializes_as_hex_string() {
+        let fingerprint = ConfigFingerprint::new(&Config::default(), &LayeredAllowlist::default());
+        let json = serde_json::to_string(&fingerprint).expect("serialize");
+        assert_eq!(json, format!("\"{fingerprint}\""));
+        let parsed: ConfigFingerprint = serde_json::from_str(&json).expect("parse");
+        assert_eq!(parsed, fingerprint);
+        assert!(fingerprint.as_str().starts_with(fingerprint.short()));
+    }
+}
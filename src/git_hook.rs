@@ -0,0 +1,393 @@
+//! Git hook mode: `pre-push` and `pre-rebase` checks.
+//!
+//! Agent hooks only see commands that flow through the agent's shell tool. A
+//! force push typed in a terminal, run by a script, or triggered by an IDE never
+//! reaches them. `dcg git-hook install` closes that gap by writing git hooks
+//! that translate what git is *about* to do into the equivalent command and run
+//! it through the normal evaluator, so packs, allowlists, and policy modes all
+//! apply unchanged:
+//!
+//! - **pre-push**: each ref update git reports on stdin is classified by
+//!   comparing the old and new object ids. A non-fast-forward update becomes
+//!   `git push --force <remote> <src>:<dst>`, a deletion becomes
+//!   `git push <remote> --delete <dst>`.
+//! - **pre-rebase**: if any commit being rebased is already reachable from a
+//!   remote-tracking ref, publishing the result will need a force push, so the
+//!   rebase is evaluated as `git push --force <remote> <branch>`.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Marker line identifying hooks written by `dcg git-hook install`.
+pub const GIT_HOOK_SENTINEL: &str = "# dcg:git-hook";
+
+/// Git hooks managed by `dcg git-hook install`.
+pub const MANAGED_HOOKS: &[&str] = &["pre-push", "pre-rebase"];
+
+/// One line of `pre-push` stdin: `<local ref> <local oid> <remote ref> <remote oid>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PushUpdate {
+    pub local_ref: String,
+    pub local_oid: String,
+    pub remote_ref: String,
+    pub remote_oid: String,
+}
+
+/// What a single ref update does to the remote.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PushEffect {
+    /// New ref on the remote.
+    Create,
+    /// Remote ref is deleted.
+    Delete,
+    /// Remote ref moves forward; no commits are lost.
+    FastForward,
+    /// Remote ref moves to a commit that does not contain the old one.
+    Rewrite,
+}
+
+/// A git operation translated into the command the evaluator should judge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImpliedCommand {
+    /// Human-readable description of what git is about to do.
+    pub description: String,
+    /// Equivalent shell command, evaluated like any agent command.
+    pub command: String,
+}
+
+/// Parse `pre-push` stdin. Malformed lines are skipped.
+#[must_use]
+pub fn parse_pre_push_input(input: &str) -> Vec<PushUpdate> {
+    input
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let update = PushUpdate {
+                local_ref: parts.next()?.to_string(),
+                local_oid: parts.next()?.to_string(),
+                remote_ref: parts.next()?.to_string(),
+                remote_oid: parts.next()?.to_string(),
+            };
+            parts.next().is_none().then_some(update)
+        })
+        .collect()
+}
+
+/// True for git's all-zero object id (SHA-1 or SHA-256).
+#[must_use]
+pub fn is_zero_oid(oid: &str) -> bool {
+    !oid.is_empty() && oid.bytes().all(|b| b == b'0')
+}
+
+/// Classify a ref update by comparing old and new object ids in `repo`.
+///
+/// An old remote commit that is missing locally cannot be an ancestor of what
+/// is being pushed, so it is treated as a rewrite.
+#[must_use]
+pub fn classify_push(repo: &Path, update: &PushUpdate) -> PushEffect {
+    if is_zero_oid(&update.local_oid) {
+        return PushEffect::Delete;
+    }
+    if is_zero_oid(&update.remote_oid) {
+        return PushEffect::Create;
+    }
+    if update.local_oid == update.remote_oid
+        || git_succeeds(
+            repo,
+            &[
+                "merge-base",
+                "--is-ancestor",
+                &update.remote_oid,
+                &update.local_oid,
+            ],
+        )
+    {
+        PushEffect::FastForward
+    } else {
+        PushEffect::Rewrite
+    }
+}
+
+/// The command a ref update is equivalent to, or `None` for harmless updates.
+#[must_use]
+pub fn implied_push_command(
+    remote: &str,
+    update: &PushUpdate,
+    effect: PushEffect,
+) -> Option<ImpliedCommand> {
+    let dst = short_ref(&update.remote_ref);
+    match effect {
+        PushEffect::Create | PushEffect::FastForward => None,
+        PushEffect::Delete => Some(ImpliedCommand {
+            description: format!("delete {dst} on {remote}"),
+            command: format!("git push {remote} --delete {dst}"),
+        }),
+        PushEffect::Rewrite => Some(ImpliedCommand {
+            description: format!(
+                "non-fast-forward update of {dst} on {remote} ({} -> {})",
+                abbrev(&update.remote_oid),
+                abbrev(&update.local_oid)
+            ),
+            command: format!(
+                "git push --force {remote} {}:{dst}",
+                short_ref(&update.local_ref)
+            ),
+        }),
+    }
+}
+
+/// Rebase arguments as passed to `pre-rebase`: `<upstream> [<branch>]`.
+///
+/// Returns the implied force push when the rebase would rewrite commits that
+/// are already on a remote, or `None` when every rebased commit is local.
+#[must_use]
+pub fn implied_rebase_command(
+    repo: &Path,
+    upstream: &str,
+    branch: Option<&str>,
+) -> Option<ImpliedCommand> {
+    let branch = match branch.filter(|b| !b.is_empty()) {
+        Some(branch) => branch.to_string(),
+        None => git_stdout(repo, &["symbolic-ref", "--quiet", "--short", "HEAD"])?,
+    };
+    let range = format!("{upstream}..{branch}");
+    let total = count_commits(repo, &[&range])?;
+    let local_only = count_commits(repo, &[&range, "--not", "--remotes"])?;
+    let published = total.saturating_sub(local_only);
+    if published == 0 {
+        return None;
+    }
+
+    let remote = git_stdout(repo, &["config", &format!("branch.{branch}.remote")])
+        .unwrap_or_else(|| "origin".to_string());
+    Some(ImpliedCommand {
+        description: format!(
+            "rebase of {branch} onto {upstream} rewrites {published} published commit{}",
+            if published == 1 { "" } else { "s" }
+        ),
+        command: format!("git push --force {remote} {branch}"),
+    })
+}
+
+/// Shell script for a managed hook. The hook forwards its arguments (and, for
+/// `pre-push`, stdin) to `dcg git-hook <name>`.
+#[must_use]
+pub fn hook_script(name: &str) -> String {
+    let bypass = if name == "pre-push" {
+        "git push --no-verify"
+    } else {
+        "git rebase --no-verify"
+    };
+    format!(
+        r#"#!/usr/bin/env sh
+{GIT_HOOK_SENTINEL}
+# Generated by: dcg git-hook install
+#
+# Evaluates what this git operation implies (force push, remote branch
+# deletion, rewriting published history) with the same packs, allowlists, and
+# policy as the agent hook.
+#
+# Bypass once (unsafe): {bypass}
+
+if ! command -v dcg >/dev/null 2>&1; then
+  echo "dcg {name} hook: 'dcg' not found in PATH; skipping." >&2
+  echo "Fix: install dcg or remove this hook via: dcg git-hook uninstall" >&2
+  exit 0
+fi
+
+exec dcg git-hook {name} "$@"
+"#
+    )
+}
+
+/// True when `contents` is a hook written by `dcg git-hook install`.
+#[must_use]
+pub fn is_managed_hook(contents: &[u8]) -> bool {
+    String::from_utf8_lossy(contents).contains(GIT_HOOK_SENTINEL)
+}
+
+/// Resolve `hooks/<name>` for the repository containing `cwd`, honouring
+/// `core.hooksPath` and worktrees.
+///
+/// # Errors
+///
+/// Returns an error when `cwd` is not inside a git repository.
+pub fn hook_path(cwd: &Path, name: &str) -> Result<PathBuf, String> {
+    let git_path = format!("hooks/{name}");
+    let path = git_stdout(cwd, &["rev-parse", "--git-path", &git_path])
+        .ok_or_else(|| format!("{} is not inside a git repository", cwd.display()))?;
+    let path = PathBuf::from(path);
+    Ok(if path.is_absolute() {
+        path
+    } else {
+        cwd.join(path)
+    })
+}
+
+fn short_ref(name: &str) -> &str {
+    name.strip_prefix("refs/heads/")
+        .or_else(|| name.strip_prefix("refs/tags/"))
+        .unwrap_or(name)
+}
+
+fn abbrev(oid: &str) -> &str {
+    oid.get(..12).unwrap_or(oid)
+}
+
+fn count_commits(repo: &Path, args: &[&str]) -> Option<usize> {
+    let mut full = vec!["rev-list", "--count"];
+    full.extend_from_slice(args);
+    git_stdout(repo, &full)?.parse().ok()
+}
+
+fn git_succeeds(repo: &Path, args: &[&str]) -> bool {
+    Command::new("git")
+        .current_dir(repo)
+        .args(args)
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+fn git_stdout(repo: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .current_dir(repo)
+        .args(args)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!stdout.is_empty()).then_some(stdout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const A: &str = "1111111111111111111111111111111111111111";
+    const B: &str = "2222222222222222222222222222222222222222";
+    const ZERO: &str = "0000000000000000000000000000000000000000";
+
+    fn update(local_oid: &str, remote_oid: &str) -> PushUpdate {
+        PushUpdate {
+            local_ref: "refs/heads/main".to_string(),
+            local_oid: local_oid.to_string(),
+            remote_ref: "refs/heads/main".to_string(),
+            remote_oid: remote_oid.to_string(),
+        }
+    }
+
+    fn git(repo: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .current_dir(repo)
+            .args(args)
+            .env("GIT_AUTHOR_NAME", "dcg")
+            .env("GIT_AUTHOR_EMAIL", "dcg@example.com")
+            .env("GIT_COMMITTER_NAME", "dcg")
+            .env("GIT_COMMITTER_EMAIL", "dcg@example.com")
+            .output()
+            .expect("git runs")
+            .status;
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    fn rev(repo: &Path, name: &str) -> String {
+        git_stdout(repo, &["rev-parse", name]).expect("rev-parse")
+    }
+
+    #[test]
+    fn parses_pre_push_lines() {
+        let input = format!(
+            "refs/heads/main {A} refs/heads/main {B}\nrefs/heads/gone {ZERO} refs/heads/gone {A}\n\ngarbage\n"
+        );
+        let updates = parse_pre_push_input(&input);
+        assert_eq!(updates.len(), 2);
+        assert_eq!(updates[0].local_oid, A);
+        assert_eq!(updates[1].remote_ref, "refs/heads/gone");
+    }
+
+    #[test]
+    fn zero_oid_detects_create_and_delete() {
+        let repo = Path::new(".");
+        assert_eq!(classify_push(repo, &update(ZERO, A)), PushEffect::Delete);
+        assert_eq!(classify_push(repo, &update(A, ZERO)), PushEffect::Create);
+        assert_eq!(classify_push(repo, &update(A, A)), PushEffect::FastForward);
+        assert!(is_zero_oid(&"0".repeat(64)));
+        assert!(!is_zero_oid(""));
+    }
+
+    #[test]
+    fn implied_commands_name_the_effect() {
+        let forced = implied_push_command("origin", &update(A, B), PushEffect::Rewrite)
+            .expect("rewrite is reported");
+        assert_eq!(forced.command, "git push --force origin main:main");
+        assert!(forced.description.contains("non-fast-forward"));
+
+        let deleted = implied_push_command("origin", &update(ZERO, A), PushEffect::Delete)
+            .expect("delete is reported");
+        assert_eq!(deleted.command, "git push origin --delete main");
+
+        assert!(implied_push_command("origin", &update(A, B), PushEffect::FastForward).is_none());
+        assert!(implied_push_command("origin", &update(A, ZERO), PushEffect::Create).is_none());
+    }
+
+    #[test]
+    fn ref_comparison_detects_rewrites() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let repo = tmp.path();
+        git(repo, &["init", "-q", "-b", "main"]);
+        git(repo, &["commit", "-q", "--allow-empty", "-m", "one"]);
+        let one = rev(repo, "HEAD");
+        git(repo, &["commit", "-q", "--allow-empty", "-m", "two"]);
+        let two = rev(repo, "HEAD");
+        git(
+            repo,
+            &["commit", "-q", "--amend", "--allow-empty", "-m", "two'"],
+        );
+        let amended = rev(repo, "HEAD");
+
+        assert_eq!(
+            classify_push(repo, &update(&two, &one)),
+            PushEffect::FastForward
+        );
+        assert_eq!(
+            classify_push(repo, &update(&amended, &two)),
+            PushEffect::Rewrite
+        );
+        assert_eq!(
+            classify_push(repo, &update(&amended, B)),
+            PushEffect::Rewrite
+        );
+    }
+
+    #[test]
+    fn rebase_of_published_commits_implies_force_push() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let repo = tmp.path();
+        git(repo, &["init", "-q", "-b", "main"]);
+        git(repo, &["commit", "-q", "--allow-empty", "-m", "base"]);
+        git(repo, &["checkout", "-q", "-b", "feature"]);
+        git(repo, &["commit", "-q", "--allow-empty", "-m", "pushed"]);
+        git(repo, &["update-ref", "refs/remotes/origin/feature", "HEAD"]);
+        git(repo, &["commit", "-q", "--allow-empty", "-m", "local"]);
+
+        let implied =
+            implied_rebase_command(repo, "main", Some("feature")).expect("published commit");
+        assert_eq!(implied.command, "git push --force origin feature");
+        assert!(implied.description.contains("1 published commit"));
+
+        git(repo, &["update-ref", "-d", "refs/remotes/origin/feature"]);
+        assert!(implied_rebase_command(repo, "main", None).is_none());
+    }
+
+    #[test]
+    fn hook_script_is_recognised() {
+        for name in MANAGED_HOOKS {
+            let script = hook_script(name);
+            assert!(is_managed_hook(script.as_bytes()));
+            assert!(script.contains(&format!("dcg git-hook {name}")));
+        }
+        assert!(!is_managed_hook(b"#!/bin/sh\nexit 0\n"));
+    }
+}
@@ -62,7 +62,7 @@ use tracing::{debug, instrument, trace, warn};
 /// quote-aware scanner so we can suppress obvious false positives inside quoted
 /// literals (commit messages, search patterns, etc.) without introducing false
 /// negatives for real shell syntax (including `$()`/backtick substitutions).
-const HEREDOC_TRIGGER_PATTERNS: [&str; 12] = [
+const HEREDOC_TRIGGER_PATTERNS: [&str; 15] = [
     // Inline interpreter execution. These patterns intentionally allow:
     // - interleaved flags (python -I -c, bash --norc -c)
     // - combined short-flag clusters (bash -lc, node -pe, perl -pi -e)
@@ -85,6 +85,13 @@ const HEREDOC_TRIGGER_PATTERNS: [&str; 12] = [
     r"\blua[0-9.]*(?:\.exe)?\b(?:\s+(?:--\S+|-[A-Za-z]+))*\s+-[A-Za-z]*e[A-Za-z]*\s",
     // Shell inline execution (sh -c, bash -c, zsh -c, fish -c, bash -lc, etc.)
     r"\b(?:sh|bash|zsh|fish)(?:\.exe)?\b(?:\s+(?:--\S+|-[A-Za-z]+))*\s+-[A-Za-z]*c[A-Za-z]*\s",
+    // SQL client inline statements (psql -c, mysql -e, mariadb --execute). Client flags
+    // take values (-U postgres, -h db), so anything up to a command separator is allowed.
+    r"\b(?:psql|mysql|mariadb)(?:\.exe)?\b[^|;&]*\s(?:-[ce]|--command|--execute)\b",
+    // sqlite3 takes its SQL as the quoted argument after the database file
+    r#"\bsqlite3?(?:\.exe)?\b(?:\s+-[A-Za-z]+)*\s+[^\s'"|;&<>-]\S*\s+['"]"#,
+    // MongoDB shell inline JavaScript (mongosh --eval, legacy mongo --eval)
+    r"\bmongo(?:sh)?(?:\.exe)?\b[^|;&]*\s--eval\b",
     // Piped execution to interpreters (versioned, with optional .exe)
     r"\|\s*(?:python[0-9.]*|ruby[0-9.]*|perl[0-9.]*|node(?:js)?[0-9.]*|php[0-9.]*|lua[0-9.]*|sh|bash)(?:\.exe)?\b",
     // Piped to xargs (can execute arbitrary commands)
@@ -471,10 +478,32 @@ pub enum ScriptLanguage {
     Perl,
     JavaScript,
     TypeScript,
+    Sql,
+    Hcl,
+    Yaml,
     Unknown,
 }
 
 impl ScriptLanguage {
+    /// Lowercase language name used in denial reasons and explain output.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Bash => "bash",
+            Self::Go => "go",
+            Self::Php => "php",
+            Self::Python => "python",
+            Self::Ruby => "ruby",
+            Self::Perl => "perl",
+            Self::JavaScript => "javascript",
+            Self::TypeScript => "typescript",
+            Self::Sql => "sql",
+            Self::Hcl => "hcl",
+            Self::Yaml => "yaml",
+            Self::Unknown => "unknown",
+        }
+    }
+
     /// Infer language from a command prefix (e.g., "python", "python3", "python3.11").
     ///
     /// Matches exact command names or names with version suffixes (e.g., "python3.11").
@@ -516,6 +545,18 @@ impl ScriptLanguage {
         } else if matches_interpreter("go") {
             // Note: Go doesn't typically use version suffixes in command names
             Self::Go
+        } else if matches_interpreter("mongosh") || matches_interpreter("mongo") {
+            // The MongoDB shells evaluate JavaScript (mongosh --eval, mongosh <<EOF)
+            Self::JavaScript
+        } else if matches_interpreter("psql")
+            || matches_interpreter("mysql")
+            || matches_interpreter("mariadb")
+            || matches_interpreter("sqlite")
+            || matches_interpreter("duckdb")
+            || matches_interpreter("sqlcmd")
+        {
+            // SQL clients read statements from stdin (psql <<EOF, sqlite3 db <<EOF)
+            Self::Sql
         } else if matches_interpreter("sh")
             || matches_interpreter("bash")
             || matches_interpreter("zsh")
@@ -674,6 +715,47 @@ impl ScriptLanguage {
             return Some(Self::Perl);
         }
 
+        // Terraform/HCL indicators (top-level blocks). Checked before bash because
+        // HCL interpolation (`${var.x}`) looks like shell parameter expansion.
+        let has_hcl_patterns = lines.iter().any(|l| {
+            let trimmed = l.trim();
+            trimmed.starts_with("resource \"")
+                || trimmed.starts_with("provider \"")
+                || trimmed.starts_with("module \"")
+                || trimmed.starts_with("data \"")
+                || trimmed.starts_with("provisioner \"")
+                || trimmed == "terraform {"
+                || trimmed == "lifecycle {"
+        });
+        if has_hcl_patterns {
+            return Some(Self::Hcl);
+        }
+
+        // Kubernetes manifest indicators
+        let has_k8s_yaml_patterns = lines.iter().any(|l| {
+            let trimmed = l.trim();
+            trimmed.starts_with("apiVersion:") || trimmed.starts_with("kind:")
+        });
+        if has_k8s_yaml_patterns {
+            return Some(Self::Yaml);
+        }
+
+        // SQL indicators (statement keywords at line start, any case)
+        let has_sql_patterns = lines.iter().any(|l| {
+            let upper = l.trim().to_ascii_uppercase();
+            upper.starts_with("CREATE TABLE ")
+                || upper.starts_with("DROP TABLE ")
+                || upper.starts_with("DROP DATABASE ")
+                || upper.starts_with("ALTER TABLE ")
+                || upper.starts_with("INSERT INTO ")
+                || upper.starts_with("DELETE FROM ")
+                || upper.starts_with("TRUNCATE ")
+                || (upper.starts_with("SELECT ") && upper.contains(" FROM "))
+        });
+        if has_sql_patterns {
+            return Some(Self::Sql);
+        }
+
         // Bash indicators (low priority - many scripts look like bash)
         let has_bash_patterns = lines.iter().any(|l| {
             let trimmed = l.trim();
@@ -982,6 +1064,55 @@ static INLINE_SCRIPT_DOUBLE_QUOTE: LazyLock<Regex> = LazyLock::new(|| {
         .expect("inline script double-quote regex compiles")
 });
 
+/// Regex for SQL client inline statements with single quotes.
+static INLINE_SQL_SINGLE_QUOTE: LazyLock<Regex> = LazyLock::new(|| {
+    // Matches: psql/mysql/mariadb [args] -c/--command/-e/--execute followed by single-quoted SQL
+    // Groups: (1) client, (2) flag, (3) content
+    // Positional args, flag values, and quoted connection strings are skipped lazily.
+    Regex::new(r"\b(psql|mysql|mariadb)(?:\.exe)?\b(?:[ \t]+(?:'[^']*'|\x22[^\x22]*\x22|[^\s'\x22|;&]+))*?[ \t]+(-c|--command|-e|--execute)[ \t]*=?[ \t]*'([^']*)'")
+        .expect("inline sql single-quote regex compiles")
+});
+
+/// Regex for SQL client inline statements with double quotes.
+static INLINE_SQL_DOUBLE_QUOTE: LazyLock<Regex> = LazyLock::new(|| {
+    // Matches: psql/mysql/mariadb [args] -c/--command/-e/--execute followed by double-quoted SQL
+    // Groups: (1) client, (2) flag, (3) content
+    Regex::new(r#"\b(psql|mysql|mariadb)(?:\.exe)?\b(?:[ \t]+(?:'[^']*'|"[^"]*"|[^\s'"|;&]+))*?[ \t]+(-c|--command|-e|--execute)[ \t]*=?[ \t]*"([^"]*)""#)
+        .expect("inline sql double-quote regex compiles")
+});
+
+/// Regex for sqlite3 inline SQL with single quotes.
+static INLINE_SQLITE_SINGLE_QUOTE: LazyLock<Regex> = LazyLock::new(|| {
+    // Matches: sqlite3 [-flags] DATABASE followed by single-quoted SQL
+    // Groups: (1) client, (2) database, (3) content
+    Regex::new(r"\b(sqlite3?)(?:\.exe)?\b(?:[ \t]+-[A-Za-z]+)*[ \t]+(\x22[^\x22]*\x22|[^\s'\x22|;&<>-][^\s'\x22|;&<>]*)[ \t]+'([^']*)'")
+        .expect("inline sqlite single-quote regex compiles")
+});
+
+/// Regex for sqlite3 inline SQL with double quotes.
+static INLINE_SQLITE_DOUBLE_QUOTE: LazyLock<Regex> = LazyLock::new(|| {
+    // Matches: sqlite3 [-flags] DATABASE followed by double-quoted SQL
+    // Groups: (1) client, (2) database, (3) content
+    Regex::new(r#"\b(sqlite3?)(?:\.exe)?\b(?:[ \t]+-[A-Za-z]+)*[ \t]+('[^']*'|[^\s'"|;&<>-][^\s'"|;&<>]*)[ \t]+"([^"]*)""#)
+        .expect("inline sqlite double-quote regex compiles")
+});
+
+/// Regex for MongoDB shell inline JavaScript with single quotes.
+static INLINE_MONGO_SINGLE_QUOTE: LazyLock<Regex> = LazyLock::new(|| {
+    // Matches: mongosh/mongo [args] --eval followed by single-quoted JavaScript
+    // Groups: (1) shell, (2) flag, (3) content
+    Regex::new(r"\b(mongosh|mongo)(?:\.exe)?\b(?:[ \t]+(?:'[^']*'|\x22[^\x22]*\x22|[^\s'\x22|;&]+))*?[ \t]+(--eval)[ \t]*=?[ \t]*'([^']*)'")
+        .expect("inline mongo single-quote regex compiles")
+});
+
+/// Regex for MongoDB shell inline JavaScript with double quotes.
+static INLINE_MONGO_DOUBLE_QUOTE: LazyLock<Regex> = LazyLock::new(|| {
+    // Matches: mongosh/mongo [args] --eval followed by double-quoted JavaScript
+    // Groups: (1) shell, (2) flag, (3) content
+    Regex::new(r#"\b(mongosh|mongo)(?:\.exe)?\b(?:[ \t]+(?:'[^']*'|"[^"]*"|[^\s'"|;&]+))*?[ \t]+(--eval)[ \t]*=?[ \t]*"([^"]*)""#)
+        .expect("inline mongo double-quote regex compiles")
+});
+
 // ============================================================================
 // Robustness: Binary Content Detection
 // ============================================================================
@@ -1216,9 +1347,9 @@ fn extract_inline_scripts(
         return;
     }
 
-    // Helper to extract from a given regex pattern
+    // Helper to extract from a given regex pattern; the interpreter is always group 1
     let mut hit_limit = false;
-    let mut extract_from_pattern = |pattern: &Regex| {
+    let mut extract_from_pattern = |pattern: &Regex, flag_group: usize, content_group: usize| {
         for cap in pattern.captures_iter(command) {
             if record_timeout_if_needed(start_time, timeout, limits.timeout_ms, skip_reasons) {
                 return;
@@ -1229,9 +1360,8 @@ fn extract_inline_scripts(
             }
 
             let cmd_name = cap.get(1).map_or("", |m| m.as_str());
-            let flag = cap.get(3).map_or("", |m| m.as_str());
-            // Content is in group 4: (1) interpreter, (2) optional "js", (3) flag, (4) content
-            let content_match = cap.get(4);
+            let flag = cap.get(flag_group).map_or("", |m| m.as_str());
+            let content_match = cap.get(content_group);
             let content = content_match.map_or("", |m| m.as_str());
 
             // The regex covers multiple interpreters; validate that the matched flag actually
@@ -1248,6 +1378,15 @@ fn extract_inline_scripts(
                 flag.contains('r')
             } else if cmd_name.starts_with("lua") {
                 flag.contains('e')
+            } else if cmd_name.starts_with("psql") {
+                flag == "-c" || flag == "--command"
+            } else if cmd_name.starts_with("mysql") || cmd_name.starts_with("mariadb") {
+                flag == "-e" || flag == "--execute"
+            } else if cmd_name.starts_with("sqlite") {
+                // The "flag" group holds the database file; the SQL follows it positionally.
+                true
+            } else if cmd_name.starts_with("mongo") {
+                flag == "--eval"
             } else {
                 // sh/bash/zsh/fish
                 flag.contains('c')
@@ -1277,9 +1416,19 @@ fn extract_inline_scripts(
         }
     };
 
-    // Extract from both single-quoted and double-quoted patterns
-    extract_from_pattern(&INLINE_SCRIPT_SINGLE_QUOTE);
-    extract_from_pattern(&INLINE_SCRIPT_DOUBLE_QUOTE);
+    // Extract from both single-quoted and double-quoted patterns.
+    // Interpreter groups: (1) interpreter, (2) optional "js", (3) flag, (4) content
+    extract_from_pattern(&INLINE_SCRIPT_SINGLE_QUOTE, 3, 4);
+    extract_from_pattern(&INLINE_SCRIPT_DOUBLE_QUOTE, 3, 4);
+    // SQL client groups: (1) client, (2) flag, (3) content
+    extract_from_pattern(&INLINE_SQL_SINGLE_QUOTE, 2, 3);
+    extract_from_pattern(&INLINE_SQL_DOUBLE_QUOTE, 2, 3);
+    // sqlite3 groups: (1) client, (2) database, (3) content
+    extract_from_pattern(&INLINE_SQLITE_SINGLE_QUOTE, 2, 3);
+    extract_from_pattern(&INLINE_SQLITE_DOUBLE_QUOTE, 2, 3);
+    // MongoDB shell groups: (1) shell, (2) flag, (3) content
+    extract_from_pattern(&INLINE_MONGO_SINGLE_QUOTE, 2, 3);
+    extract_from_pattern(&INLINE_MONGO_DOUBLE_QUOTE, 2, 3);
 
     if hit_limit {
         skip_reasons.push(SkipReason::ExceededHeredocLimit {
@@ -1330,9 +1479,16 @@ fn extract_herestrings(
             // Extract the command that receives the here-string
             let target_cmd = extract_heredoc_target_command(command, full_match.start());
 
+            // Here-strings are bash-specific, except when fed to a SQL client
+            // (mysql <<< "DROP TABLE t").
+            let language = match ScriptLanguage::detect(command, content) {
+                (ScriptLanguage::Sql, DetectionConfidence::CommandPrefix) => ScriptLanguage::Sql,
+                _ => ScriptLanguage::Bash,
+            };
+
             extracted.push(ExtractedContent {
                 content: content.to_string(),
-                language: ScriptLanguage::Bash, // Here-strings are bash-specific
+                language,
                 delimiter: None,
                 byte_range: full_match.start()..full_match.end(),
                 content_range: content_match.map(|m| m.start()..m.end()),
@@ -1411,6 +1567,7 @@ fn extract_heredocs(
         start_pos = command[start_pos..]
             .find('\n')
             .map_or(command.len(), |rel| start_pos.saturating_add(rel));
+        let trailing = &command[full_match.end()..start_pos];
 
         // Find the terminating delimiter
         match extract_heredoc_body(
@@ -1425,7 +1582,17 @@ fn extract_heredocs(
             Ok((content, end_pos, body_start_abs, body_end_abs)) => {
                 let (language, _confidence) = ScriptLanguage::detect(command, &content);
                 // Extract the command that receives the heredoc
-                let target_cmd = extract_heredoc_target_command(command, full_match.start());
+                let mut target_cmd = extract_heredoc_target_command(command, full_match.start());
+                // `cat <<EOF | kubectl apply -f -`: cat only forwards the body, so the
+                // pipe consumer is what actually runs it.
+                if target_cmd
+                    .as_deref()
+                    .is_some_and(is_non_executing_heredoc_command)
+                {
+                    if let Some(consumer) = piped_heredoc_consumer(trailing) {
+                        target_cmd = Some(consumer);
+                    }
+                }
                 extracted.push(ExtractedContent {
                     content,
                     language,
@@ -1453,6 +1620,25 @@ fn extract_heredocs(
     }
 }
 
+/// First executing command in a pipeline that follows a heredoc operator.
+///
+/// `trailing` is the rest of the operator line (`| kubectl apply -f -` in
+/// `cat <<EOF | kubectl apply -f -`). Stops at `||`, `&&`, and `;`, which do not
+/// forward the body.
+fn piped_heredoc_consumer(trailing: &str) -> Option<String> {
+    let end = [trailing.find("||"), trailing.find(['&', ';'])]
+        .into_iter()
+        .flatten()
+        .min()
+        .unwrap_or(trailing.len());
+
+    trailing[..end]
+        .split('|')
+        .skip(1)
+        .filter_map(ScriptLanguage::extract_head_interpreter)
+        .find(|cmd| !is_non_executing_heredoc_command(cmd))
+}
+
 /// Extract the command that receives a heredoc or here-string.
 ///
 /// Looks backwards from the heredoc operator position to find the command word.
@@ -2595,6 +2781,145 @@ mod tests {
             }
         }
 
+        #[test]
+        fn extracts_inline_sql_from_client_flags() {
+            let cases = [
+                (
+                    "psql -U postgres mydb -c 'DROP TABLE users'",
+                    "DROP TABLE users",
+                ),
+                (
+                    r#"mysql -uroot -psecret shop -e "DELETE FROM orders""#,
+                    "DELETE FROM orders",
+                ),
+                (r#"mariadb --execute="TRUNCATE t""#, "TRUNCATE t"),
+                (
+                    r#"psql "postgresql://app@db/prod" -c "DROP SCHEMA app""#,
+                    "DROP SCHEMA app",
+                ),
+                (r#"sqlite3 app.db "DROP TABLE users""#, "DROP TABLE users"),
+                (
+                    "sqlite3 -bail data/prod.sqlite 'DELETE FROM t'",
+                    "DELETE FROM t",
+                ),
+            ];
+
+            for (cmd, expected) in cases {
+                assert_eq!(
+                    check_triggers(cmd),
+                    TriggerResult::Triggered,
+                    "should trigger: {cmd}"
+                );
+                let ExtractionResult::Extracted(contents) =
+                    extract_content(cmd, &ExtractionLimits::default())
+                else {
+                    panic!("Expected Extracted result for {cmd}");
+                };
+                assert_eq!(contents.len(), 1, "{cmd}");
+                assert_eq!(contents[0].content, expected);
+                assert_eq!(contents[0].language, ScriptLanguage::Sql);
+            }
+        }
+
+        #[test]
+        fn extracts_inline_javascript_from_mongo_eval() {
+            let cases = [
+                (
+                    r#"mongosh --quiet "mongodb://db/app" --eval "db.dropDatabase()""#,
+                    "db.dropDatabase()",
+                ),
+                ("mongo app --eval 'db.users.drop()'", "db.users.drop()"),
+                (
+                    "mongosh --eval='db.orders.deleteMany({})'",
+                    "db.orders.deleteMany({})",
+                ),
+            ];
+
+            for (cmd, expected) in cases {
+                assert_eq!(
+                    check_triggers(cmd),
+                    TriggerResult::Triggered,
+                    "should trigger: {cmd}"
+                );
+                let ExtractionResult::Extracted(contents) =
+                    extract_content(cmd, &ExtractionLimits::default())
+                else {
+                    panic!("Expected Extracted result for {cmd}");
+                };
+                assert_eq!(contents.len(), 1, "{cmd}");
+                assert_eq!(contents[0].content, expected);
+                assert_eq!(contents[0].language, ScriptLanguage::JavaScript);
+            }
+        }
+
+        #[test]
+        fn sql_client_heredocs_and_here_strings_are_sql() {
+            let result = extract_content(
+                "psql -d app <<'SQL'\nDROP TABLE users;\nSQL",
+                &ExtractionLimits::default(),
+            );
+            let ExtractionResult::Extracted(contents) = result else {
+                panic!("Expected Extracted result");
+            };
+            assert_eq!(contents[0].language, ScriptLanguage::Sql);
+
+            let result = extract_content(
+                r#"mysql -u root app <<< "TRUNCATE sessions""#,
+                &ExtractionLimits::default(),
+            );
+            let ExtractionResult::Extracted(contents) = result else {
+                panic!("Expected Extracted result");
+            };
+            assert_eq!(contents[0].language, ScriptLanguage::Sql);
+        }
+
+        #[test]
+        fn detects_terraform_and_kubernetes_manifests() {
+            let result = extract_content(
+                "tee main.tf <<'EOF'\nresource \"aws_s3_bucket\" \"logs\" {\n  bucket = \"${var.name}\"\n}\nEOF",
+                &ExtractionLimits::default(),
+            );
+            let ExtractionResult::Extracted(contents) = result else {
+                panic!("Expected Extracted result");
+            };
+            assert_eq!(contents[0].language, ScriptLanguage::Hcl);
+            assert_eq!(contents[0].target_command.as_deref(), Some("tee"));
+
+            let result = extract_content(
+                "kubectl apply -f - <<EOF\napiVersion: v1\nkind: Namespace\nEOF",
+                &ExtractionLimits::default(),
+            );
+            let ExtractionResult::Extracted(contents) = result else {
+                panic!("Expected Extracted result");
+            };
+            assert_eq!(contents[0].language, ScriptLanguage::Yaml);
+        }
+
+        #[test]
+        fn piped_heredoc_targets_the_executing_consumer() {
+            let result = extract_content(
+                "cat <<EOF | kubectl apply -f -\napiVersion: v1\nkind: Namespace\nEOF",
+                &ExtractionLimits::default(),
+            );
+            let ExtractionResult::Extracted(contents) = result else {
+                panic!("Expected Extracted result");
+            };
+            assert_eq!(contents[0].target_command.as_deref(), Some("kubectl"));
+
+            // Non-executing consumers and list operators leave cat as the target.
+            for cmd in [
+                "cat <<EOF | grep kind\nkind: Namespace\nEOF",
+                "cat <<EOF || kubectl apply -f -\nkind: Namespace\nEOF",
+            ] {
+                let ExtractionResult::Extracted(contents) =
+                    extract_content(cmd, &ExtractionLimits::default())
+                else {
+                    panic!("Expected Extracted result for {cmd}");
+                };
+                assert_eq!(contents[0].target_command.as_deref(), Some("cat"), "{cmd}");
+            }
+        }
+
         #[test]
         fn extracts_here_string() {
             let result = extract_content("cat <<< 'hello world'", &ExtractionLimits::default());
@@ -2862,6 +3187,10 @@ mod tests {
                 ScriptLanguage::from_command("bashful"),
                 ScriptLanguage::Unknown
             );
+            assert_eq!(
+                ScriptLanguage::from_command("mysqldump"),
+                ScriptLanguage::Unknown
+            );
         }
 
         #[test]
@@ -2884,6 +3213,7 @@ mod tests {
                 ScriptLanguage::JavaScript
             );
             assert_eq!(ScriptLanguage::from_command("perl5"), ScriptLanguage::Perl);
+            assert_eq!(ScriptLanguage::from_command("sqlite3"), ScriptLanguage::Sql);
         }
 
         #[test]
@@ -2905,6 +3235,12 @@ mod tests {
                 ScriptLanguage::JavaScript
             );
             assert_eq!(ScriptLanguage::from_command("bash"), ScriptLanguage::Bash);
+            assert_eq!(ScriptLanguage::from_command("psql"), ScriptLanguage::Sql);
+            assert_eq!(ScriptLanguage::from_command("mysql"), ScriptLanguage::Sql);
+            assert_eq!(
+                ScriptLanguage::from_command("mongosh"),
+                ScriptLanguage::JavaScript
+            );
             assert_eq!(
                 ScriptLanguage::from_command("unknown"),
                 ScriptLanguage::Unknown
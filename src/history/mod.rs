@@ -45,12 +45,14 @@ use std::time::{Duration, Instant};
 use tracing::{debug, error, trace, warn};
 
 pub use schema::{
-    AgentStat, BackupResult, CURRENT_SCHEMA_VERSION, CheckResult, CommandEntry,
-    DEFAULT_DB_FILENAME, ExportFilters, ExportOptions, ExportedData, FrequentBlock,
-    HistoryAnalyzer, HistoryDb, HistoryError, HistoryStats, Outcome, OutcomeStats,
-    PackEffectivenessAnalysis, PackRecommendation, PathCluster, PatternEffectiveness, PatternStat,
-    PerformanceStats, PotentialGap, ProjectStat, RecommendationType, RuleMetrics, RuleTrend,
-    StatsTrends, SuggestionAction, SuggestionAuditEntry, SuggestionCandidate,
+    AgentStat, BackupResult, CURRENT_SCHEMA_VERSION, CheckResult, CommandEntry, CompactionReport,
+    DEFAULT_DB_FILENAME, DailyOutcomeStat, DashboardHitStat, DashboardStats, DenyReviewItem,
+    ExportFilters, ExportOptions, ExportedData, FALSE_POSITIVE_WINDOW_HOURS, FalsePositiveEstimate,
+    FrequentBlock, HistoryAnalyzer, HistoryDb, HistoryError, HistoryStats, LiveSnapshot, Outcome,
+    OutcomeStats, PackEffectivenessAnalysis, PackRecommendation, PathCluster, PatternEffectiveness,
+    PatternStat, PerformanceStats, PotentialGap, ProjectStat, RecommendationType, RetentionPolicy,
+    ReviewVerdict, RuleMetrics, RuleTrend, SearchOptions, SessionRisk, SessionStat, StatsTrends,
+    SuggestionAction, SuggestionAuditEntry, SuggestionCandidate, ToolResult,
 };
 
 /// Environment variable to override the history database path.
@@ -70,9 +72,9 @@ enum HistoryMessage {
 struct WorkerConfig {
     batch_size: usize,
     flush_interval: Duration,
-    auto_prune: bool,
-    retention_days: u32,
-    prune_check_interval: Duration,
+    /// `None` when `[history.retention]` is disabled.
+    retention: Option<RetentionPolicy>,
+    compaction_interval: Duration,
 }
 
 impl Default for WorkerConfig {
@@ -80,9 +82,8 @@ impl Default for WorkerConfig {
         Self {
             batch_size: 50,
             flush_interval: Duration::from_millis(100),
-            auto_prune: false,
-            retention_days: 90,
-            prune_check_interval: Duration::from_secs(24 * 3600),
+            retention: None,
+            compaction_interval: Duration::from_secs(3600),
         }
     }
 }
@@ -92,11 +93,8 @@ impl From<&HistoryConfig> for WorkerConfig {
         Self {
             batch_size: config.batch_size as usize,
             flush_interval: Duration::from_millis(u64::from(config.batch_flush_interval_ms)),
-            auto_prune: config.auto_prune,
-            retention_days: config.retention_days,
-            prune_check_interval: Duration::from_secs(
-                u64::from(config.prune_check_interval_hours) * 3600,
-            ),
+            retention: config.retention.enabled.then(|| config.retention.policy()),
+            compaction_interval: Duration::from_secs(config.retention.compaction_interval_secs),
         }
     }
 }
@@ -263,9 +261,9 @@ fn history_worker(db: HistoryDb, receiver: mpsc::Receiver<HistoryMessage>, confi
     let mut last_flush = Instant::now();
     let mut last_prune_check = Instant::now();
 
-    // Check if we need auto-prune on startup
-    if config.auto_prune {
-        check_and_prune(&db, config.retention_days);
+    // Compact on startup if the last pass is older than the interval
+    if let Some(policy) = &config.retention {
+        check_and_compact(&db, policy, config.compaction_interval);
     }
 
     loop {
@@ -342,10 +340,12 @@ fn history_worker(db: HistoryDb, receiver: mpsc::Receiver<HistoryMessage>, confi
                     last_flush = Instant::now();
                 }
 
-                // Check for auto-prune periodically
-                if config.auto_prune && last_prune_check.elapsed() >= config.prune_check_interval {
-                    check_and_prune(&db, config.retention_days);
-                    last_prune_check = Instant::now();
+                // Re-check compaction periodically in long-lived processes
+                if let Some(policy) = &config.retention {
+                    if last_prune_check.elapsed() >= config.compaction_interval {
+                        check_and_compact(&db, policy, config.compaction_interval);
+                        last_prune_check = Instant::now();
+                    }
                 }
             }
             Err(mpsc::RecvTimeoutError::Disconnected) => {
@@ -462,33 +462,58 @@ fn flush_batch(db: &HistoryDb, batch: &mut Vec<CommandEntry>) {
     batch.clear();
 }
 
-/// Check if pruning is needed and perform it.
-fn check_and_prune(db: &HistoryDb, retention_days: u32) {
-    // Check if enough time has passed since last prune
-    match db.should_auto_prune() {
+/// Check if compaction is due and perform it.
+fn check_and_compact(db: &HistoryDb, policy: &RetentionPolicy, interval: Duration) {
+    // Check if enough time has passed since the last pass
+    match db.should_auto_prune(interval) {
         Ok(true) => {
-            debug!(retention_days = retention_days, "Starting auto-prune");
-            match db.prune_older_than_days(u64::from(retention_days), false) {
-                Ok(pruned_count) => {
-                    debug!(pruned = pruned_count, "Auto-prune completed");
+            debug!(?policy, "Starting history compaction");
+            match db.compact(policy) {
+                Ok(report) => {
+                    debug!(
+                        pruned_by_age = report.pruned_by_age,
+                        pruned_by_rows = report.pruned_by_rows,
+                        pruned_by_size = report.pruned_by_size,
+                        "History compaction completed"
+                    );
                     if let Err(e) = db.record_prune_timestamp() {
                         warn!(error = %e, "Failed to record prune timestamp");
                     }
                 }
                 Err(e) => {
-                    error!(error = %e, "Auto-prune failed");
+                    error!(error = %e, "History compaction failed");
                 }
             }
         }
         Ok(false) => {
-            trace!("Auto-prune not needed yet");
+            trace!("History compaction not due yet");
         }
         Err(e) => {
-            warn!(error = %e, "Failed to check if auto-prune is needed");
+            warn!(error = %e, "Failed to check if history compaction is due");
         }
     }
 }
 
+/// Attach a `PostToolUse` result to the history entry for its command.
+///
+/// The command and stderr are redacted the same way [`HistoryWriter::log`]
+/// redacts commands, so the entry is found by its stored form.
+///
+/// # Errors
+///
+/// Returns an error if the lookup or update fails.
+pub fn record_tool_result(
+    db: &HistoryDb,
+    config: &HistoryConfig,
+    mut result: ToolResult,
+) -> Result<Option<i64>, HistoryError> {
+    result.command = redact_for_history(&result.command, config.redaction_mode);
+    result.stderr = result
+        .stderr
+        .map(|stderr| redact_for_history(&stderr, config.redaction_mode));
+    db.record_tool_result(&result)
+}
+
 fn redact_for_history(command: &str, mode: HistoryRedactionMode) -> String {
     match mode {
         HistoryRedactionMode::None => command.to_string(),
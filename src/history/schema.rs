@@ -9,7 +9,7 @@
 //! - Graceful schema migrations
 
 use chrono::{DateTime, Duration, Utc};
-use rusqlite::{Connection, params};
+use rusqlite::{Connection, OptionalExtension, params};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
@@ -18,7 +18,7 @@ use std::fmt::Write as FmtWrite;
 use std::path::{Path, PathBuf};
 
 /// Current schema version for migrations.
-pub const CURRENT_SCHEMA_VERSION: u32 = 5;
+pub const CURRENT_SCHEMA_VERSION: u32 = 10;
 
 /// Default database filename.
 pub const DEFAULT_DB_FILENAME: &str = "history.db";
@@ -128,6 +128,49 @@ impl std::str::FromStr for Outcome {
     }
 }
 
+/// Verdict recorded for a denied command in `dcg review`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewVerdict {
+    /// The deny was a false positive and the command was approved.
+    Approved,
+    /// Dismissed without a judgement.
+    Ignored,
+    /// The deny was correct.
+    TruePositive,
+}
+
+impl ReviewVerdict {
+    /// Convert to database string representation.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Approved => "approved",
+            Self::Ignored => "ignored",
+            Self::TruePositive => "true_positive",
+        }
+    }
+
+    /// Parse from database string representation.
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "approved" => Some(Self::Approved),
+            "ignored" => Some(Self::Ignored),
+            "true_positive" => Some(Self::TruePositive),
+            _ => None,
+        }
+    }
+}
+
+/// A denied command awaiting review, with its row id.
+#[derive(Debug, Clone)]
+pub struct DenyReviewItem {
+    /// Row id in the `commands` table.
+    pub id: i64,
+    pub entry: CommandEntry,
+}
+
 /// A single command entry for the history database.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommandEntry {
@@ -156,11 +199,23 @@ pub struct CommandEntry {
     #[serde(default)]
     pub eval_duration_us: u64,
     /// Optional session ID to group commands.
+    ///
+    /// The agent's own session id when the hook payload carries one,
+    /// otherwise an id generated per history writer.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub session_id: Option<String>,
+    /// Path to the agent's session transcript, when the hook payload has one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transcript_path: Option<String>,
     /// Exit code if the command was executed.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub exit_code: Option<i32>,
+    /// Captured stderr if the command was executed (truncated).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stderr: Option<String>,
+    /// Agent tool-call id, used to attach the `PostToolUse` result.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_use_id: Option<String>,
     /// Parent command ID for subshell tracking.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub parent_command_id: Option<i64>,
@@ -173,6 +228,10 @@ pub struct CommandEntry {
     /// Bypass code used (if command was bypassed).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bypass_code: Option<String>,
+    /// [`crate::fingerprint::ConfigFingerprint`] of the configuration the
+    /// command was evaluated under.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config_fingerprint: Option<String>,
 }
 
 impl Default for CommandEntry {
@@ -189,10 +248,14 @@ impl Default for CommandEntry {
             eval_duration_us: 0,
             session_id: None,
             exit_code: None,
+            stderr: None,
+            tool_use_id: None,
             parent_command_id: None,
             hostname: None,
             allowlist_layer: None,
             bypass_code: None,
+            transcript_path: None,
+            config_fingerprint: None,
         }
     }
 }
@@ -266,6 +329,64 @@ pub struct AgentStat {
     pub count: u64,
 }
 
+/// Per-session summary (`dcg history sessions`).
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionStat {
+    pub session_id: String,
+    pub agent_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transcript_path: Option<String>,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+    pub total: u64,
+    pub denied: u64,
+    pub warned: u64,
+    /// Commands that ran and exited non-zero (from `PostToolUse`).
+    pub failed: u64,
+}
+
+/// Recent activity for the live view (`dcg top`).
+#[derive(Debug, Clone, Serialize)]
+pub struct LiveSnapshot {
+    /// Start of the window the counts and percentiles cover.
+    pub since: DateTime<Utc>,
+    /// Commands evaluated in the window.
+    pub total: u64,
+    pub outcomes: OutcomeStats,
+    /// Denied / total in the window.
+    pub deny_rate: f64,
+    /// Evaluation latency in the window.
+    pub latency: PerformanceStats,
+    /// Sessions with activity in the window, most recently active first.
+    pub active_sessions: Vec<SessionStat>,
+    /// Most recent evaluations, newest first (not limited to the window).
+    pub recent: Vec<CommandEntry>,
+}
+
+/// Running risk score for one agent session or working directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct SessionRisk {
+    pub score: u64,
+    /// Number of scored commands.
+    pub events: u64,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Result of an executed command, reported by a `PostToolUse` hook.
+#[derive(Debug, Clone, Default)]
+pub struct ToolResult {
+    /// Agent tool-call id, matched against the `PreToolUse` entry.
+    pub tool_use_id: Option<String>,
+    /// Agent session id, used when there is no tool-call id.
+    pub session_id: Option<String>,
+    /// Command as stored in history (after redaction).
+    pub command: String,
+    /// Process exit code, when the agent reports one.
+    pub exit_code: Option<i32>,
+    /// Captured stderr.
+    pub stderr: Option<String>,
+}
+
 /// Trend comparison for history stats.
 #[derive(Debug, Clone, Serialize)]
 pub struct StatsTrends {
@@ -305,6 +426,36 @@ pub struct CheckResult {
     pub freelist_count: u64,
 }
 
+/// Limits enforced by [`HistoryDb::compact`]. Zero disables a limit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    /// Keep at most this many command entries.
+    pub max_rows: u64,
+    /// Delete entries older than this many days.
+    pub max_age_days: u64,
+    /// Keep live database pages under this many bytes.
+    pub max_db_size_bytes: u64,
+}
+
+/// Rows deleted by one [`HistoryDb::compact`] pass, by limit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct CompactionReport {
+    /// Entries older than `max_age_days`.
+    pub pruned_by_age: u64,
+    /// Oldest entries beyond `max_rows`.
+    pub pruned_by_rows: u64,
+    /// Oldest entries removed to fit `max_db_size_bytes`.
+    pub pruned_by_size: u64,
+}
+
+impl CompactionReport {
+    /// Total entries deleted.
+    #[must_use]
+    pub const fn total(&self) -> u64 {
+        self.pruned_by_age + self.pruned_by_rows + self.pruned_by_size
+    }
+}
+
 /// Result of a database backup operation.
 #[derive(Debug, Clone, Serialize)]
 pub struct BackupResult {
@@ -335,6 +486,65 @@ pub struct HistoryStats {
     pub trends: Option<StatsTrends>,
 }
 
+/// Outcome counts for one pack or pattern in the stats dashboard.
+#[derive(Debug, Clone, Serialize)]
+pub struct DashboardHitStat {
+    /// Pack ID, or `pack:pattern` for pattern rows.
+    pub name: String,
+    /// All matches (allowlisted, warned, and denied).
+    pub hits: u64,
+    /// Matches let through by an allowlist or bypass.
+    pub allowed: u64,
+    pub warned: u64,
+    pub denied: u64,
+    /// Denies that look like false positives (see [`FalsePositiveEstimate`]).
+    pub likely_false_positives: u64,
+}
+
+/// Per-day outcome counts for the stats dashboard.
+#[derive(Debug, Clone, Serialize)]
+pub struct DailyOutcomeStat {
+    /// UTC date (`YYYY-MM-DD`).
+    pub day: String,
+    pub allowed: u64,
+    pub warned: u64,
+    pub denied: u64,
+}
+
+/// Estimate of how many denies were false positives.
+///
+/// A deny counts as a likely false positive when the same command was allowed
+/// (typically via `dcg allow-once`) or bypassed within
+/// [`FALSE_POSITIVE_WINDOW_HOURS`] afterwards.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FalsePositiveEstimate {
+    pub denied: u64,
+    pub likely_false_positives: u64,
+    /// `likely_false_positives / denied`, 0.0-1.0.
+    pub rate: f64,
+}
+
+/// How long after a deny an allow of the same command counts as an override.
+pub const FALSE_POSITIVE_WINDOW_HOURS: u32 = 24;
+
+/// Data behind `dcg stats --dashboard`.
+#[derive(Debug, Clone, Serialize)]
+pub struct DashboardStats {
+    pub period_days: u64,
+    pub total_commands: u64,
+    pub outcomes: OutcomeStats,
+    /// Per-pack hit counts, busiest first.
+    pub packs: Vec<DashboardHitStat>,
+    /// Patterns with the most denies.
+    pub top_blocked: Vec<DashboardHitStat>,
+    /// Outcome counts per day, oldest first.
+    pub daily: Vec<DailyOutcomeStat>,
+    /// Median evaluation latency in microseconds.
+    pub median_eval_us: u64,
+    pub performance: PerformanceStats,
+    pub false_positives: FalsePositiveEstimate,
+}
+
 // ============================================================================
 // Suggestion Analysis Types
 // ============================================================================
@@ -516,6 +726,15 @@ struct StatsSnapshot {
     performance: PerformanceStats,
 }
 
+/// SQL condition (over a `commands c` row) that holds when the same command was
+/// allowed or bypassed within [`FALSE_POSITIVE_WINDOW_HOURS`] afterwards.
+const LATER_ALLOW_EXISTS: &str = "EXISTS (
+    SELECT 1 FROM commands l
+    WHERE l.command_hash = c.command_hash
+      AND l.outcome IN ('allow', 'bypass')
+      AND l.timestamp > c.timestamp
+      AND l.timestamp <= strftime('%Y-%m-%dT%H:%M:%fZ', c.timestamp, '+24 hours'))";
+
 fn format_timestamp(dt: DateTime<Utc>) -> String {
     dt.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string()
 }
@@ -729,6 +948,113 @@ impl HistoryDb {
         Ok(u64::try_from(count).unwrap_or(0))
     }
 
+    /// Count entries with `outcome` for one agent recorded at or after `since`.
+    ///
+    /// Entries are scoped to `session_id` when given, otherwise to
+    /// `working_dir`. Used by anomaly detection to measure how often an agent
+    /// is being denied.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub fn count_recent_outcomes(
+        &self,
+        outcome: Outcome,
+        agent_type: &str,
+        working_dir: &str,
+        session_id: Option<&str>,
+        since: DateTime<Utc>,
+    ) -> Result<u64, HistoryError> {
+        let (scope_sql, scope) = session_id.map_or(("working_dir = ?3", working_dir), |id| {
+            ("session_id = ?3", id)
+        });
+        let sql = format!(
+            "SELECT COUNT(*) FROM commands
+             WHERE outcome = ?1 AND agent_type = ?2 AND {scope_sql} AND timestamp >= ?4"
+        );
+        let count: i64 = self.conn.query_row(
+            &sql,
+            params![outcome.as_str(), agent_type, scope, format_timestamp(since)],
+            |row| row.get(0),
+        )?;
+        Ok(u64::try_from(count).unwrap_or(0))
+    }
+
+    /// Read the running risk score for `scope` (a session id or working directory).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub fn session_risk(
+        &self,
+        agent_type: &str,
+        scope: &str,
+    ) -> Result<Option<SessionRisk>, HistoryError> {
+        let row = self
+            .conn
+            .query_row(
+                "SELECT score, events, updated_at FROM session_risk
+                 WHERE agent_type = ?1 AND scope = ?2",
+                params![agent_type, scope],
+                |row| {
+                    Ok((
+                        row.get::<_, i64>(0)?,
+                        row.get::<_, i64>(1)?,
+                        row.get::<_, String>(2)?,
+                    ))
+                },
+            )
+            .optional()?;
+
+        Ok(row.map(|(score, events, updated_at)| SessionRisk {
+            score: u64::try_from(score).unwrap_or(0),
+            events: u64::try_from(events).unwrap_or(0),
+            updated_at: DateTime::parse_from_rfc3339(&updated_at)
+                .map_or_else(|_| Utc::now(), |dt| dt.with_timezone(&Utc)),
+        }))
+    }
+
+    /// Add `points` to the risk score for `scope` and return the new score.
+    ///
+    /// When `reset_before` is given, a score last updated before it is
+    /// discarded first, so an idle session starts over.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the update fails.
+    pub fn add_session_risk(
+        &self,
+        agent_type: &str,
+        scope: &str,
+        points: u64,
+        now: DateTime<Utc>,
+        reset_before: Option<DateTime<Utc>>,
+    ) -> Result<u64, HistoryError> {
+        let previous = self
+            .session_risk(agent_type, scope)?
+            .filter(|risk| reset_before.is_none_or(|cutoff| risk.updated_at >= cutoff));
+        let (score, events) = previous.map_or((points, 1), |risk| {
+            (risk.score.saturating_add(points), risk.events + 1)
+        });
+
+        self.conn.execute(
+            "INSERT INTO session_risk (agent_type, scope, score, events, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(agent_type, scope) DO UPDATE SET
+                score = excluded.score,
+                events = excluded.events,
+                updated_at = excluded.updated_at",
+            params![
+                agent_type,
+                scope,
+                i64::try_from(score).unwrap_or(i64::MAX),
+                i64::try_from(events).unwrap_or(i64::MAX),
+                format_timestamp(now)
+            ],
+        )?;
+        Ok(score)
+    }
+
     /// Prune history entries older than the specified number of days.
     ///
     /// When `dry_run` is true, no rows are deleted.
@@ -955,6 +1281,157 @@ impl HistoryDb {
         })
     }
 
+    /// Compute the `dcg stats --dashboard` view for the last `period_days` days.
+    ///
+    /// `limit` caps the number of blocked patterns returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any underlying query fails.
+    pub fn compute_dashboard(
+        &self,
+        period_days: u64,
+        limit: usize,
+    ) -> Result<DashboardStats, HistoryError> {
+        let now = Utc::now();
+        let period_days_i64 = i64::try_from(period_days).unwrap_or(i64::MAX);
+        let since = now - Duration::days(period_days_i64);
+        let snapshot = self.compute_stats_range(since, now)?;
+
+        let start_ts = format_timestamp(since);
+        let end_ts = format_timestamp(now);
+        let limit_i64 = i64::try_from(limit).unwrap_or(i64::MAX);
+
+        let packs = self.dashboard_breakdown(
+            "pack_id",
+            "pack_id IS NOT NULL",
+            "hits DESC, name ASC",
+            &start_ts,
+            &end_ts,
+            i64::MAX,
+        )?;
+        let top_blocked = self
+            .dashboard_breakdown(
+                "COALESCE(pack_id, '?') || ':' || pattern_name",
+                "pattern_name IS NOT NULL",
+                "denied DESC, name ASC",
+                &start_ts,
+                &end_ts,
+                limit_i64,
+            )?
+            .into_iter()
+            .filter(|stat| stat.denied > 0)
+            .collect();
+
+        let mut daily = Vec::new();
+        let mut stmt = self.conn.prepare(
+            "SELECT substr(timestamp, 1, 10) AS day,
+                    SUM(CASE WHEN outcome IN ('allow', 'bypass') THEN 1 ELSE 0 END),
+                    SUM(CASE WHEN outcome = 'warn' THEN 1 ELSE 0 END),
+                    SUM(CASE WHEN outcome = 'deny' THEN 1 ELSE 0 END)
+             FROM commands
+             WHERE timestamp >= ?1 AND timestamp < ?2
+             GROUP BY day
+             ORDER BY day ASC",
+        )?;
+        let rows = stmt.query_map(params![&start_ts, &end_ts], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, i64>(3)?,
+            ))
+        })?;
+        for row in rows {
+            let (day, allowed, warned, denied) = row?;
+            daily.push(DailyOutcomeStat {
+                day,
+                allowed: u64::try_from(allowed).unwrap_or(0),
+                warned: u64::try_from(warned).unwrap_or(0),
+                denied: u64::try_from(denied).unwrap_or(0),
+            });
+        }
+
+        let likely_false_positives: i64 = self.conn.query_row(
+            &format!(
+                "SELECT COUNT(*) FROM commands c
+                 WHERE c.timestamp >= ?1 AND c.timestamp < ?2 AND c.outcome = 'deny'
+                   AND {LATER_ALLOW_EXISTS}"
+            ),
+            params![&start_ts, &end_ts],
+            |row| row.get(0),
+        )?;
+        let likely_false_positives = u64::try_from(likely_false_positives).unwrap_or(0);
+        let false_positives = FalsePositiveEstimate {
+            denied: snapshot.outcomes.denied,
+            likely_false_positives,
+            rate: ratio(likely_false_positives, snapshot.outcomes.denied),
+        };
+
+        Ok(DashboardStats {
+            period_days,
+            total_commands: snapshot.total_commands,
+            outcomes: snapshot.outcomes,
+            packs,
+            top_blocked,
+            daily,
+            median_eval_us: snapshot.performance.p50_us,
+            performance: snapshot.performance,
+            false_positives,
+        })
+    }
+
+    /// Outcome counts grouped by `group_expr` for rows matching `filter`.
+    fn dashboard_breakdown(
+        &self,
+        group_expr: &str,
+        filter: &str,
+        order_by: &str,
+        start_ts: &str,
+        end_ts: &str,
+        limit: i64,
+    ) -> Result<Vec<DashboardHitStat>, HistoryError> {
+        let sql = format!(
+            "SELECT {group_expr} AS name,
+                    COUNT(*) AS hits,
+                    SUM(CASE WHEN c.outcome IN ('allow', 'bypass') THEN 1 ELSE 0 END),
+                    SUM(CASE WHEN c.outcome = 'warn' THEN 1 ELSE 0 END),
+                    SUM(CASE WHEN c.outcome = 'deny' THEN 1 ELSE 0 END) AS denied,
+                    SUM(CASE WHEN c.outcome = 'deny' AND {LATER_ALLOW_EXISTS}
+                        THEN 1 ELSE 0 END)
+             FROM commands c
+             WHERE c.timestamp >= ?1 AND c.timestamp < ?2 AND {filter}
+             GROUP BY name
+             ORDER BY {order_by}
+             LIMIT ?3"
+        );
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt.query_map(params![start_ts, end_ts, limit], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, i64>(3)?,
+                row.get::<_, i64>(4)?,
+                row.get::<_, i64>(5)?,
+            ))
+        })?;
+
+        let mut stats = Vec::new();
+        for row in rows {
+            let (name, hits, allowed, warned, denied, likely_false_positives) = row?;
+            stats.push(DashboardHitStat {
+                name,
+                hits: u64::try_from(hits).unwrap_or(0),
+                allowed: u64::try_from(allowed).unwrap_or(0),
+                warned: u64::try_from(warned).unwrap_or(0),
+                denied: u64::try_from(denied).unwrap_or(0),
+                likely_false_positives: u64::try_from(likely_false_positives).unwrap_or(0),
+            });
+        }
+        Ok(stats)
+    }
+
     /// Log a command entry to the database.
     ///
     /// # Errors
@@ -974,9 +1451,11 @@ impl HistoryDb {
                 timestamp, agent_type, working_dir, command, command_hash,
                 outcome, pack_id, pattern_name, rule_id, eval_duration_us,
                 session_id, exit_code, parent_command_id, hostname,
-                allowlist_layer, bypass_code
+                allowlist_layer, bypass_code, transcript_path, stderr, tool_use_id,
+                config_fingerprint
             ) VALUES (
-                ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16
+                ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17,
+                ?18, ?19, ?20
             )",
             params![
                 timestamp,
@@ -995,12 +1474,67 @@ impl HistoryDb {
                 entry.hostname,
                 entry.allowlist_layer,
                 entry.bypass_code,
+                entry.transcript_path,
+                entry.stderr,
+                entry.tool_use_id,
+                entry.config_fingerprint,
             ],
         )?;
 
         Ok(self.conn.last_insert_rowid())
     }
 
+    /// Attach an executed command's exit code and stderr to its entry.
+    ///
+    /// The entry is found by `tool_use_id` when one is given and recorded,
+    /// otherwise by the most recent entry in the same session with the same
+    /// command and no exit code yet. Returns the updated row id, or `None`
+    /// if no entry matched.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the lookup or update fails.
+    pub fn record_tool_result(&self, result: &ToolResult) -> Result<Option<i64>, HistoryError> {
+        let by_tool_use_id = match result.tool_use_id.as_deref() {
+            Some(tool_use_id) => self
+                .conn
+                .query_row(
+                    "SELECT id FROM commands WHERE tool_use_id = ?1 ORDER BY id DESC LIMIT 1",
+                    params![tool_use_id],
+                    |row| row.get::<_, i64>(0),
+                )
+                .optional()?,
+            None => None,
+        };
+        let id = match by_tool_use_id {
+            Some(id) => Some(id),
+            None => {
+                let command_hash = CommandEntry {
+                    command: result.command.clone(),
+                    ..Default::default()
+                }
+                .command_hash();
+                self.conn
+                    .query_row(
+                        "SELECT id FROM commands
+                         WHERE command_hash = ?1 AND session_id IS ?2 AND exit_code IS NULL
+                         ORDER BY id DESC LIMIT 1",
+                        params![command_hash, result.session_id],
+                        |row| row.get::<_, i64>(0),
+                    )
+                    .optional()?
+            }
+        };
+
+        if let Some(id) = id {
+            self.conn.execute(
+                "UPDATE commands SET exit_code = ?1, stderr = ?2 WHERE id = ?3",
+                params![result.exit_code, result.stderr, id],
+            )?;
+        }
+        Ok(id)
+    }
+
     /// Run VACUUM to reclaim space after deletions.
     ///
     /// # Errors
@@ -1076,7 +1610,13 @@ impl HistoryDb {
                 parent_command_id INTEGER REFERENCES commands(id),
                 hostname TEXT,
                 allowlist_layer TEXT,
-                bypass_code TEXT
+                bypass_code TEXT,
+                transcript_path TEXT,
+                stderr TEXT,
+                tool_use_id TEXT,
+                config_fingerprint TEXT,
+                review TEXT CHECK (review IN ('approved', 'ignored', 'true_positive')),
+                reviewed_at TEXT
             )",
             [],
         )?;
@@ -1106,6 +1646,10 @@ impl HistoryDb {
             -- Session grouping
             CREATE INDEX IF NOT EXISTS idx_commands_session_id ON commands(session_id);
 
+            -- PostToolUse lookup (only indexed for non-NULL values)
+            CREATE INDEX IF NOT EXISTS idx_commands_tool_use_id ON commands(tool_use_id)
+                WHERE tool_use_id IS NOT NULL;
+
             -- Command hash for deduplication analysis
             CREATE INDEX IF NOT EXISTS idx_commands_command_hash ON commands(command_hash);
 
@@ -1200,6 +1744,19 @@ impl HistoryDb {
             ",
         )?;
 
+        // Create session_risk table for running per-session risk scores (v8 feature)
+        self.conn.execute(
+            r"CREATE TABLE IF NOT EXISTS session_risk (
+                agent_type TEXT NOT NULL,
+                scope TEXT NOT NULL,
+                score INTEGER NOT NULL DEFAULT 0,
+                events INTEGER NOT NULL DEFAULT 0,
+                updated_at TEXT NOT NULL,
+                PRIMARY KEY (agent_type, scope)
+            )",
+            [],
+        )?;
+
         // Record schema version
         self.conn.execute(
             "INSERT INTO schema_version (version, description, last_prune_at) VALUES (?1, ?2, NULL)",
@@ -1224,6 +1781,21 @@ impl HistoryDb {
         if from_version < 5 {
             self.migrate_v4_to_v5()?;
         }
+        if from_version < 6 {
+            self.migrate_v5_to_v6()?;
+        }
+        if from_version < 7 {
+            self.migrate_v6_to_v7()?;
+        }
+        if from_version < 8 {
+            self.migrate_v7_to_v8()?;
+        }
+        if from_version < 9 {
+            self.migrate_v8_to_v9()?;
+        }
+        if from_version < 10 {
+            self.migrate_v9_to_v10()?;
+        }
 
         // Ensure we're at the expected version
         let current = self.get_schema_version()?;
@@ -1370,19 +1942,148 @@ impl HistoryDb {
             CREATE INDEX IF NOT EXISTS idx_suggestion_audit_action
                 ON suggestion_audit(action);
 
-            -- Session grouping
-            CREATE INDEX IF NOT EXISTS idx_suggestion_audit_session_id
-                ON suggestion_audit(session_id);
-            ",
-        )?;
+            -- Session grouping
+            CREATE INDEX IF NOT EXISTS idx_suggestion_audit_session_id
+                ON suggestion_audit(session_id);
+            ",
+        )?;
+
+        // Record migration
+        self.conn.execute(
+            "INSERT INTO schema_version (version, description) VALUES (?1, ?2)",
+            params![
+                5_u32,
+                "Add suggestion_audit table for tracking suggestion actions"
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    fn migrate_v5_to_v6(&self) -> Result<(), HistoryError> {
+        // Add transcript_path column for agent session transcripts
+        let columns: Vec<String> = self
+            .conn
+            .prepare("PRAGMA table_info(commands)")?
+            .query_map([], |row| row.get::<_, String>(1))?
+            .collect::<Result<_, _>>()?;
+
+        if !columns.iter().any(|col| col == "transcript_path") {
+            self.conn
+                .execute("ALTER TABLE commands ADD COLUMN transcript_path TEXT", [])?;
+        }
+
+        // Record migration
+        self.conn.execute(
+            "INSERT INTO schema_version (version, description) VALUES (?1, ?2)",
+            params![6_u32, "Add transcript_path column for agent sessions"],
+        )?;
+
+        Ok(())
+    }
+
+    fn migrate_v6_to_v7(&self) -> Result<(), HistoryError> {
+        // Add stderr and tool_use_id columns for PostToolUse results
+        let columns: Vec<String> = self
+            .conn
+            .prepare("PRAGMA table_info(commands)")?
+            .query_map([], |row| row.get::<_, String>(1))?
+            .collect::<Result<_, _>>()?;
+
+        if !columns.iter().any(|col| col == "stderr") {
+            self.conn
+                .execute("ALTER TABLE commands ADD COLUMN stderr TEXT", [])?;
+        }
+        if !columns.iter().any(|col| col == "tool_use_id") {
+            self.conn
+                .execute("ALTER TABLE commands ADD COLUMN tool_use_id TEXT", [])?;
+        }
+        self.conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_commands_tool_use_id ON commands(tool_use_id)
+                WHERE tool_use_id IS NOT NULL",
+            [],
+        )?;
+
+        // Record migration
+        self.conn.execute(
+            "INSERT INTO schema_version (version, description) VALUES (?1, ?2)",
+            params![7_u32, "Add stderr and tool_use_id columns for PostToolUse"],
+        )?;
+
+        Ok(())
+    }
+
+    fn migrate_v7_to_v8(&self) -> Result<(), HistoryError> {
+        // Add session_risk table for running per-session risk scores
+        self.conn.execute(
+            r"CREATE TABLE IF NOT EXISTS session_risk (
+                agent_type TEXT NOT NULL,
+                scope TEXT NOT NULL,
+                score INTEGER NOT NULL DEFAULT 0,
+                events INTEGER NOT NULL DEFAULT 0,
+                updated_at TEXT NOT NULL,
+                PRIMARY KEY (agent_type, scope)
+            )",
+            [],
+        )?;
+
+        // Record migration
+        self.conn.execute(
+            "INSERT INTO schema_version (version, description) VALUES (?1, ?2)",
+            params![8_u32, "Add session_risk table for risk scoring"],
+        )?;
+
+        Ok(())
+    }
+
+    fn migrate_v8_to_v9(&self) -> Result<(), HistoryError> {
+        // Add review columns for `dcg review` verdicts on denied commands
+        let columns: Vec<String> = self
+            .conn
+            .prepare("PRAGMA table_info(commands)")?
+            .query_map([], |row| row.get::<_, String>(1))?
+            .collect::<Result<_, _>>()?;
+
+        if !columns.iter().any(|col| col == "review") {
+            self.conn.execute(
+                "ALTER TABLE commands ADD COLUMN review TEXT
+                    CHECK (review IN ('approved', 'ignored', 'true_positive'))",
+                [],
+            )?;
+        }
+        if !columns.iter().any(|col| col == "reviewed_at") {
+            self.conn
+                .execute("ALTER TABLE commands ADD COLUMN reviewed_at TEXT", [])?;
+        }
+
+        // Record migration
+        self.conn.execute(
+            "INSERT INTO schema_version (version, description) VALUES (?1, ?2)",
+            params![9_u32, "Add review columns for deny-queue review"],
+        )?;
+
+        Ok(())
+    }
+
+    fn migrate_v9_to_v10(&self) -> Result<(), HistoryError> {
+        // Add config_fingerprint column so old decisions can be matched to their config
+        let columns: Vec<String> = self
+            .conn
+            .prepare("PRAGMA table_info(commands)")?
+            .query_map([], |row| row.get::<_, String>(1))?
+            .collect::<Result<_, _>>()?;
+
+        if !columns.iter().any(|col| col == "config_fingerprint") {
+            self.conn.execute(
+                "ALTER TABLE commands ADD COLUMN config_fingerprint TEXT",
+                [],
+            )?;
+        }
 
         // Record migration
         self.conn.execute(
             "INSERT INTO schema_version (version, description) VALUES (?1, ?2)",
-            params![
-                5_u32,
-                "Add suggestion_audit table for tracking suggestion actions"
-            ],
+            params![10_u32, "Add config_fingerprint column"],
         )?;
 
         Ok(())
@@ -1416,9 +2117,11 @@ impl HistoryDb {
                     timestamp, agent_type, working_dir, command, command_hash,
                     outcome, pack_id, pattern_name, eval_duration_us,
                     session_id, exit_code, parent_command_id, hostname,
-                    allowlist_layer, bypass_code, rule_id
+                    allowlist_layer, bypass_code, rule_id, transcript_path, stderr,
+                    tool_use_id, config_fingerprint
                 ) VALUES (
-                    ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16
+                    ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17,
+                    ?18, ?19, ?20
                 )",
                 params![
                     timestamp,
@@ -1437,6 +2140,10 @@ impl HistoryDb {
                     entry.allowlist_layer,
                     entry.bypass_code,
                     entry.get_rule_id(),
+                    entry.transcript_path,
+                    entry.stderr,
+                    entry.tool_use_id,
+                    entry.config_fingerprint,
                 ],
             )?;
         }
@@ -1481,13 +2188,17 @@ impl HistoryDb {
 
     /// Check if automatic pruning should run based on the last prune timestamp.
     ///
-    /// Returns true if no prune has been recorded or if the last prune was
-    /// more than 24 hours ago.
+    /// Returns true if no prune has been recorded or if the last prune was at
+    /// least `min_interval` ago. The timestamp lives in the database so the
+    /// interval holds across short-lived hook processes.
     ///
     /// # Errors
     ///
     /// Returns an error if the query fails.
-    pub fn should_auto_prune(&self) -> Result<bool, HistoryError> {
+    pub fn should_auto_prune(
+        &self,
+        min_interval: std::time::Duration,
+    ) -> Result<bool, HistoryError> {
         let result: Option<String> = self
             .conn
             .query_row(
@@ -1501,9 +2212,10 @@ impl HistoryDb {
             chrono::DateTime::parse_from_rfc3339(&timestamp_str).map_or(
                 Ok(true), // Invalid timestamp, assume prune needed
                 |last_prune| {
-                    let hours_since_prune =
-                        (Utc::now() - last_prune.with_timezone(&Utc)).num_hours();
-                    Ok(hours_since_prune >= 24)
+                    let secs_since_prune =
+                        (Utc::now() - last_prune.with_timezone(&Utc)).num_seconds();
+                    let min_secs = i64::try_from(min_interval.as_secs()).unwrap_or(i64::MAX);
+                    Ok(secs_since_prune >= min_secs)
                 },
             )
         })
@@ -1523,6 +2235,99 @@ impl HistoryDb {
         Ok(())
     }
 
+    /// Bytes occupied by live pages (`page_count - freelist_count`).
+    ///
+    /// Unlike [`Self::file_size`] this drops as soon as rows are deleted, so
+    /// it can be used to enforce a size limit without running VACUUM.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a PRAGMA query fails.
+    pub fn used_bytes(&self) -> Result<u64, HistoryError> {
+        let page_size: i64 = self
+            .conn
+            .query_row("PRAGMA page_size", [], |row| row.get(0))?;
+        let page_count: i64 = self
+            .conn
+            .query_row("PRAGMA page_count", [], |row| row.get(0))?;
+        let freelist_count: i64 = self
+            .conn
+            .query_row("PRAGMA freelist_count", [], |row| row.get(0))?;
+        let live_pages = page_count.saturating_sub(freelist_count).max(0);
+        Ok(u64::try_from(live_pages.saturating_mul(page_size)).unwrap_or(0))
+    }
+
+    /// Delete the `count` oldest command entries. Returns the number deleted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the delete fails.
+    pub fn delete_oldest(&self, count: u64) -> Result<u64, HistoryError> {
+        if count == 0 {
+            return Ok(0);
+        }
+        let limit = i64::try_from(count).unwrap_or(i64::MAX);
+        let deleted = self.conn.execute(
+            "DELETE FROM commands WHERE id IN \
+             (SELECT id FROM commands ORDER BY timestamp ASC, id ASC LIMIT ?1)",
+            [limit],
+        )?;
+        Ok(u64::try_from(deleted).unwrap_or(u64::MAX))
+    }
+
+    /// Enforce `policy` by deleting old entries: first by age, then by row
+    /// count, then oldest-first until live pages fit the size limit.
+    ///
+    /// Freed pages are reused by later inserts; the file itself only shrinks
+    /// after [`Self::vacuum`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any query fails.
+    pub fn compact(&self, policy: &RetentionPolicy) -> Result<CompactionReport, HistoryError> {
+        let mut report = CompactionReport::default();
+
+        if policy.max_age_days > 0 {
+            report.pruned_by_age = self.prune_older_than_days(policy.max_age_days, false)?;
+        }
+
+        if policy.max_rows > 0 {
+            let count = self.count_commands()?;
+            if count > policy.max_rows {
+                report.pruned_by_rows = self.delete_oldest(count - policy.max_rows)?;
+            }
+        }
+
+        if policy.max_db_size_bytes > 0 {
+            let mut used = self.used_bytes()?;
+            while used > policy.max_db_size_bytes {
+                let count = self.count_commands()?;
+                if count == 0 {
+                    break;
+                }
+                // Delete the share of rows proportional to the overshoot, plus
+                // a 10% margin so the next insert doesn't immediately re-trigger.
+                let overshoot = used - policy.max_db_size_bytes;
+                let share = u128::from(count) * u128::from(overshoot) / u128::from(used);
+                let batch = (u64::try_from(share).unwrap_or(count) + count / 10).clamp(1, count);
+                report.pruned_by_size += self.delete_oldest(batch)?;
+                // FTS deletes are tombstones until the index is merged.
+                self.conn.execute(
+                    "INSERT INTO commands_fts(commands_fts) VALUES('optimize')",
+                    [],
+                )?;
+                let after = self.used_bytes()?;
+                if after >= used {
+                    // No progress: what remains is not row data.
+                    break;
+                }
+                used = after;
+            }
+        }
+
+        Ok(report)
+    }
+
     // ========================================================================
     // Statistics Cache
     // ========================================================================
@@ -1870,7 +2675,8 @@ impl HistoryDb {
         let mut sql = String::from(
             "SELECT timestamp, agent_type, working_dir, command, outcome,
                     pack_id, pattern_name, rule_id, eval_duration_us, session_id,
-                    exit_code, parent_command_id, hostname, allowlist_layer, bypass_code
+                    exit_code, parent_command_id, hostname, allowlist_layer, bypass_code,
+                    transcript_path, stderr, tool_use_id, config_fingerprint
              FROM commands WHERE 1=1",
         );
         let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
@@ -1890,6 +2696,46 @@ impl HistoryDb {
             params.push(Box::new(format_timestamp(*until)));
         }
 
+        if let Some(pack) = &options.pack_id {
+            // Exact pack ID or a whole category ("core" matches "core.git").
+            sql.push_str(" AND (pack_id = ? OR pack_id LIKE ? ESCAPE '\\')");
+            params.push(Box::new(pack.clone()));
+            params.push(Box::new(format!("{}.%", like_escape(pack))));
+        }
+
+        if let Some(rule_id) = &options.rule_id {
+            sql.push_str(" AND rule_id = ?");
+            params.push(Box::new(rule_id.clone()));
+        }
+
+        if let Some(agent) = &options.agent_type {
+            sql.push_str(" AND agent_type = ?");
+            params.push(Box::new(agent.clone()));
+        }
+
+        if let Some(dir) = &options.working_dir {
+            // The directory itself or anything below it.
+            let dir = dir.trim_end_matches('/');
+            sql.push_str(" AND (working_dir = ? OR working_dir LIKE ? ESCAPE '\\')");
+            params.push(Box::new(dir.to_string()));
+            params.push(Box::new(format!("{}/%", like_escape(dir))));
+        }
+
+        if let Some(session_id) = &options.session_id {
+            sql.push_str(" AND session_id = ?");
+            params.push(Box::new(session_id.clone()));
+        }
+
+        if let Some(hostname) = &options.hostname {
+            sql.push_str(" AND hostname = ?");
+            params.push(Box::new(hostname.clone()));
+        }
+
+        if let Some(needle) = &options.command_contains {
+            sql.push_str(" AND command LIKE ? ESCAPE '\\'");
+            params.push(Box::new(format!("%{}%", like_escape(needle))));
+        }
+
         sql.push_str(" ORDER BY timestamp DESC");
 
         if let Some(limit) = options.limit {
@@ -1899,34 +2745,86 @@ impl HistoryDb {
 
         let param_refs: Vec<&dyn rusqlite::ToSql> = params.iter().map(|p| p.as_ref()).collect();
         let mut stmt = self.conn.prepare(&sql)?;
-        let rows = stmt.query_map(param_refs.as_slice(), |row| {
-            let timestamp_str: String = row.get(0)?;
-            let timestamp = DateTime::parse_from_rfc3339(&timestamp_str)
-                .map_or_else(|_| Utc::now(), |dt| dt.with_timezone(&Utc));
+        let rows = stmt.query_map(param_refs.as_slice(), command_entry_from_row)?;
 
-            let outcome_str: String = row.get(4)?;
-            let outcome = Outcome::parse(&outcome_str).unwrap_or(Outcome::Allow);
+        let mut entries = Vec::new();
+        for row in rows {
+            entries.push(row?);
+        }
+        Ok(entries)
+    }
 
-            let eval_duration_us: i64 = row.get(8)?;
+    /// Full-text search over recorded commands via `commands_fts`.
+    ///
+    /// Each whitespace-separated term in `query` is matched as an FTS5 phrase
+    /// (all terms must match). An empty query skips the full-text match and
+    /// returns the most recent commands that satisfy the filters.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    #[allow(clippy::redundant_closure_for_method_calls)]
+    pub fn search_commands(
+        &self,
+        query: &str,
+        options: &SearchOptions,
+    ) -> Result<Vec<CommandEntry>, HistoryError> {
+        let mut sql = String::from(
+            "SELECT c.timestamp, c.agent_type, c.working_dir, c.command, c.outcome,
+                    c.pack_id, c.pattern_name, c.rule_id, c.eval_duration_us, c.session_id,
+                    c.exit_code, c.parent_command_id, c.hostname, c.allowlist_layer, c.bypass_code,
+                    c.transcript_path, c.stderr, c.tool_use_id, c.config_fingerprint
+             FROM commands c",
+        );
+        let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
 
-            Ok(CommandEntry {
-                timestamp,
-                agent_type: row.get(1)?,
-                working_dir: row.get(2)?,
-                command: row.get(3)?,
-                outcome,
-                pack_id: row.get(5)?,
-                pattern_name: row.get(6)?,
-                rule_id: row.get(7)?,
-                eval_duration_us: u64::try_from(eval_duration_us).unwrap_or(0),
-                session_id: row.get(9)?,
-                exit_code: row.get(10)?,
-                parent_command_id: row.get(11)?,
-                hostname: row.get(12)?,
-                allowlist_layer: row.get(13)?,
-                bypass_code: row.get(14)?,
-            })
-        })?;
+        if let Some(fts_query) = fts_match_query(query) {
+            sql.push_str(" JOIN commands_fts f ON f.rowid = c.id WHERE commands_fts MATCH ?");
+            params.push(Box::new(fts_query));
+        } else {
+            sql.push_str(" WHERE 1=1");
+        }
+
+        if let Some(outcome) = &options.outcome_filter {
+            sql.push_str(" AND c.outcome = ?");
+            params.push(Box::new(outcome.as_str().to_string()));
+        }
+
+        if let Some(pack) = &options.pack_id {
+            // Exact pack ID or a whole category ("core" matches "core.git").
+            sql.push_str(" AND (c.pack_id = ? OR c.pack_id LIKE ? ESCAPE '\\')");
+            params.push(Box::new(pack.clone()));
+            params.push(Box::new(format!("{}.%", like_escape(pack))));
+        }
+
+        if let Some(since) = &options.since {
+            sql.push_str(" AND c.timestamp >= ?");
+            params.push(Box::new(format_timestamp(*since)));
+        }
+
+        if let Some(dir) = &options.working_dir {
+            // The directory itself or anything below it.
+            let dir = dir.trim_end_matches('/');
+            sql.push_str(" AND (c.working_dir = ? OR c.working_dir LIKE ? ESCAPE '\\')");
+            params.push(Box::new(dir.to_string()));
+            params.push(Box::new(format!("{}/%", like_escape(dir))));
+        }
+
+        if let Some(session_id) = &options.session_id {
+            sql.push_str(" AND c.session_id = ?");
+            params.push(Box::new(session_id.clone()));
+        }
+
+        sql.push_str(" ORDER BY c.timestamp DESC");
+
+        if let Some(limit) = options.limit {
+            sql.push_str(" LIMIT ?");
+            params.push(Box::new(i64::try_from(limit).unwrap_or(i64::MAX)));
+        }
+
+        let param_refs: Vec<&dyn rusqlite::ToSql> = params.iter().map(|p| p.as_ref()).collect();
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt.query_map(param_refs.as_slice(), command_entry_from_row)?;
 
         let mut entries = Vec::new();
         for row in rows {
@@ -1935,6 +2833,145 @@ impl HistoryDb {
         Ok(entries)
     }
 
+    /// List denied commands that have not been reviewed yet, newest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub fn unreviewed_denies(
+        &self,
+        since: Option<DateTime<Utc>>,
+        limit: usize,
+    ) -> Result<Vec<DenyReviewItem>, HistoryError> {
+        let since_ts = since.map_or_else(String::new, format_timestamp);
+        let mut stmt = self.conn.prepare(
+            "SELECT c.timestamp, c.agent_type, c.working_dir, c.command, c.outcome,
+                    c.pack_id, c.pattern_name, c.rule_id, c.eval_duration_us, c.session_id,
+                    c.exit_code, c.parent_command_id, c.hostname, c.allowlist_layer, c.bypass_code,
+                    c.transcript_path, c.stderr, c.tool_use_id, c.config_fingerprint, c.id
+             FROM commands c
+             WHERE c.outcome = 'deny' AND c.review IS NULL AND c.timestamp >= ?1
+             ORDER BY c.timestamp DESC
+             LIMIT ?2",
+        )?;
+        let rows = stmt.query_map(
+            params![since_ts, i64::try_from(limit).unwrap_or(i64::MAX)],
+            |row| {
+                Ok(DenyReviewItem {
+                    id: row.get(19)?,
+                    entry: command_entry_from_row(row)?,
+                })
+            },
+        )?;
+
+        let mut items = Vec::new();
+        for row in rows {
+            items.push(row?);
+        }
+        Ok(items)
+    }
+
+    /// Record a review verdict for a denied command.
+    ///
+    /// Returns `false` when no denied command has that id.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the update fails.
+    pub fn record_review(
+        &self,
+        id: i64,
+        verdict: ReviewVerdict,
+        now: DateTime<Utc>,
+    ) -> Result<bool, HistoryError> {
+        let updated = self.conn.execute(
+            "UPDATE commands SET review = ?1, reviewed_at = ?2 WHERE id = ?3 AND outcome = 'deny'",
+            params![verdict.as_str(), format_timestamp(now), id],
+        )?;
+        Ok(updated > 0)
+    }
+
+    /// Summarize commands per session, most recently active first.
+    ///
+    /// Entries without a session id are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub fn session_stats(
+        &self,
+        since: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<SessionStat>, HistoryError> {
+        let mut stmt = self.conn.prepare(&format!(
+            "{SESSION_STAT_SELECT}
+             WHERE session_id IS NOT NULL AND timestamp >= ?1
+             GROUP BY session_id
+             ORDER BY MAX(timestamp) DESC
+             LIMIT ?2"
+        ))?;
+        let rows = stmt.query_map(
+            params![
+                format_timestamp(since),
+                i64::try_from(limit).unwrap_or(i64::MAX)
+            ],
+            session_stat_from_row,
+        )?;
+
+        let mut sessions = Vec::new();
+        for row in rows {
+            sessions.push(row?);
+        }
+        Ok(sessions)
+    }
+
+    /// Summarize a single session (Stop hook summary).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub fn session_stat(&self, session_id: &str) -> Result<Option<SessionStat>, HistoryError> {
+        Ok(self
+            .conn
+            .query_row(
+                &format!("{SESSION_STAT_SELECT} WHERE session_id = ?1 GROUP BY session_id"),
+                params![session_id],
+                session_stat_from_row,
+            )
+            .optional()?)
+    }
+
+    /// Summarize activity between `since` and `now` for `dcg top`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a query fails.
+    pub fn live_snapshot(
+        &self,
+        since: DateTime<Utc>,
+        now: DateTime<Utc>,
+        session_limit: usize,
+        recent_limit: usize,
+    ) -> Result<LiveSnapshot, HistoryError> {
+        // The window end is exclusive; include entries logged this millisecond.
+        let window = self.compute_stats_range(since, now + Duration::milliseconds(1))?;
+        Ok(LiveSnapshot {
+            since,
+            total: window.total_commands,
+            outcomes: window.outcomes,
+            deny_rate: window.block_rate,
+            latency: window.performance,
+            active_sessions: self.session_stats(since, session_limit)?,
+            recent: self.search_commands(
+                "",
+                &SearchOptions {
+                    limit: Some(recent_limit),
+                    ..Default::default()
+                },
+            )?,
+        })
+    }
+
     /// Export commands to JSON format.
     ///
     /// Returns a JSON object with metadata and commands array.
@@ -1957,6 +2994,13 @@ impl HistoryDb {
                 outcome: options.outcome_filter.map(|o| o.as_str().to_string()),
                 since: options.since,
                 until: options.until,
+                pack: options.pack_id.clone(),
+                rule: options.rule_id.clone(),
+                agent: options.agent_type.clone(),
+                cwd: options.working_dir.clone(),
+                session: options.session_id.clone(),
+                host: options.hostname.clone(),
+                command: options.command_contains.clone(),
             },
             commands: entries,
         };
@@ -2004,8 +3048,138 @@ impl HistoryDb {
         options: &ExportOptions,
     ) -> Result<usize, HistoryError> {
         let entries = self.query_commands_for_export(options)?;
-        let count = entries.len();
+        Self::write_csv(writer, &entries)?;
+        Ok(entries.len())
+    }
+
+    /// Export commands to Apache Parquet for DuckDB/Spark/pandas.
+    ///
+    /// Every `CommandEntry` field becomes a column; the timestamp is stored as
+    /// a UTC microsecond timestamp. Rows are written in row groups of
+    /// [`PARQUET_BATCH_ROWS`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query, encoding, or write fails.
+    #[cfg(feature = "parquet")]
+    pub fn export_parquet<W: std::io::Write + Send>(
+        &self,
+        writer: W,
+        options: &ExportOptions,
+    ) -> Result<usize, HistoryError> {
+        use arrow_array::{
+            ArrayRef, Int32Array, Int64Array, RecordBatch, StringArray, TimestampMicrosecondArray,
+            UInt64Array,
+        };
+        use arrow_schema::{DataType, Field, Schema, TimeUnit};
+        use parquet::arrow::ArrowWriter;
+        use parquet::basic::Compression;
+        use parquet::file::properties::WriterProperties;
+        use std::sync::Arc;
+
+        fn opt_str<'a>(
+            entries: &'a [CommandEntry],
+            field: impl Fn(&'a CommandEntry) -> Option<&'a str>,
+        ) -> ArrayRef {
+            Arc::new(entries.iter().map(field).collect::<StringArray>())
+        }
+
+        let entries = self.query_commands_for_export(options)?;
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new(
+                "timestamp",
+                DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+                false,
+            ),
+            Field::new("agent_type", DataType::Utf8, false),
+            Field::new("working_dir", DataType::Utf8, false),
+            Field::new("command", DataType::Utf8, false),
+            Field::new("outcome", DataType::Utf8, false),
+            Field::new("pack_id", DataType::Utf8, true),
+            Field::new("pattern_name", DataType::Utf8, true),
+            Field::new("rule_id", DataType::Utf8, true),
+            Field::new("eval_duration_us", DataType::UInt64, false),
+            Field::new("session_id", DataType::Utf8, true),
+            Field::new("transcript_path", DataType::Utf8, true),
+            Field::new("exit_code", DataType::Int32, true),
+            Field::new("stderr", DataType::Utf8, true),
+            Field::new("tool_use_id", DataType::Utf8, true),
+            Field::new("parent_command_id", DataType::Int64, true),
+            Field::new("hostname", DataType::Utf8, true),
+            Field::new("allowlist_layer", DataType::Utf8, true),
+            Field::new("bypass_code", DataType::Utf8, true),
+            Field::new("config_fingerprint", DataType::Utf8, true),
+        ]));
+
+        let to_io =
+            |e: &dyn std::fmt::Display| HistoryError::Io(std::io::Error::other(e.to_string()));
+        let props = WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .build();
+        let mut parquet = ArrowWriter::try_new(writer, Arc::clone(&schema), Some(props))
+            .map_err(|e| to_io(&e))?;
+
+        for chunk in entries.chunks(PARQUET_BATCH_ROWS) {
+            let columns: Vec<ArrayRef> = vec![
+                Arc::new(
+                    TimestampMicrosecondArray::from_iter_values(
+                        chunk.iter().map(|e| e.timestamp.timestamp_micros()),
+                    )
+                    .with_timezone("UTC"),
+                ),
+                Arc::new(StringArray::from_iter_values(
+                    chunk.iter().map(|e| e.agent_type.as_str()),
+                )),
+                Arc::new(StringArray::from_iter_values(
+                    chunk.iter().map(|e| e.working_dir.as_str()),
+                )),
+                Arc::new(StringArray::from_iter_values(
+                    chunk.iter().map(|e| e.command.as_str()),
+                )),
+                Arc::new(StringArray::from_iter_values(
+                    chunk.iter().map(|e| e.outcome.as_str()),
+                )),
+                opt_str(chunk, |e| e.pack_id.as_deref()),
+                opt_str(chunk, |e| e.pattern_name.as_deref()),
+                opt_str(chunk, |e| e.rule_id.as_deref()),
+                Arc::new(UInt64Array::from_iter_values(
+                    chunk.iter().map(|e| e.eval_duration_us),
+                )),
+                opt_str(chunk, |e| e.session_id.as_deref()),
+                opt_str(chunk, |e| e.transcript_path.as_deref()),
+                Arc::new(chunk.iter().map(|e| e.exit_code).collect::<Int32Array>()),
+                opt_str(chunk, |e| e.stderr.as_deref()),
+                opt_str(chunk, |e| e.tool_use_id.as_deref()),
+                Arc::new(
+                    chunk
+                        .iter()
+                        .map(|e| e.parent_command_id)
+                        .collect::<Int64Array>(),
+                ),
+                opt_str(chunk, |e| e.hostname.as_deref()),
+                opt_str(chunk, |e| e.allowlist_layer.as_deref()),
+                opt_str(chunk, |e| e.bypass_code.as_deref()),
+                opt_str(chunk, |e| e.config_fingerprint.as_deref()),
+            ];
+            let batch =
+                RecordBatch::try_new(Arc::clone(&schema), columns).map_err(|e| to_io(&e))?;
+            parquet.write(&batch).map_err(|e| to_io(&e))?;
+        }
 
+        parquet.close().map_err(|e| to_io(&e))?;
+        Ok(entries.len())
+    }
+
+    /// Write command entries as CSV (header row followed by data rows).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the write fails.
+    pub fn write_csv<W: std::io::Write>(
+        writer: &mut W,
+        entries: &[CommandEntry],
+    ) -> Result<(), HistoryError> {
         // Write header
         writeln!(
             writer,
@@ -2013,7 +3187,7 @@ impl HistoryDb {
         )?;
 
         // Write data rows
-        for entry in &entries {
+        for entry in entries {
             writeln!(
                 writer,
                 "{},{},{},{},{},{},{},{}",
@@ -2028,7 +3202,7 @@ impl HistoryDb {
             )?;
         }
 
-        Ok(count)
+        Ok(())
     }
 
     // ========================================================================
@@ -2155,6 +3329,36 @@ impl HistoryDb {
             bypass_counts.insert((pattern, pack_id), u64::try_from(count).unwrap_or(0));
         }
 
+        // Get `dcg review` verdicts per pattern
+        let mut review_counts: HashMap<(String, Option<String>), (u64, u64)> = HashMap::new();
+        let mut stmt = self.conn.prepare(
+            "SELECT pattern_name, pack_id,
+                    COUNT(CASE WHEN review = 'approved' THEN 1 END),
+                    COUNT(CASE WHEN review = 'true_positive' THEN 1 END)
+             FROM commands
+             WHERE timestamp >= ?1 AND timestamp < ?2
+             AND outcome = 'deny' AND pattern_name IS NOT NULL AND review IS NOT NULL
+             GROUP BY pattern_name, pack_id",
+        )?;
+        let rows = stmt.query_map(params![since_ts, end_ts], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, i64>(3)?,
+            ))
+        })?;
+        for row in rows {
+            let (pattern, pack_id, approved, confirmed) = row?;
+            review_counts.insert(
+                (pattern, pack_id),
+                (
+                    u64::try_from(approved).unwrap_or(0),
+                    u64::try_from(confirmed).unwrap_or(0),
+                ),
+            );
+        }
+
         // Merge into PatternEffectiveness structs
         let mut all_patterns: HashMap<(String, Option<String>), (u64, u64)> = HashMap::new();
         for (key, count) in deny_counts {
@@ -2166,6 +3370,10 @@ impl HistoryDb {
 
         for ((pattern, pack_id), (denied, bypassed)) in all_patterns {
             let total = denied + bypassed;
+            let (approved, confirmed) = review_counts
+                .get(&(pattern.clone(), pack_id.clone()))
+                .copied()
+                .unwrap_or((0, 0));
             #[allow(clippy::cast_precision_loss)]
             let bypass_rate = if total > 0 {
                 (bypassed as f64 / total as f64) * 100.0
@@ -2180,6 +3388,8 @@ impl HistoryDb {
                 denied_count: denied,
                 bypassed_count: bypassed,
                 bypass_rate,
+                approved_count: approved,
+                confirmed_count: confirmed,
             });
         }
 
@@ -2203,9 +3413,10 @@ impl HistoryDb {
         let mut aggressive = Vec::new();
 
         for p in patterns {
-            // High value: high volume + low bypass rate
+            // High value: high volume + low bypass rate, not mostly approved on review
             if p.total_triggers >= MIN_TRIGGERS_FOR_HIGH_VALUE
                 && p.bypass_rate <= LOW_BYPASS_THRESHOLD
+                && p.approved_count <= p.confirmed_count
             {
                 high_value.push(p.clone());
             }
@@ -2300,6 +3511,8 @@ impl HistoryDb {
                         reason: reason.to_string(),
                         timestamp,
                         working_dir: working_dir.clone(),
+                        pack_id: None,
+                        pattern_name: None,
                     });
                     break; // Only report each command once
                 }
@@ -2337,6 +3550,9 @@ impl HistoryDb {
                 config_change: None,
                 related_pattern: Some(p.pattern.clone()),
                 priority: 8,
+                keyword_matches: None,
+                would_block_count: None,
+                examples: Vec::new(),
             });
         }
 
@@ -2355,6 +3571,9 @@ impl HistoryDb {
                 )),
                 related_pattern: Some(pack.clone()),
                 priority: 3,
+                keyword_matches: None,
+                would_block_count: None,
+                examples: Vec::new(),
             });
         }
 
@@ -2375,6 +3594,9 @@ impl HistoryDb {
                 config_change: None,
                 related_pattern: None,
                 priority: 7,
+                keyword_matches: None,
+                would_block_count: None,
+                examples: Vec::new(),
             });
         }
 
@@ -2392,6 +3614,9 @@ impl HistoryDb {
                 config_change: None,
                 related_pattern: None,
                 priority: 1,
+                keyword_matches: None,
+                would_block_count: None,
+                examples: Vec::new(),
             });
         }
 
@@ -2856,16 +4081,94 @@ fn truncate_string(s: &str, max_len: usize) -> String {
     }
 }
 
-/// Options for export operations.
+/// Options for export operations.
+#[derive(Debug, Clone, Default)]
+pub struct ExportOptions {
+    /// Filter by outcome (allow, deny, warn, bypass).
+    pub outcome_filter: Option<Outcome>,
+    /// Include only commands since this timestamp.
+    pub since: Option<DateTime<Utc>>,
+    /// Include only commands until this timestamp.
+    pub until: Option<DateTime<Utc>>,
+    /// Maximum number of records to export.
+    pub limit: Option<usize>,
+    /// Filter by pack ID (exact) or pack category (e.g. "core").
+    pub pack_id: Option<String>,
+    /// Filter by rule ID (`pack_id:pattern_name`).
+    pub rule_id: Option<String>,
+    /// Filter by agent type.
+    pub agent_type: Option<String>,
+    /// Include only commands run in this directory or below it.
+    pub working_dir: Option<String>,
+    /// Include only commands from this agent session.
+    pub session_id: Option<String>,
+    /// Filter by hostname.
+    pub hostname: Option<String>,
+    /// Include only commands containing this substring.
+    pub command_contains: Option<String>,
+}
+
+impl ExportOptions {
+    /// Keys accepted by [`ExportOptions::apply_filter`].
+    pub const FILTER_KEYS: &'static [&'static str] = &[
+        "outcome", "pack", "rule", "agent", "cwd", "session", "host", "command",
+    ];
+
+    /// Apply a `key=value` filter expression (as passed to `--filter`).
+    ///
+    /// # Errors
+    ///
+    /// Returns a message when the expression is malformed, the key is
+    /// unknown, or an `outcome` value is invalid.
+    pub fn apply_filter(&mut self, expr: &str) -> Result<(), String> {
+        let (key, value) = expr
+            .split_once('=')
+            .map(|(k, v)| (k.trim(), v.trim()))
+            .filter(|(k, v)| !k.is_empty() && !v.is_empty())
+            .ok_or_else(|| format!("Invalid filter '{expr}' (expected KEY=VALUE)"))?;
+        let value = value.to_string();
+        match key {
+            "outcome" => {
+                self.outcome_filter = Some(
+                    Outcome::parse(&value).ok_or_else(|| format!("Invalid outcome: {value}"))?,
+                );
+            }
+            "pack" => self.pack_id = Some(value),
+            "rule" => self.rule_id = Some(value),
+            "agent" => self.agent_type = Some(value),
+            "cwd" => self.working_dir = Some(value),
+            "session" => self.session_id = Some(value),
+            "host" => self.hostname = Some(value),
+            "command" => self.command_contains = Some(value),
+            _ => {
+                return Err(format!(
+                    "Unknown filter key '{key}' (expected one of: {})",
+                    Self::FILTER_KEYS.join(", ")
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Rows per Parquet row group written by `HistoryDb::export_parquet`.
+#[cfg(feature = "parquet")]
+pub const PARQUET_BATCH_ROWS: usize = 8192;
+
+/// Options for full-text history search.
 #[derive(Debug, Clone, Default)]
-pub struct ExportOptions {
+pub struct SearchOptions {
     /// Filter by outcome (allow, deny, warn, bypass).
     pub outcome_filter: Option<Outcome>,
+    /// Filter by pack ID (exact) or pack category (e.g. "core").
+    pub pack_id: Option<String>,
     /// Include only commands since this timestamp.
     pub since: Option<DateTime<Utc>>,
-    /// Include only commands until this timestamp.
-    pub until: Option<DateTime<Utc>>,
-    /// Maximum number of records to export.
+    /// Include only commands run in this directory or below it.
+    pub working_dir: Option<String>,
+    /// Include only commands from this agent session.
+    pub session_id: Option<String>,
+    /// Maximum number of results.
     pub limit: Option<usize>,
 }
 
@@ -2894,6 +4197,27 @@ pub struct ExportFilters {
     /// Until timestamp if any.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub until: Option<DateTime<Utc>>,
+    /// Pack ID or category filter if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pack: Option<String>,
+    /// Rule ID filter if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rule: Option<String>,
+    /// Agent type filter if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+    /// Working directory filter if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+    /// Session ID filter if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session: Option<String>,
+    /// Hostname filter if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+    /// Command substring filter if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
 }
 
 // ============================================================================
@@ -2916,6 +4240,10 @@ pub struct PatternEffectiveness {
     pub bypassed_count: u64,
     /// Bypass rate as a percentage (0.0-100.0).
     pub bypass_rate: f64,
+    /// Denials approved (marked false positive) in `dcg review`.
+    pub approved_count: u64,
+    /// Denials marked as true positives in `dcg review`.
+    pub confirmed_count: u64,
 }
 
 /// A potential coverage gap where dangerous commands were allowed.
@@ -2930,6 +4258,12 @@ pub struct PotentialGap {
     /// Working directory where command was executed.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub working_dir: Option<String>,
+    /// Disabled pack that would have blocked the command, when measured.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pack_id: Option<String>,
+    /// Pattern of that pack that would have matched.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern_name: Option<String>,
 }
 
 /// Type of recommendation for pack configuration.
@@ -2967,6 +4301,15 @@ pub struct PackRecommendation {
     pub related_pattern: Option<String>,
     /// Priority score (higher = more important).
     pub priority: u8,
+    /// Allowed commands that contain the pack's keywords (`EnablePack` only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keyword_matches: Option<u64>,
+    /// Allowed commands the pack would have blocked (`EnablePack` only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub would_block_count: Option<u64>,
+    /// Example commands the recommendation is based on.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub examples: Vec<PotentialGap>,
 }
 
 /// Complete pack effectiveness analysis result.
@@ -3170,6 +4513,93 @@ impl Default for SuggestionAuditEntry {
     }
 }
 
+/// Aggregate columns read by [`session_stat_from_row`].
+const SESSION_STAT_SELECT: &str = "SELECT session_id, MAX(agent_type), MAX(transcript_path),
+        MIN(timestamp), MAX(timestamp), COUNT(*),
+        SUM(CASE WHEN outcome = 'deny' THEN 1 ELSE 0 END),
+        SUM(CASE WHEN outcome = 'warn' THEN 1 ELSE 0 END),
+        SUM(CASE WHEN exit_code IS NOT NULL AND exit_code != 0 THEN 1 ELSE 0 END)
+    FROM commands";
+
+fn session_stat_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<SessionStat> {
+    let parse_ts = |value: String| {
+        DateTime::parse_from_rfc3339(&value)
+            .map_or_else(|_| Utc::now(), |dt| dt.with_timezone(&Utc))
+    };
+    Ok(SessionStat {
+        session_id: row.get(0)?,
+        agent_type: row.get(1)?,
+        transcript_path: row.get(2)?,
+        first_seen: parse_ts(row.get(3)?),
+        last_seen: parse_ts(row.get(4)?),
+        total: u64::try_from(row.get::<_, i64>(5)?).unwrap_or(0),
+        denied: u64::try_from(row.get::<_, i64>(6)?).unwrap_or(0),
+        warned: u64::try_from(row.get::<_, i64>(7)?).unwrap_or(0),
+        failed: u64::try_from(row.get::<_, i64>(8)?).unwrap_or(0),
+    })
+}
+
+/// Map a `commands` row (export column order) to a `CommandEntry`.
+fn command_entry_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<CommandEntry> {
+    let timestamp_str: String = row.get(0)?;
+    let timestamp = DateTime::parse_from_rfc3339(&timestamp_str)
+        .map_or_else(|_| Utc::now(), |dt| dt.with_timezone(&Utc));
+
+    let outcome_str: String = row.get(4)?;
+    let outcome = Outcome::parse(&outcome_str).unwrap_or(Outcome::Allow);
+
+    let eval_duration_us: i64 = row.get(8)?;
+
+    Ok(CommandEntry {
+        timestamp,
+        agent_type: row.get(1)?,
+        working_dir: row.get(2)?,
+        command: row.get(3)?,
+        outcome,
+        pack_id: row.get(5)?,
+        pattern_name: row.get(6)?,
+        rule_id: row.get(7)?,
+        eval_duration_us: u64::try_from(eval_duration_us).unwrap_or(0),
+        session_id: row.get(9)?,
+        exit_code: row.get(10)?,
+        stderr: row.get(16)?,
+        tool_use_id: row.get(17)?,
+        parent_command_id: row.get(11)?,
+        hostname: row.get(12)?,
+        allowlist_layer: row.get(13)?,
+        bypass_code: row.get(14)?,
+        transcript_path: row.get(15)?,
+        config_fingerprint: row.get(18)?,
+    })
+}
+
+/// Build an FTS5 MATCH expression that treats each term as a literal phrase.
+///
+/// Returns `None` for an empty query.
+fn fts_match_query(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+        .collect();
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
+}
+
+/// Escape `%`, `_`, and `\` for a `LIKE ... ESCAPE '\'` pattern.
+fn like_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
 /// Escape a string for CSV output.
 fn csv_escape(s: &str) -> String {
     if s.contains(',') || s.contains('"') || s.contains('\n') || s.contains('\r') {
@@ -3453,6 +4883,7 @@ mod tests {
 
         assert!(tables.contains(&"commands".to_string()));
         assert!(tables.contains(&"schema_version".to_string()));
+        assert!(tables.contains(&"session_risk".to_string()));
     }
 
     #[test]
@@ -3483,6 +4914,9 @@ mod tests {
         assert!(columns.contains(&"exit_code".to_string()));
         assert!(columns.contains(&"parent_command_id".to_string()));
         assert!(columns.contains(&"hostname".to_string()));
+        assert!(columns.contains(&"stderr".to_string()));
+        assert!(columns.contains(&"tool_use_id".to_string()));
+        assert!(columns.contains(&"config_fingerprint".to_string()));
     }
 
     #[test]
@@ -3698,6 +5132,80 @@ mod tests {
         assert!(parsed["total_commands"].is_number());
     }
 
+    #[test]
+    fn test_dashboard_pack_and_pattern_breakdown() {
+        let db = create_test_db_with_outcomes(70, 20, 10);
+
+        let dashboard = db.compute_dashboard(30, 10).unwrap();
+
+        assert_eq!(dashboard.total_commands, 100);
+        assert_eq!(dashboard.packs.len(), 1);
+        assert_eq!(dashboard.packs[0].name, "core.git");
+        assert_eq!(dashboard.packs[0].hits, 30);
+        assert_eq!(dashboard.packs[0].denied, 20);
+        assert_eq!(dashboard.packs[0].warned, 10);
+        // Warn-only patterns are not "blocked".
+        assert_eq!(dashboard.top_blocked.len(), 1);
+        assert_eq!(dashboard.top_blocked[0].name, "core.git:reset-hard");
+        assert_eq!(dashboard.daily.len(), 1);
+        assert_eq!(dashboard.daily[0].denied, 20);
+        assert_eq!(dashboard.median_eval_us, 100);
+    }
+
+    #[test]
+    fn test_dashboard_false_positive_estimate() {
+        let db = HistoryDb::open_in_memory().unwrap();
+        let denied_at = Utc::now() - Duration::hours(30);
+        for command in ["rm -rf build", "git reset --hard", "git clean -fdx"] {
+            let entry = CommandEntry {
+                timestamp: denied_at,
+                agent_type: "claude_code".to_string(),
+                working_dir: "/project/a".to_string(),
+                command: command.to_string(),
+                outcome: Outcome::Deny,
+                pack_id: Some("core.git".to_string()),
+                pattern_name: Some("p".to_string()),
+                ..Default::default()
+            };
+            db.log_command(&entry).unwrap();
+        }
+        // Allowed again shortly after the deny (allow-once): likely false positive.
+        insert_command(
+            &db,
+            "rm -rf build",
+            Outcome::Allow,
+            "/project/a",
+            denied_at + Duration::minutes(5),
+        );
+        // Allowed much later: not attributed to the deny.
+        insert_command(
+            &db,
+            "git reset --hard",
+            Outcome::Allow,
+            "/project/a",
+            denied_at + Duration::hours(26),
+        );
+
+        let dashboard = db.compute_dashboard(30, 10).unwrap();
+
+        assert_eq!(dashboard.false_positives.denied, 3);
+        assert_eq!(dashboard.false_positives.likely_false_positives, 1);
+        assert!((dashboard.false_positives.rate - 1.0 / 3.0).abs() < f64::EPSILON);
+        assert_eq!(dashboard.packs[0].likely_false_positives, 1);
+    }
+
+    #[test]
+    fn test_dashboard_empty_db() {
+        let db = HistoryDb::open_in_memory().unwrap();
+
+        let dashboard = db.compute_dashboard(30, 10).unwrap();
+
+        assert_eq!(dashboard.total_commands, 0);
+        assert!(dashboard.packs.is_empty());
+        assert!(dashboard.daily.is_empty());
+        assert!(dashboard.false_positives.rate.abs() < f64::EPSILON);
+    }
+
     #[test]
     fn test_timestamp_format() {
         let db = HistoryDb::open_in_memory().unwrap();
@@ -3891,6 +5399,50 @@ mod tests {
         assert_eq!(db.count_commands().unwrap(), 10);
     }
 
+    #[test]
+    fn test_count_recent_outcomes() {
+        let db = HistoryDb::open_in_memory().unwrap();
+        let now = Utc::now();
+
+        for minutes_ago in [0, 1, 10] {
+            let mut entry = test_entry();
+            entry.outcome = Outcome::Deny;
+            entry.timestamp = now - Duration::minutes(minutes_ago);
+            db.log_command(&entry).unwrap();
+        }
+        let mut other_dir = test_entry();
+        other_dir.outcome = Outcome::Deny;
+        other_dir.working_dir = "/other/project".to_string();
+        db.log_command(&other_dir).unwrap();
+        db.log_command(&test_entry()).unwrap();
+
+        let since = now - Duration::minutes(2);
+        let count = db
+            .count_recent_outcomes(Outcome::Deny, "claude_code", "/test/project", None, since)
+            .unwrap();
+        assert_eq!(count, 2);
+        let count = db
+            .count_recent_outcomes(Outcome::Allow, "claude_code", "/test/project", None, since)
+            .unwrap();
+        assert_eq!(count, 1);
+
+        let mut in_session = test_entry();
+        in_session.outcome = Outcome::Deny;
+        in_session.working_dir = "/elsewhere".to_string();
+        in_session.session_id = Some("abc".to_string());
+        db.log_command(&in_session).unwrap();
+        let count = db
+            .count_recent_outcomes(
+                Outcome::Deny,
+                "claude_code",
+                "/test/project",
+                Some("abc"),
+                since,
+            )
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
     #[test]
     fn test_prune_older_than_days() {
         let db = HistoryDb::open_in_memory().unwrap();
@@ -3923,6 +5475,77 @@ mod tests {
         assert_eq!(db.count_commands().unwrap(), 1);
     }
 
+    #[test]
+    fn test_compact_enforces_age_and_row_limits() {
+        let db = HistoryDb::open_in_memory().unwrap();
+        let now = Utc::now();
+
+        let mut old_entry = test_entry();
+        old_entry.timestamp = now - Duration::days(30);
+        db.log_command(&old_entry).unwrap();
+        for minutes in (1..=5).rev() {
+            let mut entry = test_entry();
+            entry.timestamp = now - Duration::minutes(minutes);
+            entry.command = format!("echo {minutes}");
+            db.log_command(&entry).unwrap();
+        }
+
+        let report = db
+            .compact(&RetentionPolicy {
+                max_rows: 3,
+                max_age_days: 7,
+                max_db_size_bytes: 0,
+            })
+            .unwrap();
+        assert_eq!(report.pruned_by_age, 1);
+        assert_eq!(report.pruned_by_rows, 2);
+        assert_eq!(report.total(), 3);
+
+        let remaining: Vec<String> = db
+            .connection()
+            .prepare("SELECT command FROM commands ORDER BY timestamp")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(remaining, vec!["echo 3", "echo 2", "echo 1"]);
+    }
+
+    #[test]
+    fn test_compact_enforces_size_limit() {
+        let db = HistoryDb::open_in_memory().unwrap();
+        let now = Utc::now();
+        for i in 0..2000_i64 {
+            let mut entry = test_entry();
+            entry.timestamp = now - Duration::seconds(2000 - i);
+            entry.command = format!("echo {} {i}", "x".repeat(200));
+            db.log_command(&entry).unwrap();
+        }
+
+        let before = db.used_bytes().unwrap();
+        let limit = before / 2;
+        let report = db
+            .compact(&RetentionPolicy {
+                max_db_size_bytes: limit,
+                ..RetentionPolicy::default()
+            })
+            .unwrap();
+        assert!(report.pruned_by_size > 0);
+        assert!(db.count_commands().unwrap() > 0, "newest rows are kept");
+        assert!(db.used_bytes().unwrap() <= limit);
+    }
+
+    #[test]
+    fn test_should_auto_prune_respects_interval() {
+        let db = HistoryDb::open_in_memory().unwrap();
+        let hour = std::time::Duration::from_secs(3600);
+        assert!(db.should_auto_prune(hour).unwrap());
+        db.record_prune_timestamp().unwrap();
+        assert!(!db.should_auto_prune(hour).unwrap());
+        assert!(db.should_auto_prune(std::time::Duration::ZERO).unwrap());
+    }
+
     #[test]
     fn test_file_size_in_memory() {
         let db = HistoryDb::open_in_memory().unwrap();
@@ -3949,6 +5572,10 @@ mod tests {
             hostname: Some("dev-machine".to_string()),
             allowlist_layer: None,
             bypass_code: Some("ab12".to_string()),
+            transcript_path: Some("/tmp/transcript.jsonl".to_string()),
+            stderr: None,
+            tool_use_id: None,
+            config_fingerprint: Some("0f3a9c".to_string()),
         };
 
         let id = db.log_command(&entry).unwrap();
@@ -3978,6 +5605,11 @@ mod tests {
         assert_eq!(session_id, Some("session-123".to_string()));
         assert_eq!(hostname, Some("dev-machine".to_string()));
         assert_eq!(bypass_code, Some("ab12".to_string()));
+
+        let exported = db
+            .query_commands_for_export(&ExportOptions::default())
+            .unwrap();
+        assert_eq!(exported[0].config_fingerprint.as_deref(), Some("0f3a9c"));
     }
 
     #[test]
@@ -4118,6 +5750,238 @@ mod tests {
         assert_eq!(content.lines().count(), 50);
     }
 
+    #[test]
+    fn test_search_commands_full_text_and_filters() {
+        let db = HistoryDb::open_in_memory().unwrap();
+        let now = Utc::now();
+        let entries = [
+            (
+                "git reset --hard HEAD",
+                Outcome::Deny,
+                Some("core.git"),
+                "/work/app",
+                1,
+            ),
+            ("git status", Outcome::Allow, None, "/work/app/sub", 2),
+            (
+                "rm -rf /work/app/build",
+                Outcome::Deny,
+                Some("core.filesystem"),
+                "/work/app",
+                3,
+            ),
+            (
+                "git reset --hard origin/main",
+                Outcome::Deny,
+                Some("core.git"),
+                "/other",
+                20,
+            ),
+        ];
+        for (command, outcome, pack_id, dir, days_ago) in entries {
+            db.log_command(&CommandEntry {
+                timestamp: now - Duration::days(days_ago),
+                command: command.to_string(),
+                outcome,
+                pack_id: pack_id.map(str::to_string),
+                working_dir: dir.to_string(),
+                ..test_entry()
+            })
+            .unwrap();
+        }
+
+        let hits = db
+            .search_commands("reset hard", &SearchOptions::default())
+            .unwrap();
+        assert_eq!(hits.len(), 2);
+        // Newest first.
+        assert_eq!(hits[0].command, "git reset --hard HEAD");
+
+        let hits = db
+            .search_commands(
+                "reset",
+                &SearchOptions {
+                    since: Some(now - Duration::days(7)),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(hits.len(), 1);
+
+        let hits = db
+            .search_commands(
+                "",
+                &SearchOptions {
+                    outcome_filter: Some(Outcome::Deny),
+                    pack_id: Some("core".to_string()),
+                    working_dir: Some("/work/app/".to_string()),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        let commands: Vec<&str> = hits.iter().map(|e| e.command.as_str()).collect();
+        assert_eq!(
+            commands,
+            vec!["git reset --hard HEAD", "rm -rf /work/app/build"]
+        );
+
+        let hits = db
+            .search_commands(
+                "git",
+                &SearchOptions {
+                    working_dir: Some("/work/app".to_string()),
+                    limit: Some(1),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(hits.len(), 1);
+    }
+
+    #[test]
+    fn test_session_filter_and_stats() {
+        let db = HistoryDb::open_in_memory().unwrap();
+        let now = Utc::now();
+        for (session, outcome, minutes_ago) in [
+            ("s1", Outcome::Allow, 30),
+            ("s1", Outcome::Deny, 20),
+            ("s2", Outcome::Warn, 10),
+        ] {
+            let mut entry = test_entry();
+            entry.session_id = Some(session.to_string());
+            entry.transcript_path = Some(format!("/tmp/{session}.jsonl"));
+            entry.outcome = outcome;
+            entry.timestamp = now - Duration::minutes(minutes_ago);
+            db.log_command(&entry).unwrap();
+        }
+        db.log_command(&test_entry()).unwrap();
+
+        let hits = db
+            .search_commands(
+                "",
+                &SearchOptions {
+                    session_id: Some("s1".to_string()),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].transcript_path.as_deref(), Some("/tmp/s1.jsonl"));
+
+        let sessions = db.session_stats(now - Duration::days(1), 10).unwrap();
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[0].session_id, "s2");
+        assert_eq!(sessions[0].warned, 1);
+        assert_eq!(sessions[1].session_id, "s1");
+        assert_eq!((sessions[1].total, sessions[1].denied), (2, 1));
+    }
+
+    #[test]
+    fn test_live_snapshot_covers_window() {
+        let db = HistoryDb::open_in_memory().unwrap();
+        let now = Utc::now();
+        for (session, outcome, minutes_ago, duration_us) in [
+            ("s1", Outcome::Allow, 30, 900),
+            ("s1", Outcome::Allow, 3, 100),
+            ("s1", Outcome::Deny, 2, 300),
+            ("s2", Outcome::Allow, 1, 200),
+        ] {
+            let mut entry = test_entry();
+            entry.session_id = Some(session.to_string());
+            entry.outcome = outcome;
+            entry.eval_duration_us = duration_us;
+            entry.timestamp = now - Duration::minutes(minutes_ago);
+            db.log_command(&entry).unwrap();
+        }
+
+        let snapshot = db
+            .live_snapshot(now - Duration::minutes(5), now, 10, 2)
+            .unwrap();
+        assert_eq!(snapshot.total, 3);
+        assert_eq!(
+            (snapshot.outcomes.allowed, snapshot.outcomes.denied),
+            (2, 1)
+        );
+        assert!((snapshot.deny_rate - 1.0 / 3.0).abs() < f64::EPSILON);
+        assert_eq!(snapshot.latency.p50_us, 200);
+        assert_eq!(snapshot.latency.max_us, 300);
+        assert_eq!(snapshot.active_sessions.len(), 2);
+        assert_eq!(snapshot.active_sessions[0].session_id, "s2");
+        assert_eq!(snapshot.recent.len(), 2);
+        assert_eq!(snapshot.recent[0].session_id.as_deref(), Some("s2"));
+    }
+
+    #[test]
+    fn test_record_tool_result_links_pre_tool_use_entry() {
+        let db = HistoryDb::open_in_memory().unwrap();
+        let mut by_id = test_entry();
+        by_id.session_id = Some("s1".to_string());
+        by_id.tool_use_id = Some("toolu_1".to_string());
+        let by_id_row = db.log_command(&by_id).unwrap();
+
+        let mut by_command = test_entry();
+        by_command.session_id = Some("s1".to_string());
+        by_command.command = "cargo test".to_string();
+        let by_command_row = db.log_command(&by_command).unwrap();
+
+        let updated = db
+            .record_tool_result(&ToolResult {
+                tool_use_id: Some("toolu_1".to_string()),
+                session_id: Some("s1".to_string()),
+                command: by_id.command.clone(),
+                exit_code: Some(0),
+                stderr: None,
+            })
+            .unwrap();
+        assert_eq!(updated, Some(by_id_row));
+
+        // No tool-call id: fall back to the latest unresolved entry for the command.
+        let updated = db
+            .record_tool_result(&ToolResult {
+                session_id: Some("s1".to_string()),
+                command: "cargo test".to_string(),
+                exit_code: Some(101),
+                stderr: Some("test failed".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(updated, Some(by_command_row));
+
+        let unmatched = db
+            .record_tool_result(&ToolResult {
+                session_id: Some("s2".to_string()),
+                command: "cargo test".to_string(),
+                exit_code: Some(1),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(unmatched, None);
+
+        let hits = db
+            .search_commands(
+                "cargo",
+                &SearchOptions {
+                    session_id: Some("s1".to_string()),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(hits[0].exit_code, Some(101));
+        assert_eq!(hits[0].stderr.as_deref(), Some("test failed"));
+
+        let session = db.session_stat("s1").unwrap().expect("session recorded");
+        assert_eq!((session.total, session.failed), (2, 1));
+        assert!(db.session_stat("missing").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_search_commands_tolerates_fts_syntax() {
+        let db = create_test_db_with_data(5);
+        // Quotes and operators are treated literally, not as FTS5 syntax.
+        db.search_commands("\"unterminated AND OR NEAR(", &SearchOptions::default())
+            .unwrap();
+    }
+
     #[test]
     fn test_export_with_outcome_filter() {
         let db = create_test_db_with_mixed_outcomes(100);
@@ -4217,6 +6081,88 @@ mod tests {
         assert!(content.contains("\"echo \"\"hello, world\"\""));
     }
 
+    #[test]
+    fn test_export_options_apply_filter() {
+        let mut options = ExportOptions::default();
+        options.apply_filter("pack=core").unwrap();
+        options.apply_filter("outcome = deny").unwrap();
+        options.apply_filter("command=rm -rf").unwrap();
+        assert_eq!(options.pack_id.as_deref(), Some("core"));
+        assert_eq!(options.outcome_filter, Some(Outcome::Deny));
+        assert_eq!(options.command_contains.as_deref(), Some("rm -rf"));
+
+        assert!(options.apply_filter("pack").is_err());
+        assert!(options.apply_filter("pack=").is_err());
+        assert!(options.apply_filter("outcome=maybe").is_err());
+        assert!(options.apply_filter("color=red").is_err());
+    }
+
+    #[test]
+    fn test_export_with_field_filters() {
+        let db = HistoryDb::open_in_memory().unwrap();
+        for (command, pack, dir) in [
+            ("git reset --hard", Some("core.git"), "/repo"),
+            ("rm -rf build", Some("core.filesystem"), "/repo/sub"),
+            ("docker system prune", Some("containers.docker"), "/repo"),
+            ("ls", None, "/other"),
+        ] {
+            db.log_command(&CommandEntry {
+                command: command.to_string(),
+                pack_id: pack.map(str::to_string),
+                working_dir: dir.to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+        }
+
+        let commands = |options: &ExportOptions| -> Vec<String> {
+            let mut found: Vec<String> = db
+                .query_commands_for_export(options)
+                .unwrap()
+                .into_iter()
+                .map(|e| e.command)
+                .collect();
+            found.sort();
+            found
+        };
+
+        let mut options = ExportOptions::default();
+        options.apply_filter("pack=core").unwrap();
+        assert_eq!(commands(&options), ["git reset --hard", "rm -rf build"]);
+
+        options.apply_filter("cwd=/repo/").unwrap();
+        options.apply_filter("command=reset").unwrap();
+        assert_eq!(commands(&options), ["git reset --hard"]);
+
+        let mut options = ExportOptions::default();
+        options.apply_filter("command=100%").unwrap();
+        assert!(commands(&options).is_empty());
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn test_export_parquet() {
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+
+        let db = create_test_db_with_data(20);
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("history.parquet");
+        let count = db
+            .export_parquet(
+                std::fs::File::create(&path).unwrap(),
+                &ExportOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(count, 20);
+
+        let reader = SerializedFileReader::new(std::fs::File::open(&path).unwrap()).unwrap();
+        let metadata = reader.metadata();
+        assert_eq!(metadata.file_metadata().num_rows(), 20);
+        let schema = metadata.file_metadata().schema_descr();
+        assert_eq!(schema.column(0).name(), "timestamp");
+        assert_eq!(schema.column(3).name(), "command");
+    }
+
     #[test]
     fn test_query_commands_for_export() {
         let db = create_test_db_with_data(25);
@@ -4434,6 +6380,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_review_verdicts_feed_effectiveness() {
+        let db = HistoryDb::open_in_memory().unwrap();
+        let now = Utc::now();
+
+        for _ in 0..12 {
+            insert_analysis_entry(&db, "pattern-a", "core.git", Outcome::Deny, now);
+        }
+        insert_analysis_entry(&db, "pattern-a", "core.git", Outcome::Allow, now);
+
+        let pending = db.unreviewed_denies(None, 100).unwrap();
+        assert_eq!(pending.len(), 12);
+        assert!(
+            pending
+                .iter()
+                .all(|item| item.entry.outcome == Outcome::Deny)
+        );
+
+        for item in &pending[..8] {
+            assert!(
+                db.record_review(item.id, ReviewVerdict::Approved, now)
+                    .unwrap()
+            );
+        }
+        assert!(
+            db.record_review(pending[8].id, ReviewVerdict::TruePositive, now)
+                .unwrap()
+        );
+        assert!(
+            db.record_review(pending[9].id, ReviewVerdict::Ignored, now)
+                .unwrap()
+        );
+        assert_eq!(db.unreviewed_denies(None, 100).unwrap().len(), 2);
+        assert_eq!(db.unreviewed_denies(None, 1).unwrap().len(), 1);
+
+        let analysis = db.analyze_pack_effectiveness(30, &["core.git"]).unwrap();
+        assert!(analysis.high_value_patterns.is_empty());
+        let end = format_timestamp(now + Duration::seconds(1));
+        let stats = db.query_pattern_effectiveness("", &end).unwrap();
+        assert_eq!(stats[0].approved_count, 8);
+        assert_eq!(stats[0].confirmed_count, 1);
+    }
+
     #[test]
     fn test_generates_actionable_recommendations() {
         let db = HistoryDb::open_in_memory().unwrap();
@@ -1,10 +1,13 @@
 //! Claude Code hook protocol handling.
 //!
 //! This module handles the JSON input/output for the Claude Code `PreToolUse` hook.
-//! It parses incoming hook requests and formats denial responses.
+//! It parses incoming hook requests and formats denial responses. `PostToolUse`
+//! and `Stop` payloads are recognized via [`hook_event`] so hook mode can record
+//! results and summarize the session instead of evaluating.
 
 use crate::evaluator::MatchSpan;
 use crate::highlight::HighlightSpan;
+use crate::i18n;
 use crate::output::auto_theme;
 #[cfg(feature = "rich-output")]
 use crate::output::console::console;
@@ -18,6 +21,7 @@ use rich_rust::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 use std::io::{self, IsTerminal, Read, Write};
+use std::sync::OnceLock;
 use std::time::Duration;
 
 /// Input structure from Claude Code's `PreToolUse` hook.
@@ -38,6 +42,133 @@ pub struct HookInput {
     /// May be a JSON string (e.g. "{\"command\":\"...\"}") or an object.
     #[serde(alias = "toolArgs")]
     pub tool_args: Option<serde_json::Value>,
+
+    /// Hook event name in `hook_event_name` form (e.g. Cursor: "beforeShellExecution").
+    #[serde(alias = "hookEventName")]
+    pub hook_event_name: Option<String>,
+
+    /// Top-level command: a string for Cursor `beforeShellExecution` payloads,
+    /// or an argv array for Codex CLI.
+    pub command: Option<serde_json::Value>,
+
+    /// Agent session identifier (Claude Code, Gemini CLI).
+    #[serde(default, alias = "sessionId")]
+    pub session_id: Option<String>,
+
+    /// Path to the agent's session transcript (JSONL).
+    #[serde(default, alias = "transcriptPath")]
+    pub transcript_path: Option<String>,
+
+    /// Tool-call identifier, shared by a call's `PreToolUse` and `PostToolUse`.
+    #[serde(default, alias = "toolUseId")]
+    pub tool_use_id: Option<String>,
+
+    /// Tool result (`PostToolUse` only), e.g. `{"stdout", "stderr", ...}` for Bash.
+    #[serde(default, alias = "toolResponse")]
+    pub tool_response: Option<serde_json::Value>,
+}
+
+impl HookInput {
+    /// Agent session identifier, if the payload carries a non-empty one.
+    #[must_use]
+    pub fn session_id(&self) -> Option<&str> {
+        self.session_id
+            .as_deref()
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+    }
+
+    /// Session transcript path, if the payload carries a non-empty one.
+    #[must_use]
+    pub fn transcript_path(&self) -> Option<&str> {
+        self.transcript_path
+            .as_deref()
+            .map(str::trim)
+            .filter(|path| !path.is_empty())
+    }
+
+    /// Tool-call identifier, if the payload carries a non-empty one.
+    #[must_use]
+    pub fn tool_use_id(&self) -> Option<&str> {
+        self.tool_use_id
+            .as_deref()
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+    }
+
+    /// Exit code from a `PostToolUse` tool response, if the agent reports one.
+    #[must_use]
+    pub fn tool_exit_code(&self) -> Option<i32> {
+        let response = self.tool_response.as_ref()?;
+        ["exit_code", "exitCode", "returnCode", "return_code"]
+            .iter()
+            .find_map(|key| response.get(*key)?.as_i64())
+            .and_then(|code| i32::try_from(code).ok())
+    }
+
+    /// Stderr from a `PostToolUse` tool response, truncated to
+    /// [`MAX_RECORDED_STDERR_BYTES`].
+    #[must_use]
+    pub fn tool_stderr(&self) -> Option<String> {
+        let stderr = self.tool_response.as_ref()?.get("stderr")?.as_str()?;
+        if stderr.trim().is_empty() {
+            return None;
+        }
+        let mut end = stderr.len().min(MAX_RECORDED_STDERR_BYTES);
+        while !stderr.is_char_boundary(end) {
+            end -= 1;
+        }
+        Some(stderr[..end].to_string())
+    }
+}
+
+/// Maximum stderr bytes recorded in history for a `PostToolUse` result.
+pub const MAX_RECORDED_STDERR_BYTES: usize = 4096;
+
+/// Hook event a payload was sent for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookEvent {
+    /// Before a tool runs: evaluate and allow/deny (the default).
+    PreToolUse,
+    /// After a tool ran: record its exit status and stderr.
+    PostToolUse,
+    /// The agent finished responding: flush history and summarize the session.
+    Stop,
+}
+
+/// Classify a payload by its `hook_event_name`.
+///
+/// Payloads without a recognized event name are treated as `PreToolUse`, so
+/// clients that never send one keep their current behavior.
+#[must_use]
+pub fn hook_event(input: &HookInput) -> HookEvent {
+    match input.hook_event_name.as_deref() {
+        Some(name) if name.eq_ignore_ascii_case("PostToolUse") => HookEvent::PostToolUse,
+        Some(name) if name.eq_ignore_ascii_case("Stop") => HookEvent::Stop,
+        _ => HookEvent::PreToolUse,
+    }
+}
+
+/// Emit the session summary for a `Stop` hook as a `systemMessage`.
+///
+/// Claude Code shows `systemMessage` to the user without feeding it back to
+/// the model or blocking the stop.
+pub fn output_session_summary(summary: &crate::history::SessionStat) {
+    let total = summary.total.to_string();
+    let denied = summary.denied.to_string();
+    let warned = summary.warned.to_string();
+    let failed = summary.failed.to_string();
+    let message = i18n::t_args(
+        "session-summary",
+        &[
+            ("total", total.as_str()),
+            ("denied", denied.as_str()),
+            ("warned", warned.as_str()),
+            ("failed", failed.as_str()),
+        ],
+    );
+    let output = serde_json::json!({ "systemMessage": message });
+    let _ = writeln!(io::stdout().lock(), "{output}");
 }
 
 /// Tool-specific input containing the command to execute.
@@ -97,9 +228,25 @@ pub struct HookSpecificOutput<'a> {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub confidence: Option<f64>,
 
+    /// Safe variant of the blocked command, templated against its arguments
+    /// (e.g., `git push --force-with-lease origin main`), for automatic retry.
+    #[serde(
+        rename = "suggestedAlternative",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub suggested_alternative: Option<String>,
+
     /// Remediation suggestions for the blocked command.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub remediation: Option<Remediation>,
+
+    /// Version of the structured `details` payload (only with `structured_deny`).
+    #[serde(rename = "schemaVersion", skip_serializing_if = "Option::is_none")]
+    pub schema_version: Option<u32>,
+
+    /// Structured match details (only with `structured_deny`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<DenyDetails>,
 }
 
 /// Copilot-compatible denial output for pre-tool-use hooks.
@@ -151,9 +298,85 @@ pub struct CopilotHookOutput<'a> {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub confidence: Option<f64>,
 
+    /// Safe variant of the blocked command, templated against its arguments
+    /// (e.g., `git push --force-with-lease origin main`), for automatic retry.
+    #[serde(
+        rename = "suggestedAlternative",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub suggested_alternative: Option<String>,
+
+    /// Remediation suggestions for the blocked command.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remediation: Option<Remediation>,
+
+    /// Version of the structured `details` payload (only with `structured_deny`).
+    #[serde(rename = "schemaVersion", skip_serializing_if = "Option::is_none")]
+    pub schema_version: Option<u32>,
+
+    /// Structured match details (only with `structured_deny`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<DenyDetails>,
+}
+
+/// Cursor-compatible denial output for `beforeShellExecution` hooks.
+///
+/// Cursor reads `permission` plus separate messages for the user and the agent.
+#[derive(Debug, Serialize)]
+pub struct CursorHookOutput<'a> {
+    /// Permission decision (`deny`).
+    pub permission: &'static str,
+
+    /// Short message shown to the user in the Cursor UI.
+    #[serde(rename = "userMessage")]
+    pub user_message: Cow<'a, str>,
+
+    /// Full explanation returned to the agent.
+    #[serde(rename = "agentMessage")]
+    pub agent_message: Cow<'a, str>,
+
+    /// Short allow-once code (if a pending exception was recorded).
+    #[serde(rename = "allowOnceCode", skip_serializing_if = "Option::is_none")]
+    pub allow_once_code: Option<String>,
+
+    /// Full hash for allow-once disambiguation (if available).
+    #[serde(rename = "allowOnceFullHash", skip_serializing_if = "Option::is_none")]
+    pub allow_once_full_hash: Option<String>,
+
+    /// Stable rule identifier (e.g., "core.git:reset-hard").
+    #[serde(rename = "ruleId", skip_serializing_if = "Option::is_none")]
+    pub rule_id: Option<String>,
+
+    /// Pack identifier that matched (e.g., "core.git").
+    #[serde(rename = "packId", skip_serializing_if = "Option::is_none")]
+    pub pack_id: Option<String>,
+
+    /// Severity level of the matched pattern.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub severity: Option<crate::packs::Severity>,
+
+    /// Confidence score for this match (0.0-1.0).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f64>,
+
+    /// Safe variant of the blocked command, templated against its arguments.
+    #[serde(
+        rename = "suggestedAlternative",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub suggested_alternative: Option<String>,
+
     /// Remediation suggestions for the blocked command.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub remediation: Option<Remediation>,
+
+    /// Version of the structured `details` payload (only with `structured_deny`).
+    #[serde(rename = "schemaVersion", skip_serializing_if = "Option::is_none")]
+    pub schema_version: Option<u32>,
+
+    /// Structured match details (only with `structured_deny`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<DenyDetails>,
 }
 
 /// Hook protocol variant for response formatting.
@@ -163,6 +386,188 @@ pub enum HookProtocol {
     ClaudeCompatible,
     /// Copilot hook protocol (`continue` / `stopReason` + permission fields).
     Copilot,
+    /// Cursor `beforeShellExecution` protocol (`permission` + user/agent messages).
+    Cursor,
+    /// Gemini CLI `BeforeTool` protocol (`decision` / `reason` / `systemMessage`).
+    Gemini,
+    /// Codex CLI protocol (argv input, `decision` / `reason` output).
+    Codex,
+}
+
+impl HookProtocol {
+    /// Resolve the protocol for an explicit `--agent` name.
+    ///
+    /// Returns `None` for agents without a dedicated protocol, in which case
+    /// the protocol is detected from the payload shape.
+    #[must_use]
+    pub fn for_agent(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().replace('_', "-").as_str() {
+            "claude" | "claude-code" | "augment" | "augment-code" | "auggie" => {
+                Some(Self::ClaudeCompatible)
+            }
+            "copilot" | "copilot-cli" => Some(Self::Copilot),
+            "cursor" => Some(Self::Cursor),
+            "gemini" | "gemini-cli" => Some(Self::Gemini),
+            "codex" | "codex-cli" => Some(Self::Codex),
+            _ => None,
+        }
+    }
+}
+
+/// Version of the structured deny payload emitted with `structured_deny`.
+///
+/// Bumped whenever a field in [`DenyDetails`] changes meaning or is removed;
+/// adding optional fields does not bump it.
+pub const DENY_SCHEMA_VERSION: u32 = 1;
+
+/// Set once from `[output] structured_deny` before any denial is written.
+static STRUCTURED_DENY: OnceLock<bool> = OnceLock::new();
+
+/// Enable the versioned `schemaVersion` / `details` fields in deny JSON.
+///
+/// Call this early in `main()`; later calls are ignored.
+pub fn init_structured_deny(enabled: bool) {
+    let _ = STRUCTURED_DENY.set(enabled);
+}
+
+fn structured_deny_enabled() -> bool {
+    STRUCTURED_DENY.get().copied().unwrap_or(false)
+}
+
+/// Set once from `[output.deny_templates]` before any denial is written.
+static DENY_TEMPLATES: OnceLock<crate::config::DenyTemplatesConfig> = OnceLock::new();
+
+/// Use custom deny message templates for the agent-facing message.
+///
+/// Call this early in `main()`; later calls are ignored.
+pub fn init_deny_templates(templates: crate::config::DenyTemplatesConfig) {
+    if !templates.is_empty() {
+        let _ = DENY_TEMPLATES.set(templates);
+    }
+}
+
+/// Values substituted into a deny template.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DenyTemplateFields<'a> {
+    pub command: &'a str,
+    pub reason: &'a str,
+    pub pack: Option<&'a str>,
+    pub pattern: Option<&'a str>,
+    pub severity: Option<crate::packs::Severity>,
+    pub explanation: Option<&'a str>,
+    pub suggestion: Option<&'a str>,
+    pub allow_once_code: Option<&'a str>,
+}
+
+/// Fill `{placeholder}`s in a deny template; `{{` and `}}` are literal braces.
+///
+/// Placeholders without a value (no pack, no allow-once code) become empty;
+/// unknown ones are kept as written.
+#[must_use]
+pub fn render_deny_template(template: &str, fields: &DenyTemplateFields<'_>) -> String {
+    let rule = build_rule_id(fields.pack, fields.pattern);
+    let mut out = String::with_capacity(template.len() + fields.reason.len());
+    let mut rest = template;
+    while let Some(pos) = rest.find(['{', '}']) {
+        out.push_str(&rest[..pos]);
+        let tail = &rest[pos..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            out.push_str(&tail[..1]);
+            rest = &tail[2..];
+            continue;
+        }
+        let placeholder = tail.strip_prefix('{').and_then(|inner| {
+            let name = &inner[..inner.find('}')?];
+            (!name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'))
+                .then_some(name)
+        });
+        let Some(name) = placeholder else {
+            out.push_str(&tail[..1]);
+            rest = &tail[1..];
+            continue;
+        };
+        let value = match name {
+            "reason" => Some(fields.reason),
+            "command" => Some(fields.command),
+            "pack" => Some(fields.pack.unwrap_or_default()),
+            "pattern" => Some(fields.pattern.unwrap_or_default()),
+            "rule" => Some(rule.as_deref().unwrap_or_default()),
+            "severity" => Some(fields.severity.map_or("", |s| s.label())),
+            "explanation" => Some(fields.explanation.map_or("", str::trim)),
+            "suggestion" => Some(fields.suggestion.unwrap_or_default()),
+            "allow_once_code" => Some(fields.allow_once_code.unwrap_or_default()),
+            _ => None,
+        };
+        out.push_str(value.unwrap_or(&tail[..name.len() + 2]));
+        rest = &tail[name.len() + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Structured, machine-readable description of a denial.
+///
+/// Mirrors what `permissionDecisionReason` says in prose so downstream tools
+/// don't have to parse it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DenyDetails {
+    /// Pack identifier that matched (e.g., "core.git").
+    #[serde(rename = "packId", skip_serializing_if = "Option::is_none")]
+    pub pack_id: Option<String>,
+
+    /// Pattern name within the pack (e.g., "reset-hard").
+    #[serde(rename = "patternName", skip_serializing_if = "Option::is_none")]
+    pub pattern_name: Option<String>,
+
+    /// Severity level of the matched pattern.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub severity: Option<crate::packs::Severity>,
+
+    /// Byte range of the match within the command.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub span: Option<DenySpan>,
+
+    /// Safer command to try instead, if one is known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+}
+
+/// Byte range (`start` inclusive, `end` exclusive) of a match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct DenySpan {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl DenyDetails {
+    /// Build details for a denial; `suggestion` prefers the templated rewrite,
+    /// then the first pattern suggestion for this platform.
+    #[must_use]
+    pub fn new(
+        pack: Option<&str>,
+        pattern: Option<&str>,
+        severity: Option<crate::packs::Severity>,
+        matched_span: Option<&MatchSpan>,
+        suggested_alternative: Option<&str>,
+        pattern_suggestions: &[PatternSuggestion],
+    ) -> Self {
+        let suggestion = suggested_alternative.map(String::from).or_else(|| {
+            pattern_suggestions
+                .iter()
+                .find(|s| s.platform.matches_current())
+                .map(|s| s.command.to_string())
+        });
+        Self {
+            pack_id: pack.map(String::from),
+            pattern_name: pattern.map(String::from),
+            severity,
+            span: matched_span.map(|span| DenySpan {
+                start: span.start,
+                end: span.end,
+            }),
+            suggestion,
+        }
+    }
 }
 
 /// Allow-once metadata for denial output.
@@ -251,9 +656,30 @@ pub fn read_hook_input(max_bytes: usize) -> Result<HookInput, HookReadError> {
     serde_json::from_str(&input).map_err(HookReadError::Json)
 }
 
+/// Returns `true` for Cursor `beforeShellExecution` payloads, which carry the
+/// command at the top level instead of inside a tool input object.
+fn is_cursor_payload(input: &HookInput) -> bool {
+    input
+        .hook_event_name
+        .as_deref()
+        .is_some_and(|name| name.eq_ignore_ascii_case("beforeShellExecution"))
+        || (input.tool_name.is_none()
+            && input
+                .command
+                .as_ref()
+                .is_some_and(serde_json::Value::is_string))
+}
+
 /// Detect which hook protocol should be used for output formatting.
 #[must_use]
 pub fn detect_protocol(input: &HookInput) -> HookProtocol {
+    if is_cursor_payload(input) {
+        return HookProtocol::Cursor;
+    }
+    if crate::adapters::is_gemini_payload(input) {
+        return HookProtocol::Gemini;
+    }
+
     let tool_name = input
         .tool_name
         .as_deref()
@@ -306,6 +732,21 @@ fn extract_command_from_tool_args(tool_args: &serde_json::Value) -> Option<Strin
 /// Extract command and protocol from hook input.
 #[must_use]
 pub fn extract_command_with_protocol(input: &HookInput) -> Option<(String, HookProtocol)> {
+    // Codex passes the shell invocation as an argv array.
+    if let Some(command) = crate::adapters::codex_command(input) {
+        return Some((command, HookProtocol::Codex));
+    }
+
+    // Cursor only invokes `beforeShellExecution` for shell commands.
+    if is_cursor_payload(input) {
+        return input
+            .command
+            .as_ref()
+            .and_then(serde_json::Value::as_str)
+            .filter(|command| !command.is_empty())
+            .map(|command| (command.to_string(), HookProtocol::Cursor));
+    }
+
     // Only process shell-command invocations for supported clients.
     if !is_supported_shell_tool(input.tool_name.as_deref()) {
         return None;
@@ -352,7 +793,7 @@ pub fn configure_colors() {
 fn format_explain_hint(command: &str) -> String {
     // Escape double quotes in command for safe copy-paste
     let escaped = command.replace('"', "\\\"");
-    format!("Tip: dcg explain \"{escaped}\"")
+    i18n::t_args("denial-explain-tip", &[("command", escaped.as_str())])
 }
 
 fn build_rule_id(pack: Option<&str>, pattern: Option<&str>) -> Option<String> {
@@ -374,31 +815,28 @@ fn format_explanation_text(
     }
 
     if let Some(rule) = rule_id {
-        return format!(
-            "Matched destructive pattern {rule}. No additional explanation is available yet. See pack documentation for details."
-        );
+        return i18n::t_args("explanation-missing-rule", &[("rule", rule)]);
     }
 
     if let Some(pack_name) = pack {
-        return format!(
-            "Matched destructive pack {pack_name}. No additional explanation is available yet. See pack documentation for details."
-        );
+        return i18n::t_args("explanation-missing-pack", &[("pack", pack_name)]);
     }
 
-    "Matched a destructive pattern. No additional explanation is available yet. See pack documentation for details."
-        .to_string()
+    i18n::t("explanation-missing")
 }
 
 fn format_explanation_block(explanation: &str) -> String {
+    let label = i18n::t("denial-explanation-label");
     let mut lines = explanation.lines();
     let Some(first) = lines.next() else {
-        return "Explanation:".to_string();
+        return label;
     };
 
-    let mut output = format!("Explanation: {first}");
+    let indent = " ".repeat(label.chars().count() + 1);
+    let mut output = format!("{label} {first}");
     for line in lines {
         output.push('\n');
-        output.push_str("             ");
+        output.push_str(&indent);
         output.push_str(line);
     }
     output
@@ -420,21 +858,29 @@ pub fn format_denial_message(
 
     let rule_line = rule_id.as_deref().map_or_else(
         || {
-            pack.map(|pack_name| format!("Pack: {pack_name}\n\n"))
-                .unwrap_or_default()
+            pack.map(|pack_name| {
+                format!(
+                    "{}\n\n",
+                    i18n::t_args("denial-pack", &[("pack", pack_name)])
+                )
+            })
+            .unwrap_or_default()
         },
-        |rule| format!("Rule: {rule}\n\n"),
+        |rule| format!("{}\n\n", i18n::t_args("denial-rule", &[("rule", rule)])),
     );
 
     format!(
-        "BLOCKED by dcg\n\n\
+        "{title}\n\n\
          {explain_hint}\n\n\
-         Reason: {reason}\n\n\
+         {reason_line}\n\n\
          {explanation_block}\n\n\
          {rule_line}\
-         Command: {command}\n\n\
-         If this operation is truly needed, ask the user for explicit \
-         permission and have them run the command manually."
+         {command_line}\n\n\
+         {ask_user}",
+        title = i18n::t("denial-title"),
+        reason_line = i18n::t_args("denial-reason", &[("reason", reason)]),
+        command_line = i18n::t_args("denial-command", &[("command", command)]),
+        ask_user = i18n::t("denial-ask-user"),
     )
 }
 
@@ -502,7 +948,7 @@ pub fn print_colorful_warning(
     // Add contextual suggestion if available and no pattern suggestions
     if suggestions_enabled && alternatives.is_empty() {
         if let Some(sugg) = get_contextual_suggestion(command) {
-            alternatives.push(sugg.to_string());
+            alternatives.push(sugg);
         }
     }
 
@@ -539,7 +985,7 @@ pub fn print_colorful_warning(
     let reset = if theme.colors_enabled { "\x1b[0m" } else { "" };
     let cyan = if theme.colors_enabled { "\x1b[36m" } else { "" };
 
-    eprintln!("{footer_style}Learn more:{reset}");
+    eprintln!("{footer_style}{}{reset}", i18n::t("footer-learn-more"));
     eprintln!("  $ {cyan}{explain_cmd}{reset}");
 
     if let Some(ref rule) = rule_id {
@@ -547,7 +993,7 @@ pub fn print_colorful_warning(
     }
 
     eprintln!();
-    eprintln!("{footer_style}False positive? File an issue:{reset}");
+    eprintln!("{footer_style}{}{reset}", i18n::t("footer-false-positive"));
     eprintln!(
         "{footer_style}https://github.com/Dicklesworthstone/destructive_command_guard/issues/new?template=false_positive.yml{reset}"
     );
@@ -603,26 +1049,27 @@ fn truncate_for_display(s: &str, max_len: usize) -> String {
 }
 
 /// Get context-specific suggestion based on the blocked command.
-fn get_contextual_suggestion(command: &str) -> Option<&'static str> {
-    if command.contains("reset") || command.contains("checkout") {
-        Some("Consider using 'git stash' first to save your changes.")
+fn get_contextual_suggestion(command: &str) -> Option<String> {
+    let id = if command.contains("reset") || command.contains("checkout") {
+        "suggest-git-stash"
     } else if command.contains("clean") {
-        Some("Use 'git clean -n' first to preview what would be deleted.")
+        "suggest-git-clean-dry-run"
     } else if command.contains("push") && command.contains("force") {
-        Some("Consider using '--force-with-lease' for safer force pushing.")
+        "suggest-force-with-lease"
     } else if command.contains("rm -rf") || command.contains("rm -r") {
-        Some("Verify the path carefully before running rm -rf manually.")
+        "suggest-rm-verify"
     } else if command.contains("DROP") || command.contains("drop") {
-        Some("Consider backing up the database/table before dropping.")
+        "suggest-db-backup"
     } else if command.contains("kubectl") && command.contains("delete") {
-        Some("Use 'kubectl delete --dry-run=client' to preview changes first.")
+        "suggest-kubectl-dry-run"
     } else if command.contains("docker") && command.contains("prune") {
-        Some("Use 'docker system df' to see what would be affected.")
+        "suggest-docker-df"
     } else if command.contains("terraform") && command.contains("destroy") {
-        Some("Use 'terraform plan -destroy' to preview changes first.")
+        "suggest-terraform-plan"
     } else {
-        None
-    }
+        return None;
+    };
+    Some(i18n::t(id))
 }
 
 /// Output a denial response to stdout (JSON for hook protocol).
@@ -657,16 +1104,61 @@ pub fn output_denial_for_protocol(
     );
 
     // Build JSON response for hook protocol (stdout)
-    let message = format_denial_message(command, reason, explanation, pack, pattern);
     let rule_id = build_rule_id(pack, pattern);
+    let suggested_alternative = rule_id
+        .as_deref()
+        .and_then(|rule| crate::suggestions::suggested_alternative(rule, command));
+    let message = DENY_TEMPLATES
+        .get()
+        .and_then(|templates| templates.template_for(pack, severity))
+        .map_or_else(
+            || format_denial_message(command, reason, explanation, pack, pattern),
+            |template| {
+                let contextual = get_contextual_suggestion(command);
+                let suggestion = suggested_alternative
+                    .as_deref()
+                    .or_else(|| {
+                        pattern_suggestions
+                            .iter()
+                            .find(|s| s.platform.matches_current())
+                            .map(|s| s.command)
+                    })
+                    .or(contextual.as_deref());
+                render_deny_template(
+                    template,
+                    &DenyTemplateFields {
+                        command,
+                        reason,
+                        pack,
+                        pattern,
+                        severity,
+                        explanation,
+                        suggestion,
+                        allow_once_code,
+                    },
+                )
+            },
+        );
     let remediation = allow_once.map(|info| {
         let explanation_text = format_explanation_text(explanation, rule_id.as_deref(), pack);
         Remediation {
-            safe_alternative: get_contextual_suggestion(command).map(String::from),
+            safe_alternative: get_contextual_suggestion(command),
             explanation: explanation_text,
             allow_once_command: format!("dcg allow-once {}", info.code),
         }
     });
+    let structured = structured_deny_enabled();
+    let schema_version = structured.then_some(DENY_SCHEMA_VERSION);
+    let details = structured.then(|| {
+        DenyDetails::new(
+            pack,
+            pattern,
+            severity,
+            matched_span,
+            suggested_alternative.as_deref(),
+            pattern_suggestions,
+        )
+    });
 
     let stdout = io::stdout();
     let mut handle = stdout.lock();
@@ -684,7 +1176,10 @@ pub fn output_denial_for_protocol(
                     pack_id: pack.map(String::from),
                     severity,
                     confidence,
+                    suggested_alternative,
                     remediation,
+                    schema_version,
+                    details,
                 },
             };
 
@@ -694,7 +1189,7 @@ pub fn output_denial_for_protocol(
         HookProtocol::Copilot => {
             let output = CopilotHookOutput {
                 continue_execution: false,
-                stop_reason: Cow::Owned(format!("BLOCKED by dcg: {reason}")),
+                stop_reason: Cow::Owned(i18n::t_args("denial-short", &[("reason", reason)])),
                 permission_decision: "deny",
                 permission_decision_reason: Cow::Owned(message),
                 allow_once_code: allow_once.map(|info| info.code.clone()),
@@ -703,7 +1198,62 @@ pub fn output_denial_for_protocol(
                 pack_id: pack.map(String::from),
                 severity,
                 confidence,
+                suggested_alternative,
+                remediation,
+                schema_version,
+                details,
+            };
+
+            let _ = serde_json::to_writer(&mut handle, &output);
+            let _ = writeln!(handle);
+        }
+        HookProtocol::Gemini | HookProtocol::Codex => {
+            let details = crate::adapters::DenialDetails {
+                allow_once_code: allow_once.map(|info| info.code.clone()),
+                allow_once_full_hash: allow_once.map(|info| info.full_hash.clone()),
+                rule_id,
+                pack_id: pack.map(String::from),
+                severity,
+                confidence,
+                suggested_alternative,
+                remediation,
+                schema_version,
+                details,
+            };
+
+            if protocol == HookProtocol::Gemini {
+                let output = crate::adapters::GeminiHookOutput {
+                    decision: "deny",
+                    reason: Cow::Owned(message),
+                    system_message: Cow::Owned(i18n::t_args("denial-short", &[("reason", reason)])),
+                    details,
+                };
+                let _ = serde_json::to_writer(&mut handle, &output);
+            } else {
+                let output = crate::adapters::CodexHookOutput {
+                    decision: "deny",
+                    reason: Cow::Owned(message),
+                    details,
+                };
+                let _ = serde_json::to_writer(&mut handle, &output);
+            }
+            let _ = writeln!(handle);
+        }
+        HookProtocol::Cursor => {
+            let output = CursorHookOutput {
+                permission: "deny",
+                user_message: Cow::Owned(i18n::t_args("denial-short", &[("reason", reason)])),
+                agent_message: Cow::Owned(message),
+                allow_once_code: allow_once.map(|info| info.code.clone()),
+                allow_once_full_hash: allow_once.map(|info| info.full_hash.clone()),
+                rule_id,
+                pack_id: pack.map(String::from),
+                severity,
+                confidence,
+                suggested_alternative,
                 remediation,
+                schema_version,
+                details,
             };
 
             let _ = serde_json::to_writer(&mut handle, &output);
@@ -752,6 +1302,7 @@ pub fn output_warning(
     pack: Option<&str>,
     pattern: Option<&str>,
     explanation: Option<&str>,
+    recovery_hint: Option<&str>,
 ) {
     let stderr = io::stderr();
     let mut handle = stderr.lock();
@@ -760,7 +1311,7 @@ pub fn output_warning(
     let _ = writeln!(
         handle,
         "{} {}",
-        "dcg WARNING (allowed by policy):".yellow().bold(),
+        i18n::t("warning-header").yellow().bold(),
         reason
     );
 
@@ -770,23 +1321,64 @@ pub fn output_warning(
     let mut explanation_lines = explanation_text.lines();
 
     if let Some(first) = explanation_lines.next() {
-        let _ = writeln!(handle, "  {} {}", "Explanation:".bright_black(), first);
+        let label = i18n::t("warning-explanation");
+        let indent = " ".repeat(label.chars().count() + 3);
+        let _ = writeln!(handle, "  {} {}", label.bright_black(), first);
         for line in explanation_lines {
-            let _ = writeln!(handle, "               {line}");
+            let _ = writeln!(handle, "{indent}{line}");
         }
     }
 
     if let Some(ref rule) = rule_id {
-        let _ = writeln!(handle, "  {} {}", "Rule:".bright_black(), rule);
+        let _ = writeln!(
+            handle,
+            "  {} {}",
+            i18n::t("warning-rule").bright_black(),
+            rule
+        );
     } else if let Some(pack_name) = pack {
-        let _ = writeln!(handle, "  {} {}", "Pack:".bright_black(), pack_name);
+        let _ = writeln!(
+            handle,
+            "  {} {}",
+            i18n::t("warning-pack").bright_black(),
+            pack_name
+        );
     }
 
-    let _ = writeln!(handle, "  {} {}", "Command:".bright_black(), command);
+    let _ = writeln!(
+        handle,
+        "  {} {}",
+        i18n::t("warning-command").bright_black(),
+        command
+    );
+    if let Some(hint) = recovery_hint {
+        let label = i18n::t("warning-recovery");
+        let indent = " ".repeat(label.chars().count() + 3);
+        let mut lines = hint.lines();
+        if let Some(first) = lines.next() {
+            let _ = writeln!(handle, "  {} {}", label.bright_black(), first);
+        }
+        for line in lines {
+            let _ = writeln!(handle, "{indent}{line}");
+        }
+    }
     let _ = writeln!(
         handle,
         "  {}",
-        "No hook JSON deny was emitted; this warning is informational.".bright_black()
+        i18n::t("warning-informational").bright_black()
+    );
+}
+
+/// Footer reminder printed after a denial when allowlist entries have expired.
+#[cold]
+#[inline(never)]
+pub fn output_expired_allowlist_reminder(expired: usize) {
+    let count = expired.to_string();
+    let _ = writeln!(
+        io::stderr().lock(),
+        "{} {}\n",
+        i18n::t("reminder-label").bright_black(),
+        i18n::t_args("reminder-expired-allowlist", &[("count", count.as_str())]),
     );
 }
 
@@ -978,6 +1570,193 @@ mod tests {
         assert_eq!(detect_protocol(&input), HookProtocol::Copilot);
     }
 
+    #[test]
+    fn test_parse_cursor_before_shell_execution() {
+        let json = r#"{"conversation_id":"c1","generation_id":"g1","hook_event_name":"beforeShellExecution","command":"git reset --hard","cwd":"/repo","workspace_roots":["/repo"]}"#;
+        let input: HookInput = serde_json::from_str(json).unwrap();
+        assert_eq!(detect_protocol(&input), HookProtocol::Cursor);
+        assert_eq!(
+            extract_command_with_protocol(&input),
+            Some(("git reset --hard".to_string(), HookProtocol::Cursor))
+        );
+
+        // Claude Code payloads also carry hook_event_name but stay Claude-compatible.
+        let json =
+            r#"{"hook_event_name":"PreToolUse","tool_name":"Bash","tool_input":{"command":"ls"}}"#;
+        let input: HookInput = serde_json::from_str(json).unwrap();
+        assert_eq!(detect_protocol(&input), HookProtocol::ClaudeCompatible);
+    }
+
+    #[test]
+    fn test_parse_session_context() {
+        let json = r#"{"session_id":"abc-123","transcript_path":"/home/u/.claude/projects/p/abc-123.jsonl","hook_event_name":"PreToolUse","tool_name":"Bash","tool_input":{"command":"ls"}}"#;
+        let input: HookInput = serde_json::from_str(json).unwrap();
+        assert_eq!(input.session_id(), Some("abc-123"));
+        assert_eq!(
+            input.transcript_path(),
+            Some("/home/u/.claude/projects/p/abc-123.jsonl")
+        );
+
+        let json = r#"{"sessionId":"  ","tool_name":"Bash","tool_input":{"command":"ls"}}"#;
+        let input: HookInput = serde_json::from_str(json).unwrap();
+        assert_eq!(input.session_id(), None);
+        assert_eq!(input.transcript_path(), None);
+    }
+
+    #[test]
+    fn test_hook_event_classification() {
+        let json = r#"{"session_id":"s1","hook_event_name":"PostToolUse","tool_name":"Bash","tool_use_id":"toolu_1","tool_input":{"command":"cargo test"},"tool_response":{"stdout":"","stderr":"error: test failed","exit_code":101,"interrupted":false}}"#;
+        let input: HookInput = serde_json::from_str(json).unwrap();
+        assert_eq!(hook_event(&input), HookEvent::PostToolUse);
+        assert_eq!(input.tool_use_id(), Some("toolu_1"));
+        assert_eq!(input.tool_exit_code(), Some(101));
+        assert_eq!(input.tool_stderr().as_deref(), Some("error: test failed"));
+        assert_eq!(extract_command(&input).as_deref(), Some("cargo test"));
+
+        let json = r#"{"session_id":"s1","hook_event_name":"Stop","stop_hook_active":false}"#;
+        let input: HookInput = serde_json::from_str(json).unwrap();
+        assert_eq!(hook_event(&input), HookEvent::Stop);
+
+        // No event name (older clients) and other agents' events stay PreToolUse.
+        for json in [
+            r#"{"tool_name":"Bash","tool_input":{"command":"ls"}}"#,
+            r#"{"hook_event_name":"BeforeTool","tool_name":"run_shell_command","tool_input":{"command":"ls"}}"#,
+        ] {
+            let input: HookInput = serde_json::from_str(json).unwrap();
+            assert_eq!(hook_event(&input), HookEvent::PreToolUse);
+            assert_eq!(input.tool_exit_code(), None);
+        }
+    }
+
+    #[test]
+    fn test_tool_stderr_truncates_on_char_boundary() {
+        let stderr = "é".repeat(MAX_RECORDED_STDERR_BYTES);
+        let json = serde_json::json!({
+            "hook_event_name": "PostToolUse",
+            "tool_response": { "stderr": stderr },
+        });
+        let input: HookInput = serde_json::from_value(json).unwrap();
+        let recorded = input.tool_stderr().unwrap();
+        assert!(recorded.len() <= MAX_RECORDED_STDERR_BYTES);
+        assert!(recorded.chars().all(|c| c == 'é'));
+    }
+
+    #[test]
+    fn test_parse_gemini_and_codex_payloads() {
+        let json = r#"{"session_id":"s1","hook_event_name":"BeforeTool","tool_name":"run_shell_command","tool_input":{"command":"git clean -fdx"}}"#;
+        let input: HookInput = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            extract_command_with_protocol(&input),
+            Some(("git clean -fdx".to_string(), HookProtocol::Gemini))
+        );
+
+        let json = r#"{"command":["bash","-lc","git reset --hard"],"cwd":"/repo"}"#;
+        let input: HookInput = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            extract_command_with_protocol(&input),
+            Some(("git reset --hard".to_string(), HookProtocol::Codex))
+        );
+    }
+
+    #[test]
+    fn test_protocol_for_agent() {
+        assert_eq!(
+            HookProtocol::for_agent("cursor"),
+            Some(HookProtocol::Cursor)
+        );
+        assert_eq!(
+            HookProtocol::for_agent("Claude_Code"),
+            Some(HookProtocol::ClaudeCompatible)
+        );
+        assert_eq!(
+            HookProtocol::for_agent("copilot"),
+            Some(HookProtocol::Copilot)
+        );
+        assert_eq!(
+            HookProtocol::for_agent("gemini"),
+            Some(HookProtocol::Gemini)
+        );
+        assert_eq!(
+            HookProtocol::for_agent("codex-cli"),
+            Some(HookProtocol::Codex)
+        );
+        assert_eq!(HookProtocol::for_agent("aider"), None);
+    }
+
+    #[test]
+    fn test_cursor_output_shape() {
+        let output = CursorHookOutput {
+            permission: "deny",
+            user_message: Cow::Borrowed("BLOCKED by dcg: destructive"),
+            agent_message: Cow::Borrowed("BLOCKED by dcg\n\nReason: destructive"),
+            allow_once_code: None,
+            allow_once_full_hash: None,
+            rule_id: Some("core.git:reset-hard".to_string()),
+            pack_id: Some("core.git".to_string()),
+            severity: None,
+            confidence: None,
+            suggested_alternative: None,
+            remediation: None,
+            schema_version: None,
+            details: None,
+        };
+        let json: serde_json::Value = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["permission"], "deny");
+        assert_eq!(json["userMessage"], "BLOCKED by dcg: destructive");
+        assert_eq!(json["ruleId"], "core.git:reset-hard");
+        assert!(json.get("hookSpecificOutput").is_none());
+    }
+
+    #[test]
+    fn test_structured_deny_details_shape() {
+        let suggestions = [PatternSuggestion::new("git stash", "Save changes first")];
+        let details = DenyDetails::new(
+            Some("core.git"),
+            Some("reset-hard"),
+            Some(crate::packs::Severity::Critical),
+            Some(&MatchSpan { start: 0, end: 16 }),
+            None,
+            &suggestions,
+        );
+        let output = HookSpecificOutput {
+            hook_event_name: "PreToolUse",
+            permission_decision: "deny",
+            permission_decision_reason: Cow::Borrowed("BLOCKED by dcg"),
+            allow_once_code: None,
+            allow_once_full_hash: None,
+            rule_id: Some("core.git:reset-hard".to_string()),
+            pack_id: Some("core.git".to_string()),
+            severity: Some(crate::packs::Severity::Critical),
+            confidence: None,
+            suggested_alternative: None,
+            remediation: None,
+            schema_version: Some(DENY_SCHEMA_VERSION),
+            details: Some(details),
+        };
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["schemaVersion"], DENY_SCHEMA_VERSION);
+        assert_eq!(json["details"]["packId"], "core.git");
+        assert_eq!(json["details"]["patternName"], "reset-hard");
+        assert_eq!(json["details"]["severity"], "critical");
+        assert_eq!(json["details"]["span"]["start"], 0);
+        assert_eq!(json["details"]["span"]["end"], 16);
+        assert_eq!(json["details"]["suggestion"], "git stash");
+
+        // Templated rewrites win over generic pattern suggestions.
+        let details = DenyDetails::new(
+            None,
+            None,
+            None,
+            None,
+            Some("git push --force-with-lease"),
+            &suggestions,
+        );
+        assert_eq!(
+            details.suggestion.as_deref(),
+            Some("git push --force-with-lease")
+        );
+    }
+
     #[test]
     fn test_parse_non_string_command() {
         let json = r#"{"tool_name":"Bash","tool_input":{"command":123}}"#;
@@ -1015,4 +1794,50 @@ mod tests {
 
         assert!(std::env::var(key).is_err());
     }
+
+    #[test]
+    fn test_render_deny_template_fills_placeholders() {
+        let fields = DenyTemplateFields {
+            command: "git reset --hard",
+            reason: "destroys uncommitted changes",
+            pack: Some("core.git"),
+            pattern: Some("reset-hard"),
+            severity: Some(crate::packs::Severity::Critical),
+            explanation: Some("  Discards the working tree.\n"),
+            suggestion: Some("git stash"),
+            allow_once_code: Some("ab12c"),
+        };
+        let rendered = render_deny_template(
+            "[{severity}] {rule}: {reason} ({command}). {explanation} Try `{suggestion}` \
+             or `dcg allow-once {allow_once_code}`. Runbook: https://wiki.example.com/dcg/{pack}",
+            &fields,
+        );
+        assert_eq!(
+            rendered,
+            "[critical] core.git:reset-hard: destroys uncommitted changes (git reset --hard). \
+             Discards the working tree. Try `git stash` or `dcg allow-once ab12c`. \
+             Runbook: https://wiki.example.com/dcg/core.git"
+        );
+    }
+
+    #[test]
+    fn test_render_deny_template_escapes_and_missing_values() {
+        let fields = DenyTemplateFields {
+            command: "rm -rf /",
+            reason: "recursive delete of root",
+            ..DenyTemplateFields::default()
+        };
+        assert_eq!(
+            render_deny_template(
+                "{{reason}} {reason} [{pack}] {unknown} {not a name} }",
+                &fields
+            ),
+            "{reason} recursive delete of root [] {unknown} {not a name} }"
+        );
+        assert_eq!(
+            render_deny_template("no placeholders", &fields),
+            "no placeholders"
+        );
+        assert_eq!(render_deny_template("trailing {", &fields), "trailing {");
+    }
 }
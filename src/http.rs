@@ -0,0 +1,280 @@
+//! Minimal blocking HTTP/1.1 client for webhooks and policy sync.
+//!
+//! dcg only needs a handful of small requests (webhook posts, fetching a
+//! policy bundle), so this speaks just enough HTTP/1.1 over `std::net` and
+//! OpenSSL: one request per connection (`Connection: close`), fixed-length or
+//! chunked response bodies, and a hard timeout on every socket operation.
+
+use std::fmt::Write as FmtWrite;
+use std::io::{self, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::Path;
+use std::time::Duration;
+
+/// Largest response body accepted; anything bigger is an error.
+pub const MAX_RESPONSE_BYTES: usize = 8 * 1024 * 1024;
+
+/// Well-known CA bundle locations, tried in addition to the TLS library's defaults.
+const CA_BUNDLES: &[&str] = &[
+    "/etc/ssl/certs/ca-certificates.crt",
+    "/etc/pki/tls/certs/ca-bundle.crt",
+    "/etc/ssl/cert.pem",
+];
+
+/// A parsed HTTP response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    /// Status code.
+    pub status: u16,
+    /// Header names (lowercased) and values, in order.
+    pub headers: Vec<(String, String)>,
+    /// Decoded body.
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    /// First value of a header, matched case-insensitively.
+    #[must_use]
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Whether the status is 2xx.
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+/// Parsed `http(s)://host[:port]/path` URL.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Url {
+    /// `https://`.
+    pub tls: bool,
+    /// Host name or address (IPv6 without brackets).
+    pub host: String,
+    /// Explicit or default port.
+    pub port: u16,
+    /// Path and query, starting with `/`.
+    pub path: String,
+}
+
+impl Url {
+    /// Parse an `http://` or `https://` URL. Userinfo is rejected.
+    #[must_use]
+    pub fn parse(url: &str) -> Option<Self> {
+        let (tls, rest) = if let Some(rest) = url.strip_prefix("https://") {
+            (true, rest)
+        } else {
+            (false, url.strip_prefix("http://")?)
+        };
+        let (authority, path) = rest
+            .find('/')
+            .map_or((rest, "/"), |idx| (&rest[..idx], &rest[idx..]));
+        if authority.is_empty() || authority.contains('@') {
+            return None;
+        }
+        // `[v6addr]:port` keeps its colons inside the brackets.
+        let port_sep = authority
+            .rfind(':')
+            .filter(|&idx| authority[idx..].find(']').is_none());
+        let (host, port) = match port_sep {
+            Some(idx) => (&authority[..idx], authority[idx + 1..].parse().ok()?),
+            None => (authority, if tls { 443 } else { 80 }),
+        };
+        Some(Self {
+            tls,
+            host: host
+                .trim_start_matches('[')
+                .trim_end_matches(']')
+                .to_string(),
+            port,
+            path: path.to_string(),
+        })
+    }
+}
+
+/// Send one request and read the whole response.
+///
+/// # Errors
+///
+/// Returns an error for unsupported URLs, connection/TLS failures, timeouts,
+/// malformed responses, or bodies larger than [`MAX_RESPONSE_BYTES`].
+pub fn request(
+    method: &str,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: Option<&[u8]>,
+    timeout: Duration,
+) -> io::Result<Response> {
+    let target = Url::parse(url)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "unsupported URL"))?;
+    let addr = (target.host.as_str(), target.port)
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| io::Error::other("host did not resolve"))?;
+    let mut stream = TcpStream::connect_timeout(&addr, timeout)?;
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(timeout))?;
+
+    let mut head = format!(
+        "{method} {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: dcg/{}\r\nConnection: close\r\n",
+        target.path,
+        target.host,
+        env!("CARGO_PKG_VERSION"),
+    );
+    for (name, value) in headers {
+        let _ = write!(head, "{name}: {value}\r\n");
+    }
+    if let Some(body) = body {
+        let _ = write!(head, "Content-Length: {}\r\n", body.len());
+    }
+    head.push_str("\r\n");
+    let mut message = head.into_bytes();
+    message.extend_from_slice(body.unwrap_or_default());
+
+    if target.tls {
+        let mut builder =
+            openssl::ssl::SslConnector::builder(openssl::ssl::SslMethod::tls_client())
+                .map_err(io::Error::other)?;
+        if let Some(bundle) = CA_BUNDLES.iter().find(|path| Path::new(path).exists()) {
+            let _ = builder.set_ca_file(bundle);
+        }
+        let mut tls = builder
+            .build()
+            .connect(&target.host, stream)
+            .map_err(io::Error::other)?;
+        exchange(&mut tls, &message)
+    } else {
+        exchange(&mut stream, &message)
+    }
+}
+
+fn exchange(stream: &mut (impl Read + Write), message: &[u8]) -> io::Result<Response> {
+    stream.write_all(message)?;
+    stream.flush()?;
+    let mut raw = Vec::new();
+    stream
+        .take(u64::try_from(MAX_RESPONSE_BYTES).unwrap_or(u64::MAX) + 1)
+        .read_to_end(&mut raw)?;
+    if raw.len() > MAX_RESPONSE_BYTES {
+        return Err(io::Error::other("response too large"));
+    }
+    parse_response(&raw).ok_or_else(|| io::Error::other("malformed HTTP response"))
+}
+
+fn parse_response(raw: &[u8]) -> Option<Response> {
+    let split = raw.windows(4).position(|w| w == b"\r\n\r\n")?;
+    let head = std::str::from_utf8(&raw[..split]).ok()?;
+    let body = &raw[split + 4..];
+
+    let mut lines = head.split("\r\n");
+    let status = parse_status_line(lines.next()?)?;
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+        .collect();
+
+    let chunked = headers.iter().any(|(name, value)| {
+        name == "transfer-encoding" && value.to_ascii_lowercase().contains("chunked")
+    });
+    let body = if chunked {
+        decode_chunked(body)?
+    } else {
+        let length = headers
+            .iter()
+            .find(|(name, _)| name == "content-length")
+            .and_then(|(_, value)| value.parse::<usize>().ok());
+        match length {
+            Some(length) => body.get(..length)?.to_vec(),
+            None => body.to_vec(),
+        }
+    };
+    Some(Response {
+        status,
+        headers,
+        body,
+    })
+}
+
+fn parse_status_line(line: &str) -> Option<u16> {
+    let mut parts = line.split_whitespace();
+    parts
+        .next()
+        .filter(|version| version.starts_with("HTTP/"))?;
+    parts.next()?.parse().ok()
+}
+
+fn decode_chunked(mut data: &[u8]) -> Option<Vec<u8>> {
+    let mut body = Vec::new();
+    loop {
+        let line_end = data.windows(2).position(|w| w == b"\r\n")?;
+        let size_line = std::str::from_utf8(&data[..line_end]).ok()?;
+        let size_hex = size_line.split(';').next()?.trim();
+        let size = usize::from_str_radix(size_hex, 16).ok()?;
+        data = &data[line_end + 2..];
+        if size == 0 {
+            return Some(body);
+        }
+        body.extend_from_slice(data.get(..size)?);
+        data = data.get(size + 2..)?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_urls() {
+        assert_eq!(
+            Url::parse("https://hooks.slack.com/services/T/B/X"),
+            Some(Url {
+                tls: true,
+                host: "hooks.slack.com".to_string(),
+                port: 443,
+                path: "/services/T/B/X".to_string(),
+            })
+        );
+        assert_eq!(
+            Url::parse("http://localhost:8080"),
+            Some(Url {
+                tls: false,
+                host: "localhost".to_string(),
+                port: 8080,
+                path: "/".to_string(),
+            })
+        );
+        assert_eq!(
+            Url::parse("http://[::1]:9000/hook").map(|url| (url.host, url.port)),
+            Some(("::1".to_string(), 9000))
+        );
+        assert!(Url::parse("ftp://example.com/x").is_none());
+        assert!(Url::parse("https://user:pw@example.com/x").is_none());
+    }
+
+    #[test]
+    fn parses_fixed_length_and_chunked_responses() {
+        let fixed =
+            parse_response(b"HTTP/1.1 200 OK\r\nETag: \"abc\"\r\nContent-Length: 5\r\n\r\nhello")
+                .unwrap();
+        assert!(fixed.is_success());
+        assert_eq!(fixed.header("etag"), Some("\"abc\""));
+        assert_eq!(fixed.body, b"hello");
+
+        let chunked = parse_response(
+            b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n4\r\nWiki\r\n5\r\npedia\r\n0\r\n\r\n",
+        )
+        .unwrap();
+        assert_eq!(chunked.body, b"Wikipedia");
+
+        let not_modified = parse_response(b"HTTP/1.1 304 Not Modified\r\n\r\n").unwrap();
+        assert_eq!(not_modified.status, 304);
+        assert!(!not_modified.is_success());
+
+        assert!(parse_response(b"garbage").is_none());
+    }
+}
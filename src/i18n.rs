@@ -0,0 +1,581 @@
+//! Localization of user-facing messages (`[output] locale`).
+//!
+//! Denial text, warnings, and `dcg test` labels are looked up in message
+//! catalogs rather than hard-coded. Catalogs are `.ftl` files using a subset
+//! of [Project Fluent](https://projectfluent.org) syntax: messages,
+//! `.attribute`s, `{ $variable }` placeables, and plural selectors.
+//!
+//! ```text
+//! [output] locale, else DCG_LOCALE        -> explicit locale tag
+//! otherwise LC_ALL / LC_MESSAGES / LANG   -> de_DE.UTF-8 becomes de-DE
+//! lookup, most specific first             -> de-DE, de, en
+//! per tag                                 -> [output] locale_dir, ~/.config/dcg/locales, bundled
+//! ```
+//!
+//! A message missing from every translated catalog falls back to the bundled
+//! English catalog (`locales/en.ftl`), so translations may be partial.
+//!
+//! Pack reasons and explanations live in the packs as English literals. A
+//! catalog can override them per rule with a `rule-<pack>-<pattern>` message
+//! (pack id dots replaced by dashes) carrying `.reason`, `.explanation`, and
+//! `.recovery` attributes; rules without a translation keep the pack text.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use crate::config::OutputConfig;
+
+/// Environment variable selecting the locale when `[output] locale` is unset.
+pub const ENV_LOCALE: &str = "DCG_LOCALE";
+
+/// Locale of the bundled catalog every lookup falls back to.
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Catalogs compiled into the binary.
+const BUNDLED: &[(&str, &str)] = &[(DEFAULT_LOCALE, include_str!("../locales/en.ftl"))];
+
+/// Set once from `[output]` before any message is rendered.
+static LOCALIZER: OnceLock<Localizer> = OnceLock::new();
+
+/// Select the locale and load catalogs from `[output]`.
+///
+/// Call this early in `main()`; later calls are ignored. Until it is called,
+/// messages come from the bundled English catalog.
+pub fn init(output: &OutputConfig) {
+    let _ = LOCALIZER.set(Localizer::from_config(output));
+}
+
+/// The process-wide localizer.
+#[must_use]
+pub fn localizer() -> &'static Localizer {
+    LOCALIZER.get_or_init(Localizer::bundled)
+}
+
+/// Look up a message with no arguments.
+#[must_use]
+pub fn t(id: &str) -> String {
+    localizer().message(id, &[])
+}
+
+/// Look up a message, substituting `{ $name }` placeables from `args`.
+#[must_use]
+pub fn t_args(id: &str, args: &[(&str, &str)]) -> String {
+    localizer().message(id, args)
+}
+
+/// Determine the locale tag from config, `DCG_LOCALE`, or the POSIX locale.
+#[must_use]
+pub fn resolve_locale(configured: Option<&str>) -> String {
+    let env_value = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+    configured
+        .map(str::to_string)
+        .filter(|value| !value.trim().is_empty())
+        .or_else(|| env_value(ENV_LOCALE))
+        .or_else(|| env_value("LC_ALL"))
+        .or_else(|| env_value("LC_MESSAGES"))
+        .or_else(|| env_value("LANG"))
+        .and_then(|value| normalize_locale(&value))
+        .unwrap_or_else(|| DEFAULT_LOCALE.to_string())
+}
+
+/// Normalize a POSIX or BCP 47 locale to a catalog tag.
+///
+/// `de_DE.UTF-8@euro` becomes `de-DE`; `C` and `POSIX` have no tag.
+#[must_use]
+pub fn normalize_locale(value: &str) -> Option<String> {
+    let base = value
+        .trim()
+        .split(['.', '@'])
+        .next()
+        .unwrap_or_default()
+        .replace('_', "-");
+    if base.is_empty() || base == "C" || base == "POSIX" {
+        return None;
+    }
+    let mut parts = base.split('-');
+    let language = parts.next()?.to_ascii_lowercase();
+    if language.is_empty() || !language.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let mut tag = language;
+    for part in parts.filter(|part| !part.is_empty()) {
+        tag.push('-');
+        if part.len() == 2 {
+            tag.push_str(&part.to_ascii_uppercase());
+        } else {
+            tag.push_str(part);
+        }
+    }
+    Some(tag)
+}
+
+/// Tags to consult for `locale`, most specific first, ending in English.
+fn fallback_chain(locale: &str) -> Vec<String> {
+    let mut chain = Vec::new();
+    let mut tag = locale;
+    loop {
+        chain.push(tag.to_string());
+        match tag.rfind('-') {
+            Some(index) => tag = &tag[..index],
+            None => break,
+        }
+    }
+    if !chain.iter().any(|tag| tag == DEFAULT_LOCALE) {
+        chain.push(DEFAULT_LOCALE.to_string());
+    }
+    chain
+}
+
+/// Message id carrying the translation of a pack rule.
+#[must_use]
+pub fn rule_message_id(pack_id: &str, pattern_name: &str) -> String {
+    format!("rule-{}-{pattern_name}", pack_id.replace('.', "-"))
+}
+
+/// Loaded catalogs for one locale.
+#[derive(Debug, Clone)]
+pub struct Localizer {
+    locale: String,
+    catalogs: Vec<Catalog>,
+}
+
+impl Localizer {
+    /// Bundled English only; used before [`init`] runs.
+    #[must_use]
+    pub fn bundled() -> Self {
+        Self::load(DEFAULT_LOCALE, &[])
+    }
+
+    /// Resolve the locale and catalog directories from `[output]`.
+    #[must_use]
+    pub fn from_config(output: &OutputConfig) -> Self {
+        let locale = resolve_locale(output.locale.as_deref());
+        let mut dirs = Vec::new();
+        if let Some(dir) = output.locale_dir() {
+            dirs.push(dir);
+        }
+        if let Some(home) = dirs::home_dir() {
+            dirs.push(home.join(".config").join("dcg").join("locales"));
+        }
+        Self::load(&locale, &dirs)
+    }
+
+    /// Load `<tag>.ftl` from each directory, then the bundled catalog, for
+    /// every tag in the fallback chain of `locale`.
+    ///
+    /// Unreadable catalog files are skipped.
+    #[must_use]
+    pub fn load(locale: &str, dirs: &[PathBuf]) -> Self {
+        let mut catalogs = Vec::new();
+        for tag in fallback_chain(locale) {
+            for dir in dirs {
+                if let Ok(source) = fs::read_to_string(dir.join(format!("{tag}.ftl"))) {
+                    catalogs.push(Catalog::parse(&source));
+                }
+            }
+            if let Some((_, source)) = BUNDLED.iter().find(|(bundled, _)| *bundled == tag) {
+                catalogs.push(Catalog::parse(source));
+            }
+        }
+        Self {
+            locale: locale.to_string(),
+            catalogs,
+        }
+    }
+
+    /// The selected locale tag.
+    #[must_use]
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    /// Format message `id`; unknown ids render as the id itself.
+    #[must_use]
+    pub fn message(&self, id: &str, args: &[(&str, &str)]) -> String {
+        self.catalogs
+            .iter()
+            .find_map(|catalog| catalog.value(id))
+            .map_or_else(|| id.to_string(), |pattern| format_pattern(pattern, args))
+    }
+
+    /// Format attribute `attribute` of message `id`, if any catalog has it.
+    #[must_use]
+    pub fn attribute(&self, id: &str, attribute: &str, args: &[(&str, &str)]) -> Option<String> {
+        self.catalogs
+            .iter()
+            .find_map(|catalog| catalog.attribute(id, attribute))
+            .map(|pattern| format_pattern(pattern, args))
+    }
+
+    /// Translated reason for a pack rule, if a catalog provides one.
+    #[must_use]
+    pub fn rule_reason(&self, pack_id: Option<&str>, pattern_name: Option<&str>) -> Option<String> {
+        let (pack_id, pattern_name) = pack_id.zip(pattern_name)?;
+        self.attribute(&rule_message_id(pack_id, pattern_name), "reason", &[])
+    }
+
+    /// Translated explanation for a pack rule, if a catalog provides one.
+    #[must_use]
+    pub fn rule_explanation(
+        &self,
+        pack_id: Option<&str>,
+        pattern_name: Option<&str>,
+    ) -> Option<String> {
+        let (pack_id, pattern_name) = pack_id.zip(pattern_name)?;
+        self.attribute(&rule_message_id(pack_id, pattern_name), "explanation", &[])
+    }
+
+    /// Translated recovery hint for a pack rule, if a catalog provides one.
+    #[must_use]
+    pub fn rule_recovery_hint(
+        &self,
+        pack_id: Option<&str>,
+        pattern_name: Option<&str>,
+    ) -> Option<String> {
+        let (pack_id, pattern_name) = pack_id.zip(pattern_name)?;
+        self.attribute(&rule_message_id(pack_id, pattern_name), "recovery", &[])
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct Message {
+    value: String,
+    attributes: Vec<(String, String)>,
+}
+
+impl Message {
+    fn slot(&mut self, attribute: Option<usize>) -> &mut String {
+        match attribute {
+            Some(index) => &mut self.attributes[index].1,
+            None => &mut self.value,
+        }
+    }
+
+    fn finish(mut self) -> Self {
+        let trim = |text: &mut String| text.truncate(text.trim_end().len());
+        trim(&mut self.value);
+        for (_, text) in &mut self.attributes {
+            trim(text);
+        }
+        self
+    }
+}
+
+/// One parsed `.ftl` file.
+#[derive(Debug, Clone, Default)]
+pub struct Catalog {
+    messages: HashMap<String, Message>,
+}
+
+impl Catalog {
+    /// Parse catalog source; lines that are not valid entries are ignored.
+    #[must_use]
+    pub fn parse(source: &str) -> Self {
+        let mut messages = HashMap::new();
+        let mut current: Option<(String, Message)> = None;
+        let mut attribute: Option<usize> = None;
+
+        for line in source.lines() {
+            if line.starts_with('#') {
+                continue;
+            }
+            if line.trim().is_empty() {
+                if let Some((_, message)) = current.as_mut() {
+                    let slot = message.slot(attribute);
+                    if !slot.is_empty() {
+                        slot.push('\n');
+                    }
+                }
+                continue;
+            }
+
+            // Indented lines, and a select expression's closing brace, continue
+            // the current entry.
+            if line.starts_with([' ', '\t', '}']) {
+                let Some((_, message)) = current.as_mut() else {
+                    continue;
+                };
+                let text = line.trim();
+                if let Some((name, value)) = parse_entry(text.strip_prefix('.').unwrap_or("")) {
+                    message
+                        .attributes
+                        .push((name.to_string(), value.to_string()));
+                    attribute = Some(message.attributes.len() - 1);
+                } else {
+                    let slot = message.slot(attribute);
+                    if !slot.is_empty() {
+                        slot.push('\n');
+                    }
+                    slot.push_str(text);
+                }
+                continue;
+            }
+
+            if let Some((id, message)) = current.take() {
+                messages.insert(id, message.finish());
+            }
+            attribute = None;
+            current = parse_entry(line).map(|(id, value)| {
+                (
+                    id.to_string(),
+                    Message {
+                        value: value.to_string(),
+                        attributes: Vec::new(),
+                    },
+                )
+            });
+        }
+        if let Some((id, message)) = current {
+            messages.insert(id, message.finish());
+        }
+
+        Self { messages }
+    }
+
+    /// Number of messages in the catalog.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Whether the catalog has no messages.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    fn value(&self, id: &str) -> Option<&str> {
+        self.messages
+            .get(id)
+            .map(|message| message.value.as_str())
+            .filter(|value| !value.is_empty())
+    }
+
+    fn attribute(&self, id: &str, attribute: &str) -> Option<&str> {
+        self.messages.get(id).and_then(|message| {
+            message
+                .attributes
+                .iter()
+                .find(|(name, _)| name == attribute)
+                .map(|(_, value)| value.as_str())
+        })
+    }
+}
+
+/// Split `identifier = value`, requiring a Fluent identifier on the left.
+fn parse_entry(line: &str) -> Option<(&str, &str)> {
+    let (id, value) = line.split_once('=')?;
+    let id = id.trim();
+    let mut chars = id.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    valid.then_some((id, value.trim()))
+}
+
+/// Substitute placeables in a message pattern.
+fn format_pattern(pattern: &str, args: &[(&str, &str)]) -> String {
+    let mut output = String::with_capacity(pattern.len());
+    let mut rest = pattern;
+    while let Some(open) = rest.find('{') {
+        output.push_str(&rest[..open]);
+        let inner = &rest[open + 1..];
+        let Some(close) = matching_brace(inner) else {
+            output.push_str(&rest[open..]);
+            return output;
+        };
+        output.push_str(&resolve_placeable(inner[..close].trim(), args));
+        rest = &inner[close + 1..];
+    }
+    output.push_str(rest);
+    output
+}
+
+fn matching_brace(text: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    for (index, c) in text.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth == 0 => return Some(index),
+            '}' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+fn resolve_placeable(expression: &str, args: &[(&str, &str)]) -> String {
+    if let Some((selector, variants)) = expression.split_once("->") {
+        let value = argument(selector.trim(), args);
+        return select_variant(variants, value)
+            .map(|variant| format_pattern(&variant, args))
+            .unwrap_or_default();
+    }
+    if let Some(literal) = expression
+        .strip_prefix('"')
+        .and_then(|text| text.strip_suffix('"'))
+    {
+        return literal.to_string();
+    }
+    argument(expression, args).map_or_else(|| format!("{{{expression}}}"), str::to_string)
+}
+
+fn argument<'a>(expression: &str, args: &[(&str, &'a str)]) -> Option<&'a str> {
+    let name = expression.strip_prefix('$')?;
+    args.iter()
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| *value)
+}
+
+/// Pick a variant by exact key, then plural category, then the `*` default.
+///
+/// The plural rule is `one` for 1 and `other` otherwise; catalogs for
+/// languages with more categories can key variants by exact number.
+fn select_variant(variants: &str, value: Option<&str>) -> Option<String> {
+    let mut parsed: Vec<(bool, &str, String)> = Vec::new();
+    for line in variants.lines() {
+        let line = line.trim();
+        let (default, body) = line
+            .strip_prefix('*')
+            .map_or((false, line), |body| (true, body));
+        if let Some((key, text)) = body.strip_prefix('[').and_then(|body| body.split_once(']')) {
+            parsed.push((default, key.trim(), text.trim().to_string()));
+        } else if let Some((_, _, text)) = parsed.last_mut().filter(|_| !line.is_empty()) {
+            text.push('\n');
+            text.push_str(line);
+        }
+    }
+
+    let category = value
+        .and_then(|value| value.parse::<f64>().ok())
+        .map(|number| {
+            if (number - 1.0).abs() < f64::EPSILON {
+                "one"
+            } else {
+                "other"
+            }
+        });
+    let find = |key: &str| {
+        parsed
+            .iter()
+            .find(|(_, variant, _)| *variant == key)
+            .map(|(_, _, text)| text.clone())
+    };
+    value
+        .and_then(find)
+        .or_else(|| category.and_then(find))
+        .or_else(|| {
+            parsed
+                .iter()
+                .find(|(default, _, _)| *default)
+                .map(|(_, _, text)| text.clone())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_posix_locales() {
+        assert_eq!(normalize_locale("de_DE.UTF-8").as_deref(), Some("de-DE"));
+        assert_eq!(normalize_locale("fr_ca@euro").as_deref(), Some("fr-CA"));
+        assert_eq!(normalize_locale("pt-BR").as_deref(), Some("pt-BR"));
+        assert_eq!(normalize_locale("ja").as_deref(), Some("ja"));
+        assert_eq!(normalize_locale("C.UTF-8"), None);
+        assert_eq!(normalize_locale("POSIX"), None);
+        assert_eq!(resolve_locale(Some("es_MX")), "es-MX");
+    }
+
+    #[test]
+    fn fallback_chain_ends_in_english() {
+        assert_eq!(fallback_chain("de-DE"), vec!["de-DE", "de", "en"]);
+        assert_eq!(fallback_chain("en-GB"), vec!["en-GB", "en"]);
+        assert_eq!(fallback_chain("en"), vec!["en"]);
+    }
+
+    #[test]
+    fn parses_messages_attributes_and_multiline_values() {
+        let catalog = Catalog::parse(
+            "# comment\n\
+             greeting = Hello, { $name }!\n\
+             multi =\n    first line\n\n    second paragraph\n\
+             rule-core-git-reset-hard =\n    .reason = translated reason\n    .explanation =\n        line one\n        line two\n\
+             not an entry\n",
+        );
+        assert_eq!(catalog.len(), 3);
+        assert_eq!(
+            catalog.value("multi"),
+            Some("first line\n\nsecond paragraph")
+        );
+        assert_eq!(catalog.value("rule-core-git-reset-hard"), None);
+        assert_eq!(
+            catalog.attribute("rule-core-git-reset-hard", "explanation"),
+            Some("line one\nline two")
+        );
+        assert_eq!(
+            format_pattern(catalog.value("greeting").unwrap(), &[("name", "dcg")]),
+            "Hello, dcg!"
+        );
+    }
+
+    #[test]
+    fn selects_plural_variants() {
+        let localizer = Localizer::bundled();
+        let one = localizer.message("reminder-expired-allowlist", &[("count", "1")]);
+        let many = localizer.message("reminder-expired-allowlist", &[("count", "3")]);
+        assert!(one.starts_with("1 allowlist entry has expired"), "{one}");
+        assert!(
+            many.starts_with("3 allowlist entries have expired"),
+            "{many}"
+        );
+    }
+
+    #[test]
+    fn bundled_catalog_covers_denial_text() {
+        let localizer = Localizer::bundled();
+        assert_eq!(localizer.locale(), "en");
+        assert_eq!(localizer.message("denial-title", &[]), "BLOCKED by dcg");
+        assert_eq!(
+            localizer.message("denial-short", &[("reason", "nope")]),
+            "BLOCKED by dcg: nope"
+        );
+        assert_eq!(localizer.message("no-such-message", &[]), "no-such-message");
+        assert_eq!(
+            localizer.rule_reason(Some("core.git"), Some("reset-hard")),
+            None
+        );
+    }
+
+    #[test]
+    fn user_catalogs_override_and_fall_back() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        fs::write(
+            dir.path().join("de.ftl"),
+            "denial-title = BLOCKIERT durch dcg\n\
+             rule-core-git-reset-hard =\n    .reason = git reset --hard verwirft Änderungen.\n",
+        )
+        .expect("write catalog");
+
+        let localizer = Localizer::load("de-AT", &[dir.path().to_path_buf()]);
+        assert_eq!(
+            localizer.message("denial-title", &[]),
+            "BLOCKIERT durch dcg"
+        );
+        assert_eq!(
+            localizer.message("denial-command", &[("command", "ls")]),
+            "Command: ls"
+        );
+        assert_eq!(
+            localizer
+                .rule_reason(Some("core.git"), Some("reset-hard"))
+                .as_deref(),
+            Some("git reset --hard verwirft Änderungen.")
+        );
+        assert_eq!(
+            localizer.rule_explanation(Some("core.git"), Some("reset-hard")),
+            None
+        );
+    }
+}
@@ -0,0 +1,442 @@
+//! Kubernetes context-aware severity (`[kube_context]`).
+//!
+//! `kubectl delete ns payments` means something very different against
+//! `prod-eu-1` than against `kind-dev`. For matches from `kubernetes.*` packs
+//! this module determines the target context and adjusts the match severity
+//! before the policy resolves a decision mode:
+//!
+//! ```text
+//! --context / --kube-context in the command  -> that context
+//! otherwise `kubectl config current-context` -> cached for cache_ttl_secs
+//! production_contexts match                  -> Medium -> High, High -> Critical
+//! development_contexts match                 -> High -> Medium, Medium -> Low
+//! ```
+//!
+//! Critical matches are never lowered. Lookups that fail or time out leave
+//! the severity unchanged (fail-open), and failures are cached too so a
+//! missing `kubectl` does not slow down every hook invocation.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::KubeContextConfig;
+use crate::packs::Severity;
+
+/// Environment variable overriding the lookup cache file.
+pub const ENV_KUBE_CONTEXT_CACHE: &str = "DCG_KUBE_CONTEXT_CACHE";
+
+/// How a context name was classified.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContextClass {
+    /// Matched `production_contexts`; severity is raised.
+    Production,
+    /// Matched `development_contexts`; severity is lowered.
+    Development,
+}
+
+impl ContextClass {
+    /// Severity after adjusting for this class.
+    #[must_use]
+    pub const fn adjust(self, severity: Severity) -> Severity {
+        match (self, severity) {
+            (Self::Production, Severity::Critical | Severity::High) => Severity::Critical,
+            (Self::Production, Severity::Medium) => Severity::High,
+            (Self::Production, Severity::Low) => Severity::Medium,
+            (Self::Development, Severity::Critical) => Severity::Critical,
+            (Self::Development, Severity::High) => Severity::Medium,
+            (Self::Development, Severity::Medium | Severity::Low) => Severity::Low,
+        }
+    }
+
+    /// Lowercase label for messages.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Production => "production",
+            Self::Development => "development",
+        }
+    }
+}
+
+/// A severity change caused by the target context.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeverityAdjustment {
+    /// Context the command targets.
+    pub context: String,
+    /// Classification of that context.
+    pub class: ContextClass,
+    /// Severity reported by the pack.
+    pub from: Severity,
+    /// Severity after adjustment.
+    pub to: Severity,
+}
+
+impl SeverityAdjustment {
+    /// One-line explanation appended to denial messages.
+    #[must_use]
+    pub fn notice(&self) -> String {
+        format!(
+            "Kubernetes context '{}' is {}: severity {} -> {}.",
+            self.context,
+            self.class.label(),
+            self.from.label(),
+            self.to.label()
+        )
+    }
+}
+
+/// Adjust the severity of a `kubernetes.*` match for the context `command` targets.
+///
+/// Returns `None` when disabled, not a Kubernetes match, the context is unknown
+/// or unclassified, or the severity would not change.
+#[must_use]
+pub fn adjust_severity(
+    config: &KubeContextConfig,
+    command: &str,
+    pack_id: Option<&str>,
+    severity: Option<Severity>,
+) -> Option<SeverityAdjustment> {
+    if !config.enabled || !pack_id.is_some_and(|id| id.starts_with("kubernetes.")) {
+        return None;
+    }
+    let from = severity?;
+    let target = CommandTarget::parse(command);
+    let context = match target.context {
+        Some(context) => context,
+        None => current_context(config, target.kubeconfig.as_deref())?,
+    };
+    let class = classify(config, &context)?;
+    let to = class.adjust(from);
+    (to != from).then_some(SeverityAdjustment {
+        context,
+        class,
+        from,
+        to,
+    })
+}
+
+/// Classify a context name; production patterns win over development ones.
+#[must_use]
+pub fn classify(config: &KubeContextConfig, context: &str) -> Option<ContextClass> {
+    let matches = |patterns: &[String]| {
+        patterns
+            .iter()
+            .any(|pattern| wildcard_match(pattern, context))
+    };
+    if matches(&config.production_contexts) {
+        Some(ContextClass::Production)
+    } else if matches(&config.development_contexts) {
+        Some(ContextClass::Development)
+    } else {
+        None
+    }
+}
+
+/// Match `text` against a pattern where `*` matches any run of characters.
+pub(crate) fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        let Some(pos) = rest.find(part) else {
+            return false;
+        };
+        rest = &rest[pos + part.len()..];
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+/// Context and kubeconfig selected on the command line.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct CommandTarget {
+    context: Option<String>,
+    kubeconfig: Option<String>,
+}
+
+impl CommandTarget {
+    fn parse(command: &str) -> Self {
+        let mut target = Self::default();
+        let mut words = command
+            .split_whitespace()
+            .map(|word| word.trim_matches(|c| c == '"' || c == '\''));
+        while let Some(word) = words.next() {
+            let (flag, inline) = word
+                .split_once('=')
+                .map_or((word, None), |(flag, value)| (flag, Some(value)));
+            let slot = match flag {
+                "--context" | "--kube-context" => &mut target.context,
+                "--kubeconfig" => &mut target.kubeconfig,
+                _ => continue,
+            };
+            let value = inline.or_else(|| words.next());
+            if let Some(value) = value.filter(|value| !value.is_empty()) {
+                *slot = Some(value.to_string());
+            }
+        }
+        target
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct CacheFile {
+    entries: HashMap<String, CacheEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    /// `None` records a failed lookup.
+    context: Option<String>,
+    fetched_at: u64,
+}
+
+fn cache_path() -> Option<PathBuf> {
+    if let Ok(path) = std::env::var(ENV_KUBE_CONTEXT_CACHE) {
+        return Some(PathBuf::from(path));
+    }
+    dirs::cache_dir().map(|dir| dir.join("dcg").join("kube_context.json"))
+}
+
+/// Current context for `kubeconfig` (or `$KUBECONFIG`/the default file).
+fn current_context(config: &KubeContextConfig, kubeconfig: Option<&str>) -> Option<String> {
+    let key = kubeconfig.map_or_else(
+        || std::env::var("KUBECONFIG").unwrap_or_default(),
+        str::to_string,
+    );
+    let timeout = Duration::from_millis(config.timeout_ms);
+    let fetch = || kubectl_current_context(kubeconfig, timeout);
+    match cache_path() {
+        Some(path) => cached_lookup(&path, &key, config.cache_ttl_secs, fetch),
+        None => fetch(),
+    }
+}
+
+/// Return the cached context for `key` if fresher than `ttl_secs`, otherwise
+/// call `fetch` and record its result.
+fn cached_lookup(
+    path: &Path,
+    key: &str,
+    ttl_secs: u64,
+    fetch: impl FnOnce() -> Option<String>,
+) -> Option<String> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    let mut cache: CacheFile = fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+    if let Some(entry) = cache
+        .entries
+        .get(key)
+        .filter(|entry| now.saturating_sub(entry.fetched_at) < ttl_secs)
+    {
+        return entry.context.clone();
+    }
+
+    let context = fetch();
+    cache.entries.insert(
+        key.to_string(),
+        CacheEntry {
+            context: context.clone(),
+            fetched_at: now,
+        },
+    );
+    // Best effort: a cache that cannot be written only costs another lookup.
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    if let Ok(json) = serde_json::to_string(&cache) {
+        let tmp = path.with_extension("json.tmp");
+        if fs::write(&tmp, json).is_ok() {
+            let _ = fs::rename(&tmp, path);
+        }
+    }
+    context
+}
+
+/// Run `kubectl config current-context`, giving up after `timeout`.
+fn kubectl_current_context(kubeconfig: Option<&str>, timeout: Duration) -> Option<String> {
+    let mut cmd = Command::new("kubectl");
+    cmd.args(["config", "current-context"]);
+    if let Some(kubeconfig) = kubeconfig {
+        cmd.args(["--kubeconfig", kubeconfig]);
+    }
+    let mut child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait().ok()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return None;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    };
+    if !status.success() {
+        return None;
+    }
+    let mut output = String::new();
+    child.stdout.take()?.read_to_string(&mut output).ok()?;
+    let context = output.trim();
+    (!context.is_empty()).then(|| context.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn wildcard_patterns() {
+        assert!(wildcard_match("*prod*", "eks-prod-eu"));
+        assert!(wildcard_match("*prod*", "prod"));
+        assert!(wildcard_match("kind-*", "kind-dev"));
+        assert!(wildcard_match("minikube", "minikube"));
+        assert!(wildcard_match(
+            "arn:*:cluster/live-*",
+            "arn:aws:eks:eu:1:cluster/live-a"
+        ));
+        assert!(!wildcard_match("kind-*", "prod-kind-x"));
+        assert!(!wildcard_match("minikube", "minikube-2"));
+        assert!(!wildcard_match("a*a", "a"));
+    }
+
+    #[test]
+    fn classification_and_adjustment() {
+        let config = KubeContextConfig::default();
+        assert_eq!(
+            classify(&config, "gke_prod_cluster"),
+            Some(ContextClass::Production)
+        );
+        assert_eq!(
+            classify(&config, "kind-dev"),
+            Some(ContextClass::Development)
+        );
+        assert_eq!(classify(&config, "staging"), None);
+
+        assert_eq!(
+            ContextClass::Production.adjust(Severity::High),
+            Severity::Critical
+        );
+        assert_eq!(
+            ContextClass::Production.adjust(Severity::Medium),
+            Severity::High
+        );
+        assert_eq!(
+            ContextClass::Development.adjust(Severity::High),
+            Severity::Medium
+        );
+        assert_eq!(
+            ContextClass::Development.adjust(Severity::Critical),
+            Severity::Critical
+        );
+    }
+
+    #[test]
+    fn explicit_context_flags_drive_adjustment() {
+        let config = KubeContextConfig {
+            enabled: true,
+            ..KubeContextConfig::default()
+        };
+        let adjustment = adjust_severity(
+            &config,
+            "kubectl --context=prod-eu drain node-1",
+            Some("kubernetes.kubectl"),
+            Some(Severity::High),
+        )
+        .expect("production context escalates");
+        assert_eq!(adjustment.to, Severity::Critical);
+        assert!(adjustment.notice().contains("'prod-eu' is production"));
+
+        let adjustment = adjust_severity(
+            &config,
+            "helm uninstall web --kube-context kind-local",
+            Some("kubernetes.helm"),
+            Some(Severity::High),
+        )
+        .expect("development context relaxes");
+        assert_eq!(adjustment.to, Severity::Medium);
+
+        assert!(
+            adjust_severity(
+                &config,
+                "kubectl --context prod delete ns x",
+                Some("core.git"),
+                Some(Severity::High),
+            )
+            .is_none()
+        );
+        assert!(
+            adjust_severity(
+                &KubeContextConfig::default(),
+                "kubectl --context prod delete ns x",
+                Some("kubernetes.kubectl"),
+                Some(Severity::High),
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn command_target_parsing() {
+        assert_eq!(
+            CommandTarget::parse("kubectl --kubeconfig ~/.kube/live get pods"),
+            CommandTarget {
+                context: None,
+                kubeconfig: Some("~/.kube/live".to_string()),
+            }
+        );
+        assert_eq!(
+            CommandTarget::parse(r#"kubectl delete ns x --context "prod""#).context,
+            Some("prod".to_string())
+        );
+    }
+
+    #[test]
+    fn lookups_are_cached_including_failures() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("kube_context.json");
+        let calls = Cell::new(0);
+        let fetch = |result: Option<&str>| {
+            calls.set(calls.get() + 1);
+            result.map(str::to_string)
+        };
+
+        let first = cached_lookup(&path, "", 60, || fetch(Some("prod")));
+        let second = cached_lookup(&path, "", 60, || fetch(Some("other")));
+        assert_eq!(first.as_deref(), Some("prod"));
+        assert_eq!(second.as_deref(), Some("prod"));
+        assert_eq!(calls.get(), 1);
+
+        assert_eq!(cached_lookup(&path, "/tmp/kc", 60, || fetch(None)), None);
+        assert_eq!(
+            cached_lookup(&path, "/tmp/kc", 60, || fetch(Some("x"))),
+            None
+        );
+        assert_eq!(calls.get(), 2);
+
+        let refreshed = cached_lookup(&path, "", 0, || fetch(Some("kind-a")));
+        assert_eq!(refreshed.as_deref(), Some("kind-a"));
+        assert_eq!(calls.get(), 3);
+    }
+}
@@ -60,36 +60,73 @@
 //! }
 //! ```
 
+pub mod adapters;
 pub mod agent;
+pub mod aliases;
+pub mod allow_once_secret;
 pub mod allowlist;
+pub mod allowlist_bundle;
+pub mod anomaly;
+pub mod ansible;
 pub mod ast_matcher;
+pub mod audit;
+pub mod bench;
 pub mod cli;
 pub mod confidence;
 pub mod config;
 pub mod context;
+pub mod daemon;
+pub mod encoded;
+pub mod env_expand;
 pub mod error_codes;
+pub mod eval_cache;
 pub mod evaluator;
 pub mod exit_codes;
+pub mod external_policy;
+pub mod fingerprint;
 pub mod git;
+pub mod git_hook;
 pub mod heredoc;
 pub mod highlight;
 pub mod history;
 pub mod hook;
+pub mod http;
+pub mod i18n;
 pub mod interactive;
+pub mod kube_context;
 pub mod logging;
 pub mod mcp;
 pub mod normalize;
+pub mod notifications;
+pub mod otel;
 pub mod output;
 pub mod packs;
+pub mod pattern_test;
 pub mod pending_exceptions;
 pub mod perf;
+pub mod protected_paths;
+pub mod quick_allow;
+pub mod recommend;
+pub mod rego_policy;
+pub mod reload;
+pub mod remote;
+pub mod remote_exec;
+pub mod replay;
+pub mod review;
+pub mod risk;
+pub mod rm_preview;
 pub mod sarif;
 pub mod scan;
+pub mod scripts;
+pub mod sessions;
 pub mod simulate;
 pub mod stats;
 pub mod suggest;
 pub mod suggestions;
+pub mod suppression;
+pub mod top;
 pub mod trace;
+pub mod trust_ledger;
 pub mod update;
 
 // Re-export commonly used types
@@ -100,17 +137,18 @@ pub use allowlist::{
 pub use config::Config;
 pub use error_codes::{DcgError, ErrorCategory, ErrorCode, ErrorResponse};
 pub use evaluator::{
-    ConfidenceResult, DetailedEvaluationResult, EvaluationDecision, EvaluationResult,
-    LegacyDestructivePattern, LegacySafePattern, MatchSource, MatchSpan, PatternMatch,
-    apply_confidence_scoring, evaluate_command, evaluate_command_with_deadline,
+    BatchEvaluator, ConfidenceResult, DetailedEvaluationResult, EvaluationDecision,
+    EvaluationResult, LegacyDestructivePattern, LegacySafePattern, MatchSource, MatchSpan,
+    PatternMatch, apply_confidence_scoring, evaluate_command, evaluate_command_with_deadline,
     evaluate_command_with_pack_order, evaluate_command_with_pack_order_at_path,
     evaluate_command_with_pack_order_deadline, evaluate_command_with_pack_order_deadline_at_path,
-    evaluate_detailed, evaluate_detailed_with_allowlists,
+    evaluate_commands, evaluate_detailed, evaluate_detailed_with_allowlists,
 };
 pub use exit_codes::{
     EXIT_CONFIG_ERROR, EXIT_DENIED, EXIT_IO_ERROR, EXIT_PARSE_ERROR, EXIT_SUCCESS, EXIT_WARNING,
     ToExitCode, exit_with, to_exit_code,
 };
+pub use fingerprint::ConfigFingerprint;
 pub use hook::{HookInput, HookOutput, HookResult, HookSpecificOutput};
 pub use packs::external::{ExternalPack, parse_pack_file, parse_pack_string};
 pub use packs::{Pack, PackId, PackRegistry, PatternSuggestion, Platform};
@@ -1,14 +1,17 @@
 //! Structured logging for allow/deny decisions.
 //!
 //! This module provides structured logging for command evaluation decisions,
-//! supporting both text and JSON output formats with optional redaction.
+//! supporting both text and JSON output formats with optional redaction, plus
+//! a rotating newline-delimited JSON sink (`[logging.json]`) for SIEM ingestion
+//! and syslog/journald forwarding of deny/warn events (`[logging.syslog]`).
 
 use serde::{Deserialize, Serialize};
-use std::fs::{File, OpenOptions};
-use std::io::{BufWriter, Write};
-use std::path::Path;
+use std::fmt::Write as FmtWrite;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 
 use crate::evaluator::{EvaluationDecision, EvaluationResult};
 use crate::packs::DecisionMode;
@@ -31,6 +34,12 @@ pub struct LoggingConfig {
     pub redaction: RedactionConfig,
     /// Events to log.
     pub events: LogEventFilter,
+    /// Rotating NDJSON sink (independent of `enabled`/`file`).
+    pub json: JsonSinkConfig,
+    /// Syslog/journald forwarding of deny/warn events.
+    pub syslog: SyslogConfig,
+    /// OpenTelemetry spans and decision counters (requires the `otel` feature).
+    pub otel: OtelConfig,
 }
 
 impl Default for LoggingConfig {
@@ -41,6 +50,9 @@ impl Default for LoggingConfig {
             format: LogFormat::Text,
             redaction: RedactionConfig::default(),
             events: LogEventFilter::default(),
+            json: JsonSinkConfig::default(),
+            syslog: SyslogConfig::default(),
+            otel: OtelConfig::default(),
         }
     }
 }
@@ -102,6 +114,165 @@ impl Default for LogEventFilter {
     }
 }
 
+/// Default file name for the NDJSON decision sink.
+pub const DEFAULT_JSON_SINK_FILENAME: &str = "decisions.ndjson";
+
+/// `[logging.json]` configuration: newline-delimited JSON with rotation.
+///
+/// The active segment is rotated to `<path>.1` (or `<path>.1.gz` when
+/// `compress` is set) once it exceeds `max_size_mb` or its first entry is
+/// older than `max_age_hours`; older segments shift up and anything beyond
+/// `max_files` is dropped.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JsonSinkConfig {
+    /// Whether the NDJSON sink is enabled.
+    pub enabled: bool,
+    /// Path to the active segment. Supports ~ expansion.
+    /// Defaults to `decisions.ndjson` in the dcg config directory.
+    pub path: Option<String>,
+    /// Rotate once the active segment would exceed this size (0 disables).
+    pub max_size_mb: u64,
+    /// Rotate once the active segment's first entry is this old (0 disables).
+    pub max_age_hours: u64,
+    /// Number of rotated segments to keep.
+    pub max_files: usize,
+    /// Gzip rotated segments.
+    pub compress: bool,
+}
+
+impl Default for JsonSinkConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: None,
+            max_size_mb: 10,
+            max_age_hours: 24,
+            max_files: 7,
+            compress: true,
+        }
+    }
+}
+
+impl JsonSinkConfig {
+    /// Resolve the active segment path (explicit path or the default location).
+    #[must_use]
+    pub fn resolved_path(&self) -> Option<PathBuf> {
+        match self.path.as_deref().map(str::trim) {
+            Some(path) if !path.is_empty() => Some(PathBuf::from(expand_tilde(path))),
+            _ => dirs::config_dir().map(|dir| dir.join("dcg").join(DEFAULT_JSON_SINK_FILENAME)),
+        }
+    }
+}
+
+/// Where `[logging.syslog]` forwards events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SyslogTarget {
+    /// RFC 5424 syslog over the network.
+    #[default]
+    Syslog,
+    /// The local systemd journal (native protocol, structured fields).
+    Journald,
+}
+
+/// Network transport for RFC 5424 syslog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SyslogTransport {
+    #[default]
+    Udp,
+    /// TCP with octet-counting framing (RFC 6587).
+    Tcp,
+}
+
+/// `[logging.syslog]` configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SyslogConfig {
+    /// Whether forwarding is enabled.
+    pub enabled: bool,
+    /// `syslog` (RFC 5424) or `journald`.
+    pub target: SyslogTarget,
+    /// Transport for `syslog`: `udp` or `tcp`.
+    pub transport: SyslogTransport,
+    /// Collector address for `syslog` (`host:port`).
+    pub address: String,
+    /// Syslog facility name (`user`, `auth`, `local0`..`local7`, ...).
+    pub facility: String,
+    /// APP-NAME / `SYSLOG_IDENTIFIER`.
+    pub app_name: String,
+    /// Forward denied commands.
+    pub deny: bool,
+    /// Forward warn-mode matches.
+    pub warn: bool,
+    /// Only forward these pattern severities (empty = all).
+    pub severities: Vec<String>,
+}
+
+impl Default for SyslogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            target: SyslogTarget::Syslog,
+            transport: SyslogTransport::Udp,
+            address: "127.0.0.1:514".to_string(),
+            facility: "user".to_string(),
+            app_name: "dcg".to_string(),
+            deny: true,
+            warn: true,
+            severities: Vec::new(),
+        }
+    }
+}
+
+/// `[logging.otel]` configuration.
+///
+/// Spans and metrics are sent over OTLP/HTTP (protobuf). The standard
+/// `OTEL_EXPORTER_OTLP_ENDPOINT` environment variable overrides `endpoint`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OtelConfig {
+    /// Whether export is enabled.
+    pub enabled: bool,
+    /// Collector base URL; `/v1/traces` and `/v1/metrics` are appended.
+    pub endpoint: String,
+    /// `service.name` resource attribute.
+    pub service_name: String,
+    /// Per-export timeout. Export happens when the hook exits.
+    pub timeout_ms: u64,
+    /// Export per-stage evaluation spans.
+    pub traces: bool,
+    /// Export decision counters and the evaluation latency histogram.
+    pub metrics: bool,
+}
+
+impl Default for OtelConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: "http://localhost:4318".to_string(),
+            service_name: "dcg".to_string(),
+            timeout_ms: 500,
+            traces: true,
+            metrics: true,
+        }
+    }
+}
+
+impl OtelConfig {
+    /// Collector base URL: `OTEL_EXPORTER_OTLP_ENDPOINT` when set, else `endpoint`.
+    #[must_use]
+    pub fn resolved_endpoint(&self) -> String {
+        std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT")
+            .ok()
+            .filter(|value| !value.trim().is_empty())
+            .unwrap_or_else(|| self.endpoint.clone())
+            .trim_end_matches('/')
+            .to_string()
+    }
+}
+
 // ============================================================================
 // Log Entry
 // ============================================================================
@@ -122,6 +293,8 @@ pub struct LogEntry {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rule_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub severity: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub reason: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub elapsed_us: Option<u64>,
@@ -129,6 +302,8 @@ pub struct LogEntry {
     pub budget_skip: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub allowlist_layer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
 }
 
 impl LogEntry {
@@ -180,6 +355,12 @@ impl LogEntry {
                     (pack, pattern, rule, r)
                 });
 
+        let severity = result
+            .pattern_info
+            .as_ref()
+            .and_then(|pm| pm.severity)
+            .map(|sev| sev.label().to_string());
+
         let allowlist_layer = result
             .allowlist_override
             .as_ref()
@@ -197,6 +378,7 @@ impl LogEntry {
             pack_id,
             pattern_name,
             rule_id,
+            severity,
             reason,
             elapsed_us,
             budget_skip: if result.skipped_due_to_budget {
@@ -205,9 +387,17 @@ impl LogEntry {
                 None
             },
             allowlist_layer,
+            cwd: None,
         }
     }
 
+    /// Attach the working directory the command was evaluated in.
+    #[must_use]
+    pub fn with_cwd(mut self, cwd: Option<&str>) -> Self {
+        self.cwd = cwd.map(String::from);
+        self
+    }
+
     /// Format as text log line.
     #[must_use]
     pub fn format_text(&self) -> String {
@@ -307,18 +497,455 @@ impl DecisionLogger {
     /// filter regardless of mode. Log mode means "don't block, just observe" - but
     /// the pattern still matched, so users who enable deny logging should see it.
     const fn should_log(&self, result: &EvaluationResult, mode: DecisionMode) -> bool {
-        match result.decision {
-            EvaluationDecision::Allow => self.config.events.allow,
-            EvaluationDecision::Deny => match mode {
-                DecisionMode::Warn => self.config.events.warn,
-                // Log mode: pattern matched but we're just observing. Use deny filter
-                // since a destructive pattern did match, even if we're not blocking.
-                DecisionMode::Deny | DecisionMode::Log => self.config.events.deny,
+        event_enabled(&self.config.events, result, mode)
+    }
+}
+
+const fn event_enabled(
+    events: &LogEventFilter,
+    result: &EvaluationResult,
+    mode: DecisionMode,
+) -> bool {
+    match result.decision {
+        EvaluationDecision::Allow => events.allow,
+        EvaluationDecision::Deny => match mode {
+            DecisionMode::Warn => events.warn,
+            // Log mode: pattern matched but we're just observing. Use deny filter
+            // since a destructive pattern did match, even if we're not blocking.
+            DecisionMode::Deny | DecisionMode::Log => events.deny,
+        },
+    }
+}
+
+// ============================================================================
+// Rotating JSON Sink
+// ============================================================================
+
+/// Appends decision entries as NDJSON to a rotating file set.
+///
+/// Each append takes an exclusive lock on `<path>.lock` so concurrent hook
+/// processes never interleave lines or rotate the same segment twice.
+pub struct JsonSink {
+    path: PathBuf,
+    max_bytes: u64,
+    max_age: Option<Duration>,
+    max_files: usize,
+    compress: bool,
+    events: LogEventFilter,
+    redaction: RedactionConfig,
+}
+
+impl JsonSink {
+    /// Create a sink from configuration, or `None` when `[logging.json]` is disabled.
+    #[must_use]
+    pub fn new(config: &LoggingConfig) -> Option<Self> {
+        let json = &config.json;
+        if !json.enabled {
+            return None;
+        }
+        Some(Self {
+            path: json.resolved_path()?,
+            max_bytes: json.max_size_mb.saturating_mul(1024 * 1024),
+            max_age: if json.max_age_hours > 0 {
+                Some(Duration::from_secs(json.max_age_hours.saturating_mul(3600)))
+            } else {
+                None
             },
+            max_files: json.max_files,
+            compress: json.compress,
+            events: config.events.clone(),
+            redaction: config.redaction.clone(),
+        })
+    }
+
+    /// Path of the active segment.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Log an evaluation result (subject to the `[logging.events]` filter).
+    pub fn log(
+        &self,
+        result: &EvaluationResult,
+        command: &str,
+        mode: DecisionMode,
+        elapsed_us: Option<u64>,
+        cwd: Option<&str>,
+    ) {
+        if !event_enabled(&self.events, result, mode) {
+            return;
+        }
+        let entry = LogEntry::from_result(result, command, None, mode, &self.redaction, elapsed_us)
+            .with_cwd(cwd);
+        let _ = self.append(&entry.format_json());
+    }
+
+    /// Append one line, rotating the active segment first if it is due.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the lock file, segment, or rotation cannot be written.
+    pub fn append(&self, line: &str) -> std::io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let lock = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(false)
+            .open(with_suffix(&self.path, ".lock"))?;
+        fs2::FileExt::lock_exclusive(&lock)?;
+
+        let result = self.append_locked(line);
+        let _ = fs2::FileExt::unlock(&lock);
+        result
+    }
+
+    fn append_locked(&self, line: &str) -> std::io::Result<()> {
+        let incoming = u64::try_from(line.len())
+            .unwrap_or(u64::MAX)
+            .saturating_add(1);
+        if self.needs_rotation(incoming) {
+            self.rotate()?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{line}")
+    }
+
+    fn needs_rotation(&self, incoming: u64) -> bool {
+        let Ok(meta) = fs::metadata(&self.path) else {
+            return false;
+        };
+        if meta.len() == 0 {
+            return false;
+        }
+        if self.max_bytes > 0 && meta.len().saturating_add(incoming) > self.max_bytes {
+            return true;
+        }
+        self.max_age.is_some_and(|max_age| {
+            segment_started_at(&self.path)
+                .and_then(|started| SystemTime::now().duration_since(started).ok())
+                .is_some_and(|age| age >= max_age)
+        })
+    }
+
+    fn rotate(&self) -> std::io::Result<()> {
+        if self.max_files == 0 {
+            return fs::remove_file(&self.path);
+        }
+
+        // Shift existing segments up by one; the rename onto `.max_files`
+        // replaces (drops) the oldest segment.
+        for index in (1..self.max_files).rev() {
+            for suffix in ["", ".gz"] {
+                let from = with_suffix(&self.path, &format!(".{index}{suffix}"));
+                if from.exists() {
+                    let to = with_suffix(&self.path, &format!(".{}{suffix}", index + 1));
+                    fs::rename(from, to)?;
+                }
+            }
+        }
+
+        if self.compress {
+            gzip_segment(&self.path, &with_suffix(&self.path, ".1.gz"))
+        } else {
+            fs::rename(&self.path, with_suffix(&self.path, ".1"))
         }
     }
 }
 
+/// Timestamp of the first entry in a segment (used for age-based rotation).
+fn segment_started_at(path: &Path) -> Option<SystemTime> {
+    let mut first = String::new();
+    BufReader::new(File::open(path).ok()?)
+        .read_line(&mut first)
+        .ok()?;
+    let value: serde_json::Value = serde_json::from_str(first.trim()).ok()?;
+    let timestamp = value.get("timestamp")?.as_str()?;
+    chrono::DateTime::parse_from_rfc3339(timestamp)
+        .ok()
+        .map(SystemTime::from)
+}
+
+fn gzip_segment(src: &Path, dest: &Path) -> std::io::Result<()> {
+    let tmp = with_suffix(dest, ".tmp");
+    {
+        let mut input = File::open(src)?;
+        let mut encoder =
+            flate2::write::GzEncoder::new(File::create(&tmp)?, flate2::Compression::default());
+        std::io::copy(&mut input, &mut encoder)?;
+        encoder.finish()?;
+    }
+    fs::rename(&tmp, dest)?;
+    fs::remove_file(src)
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut raw = path.as_os_str().to_os_string();
+    raw.push(suffix);
+    PathBuf::from(raw)
+}
+
+// ============================================================================
+// Syslog / journald Forwarding
+// ============================================================================
+
+/// Structured-data ID for RFC 5424 messages (32473 is the documentation PEN).
+const SYSLOG_SD_ID: &str = "dcg@32473";
+
+/// Native-protocol socket of the systemd journal.
+const JOURNALD_SOCKET: &str = "/run/systemd/journal/socket";
+
+/// Upper bound on connecting/sending so a dead collector can't stall the hook.
+const SYSLOG_IO_TIMEOUT: Duration = Duration::from_millis(250);
+
+/// Forwards deny/warn decisions to syslog (RFC 5424) or journald.
+///
+/// Delivery is best-effort: errors are swallowed so forwarding never changes
+/// the hook's decision.
+pub struct SyslogForwarder {
+    config: SyslogConfig,
+    facility: u8,
+    redaction: RedactionConfig,
+}
+
+impl SyslogForwarder {
+    /// Create a forwarder, or `None` when `[logging.syslog]` is disabled.
+    #[must_use]
+    pub fn new(config: &LoggingConfig) -> Option<Self> {
+        if !config.syslog.enabled {
+            return None;
+        }
+        Some(Self {
+            facility: syslog_facility_code(&config.syslog.facility).unwrap_or(1),
+            config: config.syslog.clone(),
+            redaction: config.redaction.clone(),
+        })
+    }
+
+    /// Forward a denied evaluation if its mode and severity are selected.
+    pub fn forward(
+        &self,
+        result: &EvaluationResult,
+        command: &str,
+        mode: DecisionMode,
+        cwd: Option<&str>,
+    ) {
+        if result.decision != EvaluationDecision::Deny || !self.should_forward(result, mode) {
+            return;
+        }
+        let entry =
+            LogEntry::from_result(result, command, None, mode, &self.redaction, None).with_cwd(cwd);
+        let _ = match self.config.target {
+            SyslogTarget::Syslog => self.send_syslog(&entry),
+            SyslogTarget::Journald => self.send_journald(&entry),
+        };
+    }
+
+    fn should_forward(&self, result: &EvaluationResult, mode: DecisionMode) -> bool {
+        let mode_enabled = match mode {
+            DecisionMode::Deny => self.config.deny,
+            DecisionMode::Warn => self.config.warn,
+            DecisionMode::Log => false,
+        };
+        if !mode_enabled {
+            return false;
+        }
+        if self.config.severities.is_empty() {
+            return true;
+        }
+        let severity = result
+            .pattern_info
+            .as_ref()
+            .and_then(|pm| pm.severity)
+            .map_or("high", |sev| sev.label());
+        self.config
+            .severities
+            .iter()
+            .any(|s| s.trim().eq_ignore_ascii_case(severity))
+    }
+
+    fn send_syslog(&self, entry: &LogEntry) -> std::io::Result<()> {
+        use std::net::{TcpStream, ToSocketAddrs, UdpSocket};
+
+        let message = format_rfc5424(entry, self.facility, &self.config.app_name);
+        let addr = self
+            .config
+            .address
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| std::io::Error::other("syslog address did not resolve"))?;
+
+        match self.config.transport {
+            SyslogTransport::Udp => {
+                let bind = if addr.is_ipv6() {
+                    "[::]:0"
+                } else {
+                    "0.0.0.0:0"
+                };
+                let socket = UdpSocket::bind(bind)?;
+                socket.set_write_timeout(Some(SYSLOG_IO_TIMEOUT))?;
+                socket.send_to(message.as_bytes(), addr)?;
+            }
+            SyslogTransport::Tcp => {
+                let mut stream = TcpStream::connect_timeout(&addr, SYSLOG_IO_TIMEOUT)?;
+                stream.set_write_timeout(Some(SYSLOG_IO_TIMEOUT))?;
+                write!(stream, "{} {message}", message.len())?;
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    fn send_journald(&self, entry: &LogEntry) -> std::io::Result<()> {
+        let socket = std::os::unix::net::UnixDatagram::unbound()?;
+        socket.set_write_timeout(Some(SYSLOG_IO_TIMEOUT))?;
+        socket.send_to(
+            &format_journald(entry, &self.config.app_name),
+            JOURNALD_SOCKET,
+        )?;
+        Ok(())
+    }
+
+    #[cfg(not(unix))]
+    fn send_journald(&self, _entry: &LogEntry) -> std::io::Result<()> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "journald forwarding requires a Unix platform",
+        ))
+    }
+}
+
+/// Map a facility name to its RFC 5424 numeric code.
+fn syslog_facility_code(name: &str) -> Option<u8> {
+    let code = match name.trim().to_ascii_lowercase().as_str() {
+        "kern" => 0,
+        "user" => 1,
+        "daemon" => 3,
+        "auth" => 4,
+        "syslog" => 5,
+        "authpriv" => 10,
+        "local0" => 16,
+        "local1" => 17,
+        "local2" => 18,
+        "local3" => 19,
+        "local4" => 20,
+        "local5" => 21,
+        "local6" => 22,
+        "local7" => 23,
+        _ => return None,
+    };
+    Some(code)
+}
+
+/// Syslog severity for a log entry: warn-mode is `warning`, denials scale
+/// with the pattern severity.
+fn syslog_severity_code(entry: &LogEntry) -> u8 {
+    if entry.decision == "warn" {
+        return 4;
+    }
+    match entry.severity.as_deref() {
+        Some("critical") => 2,
+        Some("medium") => 4,
+        Some("low") => 5,
+        _ => 3,
+    }
+}
+
+fn syslog_message_text(entry: &LogEntry) -> String {
+    let rule = entry.rule_id.as_deref().unwrap_or("config");
+    match entry.reason.as_deref() {
+        Some(reason) => format!("{} {rule}: {reason} ({})", entry.decision, entry.command),
+        None => format!("{} {rule}: {}", entry.decision, entry.command),
+    }
+}
+
+/// Escape an RFC 5424 SD-PARAM value (`"`, `\`, and `]` must be escaped).
+fn escape_sd_value(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '"' | '\\' | ']') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+pub(crate) fn local_hostname() -> String {
+    std::env::var("HOSTNAME")
+        .ok()
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .map(|h| h.trim().to_string())
+        .filter(|h| !h.is_empty() && h.is_ascii() && !h.contains(' '))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// Format an RFC 5424 message with dcg fields as structured data.
+fn format_rfc5424(entry: &LogEntry, facility: u8, app_name: &str) -> String {
+    let pri = u16::from(facility) * 8 + u16::from(syslog_severity_code(entry));
+    let mut params = vec![("decision", entry.decision.as_str())];
+    for (key, value) in [
+        ("pack", entry.pack_id.as_deref()),
+        ("pattern", entry.pattern_name.as_deref()),
+        ("severity", entry.severity.as_deref()),
+        ("cwd", entry.cwd.as_deref()),
+    ] {
+        if let Some(value) = value {
+            params.push((key, value));
+        }
+    }
+    let mut sd = String::new();
+    for (key, value) in &params {
+        let _ = write!(sd, " {key}=\"{}\"", escape_sd_value(value));
+    }
+
+    format!(
+        "<{pri}>1 {} {} {app_name} {} {} [{SYSLOG_SD_ID}{sd}] {}",
+        entry.timestamp,
+        local_hostname(),
+        std::process::id(),
+        entry.decision,
+        syslog_message_text(entry),
+    )
+}
+
+/// Encode a journald native-protocol datagram with `DCG_*` fields.
+fn format_journald(entry: &LogEntry, app_name: &str) -> Vec<u8> {
+    let mut out = Vec::new();
+    let priority = syslog_severity_code(entry).to_string();
+    let message = syslog_message_text(entry);
+    let fields = [
+        ("MESSAGE", Some(message.as_str())),
+        ("PRIORITY", Some(priority.as_str())),
+        ("SYSLOG_IDENTIFIER", Some(app_name)),
+        ("DCG_DECISION", Some(entry.decision.as_str())),
+        ("DCG_COMMAND", Some(entry.command.as_str())),
+        ("DCG_PACK_ID", entry.pack_id.as_deref()),
+        ("DCG_PATTERN", entry.pattern_name.as_deref()),
+        ("DCG_SEVERITY", entry.severity.as_deref()),
+        ("DCG_CWD", entry.cwd.as_deref()),
+    ];
+    for (key, value) in fields {
+        let Some(value) = value else { continue };
+        out.extend_from_slice(key.as_bytes());
+        if value.contains('\n') {
+            // Multi-line values use the length-prefixed binary form.
+            out.push(b'\n');
+            out.extend_from_slice(&u64::try_from(value.len()).unwrap_or(u64::MAX).to_le_bytes());
+        } else {
+            out.push(b'=');
+        }
+        out.extend_from_slice(value.as_bytes());
+        out.push(b'\n');
+    }
+    out
+}
+
 // ============================================================================
 // Helpers
 // ============================================================================
@@ -1374,4 +2001,160 @@ mod tests {
         assert!(content.contains(r#""event":"issued""#));
         assert!(content.contains(r#""short_code":"test""#));
     }
+
+    fn test_json_sink(path: PathBuf, max_bytes: u64, max_files: usize, compress: bool) -> JsonSink {
+        JsonSink {
+            path,
+            max_bytes,
+            max_age: None,
+            max_files,
+            compress,
+            events: LogEventFilter::default(),
+            redaction: RedactionConfig::default(),
+        }
+    }
+
+    #[test]
+    fn json_sink_rotates_by_size_and_keeps_max_files() {
+        let dir = tempfile::TempDir::new().expect("tempdir");
+        let path = dir.path().join("decisions.ndjson");
+        let sink = test_json_sink(path.clone(), 40, 2, false);
+
+        for i in 0..4 {
+            sink.append(&format!(
+                r#"{{"timestamp":"2026-01-11T12:00:00Z","n":{i}}}"#
+            ))
+            .unwrap();
+        }
+
+        let active = std::fs::read_to_string(&path).unwrap();
+        assert!(active.contains(r#""n":3"#));
+        assert!(!active.contains(r#""n":2"#));
+        let first = std::fs::read_to_string(with_suffix(&path, ".1")).unwrap();
+        assert!(first.contains(r#""n":2"#));
+        let second = std::fs::read_to_string(with_suffix(&path, ".2")).unwrap();
+        assert!(second.contains(r#""n":1"#));
+        assert!(!with_suffix(&path, ".3").exists());
+    }
+
+    #[test]
+    fn json_sink_compresses_rotated_segments() {
+        use std::io::Read;
+
+        let dir = tempfile::TempDir::new().expect("tempdir");
+        let path = dir.path().join("decisions.ndjson");
+        let sink = test_json_sink(path.clone(), 40, 3, true);
+
+        sink.append(r#"{"timestamp":"2026-01-11T12:00:00Z","n":0}"#)
+            .unwrap();
+        sink.append(r#"{"timestamp":"2026-01-11T12:00:00Z","n":1}"#)
+            .unwrap();
+
+        let gz = File::open(with_suffix(&path, ".1.gz")).unwrap();
+        let mut rotated = String::new();
+        flate2::read::GzDecoder::new(gz)
+            .read_to_string(&mut rotated)
+            .unwrap();
+        assert!(rotated.contains(r#""n":0"#));
+        assert!(!with_suffix(&path, ".1").exists());
+    }
+
+    #[test]
+    fn json_sink_rotates_by_age_of_first_entry() {
+        let dir = tempfile::TempDir::new().expect("tempdir");
+        let path = dir.path().join("decisions.ndjson");
+        let mut sink = test_json_sink(path.clone(), 0, 3, false);
+        sink.max_age = Some(Duration::from_secs(3600));
+
+        std::fs::write(&path, "{\"timestamp\":\"2020-01-01T00:00:00Z\"}\n").unwrap();
+        sink.append(r#"{"timestamp":"2026-01-11T12:00:00Z"}"#)
+            .unwrap();
+
+        assert!(with_suffix(&path, ".1").exists());
+        let active = std::fs::read_to_string(&path).unwrap();
+        assert!(!active.contains("2020-01-01"));
+    }
+
+    #[test]
+    fn json_sink_disabled_by_default() {
+        assert!(JsonSink::new(&LoggingConfig::default()).is_none());
+
+        let mut config = LoggingConfig::default();
+        config.json.enabled = true;
+        config.json.path = Some("/tmp/dcg-test/decisions.ndjson".to_string());
+        let sink = JsonSink::new(&config).expect("sink enabled");
+        assert_eq!(sink.path(), Path::new("/tmp/dcg-test/decisions.ndjson"));
+    }
+
+    fn deny_entry() -> LogEntry {
+        LogEntry {
+            timestamp: "2026-01-11T12:00:00Z".to_string(),
+            decision: "deny".to_string(),
+            mode: "deny".to_string(),
+            command: "git reset --hard".to_string(),
+            normalized_command: None,
+            pack_id: Some("core.git".to_string()),
+            pattern_name: Some("reset-hard".to_string()),
+            rule_id: Some("core.git:reset-hard".to_string()),
+            severity: Some("critical".to_string()),
+            reason: Some("destroys \"uncommitted\" work]".to_string()),
+            elapsed_us: None,
+            budget_skip: None,
+            allowlist_layer: None,
+            cwd: Some("/work/repo".to_string()),
+        }
+    }
+
+    #[test]
+    fn syslog_facility_names_map_to_codes() {
+        assert_eq!(syslog_facility_code("user"), Some(1));
+        assert_eq!(syslog_facility_code("LOCAL0"), Some(16));
+        assert_eq!(syslog_facility_code("bogus"), None);
+    }
+
+    #[test]
+    fn rfc5424_message_has_priority_and_structured_data() {
+        let message = format_rfc5424(&deny_entry(), 16, "dcg");
+        // local0 (16) * 8 + crit (2)
+        assert!(message.starts_with("<130>1 2026-01-11T12:00:00Z "));
+        assert!(message.contains(" dcg "));
+        assert!(message.contains(r#"[dcg@32473 decision="deny" pack="core.git""#));
+        assert!(message.contains(r#"severity="critical" cwd="/work/repo"]"#));
+        assert!(message.ends_with(
+            r#"deny core.git:reset-hard: destroys "uncommitted" work] (git reset --hard)"#
+        ));
+    }
+
+    #[test]
+    fn sd_values_are_escaped() {
+        assert_eq!(escape_sd_value(r#"a"b\c]d"#), r#"a\"b\\c\]d"#);
+    }
+
+    #[test]
+    fn warn_entries_use_warning_severity() {
+        let mut entry = deny_entry();
+        entry.decision = "warn".to_string();
+        assert_eq!(syslog_severity_code(&entry), 4);
+    }
+
+    #[test]
+    fn journald_datagram_carries_dcg_fields() {
+        let datagram = format_journald(&deny_entry(), "dcg");
+        let text = String::from_utf8_lossy(&datagram);
+        assert!(text.contains("PRIORITY=2\n"));
+        assert!(text.contains("SYSLOG_IDENTIFIER=dcg\n"));
+        assert!(text.contains("DCG_PACK_ID=core.git\n"));
+        assert!(text.contains("DCG_PATTERN=reset-hard\n"));
+        assert!(text.contains("DCG_SEVERITY=critical\n"));
+        assert!(text.contains("DCG_CWD=/work/repo\n"));
+    }
+
+    #[test]
+    fn journald_multiline_values_use_binary_form() {
+        let mut entry = deny_entry();
+        entry.command = "echo a\necho b".to_string();
+        let datagram = format_journald(&entry, "dcg");
+        let needle = b"DCG_COMMAND\n\x0d\0\0\0\0\0\0\0echo a\necho b\n";
+        assert!(datagram.windows(needle.len()).any(|w| w == needle));
+    }
 }
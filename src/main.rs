@@ -19,32 +19,49 @@
 
 use clap::Parser;
 use colored::Colorize;
+use destructive_command_guard::aliases::AliasTable;
+use destructive_command_guard::anomaly::{self, AnomalyStatus};
 use destructive_command_guard::cli::{self, Cli};
 // Exit codes are used by cli.rs for robot mode; main.rs uses them for hook mode errors
-use destructive_command_guard::config::Config;
+use destructive_command_guard::config::{Config, FailureClass};
+use destructive_command_guard::daemon;
+use destructive_command_guard::env_expand;
+use destructive_command_guard::eval_cache::{self, EvalCache};
 use destructive_command_guard::evaluator::{
-    EvaluationDecision, MatchSource, evaluate_command_with_pack_order_deadline_at_path,
+    EvaluationDecision, EvaluationResult, MatchSource,
+    evaluate_command_with_pack_order_deadline_at_path,
 };
 #[allow(unused_imports)]
 use destructive_command_guard::exit_codes::{EXIT_DENIED, EXIT_PARSE_ERROR, EXIT_SUCCESS};
+use destructive_command_guard::external_policy::{self, PolicyQuery};
+use destructive_command_guard::fingerprint::ConfigFingerprint;
 use destructive_command_guard::history::{
     CommandEntry, ENV_HISTORY_DB_PATH, HistoryDb, HistoryWriter, Outcome as HistoryOutcome,
+    ToolResult,
 };
 use destructive_command_guard::hook;
 use destructive_command_guard::load_default_allowlists;
+use destructive_command_guard::logging::{JsonSink, SyslogForwarder};
 use destructive_command_guard::normalize::normalize_command;
+use destructive_command_guard::notifications::{DenyEvent, Notifier};
+use destructive_command_guard::otel::{self, OtelExporter};
 use destructive_command_guard::packs::load_external_packs;
 #[cfg(test)]
 use destructive_command_guard::packs::pack_aware_quick_reject;
 use destructive_command_guard::packs::{DecisionMode, REGISTRY};
-use destructive_command_guard::pending_exceptions::{PendingExceptionStore, log_maintenance};
+use destructive_command_guard::pending_exceptions::{self, PendingExceptionStore, log_maintenance};
 use destructive_command_guard::perf::{Deadline, HOOK_EVALUATION_BUDGET};
+use destructive_command_guard::quick_allow::QuickAllowFilter;
+use destructive_command_guard::rego_policy::{RegoInput, RegoPolicy};
+use destructive_command_guard::remote;
+use destructive_command_guard::risk;
+use destructive_command_guard::rm_preview;
 use destructive_command_guard::sanitize_for_pattern_matching;
+use destructive_command_guard::suppression::{InlineSuppression, SuppressionVerdict};
 // Import HookInput for parsing stdin JSON in hook mode
-#[cfg(test)]
 use destructive_command_guard::hook::HookInput;
-#[cfg(test)]
 use std::borrow::Cow;
+use std::cell::OnceCell;
 use std::collections::HashSet;
 use std::io::{self, IsTerminal};
 use std::path::PathBuf;
@@ -82,7 +99,9 @@ fn history_db_path(config: &destructive_command_guard::config::HistoryConfig) ->
     config.expanded_database_path()
 }
 
+#[allow(clippy::too_many_arguments)]
 fn build_history_entry(
+    hook_input: &HookInput,
     command: &str,
     working_dir: &str,
     outcome: HistoryOutcome,
@@ -90,6 +109,7 @@ fn build_history_entry(
     pack_id: Option<&str>,
     pattern_name: Option<&str>,
     allowlist_layer: Option<&str>,
+    config_fingerprint: &ConfigFingerprint,
 ) -> CommandEntry {
     let eval_duration_us = u64::try_from(eval_duration.as_micros()).unwrap_or(u64::MAX);
 
@@ -102,10 +122,90 @@ fn build_history_entry(
         pattern_name: pattern_name.map(str::to_string),
         eval_duration_us,
         allowlist_layer: allowlist_layer.map(str::to_string),
+        session_id: hook_input.session_id().map(str::to_string),
+        transcript_path: hook_input.transcript_path().map(str::to_string),
+        tool_use_id: hook_input.tool_use_id().map(str::to_string),
+        config_fingerprint: Some(config_fingerprint.to_string()),
         ..Default::default()
     }
 }
 
+/// `PostToolUse`: attach the command's exit code and stderr to the history
+/// entry written when it was checked.
+fn record_post_tool_use(config: &Config, hook_input: &HookInput) {
+    if !config.history.enabled {
+        return;
+    }
+    let Some(command) = hook::extract_command(hook_input) else {
+        return;
+    };
+    let Some(db) = HistoryDb::try_open(history_db_path(&config.history)) else {
+        return;
+    };
+    let result = ToolResult {
+        tool_use_id: hook_input.tool_use_id().map(str::to_string),
+        session_id: hook_input.session_id().map(str::to_string),
+        command,
+        exit_code: hook_input.tool_exit_code(),
+        stderr: hook_input.tool_stderr(),
+    };
+    // Fail open: a missing link only loses the exit status.
+    let _ = destructive_command_guard::history::record_tool_result(&db, &config.history, result);
+}
+
+/// `Stop`: checkpoint the history WAL and summarize the session.
+fn summarize_session(config: &Config, hook_input: &HookInput) {
+    if !config.history.enabled {
+        return;
+    }
+    let Some(db) = HistoryDb::try_open(history_db_path(&config.history)) else {
+        return;
+    };
+    let _ = db.checkpoint();
+    let Some(session_id) = hook_input.session_id() else {
+        return;
+    };
+    if let Ok(Some(stat)) = db.session_stat(session_id) {
+        if stat.total > 0 {
+            hook::output_session_summary(&stat);
+        }
+    }
+}
+
+/// Count recent denials for this agent; `None` unless `[anomaly]` is enabled
+/// and the decision could be escalated or annotated.
+fn detect_anomaly(
+    config: &Config,
+    db: Option<&HistoryDb>,
+    mode: DecisionMode,
+    working_dir: &str,
+    session_id: Option<&str>,
+) -> Option<AnomalyStatus> {
+    if !config.anomaly.is_active() || mode == DecisionMode::Log {
+        return None;
+    }
+    anomaly::detect(
+        db?,
+        &config.anomaly,
+        HISTORY_AGENT_TYPE,
+        working_dir,
+        session_id,
+        chrono::Utc::now(),
+    )
+}
+
+/// Open the history database once for the readers of a hook invocation
+/// (anomaly detection and session risk scoring); `None` unless history and at
+/// least one of them is enabled.
+///
+/// The history writer keeps its own handle on its background thread.
+fn open_history_reader(config: &Config) -> Option<HistoryDb> {
+    if !config.history.enabled || !(config.anomaly.is_active() || config.risk.is_active()) {
+        return None;
+    }
+    HistoryDb::try_open(history_db_path(&config.history))
+}
+
 fn install_history_shutdown_handler(
     handle: destructive_command_guard::history::HistoryFlushHandle,
 ) {
@@ -248,14 +348,21 @@ fn main() {
 
     // Load configuration
     let config = Config::load();
+    hook::init_structured_deny(config.output.structured_deny_enabled());
+    hook::init_deny_templates(config.output.deny_templates.clone());
+    destructive_command_guard::i18n::init(&config.output);
 
     // Check if bypass is requested (escape hatch)
     if Config::is_bypassed() {
         return;
     }
 
+    // Refresh the team policy bundle in the background when it is due; the
+    // new bundle takes effect on the next invocation.
+    remote::spawn_sync_if_stale(&config.remote);
+
     // Compile overrides once (precompiled regexes, no per-command compilation)
-    let compiled_overrides = config.overrides.compile();
+    let compiled_overrides = config.compile_overrides();
 
     // Load layered allowlists (project/user/system). Missing/invalid files are treated
     // as empty for hook safety; allowlist decisions are only consulted on matches.
@@ -312,15 +419,43 @@ fn main() {
     let max_input_bytes = config.general.max_hook_input_bytes();
     let hook_input = match hook::read_hook_input(max_input_bytes) {
         Ok(input) => input,
-        Err(hook::HookReadError::InputTooLarge(len)) => {
-            eprintln!(
-                "[dcg] Warning: stdin input ({len} bytes) exceeds limit ({max_input_bytes} bytes); allowing command (fail-open)"
-            );
+        Err(err) => {
+            let (class, detail) = match err {
+                hook::HookReadError::InputTooLarge(len) => (
+                    FailureClass::SizeLimit,
+                    format!("stdin input ({len} bytes) exceeds limit ({max_input_bytes} bytes)"),
+                ),
+                hook::HookReadError::Io(e) => {
+                    (FailureClass::Io, format!("failed to read stdin: {e}"))
+                }
+                hook::HookReadError::Json(e) => {
+                    (FailureClass::ParseError, format!("invalid hook input: {e}"))
+                }
+            };
+            // The payload was never parsed, so answer in the requested or default protocol.
+            let protocol = cli
+                .agent
+                .as_deref()
+                .and_then(hook::HookProtocol::for_agent)
+                .unwrap_or(hook::HookProtocol::ClaudeCompatible);
+            handle_evaluation_failure(&config, protocol, "", class, &detail);
             return;
         }
-        Err(_) => return, // Fail open on IO or JSON errors
     };
 
+    // Only PreToolUse payloads are evaluated; the other events feed history.
+    match hook::hook_event(&hook_input) {
+        hook::HookEvent::PostToolUse => {
+            record_post_tool_use(&config, &hook_input);
+            return;
+        }
+        hook::HookEvent::Stop => {
+            summarize_session(&config, &hook_input);
+            return;
+        }
+        hook::HookEvent::PreToolUse => {}
+    }
+
     // Start evaluation deadline after input size checks (includes evaluation).
     let deadline = Deadline::new(
         config
@@ -329,17 +464,34 @@ fn main() {
             .map_or(HOOK_EVALUATION_BUDGET, Duration::from_millis),
     );
 
-    let Some((command, hook_protocol)) = hook::extract_command_with_protocol(&hook_input) else {
+    let Some((command, detected_protocol)) = hook::extract_command_with_protocol(&hook_input)
+    else {
         return;
     };
-
-    // Check command size limit (fail-open: allow and warn)
+    // Allow-once entries scoped with `--session` only match in the same agent session.
+    if let Some(session_id) = hook_input.session_id() {
+        pending_exceptions::set_hook_session_id(session_id);
+    }
+    // An explicit `--agent` picks the response format regardless of payload shape.
+    let hook_protocol = cli
+        .agent
+        .as_deref()
+        .and_then(hook::HookProtocol::for_agent)
+        .unwrap_or(detected_protocol);
+
+    // Check command size limit (fail-open unless [policy.fail_mode] says otherwise)
     let max_command_bytes = config.general.max_command_bytes();
     if command.len() > max_command_bytes {
-        eprintln!(
-            "[dcg] Warning: command ({} bytes) exceeds limit ({} bytes); allowing command (fail-open)",
-            command.len(),
-            max_command_bytes
+        let detail = format!(
+            "command ({} bytes) exceeds limit ({max_command_bytes} bytes)",
+            command.len()
+        );
+        handle_evaluation_failure(
+            &config,
+            hook_protocol,
+            &command,
+            FailureClass::SizeLimit,
+            &detail,
         );
         return;
     }
@@ -351,8 +503,19 @@ fn main() {
     );
 
     let history_writer = if config.history.enabled {
-        HistoryDb::try_open(history_db_path(&config.history))
-            .map(|db| HistoryWriter::new(db, &config.history))
+        let writer = HistoryDb::try_open(history_db_path(&config.history))
+            .map(|db| HistoryWriter::new(db, &config.history));
+        if writer.is_none() && config.policy.fail_mode.is_closed(FailureClass::Telemetry) {
+            handle_evaluation_failure(
+                &config,
+                hook_protocol,
+                &command,
+                FailureClass::Telemetry,
+                "the history database could not be opened",
+            );
+            return;
+        }
+        writer
     } else {
         None
     };
@@ -363,6 +526,10 @@ fn main() {
         }
     }
 
+    let json_sink = JsonSink::new(&config.logging);
+    // Opt-in: spans and decision counters are flushed when this is dropped.
+    let otel_exporter = OtelExporter::init(&config.logging.otel);
+
     if deadline.is_exceeded() {
         if let Some(log_file) = config.general.log_file.as_deref() {
             let _ = hook::log_budget_skip(
@@ -373,39 +540,133 @@ fn main() {
                 HOOK_EVALUATION_BUDGET,
             );
         }
+        if config.policy.fail_mode.is_closed(FailureClass::Timeout) {
+            handle_evaluation_failure(
+                &config,
+                hook_protocol,
+                &command,
+                FailureClass::Timeout,
+                "the evaluation deadline ran out before evaluation started",
+            );
+        }
         return;
     }
 
+    // Expand shell aliases/functions so `grf` is judged as `git reset --hard`.
+    let alias_table = AliasTable::load(&config.aliases);
+    let eval_command = alias_table
+        .as_ref()
+        .map_or(Cow::Borrowed(command.as_str()), |table| {
+            table.expand(&command)
+        });
+    // Resolve `$VAR` references so `DIR=/; rm -rf $DIR` is judged as `rm -rf /`.
+    let eval_command = env_expand::expand_if_enabled(eval_command, config.general.expand_variables);
+
+    // One fingerprint for the cache, quick-allow filter, daemon, and history,
+    // computed on first use.
+    let fingerprint_cell = OnceCell::new();
+    let config_fingerprint =
+        || fingerprint_cell.get_or_init(|| ConfigFingerprint::new(&config, &allowlists));
+
+    // Opt-in: identical commands that were a clean allow in this directory skip evaluation.
+    // Project script invocations are never cached: their verdict depends on
+    // package.json, the Makefile, or the justfile, which the cache key does not cover.
+    let cacheable_command =
+        !destructive_command_guard::scripts::mentions_script_runner(&eval_command);
+    let eval_cache = (config.cache.is_active() && cacheable_command)
+        .then(|| EvalCache::for_config(&config, config_fingerprint()))
+        .flatten();
+    let quick_allow = (config.cache.quick_allow && cacheable_command)
+        .then(|| QuickAllowFilter::for_config(&config, config_fingerprint()))
+        .flatten();
+
     // Use the shared evaluator for hook mode parity with `dcg test`.
     let eval_start = Instant::now();
-    let result = evaluate_command_with_pack_order_deadline_at_path(
-        &command,
-        &enabled_keywords,
-        &ordered_packs,
-        keyword_index.as_ref(),
-        &compiled_overrides,
-        &allowlists,
-        &heredoc_settings,
-        None, // allow_once_audit
-        None, // project_path
-        Some(&deadline),
-    );
+    let quick_hit = quick_allow
+        .as_ref()
+        .is_some_and(|filter| filter.contains(&eval_command, &working_dir));
+    let cache_hit = quick_hit
+        || eval_cache
+            .as_ref()
+            .is_some_and(|cache| cache.lookup(&eval_command, &working_dir));
+    // A running `dcg daemon` has the packs compiled already; ask it first.
+    let remote_result = if cache_hit {
+        None
+    } else {
+        cwd_path.as_deref().and_then(|cwd| {
+            daemon::evaluate_remote(
+                &config,
+                config_fingerprint(),
+                &eval_command,
+                cwd,
+                hook_input.session_id(),
+                &deadline,
+            )
+        })
+    };
+    let result = if cache_hit {
+        EvaluationResult::allowed()
+    } else if let Some(result) = remote_result {
+        result
+    } else {
+        evaluate_command_with_pack_order_deadline_at_path(
+            &eval_command,
+            &enabled_keywords,
+            &ordered_packs,
+            keyword_index.as_ref(),
+            &compiled_overrides,
+            &allowlists,
+            &heredoc_settings,
+            None, // allow_once_audit
+            None, // project_path
+            Some(&deadline),
+        )
+    };
+    let clean_allow = eval_cache::is_cacheable(&result);
+    if let Some(cache) = eval_cache.as_ref().filter(|_| !cache_hit && clean_allow) {
+        let _ = cache.record_allow(&eval_command, &working_dir);
+    }
+    // Evaluation-cache hits count towards promotion into the quick-allow filter.
+    if let Some(filter) = quick_allow.as_ref().filter(|_| !quick_hit && clean_allow) {
+        let _ = filter.record_allow(&eval_command, &working_dir);
+    }
 
     // NOTE: External packs from custom_paths are now checked in evaluate_command()
     // alongside built-in packs, so no separate fallback check is needed here.
 
     let eval_duration = eval_start.elapsed();
+    let eval_duration_us = u64::try_from(eval_duration.as_micros()).unwrap_or(u64::MAX);
 
     if result.skipped_due_to_budget {
+        let fail_closed = config.policy.fail_mode.is_closed(FailureClass::Timeout);
+        let (mode, outcome) = if fail_closed {
+            (DecisionMode::Deny, HistoryOutcome::Deny)
+        } else {
+            (DecisionMode::Log, HistoryOutcome::Allow)
+        };
+        if let Some(exporter) = otel_exporter.as_ref() {
+            exporter.record(otel::decision_label(mode), None, eval_duration);
+        }
+        if let Some(sink) = json_sink.as_ref() {
+            sink.log(
+                &result,
+                &command,
+                mode,
+                Some(eval_duration_us),
+                Some(&working_dir),
+            );
+        }
         if let Some(writer) = history_writer.as_ref() {
             let entry = build_history_entry(
+                &hook_input,
                 &command,
                 &working_dir,
-                HistoryOutcome::Allow,
+                outcome,
                 eval_duration,
                 None,
                 None,
                 None,
+                config_fingerprint(),
             );
             writer.log(entry);
         }
@@ -418,10 +679,39 @@ fn main() {
                 HOOK_EVALUATION_BUDGET,
             );
         }
+        if fail_closed {
+            let detail = format!(
+                "evaluation exceeded the {}ms deadline",
+                deadline.max_duration().as_millis()
+            );
+            handle_evaluation_failure(
+                &config,
+                hook_protocol,
+                &command,
+                FailureClass::Timeout,
+                &detail,
+            );
+        }
         return;
     }
 
     if result.decision != EvaluationDecision::Deny {
+        if let Some(exporter) = otel_exporter.as_ref() {
+            let pack = result
+                .allowlist_override
+                .as_ref()
+                .and_then(|override_| override_.matched.pack_id.as_deref());
+            exporter.record("allow", pack, eval_duration);
+        }
+        if let Some(sink) = json_sink.as_ref() {
+            sink.log(
+                &result,
+                &command,
+                DecisionMode::Log,
+                Some(eval_duration_us),
+                Some(&working_dir),
+            );
+        }
         if let Some(writer) = history_writer.as_ref() {
             let mut pack_id = None;
             let mut pattern_name = None;
@@ -434,6 +724,7 @@ fn main() {
             }
 
             let entry = build_history_entry(
+                &hook_input,
                 &command,
                 &working_dir,
                 HistoryOutcome::Allow,
@@ -441,6 +732,7 @@ fn main() {
                 pack_id,
                 pattern_name,
                 allowlist_layer,
+                config_fingerprint(),
             );
             writer.log(entry);
         }
@@ -449,8 +741,12 @@ fn main() {
 
     let Some(ref info) = result.pattern_info else {
         // Fail open: structurally unexpected, but hook safety wins.
+        if let Some(exporter) = otel_exporter.as_ref() {
+            exporter.record("allow", None, eval_duration);
+        }
         if let Some(writer) = history_writer.as_ref() {
             let entry = build_history_entry(
+                &hook_input,
                 &command,
                 &working_dir,
                 HistoryOutcome::Allow,
@@ -458,6 +754,7 @@ fn main() {
                 None,
                 None,
                 None,
+                config_fingerprint(),
             );
             writer.log(entry);
         }
@@ -465,16 +762,72 @@ fn main() {
     };
 
     let pack = info.pack_id.as_deref();
+    // Opt-in: kubernetes.* severities follow the target cluster context.
+    let kube_adjustment = destructive_command_guard::kube_context::adjust_severity(
+        &config.kube_context,
+        &command,
+        pack,
+        info.severity,
+    );
+    let severity = kube_adjustment
+        .as_ref()
+        .map_or(info.severity, |adjustment| Some(adjustment.to));
+    // Commands run over ssh on a production host are one level more severe.
+    let host_escalation = destructive_command_guard::remote_exec::escalate_severity(
+        &config.wrappers,
+        &command,
+        severity,
+    );
+    let severity = host_escalation
+        .as_ref()
+        .map_or(severity, |escalation| Some(escalation.to));
+    // Log-only matches warn inside `[protection.workspaces]` checkouts.
+    let workspace_escalation = destructive_command_guard::protected_paths::escalate_in_workspace(
+        &config.protection.workspaces,
+        cwd_path.as_deref(),
+        severity,
+    );
+    let severity = workspace_escalation
+        .as_ref()
+        .map_or(severity, |escalation| Some(escalation.to));
     let mut mode = match info.source {
         MatchSource::Pack | MatchSource::HeredocAst => {
             config
                 .policy()
-                .resolve_mode(pack, info.pattern_name.as_deref(), info.severity)
+                .resolve_mode(pack, info.pattern_name.as_deref(), severity)
         }
         // Never downgrade explicit blocks.
         MatchSource::ConfigOverride | MatchSource::LegacyPattern => DecisionMode::Deny,
     };
 
+    // `[policy.rego]` can replace the built-in mode for pack matches.
+    let rego_decision = config
+        .policy
+        .rego
+        .as_ref()
+        .filter(|_| matches!(info.source, MatchSource::Pack | MatchSource::HeredocAst))
+        .and_then(|rego| RegoPolicy::load(rego, cwd_path.as_deref()))
+        .and_then(|mut policy| {
+            let input = RegoInput::new(
+                &command,
+                &working_dir,
+                pack,
+                info.pattern_name.as_deref(),
+                severity,
+                &info.reason,
+                mode,
+                info.source == MatchSource::HeredocAst,
+            );
+            policy
+                .evaluate(&input)
+                .map_err(|e| eprintln!("[dcg] Warning: [policy.rego] evaluation failed: {e}"))
+                .ok()
+                .flatten()
+        });
+    if let Some(decision) = rego_decision.as_ref() {
+        mode = decision.mode;
+    }
+
     // Apply confidence scoring (if enabled) to potentially downgrade Deny to Warn.
     // Only applies to pack/heredoc matches, not config overrides.
     if matches!(info.source, MatchSource::Pack | MatchSource::HeredocAst) {
@@ -502,8 +855,131 @@ fn main() {
         mode = confidence_result.mode;
     }
 
+    // `# dcg:allow rule=... reason=...` may clear the one rule it names.
+    let inline_suppression = InlineSuppression::parse(&command)
+        .filter(|_| mode == DecisionMode::Deny)
+        .filter(|_| matches!(info.source, MatchSource::Pack | MatchSource::HeredocAst))
+        .map(|suppression| {
+            let verdict = suppression.verdict(
+                &config.suppressions,
+                pack,
+                info.pattern_name.as_deref(),
+                severity,
+                || {
+                    let scoped = suppression.scoped_allowlists(&allowlists);
+                    let rescan = evaluate_command_with_pack_order_deadline_at_path(
+                        &eval_command,
+                        &enabled_keywords,
+                        &ordered_packs,
+                        keyword_index.as_ref(),
+                        &compiled_overrides,
+                        &scoped,
+                        &heredoc_settings,
+                        None, // allow_once_audit
+                        None, // project_path
+                        Some(&deadline),
+                    );
+                    !rescan.is_denied() && !rescan.skipped_due_to_budget
+                },
+            );
+            (suppression, verdict)
+        });
+    if matches!(inline_suppression, Some((_, SuppressionVerdict::Apply))) {
+        mode = DecisionMode::Warn;
+    }
+
+    // An agent that keeps getting denied is treated as fighting the guard.
+    let history_reader = open_history_reader(&config);
+    let anomaly_status = detect_anomaly(
+        &config,
+        history_reader.as_ref(),
+        mode,
+        &working_dir,
+        hook_input.session_id(),
+    );
+    if let Some(status) = anomaly_status.as_ref() {
+        mode = status.escalate(mode);
+    }
+
+    // A session that has piled up risky commands loses its warn-only leniency.
+    let risk_db = history_reader.as_ref().filter(|_| config.risk.is_active());
+    let risk_status = risk_db.and_then(|db| {
+        risk::check(
+            db,
+            &config.risk,
+            HISTORY_AGENT_TYPE,
+            &working_dir,
+            hook_input.session_id(),
+            chrono::Utc::now(),
+        )
+    });
+    if let Some(status) = risk_status.as_ref() {
+        mode = status.escalate(mode);
+    }
+
+    // Borderline matches get their final decision from the external policy endpoint.
+    let external_decision = config
+        .policy
+        .external
+        .as_ref()
+        .filter(|external| external.is_active() && external.applies_to(severity))
+        .filter(|_| matches!(info.source, MatchSource::Pack | MatchSource::HeredocAst))
+        .map(|external| {
+            let query = PolicyQuery::new(
+                &command,
+                &working_dir,
+                pack,
+                info.pattern_name.as_deref(),
+                severity,
+                &info.reason,
+                mode,
+                hook_input.session_id(),
+                &config.logging.redaction,
+            );
+            external_policy::consult(external, &query)
+        });
+    if let Some(decision) = external_decision.as_ref() {
+        mode = decision.apply(mode);
+    }
+    if let Some((suppression, SuppressionVerdict::Apply)) = inline_suppression.as_ref() {
+        if mode != DecisionMode::Deny {
+            let _ = suppression.record(
+                &command,
+                cwd_path.as_deref(),
+                false,
+                &config.logging.redaction,
+            );
+        }
+    }
+
     let pattern = info.pattern_name.as_deref();
-    let explanation = info.explanation.as_deref();
+    // Catalog translations replace pack text in what the user and agent see;
+    // logs, history, and notifications keep the pack's English reason.
+    let localizer = destructive_command_guard::i18n::localizer();
+    let localized_reason = localizer.rule_reason(pack, pattern);
+    let display_reason = localized_reason.as_deref().unwrap_or(&info.reason);
+    let localized_explanation = localizer.rule_explanation(pack, pattern);
+    let explanation = localized_explanation
+        .as_deref()
+        .or(info.explanation.as_deref());
+    let localized_recovery_hint = localizer.rule_recovery_hint(pack, pattern);
+    let recovery_hint = localized_recovery_hint.as_deref().or(info.recovery_hint);
+
+    if let Some(sink) = json_sink.as_ref() {
+        sink.log(
+            &result,
+            &command,
+            mode,
+            Some(eval_duration_us),
+            Some(&working_dir),
+        );
+    }
+    if let Some(forwarder) = SyslogForwarder::new(&config.logging) {
+        forwarder.forward(&result, &command, mode, Some(&working_dir));
+    }
+    if let Some(exporter) = otel_exporter.as_ref() {
+        exporter.record(otel::decision_label(mode), pack, eval_duration);
+    }
 
     if let Some(writer) = history_writer.as_ref() {
         let outcome = match mode {
@@ -512,6 +988,7 @@ fn main() {
             DecisionMode::Log => HistoryOutcome::Allow,
         };
         let entry = build_history_entry(
+            &hook_input,
             &command,
             &working_dir,
             outcome,
@@ -519,9 +996,22 @@ fn main() {
             pack,
             pattern,
             None,
+            config_fingerprint(),
         );
         writer.log(entry);
     }
+    if let Some(db) = risk_db {
+        let _ = risk::record(
+            db,
+            &config.risk,
+            HISTORY_AGENT_TYPE,
+            &working_dir,
+            hook_input.session_id(),
+            mode,
+            severity,
+            chrono::Utc::now(),
+        );
+    }
 
     match mode {
         DecisionMode::Deny => {
@@ -542,6 +1032,7 @@ fn main() {
                 &config.logging.redaction,
                 false,
                 Some(format!("{:?}", info.source)),
+                hook_input.session_id(),
                 None,
             ) {
                 allow_once_info = Some(hook::AllowOnceInfo {
@@ -553,27 +1044,142 @@ fn main() {
                 }
             }
 
+            // Opt-in: tell the human what the denied `rm` would have removed.
+            let preview = cwd_path
+                .as_deref()
+                .filter(|_| config.protection.rm_preview.enabled)
+                .and_then(|cwd| rm_preview::preview(&command, cwd, &config.protection.rm_preview));
+            let mut deny_reason = preview.map_or(Cow::Borrowed(display_reason), |p| {
+                Cow::Owned(format!("{display_reason}\n\n{}", p.summary()))
+            });
+            if let Some(status) = anomaly_status.as_ref() {
+                let notice = status.notice();
+                if let Some(log_file) = config.general.log_file.as_deref() {
+                    let _ = hook::log_blocked_command(log_file, &command, &notice, Some("anomaly"));
+                }
+                deny_reason = Cow::Owned(format!("{deny_reason}\n\n{notice}"));
+            }
+            if let Some(status) = risk_status.as_ref() {
+                let notice = status.notice();
+                if let Some(log_file) = config.general.log_file.as_deref() {
+                    let _ = hook::log_blocked_command(log_file, &command, &notice, Some("risk"));
+                }
+                deny_reason = Cow::Owned(format!("{deny_reason}\n\n{notice}"));
+            }
+            if let Some(adjustment) = kube_adjustment.as_ref() {
+                deny_reason = Cow::Owned(format!("{deny_reason}\n\n{}", adjustment.notice()));
+            }
+            if let Some(escalation) = host_escalation.as_ref() {
+                deny_reason = Cow::Owned(format!("{deny_reason}\n\n{}", escalation.notice()));
+            }
+            if let Some(escalation) = workspace_escalation.as_ref() {
+                deny_reason = Cow::Owned(format!("{deny_reason}\n\n{}", escalation.notice()));
+            }
+            if let Some(notice) = rego_decision
+                .as_ref()
+                .and_then(destructive_command_guard::rego_policy::RegoDecision::notice)
+            {
+                deny_reason = Cow::Owned(format!("{deny_reason}\n\n{notice}"));
+            }
+            if let Some(notice) = external_decision
+                .as_ref()
+                .and_then(external_policy::ExternalDecision::notice)
+            {
+                if let Some(log_file) = config.general.log_file.as_deref() {
+                    let _ = hook::log_blocked_command(
+                        log_file,
+                        &command,
+                        &notice,
+                        Some("policy.external"),
+                    );
+                }
+                deny_reason = Cow::Owned(format!("{deny_reason}\n\n{notice}"));
+            }
+            if let Some((suppression, verdict)) = inline_suppression.as_ref() {
+                let code = allow_once_info.as_ref().map(|info| info.code.as_str());
+                let notice = suppression.notice(verdict, code);
+                deny_reason = Cow::Owned(format!("{deny_reason}\n\n{notice}"));
+            }
+            // A lapsed allowlist entry for this rule is reported, not silently skipped.
+            if let Some(expired) = pack.zip(pattern).and_then(|(pack_id, pattern_name)| {
+                allowlists.match_expired_rule_at_path(pack_id, pattern_name, cwd_path.as_deref())
+            }) {
+                let expiry = expired.expiry();
+                let note = destructive_command_guard::i18n::t_args(
+                    "allowlist-expired-note",
+                    &[
+                        ("layer", expired.layer.label()),
+                        ("expiry", expiry.as_str()),
+                    ],
+                );
+                deny_reason = Cow::Owned(format!("{deny_reason}\n\n{note}"));
+            }
+
             hook::output_denial_for_protocol(
                 hook_protocol,
                 &command,
-                &info.reason,
+                &deny_reason,
                 pack,
                 pattern,
                 explanation,
                 allow_once_info.as_ref(),
                 info.matched_span.as_ref(),
-                info.severity,
+                severity,
                 None, // confidence not yet available in PatternMatch
                 info.suggestions,
             );
 
+            let expired_allowlist = allowlists.expired_entries().len();
+            if expired_allowlist > config.general.expired_allowlist_reminder() {
+                hook::output_expired_allowlist_reminder(expired_allowlist);
+            }
+
             // Log if configured
             if let Some(log_file) = &config.general.log_file {
                 let _ = hook::log_blocked_command(log_file, &command, &info.reason, pack);
             }
+
+            // Queued only; a detached `dcg notify flush` does the network I/O.
+            if let Some(notifier) = Notifier::new(&config.notifications) {
+                notifier.notify_deny(&DenyEvent::new(
+                    &command,
+                    pack,
+                    pattern,
+                    severity,
+                    &info.reason,
+                    cwd_path.as_deref(),
+                    &config.logging.redaction,
+                ));
+            }
         }
         DecisionMode::Warn => {
-            hook::output_warning(&command, &info.reason, pack, pattern, explanation);
+            let mut warn_reason = match inline_suppression.as_ref() {
+                Some((suppression, SuppressionVerdict::Apply)) => Cow::Owned(format!(
+                    "{display_reason} (suppressed inline: {})",
+                    suppression.reason
+                )),
+                _ => Cow::Borrowed(display_reason),
+            };
+            let notices = rego_decision
+                .as_ref()
+                .and_then(destructive_command_guard::rego_policy::RegoDecision::notice)
+                .into_iter()
+                .chain(
+                    external_decision
+                        .as_ref()
+                        .and_then(external_policy::ExternalDecision::notice),
+                );
+            for notice in notices {
+                warn_reason = Cow::Owned(format!("{warn_reason} ({notice})"));
+            }
+            hook::output_warning(
+                &command,
+                &warn_reason,
+                pack,
+                pattern,
+                explanation,
+                recovery_hint,
+            );
         }
         DecisionMode::Log => {
             // Silent allow; optionally log to file for history.
@@ -584,6 +1190,49 @@ fn main() {
     }
 }
 
+/// Allow or deny a command dcg could not evaluate, per `[policy.fail_mode]`.
+///
+/// Fail-open keeps the historical behavior (a stderr warning for size limits,
+/// silence otherwise); fail-closed writes a denial naming the failure and how
+/// to relax it.
+fn handle_evaluation_failure(
+    config: &Config,
+    protocol: hook::HookProtocol,
+    command: &str,
+    class: FailureClass,
+    detail: &str,
+) {
+    if !config.policy.fail_mode.is_closed(class) {
+        if class == FailureClass::SizeLimit {
+            eprintln!("[dcg] Warning: {detail}; allowing command (fail-open)");
+        }
+        return;
+    }
+
+    let reason = format!("dcg could not evaluate this command: {detail} (fail_mode is closed)");
+    let explanation = format!(
+        "[policy.fail_mode] blocks commands dcg cannot evaluate. Retry, or set \
+         `{} = \"open\"` under [policy.fail_mode] to allow this kind of failure.",
+        class.label()
+    );
+    hook::output_denial_for_protocol(
+        protocol,
+        command,
+        &reason,
+        None,
+        None,
+        Some(&explanation),
+        None,
+        None,
+        None,
+        None,
+        &[],
+    );
+    if let Some(log_file) = config.general.log_file.as_deref() {
+        let _ = hook::log_blocked_command(log_file, command, &reason, Some("fail_mode"));
+    }
+}
+
 /// Print help information.
 #[allow(clippy::too_many_lines)]
 fn print_help() {
@@ -718,6 +1367,14 @@ fn print_help() {
         "    {}  Suggest allowlist patterns from history",
         "suggest-allowlist".green()
     );
+    eprintln!(
+        "    {}       Review recent denies interactively",
+        "review".green()
+    );
+    eprintln!(
+        "    {}        Verify the config change audit log",
+        "audit".green()
+    );
     eprintln!("    {}       Run regression corpus tests", "corpus".green());
     eprintln!(
         "    {}         Run in explicit hook mode (batch support)",
@@ -908,7 +1565,10 @@ mod tests {
                     pack_id: None,
                     severity: None,
                     confidence: None,
+                    suggested_alternative: None,
                     remediation: None,
+                    schema_version: None,
+                    details: None,
                 },
             }
         }
@@ -3,12 +3,10 @@
 //! This exposes dcg as an MCP tool server over stdio, providing structured
 //! checks without shell-hook overhead.
 
-use crate::config::Config;
 use crate::evaluator::{EvaluationDecision, evaluate_command};
 use crate::packs::REGISTRY;
-use crate::scan::{
-    ScanEvalContext, ScanFailOn, ScanFormat, ScanOptions, ScanRedactMode, scan_paths,
-};
+use crate::reload::{ConfigWatcher, LiveConfig};
+use crate::scan::{ScanFailOn, ScanFormat, ScanOptions, ScanRedactMode, scan_paths};
 use async_trait::async_trait;
 use rust_mcp_sdk::mcp_server::{
     McpServerOptions, ServerHandler, ToMcpServerHandler, server_runtime,
@@ -29,8 +27,9 @@ use std::sync::Arc;
 #[derive(Debug)]
 pub struct DcgMcpServer {
     server_info: InitializeResult,
-    config: Config,
-    scan_ctx: ScanEvalContext,
+    live: Arc<LiveConfig>,
+    /// Reloads `live` when config or allowlist files change.
+    _watcher: Option<ConfigWatcher>,
 }
 
 #[derive(Serialize)]
@@ -68,8 +67,10 @@ struct ExplainPatternResponse {
 impl DcgMcpServer {
     #[must_use]
     pub fn new() -> Self {
-        let config = Config::load();
-        let scan_ctx = ScanEvalContext::from_config(&config);
+        let live = Arc::new(LiveConfig::load());
+        // Hot-reload is best-effort: without a watcher the server keeps the
+        // configuration it started with.
+        let watcher = live.watch().ok();
         let server_info = InitializeResult {
             protocol_version: ProtocolVersion::V2025_11_25.into(),
             server_info: Implementation {
@@ -102,8 +103,8 @@ impl DcgMcpServer {
 
         Self {
             server_info,
-            config,
-            scan_ctx,
+            live,
+            _watcher: watcher,
         }
     }
 
@@ -184,12 +185,13 @@ impl DcgMcpServer {
     }
 
     fn check_command(&self, command: &str) -> CheckCommandResponse {
+        let snapshot = self.live.snapshot();
         let result = evaluate_command(
             command,
-            &self.config,
-            &self.scan_ctx.enabled_keywords,
-            &self.scan_ctx.compiled_overrides,
-            &self.scan_ctx.allowlists,
+            &snapshot.config,
+            &snapshot.ctx.enabled_keywords,
+            &snapshot.ctx.compiled_overrides,
+            &snapshot.ctx.allowlists,
         );
 
         let mode = result.effective_mode.map(|m| m.label().to_string());
@@ -346,11 +348,12 @@ impl ServerHandler for DcgMcpServer {
                 let options = Self::default_scan_options();
                 let include: Vec<String> = Vec::new();
                 let exclude: Vec<String> = Vec::new();
+                let snapshot = self.live.snapshot();
                 let report = scan_paths(
                     &[path_buf],
                     &options,
-                    &self.config,
-                    &self.scan_ctx,
+                    &snapshot.config,
+                    &snapshot.ctx,
                     &include,
                     &exclude,
                     None,
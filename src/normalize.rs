@@ -15,6 +15,12 @@
 //! - `env [-i] [-u name] [NAME=VALUE]... command` - environment modification
 //! - `\git`, `\rm` - bash alias bypass (leading backslash)
 //! - `command [-p] [--] cmd` - but NOT `command -v` or `command -V` (query mode)
+//! - `cmd [/d] [/q] [/s] /c ...`, `cmd.exe /k ...` - Windows command interpreter
+//! - `doas`, `nice`, `ionice`, `timeout DURATION`, `nohup`, `setsid`, `stdbuf`,
+//!   `xargs` with their options - commands that run the rest of the line
+//!
+//! [`resolve_wrapper_chain`] additionally unwraps `ssh host '<cmd>'` and the
+//! `[wrappers] extra` commands, for evaluating the command that finally runs.
 
 use fancy_regex::Regex;
 use smallvec::SmallVec;
@@ -109,12 +115,24 @@ pub fn strip_wrapper_prefixes(command: &str) -> NormalizedCommand<'_> {
             continue;
         }
 
+        if let Some((remaining, wrapper)) = strip_exec_wrapper(&current) {
+            stripped_wrappers.push(wrapper);
+            current = remaining;
+            continue;
+        }
+
         if let Some((remaining, wrapper)) = strip_command_wrapper(&current) {
             stripped_wrappers.push(wrapper);
             current = remaining;
             continue;
         }
 
+        if let Some((remaining, wrapper)) = strip_cmd_wrapper(&current) {
+            stripped_wrappers.push(wrapper);
+            current = remaining;
+            continue;
+        }
+
         if let Some((remaining, wrapper)) = strip_leading_backslash(&current) {
             stripped_wrappers.push(wrapper);
             current = remaining;
@@ -744,6 +762,477 @@ fn strip_command_wrapper(command: &str) -> Option<(String, StrippedWrapper)> {
     ))
 }
 
+/// Strip a Windows `cmd /c` or `cmd /k` prefix.
+///
+/// Matches `cmd`, `cmd.exe`, or a full path such as `C:\Windows\System32\cmd.exe`,
+/// case-insensitively. Switches before `/c` (`/d`, `/q`, `/s`, `/e:on`, `/v:off`, ...)
+/// are skipped. A single pair of double quotes around the rest of the line is removed,
+/// as `cmd /s /c "..."` does.
+fn strip_cmd_wrapper(command: &str) -> Option<(String, StrippedWrapper)> {
+    let trimmed = command.trim_start();
+
+    let first_word_end = trimmed.find(char::is_whitespace)?;
+    let first_word = trimmed[..first_word_end].trim_matches('"');
+    let basename = first_word.rsplit(['/', '\\']).next().unwrap_or(first_word);
+    if !basename.eq_ignore_ascii_case("cmd") && !basename.eq_ignore_ascii_case("cmd.exe") {
+        return None;
+    }
+
+    let mut rest = &trimmed[first_word_end..];
+    loop {
+        rest = rest.trim_start();
+        let word_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let word = &rest[..word_end];
+        if word.eq_ignore_ascii_case("/c") || word.eq_ignore_ascii_case("/k") {
+            rest = rest[word_end..].trim_start();
+            break;
+        }
+
+        // Any other switch is a slash, one letter, and an optional `:value`.
+        let bytes = word.as_bytes();
+        let is_switch = bytes.len() >= 2
+            && bytes[0] == b'/'
+            && bytes[1].is_ascii_alphabetic()
+            && (bytes.len() == 2 || bytes[2] == b':');
+        if !is_switch {
+            return None;
+        }
+        rest = &rest[word_end..];
+    }
+
+    let remaining = match rest.strip_prefix('"').and_then(|r| r.strip_suffix('"')) {
+        Some(inner) if !inner.contains('"') => inner.trim(),
+        _ => rest,
+    };
+    if remaining.is_empty() {
+        return None;
+    }
+
+    let stripped_text = trimmed[..trimmed.len() - rest.len()].trim_end().to_string();
+
+    Some((
+        remaining.to_string(),
+        StrippedWrapper {
+            wrapper_type: "cmd",
+            stripped_text,
+        },
+    ))
+}
+
+/// Option syntax of a wrapper that runs the rest of its arguments as a command.
+///
+/// Options not listed here make the wrapper ambiguous (or put it in a mode
+/// that runs nothing, like `ionice -p PID`), so the command is left alone.
+struct ExecWrapperSpec {
+    name: &'static str,
+    /// Single-letter options without a value (may be clustered: `-0r`).
+    short_flags: &'static str,
+    /// Single-letter options with a value (`-n 10` or `-n10`).
+    short_values: &'static str,
+    long_flags: &'static [&'static str],
+    /// Long options with a value (`--signal KILL` or `--signal=KILL`).
+    long_values: &'static [&'static str],
+    /// Positional arguments before the command (`timeout DURATION`).
+    positional: usize,
+    /// Accept `-N` priority options (`nice -10`).
+    numeric_options: bool,
+}
+
+const EXEC_WRAPPERS: &[ExecWrapperSpec] = &[
+    ExecWrapperSpec {
+        name: "doas",
+        short_flags: "ns",
+        short_values: "u",
+        long_flags: &[],
+        long_values: &[],
+        positional: 0,
+        numeric_options: false,
+    },
+    ExecWrapperSpec {
+        name: "nice",
+        short_flags: "",
+        short_values: "n",
+        long_flags: &[],
+        long_values: &["--adjustment"],
+        positional: 0,
+        numeric_options: true,
+    },
+    ExecWrapperSpec {
+        name: "ionice",
+        short_flags: "t",
+        short_values: "cn",
+        long_flags: &["--ignore"],
+        long_values: &["--class", "--classdata"],
+        positional: 0,
+        numeric_options: false,
+    },
+    ExecWrapperSpec {
+        name: "nohup",
+        short_flags: "",
+        short_values: "",
+        long_flags: &[],
+        long_values: &[],
+        positional: 0,
+        numeric_options: false,
+    },
+    ExecWrapperSpec {
+        name: "setsid",
+        short_flags: "cfw",
+        short_values: "",
+        long_flags: &["--ctty", "--fork", "--wait"],
+        long_values: &[],
+        positional: 0,
+        numeric_options: false,
+    },
+    ExecWrapperSpec {
+        name: "timeout",
+        short_flags: "v",
+        short_values: "sk",
+        long_flags: &["--preserve-status", "--foreground", "--verbose"],
+        long_values: &["--signal", "--kill-after"],
+        positional: 1,
+        numeric_options: false,
+    },
+    ExecWrapperSpec {
+        name: "stdbuf",
+        short_flags: "",
+        short_values: "ioe",
+        long_flags: &[],
+        long_values: &["--input", "--output", "--error"],
+        positional: 0,
+        numeric_options: false,
+    },
+    ExecWrapperSpec {
+        name: "xargs",
+        short_flags: "0rtpxo",
+        short_values: "adEILnPs",
+        long_flags: &[
+            "--null",
+            "--no-run-if-empty",
+            "--verbose",
+            "--interactive",
+            "--exit",
+            "--open-tty",
+        ],
+        long_values: &[
+            "--arg-file",
+            "--delimiter",
+            "--eof",
+            "--replace",
+            "--max-lines",
+            "--max-args",
+            "--max-procs",
+            "--max-chars",
+            "--process-slot-var",
+        ],
+        positional: 0,
+        numeric_options: false,
+    },
+];
+
+/// Strip `doas`, `nice`, `ionice`, `timeout`, `nohup`, `setsid`, `stdbuf`, or
+/// `xargs` with their options.
+///
+/// `xargs` appends its input to the command, so `find . | xargs rm -rf`
+/// leaves `rm -rf` for matching.
+fn strip_exec_wrapper(command: &str) -> Option<(String, StrippedWrapper)> {
+    let trimmed = command.trim_start();
+    let first_word_end = trimmed.find(char::is_whitespace).unwrap_or(trimmed.len());
+    let first_word = &trimmed[..first_word_end];
+    let basename = first_word.rsplit('/').next().unwrap_or(first_word);
+    let spec = EXEC_WRAPPERS.iter().find(|spec| spec.name == basename)?;
+
+    let (command_start, _) = parse_exec_wrapper(trimmed, first_word_end, spec)?;
+    let remaining = &trimmed[command_start..];
+    if remaining.trim().is_empty() {
+        return None;
+    }
+
+    Some((
+        remaining.to_string(),
+        StrippedWrapper {
+            wrapper_type: spec.name,
+            stripped_text: trimmed[..command_start].trim_end().to_string(),
+        },
+    ))
+}
+
+/// Skip a wrapper's options and positional arguments, starting after the
+/// wrapper name. Returns where the wrapped command starts and the byte
+/// ranges of the positional arguments.
+fn parse_exec_wrapper(
+    command: &str,
+    mut idx: usize,
+    spec: &ExecWrapperSpec,
+) -> Option<(usize, Vec<Range<usize>>)> {
+    let bytes = command.as_bytes();
+    let len = bytes.len();
+    let mut positional = Vec::new();
+    let mut options_done = false;
+
+    // Skip the value of an option; a missing value means the line is not a
+    // complete wrapper invocation.
+    let skip_value = |idx: usize| -> Option<usize> {
+        let start = skip_ascii_whitespace(bytes, idx, len);
+        let end = consume_word_token(bytes, start, len);
+        (end > start).then_some(end)
+    };
+
+    loop {
+        idx = skip_ascii_whitespace(bytes, idx, len);
+        let end = consume_word_token(bytes, idx, len);
+        if end == idx {
+            return None;
+        }
+        let word = &command[idx..end];
+
+        if options_done || !word.starts_with('-') || word == "-" {
+            if positional.len() == spec.positional {
+                return Some((idx, positional));
+            }
+            positional.push(idx..end);
+            options_done = true;
+            idx = end;
+            continue;
+        }
+        if word == "--" {
+            options_done = true;
+            idx = end;
+            continue;
+        }
+
+        if let Some(long) = word.strip_prefix("--") {
+            let (name, inline_value) = long
+                .split_once('=')
+                .map_or((word, false), |(name, _)| (&word[..name.len() + 2], true));
+            idx = if spec.long_values.contains(&name) {
+                if inline_value { end } else { skip_value(end)? }
+            } else if !inline_value && spec.long_flags.contains(&name) {
+                end
+            } else {
+                return None;
+            };
+            continue;
+        }
+
+        let cluster = &word[1..];
+        if spec.numeric_options && cluster.bytes().all(|b| b.is_ascii_digit()) {
+            idx = end;
+            continue;
+        }
+        idx = end;
+        for (pos, flag) in cluster.char_indices() {
+            if spec.short_flags.contains(flag) {
+                continue;
+            }
+            if !spec.short_values.contains(flag) {
+                return None;
+            }
+            // Value flags take the rest of the word (`-n10`) or the next word.
+            if pos + flag.len_utf8() == cluster.len() {
+                idx = skip_value(end)?;
+            }
+            break;
+        }
+    }
+}
+
+/// Strip one of the `[wrappers] extra` commands and any leading `-` options.
+fn strip_extra_wrapper(command: &str, extra: &[String]) -> Option<(String, StrippedWrapper)> {
+    let trimmed = command.trim_start();
+    let first_word_end = trimmed.find(char::is_whitespace)?;
+    let first_word = &trimmed[..first_word_end];
+    let basename = first_word.rsplit('/').next().unwrap_or(first_word);
+    if !extra.iter().any(|name| name == basename) {
+        return None;
+    }
+
+    let bytes = trimmed.as_bytes();
+    let mut idx = first_word_end;
+    loop {
+        idx = skip_ascii_whitespace(bytes, idx, bytes.len());
+        if bytes.get(idx) != Some(&b'-') {
+            break;
+        }
+        idx = consume_word_token(bytes, idx, bytes.len());
+    }
+    let remaining = &trimmed[idx..];
+    if remaining.trim().is_empty() {
+        return None;
+    }
+
+    Some((
+        remaining.to_string(),
+        StrippedWrapper {
+            wrapper_type: "wrapper",
+            stripped_text: trimmed[..idx].trim_end().to_string(),
+        },
+    ))
+}
+
+const SSH_SPEC: ExecWrapperSpec = ExecWrapperSpec {
+    name: "ssh",
+    short_flags: "46AaCfgKkMnqTtvXxYy",
+    short_values: "BbcDEeFIiJLlmOopRSWw",
+    long_flags: &[],
+    long_values: &[],
+    positional: 1,
+    numeric_options: false,
+};
+
+/// Split `ssh [options] destination command...` into the remote command and
+/// host.
+///
+/// The remote command is what the remote shell receives: the words after the
+/// destination, unquoted and joined with spaces, as ssh itself does. `ssh`
+/// without a command (a login shell) and modes that run no command
+/// (`-N`, `-G`, `-V`, `-s` subsystems) are not stripped.
+fn strip_ssh(command: &str) -> Option<(String, String, StrippedWrapper)> {
+    let trimmed = command.trim_start();
+    let first_word_end = trimmed.find(char::is_whitespace)?;
+    let first_word = &trimmed[..first_word_end];
+    if first_word.rsplit('/').next() != Some(SSH_SPEC.name) {
+        return None;
+    }
+
+    let (command_start, positional) = parse_exec_wrapper(trimmed, first_word_end, &SSH_SPEC)?;
+    let destination = unquote_word(&trimmed[positional.first()?.clone()]);
+    // `user@host`, or `ssh://user@host:port`.
+    let (destination, is_uri) = destination
+        .strip_prefix("ssh://")
+        .map_or((destination.as_str(), false), |rest| (rest, true));
+    let host = destination.rsplit('@').next().unwrap_or(destination);
+    let host = if is_uri {
+        host.split(':').next().unwrap_or(host)
+    } else {
+        host
+    }
+    .to_string();
+
+    let bytes = trimmed.as_bytes();
+    let mut words = Vec::new();
+    let mut idx = command_start;
+    loop {
+        idx = skip_ascii_whitespace(bytes, idx, bytes.len());
+        let end = consume_word_token(bytes, idx, bytes.len());
+        if end == idx {
+            break;
+        }
+        words.push(unquote_word(&trimmed[idx..end]));
+        idx = end;
+    }
+    let remote = words.join(" ");
+    if remote.trim().is_empty() {
+        return None;
+    }
+
+    Some((
+        remote,
+        host,
+        StrippedWrapper {
+            wrapper_type: "ssh",
+            stripped_text: trimmed[..command_start].trim_end().to_string(),
+        },
+    ))
+}
+
+/// Remove shell quoting from a single word (`'...'`, `"..."`, `\x`).
+fn unquote_word(word: &str) -> String {
+    let mut out = String::with_capacity(word.len());
+    let mut chars = word.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => out.extend(chars.by_ref().take_while(|&c| c != '\'')),
+            '"' => {
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' => {
+                            if let Some(next) = chars.next() {
+                                if !matches!(next, '"' | '\\' | '$' | '`') {
+                                    out.push('\\');
+                                }
+                                out.push(next);
+                            }
+                        }
+                        _ => out.push(c),
+                    }
+                }
+            }
+            '\\' => out.extend(chars.next()),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// A command with its whole wrapper chain resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WrapperChain {
+    /// The innermost command.
+    pub command: String,
+    /// Wrappers that were removed, outermost first.
+    pub wrappers: Vec<StrippedWrapper>,
+    /// Hosts `ssh` runs the command on, outermost first.
+    pub remote_hosts: Vec<String>,
+}
+
+impl WrapperChain {
+    /// Whether the chain has a wrapper that [`strip_wrapper_prefixes`] does
+    /// not remove (`ssh` or a `[wrappers] extra` command), so the innermost
+    /// command needs its own evaluation.
+    #[must_use]
+    pub fn needs_inner_evaluation(&self) -> bool {
+        self.wrappers
+            .iter()
+            .any(|w| matches!(w.wrapper_type, "ssh" | "wrapper"))
+    }
+}
+
+/// Resolve a wrapper chain down to the command that finally runs.
+///
+/// Applies [`strip_wrapper_prefixes`], the `[wrappers] extra` commands, and
+/// (when enabled) `ssh host '<cmd>'` repeatedly, so
+/// `sudo ssh -p 2222 db1 "sudo -u postgres timeout 60 psql -c 'DROP ...'"`
+/// resolves to `psql -c 'DROP ...'` on `db1`.
+#[must_use]
+pub fn resolve_wrapper_chain(
+    command: &str,
+    config: &crate::config::WrappersConfig,
+) -> WrapperChain {
+    const MAX_CHAIN_DEPTH: usize = 16;
+
+    let mut chain = WrapperChain {
+        command: command.trim().to_string(),
+        wrappers: Vec::new(),
+        remote_hosts: Vec::new(),
+    };
+    for _ in 0..MAX_CHAIN_DEPTH {
+        let stripped = strip_wrapper_prefixes(&chain.command);
+        if stripped.was_normalized() {
+            let wrappers = stripped.stripped_wrappers;
+            chain.command = stripped.normalized.into_owned();
+            chain.wrappers.extend(wrappers);
+            continue;
+        }
+        if let Some((remaining, wrapper)) = strip_extra_wrapper(&chain.command, &config.extra) {
+            chain.command = remaining;
+            chain.wrappers.push(wrapper);
+            continue;
+        }
+        if config.ssh {
+            if let Some((remote, host, wrapper)) = strip_ssh(&chain.command) {
+                chain.command = remote;
+                chain.remote_hosts.push(host);
+                chain.wrappers.push(wrapper);
+                continue;
+            }
+        }
+        break;
+    }
+    chain
+}
+
 #[must_use]
 pub fn consume_word_token(bytes: &[u8], mut i: usize, len: usize) -> usize {
     while i < len {
@@ -1177,6 +1666,14 @@ pub fn normalize_command_word_token(token: &str) -> Option<String> {
         false
     };
 
+    // Windows paths (`C:\Tools\git.exe`, `\\server\share\git.exe`) use backslash as
+    // the separator, not as an escape. Switch to forward slashes so the escape handling
+    // below doesn't glue the segments together and `PATH_NORMALIZER` can strip them.
+    if let Some(converted) = windows_path_with_forward_slashes(&out) {
+        out = converted;
+        changed = true;
+    }
+
     let stripped = out.trim_start_matches('\\');
     if !stripped.is_empty() && stripped.len() != out.len() {
         // Only strip leading backslashes when it looks like an escaped command word.
@@ -1306,6 +1803,26 @@ pub fn normalize_command_word_token(token: &str) -> Option<String> {
     if changed { Some(out) } else { None }
 }
 
+/// Rewrite a Windows drive or UNC path (optionally quoted) with forward slashes.
+///
+/// Returns `None` for anything else, including `\git` alias bypasses.
+fn windows_path_with_forward_slashes(token: &str) -> Option<String> {
+    if !token.contains('\\') {
+        return None;
+    }
+    let inner = token.strip_prefix(['"', '\'']).unwrap_or(token);
+    let bytes = inner.as_bytes();
+    let is_drive_path = bytes.len() >= 3
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && matches!(bytes[2], b'\\' | b'/');
+    let is_unc_path = bytes.len() >= 3
+        && bytes[0] == b'\\'
+        && bytes[1] == b'\\'
+        && bytes[2].is_ascii_alphanumeric();
+    (is_drive_path || is_unc_path).then(|| token.replace('\\', "/"))
+}
+
 #[inline]
 fn looks_like_subcommand_word(token: &str) -> bool {
     // Treat only simple alnum/underscore/dash words as subcommands.
@@ -1373,6 +1890,7 @@ fn normalize_subcommand_token(token: &str) -> Option<String> {
 /// - `"git" reset --hard` → `git reset --hard`
 /// - `sudo "/bin/rm" -rf /etc` → `sudo /bin/rm -rf /etc`
 /// - `git.exe reset --hard` → `git reset --hard`
+/// - `C:\Git\bin\git.exe reset --hard` → `C:/Git/bin/git reset --hard`
 ///
 /// Quoted **arguments** are intentionally left alone *unless* they look like
 /// subcommand words (e.g., `git "reset" --hard`). Path-like tokens (e.g. quoted
@@ -1686,6 +2204,108 @@ mod tests {
         assert!(!result.was_normalized());
     }
 
+    #[test]
+    fn test_exec_wrappers() {
+        let cases = [
+            ("doas -u root git reset --hard", "doas"),
+            ("nice -n 10 git reset --hard", "nice"),
+            ("nice -10 git reset --hard", "nice"),
+            ("ionice -c3 -n7 git reset --hard", "ionice"),
+            ("nohup git reset --hard", "nohup"),
+            ("setsid -f git reset --hard", "setsid"),
+            ("timeout 10 git reset --hard", "timeout"),
+            (
+                "timeout -s KILL --preserve-status 5m git reset --hard",
+                "timeout",
+            ),
+            ("stdbuf -oL -e0 git reset --hard", "stdbuf"),
+            ("xargs -0 -r -I {} git reset --hard", "xargs"),
+            ("/usr/bin/xargs --max-procs=4 -n1 git reset --hard", "xargs"),
+        ];
+        for (command, wrapper) in cases {
+            let result = strip_wrapper_prefixes(command);
+            assert_eq!(result.normalized, "git reset --hard", "{command}");
+            assert_eq!(result.stripped_wrappers[0].wrapper_type, wrapper);
+        }
+
+        let result = strip_wrapper_prefixes("sudo nice -n 5 timeout 30 rm -rf /srv");
+        assert_eq!(result.normalized, "rm -rf /srv");
+        let types: Vec<_> = result
+            .stripped_wrappers
+            .iter()
+            .map(|w| w.wrapper_type)
+            .collect();
+        assert_eq!(types, ["sudo", "nice", "timeout"]);
+    }
+
+    #[test]
+    fn test_exec_wrappers_left_alone_when_ambiguous() {
+        for command in [
+            "timeout 10",
+            "nohup",
+            "ionice -p 1234",
+            "doas -C /etc/doas.conf rm",
+            "nice --unknown rm -rf /",
+            "xargs",
+            "nicer rm -rf /",
+        ] {
+            let result = strip_wrapper_prefixes(command);
+            assert!(!result.was_normalized(), "{command}");
+        }
+    }
+
+    #[test]
+    fn test_resolve_wrapper_chain_through_ssh() {
+        let config = crate::config::WrappersConfig::default();
+        let chain = resolve_wrapper_chain(
+            r#"sudo ssh -p 2222 -o BatchMode=yes deploy@db1 "sudo -u postgres timeout 60 psql -c 'DROP TABLE users'""#,
+            &config,
+        );
+        assert_eq!(chain.command, "psql -c 'DROP TABLE users'");
+        assert_eq!(chain.remote_hosts, ["db1"]);
+        assert!(chain.needs_inner_evaluation());
+
+        let chain = resolve_wrapper_chain(
+            "ssh -J bastion ssh://admin@web1:22 ssh web2 rm -rf /var/www",
+            &config,
+        );
+        assert_eq!(chain.command, "rm -rf /var/www");
+        assert_eq!(chain.remote_hosts, ["web1", "web2"]);
+
+        // A login shell, or a mode that runs no command, is not a wrapper.
+        for command in [
+            "ssh db1",
+            "ssh -N -L 5432:localhost:5432 db1",
+            "ssh -G db1 uptime",
+        ] {
+            let chain = resolve_wrapper_chain(command, &config);
+            assert!(chain.remote_hosts.is_empty(), "{command}");
+        }
+
+        let chain = resolve_wrapper_chain("sudo git reset --hard", &config);
+        assert_eq!(chain.command, "git reset --hard");
+        assert!(!chain.needs_inner_evaluation());
+
+        let no_ssh = crate::config::WrappersConfig {
+            ssh: false,
+            ..Default::default()
+        };
+        let chain = resolve_wrapper_chain("ssh db1 'rm -rf /'", &no_ssh);
+        assert_eq!(chain.command, "ssh db1 'rm -rf /'");
+    }
+
+    #[test]
+    fn test_resolve_wrapper_chain_extra_wrappers() {
+        let config = crate::config::WrappersConfig {
+            extra: vec!["chronic".to_string()],
+            ..Default::default()
+        };
+        let chain = resolve_wrapper_chain("chronic -e nice git clean -fdx", &config);
+        assert_eq!(chain.command, "git clean -fdx");
+        assert_eq!(chain.wrappers[0].stripped_text, "chronic -e");
+        assert!(chain.needs_inner_evaluation());
+    }
+
     #[test]
     fn test_command_wrapper() {
         let result = strip_wrapper_prefixes("command git reset --hard");
@@ -1900,6 +2520,50 @@ mod windows_exe_tests {
         eprintln!("Normalized result: {:?}", result.as_ref());
         assert_eq!(result.as_ref(), "git reset --hard");
     }
+
+    #[test]
+    fn test_backslash_windows_path_normalization() {
+        assert_eq!(
+            normalize_command(r"C:\Git\bin\git.exe reset --hard").as_ref(),
+            "git reset --hard"
+        );
+        assert_eq!(
+            normalize_command(r"c:\msys64\usr\bin\rm -rf C:\work").as_ref(),
+            r"rm -rf C:\work"
+        );
+        assert_eq!(
+            normalize_command_word_token(r"\\fileserver\tools\git.exe"),
+            Some("//fileserver/tools/git".to_string())
+        );
+        // Arguments keep their backslashes.
+        assert_eq!(
+            normalize_command(r"rmdir /s /q C:\Users\me\project").as_ref(),
+            r"rmdir /s /q C:\Users\me\project"
+        );
+    }
+
+    #[test]
+    fn test_cmd_wrapper() {
+        let result = strip_wrapper_prefixes(r"cmd /c rmdir /s /q C:\build");
+        assert_eq!(result.normalized, r"rmdir /s /q C:\build");
+        assert_eq!(result.stripped_wrappers[0].wrapper_type, "cmd");
+        assert_eq!(result.stripped_wrappers[0].stripped_text, "cmd /c");
+
+        let result =
+            strip_wrapper_prefixes(r#"C:\Windows\System32\CMD.EXE /d /s /c "del /s /q C:\build""#);
+        assert_eq!(result.normalized, r"del /s /q C:\build");
+
+        let result = strip_wrapper_prefixes("cmd.exe /K git reset --hard");
+        assert_eq!(result.normalized, "git reset --hard");
+    }
+
+    #[test]
+    fn test_cmd_without_command_not_wrapper() {
+        assert!(!strip_wrapper_prefixes("cmd").was_normalized());
+        assert!(!strip_wrapper_prefixes("cmd /c").was_normalized());
+        assert!(!strip_wrapper_prefixes("cmd /?").was_normalized());
+        assert!(!strip_wrapper_prefixes("cmd --help").was_normalized());
+    }
 }
 
 #[test]
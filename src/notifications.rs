@@ -0,0 +1,509 @@
+//! Webhook notifications for denied commands.
+//!
+//! The hook must never wait on the network, so delivery is split in two:
+//!
+//! 1. On a denial at or above `[notifications] min_severity`, the hook appends
+//!    a redacted [`DenyEvent`] to a spool file and, if no flusher is running,
+//!    spawns a detached `dcg notify flush`.
+//! 2. The flusher waits `batch_window_ms` for more events, drains the spool,
+//!    and posts one message per webhook. It keeps draining until the spool is
+//!    empty, then exits.
+//!
+//! Each webhook has a circuit breaker: after `failure_threshold` consecutive
+//! failures it is skipped for `cooldown_secs`, and events for it are dropped
+//! rather than piling up. The spool is capped at [`MAX_SPOOL_BYTES`].
+//!
+//! Everything is best-effort: errors are swallowed on the hook side so a
+//! broken webhook can never change a decision.
+
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::Utc;
+use fs2::FileExt;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::config::{NotificationsConfig, WebhookConfig, WebhookFormat};
+use crate::logging::RedactionConfig;
+use crate::packs::Severity;
+
+/// Environment variable overriding the spool directory.
+pub const ENV_NOTIFICATIONS_DIR: &str = "DCG_NOTIFICATIONS_DIR";
+
+/// Spool size above which new events are dropped.
+pub const MAX_SPOOL_BYTES: u64 = 1024 * 1024;
+
+const SPOOL_FILE: &str = "spool.jsonl";
+const FLUSH_LOCK_FILE: &str = "flush.lock";
+const STATE_FILE: &str = "state.json";
+/// Events listed individually in Slack/Discord messages; the rest are summarized.
+const MAX_EVENTS_PER_MESSAGE: usize = 20;
+/// Discord rejects message content longer than this.
+const DISCORD_CONTENT_LIMIT: usize = 2000;
+
+/// A denied command, as sent to webhooks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DenyEvent {
+    /// RFC 3339 time of the denial.
+    pub timestamp: String,
+    /// Command text after redaction.
+    pub command: String,
+    /// Pack that matched, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pack_id: Option<String>,
+    /// Pattern that matched, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern_name: Option<String>,
+    /// Severity label (`critical`, `high`, ...).
+    pub severity: String,
+    /// Why the command was denied.
+    pub reason: String,
+    /// Name of the git repository the command ran in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repo: Option<String>,
+    /// Host the hook ran on.
+    pub host: String,
+}
+
+impl DenyEvent {
+    /// Build an event for a denial, redacting the command.
+    #[must_use]
+    pub fn new(
+        command: &str,
+        pack_id: Option<&str>,
+        pattern_name: Option<&str>,
+        severity: Option<Severity>,
+        reason: &str,
+        cwd: Option<&Path>,
+        redaction: &RedactionConfig,
+    ) -> Self {
+        let repo = cwd
+            .and_then(|dir| {
+                crate::config::find_repo_root(dir, crate::config::REPO_ROOT_SEARCH_MAX_HOPS)
+            })
+            .and_then(|root| root.file_name().map(|n| n.to_string_lossy().into_owned()));
+        Self {
+            timestamp: Utc::now().to_rfc3339(),
+            command: crate::logging::redact_command(command, redaction),
+            pack_id: pack_id.map(str::to_string),
+            pattern_name: pattern_name.map(str::to_string),
+            severity: severity.unwrap_or(Severity::High).label().to_string(),
+            reason: reason.to_string(),
+            repo,
+            host: crate::logging::local_hostname(),
+        }
+    }
+
+    /// `pack:pattern`, or the pack alone when no pattern name is known.
+    fn rule(&self) -> String {
+        match (self.pack_id.as_deref(), self.pattern_name.as_deref()) {
+            (Some(pack), Some(pattern)) => format!("{pack}:{pattern}"),
+            (Some(pack), None) => pack.to_string(),
+            _ => "custom rule".to_string(),
+        }
+    }
+
+    /// One-line summary for chat messages.
+    fn summary_line(&self) -> String {
+        let location = self.repo.as_deref().map_or_else(
+            || self.host.clone(),
+            |repo| format!("{repo} on {}", self.host),
+        );
+        format!(
+            "`{}` ({}, {}) in {location}",
+            self.command.replace('`', "'"),
+            self.rule(),
+            self.severity
+        )
+    }
+}
+
+/// Queues denials for the background flusher.
+#[derive(Debug, Clone)]
+pub struct Notifier {
+    dir: PathBuf,
+    config: NotificationsConfig,
+}
+
+impl Notifier {
+    /// Create a notifier, or `None` when notifications are disabled or no
+    /// spool directory can be determined.
+    #[must_use]
+    pub fn new(config: &NotificationsConfig) -> Option<Self> {
+        if !config.is_active() {
+            return None;
+        }
+        Some(Self {
+            dir: spool_dir()?,
+            config: config.clone(),
+        })
+    }
+
+    /// Queue `event` if its severity qualifies and start a flusher if none is
+    /// running. Never blocks on the network.
+    pub fn notify_deny(&self, event: &DenyEvent) {
+        let severity = Severity::from_label(&event.severity).unwrap_or(Severity::High);
+        if !self.config.min_severity.should_block(severity) {
+            return;
+        }
+        if append_event(&self.dir, event).is_ok() && !flusher_running(&self.dir) {
+            let _ = spawn_flusher();
+        }
+    }
+}
+
+/// Outcome of a flush, for `dcg notify flush`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct FlushSummary {
+    /// Events drained from the spool.
+    pub events: usize,
+    /// Webhook requests that succeeded.
+    pub delivered: usize,
+    /// Webhook requests that failed.
+    pub failed: usize,
+    /// Webhook deliveries skipped because the circuit was open.
+    pub skipped: usize,
+}
+
+/// Drain the spool and deliver batches until it is empty.
+///
+/// Returns immediately with an empty summary if another flusher holds the
+/// lock.
+///
+/// # Errors
+///
+/// Returns an error if the spool directory cannot be determined or accessed.
+pub fn flush(config: &NotificationsConfig) -> io::Result<FlushSummary> {
+    let dir = spool_dir().ok_or_else(|| io::Error::other("no notification spool directory"))?;
+    fs::create_dir_all(&dir)?;
+    let lock = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(dir.join(FLUSH_LOCK_FILE))?;
+    if lock.try_lock_exclusive().is_err() {
+        return Ok(FlushSummary::default());
+    }
+
+    let mut summary = FlushSummary::default();
+    let mut state = BreakerState::load(&dir);
+    loop {
+        std::thread::sleep(Duration::from_millis(config.batch_window_ms));
+        let events = drain_spool(&dir)?;
+        if events.is_empty() {
+            break;
+        }
+        summary.events += events.len();
+        let now = Utc::now().timestamp();
+        for webhook in &config.webhooks {
+            let key = webhook_key(&webhook.url);
+            if state.is_open(&key, now) {
+                summary.skipped += 1;
+                continue;
+            }
+            let delivered = send(webhook, &events, config.timeout_ms).is_ok();
+            if delivered {
+                summary.delivered += 1;
+            } else {
+                summary.failed += 1;
+            }
+            state.record(&key, delivered, now, config);
+        }
+        let _ = state.save(&dir);
+    }
+    let _ = FileExt::unlock(&lock);
+    Ok(summary)
+}
+
+/// Post `events` to one webhook.
+///
+/// # Errors
+///
+/// Returns an error if the request fails or the endpoint answers with a
+/// non-2xx status.
+pub fn send(webhook: &WebhookConfig, events: &[DenyEvent], timeout_ms: u64) -> io::Result<()> {
+    let body = render_payload(webhook.format, events);
+    let response = crate::http::request(
+        "POST",
+        &webhook.url,
+        &[("Content-Type", "application/json")],
+        Some(body.as_bytes()),
+        Duration::from_millis(timeout_ms.max(1)),
+    )?;
+    if response.is_success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!(
+            "webhook answered HTTP {}",
+            response.status
+        )))
+    }
+}
+
+/// Resolve the spool directory: `DCG_NOTIFICATIONS_DIR`, then
+/// `<cache dir>/dcg/notifications`.
+#[must_use]
+pub fn spool_dir() -> Option<PathBuf> {
+    if let Ok(dir) = std::env::var(ENV_NOTIFICATIONS_DIR) {
+        return Some(PathBuf::from(dir));
+    }
+    dirs::cache_dir().map(|dir| dir.join("dcg").join("notifications"))
+}
+
+fn append_event(dir: &Path, event: &DenyEvent) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join(SPOOL_FILE))?;
+    file.lock_exclusive()?;
+    let result = if file.metadata()?.len() >= MAX_SPOOL_BYTES {
+        Err(io::Error::other("notification spool is full"))
+    } else {
+        let mut line = serde_json::to_string(event).map_err(io::Error::other)?;
+        line.push('\n');
+        file.write_all(line.as_bytes())
+    };
+    let _ = FileExt::unlock(&file);
+    result
+}
+
+/// Read and clear the spool. Lines that fail to parse are dropped.
+fn drain_spool(dir: &Path) -> io::Result<Vec<DenyEvent>> {
+    let mut file = match OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(dir.join(SPOOL_FILE))
+    {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    file.lock_exclusive()?;
+    let events = BufReader::new(&file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str(&line).ok())
+        .collect();
+    file.set_len(0)?;
+    file.seek(SeekFrom::Start(0))?;
+    let _ = FileExt::unlock(&file);
+    Ok(events)
+}
+
+fn flusher_running(dir: &Path) -> bool {
+    let Ok(lock) = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(dir.join(FLUSH_LOCK_FILE))
+    else {
+        return false;
+    };
+    if lock.try_lock_exclusive().is_err() {
+        return true;
+    }
+    let _ = FileExt::unlock(&lock);
+    false
+}
+
+fn spawn_flusher() -> io::Result<()> {
+    let exe = std::env::current_exe()?;
+    std::process::Command::new(exe)
+        .args(["notify", "flush"])
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .map(drop)
+}
+
+/// Per-webhook circuit breaker state, keyed by a hash of the URL so secrets
+/// in webhook URLs are not written to disk.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct BreakerState {
+    webhooks: HashMap<String, BreakerEntry>,
+}
+
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+struct BreakerEntry {
+    failures: u32,
+    open_until: i64,
+}
+
+impl BreakerState {
+    fn load(dir: &Path) -> Self {
+        File::open(dir.join(STATE_FILE))
+            .ok()
+            .and_then(|mut file| {
+                let mut content = String::new();
+                file.read_to_string(&mut content).ok()?;
+                serde_json::from_str(&content).ok()
+            })
+            .unwrap_or_default()
+    }
+
+    fn save(&self, dir: &Path) -> io::Result<()> {
+        let content = serde_json::to_string(self).map_err(io::Error::other)?;
+        fs::write(dir.join(STATE_FILE), content)
+    }
+
+    fn is_open(&self, key: &str, now: i64) -> bool {
+        self.webhooks
+            .get(key)
+            .is_some_and(|entry| entry.open_until > now)
+    }
+
+    fn record(&mut self, key: &str, delivered: bool, now: i64, config: &NotificationsConfig) {
+        let entry = self.webhooks.entry(key.to_string()).or_default();
+        if delivered {
+            *entry = BreakerEntry::default();
+            return;
+        }
+        entry.failures += 1;
+        if entry.failures >= config.failure_threshold.max(1) {
+            entry.failures = 0;
+            entry.open_until =
+                now.saturating_add(i64::try_from(config.cooldown_secs).unwrap_or(i64::MAX));
+        }
+    }
+}
+
+fn webhook_key(url: &str) -> String {
+    use std::fmt::Write as _;
+
+    let digest = Sha256::digest(url.as_bytes());
+    digest
+        .iter()
+        .take(8)
+        .fold(String::with_capacity(16), |mut out, b| {
+            let _ = write!(out, "{b:02x}");
+            out
+        })
+}
+
+/// Render the request body for a webhook format.
+#[must_use]
+pub fn render_payload(format: WebhookFormat, events: &[DenyEvent]) -> String {
+    let payload = match format {
+        WebhookFormat::Json => serde_json::json!({
+            "source": "dcg",
+            "version": env!("CARGO_PKG_VERSION"),
+            "events": events,
+        }),
+        WebhookFormat::Slack => serde_json::json!({ "text": chat_message(events) }),
+        WebhookFormat::Discord => {
+            let mut content = chat_message(events);
+            if content.chars().count() > DISCORD_CONTENT_LIMIT {
+                content = content.chars().take(DISCORD_CONTENT_LIMIT - 1).collect();
+                content.push('…');
+            }
+            serde_json::json!({ "content": content })
+        }
+    };
+    payload.to_string()
+}
+
+fn chat_message(events: &[DenyEvent]) -> String {
+    let header = if events.len() == 1 {
+        "dcg blocked a destructive command:".to_string()
+    } else {
+        format!("dcg blocked {} destructive commands:", events.len())
+    };
+    let mut lines = vec![header];
+    lines.extend(
+        events
+            .iter()
+            .take(MAX_EVENTS_PER_MESSAGE)
+            .map(|event| format!("• {}", event.summary_line())),
+    );
+    if events.len() > MAX_EVENTS_PER_MESSAGE {
+        lines.push(format!(
+            "…and {} more",
+            events.len() - MAX_EVENTS_PER_MESSAGE
+        ));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn event(command: &str) -> DenyEvent {
+        DenyEvent {
+            timestamp: "2026-01-01T00:00:00+00:00".to_string(),
+            command: command.to_string(),
+            pack_id: Some("core.git".to_string()),
+            pattern_name: Some("reset-hard".to_string()),
+            severity: "critical".to_string(),
+            reason: "destroys uncommitted changes".to_string(),
+            repo: Some("app".to_string()),
+            host: "build-1".to_string(),
+        }
+    }
+
+    #[test]
+    fn renders_each_payload_format() {
+        let events = vec![event("git reset --hard"), event("rm -rf build")];
+
+        let json: serde_json::Value =
+            serde_json::from_str(&render_payload(WebhookFormat::Json, &events)).unwrap();
+        assert_eq!(json["source"], "dcg");
+        assert_eq!(json["events"].as_array().unwrap().len(), 2);
+        assert_eq!(json["events"][0]["pack_id"], "core.git");
+        assert_eq!(json["events"][0]["host"], "build-1");
+
+        let slack: serde_json::Value =
+            serde_json::from_str(&render_payload(WebhookFormat::Slack, &events)).unwrap();
+        let text = slack["text"].as_str().unwrap();
+        assert!(text.starts_with("dcg blocked 2 destructive commands:"));
+        assert!(
+            text.contains("`git reset --hard` (core.git:reset-hard, critical) in app on build-1")
+        );
+
+        let many: Vec<DenyEvent> = (0..300).map(|i| event(&"x".repeat(i))).collect();
+        let discord: serde_json::Value =
+            serde_json::from_str(&render_payload(WebhookFormat::Discord, &many)).unwrap();
+        let content = discord["content"].as_str().unwrap();
+        assert!(content.chars().count() <= DISCORD_CONTENT_LIMIT);
+    }
+
+    #[test]
+    fn spool_round_trips_and_drains() {
+        let dir = TempDir::new().unwrap();
+        append_event(dir.path(), &event("git reset --hard")).unwrap();
+        append_event(dir.path(), &event("rm -rf build")).unwrap();
+
+        let drained = drain_spool(dir.path()).unwrap();
+        assert_eq!(drained.len(), 2);
+        assert_eq!(drained[1].command, "rm -rf build");
+        assert!(drain_spool(dir.path()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn circuit_opens_after_threshold_and_resets_on_success() {
+        let config = NotificationsConfig {
+            failure_threshold: 2,
+            cooldown_secs: 60,
+            ..NotificationsConfig::default()
+        };
+        let mut state = BreakerState::default();
+        state.record("a", false, 100, &config);
+        assert!(!state.is_open("a", 100));
+        state.record("a", false, 100, &config);
+        assert!(state.is_open("a", 100));
+        assert!(state.is_open("a", 159));
+        assert!(!state.is_open("a", 160));
+
+        state.record("a", false, 200, &config);
+        state.record("a", true, 200, &config);
+        state.record("a", false, 200, &config);
+        assert!(!state.is_open("a", 200));
+    }
+}
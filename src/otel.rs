@@ -0,0 +1,241 @@
+//! OpenTelemetry export of evaluation spans and decision counters.
+//!
+//! The evaluator always opens `tracing` spans for its stages (`dcg.heredoc`,
+//! `dcg.quick_reject`, `dcg.sanitize`, `dcg.pack_loop`). Without a subscriber
+//! they cost a few nanoseconds each. When dcg is built with the `otel` feature
+//! and `[logging.otel]` is enabled, the hook installs a subscriber that turns
+//! those spans into OTLP spans under a `dcg.evaluate` root, and records:
+//!
+//! - `dcg.decisions` — counter with `decision` (allow/warn/deny/log) and
+//!   `pack` attributes. Commands that matched no rule use `pack = "none"`.
+//! - `dcg.evaluation.duration` — histogram of evaluation time in microseconds.
+//!
+//! Every hook invocation is a fresh process, so nothing is exported while the
+//! command is being evaluated: spans and metrics are batched and flushed when
+//! the [`OtelExporter`] is dropped, bounded by `timeout_ms`. Export failures
+//! are ignored; telemetry can never block a command.
+//!
+//! Without the `otel` feature, [`OtelExporter::init`] always returns `None`.
+
+/// Label for commands that matched no pack.
+pub const NO_PACK: &str = "none";
+
+#[cfg(feature = "otel")]
+pub use enabled::OtelExporter;
+
+#[cfg(not(feature = "otel"))]
+pub use disabled::OtelExporter;
+
+#[cfg(feature = "otel")]
+mod enabled {
+    use std::time::Duration;
+
+    use opentelemetry::KeyValue;
+    use opentelemetry::metrics::{Counter, Histogram, MeterProvider as _};
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry_otlp::{MetricExporter, Protocol, SpanExporter, WithExportConfig};
+    use opentelemetry_sdk::Resource;
+    use opentelemetry_sdk::metrics::{PeriodicReader, SdkMeterProvider};
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+    use tracing_subscriber::layer::SubscriberExt;
+    use tracing_subscriber::util::SubscriberInitExt;
+
+    use crate::logging::OtelConfig;
+
+    /// Installed OTLP pipeline; flushes on drop.
+    pub struct OtelExporter {
+        tracer_provider: Option<SdkTracerProvider>,
+        meter_provider: Option<SdkMeterProvider>,
+        decisions: Option<Counter<u64>>,
+        duration: Option<Histogram<u64>>,
+        root: tracing::span::EnteredSpan,
+    }
+
+    impl OtelExporter {
+        /// Install the OTLP pipeline, or `None` when `[logging.otel]` is
+        /// disabled or the exporters cannot be built.
+        #[must_use]
+        pub fn init(config: &OtelConfig) -> Option<Self> {
+            if !config.enabled || (!config.traces && !config.metrics) {
+                return None;
+            }
+            let endpoint = config.resolved_endpoint();
+            let timeout = Duration::from_millis(config.timeout_ms);
+            let resource = Resource::builder()
+                .with_service_name(config.service_name.clone())
+                .with_attribute(KeyValue::new("service.version", env!("CARGO_PKG_VERSION")))
+                .build();
+
+            let tracer_provider = if config.traces {
+                let exporter = SpanExporter::builder()
+                    .with_http()
+                    .with_protocol(Protocol::HttpBinary)
+                    .with_endpoint(format!("{endpoint}/v1/traces"))
+                    .with_timeout(timeout)
+                    .build()
+                    .ok()?;
+                let provider = SdkTracerProvider::builder()
+                    .with_batch_exporter(exporter)
+                    .with_resource(resource.clone())
+                    .build();
+                let layer = tracing_opentelemetry::layer().with_tracer(provider.tracer("dcg"));
+                // Another subscriber (tests, embedding) wins; spans then stay local.
+                let _ = tracing_subscriber::registry().with(layer).try_init();
+                Some(provider)
+            } else {
+                None
+            };
+
+            let meter_provider = if config.metrics {
+                let exporter = MetricExporter::builder()
+                    .with_http()
+                    .with_protocol(Protocol::HttpBinary)
+                    .with_endpoint(format!("{endpoint}/v1/metrics"))
+                    .with_timeout(timeout)
+                    .build()
+                    .ok()?;
+                Some(
+                    SdkMeterProvider::builder()
+                        .with_reader(PeriodicReader::builder(exporter).build())
+                        .with_resource(resource)
+                        .build(),
+                )
+            } else {
+                None
+            };
+
+            let meter = meter_provider
+                .as_ref()
+                .map(|provider| provider.meter("dcg"));
+            let decisions = meter.as_ref().map(|meter| {
+                meter
+                    .u64_counter("dcg.decisions")
+                    .with_description("Hook decisions by outcome and pack")
+                    .build()
+            });
+            let duration = meter.as_ref().map(|meter| {
+                meter
+                    .u64_histogram("dcg.evaluation.duration")
+                    .with_description("Command evaluation time")
+                    .with_unit("us")
+                    .build()
+            });
+
+            Some(Self {
+                tracer_provider,
+                meter_provider,
+                decisions,
+                duration,
+                root: tracing::info_span!("dcg.evaluate", dcg.decision = tracing::field::Empty)
+                    .entered(),
+            })
+        }
+
+        /// Record one hook decision.
+        pub fn record(&self, decision: &'static str, pack: Option<&str>, elapsed: Duration) {
+            let pack = pack.unwrap_or(super::NO_PACK).to_string();
+            self.root.record("dcg.decision", decision);
+            if let Some(counter) = self.decisions.as_ref() {
+                counter.add(
+                    1,
+                    &[
+                        KeyValue::new("decision", decision),
+                        KeyValue::new("pack", pack.clone()),
+                    ],
+                );
+            }
+            if let Some(histogram) = self.duration.as_ref() {
+                histogram.record(
+                    u64::try_from(elapsed.as_micros()).unwrap_or(u64::MAX),
+                    &[
+                        KeyValue::new("decision", decision),
+                        KeyValue::new("pack", pack),
+                    ],
+                );
+            }
+        }
+    }
+
+    impl Drop for OtelExporter {
+        fn drop(&mut self) {
+            // Close the root span before flushing so it is part of the batch.
+            let root = std::mem::replace(&mut self.root, tracing::Span::none().entered());
+            drop(root.exit());
+            if let Some(provider) = self.tracer_provider.take() {
+                let _ = provider.shutdown();
+            }
+            if let Some(provider) = self.meter_provider.take() {
+                let _ = provider.shutdown();
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "otel"))]
+mod disabled {
+    use std::time::Duration;
+
+    use crate::logging::OtelConfig;
+
+    /// Placeholder exporter for builds without the `otel` feature.
+    pub struct OtelExporter {
+        _private: (),
+    }
+
+    impl OtelExporter {
+        /// Always `None`: this build has no OTLP support. Warns on stderr when
+        /// `[logging.otel]` is enabled so the setting is not silently ignored.
+        #[must_use]
+        pub fn init(config: &OtelConfig) -> Option<Self> {
+            if config.enabled {
+                eprintln!(
+                    "[dcg] Warning: [logging.otel] is enabled but this build lacks the `otel` feature"
+                );
+            }
+            None
+        }
+
+        /// No-op.
+        pub fn record(&self, _decision: &'static str, _pack: Option<&str>, _elapsed: Duration) {}
+    }
+}
+
+/// Decision label for [`OtelExporter::record`].
+#[must_use]
+pub const fn decision_label(mode: crate::packs::DecisionMode) -> &'static str {
+    match mode {
+        crate::packs::DecisionMode::Deny => "deny",
+        crate::packs::DecisionMode::Warn => "warn",
+        crate::packs::DecisionMode::Log => "log",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logging::OtelConfig;
+    use crate::packs::DecisionMode;
+
+    #[test]
+    fn decision_labels() {
+        assert_eq!(decision_label(DecisionMode::Deny), "deny");
+        assert_eq!(decision_label(DecisionMode::Warn), "warn");
+        assert_eq!(decision_label(DecisionMode::Log), "log");
+    }
+
+    #[test]
+    fn disabled_config_installs_nothing() {
+        assert!(OtelExporter::init(&OtelConfig::default()).is_none());
+    }
+
+    #[test]
+    fn endpoint_trailing_slash_is_trimmed() {
+        let config = OtelConfig {
+            endpoint: "http://collector:4318/".to_string(),
+            ..OtelConfig::default()
+        };
+        if std::env::var_os("OTEL_EXPORTER_OTLP_ENDPOINT").is_none() {
+            assert_eq!(config.resolved_endpoint(), "http://collector:4318");
+        }
+    }
+}
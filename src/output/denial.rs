@@ -11,6 +11,7 @@
 
 use super::theme::{BorderStyle, Severity, Theme};
 use crate::highlight::{HighlightSpan, format_highlighted_command};
+use crate::i18n;
 #[cfg(feature = "rich-output")]
 use crate::output::rich_theme::{RichThemeExt, color_to_markup};
 use crate::output::terminal_width;
@@ -137,17 +138,27 @@ impl DenialBox {
 
         // 1. Header is handled by Panel title, but we add inner padding text
         let severity_markup = theme.severity_markup(self.severity);
-        lines.push(format!("[{severity_markup}]🛑 COMMAND BLOCKED[/]"));
+        lines.push(format!(
+            "[{severity_markup}]🛑 {}[/]",
+            i18n::t("box-header-short")
+        ));
         lines.push(String::new());
 
         // 2. Command with highlighting
         // Note: We use manual highlighting for now, but rich_rust Syntax could be used later
-        lines.push(format!("[dim]Command:[/]  [bold]{}[/]", self.command));
+        lines.push(format!(
+            "[dim]{}[/]  [bold]{}[/]",
+            i18n::t("box-command"),
+            self.command
+        ));
 
         // 3. Explanation
         if let Some(explanation) = &self.explanation {
             lines.push(String::new());
-            lines.push(format!("[{severity_markup}]Explanation:[/]"));
+            lines.push(format!(
+                "[{severity_markup}]{}[/]",
+                i18n::t("box-explanation")
+            ));
             for line in wrap_text(explanation, width) {
                 lines.push(line);
             }
@@ -162,7 +173,11 @@ impl DenialBox {
         // 5. Alternatives
         if !self.alternatives.is_empty() {
             lines.push(String::new());
-            lines.push(format!("[{}]Safe alternatives:[/]", theme.success_markup()));
+            lines.push(format!(
+                "[{}]{}[/]",
+                theme.success_markup(),
+                i18n::t("box-alternatives")
+            ));
             for alt in &self.alternatives {
                 lines.push(format!("  [green]•[/] {alt}"));
             }
@@ -173,7 +188,8 @@ impl DenialBox {
             lines.push(String::new());
             lines.push("[dim]─────────────────────────────────────[/]".to_string());
             lines.push(format!(
-                "[yellow]To allow once:[/] [bold]dcg allow-once {code}[/]"
+                "[yellow]{}[/] [bold]dcg allow-once {code}[/]",
+                i18n::t("box-allow-once")
             ));
         }
 
@@ -210,23 +226,25 @@ impl DenialBox {
         let pattern_lines = format_pattern_lines(&self.pattern_id, &severity_label);
 
         // Header
-        let _ = writeln!(output, "BLOCKED: Destructive Command Detected");
+        let _ = writeln!(output, "{}", i18n::t("box-header"));
         let _ = writeln!(output);
 
         // Command with highlighting
         let highlighted =
             format_highlighted_command(&self.command, &self.span, false, terminal_width().into());
-        let _ = writeln!(output, "  Command: {}", highlighted.command_line);
-        let _ = writeln!(output, "           {}", highlighted.caret_line);
+        let command_label = i18n::t("box-command");
+        let indent = " ".repeat(command_label.chars().count() + 3);
+        let _ = writeln!(output, "  {command_label} {}", highlighted.command_line);
+        let _ = writeln!(output, "{indent}{}", highlighted.caret_line);
         if let Some(label) = &highlighted.label_line {
-            let _ = writeln!(output, "           {label}");
+            let _ = writeln!(output, "{indent}{label}");
         }
         let _ = writeln!(output);
 
         // Explanation
         if let Some(explanation) = &self.explanation {
             let _ = writeln!(output);
-            let _ = writeln!(output, "  Explanation:");
+            let _ = writeln!(output, "  {}", i18n::t("box-explanation"));
             for line in wrap_text(explanation, width.saturating_sub(2)) {
                 let _ = writeln!(output, "  {line}");
             }
@@ -241,7 +259,7 @@ impl DenialBox {
         // Alternatives
         if !self.alternatives.is_empty() {
             let _ = writeln!(output);
-            let _ = writeln!(output, "  Safe alternatives:");
+            let _ = writeln!(output, "  {}", i18n::t("box-alternatives"));
             for alt in &self.alternatives {
                 let _ = writeln!(output, "    - {alt}");
             }
@@ -260,10 +278,14 @@ impl DenialBox {
         let success_code = ansi_color_code(theme.success_color);
         let pattern_lines =
             format_pattern_lines(&self.pattern_id, theme.severity_label(self.severity));
-        let explanation_label = format!("\x1b[1;{}mExplanation:\x1b[0m", &severity_code);
+        let explanation_label = format!(
+            "\x1b[1;{}m{}\x1b[0m",
+            severity_code,
+            i18n::t("box-explanation")
+        );
 
         // Top border with header
-        let header = " \u{26d4}  BLOCKED: Destructive Command Detected ";
+        let header = format!(" \u{26d4}  {} ", i18n::t("box-header"));
         let header_len = header.chars().count();
         let top_pad = width.saturating_sub(header_len);
 
@@ -394,14 +416,14 @@ impl DenialBox {
                 &severity_code
             );
 
-            let alt_header = "Safe alternatives:";
+            let alt_header = i18n::t("box-alternatives");
             let _ = writeln!(
                 output,
                 "\x1b[{}m\u{2502}\x1b[0m  \x1b[{}m{}\x1b[0m{}  \x1b[{}m\u{2502}\x1b[0m",
                 &severity_code,
                 &success_code,
                 alt_header,
-                padding_for(alt_header, width.saturating_sub(4)),
+                padding_for(&alt_header, width.saturating_sub(4)),
                 &severity_code
             );
 
@@ -439,7 +461,7 @@ impl DenialBox {
             format_pattern_lines(&self.pattern_id, theme.severity_label(self.severity));
 
         // Top border with header
-        let header = " !  BLOCKED: Destructive Command Detected ";
+        let header = format!(" !  {} ", i18n::t("box-header"));
         let header_len = header.chars().count();
         let top_pad = width.saturating_sub(header_len);
 
@@ -482,12 +504,12 @@ impl DenialBox {
         // Explanation
         if let Some(explanation) = &self.explanation {
             let _ = writeln!(output, "|{}  |", " ".repeat(width.saturating_sub(2)));
-            let explanation_label = "EXPLANATION:";
+            let explanation_label = i18n::t("box-explanation").to_uppercase();
             let _ = writeln!(
                 output,
                 "|  {}{}  |",
                 explanation_label,
-                padding_for(explanation_label, width.saturating_sub(4))
+                padding_for(&explanation_label, width.saturating_sub(4))
             );
             for line in wrap_text(explanation, width.saturating_sub(4)) {
                 let _ = writeln!(
@@ -513,12 +535,12 @@ impl DenialBox {
         // Alternatives
         if !self.alternatives.is_empty() {
             let _ = writeln!(output, "|{}  |", " ".repeat(width.saturating_sub(2)));
-            let alt_header = "Safe alternatives:";
+            let alt_header = i18n::t("box-alternatives");
             let _ = writeln!(
                 output,
                 "|  {}{}  |",
                 alt_header,
-                padding_for(alt_header, width.saturating_sub(4))
+                padding_for(&alt_header, width.saturating_sub(4))
             );
             for alt in &self.alternatives {
                 let bullet_line = format!("* {alt}");
@@ -549,8 +571,9 @@ impl DenialBox {
         // Header with color
         let _ = writeln!(
             output,
-            "\x1b[{}m\u{26d4}  BLOCKED\x1b[0m: Destructive Command Detected",
-            &severity_code
+            "\x1b[{}m\u{26d4}  {}\x1b[0m",
+            severity_code,
+            i18n::t("box-header")
         );
         let _ = writeln!(output);
 
@@ -573,7 +596,11 @@ impl DenialBox {
         // Explanation
         if let Some(explanation) = &self.explanation {
             let _ = writeln!(output);
-            let explanation_label = format!("\x1b[1;{}mExplanation:\x1b[0m", &severity_code);
+            let explanation_label = format!(
+                "\x1b[1;{}m{}\x1b[0m",
+                severity_code,
+                i18n::t("box-explanation")
+            );
             let width = terminal_width().saturating_sub(4).max(40) as usize;
             let _ = writeln!(output, "  {explanation_label}");
             for line in wrap_text(explanation, width.saturating_sub(2)) {
@@ -590,7 +617,12 @@ impl DenialBox {
         // Alternatives
         if !self.alternatives.is_empty() {
             let _ = writeln!(output);
-            let _ = writeln!(output, "  \x1b[{}mSafe alternatives:\x1b[0m", &success_code);
+            let _ = writeln!(
+                output,
+                "  \x1b[{}m{}\x1b[0m",
+                success_code,
+                i18n::t("box-alternatives")
+            );
             for alt in &self.alternatives {
                 let _ = writeln!(output, "    \x1b[{}m\u{2022}\x1b[0m {alt}", &success_code);
             }
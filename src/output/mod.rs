@@ -38,7 +38,9 @@ pub use progress::{
 #[cfg(feature = "rich-output")]
 pub use progress::{RichProgressStyle, render_progress_bar_rich};
 pub use rich_theme::{RichThemeExt, color_to_markup, severity_badge_markup, severity_panel_title};
-pub use tables::{ScanResultRow, ScanResultsTable, TableStyle};
+pub use tables::{
+    HistoryRow, HistoryTable, ScanResultRow, ScanResultsTable, StatsRow, StatsTable, TableStyle,
+};
 pub use test::{AllowedReason, TestOutcome, TestResultBox};
 pub use theme::{BorderStyle, Severity, SeverityColors, Theme, ThemePalette};
 pub use tree::{DcgTree, DcgTreeGuides, ExplainTreeBuilder, TreeNode};
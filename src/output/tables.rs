@@ -899,6 +899,95 @@ impl PackListTable {
     }
 }
 
+/// A single history row for display (`dcg history search`).
+#[derive(Debug, Clone)]
+pub struct HistoryRow {
+    /// Timestamp, already formatted for display.
+    pub timestamp: String,
+    /// Outcome label (allow, deny, warn, bypass).
+    pub outcome: String,
+    /// Rule ID (`pack:pattern`) or pack ID, if any.
+    pub rule: Option<String>,
+    /// Working directory.
+    pub working_dir: String,
+    /// The command text.
+    pub command: String,
+}
+
+/// Table renderer for history search results.
+#[derive(Debug)]
+pub struct HistoryTable {
+    rows: Vec<HistoryRow>,
+    style: TableStyle,
+    colors_enabled: bool,
+    max_width: Option<u16>,
+}
+
+impl HistoryTable {
+    /// Creates a new history table.
+    #[must_use]
+    pub fn new(rows: Vec<HistoryRow>) -> Self {
+        Self {
+            rows,
+            style: TableStyle::default(),
+            colors_enabled: true,
+            max_width: None,
+        }
+    }
+
+    /// Configures from a theme.
+    #[must_use]
+    pub fn with_theme(mut self, theme: &Theme) -> Self {
+        self.colors_enabled = theme.colors_enabled;
+        self.style = theme.border_style.into();
+        self
+    }
+
+    /// Sets maximum table width.
+    #[must_use]
+    pub fn with_max_width(mut self, width: u16) -> Self {
+        self.max_width = Some(width);
+        self
+    }
+
+    /// Renders the table to a string.
+    #[must_use]
+    pub fn render(&self) -> String {
+        if self.rows.is_empty() {
+            return String::from("No matching commands.");
+        }
+
+        let mut table = Table::new();
+        self.style.apply_preset(&mut table);
+        table.set_content_arrangement(ContentArrangement::Dynamic);
+        if let Some(width) = self.max_width {
+            table.set_width(width);
+        }
+        table.set_header(vec!["Time", "Outcome", "Rule", "Directory", "Command"]);
+
+        for row in &self.rows {
+            let mut outcome = Cell::new(&row.outcome);
+            if self.colors_enabled {
+                outcome = match row.outcome.as_str() {
+                    "deny" => outcome.fg(Color::Red).add_attribute(Attribute::Bold),
+                    "warn" => outcome.fg(Color::Yellow),
+                    "bypass" => outcome.fg(Color::Magenta),
+                    _ => outcome.fg(Color::Green),
+                };
+            }
+            table.add_row(Row::from(vec![
+                Cell::new(&row.timestamp),
+                outcome,
+                Cell::new(row.rule.as_deref().unwrap_or("-")),
+                Cell::new(&row.working_dir),
+                Cell::new(truncate_with_ellipsis(&row.command, 120)),
+            ]));
+        }
+
+        table.to_string()
+    }
+}
+
 /// Summary line formatter for table footers.
 pub fn format_summary(total: usize, categories: &[(&str, usize)]) -> String {
     let parts: Vec<String> = categories
@@ -934,6 +1023,21 @@ fn truncate_with_ellipsis(text: &str, max_chars: usize) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_history_table_renders_rows() {
+        let table = HistoryTable::new(vec![HistoryRow {
+            timestamp: "2026-01-07 10:00:00".to_string(),
+            outcome: "deny".to_string(),
+            rule: Some("core.git:reset-hard".to_string()),
+            working_dir: "/work/app".to_string(),
+            command: "git reset --hard".to_string(),
+        }]);
+        let rendered = table.render();
+        assert!(rendered.contains("core.git:reset-hard"));
+        assert!(rendered.contains("git reset --hard"));
+        assert_eq!(HistoryTable::new(vec![]).render(), "No matching commands.");
+    }
+
     #[test]
     fn test_scan_results_table_empty() {
         let table = ScanResultsTable::new(vec![]);
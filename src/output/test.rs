@@ -13,6 +13,7 @@ use super::terminal_width;
 use super::theme::BorderStyle;
 use super::theme::Theme;
 use crate::evaluator::{EvaluationDecision, EvaluationResult, PatternMatch};
+use crate::i18n;
 #[cfg(feature = "rich-output")]
 use crate::output::rich_theme::RichThemeExt;
 use crate::packs::Severity;
@@ -74,6 +75,14 @@ pub enum AllowedReason {
 }
 
 impl TestResultBox {
+    /// Localized "WOULD BE BLOCKED" / "WOULD BE ALLOWED" title.
+    fn outcome_title(&self) -> String {
+        match &self.result {
+            TestOutcome::Blocked { .. } => i18n::t("test-would-block"),
+            TestOutcome::Allowed { .. } => i18n::t("test-would-allow"),
+        }
+    }
+
     /// Create a test result box from an evaluation result.
     #[must_use]
     pub fn from_evaluation(command: impl Into<String>, eval: &EvaluationResult) -> Self {
@@ -209,23 +218,21 @@ impl TestResultBox {
         use rich_rust::r#box::{DOUBLE, HEAVY, ROUNDED};
         use rich_rust::prelude::*;
 
-        let (title, border_style, header_color): (
-            &str,
-            &'static rich_rust::r#box::BoxChars,
-            String,
-        ) = match &self.result {
-            TestOutcome::Blocked { severity, .. } => {
-                let box_style = match severity {
-                    Some(Severity::Critical) => &DOUBLE,
-                    Some(Severity::High) => &HEAVY,
-                    _ => &ROUNDED,
-                };
-                // Determine color for the title based on theme
-                let color_str = theme.error_markup();
-                (" WOULD BE BLOCKED ", box_style, color_str)
-            }
-            TestOutcome::Allowed { .. } => (" WOULD BE ALLOWED ", &ROUNDED, theme.success_markup()),
-        };
+        let title = format!(" {} ", self.outcome_title());
+        let (border_style, header_color): (&'static rich_rust::r#box::BoxChars, String) =
+            match &self.result {
+                TestOutcome::Blocked { severity, .. } => {
+                    let box_style = match severity {
+                        Some(Severity::Critical) => &DOUBLE,
+                        Some(Severity::High) => &HEAVY,
+                        _ => &ROUNDED,
+                    };
+                    // Determine color for the title based on theme
+                    let color_str = theme.error_markup();
+                    (box_style, color_str)
+                }
+                TestOutcome::Allowed { .. } => (&ROUNDED, theme.success_markup()),
+            };
 
         // Build content as a Vec of lines
         let mut lines = Vec::new();
@@ -319,7 +326,7 @@ impl TestResultBox {
                 confidence,
                 explanation,
             } => {
-                let _ = writeln!(output, "WOULD BE BLOCKED");
+                let _ = writeln!(output, "{}", self.outcome_title());
                 let _ = writeln!(output);
                 let _ = writeln!(output, "  Command:    {}", self.command);
                 if let Some(pattern) = pattern_id {
@@ -340,7 +347,7 @@ impl TestResultBox {
                 }
             }
             TestOutcome::Allowed { reason } => {
-                let _ = writeln!(output, "WOULD BE ALLOWED");
+                let _ = writeln!(output, "{}", self.outcome_title());
                 let _ = writeln!(output);
                 let _ = writeln!(output, "  Command:    {}", self.command);
                 match reason {
@@ -371,9 +378,10 @@ impl TestResultBox {
         let width = terminal_width().saturating_sub(4).max(40) as usize;
         let mut output = String::new();
 
-        let (header, header_color) = match &self.result {
-            TestOutcome::Blocked { .. } => (" WOULD BE BLOCKED ", theme.error_color),
-            TestOutcome::Allowed { .. } => (" WOULD BE ALLOWED ", theme.success_color),
+        let header = format!(" {} ", self.outcome_title());
+        let header_color = match &self.result {
+            TestOutcome::Blocked { .. } => theme.error_color,
+            TestOutcome::Allowed { .. } => theme.success_color,
         };
 
         let color_code = ansi_color_code(header_color);
@@ -526,10 +534,7 @@ impl TestResultBox {
         let width = terminal_width().saturating_sub(4).max(40) as usize;
         let mut output = String::new();
 
-        let header = match &self.result {
-            TestOutcome::Blocked { .. } => " WOULD BE BLOCKED ",
-            TestOutcome::Allowed { .. } => " WOULD BE ALLOWED ",
-        };
+        let header = format!(" {} ", self.outcome_title());
 
         let header_len = header.chars().count();
         let top_pad = width.saturating_sub(header_len);
@@ -630,9 +635,10 @@ impl TestResultBox {
     fn render_minimal(&self, theme: &Theme) -> String {
         let mut output = String::new();
 
-        let (header, header_color) = match &self.result {
-            TestOutcome::Blocked { .. } => ("WOULD BE BLOCKED", theme.error_color),
-            TestOutcome::Allowed { .. } => ("WOULD BE ALLOWED", theme.success_color),
+        let header = self.outcome_title();
+        let header_color = match &self.result {
+            TestOutcome::Blocked { .. } => theme.error_color,
+            TestOutcome::Allowed { .. } => theme.success_color,
         };
 
         let color_code = ansi_color_code(header_color);
@@ -960,6 +966,7 @@ mod tests {
                 matched_text_preview: None,
                 explanation: None,
                 suggestions: &[],
+                recovery_hint: None,
             }),
             allowlist_override: None,
             effective_mode: Some(crate::packs::DecisionMode::Deny),
@@ -1028,6 +1035,7 @@ mod tests {
             matched_text_preview: None,
             explanation: None,
             suggestions: &[],
+            recovery_hint: None,
         };
 
         assert_eq!(confidence_from_severity(&pattern), Some(0.95));
@@ -22,6 +22,8 @@ pub fn create_pack() -> Pack {
         keyword_matcher: None,
         safe_regex_set: None,
         safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
     }
 }
 
@@ -5,6 +5,7 @@
 //! - Deleting secrets / variables
 //! - Disabling workflows
 //! - Canceling runs
+//! - Manually dispatching production workflows
 //! - `gh api` DELETE calls against `/actions/*` endpoints
 
 use crate::packs::{DestructivePattern, Pack, SafePattern};
@@ -16,8 +17,8 @@ pub fn create_pack() -> Pack {
     Pack {
         id: "cicd.github_actions".to_string(),
         name: "GitHub Actions",
-        description: "Protects against destructive GitHub Actions operations like deleting secrets/variables \
-             or using gh api DELETE against /actions endpoints.",
+        description: "Protects against destructive GitHub Actions operations like deleting secrets/variables, \
+             dispatching production workflows, or using gh api DELETE against /actions endpoints.",
         // Broad on purpose: global `gh` flags can appear before the subcommand.
         keywords: &["gh"],
         safe_patterns: create_safe_patterns(),
@@ -25,6 +26,8 @@ pub fn create_pack() -> Pack {
         keyword_matcher: None,
         safe_regex_set: None,
         safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
     }
 }
 
@@ -116,6 +119,19 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              - gh run list: Review running workflows\n\
              - Wait for natural completion if possible"
         ),
+        destructive_pattern!(
+            "gh-actions-workflow-run-production",
+            r"gh(?:\s+--?[A-Za-z][A-Za-z0-9-]*\b(?:\s+(?!(?:secret|variable|workflow|run|api)\b)\S+)?)*\s+workflow\s+run\b.*(?i:\bprod(?:uction)?\b)",
+            "gh workflow run dispatches a production workflow outside the normal release path.",
+            High,
+            "Manually dispatching a workflow whose name, file, ref, or inputs target \
+             production can deploy, migrate, or roll back live systems without the review \
+             that a merge-triggered run would get.\n\n\
+             Safer alternatives:\n\
+             - gh workflow view <workflow>: Review what the workflow does first\n\
+             - Dispatch against a staging environment before production\n\
+             - Let the normal merge/release pipeline trigger the deployment"
+        ),
         destructive_pattern!(
             "gh-actions-api-delete-secrets",
             r"gh(?:\s+--?[A-Za-z][A-Za-z0-9-]*\b(?:\s+(?!(?:secret|variable|workflow|run|api)\b)\S+)?)*\s+api\b.*(?:-X|--method)\s+DELETE\b.*\b/?repos/[^\s/]+/[^\s/]+/actions/secrets\b",
@@ -193,6 +209,23 @@ mod tests {
         assert_eq!(matched.name, Some("gh-actions-run-cancel"));
     }
 
+    #[test]
+    fn blocks_production_workflow_dispatch() {
+        let pack = create_pack();
+
+        let matched = pack
+            .check("gh workflow run deploy-production.yml")
+            .expect("production workflow dispatch should be detected");
+        assert_eq!(matched.name, Some("gh-actions-workflow-run-production"));
+
+        let matched = pack
+            .check("gh workflow run deploy.yml -f environment=prod")
+            .expect("production input should be detected");
+        assert_eq!(matched.name, Some("gh-actions-workflow-run-production"));
+
+        assert!(pack.check("gh workflow run ci.yml --ref main").is_none());
+    }
+
     #[test]
     fn detects_gh_api_delete_against_actions_endpoints() {
         let pack = create_pack();
@@ -19,6 +19,8 @@ pub fn create_pack() -> Pack {
         keyword_matcher: None,
         safe_regex_set: None,
         safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
     }
 }
 
@@ -96,6 +98,19 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              - GET request first: Verify variable exists\n\
              - Prefer CLI commands over direct API calls"
         ),
+        destructive_pattern!(
+            "glab-api-delete-runner",
+            r"glab(?:\s+--?\S+(?:\s+\S+)?)*\s+api\b.*(?:-X|--method)\s+DELETE\b.*\brunners\b",
+            "glab api DELETE against runners endpoints unregisters or unassigns runners.",
+            High,
+            "DELETE /runners/:id removes a runner from the instance, and \
+             DELETE /projects/:id/runners/:id detaches it from a project. Either way, jobs that \
+             relied on the runner queue up with nothing to pick them up.\n\n\
+             Safer alternatives:\n\
+             - glab api runners: List runners and their assignments first\n\
+             - Pause the runner (PUT /runners/:id with paused=true) instead\n\
+             - Verify replacement runners are available before removing"
+        ),
         destructive_pattern!(
             "gitlab-runner-unregister",
             r"gitlab-runner(?:\s+--?\S+(?:\s+\S+)?)*\s+unregister\b",
@@ -156,6 +171,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_api_delete_runner_blocked() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "glab api --method DELETE /runners/42",
+            "glab-api-delete-runner",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "glab api -X DELETE projects/123/runners/42",
+            "glab-api-delete-runner",
+        );
+    }
+
     #[test]
     fn test_runner_unregister_blocked() {
         let pack = create_pack();
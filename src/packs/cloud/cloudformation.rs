@@ -0,0 +1,278 @@
+//! CloudFormation and AWS CDK patterns - protections against stack destruction.
+//!
+//! This includes patterns for:
+//! - aws cloudformation delete-stack / delete-stack-set / delete-stack-instances
+//! - stack policy replacement and per-update policy overrides
+//! - disabling termination protection
+//! - cdk destroy
+
+use crate::packs::{DestructivePattern, Pack, SafePattern};
+use crate::{destructive_pattern, safe_pattern};
+
+/// Create the CloudFormation/CDK pack.
+#[must_use]
+pub fn create_pack() -> Pack {
+    Pack {
+        id: "cloud.cloudformation".to_string(),
+        name: "CloudFormation",
+        description: "Protects against destructive CloudFormation and AWS CDK operations like \
+                      delete-stack, cdk destroy, stack policy removal, and disabling \
+                      termination protection",
+        keywords: &["cloudformation", "cdk"],
+        safe_patterns: create_safe_patterns(),
+        destructive_patterns: create_destructive_patterns(),
+        keyword_matcher: None,
+        safe_regex_set: None,
+        safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
+    }
+}
+
+fn create_safe_patterns() -> Vec<SafePattern> {
+    // Anchored to the whole command so a read-only call cannot whitelist a
+    // chained `delete-stack` or `cdk destroy`.
+    vec![
+        // describe/list/get/validate/estimate/detect are read-only
+        safe_pattern!(
+            "cfn-read-only",
+            r"^\s*aws\s+cloudformation\s+(?:describe|list|get|validate|estimate|detect)-[\w-]+[^;&|\n$`]*$"
+        ),
+        // deploy without executing the change set only creates a preview
+        safe_pattern!(
+            "cfn-deploy-no-execute",
+            r"^\s*aws\s+cloudformation\s+deploy\b[^;&|\n$`]*\s--no-execute-changeset\b[^;&|\n$`]*$"
+        ),
+        // synth renders templates locally
+        safe_pattern!(
+            "cdk-synth",
+            r"^\s*(?:npx\s+)?cdk\s+(?:synth|synthesize)\b[^;&|\n$`]*$"
+        ),
+        // diff compares the app against deployed stacks
+        safe_pattern!("cdk-diff", r"^\s*(?:npx\s+)?cdk\s+diff\b[^;&|\n$`]*$"),
+        // ls/list and doctor only inspect the app
+        safe_pattern!(
+            "cdk-list",
+            r"^\s*(?:npx\s+)?cdk\s+(?:ls|list|doctor)\b[^;&|\n$`]*$"
+        ),
+    ]
+}
+
+#[allow(clippy::too_many_lines)]
+fn create_destructive_patterns() -> Vec<DestructivePattern> {
+    vec![
+        // delete-stack-set / delete-stack-instances before delete-stack
+        destructive_pattern!(
+            "cfn-delete-stack-instances",
+            r"aws\s+cloudformation\s+delete-stack-instances\b",
+            "aws cloudformation delete-stack-instances removes stack set instances (and their \
+             stacks unless --retain-stacks) from target accounts and regions.",
+            High,
+            "delete-stack-instances removes a StackSet from accounts/regions:\n\n\
+             - With --no-retain-stacks every stack instance is deleted\n\
+             - Resources in each account/region are destroyed\n\
+             - Runs across many accounts at once\n\n\
+             Keep the stacks and only detach them:\n  \
+             aws cloudformation delete-stack-instances ... --retain-stacks"
+        ),
+        destructive_pattern!(
+            "cfn-delete-stack-set",
+            r"aws\s+cloudformation\s+delete-stack-set\b",
+            "aws cloudformation delete-stack-set removes a StackSet definition.",
+            High,
+            "delete-stack-set removes the StackSet itself:\n\n\
+             - Template and parameters for every account are lost\n\
+             - Fails while instances still exist; often preceded by delete-stack-instances\n\n\
+             Inspect first:\n  \
+             aws cloudformation list-stack-instances --stack-set-name NAME"
+        ),
+        // delete-stack removes every resource in the stack
+        destructive_pattern!(
+            "cfn-delete-stack",
+            r"aws\s+cloudformation\s+delete-stack(?:\s|$)",
+            "aws cloudformation delete-stack removes the entire stack and its resources.",
+            Critical,
+            "CloudFormation delete-stack removes the stack AND all resources it created:\n\n\
+             - Instances, databases, queues, and buckets are deleted\n\
+             - Only resources with DeletionPolicy: Retain survive (orphaned)\n\
+             - Blocked only if termination protection is enabled\n\n\
+             Preview resources:\n  \
+             aws cloudformation describe-stack-resources --stack-name NAME\n\n\
+             Keep specific resources:\n  \
+             aws cloudformation delete-stack --stack-name NAME --retain-resources ID1 ID2"
+        ),
+        // turning off termination protection is the usual first step before delete
+        destructive_pattern!(
+            "cfn-disable-termination-protection",
+            r"aws\s+cloudformation\s+update-termination-protection\b[^;&|\n]*--no-enable-termination-protection\b",
+            "Disabling termination protection lets the stack be deleted.",
+            High,
+            "update-termination-protection --no-enable-termination-protection removes \
+             the guard against delete-stack:\n\n\
+             - The stack can then be deleted by anyone with delete permissions\n\
+             - Usually the first step of tearing a production stack down\n\n\
+             Check the current setting:\n  \
+             aws cloudformation describe-stacks --stack-name NAME \
+             --query 'Stacks[0].EnableTerminationProtection'"
+        ),
+        // replacing the stack policy can remove update protection
+        destructive_pattern!(
+            "cfn-set-stack-policy",
+            r"aws\s+cloudformation\s+set-stack-policy\b",
+            "aws cloudformation set-stack-policy replaces the policy protecting stack resources \
+             from updates.",
+            High,
+            "set-stack-policy replaces the stack policy wholesale:\n\n\
+             - A permissive policy removes protection from replacement/deletion on update\n\
+             - The previous policy is not kept anywhere\n\n\
+             Save the current policy first:\n  \
+             aws cloudformation get-stack-policy --stack-name NAME"
+        ),
+        // per-update stack policy override
+        destructive_pattern!(
+            "cfn-stack-policy-override",
+            r"aws\s+cloudformation\s+update-stack\b[^;&|\n]*--stack-policy-during-update-(?:body|url)\b",
+            "update-stack with --stack-policy-during-update overrides stack policy protection for \
+             this update.",
+            High,
+            "--stack-policy-during-update-body/url temporarily replaces the stack policy:\n\n\
+             - Resources normally protected from replacement can be replaced or deleted\n\
+             - The override applies for the whole update\n\n\
+             Review the change set first:\n  \
+             aws cloudformation create-change-set ... && \
+             aws cloudformation describe-change-set ..."
+        ),
+        // cdk destroy --force skips the confirmation prompt
+        destructive_pattern!(
+            "cdk-destroy-force",
+            r"\bcdk\s+destroy\b[^;&|\n]*\s(?:--force|-f)\b",
+            "cdk destroy --force deletes the stacks without confirmation.",
+            Critical,
+            "cdk destroy --force deletes CloudFormation stacks without prompting:\n\n\
+             - Every resource in the selected stacks is deleted\n\
+             - --all extends this to every stack in the app\n\
+             - No chance to confirm the stack list\n\n\
+             Review what would be removed:\n  \
+             cdk ls && cdk diff"
+        ),
+        // cdk destroy
+        destructive_pattern!(
+            "cdk-destroy",
+            r"\bcdk\s+destroy\b",
+            "cdk destroy deletes the app's CloudFormation stacks and their resources.",
+            Critical,
+            "cdk destroy deletes CloudFormation stacks:\n\n\
+             - All resources in the stacks are deleted\n\
+             - Only resources with RemovalPolicy.RETAIN survive\n\
+             - Cannot be undone\n\n\
+             List stacks first:\n  \
+             cdk ls"
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_pack_creation() {
+        let pack = create_pack();
+        assert_eq!(pack.id, "cloud.cloudformation");
+        assert!(pack.keywords.contains(&"cloudformation"));
+        assert!(pack.keywords.contains(&"cdk"));
+
+        assert_patterns_compile(&pack);
+        assert_all_patterns_have_reasons(&pack);
+        assert_unique_pattern_names(&pack);
+    }
+
+    #[test]
+    fn allows_read_only_and_preview_commands() {
+        let pack = create_pack();
+        assert_safe_pattern_matches(&pack, "aws cloudformation describe-stacks");
+        assert_safe_pattern_matches(
+            &pack,
+            "aws cloudformation list-stack-resources --stack-name prod",
+        );
+        assert_safe_pattern_matches(
+            &pack,
+            "aws cloudformation validate-template --template-body file://t.yaml",
+        );
+        assert_safe_pattern_matches(
+            &pack,
+            "aws cloudformation deploy --template-file t.yaml --stack-name s --no-execute-changeset",
+        );
+        assert_safe_pattern_matches(&pack, "cdk synth");
+        assert_safe_pattern_matches(&pack, "npx cdk diff MyStack");
+        assert_safe_pattern_matches(&pack, "cdk ls");
+    }
+
+    #[test]
+    fn safe_patterns_do_not_cover_chained_commands() {
+        let pack = create_pack();
+        assert_no_safe_match(
+            &pack,
+            "aws cloudformation describe-stacks && aws cloudformation delete-stack --stack-name prod",
+        );
+        assert_no_safe_match(&pack, "cdk diff; cdk destroy --force");
+    }
+
+    #[test]
+    fn blocks_stack_deletion() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "aws cloudformation delete-stack --stack-name prod",
+            "cfn-delete-stack",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "aws cloudformation delete-stack-set --stack-set-name baseline",
+            "cfn-delete-stack-set",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "aws cloudformation delete-stack-instances --stack-set-name baseline \
+             --accounts 111 --regions us-east-1 --no-retain-stacks",
+            "cfn-delete-stack-instances",
+        );
+    }
+
+    #[test]
+    fn blocks_protection_removal() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "aws cloudformation update-termination-protection \
+             --no-enable-termination-protection --stack-name prod",
+            "cfn-disable-termination-protection",
+        );
+        assert_allows(
+            &pack,
+            "aws cloudformation update-termination-protection \
+             --enable-termination-protection --stack-name prod",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "aws cloudformation set-stack-policy --stack-name prod --stack-policy-body file://open.json",
+            "cfn-set-stack-policy",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "aws cloudformation update-stack --stack-name prod \
+             --stack-policy-during-update-body file://allow-all.json",
+            "cfn-stack-policy-override",
+        );
+    }
+
+    #[test]
+    fn blocks_cdk_destroy() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "cdk destroy MyStack", "cdk-destroy");
+        assert_blocks_with_pattern(&pack, "npx cdk destroy --all --force", "cdk-destroy-force");
+        assert_blocks_with_pattern(&pack, "cdk destroy -f", "cdk-destroy-force");
+        assert_allows(&pack, "cdk deploy MyStack");
+    }
+}
@@ -4,7 +4,9 @@
 //! - AWS CLI (aws)
 //! - Google Cloud SDK (gcloud)
 //! - Azure CLI (az)
+//! - CloudFormation and AWS CDK (aws cloudformation, cdk)
 
 pub mod aws;
 pub mod azure;
+pub mod cloudformation;
 pub mod gcp;
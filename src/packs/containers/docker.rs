@@ -163,6 +163,8 @@ pub fn create_pack() -> Pack {
         keyword_matcher: None,
         safe_regex_set: None,
         safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
     }
 }
 
@@ -211,7 +213,9 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              Safer alternative:\n  \
              docker container prune    # Only stopped containers\n  \
              docker image prune        # Only dangling images",
-            SYSTEM_PRUNE_SUGGESTIONS
+            SYSTEM_PRUNE_SUGGESTIONS,
+            "Removed images can be re-pulled or rebuilt; removed containers cannot be restored. \
+             Before the next prune, snapshot `docker ps -a` and `docker volume ls`."
         ),
         // volume prune - removes all unused volumes
         destructive_pattern!(
@@ -230,7 +234,10 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              docker volume ls -f dangling=true   # Show only unused\n\n\
              Safer approach:\n  \
              docker volume rm <specific-volume>  # Remove by name",
-            VOLUME_PRUNE_SUGGESTIONS
+            VOLUME_PRUNE_SUGGESTIONS,
+            "Pruned volume data cannot be recovered with Docker; restore from a backup. \
+             Before pruning, snapshot `docker volume ls` and archive the volumes you need:\n  \
+             docker run --rm -v <vol>:/data -v \"$PWD\":/backup alpine tar czf /backup/<vol>.tgz /data"
         ),
         // network prune - removes all unused networks
         destructive_pattern!(
@@ -265,7 +272,9 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              docker images -f dangling=true\n  \
              docker images                       # With -a flag\n\n\
              Usually safe, but may slow down builds.",
-            IMAGE_PRUNE_SUGGESTIONS
+            IMAGE_PRUNE_SUGGESTIONS,
+            "Pruned images are restored with `docker pull <image>` or by rebuilding. \
+             Run `docker images` first to note the tags you need."
         ),
         // container prune - removes stopped containers (Medium: only affects stopped)
         destructive_pattern!(
@@ -282,7 +291,9 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              docker ps -a -f status=exited\n  \
              docker ps -a -f status=created\n\n\
              Consider keeping recent containers for debugging.",
-            CONTAINER_PRUNE_SUGGESTIONS
+            CONTAINER_PRUNE_SUGGESTIONS,
+            "Pruned containers cannot be restarted; recreate them from their image. \
+             Run `docker ps -a` first to note names and images."
         ),
         // rm -f (force remove containers)
         destructive_pattern!(
@@ -3,6 +3,8 @@
 //! This includes patterns for:
 //! - rm -rf outside temp directories (blocked)
 //! - rm -rf in /tmp, /var/tmp, $TMPDIR (allowed)
+//! - rm -rf on Windows drive and UNC paths (C:\, C:/Users, \\server\share)
+//! - cmd.exe `rmdir /s /q` and `del /s` (blocked; %TEMP% allowed)
 
 use crate::packs::{DestructivePattern, Pack, PatternSuggestion, Platform, SafePattern, Severity};
 use crate::{destructive_pattern, safe_pattern};
@@ -81,6 +83,25 @@ const RM_R_F_SEPARATE_SUGGESTIONS: &[PatternSuggestion] = &[
     ),
 ];
 
+/// Suggestions for cmd.exe recursive delete patterns.
+const WINDOWS_DELETE_SUGGESTIONS: &[PatternSuggestion] = &[
+    PatternSuggestion::with_platform(
+        "dir /s /b {path}",
+        "List everything that would be deleted",
+        Platform::Windows,
+    ),
+    PatternSuggestion::with_platform(
+        "rmdir /s {path}",
+        "Drop /q so cmd asks for confirmation first",
+        Platform::Windows,
+    ),
+    PatternSuggestion::with_platform(
+        "rmdir /s /q %TEMP%\\{subdir}",
+        "Deleting under %TEMP% is allowed without confirmation",
+        Platform::Windows,
+    ),
+];
+
 /// Suggestions for `rm --recursive --force` (long flags) pattern.
 const RM_RECURSIVE_FORCE_SUGGESTIONS: &[PatternSuggestion] = &[
     PatternSuggestion::new(
@@ -204,10 +225,17 @@ pub(crate) fn parse_rm_command(command: &str) -> RmParseDecision {
         return RmParseDecision::NoMatch;
     }
 
+    // Directory a preceding `cd` moved to, kept only along an unbroken `&&` chain.
+    let mut cd_dir: Option<String> = None;
+    let mut decision: Option<RmParseDecision> = None;
+    let mut saw_windows_delete = false;
     let mut i = 0;
     while i < tokens.len() {
         let current = &tokens[i];
         if current.kind == NormalizeTokenKind::Separator {
+            if current.text(command) != Some("&&") {
+                cd_dir = None;
+            }
             i += 1;
             continue;
         }
@@ -217,8 +245,20 @@ pub(crate) fn parse_rm_command(command: &str) -> RmParseDecision {
             continue;
         };
 
-        if text == "rm" {
-            return parse_rm_segment(command, &tokens, i + 1);
+        if text == "rm" && decision.is_none() {
+            decision = Some(parse_rm_segment(command, &tokens, i + 1, cd_dir.as_deref()));
+        }
+
+        if is_windows_recursive_delete(command, &tokens, i) {
+            saw_windows_delete = true;
+        }
+
+        if text == "cd" || text == "pushd" {
+            let target = tokens
+                .get(i + 1)
+                .filter(|token| token.kind == NormalizeTokenKind::Word)
+                .and_then(|token| token.text(command));
+            cd_dir = resolve_cd_target(cd_dir.as_deref(), target);
         }
 
         // Skip to the next separator before scanning for another command word.
@@ -228,7 +268,77 @@ pub(crate) fn parse_rm_command(command: &str) -> RmParseDecision {
         }
     }
 
-    RmParseDecision::NoMatch
+    // A safe rm must not skip the pack when the same command also runs a
+    // cmd.exe recursive delete; let the regex patterns judge both.
+    match decision {
+        Some(RmParseDecision::Allow) if saw_windows_delete => RmParseDecision::NoMatch,
+        Some(decision) => decision,
+        None => RmParseDecision::NoMatch,
+    }
+}
+
+/// Whether the segment starting at `start_idx` is `rmdir`/`rd`/`del`/`erase`
+/// with a `/s` switch.
+fn is_windows_recursive_delete(
+    command: &str,
+    tokens: &[crate::normalize::NormalizeToken],
+    start_idx: usize,
+) -> bool {
+    let is_delete_word = tokens[start_idx].text(command).is_some_and(|word| {
+        ["rmdir", "rd", "del", "erase"]
+            .iter()
+            .any(|name| word.eq_ignore_ascii_case(name))
+    });
+    is_delete_word
+        && tokens[start_idx + 1..]
+            .iter()
+            .take_while(|token| token.kind != NormalizeTokenKind::Separator)
+            .filter_map(|token| token.text(command))
+            .filter_map(|word| word.strip_prefix('/'))
+            .any(|switches| switches.split('/').any(|s| s.eq_ignore_ascii_case("s")))
+}
+
+/// Resolve the directory `cd <target>` moves to, as unexpanded shell text.
+///
+/// Returns `None` when the result can't be known statically (`cd -`, options,
+/// quoted `~`, or a relative target with no known base).
+fn resolve_cd_target(base: Option<&str>, target: Option<&str>) -> Option<String> {
+    let Some(target) = target else {
+        return Some("~".to_string());
+    };
+    let (quote, unquoted) = strip_outer_quotes(target);
+    if quote == QuoteKind::Single || unquoted.is_empty() || unquoted.starts_with('-') {
+        return None;
+    }
+    if unquoted.starts_with('~') {
+        return (quote == QuoteKind::None).then(|| unquoted.to_string());
+    }
+    if is_anchored_path(unquoted) {
+        return Some(unquoted.to_string());
+    }
+    base.map(|dir| join_cd_path(dir, unquoted))
+}
+
+/// Paths that don't depend on the working directory.
+fn is_anchored_path(path: &str) -> bool {
+    path.starts_with('/')
+        || path.starts_with('~')
+        || path.starts_with('$')
+        || is_windows_absolute_path(path)
+}
+
+/// Drive (`C:`, `C:\`, `C:/Users`) and UNC (`\\server\share`) paths.
+fn is_windows_absolute_path(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    let is_drive = bytes.len() >= 2
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && (bytes.len() == 2 || matches!(bytes[2], b'/' | b'\\'));
+    is_drive || path.starts_with("\\\\")
+}
+
+fn join_cd_path(dir: &str, path: &str) -> String {
+    format!("{}/{path}", dir.trim_end_matches('/'))
 }
 
 #[allow(clippy::too_many_lines)]
@@ -236,6 +346,7 @@ fn parse_rm_segment(
     command: &str,
     tokens: &[crate::normalize::NormalizeToken],
     start_idx: usize,
+    cd_dir: Option<&str>,
 ) -> RmParseDecision {
     let mut options_ended = false;
     let mut flags = RmFlagTracker::default();
@@ -312,11 +423,21 @@ fn parse_rm_segment(
         return RmParseDecision::NoMatch;
     };
 
+    // Unquoted relative paths after `cd DIR &&` are judged as `DIR/path`.
+    let resolve_in_cd_dir = |path: &PathToken<'_>| {
+        cd_dir
+            .filter(|_| path.quote == QuoteKind::None && !is_anchored_path(path.unquoted))
+            .map(|dir| join_cd_path(dir, path.unquoted))
+    };
+
     let safe_paths = !paths.is_empty()
         && !flag_state.saw_terminator
-        && paths
-            .iter()
-            .all(|path| path_is_safe_for_style(path, flag_state.style));
+        && paths.iter().all(|path| {
+            resolve_in_cd_dir(path).map_or_else(
+                || path_is_safe_for_style(path, flag_state.style),
+                |joined| path_is_safe_unquoted(&joined),
+            )
+        });
 
     if safe_paths {
         return RmParseDecision::Allow;
@@ -325,7 +446,16 @@ fn parse_rm_segment(
     let first_path = paths.first();
     let is_critical = flag_state.style == RmFlagStyle::Combined
         && !flag_state.saw_terminator
-        && first_path.is_some_and(path_is_root_home);
+        && first_path.is_some_and(|path| {
+            resolve_in_cd_dir(path).map_or_else(
+                || path_is_root_home(path),
+                |joined| {
+                    joined.starts_with('/')
+                        || joined.starts_with('~')
+                        || is_windows_absolute_path(&joined)
+                },
+            )
+        });
 
     let (pattern_name, reason, severity) = if is_critical {
         (
@@ -441,6 +571,11 @@ fn path_is_root_home(path: &PathToken<'_>) -> bool {
         return true;
     }
 
+    // Same for Windows drive and UNC paths (rm under Git Bash, MSYS2, Cygwin)
+    if is_windows_absolute_path(text) {
+        return true;
+    }
+
     // Tilde expansion (~/) only happens if unquoted
     if path.quote == QuoteKind::None && text.starts_with('~') {
         return true;
@@ -455,13 +590,18 @@ pub fn create_pack() -> Pack {
     Pack {
         id: "core.filesystem".to_string(),
         name: "Core Filesystem",
-        description: "Protects against dangerous rm -rf commands outside temp directories",
-        keywords: &["rm"],
+        description: "Protects against dangerous rm -rf commands outside temp directories, \
+                      including Windows drive/UNC paths and cmd.exe rmdir /s and del /s",
+        keywords: &[
+            "rm", "rmdir", "rd", "del", "erase", "RMDIR", "RD", "DEL", "ERASE",
+        ],
         safe_patterns: create_safe_patterns(),
         destructive_patterns: create_destructive_patterns(),
         keyword_matcher: None,
         safe_regex_set: None,
         safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
     }
 }
 
@@ -594,6 +734,11 @@ fn create_safe_patterns() -> Vec<SafePattern> {
             "rm-force-recursive-tmpdir-brace",
             r"^rm\s+.*--force.*--recursive\s+(?:\$\{TMPDIR\}/(?!\.\.(?:/|\s|$)|[^\s]*/\.\.(?:/|\s|$))\S*(?:\s+|$))+$"
         ),
+        // cmd.exe rmdir /s /q and del /s under %TEMP% or %TMP%
+        safe_pattern!(
+            "windows-delete-temp",
+            r#"(?i)^(?:rmdir|rd|del|erase)(?:\s+/[a-z](?:/[a-z])*)*\s+"?%te?mp%[\\/](?![^\s"]*\.\.)[^\s"]+"?(?:\s+/[a-z](?:/[a-z])*)*\s*$"#
+        ),
     ]
 }
 
@@ -608,7 +753,7 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
         // rm -rf on root or home paths (CRITICAL - catastrophic, never allow)
         destructive_pattern!(
             "rm-rf-root-home",
-            r"rm\s+-[a-zA-Z]*[rR][a-zA-Z]*f[a-zA-Z]*\s+[/~]|rm\s+-[a-zA-Z]*f[a-zA-Z]*[rR][a-zA-Z]*\s+[/~]",
+            r"rm\s+-[a-zA-Z]*[rR][a-zA-Z]*f[a-zA-Z]*\s+(?:[/~]|[a-zA-Z]:(?:[/\\]|\s|$)|\\\\)|rm\s+-[a-zA-Z]*f[a-zA-Z]*[rR][a-zA-Z]*\s+(?:[/~]|[a-zA-Z]:(?:[/\\]|\s|$)|\\\\)",
             "rm -rf on root or home paths is EXTREMELY DANGEROUS. This command will NOT be executed. Ask the user to run it manually if truly needed.",
             Critical,
             "This command would recursively delete files starting from the root filesystem (/) \
@@ -688,6 +833,50 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              find /path --maxdepth 2 -ls | head -30",
             RM_RECURSIVE_FORCE_SUGGESTIONS
         ),
+        // cmd.exe rmdir /s, del /s on a drive, UNC share, or profile/system folder
+        destructive_pattern!(
+            "windows-delete-root-home",
+            r#"(?i)\b(?:rmdir|rd|del|erase)(?=[^;&|\n]*(?:\s|\b[a-z])/s\b)\s+(?:[^;&|\n]*\s)?"?(?:[a-z]:(?:[\\/]|\s|"|$)|\\\\|%(?:systemdrive|systemroot|windir|userprofile|homedrive|homepath|programfiles|programdata|appdata|localappdata)%)"#,
+            "Recursive delete of a Windows drive, share, or profile path is EXTREMELY DANGEROUS. This command will NOT be executed. Ask the user to run it manually if truly needed.",
+            Critical,
+            "rmdir /s and del /s walk the whole tree under the target. On a drive root \
+             (C:\\), a UNC share (\\\\server\\share), or a folder such as %USERPROFILE% or \
+             %SystemRoot%, that means:\n\n\
+             - User documents, settings, and application data are deleted\n\
+             - Windows system files can be removed, leaving the machine unbootable\n\
+             - Files on a network share are deleted for every user of the share\n\n\
+             cmd.exe deletes files directly; nothing goes to the Recycle Bin.\n\n\
+             List what would be removed first:\n  \
+             dir /s /b C:\\path\\to\\folder",
+            WINDOWS_DELETE_SUGGESTIONS
+        ),
+        destructive_pattern!(
+            "windows-rmdir-recursive",
+            r"(?i)\b(?:rmdir|rd)(?=[^;&|\n]*(?:\s|\b[a-z])/s\b)(?=[^;&|\n]*(?:\s|\b[a-z])/q\b)\s",
+            "rmdir /s /q deletes a directory tree without confirmation and requires human approval.",
+            High,
+            "rmdir /s removes a directory and everything under it; /q suppresses the \
+             \"Are you sure (Y/N)?\" prompt. It is the cmd.exe equivalent of rm -rf:\n\n\
+             - Files bypass the Recycle Bin\n\
+             - A wrong or relative path deletes the wrong tree\n\n\
+             Deleting under %TEMP% is allowed. Otherwise, list the tree first:\n  \
+             dir /s /b <path>",
+            WINDOWS_DELETE_SUGGESTIONS
+        ),
+        destructive_pattern!(
+            "windows-del-recursive",
+            r"(?i)\b(?:del|erase)(?=[^;&|\n]*(?:\s|\b[a-z])/s\b)\s",
+            "del /s deletes matching files in every subdirectory and requires human approval.",
+            High,
+            "del /s applies the file pattern to the directory and all of its \
+             subdirectories. With /q (or any pattern other than *.*) there is no prompt, \
+             and with /f read-only files go too:\n\n\
+             - del /s /q *.* empties a whole tree\n\
+             - Files bypass the Recycle Bin\n\n\
+             Preview the matches first:\n  \
+             dir /s /b <pattern>",
+            WINDOWS_DELETE_SUGGESTIONS
+        ),
     ]
 }
 
@@ -862,4 +1051,117 @@ mod tests {
     fn test_rm_parser_option_terminator() {
         assert_rm_parser_no_match("rm -- -rf /tmp/safe");
     }
+
+    #[test]
+    fn test_rm_parser_follows_cd_chain() {
+        assert_rm_parser_allows("cd /tmp/work && rm -rf build");
+        assert_rm_parser_allows("cd $TMPDIR && cd work && rm -rf build dist");
+        assert_rm_parser_allows("cd /tmp/work && make clean && rm -rf build");
+        assert_rm_parser_allows("(cd /var/tmp/x && rm -rf cache)");
+
+        // The cd may have failed, so the rm could run anywhere.
+        assert_rm_parser_denies("cd /tmp; rm -rf build", RM_RF_GENERAL_NAME, Severity::High);
+        assert_rm_parser_denies(
+            "cd /tmp || rm -rf build",
+            RM_RF_GENERAL_NAME,
+            Severity::High,
+        );
+        // Leaving the temp dir, or an unknown destination, is not safe.
+        assert_rm_parser_denies(
+            "cd /tmp && cd .. && rm -rf etc",
+            RM_RF_ROOT_HOME_NAME,
+            Severity::Critical,
+        );
+        assert_rm_parser_denies("cd - && rm -rf build", RM_RF_GENERAL_NAME, Severity::High);
+        assert_rm_parser_denies(
+            "cd '/tmp' && rm -rf build",
+            RM_RF_GENERAL_NAME,
+            Severity::High,
+        );
+        assert_rm_parser_denies("cd / && rm -rf *", RM_RF_ROOT_HOME_NAME, Severity::Critical);
+        assert_rm_parser_denies("cd && rm -rf *", RM_RF_ROOT_HOME_NAME, Severity::Critical);
+    }
+
+    #[test]
+    fn test_rm_windows_paths_critical() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "rm -rf C:/", "rm-rf-root-home");
+        assert_blocks_with_pattern(&pack, r"rm -rf C:\Users\me", "rm-rf-root-home");
+        assert_blocks_with_pattern(&pack, r"rm -rf \\nas\share", "rm-rf-root-home");
+
+        assert_rm_parser_denies("rm -rf C:/", RM_RF_ROOT_HOME_NAME, Severity::Critical);
+        assert_rm_parser_denies(
+            r#"rm -rf "D:\data""#,
+            RM_RF_ROOT_HOME_NAME,
+            Severity::Critical,
+        );
+        assert_rm_parser_denies(
+            r"rm -rf \\nas\share",
+            RM_RF_ROOT_HOME_NAME,
+            Severity::Critical,
+        );
+        assert_rm_parser_denies(
+            "cd C:/work && rm -rf build",
+            RM_RF_ROOT_HOME_NAME,
+            Severity::Critical,
+        );
+        // A drive path is absolute, so a preceding cd into /tmp doesn't apply.
+        assert_rm_parser_denies(
+            "cd /tmp && rm -rf C:/build",
+            RM_RF_ROOT_HOME_NAME,
+            Severity::Critical,
+        );
+    }
+
+    #[test]
+    fn test_windows_recursive_delete() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, r"rmdir /s /q C:\", "windows-delete-root-home");
+        assert_blocks_with_pattern(&pack, r"RD C:\Users /S /Q", "windows-delete-root-home");
+        assert_blocks_with_pattern(
+            &pack,
+            r"del /s /q \\fs01\builds",
+            "windows-delete-root-home",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            r"del /f/s/q %USERPROFILE%\*",
+            "windows-delete-root-home",
+        );
+        assert_blocks_with_severity(&pack, r"erase /s C:\*.*", Severity::Critical);
+
+        assert_blocks_with_pattern(&pack, "rmdir /s /q build", "windows-rmdir-recursive");
+        assert_blocks_with_pattern(&pack, r"rd /s/q node_modules", "windows-rmdir-recursive");
+        assert_blocks_with_pattern(&pack, "del /s /q *.obj", "windows-del-recursive");
+        assert_blocks_with_severity(&pack, "del /s *.log", Severity::High);
+
+        assert_allows(&pack, "rmdir build");
+        assert_allows(&pack, "rmdir /s build");
+        assert_allows(&pack, "del notes.txt");
+        assert_allows(&pack, "kubectl delete pod web-0");
+        assert_allows(&pack, "cargo build --release");
+    }
+
+    #[test]
+    fn test_windows_delete_in_temp_allowed() {
+        let pack = create_pack();
+        assert_safe_pattern_matches(&pack, r"rmdir /s /q %TEMP%\build-cache");
+        assert_safe_pattern_matches(&pack, r#"rd /s /q "%TMP%\dcg-test""#);
+        assert_safe_pattern_matches(&pack, r"del /s /q %temp%\*.tmp");
+        assert!(!pack.matches_safe(r"rmdir /s /q %TEMP%\..\Documents"));
+        assert!(!pack.matches_safe(r"rmdir /s /q %TEMP%\x & rmdir /s /q C:\"));
+    }
+
+    #[test]
+    fn test_rm_parser_defers_to_patterns_for_windows_delete() {
+        assert_rm_parser_allows("rm -rf /tmp/x && rmdir /tmp/empty");
+        assert_rm_parser_no_match(r"rm -rf /tmp/x && rmdir /s /q C:\");
+        assert_rm_parser_no_match(r"rd /s /q build");
+        // The regex patterns then block the whole command.
+        assert_blocks_with_severity(
+            &create_pack(),
+            r"rm -rf /tmp/x && rmdir /s /q C:\",
+            Severity::Critical,
+        );
+    }
 }
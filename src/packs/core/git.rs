@@ -22,6 +22,8 @@ pub fn create_pack() -> Pack {
         keyword_matcher: None,
         safe_regex_set: None,
         safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
     }
 }
 
@@ -212,7 +214,9 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
                         "Reset a specific file only, preserving other changes",
                     ),
                 ]
-            }
+            },
+            "Commits HEAD moved away from are still reachable: find them with `git reflog` \
+             and restore with `git reset --hard HEAD@{1}`. Uncommitted edits are not recoverable."
         ),
         destructive_pattern!(
             "reset-merge",
@@ -308,7 +312,10 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
                         "Preview what you're about to overwrite on the remote",
                     ),
                 ]
-            }
+            },
+            "The push output shows the old remote tip (`+ <old>...<new>`). Restore it with \
+             `git push --force-with-lease origin <old>:<branch>`, or from `git reflog` in any \
+             clone that still has it."
         ),
         destructive_pattern!(
             "push-force-short",
@@ -340,7 +347,10 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
                         "Preview what you're about to overwrite on the remote",
                     ),
                 ]
-            }
+            },
+            "The push output shows the old remote tip (`+ <old>...<new>`). Restore it with \
+             `git push --force-with-lease origin <old>:<branch>`, or from `git reflog` in any \
+             clone that still has it."
         ),
         // branch -D/-f force deletes or overwrites without checks (Medium: recoverable via reflog)
         destructive_pattern!(
@@ -373,7 +383,9 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
                         "Review branch commits before deleting",
                     ),
                 ]
-            }
+            },
+            "The delete message prints the branch tip (`was <sha>`). Recreate it with \
+             `git branch <branch> <sha>`, or find the tip in `git reflog`."
         ),
         // stash destruction (Medium: single stash, recoverable via fsck/unreachable objects)
         destructive_pattern!(
@@ -410,7 +422,9 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
                         "Review all stashes before dropping any",
                     ),
                 ]
-            }
+            },
+            "The drop message prints the stash commit (`Dropped ... (<sha>)`). Restore it with \
+             `git stash apply <sha>`; otherwise search `git fsck --unreachable | grep commit`."
         ),
         // stash clear destroys ALL stashes (CRITICAL)
         destructive_pattern!(
@@ -442,7 +456,9 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
                         "Inspect each stash before deciding to delete",
                     ),
                 ]
-            }
+            },
+            "Cleared stashes are dangling commits until garbage collection. Find them with \
+             `git fsck --unreachable | grep commit` and restore with `git stash apply <sha>`."
         ),
     ]
 }
@@ -577,6 +593,25 @@ mod tests {
         assert_blocks(&pack, "git stash drop stash@{0}", "Recoverable");
     }
 
+    #[test]
+    fn test_recoverable_patterns_carry_recovery_hints() {
+        let pack = create_pack();
+
+        for command in [
+            "git reset --hard HEAD~1",
+            "git branch -D feature",
+            "git stash drop",
+        ] {
+            let matched = pack.check(command).expect("pattern matches");
+            assert!(
+                matched
+                    .recovery_hint
+                    .is_some_and(|hint| hint.contains("reflog") || hint.contains("stash apply")),
+                "{command} should explain how to recover"
+            );
+        }
+    }
+
     // =========================================================================
     // Safe Pattern Tests
     // =========================================================================
@@ -6,6 +6,8 @@
 //! - Git commands that rewrite history
 //! - Git commands that destroy stashes
 //! - Filesystem commands that recursively delete outside temp directories
+//! - Downloaded scripts piped straight into a shell
 
 pub mod filesystem;
 pub mod git;
+pub mod pipe_shell;
@@ -0,0 +1,225 @@
+//! Cassandra/`ScyllaDB` patterns - protections against destructive cqlsh and nodetool commands.
+//!
+//! This includes patterns for:
+//! - DROP KEYSPACE / DROP TABLE run through cqlsh
+//! - TRUNCATE run through cqlsh
+//! - nodetool decommission/removenode/assassinate
+//! - nodetool clearsnapshot
+
+use crate::packs::{DestructivePattern, Pack, SafePattern};
+use crate::{destructive_pattern, safe_pattern};
+
+/// Create the Cassandra/`ScyllaDB` pack.
+#[must_use]
+pub fn create_pack() -> Pack {
+    Pack {
+        id: "database.cassandra".to_string(),
+        name: "Cassandra/ScyllaDB",
+        description: "Protects against destructive Cassandra/ScyllaDB operations like \
+                      DROP KEYSPACE, TRUNCATE, and nodetool decommission/removenode",
+        keywords: &["cqlsh", "nodetool", "KEYSPACE", "keyspace"],
+        safe_patterns: create_safe_patterns(),
+        destructive_patterns: create_destructive_patterns(),
+        keyword_matcher: None,
+        safe_regex_set: None,
+        safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
+    }
+}
+
+fn create_safe_patterns() -> Vec<SafePattern> {
+    vec![
+        // cqlsh -e with a single DESCRIBE statement is read-only
+        safe_pattern!(
+            "cqlsh-describe",
+            r#"(?i)\bcqlsh\b[^;|&]*\s(?:-e|--execute)[\s=]*['"]\s*DESC(?:RIBE)?\b[^;'"]*;?\s*['"]\s*$"#
+        ),
+        // cqlsh -e with a single SELECT statement is read-only
+        safe_pattern!(
+            "cqlsh-select",
+            r#"(?i)\bcqlsh\b[^;|&]*\s(?:-e|--execute)[\s=]*['"]\s*SELECT\b[^;'"]*;?\s*['"]\s*$"#
+        ),
+    ]
+}
+
+#[allow(clippy::too_many_lines)]
+fn create_destructive_patterns() -> Vec<DestructivePattern> {
+    vec![
+        // DROP KEYSPACE
+        destructive_pattern!(
+            "drop-keyspace",
+            r"(?i)\bDROP\s+KEYSPACE\b",
+            "DROP KEYSPACE permanently deletes the keyspace and every table in it.",
+            Critical,
+            "DROP KEYSPACE removes a keyspace and ALL of its contents cluster-wide:\n\n\
+             - Every table, materialized view, and index in the keyspace\n\
+             - User-defined types and functions\n\
+             - The schema change replicates to every node\n\n\
+             IF EXISTS only prevents errors if the keyspace doesn't exist - it still deletes!\n\n\
+             With auto_snapshot enabled (the default) a snapshot is taken first, but it must \
+             be restored node by node.\n\n\
+             Before dropping:\n  \
+             nodetool snapshot -t before-drop <keyspace>\n  \
+             cqlsh -e 'DESCRIBE KEYSPACE <keyspace>' > schema.cql"
+        ),
+        // DROP TABLE / COLUMNFAMILY / MATERIALIZED VIEW via cqlsh
+        destructive_pattern!(
+            "drop-table",
+            r"(?i)\bcqlsh\b.*\bDROP\s+(?:TABLE|COLUMNFAMILY|MATERIALIZED\s+VIEW)\b",
+            "DROP TABLE permanently deletes the table and its data on every node.",
+            High,
+            "DROP TABLE removes the table schema and all SSTables across the cluster:\n\n\
+             - All rows on every replica are removed\n\
+             - Dependent materialized views and indexes are dropped\n\
+             - The schema change replicates immediately\n\n\
+             Snapshot and export the schema first:\n  \
+             nodetool snapshot -t before-drop -cf <table> <keyspace>\n  \
+             cqlsh -e 'DESCRIBE TABLE <keyspace>.<table>'"
+        ),
+        // TRUNCATE via cqlsh
+        destructive_pattern!(
+            "truncate",
+            r"(?i)\bcqlsh\b.*\bTRUNCATE\b",
+            "TRUNCATE permanently deletes all rows in the table on every node.",
+            High,
+            "TRUNCATE removes every row of a table on all replicas:\n\n\
+             - Requires all nodes to be up and acknowledges cluster-wide\n\
+             - Cannot be rolled back\n\
+             - Materialized views on the table are emptied too\n\n\
+             Check data volume first:\n  \
+             nodetool tablestats <keyspace>.<table>\n\n\
+             Snapshot before truncating:\n  \
+             nodetool snapshot -t before-truncate -cf <table> <keyspace>"
+        ),
+        // nodetool decommission
+        destructive_pattern!(
+            "nodetool-decommission",
+            r"\bnodetool\b.*\sdecommission\b",
+            "nodetool decommission removes this node from the ring and streams its data away.",
+            High,
+            "nodetool decommission permanently retires the node:\n\n\
+             - Token ranges are reassigned to other nodes\n\
+             - All data is streamed off the node (heavy I/O and network load)\n\
+             - Running it on the wrong node shrinks the cluster's capacity\n\
+             - The node cannot rejoin without being wiped and bootstrapped again\n\n\
+             Confirm the target node and cluster health first:\n  \
+             nodetool status\n  \
+             nodetool describecluster"
+        ),
+        // nodetool removenode / assassinate
+        destructive_pattern!(
+            "nodetool-removenode",
+            r"\bnodetool\b.*\s(?:removenode|assassinate)\b",
+            "nodetool removenode/assassinate forcibly drops a node from the cluster.",
+            Critical,
+            "removenode and assassinate remove a node without it streaming its own data:\n\n\
+             - Replicas it held must be rebuilt from the remaining nodes\n\
+             - With RF=1 or a second node down, data can be lost\n\
+             - assassinate skips even the gossip safety checks\n\
+             - Removing a live node by mistake causes inconsistency\n\n\
+             Verify the host ID really is dead:\n  \
+             nodetool status   # Look for DN (down/normal)\n\n\
+             Prefer decommission for nodes that are still up."
+        ),
+        // nodetool clearsnapshot
+        destructive_pattern!(
+            "nodetool-clearsnapshot",
+            r"\bnodetool\b.*\sclearsnapshot\b",
+            "nodetool clearsnapshot deletes snapshots, which may be your only backups.",
+            High,
+            "nodetool clearsnapshot deletes snapshot directories on this node:\n\n\
+             - Without -t it removes ALL snapshots (including --all)\n\
+             - Snapshots taken automatically before DROP/TRUNCATE are lost\n\
+             - Deleted snapshots cannot be restored\n\n\
+             List snapshots first:\n  \
+             nodetool listsnapshots\n\n\
+             Remove a single snapshot by tag:\n  \
+             nodetool clearsnapshot -t <tag> <keyspace>"
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_drop_keyspace() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            r#"cqlsh -e "DROP KEYSPACE analytics""#,
+            "drop-keyspace",
+        );
+        assert_blocks(
+            &pack,
+            "drop keyspace if exists analytics;",
+            "deletes the keyspace",
+        );
+    }
+
+    #[test]
+    fn test_drop_table_and_truncate() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            r#"cqlsh cass01 -e "DROP TABLE shop.orders""#,
+            "drop-table",
+        );
+        assert_blocks_with_pattern(&pack, "cqlsh -e 'TRUNCATE shop.orders'", "truncate");
+        assert_blocks_with_pattern(&pack, "cqlsh -e 'TRUNCATE TABLE shop.orders;'", "truncate");
+    }
+
+    #[test]
+    fn test_nodetool() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "nodetool decommission", "nodetool-decommission");
+        assert_blocks_with_pattern(
+            &pack,
+            "nodetool -h 10.0.0.5 -p 7199 decommission",
+            "nodetool-decommission",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "nodetool removenode 2f3c9a8e-1b2d-4c5e-8f90-123456789abc",
+            "nodetool-removenode",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "nodetool assassinate 10.0.0.7",
+            "nodetool-removenode",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "nodetool clearsnapshot --all",
+            "nodetool-clearsnapshot",
+        );
+    }
+
+    #[test]
+    fn test_safe_patterns() {
+        let pack = create_pack();
+        assert_allows(&pack, r#"cqlsh -e "DESCRIBE KEYSPACES""#);
+        assert_allows(&pack, "cqlsh cass01 -e 'DESC TABLE shop.orders;'");
+        assert_allows(&pack, r#"cqlsh -e "SELECT * FROM shop.orders LIMIT 10""#);
+        assert_allows(&pack, "nodetool status");
+        assert_allows(&pack, "nodetool listsnapshots");
+    }
+
+    #[test]
+    fn test_safe_patterns_do_not_cover_chained_statements() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            r#"cqlsh -e "SELECT * FROM ks.t; DROP KEYSPACE ks""#,
+            "drop-keyspace",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            r#"cqlsh -e "DESCRIBE KEYSPACES" && nodetool decommission"#,
+            "nodetool-decommission",
+        );
+    }
+}
@@ -0,0 +1,299 @@
+//! Database migration tool patterns - protections against commands that drop
+//! or rebuild a database through its migration tooling.
+//!
+//! Covers:
+//! - Prisma (`prisma migrate reset`, `prisma db push --force-reset`)
+//! - Rails (`rails db:drop`, `rails db:reset`, `rake db:purge`)
+//! - Alembic (`alembic downgrade base`)
+//! - Flyway (`flyway clean`)
+//! - Sequelize (`sequelize db:drop`, `sequelize db:migrate:undo:all`)
+//! - Diesel (`diesel database reset`)
+//!
+//! Status, generate, and migrate-up commands are allowed. The safe patterns
+//! cover the whole command and refuse any that also names a drop/reset verb,
+//! so `rails db:migrate db:drop` or `flyway info clean` still reach the
+//! destructive patterns.
+
+use crate::packs::{DestructivePattern, Pack, SafePattern};
+use crate::{destructive_pattern, safe_pattern};
+
+/// Create the database migrations pack.
+#[must_use]
+pub fn create_pack() -> Pack {
+    Pack {
+        id: "database.migrations".to_string(),
+        name: "Database Migrations",
+        description: "Protects against migration tool commands that drop or reset databases \
+                      (prisma migrate reset, rails db:drop, alembic downgrade base, flyway clean, \
+                      sequelize db:drop, diesel database reset)",
+        keywords: &[
+            "prisma",
+            "rails",
+            "rake",
+            "alembic",
+            "flyway",
+            "sequelize",
+            "diesel",
+        ],
+        safe_patterns: create_safe_patterns(),
+        destructive_patterns: create_destructive_patterns(),
+        keyword_matcher: None,
+        safe_regex_set: None,
+        safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
+    }
+}
+
+fn create_safe_patterns() -> Vec<SafePattern> {
+    vec![
+        // Migration status
+        safe_pattern!(
+            "migrations-status",
+            r"^\s*(?!.*\b(?:drop|reset|purge|clean|downgrade|undo)\b)(?:npx\s+|bunx\s+|pnpm\s+(?:exec\s+)?|yarn\s+|bundle\s+exec\s+|poetry\s+run\s+|uv\s+run\s+)?(?:prisma\s+migrate\s+status|(?:\./)?(?:bin/)?(?:rails|rake)\s+db:migrate:status|alembic\s+(?:current|history|heads|show|check)|flyway\s+(?:-\S+\s+)*(?:info|validate)|sequelize(?:-cli)?\s+db:migrate:status|diesel\s+migration\s+(?:list|pending))\b[^;&|\n$`]*$"
+        ),
+        // Generating migration files or clients
+        safe_pattern!(
+            "migrations-generate",
+            r"^\s*(?!.*\b(?:drop|reset|purge|clean|downgrade|undo)\b)(?:npx\s+|bunx\s+|pnpm\s+(?:exec\s+)?|yarn\s+|bundle\s+exec\s+|poetry\s+run\s+|uv\s+run\s+)?(?:prisma\s+generate|prisma\s+migrate\s+diff|(?:\./)?(?:bin/)?rails\s+(?:generate|g)\s+migration|alembic\s+revision|sequelize(?:-cli)?\s+(?:migration:generate|migration:create|model:generate)|diesel\s+migration\s+generate)\b[^;&|\n$`]*$"
+        ),
+        // Applying pending migrations
+        safe_pattern!(
+            "migrations-up",
+            r"^\s*(?!.*\b(?:drop|reset|purge|clean|downgrade|undo)\b)(?:npx\s+|bunx\s+|pnpm\s+(?:exec\s+)?|yarn\s+|bundle\s+exec\s+|poetry\s+run\s+|uv\s+run\s+)?(?:prisma\s+migrate\s+deploy|(?:\./)?(?:bin/)?(?:rails|rake)\s+db:migrate(?::up)?|alembic\s+upgrade|flyway\s+(?:-\S+\s+)*migrate|sequelize(?:-cli)?\s+db:migrate|diesel\s+migration\s+run)\b[^;&|\n$`]*$"
+        ),
+    ]
+}
+
+fn create_destructive_patterns() -> Vec<DestructivePattern> {
+    vec![
+        // Prisma
+        destructive_pattern!(
+            "prisma-migrate-reset",
+            r"\bprisma\s+migrate\s+reset\b",
+            "prisma migrate reset drops the database, reapplies every migration, and runs seed scripts.",
+            High,
+            "prisma migrate reset drops the database (or every schema object it can reach), \
+             recreates it, applies all migrations from scratch, and runs the seed script. \
+             Every row written since the last seed is lost. --force skips the confirmation \
+             prompt.\n\n\
+             Safer alternatives:\n\
+             - prisma migrate status: See which migrations are pending or drifted\n\
+             - prisma migrate deploy: Apply pending migrations without resetting\n\
+             - pg_dump / mysqldump: Back up the data before resetting"
+        ),
+        destructive_pattern!(
+            "prisma-db-push-force-reset",
+            r"\bprisma\s+db\s+push\b[^;&|\n]*\s--force-reset\b",
+            "prisma db push --force-reset drops all data before pushing the schema.",
+            High,
+            "--force-reset resets the database before synchronizing it with the Prisma \
+             schema, deleting every table and row.\n\n\
+             Safer alternatives:\n\
+             - prisma db push: Push without resetting; Prisma warns about data loss\n\
+             - prisma migrate diff: Review the schema change first"
+        ),
+        // Rails
+        destructive_pattern!(
+            "rails-db-drop",
+            r"\b(?:rails|rake)\b[^;&|\n]*\sdb:(?:drop|purge)(?::all)?\b",
+            "rails db:drop / db:purge deletes the database for the current environment.",
+            High,
+            "db:drop deletes the database for RAILS_ENV (db:drop:all deletes every database \
+             in config/database.yml); db:purge empties it. Rails refuses to drop a \
+             production database unless DISABLE_DATABASE_ENVIRONMENT_CHECK=1 is set, but \
+             development and staging data are gone either way.\n\n\
+             Safer alternatives:\n\
+             - rails db:migrate:status: Inspect migration state\n\
+             - rails db:rollback: Revert the last migration only"
+        ),
+        destructive_pattern!(
+            "rails-db-reset",
+            r"\b(?:rails|rake)\b[^;&|\n]*\sdb:(?:migrate:)?reset\b",
+            "rails db:reset drops and recreates the database from the schema; all data is lost.",
+            High,
+            "db:reset drops the database and reloads it from db/schema.rb and db/seeds.rb. \
+             db:migrate:reset drops it and reruns every migration. Either way, existing \
+             rows are deleted.\n\n\
+             Safer alternatives:\n\
+             - rails db:migrate: Apply pending migrations only\n\
+             - rails db:rollback STEP=1: Revert a single migration"
+        ),
+        // Alembic
+        destructive_pattern!(
+            "alembic-downgrade-base",
+            r"\balembic\b[^;&|\n]*\sdowngrade\s+base\b",
+            "alembic downgrade base reverts every migration, dropping the tables they created.",
+            High,
+            "Downgrading to base runs every downgrade() function in reverse order, which \
+             usually drops every table and column the migrations created.\n\n\
+             Safer alternatives:\n\
+             - alembic current / alembic history: Check where the database is\n\
+             - alembic downgrade -1: Revert one revision at a time\n\
+             - alembic downgrade base --sql: Print the SQL instead of running it"
+        ),
+        // Flyway
+        destructive_pattern!(
+            "flyway-clean",
+            r"\bflyway\b[^;&|\n]*\sclean\b(?![-=\w])",
+            "flyway clean drops all objects in the configured schemas.",
+            Critical,
+            "flyway clean drops every table, view, procedure, and other object in the \
+             configured schemas, including the schema history table. Flyway 9+ disables \
+             it by default (cleanDisabled=true); running it means that safeguard was \
+             turned off.\n\n\
+             Safer alternatives:\n\
+             - flyway info: Show migration state\n\
+             - flyway validate: Check applied migrations against local files\n\
+             - flyway undo: Revert the latest versioned migration (Teams edition)"
+        ),
+        // Sequelize
+        destructive_pattern!(
+            "sequelize-db-drop",
+            r"\bsequelize(?:-cli)?\s+db:drop\b",
+            "sequelize db:drop deletes the database for the current environment.",
+            High,
+            "sequelize db:drop drops the database named in the config for NODE_ENV, \
+             including all tables and data.\n\n\
+             Safer alternatives:\n\
+             - sequelize db:migrate:status: Inspect migration state\n\
+             - sequelize db:migrate:undo: Revert the last migration only"
+        ),
+        destructive_pattern!(
+            "sequelize-migrate-undo-all",
+            r"\bsequelize(?:-cli)?\s+db:migrate:undo:all\b",
+            "sequelize db:migrate:undo:all reverts every migration, dropping their tables.",
+            High,
+            "undo:all runs every migration's down() in reverse, which usually drops every \
+             table the migrations created.\n\n\
+             Safer alternatives:\n\
+             - sequelize db:migrate:undo: Revert the last migration only\n\
+             - sequelize db:migrate:undo:all --to <migration>: Stop at a known migration"
+        ),
+        // Diesel
+        destructive_pattern!(
+            "diesel-database-reset",
+            r"\bdiesel\s+database\s+reset\b",
+            "diesel database reset drops the database and reruns all migrations.",
+            High,
+            "diesel database reset drops the database at DATABASE_URL, recreates it, and \
+             runs every migration. All data is lost.\n\n\
+             Safer alternatives:\n\
+             - diesel migration list: Inspect migration state\n\
+             - diesel migration revert: Revert the last migration only"
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::Severity;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_pack_creation() {
+        let pack = create_pack();
+        assert_eq!(pack.id, "database.migrations");
+        assert!(pack.keywords.contains(&"prisma"));
+        assert!(pack.keywords.contains(&"rails"));
+
+        assert_patterns_compile(&pack);
+        assert_all_patterns_have_reasons(&pack);
+        assert_unique_pattern_names(&pack);
+    }
+
+    #[test]
+    fn blocks_destructive_commands() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "npx prisma migrate reset", "prisma-migrate-reset");
+        assert_blocks_with_pattern(
+            &pack,
+            "prisma migrate reset --force --skip-seed",
+            "prisma-migrate-reset",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "npx prisma db push --force-reset",
+            "prisma-db-push-force-reset",
+        );
+        assert_blocks_with_pattern(&pack, "rails db:drop", "rails-db-drop");
+        assert_blocks_with_pattern(&pack, "bin/rails db:drop:all", "rails-db-drop");
+        assert_blocks_with_pattern(&pack, "bundle exec rake db:purge", "rails-db-drop");
+        assert_blocks_with_pattern(&pack, "RAILS_ENV=test rails db:reset", "rails-db-reset");
+        assert_blocks_with_pattern(&pack, "rake db:migrate:reset", "rails-db-reset");
+        assert_blocks_with_pattern(&pack, "alembic downgrade base", "alembic-downgrade-base");
+        assert_blocks_with_pattern(
+            &pack,
+            "alembic -c alembic.ini downgrade base",
+            "alembic-downgrade-base",
+        );
+        assert_blocks_with_pattern(&pack, "flyway clean", "flyway-clean");
+        assert_blocks_with_severity(
+            &pack,
+            "flyway -url=jdbc:postgresql://db/app -cleanDisabled=false clean",
+            Severity::Critical,
+        );
+        assert_blocks_with_pattern(&pack, "npx sequelize-cli db:drop", "sequelize-db-drop");
+        assert_blocks_with_pattern(
+            &pack,
+            "sequelize db:migrate:undo:all",
+            "sequelize-migrate-undo-all",
+        );
+        assert_blocks_with_pattern(&pack, "diesel database reset", "diesel-database-reset");
+    }
+
+    #[test]
+    fn allows_status_generate_and_migrate_up() {
+        let pack = create_pack();
+        for command in [
+            "npx prisma migrate status",
+            "prisma generate",
+            "npx prisma migrate deploy",
+            "rails db:migrate",
+            "bin/rails db:migrate:status",
+            "bundle exec rails generate migration AddEmailToUsers email:string",
+            "rake db:migrate:up VERSION=20240101000000",
+            "alembic current",
+            "alembic revision --autogenerate -m 'add users'",
+            "alembic upgrade head",
+            "flyway info",
+            "flyway -url=jdbc:postgresql://db/app migrate",
+            "npx sequelize-cli db:migrate:status",
+            "npx sequelize-cli migration:generate --name add-users",
+            "npx sequelize-cli db:migrate",
+            "diesel migration list",
+            "diesel migration generate create_users",
+            "diesel migration run",
+        ] {
+            assert_safe_pattern_matches(&pack, command);
+            assert_allows(&pack, command);
+        }
+    }
+
+    #[test]
+    fn safe_patterns_do_not_mask_destructive_tasks() {
+        let pack = create_pack();
+        assert_no_safe_match(&pack, "rails db:migrate db:drop");
+        assert_blocks_with_pattern(&pack, "rails db:migrate db:drop", "rails-db-drop");
+        assert_no_safe_match(&pack, "flyway info clean");
+        assert_blocks_with_pattern(&pack, "flyway info clean", "flyway-clean");
+        assert_no_safe_match(&pack, "prisma migrate status; prisma migrate reset --force");
+        assert_blocks_with_pattern(
+            &pack,
+            "prisma migrate status; prisma migrate reset --force",
+            "prisma-migrate-reset",
+        );
+        assert_no_safe_match(&pack, "rails db:migrate && rails db:reset");
+    }
+
+    #[test]
+    fn ignores_non_destructive_lookalikes() {
+        let pack = create_pack();
+        assert_allows(&pack, "flyway -cleanDisabled=true migrate");
+        assert_allows(&pack, "alembic downgrade -1");
+        assert_allows(&pack, "rails db:rollback");
+        assert_allows(&pack, "rails db:seed");
+        assert_allows(&pack, "grep -r prisma src/");
+    }
+}
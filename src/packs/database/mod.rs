@@ -6,7 +6,11 @@
 //! - `MongoDB` (`mongosh`, `mongodump`)
 //! - `Redis` (`redis-cli`)
 //! - `SQLite` (`sqlite3`)
+//! - Cassandra/`ScyllaDB` (`cqlsh`, `nodetool`)
+//! - Migration tools (Prisma, Rails, Alembic, Flyway, Sequelize, Diesel)
 
+pub mod cassandra;
+pub mod migrations;
 pub mod mongodb;
 pub mod mysql;
 pub mod postgresql;
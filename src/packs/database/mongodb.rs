@@ -4,6 +4,12 @@
 //! - dropDatabase/dropCollection commands
 //! - db.collection.remove({}) without criteria
 //! - mongosh destructive operations
+//! - Atlas CLI cluster deletion
+//!
+//! `mongosh --eval` payloads are also parsed as JavaScript by the heredoc
+//! analyzer (`heredoc.javascript.mongo_*`), which catches them even when this
+//! pack is disabled. The flat patterns below are the fallback for when
+//! heredoc scanning is off.
 
 use crate::packs::{DestructivePattern, Pack, SafePattern};
 use crate::{destructive_pattern, safe_pattern};
@@ -15,19 +21,22 @@ pub fn create_pack() -> Pack {
         id: "database.mongodb".to_string(),
         name: "MongoDB",
         description: "Protects against destructive MongoDB operations like dropDatabase, \
-                      dropCollection, and remove without criteria",
+                      dropCollection, remove without criteria, and Atlas cluster deletion",
         keywords: &[
             "mongo",
             "mongosh",
             "dropDatabase",
             "dropCollection",
             "deleteMany",
+            "atlas",
         ],
         safe_patterns: create_safe_patterns(),
         destructive_patterns: create_destructive_patterns(),
         keyword_matcher: None,
         safe_regex_set: None,
         safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
     }
 }
 
@@ -131,5 +140,66 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              Backup:\n  \
              mongodump --db=mydb --collection=mycollection"
         ),
+        // atlas clusters delete
+        destructive_pattern!(
+            "atlas-cluster-delete",
+            r"\batlas(?:\s+--?[A-Za-z][\w-]*(?:[=\s]+[^\s-]\S*)?)*\s+clusters?\s+delete\b",
+            "atlas clusters delete permanently deletes the Atlas cluster and its data.",
+            Critical,
+            "atlas clusters delete tears down a hosted MongoDB Atlas cluster:\n\n\
+             - Every database on the cluster is deleted\n\
+             - Cloud backups are removed unless retained by a backup policy\n\
+             - Connection strings stop resolving for all applications\n\
+             - With --force there is no confirmation prompt\n\n\
+             Check the cluster and its snapshots first:\n  \
+             atlas clusters describe <name>\n  \
+             atlas backups snapshots list <name>\n\n\
+             Enable termination protection to guard production clusters:\n  \
+             atlas clusters update <name> --enableTerminationProtection"
+        ),
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::Severity;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_shell_operations() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "mongosh app --eval 'db.dropDatabase()'",
+            "drop-database",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "mongosh --eval 'db.orders.deleteMany({})'",
+            "delete-all",
+        );
+        assert_blocks(&pack, "mongosh --eval 'db.users.drop()'", "drop");
+        assert_allows(
+            &pack,
+            "mongosh --eval 'db.orders.deleteMany({ status: 1 })'",
+        );
+    }
+
+    #[test]
+    fn test_atlas_cluster_delete() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "atlas clusters delete prod --force",
+            "atlas-cluster-delete",
+        );
+        assert_blocks_with_severity(
+            &pack,
+            "atlas --profile prod cluster delete analytics",
+            Severity::Critical,
+        );
+        assert_allows(&pack, "atlas clusters list");
+        assert_allows(&pack, "atlas clusters describe prod");
+    }
+}
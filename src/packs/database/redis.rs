@@ -1,10 +1,14 @@
 //! Redis patterns - protections against destructive redis-cli commands.
 //!
 //! This includes patterns for:
-//! - FLUSHALL/FLUSHDB commands
-//! - DEL with wildcards
-//! - CONFIG RESETSTAT
+//! - FLUSHALL/FLUSHDB commands (including ASYNC)
 //! - DEBUG commands
+//! - SHUTDOWN (with and without NOSAVE)
+//! - CONFIG SET that disables persistence or enables file-write attacks
+//! - CLUSTER RESET and `redis-cli --cluster del-node`
+//!
+//! Commands are matched wherever they appear, so `echo FLUSHALL | redis-cli`
+//! is caught as well as `redis-cli FLUSHALL`.
 
 use crate::packs::{DestructivePattern, Pack, SafePattern};
 use crate::{destructive_pattern, safe_pattern};
@@ -23,21 +27,21 @@ pub fn create_pack() -> Pack {
         keyword_matcher: None,
         safe_regex_set: None,
         safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
     }
 }
 
 fn create_safe_patterns() -> Vec<SafePattern> {
     vec![
-        // GET/MGET operations are safe
-        safe_pattern!("redis-get", r"(?i)\b(?:GET|MGET)\b"),
-        // SCAN is safe (cursor-based iteration)
-        safe_pattern!("redis-scan", r"(?i)\bSCAN\b"),
-        // INFO is safe (server info)
-        safe_pattern!("redis-info", r"(?i)\bINFO\b"),
-        // KEYS (read-only, though potentially slow)
-        safe_pattern!("redis-keys", r"(?i)\bKEYS\b"),
-        // DBSIZE is safe
-        safe_pattern!("redis-dbsize", r"(?i)\bDBSIZE\b"),
+        // A single `redis-cli [options] <read command> ...` invocation.
+        // Anchored so a read elsewhere in the line (`redis-cli INFO; redis-cli
+        // FLUSHALL`, `echo 'KEYS *; FLUSHALL' | redis-cli`) does not make the
+        // whole command safe.
+        safe_pattern!(
+            "redis-cli-read-only",
+            r"(?i)^\s*redis-cli(?:\s+-{1,2}[a-z][\w-]*(?:=[^\s;&|$\x60]+|\s+[^\s;&|$\x60-][^\s;&|$\x60]*)?)*\s+(?:GET|MGET|SCAN|INFO|KEYS|DBSIZE|TTL|TYPE|EXISTS|PING)\b[^;&|\n$\x60]*$"
+        ),
     ]
 }
 
@@ -55,7 +59,8 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              - All queues are emptied\n\
              - Affects ALL databases, not just current\n\n\
              This is instant and cannot be undone. RDB/AOF backups are not affected \
-             until the next save.\n\n\
+             until the next save. FLUSHALL ASYNC only frees the memory in the \
+             background; the keys are gone as soon as the command returns.\n\n\
              Check database sizes first:\n  \
              INFO keyspace\n  \
              DBSIZE\n\n\
@@ -111,6 +116,23 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              Disable in production:\n  \
              CONFIG SET debug-command no"
         ),
+        // SHUTDOWN NOSAVE - stops the server and discards unsaved writes
+        destructive_pattern!(
+            "shutdown-nosave",
+            r"(?i)\bSHUTDOWN\s+NOSAVE\b",
+            "SHUTDOWN NOSAVE stops the Redis server and discards every write since the last save.",
+            Critical,
+            "SHUTDOWN NOSAVE exits without writing an RDB snapshot:\n\n\
+             - Every write since the last save is lost (AOF aside)\n\
+             - All client connections are closed\n\
+             - Services depending on Redis fail until it is restarted\n\n\
+             Check how much would be lost:\n  \
+             INFO persistence  # rdb_changes_since_last_save, aof_enabled\n\n\
+             Save first, or use a plain SHUTDOWN (which saves when persistence is \
+             configured):\n  \
+             BGSAVE\n  \
+             SHUTDOWN SAVE"
+        ),
         // SHUTDOWN without NOSAVE
         destructive_pattern!(
             "shutdown",
@@ -144,5 +166,136 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              rename-command CONFIG \"\"  # In redis.conf\n\n\
              Use ACLs to restrict these commands."
         ),
+        // CONFIG SET that turns persistence off
+        destructive_pattern!(
+            "config-disable-persistence",
+            r#"(?i)\bCONFIG\s+SET\s+(?:appendonly\s+["']?no\b|save\s+(?:""|''))"#,
+            "CONFIG SET appendonly no / save \"\" disables Redis persistence.",
+            High,
+            "These CONFIG SET calls switch off durability on a running server:\n\n\
+             - appendonly no: stops the AOF; writes since the last RDB snapshot are \
+             lost on restart\n\
+             - save \"\": removes every RDB snapshot schedule\n\
+             - Takes effect immediately, without a restart\n\
+             - A later CONFIG REWRITE makes it permanent in redis.conf\n\n\
+             Check current settings first:\n  \
+             CONFIG GET appendonly\n  \
+             CONFIG GET save\n  \
+             INFO persistence"
+        ),
+        // CLUSTER RESET - drops the node out of the cluster
+        destructive_pattern!(
+            "cluster-reset",
+            r"(?i)\bCLUSTER\s+RESET\b",
+            "CLUSTER RESET removes the node from its cluster; a replica's dataset is flushed.",
+            High,
+            "CLUSTER RESET (SOFT by default, or HARD) makes the node forget the cluster:\n\n\
+             - All other nodes are forgotten and slot assignments are cleared\n\
+             - A replica is turned into an empty master: its data is flushed\n\
+             - HARD also generates a new node ID and resets the epochs\n\
+             - The cluster may lose coverage of slots served by this node\n\n\
+             Inspect the node's role and slots first:\n  \
+             CLUSTER INFO\n  \
+             CLUSTER NODES\n  \
+             redis-cli --cluster check <host>:<port>"
+        ),
+        // redis-cli --cluster del-node
+        destructive_pattern!(
+            "cluster-del-node",
+            r"(?i)\bredis-cli\b[^|;&\n]*\s--cluster\s+del-node\b",
+            "redis-cli --cluster del-node removes a node from the cluster and shuts it down.",
+            High,
+            "redis-cli --cluster del-node makes every node forget the target and then \
+             shuts the target down:\n\n\
+             - The node is removed from the cluster configuration everywhere\n\
+             - The removed node is stopped\n\
+             - A master must own no slots, or its slots become unreachable\n\n\
+             Check the node's slots and move them first:\n  \
+             redis-cli --cluster check <host>:<port>\n  \
+             redis-cli --cluster reshard <host>:<port>"
+        ),
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::Severity;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_flush_and_debug() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "redis-cli FLUSHALL ASYNC", "flushall");
+        assert_blocks_with_pattern(&pack, "redis-cli -n 2 flushdb async", "flushdb");
+        assert_blocks_with_pattern(&pack, "redis-cli -h cache DEBUG SEGFAULT", "debug-crash");
+    }
+
+    #[test]
+    fn test_persistence_and_shutdown() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "redis-cli CONFIG SET appendonly no",
+            "config-disable-persistence",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "redis-cli config set save \"\"",
+            "config-disable-persistence",
+        );
+        assert_blocks_with_pattern(&pack, "redis-cli SHUTDOWN NOSAVE", "shutdown-nosave");
+        assert_blocks_with_severity(&pack, "redis-cli shutdown nosave", Severity::Critical);
+        assert_blocks_with_pattern(&pack, "redis-cli SHUTDOWN SAVE", "shutdown");
+        assert_allows(&pack, "redis-cli CONFIG SET appendonly yes");
+        assert_allows(&pack, "redis-cli CONFIG SET save \"3600 1\"");
+    }
+
+    #[test]
+    fn test_cluster_operations() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "redis-cli -p 7000 CLUSTER RESET HARD",
+            "cluster-reset",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "redis-cli --cluster del-node 10.0.0.1:7000 3a8f1c2b",
+            "cluster-del-node",
+        );
+        assert_allows(&pack, "redis-cli --cluster check 10.0.0.1:7000");
+        assert_allows(&pack, "redis-cli -p 7000 CLUSTER NODES");
+    }
+
+    #[test]
+    fn test_piped_commands() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "echo FLUSHALL ASYNC | redis-cli", "flushall");
+        assert_blocks_with_pattern(
+            &pack,
+            "echo 'CLUSTER RESET HARD' | redis-cli -p 7000",
+            "cluster-reset",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "printf 'CONFIG SET appendonly no\\n' | redis-cli",
+            "config-disable-persistence",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "echo 'INFO keyspace; SHUTDOWN NOSAVE' | redis-cli",
+            "shutdown-nosave",
+        );
+    }
+
+    #[test]
+    fn test_read_only_commands() {
+        let pack = create_pack();
+        assert_allows(&pack, "redis-cli -h cache -p 6379 INFO keyspace");
+        assert_allows(&pack, "redis-cli --raw GET session:42");
+        assert_allows(&pack, "redis-cli KEYS 'user:*'");
+        assert_allows(&pack, "redis-cli DBSIZE");
+        assert_blocks_with_pattern(&pack, "redis-cli INFO; redis-cli FLUSHALL", "flushall");
+    }
+}
@@ -4,6 +4,12 @@
 //! - DROP TABLE/DATABASE commands
 //! - DELETE without WHERE
 //! - .quit without .backup
+//! - rm of database files and their -wal/-shm/-journal sidecars
+//!
+//! Inline SQL (`sqlite3 app.db "..."`) and `sqlite3 <<EOF` scripts, including
+//! `.open`/`.restore` dot-commands, are also scanned by the heredoc SQL
+//! analyzer. Production database files can be listed in `[protection.paths]`
+//! to deny any non-read-only `sqlite3` session on them.
 
 use crate::packs::{DestructivePattern, Pack, SafePattern};
 use crate::{destructive_pattern, safe_pattern};
@@ -16,12 +22,16 @@ pub fn create_pack() -> Pack {
         name: "SQLite",
         description: "Protects against destructive SQLite operations like DROP TABLE, \
                       DELETE without WHERE, and accidental data loss",
-        keywords: &["sqlite", "sqlite3", "DROP", "TRUNCATE", "DELETE"],
+        keywords: &[
+            "sqlite", "sqlite3", "DROP", "TRUNCATE", "DELETE", ".db", ".db3",
+        ],
         safe_patterns: create_safe_patterns(),
         destructive_patterns: create_destructive_patterns(),
         keyword_matcher: None,
         safe_regex_set: None,
         safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
     }
 }
 
@@ -29,12 +39,25 @@ fn create_safe_patterns() -> Vec<SafePattern> {
     vec![
         // SELECT queries are safe
         safe_pattern!("select-query", r"(?i)^\s*SELECT\s+"),
-        // .schema, .tables, .dump are read-only
-        safe_pattern!("dot-schema", r"\.schema"),
-        safe_pattern!("dot-tables", r"\.tables"),
-        safe_pattern!("dot-dump", r"\.dump"),
+        // .schema, .tables, .dump are read-only. Anchored to a lone
+        // `sqlite3 DB .command` so a chained `&& rm app.db` is still checked.
+        safe_pattern!(
+            "dot-schema",
+            r#"^\s*sqlite3?(?:\s+-[A-Za-z]+)*\s+[^\s;&|$\x60<>'"]+\s+["']?\.schema(?:\s+[\w./%*-]+){0,2}["']?(?:\s*>\s*[^\s;&|$\x60]+)?\s*$"#
+        ),
+        safe_pattern!(
+            "dot-tables",
+            r#"^\s*sqlite3?(?:\s+-[A-Za-z]+)*\s+[^\s;&|$\x60<>'"]+\s+["']?\.tables(?:\s+[\w./%*-]+){0,2}["']?(?:\s*>\s*[^\s;&|$\x60]+)?\s*$"#
+        ),
+        safe_pattern!(
+            "dot-dump",
+            r#"^\s*sqlite3?(?:\s+-[A-Za-z]+)*\s+[^\s;&|$\x60<>'"]+\s+["']?\.dump(?:\s+[\w./%*-]+){0,2}["']?(?:\s*>\s*[^\s;&|$\x60]+)?\s*$"#
+        ),
         // .backup is safe (creates backup)
-        safe_pattern!("dot-backup", r"\.backup"),
+        safe_pattern!(
+            "dot-backup",
+            r#"^\s*sqlite3?(?:\s+-[A-Za-z]+)*\s+[^\s;&|$\x60<>'"]+\s+["']?\.backup(?:\s+[\w./%*-]+){0,2}["']?\s*$"#
+        ),
         // EXPLAIN is safe
         safe_pattern!("explain", r"(?i)^\s*EXPLAIN\s+"),
     ]
@@ -104,5 +127,59 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              - .read filename inside sqlite3: Allows Ctrl+C interruption\n\
              - Run in a transaction: Wrap file contents in BEGIN/COMMIT"
         ),
+        // rm app.db / rm *.sqlite3 / rm app.db-wal
+        destructive_pattern!(
+            "rm-database-file",
+            r#"(?:^|[;&|(]|\bsudo)\s*rm\s+(?:[^;&|\n]*\s)?["']?[^\s;&|"']*\.(?:db|db3|sqlite3?)(?:-wal|-shm|-journal)?["']?(?:\s|$|[;&|)])"#,
+            "rm deletes a SQLite database file (or its WAL/journal). Back it up first.",
+            High,
+            "A SQLite database is a single file, so rm deletes every table and row at once. \
+             Removing the -wal or -journal sidecar of a database that is in use is just as \
+             bad: committed transactions that have not been checkpointed are lost and the \
+             database may be left corrupt.\n\n\
+             Safer alternatives:\n\
+             - sqlite3 app.db \".backup app.db.bak\": Take a consistent copy first\n\
+             - mv app.db app.db.old: Keep the file until you are sure\n\
+             - Stop the writing process before touching -wal/-shm files\n\
+             - [protection.paths]: Protect production database files outright"
+        ),
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_rm_database_files() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "rm app.db", "rm-database-file");
+        assert_blocks_with_pattern(&pack, "rm -f *.db", "rm-database-file");
+        assert_blocks_with_pattern(&pack, "sudo rm data/prod.sqlite3", "rm-database-file");
+        assert_blocks_with_pattern(&pack, "rm -f 'app.db-wal' app.db-shm", "rm-database-file");
+        assert_allows(&pack, "rm app.db.bak");
+        assert_allows(&pack, "git rm --cached app.db");
+        assert_allows(&pack, "rm -rf build && ls app.db");
+    }
+
+    #[test]
+    fn test_inline_sql_and_dot_commands() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, r#"sqlite3 app.db "DROP TABLE users""#, "drop-table");
+        assert_allows(&pack, "sqlite3 app.db .schema");
+        assert_allows(&pack, "sqlite3 -header app.db .dump > backup.sql");
+        assert_allows(&pack, r#"sqlite3 app.db ".backup app.db.bak""#);
+        // A read-only dot-command does not shield the rest of the chain.
+        assert_blocks_with_pattern(
+            &pack,
+            "sqlite3 app.db .tables && rm app.db",
+            "rm-database-file",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            r#"sqlite3 app.db "DROP TABLE users" .schema"#,
+            "drop-table",
+        );
+    }
+}
@@ -33,6 +33,12 @@
 //!   - name: staging-deploy
 //!     pattern: deploy\s+--env\s*=?\s*(staging|dev)
 //!     description: Non-production deployments are allowed
+//!
+//! examples:
+//!   positive:
+//!     - deploy --env=prod
+//!   negative:
+//!     - deploy --env staging
 //! ```
 
 use serde::Deserialize;
@@ -86,6 +92,10 @@ pub struct ExternalPack {
     /// Safe patterns that explicitly allow commands.
     #[serde(default)]
     pub safe_patterns: Vec<ExternalSafePattern>,
+
+    /// Example commands for `dcg pattern test`; not used when evaluating.
+    #[serde(default)]
+    pub examples: ExternalExamples,
 }
 
 /// Default schema version for packs that don't specify one.
@@ -117,6 +127,10 @@ pub struct ExternalDestructivePattern {
     /// Safer command alternatives to suggest when this pattern matches.
     #[serde(default)]
     pub suggestions: Vec<ExternalSuggestion>,
+
+    /// How to recover if the command runs anyway; shown in warn mode.
+    #[serde(default)]
+    pub recovery_hint: Option<String>,
 }
 
 /// A safer command suggestion from an external pack file.
@@ -172,6 +186,18 @@ pub struct ExternalSafePattern {
     pub description: Option<String>,
 }
 
+/// Example commands declared in an external pack file.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ExternalExamples {
+    /// Commands the pack must block.
+    #[serde(default)]
+    pub positive: Vec<String>,
+
+    /// Commands the pack must allow.
+    #[serde(default)]
+    pub negative: Vec<String>,
+}
+
 /// Severity level as specified in external pack files.
 #[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -542,6 +568,9 @@ impl ExternalPack {
                 let explanation: Option<&'static str> = p
                     .explanation
                     .map(|s| Box::leak(s.into_boxed_str()) as &'static str);
+                let recovery_hint: Option<&'static str> = p
+                    .recovery_hint
+                    .map(|s| Box::leak(s.into_boxed_str()) as &'static str);
 
                 // Convert suggestions to static slice
                 let suggestions: &'static [super::PatternSuggestion] = if p.suggestions.is_empty() {
@@ -566,18 +595,21 @@ impl ExternalPack {
                     severity: p.severity.into(),
                     explanation,
                     suggestions,
+                    recovery_hint,
                 }
             })
             .collect();
 
-        Pack::new(
+        let mut pack = Pack::new(
             self.id,
             name,
             description,
             keywords,
             safe_patterns,
             destructive_patterns,
-        )
+        );
+        pack.critical_patterns = Pack::critical_pattern_indices(&pack.destructive_patterns);
+        pack
     }
 }
 
@@ -3,6 +3,12 @@
 //! This includes patterns for:
 //! - ansible-playbook with dangerous patterns
 //! - ansible with shell/command modules doing destructive things
+//! - ad-hoc `-m file` with state=absent against system paths
+//! - --extra-vars that switch off a playbook's safety variables
+//!
+//! With this pack enabled, the evaluator also checks the `-a` argument of
+//! ad-hoc shell/command/raw invocations as a command of its own (see
+//! [`crate::ansible`]).
 
 use crate::packs::{DestructivePattern, Pack, SafePattern};
 use crate::{destructive_pattern, safe_pattern};
@@ -14,13 +20,16 @@ pub fn create_pack() -> Pack {
         id: "infrastructure.ansible".to_string(),
         name: "Ansible",
         description: "Protects against destructive Ansible operations like dangerous shell \
-                      commands and unchecked playbook runs",
+                      commands, file state=absent on system paths, extra-vars that disable \
+                      safety checks, and unchecked playbook runs",
         keywords: &["ansible", "playbook"],
         safe_patterns: create_safe_patterns(),
         destructive_patterns: create_destructive_patterns(),
         keyword_matcher: None,
         safe_regex_set: None,
         safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
     }
 }
 
@@ -54,8 +63,26 @@ fn create_safe_patterns() -> Vec<SafePattern> {
     ]
 }
 
+#[allow(clippy::too_many_lines)]
 fn create_destructive_patterns() -> Vec<DestructivePattern> {
     vec![
+        // ansible all -m file -a "path=/etc state=absent" (option order varies)
+        destructive_pattern!(
+            "file-absent-system-path",
+            r#"\bansible\s(?=[^;&|\n]*-m\s*['"]?(?:ansible\.(?:builtin|legacy)\.)?file\b)(?=[^;&|\n]*\bstate\s*=\s*['"]?absent\b)(?=[^;&|\n]*\b(?:path|dest|name)\s*=\s*['"]?/(?:(?:etc|usr|bin|sbin|lib|lib32|lib64|boot|var|opt|root|home|srv|sys|proc|dev|mnt|media|snap)(?:/[^/\s'"]+)?/?)?(?:\s|['"]|$))"#,
+            "Ansible file module with state=absent on a system path deletes it on every targeted host.",
+            Critical,
+            "The file module with state=absent removes the path recursively, like rm -rf, on \
+             every host the pattern matches:\n\n\
+             - /, /etc, /usr, /var/lib, /home and their direct children break the OS or \
+             wipe user data\n\
+             - Ad-hoc runs have no review step and execute in parallel (-f forks)\n\
+             - Each host needs its own restore from backup\n\n\
+             Safer alternatives:\n\
+             - Run with --check first to see which hosts would change\n\
+             - Restrict hosts with --limit and target the exact file, not its directory\n\
+             - Put the deletion in a reviewed playbook task"
+        ),
         // ansible with rm -rf in command
         destructive_pattern!(
             "shell-rm-rf",
@@ -93,6 +120,24 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              - Use --limit to test on single host first\n\
              - Coordinate with maintenance windows"
         ),
+        // -e/--extra-vars turning off a playbook's own safety switches
+        destructive_pattern!(
+            "extra-vars-safety-override",
+            r#"\bansible(?:-playbook)?\s[^;&|\n]*\s(?:-e|--extra-vars)(?:\s+|=)[^;&|\n]*?(?:\b(?:dry_run|check_mode|ansible_check_mode|safe_mode|safety_checks?|require_confirmation|confirmation_required|protect(?:ed|ion)?)['"]?\s*[=:]\s*['"]?(?:false|no|off|0)\b|\b(?:force(?:_(?:delete|destroy|remove|wipe|recreate|reinstall))?|skip_(?:\w+_)?(?:checks?|safety|validation|backups?|confirm\w*)|allow_\w*(?:delete|deletion|destroy|destructive|wipe|drop)\w*|i_know_what_i_am_doing|confirm(?:ed)?|purge)['"]?\s*[=:]\s*['"]?(?:true|yes|on|1)\b)"#,
+            "Ansible extra-vars override a safety variable (dry_run=false, force=true, skip_checks=true).",
+            High,
+            "Extra variables have the highest precedence in Ansible, so -e overrides whatever \
+             the playbook, inventory, and role defaults set:\n\n\
+             - dry_run=false, check_mode=false, or safe_mode=false turns a guarded run into \
+             a real one\n\
+             - force=true, confirm=true, or skip_checks=true bypasses the playbook's own \
+             confirmation and validation steps\n\
+             - The override applies to every play and every host in the run\n\n\
+             Safer alternatives:\n\
+             - Run with --check --diff and the same variables first\n\
+             - Use --limit to apply to one host before the rest\n\
+             - Keep the override in a reviewed vars file instead of the command line"
+        ),
         // ansible-playbook targeting all hosts without limit
         destructive_pattern!(
             "playbook-all-hosts",
@@ -130,3 +175,83 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
         ),
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_pack_creation() {
+        let pack = create_pack();
+        assert_eq!(pack.id, "infrastructure.ansible");
+        assert!(pack.keywords.contains(&"ansible"));
+
+        assert_patterns_compile(&pack);
+        assert_all_patterns_have_reasons(&pack);
+        assert_unique_pattern_names(&pack);
+    }
+
+    #[test]
+    fn blocks_file_absent_on_system_paths() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "ansible all -m file -a 'path=/etc state=absent'",
+            "file-absent-system-path",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            r#"ansible web -a "state=absent path=/var/lib" -m ansible.builtin.file"#,
+            "file-absent-system-path",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "ansible all -m file -a 'path=/ state=absent'",
+            "file-absent-system-path",
+        );
+        assert_allows(
+            &pack,
+            "ansible all -m file -a 'path=/srv/app/releases/old/cache state=absent'",
+        );
+        assert_allows(&pack, "ansible all -m file -a 'path=/etc/motd state=touch'");
+    }
+
+    #[test]
+    fn blocks_extra_vars_safety_overrides() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "ansible-playbook site.yml --limit web -e dry_run=false",
+            "extra-vars-safety-override",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            r#"ansible-playbook wipe.yml --extra-vars '{"confirm": true}'"#,
+            "extra-vars-safety-override",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "ansible-playbook db.yml --extra-vars=skip_backup=yes",
+            "extra-vars-safety-override",
+        );
+        assert_allows(
+            &pack,
+            "ansible-playbook site.yml --limit web -e dry_run=true",
+        );
+        assert_allows(
+            &pack,
+            "ansible-playbook site.yml --limit web -e app_version=1.2.3",
+        );
+    }
+
+    #[test]
+    fn blocks_adhoc_shell_rm_rf() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            r#"ansible all -m shell -a "rm -rf /var/lib/app""#,
+            "shell-rm-rf",
+        );
+    }
+}
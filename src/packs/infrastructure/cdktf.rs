@@ -0,0 +1,145 @@
+//! CDK for Terraform patterns - protections against destructive cdktf commands.
+//!
+//! cdktf synthesizes Terraform configuration and drives terraform itself, so
+//! `cdktf destroy` is `terraform destroy` for every stack it selects. This
+//! includes patterns for:
+//! - cdktf destroy (and --auto-approve)
+//! - cdktf deploy with --auto-approve
+
+use crate::packs::{DestructivePattern, Pack, SafePattern};
+use crate::{destructive_pattern, safe_pattern};
+
+/// Create the CDK for Terraform pack.
+#[must_use]
+pub fn create_pack() -> Pack {
+    Pack {
+        id: "infrastructure.cdktf".to_string(),
+        name: "CDK for Terraform",
+        description: "Protects against destructive CDK for Terraform operations like \
+                      cdktf destroy --auto-approve and deploy with --auto-approve",
+        keywords: &["cdktf"],
+        safe_patterns: create_safe_patterns(),
+        destructive_patterns: create_destructive_patterns(),
+        keyword_matcher: None,
+        safe_regex_set: None,
+        safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
+    }
+}
+
+fn create_safe_patterns() -> Vec<SafePattern> {
+    // Anchored to the whole command so a read-only call cannot whitelist a
+    // chained `cdktf destroy`.
+    vec![
+        // synth renders Terraform JSON locally
+        safe_pattern!(
+            "cdktf-synth",
+            r"^\s*(?:npx\s+)?cdktf\s+(?:synth|synthesize)\b[^;&|\n$`]*$"
+        ),
+        // diff runs terraform plan for the selected stacks
+        safe_pattern!(
+            "cdktf-diff",
+            r"^\s*(?:npx\s+)?cdktf\s+(?:diff|plan)\b[^;&|\n$`]*$"
+        ),
+        // list/get/output/debug only inspect the app or fetch bindings
+        safe_pattern!(
+            "cdktf-read-only",
+            r"^\s*(?:npx\s+)?cdktf\s+(?:ls|list|get|output|outputs|debug|--version)\b[^;&|\n$`]*$"
+        ),
+    ]
+}
+
+fn create_destructive_patterns() -> Vec<DestructivePattern> {
+    vec![
+        // destroy --auto-approve before destroy
+        destructive_pattern!(
+            "destroy-auto-approve",
+            r"\bcdktf\s+destroy\b[^;&|\n]*\s--auto-approve\b",
+            "cdktf destroy --auto-approve removes ALL resources of the selected stacks without confirmation.",
+            Critical,
+            "cdktf destroy --auto-approve runs terraform destroy with no prompt:\n\n\
+             - Every resource in the selected stacks is destroyed immediately\n\
+             - Passing several stacks or '*' extends this to all of them\n\
+             - Cannot be undone without backups/recreation\n\n\
+             Preview first: cdktf diff <stack>\n\
+             Then run cdktf destroy without --auto-approve and review the plan"
+        ),
+        // destroy
+        destructive_pattern!(
+            "destroy",
+            r"\bcdktf\s+destroy\b",
+            "cdktf destroy removes ALL resources of the selected stacks. Use 'cdktf diff' first.",
+            High,
+            "cdktf destroy runs terraform destroy for the selected stacks:\n\n\
+             - Every resource in those stacks' state is destroyed\n\
+             - Cloud resources (VMs, databases, networks) deleted\n\
+             - Cannot be undone without backups/recreation\n\n\
+             List stacks first: cdktf list\n\
+             Preview: cdktf diff <stack>"
+        ),
+        // deploy --auto-approve (skips confirmation)
+        destructive_pattern!(
+            "deploy-auto-approve",
+            r"\bcdktf\s+deploy\b[^;&|\n]*\s--auto-approve\b",
+            "cdktf deploy --auto-approve applies changes without confirmation. Review with 'cdktf diff' first.",
+            High,
+            "cdktf deploy --auto-approve skips the plan review:\n\n\
+             - Replacements and deletions in the plan are applied immediately\n\
+             - Intended for CI/CD, not interactive use\n\
+             - Replacements can cause downtime or data loss\n\n\
+             Preview first: cdktf diff <stack>"
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::Severity;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_pack_creation() {
+        let pack = create_pack();
+        assert_eq!(pack.id, "infrastructure.cdktf");
+        assert!(pack.keywords.contains(&"cdktf"));
+
+        assert_patterns_compile(&pack);
+        assert_all_patterns_have_reasons(&pack);
+        assert_unique_pattern_names(&pack);
+    }
+
+    #[test]
+    fn allows_read_only_commands() {
+        let pack = create_pack();
+        assert_safe_pattern_matches(&pack, "cdktf synth");
+        assert_safe_pattern_matches(&pack, "npx cdktf diff my-stack");
+        assert_safe_pattern_matches(&pack, "cdktf list");
+        assert_safe_pattern_matches(&pack, "cdktf get");
+        assert_no_safe_match(&pack, "cdktf diff && cdktf destroy --auto-approve");
+        assert_allows(&pack, "cdktf deploy my-stack");
+    }
+
+    #[test]
+    fn blocks_destroy_and_unattended_deploy() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "cdktf destroy my-stack", "destroy");
+        assert_blocks_with_pattern(
+            &pack,
+            "cdktf destroy --auto-approve",
+            "destroy-auto-approve",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "npx cdktf destroy '*' --auto-approve",
+            "destroy-auto-approve",
+        );
+        assert_blocks_with_severity(&pack, "cdktf destroy --auto-approve", Severity::Critical);
+        assert_blocks_with_pattern(
+            &pack,
+            "cdktf deploy my-stack --auto-approve",
+            "deploy-auto-approve",
+        );
+    }
+}
@@ -4,7 +4,9 @@
 //! - `Terraform` (`terraform destroy`, `terraform taint`)
 //! - `Ansible` (with dangerous flags)
 //! - `Pulumi` (`pulumi destroy`)
+//! - `CDK for Terraform` (`cdktf destroy`)
 
 pub mod ansible;
+pub mod cdktf;
 pub mod pulumi;
 pub mod terraform;
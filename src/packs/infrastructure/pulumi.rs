@@ -1,9 +1,12 @@
 //! Pulumi patterns - protections against destructive pulumi commands.
 //!
 //! This includes patterns for:
-//! - pulumi destroy
+//! - pulumi destroy (and destroy --yes)
 //! - pulumi up with -y (auto-approve)
-//! - pulumi state delete
+//! - pulumi state delete (and --force on protected resources)
+//! - pulumi stack rm (and --force with resources still under management)
+//! - pulumi cancel
+//! - pulumi stack change-secrets-provider
 
 use crate::packs::{DestructivePattern, Pack, SafePattern};
 use crate::{destructive_pattern, safe_pattern};
@@ -14,14 +17,17 @@ pub fn create_pack() -> Pack {
     Pack {
         id: "infrastructure.pulumi".to_string(),
         name: "Pulumi",
-        description: "Protects against destructive Pulumi operations like destroy \
-                      and up with -y (auto-approve)",
+        description: "Protects against destructive Pulumi operations like destroy --yes, \
+                      up with -y (auto-approve), stack rm --force, state delete, cancel, and \
+                      secrets provider changes",
         keywords: &["pulumi", "destroy", "state"],
         safe_patterns: create_safe_patterns(),
         destructive_patterns: create_destructive_patterns(),
         keyword_matcher: None,
         safe_regex_set: None,
         safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
     }
 }
 
@@ -46,8 +52,22 @@ fn create_safe_patterns() -> Vec<SafePattern> {
     ]
 }
 
+#[allow(clippy::too_many_lines)]
 fn create_destructive_patterns() -> Vec<DestructivePattern> {
     vec![
+        // destroy --yes / -y (no confirmation at all)
+        destructive_pattern!(
+            "destroy-yes",
+            r"pulumi\s+destroy\b[^;&|\n]*\s(?:--yes\b|-[a-zA-Z]*y[a-zA-Z]*\b)",
+            "pulumi destroy --yes removes ALL managed infrastructure without confirmation.",
+            Critical,
+            "pulumi destroy --yes skips the only confirmation step:\n\n\
+             - Every resource in the stack is destroyed immediately\n\
+             - No chance to review the destroy preview\n\
+             - Protected resources are the only ones kept\n\n\
+             Preview first: pulumi preview --diff\n\
+             Then run pulumi destroy without --yes and review the prompt"
+        ),
         // destroy
         destructive_pattern!(
             "destroy",
@@ -74,6 +94,19 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              - Replacements can cause downtime\n\n\
              For safety: remove -y and review the preview"
         ),
+        // state delete --force removes protected resources too
+        destructive_pattern!(
+            "state-delete-force",
+            r"pulumi\s+state\s+delete\b[^;&|\n]*\s(?:--force|-f)\b",
+            "pulumi state delete --force removes a resource from state even when it is protected.",
+            High,
+            "pulumi state delete --force ignores the protect flag:\n\n\
+             - Protected resources exist precisely so they are not dropped by accident\n\
+             - The cloud resource keeps running but Pulumi stops managing it\n\
+             - --target-dependents drops everything that depends on it as well\n\n\
+             Export the state first: pulumi stack export --file backup.json\n\
+             Unprotect deliberately if intended: pulumi state unprotect <urn>"
+        ),
         // state delete
         destructive_pattern!(
             "state-delete",
@@ -87,6 +120,19 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              - May cause drift between state and reality\n\n\
              Consider: pulumi refresh to sync state with reality"
         ),
+        // stack rm --force drops the stack even while it still manages resources
+        destructive_pattern!(
+            "stack-rm-force",
+            r"pulumi\s+stack\s+rm\b[^;&|\n]*\s(?:--force\b|-[a-zA-Z]*f[a-zA-Z]*\b)",
+            "pulumi stack rm --force deletes the stack and its state while resources still exist.",
+            Critical,
+            "pulumi stack rm --force removes a stack that still has resources:\n\n\
+             - The stack's state, config, and history are deleted\n\
+             - Every cloud resource it managed keeps running, now orphaned\n\
+             - Nothing in Pulumi can destroy or update them afterwards\n\n\
+             Export the state first: pulumi stack export --file backup.json\n\
+             Destroy resources, then remove the empty stack: pulumi destroy && pulumi stack rm"
+        ),
         // stack rm (remove stack)
         destructive_pattern!(
             "stack-rm",
@@ -125,5 +171,87 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              - May require manual cleanup\n\n\
              Use only when operation is stuck/hung"
         ),
+        // change-secrets-provider re-encrypts every secret in the stack config
+        destructive_pattern!(
+            "change-secrets-provider",
+            r"pulumi\s+stack\s+change-secrets-provider\b",
+            "pulumi stack change-secrets-provider re-encrypts the stack's secrets with a new provider.",
+            High,
+            "pulumi stack change-secrets-provider rewrites every secret in the stack:\n\n\
+             - Config and state secrets are re-encrypted with the new key\n\
+             - Anyone without access to the new provider can no longer deploy\n\
+             - Losing the new key (or passphrase) makes the secrets unrecoverable\n\n\
+             Export the state first: pulumi stack export --show-secrets --file backup.json\n\
+             Confirm the new provider is reachable by everyone who deploys the stack"
+        ),
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::Severity;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_pack_creation() {
+        let pack = create_pack();
+        assert_eq!(pack.id, "infrastructure.pulumi");
+        assert!(pack.keywords.contains(&"pulumi"));
+
+        assert_patterns_compile(&pack);
+        assert_all_patterns_have_reasons(&pack);
+        assert_unique_pattern_names(&pack);
+    }
+
+    #[test]
+    fn blocks_destroy_variants() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "pulumi destroy", "destroy");
+        assert_blocks_with_pattern(&pack, "pulumi destroy --yes", "destroy-yes");
+        assert_blocks_with_pattern(&pack, "pulumi destroy -s prod -y", "destroy-yes");
+        assert_blocks_with_pattern(&pack, "pulumi destroy -yf", "destroy-yes");
+        assert_blocks_with_pattern(&pack, "pulumi destroy --stack my-y", "destroy");
+        assert_blocks_with_severity(&pack, "pulumi destroy --yes", Severity::Critical);
+    }
+
+    #[test]
+    fn blocks_stack_and_state_removal() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "pulumi stack rm dev", "stack-rm");
+        assert_blocks_with_pattern(&pack, "pulumi stack rm dev --force", "stack-rm-force");
+        assert_blocks_with_pattern(&pack, "pulumi stack rm -fy dev", "stack-rm-force");
+        assert_blocks_with_severity(&pack, "pulumi stack rm --force", Severity::Critical);
+        assert_blocks_with_pattern(&pack, "pulumi state delete urn:pulumi:x", "state-delete");
+        assert_blocks_with_pattern(
+            &pack,
+            "pulumi state delete urn:pulumi:x --force",
+            "state-delete-force",
+        );
+        assert_blocks_with_pattern(&pack, "pulumi cancel --yes", "cancel");
+    }
+
+    #[test]
+    fn blocks_secrets_provider_change() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "pulumi stack change-secrets-provider awskms://alias/new",
+            "change-secrets-provider",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "pulumi stack change-secrets-provider passphrase",
+            "change-secrets-provider",
+        );
+    }
+
+    #[test]
+    fn allows_read_only_commands() {
+        let pack = create_pack();
+        assert_allows(&pack, "pulumi preview --diff");
+        assert_allows(&pack, "pulumi stack ls");
+        assert_allows(&pack, "pulumi stack export --file backup.json");
+        assert_allows(&pack, "pulumi up");
+    }
+}
@@ -1,10 +1,11 @@
 //! Terraform patterns - protections against destructive terraform commands.
 //!
 //! This includes patterns for:
-//! - terraform destroy
+//! - terraform destroy (and apply -destroy)
 //! - terraform taint
 //! - terraform apply with -auto-approve
-//! - terraform state rm
+//! - terraform state rm/mv/push, force-unlock, and workspace delete
+//! - deleting or overwriting `.tfstate` files directly
 
 use crate::packs::{DestructivePattern, Pack, SafePattern};
 use crate::{destructive_pattern, safe_pattern};
@@ -16,13 +17,15 @@ pub fn create_pack() -> Pack {
         id: "infrastructure.terraform".to_string(),
         name: "Terraform",
         description: "Protects against destructive Terraform operations like destroy, \
-                      taint, and apply with -auto-approve",
-        keywords: &["terraform", "destroy", "taint", "state"],
+                      taint, apply with -auto-approve, state surgery, and deleting state files",
+        keywords: &["terraform", "destroy", "taint", "state", "tfstate"],
         safe_patterns: create_safe_patterns(),
         destructive_patterns: create_destructive_patterns(),
         keyword_matcher: None,
         safe_regex_set: None,
         safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
     }
 }
 
@@ -49,11 +52,35 @@ fn create_safe_patterns() -> Vec<SafePattern> {
         safe_pattern!("terraform-version", r"terraform\s+version"),
         // providers is safe
         safe_pattern!("terraform-providers", r"terraform\s+providers"),
+        // A targeted plan is a scoped dry run, even with -destroy. Anchored to the
+        // whole command so a plan cannot whitelist a chained destroy.
+        safe_pattern!(
+            "terraform-plan-target",
+            r"^\s*terraform\s+plan\b[^;&|\n$`]*\s-target[=\s][^;&|\n$`]*$"
+        ),
+        // state rm/mv -dry-run only prints what would change
+        safe_pattern!(
+            "terraform-state-dry-run",
+            r"^\s*terraform\s+state\s+(?:rm|mv)\b[^;&|\n$`]*\s-dry-run\b[^;&|\n$`]*$"
+        ),
     ]
 }
 
 fn create_destructive_patterns() -> Vec<DestructivePattern> {
     vec![
+        // destroy -auto-approve (no confirmation at all)
+        destructive_pattern!(
+            "destroy-auto-approve",
+            r"terraform\s+destroy\b[^;&|\n]*\s-auto-approve\b",
+            "terraform destroy -auto-approve removes ALL managed infrastructure without confirmation.",
+            Critical,
+            "terraform destroy -auto-approve skips the only confirmation step:\n\n\
+             - Every resource in the state is destroyed immediately\n\
+             - No chance to review the destroy plan\n\
+             - Cannot be undone without backups/recreation\n\n\
+             Preview first: terraform plan -destroy\n\
+             Then run terraform destroy without -auto-approve and review the prompt"
+        ),
         // destroy
         destructive_pattern!(
             "destroy",
@@ -79,6 +106,19 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              - Review output carefully before proceeding\n\n\
              This is actually the safe way to preview destroy."
         ),
+        // apply -destroy is an alias for destroy
+        destructive_pattern!(
+            "apply-destroy",
+            r"terraform\s+apply\b[^;&|\n]*\s-destroy\b",
+            "terraform apply -destroy removes ALL managed infrastructure, same as terraform destroy.",
+            Critical,
+            "terraform apply -destroy is equivalent to terraform destroy:\n\n\
+             - Every resource in your state file is destroyed\n\
+             - With -auto-approve there is no confirmation prompt\n\
+             - Cannot be undone without backups/recreation\n\n\
+             Preview first: terraform plan -destroy\n\
+             Scope it: terraform plan -destroy -target=ADDRESS"
+        ),
         // apply with -auto-approve (skips confirmation)
         destructive_pattern!(
             "apply-auto-approve",
@@ -130,6 +170,18 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              - Does not affect actual cloud resources\n\n\
              Preview first: terraform state mv -dry-run SOURCE DEST"
         ),
+        // state push overwrites the remote state
+        destructive_pattern!(
+            "state-push",
+            r"terraform\s+state\s+push\b",
+            "terraform state push overwrites the remote state with a local file.",
+            High,
+            "terraform state push replaces the remote state:\n\n\
+             - The uploaded file becomes the source of truth\n\
+             - A stale or wrong file orphans or re-creates resources\n\
+             - -force skips the lineage and serial safety checks\n\n\
+             Back up first: terraform state pull > backup.tfstate"
+        ),
         // force-unlock
         destructive_pattern!(
             "force-unlock",
@@ -143,6 +195,18 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              - Lock ID required to prevent accidents\n\n\
              Verify no other operations: check CI/CD pipelines, other users"
         ),
+        // workspace delete -force drops a workspace that still tracks resources
+        destructive_pattern!(
+            "workspace-delete-force",
+            r"terraform\s+workspace\s+delete\b[^;&|\n]*\s-force\b",
+            "terraform workspace delete -force deletes a workspace even if it still manages resources.",
+            High,
+            "terraform workspace delete -force skips the empty-state check:\n\n\
+             - Workspace state is deleted while resources still exist\n\
+             - Those resources become unmanaged (orphaned)\n\
+             - Cannot be undone without a state backup\n\n\
+             Check first: terraform workspace select NAME && terraform state list"
+        ),
         // workspace delete
         destructive_pattern!(
             "workspace-delete",
@@ -156,5 +220,123 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              - Cannot be undone without state backup\n\n\
              Destroy resources first: terraform destroy, then delete workspace"
         ),
+        // Deleting state files directly
+        destructive_pattern!(
+            "tfstate-delete",
+            r"\b(?:rm|unlink|shred|truncate)\b[^;&|\n]*\.tfstate(?:\.backup)?(?:$|[\s;&|)'\x22])",
+            "Deleting a .tfstate file makes Terraform forget every resource it manages.",
+            High,
+            "Deleting Terraform state files orphans infrastructure:\n\n\
+             - Terraform no longer knows which resources it created\n\
+             - The next apply tries to create everything again\n\
+             - Existing resources must be re-imported by hand\n\n\
+             Back up first: terraform state pull > backup.tfstate\n\
+             Remove single resources with: terraform state rm ADDRESS"
+        ),
+        // Overwriting state files with a redirect
+        destructive_pattern!(
+            "tfstate-overwrite",
+            r">\s*[\w./~-]*\.tfstate(?:$|[\s;&|)'\x22])",
+            "Redirecting output into a .tfstate file overwrites Terraform state.",
+            High,
+            "Writing into a .tfstate file with a shell redirect replaces the state:\n\n\
+             - Hand-edited or truncated state corrupts Terraform's view of resources\n\
+             - Serial and lineage checks are bypassed entirely\n\n\
+             Use terraform state push (after review) or terraform import instead.\n\
+             Write backups to a different name: terraform state pull > backup.tfstate"
+        ),
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::Severity;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_pack_creation() {
+        let pack = create_pack();
+        assert_eq!(pack.id, "infrastructure.terraform");
+        assert!(pack.keywords.contains(&"tfstate"));
+
+        assert_patterns_compile(&pack);
+        assert_all_patterns_have_reasons(&pack);
+        assert_unique_pattern_names(&pack);
+    }
+
+    #[test]
+    fn allows_dry_runs() {
+        let pack = create_pack();
+        assert_safe_pattern_matches(&pack, "terraform plan -out=tfplan");
+        assert_safe_pattern_matches(&pack, "terraform plan -destroy -target=aws_instance.web");
+        assert_safe_pattern_matches(&pack, "terraform plan -destroy -target aws_instance.web");
+        assert_safe_pattern_matches(&pack, "terraform state rm -dry-run aws_instance.web");
+        assert_safe_pattern_matches(&pack, "terraform state mv -dry-run a.b c.d");
+        assert_allows(&pack, "terraform state pull > backup.tfstate.json");
+        assert_allows(&pack, "cat terraform.tfstate");
+    }
+
+    #[test]
+    fn dry_run_safe_patterns_do_not_cover_chained_commands() {
+        let pack = create_pack();
+        assert_no_safe_match(
+            &pack,
+            "terraform plan -destroy -target=x && terraform apply -destroy -auto-approve",
+        );
+        assert_no_safe_match(&pack, "terraform state rm -dry-run a; terraform state rm a");
+    }
+
+    #[test]
+    fn blocks_destroy_variants() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "terraform destroy", "destroy");
+        assert_blocks_with_pattern(
+            &pack,
+            "terraform destroy -auto-approve",
+            "destroy-auto-approve",
+        );
+        assert_blocks_with_pattern(&pack, "terraform apply -destroy", "apply-destroy");
+        assert_blocks_with_pattern(
+            &pack,
+            "terraform apply -auto-approve -destroy",
+            "apply-destroy",
+        );
+        assert_blocks_with_severity(&pack, "terraform apply -destroy", Severity::Critical);
+    }
+
+    #[test]
+    fn blocks_state_surgery() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "terraform state rm aws_s3_bucket.logs", "state-rm");
+        assert_blocks_with_pattern(
+            &pack,
+            "terraform state push -force old.tfstate",
+            "state-push",
+        );
+        assert_blocks_with_pattern(&pack, "terraform force-unlock 1234-abcd", "force-unlock");
+        assert_blocks_with_pattern(
+            &pack,
+            "terraform workspace delete -force staging",
+            "workspace-delete-force",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "terraform workspace delete staging",
+            "workspace-delete",
+        );
+    }
+
+    #[test]
+    fn blocks_direct_state_file_edits() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "rm terraform.tfstate", "tfstate-delete");
+        assert_blocks_with_pattern(
+            &pack,
+            "rm -f envs/prod/terraform.tfstate.backup",
+            "tfstate-delete",
+        );
+        assert_blocks_with_pattern(&pack, "truncate -s 0 prod.tfstate", "tfstate-delete");
+        assert_blocks_with_pattern(&pack, "echo '{}' > terraform.tfstate", "tfstate-overwrite");
+    }
+}
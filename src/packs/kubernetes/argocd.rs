@@ -0,0 +1,199 @@
+//! Argo CD patterns - protections against destructive argocd commands.
+//!
+//! Argo CD owns everything its applications have synced, so deleting an
+//! application (cascading by default) deletes the workloads with it. This
+//! includes patterns for:
+//! - app delete (cascading and non-cascading)
+//! - app sync with --prune, --force or --replace
+//! - proj delete
+//! - repo rm and cluster rm
+
+use crate::packs::{DestructivePattern, Pack, SafePattern};
+use crate::{destructive_pattern, safe_pattern};
+
+/// Create the Argo CD pack.
+#[must_use]
+pub fn create_pack() -> Pack {
+    Pack {
+        id: "kubernetes.argocd".to_string(),
+        name: "Argo CD",
+        description: "Protects against destructive Argo CD operations like cascading \
+                      app delete, proj delete, and repo rm",
+        keywords: &["argocd"],
+        safe_patterns: create_safe_patterns(),
+        destructive_patterns: create_destructive_patterns(),
+        keyword_matcher: None,
+        safe_regex_set: None,
+        safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
+    }
+}
+
+fn create_safe_patterns() -> Vec<SafePattern> {
+    // Anchored to the whole command so a read-only call cannot whitelist a
+    // chained `argocd app delete`.
+    vec![
+        // get/list/diff/history/manifests/resources/logs only read app state
+        safe_pattern!(
+            "argocd-app-read-only",
+            r"^\s*argocd\s+app\s+(?:get|list|diff|history|manifests|resources|logs|wait)\b[^;&|\n$`]*$"
+        ),
+        // sync --dry-run renders the sync without applying it
+        safe_pattern!(
+            "argocd-app-sync-dry-run",
+            r"^\s*argocd\s+app\s+sync\b[^;&|\n$`]*\s--dry-run\b[^;&|\n$`]*$"
+        ),
+        // proj/repo/cluster get and list
+        safe_pattern!(
+            "argocd-list-get",
+            r"^\s*argocd\s+(?:proj|repo|cluster|account|cert|gpg)\s+(?:get|list)\b[^;&|\n$`]*$"
+        ),
+        safe_pattern!(
+            "argocd-version",
+            r"^\s*argocd\s+(?:version|context)\b[^;&|\n$`]*$"
+        ),
+    ]
+}
+
+fn create_destructive_patterns() -> Vec<DestructivePattern> {
+    vec![
+        // app delete with cascade (the default) before the non-cascading form
+        destructive_pattern!(
+            "app-delete-cascade",
+            r"\bargocd\s+app\s+delete\b(?![^;&|\n]*--cascade(?:=|\s+)false\b)",
+            "argocd app delete cascades by default and deletes every resource the application manages.",
+            Critical,
+            "argocd app delete removes the Application and, because --cascade defaults to \
+             true, every Kubernetes resource it synced:\n\n\
+             - Deployments, services, config maps and secrets are deleted\n\
+             - PersistentVolumeClaims (and their data) may be deleted with them\n\
+             - --selector/-l or several app names extend this to many applications\n\n\
+             Review first: argocd app resources <app>\n\
+             To stop managing the app but keep the workloads: \
+             argocd app delete <app> --cascade=false"
+        ),
+        // app delete --cascade=false
+        destructive_pattern!(
+            "app-delete",
+            r"\bargocd\s+app\s+delete\b",
+            "argocd app delete --cascade=false removes the Application; its resources are left unmanaged.",
+            High,
+            "argocd app delete --cascade=false deletes the Application object only:\n\n\
+             - Resources keep running but are no longer reconciled from git\n\
+             - Drift and manual changes are no longer detected or reverted\n\
+             - Recreating the app may require adopting the existing resources\n\n\
+             Review first: argocd app get <app>"
+        ),
+        // app sync --prune/--force/--replace
+        destructive_pattern!(
+            "app-sync-prune",
+            r"\bargocd\s+app\s+sync\b(?![^;&|\n]*--dry-run\b)[^;&|\n]*\s--(?:prune|force|replace)\b",
+            "argocd app sync --prune/--force/--replace deletes or recreates live resources. Use --dry-run first.",
+            High,
+            "argocd app sync with these flags goes beyond applying changes:\n\n\
+             - --prune deletes live resources that are no longer in git\n\
+             - --force and --replace delete and recreate resources (downtime)\n\
+             - A bad commit or wrong revision prunes healthy workloads\n\n\
+             Preview first: argocd app diff <app>\n\
+             Or: argocd app sync <app> --prune --dry-run"
+        ),
+        // proj delete
+        destructive_pattern!(
+            "proj-delete",
+            r"\bargocd\s+proj\s+delete\b",
+            "argocd proj delete removes the project and the access rules its applications depend on.",
+            High,
+            "argocd proj delete removes an AppProject:\n\n\
+             - Source repos, destinations and RBAC roles defined on it are lost\n\
+             - Applications in the project can no longer sync\n\
+             - Project tokens stop working\n\n\
+             Review first: argocd proj get <project>\n\
+             List its applications: argocd app list --project <project>"
+        ),
+        // repo rm
+        destructive_pattern!(
+            "repo-rm",
+            r"\bargocd\s+repo\s+(?:rm|remove)\b",
+            "argocd repo rm removes the repository and its credentials; applications using it stop syncing.",
+            High,
+            "argocd repo rm deletes a repository connection:\n\n\
+             - Stored credentials (SSH keys, tokens) are deleted\n\
+             - Applications sourcing from it fail to sync\n\
+             - Re-adding it requires the original credentials\n\n\
+             Review first: argocd repo list"
+        ),
+        // cluster rm
+        destructive_pattern!(
+            "cluster-rm",
+            r"\bargocd\s+cluster\s+(?:rm|remove)\b",
+            "argocd cluster rm removes the cluster; applications deployed to it stop syncing.",
+            High,
+            "argocd cluster rm deletes a destination cluster:\n\n\
+             - Its credentials are deleted from Argo CD\n\
+             - Applications targeting it become Unknown and stop syncing\n\n\
+             Review first: argocd app list --dest-server <server>"
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::Severity;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_pack_creation() {
+        let pack = create_pack();
+        assert_eq!(pack.id, "kubernetes.argocd");
+        assert!(pack.keywords.contains(&"argocd"));
+
+        assert_patterns_compile(&pack);
+        assert_all_patterns_have_reasons(&pack);
+        assert_unique_pattern_names(&pack);
+    }
+
+    #[test]
+    fn allows_read_only_commands() {
+        let pack = create_pack();
+        assert_safe_pattern_matches(&pack, "argocd app get guestbook");
+        assert_safe_pattern_matches(&pack, "argocd app diff guestbook --revision main");
+        assert_safe_pattern_matches(&pack, "argocd app sync guestbook --prune --dry-run");
+        assert_safe_pattern_matches(&pack, "argocd proj list");
+        assert_no_safe_match(
+            &pack,
+            "argocd app get guestbook && argocd app delete guestbook",
+        );
+        assert_allows(&pack, "argocd app sync guestbook");
+    }
+
+    #[test]
+    fn blocks_destructive_commands() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "argocd app delete guestbook", "app-delete-cascade");
+        assert_blocks_with_pattern(
+            &pack,
+            "argocd app delete guestbook --cascade -y",
+            "app-delete-cascade",
+        );
+        assert_blocks_with_severity(&pack, "argocd app delete guestbook", Severity::Critical);
+        assert_blocks_with_pattern(
+            &pack,
+            "argocd app delete guestbook --cascade=false",
+            "app-delete",
+        );
+        assert_blocks_with_pattern(&pack, "argocd app sync guestbook --prune", "app-sync-prune");
+        assert_blocks_with_pattern(&pack, "argocd proj delete team-a", "proj-delete");
+        assert_blocks_with_pattern(
+            &pack,
+            "argocd repo rm https://github.com/org/deploy.git",
+            "repo-rm",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "argocd cluster rm https://prod.example.com",
+            "cluster-rm",
+        );
+    }
+}
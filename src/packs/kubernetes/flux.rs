@@ -0,0 +1,165 @@
+//! Flux patterns - protections against destructive flux commands.
+//!
+//! Flux garbage-collects what a Kustomization or HelmRelease applied when the
+//! object is deleted with pruning enabled, so a single `flux delete` can
+//! remove a whole environment. This includes patterns for:
+//! - flux uninstall
+//! - flux delete (and --silent, which skips the confirmation prompt)
+//! - flux suspend on production sources or --all
+
+use crate::packs::{DestructivePattern, Pack, SafePattern};
+use crate::{destructive_pattern, safe_pattern};
+
+/// Create the Flux pack.
+#[must_use]
+pub fn create_pack() -> Pack {
+    Pack {
+        id: "kubernetes.flux".to_string(),
+        name: "Flux",
+        description: "Protects against destructive Flux operations like flux uninstall, \
+                      flux delete --silent, and suspending production sources",
+        keywords: &["flux"],
+        safe_patterns: create_safe_patterns(),
+        destructive_patterns: create_destructive_patterns(),
+        keyword_matcher: None,
+        safe_regex_set: None,
+        safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
+    }
+}
+
+fn create_safe_patterns() -> Vec<SafePattern> {
+    // Anchored to the whole command so a read-only call cannot whitelist a
+    // chained `flux uninstall`.
+    vec![
+        // get/logs/events/tree/trace/stats only read cluster state
+        safe_pattern!(
+            "flux-read-only",
+            r"^\s*flux\s+(?:get|logs|events|tree|trace|stats|version|check)\b[^;&|\n$`]*$"
+        ),
+        // diff/build render manifests locally and compare them
+        safe_pattern!("flux-diff", r"^\s*flux\s+(?:diff|build)\b[^;&|\n$`]*$"),
+        // export prints resources as YAML
+        safe_pattern!("flux-export", r"^\s*flux\s+export\b[^;&|\n$`]*$"),
+        // uninstall --dry-run only lists what would be removed
+        safe_pattern!(
+            "flux-uninstall-dry-run",
+            r"^\s*flux\s+uninstall\b[^;&|\n$`]*\s--dry-run\b[^;&|\n$`]*$"
+        ),
+    ]
+}
+
+fn create_destructive_patterns() -> Vec<DestructivePattern> {
+    vec![
+        // uninstall
+        destructive_pattern!(
+            "uninstall",
+            r"\bflux\s+uninstall\b(?![^;&|\n]*--dry-run\b)",
+            "flux uninstall removes the Flux controllers and CRDs; the cluster stops reconciling from git.",
+            High,
+            "flux uninstall removes Flux from the cluster:\n\n\
+             - Controllers, CRDs and every Flux object (sources, Kustomizations, \
+             HelmReleases) are deleted\n\
+             - The flux-system namespace is deleted unless --keep-namespace is set\n\
+             - Workloads keep running but are no longer reconciled; drift goes unnoticed\n\
+             - Re-bootstrapping is required to restore GitOps\n\n\
+             Preview first: flux uninstall --dry-run"
+        ),
+        // delete --silent before plain delete
+        destructive_pattern!(
+            "delete-silent",
+            r"\bflux\s+delete\b[^;&|\n]*\s(?:--silent|-s)\b",
+            "flux delete --silent deletes without confirmation; pruned Kustomizations and HelmReleases remove everything they applied.",
+            Critical,
+            "flux delete --silent skips the confirmation prompt:\n\n\
+             - Deleting a Kustomization with prune: true deletes every resource it applied\n\
+             - Deleting a HelmRelease uninstalls the release\n\
+             - Deleting a source stalls every object that reconciles from it\n\n\
+             Review first: flux tree kustomization <name>\n\
+             Or suspend instead: flux suspend kustomization <name>"
+        ),
+        // delete
+        destructive_pattern!(
+            "delete",
+            r"\bflux\s+delete\b",
+            "flux delete removes a Flux object; pruned Kustomizations and HelmReleases take their workloads with them.",
+            High,
+            "flux delete removes a source, Kustomization, HelmRelease or other Flux object:\n\n\
+             - Kustomizations with prune: true garbage-collect everything they applied\n\
+             - HelmReleases are uninstalled\n\
+             - Objects depending on a deleted source stop reconciling\n\n\
+             Review first: flux tree kustomization <name>"
+        ),
+        // suspend on prod sources or everything
+        destructive_pattern!(
+            "suspend-production",
+            r"\bflux\s+suspend\b[^;&|\n]*(?:\bprod(?:uction)?\b|\s--all\b)",
+            "flux suspend on production or --all stops reconciliation; fixes and rollbacks in git stop reaching the cluster.",
+            High,
+            "flux suspend pauses reconciliation of the selected objects:\n\n\
+             - Commits (including fixes and rollbacks) are no longer applied\n\
+             - Drift and manual changes are no longer reverted\n\
+             - Suspended objects are easy to forget; nothing alerts on them by default\n\n\
+             Check what is suspended: flux get all -A --status-selector ready=false\n\
+             Resume with: flux resume <kind> <name>"
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::Severity;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_pack_creation() {
+        let pack = create_pack();
+        assert_eq!(pack.id, "kubernetes.flux");
+        assert!(pack.keywords.contains(&"flux"));
+
+        assert_patterns_compile(&pack);
+        assert_all_patterns_have_reasons(&pack);
+        assert_unique_pattern_names(&pack);
+    }
+
+    #[test]
+    fn allows_read_only_commands() {
+        let pack = create_pack();
+        assert_safe_pattern_matches(&pack, "flux get kustomizations -A");
+        assert_safe_pattern_matches(&pack, "flux diff kustomization apps --path ./clusters/prod");
+        assert_safe_pattern_matches(&pack, "flux uninstall --dry-run");
+        assert_no_safe_match(&pack, "flux get sources git; flux uninstall --silent");
+        assert_allows(&pack, "flux reconcile kustomization apps --with-source");
+        assert_allows(&pack, "flux suspend kustomization apps-staging");
+    }
+
+    #[test]
+    fn blocks_destructive_commands() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "flux uninstall --silent", "uninstall");
+        assert_blocks_with_pattern(
+            &pack,
+            "flux delete kustomization apps --silent",
+            "delete-silent",
+        );
+        assert_blocks_with_severity(&pack, "flux delete helmrelease api -s", Severity::Critical);
+        assert_blocks_with_pattern(&pack, "flux delete source git infra", "delete");
+        assert_blocks_with_pattern(
+            &pack,
+            "flux suspend kustomization apps-production",
+            "suspend-production",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "flux suspend source git flux-system -n prod",
+            "suspend-production",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "flux suspend kustomization --all",
+            "suspend-production",
+        );
+    }
+}
@@ -1,10 +1,16 @@
 //! kubectl patterns - protections against destructive kubectl commands.
 //!
 //! This includes patterns for:
-//! - delete namespace/all resources
-//! - drain nodes
+//! - delete namespace/all resources (including `--all --all-namespaces`)
+//! - delete persistent volumes, claims, and CRDs
+//! - drain nodes (including `--delete-emptydir-data --force`)
 //! - cordon nodes
+//! - replace/apply --force
 //! - delete without dry-run
+//!
+//! Flags such as `-n <ns>` may appear between `delete` and the resource type.
+//! `--dry-run=client` and `--dry-run=server` are allowed; `--dry-run=none`
+//! executes for real and is not treated as safe.
 
 use crate::packs::{DestructivePattern, Pack, PatternSuggestion, SafePattern};
 use crate::{destructive_pattern, safe_pattern};
@@ -127,6 +133,8 @@ pub fn create_pack() -> Pack {
         keyword_matcher: None,
         safe_regex_set: None,
         safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
     }
 }
 
@@ -136,10 +144,11 @@ fn create_safe_patterns() -> Vec<SafePattern> {
         safe_pattern!("kubectl-get", r"kubectl\s+get"),
         safe_pattern!("kubectl-describe", r"kubectl\s+describe"),
         safe_pattern!("kubectl-logs", r"kubectl\s+logs"),
-        // dry-run is safe
+        // dry-run is safe (`--dry-run=none` is a real run, and the flag must
+        // belong to this kubectl invocation)
         safe_pattern!(
             "kubectl-dry-run",
-            r"kubectl\s+.*--dry-run(?:=(?:client|server|none))?"
+            r"kubectl\s+[^;&|]*--dry-run(?:=(?:client|server))?(?:\s|$)"
         ),
         // diff is safe (shows what would change)
         safe_pattern!("kubectl-diff", r"kubectl\s+diff"),
@@ -162,7 +171,7 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
         // delete namespace
         destructive_pattern!(
             "delete-namespace",
-            r"kubectl\s+delete\s+(?:namespace|ns)\b",
+            r"kubectl\s+delete\s+(?:(?:-n|--namespace|--context|--cluster|--kubeconfig)\s+\S+\s+|(?!(?:-n|--namespace|--context|--cluster|--kubeconfig)\s)--?[\w-]+(?:=\S+)?\s+)*(?:namespaces?|ns)\b",
             "kubectl delete namespace removes the entire namespace and ALL resources within it.",
             Critical,
             "Deleting a namespace destroys EVERYTHING inside it:\n\n\
@@ -179,10 +188,28 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              kubectl delete deployment <name> -n <namespace>  # Delete specific resources",
             DELETE_NAMESPACE_SUGGESTIONS
         ),
+        // delete all in every namespace
+        destructive_pattern!(
+            "delete-all-all-namespaces",
+            r"kubectl\s+delete\s+(?=[^;&|]*--all(?:=true)?(?:\s|$))(?=[^;&|]*(?:-A\b|--all-namespaces\b))",
+            "kubectl delete --all --all-namespaces removes every resource of that type in the entire cluster.",
+            Critical,
+            "Combining --all with -A/--all-namespaces deletes EVERY resource of the given type \
+             in EVERY namespace, including kube-system:\n\n\
+             - kubectl delete pods --all -A: Restarts every workload in the cluster at once\n\
+             - kubectl delete pvc --all -A: May delete all persistent data cluster-wide\n\
+             - kubectl delete deployments --all -A: Removes every application\n\n\
+             There is no undo. Preview the full blast radius first:\n  \
+             kubectl get <resource> -A\n  \
+             kubectl delete <resource> --all -A --dry-run=server\n\n\
+             Safer alternative:\n  \
+             kubectl delete <resource> --all -n <namespace>  # One namespace at a time",
+            DELETE_ALL_SUGGESTIONS
+        ),
         // delete all
         destructive_pattern!(
             "delete-all",
-            r"kubectl\s+delete\s+.*--all\b",
+            r"kubectl\s+delete\s+.*--all(?:=true)?(?:\s|$)",
             "kubectl delete --all removes ALL resources of that type. Use --dry-run=client first.",
             High,
             "The --all flag deletes EVERY resource of the specified type in the namespace.\n\n\
@@ -212,6 +239,22 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              Preview cluster-wide resources:\n  \
              kubectl get <resource> -A"
         ),
+        // drain node, discarding emptyDir data and unmanaged pods
+        destructive_pattern!(
+            "drain-delete-data-force",
+            r"kubectl\s+drain\b(?=[^;&|]*--delete-(?:emptydir|local)-data\b)(?=[^;&|]*--force\b)",
+            "kubectl drain --delete-emptydir-data --force deletes emptyDir data and unmanaged pods permanently.",
+            Critical,
+            "This drain variant goes beyond eviction:\n\n\
+             - --delete-emptydir-data: Data in emptyDir volumes is deleted with the pods\n\
+             - --force: Pods not managed by a controller are deleted and never recreated\n\
+             - Caches, scratch data, and bare pods on the node are lost for good\n\n\
+             Check what would be lost first:\n  \
+             kubectl get pods -A -o wide --field-selector spec.nodeName=<node>\n  \
+             kubectl drain <node> --dry-run=server\n\n\
+             Safer approach:\n  \
+             kubectl cordon <node>  # Stop scheduling, then move workloads deliberately"
+        ),
         // drain node
         destructive_pattern!(
             "drain-node",
@@ -286,7 +329,7 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
         // delete pvc (persistent volume claim)
         destructive_pattern!(
             "delete-pvc",
-            r"kubectl\s+delete\s+(?:pvc|persistentvolumeclaim)\b(?!.*--dry-run)",
+            r"kubectl\s+delete\s+(?:(?:-n|--namespace|--context|--cluster|--kubeconfig)\s+\S+\s+|(?!(?:-n|--namespace|--context|--cluster|--kubeconfig)\s)--?[\w-]+(?:=\S+)?\s+)*(?:pvc|persistentvolumeclaims?)\b(?!.*--dry-run)",
             "kubectl delete pvc may permanently delete data if ReclaimPolicy is Delete.",
             Critical,
             "Deleting a PVC can cause permanent data loss depending on the PV's reclaimPolicy:\n\n\
@@ -304,7 +347,7 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
         // delete pv (persistent volume)
         destructive_pattern!(
             "delete-pv",
-            r"kubectl\s+delete\s+(?:pv|persistentvolume)\b(?!.*--dry-run)",
+            r"kubectl\s+delete\s+(?:(?:-n|--namespace|--context|--cluster|--kubeconfig)\s+\S+\s+|(?!(?:-n|--namespace|--context|--cluster|--kubeconfig)\s)--?[\w-]+(?:=\S+)?\s+)*(?:pv|persistentvolumes?)\b(?!.*--dry-run)",
             "kubectl delete pv may permanently delete the underlying storage.",
             Critical,
             "Deleting a PersistentVolume can permanently destroy the underlying storage:\n\n\
@@ -319,6 +362,23 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              Preview:\n  \
              kubectl delete pv <name> --dry-run=client"
         ),
+        // delete custom resource definition
+        destructive_pattern!(
+            "delete-crd",
+            r"kubectl\s+delete\s+(?:(?:-n|--namespace|--context|--cluster|--kubeconfig)\s+\S+\s+|(?!(?:-n|--namespace|--context|--cluster|--kubeconfig)\s)--?[\w-]+(?:=\S+)?\s+)*(?:crds?|customresourcedefinitions?)\b",
+            "kubectl delete crd removes the CRD and every custom resource of that kind in all namespaces.",
+            Critical,
+            "Deleting a CustomResourceDefinition cascades to ALL of its custom resources \
+             across the whole cluster:\n\n\
+             - Every instance (e.g. all Certificates, all Prometheus rules) is deleted\n\
+             - Operators relying on the CRD stop working or tear down what they manage\n\
+             - Reinstalling the CRD does not bring the old resources back\n\n\
+             See what would be removed:\n  \
+             kubectl get <kind> -A\n  \
+             kubectl get crd <name> -o yaml > crd-backup.yaml\n\n\
+             Back up the resources before deleting:\n  \
+             kubectl get <kind> -A -o yaml > <kind>-backup.yaml"
+        ),
         // scale to 0
         destructive_pattern!(
             "scale-to-zero",
@@ -374,6 +434,23 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              kubectl apply --server-side -f <file>",
             APPLY_FORCE_SUGGESTIONS
         ),
+        // replace --force
+        destructive_pattern!(
+            "replace-force",
+            r"kubectl\s+replace\s+[^;&|]*--force\b",
+            "kubectl replace --force deletes and recreates resources, causing downtime.",
+            High,
+            "kubectl replace --force deletes the live object and creates it again from the \
+             manifest:\n\n\
+             - Pods are terminated before replacements exist\n\
+             - Runtime state and fields not in the manifest are lost\n\
+             - Services may briefly lose their endpoints\n\n\
+             Preview the change first:\n  \
+             kubectl diff -f <file>\n\n\
+             Update in place instead:\n  \
+             kubectl replace -f <file>\n  \
+             kubectl apply --server-side -f <file>"
+        ),
         // delete -f with directory (batch deletion)
         destructive_pattern!(
             "delete-from-directory",
@@ -393,3 +470,83 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
         ),
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::test_helpers::{assert_allows, assert_blocks_with_pattern};
+
+    #[test]
+    fn node_namespace_and_storage_destruction_block() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "kubectl drain node-1 --ignore-daemonsets --delete-emptydir-data --force",
+            "drain-delete-data-force",
+        );
+        assert_blocks_with_pattern(&pack, "kubectl drain node-1", "drain-node");
+        assert_blocks_with_pattern(&pack, "kubectl delete ns staging", "delete-namespace");
+        assert_blocks_with_pattern(
+            &pack,
+            "kubectl delete --wait=false namespaces staging",
+            "delete-namespace",
+        );
+        assert_blocks_with_pattern(&pack, "kubectl delete -n data pvc/db-0", "delete-pvc");
+        assert_blocks_with_pattern(&pack, "kubectl delete pv pv-123", "delete-pv");
+        assert_blocks_with_pattern(
+            &pack,
+            "kubectl delete crd certificates.cert-manager.io",
+            "delete-crd",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "kubectl delete customresourcedefinition widgets.example.com",
+            "delete-crd",
+        );
+    }
+
+    #[test]
+    fn all_namespace_combinations_and_replace_force_block() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "kubectl delete pods --all --all-namespaces",
+            "delete-all-all-namespaces",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "kubectl delete -A deployments --all",
+            "delete-all-all-namespaces",
+        );
+        assert_blocks_with_pattern(&pack, "kubectl delete pods --all", "delete-all");
+        assert_blocks_with_pattern(
+            &pack,
+            "kubectl delete pods --all-namespaces -l app=web",
+            "delete-all-namespaces",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "kubectl replace --force -f deploy.yaml",
+            "replace-force",
+        );
+    }
+
+    #[test]
+    fn dry_run_client_and_server_are_safe_but_none_is_not() {
+        let pack = create_pack();
+        assert_allows(&pack, "kubectl delete ns staging --dry-run=client");
+        assert_allows(
+            &pack,
+            "kubectl delete crd widgets.example.com --dry-run=server",
+        );
+        assert_allows(&pack, "kubectl drain node-1 --force --dry-run=server");
+        assert_allows(&pack, "kubectl delete pods --all -A --dry-run");
+        assert_allows(&pack, "kubectl replace -f deploy.yaml");
+        assert_allows(&pack, "kubectl get crd");
+        assert_blocks_with_pattern(
+            &pack,
+            "kubectl delete ns staging --dry-run=none",
+            "delete-namespace",
+        );
+    }
+}
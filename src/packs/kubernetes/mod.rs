@@ -4,7 +4,11 @@
 //! - kubectl (delete, drain, cordon)
 //! - Helm (uninstall, delete)
 //! - Kustomize (with dangerous flags)
+//! - Argo CD (app delete, proj delete, repo rm)
+//! - Flux (uninstall, delete, suspend)
 
+pub mod argocd;
+pub mod flux;
 pub mod helm;
 pub mod kubectl;
 pub mod kustomize;
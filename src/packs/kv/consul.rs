@@ -0,0 +1,144 @@
+//! Consul patterns - protections against destructive consul commands.
+//!
+//! Consul KV often holds service configuration and feature flags, and the raft
+//! peer set decides whether the cluster has quorum. This includes patterns for:
+//! - kv delete -recurse
+//! - operator raft remove-peer
+//! - snapshot restore
+
+use crate::packs::{DestructivePattern, Pack, SafePattern};
+use crate::{destructive_pattern, safe_pattern};
+
+/// Create the Consul pack.
+#[must_use]
+pub fn create_pack() -> Pack {
+    Pack {
+        id: "kv.consul".to_string(),
+        name: "Consul",
+        description: "Protects against destructive Consul operations like kv delete -recurse, \
+                      operator raft remove-peer, and snapshot restore",
+        keywords: &["consul"],
+        safe_patterns: create_safe_patterns(),
+        destructive_patterns: create_destructive_patterns(),
+        keyword_matcher: None,
+        safe_regex_set: None,
+        safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
+    }
+}
+
+fn create_safe_patterns() -> Vec<SafePattern> {
+    // Anchored to the whole command so a read-only call cannot whitelist a
+    // chained `consul kv delete`.
+    vec![
+        // kv get/export read keys
+        safe_pattern!(
+            "consul-kv-read",
+            r"^\s*consul\s+kv\s+(?:get|export)\b[^;&|\n$`]*$"
+        ),
+        // members/catalog/info/version/monitor report cluster state
+        safe_pattern!(
+            "consul-status",
+            r"^\s*consul\s+(?:members|catalog|info|version|monitor|operator\s+raft\s+list-peers|operator\s+autopilot\s+get-config)\b[^;&|\n$`]*$"
+        ),
+        // snapshot save/inspect only read the cluster or a snapshot file
+        safe_pattern!(
+            "consul-snapshot-save-inspect",
+            r"^\s*consul\s+snapshot\s+(?:save|inspect)\b[^;&|\n$`]*$"
+        ),
+    ]
+}
+
+fn create_destructive_patterns() -> Vec<DestructivePattern> {
+    vec![
+        // kv delete -recurse
+        destructive_pattern!(
+            "kv-delete-recurse",
+            r"\bconsul\s+kv\s+delete\b[^;&|\n]*\s--?recurse\b",
+            "consul kv delete -recurse deletes every key under the prefix (or the whole KV store without one).",
+            High,
+            "consul kv delete -recurse removes a whole key tree:\n\n\
+             - Without a prefix (or with \"\") every key in the datacenter is deleted\n\
+             - Services reading configuration or feature flags from KV lose them\n\
+             - Consul keeps no history; only a snapshot can bring the keys back\n\n\
+             Preview: consul kv get -recurse <prefix>\n\
+             Back up first: consul kv export <prefix> > kv-backup.json"
+        ),
+        // operator raft remove-peer
+        destructive_pattern!(
+            "raft-remove-peer",
+            r"\bconsul\s+operator\s+raft\s+remove-peer\b",
+            "consul operator raft remove-peer removes a server from the raft peer set and can cost quorum.",
+            High,
+            "consul operator raft remove-peer edits the server peer set directly:\n\n\
+             - Removing a healthy server reduces fault tolerance\n\
+             - With too few voters left the cluster loses quorum and stops serving writes\n\
+             - Intended for clearing failed servers, not routine maintenance\n\n\
+             Review peers first: consul operator raft list-peers\n\
+             For a healthy server prefer: consul leave (run on that server)"
+        ),
+        // snapshot restore
+        destructive_pattern!(
+            "snapshot-restore",
+            r"\bconsul\s+snapshot\s+restore\b",
+            "consul snapshot restore replaces the cluster's KV, catalog, ACLs and sessions with the snapshot.",
+            High,
+            "consul snapshot restore overwrites the live state of the cluster:\n\n\
+             - Writes made after the snapshot was taken are lost\n\
+             - ACL tokens, intentions and the service catalog are replaced too\n\
+             - A snapshot from another datacenter silently imports its state\n\n\
+             Inspect the snapshot first: consul snapshot inspect <file>\n\
+             Save the current state: consul snapshot save before-restore.snap"
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_pack_creation() {
+        let pack = create_pack();
+        assert_eq!(pack.id, "kv.consul");
+        assert!(pack.keywords.contains(&"consul"));
+
+        assert_patterns_compile(&pack);
+        assert_all_patterns_have_reasons(&pack);
+        assert_unique_pattern_names(&pack);
+    }
+
+    #[test]
+    fn allows_read_only_commands() {
+        let pack = create_pack();
+        assert_safe_pattern_matches(&pack, "consul kv get -recurse config/");
+        assert_safe_pattern_matches(&pack, "consul kv export config/ > backup.json");
+        assert_safe_pattern_matches(&pack, "consul operator raft list-peers");
+        assert_safe_pattern_matches(&pack, "consul snapshot save backup.snap");
+        assert_no_safe_match(&pack, "consul members; consul kv delete -recurse");
+        assert_allows(&pack, "consul kv delete config/app/feature-x");
+    }
+
+    #[test]
+    fn blocks_destructive_commands() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "consul kv delete -recurse config/",
+            "kv-delete-recurse",
+        );
+        assert_blocks_with_pattern(&pack, "consul kv delete --recurse", "kv-delete-recurse");
+        assert_blocks_with_pattern(
+            &pack,
+            "consul operator raft remove-peer -id=a1b2c3",
+            "raft-remove-peer",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "consul snapshot restore backup.snap",
+            "snapshot-restore",
+        );
+    }
+}
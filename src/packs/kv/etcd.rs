@@ -0,0 +1,179 @@
+//! etcd patterns - protections against destructive etcdctl/etcdutl commands.
+//!
+//! etcd usually backs a Kubernetes control plane, so losing keys loses
+//! cluster state. This includes patterns for:
+//! - del of every key (`--prefix ""`, `--from-key ""`) and other prefix deletes
+//! - compact (discards key history)
+//! - member remove
+//! - snapshot restore into the live data directory
+
+use crate::packs::{DestructivePattern, Pack, SafePattern};
+use crate::{destructive_pattern, safe_pattern};
+
+/// Create the etcd pack.
+#[must_use]
+pub fn create_pack() -> Pack {
+    Pack {
+        id: "kv.etcd".to_string(),
+        name: "etcd",
+        description: "Protects against destructive etcd operations like deleting every key \
+                      with del --prefix \"\", compact, member remove, and snapshot restore \
+                      over the live data directory",
+        keywords: &["etcdctl", "etcdutl"],
+        safe_patterns: create_safe_patterns(),
+        destructive_patterns: create_destructive_patterns(),
+        keyword_matcher: None,
+        safe_regex_set: None,
+        safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
+    }
+}
+
+fn create_safe_patterns() -> Vec<SafePattern> {
+    // Anchored to the whole command so a read-only call cannot whitelist a
+    // chained `etcdctl del`. Global flags may precede the subcommand.
+    vec![
+        // get/watch read keys
+        safe_pattern!(
+            "etcdctl-get-watch",
+            r"^\s*etcdctl\s+(?:--?[\w-]+(?:=\S+)?\s+)*(?:get|watch)\b[^;&|\n$`]*$"
+        ),
+        // endpoint/member list/version/alarm list/check report cluster state
+        safe_pattern!(
+            "etcdctl-status",
+            r"^\s*etcdctl\s+(?:--?[\w-]+(?:=\S+)?\s+)*(?:endpoint\s+(?:health|status|hashkv)|member\s+list|version|alarm\s+list|check\s+perf|lease\s+(?:list|timetolive)|(?:user|role)\s+(?:list|get)|auth\s+status)\b[^;&|\n$`]*$"
+        ),
+        // snapshot save/status only read the cluster or a snapshot file
+        safe_pattern!(
+            "etcd-snapshot-save-status",
+            r"^\s*(?:etcdctl|etcdutl)\s+(?:--?[\w-]+(?:=\S+)?\s+)*snapshot\s+(?:save|status)\b[^;&|\n$`]*$"
+        ),
+    ]
+}
+
+fn create_destructive_patterns() -> Vec<DestructivePattern> {
+    vec![
+        // del of the empty prefix before other prefix deletes
+        destructive_pattern!(
+            "del-all-keys",
+            r#"\betcdctl\b[^;&|\n]*\sdel\b(?=[^;&|\n]*\s--(?:prefix|from-key)\b)(?=[^;&|\n]*\s(?:""|'')(?:\s|$))"#,
+            "etcdctl del with --prefix \"\" or --from-key \"\" deletes EVERY key in etcd.",
+            Critical,
+            "An empty key with --prefix or --from-key matches the whole keyspace:\n\n\
+             - Every key is deleted; for Kubernetes that is the entire cluster state\n\
+             - Watchers see the deletions immediately and controllers act on them\n\
+             - Recovery needs a snapshot restore and a control plane restart\n\n\
+             Check what matches first: etcdctl get --prefix \"\" --keys-only | head\n\
+             Take a snapshot: etcdctl snapshot save backup.db"
+        ),
+        // del --prefix / --from-key
+        destructive_pattern!(
+            "del-prefix",
+            r"\betcdctl\b[^;&|\n]*\sdel\b[^;&|\n]*\s--(?:prefix|from-key)\b",
+            "etcdctl del --prefix deletes every key under the prefix. Check with get --prefix --keys-only first.",
+            High,
+            "etcdctl del --prefix/--from-key deletes a whole range of keys:\n\n\
+             - A short prefix (\"/registry\", \"/\") covers most or all of the keyspace\n\
+             - Deleted keys are only recoverable from history until it is compacted\n\n\
+             Preview: etcdctl get <prefix> --prefix --keys-only\n\
+             Take a snapshot first: etcdctl snapshot save backup.db"
+        ),
+        // compact
+        destructive_pattern!(
+            "compact",
+            r"\betcdctl\b[^;&|\n]*\scompact(?:ion)?\s",
+            "etcdctl compact permanently discards key history before the given revision.",
+            High,
+            "etcdctl compact removes all revisions older than the target:\n\n\
+             - Watches and reads at older revisions start failing\n\
+             - Deleted or overwritten keys can no longer be recovered from history\n\
+             - Kubernetes compacts automatically; manual compaction is rarely needed\n\n\
+             Check the current revision: etcdctl endpoint status -w json"
+        ),
+        // member remove
+        destructive_pattern!(
+            "member-remove",
+            r"\betcdctl\b[^;&|\n]*\smember\s+remove\b",
+            "etcdctl member remove drops a member from the cluster and can cost quorum.",
+            High,
+            "etcdctl member remove shrinks the cluster:\n\n\
+             - Removing the wrong ID can take out a healthy member\n\
+             - With too few members left the cluster loses quorum and stops writes\n\
+             - The removed member's data directory cannot simply rejoin\n\n\
+             Review members first: etcdctl member list -w table\n\
+             Check health: etcdctl endpoint health --cluster"
+        ),
+        // snapshot restore into the default live data directory
+        destructive_pattern!(
+            "snapshot-restore-live-data-dir",
+            r#"\b(?:etcdctl|etcdutl)\b[^;&|\n]*\ssnapshot\s+restore\b[^;&|\n]*\s--data-dir(?:=|\s+)["']?/var/lib/etcd/?(?:["'\s]|$)"#,
+            "etcd snapshot restore into /var/lib/etcd overwrites the live data directory.",
+            Critical,
+            "Restoring a snapshot over the data directory of a running member:\n\n\
+             - Replaces the member's state with the snapshot's (newer writes are lost)\n\
+             - Corrupts a member that is still running on that directory\n\
+             - Restores a single member out of step with the rest of the cluster\n\n\
+             Restore into a new directory instead:\n\
+             etcdutl snapshot restore backup.db --data-dir /var/lib/etcd-restore\n\
+             Then stop etcd and point it at the restored directory"
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::Severity;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_pack_creation() {
+        let pack = create_pack();
+        assert_eq!(pack.id, "kv.etcd");
+        assert!(pack.keywords.contains(&"etcdctl"));
+
+        assert_patterns_compile(&pack);
+        assert_all_patterns_have_reasons(&pack);
+        assert_unique_pattern_names(&pack);
+    }
+
+    #[test]
+    fn allows_read_only_commands() {
+        let pack = create_pack();
+        assert_safe_pattern_matches(&pack, "etcdctl get /registry/pods --prefix --keys-only");
+        assert_safe_pattern_matches(
+            &pack,
+            "etcdctl --endpoints=https://10.0.0.1:2379 endpoint health",
+        );
+        assert_safe_pattern_matches(&pack, "etcdctl member list -w table");
+        assert_safe_pattern_matches(&pack, "etcdctl snapshot save /backup/etcd.db");
+        assert_no_safe_match(&pack, "etcdctl get foo && etcdctl del --prefix \"\"");
+        assert_allows(
+            &pack,
+            "etcdutl snapshot restore backup.db --data-dir /var/lib/etcd-new",
+        );
+    }
+
+    #[test]
+    fn blocks_destructive_commands() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "etcdctl del --prefix \"\"", "del-all-keys");
+        assert_blocks_with_pattern(&pack, "etcdctl del '' --from-key", "del-all-keys");
+        assert_blocks_with_severity(&pack, "etcdctl del \"\" --prefix", Severity::Critical);
+        assert_blocks_with_pattern(&pack, "etcdctl del /registry --prefix", "del-prefix");
+        assert_blocks_with_pattern(&pack, "etcdctl del --prefix /registry/pods", "del-prefix");
+        assert_blocks_with_pattern(&pack, "etcdctl compact 123456", "compact");
+        assert_blocks_with_pattern(
+            &pack,
+            "etcdctl member remove 8e9e05c52164694d",
+            "member-remove",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "etcdutl snapshot restore backup.db --data-dir /var/lib/etcd",
+            "snapshot-restore-live-data-dir",
+        );
+        assert_allows(&pack, "etcdctl del /app/config/feature-x");
+    }
+}
@@ -0,0 +1,8 @@
+//! Key-value store packs - protections for cluster coordination stores.
+//!
+//! This pack provides protection against destructive operations on:
+//! - etcd (`etcdctl del --prefix`, compact, member remove, snapshot restore)
+//! - Consul (`consul kv delete -recurse`, raft remove-peer, snapshot restore)
+
+pub mod consul;
+pub mod etcd;
@@ -1,7 +1,8 @@
 //! `Apache Kafka` pack - protections for destructive Kafka CLI operations.
 //!
 //! This pack targets high-impact Kafka operations like deleting topics,
-//! resetting consumer offsets, removing ACLs, and deleting records.
+//! resetting consumer offsets, removing ACLs, zeroing retention, and deleting
+//! records.
 
 use crate::packs::{DestructivePattern, Pack, SafePattern};
 use crate::{destructive_pattern, safe_pattern};
@@ -35,6 +36,8 @@ pub fn create_pack() -> Pack {
         keyword_matcher: None,
         safe_regex_set: None,
         safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
     }
 }
 
@@ -127,6 +130,20 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              - Set explicit values instead of deleting to revert to defaults\n\
              - Test configuration changes in a non-production environment"
         ),
+        destructive_pattern!(
+            "kafka-configs-retention-zero",
+            r#"kafka-configs(?:\.sh)?\b.*\s--alter\b.*\s--add-config[\s=]+\S*\bretention\.(?:ms|bytes)=0(?:[\s,'"]|$)"#,
+            "kafka-configs --alter setting retention to 0 purges all existing topic data.",
+            Critical,
+            "Setting retention.ms or retention.bytes to 0 makes every existing segment \
+             eligible for deletion on the next log cleanup pass. This is a common \
+             \"purge the topic\" trick and is just as destructive as deleting the topic: \
+             all unconsumed messages are lost.\n\n\
+             Safer alternatives:\n\
+             - kafka-configs --describe: Review the current retention first\n\
+             - Lower retention gradually to a non-zero value\n\
+             - Consume or mirror the data elsewhere before purging"
+        ),
         destructive_pattern!(
             "kafka-acls-remove",
             r"kafka-acls(?:\.sh)?\b.*\s--remove\b",
@@ -227,6 +244,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_configs_retention_zero_blocked() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "kafka-configs --bootstrap-server localhost:9092 --alter --entity-type topics --entity-name logs --add-config retention.ms=0",
+            "kafka-configs-retention-zero",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "kafka-configs.sh --bootstrap-server localhost:9092 --alter --add-config cleanup.policy=delete,retention.bytes=0 --entity-type topics --entity-name logs",
+            "kafka-configs-retention-zero",
+        );
+        assert_no_match(
+            &pack,
+            "kafka-configs --bootstrap-server localhost:9092 --alter --entity-type topics --entity-name logs --add-config retention.ms=604800000",
+        );
+    }
+
     #[test]
     fn test_acls_remove_blocked() {
         let pack = create_pack();
@@ -1,8 +1,8 @@
 //! `RabbitMQ` pack - protections for destructive `RabbitMQ` admin operations.
 //!
 //! Covers destructive CLI operations:
-//! - Queue/exchange deletion
-//! - Queue purge
+//! - Queue/exchange deletion (rabbitmqadmin and rabbitmqctl)
+//! - Queue purge (rabbitmqadmin and rabbitmqctl)
 //! - Vhost deletion
 //! - Cluster resets / node removal
 
@@ -23,6 +23,8 @@ pub fn create_pack() -> Pack {
         keyword_matcher: None,
         safe_regex_set: None,
         safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
     }
 }
 
@@ -52,6 +54,11 @@ fn create_safe_patterns() -> Vec<SafePattern> {
             "rabbitmqctl-report",
             r"rabbitmqctl(?:\s+--?\S+(?:\s+\S+)?)*\s+report\b"
         ),
+        // delete_queue guarded by --if-empty/--if-unused (anchored, no chaining)
+        safe_pattern!(
+            "rabbitmqctl-delete-queue-guarded",
+            r"^\s*rabbitmqctl(?:\s+--?\S+(?:\s+\S+)?)*\s+delete_queue\b[^;&|\x60\n]*\s--if-(?:empty|unused)(?:\s[^;&|\x60\n]*)?$"
+        ),
     ]
 }
 
@@ -95,6 +102,31 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              - Consume and process messages instead of purging\n\
              - Set message TTL for automatic expiration"
         ),
+        destructive_pattern!(
+            "rabbitmqctl-delete-queue",
+            r"rabbitmqctl(?:\s+--?\S+(?:\s+\S+)?)*\s+delete_queue\b",
+            "rabbitmqctl delete_queue permanently deletes a queue.",
+            High,
+            "Deleting a queue via rabbitmqctl removes the queue, its bindings, and \
+             every message it holds. Unlike rabbitmqadmin this runs directly against \
+             the node, bypassing the management API.\n\n\
+             Safer alternatives:\n\
+             - rabbitmqctl list_queues: Review queues and message counts first\n\
+             - Use --if-empty or --if-unused to avoid deleting live queues\n\
+             - Set TTL or max-length policies for automatic cleanup"
+        ),
+        destructive_pattern!(
+            "rabbitmqctl-purge-queue",
+            r"rabbitmqctl(?:\s+--?\S+(?:\s+\S+)?)*\s+purge_queue\b",
+            "rabbitmqctl purge_queue deletes ALL messages in the queue.",
+            High,
+            "Purging a queue deletes all ready messages immediately. Messages that \
+             were waiting to be consumed are permanently lost. This cannot be undone.\n\n\
+             Safer alternatives:\n\
+             - rabbitmqctl list_queues name messages: Check message counts first\n\
+             - Consume and process messages instead of purging\n\
+             - Set message TTL for automatic expiration"
+        ),
         destructive_pattern!(
             "rabbitmqctl-delete-vhost",
             r"rabbitmqctl(?:\s+--?\S+(?:\s+\S+)?)*\s+delete_vhost\b",
@@ -153,6 +185,7 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::packs::Severity;
     use crate::packs::test_helpers::*;
 
     #[test]
@@ -197,6 +230,16 @@ mod tests {
             "rabbitmqadmin purge queue name=jobs",
             "rabbitmqadmin-purge-queue",
         );
+        assert_blocks_with_pattern(
+            &pack,
+            "rabbitmqctl delete_queue jobs",
+            "rabbitmqctl-delete-queue",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "rabbitmqctl -p /prod purge_queue jobs",
+            "rabbitmqctl-purge-queue",
+        );
         assert_blocks_with_pattern(
             &pack,
             "rabbitmqctl delete_vhost /prod",
@@ -210,4 +253,31 @@ mod tests {
         assert_blocks_with_pattern(&pack, "rabbitmqctl reset", "rabbitmqctl-reset");
         assert_blocks_with_pattern(&pack, "rabbitmqctl force_reset", "rabbitmqctl-force-reset");
     }
+
+    #[test]
+    fn delete_queue_guard_flags_are_allowed() {
+        let pack = create_pack();
+        assert_blocks_with_severity(&pack, "rabbitmqctl delete_queue jobs", Severity::High);
+        assert_safe_pattern_matches(&pack, "rabbitmqctl delete_queue jobs --if-empty");
+        assert_safe_pattern_matches(&pack, "rabbitmqctl -p /prod delete_queue --if-unused jobs");
+        assert_allows(
+            &pack,
+            "rabbitmqctl delete_queue jobs --if-empty --if-unused",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "rabbitmqctl delete_queue jobs --if-empty-ish",
+            "rabbitmqctl-delete-queue",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "rabbitmqctl delete_queue tmp --if-empty; rabbitmqctl delete_queue jobs",
+            "rabbitmqctl-delete-queue",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "rabbitmqctl delete_queue jobs && rabbitmqctl delete_queue tmp --if-unused",
+            "rabbitmqctl-delete-queue",
+        );
+    }
 }
@@ -27,9 +27,11 @@ pub mod external;
 pub mod featureflags;
 pub mod infrastructure;
 pub mod kubernetes;
+pub mod kv;
 pub mod loadbalancer;
 pub mod messaging;
 pub mod monitoring;
+pub mod paas;
 pub mod package_managers;
 pub mod payment;
 pub mod platform;
@@ -49,7 +51,7 @@ mod test_template;
 
 pub use crate::normalize::normalize_command;
 use memchr::memmem;
-use regex_engine::LazyCompiledRegex;
+use regex_engine::{LazyCompiledRegex, RegexSetPrefilter};
 use serde::Serialize;
 use smallvec::SmallVec;
 use std::collections::{HashMap, HashSet};
@@ -113,6 +115,18 @@ impl Severity {
             Self::Low => "low",
         }
     }
+
+    /// Parse a severity label (`critical`, `high`, `medium`, `low`; case-insensitive).
+    #[must_use]
+    pub fn from_label(label: &str) -> Option<Self> {
+        match label.trim().to_ascii_lowercase().as_str() {
+            "critical" => Some(Self::Critical),
+            "high" => Some(Self::High),
+            "medium" => Some(Self::Medium),
+            "low" => Some(Self::Low),
+            _ => None,
+        }
+    }
 }
 
 /// Decision mode for how to handle a matched pattern.
@@ -281,6 +295,9 @@ pub struct DestructivePattern {
     /// Safer command alternatives to suggest when this pattern matches.
     /// Each suggestion includes the command, why it's safer, and which platforms it applies to.
     pub suggestions: &'static [PatternSuggestion],
+    /// How to undo the damage if the command runs anyway (e.g. `git reflog`
+    /// after a hard reset). Shown when the pattern warns instead of denying.
+    pub recovery_hint: Option<&'static str>,
 }
 
 impl std::fmt::Debug for DestructivePattern {
@@ -292,6 +309,7 @@ impl std::fmt::Debug for DestructivePattern {
             .field("severity", &self.severity)
             .field("explanation", &self.explanation)
             .field("suggestions", &self.suggestions)
+            .field("recovery_hint", &self.recovery_hint)
             .finish()
     }
 }
@@ -320,6 +338,7 @@ macro_rules! safe_pattern {
 /// - `destructive_pattern!("name", "regex", "reason", Critical)` - named with explicit severity
 /// - `destructive_pattern!("name", "regex", "reason", Critical, "explanation")` - with explanation
 /// - `destructive_pattern!("name", "regex", "reason", Critical, "explanation", &[...])` - with suggestions
+/// - `destructive_pattern!("name", "regex", "reason", Medium, "explanation", &[...], "hint")` - with recovery hint
 #[macro_export]
 macro_rules! destructive_pattern {
     // Unnamed pattern, default severity (High)
@@ -331,6 +350,7 @@ macro_rules! destructive_pattern {
             severity: $crate::packs::Severity::High,
             explanation: None,
             suggestions: &[],
+            recovery_hint: None,
         }
     };
     // Named pattern, default severity (High)
@@ -342,6 +362,7 @@ macro_rules! destructive_pattern {
             severity: $crate::packs::Severity::High,
             explanation: None,
             suggestions: &[],
+            recovery_hint: None,
         }
     };
     // Named pattern with explicit severity
@@ -353,6 +374,7 @@ macro_rules! destructive_pattern {
             severity: $crate::packs::Severity::$severity,
             explanation: None,
             suggestions: &[],
+            recovery_hint: None,
         }
     };
     // Named pattern with explicit severity and explanation
@@ -364,6 +386,7 @@ macro_rules! destructive_pattern {
             severity: $crate::packs::Severity::$severity,
             explanation: Some($explanation),
             suggestions: &[],
+            recovery_hint: None,
         }
     };
     // Named pattern with explicit severity, explanation, and suggestions
@@ -375,6 +398,19 @@ macro_rules! destructive_pattern {
             severity: $crate::packs::Severity::$severity,
             explanation: Some($explanation),
             suggestions: $suggestions,
+            recovery_hint: None,
+        }
+    };
+    // Named pattern with explicit severity, explanation, suggestions, and recovery hint
+    ($name:literal, $re:literal, $reason:literal, $severity:ident, $explanation:literal, $suggestions:expr, $recovery_hint:literal) => {
+        $crate::packs::DestructivePattern {
+            regex: $crate::packs::regex_engine::LazyCompiledRegex::new($re),
+            reason: $reason,
+            name: Some($name),
+            severity: $crate::packs::Severity::$severity,
+            explanation: Some($explanation),
+            suggestions: $suggestions,
+            recovery_hint: Some($recovery_hint),
         }
     };
 }
@@ -406,23 +442,34 @@ pub struct Pack {
     /// constructors; the registry initializes this during registration.
     pub keyword_matcher: Option<aho_corasick::AhoCorasick>,
 
-    /// Pre-built `RegexSet` for O(n) safe pattern matching.
-    /// Allows checking all safe patterns in a single pass. Built lazily when
-    /// the pack is instantiated. Only includes patterns that can use the
-    /// linear-time regex engine (no lookahead/lookbehind).
-    pub safe_regex_set: Option<regex::RegexSet>,
+    /// `RegexSet` for O(n) safe pattern matching.
+    /// Allows checking all safe patterns in a single pass. Prepared when the
+    /// pack is instantiated and compiled on the first check. Only includes
+    /// patterns that can use the linear-time regex engine (no lookahead/lookbehind).
+    pub safe_regex_set: Option<RegexSetPrefilter>,
 
     /// True if `safe_regex_set` covers ALL safe patterns (no backtracking patterns exist).
     /// When true and the `RegexSet` misses, we can skip individual pattern checks.
     pub safe_regex_set_is_complete: bool,
+
+    /// Single-pass prefilter over the destructive patterns.
+    /// Built alongside `safe_regex_set`; patterns that need the backtracking
+    /// engine are not in the set and are always checked individually.
+    pub destructive_regex_set: Option<RegexSetPrefilter>,
+
+    /// Indices (ascending) of the Critical-severity destructive patterns.
+    /// Filled in at registration; evaluation checks these first across all
+    /// candidate packs when the deadline is tight.
+    pub critical_patterns: Vec<usize>,
 }
 
 impl Pack {
     /// Create a new pack with the given patterns.
     ///
     /// This constructor initializes the lazy fields (`keyword_matcher`, `safe_regex_set`,
-    /// `safe_regex_set_is_complete`) to their default values. These are populated
-    /// during pack registration by `PackEntry::get_pack()`.
+    /// `safe_regex_set_is_complete`, `destructive_regex_set`, `critical_patterns`) to their
+    /// default values.
+    /// These are populated during pack registration by `PackEntry::get_pack()`.
     #[must_use]
     pub const fn new(
         id: PackId,
@@ -442,9 +489,22 @@ impl Pack {
             keyword_matcher: None,
             safe_regex_set: None,
             safe_regex_set_is_complete: false,
+            destructive_regex_set: None,
+            critical_patterns: Vec::new(),
         }
     }
 
+    /// Indices of the Critical-severity patterns in `destructive_patterns`.
+    #[must_use]
+    pub fn critical_pattern_indices(destructive_patterns: &[DestructivePattern]) -> Vec<usize> {
+        destructive_patterns
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.severity == Severity::Critical)
+            .map(|(index, _)| index)
+            .collect()
+    }
+
     /// Check if a command contains any of this pack's keywords.
     /// Returns false if the command doesn't contain any keywords (quick reject).
     ///
@@ -490,14 +550,20 @@ impl Pack {
     #[must_use]
     pub fn matches_safe(&self, cmd: &str) -> bool {
         // Fast path: use RegexSet if available
-        if let Some(ref set) = self.safe_regex_set {
-            if set.is_match(cmd) {
+        if let Some(candidates) = self.safe_regex_set.as_ref().and_then(|s| s.candidates(cmd)) {
+            if candidates.set_matched() {
                 return true;
             }
             // If RegexSet covers all patterns and missed, no match
             if self.safe_regex_set_is_complete {
                 return false;
             }
+            // Only the backtracking patterns outside the set are left to check.
+            return self
+                .safe_patterns
+                .iter()
+                .enumerate()
+                .any(|(i, p)| candidates.may_match(i) && p.regex.is_match(cmd));
         }
 
         // Fallback: check patterns individually
@@ -505,18 +571,39 @@ impl Pack {
         self.safe_patterns.iter().any(|p| p.regex.is_match(cmd))
     }
 
+    /// Which destructive patterns could match `cmd`, from one `RegexSet` pass.
+    ///
+    /// Returns `None` when no set was built; every pattern is then a candidate.
+    #[must_use]
+    pub fn destructive_candidates(
+        &self,
+        cmd: &str,
+    ) -> Option<regex_engine::PrefilterCandidates<'_>> {
+        self.destructive_regex_set
+            .as_ref()
+            .and_then(|set| set.candidates(cmd))
+    }
+
     /// Check if a command matches any destructive pattern.
     /// Returns the matched pattern's reason, name, severity, and explanation if found.
     #[must_use]
     pub fn matches_destructive(&self, cmd: &str) -> Option<DestructiveMatch> {
+        let candidates = self.destructive_candidates(cmd);
+        if candidates.as_ref().is_some_and(|c| !c.any()) {
+            return None;
+        }
         self.destructive_patterns
             .iter()
+            .enumerate()
+            .filter(|(i, _)| candidates.as_ref().is_none_or(|c| c.may_match(*i)))
+            .map(|(_, p)| p)
             .find(|p| p.regex.is_match(cmd))
             .map(|p| DestructiveMatch {
                 reason: p.reason,
                 name: p.name,
                 severity: p.severity,
                 explanation: p.explanation,
+                recovery_hint: p.recovery_hint,
             })
     }
 
@@ -552,6 +639,8 @@ pub struct DestructiveMatch {
     /// More verbose than `reason`, intended for explain/verbose output modes.
     /// Falls back to `reason` when not provided.
     pub explanation: Option<&'static str>,
+    /// Recovery guidance shown when the match only warns.
+    pub recovery_hint: Option<&'static str>,
 }
 
 /// Result of checking a command against all packs.
@@ -647,6 +736,12 @@ impl PackEntry {
         }
     }
 
+    /// Whether the pack has been instantiated (by a keyword hit or an explicit lookup).
+    #[must_use]
+    pub fn is_instantiated(&self) -> bool {
+        self.instance.get().is_some()
+    }
+
     /// Get or build the pack instance.
     ///
     /// # Panics
@@ -663,23 +758,25 @@ impl PackEntry {
                         .expect("pack keywords should be valid patterns"),
                 );
             }
-            // Build RegexSet for safe pattern matching (fast path)
+            // Prepare RegexSet for safe pattern matching (fast path; compiled on first check)
             if !pack.safe_patterns.is_empty() && pack.safe_regex_set.is_none() {
-                // Collect pattern strings that can use linear-time engine
-                let patterns: Vec<&str> = pack
-                    .safe_patterns
-                    .iter()
-                    .filter(|p| !regex_engine::needs_backtracking_engine(p.regex.as_str()))
-                    .map(|p| p.regex.as_str())
-                    .collect();
-
+                pack.safe_regex_set =
+                    RegexSetPrefilter::new(pack.safe_patterns.iter().map(|p| p.regex.as_str()));
                 // Track if RegexSet covers all patterns (no backtracking patterns)
-                pack.safe_regex_set_is_complete = patterns.len() == pack.safe_patterns.len();
-
-                // Only build RegexSet if we have linear patterns
-                if !patterns.is_empty() {
-                    pack.safe_regex_set = regex::RegexSet::new(patterns).ok();
-                }
+                pack.safe_regex_set_is_complete = pack
+                    .safe_regex_set
+                    .as_ref()
+                    .is_some_and(RegexSetPrefilter::is_complete);
+            }
+            // Prepare the destructive-pattern prefilter (one scan instead of one per pattern)
+            if !pack.destructive_patterns.is_empty() && pack.destructive_regex_set.is_none() {
+                pack.destructive_regex_set = RegexSetPrefilter::new(
+                    pack.destructive_patterns.iter().map(|p| p.regex.as_str()),
+                );
+            }
+            // Critical patterns run first when the evaluation deadline is tight
+            if pack.critical_patterns.is_empty() {
+                pack.critical_patterns = Pack::critical_pattern_indices(&pack.destructive_patterns);
             }
             pack
         })
@@ -795,13 +892,26 @@ impl EnabledKeywordIndex {
 
 /// Static pack entries - metadata is available without instantiating packs.
 /// Packs are built lazily on first access.
-static PACK_ENTRIES: [PackEntry; 82] = [
+static PACK_ENTRIES: [PackEntry; 107] = [
     PackEntry::new("core.git", &["git"], core::git::create_pack),
     PackEntry::new(
         "core.filesystem",
         &["rm", "/rm"],
         core::filesystem::create_pack,
     ),
+    PackEntry::new(
+        "core.pipe_shell",
+        &[
+            "curl",
+            "wget",
+            "iex",
+            "IEX",
+            "Iex",
+            "Invoke-Expression",
+            "invoke-expression",
+        ],
+        core::pipe_shell::create_pack,
+    ),
     PackEntry::new("storage.s3", &["s3", "s3api"], storage::s3::create_pack),
     PackEntry::new(
         "storage.gcs",
@@ -814,6 +924,11 @@ static PACK_ENTRIES: [PackEntry; 82] = [
         &["az storage", "azcopy"],
         storage::azure_blob::create_pack,
     ),
+    PackEntry::new(
+        "storage.objectstore",
+        &["rclone", "s3cmd", "gsutil", "mc", "s3"],
+        storage::objectstore::create_pack,
+    ),
     PackEntry::new("remote.rsync", &["rsync"], remote::rsync::create_pack),
     PackEntry::new(
         "remote.ssh",
@@ -843,6 +958,11 @@ static PACK_ENTRIES: [PackEntry; 82] = [
         &["aws", "secretsmanager", "ssm"],
         secrets::aws_secrets::create_pack,
     ),
+    PackEntry::new(
+        "secrets.gcp_secrets",
+        &["gcloud"],
+        secrets::gcp_secrets::create_pack,
+    ),
     PackEntry::new(
         "secrets.onepassword",
         &["op"],
@@ -859,6 +979,16 @@ static PACK_ENTRIES: [PackEntry; 82] = [
         &["glab", "gitlab-rails", "gitlab-rake"],
         platform::gitlab::create_pack,
     ),
+    PackEntry::new("paas.fly", &["fly", "flyctl"], paas::fly::create_pack),
+    PackEntry::new("paas.heroku", &["heroku"], paas::heroku::create_pack),
+    PackEntry::new(
+        "paas.netlify",
+        &["netlify", "ntl"],
+        paas::netlify::create_pack,
+    ),
+    PackEntry::new("paas.railway", &["railway"], paas::railway::create_pack),
+    PackEntry::new("paas.render", &["render"], paas::render::create_pack),
+    PackEntry::new("paas.vercel", &["vercel"], paas::vercel::create_pack),
     PackEntry::new(
         "dns.cloudflare",
         &[
@@ -1069,6 +1199,8 @@ static PACK_ENTRIES: [PackEntry; 82] = [
     PackEntry::new("backup.rclone", &["rclone"], backup::rclone::create_pack),
     PackEntry::new("backup.restic", &["restic"], backup::restic::create_pack),
     PackEntry::new("backup.velero", &["velero"], backup::velero::create_pack),
+    PackEntry::new("kv.etcd", &["etcdctl", "etcdutl"], kv::etcd::create_pack),
+    PackEntry::new("kv.consul", &["consul"], kv::consul::create_pack),
     PackEntry::new(
         "database.postgresql",
         &[
@@ -1097,6 +1229,7 @@ static PACK_ENTRIES: [PackEntry; 82] = [
             "mongorestore",
             "dropDatabase",
             "dropCollection",
+            "atlas",
         ],
         database::mongodb::create_pack,
     ),
@@ -1107,9 +1240,29 @@ static PACK_ENTRIES: [PackEntry; 82] = [
     ),
     PackEntry::new(
         "database.sqlite",
-        &["sqlite3", "DROP", "DELETE", "TRUNCATE"],
+        &[
+            "sqlite3", "sqlite", "DROP", "DELETE", "TRUNCATE", ".db", ".db3",
+        ],
         database::sqlite::create_pack,
     ),
+    PackEntry::new(
+        "database.cassandra",
+        &["cqlsh", "nodetool", "KEYSPACE", "keyspace"],
+        database::cassandra::create_pack,
+    ),
+    PackEntry::new(
+        "database.migrations",
+        &[
+            "prisma",
+            "rails",
+            "rake",
+            "alembic",
+            "flyway",
+            "sequelize",
+            "diesel",
+        ],
+        database::migrations::create_pack,
+    ),
     PackEntry::new(
         "containers.docker",
         &["docker"],
@@ -1136,6 +1289,12 @@ static PACK_ENTRIES: [PackEntry; 82] = [
         &["kustomize"],
         kubernetes::kustomize::create_pack,
     ),
+    PackEntry::new(
+        "kubernetes.argocd",
+        &["argocd"],
+        kubernetes::argocd::create_pack,
+    ),
+    PackEntry::new("kubernetes.flux", &["flux"], kubernetes::flux::create_pack),
     PackEntry::new("cloud.aws", &["aws"], cloud::aws::create_pack),
     PackEntry::new(
         "cloud.gcp",
@@ -1143,6 +1302,11 @@ static PACK_ENTRIES: [PackEntry; 82] = [
         cloud::gcp::create_pack,
     ),
     PackEntry::new("cloud.azure", &["az"], cloud::azure::create_pack),
+    PackEntry::new(
+        "cloud.cloudformation",
+        &["cloudformation", "cdk"],
+        cloud::cloudformation::create_pack,
+    ),
     PackEntry::new(
         "cdn.cloudflare_workers",
         &["wrangler"],
@@ -1184,6 +1348,11 @@ static PACK_ENTRIES: [PackEntry; 82] = [
         &["pulumi"],
         infrastructure::pulumi::create_pack,
     ),
+    PackEntry::new(
+        "infrastructure.cdktf",
+        &["cdktf"],
+        infrastructure::cdktf::create_pack,
+    ),
     PackEntry::new(
         "system.disk",
         &[
@@ -1207,16 +1376,70 @@ static PACK_ENTRIES: [PackEntry; 82] = [
         ],
         system::disk::create_pack,
     ),
+    PackEntry::new(
+        "system.network",
+        &[
+            "iptables",
+            "ip6tables",
+            "nft",
+            "ufw",
+            "firewall-cmd",
+            "ip l",
+            "ip r",
+            "ip -",
+            "route",
+            "ifconfig",
+            "ifdown",
+            "nmcli",
+        ],
+        system::network::create_pack,
+    ),
     PackEntry::new(
         "system.permissions",
-        &["chmod", "chown", "setfacl"],
+        &["chmod", "chown", "chgrp", "setfacl", "chattr", "umask"],
         system::permissions::create_pack,
     ),
+    PackEntry::new(
+        "system.scheduling",
+        &["crontab", "cron", "atd", "atrm", "at -", "launchctl"],
+        system::scheduling::create_pack,
+    ),
     PackEntry::new(
         "system.services",
         &["systemctl", "service"],
         system::services::create_pack,
     ),
+    PackEntry::new(
+        "system.sessions",
+        &["tmux", "screen"],
+        system::sessions::create_pack,
+    ),
+    PackEntry::new(
+        "system.users",
+        &[
+            "userdel",
+            "deluser",
+            "passwd",
+            "usermod",
+            "sudoers",
+            "visudo",
+            "ssh-keygen",
+            ".ssh",
+        ],
+        system::users::create_pack,
+    ),
+    PackEntry::new(
+        "system.virtualization",
+        &[
+            "machinectl",
+            "virsh",
+            "VBoxManage",
+            "vboxmanage",
+            "multipass",
+            "vagrant",
+        ],
+        system::virtualization::create_pack,
+    ),
     PackEntry::new("strict_git", &["git"], strict_git::create_pack),
     PackEntry::new(
         "package_managers",
@@ -1225,6 +1448,21 @@ static PACK_ENTRIES: [PackEntry; 82] = [
         ],
         package_managers::create_pack,
     ),
+    PackEntry::new(
+        "package_managers.homebrew",
+        &["brew"],
+        package_managers::homebrew::create_pack,
+    ),
+    PackEntry::new(
+        "package_managers.nix",
+        &["nix", "nixos-rebuild"],
+        package_managers::nix::create_pack,
+    ),
+    PackEntry::new(
+        "package_managers.cargo",
+        &["cargo", "rustup"],
+        package_managers::cargo::create_pack,
+    ),
 ];
 
 impl PackRegistry {
@@ -1342,12 +1580,12 @@ impl PackRegistry {
     ///
     /// 0. **Tier 0 (safe)**: `safe.*` packs - safe patterns checked first to whitelist
     /// 1. **Tier 1 (core/storage/remote)**: `core.*`, `storage.*`, `remote.*` packs - most fundamental protections
-    /// 2. **Tier 2 (system)**: `system.*` - disk, permissions, services
-    /// 3. **Tier 3 (infrastructure)**: `infrastructure.*` - terraform, ansible, pulumi
-    /// 4. **Tier 4 (apigateway/cloud/dns/platform/cdn/loadbalancer)**: `apigateway.*`, `cloud.*`, `dns.*`, `platform.*`, `cdn.*`, `loadbalancer.*`
-    /// 5. **Tier 5 (kubernetes)**: `kubernetes.*` - kubectl, helm, kustomize
+    /// 2. **Tier 2 (system)**: `system.*` - disk, network, permissions, scheduling, services, sessions, users, virtualization
+    /// 3. **Tier 3 (infrastructure)**: `infrastructure.*` - terraform, ansible, pulumi, cdktf
+    /// 4. **Tier 4 (apigateway/cloud/dns/platform/paas/cdn/loadbalancer)**: `apigateway.*`, `cloud.*`, `dns.*`, `platform.*`, `paas.*`, `cdn.*`, `loadbalancer.*`
+    /// 5. **Tier 5 (kubernetes)**: `kubernetes.*` - kubectl, helm, kustomize, argocd, flux
     /// 6. **Tier 6 (containers)**: `containers.*` - docker, compose, podman
-    /// 7. **Tier 7 (database/search/messaging/backup)**: `database.*`, `search.*`, `messaging.*`, `backup.*`
+    /// 7. **Tier 7 (database/search/messaging/backup/kv)**: `database.*`, `search.*`, `messaging.*`, `backup.*`, `kv.*`
     /// 8. **Tier 8 (`package_managers`)**: package manager protections
     /// 9. **Tier 9 (`strict_git`)**: extra git paranoia
     /// 10. **Tier 10 (services)**: `cicd.*`, `email.*`, `featureflags.*`, `secrets.*`, `monitoring.*`, `payment.*`
@@ -1384,10 +1622,10 @@ impl PackRegistry {
             "core" | "storage" | "remote" => 1,
             "system" => 2,
             "infrastructure" => 3,
-            "apigateway" | "cdn" | "cloud" | "dns" | "loadbalancer" | "platform" => 4,
+            "apigateway" | "cdn" | "cloud" | "dns" | "loadbalancer" | "paas" | "platform" => 4,
             "kubernetes" => 5,
             "containers" => 6,
-            "backup" | "database" | "messaging" | "search" => 7,
+            "backup" | "database" | "kv" | "messaging" | "search" => 7,
             "package_managers" => 8,
             "strict_git" => 9,
             "cicd" | "email" | "featureflags" | "secrets" | "monitoring" | "payment" => 10, // CI/CD + email + feature flags + secrets + monitoring + payment tooling
@@ -1490,6 +1728,48 @@ impl PackRegistry {
         infos
     }
 
+    /// Instantiate the given packs and compile all of their regexes now.
+    ///
+    /// Packs are normally built on their first keyword hit and each pattern is
+    /// compiled on first use; long-running processes call this up front so the
+    /// first command they check does not pay for it. Unknown IDs are ignored.
+    #[must_use]
+    pub fn warm_up(&self, pack_ids: &[String]) -> Vec<PackWarmup> {
+        pack_ids
+            .iter()
+            .filter_map(|id| self.get_entry(id))
+            .map(|entry| {
+                let start = std::time::Instant::now();
+                let pack = entry.get_pack();
+                let mut errors = Vec::new();
+                let safe = pack.safe_patterns.iter().map(|p| (p.name, &p.regex));
+                let destructive = pack
+                    .destructive_patterns
+                    .iter()
+                    .map(|p| (p.name.unwrap_or("<unnamed>"), &p.regex));
+                for (name, regex) in safe.chain(destructive) {
+                    if let Err(e) = regex.warm() {
+                        errors.push(format!("{name}: {e}"));
+                    }
+                }
+                for set in [&pack.safe_regex_set, &pack.destructive_regex_set]
+                    .into_iter()
+                    .flatten()
+                {
+                    if !set.warm() {
+                        errors.push("RegexSet failed to compile".to_string());
+                    }
+                }
+                PackWarmup {
+                    id: entry.id,
+                    pattern_count: pack.safe_patterns.len() + pack.destructive_patterns.len(),
+                    duration: start.elapsed(),
+                    errors,
+                }
+            })
+            .collect()
+    }
+
     /// Get a pack entry by ID (metadata only, no pack instantiation).
     #[must_use]
     pub fn get_entry(&self, id: &str) -> Option<&PackEntry> {
@@ -1612,6 +1892,19 @@ pub struct PackInfo {
     pub destructive_pattern_count: usize,
 }
 
+/// Result of warming up one pack (see [`PackRegistry::warm_up`]).
+#[derive(Debug, Clone)]
+pub struct PackWarmup {
+    /// Pack ID.
+    pub id: &'static str,
+    /// Number of safe and destructive patterns compiled.
+    pub pattern_count: usize,
+    /// Time spent building the pack and compiling its regexes.
+    pub duration: std::time::Duration,
+    /// Patterns that failed to compile (they fail open at runtime).
+    pub errors: Vec<String>,
+}
+
 /// Global pack registry (lazily initialized).
 pub static REGISTRY: LazyLock<PackRegistry> = LazyLock::new(PackRegistry::new);
 
@@ -2306,6 +2599,7 @@ mod tests {
         assert_eq!(PackRegistry::pack_tier("dns.route53"), 4);
         assert_eq!(PackRegistry::pack_tier("dns.generic"), 4);
         assert_eq!(PackRegistry::pack_tier("platform.github"), 4);
+        assert_eq!(PackRegistry::pack_tier("paas.heroku"), 4);
         assert_eq!(PackRegistry::pack_tier("cdn.cloudflare_workers"), 4);
         assert_eq!(PackRegistry::pack_tier("loadbalancer.nginx"), 4);
 
@@ -2321,11 +2615,16 @@ mod tests {
         assert_eq!(PackRegistry::pack_tier("backup.rclone"), 7);
         assert_eq!(PackRegistry::pack_tier("backup.restic"), 7);
         assert_eq!(PackRegistry::pack_tier("backup.velero"), 7);
+        assert_eq!(PackRegistry::pack_tier("kv.etcd"), 7);
+        assert_eq!(PackRegistry::pack_tier("kv.consul"), 7);
         assert_eq!(PackRegistry::pack_tier("messaging.kafka"), 7);
         assert_eq!(PackRegistry::pack_tier("search.elasticsearch"), 7);
 
         // Package managers should be tier 8
         assert_eq!(PackRegistry::pack_tier("package_managers"), 8);
+        assert_eq!(PackRegistry::pack_tier("package_managers.homebrew"), 8);
+        assert_eq!(PackRegistry::pack_tier("package_managers.nix"), 8);
+        assert_eq!(PackRegistry::pack_tier("package_managers.cargo"), 8);
 
         // Strict git should be tier 9
         assert_eq!(PackRegistry::pack_tier("strict_git"), 9);
@@ -3051,4 +3350,98 @@ mod tests {
             errors.join("\n\n")
         );
     }
+
+    #[test]
+    fn pack_entry_defers_regex_compilation() {
+        let entry = PackEntry::new("core.git", &["git"], core::git::create_pack);
+        assert!(!entry.is_instantiated());
+
+        let pack = entry.get_pack();
+        assert!(entry.is_instantiated());
+        let destructive = pack.destructive_regex_set.as_ref().expect("prefilter");
+        assert!(!destructive.is_compiled(), "set compiles on first check");
+
+        assert!(pack.matches_destructive("git reset --hard").is_some());
+        assert!(destructive.is_compiled());
+    }
+
+    #[test]
+    fn warm_up_compiles_requested_packs() {
+        let results = REGISTRY.warm_up(&["core.git".to_string(), "no.such.pack".to_string()]);
+        assert_eq!(results.len(), 1, "unknown IDs are skipped");
+        assert_eq!(results[0].id, "core.git");
+        assert!(results[0].pattern_count > 0);
+        assert!(results[0].errors.is_empty(), "{:?}", results[0].errors);
+
+        let pack = REGISTRY.get("core.git").expect("core.git");
+        for set in [&pack.safe_regex_set, &pack.destructive_regex_set]
+            .into_iter()
+            .flatten()
+        {
+            assert!(set.is_compiled());
+        }
+    }
+
+    /// Every pack with linear-engine destructive patterns must get a prefilter;
+    /// a set that silently failed to compile would fall back to serial matching.
+    #[test]
+    fn all_packs_build_destructive_prefilter() {
+        let mut missing: Vec<&str> = Vec::new();
+
+        for pack_id in REGISTRY.all_pack_ids() {
+            let pack = REGISTRY.get(pack_id).expect("pack must exist");
+            let has_linear = pack
+                .destructive_patterns
+                .iter()
+                .any(|p| !regex_engine::needs_backtracking_engine(p.regex.as_str()));
+            if has_linear && pack.destructive_regex_set.is_none() {
+                missing.push(pack_id);
+            }
+        }
+
+        assert!(
+            missing.is_empty(),
+            "packs without a destructive RegexSet: {}",
+            missing.join(", ")
+        );
+    }
+
+    #[test]
+    fn registry_indexes_critical_patterns() {
+        for pack_id in REGISTRY.all_pack_ids() {
+            let pack = REGISTRY.get(pack_id).expect("pack must exist");
+            let expected: Vec<usize> = pack
+                .destructive_patterns
+                .iter()
+                .enumerate()
+                .filter(|(_, p)| p.severity == Severity::Critical)
+                .map(|(index, _)| index)
+                .collect();
+            assert_eq!(pack.critical_patterns, expected, "{pack_id}");
+        }
+        let git = REGISTRY.get("core.git").expect("core.git exists");
+        assert!(!git.critical_patterns.is_empty());
+    }
+
+    #[test]
+    fn destructive_prefilter_agrees_with_serial_matching() {
+        let pack = REGISTRY.get("core.git").expect("core.git exists");
+        assert!(pack.destructive_regex_set.is_some());
+
+        for cmd in [
+            "git reset --hard HEAD~1",
+            "git push --force origin main",
+            "git clean -fdx",
+            "git status",
+            "git checkout -- src/main.rs",
+        ] {
+            let serial = pack
+                .destructive_patterns
+                .iter()
+                .find(|p| p.regex.is_match(cmd))
+                .and_then(|p| p.name);
+            let prefiltered = pack.matches_destructive(cmd).and_then(|m| m.name);
+            assert_eq!(prefiltered, serial, "mismatch for {cmd:?}");
+        }
+    }
 }
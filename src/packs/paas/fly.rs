@@ -0,0 +1,196 @@
+//! Fly.io pack - protections for destructive `fly`/`flyctl` operations.
+//!
+//! Covers destructive operations:
+//! - App destruction (`fly apps destroy`, `fly destroy`)
+//! - Volume destruction (`fly volumes destroy`)
+//! - Machine destruction (`fly machines destroy`)
+//! - Secret removal and IP release
+//!
+//! `-y`/`--yes` skips the confirmation prompt, so those variants are Critical.
+
+use crate::packs::{DestructivePattern, Pack, SafePattern};
+use crate::{destructive_pattern, safe_pattern};
+
+/// Create the Fly.io pack.
+#[must_use]
+pub fn create_pack() -> Pack {
+    Pack {
+        id: "paas.fly".to_string(),
+        name: "Fly.io",
+        description: "Protects against destructive Fly.io CLI operations like destroying apps, \
+                      volumes, and machines.",
+        keywords: &["fly", "flyctl"],
+        safe_patterns: create_safe_patterns(),
+        destructive_patterns: create_destructive_patterns(),
+        keyword_matcher: None,
+        safe_regex_set: None,
+        safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
+    }
+}
+
+fn create_safe_patterns() -> Vec<SafePattern> {
+    vec![
+        // Listing and inspection
+        safe_pattern!(
+            "fly-list",
+            r"\b(?:fly|flyctl)\s+(?:apps|volumes?|vol|machines?|m|secrets|ips|certs)\s+(?:list|ls|show|status)\b"
+        ),
+        safe_pattern!(
+            "fly-status",
+            r"\b(?:fly|flyctl)\s+(?:status|logs|info|releases|checks|dashboard|doctor)\b"
+        ),
+        // Version/help/auth
+        safe_pattern!(
+            "fly-version",
+            r"\b(?:fly|flyctl)\s+(?:version|help|-h|--help|auth\s+whoami)\b"
+        ),
+    ]
+}
+
+fn create_destructive_patterns() -> Vec<DestructivePattern> {
+    vec![
+        // App destruction without confirmation
+        destructive_pattern!(
+            "fly-apps-destroy-yes",
+            r"\b(?:fly|flyctl)\s+(?:apps\s+(?:destroy|delete|remove|rm)|destroy)\b(?=[^;&|]*\s(?:-y|--yes)\b)",
+            "fly apps destroy --yes permanently deletes the app without a confirmation prompt.",
+            Critical,
+            "Destroying a Fly.io app removes all of its machines, volumes, IP addresses, \
+             certificates, and secrets. Volume data is gone for good and the app name is \
+             released. --yes skips the only safety prompt.\n\n\
+             Safer alternatives:\n\
+             - fly status -a <app>: Confirm which app you are targeting\n\
+             - fly volumes snapshots create <volume-id>: Snapshot data first\n\
+             - fly scale count 0 -a <app>: Stop the app without deleting it"
+        ),
+        // App destruction
+        destructive_pattern!(
+            "fly-apps-destroy",
+            r"\b(?:fly|flyctl)\s+(?:apps\s+(?:destroy|delete|remove|rm)|destroy)\b",
+            "fly apps destroy permanently deletes the app and all of its resources.",
+            High,
+            "Destroying a Fly.io app removes all of its machines, volumes, IP addresses, \
+             certificates, and secrets. This cannot be undone.\n\n\
+             Safer alternatives:\n\
+             - fly status -a <app>: Confirm which app you are targeting\n\
+             - fly scale count 0 -a <app>: Stop the app without deleting it"
+        ),
+        // Volume destruction without confirmation
+        destructive_pattern!(
+            "fly-volumes-destroy-yes",
+            r"\b(?:fly|flyctl)\s+(?:volumes?|vol)\s+(?:destroy|delete|rm)\b(?=[^;&|]*\s(?:-y|--yes)\b)",
+            "fly volumes destroy --yes permanently deletes volume data without a confirmation prompt.",
+            Critical,
+            "A Fly volume is the persistent disk behind databases and uploads. Destroying \
+             it deletes the data immediately; --yes skips the confirmation prompt.\n\n\
+             Safer alternatives:\n\
+             - fly volumes list -a <app>: Verify the volume ID\n\
+             - fly volumes snapshots create <volume-id>: Take a snapshot first"
+        ),
+        // Volume destruction
+        destructive_pattern!(
+            "fly-volumes-destroy",
+            r"\b(?:fly|flyctl)\s+(?:volumes?|vol)\s+(?:destroy|delete|rm)\b",
+            "fly volumes destroy permanently deletes the volume and its data.",
+            High,
+            "A Fly volume is the persistent disk behind databases and uploads. Destroying \
+             it deletes the data immediately.\n\n\
+             Safer alternatives:\n\
+             - fly volumes list -a <app>: Verify the volume ID\n\
+             - fly volumes snapshots create <volume-id>: Take a snapshot first"
+        ),
+        // Machine destruction
+        destructive_pattern!(
+            "fly-machines-destroy",
+            r"\b(?:fly|flyctl)\s+(?:machines?|m)\s+(?:destroy|remove|rm)\b",
+            "fly machines destroy deletes machines; --force also kills running ones.",
+            High,
+            "Destroyed machines are not recreated. With --force, running machines are \
+             killed mid-request.\n\n\
+             Safer alternatives:\n\
+             - fly machines stop <id>: Stop without deleting\n\
+             - fly scale count <n>: Let Fly manage the machine count"
+        ),
+        // Secrets removal
+        destructive_pattern!(
+            "fly-secrets-unset",
+            r"\b(?:fly|flyctl)\s+secrets\s+unset\b",
+            "fly secrets unset removes secrets and restarts the app without them.",
+            Medium,
+            "Unsetting a secret triggers a redeploy; the app restarts without the value \
+             and secret values cannot be read back from Fly.\n\n\
+             Safer alternatives:\n\
+             - fly secrets list: Check which secrets are set\n\
+             - fly secrets unset --stage: Stage the change for the next deploy"
+        ),
+        // IP release
+        destructive_pattern!(
+            "fly-ips-release",
+            r"\b(?:fly|flyctl)\s+ips\s+release\b",
+            "fly ips release gives up the IP address; the same address cannot be reclaimed.",
+            High,
+            "Released IPv4/IPv6 addresses return to Fly's pool. DNS records and allowlists \
+             pointing at the old address break.\n\n\
+             Safer alternatives:\n\
+             - fly ips list: Confirm which address is in use"
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::Severity;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_pack_creation() {
+        let pack = create_pack();
+        assert_eq!(pack.id, "paas.fly");
+        assert!(pack.keywords.contains(&"flyctl"));
+
+        assert_patterns_compile(&pack);
+        assert_all_patterns_have_reasons(&pack);
+        assert_unique_pattern_names(&pack);
+    }
+
+    #[test]
+    fn allows_safe_commands() {
+        let pack = create_pack();
+        assert_safe_pattern_matches(&pack, "fly apps list");
+        assert_safe_pattern_matches(&pack, "flyctl volumes list -a web");
+        assert_safe_pattern_matches(&pack, "fly status -a web");
+        assert_safe_pattern_matches(&pack, "fly version");
+        assert_allows(&pack, "fly deploy");
+        assert_allows(&pack, "butterfly apps destroy");
+    }
+
+    #[test]
+    fn blocks_destroy_with_confirmation_bypass_as_critical() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "fly apps destroy web --yes", "fly-apps-destroy-yes");
+        assert_blocks_with_pattern(&pack, "flyctl destroy web -y", "fly-apps-destroy-yes");
+        assert_blocks_with_severity(&pack, "fly apps destroy web --yes", Severity::Critical);
+        assert_blocks_with_pattern(
+            &pack,
+            "fly volumes destroy vol_123 -y",
+            "fly-volumes-destroy-yes",
+        );
+    }
+
+    #[test]
+    fn blocks_destructive_commands() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "fly apps destroy web", "fly-apps-destroy");
+        assert_blocks_with_pattern(&pack, "fly vol rm vol_123", "fly-volumes-destroy");
+        assert_blocks_with_pattern(
+            &pack,
+            "fly machines destroy 148e --force",
+            "fly-machines-destroy",
+        );
+        assert_blocks_with_pattern(&pack, "fly secrets unset API_KEY", "fly-secrets-unset");
+        assert_blocks_with_pattern(&pack, "fly ips release 1.2.3.4", "fly-ips-release");
+    }
+}
@@ -0,0 +1,233 @@
+//! Heroku pack - protections for destructive Heroku CLI operations.
+//!
+//! Covers destructive operations:
+//! - App destruction (`heroku apps:destroy`)
+//! - Database resets and overwrites (`heroku pg:reset`, `heroku pg:copy`)
+//! - Add-on and backup deletion
+//! - Config removal and scaling to zero
+//!
+//! `--confirm <app>` skips the confirmation prompt, so those variants are
+//! Critical.
+
+use crate::packs::{DestructivePattern, Pack, SafePattern};
+use crate::{destructive_pattern, safe_pattern};
+
+/// Create the Heroku pack.
+#[must_use]
+pub fn create_pack() -> Pack {
+    Pack {
+        id: "paas.heroku".to_string(),
+        name: "Heroku",
+        description: "Protects against destructive Heroku CLI operations like destroying apps, \
+                      resetting Postgres databases, and removing add-ons.",
+        keywords: &["heroku"],
+        safe_patterns: create_safe_patterns(),
+        destructive_patterns: create_destructive_patterns(),
+        keyword_matcher: None,
+        safe_regex_set: None,
+        safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
+    }
+}
+
+fn create_safe_patterns() -> Vec<SafePattern> {
+    vec![
+        // Listing and inspection
+        safe_pattern!(
+            "heroku-info",
+            r"heroku\s+(?:apps|apps:info|info|ps|logs|releases|addons|config|domains|pg:info|pg:backups|pg:credentials|access)(?:\s|$)"
+        ),
+        // Backups: capture/download are safe
+        safe_pattern!(
+            "heroku-pg-backups-capture",
+            r"heroku\s+pg:backups:(?:capture|download|info|url|schedules)\b"
+        ),
+        // Version/help/auth
+        safe_pattern!(
+            "heroku-version",
+            r"heroku\s+(?:version|--version|help|--help|auth:whoami|whoami)\b"
+        ),
+    ]
+}
+
+fn create_destructive_patterns() -> Vec<DestructivePattern> {
+    vec![
+        // App destruction without confirmation
+        destructive_pattern!(
+            "heroku-apps-destroy-confirm",
+            r"heroku\s+(?:apps:destroy|destroy)\b(?=[^;&|]*\s--confirm(?:\s|=))",
+            "heroku apps:destroy --confirm permanently deletes the app without a confirmation prompt.",
+            Critical,
+            "Destroying a Heroku app deletes its dynos, config vars, domains, add-ons \
+             (including Heroku Postgres databases and their data), and release history. \
+             --confirm answers the only safety prompt.\n\n\
+             Safer alternatives:\n\
+             - heroku apps:info -a <app>: Confirm which app you are targeting\n\
+             - heroku pg:backups:capture -a <app>: Back up databases first\n\
+             - heroku maintenance:on -a <app>: Take the app offline without deleting it"
+        ),
+        // App destruction
+        destructive_pattern!(
+            "heroku-apps-destroy",
+            r"heroku\s+(?:apps:destroy|destroy)\b",
+            "heroku apps:destroy permanently deletes the app, its add-ons, and their data.",
+            High,
+            "Destroying a Heroku app deletes its dynos, config vars, domains, add-ons, and \
+             release history. This cannot be undone.\n\n\
+             Safer alternatives:\n\
+             - heroku apps:info -a <app>: Confirm which app you are targeting\n\
+             - heroku pg:backups:capture -a <app>: Back up databases first"
+        ),
+        // Database reset without confirmation
+        destructive_pattern!(
+            "heroku-pg-reset-confirm",
+            r"heroku\s+pg:reset\b(?=[^;&|]*\s--confirm(?:\s|=))",
+            "heroku pg:reset --confirm wipes the database without a confirmation prompt.",
+            Critical,
+            "pg:reset drops every table, view, and row in the database. With --confirm \
+             there is no prompt.\n\n\
+             Safer alternatives:\n\
+             - heroku pg:backups:capture: Take a backup first\n\
+             - heroku pg:info: Confirm which database DATABASE_URL points to"
+        ),
+        // Database reset
+        destructive_pattern!(
+            "heroku-pg-reset",
+            r"heroku\s+pg:reset\b",
+            "heroku pg:reset deletes all data in the Heroku Postgres database.",
+            High,
+            "pg:reset drops every table, view, and row in the database.\n\n\
+             Safer alternatives:\n\
+             - heroku pg:backups:capture: Take a backup first\n\
+             - heroku pg:info: Confirm which database DATABASE_URL points to"
+        ),
+        // Database overwrite
+        destructive_pattern!(
+            "heroku-pg-copy",
+            r"heroku\s+pg:(?:copy|pull|push)\b",
+            "heroku pg:copy/pg:push overwrite the target database.",
+            High,
+            "pg:copy replaces all data in the target database with the source. pg:push and \
+             pg:pull load a full database into the target.\n\n\
+             Safer alternatives:\n\
+             - heroku pg:backups:capture: Back up the target first\n\
+             - Copy into a freshly provisioned database instead"
+        ),
+        // Backup deletion
+        destructive_pattern!(
+            "heroku-pg-backups-delete",
+            r"heroku\s+pg:backups:(?:delete|cancel)\b",
+            "heroku pg:backups:delete permanently removes a database backup.",
+            High,
+            "Deleted backups cannot be restored; they may be the only copy of past data.\n\n\
+             Safer alternatives:\n\
+             - heroku pg:backups: List backups and their dates first"
+        ),
+        // Add-on destruction
+        destructive_pattern!(
+            "heroku-addons-destroy",
+            r"heroku\s+(?:addons:destroy|addons:remove|addons:detach)\b",
+            "heroku addons:destroy deletes the add-on and any data it holds.",
+            High,
+            "Destroying an add-on such as Heroku Postgres, Redis, or Kafka deletes its \
+             data. --confirm skips the prompt.\n\n\
+             Safer alternatives:\n\
+             - heroku addons:info <addon>: Check what the add-on is\n\
+             - Take a backup through the add-on first"
+        ),
+        // Config removal
+        destructive_pattern!(
+            "heroku-config-unset",
+            r"heroku\s+config:(?:unset|remove)\b",
+            "heroku config:unset removes config vars and restarts the app.",
+            Medium,
+            "Removing a config var restarts dynos without it; values cannot be recovered \
+             unless saved elsewhere.\n\n\
+             Safer alternatives:\n\
+             - heroku config -a <app> --shell > config.env: Save current values first"
+        ),
+        // Scale to zero
+        destructive_pattern!(
+            "heroku-ps-scale-zero",
+            r"heroku\s+ps:scale\b[^;&|]*=0\b",
+            "heroku ps:scale <type>=0 stops all dynos of that type.",
+            Medium,
+            "Scaling a process type to zero takes it offline until scaled back up.\n\n\
+             Safer alternatives:\n\
+             - heroku maintenance:on: Serve a maintenance page instead"
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::Severity;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_pack_creation() {
+        let pack = create_pack();
+        assert_eq!(pack.id, "paas.heroku");
+        assert!(pack.keywords.contains(&"heroku"));
+
+        assert_patterns_compile(&pack);
+        assert_all_patterns_have_reasons(&pack);
+        assert_unique_pattern_names(&pack);
+    }
+
+    #[test]
+    fn allows_safe_commands() {
+        let pack = create_pack();
+        assert_safe_pattern_matches(&pack, "heroku apps");
+        assert_safe_pattern_matches(&pack, "heroku pg:info -a web");
+        assert_safe_pattern_matches(&pack, "heroku pg:backups:capture -a web");
+        assert_allows(&pack, "heroku ps:scale web=2");
+        assert_allows(&pack, "heroku config:set FOO=bar");
+    }
+
+    #[test]
+    fn blocks_confirmation_bypass_as_critical() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "heroku apps:destroy -a web --confirm web",
+            "heroku-apps-destroy-confirm",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "heroku pg:reset DATABASE_URL --confirm=web",
+            "heroku-pg-reset-confirm",
+        );
+        assert_blocks_with_severity(
+            &pack,
+            "heroku pg:reset DATABASE_URL -a web --confirm web",
+            Severity::Critical,
+        );
+    }
+
+    #[test]
+    fn blocks_destructive_commands() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "heroku apps:destroy -a web", "heroku-apps-destroy");
+        assert_blocks_with_pattern(&pack, "heroku pg:reset -a web", "heroku-pg-reset");
+        assert_blocks_with_pattern(
+            &pack,
+            "heroku pg:copy src::DATABASE_URL DATABASE_URL -a web",
+            "heroku-pg-copy",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "heroku pg:backups:delete b101",
+            "heroku-pg-backups-delete",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "heroku addons:destroy heroku-postgresql",
+            "heroku-addons-destroy",
+        );
+        assert_blocks_with_pattern(&pack, "heroku config:unset API_KEY", "heroku-config-unset");
+        assert_blocks_with_pattern(&pack, "heroku ps:scale web=0", "heroku-ps-scale-zero");
+    }
+}
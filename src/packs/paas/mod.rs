@@ -0,0 +1,16 @@
+//! Platform-as-a-Service pack category.
+//!
+//! Provides protection for PaaS deployment CLIs:
+//! - Fly.io (`fly`/`flyctl`)
+//! - Heroku
+//! - Netlify (`netlify`/`ntl`)
+//! - Railway
+//! - Render
+//! - Vercel
+
+pub mod fly;
+pub mod heroku;
+pub mod netlify;
+pub mod railway;
+pub mod render;
+pub mod vercel;
@@ -0,0 +1,147 @@
+//! Netlify pack - protections for destructive Netlify CLI operations.
+//!
+//! Covers destructive operations:
+//! - Site deletion (`netlify sites:delete`)
+//! - Environment variable removal (`netlify env:unset`)
+//! - Blob deletion
+//!
+//! `--force`/`-f` skips the confirmation prompt, so those variants are
+//! Critical. Draft deploys and listing commands are allowed.
+
+use crate::packs::{DestructivePattern, Pack, SafePattern};
+use crate::{destructive_pattern, safe_pattern};
+
+/// Create the Netlify pack.
+#[must_use]
+pub fn create_pack() -> Pack {
+    Pack {
+        id: "paas.netlify".to_string(),
+        name: "Netlify",
+        description: "Protects against destructive Netlify CLI operations like deleting sites \
+                      and unsetting environment variables.",
+        keywords: &["netlify", "ntl"],
+        safe_patterns: create_safe_patterns(),
+        destructive_patterns: create_destructive_patterns(),
+        keyword_matcher: None,
+        safe_regex_set: None,
+        safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
+    }
+}
+
+fn create_safe_patterns() -> Vec<SafePattern> {
+    vec![
+        // Draft deploys (no --prod)
+        safe_pattern!(
+            "netlify-deploy-preview",
+            r"\b(?:netlify|ntl)\s+deploy\b(?![^;&|]*--prod\b)[^;&|]*$"
+        ),
+        // Listing and inspection
+        safe_pattern!(
+            "netlify-list",
+            r"\b(?:netlify|ntl)\s+(?:status|sites:list|env:list|env:get|logs(?::\w+)?|open(?::\w+)?|watch|whoami|api\s+list\w*)\b[^;&|]*$"
+        ),
+        // Version/help
+        safe_pattern!(
+            "netlify-version",
+            r"\b(?:netlify|ntl)\s+(?:--version|-v|help|--help|-h)\b[^;&|]*$"
+        ),
+    ]
+}
+
+fn create_destructive_patterns() -> Vec<DestructivePattern> {
+    vec![
+        // Site deletion without confirmation
+        destructive_pattern!(
+            "netlify-sites-delete-force",
+            r"\b(?:netlify|ntl)\s+sites:delete\b(?=[^;&|]*\s(?:--force|-f)\b)",
+            "netlify sites:delete --force deletes the site without a confirmation prompt.",
+            Critical,
+            "Deleting a Netlify site removes every deploy, form submission, environment \
+             variable, and domain assignment. --force skips the confirmation prompt.\n\n\
+             Safer alternatives:\n\
+             - netlify sites:list: Confirm the site ID\n\
+             - netlify env:list --json > env.json: Save environment variables first"
+        ),
+        // Site deletion
+        destructive_pattern!(
+            "netlify-sites-delete",
+            r"\b(?:netlify|ntl)\s+sites:delete\b",
+            "netlify sites:delete permanently deletes the site and all of its deploys.",
+            High,
+            "Deleting a Netlify site removes every deploy, form submission, environment \
+             variable, and domain assignment.\n\n\
+             Safer alternatives:\n\
+             - netlify sites:list: Confirm the site ID"
+        ),
+        // Env var removal
+        destructive_pattern!(
+            "netlify-env-unset",
+            r"\b(?:netlify|ntl)\s+env:(?:unset|delete|remove)\b",
+            "netlify env:unset removes an environment variable from the site.",
+            Medium,
+            "Removed values cannot be read back and the next deploy builds without them.\n\n\
+             Safer alternatives:\n\
+             - netlify env:get <key>: Save the current value first\n\
+             - netlify env:list: Check which contexts use the variable"
+        ),
+        // Blob deletion
+        destructive_pattern!(
+            "netlify-blobs-delete",
+            r"\b(?:netlify|ntl)\s+blobs:delete\b",
+            "netlify blobs:delete permanently deletes a blob from the store.",
+            Medium,
+            "Blob store entries are not versioned; a deleted blob cannot be restored.\n\n\
+             Safer alternatives:\n\
+             - netlify blobs:get <store> <key>: Save the value first"
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::Severity;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_pack_creation() {
+        let pack = create_pack();
+        assert_eq!(pack.id, "paas.netlify");
+        assert!(pack.keywords.contains(&"netlify"));
+
+        assert_patterns_compile(&pack);
+        assert_all_patterns_have_reasons(&pack);
+        assert_unique_pattern_names(&pack);
+    }
+
+    #[test]
+    fn allows_draft_deploys_and_listing() {
+        let pack = create_pack();
+        assert_safe_pattern_matches(&pack, "netlify deploy");
+        assert_safe_pattern_matches(&pack, "netlify deploy --dir dist --alias pr-12");
+        assert_safe_pattern_matches(&pack, "netlify env:list");
+        assert_safe_pattern_matches(&pack, "ntl status");
+        assert_no_safe_match(&pack, "netlify deploy --prod");
+        assert_allows(&pack, "netlify deploy --prod");
+    }
+
+    #[test]
+    fn blocks_destructive_commands() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "netlify sites:delete 1234-abcd --force",
+            "netlify-sites-delete-force",
+        );
+        assert_blocks_with_severity(&pack, "ntl sites:delete 1234 -f", Severity::Critical);
+        assert_blocks_with_pattern(&pack, "netlify sites:delete 1234", "netlify-sites-delete");
+        assert_blocks_with_pattern(&pack, "netlify env:unset API_KEY", "netlify-env-unset");
+        assert_blocks_with_pattern(
+            &pack,
+            "netlify blobs:delete uploads avatar.png",
+            "netlify-blobs-delete",
+        );
+    }
+}
@@ -0,0 +1,165 @@
+//! Railway pack - protections for destructive Railway CLI operations.
+//!
+//! Covers destructive operations:
+//! - Removing the latest deployment (`railway down`)
+//! - Project, environment, service, and volume deletion
+//! - Variable removal
+//!
+//! `-y`/`--yes` skips the confirmation prompt, so those variants are Critical.
+
+use crate::packs::{DestructivePattern, Pack, SafePattern};
+use crate::{destructive_pattern, safe_pattern};
+
+/// Create the Railway pack.
+#[must_use]
+pub fn create_pack() -> Pack {
+    Pack {
+        id: "paas.railway".to_string(),
+        name: "Railway",
+        description: "Protects against destructive Railway CLI operations like removing \
+                      deployments and deleting projects, environments, and volumes.",
+        keywords: &["railway"],
+        safe_patterns: create_safe_patterns(),
+        destructive_patterns: create_destructive_patterns(),
+        keyword_matcher: None,
+        safe_regex_set: None,
+        safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
+    }
+}
+
+fn create_safe_patterns() -> Vec<SafePattern> {
+    vec![
+        // Inspection
+        safe_pattern!(
+            "railway-status",
+            r"railway\s+(?:status|logs|list|whoami|domain|open|docs)(?:\s|$)"
+        ),
+        // Listing variables (flags only, no subcommand)
+        safe_pattern!(
+            "railway-variables-list",
+            r"railway\s+variables?(?:\s+-|\s*$)"
+        ),
+        // Version/help
+        safe_pattern!(
+            "railway-version",
+            r"railway\s+(?:--version|-V|version|help|--help|-h)\b"
+        ),
+    ]
+}
+
+fn create_destructive_patterns() -> Vec<DestructivePattern> {
+    vec![
+        // Project/environment/service/volume deletion without confirmation
+        destructive_pattern!(
+            "railway-delete-yes",
+            r"railway\s+(?:delete|(?:environment|env|service|volume)\s+(?:delete|remove|rm))\b(?=[^;&|]*\s(?:-y|--yes)\b)",
+            "railway delete --yes removes Railway resources without a confirmation prompt.",
+            Critical,
+            "Deleting a Railway project removes every service, database, and volume in it; \
+             deleting an environment or volume removes its deployments and data. --yes \
+             skips the only safety prompt.\n\n\
+             Safer alternatives:\n\
+             - railway status: Confirm the linked project and environment\n\
+             - Back up database volumes before deleting"
+        ),
+        // Project/environment/service/volume deletion
+        destructive_pattern!(
+            "railway-delete",
+            r"railway\s+(?:delete|(?:environment|env|service|volume)\s+(?:delete|remove|rm))\b",
+            "railway delete removes a Railway project, environment, service, or volume.",
+            High,
+            "Deleting a Railway project removes every service, database, and volume in it; \
+             deleting an environment or volume removes its deployments and data.\n\n\
+             Safer alternatives:\n\
+             - railway status: Confirm the linked project and environment"
+        ),
+        // Remove latest deployment without confirmation
+        destructive_pattern!(
+            "railway-down-yes",
+            r"railway\s+down\b(?=[^;&|]*\s(?:-y|--yes)\b)",
+            "railway down --yes removes the latest deployment without a confirmation prompt.",
+            Critical,
+            "railway down removes the most recent deployment of the linked service, taking \
+             it offline. --yes skips the confirmation prompt.\n\n\
+             Safer alternatives:\n\
+             - railway status: Confirm the linked service and environment\n\
+             - railway redeploy: Roll forward instead"
+        ),
+        // Remove latest deployment
+        destructive_pattern!(
+            "railway-down",
+            r"railway\s+down\b",
+            "railway down removes the latest deployment and takes the service offline.",
+            High,
+            "railway down removes the most recent deployment of the linked service.\n\n\
+             Safer alternatives:\n\
+             - railway status: Confirm the linked service and environment"
+        ),
+        // Variable removal
+        destructive_pattern!(
+            "railway-variables-delete",
+            r"railway\s+variables?\s+(?:delete|remove|rm|unset)\b",
+            "railway variables delete removes environment variables from the service.",
+            Medium,
+            "Removed variables cannot be read back and the service redeploys without them.\n\n\
+             Safer alternatives:\n\
+             - railway variables --kv > vars.env: Save current values first"
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::Severity;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_pack_creation() {
+        let pack = create_pack();
+        assert_eq!(pack.id, "paas.railway");
+        assert!(pack.keywords.contains(&"railway"));
+
+        assert_patterns_compile(&pack);
+        assert_all_patterns_have_reasons(&pack);
+        assert_unique_pattern_names(&pack);
+    }
+
+    #[test]
+    fn allows_safe_commands() {
+        let pack = create_pack();
+        assert_safe_pattern_matches(&pack, "railway status");
+        assert_safe_pattern_matches(&pack, "railway logs --deployment");
+        assert_safe_pattern_matches(&pack, "railway --version");
+        assert_safe_pattern_matches(&pack, "railway variables --kv");
+        assert_allows(&pack, "railway up");
+        assert_allows(&pack, "railway link");
+    }
+
+    #[test]
+    fn blocks_destructive_commands() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "railway down", "railway-down");
+        assert_blocks_with_pattern(&pack, "railway down --yes", "railway-down-yes");
+        assert_blocks_with_severity(&pack, "railway down -y", Severity::Critical);
+        assert_blocks_with_pattern(&pack, "railway delete", "railway-delete");
+        assert_blocks_with_pattern(&pack, "railway delete -y", "railway-delete-yes");
+        assert_blocks_with_pattern(
+            &pack,
+            "railway environment delete staging",
+            "railway-delete",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "railway volume delete --volume data --yes",
+            "railway-delete-yes",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "railway variables delete API_KEY",
+            "railway-variables-delete",
+        );
+    }
+}
@@ -0,0 +1,140 @@
+//! Render pack - protections for destructive Render CLI operations.
+//!
+//! Covers destructive operations:
+//! - Service deletion (`render services delete`)
+//! - Postgres and key-value store deletion
+//!
+//! `--confirm`/`-y`/`--yes` skips the confirmation prompt, so those variants
+//! are Critical.
+
+use crate::packs::{DestructivePattern, Pack, SafePattern};
+use crate::{destructive_pattern, safe_pattern};
+
+/// Create the Render pack.
+#[must_use]
+pub fn create_pack() -> Pack {
+    Pack {
+        id: "paas.render".to_string(),
+        name: "Render",
+        description: "Protects against destructive Render CLI operations like deleting \
+                      services and databases.",
+        keywords: &["render"],
+        safe_patterns: create_safe_patterns(),
+        destructive_patterns: create_destructive_patterns(),
+        keyword_matcher: None,
+        safe_regex_set: None,
+        safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
+    }
+}
+
+fn create_safe_patterns() -> Vec<SafePattern> {
+    vec![
+        // Listing and inspection
+        safe_pattern!(
+            "render-list",
+            r"\brender\s+(?:services|deploys|postgres|keyvalue|redis|jobs|environments|projects)(?:\s+(?:list|get|show))?(?:\s+-\S+(?:\s+\S+)?)*\s*$"
+        ),
+        safe_pattern!("render-logs", r"\brender\s+(?:logs|whoami|workspace)\b"),
+        // Version/help
+        safe_pattern!(
+            "render-version",
+            r"\brender\s+(?:--version|version|help|--help|-h)\b"
+        ),
+    ]
+}
+
+fn create_destructive_patterns() -> Vec<DestructivePattern> {
+    vec![
+        // Service deletion without confirmation
+        destructive_pattern!(
+            "render-services-delete-confirm",
+            r"\brender\s+services?\s+(?:delete|rm)\b(?=[^;&|]*\s(?:--confirm|-y|--yes)\b)",
+            "render services delete --confirm removes the service without a confirmation prompt.",
+            Critical,
+            "Deleting a Render service removes its deploys, custom domains, environment \
+             variables, and attached disks (with their data). The confirmation flag skips \
+             the only safety prompt.\n\n\
+             Safer alternatives:\n\
+             - render services --output json: Confirm the service ID\n\
+             - Suspend the service in the dashboard instead of deleting it"
+        ),
+        // Service deletion
+        destructive_pattern!(
+            "render-services-delete",
+            r"\brender\s+services?\s+(?:delete|rm)\b",
+            "render services delete removes the service, its disks, and its configuration.",
+            High,
+            "Deleting a Render service removes its deploys, custom domains, environment \
+             variables, and attached disks (with their data).\n\n\
+             Safer alternatives:\n\
+             - render services --output json: Confirm the service ID\n\
+             - Suspend the service instead of deleting it"
+        ),
+        // Datastore deletion
+        destructive_pattern!(
+            "render-datastore-delete",
+            r"\brender\s+(?:postgres|keyvalue|redis)\s+(?:delete|rm)\b",
+            "render postgres/keyvalue delete permanently deletes the datastore and its data.",
+            Critical,
+            "Deleting a Render Postgres or Key Value instance deletes its data and \
+             backups.\n\n\
+             Safer alternatives:\n\
+             - Export the data (pg_dump) before deleting"
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::Severity;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_pack_creation() {
+        let pack = create_pack();
+        assert_eq!(pack.id, "paas.render");
+        assert!(pack.keywords.contains(&"render"));
+
+        assert_patterns_compile(&pack);
+        assert_all_patterns_have_reasons(&pack);
+        assert_unique_pattern_names(&pack);
+    }
+
+    #[test]
+    fn allows_safe_commands() {
+        let pack = create_pack();
+        assert_safe_pattern_matches(&pack, "render services");
+        assert_safe_pattern_matches(&pack, "render services --output json");
+        assert_safe_pattern_matches(&pack, "render logs -r srv-123");
+        assert_allows(&pack, "render deploys create srv-123");
+        assert_allows(&pack, "npm run render");
+    }
+
+    #[test]
+    fn blocks_destructive_commands() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "render services delete srv-123",
+            "render-services-delete",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "render services delete srv-123 --confirm",
+            "render-services-delete-confirm",
+        );
+        assert_blocks_with_severity(
+            &pack,
+            "render services delete srv-123 --yes",
+            Severity::Critical,
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "render postgres delete dpg-123",
+            "render-datastore-delete",
+        );
+    }
+}
@@ -0,0 +1,164 @@
+//! Vercel pack - protections for destructive Vercel CLI operations.
+//!
+//! Covers destructive operations:
+//! - Deployment and project removal (`vercel remove`, `vercel project rm`)
+//! - Environment variable removal (`vercel env rm`)
+//! - Domain, alias, and DNS record removal
+//!
+//! `--yes`/`-y` skips the confirmation prompt, so `vercel remove --yes` is
+//! Critical. Preview deploys and listing commands are allowed.
+
+use crate::packs::{DestructivePattern, Pack, SafePattern};
+use crate::{destructive_pattern, safe_pattern};
+
+/// Create the Vercel pack.
+#[must_use]
+pub fn create_pack() -> Pack {
+    Pack {
+        id: "paas.vercel".to_string(),
+        name: "Vercel",
+        description: "Protects against destructive Vercel CLI operations like removing \
+                      deployments and projects or deleting environment variables.",
+        keywords: &["vercel"],
+        safe_patterns: create_safe_patterns(),
+        destructive_patterns: create_destructive_patterns(),
+        keyword_matcher: None,
+        safe_regex_set: None,
+        safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
+    }
+}
+
+fn create_safe_patterns() -> Vec<SafePattern> {
+    vec![
+        // Preview deploys (no --prod)
+        safe_pattern!(
+            "vercel-deploy-preview",
+            r"\bvercel(?:\s+deploy\b|\s*$)(?![^;&|]*--prod\b)[^;&|]*$"
+        ),
+        // Listing and inspection
+        safe_pattern!(
+            "vercel-list",
+            r"\bvercel\s+(?:ls|list|inspect|logs|whoami|env\s+(?:ls|list|pull)|project\s+(?:ls|list)|domains\s+(?:ls|inspect)|alias\s+ls)\b[^;&|]*$"
+        ),
+        // Version/help
+        safe_pattern!(
+            "vercel-version",
+            r"\bvercel\s+(?:--version|-v|help|--help|-h)\b[^;&|]*$"
+        ),
+    ]
+}
+
+fn create_destructive_patterns() -> Vec<DestructivePattern> {
+    vec![
+        // Removal without confirmation
+        destructive_pattern!(
+            "vercel-remove-yes",
+            r"\bvercel\s+(?:remove|rm)\b(?=[^;&|]*\s(?:--yes|-y)\b)",
+            "vercel remove --yes deletes deployments or whole projects without a confirmation prompt.",
+            Critical,
+            "vercel remove deletes the named deployments; given a project name it removes \
+             every deployment of that project, taking production offline. --yes skips the \
+             confirmation prompt.\n\n\
+             Safer alternatives:\n\
+             - vercel ls <project>: Check which deployments would be removed\n\
+             - vercel remove <project> --safe: Skip deployments with active aliases\n\
+             - vercel rollback: Restore a previous production deployment instead"
+        ),
+        // Removal
+        destructive_pattern!(
+            "vercel-remove",
+            r"\bvercel\s+(?:remove|rm)\b",
+            "vercel remove deletes deployments; with a project name it removes all of them.",
+            High,
+            "vercel remove deletes the named deployments; given a project name it removes \
+             every deployment of that project.\n\n\
+             Safer alternatives:\n\
+             - vercel ls <project>: Check which deployments would be removed\n\
+             - vercel remove <project> --safe: Skip deployments with active aliases"
+        ),
+        // Project deletion
+        destructive_pattern!(
+            "vercel-project-rm",
+            r"\bvercel\s+projects?\s+(?:rm|remove)\b",
+            "vercel project rm deletes the project, its deployments, domains, and settings.",
+            Critical,
+            "Removing a Vercel project deletes every deployment, its environment variables, \
+             domain assignments, and build settings. This cannot be undone.\n\n\
+             Safer alternatives:\n\
+             - vercel project ls: Confirm the project name\n\
+             - vercel env pull: Save environment variables first"
+        ),
+        // Env var removal
+        destructive_pattern!(
+            "vercel-env-rm",
+            r"\bvercel\s+env\s+(?:rm|remove)\b",
+            "vercel env rm removes an environment variable from the project.",
+            Medium,
+            "Removed values cannot be read back and the next deployment builds without \
+             them.\n\n\
+             Safer alternatives:\n\
+             - vercel env pull .env.backup: Save current values first\n\
+             - vercel env ls: Check which environments use the variable"
+        ),
+        // Domains, aliases, DNS
+        destructive_pattern!(
+            "vercel-domain-rm",
+            r"\bvercel\s+(?:domains?|alias|aliases|dns|certs?)\s+(?:rm|remove)\b",
+            "vercel domains/alias/dns rm removes routing for live traffic.",
+            High,
+            "Removing a domain, alias, DNS record, or certificate stops traffic from \
+             reaching the deployment.\n\n\
+             Safer alternatives:\n\
+             - vercel domains inspect <domain>: Check what is attached first"
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::Severity;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_pack_creation() {
+        let pack = create_pack();
+        assert_eq!(pack.id, "paas.vercel");
+        assert!(pack.keywords.contains(&"vercel"));
+
+        assert_patterns_compile(&pack);
+        assert_all_patterns_have_reasons(&pack);
+        assert_unique_pattern_names(&pack);
+    }
+
+    #[test]
+    fn allows_preview_deploys_and_listing() {
+        let pack = create_pack();
+        assert_safe_pattern_matches(&pack, "vercel");
+        assert_safe_pattern_matches(&pack, "vercel deploy");
+        assert_safe_pattern_matches(&pack, "vercel deploy --prebuilt");
+        assert_safe_pattern_matches(&pack, "vercel env ls");
+        assert_safe_pattern_matches(&pack, "vercel env pull .env.local");
+        assert_safe_pattern_matches(&pack, "vercel ls my-app");
+        assert_no_safe_match(&pack, "vercel deploy --prod");
+        assert_allows(&pack, "vercel deploy --prod");
+    }
+
+    #[test]
+    fn blocks_destructive_commands() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "vercel remove my-app --yes", "vercel-remove-yes");
+        assert_blocks_with_severity(&pack, "vercel rm my-app -y", Severity::Critical);
+        assert_blocks_with_pattern(&pack, "vercel remove my-app", "vercel-remove");
+        assert_blocks_with_pattern(&pack, "vercel project rm my-app", "vercel-project-rm");
+        assert_blocks_with_pattern(&pack, "vercel env rm API_KEY production", "vercel-env-rm");
+        assert_blocks_with_pattern(&pack, "vercel domains rm example.com", "vercel-domain-rm");
+        assert_blocks_with_pattern(
+            &pack,
+            "vercel deploy && vercel remove my-app --yes",
+            "vercel-remove-yes",
+        );
+    }
+}
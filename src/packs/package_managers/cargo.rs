@@ -0,0 +1,172 @@
+//! Cargo patterns - protections against destructive cargo and rustup operations.
+//!
+//! This includes patterns for:
+//! - `cargo publish` without `--dry-run` and `cargo yank` (crates.io is append-only)
+//! - `cargo clean` of the whole target directory (log-only; raised to a warning
+//!   inside `[protection.workspaces]`)
+//! - `rustup self uninstall` and `rustup toolchain uninstall`
+
+use crate::packs::{DestructivePattern, Pack, SafePattern};
+use crate::{destructive_pattern, safe_pattern};
+
+/// Create the Cargo pack.
+#[must_use]
+pub fn create_pack() -> Pack {
+    Pack {
+        id: "package_managers.cargo".to_string(),
+        name: "Cargo",
+        description: "Protects against destructive cargo and rustup operations like publish \
+                      without --dry-run, yank, cargo clean in protected workspaces, and \
+                      rustup self uninstall",
+        keywords: &["cargo", "rustup"],
+        safe_patterns: create_safe_patterns(),
+        destructive_patterns: create_destructive_patterns(),
+        keyword_matcher: None,
+        safe_regex_set: None,
+        safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
+    }
+}
+
+fn create_safe_patterns() -> Vec<SafePattern> {
+    // Anchored to the whole command so a build cannot whitelist a chained
+    // `cargo publish`. `+toolchain` may precede the subcommand.
+    vec![
+        // build/test and other commands that only write to target/
+        safe_pattern!(
+            "cargo-build-test",
+            r"^\s*cargo\s+(?:\+\S+\s+)?(?:build|b|test|t|check|c|clippy|fmt|doc|d|bench|run|r|tree|metadata)\b[^;&|\n$`]*$"
+        ),
+        // clean -p only removes the named packages' artifacts
+        safe_pattern!(
+            "cargo-clean-package",
+            r"^\s*cargo\s+(?:\+\S+\s+)?clean\b[^;&|\n$`]*\s(?:-p|--package)(?:\s|=)[^;&|\n$`]*$"
+        ),
+        // dry runs
+        safe_pattern!(
+            "cargo-dry-run",
+            r"^\s*cargo\s+(?:\+\S+\s+)?(?:publish|clean)\b[^;&|\n$`]*\s(?:--dry-run|-n)\b[^;&|\n$`]*$"
+        ),
+        safe_pattern!(
+            "rustup-read",
+            r"^\s*rustup\s+(?:show|check|which|--version|-V|toolchain\s+list|target\s+list|component\s+list)\b[^;&|\n$`]*$"
+        ),
+    ]
+}
+
+fn create_destructive_patterns() -> Vec<DestructivePattern> {
+    vec![
+        destructive_pattern!(
+            "cargo-publish",
+            r"\bcargo\s+(?:\+\S+\s+)?publish\b(?![^;&|\n]*\s(?:--dry-run|-n)\b)",
+            "cargo publish uploads the crate to the registry permanently. Use --dry-run first.",
+            High,
+            "crates.io never deletes a published version: it can be yanked, but the source \
+             stays downloadable and the version number is used up for good. Anything \
+             accidentally included (secrets, large files, private code) is public.\n\n\
+             Check the package contents first:\n  \
+             cargo package --list\n\n\
+             Rehearse the upload:\n  \
+             cargo publish --dry-run"
+        ),
+        destructive_pattern!(
+            "cargo-yank",
+            r"\bcargo\s+(?:\+\S+\s+)?yank\b(?![^;&|\n]*\s--undo\b)",
+            "cargo yank stops new projects from resolving this version. Dependents without a lockfile break.",
+            High,
+            "A yanked version stays downloadable for existing Cargo.lock files, but new \
+             resolutions skip it. Projects that require exactly that version, or have no \
+             lockfile yet, fail to build.\n\n\
+             Undo with:\n  \
+             cargo yank --version <version> --undo <crate>"
+        ),
+        destructive_pattern!(
+            "cargo-clean",
+            r"\bcargo\s+(?:\+\S+\s+)?clean\b(?![^;&|\n]*\s(?:-p|--package|--dry-run|-n)\b)",
+            "cargo clean deletes the whole target directory; the next build starts from scratch.",
+            Low,
+            "cargo clean removes every build artifact in target/ (or --target-dir). In a \
+             large workspace the next build can take a long time, and incremental caches, \
+             coverage data and benchmark baselines stored there are lost.\n\n\
+             Remove one package's artifacts instead:\n  \
+             cargo clean -p <package>\n\n\
+             See what would be removed:\n  \
+             cargo clean --dry-run"
+        ),
+        destructive_pattern!(
+            "rustup-self-uninstall",
+            r"\brustup\s+self\s+uninstall\b",
+            "rustup self uninstall removes rustup, every toolchain, and ~/.cargo including installed binaries.",
+            High,
+            "rustup self uninstall deletes RUSTUP_HOME and CARGO_HOME: every toolchain, \
+             the registry cache, cargo credentials, and all binaries installed with \
+             cargo install. -y skips the confirmation prompt.\n\n\
+             Remove a single toolchain instead:\n  \
+             rustup toolchain uninstall <toolchain>"
+        ),
+        destructive_pattern!(
+            "rustup-toolchain-uninstall",
+            r"\brustup\s+(?:toolchain\s+(?:uninstall|remove)|uninstall)\b",
+            "rustup toolchain uninstall removes the toolchain; projects pinned to it stop building.",
+            Medium,
+            "Projects whose rust-toolchain.toml pins the removed toolchain fail until it \
+             is installed again, and custom linked toolchains cannot be re-downloaded.\n\n\
+             Check what is installed and in use:\n  \
+             rustup toolchain list\n  \
+             rustup show"
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::Severity;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_pack_creation() {
+        let pack = create_pack();
+        assert_eq!(pack.id, "package_managers.cargo");
+        assert!(pack.keywords.contains(&"cargo"));
+        assert!(pack.keywords.contains(&"rustup"));
+
+        assert_patterns_compile(&pack);
+        assert_all_patterns_have_reasons(&pack);
+        assert_unique_pattern_names(&pack);
+    }
+
+    #[test]
+    fn allows_build_and_scoped_clean() {
+        let pack = create_pack();
+        assert_safe_pattern_matches(&pack, "cargo build --release");
+        assert_safe_pattern_matches(&pack, "cargo +nightly test --workspace");
+        assert_safe_pattern_matches(&pack, "cargo clean -p my-crate");
+        assert_safe_pattern_matches(&pack, "cargo publish --dry-run");
+        assert_no_safe_match(&pack, "cargo build && cargo publish");
+        assert_allows(&pack, "cargo clean --package=my-crate --release");
+        assert_allows(&pack, "cargo yank --version 1.0.1 --undo my-crate");
+        assert_allows(&pack, "rustup toolchain install nightly");
+    }
+
+    #[test]
+    fn blocks_destructive_commands() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "cargo publish", "cargo-publish");
+        assert_blocks_with_pattern(
+            &pack,
+            "cargo publish -p core --allow-dirty",
+            "cargo-publish",
+        );
+        assert_blocks_with_pattern(&pack, "cargo yank --version 1.0.1 my-crate", "cargo-yank");
+        assert_blocks_with_pattern(&pack, "cargo clean", "cargo-clean");
+        assert_blocks_with_severity(&pack, "cargo clean --release", Severity::Low);
+        assert_blocks_with_pattern(&pack, "rustup self uninstall -y", "rustup-self-uninstall");
+        assert_blocks_with_pattern(
+            &pack,
+            "rustup toolchain uninstall nightly-2024-01-01",
+            "rustup-toolchain-uninstall",
+        );
+    }
+}
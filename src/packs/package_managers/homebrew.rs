@@ -0,0 +1,152 @@
+//! Homebrew patterns - protections against destructive Homebrew operations.
+//!
+//! This includes patterns for:
+//! - forced uninstalls and cask `--zap` (deletes app data, not just the app)
+//! - autoremove and cache pruning
+//! - removing taps
+
+use crate::packs::{DestructivePattern, Pack, SafePattern};
+use crate::{destructive_pattern, safe_pattern};
+
+/// Create the Homebrew pack.
+#[must_use]
+pub fn create_pack() -> Pack {
+    Pack {
+        id: "package_managers.homebrew".to_string(),
+        name: "Homebrew",
+        description: "Protects against destructive Homebrew operations like forced uninstalls, \
+                      cask zaps, autoremove, cache pruning, and untapping",
+        keywords: &["brew"],
+        safe_patterns: create_safe_patterns(),
+        destructive_patterns: create_destructive_patterns(),
+        keyword_matcher: None,
+        safe_regex_set: None,
+        safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
+    }
+}
+
+fn create_safe_patterns() -> Vec<SafePattern> {
+    vec![
+        // install and read-only commands
+        safe_pattern!(
+            "brew-install",
+            r"\bbrew\s+(?:install|reinstall|upgrade|update|fetch)\b"
+        ),
+        safe_pattern!(
+            "brew-read",
+            r"\bbrew\s+(?:list|ls|info|search|deps|uses|leaves|outdated|doctor|config|desc|home|missing|tap-info|--version)\b"
+        ),
+        // dry runs
+        safe_pattern!(
+            "brew-dry-run",
+            r"\bbrew\s+(?:cleanup|autoremove)\s+(?:.*\s)?(?:-n|--dry-run)\b"
+        ),
+    ]
+}
+
+fn create_destructive_patterns() -> Vec<DestructivePattern> {
+    vec![
+        destructive_pattern!(
+            "brew-uninstall-zap",
+            r"\bbrew\s+(?:uninstall|remove|rm)\s+(?:.*\s)?--zap\b",
+            "brew uninstall --zap deletes the cask's preferences, caches, and support files too.",
+            High,
+            "--zap runs the cask's zap stanza after uninstalling: it removes files under \
+             ~/Library (Application Support, Preferences, Caches, Containers, ...) that \
+             the app created. Those often hold settings, local databases, and licenses \
+             that reinstalling the cask does not bring back.\n\n\
+             Uninstall just the app:\n  \
+             brew uninstall --cask <name>\n\n\
+             See what zap would delete:\n  \
+             brew info --cask <name>"
+        ),
+        destructive_pattern!(
+            "brew-uninstall-force",
+            r"\bbrew\s+(?:uninstall|remove|rm)\s+(?:.*\s)?(?:--force|-f|--ignore-dependencies)\b",
+            "brew uninstall --force removes every installed version, even ones other formulae need.",
+            High,
+            "--force deletes all installed versions of the formula instead of only the \
+             linked one, and --ignore-dependencies skips the check that other installed \
+             formulae depend on it. Either can leave dependents broken.\n\n\
+             Check what depends on it first:\n  \
+             brew uses --installed <formula>\n\n\
+             Remove only the current version:\n  \
+             brew uninstall <formula>"
+        ),
+        destructive_pattern!(
+            "brew-uninstall",
+            r"\bbrew\s+(?:uninstall|remove|rm)\b",
+            "brew uninstall removes packages. Verify no dependent packages are affected.",
+            Medium
+        ),
+        destructive_pattern!(
+            "brew-autoremove",
+            r"\bbrew\s+autoremove\b",
+            "brew autoremove uninstalls every formula that was only installed as a dependency.",
+            Medium,
+            "brew autoremove removes formulae that nothing installed on request depends \
+             on. Tools you use directly but installed indirectly (or whose dependents \
+             were removed) disappear as well.\n\n\
+             Preview first:\n  \
+             brew autoremove --dry-run"
+        ),
+        destructive_pattern!(
+            "brew-cleanup-prune-all",
+            r"\bbrew\s+cleanup\s+(?:.*\s)?--prune[=\s]+(?:all|0)\b",
+            "brew cleanup --prune=all deletes every cached download and old version.",
+            Medium
+        ),
+        destructive_pattern!(
+            "brew-untap",
+            r"\bbrew\s+untap\b",
+            "brew untap removes a tap; formulae and casks from it can no longer be upgraded.",
+            Medium
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn blocks_destructive_brew_operations() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "brew uninstall --cask --zap visual-studio-code",
+            "brew-uninstall-zap",
+        );
+        assert_blocks_with_pattern(&pack, "brew uninstall --force node", "brew-uninstall-force");
+        assert_blocks_with_pattern(
+            &pack,
+            "brew rm --ignore-dependencies openssl@3",
+            "brew-uninstall-force",
+        );
+        assert_blocks_with_pattern(&pack, "brew uninstall wget", "brew-uninstall");
+        assert_blocks_with_pattern(&pack, "brew autoremove", "brew-autoremove");
+        assert_blocks_with_pattern(&pack, "brew cleanup --prune=all", "brew-cleanup-prune-all");
+        assert_blocks_with_pattern(&pack, "brew untap homebrew/cask-fonts", "brew-untap");
+    }
+
+    #[test]
+    fn allows_install_queries_and_dry_runs() {
+        let pack = create_pack();
+        assert_allows(&pack, "brew install ripgrep");
+        assert_allows(&pack, "brew list --versions");
+        assert_allows(&pack, "brew info --cask firefox");
+        assert_allows(&pack, "brew autoremove --dry-run");
+        assert_allows(&pack, "brew cleanup -n --prune=all");
+        assert_allows(&pack, "brew cleanup");
+    }
+
+    #[test]
+    fn keyword_absent_skips_pack() {
+        let pack = create_pack();
+        assert!(!pack.might_match("echo hello"));
+        assert!(pack.check("echo hello").is_none());
+    }
+}
@@ -2,9 +2,22 @@
 //!
 //! This pack provides protection against dangerous package manager operations:
 //! - npm/yarn/pnpm publish without verification
+//! - npm unpublish/deprecate and forced publishes
+//! - yarn/pnpm cache and store wipes
 //! - pip install from untrusted sources
 //! - apt/yum remove critical packages
 //! - cargo publish
+//!
+//! `npm run` / `yarn run` / `pnpm run` scripts are resolved from package.json
+//! by the evaluator (see [`crate::scripts`]), so a script whose body is
+//! destructive is blocked under the rule its body matches.
+//!
+//! Sub-packs cover package managers with their own command sets (e.g. `nix`,
+//! `homebrew`, `cargo`).
+
+pub mod cargo;
+pub mod homebrew;
+pub mod nix;
 
 use crate::packs::{DestructivePattern, Pack, SafePattern};
 use crate::{destructive_pattern, safe_pattern};
@@ -26,6 +39,8 @@ pub fn create_pack() -> Pack {
         keyword_matcher: None,
         safe_regex_set: None,
         safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
     }
 }
 
@@ -62,6 +77,13 @@ fn create_safe_patterns() -> Vec<SafePattern> {
 
 fn create_destructive_patterns() -> Vec<DestructivePattern> {
     vec![
+        // --force republishes over registry warnings; checked before plain publish
+        destructive_pattern!(
+            "npm-publish-force",
+            r"npm\s+publish\b(?!.*--dry-run)[^;&|\n]*\s(?:--force|-f)\b",
+            "npm publish --force bypasses npm's safety checks and publishes anyway.",
+            Critical
+        ),
         // npm/yarn/pnpm publish
         destructive_pattern!(
             "npm-publish",
@@ -78,12 +100,40 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
             r"pnpm\s+publish\b(?!.*--dry-run)",
             "pnpm publish releases a package publicly."
         ),
+        // unpublish --force removes every version of the package
+        destructive_pattern!(
+            "npm-unpublish-force",
+            r"npm\s+unpublish\b[^;&|\n]*\s(?:--force|-f)\b",
+            "npm unpublish --force removes the entire package from the registry.",
+            Critical
+        ),
         // npm unpublish
         destructive_pattern!(
             "npm-unpublish",
             r"npm\s+unpublish\b",
             "npm unpublish removes a published package. This can break dependent projects."
         ),
+        // deprecate warns every installer of the matching versions
+        destructive_pattern!(
+            "npm-deprecate",
+            r"npm\s+deprecate\b",
+            "npm deprecate shows a deprecation warning to everyone installing the package.",
+            Medium
+        ),
+        // cache/store wipes force every project to re-download dependencies
+        destructive_pattern!(
+            "yarn-cache-clean-all",
+            r"yarn\s+cache\s+clean\b[^;&|\n]*\s--all\b",
+            "yarn cache clean --all deletes the shared and mirror caches for every project.",
+            Medium
+        ),
+        destructive_pattern!(
+            "pnpm-store-prune-force",
+            r"pnpm\s+store\s+prune\b[^;&|\n]*\s--force\b",
+            "pnpm store prune --force removes packages from the content-addressable store \
+             even if other projects still reference them.",
+            Medium
+        ),
         // pip uninstall
         destructive_pattern!(
             "pip-uninstall",
@@ -172,7 +222,7 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::packs::test_helpers::assert_blocks;
+    use crate::packs::test_helpers::{assert_allows, assert_blocks, assert_blocks_with_pattern};
 
     #[test]
     fn brew_uninstall_is_reachable_via_keywords() {
@@ -200,6 +250,25 @@ mod tests {
         assert_blocks(&pack, "pip3 uninstall requests", "pip uninstall");
     }
 
+    #[test]
+    fn npm_yarn_pnpm_registry_and_cache_operations_block() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "npm publish --force", "npm-publish-force");
+        assert_blocks_with_pattern(&pack, "npm publish", "npm-publish");
+        assert_blocks_with_pattern(&pack, "npm unpublish pkg --force", "npm-unpublish-force");
+        assert_blocks_with_pattern(&pack, "npm unpublish pkg@1.0.0", "npm-unpublish");
+        assert_blocks_with_pattern(
+            &pack,
+            "npm deprecate pkg@\"<2\" \"use v2\"",
+            "npm-deprecate",
+        );
+        assert_blocks_with_pattern(&pack, "yarn cache clean --all", "yarn-cache-clean-all");
+        assert_blocks_with_pattern(&pack, "pnpm store prune --force", "pnpm-store-prune-force");
+        assert_allows(&pack, "yarn cache clean lodash");
+        assert_allows(&pack, "pnpm store prune");
+        assert_allows(&pack, "npm publish --force --dry-run");
+    }
+
     #[test]
     fn keyword_absent_skips_pack() {
         let pack = create_pack();
@@ -0,0 +1,218 @@
+//! Nix patterns - protections against destructive Nix and `NixOS` operations.
+//!
+//! This includes patterns for:
+//! - garbage collection that deletes old generations (rollback targets)
+//! - uninstalling packages and deleting generations from nix-env profiles
+//! - deleting store paths directly
+//! - rolling back the running `NixOS` system
+
+use crate::packs::{DestructivePattern, Pack, SafePattern};
+use crate::{destructive_pattern, safe_pattern};
+
+/// Create the Nix pack.
+#[must_use]
+pub fn create_pack() -> Pack {
+    Pack {
+        id: "package_managers.nix".to_string(),
+        name: "Nix",
+        description: "Protects against destructive Nix operations like deleting old generations, \
+                      wiping profiles, deleting store paths, and rolling back NixOS",
+        keywords: &[
+            "nix",
+            "nix-env",
+            "nix-store",
+            "nix-collect-garbage",
+            "nixos-rebuild",
+        ],
+        safe_patterns: create_safe_patterns(),
+        destructive_patterns: create_destructive_patterns(),
+        keyword_matcher: None,
+        safe_regex_set: None,
+        safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
+    }
+}
+
+fn create_safe_patterns() -> Vec<SafePattern> {
+    vec![
+        // building and entering environments does not touch profiles or the GC roots
+        safe_pattern!(
+            "nix-build-develop",
+            r"\bnix\s+(?:build|develop|shell|run|search|eval|log|repl|fmt|why-depends|path-info)\b"
+        ),
+        safe_pattern!(
+            "nix-flake-inspect",
+            r"\bnix\s+flake\s+(?:show|check|metadata|info)\b"
+        ),
+        safe_pattern!("nix-legacy-build", r"\bnix-(?:build|shell|instantiate)\b"),
+        // queries
+        safe_pattern!(
+            "nix-env-query",
+            r"\bnix-env\s+(?:-q|--query|--list-generations)\b"
+        ),
+        safe_pattern!("nix-store-query", r"\bnix-store\s+(?:-q|--query)\b"),
+        safe_pattern!(
+            "nix-profile-list",
+            r"\bnix\s+profile\s+(?:list|history|diff-closures)\b"
+        ),
+        // dry runs
+        safe_pattern!(
+            "nix-dry-run",
+            r"\b(?:nix|nix-env|nix-store|nix-collect-garbage)\b.*\s--dry-run\b"
+        ),
+        safe_pattern!(
+            "nixos-rebuild-dry",
+            r"\bnixos-rebuild\s+(?:dry-build|dry-activate|build|build-vm|list-generations)\b"
+        ),
+    ]
+}
+
+fn create_destructive_patterns() -> Vec<DestructivePattern> {
+    vec![
+        destructive_pattern!(
+            "nix-collect-garbage-delete-old",
+            r"\bnix-collect-garbage\s+(?:.*\s)?(?:-d|--delete-old|--delete-older-than)\b",
+            "nix-collect-garbage -d deletes every old profile generation, removing all rollback targets.",
+            High,
+            "With -d (or --delete-old / --delete-older-than) nix-collect-garbage first \
+             deletes old generations of every profile, then collects the store paths they \
+             kept alive. Afterwards there is nothing to roll back to, and the next build \
+             may have to re-download or rebuild large closures.\n\n\
+             Preview what would be removed:\n  \
+             nix-collect-garbage -d --dry-run\n\n\
+             Collect garbage without touching generations:\n  \
+             nix-collect-garbage"
+        ),
+        destructive_pattern!(
+            "nix-env-uninstall-all",
+            r#"\bnix-env\s+(?:.*\s)?(?:-e|--uninstall)\s+(?:'\.\*'|"\.\*"|\.\*|'\*'|"\*")"#,
+            "nix-env -e '.*' uninstalls every package from the profile.",
+            Critical,
+            "The argument to nix-env -e is a regular expression, so '.*' matches every \
+             installed package and empties the profile, including tools the shell itself \
+             may depend on.\n\n\
+             See what is installed first:\n  \
+             nix-env -q\n\n\
+             The previous state is still available as a generation:\n  \
+             nix-env --rollback"
+        ),
+        destructive_pattern!(
+            "nix-env-uninstall",
+            r"\bnix-env\s+(?:.*\s)?(?:-e|--uninstall)\b",
+            "nix-env -e uninstalls packages from the profile. Verify the package names.",
+            Medium
+        ),
+        destructive_pattern!(
+            "nix-env-delete-generations",
+            r"\bnix-env\s+(?:.*\s)?--delete-generations\b",
+            "nix-env --delete-generations removes profile generations you could roll back to.",
+            High
+        ),
+        destructive_pattern!(
+            "nix-profile-wipe-history",
+            r"\bnix\s+profile\s+wipe-history\b",
+            "nix profile wipe-history deletes all non-current profile generations.",
+            High
+        ),
+        destructive_pattern!(
+            "nix-profile-remove",
+            r"\bnix\s+profile\s+remove\b",
+            "nix profile remove uninstalls packages from the profile. Verify what is removed.",
+            Medium
+        ),
+        destructive_pattern!(
+            "nix-store-delete",
+            r"\bnix\s+store\s+delete\b|\bnix-store\s+(?:.*\s)?--delete\b",
+            "Deleting store paths directly can break profiles and builds that still reference them.",
+            High,
+            "nix store delete / nix-store --delete removes paths from /nix/store. With \
+             --ignore-liveness it removes paths that are still referenced, which leaves \
+             profiles, system generations, or dev shells pointing at missing files.\n\n\
+             Prefer letting the garbage collector decide:\n  \
+             nix store gc --dry-run\n\n\
+             Check what still references a path:\n  \
+             nix-store --query --roots <path>"
+        ),
+        destructive_pattern!(
+            "nixos-rebuild-rollback",
+            r"\bnixos-rebuild\b.*\s--rollback\b",
+            "nixos-rebuild --rollback switches the running system to the previous generation.",
+            High,
+            "nixos-rebuild switch --rollback (or boot/test --rollback) activates the \
+             previous system generation: services, kernel modules, users, and firewall \
+             rules all revert. Run from an agent session this can drop network access \
+             or restart the services the session depends on.\n\n\
+             List generations first:\n  \
+             nixos-rebuild list-generations"
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn blocks_destructive_nix_operations() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "nix-collect-garbage -d",
+            "nix-collect-garbage-delete-old",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "sudo nix-collect-garbage --delete-older-than 7d",
+            "nix-collect-garbage-delete-old",
+        );
+        assert_blocks_with_pattern(&pack, "nix-env -e '.*'", "nix-env-uninstall-all");
+        assert_blocks_with_pattern(&pack, "nix-env -e hello", "nix-env-uninstall");
+        assert_blocks_with_pattern(
+            &pack,
+            "nix-env --delete-generations old",
+            "nix-env-delete-generations",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "nix profile wipe-history",
+            "nix-profile-wipe-history",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "nix store delete --ignore-liveness /nix/store/abc-hello",
+            "nix-store-delete",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "nix-store --delete /nix/store/abc-hello",
+            "nix-store-delete",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "sudo nixos-rebuild switch --rollback",
+            "nixos-rebuild-rollback",
+        );
+    }
+
+    #[test]
+    fn allows_builds_queries_and_dry_runs() {
+        let pack = create_pack();
+        assert_allows(&pack, "nix build .#default");
+        assert_allows(&pack, "nix develop");
+        assert_allows(&pack, "nix flake check");
+        assert_allows(&pack, "nix-env -q");
+        assert_allows(&pack, "nix-collect-garbage -d --dry-run");
+        assert_allows(&pack, "nix-collect-garbage");
+        assert_allows(&pack, "nixos-rebuild dry-build");
+        assert_allows(&pack, "nixos-rebuild switch");
+    }
+
+    #[test]
+    fn keyword_absent_skips_pack() {
+        let pack = create_pack();
+        assert!(!pack.might_match("echo hello"));
+        assert!(pack.check("echo hello").is_none());
+    }
+}
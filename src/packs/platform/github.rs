@@ -8,6 +8,7 @@
 //! - Deleting releases
 //! - Deleting issues
 //! - Deleting SSH keys
+//! - Closing pull requests with `--delete-branch`
 
 use crate::packs::{DestructivePattern, Pack, SafePattern};
 use crate::{destructive_pattern, safe_pattern};
@@ -18,7 +19,7 @@ pub fn create_pack() -> Pack {
     Pack {
         id: "platform.github".to_string(),
         name: "GitHub Platform",
-        description: "Protects against destructive GitHub CLI operations like deleting repositories, gists, releases, or SSH keys.",
+        description: "Protects against destructive GitHub CLI operations like deleting repositories, gists, releases, SSH keys, or PR head branches.",
         // Broad on purpose: global `gh` flags can appear before the subcommand.
         keywords: &["gh"],
         safe_patterns: create_safe_patterns(),
@@ -26,6 +27,8 @@ pub fn create_pack() -> Pack {
         keyword_matcher: None,
         safe_regex_set: None,
         safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
     }
 }
 
@@ -76,6 +79,10 @@ fn create_safe_patterns() -> Vec<SafePattern> {
             "gh-status",
             r"gh(?:\s+--?[A-Za-z][A-Za-z0-9-]*\b(?:\s+(?!(?:repo|gist|release|issue|ssh-key|secret|variable|run|auth|status|api)\b)(?:(?:\x22[^\x22]*\x22)|(?:'[^']*')|\S+))?)*\s+status\b"
         ),
+        safe_pattern!(
+            "gh-pr-read",
+            r"^\s*gh(?:\s+--?[A-Za-z][A-Za-z0-9-]*\b(?:\s+(?!(?:repo|gist|release|issue|ssh-key|secret|variable|run|auth|status|api)\b)(?:(?:\x22[^\x22]*\x22)|(?:'[^']*')|\S+))?)*\s+pr\s+(?:list|view|status|diff|checks)\b[^;&|\n$`]*$"
+        ),
         // Safe API GETs
         safe_pattern!(
             "gh-api-explicit-get",
@@ -161,6 +168,18 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
             r"gh(?:\s+--?[A-Za-z][A-Za-z0-9-]*\b(?:\s+(?!(?:repo|gist|release|issue|ssh-key|secret|variable|run|auth|status|api)\b)(?:(?:\x22[^\x22]*\x22)|(?:'[^']*')|\S+))?)*\s+api\b.*(?:-X|--method)\s+DELETE\b.*(?:/)?repos/[^/\s]+/[^/\s]+/releases/",
             "gh api DELETE releases removes GitHub releases."
         ),
+        destructive_pattern!(
+            "gh-pr-close-delete-branch",
+            r"gh(?:\s+--?[A-Za-z][A-Za-z0-9-]*\b(?:\s+(?!(?:repo|gist|release|issue|ssh-key|secret|variable|run|auth|status|api)\b)(?:(?:\x22[^\x22]*\x22)|(?:'[^']*')|\S+))?)*\s+pr\s+close\b.*\s(?:-d|--delete-branch)\b",
+            "gh pr close --delete-branch closes the pull request and deletes its head branch.",
+            Medium,
+            "Closing with --delete-branch removes the local and remote head branch in the same \
+             step. Commits that only exist on that branch become unreachable once GitHub's \
+             restore window passes, and anyone else working on the branch loses it.\n\n\
+             Safer alternatives:\n\
+             - gh pr close <number>: Close without touching the branch\n\
+             - gh pr view <number>: Confirm the branch is merged or no longer needed"
+        ),
         // API Deletes
         // DELETE /repos/{owner}/{repo} -> Delete a repository
         destructive_pattern!(
@@ -188,12 +207,29 @@ mod tests {
         assert!(pack.check("gh variable list").is_none());
         assert!(pack.check("gh auth status").is_none());
         assert!(pack.check("gh status").is_none());
+        assert!(pack.check("gh pr view 42").is_none());
+        assert!(pack.check("gh pr list --state open").is_none());
+        assert!(pack.check("gh pr close 42").is_none());
 
         // With global flags
         assert!(pack.check("gh -R owner/repo repo view").is_none());
         assert!(pack.check("gh -R owner/repo secret list").is_none());
     }
 
+    #[test]
+    fn pr_read_does_not_cover_chained_commands() {
+        let pack = create_pack();
+        for cmd in [
+            "gh pr view 1 && gh pr close 2 --delete-branch",
+            "gh pr list; gh pr close 2 -d",
+        ] {
+            let matched = pack
+                .check(cmd)
+                .unwrap_or_else(|| panic!("Should block: {cmd}"));
+            assert_eq!(matched.name, Some("gh-pr-close-delete-branch"), "{cmd}");
+        }
+    }
+
     #[test]
     fn blocks_destructive_variants() {
         let mut pack = create_pack();
@@ -214,6 +250,11 @@ mod tests {
             ("gh variable remove VAR_NAME", "gh-variable-delete"),
             ("gh repo deploy-key delete 123", "gh-repo-deploy-key-delete"),
             ("gh run cancel 123456", "gh-run-cancel"),
+            (
+                "gh pr close 42 --delete-branch",
+                "gh-pr-close-delete-branch",
+            ),
+            ("gh pr close 42 -d", "gh-pr-close-delete-branch"),
             (
                 "gh api -X DELETE /repos/owner/repo/actions/secrets/SECRET",
                 "gh-api-delete-actions-secret",
@@ -1,7 +1,8 @@
 //! GitLab Platform pack - protections for destructive GitLab platform operations.
 //!
 //! This pack focuses on non-CI GitLab operations like deleting projects,
-//! archiving repositories, removing protected branches, and deleting webhooks.
+//! merge requests, and environments, archiving repositories, removing
+//! protected branches, and deleting webhooks.
 
 use crate::packs::{DestructivePattern, Pack, SafePattern};
 use crate::{destructive_pattern, safe_pattern};
@@ -13,13 +14,15 @@ pub fn create_pack() -> Pack {
         id: "platform.gitlab".to_string(),
         name: "GitLab Platform",
         description: "Protects against destructive GitLab platform operations like deleting projects, \
-                      releases, protected branches, and webhooks.",
+                      merge requests, environments, releases, protected branches, and webhooks.",
         keywords: &["glab", "gitlab-rails", "gitlab-rake"],
         safe_patterns: create_safe_patterns(),
         destructive_patterns: create_destructive_patterns(),
         keyword_matcher: None,
         safe_regex_set: None,
         safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
     }
 }
 
@@ -84,6 +87,11 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
             r"glab(?:\s+--?\S+(?:\s+\S+)?)*\s+repo\s+archive\b",
             "glab repo archive makes a GitLab project read-only."
         ),
+        destructive_pattern!(
+            "glab-mr-delete",
+            r"glab(?:\s+--?\S+(?:\s+\S+)?)*\s+mr\s+(?:delete|del)\b",
+            "glab mr delete permanently deletes a merge request and its discussion."
+        ),
         destructive_pattern!(
             "glab-release-delete",
             r"glab(?:\s+--?\S+(?:\s+\S+)?)*\s+release\s+delete\b",
@@ -109,6 +117,11 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
             r"glab(?:\s+--?\S+(?:\s+\S+)?)*\s+api\b.*(?:-X|--method)\s+DELETE\b.*(?:/)?projects/[^/\s]+/variables/",
             "glab api DELETE variables removes CI/CD variables."
         ),
+        destructive_pattern!(
+            "glab-api-delete-environment",
+            r"glab(?:\s+--?\S+(?:\s+\S+)?)*\s+api\b.*(?:-X|--method)\s+DELETE\b.*(?:/)?projects/[^/\s]+/environments/",
+            "glab api DELETE environments removes a GitLab environment and its deployment history."
+        ),
         destructive_pattern!(
             "glab-api-delete-protected-branch",
             r"glab(?:\s+--?\S+(?:\s+\S+)?)*\s+api\b.*(?:-X|--method)\s+DELETE\b.*(?:/)?protected_branches/",
@@ -204,6 +217,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_mr_delete_blocked() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "glab mr delete 42", "glab-mr-delete");
+        assert_blocks_with_pattern(&pack, "glab -R my/group mr del 42", "glab-mr-delete");
+    }
+
+    #[test]
+    fn test_api_delete_environment_blocked() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "glab api --method DELETE /projects/123/environments/7",
+            "glab-api-delete-environment",
+        );
+    }
+
     #[test]
     fn test_api_delete_protected_branch_blocked() {
         let pack = create_pack();
@@ -311,6 +311,17 @@ impl LazyCompiledRegex {
     pub fn is_compiled(&self) -> bool {
         matches!(self.compiled.get(), Some(Ok(_)))
     }
+
+    /// Compile the regex now instead of on first match.
+    ///
+    /// Returns the compile error, if any.
+    pub fn warm(&self) -> Result<(), &str> {
+        self.compiled
+            .get_or_init(|| CompiledRegex::new(self.pattern.as_str()))
+            .as_ref()
+            .map(|_| ())
+            .map_err(String::as_str)
+    }
 }
 
 impl std::fmt::Display for LazyCompiledRegex {
@@ -319,10 +330,171 @@ impl std::fmt::Display for LazyCompiledRegex {
     }
 }
 
+// ============================================================================
+// RegexSet Prefilter
+// ============================================================================
+
+/// A single-pass "which patterns could match?" check over a list of patterns.
+///
+/// Every pattern that the linear-time engine accepts is compiled into one
+/// `regex::RegexSet`, so a command is scanned once instead of once per
+/// pattern. Patterns that need the backtracking engine (look-around,
+/// backreferences) are left out and always reported as candidates; callers
+/// confirm candidates with the pattern's own regex, which also yields the span.
+///
+/// Like [`LazyCompiledRegex`], the set is compiled on first use, so packs
+/// that are instantiated but never checked do not pay for it.
+#[derive(Debug)]
+pub struct RegexSetPrefilter {
+    /// Patterns compiled into the set, in set order.
+    patterns: Vec<String>,
+    /// For each input pattern, its index in the set, or `None` if it must be
+    /// checked individually.
+    slots: Vec<Option<usize>>,
+    set: OnceLock<Option<regex::RegexSet>>,
+}
+
+impl RegexSetPrefilter {
+    /// Prepare a prefilter over `patterns` (in order). No regex is compiled here.
+    ///
+    /// Returns `None` if no pattern can use the linear-time engine; callers
+    /// then check every pattern individually.
+    #[must_use]
+    pub fn new<'a>(patterns: impl IntoIterator<Item = &'a str>) -> Option<Self> {
+        let mut linear = Vec::new();
+        let slots: Vec<Option<usize>> = patterns
+            .into_iter()
+            .map(|pattern| {
+                (!needs_backtracking_engine(pattern)).then(|| {
+                    linear.push(pattern.to_string());
+                    linear.len() - 1
+                })
+            })
+            .collect();
+        if linear.is_empty() {
+            return None;
+        }
+        Some(Self {
+            patterns: linear,
+            slots,
+            set: OnceLock::new(),
+        })
+    }
+
+    /// Get or compile the set. `None` if it failed to compile (fail-open to
+    /// per-pattern checks).
+    fn get_set(&self) -> Option<&regex::RegexSet> {
+        self.set
+            .get_or_init(|| regex::RegexSet::new(&self.patterns).ok())
+            .as_ref()
+    }
+
+    /// Whether the set covers every pattern (none need individual checks).
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.slots.iter().all(Option::is_some)
+    }
+
+    /// Check if the set has been compiled.
+    #[must_use]
+    pub fn is_compiled(&self) -> bool {
+        matches!(self.set.get(), Some(Some(_)))
+    }
+
+    /// Compile the set now. Returns `false` if it fails to compile.
+    pub fn warm(&self) -> bool {
+        self.get_set().is_some()
+    }
+
+    /// Scan `haystack` once and report which patterns could match.
+    ///
+    /// Returns `None` if the set failed to compile; every pattern is then a
+    /// candidate.
+    #[must_use]
+    pub fn candidates(&self, haystack: &str) -> Option<PrefilterCandidates<'_>> {
+        self.get_set().map(|set| PrefilterCandidates {
+            matches: set.matches(haystack),
+            slots: &self.slots,
+        })
+    }
+}
+
+/// Result of [`RegexSetPrefilter::candidates`].
+#[derive(Debug)]
+pub struct PrefilterCandidates<'a> {
+    matches: regex::SetMatches,
+    slots: &'a [Option<usize>],
+}
+
+impl PrefilterCandidates<'_> {
+    /// Whether at least one pattern in the set matched (no confirmation needed).
+    #[must_use]
+    pub fn set_matched(&self) -> bool {
+        self.matches.matched_any()
+    }
+
+    /// Whether any pattern could match. `false` means none can, so the
+    /// individual patterns need not be checked at all.
+    #[must_use]
+    pub fn any(&self) -> bool {
+        self.matches.matched_any() || self.slots.iter().any(Option::is_none)
+    }
+
+    /// Whether pattern `index` could match: it matched in the set, or it is
+    /// a backtracking pattern the set does not cover.
+    #[must_use]
+    pub fn may_match(&self, index: usize) -> bool {
+        self.slots
+            .get(index)
+            .is_none_or(|slot| slot.is_none_or(|set_index| self.matches.matched(set_index)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_prefilter_reports_linear_matches_and_backtracking_candidates() {
+        let prefilter = RegexSetPrefilter::new([
+            r"git\s+reset\s+--hard",
+            r"git\s+push(?=.*--force)",
+            r"git\s+clean\s+-f",
+        ])
+        .unwrap();
+        assert!(!prefilter.is_complete());
+
+        assert!(!prefilter.is_compiled());
+        let hits = prefilter.candidates("git reset --hard HEAD").unwrap();
+        assert!(prefilter.is_compiled());
+        assert!(hits.set_matched());
+        assert!(hits.any());
+        assert!(hits.may_match(0));
+        assert!(hits.may_match(1)); // backtracking: always a candidate
+        assert!(!hits.may_match(2));
+
+        let none = prefilter.candidates("git status").unwrap();
+        assert!(!none.set_matched());
+        assert!(none.any()); // the backtracking pattern still needs a check
+        assert!(!none.may_match(0));
+        assert!(!none.may_match(2));
+    }
+
+    #[test]
+    fn test_complete_prefilter_rejects_in_one_pass() {
+        let prefilter = RegexSetPrefilter::new([r"rm\s+-rf", r"\bdd\s+if="]).unwrap();
+        assert!(prefilter.is_complete());
+        assert!(!prefilter.candidates("ls -la").unwrap().any());
+        assert!(
+            prefilter
+                .candidates("dd if=/dev/zero of=x")
+                .unwrap()
+                .may_match(1)
+        );
+
+        assert!(RegexSetPrefilter::new([r"a(?=b)"]).is_none());
+    }
+
     #[test]
     fn test_linear_engine_selection() {
         // Simple patterns should use linear engine
@@ -19,6 +19,8 @@ pub fn create_pack() -> Pack {
         keyword_matcher: None,
         safe_regex_set: None,
         safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
     }
 }
 
@@ -65,6 +67,19 @@ fn create_safe_patterns() -> Vec<SafePattern> {
 
 fn create_destructive_patterns() -> Vec<DestructivePattern> {
     vec![
+        destructive_pattern!(
+            "aws-secretsmanager-force-delete-secret",
+            r"aws(?:\s+--?\S+(?:\s+\S+)?)*\s+secretsmanager\s+delete-secret\b[^;&|\n]*\s--force-delete-without-recovery\b",
+            "aws secretsmanager delete-secret --force-delete-without-recovery deletes the secret immediately with no recovery window.",
+            Critical,
+            "--force-delete-without-recovery skips the 7-30 day recovery window. The secret \
+             and all of its versions are gone as soon as the call returns; restore-secret \
+             cannot bring it back and the name becomes free for reuse.\n\n\
+             Safer alternatives:\n\
+             - aws secretsmanager get-secret-value: Export value first\n\
+             - --recovery-window-in-days 7: Keep the secret restorable for a week\n\
+             - aws secretsmanager restore-secret: Undo a scheduled deletion"
+        ),
         destructive_pattern!(
             "aws-secretsmanager-delete-secret",
             r"aws(?:\s+--?\S+(?:\s+\S+)?)*\s+secretsmanager\s+delete-secret\b",
@@ -190,6 +205,11 @@ mod tests {
             "aws --region us-east-1 secretsmanager delete-secret --secret-id my/secret --recovery-window-in-days 7",
             "delete-secret",
         );
+        assert_blocks_with_pattern(
+            &pack,
+            "aws secretsmanager delete-secret --secret-id my/secret --force-delete-without-recovery",
+            "aws-secretsmanager-force-delete-secret",
+        );
     }
 
     #[test]
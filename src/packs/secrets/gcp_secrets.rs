@@ -0,0 +1,154 @@
+//! `GCP` Secret Manager pack - protections for destructive `gcloud secrets` operations.
+//!
+//! Secret Manager has no recovery window: deleting a secret or destroying a
+//! version erases the payload immediately.
+
+use crate::packs::{DestructivePattern, Pack, SafePattern};
+use crate::{destructive_pattern, safe_pattern};
+
+/// Create the GCP Secret Manager pack.
+#[must_use]
+pub fn create_pack() -> Pack {
+    Pack {
+        id: "secrets.gcp_secrets".to_string(),
+        name: "GCP Secret Manager",
+        description: "Protects against destructive GCP Secret Manager operations like \
+                      gcloud secrets delete and secrets versions destroy.",
+        keywords: &["gcloud"],
+        safe_patterns: create_safe_patterns(),
+        destructive_patterns: create_destructive_patterns(),
+        keyword_matcher: None,
+        safe_regex_set: None,
+        safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
+    }
+}
+
+fn create_safe_patterns() -> Vec<SafePattern> {
+    // Anchored to the whole command so a read-only call cannot whitelist a
+    // chained `gcloud secrets delete`.
+    vec![
+        safe_pattern!(
+            "gcloud-secrets-list-describe",
+            r"^\s*gcloud(?:\s+--?\S+(?:\s+\S+)?)*\s+secrets\s+(?:list|describe|get-iam-policy)\b[^;&|\n$`]*$"
+        ),
+        safe_pattern!(
+            "gcloud-secrets-versions-read",
+            r"^\s*gcloud(?:\s+--?\S+(?:\s+\S+)?)*\s+secrets\s+versions\s+(?:list|describe|access)\b[^;&|\n$`]*$"
+        ),
+    ]
+}
+
+fn create_destructive_patterns() -> Vec<DestructivePattern> {
+    vec![
+        destructive_pattern!(
+            "gcloud-secrets-delete",
+            r"gcloud(?:\s+--?\S+(?:\s+\S+)?)*\s+secrets\s+delete\b",
+            "gcloud secrets delete permanently deletes the secret and every version.",
+            Critical,
+            "Secret Manager deletes immediately: there is no recovery window like AWS \
+             Secrets Manager. Every version's payload is erased, and workloads, Cloud \
+             Run services or Cloud Build steps referencing the secret fail on their \
+             next access. --quiet also skips the confirmation prompt.\n\n\
+             Safer alternatives:\n\
+             - gcloud secrets versions access latest --secret=NAME: Export the value first\n\
+             - gcloud secrets versions disable: Make versions unreadable but restorable\n\
+             - Set an expire-time or ttl instead of deleting by hand"
+        ),
+        destructive_pattern!(
+            "gcloud-secrets-versions-destroy",
+            r"gcloud(?:\s+--?\S+(?:\s+\S+)?)*\s+secrets\s+versions\s+destroy\b",
+            "gcloud secrets versions destroy irreversibly erases the version's payload.",
+            Critical,
+            "A destroyed version keeps its metadata but its payload is gone for good; \
+             it cannot be re-enabled. Destroying `latest` breaks every client that \
+             reads the current value.\n\n\
+             Safer alternatives:\n\
+             - gcloud secrets versions list NAME: Check which version is in use\n\
+             - gcloud secrets versions disable: Reversible with versions enable\n\
+             - Add a new version before retiring the old one"
+        ),
+        destructive_pattern!(
+            "gcloud-secrets-versions-disable",
+            r"gcloud(?:\s+--?\S+(?:\s+\S+)?)*\s+secrets\s+versions\s+disable\b",
+            "gcloud secrets versions disable makes the version unreadable; clients using it start failing.",
+            Medium,
+            "A disabled version cannot be accessed until it is enabled again. Clients \
+             that pin the version, or read `latest` when it is the disabled one, fail \
+             immediately.\n\n\
+             Safer alternatives:\n\
+             - gcloud secrets versions list NAME: Check which version is in use\n\
+             - gcloud secrets versions enable: Undo the change"
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_pack_creation() {
+        let pack = create_pack();
+        assert_eq!(pack.id, "secrets.gcp_secrets");
+        assert_eq!(pack.name, "GCP Secret Manager");
+        assert!(!pack.description.is_empty());
+        assert!(pack.keywords.contains(&"gcloud"));
+
+        assert_patterns_compile(&pack);
+        assert_all_patterns_have_reasons(&pack);
+        assert_unique_pattern_names(&pack);
+    }
+
+    #[test]
+    fn test_delete_secret_blocked() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "gcloud secrets delete db-password",
+            "gcloud-secrets-delete",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "gcloud --project prod-123 secrets delete db-password --quiet",
+            "gcloud-secrets-delete",
+        );
+    }
+
+    #[test]
+    fn test_versions_destroy_and_disable_blocked() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "gcloud secrets versions destroy 3 --secret=db-password",
+            "gcloud-secrets-versions-destroy",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "gcloud secrets versions disable latest --secret=db-password",
+            "gcloud-secrets-versions-disable",
+        );
+    }
+
+    #[test]
+    fn test_safe_commands_allowed() {
+        let pack = create_pack();
+        assert_allows(&pack, "gcloud secrets list");
+        assert_allows(&pack, "gcloud secrets describe db-password");
+        assert_allows(&pack, "gcloud secrets versions list db-password");
+        assert_allows(
+            &pack,
+            "gcloud secrets versions access latest --secret=db-password",
+        );
+        assert_allows(
+            &pack,
+            "gcloud secrets versions enable 3 --secret=db-password",
+        );
+        assert_no_safe_match(
+            &pack,
+            "gcloud secrets list && gcloud secrets delete db-password",
+        );
+    }
+}
@@ -4,5 +4,6 @@
 
 pub mod aws_secrets;
 pub mod doppler;
+pub mod gcp_secrets;
 pub mod onepassword;
 pub mod vault;
@@ -1,7 +1,7 @@
 //! `HashiCorp` Vault CLI pack - protections for destructive Vault operations.
 //!
 //! This pack blocks commands that delete secrets, disable auth/secret engines,
-//! revoke leases/tokens, or remove policies.
+//! revoke leases/tokens, remove policies, or seal the server.
 
 use crate::packs::{DestructivePattern, Pack, SafePattern};
 use crate::{destructive_pattern, safe_pattern};
@@ -13,13 +13,16 @@ pub fn create_pack() -> Pack {
         id: "secrets.vault".to_string(),
         name: "HashiCorp Vault",
         description: "Protects against destructive Vault CLI operations like deleting secrets, \
-                      disabling auth/secret engines, revoking leases/tokens, and deleting policies.",
+                      disabling auth/secret engines, revoking leases/tokens, deleting policies, \
+                      and sealing the server.",
         keywords: &["vault"],
         safe_patterns: create_safe_patterns(),
         destructive_patterns: create_destructive_patterns(),
         keyword_matcher: None,
         safe_regex_set: None,
         safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
     }
 }
 
@@ -173,6 +176,20 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              - Use token TTLs for automatic expiration\n\
              - Coordinate token rotation before revocation"
         ),
+        destructive_pattern!(
+            "vault-lease-revoke-prefix",
+            r"vault(?:\s+--?\S+(?:\s+\S+)?)*\s+lease\s+revoke\b[^;&|\n]*\s--?prefix\b",
+            "vault lease revoke -prefix revokes every lease under the path at once.",
+            Critical,
+            "With -prefix, every lease issued under the path is revoked in one call. \
+             A prefix such as database/ or aws/ invalidates the dynamic credentials of \
+             every application using that engine, and -force also drops the leases \
+             from Vault when the backend revocation fails.\n\n\
+             Safer alternatives:\n\
+             - vault list sys/leases/lookup/<prefix>: Review the leases first\n\
+             - vault lease revoke <lease_id>: Revoke a single lease\n\
+             - Narrow the prefix to a single role"
+        ),
         destructive_pattern!(
             "vault-lease-revoke",
             r"vault(?:\s+--?\S+(?:\s+\S+)?)*\s+lease\s+revoke\b",
@@ -186,6 +203,20 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              - Use shorter TTLs for automatic rotation\n\
              - Coordinate credential rotation before revocation"
         ),
+        destructive_pattern!(
+            "vault-operator-seal",
+            r"vault(?:\s+--?\S+(?:\s+\S+)?)*\s+operator\s+seal\b",
+            "vault operator seal seals the server; every client loses access until it is unsealed.",
+            Critical,
+            "Sealing discards the in-memory master key. Every read, write, login and \
+             lease renewal fails until the server is unsealed again, which needs a \
+             quorum of unseal key holders (or the auto-unseal KMS) to be available. In \
+             an HA cluster, sealing the active node forces a failover.\n\n\
+             Safer alternatives:\n\
+             - vault status: Check seal type and HA state first\n\
+             - vault operator step-down: Move the active role without sealing\n\
+             - Confirm unseal key holders are available before sealing"
+        ),
     ]
 }
 
@@ -285,9 +316,31 @@ mod tests {
         let pack = create_pack();
         assert_blocks_with_pattern(
             &pack,
-            "vault lease revoke -prefix secret/",
+            "vault lease revoke database/creds/readonly/2f6a614c",
             "vault-lease-revoke",
         );
+        assert_blocks_with_pattern(
+            &pack,
+            "vault lease revoke -prefix secret/",
+            "vault-lease-revoke-prefix",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "vault lease revoke -force -prefix aws/",
+            "vault-lease-revoke-prefix",
+        );
+    }
+
+    #[test]
+    fn test_operator_seal_blocked() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "vault operator seal", "vault-operator-seal");
+        assert_blocks_with_pattern(
+            &pack,
+            "vault -address=https://vault.prod:8200 operator seal",
+            "vault-operator-seal",
+        );
+        assert_allows(&pack, "vault operator unseal");
     }
 
     #[test]
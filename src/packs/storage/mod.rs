@@ -3,4 +3,5 @@
 pub mod azure_blob;
 pub mod gcs;
 pub mod minio;
+pub mod objectstore;
 pub mod s3;
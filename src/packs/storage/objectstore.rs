@@ -0,0 +1,361 @@
+//! Object storage CLI pack - recursive and bulk deletes across `aws s3`,
+//! `s3cmd`, `gsutil`, `mc`, and `rclone`.
+//!
+//! The per-provider packs (`storage.s3`, `storage.gcs`, `storage.minio`,
+//! `backup.rclone`) treat every delete alike. This pack grades them by scope:
+//! a recursive delete under a prefix is High, while one aimed at a whole
+//! bucket is Critical. Bucket-level targets are the bucket root or a wildcard
+//! directly under it:
+//!
+//! ```text
+//! s3://bucket   s3://bucket/   s3://bucket/*   gs://bucket/**
+//! remote:bucket   remote:   myminio/bucket/
+//! ```
+//!
+//! Listing commands and dry runs are allowed.
+
+use crate::packs::{DestructivePattern, Pack, SafePattern};
+use crate::{destructive_pattern, safe_pattern};
+
+/// Create the object storage pack.
+#[must_use]
+pub fn create_pack() -> Pack {
+    Pack {
+        id: "storage.objectstore".to_string(),
+        name: "Object Storage CLIs",
+        description: "Protects against recursive object storage deletes with aws s3, s3cmd, \
+                      gsutil, mc, and rclone, escalating when a whole bucket is targeted",
+        keywords: &["rclone", "s3cmd", "gsutil", "mc", "s3"],
+        safe_patterns: create_safe_patterns(),
+        destructive_patterns: create_destructive_patterns(),
+        keyword_matcher: None,
+        safe_regex_set: None,
+        safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
+    }
+}
+
+fn create_safe_patterns() -> Vec<SafePattern> {
+    vec![
+        // Listing and inspection
+        safe_pattern!(
+            "objectstore-list",
+            r"^\s*(?:aws(?:\s+--?\S+(?:\s+\S+)?)*\s+s3\s+ls|rclone(?:\s+-\S+)*\s+(?:ls|lsd|lsl|lsf|lsjson|size|check|about|tree|cat|version)|gsutil(?:\s+-\S+)*\s+(?:ls|du|stat|cat|version)|s3cmd(?:\s+-\S+)*\s+(?:ls|la|du|info)|mc\s+(?:ls|stat|du|cat|find|tree|diff))\b[^;&|\n$`]*$"
+        ),
+        // Dry runs
+        safe_pattern!(
+            "objectstore-dry-run",
+            r"^\s*(?:(?:rclone|s3cmd)\b[^;&|\n$`]*\s(?:--dry-run|-n)|aws\b[^;&|\n$`]*\s--dryrun|mc\b[^;&|\n$`]*\s--dry-run)\b[^;&|\n$`]*$"
+        ),
+    ]
+}
+
+fn create_destructive_patterns() -> Vec<DestructivePattern> {
+    vec![
+        // aws s3
+        destructive_pattern!(
+            "aws-s3-rm-bucket",
+            r#"\baws(?:\s+--?\S+(?:\s+\S+)?)*\s+s3\s+rm\b(?=[^;&|\n]*\s--recursive\b)(?=[^;&|\n]*\s['"]?s3://[^/\s'"]+/?\*{0,2}['"]?(?:[\s;&|]|$))"#,
+            "aws s3 rm --recursive on a bucket root deletes every object in the bucket.",
+            Critical,
+            "With --recursive and a bucket-level target (s3://bucket, s3://bucket/), aws s3 rm \
+             deletes every object in the bucket. Without versioning the objects cannot be \
+             recovered.\n\n\
+             Safer alternatives:\n\
+             - aws s3 rm s3://bucket --recursive --dryrun: List what would be deleted\n\
+             - Narrow the target to a prefix, or use --exclude/--include filters\n\
+             - Lifecycle rules: Expire objects on a schedule instead"
+        ),
+        destructive_pattern!(
+            "aws-s3-rb-force",
+            r"\baws(?:\s+--?\S+(?:\s+\S+)?)*\s+s3\s+rb\b(?=[^;&|\n]*\s--force\b)",
+            "aws s3 rb --force deletes every object in the bucket, then the bucket.",
+            Critical,
+            "--force empties the bucket before removing it. All objects are deleted and the \
+             bucket name is released.\n\n\
+             Safer alternatives:\n\
+             - aws s3 ls s3://bucket --recursive --summarize: See what is in the bucket\n\
+             - aws s3 rb s3://bucket: Only removes an already-empty bucket"
+        ),
+        destructive_pattern!(
+            "aws-s3-rm-recursive",
+            r"\baws(?:\s+--?\S+(?:\s+\S+)?)*\s+s3\s+rm\b(?=[^;&|\n]*\s--recursive\b)",
+            "aws s3 rm --recursive deletes every object under the prefix.",
+            High,
+            "aws s3 rm --recursive deletes every object whose key starts with the given \
+             prefix.\n\n\
+             Safer alternatives:\n\
+             - Add --dryrun to list the objects first\n\
+             - Enable bucket versioning so deletes can be undone"
+        ),
+        // s3cmd
+        destructive_pattern!(
+            "s3cmd-del-bucket",
+            r#"\bs3cmd\b[^;&|\n]*\s(?:del|rm)\b(?=[^;&|\n]*\s(?:--recursive|-r)\b)(?=[^;&|\n]*\s['"]?s3://[^/\s'"]+/?\*{0,2}['"]?(?:[\s;&|]|$))"#,
+            "s3cmd del --recursive on a bucket root deletes every object in the bucket.",
+            Critical,
+            "With --recursive and a bucket-level target, s3cmd del deletes every object in \
+             the bucket.\n\n\
+             Safer alternatives:\n\
+             - s3cmd del --recursive --dry-run: List what would be deleted\n\
+             - s3cmd ls --recursive s3://bucket: Review the contents first"
+        ),
+        destructive_pattern!(
+            "s3cmd-rb-recursive",
+            r"\bs3cmd\b[^;&|\n]*\srb\b(?=[^;&|\n]*\s(?:--recursive|-r)\b)",
+            "s3cmd rb --recursive deletes every object in the bucket, then the bucket.",
+            Critical,
+            "s3cmd rb --recursive empties the bucket and removes it.\n\n\
+             Safer alternatives:\n\
+             - s3cmd du s3://bucket: Check how much data would be lost\n\
+             - s3cmd rb s3://bucket: Only removes an already-empty bucket"
+        ),
+        destructive_pattern!(
+            "s3cmd-del-recursive",
+            r"\bs3cmd\b[^;&|\n]*\s(?:del|rm)\b(?=[^;&|\n]*\s(?:--recursive|-r)\b)",
+            "s3cmd del --recursive deletes every object under the prefix.",
+            High,
+            "s3cmd del --recursive deletes every object under the given prefix.\n\n\
+             Safer alternatives:\n\
+             - Add --dry-run to list the objects first"
+        ),
+        // gsutil
+        destructive_pattern!(
+            "gsutil-rm-bucket",
+            r#"\bgsutil\b[^;&|\n]*\srm\b(?:(?=[^;&|\n]*\s-[a-zA-Z]*[rR][a-zA-Z]*\b)(?=[^;&|\n]*\s['"]?gs://[^/\s'"]+/?['"]?(?:[\s;&|]|$))|(?=[^;&|\n]*\s['"]?gs://[^/\s'"]+/\*{1,2}['"]?(?:[\s;&|]|$)))"#,
+            "gsutil rm on a whole bucket (gs://bucket with -r, or gs://bucket/**) deletes every object.",
+            Critical,
+            "gsutil rm -r gs://bucket deletes every object and then the bucket itself; \
+             gs://bucket/** and gs://bucket/* delete every (top-level) object.\n\n\
+             Safer alternatives:\n\
+             - gsutil ls -r gs://bucket: Review the contents first\n\
+             - gsutil du -s gs://bucket: Check how much data would be lost\n\
+             - Object versioning or retention policies: Keep deleted objects recoverable"
+        ),
+        destructive_pattern!(
+            "gsutil-rm-recursive",
+            r"\bgsutil\b[^;&|\n]*\srm\b(?=[^;&|\n]*\s-[a-zA-Z]*[rR][a-zA-Z]*\b)",
+            "gsutil rm -r deletes every object under the prefix.",
+            High,
+            "gsutil rm -r deletes every object under the given prefix.\n\n\
+             Safer alternatives:\n\
+             - gsutil ls -r <prefix>: Review what would be deleted"
+        ),
+        // mc (MinIO client)
+        destructive_pattern!(
+            "mc-rm-bucket",
+            r#"\bmc\s+rm\b(?=[^;&|\n]*\s--recursive\b)(?=[^;&|\n]*\s--force\b)(?=[^;&|\n]*\s['"]?[\w.-]+/[^/\s'"]+/?\*?['"]?(?:[\s;&|]|$))"#,
+            "mc rm --recursive --force on a bucket deletes every object in the bucket.",
+            Critical,
+            "With --recursive --force and an alias/bucket target, mc rm deletes every object \
+             in the bucket without prompting.\n\n\
+             Safer alternatives:\n\
+             - mc rm --recursive --force --dry-run alias/bucket: List what would be deleted\n\
+             - mc ls --recursive alias/bucket: Review the contents first"
+        ),
+        destructive_pattern!(
+            "mc-rm-recursive-force",
+            r"\bmc\s+rm\b(?=[^;&|\n]*\s--recursive\b)(?=[^;&|\n]*\s--force\b)",
+            "mc rm --recursive --force deletes every object under the prefix without prompting.",
+            High,
+            "mc rm --recursive --force deletes every object under the given prefix.\n\n\
+             Safer alternatives:\n\
+             - Add --dry-run to list the objects first"
+        ),
+        // rclone
+        destructive_pattern!(
+            "rclone-purge-bucket",
+            r#"\brclone\b[^;&|\n]*\spurge\b(?=[^;&|\n]*\s['"]?[\w.-]+:[^/\s'"]*/?['"]?(?:[\s;&|]|$))"#,
+            "rclone purge on a remote root or bucket deletes the bucket and everything in it.",
+            Critical,
+            "rclone purge removes a path and all of its contents, ignoring filters. Aimed at \
+             remote: or remote:bucket, it deletes the entire bucket.\n\n\
+             Safer alternatives:\n\
+             - rclone purge --dry-run remote:bucket: List what would be deleted\n\
+             - rclone size remote:bucket: Check how much data would be lost"
+        ),
+        destructive_pattern!(
+            "rclone-delete-bucket",
+            r#"\brclone\b[^;&|\n]*\sdelete\b(?=[^;&|\n]*\s['"]?[\w.-]+:[^/\s'"]*/?['"]?(?:[\s;&|]|$))"#,
+            "rclone delete on a remote root or bucket deletes every file in it.",
+            Critical,
+            "rclone delete removes every file under the path that passes the filters. Aimed \
+             at remote: or remote:bucket, it empties the bucket.\n\n\
+             Safer alternatives:\n\
+             - rclone delete --dry-run remote:bucket: List what would be deleted\n\
+             - Narrow the target to a prefix or add --include/--min-age filters"
+        ),
+        destructive_pattern!(
+            "rclone-purge",
+            r"\brclone\b[^;&|\n]*\spurge\b",
+            "rclone purge deletes the path and all of its contents.",
+            High,
+            "rclone purge removes a directory and all of its contents, ignoring filters.\n\n\
+             Safer alternatives:\n\
+             - Add --dry-run to list what would be deleted"
+        ),
+        destructive_pattern!(
+            "rclone-delete",
+            r"\brclone\b[^;&|\n]*\sdelete\b",
+            "rclone delete deletes every file under the path that passes the filters.",
+            High,
+            "rclone delete removes files under the given path, keeping the directory \
+             structure.\n\n\
+             Safer alternatives:\n\
+             - Add --dry-run to list what would be deleted"
+        ),
+        destructive_pattern!(
+            "rclone-sync-delete",
+            r"\brclone\b[^;&|\n]*\ssync\b(?=[^;&|\n]*\s--delete-(?:before|during|after|excluded)\b)",
+            "rclone sync --delete-* removes destination files missing from the source.",
+            High,
+            "rclone sync makes the destination match the source, deleting anything extra; \
+             --delete-excluded also deletes files the filters exclude. A wrong source or an \
+             empty directory wipes the destination.\n\n\
+             Safer alternatives:\n\
+             - Add --dry-run to see what would be deleted\n\
+             - rclone copy: Copy without deleting anything at the destination\n\
+             - --backup-dir: Move deleted files aside instead of removing them"
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::Severity;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_pack_creation() {
+        let pack = create_pack();
+        assert_eq!(pack.id, "storage.objectstore");
+        assert!(pack.keywords.contains(&"rclone"));
+        assert!(pack.keywords.contains(&"gsutil"));
+
+        assert_patterns_compile(&pack);
+        assert_all_patterns_have_reasons(&pack);
+        assert_unique_pattern_names(&pack);
+    }
+
+    #[test]
+    fn prefix_deletes_are_high() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "aws s3 rm s3://bucket/logs/ --recursive",
+            "aws-s3-rm-recursive",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "s3cmd del --recursive s3://bucket/tmp/",
+            "s3cmd-del-recursive",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "gsutil -m rm -r gs://bucket/cache",
+            "gsutil-rm-recursive",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "mc rm --recursive --force myminio/bucket/uploads",
+            "mc-rm-recursive-force",
+        );
+        assert_blocks_with_pattern(&pack, "rclone purge remote:bucket/old", "rclone-purge");
+        assert_blocks_with_pattern(
+            &pack,
+            "rclone delete remote:bucket/logs --min-age 30d",
+            "rclone-delete",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "rclone sync ./site remote:bucket/site --delete-excluded",
+            "rclone-sync-delete",
+        );
+        assert_blocks_with_severity(
+            &pack,
+            "rclone sync ./dist remote:bucket/dist --delete-before",
+            Severity::High,
+        );
+    }
+
+    #[test]
+    fn bucket_level_targets_are_critical() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "aws s3 rm s3://bucket --recursive",
+            "aws-s3-rm-bucket",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "aws s3 rm --recursive 's3://bucket/'",
+            "aws-s3-rm-bucket",
+        );
+        assert_blocks_with_pattern(&pack, "aws s3 rb s3://bucket --force", "aws-s3-rb-force");
+        assert_blocks_with_pattern(&pack, "s3cmd del -r s3://bucket/*", "s3cmd-del-bucket");
+        assert_blocks_with_pattern(
+            &pack,
+            "s3cmd rb --recursive s3://bucket",
+            "s3cmd-rb-recursive",
+        );
+        assert_blocks_with_pattern(&pack, "gsutil rm -r gs://bucket", "gsutil-rm-bucket");
+        assert_blocks_with_pattern(&pack, "gsutil -m rm 'gs://bucket/**'", "gsutil-rm-bucket");
+        assert_blocks_with_pattern(
+            &pack,
+            "mc rm --recursive --force myminio/bucket",
+            "mc-rm-bucket",
+        );
+        assert_blocks_with_pattern(&pack, "rclone purge remote:bucket", "rclone-purge-bucket");
+        assert_blocks_with_pattern(&pack, "rclone purge remote:", "rclone-purge-bucket");
+        assert_blocks_with_pattern(&pack, "rclone delete s3:bucket/", "rclone-delete-bucket");
+    }
+
+    #[test]
+    fn allows_listing_and_dry_runs() {
+        let pack = create_pack();
+        for command in [
+            "aws s3 ls s3://bucket --recursive",
+            "rclone lsd remote:",
+            "rclone size remote:bucket",
+            "gsutil ls -r gs://bucket",
+            "gsutil du -s gs://bucket",
+            "s3cmd ls s3://bucket",
+            "mc ls myminio/bucket",
+            "rclone purge --dry-run remote:bucket",
+            "aws s3 rm s3://bucket --recursive --dryrun",
+            "s3cmd del --recursive --dry-run s3://bucket",
+            "mc rm --recursive --force --dry-run myminio/bucket",
+        ] {
+            assert_safe_pattern_matches(&pack, command);
+            assert_allows(&pack, command);
+        }
+    }
+
+    #[test]
+    fn safe_patterns_do_not_mask_chained_deletes() {
+        let pack = create_pack();
+        assert_no_safe_match(
+            &pack,
+            "rclone ls remote:bucket && rclone purge remote:bucket",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "rclone ls remote:bucket && rclone purge remote:bucket",
+            "rclone-purge-bucket",
+        );
+        assert_no_safe_match(&pack, "aws s3 ls; aws s3 rm s3://bucket --recursive");
+    }
+
+    #[test]
+    fn ignores_non_destructive_commands() {
+        let pack = create_pack();
+        assert_allows(&pack, "aws s3 rm s3://bucket/file.txt");
+        assert_allows(&pack, "aws s3 cp ./build s3://bucket/ --recursive");
+        assert_allows(&pack, "gsutil rm gs://bucket/file.txt");
+        assert_allows(&pack, "rclone copy ./site remote:bucket/site");
+        assert_allows(&pack, "rclone sync ./site remote:bucket/site");
+        assert_allows(&pack, "mc rm myminio/bucket/file.txt");
+    }
+}
@@ -2,14 +2,16 @@
 //!
 //! This includes patterns for:
 //! - dd to block devices
-//! - fdisk/parted operations
+//! - fdisk/sfdisk/sgdisk/parted operations
 //! - mkfs (formatting)
+//! - blkdiscard, shred on devices, and hdparm secure erase
 //! - mount/umount operations
 //! - mdadm RAID management
 //! - btrfs filesystem operations
 //! - dmsetup device-mapper operations
 //! - nbd-client network block device
 //! - LVM destructive commands (pvremove, vgremove, lvremove, etc.)
+//! - Windows `format X:` and diskpart scripts
 
 use crate::packs::{DestructivePattern, Pack, SafePattern};
 use crate::{destructive_pattern, safe_pattern};
@@ -21,8 +23,9 @@ pub fn create_pack() -> Pack {
         id: "system.disk".to_string(),
         name: "Disk Operations",
         description: "Protects against destructive disk operations like dd to devices, \
-                      mkfs, partition table modifications, RAID management, \
-                      btrfs/LVM/device-mapper operations, and network block devices",
+                      mkfs, partition table modifications, device discard/secure erase, \
+                      RAID management, btrfs/LVM/device-mapper operations, network \
+                      block devices, and Windows format/diskpart",
         keywords: &[
             "dd",
             "fdisk",
@@ -31,6 +34,15 @@ pub fn create_pack() -> Pack {
             "mount",
             "wipefs",
             "/dev/",
+            "mke2fs",
+            "mkswap",
+            "mkntfs",
+            "mkdosfs",
+            "sfdisk",
+            "sgdisk",
+            "blkdiscard",
+            "shred",
+            "hdparm",
             "mdadm",
             "btrfs",
             "dmsetup",
@@ -42,12 +54,18 @@ pub fn create_pack() -> Pack {
             "lvreduce",
             "lvresize",
             "pvmove",
+            "format",
+            "FORMAT",
+            "diskpart",
+            "DISKPART",
         ],
         safe_patterns: create_safe_patterns(),
         destructive_patterns: create_destructive_patterns(),
         keyword_matcher: None,
         safe_regex_set: None,
         safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
     }
 }
 
@@ -57,6 +75,11 @@ fn create_safe_patterns() -> Vec<SafePattern> {
         safe_pattern!("dd-file-out", r"dd\s+.*of=[^/\s]+\."),
         // dd to /dev/null|zero|full is safe (discard output)
         safe_pattern!("dd-discard", r"dd\s+.*of=/dev/(?:null|zero|full)(?:\s|$)"),
+        // dd writing a regular file under /tmp (disk images, test files)
+        safe_pattern!(
+            "dd-tmp-file",
+            r"\bdd(?:\s+(?:if|bs|ibs|obs|cbs|count|skip|seek|conv|status|iflag|oflag)=[^\s;&|]*)*\s+of=/(?:var/)?tmp/(?:[\w-][\w.-]*/)*[\w-][\w.-]*(?:\s+(?:if|bs|ibs|obs|cbs|count|skip|seek|conv|status|iflag|oflag)=[^\s;&|]*)*\s*$"
+        ),
         // lsblk is safe (read-only)
         safe_pattern!("lsblk", r"\blsblk\b"),
         // fdisk -l (list) is safe
@@ -137,6 +160,12 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
             r"dd\s+.*if=/dev/(?:zero|urandom|random).*of=/dev/",
             "dd from /dev/zero or /dev/urandom to a device will WIPE all data!"
         ),
+        // sfdisk writing a partition table to a device
+        destructive_pattern!(
+            "sfdisk-write",
+            r"\bsfdisk\s+(?:(?:--force|-f|--no-reread|--delete|--wipe(?:=|\s+)\S+|-w\s+\S+)\s+)*/dev/",
+            "sfdisk writes a new partition table to the device, replacing the existing one."
+        ),
         // fdisk (partition editing)
         destructive_pattern!(
             "fdisk-edit",
@@ -149,12 +178,71 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
             r"parted\s+/dev/\S+\s+(?!print)",
             "parted can modify partition tables and cause data loss."
         ),
+        // parted in script mode (`parted -s /dev/sda mklabel gpt`)
+        destructive_pattern!(
+            "parted-script",
+            r"\bparted\b[^;&|]*\s(?:mklabel|mktable|mkpart|rm|resizepart|rescue)\b",
+            "parted mklabel/mkpart/rm rewrites the partition table and can destroy all partitions."
+        ),
+        // sgdisk zap/clear/delete/new
+        destructive_pattern!(
+            "sgdisk-modify",
+            r"\bsgdisk\b[^;&|]*\s(?:--zap(?:-all)?|-[zZo]|--clear|--delete|-d|--new|-n)(?:\s|=|$)",
+            "sgdisk --zap-all/--clear/--delete/--new modifies or destroys the GPT partition table."
+        ),
         // mkfs (format filesystem)
         destructive_pattern!(
             "mkfs",
             r"mkfs(?:\.[a-z0-9]+)?\s+",
             "mkfs formats a partition/device and ERASES all existing data."
         ),
+        // mke2fs/mkswap/mkntfs/mkdosfs (mkfs without the mkfs. prefix)
+        destructive_pattern!(
+            "mkfs-variants",
+            r"\b(?:mke2fs|mkswap|mkntfs|mkdosfs)\s+",
+            "mke2fs/mkswap/mkntfs/mkdosfs format a partition/device and ERASE all existing data."
+        ),
+        // Windows format of a drive letter (`format D: /fs:ntfs /q /y`)
+        destructive_pattern!(
+            "windows-format",
+            r#"(?i)(?<![\w.-])format(?:\.com)?\s+"?[a-z]:[\\/]?"?(?:\s|$)"#,
+            "format X: ERASES the whole volume. With /y there is no confirmation prompt.",
+            Critical
+        ),
+        // `(echo select disk 1 & echo clean) | diskpart`
+        destructive_pattern!(
+            "diskpart-destructive-input",
+            r"(?i)\b(?:clean|delete\s+(?:partition|volume|disk)|format\s+(?:fs=|quick|override))\b[^|\n]*\|\s*diskpart\b",
+            "Piping clean/delete/format into diskpart wipes partitions or whole disks without a prompt.",
+            Critical
+        ),
+        // diskpart /s script.txt, diskpart < script.txt
+        destructive_pattern!(
+            "diskpart-script",
+            r"(?i)\bdiskpart(?:\.exe)?\b(?:[^;&|\n]*\s/s\s|\s*<)",
+            "diskpart /s runs a script of partition commands (clean, delete, format) without prompting."
+        ),
+        // blkdiscard (TRIM every block on the device)
+        destructive_pattern!(
+            "blkdiscard",
+            r"\bblkdiscard\b",
+            "blkdiscard discards every block on the device. Data is unrecoverable on SSDs.",
+            Critical
+        ),
+        // shred on a block device
+        destructive_pattern!(
+            "shred-device",
+            r"\bshred\b[^;&|]*\s/dev/(?:sd|hd|vd|xvd|nvme|mmcblk|md|dm-|loop|mapper/|disk/)",
+            "shred on a block device overwrites the entire disk. Data is unrecoverable.",
+            Critical
+        ),
+        // hdparm ATA secure erase
+        destructive_pattern!(
+            "hdparm-security-erase",
+            r"\bhdparm\b[^;&|]*\s--security-erase(?:-enhanced)?\b",
+            "hdparm --security-erase wipes the whole drive in firmware. It cannot be interrupted or undone.",
+            Critical
+        ),
         // wipefs
         destructive_pattern!(
             "wipefs",
@@ -364,6 +452,7 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::packs::test_helpers::*;
 
     #[test]
     fn wipefs_is_reachable_via_keywords() {
@@ -378,6 +467,71 @@ mod tests {
         assert_eq!(matched.name, Some("wipefs"));
     }
 
+    #[test]
+    fn blocks_device_erasure() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "blkdiscard /dev/nvme0n1", "blkdiscard");
+        assert_blocks_with_pattern(&pack, "shred -vzn 1 /dev/sdb", "shred-device");
+        assert_blocks_with_pattern(
+            &pack,
+            "hdparm --user-master u --security-erase p /dev/sda",
+            "hdparm-security-erase",
+        );
+        assert_blocks_with_pattern(&pack, "mkswap /dev/sdb2", "mkfs-variants");
+        assert_allows(&pack, "shred -u secrets.txt");
+        assert_allows(&pack, "hdparm -I /dev/sda");
+    }
+
+    #[test]
+    fn blocks_partition_table_rewrites() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "parted -s /dev/sda mklabel gpt", "parted-script");
+        assert_blocks_with_pattern(&pack, "sfdisk /dev/sda < layout.sfdisk", "sfdisk-write");
+        assert_blocks_with_pattern(&pack, "sgdisk --zap-all /dev/sdc", "sgdisk-modify");
+        assert_blocks_with_pattern(&pack, "sgdisk -n 1:0:+512M /dev/sdc", "sgdisk-modify");
+        assert_allows(&pack, "sfdisk -l /dev/sda");
+        assert_allows(&pack, "sgdisk -p /dev/sda");
+    }
+
+    #[test]
+    fn dd_to_tmp_file_is_allowed() {
+        let pack = create_pack();
+        assert_allows(&pack, "dd if=/dev/zero of=/tmp/disk.img bs=1M count=64");
+        assert_allows(
+            &pack,
+            "dd if=/dev/sda of=/var/tmp/backup/sda.img status=progress",
+        );
+        assert_blocks_with_pattern(&pack, "dd if=/dev/zero of=/dev/sda bs=1M", "dd-device");
+        assert_blocks(
+            &pack,
+            "dd if=/dev/zero of=/tmp/x.img && dd if=/tmp/x.img of=/dev/sdb",
+            "block device",
+        );
+    }
+
+    #[test]
+    fn blocks_windows_format_and_diskpart() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "format D: /fs:ntfs /q /y", "windows-format");
+        assert_blocks_with_pattern(&pack, r"FORMAT.COM E:\ /Q", "windows-format");
+        assert_blocks_with_pattern(
+            &pack,
+            "(echo select disk 1 & echo clean) | diskpart",
+            "diskpart-destructive-input",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "echo delete partition override | diskpart",
+            "diskpart-destructive-input",
+        );
+        assert_blocks_with_pattern(&pack, "diskpart /s wipe.txt", "diskpart-script");
+        assert_blocks_with_pattern(&pack, "diskpart < wipe.txt", "diskpart-script");
+        assert_allows(&pack, "git log --format=%H -n 5");
+        assert_allows(&pack, "clang-format -i main.c");
+        assert_allows(&pack, "cargo fmt -- --check");
+        assert_allows(&pack, "echo list disk | diskpart");
+    }
+
     #[test]
     fn keyword_absent_skips_pack() {
         let pack = create_pack();
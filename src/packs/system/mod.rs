@@ -2,9 +2,19 @@
 //!
 //! This pack provides protection against destructive system operations:
 //! - Disk operations (dd, fdisk, mkfs)
+//! - Firewall and network reconfiguration (iptables, nft, ufw, ip, ifconfig, route)
 //! - Permission changes (chmod, chown with dangerous patterns)
+//! - Scheduled jobs (crontab, cron spools, atrm, launchctl)
 //! - Service management (systemctl, service)
+//! - Detached sessions (tmux kill-server, screen -X quit)
+//! - Accounts and credentials (userdel, passwd, sudoers, ~/.ssh)
+//! - Virtual machines and containers (machinectl, virsh, VBoxManage, multipass, vagrant)
 
 pub mod disk;
+pub mod network;
 pub mod permissions;
+pub mod scheduling;
 pub mod services;
+pub mod sessions;
+pub mod users;
+pub mod virtualization;
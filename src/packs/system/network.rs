@@ -0,0 +1,300 @@
+//! Network patterns - protections against cutting off network connectivity.
+//!
+//! An agent that flushes the firewall, downs the primary interface, or drops
+//! the default route on a remote host strands every session to it, including
+//! its own. This includes patterns for:
+//! - iptables/ip6tables -F/-X and DROP default policies
+//! - nft flush ruleset
+//! - ufw disable/reset and firewall-cmd --panic-on
+//! - ip link delete and bringing primary interfaces down (ip link, ifconfig, ifdown)
+//! - route del default / ip route del default / ip route flush
+//! - nmcli networking off
+
+use crate::packs::{DestructivePattern, Pack, SafePattern};
+use crate::{destructive_pattern, safe_pattern};
+
+/// Create the Network pack.
+#[must_use]
+pub fn create_pack() -> Pack {
+    Pack {
+        id: "system.network".to_string(),
+        name: "Network",
+        description: "Protects against firewall flushes and network reconfiguration that \
+                      cut off connectivity: iptables -F, nft flush ruleset, ufw \
+                      disable/reset, downing primary interfaces, and deleting the default route",
+        keywords: &[
+            "iptables",
+            "ip6tables",
+            "nft",
+            "ufw",
+            "firewall-cmd",
+            "ip l",
+            "ip r",
+            "ip -",
+            "route",
+            "ifconfig",
+            "ifdown",
+            "nmcli",
+        ],
+        safe_patterns: create_safe_patterns(),
+        destructive_patterns: create_destructive_patterns(),
+        keyword_matcher: None,
+        safe_regex_set: None,
+        safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
+    }
+}
+
+fn create_safe_patterns() -> Vec<SafePattern> {
+    // Anchored to the whole command so a listing cannot whitelist a chained
+    // flush.
+    vec![
+        // listing rules
+        safe_pattern!(
+            "iptables-list",
+            r"^\s*ip6?tables(?:-legacy|-nft)?\s+(?:(?:-t\s+\S+|-[nvx]+|--numeric|--verbose|--line-numbers)\s+)*(?:-[nvx]*[LS][nvx]*|--list|--list-rules)\b[^;&|\n$\x60]*$"
+        ),
+        safe_pattern!(
+            "nft-list",
+            r"^\s*nft\s+(?:-[a-zA-Z]+\s+)*list\b[^;&|\n$\x60]*$"
+        ),
+        safe_pattern!(
+            "ufw-status",
+            r"^\s*ufw\s+(?:status|show|app\s+list|version)\b[^;&|\n$\x60]*$"
+        ),
+        safe_pattern!(
+            "firewall-cmd-query",
+            r"^\s*firewall-cmd(?:\s+--(?:zone|permanent)\S*)*\s+--(?:state|list-\S+|get-\S+|query-\S+|info-\S+)\b[^;&|\n$\x60]*$"
+        ),
+        // showing links, addresses, and routes
+        safe_pattern!(
+            "ip-show",
+            r"^\s*ip\s+(?:-[a-zA-Z0-9]+\s+)*(?:a|addr|address|l|link|r|route|n|neigh|rule)(?:\s+(?:show|list|ls|get)\b[^;&|\n$\x60]*)?\s*$"
+        ),
+        safe_pattern!(
+            "ifconfig-show",
+            r"^\s*ifconfig(?:\s+-a)?(?:\s+[\w.:-]+)?\s*$"
+        ),
+        safe_pattern!("route-show", r"^\s*route(?:\s+-[nveFC]+)*\s*$"),
+        safe_pattern!(
+            "nmcli-read-only",
+            r"^\s*nmcli\s+(?:-\S+\s+)*(?:(?:g|general)(?:\s+status)?|(?:d|dev|device|c|con|connection)(?:\s+(?:show|status)\b[^;&|\n$\x60]*)?|(?:n|networking)(?:\s+connectivity)?)\s*$"
+        ),
+    ]
+}
+
+#[allow(clippy::too_many_lines)]
+fn create_destructive_patterns() -> Vec<DestructivePattern> {
+    vec![
+        // iptables -P INPUT DROP cuts off every connection not explicitly allowed
+        destructive_pattern!(
+            "iptables-policy-drop",
+            r"\bip6?tables(?:-legacy|-nft)?\b[^;&|\n]*\s(?:-P|--policy)\s+(?:INPUT|FORWARD|OUTPUT)\s+(?:DROP|REJECT)\b",
+            "iptables -P ... DROP blocks all traffic that no rule explicitly allows.",
+            Critical,
+            "A DROP default policy takes effect immediately:\n\n\
+             - Unless an ACCEPT rule for the current SSH session already exists, the \
+             session hangs and cannot reconnect\n\
+             - Combined with a flush, the host is unreachable until someone has console \
+             access\n\n\
+             Add the allow rule first and keep a timed rollback:\n  \
+             iptables-save > /tmp/rules.v4\n  \
+             iptables-apply /etc/iptables/rules.v4"
+        ),
+        // iptables -F / -X wipe the rule set
+        destructive_pattern!(
+            "iptables-flush",
+            r"\bip6?tables(?:-legacy|-nft)?\b[^;&|\n]*\s(?:-F|--flush|-X|--delete-chain)(?:\s|$)",
+            "iptables -F/-X deletes every firewall rule in the table.",
+            High,
+            "Flushing iptables removes all rules at once:\n\n\
+             - With a DROP default policy, all traffic (including SSH) is cut off\n\
+             - With an ACCEPT policy, the host is left without a firewall\n\n\
+             Save the current rules first:\n  \
+             iptables-save > /tmp/rules.v4\n\n\
+             Restore them with:\n  \
+             iptables-restore < /tmp/rules.v4"
+        ),
+        destructive_pattern!(
+            "nft-flush-ruleset",
+            r"\bnft\s+(?:-[a-zA-Z]+\s+)*flush\s+ruleset\b",
+            "nft flush ruleset deletes every nftables table, chain, and rule.",
+            High,
+            "nft flush ruleset empties the entire nftables configuration:\n\n\
+             - Every table, chain, set, and rule is removed, for all address families\n\
+             - NAT and port forwarding stop working immediately\n\n\
+             Save the current ruleset first:\n  \
+             nft list ruleset > /tmp/ruleset.nft\n\n\
+             Restore it with:\n  \
+             nft -f /tmp/ruleset.nft"
+        ),
+        destructive_pattern!(
+            "ufw-disable",
+            r"\bufw\s+(?:--force\s+)?disable\b",
+            "ufw disable turns off the host firewall.",
+            High,
+            "ufw disable unloads every ufw rule and stops the firewall:\n\n\
+             - All ports become reachable from the network\n\
+             - The firewall stays off across reboots\n\n\
+             Check what is configured:\n  \
+             ufw status verbose"
+        ),
+        destructive_pattern!(
+            "ufw-reset",
+            r"\bufw\s+(?:--force\s+)?reset\b",
+            "ufw reset disables the firewall and deletes all of its rules.",
+            High,
+            "ufw reset disables ufw and returns it to installation defaults:\n\n\
+             - Every user-added rule (including the SSH allow rule) is removed\n\
+             - Re-enabling afterwards with a deny-incoming default cuts off remote access\n\n\
+             Check what would be lost:\n  \
+             ufw status numbered"
+        ),
+        destructive_pattern!(
+            "firewall-cmd-panic",
+            r"\bfirewall-cmd\b[^;&|\n]*\s--panic-on\b",
+            "firewall-cmd --panic-on drops all incoming and outgoing packets.",
+            Critical,
+            "Panic mode makes firewalld drop every packet:\n\n\
+             - Established connections, including this session, are cut off\n\
+             - Only someone with console access can run --panic-off"
+        ),
+        // ip link delete removes the interface entirely
+        destructive_pattern!(
+            "ip-link-delete",
+            r"\bip\s+(?:-[a-zA-Z0-9]+\s+)*l(?:ink)?\s+del(?:ete)?\b",
+            "ip link delete removes a network interface.",
+            High,
+            "Deleting a link removes the interface and its addresses and routes:\n\n\
+             - Bridges, bonds, and VLANs carrying the host's traffic disappear\n\
+             - Connections over the interface drop immediately\n\n\
+             Inspect the interface first:\n  \
+             ip -d link show DEV"
+        ),
+        // bringing the primary interface down (not lo, docker0, veth*, tun*, ...)
+        destructive_pattern!(
+            "interface-down",
+            r"\b(?:ip\s+(?:-[a-zA-Z0-9]+\s+)*l(?:ink)?\s+set\s+(?:dev\s+)?(?:eth\d+|en[a-z0-9]+|wl[a-z0-9]+|em\d+|bond\d+|br\d+)\s+(?:[^;&|\n]*\s)?down|ifconfig\s+(?:eth\d+|en[a-z0-9]+|wl[a-z0-9]+|em\d+|bond\d+|br\d+)\s+(?:[^;&|\n]*\s)?down|ifdown\s+(?:-\S+\s+)*(?:eth\d+|en[a-z0-9]+|wl[a-z0-9]+|em\d+|bond\d+|br\d+|-a|--all))\b",
+            "Bringing the primary network interface down cuts off network access.",
+            Critical,
+            "Taking down eth*/en*/wl*/bond*/br* interfaces disconnects the host:\n\n\
+             - Remote sessions over the interface hang, and the command to bring it \
+             back up can no longer be sent\n\
+             - Services listening on it become unreachable\n\n\
+             Check which interface carries the default route:\n  \
+             ip route show default"
+        ),
+        destructive_pattern!(
+            "route-delete-default",
+            r"\b(?:route\s+(?:-\S+\s+)*del(?:ete)?\s+(?:-net\s+)?(?:default|0\.0\.0\.0(?:/0)?)\b|ip\s+(?:-[a-zA-Z0-9]+\s+)*r(?:oute)?\s+(?:del(?:ete)?\s+(?:default|0\.0\.0\.0/0)\b|flush\b))",
+            "Deleting the default route (or flushing routes) cuts off traffic beyond the local network.",
+            Critical,
+            "Without a default route the host can only reach directly attached networks:\n\n\
+             - Remote sessions from outside the local subnet drop\n\
+             - DNS, package mirrors, and APIs become unreachable\n\n\
+             Note the current route so it can be restored:\n  \
+             ip route show default"
+        ),
+        destructive_pattern!(
+            "nmcli-networking-off",
+            r"\bnmcli\s+(?:-\S+\s+)*(?:n|networking)\s+off\b",
+            "nmcli networking off disables every NetworkManager-managed interface.",
+            Critical,
+            "nmcli networking off deactivates all interfaces NetworkManager manages:\n\n\
+             - Every connection drops, including remote sessions\n\
+             - It stays off until someone with console access runs `nmcli networking on`"
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_pack_creation() {
+        let pack = create_pack();
+        assert_eq!(pack.id, "system.network");
+        assert!(pack.keywords.contains(&"iptables"));
+        assert!(pack.keywords.contains(&"nft"));
+
+        assert_patterns_compile(&pack);
+        assert_all_patterns_have_reasons(&pack);
+        assert_unique_pattern_names(&pack);
+    }
+
+    #[test]
+    fn allows_read_only_inspection() {
+        let pack = create_pack();
+        assert_safe_pattern_matches(&pack, "iptables -L -n");
+        assert_safe_pattern_matches(&pack, "iptables -t nat -S");
+        assert_safe_pattern_matches(&pack, "nft list ruleset");
+        assert_safe_pattern_matches(&pack, "ufw status verbose");
+        assert_safe_pattern_matches(&pack, "firewall-cmd --list-all");
+        assert_safe_pattern_matches(&pack, "ip -br addr show");
+        assert_safe_pattern_matches(&pack, "ip route");
+        assert_safe_pattern_matches(&pack, "ifconfig eth0");
+        assert_safe_pattern_matches(&pack, "route -n");
+        assert_safe_pattern_matches(&pack, "nmcli device status");
+    }
+
+    #[test]
+    fn safe_patterns_do_not_cover_chained_commands() {
+        let pack = create_pack();
+        assert_no_safe_match(&pack, "iptables -L && iptables -F");
+        assert_no_safe_match(&pack, "ip route; ip route del default");
+    }
+
+    #[test]
+    fn blocks_firewall_teardown() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "iptables -F", "iptables-flush");
+        assert_blocks_with_pattern(&pack, "sudo iptables -t nat --flush", "iptables-flush");
+        assert_blocks_with_pattern(&pack, "ip6tables -X", "iptables-flush");
+        assert_blocks_with_pattern(&pack, "iptables -P INPUT DROP", "iptables-policy-drop");
+        assert_blocks_with_pattern(&pack, "nft flush ruleset", "nft-flush-ruleset");
+        assert_blocks_with_pattern(&pack, "ufw disable", "ufw-disable");
+        assert_blocks_with_pattern(&pack, "ufw --force reset", "ufw-reset");
+        assert_blocks_with_pattern(&pack, "firewall-cmd --panic-on", "firewall-cmd-panic");
+        assert_allows(&pack, "iptables -A INPUT -p tcp --dport 22 -j ACCEPT");
+        assert_allows(&pack, "ufw allow 22/tcp");
+        assert_allows(&pack, "nft add rule inet filter input tcp dport 22 accept");
+    }
+
+    #[test]
+    fn blocks_interface_and_route_changes() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "ip link delete br0", "ip-link-delete");
+        assert_blocks_with_pattern(&pack, "ip link set eth0 down", "interface-down");
+        assert_blocks_with_pattern(&pack, "ip link set dev enp3s0 down", "interface-down");
+        assert_blocks_with_pattern(&pack, "ifconfig eth0 down", "interface-down");
+        assert_blocks_with_pattern(&pack, "ifdown -a", "interface-down");
+        assert_blocks_with_pattern(&pack, "route del default", "route-delete-default");
+        assert_blocks_with_pattern(&pack, "ip route del default", "route-delete-default");
+        assert_blocks_with_pattern(&pack, "ip route flush table main", "route-delete-default");
+        assert_blocks_with_pattern(&pack, "nmcli networking off", "nmcli-networking-off");
+        assert_blocks_with_pattern(&pack, "ip l del br0", "ip-link-delete");
+        assert_blocks_with_pattern(&pack, "ip l set eth0 down", "interface-down");
+        assert_blocks_with_pattern(&pack, "ip r flush", "route-delete-default");
+        assert_blocks_with_pattern(&pack, "ip r del default", "route-delete-default");
+        assert_allows(&pack, "ip link set docker0 down");
+        assert_allows(&pack, "ifconfig lo down");
+        assert_allows(&pack, "ip link set eth0 up");
+        assert_allows(&pack, "ip route add default via 10.0.0.1");
+        assert_allows(&pack, "route del -net 10.8.0.0 netmask 255.255.0.0");
+    }
+
+    #[test]
+    fn short_ip_subcommands_are_reachable_via_keywords() {
+        let pack = create_pack();
+        for cmd in ["ip l del br0", "ip l set eth0 down", "ip r flush"] {
+            assert!(
+                pack.might_match(cmd),
+                "{cmd} should be included in pack keywords to prevent false negatives"
+            );
+        }
+        assert!(!pack.might_match("pip install requests"));
+    }
+}
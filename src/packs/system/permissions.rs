@@ -1,10 +1,14 @@
 //! Permissions patterns - protections against dangerous permission changes.
 //!
 //! This includes patterns for:
-//! - chmod 777 (world writable)
+//! - chmod 777 (world writable), including recursive world-writable modes
 //! - chmod -R on system directories
-//! - chown -R on system directories
-//! - setfacl with dangerous patterns
+//! - chown/chgrp -R on system directories
+//! - setfacl with dangerous patterns, including `setfacl -Rb`
+//! - chattr -i/-a lifting immutable or append-only protection
+//! - umask 000
+//!
+//! Permission changes confined to project-local paths or /tmp are allowed.
 
 use crate::packs::{DestructivePattern, Pack, PatternSuggestion, SafePattern};
 use crate::{destructive_pattern, safe_pattern};
@@ -42,22 +46,26 @@ pub fn create_pack() -> Pack {
         id: "system.permissions".to_string(),
         name: "Permissions",
         description: "Protects against dangerous permission changes like chmod 777, \
-                      recursive chmod/chown on system directories",
-        keywords: &["chmod", "chown", "chgrp", "setfacl"],
+                      recursive chmod/chown on system directories, ACL stripping, \
+                      chattr -i, and umask 000",
+        keywords: &["chmod", "chown", "chgrp", "setfacl", "chattr", "umask"],
         safe_patterns: create_safe_patterns(),
         destructive_patterns: create_destructive_patterns(),
         keyword_matcher: None,
         safe_regex_set: None,
         safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
     }
 }
 
 fn create_safe_patterns() -> Vec<SafePattern> {
     vec![
-        // chmod on files (not directories recursively)
+        // chmod on files (not directories recursively); anchored so a leading
+        // chmod cannot vouch for a chained command
         safe_pattern!(
             "chmod-non-recursive",
-            r"chmod\s+(?!-[rR])(?:\d{3,4}|[ugoa][+-][rwxXst]+)\s+[^/]"
+            r"^\s*chmod\s+(?!-[rR])(?:\d{3,4}|[ugoa][+-][rwxXst]+)\s+[^/][^;&|\x60\n]*$"
         ),
         // stat is safe (read-only)
         safe_pattern!("stat", r"\bstat\b"),
@@ -67,11 +75,46 @@ fn create_safe_patterns() -> Vec<SafePattern> {
         safe_pattern!("getfacl", r"\bgetfacl\b"),
         // namei is safe
         safe_pattern!("namei", r"\bnamei\b"),
+        // chmod confined to project-local paths or /tmp (no `..`, no chaining)
+        safe_pattern!(
+            "chmod-local-path",
+            r"^\s*chmod(?:\s+-[a-zA-Z]+|\s+--(?:recursive|verbose|changes|silent|quiet))*\s+(?:[0-7]{3,4}|[ugoa]*[-+=][rwxXst]*(?:,[ugoa]*[-+=][rwxXst]*)*)(?:\s+(?:\.|(?:\./)?\.?[\w@+-][\w.@+-]*(?:/\.?[\w@+-][\w.@+-]*)*/?|/(?:var/)?tmp/\.?[\w@+-][\w.@+-]*(?:/\.?[\w@+-][\w.@+-]*)*/?))+\s*$"
+        ),
+        // chown/chgrp confined to project-local paths or /tmp
+        safe_pattern!(
+            "chown-local-path",
+            r"^\s*(?:chown|chgrp)(?:\s+-[a-zA-Z]+|\s+--(?:recursive|verbose|changes|silent|quiet))*\s+[\w.-]+(?::[\w.-]*)?(?:\s+(?:\.|(?:\./)?\.?[\w@+-][\w.@+-]*(?:/\.?[\w@+-][\w.@+-]*)*/?|/(?:var/)?tmp/\.?[\w@+-][\w.@+-]*(?:/\.?[\w@+-][\w.@+-]*)*/?))+\s*$"
+        ),
+        // setfacl confined to project-local paths or /tmp
+        safe_pattern!(
+            "setfacl-local-path",
+            r"^\s*setfacl(?:\s+-[a-zA-Z]+|\s+--(?:recursive|remove-all|remove-default|modify|remove))*(?:\s+[dugmo][\w.-]*:[\w:,.-]*)?(?:\s+(?:\.|(?:\./)?\.?[\w@+-][\w.@+-]*(?:/\.?[\w@+-][\w.@+-]*)*/?|/(?:var/)?tmp/\.?[\w@+-][\w.@+-]*(?:/\.?[\w@+-][\w.@+-]*)*/?))+\s*$"
+        ),
+        // chattr confined to project-local paths or /tmp
+        safe_pattern!(
+            "chattr-local-path",
+            r"^\s*chattr(?:\s+-[RVf]+)*\s+[-+=][aAcCdDeEFijmPsStTux]+(?:\s+(?:\.|(?:\./)?\.?[\w@+-][\w.@+-]*(?:/\.?[\w@+-][\w.@+-]*)*/?|/(?:var/)?tmp/\.?[\w@+-][\w.@+-]*(?:/\.?[\w@+-][\w.@+-]*)*/?))+\s*$"
+        ),
     ]
 }
 
 fn create_destructive_patterns() -> Vec<DestructivePattern> {
     vec![
+        // chmod -R with a world-writable mode (777, 666, o+w, a+w, ...)
+        destructive_pattern!(
+            "chmod-recursive-world-writable",
+            r#"\bchmod\s+(?:-\S+\s+)*(?:-[a-zA-Z]*R[a-zA-Z]*|--recursive)\s+(?:-\S+\s+)*["']?(?:0*[0-7]?[0-7][0-7][2367]|[ugoa]*[oa][ugoa]*[+=][rwxXst]*w[rwxXst]*)(?:[\s"',]|$)"#,
+            "chmod -R with a world-writable mode (e.g. 777) exposes an entire directory tree.",
+            Critical,
+            "Recursively making a tree world-writable lets any local user or process \
+             modify or replace every file in it, including scripts and binaries that \
+             other users run. Outside a project directory or /tmp this usually hits \
+             home directories, SSH keys, or system files, and restoring the original \
+             modes requires a backup.\n\n\
+             Grant only the access that is needed:\n  \
+             chmod -R u+rwX,go+rX <path>",
+            CHMOD_777_SUGGESTIONS
+        ),
         // chmod 777 (world writable)
         destructive_pattern!(
             "chmod-777",
@@ -86,7 +129,7 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
         // chmod -R on root or system directories
         destructive_pattern!(
             "chmod-recursive-root",
-            r"chmod\s+(?:.*(?:-[rR]|--recursive)).*\s+/(?:$|bin|boot|dev|etc|lib|lib64|opt|proc|root|run|sbin|srv|sys|usr|var)\b",
+            r"chmod\s+(?:.*(?:-[rR]|--recursive)).*\s+/(?:(?:bin|boot|dev|etc|lib|lib64|opt|proc|root|run|sbin|srv|sys|usr|var)\b|\*|\s|$)",
             "chmod -R on system directories can break system permissions.",
             Critical,
             "Recursively changing permissions on system directories can render the system \
@@ -99,7 +142,7 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
         // chown -R on root or system directories
         destructive_pattern!(
             "chown-recursive-root",
-            r"chown\s+(?:.*(?:-[rR]|--recursive)).*\s+/(?:$|bin|boot|dev|etc|lib|lib64|opt|proc|root|run|sbin|srv|sys|usr|var)\b",
+            r"chown\s+(?:.*(?:-[rR]|--recursive)).*\s+/(?:(?:bin|boot|dev|etc|lib|lib64|opt|proc|root|run|sbin|srv|sys|usr|var)\b|\*|\s|$)",
             "chown -R on system directories can break system ownership.",
             High,
             "Recursive ownership changes on system directories can disrupt services, \
@@ -107,6 +150,17 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              path or a shallow find before applying broader changes.",
             CHOWN_RECURSIVE_SUGGESTIONS
         ),
+        // chgrp -R on root or system directories
+        destructive_pattern!(
+            "chgrp-recursive-root",
+            r"chgrp\s+(?:.*(?:-[rR]|--recursive)).*\s+/(?:(?:bin|boot|dev|etc|lib|lib64|opt|proc|root|run|sbin|srv|sys|usr|var)\b|\*|\s|$)",
+            "chgrp -R on system directories can break system group ownership.",
+            High,
+            "Recursive group changes on system directories can lock services out of \
+             files they need and break package-managed permissions. Change a single \
+             path first and verify the result.",
+            CHOWN_RECURSIVE_SUGGESTIONS
+        ),
         // chmod u+s (setuid)
         destructive_pattern!(
             "chmod-setuid",
@@ -152,7 +206,7 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
         // setfacl with dangerous patterns
         destructive_pattern!(
             "setfacl-all",
-            r"setfacl\s+.*-[rR].*\s+/(?:$|bin|boot|dev|etc|lib|lib64|opt|proc|root|run|sbin|srv|sys|usr|var)\b",
+            r"setfacl\s+.*-[rR].*\s+/(?:(?:bin|boot|dev|etc|lib|lib64|opt|proc|root|run|sbin|srv|sys|usr|var)\b|\*|\s|$)",
             "setfacl -R on system directories can modify access control across the filesystem.",
             Critical,
             "Recursively modifying ACLs on system directories changes fine-grained access \
@@ -164,5 +218,152 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              Apply to a specific file instead of recursively:\n  \
              setfacl -m u:<user>:rwx <specific-file>"
         ),
+        // setfacl -Rb: strip every ACL entry in a tree
+        destructive_pattern!(
+            "setfacl-remove-all-recursive",
+            r"\bsetfacl\b[^;&|]*\s(?:-[a-zA-Z]*(?:R[a-zA-Z]*b|b[a-zA-Z]*R)[a-zA-Z]*|(?:-[a-zA-Z]*R[a-zA-Z]*|--recursive)\s(?:[^;&|]*\s)?(?:-[a-zA-Z]*b[a-zA-Z]*|--remove-all)|(?:-[a-zA-Z]*b[a-zA-Z]*|--remove-all)\s(?:[^;&|]*\s)?(?:-[a-zA-Z]*R[a-zA-Z]*|--recursive))(?:\s|$)",
+            "setfacl -Rb removes every ACL entry in the directory tree.",
+            High,
+            "Stripping ACLs recursively revokes every extended grant in the tree at \
+             once. Services and users that rely on those entries lose access, and the \
+             old entries are gone unless they were saved.\n\n\
+             Save the current ACLs first:\n  \
+             getfacl -R <path> > acls.backup\n\n\
+             Restore them later with:\n  \
+             setfacl --restore=acls.backup"
+        ),
+        // chattr -i / -a: lift immutable or append-only protection
+        destructive_pattern!(
+            "chattr-remove-immutable",
+            r"\bchattr\s+(?:-[RVf]+\s+)*-[aAcCdDeEFijmPsStTux]*[ia][aAcCdDeEFijmPsStTux]*\s",
+            "chattr -i/-a removes immutable or append-only protection from files.",
+            High,
+            "The immutable and append-only attributes are usually set deliberately to \
+             protect files such as /etc/passwd, /etc/resolv.conf, or audit logs from \
+             modification, even by root. Removing them re-opens those files to edits \
+             and truncation.\n\n\
+             Check which attributes are set:\n  \
+             lsattr <path>"
+        ),
+        // umask 000: every new file world-writable
+        destructive_pattern!(
+            "umask-000",
+            r"\bumask\s+(?:-S\s+)?(?:0+|a=rwx|u=rwx,g=rwx,o=rwx)(?:\s|;|&|\||$)",
+            "umask 000 makes every file created afterwards world-writable.",
+            Medium,
+            "With a umask of 000, files and directories created by this shell and its \
+             children get 666/777 permissions, so any local user can modify them. \
+             Prefer umask 022 (or 002 for shared group directories)."
+        ),
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::packs::Severity;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn blocks_recursive_changes_on_system_paths() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "chmod -R 777 /", "chmod-recursive-world-writable");
+        assert_blocks_with_severity(&pack, "chmod -R a+w /srv/data", Severity::Critical);
+        assert_blocks_with_pattern(&pack, "chown -R nobody /", "chown-recursive-root");
+        assert_blocks_with_pattern(&pack, "chown -R app:app /etc", "chown-recursive-root");
+        assert_blocks_with_pattern(
+            &pack,
+            "chown --recursive app /usr/*",
+            "chown-recursive-root",
+        );
+        assert_blocks_with_pattern(&pack, "chgrp -R staff /var", "chgrp-recursive-root");
+        assert_blocks_with_pattern(&pack, "chmod -R 755 /", "chmod-recursive-root");
+    }
+
+    #[test]
+    fn blocks_acl_attribute_and_umask_changes() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "setfacl -Rb /data/shared",
+            "setfacl-remove-all-recursive",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "setfacl --remove-all --recursive /home/team",
+            "setfacl-remove-all-recursive",
+        );
+        assert_blocks_with_pattern(&pack, "chattr -i /etc/passwd", "chattr-remove-immutable");
+        assert_blocks_with_pattern(
+            &pack,
+            "chattr -R -a /var/log/audit",
+            "chattr-remove-immutable",
+        );
+        assert_blocks_with_pattern(&pack, "umask 000", "umask-000");
+        assert_blocks_with_pattern(&pack, "umask 0 && make install", "umask-000");
+        assert_allows(&pack, "umask 022");
+        assert_allows(&pack, "chattr +i /etc/resolv.conf");
+        assert_allows(&pack, "lsattr /etc/passwd");
+    }
+
+    #[test]
+    fn allows_project_local_and_tmp_paths() {
+        let pack = create_pack();
+        assert_allows(&pack, "chmod -R 777 ./build");
+        assert_allows(&pack, "chmod -R a+w node_modules/.cache");
+        assert_allows(&pack, "chmod -R 777 /tmp/test-fixtures");
+        assert_allows(&pack, "chown -R 1000:1000 ./data");
+        assert_allows(&pack, "setfacl -Rb ./shared");
+        assert_allows(&pack, "setfacl -R -m u:ci:rwX /tmp/workspace");
+        assert_allows(&pack, "chattr -i ./vendor/lock.txt");
+    }
+
+    #[test]
+    fn local_path_exemptions_do_not_cover_escapes_or_chains() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "chmod -R 777 ../../etc",
+            "chmod-recursive-world-writable",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "chmod -R 777 /tmp/../etc",
+            "chmod-recursive-world-writable",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "chmod -R 777 ./build && chmod -R 777 ~",
+            "chmod-recursive-world-writable",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "chmod 644 notes.txt; chattr -i /etc/shadow",
+            "chattr-remove-immutable",
+        );
+    }
+
+    #[test]
+    fn chgrp_chattr_and_umask_are_reachable_via_keywords() {
+        let pack = create_pack();
+        let mut config = Config::default();
+        config.packs.enabled.push("system.permissions".to_string());
+        for (cmd, rule) in [
+            ("chgrp -R staff /var", "chgrp-recursive-root"),
+            ("chattr -i /etc/passwd", "chattr-remove-immutable"),
+            ("umask 000", "umask-000"),
+        ] {
+            assert!(
+                pack.might_match(cmd),
+                "{cmd} should be included in pack keywords to prevent false negatives"
+            );
+            let snapshot = eval_snapshot_with_config(cmd, &config);
+            assert_eq!(
+                snapshot.rule_id.as_deref(),
+                Some(format!("system.permissions:{rule}").as_str()),
+                "{cmd} should be caught through the registry keyword prefilter"
+            );
+        }
+    }
+}
@@ -0,0 +1,238 @@
+//! Scheduling patterns - protections against wiping scheduled jobs.
+//!
+//! Cron tables, at queues, and launchd jobs run backups, certificate renewals,
+//! and log rotation. They are rarely kept in version control, so deleting them
+//! loses the only copy, and nothing fails loudly until a job that should have
+//! run did not. This includes patterns for:
+//! - crontab -r and replacing a crontab with /dev/null
+//! - Deleting /etc/crontab, /etc/cron.d/*, or /var/spool/cron
+//! - Stopping or disabling the cron/atd services
+//! - Bulk atrm / at -r
+//! - launchctl unload/bootout of system daemons
+
+use crate::packs::{DestructivePattern, Pack, PatternSuggestion, SafePattern};
+use crate::{destructive_pattern, safe_pattern};
+
+// ============================================================================
+// Suggestion constants (must be 'static for the pattern struct)
+// ============================================================================
+
+const CRONTAB_REMOVE_SUGGESTIONS: &[PatternSuggestion] = &[
+    PatternSuggestion::new(
+        "crontab -l > ~/crontab.bak",
+        "Save the current crontab before changing it",
+    ),
+    PatternSuggestion::new("crontab -e", "Remove only the entries you no longer need"),
+];
+
+/// Create the Scheduling pack.
+#[must_use]
+pub fn create_pack() -> Pack {
+    Pack {
+        id: "system.scheduling".to_string(),
+        name: "Scheduling",
+        description: "Protects scheduled jobs: crontab -r, deleting /etc/cron.d and cron \
+                      spools, disabling the cron or atd service, bulk atrm, and launchctl \
+                      unload/bootout of system daemons",
+        keywords: &["crontab", "cron", "atd", "atrm", "at -", "launchctl"],
+        safe_patterns: create_safe_patterns(),
+        destructive_patterns: create_destructive_patterns(),
+        keyword_matcher: None,
+        safe_regex_set: None,
+        safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
+    }
+}
+
+fn create_safe_patterns() -> Vec<SafePattern> {
+    // Anchored to the whole command so a listing cannot whitelist a chained
+    // removal.
+    vec![
+        safe_pattern!(
+            "crontab-list",
+            r"^\s*crontab\s+(?:-u\s+[\w.-]+\s+)?-l\s*(?:>\s*[\w./~-]+\s*)?$"
+        ),
+        safe_pattern!("atq", r"^\s*atq\b[^;&|\n$\x60]*$"),
+        safe_pattern!(
+            "launchctl-read-only",
+            r"^\s*launchctl\s+(?:list|print|print-disabled|blame)\b[^;&|\n$\x60]*$"
+        ),
+        safe_pattern!(
+            "systemctl-list-timers",
+            r"^\s*systemctl\s+list-timers\b[^;&|\n$\x60]*$"
+        ),
+    ]
+}
+
+#[allow(clippy::too_many_lines)]
+fn create_destructive_patterns() -> Vec<DestructivePattern> {
+    vec![
+        // crontab -r deletes the whole table without asking
+        destructive_pattern!(
+            "crontab-remove",
+            r"\bcrontab\s+(?:-u\s+\S+\s+)?-[a-zA-Z]*r[a-zA-Z]*\b",
+            "crontab -r deletes the entire crontab with no backup.",
+            High,
+            "crontab -r removes every job in the user's crontab at once:\n\n\
+             - There is no undo and no copy kept by cron\n\
+             - -r sits next to -e on the keyboard, so this is a common slip\n\n\
+             Save the table first, then edit out only what should go:\n  \
+             crontab -l > ~/crontab.bak\n  \
+             crontab -e",
+            CRONTAB_REMOVE_SUGGESTIONS
+        ),
+        destructive_pattern!(
+            "crontab-replace-empty",
+            r"\bcrontab\s+(?:-u\s+\S+\s+)?/dev/null\b",
+            "Installing /dev/null as the crontab deletes every job.",
+            High,
+            "crontab <file> replaces the whole table with the file's contents. With \
+             /dev/null every scheduled job is dropped.\n\n\
+             Back up the current table first:\n  \
+             crontab -l > ~/crontab.bak"
+        ),
+        // rm /etc/cron.d/*, /etc/crontab, /var/spool/cron
+        destructive_pattern!(
+            "cron-files-delete",
+            r"\b(?:rm|shred|unlink)\s+(?:[^;&|\n]*\s)?(?:/etc/(?:cron\.(?:d|hourly|daily|weekly|monthly)|crontab|anacrontab)|/var/spool/cron)\b",
+            "Deleting system cron files removes scheduled maintenance jobs.",
+            High,
+            "/etc/crontab, /etc/cron.d, the cron.{hourly,daily,weekly,monthly} \
+             directories, and /var/spool/cron hold jobs installed by packages and \
+             admins:\n\n\
+             - Log rotation, certificate renewal, and backups silently stop\n\
+             - Package-owned files only come back on reinstall\n\n\
+             Disable a single job by commenting it out, or move the file aside:\n  \
+             mv /etc/cron.d/<job> /root/<job>.disabled"
+        ),
+        // systemctl disable --now cron / service crond stop
+        destructive_pattern!(
+            "cron-service-disable",
+            r"\b(?:systemctl\s+(?:[^;&|\n]*\s)?(?:stop|disable|mask)\s+(?:[^;&|\n]*\s)?(?:cron|crond|cronie|anacron|atd)(?:\.service)?|service\s+(?:cron|crond|cronie|anacron|atd)\s+stop)(?:\s|$|[;&|)])",
+            "Stopping or disabling cron/atd halts every scheduled job on the host.",
+            High,
+            "The cron and atd daemons run all scheduled jobs for every user:\n\n\
+             - disable --now or mask keeps them off across reboots\n\
+             - Nothing reports the jobs that stop running\n\n\
+             Disable the single job you care about instead, or check what is \
+             scheduled first:\n  \
+             systemctl list-timers\n  \
+             ls /etc/cron.d"
+        ),
+        // atrm $(atq | cut -f1), atq | xargs atrm
+        destructive_pattern!(
+            "at-remove-bulk",
+            r"(?:\bxargs\s+(?:[^;&|\n]*\s)?atrm\b|\b(?:atrm|at\s+-[rd])\s+[^;&|\n]*(?:\$\(|\x60)|\b(?:atrm|at\s+-[rd])(?:\s+\d+){2,})",
+            "Bulk atrm removes many queued at jobs at once.",
+            Medium,
+            "Feeding atq output into atrm (or passing several job ids) empties the at \
+             queue:\n\n\
+             - Queued jobs are gone with no record of what they would have run\n\n\
+             Inspect each job and remove only the ones you mean to:\n  \
+             at -c <job>\n  \
+             atrm <job>"
+        ),
+        // launchctl unload /Library/LaunchDaemons/..., bootout system/...
+        destructive_pattern!(
+            "launchctl-unload-system",
+            r"\blaunchctl\s+(?:unload|bootout|disable|remove)\s+(?:[^;&|\n]*\s)?(?:/System/Library/Launch(?:Daemons|Agents)|/Library/Launch(?:Daemons|Agents)|system(?:/|\s|$))",
+            "Unloading system launchd daemons stops macOS services and scheduled jobs.",
+            High,
+            "launchctl unload/bootout on /Library/LaunchDaemons or the system domain \
+             stops daemons for every user:\n\n\
+             - With -w or disable, the job stays off after reboot\n\
+             - Apple daemons under /System can break updates, networking, or login\n\n\
+             Inspect the job first, and prefer a one-off stop:\n  \
+             launchctl print system/<label>\n  \
+             launchctl kickstart -k system/<label>"
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_pack_creation() {
+        let pack = create_pack();
+        assert_eq!(pack.id, "system.scheduling");
+        assert!(pack.keywords.contains(&"crontab"));
+        assert!(pack.keywords.contains(&"launchctl"));
+
+        assert_patterns_compile(&pack);
+        assert_all_patterns_have_reasons(&pack);
+        assert_unique_pattern_names(&pack);
+    }
+
+    #[test]
+    fn allows_listing() {
+        let pack = create_pack();
+        assert_safe_pattern_matches(&pack, "crontab -l");
+        assert_safe_pattern_matches(&pack, "crontab -u deploy -l");
+        assert_safe_pattern_matches(&pack, "crontab -l > ~/crontab.bak");
+        assert_safe_pattern_matches(&pack, "atq");
+        assert_safe_pattern_matches(&pack, "launchctl list");
+        assert_safe_pattern_matches(&pack, "systemctl list-timers --all");
+        assert_no_safe_match(&pack, "crontab -l; crontab -r");
+    }
+
+    #[test]
+    fn blocks_crontab_wipes() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "crontab -r", "crontab-remove");
+        assert_blocks_with_pattern(&pack, "crontab -u deploy -r", "crontab-remove");
+        assert_blocks_with_pattern(&pack, "crontab /dev/null", "crontab-replace-empty");
+        assert_blocks_with_pattern(&pack, "rm /etc/cron.d/*", "cron-files-delete");
+        assert_blocks_with_pattern(&pack, "sudo rm -rf /var/spool/cron", "cron-files-delete");
+        assert_blocks_with_pattern(&pack, "rm /etc/crontab", "cron-files-delete");
+        assert_allows(&pack, "crontab -e");
+        assert_allows(&pack, "crontab jobs.txt");
+        assert_allows(&pack, "cat /etc/cron.d/certbot");
+    }
+
+    #[test]
+    fn blocks_disabling_schedulers() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "systemctl disable --now cron",
+            "cron-service-disable",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "systemctl mask crond.service",
+            "cron-service-disable",
+        );
+        assert_blocks_with_pattern(&pack, "service cron stop", "cron-service-disable");
+        assert_blocks_with_pattern(&pack, "systemctl stop atd", "cron-service-disable");
+        assert_allows(&pack, "systemctl restart cron");
+        assert_allows(&pack, "systemctl status crond");
+        assert_allows(&pack, "service cron status");
+    }
+
+    #[test]
+    fn blocks_bulk_at_and_launchd_removal() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "atrm $(atq | cut -f1)", "at-remove-bulk");
+        assert_blocks_with_pattern(&pack, "atq | cut -f1 | xargs atrm", "at-remove-bulk");
+        assert_blocks_with_pattern(&pack, "at -r 3 4 5", "at-remove-bulk");
+        assert_allows(&pack, "atrm 12");
+        assert_blocks_with_pattern(
+            &pack,
+            "sudo launchctl unload -w /Library/LaunchDaemons/com.example.backup.plist",
+            "launchctl-unload-system",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "launchctl bootout system/com.apple.softwareupdated",
+            "launchctl-unload-system",
+        );
+        assert_allows(
+            &pack,
+            "launchctl unload ~/Library/LaunchAgents/com.example.sync.plist",
+        );
+    }
+}
@@ -0,0 +1,162 @@
+//! Detached session patterns - protections against tearing down tmux and
+//! screen sessions.
+//!
+//! Long-running work (builds, migrations, dev servers, an operator's shell)
+//! often lives in a tmux or screen session. The commands those sessions are
+//! told to run (`tmux send-keys`, `screen -X stuff`, `tmux new -d '<cmd>'`)
+//! are evaluated by the evaluator itself; this pack covers the multiplexer
+//! commands that destroy the sessions. This includes patterns for:
+//! - tmux kill-server and kill-session -a
+//! - screen -X quit
+//! - pkill/killall of tmux or screen
+
+use crate::packs::{DestructivePattern, Pack, PatternSuggestion, SafePattern};
+use crate::{destructive_pattern, safe_pattern};
+
+// ============================================================================
+// Suggestion constants (must be 'static for the pattern struct)
+// ============================================================================
+
+const TMUX_KILL_SERVER_SUGGESTIONS: &[PatternSuggestion] = &[
+    PatternSuggestion::new("tmux ls", "List the sessions the server is running"),
+    PatternSuggestion::new(
+        "tmux kill-session -t <session>",
+        "Close only the session you started",
+    ),
+];
+
+/// Create the Detached Sessions pack.
+#[must_use]
+pub fn create_pack() -> Pack {
+    Pack {
+        id: "system.sessions".to_string(),
+        name: "Detached Sessions",
+        description: "Protects tmux and screen sessions: tmux kill-server, kill-session -a, \
+                      screen -X quit, and pkill/killall of the multiplexer",
+        keywords: &["tmux", "screen"],
+        safe_patterns: create_safe_patterns(),
+        destructive_patterns: create_destructive_patterns(),
+        keyword_matcher: None,
+        safe_regex_set: None,
+        safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
+    }
+}
+
+fn create_safe_patterns() -> Vec<SafePattern> {
+    // Anchored to the whole command so a listing cannot whitelist a chained
+    // kill-server.
+    vec![
+        safe_pattern!(
+            "tmux-read-only",
+            r"^\s*tmux\s+(?:ls|list-sessions|list-windows|lsw|list-panes|lsp|has-session|has|display-message|display|show-options|show|capture-pane|capturep)\b[^;&|\n$\x60]*$"
+        ),
+        safe_pattern!("screen-list", r"^\s*screen\s+-(?:ls|list)\s*$"),
+    ]
+}
+
+fn create_destructive_patterns() -> Vec<DestructivePattern> {
+    vec![
+        // tmux kill-server ends every session on the socket
+        destructive_pattern!(
+            "tmux-kill-server",
+            r"\btmux\s+(?:-[2CDluNv]+\s+|-[LSfT]\s*\S+\s+)*kill-server\b",
+            "tmux kill-server ends every session and the processes running in them.",
+            High,
+            "kill-server stops the tmux server for the socket:\n\n\
+             - Every session, window, and pane is closed at once\n\
+             - Builds, migrations, and servers running in them are killed\n\
+             - Unsaved scrollback and shell state are lost\n\n\
+             Close only your own session instead:\n  \
+             tmux kill-session -t <session>",
+            TMUX_KILL_SERVER_SUGGESTIONS
+        ),
+        // tmux kill-session -a kills all sessions but the target
+        destructive_pattern!(
+            "tmux-kill-session-all",
+            r"\btmux\s+(?:-[2CDluNv]+\s+|-[LSfT]\s*\S+\s+)*kill-session\s+(?:[^;&|\n]*\s)?-[a-zA-Z]*a\b",
+            "tmux kill-session -a ends every other session on the server.",
+            Medium,
+            "kill-session -a closes all sessions except the target one:\n\n\
+             - Sessions other users or agents started are killed with it\n\
+             - Processes running in their panes receive SIGHUP\n\n\
+             List sessions first and close them by name:\n  \
+             tmux ls\n  \
+             tmux kill-session -t <session>"
+        ),
+        // screen -X quit terminates the selected session
+        destructive_pattern!(
+            "screen-quit",
+            r"\bscreen\s+(?:[^;&|\n]*\s)?-X\s+quit\b",
+            "screen -X quit terminates the session and every window in it.",
+            Medium,
+            "quit kills all windows of the session:\n\n\
+             - Without -S it applies to whichever session screen picks\n\
+             - Processes running in the windows are killed\n\n\
+             Check the session first:\n  \
+             screen -ls"
+        ),
+        // pkill tmux / killall screen
+        destructive_pattern!(
+            "kill-multiplexer-processes",
+            r"\b(?:pkill|killall)\s+(?:-\S+\s+)*(?:tmux|screen|SCREEN)(?::\s*\S+)?(?:\s|$|[;&|)])",
+            "Killing tmux or screen processes ends every session they host.",
+            Medium,
+            "pkill/killall match every tmux or screen process on the host:\n\n\
+             - All sessions of the user (or of everyone, as root) are ended\n\
+             - Work running inside them is killed without warning\n\n\
+             End a single session through the multiplexer instead:\n  \
+             tmux kill-session -t <session>\n  \
+             screen -S <session> -X quit"
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::Severity;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_pack_creation() {
+        let pack = create_pack();
+        assert_eq!(pack.id, "system.sessions");
+        assert!(pack.keywords.contains(&"tmux"));
+        assert!(pack.keywords.contains(&"screen"));
+
+        assert_patterns_compile(&pack);
+        assert_all_patterns_have_reasons(&pack);
+        assert_unique_pattern_names(&pack);
+    }
+
+    #[test]
+    fn allows_listing() {
+        let pack = create_pack();
+        assert_safe_pattern_matches(&pack, "tmux ls");
+        assert_safe_pattern_matches(&pack, "tmux capture-pane -p -t work");
+        assert_safe_pattern_matches(&pack, "screen -ls");
+        assert_no_safe_match(&pack, "tmux ls && tmux kill-server");
+        assert_allows(&pack, "tmux kill-session -t build");
+        assert_allows(&pack, "tmux new-session -d -s build 'cargo build'");
+        assert_allows(&pack, "screen -S build -X stuff 'cargo test\\n'");
+    }
+
+    #[test]
+    fn blocks_session_teardown() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "tmux kill-server", "tmux-kill-server");
+        assert_blocks_with_pattern(&pack, "tmux -L agents kill-server", "tmux-kill-server");
+        assert_blocks_with_severity(&pack, "tmux kill-server", Severity::High);
+        assert_blocks_with_pattern(
+            &pack,
+            "tmux kill-session -a -t main",
+            "tmux-kill-session-all",
+        );
+        assert_blocks_with_pattern(&pack, "screen -S work -X quit", "screen-quit");
+        assert_blocks_with_pattern(&pack, "pkill tmux", "kill-multiplexer-processes");
+        assert_blocks_with_pattern(&pack, "killall -9 screen", "kill-multiplexer-processes");
+        assert_allows(&pack, "pkill -f tmux-resurrect");
+    }
+}
@@ -0,0 +1,296 @@
+//! User patterns - protections against destroying accounts and credentials.
+//!
+//! Accounts, sudo rules, and SSH keys are what let people (and the agent) log
+//! in to a host. Deleting or locking them is easy to do and hard to undo
+//! without console access. This includes patterns for:
+//! - userdel -r / deluser --remove-home
+//! - passwd -d and locking or renaming root
+//! - Writing /etc/sudoers without visudo
+//! - Bulk ssh-keygen -R over known_hosts
+//! - Deleting or truncating files under ~/.ssh
+
+use crate::packs::{DestructivePattern, Pack, PatternSuggestion, SafePattern};
+use crate::{destructive_pattern, safe_pattern};
+
+// ============================================================================
+// Suggestion constants (must be 'static for the pattern struct)
+// ============================================================================
+
+const USERDEL_SUGGESTIONS: &[PatternSuggestion] = &[
+    PatternSuggestion::new(
+        "usermod -L -e 1 {user}",
+        "Lock and expire the account without deleting it",
+    ),
+    PatternSuggestion::new(
+        "userdel {user}",
+        "Delete the account but keep its home directory",
+    ),
+];
+
+/// Create the Users pack.
+#[must_use]
+pub fn create_pack() -> Pack {
+    Pack {
+        id: "system.users".to_string(),
+        name: "Users",
+        description: "Protects against deleting accounts and credentials: userdel -r, \
+                      passwd -d, locking root, editing /etc/sudoers without visudo, bulk \
+                      ssh-keygen -R, and deleting ~/.ssh contents",
+        keywords: &[
+            "userdel",
+            "deluser",
+            "passwd",
+            "usermod",
+            "sudoers",
+            "visudo",
+            "ssh-keygen",
+            ".ssh",
+        ],
+        safe_patterns: create_safe_patterns(),
+        destructive_patterns: create_destructive_patterns(),
+        keyword_matcher: None,
+        safe_regex_set: None,
+        safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
+    }
+}
+
+fn create_safe_patterns() -> Vec<SafePattern> {
+    // Anchored to the whole command so a lookup cannot whitelist a chained
+    // deletion.
+    vec![
+        safe_pattern!(
+            "passwd-status",
+            r"^\s*passwd\s+(?:-S|--status)(?:\s+-a|\s+--all)?(?:\s+[\w.-]+)?\s*$"
+        ),
+        safe_pattern!(
+            "getent-passwd",
+            r"^\s*getent\s+(?:passwd|shadow|group)(?:\s+[\w.-]+)*\s*$"
+        ),
+        safe_pattern!(
+            "visudo-check",
+            r"^\s*visudo\s+(?:-c|--check)\b[^;&|\n$\x60]*$"
+        ),
+        safe_pattern!(
+            "ssh-keygen-read-only",
+            r"^\s*ssh-keygen\s+(?:-\S+\s+)*(?:-l|-F|-y|-L)\b[^;&|\n$\x60]*$"
+        ),
+        safe_pattern!(
+            "ssh-dir-list",
+            r"^\s*(?:ls|cat|stat)(?:\s+-[a-zA-Z]+)*\s+(?:~|\$HOME)/\.ssh(?:/[\w.@-]*)?\s*$"
+        ),
+    ]
+}
+
+#[allow(clippy::too_many_lines)]
+fn create_destructive_patterns() -> Vec<DestructivePattern> {
+    vec![
+        // userdel -r / deluser --remove-home delete the home directory too
+        destructive_pattern!(
+            "userdel-remove-home",
+            r"\b(?:userdel\s+(?:[^;&|\n]*\s)?(?:-[a-zA-Z]*r[a-zA-Z]*|--remove)|deluser\s+(?:[^;&|\n]*\s)?--remove-(?:home|all-files))\b",
+            "userdel -r deletes the account together with its home directory and mail spool.",
+            High,
+            "userdel -r (and deluser --remove-home) removes the user and everything \
+             they own in their home directory:\n\n\
+             - Dotfiles, SSH keys, and any work stored there are deleted, not archived\n\
+             - Running processes of the user may be left orphaned\n\n\
+             Lock the account instead, and archive the home directory first if it \
+             must go:\n  \
+             usermod -L -e 1 <user>\n  \
+             tar czf /root/<user>-home.tgz /home/<user>",
+            USERDEL_SUGGESTIONS
+        ),
+        // passwd -d leaves an account that logs in without a password
+        destructive_pattern!(
+            "passwd-delete",
+            r"(?:^|[\s;&|(])passwd\s+(?:[^;&|\n]*\s)?(?:-d|--delete)\b",
+            "passwd -d removes the password, so the account can log in without one.",
+            High,
+            "Deleting an account's password makes it passwordless:\n\n\
+             - Depending on PAM (nullok), anyone can log in as that user with an \
+             empty password\n\
+             - For root this means an unauthenticated root login on the console\n\n\
+             To disable password login, lock the password instead:\n  \
+             passwd -l <user>"
+        ),
+        // Locking or renaming root blocks every root login and many scripts
+        destructive_pattern!(
+            "lock-root-account",
+            r"\b(?:usermod|passwd)\s+(?:[^;&|\n]*\s)?(?:-L|-l|--lock|--login)\s+(?:[^;&|\n]*\s)?root\s*(?:$|[;&|)])",
+            "Locking or renaming the root account can lock you out of the host.",
+            Critical,
+            "usermod -L root / passwd -l root disables password login for root, and \
+             usermod -l <name> root renames it:\n\n\
+             - Without another sudo-capable user, no one can administer the host\n\
+             - Rescue consoles and sulogin may refuse to start\n\
+             - Anything that refers to the user 'root' by name breaks after a rename\n\n\
+             Disable root SSH login in sshd_config instead:\n  \
+             PermitRootLogin no"
+        ),
+        // echo ... >> /etc/sudoers bypasses visudo's syntax check
+        destructive_pattern!(
+            "sudoers-direct-edit",
+            r"(?:>>?\s*|\btee\s+(?:-\S+\s+)*|\bsed\s+(?:[^;&|\n]*\s)?-i\S*\s+(?:[^;&|\n]*\s)?)/etc/sudoers(?:\.d/[\w.-]*)?(?:\s|$|[;&|)])",
+            "Writing /etc/sudoers without visudo can leave sudo unusable.",
+            Critical,
+            "Appending to or editing /etc/sudoers (or /etc/sudoers.d) directly skips \
+             visudo's syntax check:\n\n\
+             - A single syntax error makes sudo refuse to run for everyone\n\
+             - Without a root password, fixing it needs console or rescue access\n\
+             - Rules written this way grant privileges without review\n\n\
+             Use visudo, or validate a drop-in before installing it:\n  \
+             visudo -cf /tmp/90-agent && install -m 0440 /tmp/90-agent /etc/sudoers.d/"
+        ),
+        destructive_pattern!(
+            "visudo-non-interactive",
+            r#"\b(?:SUDO_EDITOR|VISUAL|EDITOR)=['"]?(?:tee|sed|cp|cat|echo)\b[^;&|\n]*\bvisudo\b"#,
+            "Running visudo with a non-interactive EDITOR rewrites sudoers unreviewed.",
+            Critical,
+            "Setting EDITOR to tee, sed, or cp turns visudo into a scripted write to \
+             /etc/sudoers:\n\n\
+             - The new rules are installed without anyone reading them\n\
+             - Privilege grants made this way are a common persistence technique\n\n\
+             Have a human run visudo interactively, or validate a drop-in file:\n  \
+             visudo -cf /tmp/90-agent"
+        ),
+        // Removing host keys in a loop discards every pinned host identity
+        destructive_pattern!(
+            "ssh-keygen-remove-bulk",
+            r"(?:\bxargs\b[^;&|\n]*|\b(?:for|while)\b[^\n]*\bdo\b[^\n]*)\bssh-keygen\s+(?:-\S+\s+)*-R\b",
+            "Bulk ssh-keygen -R discards the pinned host keys for many hosts at once.",
+            Medium,
+            "ssh-keygen -R removes a host's key from known_hosts. Doing it in a loop \
+             or through xargs:\n\n\
+             - Throws away trust-on-first-use for every listed host\n\
+             - Hides man-in-the-middle warnings the next time you connect\n\n\
+             Remove only the host whose key actually changed, after verifying the \
+             new fingerprint:\n  \
+             ssh-keygen -R <host>"
+        ),
+        // rm ~/.ssh/id_* or authorized_keys
+        destructive_pattern!(
+            "ssh-dir-delete",
+            r"\b(?:rm|shred|unlink)\s+(?:[^;&|\n]*\s)?(?:~|\$HOME|\$\{HOME\}|/root|/home/[\w.-]+)/\.ssh(?:/[^\s;&|]*)?(?:\s|$|[;&|)])",
+            "Deleting files under ~/.ssh destroys private keys and authorized logins.",
+            High,
+            "~/.ssh holds private keys, authorized_keys, and known_hosts:\n\n\
+             - Private keys cannot be recovered; anything they unlock is lost\n\
+             - Removing authorized_keys locks out key-based logins, possibly your own\n\n\
+             Back up the directory before changing it:\n  \
+             cp -a ~/.ssh ~/.ssh.bak"
+        ),
+        destructive_pattern!(
+            "ssh-authorized-keys-truncate",
+            r"(?:^|[^>])>\s*(?:~|\$HOME|\$\{HOME\}|/root|/home/[\w.-]+)/\.ssh/authorized_keys2?\b",
+            "Overwriting authorized_keys with > removes every existing key-based login.",
+            High,
+            "Redirecting with a single > replaces authorized_keys:\n\n\
+             - Every key that could log in before is gone, including the one used by \
+             the current session\n\n\
+             Append with >> instead, or edit the file to remove one key:\n  \
+             echo '<key>' >> ~/.ssh/authorized_keys"
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::Severity;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_pack_creation() {
+        let pack = create_pack();
+        assert_eq!(pack.id, "system.users");
+        assert!(pack.keywords.contains(&"userdel"));
+        assert!(pack.keywords.contains(&".ssh"));
+
+        assert_patterns_compile(&pack);
+        assert_all_patterns_have_reasons(&pack);
+        assert_unique_pattern_names(&pack);
+    }
+
+    #[test]
+    fn allows_read_only_lookups() {
+        let pack = create_pack();
+        assert_safe_pattern_matches(&pack, "passwd -S deploy");
+        assert_safe_pattern_matches(&pack, "getent passwd deploy");
+        assert_safe_pattern_matches(&pack, "visudo -c");
+        assert_safe_pattern_matches(&pack, "ssh-keygen -l -f ~/.ssh/id_ed25519.pub");
+        assert_safe_pattern_matches(&pack, "ssh-keygen -F github.com");
+        assert_safe_pattern_matches(&pack, "ls -la ~/.ssh");
+        assert_no_safe_match(&pack, "ls ~/.ssh; rm ~/.ssh/id_rsa");
+    }
+
+    #[test]
+    fn blocks_account_changes() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "userdel -r deploy", "userdel-remove-home");
+        assert_blocks_with_pattern(&pack, "sudo userdel -rf deploy", "userdel-remove-home");
+        assert_blocks_with_pattern(&pack, "deluser --remove-home deploy", "userdel-remove-home");
+        assert_blocks_with_pattern(&pack, "passwd -d deploy", "passwd-delete");
+        assert_blocks_with_severity(&pack, "usermod -L root", Severity::Critical);
+        assert_blocks_with_pattern(&pack, "passwd -l root", "lock-root-account");
+        assert_blocks_with_pattern(&pack, "usermod -l admin root", "lock-root-account");
+        assert_allows(&pack, "userdel deploy");
+        assert_allows(&pack, "usermod -L deploy");
+        assert_allows(&pack, "usermod -aG docker deploy");
+        assert_allows(&pack, "grep deploy /etc/passwd");
+    }
+
+    #[test]
+    fn blocks_sudoers_edits_outside_visudo() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "echo 'deploy ALL=(ALL) NOPASSWD:ALL' >> /etc/sudoers",
+            "sudoers-direct-edit",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "echo 'deploy ALL=(ALL) ALL' | sudo tee -a /etc/sudoers.d/deploy",
+            "sudoers-direct-edit",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "sed -i 's/^%sudo.*/%sudo ALL=(ALL) NOPASSWD:ALL/' /etc/sudoers",
+            "sudoers-direct-edit",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "echo 'deploy ALL=(ALL) ALL' | EDITOR='tee -a' visudo",
+            "visudo-non-interactive",
+        );
+        assert_allows(&pack, "cat /etc/sudoers");
+        assert_allows(&pack, "visudo -f /etc/sudoers.d/deploy");
+    }
+
+    #[test]
+    fn blocks_ssh_credential_loss() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "rm ~/.ssh/id_rsa", "ssh-dir-delete");
+        assert_blocks_with_pattern(&pack, "rm -f $HOME/.ssh/authorized_keys", "ssh-dir-delete");
+        assert_blocks_with_pattern(&pack, "shred -u /root/.ssh/id_ed25519", "ssh-dir-delete");
+        assert_blocks_with_pattern(
+            &pack,
+            "echo 'ssh-ed25519 AAAA' > ~/.ssh/authorized_keys",
+            "ssh-authorized-keys-truncate",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "cat hosts.txt | xargs -n1 ssh-keygen -R",
+            "ssh-keygen-remove-bulk",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "for h in $(cat hosts); do ssh-keygen -R $h; done",
+            "ssh-keygen-remove-bulk",
+        );
+        assert_allows(&pack, "ssh-keygen -R github.com");
+        assert_allows(&pack, "echo 'ssh-ed25519 AAAA' >> ~/.ssh/authorized_keys");
+        assert_allows(&pack, "ssh-keygen -t ed25519 -f ~/.ssh/id_ed25519");
+    }
+}
@@ -0,0 +1,271 @@
+//! Virtualization patterns - protections against destroying VMs and containers.
+//!
+//! This includes patterns for:
+//! - machinectl remove/clean/terminate (systemd-nspawn machines and images)
+//! - virsh destroy and undefine with storage removal (libvirt)
+//! - VBoxManage unregistervm --delete (VirtualBox)
+//! - multipass delete --purge / multipass purge
+//! - vagrant destroy -f
+
+use crate::packs::{DestructivePattern, Pack, SafePattern};
+use crate::{destructive_pattern, safe_pattern};
+
+/// Create the Virtualization pack.
+#[must_use]
+pub fn create_pack() -> Pack {
+    Pack {
+        id: "system.virtualization".to_string(),
+        name: "Virtualization",
+        description: "Protects against destroying virtual machines and containers with \
+                      machinectl, virsh, VBoxManage, multipass, and vagrant",
+        keywords: &[
+            "machinectl",
+            "virsh",
+            "VBoxManage",
+            "vboxmanage",
+            "multipass",
+            "vagrant",
+        ],
+        safe_patterns: create_safe_patterns(),
+        destructive_patterns: create_destructive_patterns(),
+        keyword_matcher: None,
+        safe_regex_set: None,
+        safe_regex_set_is_complete: false,
+        destructive_regex_set: None,
+        critical_patterns: Vec::new(),
+    }
+}
+
+fn create_safe_patterns() -> Vec<SafePattern> {
+    // Anchored to the whole command so a status call cannot whitelist a
+    // chained destroy.
+    vec![
+        // listing and inspecting machines/images
+        safe_pattern!(
+            "machinectl-read-only",
+            r"^\s*machinectl\s+(?:list|list-images|list-transfers|status|show|show-image|image-status)\b[^;&|\n$`]*$"
+        ),
+        // domain/pool listing, inspection, and taking snapshots
+        safe_pattern!(
+            "virsh-read-only",
+            r"^\s*virsh\s+(?:(?:-c|--connect)\s+\S+\s+)?(?:list|dominfo|domstate|domblklist|dumpxml|nodeinfo|version|pool-list|vol-list|net-list|snapshot-list|snapshot-info|snapshot-dumpxml|snapshot-create|snapshot-create-as)\b[^;&|\n$`]*$"
+        ),
+        // list/showvminfo and taking or listing snapshots
+        safe_pattern!(
+            "vboxmanage-read-only",
+            r"^\s*(?:VBoxManage|vboxmanage)\s+(?:list|showvminfo|snapshot\s+\S+\s+(?:take|list|showvminfo))\b[^;&|\n$`]*$"
+        ),
+        safe_pattern!(
+            "multipass-read-only",
+            r"^\s*multipass\s+(?:list|ls|info|find|version|snapshot)\b[^;&|\n$`]*$"
+        ),
+        safe_pattern!(
+            "vagrant-read-only",
+            r"^\s*vagrant\s+(?:status|global-status|ssh-config|validate|box\s+list|snapshot\s+(?:save|list|push))\b[^;&|\n$`]*$"
+        ),
+    ]
+}
+
+#[allow(clippy::too_many_lines)]
+fn create_destructive_patterns() -> Vec<DestructivePattern> {
+    vec![
+        // machinectl remove/clean deletes images from /var/lib/machines
+        destructive_pattern!(
+            "machinectl-remove",
+            r"machinectl\s+(?:remove|clean)\b",
+            "machinectl remove/clean permanently deletes container or VM images.",
+            High,
+            "machinectl remove deletes the named images from /var/lib/machines; clean \
+             removes hidden (or with --all, every) image:\n\n\
+             - The image's root filesystem is deleted, not just unregistered\n\
+             - Read-only images are removed too\n\n\
+             Inspect first:\n  \
+             machinectl list-images\n\n\
+             Keep a copy:\n  \
+             machinectl clone NAME NAME-backup"
+        ),
+        // machinectl terminate kills every process in the machine
+        destructive_pattern!(
+            "machinectl-terminate",
+            r"machinectl\s+terminate\b",
+            "machinectl terminate kills the machine's processes without a clean shutdown.",
+            High,
+            "machinectl terminate kills every process of the container or VM at once:\n\n\
+             - No orderly shutdown; unsynced data inside the guest is lost\n\
+             - Services running in it go down immediately\n\n\
+             Shut down cleanly instead:\n  \
+             machinectl poweroff NAME"
+        ),
+        // undefine with storage removal before the plain destroy rule
+        destructive_pattern!(
+            "virsh-undefine-storage",
+            r"virsh\b[^;&|\n]*\sundefine\b[^;&|\n]*\s--(?:remove-all-storage|storage|wipe-storage)\b",
+            "virsh undefine --remove-all-storage deletes the domain and its disk images.",
+            Critical,
+            "undefine removes the libvirt domain definition; with --remove-all-storage \
+             or --storage it also deletes the backing volumes:\n\n\
+             - Disk images are deleted from their storage pools\n\
+             - --wipe-storage overwrites them first, so recovery is impossible\n\n\
+             Check which volumes are attached:\n  \
+             virsh domblklist NAME\n\n\
+             Remove only the definition:\n  \
+             virsh undefine NAME"
+        ),
+        // virsh destroy is an immediate power-off
+        destructive_pattern!(
+            "virsh-destroy",
+            r"virsh\b[^;&|\n]*\sdestroy\b",
+            "virsh destroy forcibly powers off the domain.",
+            High,
+            "virsh destroy pulls the virtual power plug:\n\n\
+             - The guest gets no chance to shut down\n\
+             - Unflushed writes can corrupt guest filesystems\n\n\
+             Shut down cleanly instead:\n  \
+             virsh shutdown NAME"
+        ),
+        // unregistervm --delete removes the VM and all of its files
+        destructive_pattern!(
+            "vboxmanage-unregistervm-delete",
+            r"(?:VBoxManage|vboxmanage)\s+unregistervm\b[^;&|\n]*\s--delete(?:-all)?\b",
+            "VBoxManage unregistervm --delete deletes the VM, its disks, and snapshots.",
+            Critical,
+            "unregistervm --delete removes the VirtualBox VM and deletes its files:\n\n\
+             - Settings file, attached disk images, saved states, and snapshots are deleted\n\
+             - --delete-all also removes disks shared with other VMs' snapshots\n\n\
+             Inspect first:\n  \
+             VBoxManage showvminfo NAME\n\n\
+             Unregister but keep the files:\n  \
+             VBoxManage unregistervm NAME"
+        ),
+        // multipass delete --purge skips the recoverable deleted state
+        destructive_pattern!(
+            "multipass-delete-purge",
+            r"multipass\s+delete\b[^;&|\n]*\s(?:--purge|-p)\b",
+            "multipass delete --purge permanently deletes the instances.",
+            High,
+            "multipass delete --purge deletes instances without the recoverable \
+             'Deleted' state:\n\n\
+             - The instance disk is destroyed immediately\n\
+             - --all extends this to every instance\n\n\
+             Delete recoverably instead:\n  \
+             multipass delete NAME   (multipass recover NAME undoes it)"
+        ),
+        // purge permanently removes previously deleted instances
+        destructive_pattern!(
+            "multipass-purge",
+            r"multipass\s+purge\b",
+            "multipass purge permanently removes all deleted instances.",
+            High,
+            "multipass purge destroys every instance in the 'Deleted' state:\n\n\
+             - They can no longer be recovered\n\n\
+             See what would be purged:\n  \
+             multipass list"
+        ),
+        // vagrant destroy -f skips the confirmation prompt
+        destructive_pattern!(
+            "vagrant-destroy-force",
+            r"vagrant\s+destroy\b[^;&|\n]*\s(?:-f|--force)\b",
+            "vagrant destroy -f deletes the machines without confirmation.",
+            High,
+            "vagrant destroy --force stops and deletes the machines and their disks:\n\n\
+             - Without a machine name every machine in the Vagrantfile is destroyed\n\
+             - No confirmation prompt\n\n\
+             Keep a restore point:\n  \
+             vagrant snapshot save NAME\n\n\
+             Stop without deleting:\n  \
+             vagrant halt"
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_pack_creation() {
+        let pack = create_pack();
+        assert_eq!(pack.id, "system.virtualization");
+        assert!(pack.keywords.contains(&"virsh"));
+        assert!(pack.keywords.contains(&"vagrant"));
+
+        assert_patterns_compile(&pack);
+        assert_all_patterns_have_reasons(&pack);
+        assert_unique_pattern_names(&pack);
+    }
+
+    #[test]
+    fn allows_list_status_and_snapshot_commands() {
+        let pack = create_pack();
+        assert_safe_pattern_matches(&pack, "machinectl list-images");
+        assert_safe_pattern_matches(&pack, "machinectl status web");
+        assert_safe_pattern_matches(&pack, "virsh list --all");
+        assert_safe_pattern_matches(&pack, "virsh -c qemu:///system dominfo db1");
+        assert_safe_pattern_matches(&pack, "virsh snapshot-create-as db1 pre-upgrade");
+        assert_safe_pattern_matches(&pack, "VBoxManage list vms");
+        assert_safe_pattern_matches(&pack, "VBoxManage snapshot dev take before-update");
+        assert_safe_pattern_matches(&pack, "multipass info primary");
+        assert_safe_pattern_matches(&pack, "vagrant status");
+        assert_safe_pattern_matches(&pack, "vagrant snapshot save clean");
+    }
+
+    #[test]
+    fn safe_patterns_do_not_cover_chained_commands() {
+        let pack = create_pack();
+        assert_no_safe_match(&pack, "virsh list --all && virsh destroy db1");
+        assert_no_safe_match(&pack, "vagrant status; vagrant destroy -f");
+    }
+
+    #[test]
+    fn blocks_machinectl() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "machinectl remove web", "machinectl-remove");
+        assert_blocks_with_pattern(&pack, "machinectl clean --all", "machinectl-remove");
+        assert_blocks_with_pattern(&pack, "machinectl terminate web", "machinectl-terminate");
+        assert_allows(&pack, "machinectl poweroff web");
+    }
+
+    #[test]
+    fn blocks_virsh() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "virsh undefine db1 --remove-all-storage",
+            "virsh-undefine-storage",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "virsh -c qemu:///system undefine db1 --storage vda",
+            "virsh-undefine-storage",
+        );
+        assert_blocks_with_pattern(&pack, "virsh destroy db1", "virsh-destroy");
+        assert_allows(&pack, "virsh shutdown db1");
+        assert_allows(&pack, "virsh undefine db1");
+    }
+
+    #[test]
+    fn blocks_vm_managers() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "VBoxManage unregistervm dev --delete",
+            "vboxmanage-unregistervm-delete",
+        );
+        assert_allows(&pack, "VBoxManage unregistervm dev");
+        assert_blocks_with_pattern(
+            &pack,
+            "multipass delete --all --purge",
+            "multipass-delete-purge",
+        );
+        assert_blocks_with_pattern(&pack, "multipass purge", "multipass-purge");
+        assert_allows(&pack, "multipass delete primary");
+        assert_blocks_with_pattern(&pack, "vagrant destroy -f", "vagrant-destroy-force");
+        assert_blocks_with_pattern(
+            &pack,
+            "vagrant destroy web --force",
+            "vagrant-destroy-force",
+        );
+        assert_allows(&pack, "vagrant halt");
+    }
+}
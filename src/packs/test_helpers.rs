@@ -836,6 +836,7 @@ impl EvalSnapshot {
             AllowlistLayer::Project => "project".to_string(),
             AllowlistLayer::User => "user".to_string(),
             AllowlistLayer::System => "system".to_string(),
+            AllowlistLayer::Remote => "remote".to_string(),
         });
 
         Self {
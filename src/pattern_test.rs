@@ -0,0 +1,318 @@
+//! Pack author test harness (`dcg pattern test`).
+//!
+//! Runs an external pack against three sets of commands:
+//!
+//! ```text
+//! examples.positive  -> must be blocked by this pack  (miss = false negative)
+//! examples.negative  -> must be allowed by this pack  (hit  = false positive)
+//! embedded corpus    -> everyday commands no pack should block (hit = false positive)
+//! ```
+//!
+//! Commands go through the same sanitization, normalization, and heredoc
+//! masking the evaluator applies before pack matching, so `sudo deploy` and
+//! `echo "deploy --env prod"` behave as they would in the hook. Only the pack
+//! under test is consulted; built-in packs, allowlists, and policy are out of
+//! scope.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::packs::Pack;
+use crate::packs::external::ExternalPack;
+
+/// False-positive corpus files shipped with the binary.
+const CORPUS_FILES: &[(&str, &str)] = &[
+    (
+        "git_safe",
+        include_str!("../tests/corpus/false_positives/git_safe.toml"),
+    ),
+    (
+        "heredoc_data",
+        include_str!("../tests/corpus/false_positives/heredoc_data.toml"),
+    ),
+    (
+        "non_execution",
+        include_str!("../tests/corpus/false_positives/non_execution.toml"),
+    ),
+    (
+        "other_safe",
+        include_str!("../tests/corpus/false_positives/other_safe.toml"),
+    ),
+    (
+        "rm_safe",
+        include_str!("../tests/corpus/false_positives/rm_safe.toml"),
+    ),
+    (
+        "substring_safe",
+        include_str!("../tests/corpus/false_positives/substring_safe.toml"),
+    ),
+];
+
+#[derive(Debug, Deserialize)]
+struct CorpusFile {
+    #[serde(default)]
+    case: Vec<CorpusCase>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CorpusCase {
+    command: String,
+}
+
+/// Commands from the embedded corpus, tagged with their corpus file.
+#[must_use]
+pub fn corpus_commands() -> Vec<(&'static str, String)> {
+    CORPUS_FILES
+        .iter()
+        .filter_map(|(name, source)| {
+            toml::from_str::<CorpusFile>(source)
+                .ok()
+                .map(|file| (name, file))
+        })
+        .flat_map(|(name, file)| file.case.into_iter().map(|case| (*name, case.command)))
+        .collect()
+}
+
+/// Where a test command came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExampleKind {
+    /// `examples.positive`: must be blocked.
+    Positive,
+    /// `examples.negative`: must be allowed.
+    Negative,
+    /// Embedded corpus: must be allowed.
+    Corpus,
+}
+
+impl ExampleKind {
+    /// Whether commands of this kind should be blocked.
+    #[must_use]
+    pub const fn expects_block(self) -> bool {
+        matches!(self, Self::Positive)
+    }
+}
+
+/// Outcome for a single command.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExampleResult {
+    /// Which list the command came from.
+    pub kind: ExampleKind,
+    /// Corpus file name, for corpus commands.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub corpus: Option<&'static str>,
+    /// The command as written.
+    pub command: String,
+    /// Destructive pattern that matched, if the pack blocked the command.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matched_pattern: Option<String>,
+    /// Whether the outcome is what the kind expects.
+    pub passed: bool,
+}
+
+/// Results of running a pack against its examples and the corpus.
+#[derive(Debug, Clone, Serialize)]
+pub struct PatternTestReport {
+    /// ID of the pack under test.
+    pub pack_id: String,
+    /// One entry per command, positives first.
+    pub results: Vec<ExampleResult>,
+    /// Destructive patterns that no positive example matched.
+    pub untested_patterns: Vec<String>,
+}
+
+impl PatternTestReport {
+    /// Positive examples the pack failed to block.
+    pub fn false_negatives(&self) -> impl Iterator<Item = &ExampleResult> {
+        self.results
+            .iter()
+            .filter(|result| !result.passed && result.kind.expects_block())
+    }
+
+    /// Negative examples and corpus commands the pack blocked.
+    pub fn false_positives(&self) -> impl Iterator<Item = &ExampleResult> {
+        self.results
+            .iter()
+            .filter(|result| !result.passed && !result.kind.expects_block())
+    }
+
+    /// True when every command behaved as expected.
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        self.results.iter().all(|result| result.passed)
+    }
+
+    /// Number of commands checked for a given kind.
+    #[must_use]
+    pub fn count(&self, kind: ExampleKind) -> usize {
+        self.results
+            .iter()
+            .filter(|result| result.kind == kind)
+            .count()
+    }
+}
+
+/// Run `pack` against its declared examples and, optionally, the corpus.
+#[must_use]
+pub fn run(pack: &ExternalPack, include_corpus: bool) -> PatternTestReport {
+    let compiled = pack.clone().into_pack();
+
+    let positives = pack
+        .examples
+        .positive
+        .iter()
+        .map(|command| (ExampleKind::Positive, None, command.clone()));
+    let negatives = pack
+        .examples
+        .negative
+        .iter()
+        .map(|command| (ExampleKind::Negative, None, command.clone()));
+    let corpus = include_corpus
+        .then(corpus_commands)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(name, command)| (ExampleKind::Corpus, Some(name), command));
+
+    let results: Vec<ExampleResult> = positives
+        .chain(negatives)
+        .chain(corpus)
+        .map(|(kind, corpus, command)| {
+            let matched_pattern = check(&compiled, &command);
+            ExampleResult {
+                kind,
+                corpus,
+                passed: matched_pattern.is_some() == kind.expects_block(),
+                command,
+                matched_pattern,
+            }
+        })
+        .collect();
+
+    let exercised: HashSet<&str> = results
+        .iter()
+        .filter(|result| result.kind.expects_block())
+        .filter_map(|result| result.matched_pattern.as_deref())
+        .collect();
+    let untested_patterns = pack
+        .destructive_patterns
+        .iter()
+        .filter(|pattern| !exercised.contains(pattern.name.as_str()))
+        .map(|pattern| pattern.name.clone())
+        .collect();
+
+    PatternTestReport {
+        pack_id: pack.id.clone(),
+        results,
+        untested_patterns,
+    }
+}
+
+/// Name of the destructive pattern that blocks `command`, if any.
+fn check(pack: &Pack, command: &str) -> Option<String> {
+    let sanitized = crate::context::sanitize_for_pattern_matching(command);
+    let normalized = crate::normalize::normalize_command(&sanitized);
+    let masked = crate::heredoc::mask_non_executing_heredocs(&normalized);
+    pack.check(&masked)
+        .map(|matched| matched.name.unwrap_or("unnamed").to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::external::parse_pack_string;
+
+    const PACK: &str = r"
+schema_version: 1
+id: mycompany.deploy
+name: Deploy policies
+version: 1.0.0
+keywords: [deploy]
+destructive_patterns:
+  - name: prod-direct
+    pattern: deploy\s+--env\s*=?\s*prod
+    severity: critical
+  - name: rollback-all
+    pattern: deploy\s+rollback\s+--all
+safe_patterns:
+  - name: staging-deploy
+    pattern: deploy\s+--env\s*=?\s*(staging|dev)
+examples:
+  positive:
+    - deploy --env=prod
+    - sudo /usr/local/bin/deploy --env prod
+    - deploy --environment prod
+  negative:
+    - deploy --env staging
+    - deploy --env prod --dry-run
+";
+
+    #[test]
+    fn embedded_corpus_parses() {
+        let commands = corpus_commands();
+        assert!(
+            commands.len() > 100,
+            "corpus has {} commands",
+            commands.len()
+        );
+        assert!(
+            commands
+                .iter()
+                .any(|(name, command)| *name == "git_safe" && command == "git status")
+        );
+    }
+
+    #[test]
+    fn reports_false_negatives_false_positives_and_untested_patterns() {
+        let pack = parse_pack_string(PACK).expect("pack parses");
+        let report = run(&pack, false);
+
+        assert_eq!(report.pack_id, "mycompany.deploy");
+        assert_eq!(report.count(ExampleKind::Positive), 3);
+        assert_eq!(report.count(ExampleKind::Negative), 2);
+        assert_eq!(report.count(ExampleKind::Corpus), 0);
+        assert!(!report.passed());
+
+        let false_negatives: Vec<&str> = report
+            .false_negatives()
+            .map(|result| result.command.as_str())
+            .collect();
+        assert_eq!(false_negatives, ["deploy --environment prod"]);
+
+        let false_positives: Vec<_> = report.false_positives().collect();
+        assert_eq!(false_positives.len(), 1);
+        assert_eq!(false_positives[0].command, "deploy --env prod --dry-run");
+        assert_eq!(
+            false_positives[0].matched_pattern.as_deref(),
+            Some("prod-direct")
+        );
+
+        assert_eq!(report.untested_patterns, ["rollback-all"]);
+    }
+
+    #[test]
+    fn corpus_commands_must_be_allowed() {
+        let pack = parse_pack_string(
+            r"
+schema_version: 1
+id: mycompany.greedy
+name: Greedy
+version: 1.0.0
+keywords: [git]
+destructive_patterns:
+  - name: any-git
+    pattern: \bgit\b
+",
+        )
+        .expect("pack parses");
+        let report = run(&pack, true);
+
+        assert!(report.count(ExampleKind::Corpus) > 0);
+        assert!(
+            report
+                .false_positives()
+                .any(|result| result.kind == ExampleKind::Corpus
+                    && result.corpus == Some("git_safe"))
+        );
+    }
+}
@@ -15,6 +15,7 @@ use std::fmt::Write as FmtWrite;
 use std::fs::{File, OpenOptions};
 use std::io::{self, BufRead, BufReader, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
+use std::sync::{PoisonError, RwLock};
 
 use crate::config::resolve_config_path_value;
 use crate::logging::{RedactionConfig, redact_command};
@@ -23,8 +24,8 @@ use crate::logging::{RedactionConfig, redact_command};
 pub const ENV_PENDING_EXCEPTIONS_PATH: &str = "DCG_PENDING_EXCEPTIONS_PATH";
 /// Environment override for allow-once entries file path.
 pub const ENV_ALLOW_ONCE_PATH: &str = "DCG_ALLOW_ONCE_PATH";
-/// Optional HMAC secret for short-code hardening.
-/// When set, codes cannot be forged without knowing the secret.
+/// Overrides the auto-generated HMAC secret for short codes
+/// (see [`crate::allow_once_secret`]).
 pub const ENV_ALLOW_ONCE_SECRET: &str = "DCG_ALLOW_ONCE_SECRET";
 
 const PENDING_EXCEPTIONS_FILE: &str = "pending_exceptions.jsonl";
@@ -32,6 +33,41 @@ const ALLOW_ONCE_FILE: &str = "allow_once.jsonl";
 const SCHEMA_VERSION: u32 = 1;
 const EXPIRY_HOURS: i64 = 24;
 
+/// Agent session of the current hook invocation (see [`set_hook_session_id`]).
+static HOOK_SESSION_ID: RwLock<Option<String>> = RwLock::new(None);
+
+/// Record the agent session id from the hook payload for this process.
+///
+/// Session-scoped allow-once entries only match while this is set to the
+/// session they were granted for. The first call wins.
+pub fn set_hook_session_id(session_id: &str) {
+    let mut current = HOOK_SESSION_ID
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    if current.is_none() {
+        *current = Some(session_id.to_string());
+    }
+}
+
+/// Replace the recorded session id.
+///
+/// For `dcg daemon`, which evaluates on behalf of many hook invocations and
+/// sets the session of each request before evaluating it.
+pub fn replace_hook_session_id(session_id: Option<&str>) {
+    *HOOK_SESSION_ID
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = session_id.map(str::to_string);
+}
+
+/// Agent session id recorded by [`set_hook_session_id`], if any.
+#[must_use]
+pub fn hook_session_id() -> Option<String> {
+    HOOK_SESSION_ID
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
 /// Scope kind for allow-once entries.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -56,6 +92,9 @@ pub struct PendingExceptionRecord {
     pub consumed_at: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub source: Option<String>,
+    /// Agent session the command was denied in (from the hook payload).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
 }
 
 /// A stored allow-once entry (JSONL line).
@@ -76,6 +115,9 @@ pub struct AllowOnceEntry {
     pub consumed_at: Option<String>,
     #[serde(default)]
     pub force_allow_config: bool,
+    /// When set, the entry only applies within this agent session.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
 }
 
 impl AllowOnceEntry {
@@ -106,6 +148,7 @@ impl AllowOnceEntry {
             single_use,
             consumed_at: None,
             force_allow_config,
+            session_id: None,
         }
     }
 
@@ -114,6 +157,14 @@ impl AllowOnceEntry {
         self.consumed_at.is_some()
     }
 
+    /// Whether the entry applies in `session_id` (unscoped entries apply everywhere).
+    #[must_use]
+    pub fn matches_session(&self, session_id: Option<&str>) -> bool {
+        self.session_id
+            .as_deref()
+            .is_none_or(|scoped| session_id == Some(scoped))
+    }
+
     #[must_use]
     pub fn matches_scope(&self, cwd: &Path) -> bool {
         let scope_path = Path::new(&self.scope_path);
@@ -154,6 +205,7 @@ impl PendingExceptionRecord {
             single_use,
             consumed_at: None,
             source,
+            session_id: None,
         }
     }
 
@@ -161,6 +213,15 @@ impl PendingExceptionRecord {
     pub const fn is_consumed(&self) -> bool {
         self.consumed_at.is_some()
     }
+
+    /// Whether `full_hash` matches the record's contents under the current secret.
+    ///
+    /// Fails for records that were edited, written by something other than
+    /// dcg, or issued before the secret was rotated.
+    #[must_use]
+    pub fn hash_verifies(&self) -> bool {
+        self.full_hash == compute_full_hash(&self.created_at, &self.cwd, &self.command_raw)
+    }
 }
 
 /// Maintenance stats produced while loading/pruning.
@@ -243,11 +304,13 @@ impl PendingExceptionStore {
         redaction: &RedactionConfig,
         single_use: bool,
         source: Option<String>,
+        session_id: Option<&str>,
         allow_once_audit: Option<&AllowOnceAuditConfig<'_>>,
     ) -> io::Result<(PendingExceptionRecord, PendingMaintenance)> {
         let now = Utc::now();
-        let record =
+        let mut record =
             PendingExceptionRecord::new(now, cwd, command, reason, redaction, single_use, source);
+        record.session_id = session_id.map(str::to_string);
 
         let mut file = open_locked(&self.path)?;
         let (active, maintenance) = load_active_from_file(&mut file, now, allow_once_audit);
@@ -337,7 +400,7 @@ impl PendingExceptionStore {
         Ok((removed, maintenance))
     }
 
-    /// Load active records matching a short code.
+    /// Load active records matching a short code whose hash verifies.
     ///
     /// # Errors
     ///
@@ -350,7 +413,7 @@ impl PendingExceptionStore {
         let (active, maintenance) = self.load_active(now)?;
         let matches = active
             .into_iter()
-            .filter(|record| record.short_code == code)
+            .filter(|record| record.short_code == code && record.hash_verifies())
             .collect();
         Ok((matches, maintenance))
     }
@@ -513,6 +576,7 @@ impl AllowOnceStore {
 
     /// Match a command against active allow-once entries.
     ///
+    /// Session-scoped entries only match when `session_id` is their session.
     /// If a single-use entry matches, it is consumed immediately.
     ///
     /// # Errors
@@ -522,6 +586,7 @@ impl AllowOnceStore {
         &self,
         command: &str,
         cwd: &Path,
+        session_id: Option<&str>,
         now: DateTime<Utc>,
         allow_once_audit: Option<&AllowOnceAuditConfig<'_>>,
     ) -> io::Result<Option<AllowOnceEntry>> {
@@ -536,9 +601,11 @@ impl AllowOnceStore {
             rewrite_allow_once_records(&mut file, &active)?;
         }
 
-        let idx = active
-            .iter()
-            .position(|entry| entry.command_raw == command && entry.matches_scope(cwd));
+        let idx = active.iter().position(|entry| {
+            entry.command_raw == command
+                && entry.matches_scope(cwd)
+                && entry.matches_session(session_id)
+        });
 
         let Some(idx) = idx else {
             return Ok(None);
@@ -589,6 +656,7 @@ impl AllowOnceStore {
         &self,
         command: &str,
         cwd: &Path,
+        session_id: Option<&str>,
         now: DateTime<Utc>,
         allow_once_audit: Option<&AllowOnceAuditConfig<'_>>,
     ) -> io::Result<Option<AllowOnceEntry>> {
@@ -603,9 +671,11 @@ impl AllowOnceStore {
             rewrite_allow_once_records(&mut file, &active)?;
         }
 
-        let idx = active
-            .iter()
-            .position(|entry| entry.command_raw == command && entry.matches_scope(cwd));
+        let idx = active.iter().position(|entry| {
+            entry.command_raw == command
+                && entry.matches_scope(cwd)
+                && entry.matches_session(session_id)
+        });
 
         let Some(idx) = idx else {
             return Ok(None);
@@ -1263,11 +1333,15 @@ fn sha256_digest(input: &str) -> Vec<u8> {
 
 /// Compute full hash for a pending exception.
 ///
-/// If `DCG_ALLOW_ONCE_SECRET` is set, uses HMAC-SHA256 for tamper resistance.
-/// Otherwise, uses plain SHA256 (backwards compatible).
+/// Uses HMAC-SHA256 keyed with the allow-once secret, or plain SHA256 when no
+/// secret could be loaded.
 fn compute_full_hash(timestamp: &str, cwd: &str, command_raw: &str) -> String {
-    let secret = env::var(ENV_ALLOW_ONCE_SECRET).ok();
-    compute_full_hash_with_secret(timestamp, cwd, command_raw, secret.as_deref())
+    compute_full_hash_with_secret(
+        timestamp,
+        cwd,
+        command_raw,
+        crate::allow_once_secret::current(),
+    )
 }
 
 /// Compute full hash with an explicit secret parameter.
@@ -1480,6 +1554,30 @@ mod tests {
         assert_eq!(matches[0].command_raw, "git status");
     }
 
+    #[test]
+    fn test_lookup_by_code_skips_records_that_fail_verification() {
+        let (store, _dir) = make_store();
+        let now = DateTime::parse_from_rfc3339("2026-01-10T06:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let redaction = redaction_config();
+
+        // A hand-written record reusing a real code for a different command.
+        let mut forged =
+            PendingExceptionRecord::new(now, "/repo", "git status", "ok", &redaction, false, None);
+        forged.command_raw = "rm -rf /".to_string();
+        assert!(!forged.hash_verifies());
+
+        std::fs::write(
+            store.path(),
+            format!("{}\n", serde_json::to_string(&forged).unwrap()),
+        )
+        .unwrap();
+
+        let (matches, _maintenance) = store.lookup_by_code(&forged.short_code, now).unwrap();
+        assert!(matches.is_empty());
+    }
+
     #[test]
     fn test_allow_once_consumes_single_use() {
         let dir = TempDir::new().expect("tempdir");
@@ -1506,10 +1604,14 @@ mod tests {
         store.add_entry(&entry, now).unwrap();
 
         let cwd = Path::new("/repo");
-        let first = store.match_command("git status", cwd, now, None).unwrap();
+        let first = store
+            .match_command("git status", cwd, None, now, None)
+            .unwrap();
         assert!(first.is_some());
 
-        let second = store.match_command("git status", cwd, now, None).unwrap();
+        let second = store
+            .match_command("git status", cwd, None, now, None)
+            .unwrap();
         assert!(second.is_none());
     }
 
@@ -1539,7 +1641,46 @@ mod tests {
         store.add_entry(&entry, now).unwrap();
 
         let cwd = Path::new("/repo/subdir");
-        let matched = store.match_command("git status", cwd, now, None).unwrap();
+        let matched = store
+            .match_command("git status", cwd, None, now, None)
+            .unwrap();
+        assert!(matched.is_some());
+    }
+
+    #[test]
+    fn test_allow_once_session_scope() {
+        let dir = TempDir::new().expect("tempdir");
+        let allow_path = dir.path().join("allow_once.jsonl");
+        let store = AllowOnceStore::new(allow_path);
+        let now = DateTime::parse_from_rfc3339("2026-01-10T06:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let redaction = redaction_config();
+
+        let pending =
+            PendingExceptionRecord::new(now, "/repo", "git status", "ok", &redaction, false, None);
+        let mut entry = AllowOnceEntry::from_pending(
+            &pending,
+            now,
+            AllowOnceScopeKind::Cwd,
+            "/repo",
+            false,
+            false,
+            &redaction,
+        );
+        entry.session_id = Some("s1".to_string());
+        store.add_entry(&entry, now).unwrap();
+
+        let cwd = Path::new("/repo");
+        for session in [None, Some("s2")] {
+            let matched = store
+                .match_command("git status", cwd, session, now, None)
+                .unwrap();
+            assert!(matched.is_none(), "{session:?}");
+        }
+        let matched = store
+            .match_command("git status", cwd, Some("s1"), now, None)
+            .unwrap();
         assert!(matched.is_some());
     }
 
@@ -1585,13 +1726,13 @@ mod tests {
         let cwd = Path::new("/repo");
         assert!(
             store
-                .match_command("git status", cwd, now, None)
+                .match_command("git status", cwd, None, now, None)
                 .unwrap()
                 .is_some()
         );
         assert!(
             store
-                .match_command("git status", cwd, now, None)
+                .match_command("git status", cwd, None, now, None)
                 .unwrap()
                 .is_some()
         );
@@ -1626,7 +1767,7 @@ mod tests {
         let cwd = Path::new("/different");
         assert!(
             store
-                .match_command("git status", cwd, now, None)
+                .match_command("git status", cwd, None, now, None)
                 .unwrap()
                 .is_none()
         );
@@ -132,6 +132,8 @@ pub struct Deadline {
     start: Instant,
     /// Maximum duration allowed.
     max_duration: Duration,
+    /// Whether the clock is stopped at `start` (see [`Deadline::frozen`]).
+    frozen: bool,
 }
 
 impl Deadline {
@@ -141,6 +143,20 @@ impl Deadline {
         Self {
             start: Instant::now(),
             max_duration,
+            frozen: false,
+        }
+    }
+
+    /// Create a deadline whose clock never advances, so exactly `remaining`
+    /// is always left.
+    ///
+    /// Makes budget-dependent behavior deterministic in tests and benchmarks.
+    #[must_use]
+    pub fn frozen(remaining: Duration) -> Self {
+        Self {
+            start: Instant::now(),
+            max_duration: remaining,
+            frozen: true,
         }
     }
 
@@ -153,19 +169,23 @@ impl Deadline {
     /// Check if the deadline has been exceeded.
     #[must_use]
     pub fn is_exceeded(&self) -> bool {
-        self.start.elapsed() > self.max_duration
+        self.elapsed() > self.max_duration
     }
 
     /// Get the remaining time before the deadline, or None if exceeded.
     #[must_use]
     pub fn remaining(&self) -> Option<Duration> {
-        self.max_duration.checked_sub(self.start.elapsed())
+        self.max_duration.checked_sub(self.elapsed())
     }
 
     /// Get the elapsed time since the deadline started.
     #[must_use]
     pub fn elapsed(&self) -> Duration {
-        self.start.elapsed()
+        if self.frozen {
+            Duration::ZERO
+        } else {
+            self.start.elapsed()
+        }
     }
 
     /// Get the maximum duration for this deadline.
@@ -219,6 +239,17 @@ pub const PATTERN_MATCH: Budget = Budget::new(
     1000, // panic: 1ms
 );
 
+/// Remaining time below which pack evaluation checks Critical patterns first.
+///
+/// The Critical patterns of every candidate pack run before any High/Medium/Low
+/// ones, so running out of budget partway through is least likely to miss the
+/// worst commands.
+pub const CRITICAL_FIRST: Budget = Budget::from_ms(
+    1, // target: 1ms
+    2, // warning: 2ms
+    5, // panic: 5ms
+);
+
 // =============================================================================
 // Tier 3: Heredoc Trigger Check
 // =============================================================================
@@ -354,6 +385,9 @@ mod tests {
         // Fast path should be faster than pattern match
         assert!(FAST_PATH.panic <= PATTERN_MATCH.panic);
 
+        // Critical-first ordering must kick in before pattern matching fails open
+        assert!(PATTERN_MATCH.panic < CRITICAL_FIRST.panic);
+
         // Heredoc trigger should be fast
         assert!(HEREDOC_TRIGGER.panic < HEREDOC_EXTRACT.target);
 
@@ -395,4 +429,14 @@ mod tests {
         // Should not have budget for operations that take longer than the deadline
         assert!(!deadline.has_budget_for(&large_budget));
     }
+
+    #[test]
+    fn frozen_deadline_keeps_its_remaining_time() {
+        let deadline = Deadline::frozen(Duration::from_micros(1500));
+        std::thread::sleep(Duration::from_millis(2));
+        assert!(!deadline.is_exceeded());
+        assert_eq!(deadline.remaining(), Some(Duration::from_micros(1500)));
+        assert!(deadline.has_budget_for(&Budget::new(100, 500, 1000)));
+        assert!(!deadline.has_budget_for(&Budget::new(1000, 1500, 2000)));
+    }
 }
@@ -0,0 +1,1146 @@
+//! Protected filesystem paths (`[protection.paths]`).
+//!
+//! Pack patterns match command *text*, so they cannot tell that
+//! `rm -rf ../../etc` run from `/home/me/app` lands in `/etc`. This module
+//! resolves the target operands of `rm`, `mv`, `truncate`, and `dd of=`
+//! against the working directory and the environment, normalizes `..`,
+//! follows symlinks for paths that exist, and matches the result against
+//! user-configured glob patterns.
+//!
+//! `sqlite3` is guarded too: its database operand is protected unless the
+//! session is `-readonly` or the SQL argument only reads. `rm app.db-wal`
+//! (and `-shm`/`-journal`) is checked as `app.db`, since deleting the
+//! sidecar of a live database loses committed transactions.
+//!
+//! ```toml
+//! [protection.paths]
+//! patterns = ["~/projects/prod-*", "/etc/**"]
+//! ```
+//!
+//! A target is protected when it, or any of its ancestors, matches a pattern.
+//! Recursive `rm` of a directory that *contains* a protected path (e.g.
+//! `rm -rf ~/projects` with `~/projects/prod-*` protected) is also denied.
+//!
+//! `cd`/`pushd` earlier in the chain moves the working directory, so
+//! `cd /etc && rm -rf nginx` is checked as `/etc/nginx`. When the `cd` is not
+//! joined by `&&` it may have failed, and both directories are checked.
+//!
+//! `[protection.workspaces]` uses the same patterns for the working directory
+//! itself: inside a protected workspace, log-only matches such as `cargo clean`
+//! are raised to warnings (see [`escalate_in_workspace`]).
+
+use std::path::{Component, Path, PathBuf};
+
+use glob::{MatchOptions, Pattern};
+
+use crate::config::ProtectedWorkspacesConfig;
+use crate::normalize::{is_env_assignment, strip_wrapper_prefixes};
+use crate::packs::Severity;
+
+/// Commands whose operands are resolved and checked.
+const GUARDED_COMMANDS: [&str; 5] = ["rm", "mv", "truncate", "dd", "sqlite3"];
+
+/// `SQLite` sidecar files that belong to the database file they extend.
+const SQLITE_SIDECAR_SUFFIXES: [&str; 3] = ["-wal", "-shm", "-journal"];
+
+/// `sqlite3` options that take a value.
+const SQLITE_VALUE_OPTIONS: [&str; 8] = [
+    "-cmd",
+    "-separator",
+    "-newline",
+    "-nullvalue",
+    "-vfs",
+    "-maxsize",
+    "-mmap",
+    "-escape",
+];
+
+/// Upper bound on segments inspected per command (DoS guard).
+const MAX_SEGMENTS: usize = 64;
+
+const GLOB_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// A single compiled protected-path pattern.
+#[derive(Debug)]
+struct ProtectedPattern {
+    /// The pattern as written in config (for diagnostics and deny messages).
+    raw: String,
+    /// The expanded glob.
+    glob: Pattern,
+    /// For `dir/**` patterns, a glob matching `dir` itself.
+    base: Option<Pattern>,
+    /// Leading components that contain no glob metacharacters.
+    literal_prefix: PathBuf,
+}
+
+impl ProtectedPattern {
+    fn compile(raw: &str) -> Result<Self, String> {
+        let trimmed = raw.trim();
+        if trimmed.is_empty() {
+            return Err("empty protected path pattern".to_string());
+        }
+
+        let expanded = expand_word(trimmed, &|key| std::env::var(key).ok());
+        let path = lexical_normalize(Path::new(&expanded));
+        if !path.is_absolute() {
+            return Err(format!(
+                "protected path pattern must be absolute (or start with ~): {trimmed}"
+            ));
+        }
+        let expanded = path.to_string_lossy().into_owned();
+
+        let glob = Pattern::new(&expanded).map_err(|e| e.to_string())?;
+        let base = expanded
+            .strip_suffix("/**")
+            .filter(|b| !b.is_empty())
+            .map(Pattern::new)
+            .transpose()
+            .map_err(|e| e.to_string())?;
+
+        let literal_prefix = path
+            .components()
+            .take_while(|c| !c.as_os_str().to_string_lossy().contains(['*', '?', '[']))
+            .collect();
+
+        Ok(Self {
+            raw: trimmed.to_string(),
+            glob,
+            base,
+            literal_prefix,
+        })
+    }
+
+    /// Does `path` (or one of its ancestors) fall inside this pattern?
+    fn contains(&self, path: &Path) -> bool {
+        path.ancestors().any(|ancestor| {
+            self.glob.matches_path_with(ancestor, GLOB_OPTIONS)
+                || self
+                    .base
+                    .as_ref()
+                    .is_some_and(|b| b.matches_path_with(ancestor, GLOB_OPTIONS))
+        })
+    }
+
+    /// Would recursively deleting `path` destroy something inside this pattern?
+    fn is_under(&self, path: &Path) -> bool {
+        self.literal_prefix.starts_with(path)
+    }
+}
+
+/// Compiled `[protection.paths]` patterns.
+#[derive(Debug, Default)]
+pub struct ProtectedPathMatcher {
+    patterns: Vec<ProtectedPattern>,
+}
+
+/// A command operand that resolved into a protected path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProtectedPathHit {
+    /// The guarded command (`rm`, `mv`, `truncate`, `dd`, `sqlite3`).
+    pub command: &'static str,
+    /// The operand as written in the command.
+    pub operand: String,
+    /// The fully resolved target path.
+    pub resolved: PathBuf,
+    /// The protected pattern that matched (as written in config).
+    pub pattern: String,
+}
+
+impl ProtectedPathHit {
+    /// Human-readable deny reason.
+    #[must_use]
+    pub fn reason(&self) -> String {
+        let reason = format!(
+            "{} target '{}' resolves to '{}', which is protected by [protection.paths] pattern '{}'",
+            self.command,
+            self.operand,
+            self.resolved.display(),
+            self.pattern
+        );
+        if self.command == "sqlite3" {
+            format!("{reason} (open it with -readonly to inspect)")
+        } else {
+            reason
+        }
+    }
+}
+
+impl ProtectedPathMatcher {
+    /// Compile the configured patterns.
+    ///
+    /// Invalid patterns are returned alongside the matcher as
+    /// `(pattern, error)` pairs (fail-open, like override patterns).
+    #[must_use]
+    pub fn compile(patterns: &[String]) -> (Self, Vec<(String, String)>) {
+        let mut matcher = Self::default();
+        let mut invalid = Vec::new();
+        for raw in patterns {
+            match ProtectedPattern::compile(raw) {
+                Ok(pattern) => matcher.patterns.push(pattern),
+                Err(e) => invalid.push((raw.clone(), e)),
+            }
+        }
+        (matcher, invalid)
+    }
+
+    /// Returns true when no patterns are configured.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// The pattern (as written in config) that contains `path`, if any.
+    #[must_use]
+    pub fn containing_pattern(&self, path: &Path) -> Option<&str> {
+        self.patterns
+            .iter()
+            .find(|pattern| pattern.contains(path))
+            .map(|pattern| pattern.raw.as_str())
+    }
+
+    /// Check a command against the protected paths using the process environment.
+    #[must_use]
+    pub fn check(&self, command: &str, cwd: Option<&Path>) -> Option<ProtectedPathHit> {
+        self.check_with_env(command, cwd, &|key| std::env::var(key).ok())
+    }
+
+    /// Check a command, expanding `$VAR`/`${VAR}` through `get_env`.
+    #[must_use]
+    pub fn check_with_env(
+        &self,
+        command: &str,
+        cwd: Option<&Path>,
+        get_env: &dyn Fn(&str) -> Option<String>,
+    ) -> Option<ProtectedPathHit> {
+        if self.patterns.is_empty() || !GUARDED_COMMANDS.iter().any(|c| command.contains(c)) {
+            return None;
+        }
+
+        let mut chain = ChainCwd::new(cwd);
+        for segment in split_segments(command).into_iter().take(MAX_SEGMENTS) {
+            let normalized = strip_wrapper_prefixes(segment.text);
+            let words = split_words(&normalized.normalized, get_env);
+            let mut words = words
+                .into_iter()
+                .skip_while(|w| is_env_assignment(&w.text))
+                .peekable();
+            if chain.enter(&segment, words.peek(), get_env) {
+                continue;
+            }
+            let Some(program) = words.next() else {
+                continue;
+            };
+            let name = program.text.rsplit('/').next().unwrap_or(&program.text);
+            let Some(&guarded) = GUARDED_COMMANDS.iter().find(|c| **c == name) else {
+                continue;
+            };
+            let args: Vec<Word> = words.collect();
+            let (operands, recursive) = if guarded == "sqlite3" {
+                (sqlite_write_target(&args).into_iter().collect(), false)
+            } else {
+                target_operands(guarded, &args)
+            };
+
+            for operand in operands {
+                // Absolute operands ignore the cwd; relative ones are unresolvable without one.
+                let dirs: Vec<Option<&Path>> = if chain.possible.is_empty() {
+                    vec![None]
+                } else {
+                    chain
+                        .possible
+                        .iter()
+                        .map(|dir| Some(dir.as_path()))
+                        .collect()
+                };
+                for dir in dirs {
+                    if let Some(hit) = self.check_operand(guarded, &operand, recursive, dir) {
+                        return Some(hit);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    fn check_operand(
+        &self,
+        command: &'static str,
+        operand: &Word,
+        recursive: bool,
+        cwd: Option<&Path>,
+    ) -> Option<ProtectedPathHit> {
+        let raw = Path::new(&operand.text);
+        let absolute = if raw.is_absolute() {
+            raw.to_path_buf()
+        } else {
+            cwd?.join(raw)
+        };
+        let lexical = lexical_normalize(&absolute);
+        let resolved = resolve_symlinks(&lexical);
+        let database = sqlite_database_of(&lexical);
+
+        // A wildcard operand may expand to anything in its parent directory.
+        let wildcard_dir = operand.has_glob.then(|| literal_dir(&lexical)).flatten();
+
+        for pattern in &self.patterns {
+            for candidate in [Some(&lexical), Some(&resolved), database.as_ref()]
+                .into_iter()
+                .flatten()
+            {
+                let hit = pattern.contains(candidate)
+                    || (recursive && pattern.is_under(candidate))
+                    || (recursive
+                        && wildcard_dir
+                            .as_deref()
+                            .is_some_and(|dir| pattern.is_under(dir)));
+                if hit {
+                    return Some(ProtectedPathHit {
+                        command,
+                        operand: operand.text.clone(),
+                        resolved: candidate.clone(),
+                        pattern: pattern.raw.clone(),
+                    });
+                }
+            }
+        }
+        None
+    }
+}
+
+/// A severity change caused by running inside a protected workspace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceEscalation {
+    /// The `[protection.workspaces]` pattern containing the working directory.
+    pub workspace: String,
+    /// Severity reported by the pack.
+    pub from: Severity,
+    /// Severity after escalation.
+    pub to: Severity,
+}
+
+impl WorkspaceEscalation {
+    /// One-line explanation appended to denial messages.
+    #[must_use]
+    pub fn notice(&self) -> String {
+        format!(
+            "Working directory is in protected workspace '{}': severity {} -> {}.",
+            self.workspace,
+            self.from.label(),
+            self.to.label()
+        )
+    }
+}
+
+/// Raise a Low severity match to Medium when `cwd` is inside a
+/// `[protection.workspaces]` pattern.
+///
+/// Returns `None` when no workspaces are configured, the working directory is
+/// unknown or outside every workspace, or the severity is not Low.
+#[must_use]
+pub fn escalate_in_workspace(
+    config: &ProtectedWorkspacesConfig,
+    cwd: Option<&Path>,
+    severity: Option<Severity>,
+) -> Option<WorkspaceEscalation> {
+    if config.patterns.is_empty() || severity != Some(Severity::Low) {
+        return None;
+    }
+    let cwd = lexical_normalize(cwd?);
+    let (matcher, _invalid) = ProtectedPathMatcher::compile(&config.patterns);
+    let workspace = matcher.containing_pattern(&cwd)?.to_string();
+    Some(WorkspaceEscalation {
+        workspace,
+        from: Severity::Low,
+        to: Severity::Medium,
+    })
+}
+
+/// Resolve the operands of every `rm` in `command` against `cwd`.
+///
+/// Paths are absolute with `..` normalized lexically; symlinks are not
+/// followed (`rm` removes the link, not its target) and wildcard operands are
+/// returned unexpanded.
+#[must_use]
+pub fn rm_targets(command: &str, cwd: &Path) -> Vec<PathBuf> {
+    if !command.contains("rm") {
+        return Vec::new();
+    }
+
+    let get_env = |key: &str| std::env::var(key).ok();
+    let mut targets = Vec::new();
+    let mut chain = ChainCwd::new(Some(cwd));
+    for segment in split_segments(command).into_iter().take(MAX_SEGMENTS) {
+        let normalized = strip_wrapper_prefixes(segment.text);
+        let words = split_words(&normalized.normalized, &get_env);
+        let mut words = words
+            .into_iter()
+            .skip_while(|w| is_env_assignment(&w.text))
+            .peekable();
+        if chain.enter(&segment, words.peek(), &get_env) {
+            continue;
+        }
+        let Some(program) = words.next() else {
+            continue;
+        };
+        if program.text.rsplit('/').next() != Some("rm") {
+            continue;
+        }
+        let args: Vec<Word> = words.collect();
+        let (operands, _) = target_operands("rm", &args);
+        // The most likely directory is the one the last `cd` moved to.
+        let dir = chain.possible.first().map_or(cwd, PathBuf::as_path);
+        targets.extend(
+            operands
+                .into_iter()
+                .map(|operand| lexical_normalize(&dir.join(&operand.text))),
+        );
+    }
+    targets
+}
+
+/// Working directories a segment may run in, following `cd`/`pushd`.
+///
+/// `cd DIR && cmd` only runs `cmd` if the `cd` succeeded, so `cmd` runs in
+/// `DIR`. Any other separator means the `cd` may have failed, so the
+/// directories from before it stay possible too.
+#[derive(Debug)]
+struct ChainCwd {
+    /// Candidate directories, most likely first.
+    possible: Vec<PathBuf>,
+    /// Directories from before the `cd`s of the current `&&` chain.
+    before_cd: Vec<PathBuf>,
+}
+
+impl ChainCwd {
+    fn new(cwd: Option<&Path>) -> Self {
+        Self {
+            possible: cwd.map(Path::to_path_buf).into_iter().collect(),
+            before_cd: Vec::new(),
+        }
+    }
+
+    /// Update the candidates for `segment`; returns `true` if it is a `cd`.
+    fn enter(
+        &mut self,
+        segment: &Segment<'_>,
+        program: Option<&Word>,
+        get_env: &dyn Fn(&str) -> Option<String>,
+    ) -> bool {
+        if !segment.after_and {
+            for dir in std::mem::take(&mut self.before_cd) {
+                push_unique(&mut self.possible, dir);
+            }
+        }
+
+        let Some(program) = program.filter(|w| matches!(w.text.as_str(), "cd" | "pushd")) else {
+            return false;
+        };
+        let normalized = strip_wrapper_prefixes(segment.text);
+        let words = split_words(&normalized.normalized, get_env);
+        let target = words
+            .iter()
+            .skip_while(|w| w.text != program.text)
+            .skip(1)
+            .find(|w| w.text == "-" || !w.text.starts_with('-'));
+
+        let targets: Vec<PathBuf> = match target {
+            None => get_env("HOME").map(PathBuf::from).into_iter().collect(),
+            // `cd -` returns to $OLDPWD, which we cannot know.
+            Some(word) if word.text == "-" => Vec::new(),
+            Some(word) if Path::new(&word.text).is_absolute() => {
+                vec![lexical_normalize(Path::new(&word.text))]
+            }
+            Some(word) => self
+                .possible
+                .iter()
+                .map(|base| lexical_normalize(&base.join(&word.text)))
+                .collect(),
+        };
+        if targets.is_empty() {
+            return true;
+        }
+
+        for dir in std::mem::replace(&mut self.possible, targets) {
+            push_unique(&mut self.before_cd, dir);
+        }
+        true
+    }
+}
+
+fn push_unique(dirs: &mut Vec<PathBuf>, dir: PathBuf) {
+    if !dirs.contains(&dir) {
+        dirs.push(dir);
+    }
+}
+
+/// Extract the path operands for a guarded command, plus whether it recurses.
+fn target_operands(command: &str, args: &[Word]) -> (Vec<Word>, bool) {
+    let mut operands = Vec::new();
+    let mut recursive = false;
+    let mut options_ended = false;
+    let mut skip_next = false;
+    let mut skip_redirect_target = false;
+
+    for arg in args {
+        let text = arg.text.as_str();
+        if skip_redirect_target {
+            skip_redirect_target = false;
+            continue;
+        }
+        // Redirections (`>out`, `2> err`, `<in`) are not operands.
+        let redirect = text.trim_start_matches(|c: char| c.is_ascii_digit() || c == '&');
+        if redirect.starts_with(['>', '<']) {
+            skip_redirect_target = redirect.trim_start_matches(['>', '<', '&', '|']).is_empty();
+            continue;
+        }
+        if skip_next {
+            skip_next = false;
+            if command == "mv" {
+                // `mv -t DIR`: DIR is a target too.
+                operands.push(arg.clone());
+            }
+            continue;
+        }
+
+        if command == "dd" {
+            if let Some(path) = text.strip_prefix("of=").filter(|p| !p.is_empty()) {
+                operands.push(arg.with_text(path));
+            }
+            continue;
+        }
+
+        if !options_ended && text == "--" {
+            options_ended = true;
+            continue;
+        }
+
+        if !options_ended && text.starts_with('-') && text.len() > 1 {
+            match command {
+                "rm" => {
+                    if text == "--recursive"
+                        || (!text.starts_with("--") && text.contains(['r', 'R']))
+                    {
+                        recursive = true;
+                    }
+                }
+                "mv" => {
+                    if text == "-t" || text == "--target-directory" {
+                        skip_next = true;
+                    } else if let Some(dir) = text.strip_prefix("--target-directory=") {
+                        operands.push(arg.with_text(dir));
+                    }
+                }
+                "truncate" => {
+                    if matches!(text, "-s" | "-r" | "--size" | "--reference") {
+                        skip_next = true;
+                    }
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        operands.push(arg.clone());
+    }
+
+    (operands, recursive)
+}
+
+/// The database operand of a `sqlite3` invocation that may write to it.
+///
+/// `-readonly` sessions and a SQL argument made only of `SELECT`/`EXPLAIN`
+/// statements and read-only dot-commands cannot modify the file.
+fn sqlite_write_target(args: &[Word]) -> Option<Word> {
+    let mut positional = Vec::new();
+    let mut skip_next = false;
+    for arg in args {
+        let text = arg.text.as_str();
+        if skip_next {
+            skip_next = false;
+            continue;
+        }
+        if text.starts_with('-') && text.len() > 1 {
+            let option = format!("-{}", text.trim_start_matches('-'));
+            if option == "-readonly" {
+                return None;
+            }
+            skip_next = SQLITE_VALUE_OPTIONS.contains(&option.as_str());
+            continue;
+        }
+        positional.push(arg);
+    }
+
+    let database = positional.first()?;
+    if database.text.is_empty() || database.text == ":memory:" {
+        return None;
+    }
+    if positional
+        .get(1)
+        .is_some_and(|sql| is_read_only_sql(&sql.text))
+    {
+        return None;
+    }
+    Some((*database).clone())
+}
+
+/// True when every statement of `sql` only reads.
+fn is_read_only_sql(sql: &str) -> bool {
+    let mut statements = sql
+        .split(['\n', ';'])
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .peekable();
+    statements.peek().is_some()
+        && statements.all(|statement| {
+            let keyword = statement
+                .split_whitespace()
+                .next()
+                .unwrap_or_default()
+                .to_ascii_lowercase();
+            matches!(
+                keyword.as_str(),
+                "select" | "explain" | ".schema" | ".tables" | ".indexes" | ".dump"
+            )
+        })
+}
+
+/// The database a `SQLite` sidecar file (`app.db-wal`) belongs to.
+fn sqlite_database_of(path: &Path) -> Option<PathBuf> {
+    let name = path.file_name()?.to_str()?;
+    SQLITE_SIDECAR_SUFFIXES
+        .iter()
+        .find_map(|suffix| name.strip_suffix(suffix))
+        .filter(|db| !db.is_empty())
+        .map(|db| path.with_file_name(db))
+}
+
+/// A shell word after quote removal and variable expansion.
+#[derive(Debug, Clone)]
+struct Word {
+    text: String,
+    /// True when the word contains unquoted glob metacharacters.
+    has_glob: bool,
+}
+
+impl Word {
+    /// Copy of this word with its text replaced (for `key=PATH` operands).
+    fn with_text(&self, text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            has_glob: self.has_glob,
+        }
+    }
+}
+
+/// A simple command within a command line.
+#[derive(Debug, Clone, Copy)]
+struct Segment<'a> {
+    text: &'a str,
+    /// True when joined to the previous segment by `&&` (runs only if it succeeded).
+    after_and: bool,
+}
+
+/// Split a command line into simple-command segments on `;`, `&`, `|`, and newlines.
+fn split_segments(command: &str) -> Vec<Segment<'_>> {
+    let mut segments = Vec::new();
+    let mut start = 0;
+    let mut in_single = false;
+    let mut in_double = false;
+    let mut escaped = false;
+    let mut after_and = false;
+    let mut chars = command.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' if !in_single => escaped = true,
+            '\'' if !in_double => in_single = !in_single,
+            '"' if !in_single => in_double = !in_double,
+            ';' | '&' | '|' | '\n' | '(' | ')' if !in_single && !in_double => {
+                let segment = command[start..i].trim();
+                if !segment.is_empty() {
+                    segments.push(Segment {
+                        text: segment,
+                        after_and,
+                    });
+                }
+                let is_and = c == '&' && chars.next_if(|&(_, next)| next == '&').is_some();
+                start = i + if is_and { 2 } else { 1 };
+                after_and = is_and;
+            }
+            _ => {}
+        }
+    }
+    let tail = command[start..].trim();
+    if !tail.is_empty() {
+        segments.push(Segment {
+            text: tail,
+            after_and,
+        });
+    }
+    segments
+}
+
+/// Split a simple command into words, removing quotes and expanding variables.
+fn split_words(segment: &str, get_env: &dyn Fn(&str) -> Option<String>) -> Vec<Word> {
+    let mut words = Vec::new();
+    let mut cur = String::new();
+    let mut started = false;
+    let mut has_glob = false;
+    let mut in_single = false;
+    let mut in_double = false;
+    let chars: Vec<char> = segment.chars().collect();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\'' if !in_double => {
+                in_single = !in_single;
+                started = true;
+            }
+            '"' if !in_single => {
+                in_double = !in_double;
+                started = true;
+            }
+            '\\' if !in_single && i + 1 < chars.len() => {
+                i += 1;
+                cur.push(chars[i]);
+                started = true;
+            }
+            '$' if !in_single => {
+                let (value, consumed) = expand_variable(&chars[i + 1..], get_env);
+                cur.push_str(&value);
+                i += consumed;
+                started = true;
+            }
+            '~' if !in_single && !in_double && !started => {
+                let next = chars.get(i + 1).copied();
+                if matches!(next, None | Some('/')) || next.is_some_and(char::is_whitespace) {
+                    if let Some(home) = dirs::home_dir() {
+                        cur.push_str(&home.to_string_lossy());
+                    } else {
+                        cur.push('~');
+                    }
+                } else {
+                    cur.push('~');
+                }
+                started = true;
+            }
+            c if c.is_whitespace() && !in_single && !in_double => {
+                finish_word(&mut cur, &mut started, &mut has_glob, &mut words);
+            }
+            c => {
+                if matches!(c, '*' | '?' | '[') && !in_single && !in_double {
+                    has_glob = true;
+                }
+                cur.push(c);
+                started = true;
+            }
+        }
+        i += 1;
+    }
+    finish_word(&mut cur, &mut started, &mut has_glob, &mut words);
+    words
+}
+
+fn finish_word(cur: &mut String, started: &mut bool, has_glob: &mut bool, words: &mut Vec<Word>) {
+    if *started {
+        words.push(Word {
+            text: std::mem::take(cur),
+            has_glob: *has_glob,
+        });
+    }
+    *started = false;
+    *has_glob = false;
+}
+
+/// Expand `$NAME` / `${NAME}` (the `$` has already been consumed).
+///
+/// Returns the expansion and the number of chars consumed after `$`. Unset
+/// variables expand to the empty string, as in the shell. Command substitution
+/// and other forms cannot be resolved statically and are kept literally.
+fn expand_variable(rest: &[char], get_env: &dyn Fn(&str) -> Option<String>) -> (String, usize) {
+    let is_name = |c: &char| c.is_ascii_alphanumeric() || *c == '_';
+    if rest.first() == Some(&'{') {
+        if let Some(end) = rest.iter().position(|c| *c == '}') {
+            let name: String = rest[1..end].iter().collect();
+            if !name.is_empty() && name.chars().all(|c| is_name(&c)) {
+                return (get_env(&name).unwrap_or_default(), end + 1);
+            }
+        }
+        return ("$".to_string(), 0);
+    }
+    let name: String = rest.iter().take_while(|c| is_name(c)).collect();
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        return ("$".to_string(), 0);
+    }
+    let len = name.chars().count();
+    (get_env(&name).unwrap_or_default(), len)
+}
+
+/// Expand `~` and environment variables in a config pattern.
+fn expand_word(word: &str, get_env: &dyn Fn(&str) -> Option<String>) -> String {
+    split_words(word, get_env)
+        .into_iter()
+        .map(|w| w.text)
+        .next()
+        .unwrap_or_default()
+}
+
+/// Resolve `.` and `..` components without touching the filesystem.
+fn lexical_normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                // `/..` is `/`; never pop past the root.
+                if !matches!(
+                    out.components().next_back(),
+                    None | Some(Component::RootDir)
+                ) {
+                    out.pop();
+                }
+            }
+            other => out.push(other.as_os_str()),
+        }
+    }
+    out
+}
+
+/// Canonicalize the longest existing prefix of `path`, re-appending the rest.
+fn resolve_symlinks(path: &Path) -> PathBuf {
+    let mut existing = path;
+    let mut suffix = Vec::new();
+    loop {
+        if let Ok(canonical) = existing.canonicalize() {
+            let mut out = canonical;
+            for part in suffix.iter().rev() {
+                out.push(part);
+            }
+            return out;
+        }
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(name)) => {
+                suffix.push(name.to_os_string());
+                existing = parent;
+            }
+            _ => return path.to_path_buf(),
+        }
+    }
+}
+
+/// Directory portion of a wildcard operand before the first glob component.
+fn literal_dir(path: &Path) -> Option<PathBuf> {
+    let dir: PathBuf = path
+        .components()
+        .take_while(|c| !c.as_os_str().to_string_lossy().contains(['*', '?', '[']))
+        .collect();
+    (dir.as_os_str() != path.as_os_str()).then_some(dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matcher(patterns: &[&str]) -> ProtectedPathMatcher {
+        let patterns: Vec<String> = patterns.iter().map(ToString::to_string).collect();
+        let (matcher, invalid) = ProtectedPathMatcher::compile(&patterns);
+        assert!(
+            invalid.is_empty(),
+            "unexpected invalid patterns: {invalid:?}"
+        );
+        matcher
+    }
+
+    fn no_env(_: &str) -> Option<String> {
+        None
+    }
+
+    #[test]
+    fn rm_targets_resolves_operands() {
+        let cwd = Path::new("/home/me/app");
+        assert_eq!(
+            rm_targets("rm -rf build ../cache -- -weird && ls", cwd),
+            vec![
+                PathBuf::from("/home/me/app/build"),
+                PathBuf::from("/home/me/cache"),
+                PathBuf::from("/home/me/app/-weird"),
+            ]
+        );
+        assert_eq!(
+            rm_targets("sudo rm /tmp/x.log 2>/dev/null", cwd),
+            vec![PathBuf::from("/tmp/x.log")]
+        );
+        assert!(rm_targets("mv a b; git rm --cached c", cwd).is_empty());
+    }
+
+    #[test]
+    fn rm_targets_follow_cd_chain() {
+        let cwd = Path::new("/home/me/app");
+        assert_eq!(
+            rm_targets("cd /srv/site && rm -rf build", cwd),
+            vec![PathBuf::from("/srv/site/build")]
+        );
+        assert_eq!(
+            rm_targets("cd sub && cd ../other && rm x", cwd),
+            vec![PathBuf::from("/home/me/app/other/x")]
+        );
+    }
+
+    #[test]
+    fn sqlite_writes_to_protected_databases_are_denied() {
+        let m = matcher(&["/srv/prod/*.db"]);
+        let cwd = Path::new("/srv/prod");
+
+        let hit = m
+            .check_with_env(r#"sqlite3 app.db "DROP TABLE users""#, Some(cwd), &no_env)
+            .expect("write to protected database");
+        assert_eq!(hit.command, "sqlite3");
+        assert_eq!(hit.resolved, PathBuf::from("/srv/prod/app.db"));
+        assert!(hit.reason().contains("-readonly"));
+        assert!(
+            m.check_with_env("sqlite3 -bail /srv/prod/app.db", None, &no_env)
+                .is_some()
+        );
+        assert!(
+            m.check_with_env(
+                "sqlite3 -cmd '.timeout 500' app.db < fix.sql",
+                Some(cwd),
+                &no_env
+            )
+            .is_some()
+        );
+
+        assert!(
+            m.check_with_env("sqlite3 -readonly app.db", Some(cwd), &no_env)
+                .is_none()
+        );
+        assert!(
+            m.check_with_env(
+                r#"sqlite3 -header app.db "SELECT count(*) FROM users; .tables""#,
+                Some(cwd),
+                &no_env
+            )
+            .is_none()
+        );
+        assert!(
+            m.check_with_env("sqlite3 /tmp/scratch.db 'DELETE FROM t'", None, &no_env)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn sqlite_sidecars_are_checked_as_their_database() {
+        let m = matcher(&["/srv/prod/app.db"]);
+        let cwd = Path::new("/srv/prod");
+        assert!(
+            m.check_with_env("rm app.db-wal app.db-shm", Some(cwd), &no_env)
+                .is_some()
+        );
+        assert!(
+            m.check_with_env("mv app.db-journal /tmp/", Some(cwd), &no_env)
+                .is_some()
+        );
+        assert!(
+            m.check_with_env("rm other.db-wal", Some(cwd), &no_env)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn cd_chain_moves_working_directory() {
+        let m = matcher(&["/etc/**"]);
+        let cwd = Path::new("/tmp");
+        assert!(
+            m.check_with_env("cd /etc && rm -rf nginx", Some(cwd), &no_env)
+                .is_some()
+        );
+        assert!(
+            m.check_with_env("cd / && cd etc && rm hosts", Some(cwd), &no_env)
+                .is_some()
+        );
+        assert!(
+            m.check_with_env("cd /etc && cd /tmp && rm -rf nginx", Some(cwd), &no_env)
+                .is_none()
+        );
+        assert!(
+            m.check_with_env("pushd /etc >/dev/null && rm hosts", None, &no_env)
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn cd_without_and_checks_both_directories() {
+        let m = matcher(&["/etc/**", "/srv/data/**"]);
+        // The `cd` may fail, so `rm` could run in either directory.
+        assert!(
+            m.check_with_env("cd /tmp; rm -rf data", Some(Path::new("/srv")), &no_env)
+                .is_some()
+        );
+        assert!(
+            m.check_with_env(
+                "cd /etc && cd /tmp; rm hosts",
+                Some(Path::new("/var")),
+                &no_env
+            )
+            .is_some()
+        );
+        // Plain `cd` goes to $HOME.
+        let home = |key: &str| (key == "HOME").then(|| "/etc".to_string());
+        assert!(
+            m.check_with_env("cd && rm hosts", Some(Path::new("/tmp")), &home)
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn blocks_direct_and_nested_targets() {
+        let m = matcher(&["/etc/**"]);
+        let cwd = Path::new("/tmp");
+        assert!(
+            m.check_with_env("rm /etc/hosts", Some(cwd), &no_env)
+                .is_some()
+        );
+        assert!(
+            m.check_with_env("rm -rf /etc", Some(cwd), &no_env)
+                .is_some()
+        );
+        assert!(
+            m.check_with_env("truncate -s 0 /etc/passwd", Some(cwd), &no_env)
+                .is_some()
+        );
+        assert!(
+            m.check_with_env("dd if=/dev/zero of=/etc/fstab", Some(cwd), &no_env)
+                .is_some()
+        );
+        assert!(
+            m.check_with_env("mv /etc/hosts /tmp/hosts", Some(cwd), &no_env)
+                .is_some()
+        );
+        assert!(
+            m.check_with_env("rm -rf /tmp/build", Some(cwd), &no_env)
+                .is_none()
+        );
+        assert!(
+            m.check_with_env("cat /etc/hosts", Some(cwd), &no_env)
+                .is_none()
+        );
+        assert!(
+            m.check_with_env("rm -f /tmp/x > /etc/log 2>&1", Some(cwd), &no_env)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn resolves_parent_components_against_cwd() {
+        let m = matcher(&["/etc/**"]);
+        let hit = m
+            .check_with_env("rm -rf ../../etc/ssh", Some(Path::new("/home/me")), &no_env)
+            .expect("should resolve into /etc");
+        assert_eq!(hit.command, "rm");
+        assert_eq!(hit.resolved, PathBuf::from("/etc/ssh"));
+        assert!(hit.reason().contains("/etc/**"));
+    }
+
+    #[test]
+    fn expands_environment_variables() {
+        let m = matcher(&["/srv/prod-*"]);
+        let env = |key: &str| (key == "TARGET").then(|| "/srv/prod-api".to_string());
+        let cwd = Some(Path::new("/"));
+        assert!(m.check_with_env("rm -rf $TARGET/data", cwd, &env).is_some());
+        assert!(
+            m.check_with_env("rm -rf \"${TARGET}\"", cwd, &env)
+                .is_some()
+        );
+        // Single quotes suppress expansion.
+        assert!(m.check_with_env("rm -rf '$TARGET'", cwd, &env).is_none());
+    }
+
+    #[test]
+    fn recursive_delete_of_ancestor_is_blocked() {
+        let m = matcher(&["/srv/prod-*"]);
+        let cwd = Some(Path::new("/"));
+        assert!(m.check_with_env("rm -rf /srv", cwd, &no_env).is_some());
+        assert!(m.check_with_env("rm -rf /srv/*", cwd, &no_env).is_some());
+        assert!(
+            m.check_with_env("rm /srv/notes.txt", cwd, &no_env)
+                .is_none()
+        );
+        assert!(
+            m.check_with_env("rm -rf /srv/staging-api", cwd, &no_env)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn sees_through_wrappers_and_chains() {
+        let m = matcher(&["/etc/**"]);
+        let cwd = Some(Path::new("/tmp"));
+        assert!(
+            m.check_with_env("sudo rm -f /etc/motd", cwd, &no_env)
+                .is_some()
+        );
+        assert!(
+            m.check_with_env("cd /tmp && /bin/rm -- /etc/motd", cwd, &no_env)
+                .is_some()
+        );
+        assert!(
+            m.check_with_env("FOO=1 mv x -t /etc", cwd, &no_env)
+                .is_some()
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn follows_symlinks() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let protected = dir.path().join("prod");
+        std::fs::create_dir(&protected).expect("mkdir");
+        let link = dir.path().join("innocent");
+        std::os::unix::fs::symlink(&protected, &link).expect("symlink");
+
+        let root = dir.path().canonicalize().expect("canonicalize");
+        let pattern = format!("{}/prod/**", root.display());
+        let m = matcher(&[pattern.as_str()]);
+        assert!(
+            m.check_with_env("rm -rf innocent/data", Some(dir.path()), &no_env)
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn relative_patterns_are_rejected() {
+        let (m, invalid) = ProtectedPathMatcher::compile(&["prod/**".to_string()]);
+        assert!(m.is_empty());
+        assert_eq!(invalid.len(), 1);
+    }
+
+    #[test]
+    fn workspaces_raise_low_matches_only() {
+        let config = ProtectedWorkspacesConfig {
+            patterns: vec!["/work/monorepo".to_string()],
+        };
+        let inside = Path::new("/work/monorepo/crates/core");
+
+        let escalation = escalate_in_workspace(&config, Some(inside), Some(Severity::Low))
+            .expect("low match inside the workspace is raised");
+        assert_eq!(escalation.workspace, "/work/monorepo");
+        assert_eq!(escalation.to, Severity::Medium);
+
+        assert!(escalate_in_workspace(&config, Some(inside), Some(Severity::High)).is_none());
+        assert!(
+            escalate_in_workspace(&config, Some(Path::new("/work/other")), Some(Severity::Low))
+                .is_none()
+        );
+        assert!(escalate_in_workspace(&config, None, Some(Severity::Low)).is_none());
+    }
+}
@@ -0,0 +1,370 @@
+//! Quick-allow filter: a persistent Bloom filter of repeated clean allows.
+//!
+//! The evaluation cache ([`crate::eval_cache`]) reads, parses, and often
+//! rewrites a JSON file on every lookup. Agent loops re-run the same handful
+//! of commands hundreds of times, so with `[cache] quick_allow = true` the
+//! hook first consults this filter, which answers with a few single-byte
+//! reads under a shared lock.
+//!
+//! The file holds two Bloom filters over the same key as the evaluation cache
+//! (command and working directory). The first clean allow of a command sets
+//! its bits in the *seen* filter; a second clean allow promotes it into the
+//! *quick* filter, which is the only one consulted before evaluation. A
+//! command therefore has to have evaluated to a clean allow at least twice
+//! under the current configuration before evaluation is skipped for it.
+//!
+//! Keys are whole commands rather than command prefixes: `rm -rf build`
+//! being a clean allow says nothing about `rm -rf /`.
+//!
+//! A Bloom filter can report a command it has never seen. The filter is sized
+//! so that, at [`QUICK_CAPACITY`] entries, that happens for roughly one
+//! unseen command in 200 million, and both filters are reset once either
+//! reaches its capacity. The header stores the same [`ConfigFingerprint`] as
+//! the evaluation cache; any config, allowlist, pack, or version change makes
+//! every lookup a miss and the next record resets the file. All failures are treated as a
+//! miss.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use fs2::FileExt;
+use serde::Serialize;
+
+use crate::config::{Config, EvalCacheConfig};
+use crate::eval_cache::{EvalCache, cache_key};
+use crate::fingerprint::ConfigFingerprint;
+
+/// Environment variable overriding the filter file location.
+pub const ENV_QUICK_ALLOW_PATH: &str = "DCG_QUICK_ALLOW_PATH";
+
+/// Commands the quick filter holds before both filters are reset.
+pub const QUICK_CAPACITY: u32 = 10_000;
+
+/// Commands the seen filter holds before both filters are reset.
+pub const SEEN_CAPACITY: u32 = 50_000;
+
+const QUICK_ALLOW_FILE: &str = "quick_allow.bin";
+const MAGIC: &[u8; 4] = b"DCGQ";
+const SCHEMA_VERSION: u32 = 1;
+/// Bits per filter (128 KiB each).
+const FILTER_BITS: u64 = 1 << 20;
+const FILTER_BYTES: u64 = FILTER_BITS / 8;
+const HASHES: u64 = 7;
+const FINGERPRINT_LEN: usize = 64;
+/// Magic, schema version, fingerprint, seen count, quick count.
+const HEADER_LEN: u64 = 4 + 4 + FINGERPRINT_LEN as u64 + 4 + 4;
+const FILE_LEN: u64 = HEADER_LEN + 2 * FILTER_BYTES;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Filter {
+    Seen,
+    Quick,
+}
+
+impl Filter {
+    const fn offset(self) -> u64 {
+        match self {
+            Self::Seen => HEADER_LEN,
+            Self::Quick => HEADER_LEN + FILTER_BYTES,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Header {
+    seen: u32,
+    quick: u32,
+}
+
+/// Summary of the filter for `dcg cache status`.
+#[derive(Debug, Clone, Serialize)]
+pub struct QuickAllowStatus {
+    /// Filter file location.
+    pub path: PathBuf,
+    /// Commands seen once as a clean allow.
+    pub seen: u32,
+    /// Commands that skip evaluation.
+    pub quick: u32,
+    /// Whether the stored fingerprint matches the current configuration.
+    pub fingerprint_matches: bool,
+}
+
+/// File-backed Bloom filter of commands that repeatedly evaluated to a clean allow.
+#[derive(Debug, Clone)]
+pub struct QuickAllowFilter {
+    path: PathBuf,
+    fingerprint: String,
+}
+
+impl QuickAllowFilter {
+    /// Create a filter handle.
+    #[must_use]
+    pub const fn new(path: PathBuf, fingerprint: String) -> Self {
+        Self { path, fingerprint }
+    }
+
+    /// Filter handle for the effective configuration, whether or not
+    /// `[cache] quick_allow` is enabled. Returns `None` when no location can
+    /// be determined.
+    #[must_use]
+    pub fn for_config(config: &Config, fingerprint: &ConfigFingerprint) -> Option<Self> {
+        let path = Self::default_path(&config.cache)?;
+        Some(Self::new(path, fingerprint.to_string()))
+    }
+
+    /// Resolve the filter file: `DCG_QUICK_ALLOW_PATH`, else
+    /// `quick_allow.bin` next to the evaluation cache.
+    #[must_use]
+    pub fn default_path(config: &EvalCacheConfig) -> Option<PathBuf> {
+        if let Ok(path) = std::env::var(ENV_QUICK_ALLOW_PATH) {
+            return Some(PathBuf::from(path));
+        }
+        EvalCache::default_path(config).map(|path| path.with_file_name(QUICK_ALLOW_FILE))
+    }
+
+    /// Filter file location.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Whether `command` run in `cwd` may skip evaluation.
+    ///
+    /// Never writes. Any I/O error or a fingerprint mismatch is a miss.
+    #[must_use]
+    pub fn contains(&self, command: &str, cwd: &str) -> bool {
+        let Ok(mut file) = File::open(&self.path) else {
+            return false;
+        };
+        if FileExt::lock_shared(&file).is_err() {
+            return false;
+        }
+        let found = self.read_header(&mut file).is_some()
+            && test_bits(&mut file, Filter::Quick, &bit_indices(command, cwd)).unwrap_or(false);
+        let _ = FileExt::unlock(&file);
+        found
+    }
+
+    /// Record that `command` run in `cwd` evaluated to a clean allow.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the filter file cannot be opened or written.
+    pub fn record_allow(&self, command: &str, cwd: &str) -> io::Result<()> {
+        let mut file = open_locked(&self.path)?;
+        let mut header = match self.read_header(&mut file) {
+            Some(header) if header.seen < SEEN_CAPACITY && header.quick < QUICK_CAPACITY => header,
+            _ => self.reset(&mut file)?,
+        };
+
+        let indices = bit_indices(command, cwd);
+        if test_bits(&mut file, Filter::Quick, &indices)? {
+            return Ok(());
+        }
+        if test_bits(&mut file, Filter::Seen, &indices)? {
+            set_bits(&mut file, Filter::Quick, &indices)?;
+            header.quick += 1;
+        } else {
+            set_bits(&mut file, Filter::Seen, &indices)?;
+            header.seen += 1;
+        }
+        self.write_header(&mut file, header)?;
+        file.flush()
+    }
+
+    /// Reset both filters. Returns how many quick-allow commands were dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the filter file cannot be opened or written.
+    pub fn clear(&self) -> io::Result<u32> {
+        if !self.path.exists() {
+            return Ok(0);
+        }
+        let mut file = open_locked(&self.path)?;
+        let dropped = self.read_header(&mut file).map_or(0, |header| header.quick);
+        self.reset(&mut file)?;
+        Ok(dropped)
+    }
+
+    /// Inspect the filter without modifying it.
+    #[must_use]
+    pub fn status(&self) -> QuickAllowStatus {
+        let header = File::open(&self.path)
+            .ok()
+            .and_then(|mut file| self.read_header(&mut file));
+        let Header { seen, quick } = header.unwrap_or_default();
+        QuickAllowStatus {
+            path: self.path.clone(),
+            seen,
+            quick,
+            fingerprint_matches: header.is_some(),
+        }
+    }
+
+    /// Read the header; `None` unless the file is complete and was written
+    /// for the current fingerprint.
+    fn read_header(&self, file: &mut File) -> Option<Header> {
+        if file.metadata().ok()?.len() != FILE_LEN {
+            return None;
+        }
+        let mut buf = [0u8; HEADER_LEN as usize];
+        file.seek(SeekFrom::Start(0)).ok()?;
+        file.read_exact(&mut buf).ok()?;
+        let (magic, rest) = buf.split_at(4);
+        let (version, rest) = rest.split_at(4);
+        let (fingerprint, rest) = rest.split_at(FINGERPRINT_LEN);
+        let (seen, quick) = rest.split_at(4);
+        if magic != MAGIC
+            || u32::from_le_bytes(version.try_into().ok()?) != SCHEMA_VERSION
+            || fingerprint != self.fingerprint_bytes()
+        {
+            return None;
+        }
+        Some(Header {
+            seen: u32::from_le_bytes(seen.try_into().ok()?),
+            quick: u32::from_le_bytes(quick.try_into().ok()?),
+        })
+    }
+
+    fn write_header(&self, file: &mut File, header: Header) -> io::Result<()> {
+        let mut buf = Vec::with_capacity(HEADER_LEN as usize);
+        buf.extend_from_slice(MAGIC);
+        buf.extend_from_slice(&SCHEMA_VERSION.to_le_bytes());
+        buf.extend_from_slice(&self.fingerprint_bytes());
+        buf.extend_from_slice(&header.seen.to_le_bytes());
+        buf.extend_from_slice(&header.quick.to_le_bytes());
+        file.seek(SeekFrom::Start(0))?;
+        file.write_all(&buf)
+    }
+
+    /// Zero both filters and write a fresh header.
+    fn reset(&self, file: &mut File) -> io::Result<Header> {
+        file.set_len(0)?;
+        file.set_len(FILE_LEN)?;
+        self.write_header(file, Header::default())?;
+        Ok(Header::default())
+    }
+
+    /// The fingerprint padded or truncated to its fixed header width.
+    fn fingerprint_bytes(&self) -> [u8; FINGERPRINT_LEN] {
+        let mut out = [0u8; FINGERPRINT_LEN];
+        let bytes = self.fingerprint.as_bytes();
+        let len = bytes.len().min(FINGERPRINT_LEN);
+        out[..len].copy_from_slice(&bytes[..len]);
+        out
+    }
+}
+
+/// Bit positions for a command, by double hashing the evaluation cache key.
+fn bit_indices(command: &str, cwd: &str) -> [u64; HASHES as usize] {
+    let key = cache_key(command, cwd);
+    let h1 = u64::from_str_radix(&key[..16], 16).unwrap_or_default();
+    // An odd step visits distinct positions in a power-of-two table.
+    let h2 = u64::from_str_radix(&key[16..32], 16).unwrap_or_default() | 1;
+    let mut indices = [0u64; HASHES as usize];
+    for (i, index) in (0..HASHES).zip(indices.iter_mut()) {
+        *index = h1.wrapping_add(i.wrapping_mul(h2)) % FILTER_BITS;
+    }
+    indices
+}
+
+fn test_bits(file: &mut File, filter: Filter, indices: &[u64]) -> io::Result<bool> {
+    for &index in indices {
+        let mut byte = [0u8; 1];
+        file.seek(SeekFrom::Start(filter.offset() + index / 8))?;
+        file.read_exact(&mut byte)?;
+        if byte[0] & (1 << (index % 8)) == 0 {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+fn set_bits(file: &mut File, filter: Filter, indices: &[u64]) -> io::Result<()> {
+    for &index in indices {
+        let offset = filter.offset() + index / 8;
+        let mut byte = [0u8; 1];
+        file.seek(SeekFrom::Start(offset))?;
+        file.read_exact(&mut byte)?;
+        byte[0] |= 1 << (index % 8);
+        file.seek(SeekFrom::Start(offset))?;
+        file.write_all(&byte)?;
+    }
+    Ok(())
+}
+
+fn open_locked(path: &Path) -> io::Result<File> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let file = OpenOptions::new()
+        .create(true)
+        .read(true)
+        .write(true)
+        .truncate(false)
+        .open(path)?;
+    file.lock_exclusive()?;
+    Ok(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn make_filter(dir: &TempDir, fingerprint: &str) -> QuickAllowFilter {
+        QuickAllowFilter::new(dir.path().join(QUICK_ALLOW_FILE), fingerprint.to_string())
+    }
+
+    #[test]
+    fn second_clean_allow_enables_quick_path() {
+        let dir = TempDir::new().unwrap();
+        let filter = make_filter(&dir, "fp");
+
+        assert!(!filter.contains("cargo test", "/repo"));
+        filter.record_allow("cargo test", "/repo").unwrap();
+        assert!(
+            !filter.contains("cargo test", "/repo"),
+            "one clean allow is not enough"
+        );
+        filter.record_allow("cargo test", "/repo").unwrap();
+        assert!(filter.contains("cargo test", "/repo"));
+        assert!(!filter.contains("cargo test", "/other"));
+        assert!(!filter.contains("cargo test --release", "/repo"));
+
+        let status = filter.status();
+        assert_eq!((status.seen, status.quick), (1, 1));
+        assert!(status.fingerprint_matches);
+    }
+
+    #[test]
+    fn fingerprint_change_invalidates_filter() {
+        let dir = TempDir::new().unwrap();
+        let old = make_filter(&dir, "old");
+        old.record_allow("ls", "/repo").unwrap();
+        old.record_allow("ls", "/repo").unwrap();
+        assert!(old.contains("ls", "/repo"));
+
+        let new = make_filter(&dir, "new");
+        assert!(!new.contains("ls", "/repo"));
+        assert!(!new.status().fingerprint_matches);
+        new.record_allow("ls", "/repo").unwrap();
+        assert!(!new.contains("ls", "/repo"), "mismatch resets both filters");
+    }
+
+    #[test]
+    fn clear_and_corrupt_file_reset() {
+        let dir = TempDir::new().unwrap();
+        let filter = make_filter(&dir, "fp");
+        std::fs::write(filter.path(), "garbage").unwrap();
+        assert!(!filter.contains("ls", "/repo"));
+
+        filter.record_allow("ls", "/repo").unwrap();
+        filter.record_allow("ls", "/repo").unwrap();
+        assert_eq!(filter.clear().unwrap(), 1);
+        assert!(!filter.contains("ls", "/repo"));
+        assert_eq!(std::fs::metadata(filter.path()).unwrap().len(), FILE_LEN);
+    }
+}
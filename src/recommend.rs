@@ -0,0 +1,218 @@
+//! Pack recommendations from command history (`dcg recommend`).
+//!
+//! Commands the history database recorded as allowed are replayed against
+//! each pack that is not enabled. A pack is recommended when some of those
+//! commands mention its keywords and would actually have been blocked by it.
+//! The replay runs the full evaluator with the pack added to the current
+//! configuration, so allowlists, severity overrides, and safe patterns of
+//! the enabled packs are taken into account: the counts are measured, not
+//! guessed from keywords.
+
+use std::collections::HashSet;
+
+use crate::allowlist::LayeredAllowlist;
+use crate::config::Config;
+use crate::evaluator::evaluate_command_with_pack_order;
+use crate::history::{CommandEntry, Outcome, PackRecommendation, PotentialGap, RecommendationType};
+use crate::packs::{REGISTRY, Severity};
+
+/// Example commands kept per recommendation.
+const MAX_EXAMPLES: usize = 3;
+
+/// Longest example command shown in a description.
+const MAX_EXAMPLE_CHARS: usize = 60;
+
+/// Recommend disabled packs that would have blocked allowed commands.
+///
+/// Only `allow` entries are considered. Packs that would have blocked fewer
+/// than `min_blocks` commands are left out. Recommendations are ordered by
+/// priority (worst severity that would have matched), then by count.
+#[must_use]
+pub fn recommend_packs(
+    entries: &[CommandEntry],
+    config: &Config,
+    allowlists: &LayeredAllowlist,
+    min_blocks: u64,
+) -> Vec<PackRecommendation> {
+    let enabled = config.enabled_pack_ids();
+    let active: HashSet<String> = REGISTRY
+        .expand_enabled_ordered(&enabled)
+        .into_iter()
+        .collect();
+    let allowed: Vec<&CommandEntry> = entries
+        .iter()
+        .filter(|entry| entry.outcome == Outcome::Allow)
+        .collect();
+    let compiled_overrides = config.compile_overrides();
+    let heredoc_settings = config.heredoc_settings();
+
+    let mut recommendations = Vec::new();
+    for pack_id in REGISTRY.all_pack_ids() {
+        if active.contains(pack_id) {
+            continue;
+        }
+        let Some(pack) = REGISTRY.get(pack_id) else {
+            continue;
+        };
+        let mentions: Vec<&CommandEntry> = allowed
+            .iter()
+            .copied()
+            .filter(|entry| pack.might_match(&entry.command))
+            .collect();
+        if mentions.is_empty() {
+            continue;
+        }
+
+        let mut with_pack = enabled.clone();
+        with_pack.insert(pack_id.to_string());
+        let ordered_packs = REGISTRY.expand_enabled_ordered(&with_pack);
+        let keywords = REGISTRY.collect_enabled_keywords(&with_pack);
+
+        let mut would_block = 0u64;
+        let mut priority = 0u8;
+        let mut examples = Vec::new();
+        for entry in &mentions {
+            let result = evaluate_command_with_pack_order(
+                &entry.command,
+                &keywords,
+                &ordered_packs,
+                None,
+                &compiled_overrides,
+                allowlists,
+                &heredoc_settings,
+            );
+            if !result.is_denied() {
+                continue;
+            }
+            let Some(info) = result.pattern_info else {
+                continue;
+            };
+            if info.pack_id.as_deref() != Some(pack_id) {
+                continue;
+            }
+            would_block += 1;
+            priority = priority.max(severity_priority(info.severity));
+            if examples.len() < MAX_EXAMPLES {
+                examples.push(PotentialGap {
+                    command: entry.command.clone(),
+                    reason: info.reason,
+                    timestamp: entry.timestamp,
+                    working_dir: Some(entry.working_dir.clone()).filter(|dir| !dir.is_empty()),
+                    pack_id: Some(pack_id.to_string()),
+                    pattern_name: info.pattern_name,
+                });
+            }
+        }
+        if would_block == 0 || would_block < min_blocks {
+            continue;
+        }
+
+        let mentioned = u64::try_from(mentions.len()).unwrap_or(u64::MAX);
+        recommendations.push(PackRecommendation {
+            recommendation_type: RecommendationType::EnablePack,
+            description: format!(
+                "Pack '{pack_id}' ({}) would have blocked {would_block} of {mentioned} allowed \
+                 commands that mention its keywords. Example: '{}'",
+                pack.name,
+                truncate_chars(&examples[0].command, MAX_EXAMPLE_CHARS)
+            ),
+            suggested_action: Some(format!("dcg pack info {pack_id}")),
+            config_change: Some(format!("[packs]\nenabled = [\"{pack_id}\"]")),
+            related_pattern: Some(pack_id.to_string()),
+            priority,
+            keyword_matches: Some(mentioned),
+            would_block_count: Some(would_block),
+            examples,
+        });
+    }
+
+    recommendations.sort_by(|a, b| {
+        b.priority
+            .cmp(&a.priority)
+            .then(b.would_block_count.cmp(&a.would_block_count))
+            .then(a.related_pattern.cmp(&b.related_pattern))
+    });
+    recommendations
+}
+
+/// Priority of an `EnablePack` recommendation by the worst severity it would
+/// have blocked.
+const fn severity_priority(severity: Option<Severity>) -> u8 {
+    match severity {
+        Some(Severity::Critical) => 9,
+        Some(Severity::High) | None => 7,
+        Some(Severity::Medium | Severity::Low) => 5,
+    }
+}
+
+fn truncate_chars(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let truncated: String = text.chars().take(max).collect();
+    format!("{truncated}...")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allowed(command: &str) -> CommandEntry {
+        CommandEntry {
+            command: command.to_string(),
+            working_dir: "/srv/app".to_string(),
+            outcome: Outcome::Allow,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn recommends_packs_that_would_have_blocked() {
+        let config = Config::default();
+        let allowlists = LayeredAllowlist::default();
+        let entries = vec![
+            allowed("kubectl delete namespace prod"),
+            allowed("kubectl get pods"),
+            allowed("git status"),
+            CommandEntry {
+                outcome: Outcome::Deny,
+                ..allowed("kubectl delete namespace staging")
+            },
+        ];
+
+        let recommendations = recommend_packs(&entries, &config, &allowlists, 1);
+        let kubectl = recommendations
+            .iter()
+            .find(|r| r.related_pattern.as_deref() == Some("kubernetes.kubectl"))
+            .expect("kubectl pack recommended");
+        assert!(matches!(
+            kubectl.recommendation_type,
+            RecommendationType::EnablePack
+        ));
+        assert_eq!(kubectl.keyword_matches, Some(2));
+        assert_eq!(kubectl.would_block_count, Some(1));
+        assert_eq!(kubectl.examples.len(), 1);
+        assert_eq!(kubectl.examples[0].command, "kubectl delete namespace prod");
+        assert_eq!(
+            kubectl.examples[0].pack_id.as_deref(),
+            Some("kubernetes.kubectl")
+        );
+        assert!(kubectl.examples[0].pattern_name.is_some());
+        assert!(
+            recommendations
+                .iter()
+                .all(|r| r.related_pattern.as_deref() != Some("core.git"))
+        );
+    }
+
+    #[test]
+    fn keyword_mentions_alone_are_not_recommended() {
+        let config = Config::default();
+        let allowlists = LayeredAllowlist::default();
+        let entries = vec![allowed("kubectl get pods"), allowed("kubectl logs web")];
+        assert!(recommend_packs(&entries, &config, &allowlists, 1).is_empty());
+
+        let entries = vec![allowed("kubectl delete namespace prod")];
+        assert!(recommend_packs(&entries, &config, &allowlists, 2).is_empty());
+    }
+}
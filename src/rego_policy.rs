@@ -0,0 +1,351 @@
+//! Embedded Rego policies (`[policy.rego]`).
+//!
+//! Admins who already write OPA policy can decide dcg's mode for a match in
+//! Rego instead of TOML. When dcg is built with the `rego` feature and
+//! `[policy.rego]` is enabled, the hook loads the policy files into a
+//! `regorus` engine and, for each pack match, evaluates `query` (default
+//! `data.dcg.decision`) with the match as `input`:
+//!
+//! ```json
+//! {
+//!   "command": "terraform destroy -target=module.cache",
+//!   "cwd": "/srv/infra",
+//!   "pack_id": "infrastructure.terraform",
+//!   "pattern_name": "destroy",
+//!   "rule_id": "infrastructure.terraform:destroy",
+//!   "severity": "high",
+//!   "reason": "terraform destroy removes managed infrastructure",
+//!   "mode": "deny",
+//!   "source": "pack",
+//!   "env": { "CI": "true" }
+//! }
+//! ```
+//!
+//! A policy such as
+//!
+//! ```rego
+//! package dcg
+//!
+//! import rego.v1
+//!
+//! decision := {"decision": "warn", "reason": "staging stack"} if {
+//!     input.pack_id == "infrastructure.terraform"
+//!     startswith(input.cwd, "/srv/staging")
+//! }
+//! ```
+//!
+//! replaces the built-in mode; when the rule is undefined the built-in mode
+//! stands. Results use the same shapes as `[policy.external]` responses.
+//! Policy errors are reported on stderr and never change a decision.
+//!
+//! Without the `rego` feature, [`RegoPolicy::load`] always returns `None`.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::external_policy::{ExternalDecision, decision_from_json};
+use crate::packs::{DecisionMode, Severity};
+
+#[cfg(feature = "rego")]
+pub use enabled::RegoPolicy;
+
+#[cfg(not(feature = "rego"))]
+pub use disabled::RegoPolicy;
+
+/// The match a policy decides on (Rego `input`).
+#[derive(Debug, Clone, Serialize)]
+pub struct RegoInput {
+    /// Command text as evaluated.
+    pub command: String,
+    /// Working directory of the hook.
+    pub cwd: String,
+    /// Pack that matched.
+    pub pack_id: Option<String>,
+    /// Pattern that matched.
+    pub pattern_name: Option<String>,
+    /// `pack_id:pattern_name`, when both are known.
+    pub rule_id: Option<String>,
+    /// Effective severity label.
+    pub severity: String,
+    /// Why the rule matched.
+    pub reason: String,
+    /// Mode from the built-in resolution (`deny`, `warn`, `log`).
+    pub mode: String,
+    /// `pack` or `heredoc`.
+    pub source: String,
+    /// Environment of the hook process.
+    pub env: BTreeMap<String, String>,
+}
+
+impl RegoInput {
+    /// Build the input for a match, capturing the current environment.
+    #[allow(clippy::too_many_arguments)]
+    #[must_use]
+    pub fn new(
+        command: &str,
+        cwd: &str,
+        pack_id: Option<&str>,
+        pattern_name: Option<&str>,
+        severity: Option<Severity>,
+        reason: &str,
+        mode: DecisionMode,
+        heredoc: bool,
+    ) -> Self {
+        Self {
+            command: command.to_string(),
+            cwd: cwd.to_string(),
+            pack_id: pack_id.map(str::to_string),
+            pattern_name: pattern_name.map(str::to_string),
+            rule_id: pack_id
+                .zip(pattern_name)
+                .map(|(pack, pattern)| format!("{pack}:{pattern}")),
+            severity: severity.unwrap_or(Severity::High).label().to_string(),
+            reason: reason.to_string(),
+            mode: crate::otel::decision_label(mode).to_string(),
+            source: if heredoc { "heredoc" } else { "pack" }.to_string(),
+            env: std::env::vars().collect(),
+        }
+    }
+}
+
+/// A decision made by a Rego policy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegoDecision {
+    /// Mode that replaces the built-in one.
+    pub mode: DecisionMode,
+    /// Reason given by the policy, if any.
+    pub reason: Option<String>,
+}
+
+impl RegoDecision {
+    /// Notice appended to the deny or warn message, if the policy gave a reason.
+    #[must_use]
+    pub fn notice(&self) -> Option<String> {
+        self.reason
+            .as_ref()
+            .map(|reason| format!("Rego policy: {reason}"))
+    }
+}
+
+/// Convert a query result (as JSON) into a decision; `None` keeps the
+/// built-in mode.
+#[cfg_attr(not(feature = "rego"), allow(dead_code))]
+fn decision_from_result(value: &serde_json::Value) -> Result<Option<RegoDecision>, String> {
+    match decision_from_json(value)? {
+        ExternalDecision::Decided { mode, reason } => Ok(Some(RegoDecision { mode, reason })),
+        ExternalDecision::Deferred | ExternalDecision::Failed { .. } => Ok(None),
+    }
+}
+
+#[cfg(feature = "rego")]
+mod enabled {
+    use std::path::Path;
+
+    use super::{RegoDecision, RegoInput, decision_from_result};
+    use crate::config::RegoPolicyConfig;
+
+    /// Loaded policy files and the rule to query.
+    pub struct RegoPolicy {
+        engine: regorus::Engine,
+        query: String,
+    }
+
+    impl RegoPolicy {
+        /// Load the configured policy files, or `None` when `[policy.rego]`
+        /// is disabled or no policy file exists. A file that fails to parse
+        /// is reported on stderr and the policy is not used.
+        #[must_use]
+        pub fn load(config: &RegoPolicyConfig, cwd: Option<&Path>) -> Option<Self> {
+            if !config.enabled {
+                return None;
+            }
+            let files = config.policy_files(cwd);
+            if files.is_empty() {
+                return None;
+            }
+            let mut engine = regorus::Engine::new();
+            for file in &files {
+                if let Err(e) = engine.add_policy_from_file(file) {
+                    eprintln!(
+                        "[dcg] Warning: ignoring [policy.rego]: {} failed to load: {e}",
+                        file.display()
+                    );
+                    return None;
+                }
+            }
+            Some(Self {
+                engine,
+                query: config.query.trim().to_string(),
+            })
+        }
+
+        /// Load policy source directly instead of from files.
+        ///
+        /// # Errors
+        ///
+        /// Returns the parser error if `source` is not valid Rego.
+        pub fn from_source(name: &str, source: &str, query: &str) -> Result<Self, String> {
+            let mut engine = regorus::Engine::new();
+            engine
+                .add_policy(name.to_string(), source.to_string())
+                .map_err(|e| e.to_string())?;
+            Ok(Self {
+                engine,
+                query: query.to_string(),
+            })
+        }
+
+        /// Evaluate the query for one match.
+        ///
+        /// # Errors
+        ///
+        /// Returns an error if evaluation fails or the result is not a
+        /// recognized decision.
+        pub fn evaluate(&mut self, input: &RegoInput) -> Result<Option<RegoDecision>, String> {
+            let input = serde_json::to_string(input).map_err(|e| e.to_string())?;
+            self.engine
+                .set_input(regorus::Value::from_json_str(&input).map_err(|e| e.to_string())?);
+            let value = self
+                .engine
+                .eval_rule(self.query.clone())
+                .map_err(|e| e.to_string())?;
+            if value == regorus::Value::Undefined {
+                return Ok(None);
+            }
+            let json: serde_json::Value =
+                serde_json::from_str(&value.to_json_str().map_err(|e| e.to_string())?)
+                    .map_err(|e| e.to_string())?;
+            decision_from_result(&json).map_err(|e| format!("{}: {e}", self.query))
+        }
+    }
+}
+
+#[cfg(not(feature = "rego"))]
+mod disabled {
+    use std::path::Path;
+
+    use super::{RegoDecision, RegoInput};
+    use crate::config::RegoPolicyConfig;
+
+    /// Placeholder policy for builds without the `rego` feature.
+    pub struct RegoPolicy {
+        _private: (),
+    }
+
+    impl RegoPolicy {
+        /// Always `None`: this build has no Rego support. Warns on stderr when
+        /// `[policy.rego]` is enabled so the setting is not silently ignored.
+        #[must_use]
+        pub fn load(config: &RegoPolicyConfig, _cwd: Option<&Path>) -> Option<Self> {
+            if config.enabled {
+                eprintln!(
+                    "[dcg] Warning: [policy.rego] is enabled but this build lacks the `rego` feature"
+                );
+            }
+            None
+        }
+
+        /// No-op.
+        ///
+        /// # Errors
+        ///
+        /// Never fails.
+        pub fn evaluate(&mut self, _input: &RegoInput) -> Result<Option<RegoDecision>, String> {
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RegoPolicyConfig;
+
+    fn input(pack_id: &str, cwd: &str) -> RegoInput {
+        RegoInput::new(
+            "terraform destroy",
+            cwd,
+            Some(pack_id),
+            Some("destroy"),
+            Some(Severity::High),
+            "terraform destroy removes managed infrastructure",
+            DecisionMode::Deny,
+            false,
+        )
+    }
+
+    #[test]
+    fn input_carries_rule_and_mode() {
+        let input = input("infrastructure.terraform", "/srv/infra");
+        assert_eq!(
+            input.rule_id.as_deref(),
+            Some("infrastructure.terraform:destroy")
+        );
+        assert_eq!(input.mode, "deny");
+        assert_eq!(input.severity, "high");
+        assert_eq!(input.source, "pack");
+    }
+
+    #[test]
+    fn result_shapes_map_to_decisions() {
+        assert_eq!(
+            decision_from_result(&serde_json::json!("warn")),
+            Ok(Some(RegoDecision {
+                mode: DecisionMode::Warn,
+                reason: None,
+            }))
+        );
+        assert_eq!(
+            decision_from_result(&serde_json::json!({"decision": "log", "reason": "sandbox"})),
+            Ok(Some(RegoDecision {
+                mode: DecisionMode::Log,
+                reason: Some("sandbox".to_string()),
+            }))
+        );
+        assert_eq!(decision_from_result(&serde_json::json!("defer")), Ok(None));
+        assert!(decision_from_result(&serde_json::json!(42)).is_err());
+    }
+
+    #[test]
+    fn disabled_config_loads_nothing() {
+        assert!(RegoPolicy::load(&RegoPolicyConfig::default(), None).is_none());
+    }
+
+    #[cfg(feature = "rego")]
+    #[test]
+    fn policy_overrides_matching_rules_only() {
+        let mut policy = RegoPolicy::from_source(
+            "policy.rego",
+            r#"
+            package dcg
+
+            import rego.v1
+
+            decision := {"decision": "warn", "reason": "staging stack"} if {
+                input.pack_id == "infrastructure.terraform"
+                startswith(input.cwd, "/srv/staging")
+            }
+            "#,
+            RegoPolicyConfig::DEFAULT_QUERY,
+        )
+        .expect("valid policy");
+
+        let staging = policy
+            .evaluate(&input("infrastructure.terraform", "/srv/staging/app"))
+            .expect("evaluates");
+        assert_eq!(
+            staging,
+            Some(RegoDecision {
+                mode: DecisionMode::Warn,
+                reason: Some("staging stack".to_string()),
+            })
+        );
+        assert_eq!(
+            policy
+                .evaluate(&input("infrastructure.terraform", "/srv/prod"))
+                .expect("evaluates"),
+            None
+        );
+    }
+}
@@ -0,0 +1,223 @@
+//! Config hot-reload for long-running modes.
+//!
+//! `dcg mcp-server` compiles its evaluation state (overrides, pack order,
+//! keyword index, allowlists) once and keeps it for the life of the process.
+//! [`LiveConfig`] holds that state as an `Arc<EvalSnapshot>` that is swapped
+//! atomically when the config or allowlist files change: in-flight checks
+//! finish against the snapshot they started with, and new checks see the new
+//! one.
+//!
+//! Changes are picked up by a `notify` watcher on the directories holding the
+//! config layers and allowlists, or on demand with `dcg reload`, which touches
+//! a trigger file in the dcg config directory.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, PoisonError, RwLock, mpsc};
+use std::time::Duration;
+
+use notify::{RecursiveMode, Watcher};
+
+use crate::config::Config;
+use crate::scan::ScanEvalContext;
+
+/// File in the dcg config directory touched by `dcg reload`.
+const RELOAD_TRIGGER_FILE: &str = "reload";
+
+/// Quiet period before rebuilding, so an editor's write/rename burst triggers
+/// a single reload.
+const DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// Evaluation state compiled from one config load.
+#[derive(Debug)]
+pub struct EvalSnapshot {
+    /// The merged configuration.
+    pub config: Config,
+    /// Compiled overrides, pack order, keyword index, and allowlists.
+    pub ctx: ScanEvalContext,
+    /// 0 for the initial load, incremented on every reload.
+    pub generation: u64,
+    /// Config layer files that contributed to `config`.
+    config_files: Vec<PathBuf>,
+}
+
+impl EvalSnapshot {
+    fn load(generation: u64) -> Self {
+        let (config, sources) = Config::load_with_sources();
+        let ctx = ScanEvalContext::from_config(&config);
+        let config_files = sources.into_iter().filter_map(|s| s.path).collect();
+        Self {
+            config,
+            ctx,
+            generation,
+            config_files,
+        }
+    }
+
+    /// Files whose changes trigger a reload: every contributing config layer,
+    /// the user config path (even if it does not exist yet), and every
+    /// allowlist layer.
+    #[must_use]
+    pub fn watched_files(&self) -> Vec<PathBuf> {
+        let mut files = self.config_files.clone();
+        files.extend(Config::user_config_path());
+        files.extend(self.ctx.allowlists.layers.iter().map(|l| l.path.clone()));
+        files.extend(reload_trigger_path());
+        files.sort();
+        files.dedup();
+        files
+    }
+}
+
+/// Atomically swappable evaluation state.
+#[derive(Debug)]
+pub struct LiveConfig {
+    current: RwLock<Arc<EvalSnapshot>>,
+    generation: AtomicU64,
+}
+
+impl LiveConfig {
+    /// Load the current configuration.
+    #[must_use]
+    pub fn load() -> Self {
+        Self {
+            current: RwLock::new(Arc::new(EvalSnapshot::load(0))),
+            generation: AtomicU64::new(0),
+        }
+    }
+
+    /// The snapshot new evaluations should use.
+    #[must_use]
+    pub fn snapshot(&self) -> Arc<EvalSnapshot> {
+        Arc::clone(&self.current.read().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    /// Re-read config and allowlists and swap in the result.
+    ///
+    /// The new snapshot is built before the write lock is taken, so readers
+    /// are never blocked on config parsing.
+    pub fn reload(&self) -> Arc<EvalSnapshot> {
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let snapshot = Arc::new(EvalSnapshot::load(generation));
+        *self.current.write().unwrap_or_else(PoisonError::into_inner) = Arc::clone(&snapshot);
+        snapshot
+    }
+
+    /// Start watching the config and allowlist files, reloading on change.
+    ///
+    /// Watching stops when the returned [`ConfigWatcher`] is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the watcher or its reload thread cannot be started.
+    pub fn watch(self: &Arc<Self>) -> notify::Result<ConfigWatcher> {
+        let files: HashSet<PathBuf> = self.snapshot().watched_files().into_iter().collect();
+        if let Some(dir) = reload_trigger_path().as_deref().and_then(Path::parent) {
+            // Make sure `dcg reload` has a directory to signal through.
+            let _ = std::fs::create_dir_all(dir);
+        }
+        let dirs: HashSet<PathBuf> = files
+            .iter()
+            .filter_map(|path| path.parent())
+            .filter(|dir| dir.is_dir())
+            .map(Path::to_path_buf)
+            .collect();
+
+        let (tx, rx) = mpsc::channel::<()>();
+        let mut watcher =
+            notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+                let relevant =
+                    res.is_ok_and(|event| event.paths.iter().any(|path| files.contains(path)));
+                if relevant {
+                    let _ = tx.send(());
+                }
+            })?;
+        for dir in &dirs {
+            watcher.watch(dir, RecursiveMode::NonRecursive)?;
+        }
+
+        let live = Arc::clone(self);
+        std::thread::Builder::new()
+            .name("dcg-config-reload".to_string())
+            .spawn(move || {
+                // Exits when the watcher (and with it the sender) is dropped.
+                while rx.recv().is_ok() {
+                    while rx.recv_timeout(DEBOUNCE).is_ok() {}
+                    let snapshot = live.reload();
+                    tracing::info!(generation = snapshot.generation, "configuration reloaded");
+                }
+            })?;
+
+        Ok(ConfigWatcher { _watcher: watcher })
+    }
+}
+
+/// Keeps a [`LiveConfig::watch`] watcher alive.
+pub struct ConfigWatcher {
+    _watcher: notify::RecommendedWatcher,
+}
+
+impl std::fmt::Debug for ConfigWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConfigWatcher").finish_non_exhaustive()
+    }
+}
+
+/// Path of the file `dcg reload` touches.
+#[must_use]
+pub fn reload_trigger_path() -> Option<PathBuf> {
+    Config::user_config_path()
+        .as_deref()
+        .and_then(Path::parent)
+        .map(|dir| dir.join(RELOAD_TRIGGER_FILE))
+}
+
+/// Ask running dcg servers to reload by touching the trigger file.
+///
+/// # Errors
+///
+/// Returns an error if the config directory cannot be determined or written.
+pub fn request_reload() -> std::io::Result<PathBuf> {
+    let path = reload_trigger_path().ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "could not determine the dcg config directory",
+        )
+    })?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, chrono::Utc::now().to_rfc3339())?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reload_swaps_snapshot_and_bumps_generation() {
+        let live = LiveConfig::load();
+        let before = live.snapshot();
+        assert_eq!(before.generation, 0);
+
+        let after = live.reload();
+        assert_eq!(after.generation, 1);
+        assert_eq!(live.snapshot().generation, 1);
+        // Holders of the old snapshot keep a consistent view.
+        assert_eq!(before.generation, 0);
+    }
+
+    #[test]
+    fn watched_files_include_allowlists_and_trigger() {
+        let snapshot = LiveConfig::load().snapshot();
+        let files = snapshot.watched_files();
+        for layer in &snapshot.ctx.allowlists.layers {
+            assert!(files.contains(&layer.path));
+        }
+        if let Some(trigger) = reload_trigger_path() {
+            assert!(files.contains(&trigger));
+        }
+    }
+}
@@ -0,0 +1,513 @@
+//! Central policy sync (`dcg sync`, `[remote]`).
+//!
+//! A team publishes one signed TOML bundle that carries fleet-wide policy:
+//!
+//! ```toml
+//! [bundle]
+//! format = 1
+//! name = "acme-engineering"
+//!
+//! [packs]
+//! enabled = ["database.postgresql", "kubernetes.kubectl"]
+//!
+//! [overrides]
+//! block = [{ pattern = "terraform destroy", reason = "use the release pipeline" }]
+//!
+//! [[allow]]
+//! rule = "core.git:reset-hard"
+//! reason = "CI cleans checkouts"
+//!
+//! [custom_packs]
+//! "acme.deploy.yaml" = '''
+//! id: acme.deploy
+//! ...
+//! '''
+//! ```
+//!
+//! `dcg sync` downloads it over HTTPS (with `If-None-Match` so unchanged
+//! bundles cost a 304), verifies the detached Ed25519 signature against
+//! `[remote] public_key`, validates every section, and only then replaces the
+//! cached copy. Nothing in a bundle that fails any check is applied.
+//!
+//! The cache lives in `<cache dir>/dcg/remote` (`DCG_REMOTE_DIR` overrides it):
+//! `policy.toml` and its `.sig`, `meta.json` (URL, ETag, timestamps), and
+//! `packs/*.yaml` for custom packs. The hook never fetches; when the cache is
+//! older than `interval_secs` it spawns a detached `dcg sync --quiet`.
+
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::Utc;
+use fs2::FileExt;
+use serde::{Deserialize, Serialize};
+
+use crate::allowlist::{AllowlistLayer, parse_allowlist_toml};
+use crate::config::{OverridesConfig, PacksConfig, RemoteConfig};
+
+/// Environment variable overriding the cache directory.
+pub const ENV_REMOTE_DIR: &str = "DCG_REMOTE_DIR";
+
+/// Policy bundle format version accepted in `[bundle] format`.
+pub const POLICY_FORMAT_VERSION: i64 = 1;
+
+/// Cached bundle file name.
+pub const POLICY_FILE: &str = "policy.toml";
+
+const SIGNATURE_FILE: &str = "policy.toml.sig";
+const META_FILE: &str = "meta.json";
+const PACKS_DIR: &str = "packs";
+const SYNC_LOCK_FILE: &str = "sync.lock";
+
+/// What `dcg sync` knows about the cached bundle.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncMeta {
+    /// URL the cached bundle was fetched from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// `ETag` returned with the cached bundle.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    /// `[bundle] name` of the cached bundle.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Unix time the bundle was last confirmed current (200 or 304).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fetched_at: Option<i64>,
+    /// Unix time of the last sync attempt, successful or not.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_attempt: Option<i64>,
+}
+
+impl SyncMeta {
+    fn load(dir: &Path) -> Self {
+        fs::read_to_string(dir.join(META_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, dir: &Path) -> io::Result<()> {
+        let content = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(dir.join(META_FILE), content)
+    }
+
+    /// Whether the bundle should be refreshed, counting failed attempts so an
+    /// unreachable server is not retried on every hook invocation.
+    fn is_stale(&self, interval_secs: u64, now: i64) -> bool {
+        let last = self.fetched_at.max(self.last_attempt);
+        last.is_none_or(|last| {
+            now.saturating_sub(last) >= i64::try_from(interval_secs).unwrap_or(i64::MAX)
+        })
+    }
+}
+
+/// Result of a successful `dcg sync`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum SyncOutcome {
+    /// A new bundle was verified and installed.
+    Updated {
+        /// `[bundle] name`, if set.
+        name: Option<String>,
+        /// Number of `[[allow]]` entries.
+        allow_entries: usize,
+        /// Number of custom packs installed.
+        custom_packs: usize,
+    },
+    /// The server reported the cached bundle is current (HTTP 304).
+    NotModified,
+}
+
+/// Sections of a policy bundle that dcg applies (`[[allow]]` is parsed by the
+/// allowlist loader).
+#[derive(Debug, Deserialize)]
+struct PolicyBundle {
+    bundle: BundleHeader,
+    packs: Option<PacksConfig>,
+    overrides: Option<OverridesConfig>,
+    #[serde(default)]
+    custom_packs: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BundleHeader {
+    format: i64,
+    name: Option<String>,
+}
+
+/// A bundle that passed validation.
+#[derive(Debug)]
+struct ValidatedBundle {
+    name: Option<String>,
+    allow_entries: usize,
+    custom_packs: BTreeMap<String, String>,
+}
+
+/// Resolve the cache directory: `DCG_REMOTE_DIR`, then
+/// `<cache dir>/dcg/remote`.
+#[must_use]
+pub fn remote_dir() -> Option<PathBuf> {
+    if let Ok(dir) = std::env::var(ENV_REMOTE_DIR) {
+        return Some(PathBuf::from(dir));
+    }
+    dirs::cache_dir().map(|dir| dir.join("dcg").join("remote"))
+}
+
+/// Path of the cached bundle, if one has been synced.
+#[must_use]
+pub fn cached_policy_path() -> Option<PathBuf> {
+    remote_dir()
+        .map(|dir| dir.join(POLICY_FILE))
+        .filter(|path| path.is_file())
+}
+
+/// `packs.custom_paths` glob for the cached custom packs, if any exist.
+#[must_use]
+pub fn custom_packs_glob() -> Option<String> {
+    let dir = remote_dir()?.join(PACKS_DIR);
+    dir.is_dir()
+        .then(|| dir.join("*.yaml").to_string_lossy().into_owned())
+}
+
+/// Metadata for the cached bundle (for `dcg sync --status`).
+#[must_use]
+pub fn status() -> Option<SyncMeta> {
+    remote_dir().map(|dir| SyncMeta::load(&dir))
+}
+
+/// Fetch, verify, and install the bundle described by `config`.
+///
+/// # Errors
+///
+/// Returns a description of the failure when the configuration is
+/// incomplete, another sync holds the lock, the download fails, the
+/// signature does not verify, or the bundle is invalid. The cached bundle is
+/// left untouched in every error case.
+pub fn sync(config: &RemoteConfig) -> Result<SyncOutcome, String> {
+    let url = config
+        .url
+        .as_deref()
+        .ok_or("remote.url is not set")?
+        .to_string();
+    let signature_url = config.signature_url().unwrap_or_default();
+    for candidate in [&url, &signature_url] {
+        if !candidate.starts_with("https://") {
+            return Err(format!("{candidate} is not an https:// URL"));
+        }
+    }
+    let key = crate::allowlist_bundle::parse_public_key(
+        config
+            .public_key
+            .as_deref()
+            .ok_or("remote.public_key is not set")?,
+    )?;
+
+    let dir = remote_dir().ok_or("no cache directory for the remote policy")?;
+    fs::create_dir_all(&dir).map_err(|e| format!("creating {}: {e}", dir.display()))?;
+    let lock = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(dir.join(SYNC_LOCK_FILE))
+        .map_err(|e| e.to_string())?;
+    if lock.try_lock_exclusive().is_err() {
+        return Err("another dcg sync is already running".to_string());
+    }
+
+    // Record the attempt up front so a failing server is retried once per
+    // interval, not on every hook invocation.
+    let now = Utc::now().timestamp();
+    let mut meta = SyncMeta::load(&dir);
+    meta.last_attempt = Some(now);
+    let _ = meta.save(&dir);
+
+    let timeout = Duration::from_millis(config.timeout_ms.max(1));
+    let etag = meta
+        .etag
+        .clone()
+        .filter(|_| meta.url.as_deref() == Some(url.as_str()) && dir.join(POLICY_FILE).is_file());
+    let mut headers = vec![("Accept", "application/toml, text/plain")];
+    if let Some(etag) = etag.as_deref() {
+        headers.push(("If-None-Match", etag));
+    }
+    let response = crate::http::request("GET", &url, &headers, None, timeout)
+        .map_err(|e| format!("fetching {url}: {e}"))?;
+    if response.status == 304 {
+        meta.fetched_at = Some(now);
+        let _ = meta.save(&dir);
+        let _ = FileExt::unlock(&lock);
+        return Ok(SyncOutcome::NotModified);
+    }
+    if !response.is_success() {
+        return Err(format!("{url} answered HTTP {}", response.status));
+    }
+    let content =
+        String::from_utf8(response.body.clone()).map_err(|_| "policy bundle is not UTF-8")?;
+
+    let signature = crate::http::request("GET", &signature_url, &[], None, timeout)
+        .map_err(|e| format!("fetching {signature_url}: {e}"))?;
+    if !signature.is_success() {
+        return Err(format!(
+            "{signature_url} answered HTTP {}",
+            signature.status
+        ));
+    }
+    let signature = String::from_utf8_lossy(&signature.body).trim().to_string();
+    crate::allowlist_bundle::verify(content.as_bytes(), &signature, &key)
+        .map_err(|_| "policy bundle signature does not verify against remote.public_key")?;
+
+    let bundle = validate_bundle(&content)?;
+    install(&dir, &content, &signature, &bundle.custom_packs)
+        .map_err(|e| format!("installing policy bundle: {e}"))?;
+
+    meta = SyncMeta {
+        url: Some(url),
+        etag: response.header("etag").map(str::to_string),
+        name: bundle.name.clone(),
+        fetched_at: Some(now),
+        last_attempt: Some(now),
+    };
+    let _ = meta.save(&dir);
+    let _ = FileExt::unlock(&lock);
+    Ok(SyncOutcome::Updated {
+        name: bundle.name,
+        allow_entries: bundle.allow_entries,
+        custom_packs: bundle.custom_packs.len(),
+    })
+}
+
+/// Start a detached `dcg sync --quiet` when sync is configured, the cache is
+/// older than `interval_secs`, and no sync is running. Never blocks.
+pub fn spawn_sync_if_stale(config: &RemoteConfig) {
+    if !config.is_active() {
+        return;
+    }
+    let Some(dir) = remote_dir() else {
+        return;
+    };
+    if !SyncMeta::load(&dir).is_stale(config.interval_secs, Utc::now().timestamp())
+        || sync_running(&dir)
+    {
+        return;
+    }
+    let Ok(exe) = std::env::current_exe() else {
+        return;
+    };
+    let _ = std::process::Command::new(exe)
+        .args(["sync", "--quiet"])
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn();
+}
+
+fn sync_running(dir: &Path) -> bool {
+    let Ok(lock) = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(dir.join(SYNC_LOCK_FILE))
+    else {
+        return false;
+    };
+    if lock.try_lock_exclusive().is_err() {
+        return true;
+    }
+    let _ = FileExt::unlock(&lock);
+    false
+}
+
+/// Check every section of a bundle; any problem rejects the whole bundle.
+fn validate_bundle(content: &str) -> Result<ValidatedBundle, String> {
+    let bundle: PolicyBundle =
+        toml::from_str(content).map_err(|e| format!("invalid policy bundle: {e}"))?;
+    if bundle.bundle.format != POLICY_FORMAT_VERSION {
+        return Err(format!(
+            "unsupported policy bundle format {} (expected {POLICY_FORMAT_VERSION})",
+            bundle.bundle.format
+        ));
+    }
+    if bundle
+        .packs
+        .as_ref()
+        .is_some_and(|packs| !packs.custom_paths.is_empty())
+    {
+        return Err(
+            "policy bundles cannot set packs.custom_paths; ship packs under [custom_packs]"
+                .to_string(),
+        );
+    }
+
+    if let Some(invalid) = bundle
+        .overrides
+        .as_ref()
+        .and_then(|overrides| overrides.compile().invalid_patterns.into_iter().next())
+    {
+        return Err(format!(
+            "invalid override pattern {:?}: {}",
+            invalid.pattern, invalid.error
+        ));
+    }
+
+    let allowlist = parse_allowlist_toml(AllowlistLayer::Remote, Path::new(POLICY_FILE), content);
+    if let Some(error) = allowlist.errors.first() {
+        return Err(format!("invalid [[allow]] entry: {}", error.message));
+    }
+
+    for (name, yaml) in &bundle.custom_packs {
+        if !is_valid_pack_file_name(name) {
+            return Err(format!(
+                "invalid custom pack name {name:?} (use letters, digits, '.', '_', '-' and a .yaml suffix)"
+            ));
+        }
+        crate::packs::external::parse_pack_string_checked(yaml)
+            .map_err(|e| format!("custom pack {name}: {e}"))?;
+    }
+
+    Ok(ValidatedBundle {
+        name: bundle.bundle.name,
+        allow_entries: allowlist.entries.len(),
+        custom_packs: bundle.custom_packs,
+    })
+}
+
+/// Custom pack names become file names, so keep them to a safe alphabet.
+fn is_valid_pack_file_name(name: &str) -> bool {
+    name.len() > ".yaml".len()
+        && Path::new(name).extension().is_some_and(|ext| ext == "yaml")
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+}
+
+/// Replace the cached bundle and custom packs.
+fn install(
+    dir: &Path,
+    content: &str,
+    signature: &str,
+    custom_packs: &BTreeMap<String, String>,
+) -> io::Result<()> {
+    let packs_dir = dir.join(PACKS_DIR);
+    fs::create_dir_all(&packs_dir)?;
+    for entry in fs::read_dir(&packs_dir)?.flatten() {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if !custom_packs.contains_key(&name) {
+            fs::remove_file(entry.path())?;
+        }
+    }
+    for (name, yaml) in custom_packs {
+        write_atomic(&packs_dir.join(name), yaml)?;
+    }
+    write_atomic(&dir.join(SIGNATURE_FILE), signature)?;
+    write_atomic(&dir.join(POLICY_FILE), content)
+}
+
+/// Write `content` to `path` via a temporary sibling file and a rename.
+pub(crate) fn write_atomic(path: &Path, content: &str) -> io::Result<()> {
+    let mut tmp = path.as_os_str().to_os_string();
+    tmp.push(".tmp");
+    fs::write(&tmp, content)?;
+    fs::rename(&tmp, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUNDLE: &str = r#"
+[bundle]
+format = 1
+name = "acme"
+
+[packs]
+enabled = ["database.postgresql"]
+
+[overrides]
+block = [{ pattern = "terraform destroy", reason = "use the pipeline" }]
+
+[[allow]]
+rule = "core.git:reset-hard"
+reason = "CI cleans checkouts"
+"#;
+
+    #[test]
+    fn validates_bundles() {
+        let bundle = validate_bundle(BUNDLE).expect("valid bundle");
+        assert_eq!(bundle.name.as_deref(), Some("acme"));
+        assert_eq!(bundle.allow_entries, 1);
+        assert!(bundle.custom_packs.is_empty());
+
+        let wrong_format = BUNDLE.replace("format = 1", "format = 2");
+        assert!(validate_bundle(&wrong_format).is_err());
+
+        let local_paths = BUNDLE.replace(
+            "enabled = [\"database.postgresql\"]",
+            "custom_paths = [\"/tmp/*.yaml\"]",
+        );
+        assert!(validate_bundle(&local_paths).is_err());
+
+        let bad_regex = BUNDLE.replace("terraform destroy", "terraform (destroy");
+        assert!(validate_bundle(&bad_regex).is_err());
+
+        let bad_allow = format!("{BUNDLE}\n[[allow]]\nreason = \"no target\"\n");
+        assert!(validate_bundle(&bad_allow).is_err());
+
+        assert!(validate_bundle("[packs]\nenabled = []\n").is_err());
+    }
+
+    #[test]
+    fn validates_custom_pack_names() {
+        assert!(is_valid_pack_file_name("acme.deploy.yaml"));
+        assert!(is_valid_pack_file_name("team_rules-v2.yaml"));
+        assert!(!is_valid_pack_file_name("../escape.yaml"));
+        assert!(!is_valid_pack_file_name("dir/pack.yaml"));
+        assert!(!is_valid_pack_file_name(".yaml"));
+        assert!(!is_valid_pack_file_name(".hidden.yaml"));
+        assert!(!is_valid_pack_file_name("pack.toml"));
+
+        let bad_name = format!("{BUNDLE}\n[custom_packs]\n\"../x.yaml\" = \"id: x\"\n");
+        assert!(validate_bundle(&bad_name).is_err());
+    }
+
+    #[test]
+    fn staleness_counts_failed_attempts() {
+        assert!(SyncMeta::default().is_stale(3600, 10_000));
+
+        let fresh = SyncMeta {
+            fetched_at: Some(9_000),
+            ..SyncMeta::default()
+        };
+        assert!(!fresh.is_stale(3600, 10_000));
+        assert!(fresh.is_stale(3600, 12_600));
+
+        let failed_recently = SyncMeta {
+            fetched_at: Some(1_000),
+            last_attempt: Some(9_500),
+            ..SyncMeta::default()
+        };
+        assert!(!failed_recently.is_stale(3600, 10_000));
+    }
+
+    #[test]
+    fn install_replaces_custom_packs() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut packs = BTreeMap::new();
+        packs.insert("old.yaml".to_string(), "id: old".to_string());
+        install(dir.path(), BUNDLE, "sig", &packs).unwrap();
+        assert!(dir.path().join(PACKS_DIR).join("old.yaml").is_file());
+
+        packs.clear();
+        packs.insert("new.yaml".to_string(), "id: new".to_string());
+        install(dir.path(), BUNDLE, "sig", &packs).unwrap();
+        assert!(!dir.path().join(PACKS_DIR).join("old.yaml").exists());
+        assert!(dir.path().join(PACKS_DIR).join("new.yaml").is_file());
+        assert_eq!(
+            fs::read_to_string(dir.path().join(POLICY_FILE)).unwrap(),
+            BUNDLE
+        );
+    }
+}
@@ -0,0 +1,218 @@
+//! Remote execution awareness for `ssh` commands.
+//!
+//! `ssh prod-db1 "rm -rf /data"` deletes data on another machine. The
+//! evaluator already checks the remote command on its own (see
+//! [`crate::normalize::resolve_wrapper_chain`]); this module reports where a
+//! command executes and raises the match severity one level when a target
+//! host matches `[wrappers] production_hosts`:
+//!
+//! ```text
+//! production_hosts = ["prod-*", "*.prod.example.com"]
+//! ssh prod-db1 'dropdb app'      -> remote on prod-db1: High -> Critical
+//! ssh staging-db1 'dropdb app'   -> remote on staging-db1: unchanged
+//! dropdb app                     -> local: unchanged
+//! ```
+//!
+//! Hosts are compared case-insensitively after removing `user@` and, for
+//! `ssh://` destinations, the port.
+
+use crate::config::WrappersConfig;
+use crate::kube_context::ContextClass;
+use crate::packs::Severity;
+
+/// Where a command executes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExecutionTarget {
+    /// On this machine.
+    Local,
+    /// On other machines, through `ssh`, in the order they appear.
+    Remote {
+        /// Target hosts; nested hops are listed outermost first.
+        hosts: Vec<String>,
+    },
+}
+
+impl ExecutionTarget {
+    /// Determine the execution target of `command`.
+    ///
+    /// A command is remote when any of its segments runs a command over
+    /// `ssh`; data such as commit messages is ignored.
+    #[must_use]
+    pub fn of(command: &str, config: &WrappersConfig) -> Self {
+        if !config.ssh || !config.may_wrap(command) {
+            return Self::Local;
+        }
+        let sanitized = crate::context::sanitize_for_pattern_matching(command);
+        let mut hosts: Vec<String> = Vec::new();
+        for (_, segment) in crate::scripts::command_segments(&sanitized) {
+            let chain = crate::normalize::resolve_wrapper_chain(segment, config);
+            for host in chain.remote_hosts {
+                if !hosts.contains(&host) {
+                    hosts.push(host);
+                }
+            }
+        }
+        if hosts.is_empty() {
+            Self::Local
+        } else {
+            Self::Remote { hosts }
+        }
+    }
+
+    /// `"local"` or `"remote"`.
+    #[must_use]
+    pub const fn label(&self) -> &'static str {
+        match self {
+            Self::Local => "local",
+            Self::Remote { .. } => "remote",
+        }
+    }
+
+    /// Remote hosts (empty for local commands).
+    #[must_use]
+    pub fn hosts(&self) -> &[String] {
+        match self {
+            Self::Local => &[],
+            Self::Remote { hosts } => hosts,
+        }
+    }
+}
+
+/// A severity change caused by a production target host.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostEscalation {
+    /// The production host the command runs on.
+    pub host: String,
+    /// Severity reported by the pack.
+    pub from: Severity,
+    /// Severity after escalation.
+    pub to: Severity,
+}
+
+impl HostEscalation {
+    /// One-line explanation appended to denial messages.
+    #[must_use]
+    pub fn notice(&self) -> String {
+        format!(
+            "Remote host '{}' is production: severity {} -> {}.",
+            self.host,
+            self.from.label(),
+            self.to.label()
+        )
+    }
+}
+
+/// Raise `severity` one level when `command` runs on a production host.
+///
+/// Returns `None` when no `production_hosts` are configured, the command is
+/// local, no host matches, or the severity is already critical.
+#[must_use]
+pub fn escalate_severity(
+    config: &WrappersConfig,
+    command: &str,
+    severity: Option<Severity>,
+) -> Option<HostEscalation> {
+    if config.production_hosts.is_empty() {
+        return None;
+    }
+    let from = severity?;
+    let target = ExecutionTarget::of(command, config);
+    let host = target
+        .hosts()
+        .iter()
+        .find(|host| is_production_host(config, host))?;
+    let to = ContextClass::Production.adjust(from);
+    (to != from).then(|| HostEscalation {
+        host: host.clone(),
+        from,
+        to,
+    })
+}
+
+/// Whether `host` matches one of the `production_hosts` patterns.
+#[must_use]
+pub fn is_production_host(config: &WrappersConfig, host: &str) -> bool {
+    let host = host.to_ascii_lowercase();
+    config
+        .production_hosts
+        .iter()
+        .any(|pattern| crate::kube_context::wildcard_match(&pattern.to_ascii_lowercase(), &host))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(production_hosts: &[&str]) -> WrappersConfig {
+        WrappersConfig {
+            production_hosts: production_hosts.iter().map(|s| (*s).to_string()).collect(),
+            ..WrappersConfig::default()
+        }
+    }
+
+    #[test]
+    fn detects_remote_targets() {
+        let config = config(&[]);
+        assert_eq!(
+            ExecutionTarget::of("ssh deploy@prod-db1 'rm -rf /data'", &config),
+            ExecutionTarget::Remote {
+                hosts: vec!["prod-db1".to_string()]
+            }
+        );
+        assert_eq!(
+            ExecutionTarget::of("cd /tmp && ssh -J bastion web1 ssh web2 uptime", &config).hosts(),
+            ["web1", "web2"]
+        );
+        assert_eq!(
+            ExecutionTarget::of("rm -rf /data", &config),
+            ExecutionTarget::Local
+        );
+        assert_eq!(
+            ExecutionTarget::of("ssh prod-db1", &config),
+            ExecutionTarget::Local
+        );
+        assert_eq!(
+            ExecutionTarget::of(r#"git commit -m "ssh prod-db1 'rm -rf /'""#, &config),
+            ExecutionTarget::Local
+        );
+    }
+
+    #[test]
+    fn escalates_production_hosts_only() {
+        let config = config(&["prod-*", "*.PROD.example.com"]);
+        let escalation = escalate_severity(
+            &config,
+            "ssh admin@prod-db1 'dropdb app'",
+            Some(Severity::High),
+        )
+        .expect("production host escalates");
+        assert_eq!(escalation.host, "prod-db1");
+        assert_eq!(escalation.to, Severity::Critical);
+        assert_eq!(
+            escalation.notice(),
+            "Remote host 'prod-db1' is production: severity high -> critical."
+        );
+
+        assert!(
+            escalate_severity(
+                &config,
+                "ssh db.prod.example.com 'dropdb app'",
+                Some(Severity::Medium)
+            )
+            .is_some_and(|e| e.to == Severity::High)
+        );
+        assert!(
+            escalate_severity(
+                &config,
+                "ssh staging-db1 'dropdb app'",
+                Some(Severity::High)
+            )
+            .is_none()
+        );
+        assert!(escalate_severity(&config, "dropdb app", Some(Severity::High)).is_none());
+        assert!(
+            escalate_severity(&config, "ssh prod-db1 'rm -rf /'", Some(Severity::Critical))
+                .is_none()
+        );
+    }
+}
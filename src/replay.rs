@@ -0,0 +1,342 @@
+//! Recorded decision corpora (`dcg corpus record` / `dcg corpus replay`).
+//!
+//! `record` snapshots a set of commands together with the decision dcg makes
+//! for each of them under the current config and packs. `replay` evaluates
+//! the same commands again and reports every command whose decision or
+//! matched rule changed, so edits to patterns, packs, allowlists, or config
+//! can be checked against real traffic before they ship.
+//!
+//! The corpus stores the [`ConfigFingerprint`] it was recorded under, and the
+//! replay report says whether the current one matches, so a clean replay
+//! under an unchanged configuration can be told apart from one that exercised
+//! a config change.
+//!
+//! A corpus file is a single JSON document:
+//!
+//! ```json
+//! {
+//!   "schema_version": 1,
+//!   "recorded_at": "2026-10-16T09:30:00Z",
+//!   "binary_version": "0.4.0",
+//!   "enabled_packs": ["core.filesystem", "core.git"],
+//!   "config_fingerprint": "5d41402abc4b2a76b9719d911017c592...",
+//!   "entries": [
+//!     { "command": "git reset --hard", "decision": "deny", "rule_id": "core.git:reset-hard", "severity": "critical" },
+//!     { "command": "git status", "decision": "allow" }
+//!   ]
+//! }
+//! ```
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::allowlist::LayeredAllowlist;
+use crate::config::{CompiledOverrides, Config, HeredocSettings};
+use crate::evaluator::{EvaluationDecision, evaluate_command_with_pack_order};
+use crate::fingerprint::ConfigFingerprint;
+use crate::packs::{EnabledKeywordIndex, REGISTRY};
+
+/// Schema version of recorded corpus files.
+pub const RECORDED_CORPUS_SCHEMA_VERSION: u32 = 1;
+
+/// A recorded corpus: commands and the decisions made for them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedCorpus {
+    /// Corpus file schema version.
+    pub schema_version: u32,
+    /// When the corpus was recorded (RFC 3339).
+    pub recorded_at: String,
+    /// dcg version that recorded the corpus.
+    pub binary_version: String,
+    /// Packs that were enabled when recording, for reference.
+    #[serde(default)]
+    pub enabled_packs: Vec<String>,
+    /// Fingerprint of the config, allowlists, and packs used for recording
+    /// (absent in corpora recorded before fingerprints existed).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config_fingerprint: Option<ConfigFingerprint>,
+    /// Recorded commands, in the order they were first seen.
+    pub entries: Vec<RecordedDecision>,
+}
+
+/// One command and the decision dcg made for it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedDecision {
+    /// The command as evaluated.
+    pub command: String,
+    /// `allow` or `deny`.
+    pub decision: String,
+    /// Rule that matched (`pack_id:pattern_name`), if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule_id: Option<String>,
+    /// Severity of the matched rule, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<String>,
+}
+
+impl RecordedDecision {
+    /// Whether the recorded decision blocks the command.
+    #[must_use]
+    pub fn is_denied(&self) -> bool {
+        self.decision == "deny"
+    }
+}
+
+/// How a replayed decision differs from the recorded one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    /// Allowed when recorded, denied now.
+    NewlyDenied,
+    /// Denied when recorded, allowed now.
+    NewlyAllowed,
+    /// Same decision, but a different rule (or severity) matched.
+    RuleChanged,
+}
+
+impl ChangeKind {
+    /// Short label for text output.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::NewlyDenied => "newly denied",
+            Self::NewlyAllowed => "newly allowed",
+            Self::RuleChanged => "rule changed",
+        }
+    }
+}
+
+/// A command whose decision changed between recording and replay.
+#[derive(Debug, Clone, Serialize)]
+pub struct DecisionChange {
+    /// Kind of change.
+    pub kind: ChangeKind,
+    /// The command.
+    pub command: String,
+    /// Decision in the corpus file.
+    pub recorded: RecordedDecision,
+    /// Decision under the current config.
+    pub current: RecordedDecision,
+}
+
+/// Result of replaying a corpus.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayReport {
+    /// Commands replayed.
+    pub total: usize,
+    /// Commands whose decision and rule are unchanged.
+    pub unchanged: usize,
+    /// Commands whose decision or rule changed.
+    pub changes: Vec<DecisionChange>,
+    /// Whether the corpus was recorded under the current config fingerprint
+    /// (`None` when the corpus has no fingerprint).
+    pub fingerprint_matches: Option<bool>,
+}
+
+impl ReplayReport {
+    /// Count changes of one kind.
+    #[must_use]
+    pub fn count(&self, kind: ChangeKind) -> usize {
+        self.changes.iter().filter(|c| c.kind == kind).count()
+    }
+}
+
+/// Evaluates commands under one config, reusing the pack setup across calls.
+pub struct DecisionRecorder<'a> {
+    enabled_packs: Vec<String>,
+    enabled_keywords: Vec<&'static str>,
+    ordered_packs: Vec<String>,
+    keyword_index: Option<EnabledKeywordIndex>,
+    compiled_overrides: CompiledOverrides,
+    heredoc_settings: HeredocSettings,
+    allowlists: &'a LayeredAllowlist,
+    fingerprint: ConfigFingerprint,
+}
+
+impl<'a> DecisionRecorder<'a> {
+    /// Prepare the packs and overrides of `config`.
+    #[must_use]
+    pub fn new(config: &Config, allowlists: &'a LayeredAllowlist) -> Self {
+        let enabled = config.enabled_pack_ids();
+        let ordered_packs = REGISTRY.expand_enabled_ordered(&enabled);
+        Self {
+            enabled_packs: ordered_packs.clone(),
+            enabled_keywords: REGISTRY.collect_enabled_keywords(&enabled),
+            keyword_index: REGISTRY.build_enabled_keyword_index(&ordered_packs),
+            ordered_packs,
+            compiled_overrides: config.compile_overrides(),
+            heredoc_settings: config.heredoc_settings(),
+            allowlists,
+            fingerprint: ConfigFingerprint::new(config, allowlists),
+        }
+    }
+
+    /// Fingerprint of the config and allowlists decisions are made under.
+    #[must_use]
+    pub const fn fingerprint(&self) -> &ConfigFingerprint {
+        &self.fingerprint
+    }
+
+    /// Evaluate one command.
+    #[must_use]
+    pub fn evaluate(&self, command: &str) -> RecordedDecision {
+        let result = evaluate_command_with_pack_order(
+            command,
+            &self.enabled_keywords,
+            &self.ordered_packs,
+            self.keyword_index.as_ref(),
+            &self.compiled_overrides,
+            self.allowlists,
+            &self.heredoc_settings,
+        );
+        let decision = match result.decision {
+            EvaluationDecision::Allow => "allow",
+            EvaluationDecision::Deny => "deny",
+        };
+        let info = result.pattern_info.as_ref();
+        RecordedDecision {
+            command: command.to_string(),
+            decision: decision.to_string(),
+            rule_id: info.and_then(|info| {
+                info.pack_id
+                    .as_ref()
+                    .zip(info.pattern_name.as_ref())
+                    .map(|(pack, pattern)| format!("{pack}:{pattern}"))
+            }),
+            severity: info
+                .and_then(|info| info.severity)
+                .map(|severity| severity.label().to_string()),
+        }
+    }
+
+    /// Record `commands` (duplicates and blank commands are skipped).
+    #[must_use]
+    pub fn record<I, S>(&self, commands: I) -> RecordedCorpus
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut seen = HashSet::new();
+        let mut entries = Vec::new();
+        for command in commands {
+            let command = command.as_ref();
+            if command.trim().is_empty() || !seen.insert(command.to_string()) {
+                continue;
+            }
+            entries.push(self.evaluate(command));
+        }
+        RecordedCorpus {
+            schema_version: RECORDED_CORPUS_SCHEMA_VERSION,
+            recorded_at: chrono::Utc::now().to_rfc3339(),
+            binary_version: env!("CARGO_PKG_VERSION").to_string(),
+            enabled_packs: self.enabled_packs.clone(),
+            config_fingerprint: Some(self.fingerprint.clone()),
+            entries,
+        }
+    }
+
+    /// Re-evaluate every command of `corpus` and compare with the recording.
+    #[must_use]
+    pub fn replay(&self, corpus: &RecordedCorpus) -> ReplayReport {
+        let mut changes = Vec::new();
+        for recorded in &corpus.entries {
+            let current = self.evaluate(&recorded.command);
+            let kind = match (recorded.is_denied(), current.is_denied()) {
+                (false, true) => ChangeKind::NewlyDenied,
+                (true, false) => ChangeKind::NewlyAllowed,
+                _ if recorded.rule_id != current.rule_id
+                    || recorded.severity != current.severity =>
+                {
+                    ChangeKind::RuleChanged
+                }
+                _ => continue,
+            };
+            changes.push(DecisionChange {
+                kind,
+                command: recorded.command.clone(),
+                recorded: recorded.clone(),
+                current,
+            });
+        }
+        ReplayReport {
+            total: corpus.entries.len(),
+            unchanged: corpus.entries.len() - changes.len(),
+            changes,
+            fingerprint_matches: corpus
+                .config_fingerprint
+                .as_ref()
+                .map(|recorded| *recorded == self.fingerprint),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_snapshots_decisions_once_per_command() {
+        let config = Config::default();
+        let allowlists = LayeredAllowlist::default();
+        let recorder = DecisionRecorder::new(&config, &allowlists);
+
+        let corpus = recorder.record(["git reset --hard", "git status", "", "git reset --hard"]);
+        assert_eq!(corpus.schema_version, RECORDED_CORPUS_SCHEMA_VERSION);
+        assert_eq!(corpus.entries.len(), 2);
+        assert!(corpus.entries[0].is_denied());
+        assert!(
+            corpus.entries[0]
+                .rule_id
+                .as_deref()
+                .is_some_and(|rule| rule.starts_with("core.git:"))
+        );
+        assert_eq!(corpus.entries[1].decision, "allow");
+        assert!(corpus.enabled_packs.iter().any(|id| id == "core.git"));
+
+        let report = recorder.replay(&corpus);
+        assert_eq!(report.total, 2);
+        assert_eq!(report.unchanged, 2);
+        assert!(report.changes.is_empty());
+        assert_eq!(report.fingerprint_matches, Some(true));
+    }
+
+    #[test]
+    fn replay_reports_decisions_changed_by_config() {
+        let allowlists = LayeredAllowlist::default();
+        let corpus = DecisionRecorder::new(&Config::default(), &allowlists)
+            .record(["kubectl delete namespace prod", "kubectl get pods"]);
+        assert!(!corpus.entries[0].is_denied());
+
+        let mut config = Config::default();
+        config.packs.enabled.push("kubernetes".to_string());
+        let report = DecisionRecorder::new(&config, &allowlists).replay(&corpus);
+        assert_eq!(report.unchanged, 1);
+        assert_eq!(report.fingerprint_matches, Some(false));
+        assert_eq!(report.count(ChangeKind::NewlyDenied), 1);
+        assert_eq!(report.changes[0].command, "kubectl delete namespace prod");
+        assert!(
+            report.changes[0]
+                .current
+                .rule_id
+                .as_deref()
+                .is_some_and(|rule| rule.starts_with("kubernetes.kubectl:"))
+        );
+    }
+
+    #[test]
+    fn corpus_round_trips_through_json() {
+        let allowlists = LayeredAllowlist::default();
+        let recorder = DecisionRecorder::new(&Config::default(), &allowlists);
+        let mut corpus = recorder.record(["rm -rf /", "ls"]);
+        corpus.entries[1].decision = "deny".to_string();
+        corpus.config_fingerprint = None;
+
+        let json = serde_json::to_string(&corpus).expect("serialize");
+        let parsed: RecordedCorpus = serde_json::from_str(&json).expect("parse");
+        let report = recorder.replay(&parsed);
+        assert_eq!(report.count(ChangeKind::NewlyAllowed), 1);
+        assert_eq!(report.changes[0].command, "ls");
+        assert_eq!(report.fingerprint_matches, None);
+    }
+}
@@ -0,0 +1,463 @@
+//! Interactive review of denied commands (`dcg review`).
+//!
+//! Lists unreviewed denies from the history database in a terminal UI. For
+//! each one the user can approve it (as an allow-once entry or an allowlist
+//! entry), ignore it, or confirm it as a true positive. The UI only collects
+//! decisions; the caller applies them after the terminal is restored, and the
+//! verdicts are written back to the history database where
+//! `dcg history analyze` uses them.
+
+use std::io;
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+
+use crate::evaluator::MatchSpan;
+use crate::history::{DenyReviewItem, ReviewVerdict};
+
+/// Key help shown in the footer.
+const KEY_HELP: &str =
+    " j/k move  o allow once  a allowlist  i ignore  t true positive  u undo  q finish ";
+
+/// What the user decided for one deny.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReviewAction {
+    /// Approve with a single-use allow-once entry.
+    AllowOnce,
+    /// Approve with a permanent allowlist entry for the exact command.
+    Allowlist,
+    /// Dismiss without a judgement.
+    Ignore,
+    /// Confirm the deny was correct.
+    TruePositive,
+}
+
+impl ReviewAction {
+    /// Verdict recorded in the history database.
+    #[must_use]
+    pub const fn verdict(self) -> ReviewVerdict {
+        match self {
+            Self::AllowOnce | Self::Allowlist => ReviewVerdict::Approved,
+            Self::Ignore => ReviewVerdict::Ignored,
+            Self::TruePositive => ReviewVerdict::TruePositive,
+        }
+    }
+
+    const fn label(self) -> &'static str {
+        match self {
+            Self::AllowOnce => "allow once",
+            Self::Allowlist => "allowlist",
+            Self::Ignore => "ignored",
+            Self::TruePositive => "true positive",
+        }
+    }
+
+    const fn color(self) -> Color {
+        match self {
+            Self::AllowOnce | Self::Allowlist => Color::Green,
+            Self::Ignore => Color::DarkGray,
+            Self::TruePositive => Color::Red,
+        }
+    }
+}
+
+/// A deny in the review queue.
+#[derive(Debug, Clone)]
+pub struct ReviewEntry {
+    pub item: DenyReviewItem,
+    /// Reason from re-evaluating the command against the current packs.
+    pub reason: Option<String>,
+    /// Matched byte span within the command, for highlighting.
+    pub span: Option<MatchSpan>,
+    action: Option<ReviewAction>,
+}
+
+impl ReviewEntry {
+    #[must_use]
+    pub const fn new(
+        item: DenyReviewItem,
+        reason: Option<String>,
+        span: Option<MatchSpan>,
+    ) -> Self {
+        Self {
+            item,
+            reason,
+            span,
+            action: None,
+        }
+    }
+}
+
+/// A decision made in the UI, applied by the caller.
+#[derive(Debug, Clone)]
+pub struct ReviewDecision {
+    pub item: DenyReviewItem,
+    pub reason: Option<String>,
+    pub action: ReviewAction,
+}
+
+/// State of the review UI.
+#[derive(Debug)]
+pub struct ReviewApp {
+    entries: Vec<ReviewEntry>,
+    state: ListState,
+    quit: bool,
+}
+
+impl ReviewApp {
+    #[must_use]
+    pub fn new(entries: Vec<ReviewEntry>) -> Self {
+        let mut state = ListState::default();
+        if !entries.is_empty() {
+            state.select(Some(0));
+        }
+        Self {
+            entries,
+            state,
+            quit: false,
+        }
+    }
+
+    /// The highlighted entry.
+    #[must_use]
+    pub fn selected(&self) -> Option<&ReviewEntry> {
+        self.state.selected().and_then(|i| self.entries.get(i))
+    }
+
+    #[must_use]
+    pub const fn should_quit(&self) -> bool {
+        self.quit
+    }
+
+    /// Apply one key press.
+    pub fn handle_key(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Down | KeyCode::Char('j') => self.move_by(1),
+            KeyCode::Up | KeyCode::Char('k') => self.move_by(-1),
+            KeyCode::Char('o') => self.decide(Some(ReviewAction::AllowOnce)),
+            KeyCode::Char('a') => self.decide(Some(ReviewAction::Allowlist)),
+            KeyCode::Char('i') => self.decide(Some(ReviewAction::Ignore)),
+            KeyCode::Char('t') => self.decide(Some(ReviewAction::TruePositive)),
+            KeyCode::Char('u') => self.decide(None),
+            _ => {}
+        }
+    }
+
+    fn move_by(&mut self, delta: isize) {
+        let Some(current) = self.state.selected() else {
+            return;
+        };
+        let last = self.entries.len().saturating_sub(1);
+        let next = current.saturating_add_signed(delta).min(last);
+        self.state.select(Some(next));
+    }
+
+    /// Set (or with `None`, clear) the decision for the highlighted entry,
+    /// then move to the next one.
+    fn decide(&mut self, action: Option<ReviewAction>) {
+        let Some(entry) = self.state.selected().and_then(|i| self.entries.get_mut(i)) else {
+            return;
+        };
+        entry.action = action;
+        if action.is_some() {
+            self.move_by(1);
+        }
+    }
+
+    /// Decisions made so far, in queue order.
+    #[must_use]
+    pub fn into_decisions(self) -> Vec<ReviewDecision> {
+        self.entries
+            .into_iter()
+            .filter_map(|entry| {
+                entry.action.map(|action| ReviewDecision {
+                    item: entry.item,
+                    reason: entry.reason,
+                    action,
+                })
+            })
+            .collect()
+    }
+
+    /// Draw the queue, the details of the highlighted deny, and the key help.
+    pub fn render(&mut self, frame: &mut Frame) {
+        let [list_area, detail_area, help_area] = Layout::vertical([
+            Constraint::Min(5),
+            Constraint::Length(9),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let decided = self.entries.iter().filter(|e| e.action.is_some()).count();
+        let items: Vec<ListItem> = self.entries.iter().map(queue_line).collect();
+        let list = List::new(items)
+            .block(Block::bordered().title(format!(
+                " Denied commands ({decided}/{} reviewed) ",
+                self.entries.len()
+            )))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+            .highlight_symbol("> ");
+        frame.render_stateful_widget(list, list_area, &mut self.state);
+
+        let details = self.selected().map_or_else(Text::default, detail_text);
+        frame.render_widget(
+            Paragraph::new(details)
+                .block(Block::bordered().title(" Details "))
+                .wrap(Wrap { trim: false }),
+            detail_area,
+        );
+
+        frame.render_widget(
+            Paragraph::new(KEY_HELP).style(Style::new().fg(Color::DarkGray)),
+            help_area,
+        );
+    }
+}
+
+/// Run the review UI until the user quits and return their decisions.
+///
+/// # Errors
+///
+/// Returns an error if the terminal cannot be drawn or read.
+pub fn run(entries: Vec<ReviewEntry>) -> io::Result<Vec<ReviewDecision>> {
+    let mut app = ReviewApp::new(entries);
+    let mut terminal = ratatui::init();
+    let result = run_app(&mut terminal, &mut app);
+    ratatui::restore();
+    result?;
+    Ok(app.into_decisions())
+}
+
+fn run_app(terminal: &mut DefaultTerminal, app: &mut ReviewApp) -> io::Result<()> {
+    while !app.should_quit() {
+        terminal.draw(|frame| app.render(frame))?;
+        match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => app.handle_key(key.code),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn queue_line(entry: &ReviewEntry) -> ListItem<'_> {
+    let status = entry.action.map_or_else(
+        || Span::raw(format!("{:<13}", "pending")),
+        |action| {
+            Span::styled(
+                format!("{:<13}", action.label()),
+                Style::new().fg(action.color()),
+            )
+        },
+    );
+    let rule = entry
+        .item
+        .entry
+        .rule_id
+        .as_deref()
+        .or(entry.item.entry.pack_id.as_deref())
+        .unwrap_or("-");
+    ListItem::new(Line::from(vec![
+        Span::styled(
+            entry
+                .item
+                .entry
+                .timestamp
+                .format("%m-%d %H:%M ")
+                .to_string(),
+            Style::new().fg(Color::DarkGray),
+        ),
+        status,
+        Span::styled(format!("{rule:<32} "), Style::new().fg(Color::Cyan)),
+        Span::raw(entry.item.entry.command.as_str()),
+    ]))
+}
+
+fn detail_text(entry: &ReviewEntry) -> Text<'_> {
+    let command = &entry.item.entry;
+    let label = |name: &'static str| Span::styled(name, Style::new().add_modifier(Modifier::BOLD));
+    let mut lines = vec![
+        Line::from(
+            [
+                vec![label("Command:   ")],
+                highlighted(&command.command, entry.span),
+            ]
+            .concat(),
+        ),
+        Line::from(vec![
+            label("Rule:      "),
+            Span::raw(
+                command
+                    .rule_id
+                    .as_deref()
+                    .or(command.pack_id.as_deref())
+                    .unwrap_or("-"),
+            ),
+        ]),
+        Line::from(vec![
+            label("Reason:    "),
+            Span::raw(
+                entry
+                    .reason
+                    .as_deref()
+                    .unwrap_or("(no longer denied by the current configuration)"),
+            ),
+        ]),
+        Line::from(vec![
+            label("Directory: "),
+            Span::raw(command.working_dir.as_str()),
+        ]),
+        Line::from(vec![
+            label("Agent:     "),
+            Span::raw(command.agent_type.as_str()),
+            Span::raw(
+                command
+                    .session_id
+                    .as_deref()
+                    .map(|id| format!(" (session {id})"))
+                    .unwrap_or_default(),
+            ),
+        ]),
+    ];
+    if let Some(action) = entry.action {
+        lines.push(Line::from(vec![
+            label("Decision:  "),
+            Span::styled(action.label(), Style::new().fg(action.color())),
+        ]));
+    }
+    Text::from(lines)
+}
+
+/// Split `command` around the matched span so the match can be styled.
+///
+/// Falls back to plain text when the span is missing or not on character
+/// boundaries (e.g. the command was evaluated after normalization).
+fn highlighted(command: &str, span: Option<MatchSpan>) -> Vec<Span<'_>> {
+    let parts = span.and_then(|span| {
+        Some((
+            command.get(..span.start)?,
+            command.get(span.start..span.end)?,
+            command.get(span.end..)?,
+        ))
+    });
+    match parts {
+        Some((before, matched, after)) if !matched.is_empty() => vec![
+            Span::raw(before),
+            Span::styled(
+                matched,
+                Style::new()
+                    .fg(Color::Red)
+                    .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            ),
+            Span::raw(after),
+        ],
+        _ => vec![Span::raw(command)],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::{CommandEntry, Outcome};
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    fn entry(id: i64, command: &str) -> ReviewEntry {
+        ReviewEntry::new(
+            DenyReviewItem {
+                id,
+                entry: CommandEntry {
+                    agent_type: "claude_code".to_string(),
+                    working_dir: "/repo".to_string(),
+                    command: command.to_string(),
+                    outcome: Outcome::Deny,
+                    pack_id: Some("core.git".to_string()),
+                    pattern_name: Some("reset-hard".to_string()),
+                    rule_id: Some("core.git:reset-hard".to_string()),
+                    ..Default::default()
+                },
+            },
+            Some("git reset --hard destroys uncommitted changes.".to_string()),
+            Some(MatchSpan { start: 0, end: 16 }),
+        )
+    }
+
+    #[test]
+    fn decisions_advance_and_can_be_undone() {
+        let mut app = ReviewApp::new(vec![
+            entry(1, "git reset --hard"),
+            entry(2, "git reset --hard HEAD~1"),
+            entry(3, "git reset --hard origin/main"),
+        ]);
+
+        app.handle_key(KeyCode::Char('o'));
+        assert_eq!(app.selected().unwrap().item.id, 2);
+        app.handle_key(KeyCode::Char('t'));
+        app.handle_key(KeyCode::Char('i'));
+        // Deciding the last entry stays on it.
+        assert_eq!(app.selected().unwrap().item.id, 3);
+        app.handle_key(KeyCode::Char('u'));
+        app.handle_key(KeyCode::Char('k'));
+        app.handle_key(KeyCode::Char('a'));
+        app.handle_key(KeyCode::Char('q'));
+        assert!(app.should_quit());
+
+        let decisions: Vec<(i64, ReviewAction)> = app
+            .into_decisions()
+            .into_iter()
+            .map(|d| (d.item.id, d.action))
+            .collect();
+        assert_eq!(
+            decisions,
+            vec![(1, ReviewAction::AllowOnce), (2, ReviewAction::Allowlist)]
+        );
+    }
+
+    #[test]
+    fn actions_map_to_verdicts() {
+        assert_eq!(ReviewAction::AllowOnce.verdict(), ReviewVerdict::Approved);
+        assert_eq!(ReviewAction::Allowlist.verdict(), ReviewVerdict::Approved);
+        assert_eq!(ReviewAction::Ignore.verdict(), ReviewVerdict::Ignored);
+        assert_eq!(
+            ReviewAction::TruePositive.verdict(),
+            ReviewVerdict::TruePositive
+        );
+    }
+
+    #[test]
+    fn highlight_splits_on_span() {
+        let spans = highlighted(
+            "sudo git reset --hard",
+            Some(MatchSpan { start: 5, end: 21 }),
+        );
+        assert_eq!(spans.len(), 3);
+        assert_eq!(spans[1].content, "git reset --hard");
+
+        // Spans that do not fall on character boundaries are ignored (`é` is
+        // bytes 10..12).
+        let spans = highlighted("rm -rf café", Some(MatchSpan { start: 0, end: 11 }));
+        assert_eq!(spans.len(), 1);
+    }
+
+    #[test]
+    fn renders_queue_and_details() {
+        let mut app = ReviewApp::new(vec![entry(1, "git reset --hard")]);
+        let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+        terminal.draw(|frame| app.render(frame)).unwrap();
+
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(ratatui::buffer::Cell::symbol)
+            .collect();
+        assert!(screen.contains("Denied commands (0/1 reviewed)"));
+        assert!(screen.contains("core.git:reset-hard"));
+        assert!(screen.contains("destroys uncommitted changes"));
+    }
+}
@@ -0,0 +1,259 @@
+//! Per-session risk scoring.
+//!
+//! Each command that matches a rule adds points to a running score for the
+//! agent session (or, when the hook payload has no session id, the working
+//! directory). Denied commands add `deny_points`; commands that were only
+//! warned about or logged add `warn_points`; critical rules count double. Once
+//! `[risk]` is enabled and the score reaches `threshold`, rules that would only
+//! warn are escalated to deny and the denial explains why.
+//!
+//! Scores are stored in the history database, so nothing is scored unless
+//! `[history]` is enabled.
+
+use chrono::{DateTime, TimeDelta, Utc};
+
+use crate::config::RiskConfig;
+use crate::history::HistoryDb;
+use crate::packs::{DecisionMode, Severity};
+
+/// A session whose risk score has reached the threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RiskStatus {
+    /// Score before the current command.
+    pub score: u64,
+    /// Configured escalation threshold.
+    pub threshold: u64,
+}
+
+impl RiskStatus {
+    /// Escalate a decision: `Warn` becomes `Deny`; `Deny` and `Log` are unchanged.
+    #[must_use]
+    pub const fn escalate(&self, mode: DecisionMode) -> DecisionMode {
+        match mode {
+            DecisionMode::Warn => DecisionMode::Deny,
+            other => other,
+        }
+    }
+
+    /// Notice appended to denials while the session is over the threshold.
+    #[must_use]
+    pub fn notice(&self) -> String {
+        format!(
+            "Session risk score is {} (threshold {}): this session has run or attempted \
+             many risky commands. Rules that normally only warn are denied until the \
+             session goes idle; ask the user before continuing.",
+            self.score, self.threshold
+        )
+    }
+}
+
+/// Key a score is stored under: the session id, else the working directory.
+fn scope(working_dir: &str, session_id: Option<&str>) -> String {
+    session_id.map_or_else(
+        || format!("dir:{working_dir}"),
+        |id| format!("session:{id}"),
+    )
+}
+
+/// Cutoff before which a stored score is considered idle and discarded.
+fn reset_before(config: &RiskConfig, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    if config.idle_reset_secs == 0 {
+        return None;
+    }
+    let idle = i64::try_from(config.idle_reset_secs)
+        .ok()
+        .and_then(TimeDelta::try_seconds)?;
+    now.checked_sub_signed(idle)
+}
+
+/// Points a matched command adds to the session score.
+#[must_use]
+pub const fn points(config: &RiskConfig, mode: DecisionMode, severity: Option<Severity>) -> u64 {
+    let base = match mode {
+        DecisionMode::Deny => config.deny_points,
+        DecisionMode::Warn | DecisionMode::Log => config.warn_points,
+    };
+    match severity {
+        Some(Severity::Critical) => base.saturating_mul(2),
+        _ => base,
+    }
+}
+
+/// Check whether the session's current score has reached the threshold.
+///
+/// Returns `None` when scoring is disabled, the query fails (fail-open), the
+/// stored score has gone idle, or the session is under the threshold.
+#[must_use]
+pub fn check(
+    db: &HistoryDb,
+    config: &RiskConfig,
+    agent_type: &str,
+    working_dir: &str,
+    session_id: Option<&str>,
+    now: DateTime<Utc>,
+) -> Option<RiskStatus> {
+    if !config.is_active() {
+        return None;
+    }
+
+    let risk = db
+        .session_risk(agent_type, &scope(working_dir, session_id))
+        .ok()??;
+    if reset_before(config, now).is_some_and(|cutoff| risk.updated_at < cutoff) {
+        return None;
+    }
+
+    (risk.score >= config.threshold).then_some(RiskStatus {
+        score: risk.score,
+        threshold: config.threshold,
+    })
+}
+
+/// Add the points for a matched command and return the new score.
+///
+/// Returns `None` when scoring is disabled, the command scores no points, or
+/// the update fails.
+#[must_use]
+#[allow(clippy::too_many_arguments)]
+pub fn record(
+    db: &HistoryDb,
+    config: &RiskConfig,
+    agent_type: &str,
+    working_dir: &str,
+    session_id: Option<&str>,
+    mode: DecisionMode,
+    severity: Option<Severity>,
+    now: DateTime<Utc>,
+) -> Option<u64> {
+    if !config.is_active() {
+        return None;
+    }
+    let points = points(config, mode, severity);
+    if points == 0 {
+        return None;
+    }
+    db.add_session_risk(
+        agent_type,
+        &scope(working_dir, session_id),
+        points,
+        now,
+        reset_before(config, now),
+    )
+    .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> RiskConfig {
+        RiskConfig {
+            enabled: true,
+            threshold: 50,
+            deny_points: 20,
+            warn_points: 5,
+            idle_reset_secs: 600,
+        }
+    }
+
+    #[test]
+    fn escalates_once_score_reaches_threshold() {
+        let db = HistoryDb::open_in_memory().unwrap();
+        let config = config();
+        let now = Utc::now();
+        let session = Some("s1");
+
+        let record_at = |mode, severity| {
+            record(
+                &db,
+                &config,
+                "claude_code",
+                "/repo",
+                session,
+                mode,
+                severity,
+                now,
+            )
+        };
+        assert_eq!(record_at(DecisionMode::Warn, None), Some(5));
+        assert_eq!(record_at(DecisionMode::Deny, None), Some(25));
+        assert_eq!(
+            check(&db, &config, "claude_code", "/repo", session, now),
+            None
+        );
+
+        assert_eq!(
+            record_at(DecisionMode::Deny, Some(Severity::Critical)),
+            Some(65)
+        );
+        let status =
+            check(&db, &config, "claude_code", "/repo", session, now).expect("over threshold");
+        assert_eq!(status.score, 65);
+        assert_eq!(status.escalate(DecisionMode::Warn), DecisionMode::Deny);
+        assert_eq!(status.escalate(DecisionMode::Log), DecisionMode::Log);
+        assert!(status.notice().contains("threshold 50"));
+
+        // Other sessions keep their own score.
+        assert_eq!(
+            check(&db, &config, "claude_code", "/repo", Some("s2"), now),
+            None
+        );
+    }
+
+    #[test]
+    fn idle_score_resets() {
+        let db = HistoryDb::open_in_memory().unwrap();
+        let config = config();
+        let earlier = Utc::now() - TimeDelta::seconds(3600);
+        let now = Utc::now();
+
+        for _ in 0..3 {
+            let _ = record(
+                &db,
+                &config,
+                "claude_code",
+                "/repo",
+                None,
+                DecisionMode::Deny,
+                None,
+                earlier,
+            );
+        }
+        assert!(check(&db, &config, "claude_code", "/repo", None, earlier).is_some());
+        assert_eq!(check(&db, &config, "claude_code", "/repo", None, now), None);
+
+        let score = record(
+            &db,
+            &config,
+            "claude_code",
+            "/repo",
+            None,
+            DecisionMode::Warn,
+            None,
+            now,
+        );
+        assert_eq!(score, Some(5));
+    }
+
+    #[test]
+    fn disabled_config_never_scores() {
+        let db = HistoryDb::open_in_memory().unwrap();
+        let config = RiskConfig::default();
+        let now = Utc::now();
+
+        assert_eq!(
+            record(
+                &db,
+                &config,
+                "claude_code",
+                "/repo",
+                None,
+                DecisionMode::Deny,
+                None,
+                now
+            ),
+            None
+        );
+        assert_eq!(db.session_risk("claude_code", "dir:/repo").unwrap(), None);
+    }
+}
@@ -0,0 +1,271 @@
+//! "What would this delete" preview for denied `rm` commands.
+//!
+//! When `[protection.rm_preview]` is enabled, the hook walks the targets of a
+//! denied `rm` (read-only, never following symlinks) and appends a summary to
+//! the deny reason: how many files and directories would go, their total size,
+//! and whether any target sits in a git work tree with uncommitted changes.
+//! The human reviewing the denial can then decide whether to run it by hand.
+//!
+//! The walk is bounded by `max_entries` and `timeout_ms`; a truncated walk is
+//! reported as a lower bound.
+
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use crate::config::RmPreviewConfig;
+use crate::protected_paths::rm_targets;
+
+/// Summary of what an `rm` command would remove.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RmPreview {
+    /// Targets (after glob expansion) that exist on disk.
+    pub existing_targets: usize,
+    /// Operands that do not exist (or whose glob matched nothing).
+    pub missing_targets: usize,
+    /// Non-directory entries that would be removed (files, symlinks, ...).
+    pub files: u64,
+    /// Directories that would be removed.
+    pub dirs: u64,
+    /// Total size of the files, in bytes.
+    pub bytes: u64,
+    /// Targets inside a git work tree.
+    pub in_git: usize,
+    /// Targets with uncommitted git changes, and how many.
+    pub uncommitted: Vec<(PathBuf, usize)>,
+    /// The walk hit `max_entries` or `timeout_ms`; counts are lower bounds.
+    pub truncated: bool,
+}
+
+impl RmPreview {
+    /// One-line summary for the deny reason.
+    #[must_use]
+    pub fn summary(&self) -> String {
+        if self.existing_targets == 0 {
+            return "rm preview: no target exists".to_string();
+        }
+
+        let at_least = if self.truncated { "at least " } else { "" };
+        let mut summary = format!(
+            "rm preview: would delete {at_least}{} file{} and {} director{} ({}) across {} target{}",
+            self.files,
+            plural(self.files == 1, "", "s"),
+            self.dirs,
+            plural(self.dirs == 1, "y", "ies"),
+            crate::cli::format_size(self.bytes),
+            self.existing_targets,
+            plural(self.existing_targets == 1, "", "s"),
+        );
+        if self.missing_targets > 0 {
+            let _ = write!(
+                summary,
+                "; {} target{} not found",
+                self.missing_targets,
+                plural(self.missing_targets == 1, "", "s")
+            );
+        }
+
+        if self.uncommitted.is_empty() {
+            summary.push_str(if self.in_git == 0 {
+                "; not under git version control"
+            } else {
+                "; tracked in git with no uncommitted changes"
+            });
+        } else {
+            for (path, changes) in &self.uncommitted {
+                let _ = write!(
+                    summary,
+                    "; {} has {changes} uncommitted change{} in git",
+                    path.display(),
+                    plural(*changes == 1, "", "s")
+                );
+            }
+        }
+
+        if self.truncated {
+            summary.push_str(" (preview stopped early)");
+        }
+        summary
+    }
+}
+
+const fn plural(is_one: bool, one: &'static str, many: &'static str) -> &'static str {
+    if is_one { one } else { many }
+}
+
+/// Preview the `rm` targets in `command`, resolved against `cwd`.
+///
+/// Returns `None` when the command contains no `rm` operands.
+#[must_use]
+pub fn preview(command: &str, cwd: &Path, config: &RmPreviewConfig) -> Option<RmPreview> {
+    let operands = rm_targets(command, cwd);
+    if operands.is_empty() {
+        return None;
+    }
+
+    let deadline = Instant::now() + Duration::from_millis(config.timeout_ms);
+    let mut preview = RmPreview::default();
+    let mut targets = Vec::new();
+
+    for operand in operands {
+        let expanded = expand_operand(&operand);
+        if expanded.is_empty() {
+            preview.missing_targets += 1;
+        }
+        targets.extend(expanded);
+    }
+    preview.existing_targets = targets.len();
+
+    let mut visited = 0usize;
+    let mut stack = targets.clone();
+    while let Some(path) = stack.pop() {
+        if visited >= config.max_entries || Instant::now() >= deadline {
+            preview.truncated = true;
+            break;
+        }
+        visited += 1;
+
+        let Ok(metadata) = std::fs::symlink_metadata(&path) else {
+            continue;
+        };
+        if metadata.is_dir() {
+            preview.dirs += 1;
+            if let Ok(entries) = std::fs::read_dir(&path) {
+                stack.extend(entries.filter_map(Result::ok).map(|entry| entry.path()));
+            }
+        } else {
+            preview.files += 1;
+            preview.bytes += metadata.len();
+        }
+    }
+
+    for target in &targets {
+        if Instant::now() >= deadline {
+            preview.truncated = true;
+            break;
+        }
+        if let Some(changes) = git_uncommitted_changes(target) {
+            preview.in_git += 1;
+            if changes > 0 {
+                preview.uncommitted.push((target.clone(), changes));
+            }
+        }
+    }
+
+    Some(preview)
+}
+
+/// Expand a wildcard operand the way the shell would; plain operands are
+/// returned as-is when they exist.
+fn expand_operand(operand: &Path) -> Vec<PathBuf> {
+    let text = operand.to_string_lossy();
+    if text.contains(['*', '?', '[']) {
+        return glob::glob(&text)
+            .map(|paths| paths.filter_map(Result::ok).collect())
+            .unwrap_or_default();
+    }
+    if std::fs::symlink_metadata(operand).is_ok() {
+        vec![operand.to_path_buf()]
+    } else {
+        Vec::new()
+    }
+}
+
+/// Count uncommitted changes (including untracked files) at or below `path`.
+///
+/// Returns `None` when `path` is not inside a git work tree.
+fn git_uncommitted_changes(path: &Path) -> Option<usize> {
+    let dir = if path.is_dir() { path } else { path.parent()? };
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["status", "--porcelain", "--untracked-files=all", "--"])
+        .arg(path)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter(|line| !line.is_empty())
+            .count(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> RmPreviewConfig {
+        RmPreviewConfig {
+            enabled: true,
+            max_entries: 10_000,
+            timeout_ms: 5_000,
+        }
+    }
+
+    #[test]
+    fn counts_files_dirs_and_bytes() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("build/nested")).unwrap();
+        std::fs::write(dir.path().join("build/a.o"), [0u8; 100]).unwrap();
+        std::fs::write(dir.path().join("build/nested/b.o"), [0u8; 50]).unwrap();
+
+        let preview = preview("rm -rf build missing", dir.path(), &config()).unwrap();
+        assert_eq!(preview.existing_targets, 1);
+        assert_eq!(preview.missing_targets, 1);
+        assert_eq!(preview.files, 2);
+        assert_eq!(preview.dirs, 2);
+        assert_eq!(preview.bytes, 150);
+        assert!(!preview.truncated);
+    }
+
+    #[test]
+    fn expands_wildcards_and_respects_entry_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        for i in 0..5 {
+            std::fs::write(dir.path().join(format!("{i}.log")), "x").unwrap();
+        }
+
+        let preview = preview("rm *.log", dir.path(), &config()).unwrap();
+        assert_eq!(preview.existing_targets, 5);
+        assert_eq!(preview.files, 5);
+
+        let limited = RmPreviewConfig {
+            max_entries: 2,
+            ..config()
+        };
+        let preview = super::preview("rm *.log", dir.path(), &limited).unwrap();
+        assert!(preview.truncated);
+        assert_eq!(preview.files, 2);
+        assert!(preview.summary().contains("at least 2 files"));
+    }
+
+    #[test]
+    fn no_rm_means_no_preview() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(preview("git reset --hard", dir.path(), &config()).is_none());
+    }
+
+    #[test]
+    fn summary_mentions_uncommitted_changes() {
+        let preview = RmPreview {
+            existing_targets: 1,
+            files: 1,
+            bytes: 10,
+            in_git: 1,
+            uncommitted: vec![(PathBuf::from("/repo/src"), 3)],
+            ..RmPreview::default()
+        };
+        assert_eq!(
+            preview.summary(),
+            "rm preview: would delete 1 file and 0 directories (10 bytes) across 1 target; \
+             /repo/src has 3 uncommitted changes in git"
+        );
+    }
+}
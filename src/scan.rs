@@ -303,7 +303,7 @@ impl ScanEvalContext {
         let enabled_keywords = REGISTRY.collect_enabled_keywords(&enabled_packs);
         let ordered_packs = REGISTRY.expand_enabled_ordered(&enabled_packs);
         let keyword_index = REGISTRY.build_enabled_keyword_index(&ordered_packs);
-        let compiled_overrides = config.overrides.compile();
+        let compiled_overrides = config.compile_overrides();
         let allowlists = crate::load_default_allowlists();
         let heredoc_settings = config.heredoc_settings();
 
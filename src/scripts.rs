@@ -0,0 +1,1009 @@
+//! Project script introspection.
+//!
+//! `npm run clean` or `make clean` looks harmless, but the script behind it
+//! may be `rm -rf /`. This module finds script invocations in a command and
+//! resolves them to the bodies that would actually run, so the evaluator can
+//! check those bodies through the normal pipeline:
+//!
+//! ```text
+//! npm run build            -> scripts.prebuild, scripts.build, scripts.postbuild
+//! npm test / npm start     -> scripts.test / scripts.start (plus pre/post)
+//! yarn deploy, pnpm deploy -> scripts.deploy, when it is not a builtin command
+//! npm --prefix web run dev -> web/package.json
+//! make clean               -> recipes of `clean` and its prerequisites
+//! make -C web              -> the default goal of web/Makefile
+//! just deploy              -> recipes of `deploy` and its dependencies
+//! ```
+//!
+//! # Design Principles
+//!
+//! - **Same lookup as the runner**: package.json and justfiles are found by
+//!   walking up from the working directory, Makefiles in the working
+//!   directory, each honoring the runner's directory/file options.
+//! - **Static only**: simple Makefile variables (`VAR = value`) and `$@` are
+//!   expanded; functions, includes, conditionals, and `{{...}}` interpolation
+//!   are left as written.
+//! - **Bounded**: files over [`MAX_SCRIPT_FILE_BYTES`] are ignored, at most
+//!   [`MAX_SCRIPTS`] bodies are returned per command, and prerequisite chains
+//!   are followed [`MAX_DEPENDENCY_DEPTH`] levels deep.
+//! - **Fail-open**: unreadable or malformed files and unknown scripts resolve
+//!   to nothing.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Largest package.json, Makefile, or justfile that is read.
+pub const MAX_SCRIPT_FILE_BYTES: u64 = 1024 * 1024;
+
+/// Most script bodies returned for a single command.
+pub const MAX_SCRIPTS: usize = 16;
+
+/// How deep Makefile prerequisites and just dependencies are followed.
+pub const MAX_DEPENDENCY_DEPTH: usize = 8;
+
+/// Parent directories searched for package.json and justfiles.
+const MAX_PARENT_HOPS: usize = 32;
+
+/// Programs whose scripts are resolved.
+const RUNNERS: &[&str] = &["npm", "pnpm", "yarn", "make", "gmake", "just"];
+
+/// Makefile names, in the order GNU make tries them.
+const MAKEFILE_NAMES: &[&str] = &["GNUmakefile", "makefile", "Makefile"];
+
+/// Justfile names just looks for.
+const JUSTFILE_NAMES: &[&str] = &["justfile", "Justfile", ".justfile"];
+
+/// Subcommands of yarn and pnpm that shadow scripts of the same name.
+const BUILTINS: &[&str] = &[
+    "add",
+    "audit",
+    "autoclean",
+    "bin",
+    "c",
+    "cache",
+    "check",
+    "config",
+    "create",
+    "dedupe",
+    "deploy",
+    "dlx",
+    "doctor",
+    "env",
+    "exec",
+    "fetch",
+    "generate-lock-entry",
+    "global",
+    "help",
+    "i",
+    "import",
+    "info",
+    "init",
+    "install",
+    "licenses",
+    "link",
+    "list",
+    "ln",
+    "login",
+    "logout",
+    "ls",
+    "m",
+    "multi",
+    "node",
+    "npm",
+    "outdated",
+    "owner",
+    "pack",
+    "patch",
+    "patch-commit",
+    "plugin",
+    "policies",
+    "prune",
+    "publish",
+    "rb",
+    "rebuild",
+    "recursive",
+    "remove",
+    "rm",
+    "root",
+    "search",
+    "server",
+    "set",
+    "setup",
+    "store",
+    "tag",
+    "team",
+    "un",
+    "uninstall",
+    "unlink",
+    "unplug",
+    "up",
+    "update",
+    "upgrade",
+    "version",
+    "why",
+    "workspace",
+    "workspaces",
+];
+
+/// One script body a command would run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptInvocation {
+    /// Runner that executes the script (`npm`, `pnpm`, `yarn`, `make`, `just`).
+    pub runner: &'static str,
+    /// Script, target, or recipe name (including `pre`/`post` lifecycle
+    /// scripts and prerequisites).
+    pub name: String,
+    /// Script body; recipe lines are joined with newlines.
+    pub body: String,
+    /// Directory the script runs in.
+    pub dir: PathBuf,
+    /// Byte offset of the invoking command segment.
+    pub start: usize,
+    /// Byte offset one past the end of the invoking command segment.
+    pub end: usize,
+}
+
+impl ScriptInvocation {
+    /// Short preview of the body for denial messages.
+    #[must_use]
+    pub fn preview(&self) -> String {
+        const MAX_PREVIEW: usize = 80;
+        let body = self.body.trim().replace('\n', "; ");
+        if body.chars().count() <= MAX_PREVIEW {
+            return body;
+        }
+        let truncated: String = body.chars().take(MAX_PREVIEW).collect();
+        format!("{truncated}...")
+    }
+}
+
+/// A resolved script before it is tied to a command segment.
+struct ResolvedScript {
+    name: String,
+    body: String,
+    dir: PathBuf,
+}
+
+/// Cheap pre-check: does the command mention a script runner at all?
+#[must_use]
+pub fn mentions_script_runner(command: &str) -> bool {
+    command
+        .split(|c: char| c.is_whitespace() || matches!(c, ';' | '&' | '|' | '(' | ')'))
+        .any(|word| RUNNERS.contains(&word.rsplit('/').next().unwrap_or(word)))
+}
+
+/// Find the script bodies `command` would run when executed in `cwd`.
+#[must_use]
+pub fn extract_script_invocations(command: &str, cwd: &Path) -> Vec<ScriptInvocation> {
+    let mut invocations = Vec::new();
+    if !mentions_script_runner(command) {
+        return invocations;
+    }
+    for (start, segment) in command_segments(command) {
+        let mut words = segment
+            .split_whitespace()
+            .map(|word| word.trim_matches(|c| c == '"' || c == '\''))
+            .skip_while(|word| is_env_assignment(word));
+        let Some(program) = words.next() else {
+            continue;
+        };
+        let program = program.rsplit('/').next().unwrap_or(program);
+        let Some(runner) = RUNNERS.iter().copied().find(|runner| *runner == program) else {
+            continue;
+        };
+        let args: Vec<&str> = words.collect();
+        let scripts = match runner {
+            "make" | "gmake" => make_scripts(&args, cwd),
+            "just" => just_scripts(&args, cwd),
+            _ => package_scripts(runner, &args, cwd),
+        };
+
+        let end = start + segment.trim_end().len();
+        for script in scripts.unwrap_or_default() {
+            if invocations.len() == MAX_SCRIPTS {
+                return invocations;
+            }
+            invocations.push(ScriptInvocation {
+                runner,
+                name: script.name,
+                body: script.body,
+                dir: script.dir,
+                start,
+                end,
+            });
+        }
+    }
+    invocations
+}
+
+/// Split a command at `;`, `&`, `|`, and newlines outside quotes, returning
+/// each segment with its byte offset (leading whitespace skipped).
+pub(crate) fn command_segments(command: &str) -> Vec<(usize, &str)> {
+    let bytes = command.as_bytes();
+    let mut segments = Vec::new();
+    let mut seg_start = 0;
+    let mut quote: Option<u8> = None;
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+        match quote {
+            Some(q) if b == q => quote = None,
+            Some(b'"') if b == b'\\' => i += 1,
+            Some(_) => {}
+            None => match b {
+                b'\'' | b'"' => quote = Some(b),
+                b'\\' => i += 1,
+                b';' | b'&' | b'|' | b'\n' | b'(' | b')' => {
+                    push_segment(command, seg_start, i, &mut segments);
+                    seg_start = i + 1;
+                }
+                _ => {}
+            },
+        }
+        i += 1;
+    }
+    push_segment(command, seg_start, command.len(), &mut segments);
+    segments
+}
+
+fn push_segment<'a>(command: &'a str, start: usize, end: usize, out: &mut Vec<(usize, &'a str)>) {
+    let Some(segment) = command.get(start..end) else {
+        return;
+    };
+    let trimmed = segment.trim_start();
+    if !trimmed.trim_end().is_empty() {
+        out.push((start + segment.len() - trimmed.len(), trimmed));
+    }
+}
+
+pub(crate) fn is_env_assignment(word: &str) -> bool {
+    word.split_once('=').is_some_and(|(name, _)| {
+        !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
+/// A word of a command segment with quotes removed.
+pub(crate) struct Word {
+    pub(crate) text: String,
+    /// Byte offsets of the word as written (quotes included).
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+}
+
+/// Split a segment into words, honoring single and double quotes and
+/// backslash escapes.
+pub(crate) fn shell_words(segment: &str) -> Vec<Word> {
+    let mut words = Vec::new();
+    let mut current: Option<Word> = None;
+    let mut quote: Option<char> = None;
+    let mut chars = segment.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if quote.is_none() && c.is_whitespace() {
+            if let Some(word) = current.take() {
+                words.push(word);
+            }
+            continue;
+        }
+        let word = current.get_or_insert_with(|| Word {
+            text: String::new(),
+            start: i,
+            end: i,
+        });
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '\\') => {
+                if let Some((_, next)) = chars.next() {
+                    word.text.push(next);
+                }
+            }
+            // Inside double quotes a backslash only escapes these.
+            (Some('"'), '\\') => match chars.next_if(|(_, next)| "$`\"\\\n".contains(*next)) {
+                Some((_, next)) => word.text.push(next),
+                None => word.text.push(c),
+            },
+            _ => word.text.push(c),
+        }
+        word.end = chars.peek().map_or(segment.len(), |(next, _)| *next);
+    }
+    if let Some(word) = current {
+        words.push(word);
+    }
+    words
+}
+
+fn read_script_file(path: &Path) -> Option<String> {
+    if fs::metadata(path).ok()?.len() > MAX_SCRIPT_FILE_BYTES {
+        return None;
+    }
+    fs::read_to_string(path).ok()
+}
+
+/// Nearest directory at or above `dir` containing one of `names`.
+fn find_upward(dir: &Path, names: &[&str]) -> Option<PathBuf> {
+    dir.ancestors().take(MAX_PARENT_HOPS).find_map(|candidate| {
+        names
+            .iter()
+            .map(|name| candidate.join(name))
+            .find(|path| path.is_file())
+    })
+}
+
+// -----------------------------------------------------------------------------
+// package.json (npm, pnpm, yarn)
+// -----------------------------------------------------------------------------
+
+/// Resolve `runner [options] <subcommand> [<script>]` to package.json scripts.
+fn package_scripts(runner: &str, args: &[&str], cwd: &Path) -> Option<Vec<ResolvedScript>> {
+    let mut words = args.iter().copied();
+    let mut dir = cwd.to_path_buf();
+    let mut subcommand = None;
+    while let Some(word) = words.next() {
+        if let Some(value) = ["--prefix=", "--cwd=", "--dir="]
+            .iter()
+            .find_map(|flag| word.strip_prefix(flag))
+        {
+            dir = cwd.join(value);
+        } else if matches!(word, "--prefix" | "--cwd" | "--dir" | "-C") {
+            dir = cwd.join(words.next()?);
+        } else if !word.starts_with('-') {
+            subcommand = Some(word);
+            break;
+        }
+    }
+    let subcommand = subcommand?;
+
+    // `yarn <name>` runs a script only when <name> is not a builtin and
+    // package.json defines it; `npm run <name>` / `npm test` always do.
+    let (name, explicit) = match subcommand {
+        "run" | "run-script" | "rum" | "urn" => (words.find(|word| !word.starts_with('-'))?, true),
+        "test" | "t" | "tst" => ("test", true),
+        "start" | "stop" | "restart" => (subcommand, true),
+        _ if runner == "npm" || BUILTINS.contains(&subcommand) => return None,
+        _ => (subcommand, false),
+    };
+
+    let path = find_upward(&dir, &["package.json"])?;
+    let content = read_script_file(&path)?;
+    let value: serde_json::Value = serde_json::from_str(&content).ok()?;
+    let scripts = value.get("scripts")?.as_object()?;
+    if !explicit && !scripts.contains_key(name) {
+        return None;
+    }
+    let package_dir = path.parent()?.to_path_buf();
+    Some(
+        [
+            format!("pre{name}"),
+            name.to_string(),
+            format!("post{name}"),
+        ]
+        .into_iter()
+        .filter_map(|script| {
+            let body = scripts.get(&script)?.as_str()?.to_string();
+            Some(ResolvedScript {
+                name: script,
+                body,
+                dir: package_dir.clone(),
+            })
+        })
+        .collect(),
+    )
+}
+
+// -----------------------------------------------------------------------------
+// Makefile (make)
+// -----------------------------------------------------------------------------
+
+/// Resolve `make [options] [VAR=value] [targets]` to recipes.
+fn make_scripts(args: &[&str], cwd: &Path) -> Option<Vec<ResolvedScript>> {
+    let mut words = args.iter().copied().peekable();
+    let mut dir = cwd.to_path_buf();
+    let mut file = None;
+    let mut targets = Vec::new();
+    while let Some(word) = words.next() {
+        match word {
+            "-C" | "--directory" => dir = dir.join(words.next()?),
+            "-f" | "--file" | "--makefile" => file = Some(words.next()?),
+            "-o" | "-W" | "-I" | "--old-file" | "--what-if" | "--include-dir" => {
+                words.next()?;
+            }
+            "-j" | "--jobs" | "-l" | "--load-average" => {
+                words.next_if(|next| next.parse::<f64>().is_ok());
+            }
+            _ if word.starts_with('-') => {
+                if let Some(value) = word
+                    .strip_prefix("--directory=")
+                    .or_else(|| word.strip_prefix("-C"))
+                {
+                    dir = dir.join(value);
+                } else if let Some(value) = word
+                    .strip_prefix("--file=")
+                    .or_else(|| word.strip_prefix("--makefile="))
+                {
+                    file = Some(value);
+                }
+            }
+            _ if is_env_assignment(word) => {}
+            _ => targets.push(word.to_string()),
+        }
+    }
+
+    let path = match file {
+        Some(file) => dir.join(file),
+        None => MAKEFILE_NAMES
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file())?,
+    };
+    let makefile = Makefile::parse(&read_script_file(&path)?);
+    if targets.is_empty() {
+        targets.push(makefile.default_goal()?);
+    }
+
+    let mut scripts = Vec::new();
+    let mut seen = Vec::new();
+    for target in &targets {
+        makefile.collect(target, &dir, 0, &mut seen, &mut scripts);
+    }
+    Some(scripts)
+}
+
+/// The parts of a Makefile needed to find recipes: explicit rules and simple
+/// variable definitions.
+#[derive(Debug, Default)]
+struct Makefile {
+    rules: Vec<MakeRule>,
+    vars: HashMap<String, String>,
+}
+
+#[derive(Debug, Default)]
+struct MakeRule {
+    targets: Vec<String>,
+    prerequisites: Vec<String>,
+    recipe: Vec<String>,
+}
+
+impl Makefile {
+    fn parse(content: &str) -> Self {
+        let mut makefile = Self::default();
+        let mut current: Option<usize> = None;
+        for line in logical_lines(content) {
+            if let Some(recipe_line) = line.strip_prefix('\t') {
+                if let Some(index) = current {
+                    makefile.rules[index]
+                        .recipe
+                        .push(recipe_line.trim().to_string());
+                }
+                continue;
+            }
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            current = None;
+            if let Some((name, op, value)) = parse_make_assignment(trimmed) {
+                let value = value.trim().to_string();
+                match op {
+                    "+=" => {
+                        let entry = makefile.vars.entry(name.to_string()).or_default();
+                        if !entry.is_empty() {
+                            entry.push(' ');
+                        }
+                        entry.push_str(&value);
+                    }
+                    "?=" => {
+                        makefile.vars.entry(name.to_string()).or_insert(value);
+                    }
+                    _ => {
+                        makefile.vars.insert(name.to_string(), value);
+                    }
+                }
+                continue;
+            }
+            let Some((targets, rest)) = trimmed.split_once(':') else {
+                continue;
+            };
+            let rest = rest.strip_prefix(':').unwrap_or(rest);
+            let (prerequisites, inline_recipe) = rest.split_once(';').unwrap_or((rest, ""));
+            // `target: VAR = value` is a target-specific variable, not a rule.
+            if targets.trim().is_empty() || targets.contains('=') || prerequisites.contains('=') {
+                continue;
+            }
+            let mut rule = MakeRule {
+                targets: targets.split_whitespace().map(str::to_string).collect(),
+                prerequisites: prerequisites
+                    .split_whitespace()
+                    .map(str::to_string)
+                    .collect(),
+                recipe: Vec::new(),
+            };
+            if !inline_recipe.trim().is_empty() {
+                rule.recipe.push(inline_recipe.trim().to_string());
+            }
+            makefile.rules.push(rule);
+            current = Some(makefile.rules.len() - 1);
+        }
+        makefile
+    }
+
+    /// `.DEFAULT_GOAL`, or the first target that is not special or a pattern.
+    fn default_goal(&self) -> Option<String> {
+        if let Some(goal) = self.vars.get(".DEFAULT_GOAL") {
+            return Some(goal.clone());
+        }
+        self.rules
+            .iter()
+            .flat_map(|rule| rule.targets.iter())
+            .find(|target| !target.starts_with('.') && !target.contains('%'))
+            .cloned()
+    }
+
+    /// Append the recipes for `target`, prerequisites first.
+    fn collect(
+        &self,
+        target: &str,
+        dir: &Path,
+        depth: usize,
+        seen: &mut Vec<String>,
+        out: &mut Vec<ResolvedScript>,
+    ) {
+        if depth > MAX_DEPENDENCY_DEPTH || seen.iter().any(|done| done == target) {
+            return;
+        }
+        seen.push(target.to_string());
+        for rule in self
+            .rules
+            .iter()
+            .filter(|rule| rule.targets.iter().any(|t| t == target))
+        {
+            for prerequisite in &rule.prerequisites {
+                let prerequisite = self.expand(prerequisite, target, &rule.prerequisites);
+                self.collect(&prerequisite, dir, depth + 1, seen, out);
+            }
+            if rule.recipe.is_empty() {
+                continue;
+            }
+            let body = rule
+                .recipe
+                .iter()
+                .map(|line| {
+                    let line = line.trim_start_matches(['@', '-', '+']).trim_start();
+                    self.expand(line, target, &rule.prerequisites)
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            out.push(ResolvedScript {
+                name: target.to_string(),
+                body,
+                dir: dir.to_path_buf(),
+            });
+        }
+    }
+
+    /// Expand `$(VAR)`, `${VAR}`, `$@`, `$<`, `$^`, and `$$`. Unknown
+    /// variables and function calls are left as written.
+    fn expand(&self, text: &str, target: &str, prerequisites: &[String]) -> String {
+        let mut current = text.to_string();
+        for _ in 0..MAX_DEPENDENCY_DEPTH {
+            let next = self.expand_once(&current, target, prerequisites);
+            if next == current {
+                break;
+            }
+            current = next;
+        }
+        current.replace('\u{0}', "$")
+    }
+
+    fn expand_once(&self, text: &str, target: &str, prerequisites: &[String]) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(pos) = rest.find('$') {
+            out.push_str(&rest[..pos]);
+            let after = &rest[pos + 1..];
+            match after.chars().next() {
+                // Kept as a placeholder so later passes do not re-expand it.
+                Some('$') => {
+                    out.push('\u{0}');
+                    rest = &after[1..];
+                }
+                Some('@') => {
+                    out.push_str(target);
+                    rest = &after[1..];
+                }
+                Some('<') => {
+                    out.push_str(prerequisites.first().map_or("", String::as_str));
+                    rest = &after[1..];
+                }
+                Some('^') => {
+                    out.push_str(&prerequisites.join(" "));
+                    rest = &after[1..];
+                }
+                Some(open @ ('(' | '{')) => {
+                    let close = if open == '(' { ')' } else { '}' };
+                    let inner = &after[1..];
+                    let value = inner
+                        .find(close)
+                        .and_then(|end| self.vars.get(&inner[..end]).map(|value| (end, value)));
+                    if let Some((end, value)) = value {
+                        out.push_str(value);
+                        rest = &inner[end + 1..];
+                    } else {
+                        out.push('$');
+                        rest = after;
+                    }
+                }
+                _ => {
+                    out.push('$');
+                    rest = after;
+                }
+            }
+        }
+        out.push_str(rest);
+        out
+    }
+}
+
+/// Split `NAME op value` for `=`, `:=`, `::=`, `?=`, `+=`, and `!=`
+/// (optionally prefixed with `export` or `override`).
+fn parse_make_assignment(line: &str) -> Option<(&str, &str, &str)> {
+    let line = line
+        .strip_prefix("export ")
+        .or_else(|| line.strip_prefix("override "))
+        .unwrap_or(line)
+        .trim_start();
+    let eq = line.find('=')?;
+    let (lhs, value) = (&line[..eq], &line[eq + 1..]);
+    let (name, op) = ["::", ":", "?", "+", "!"]
+        .iter()
+        .find_map(|prefix| lhs.strip_suffix(prefix).map(|name| (name, *prefix)))
+        .unwrap_or((lhs, ""));
+    let name = name.trim();
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'));
+    let op = match op {
+        "+" => "+=",
+        "?" => "?=",
+        "!" => "!=",
+        _ => "=",
+    };
+    valid.then_some((name, op, value))
+}
+
+/// Join backslash-continued lines.
+fn logical_lines(content: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut pending = String::new();
+    for line in content.lines() {
+        if let Some(continued) = line.strip_suffix('\\') {
+            pending.push_str(continued);
+            pending.push(' ');
+        } else {
+            pending.push_str(line);
+            lines.push(std::mem::take(&mut pending));
+        }
+    }
+    if !pending.is_empty() {
+        lines.push(pending);
+    }
+    lines
+}
+
+// -----------------------------------------------------------------------------
+// justfile (just)
+// -----------------------------------------------------------------------------
+
+/// Resolve `just [options] [recipe [args...]]...` to recipe bodies.
+fn just_scripts(args: &[&str], cwd: &Path) -> Option<Vec<ResolvedScript>> {
+    let mut words = args.iter().copied();
+    let mut search_dir = cwd.to_path_buf();
+    let mut file = None;
+    let mut positional = Vec::new();
+    while let Some(word) = words.next() {
+        match word {
+            "-f" | "--justfile" => file = Some(cwd.join(words.next()?)),
+            "-d" | "--working-directory" => search_dir = cwd.join(words.next()?),
+            "--set" => {
+                words.next()?;
+                words.next()?;
+            }
+            "--shell" | "--shell-arg" | "--dotenv-path" | "--dotenv-filename" | "--color"
+            | "--chooser" | "--unstable-feature" => {
+                words.next()?;
+            }
+            _ if word.starts_with('-') || is_env_assignment(word) => {}
+            _ => positional.push(word),
+        }
+    }
+
+    let path = match file {
+        Some(file) => file,
+        None => find_upward(&search_dir, JUSTFILE_NAMES)?,
+    };
+    let justfile = Justfile::parse(&read_script_file(&path)?);
+    let dir = path.parent()?.to_path_buf();
+
+    // Positional words are recipes or arguments to the preceding recipe.
+    let mut recipes: Vec<&str> = positional
+        .into_iter()
+        .filter(|word| justfile.resolve(word).is_some())
+        .collect();
+    if recipes.is_empty() {
+        recipes.push(justfile.recipes.first()?.name.as_str());
+    }
+
+    let mut scripts = Vec::new();
+    let mut seen = Vec::new();
+    for recipe in recipes {
+        justfile.collect(recipe, &dir, 0, &mut seen, &mut scripts);
+    }
+    Some(scripts)
+}
+
+#[derive(Debug, Default)]
+struct Justfile {
+    recipes: Vec<JustRecipe>,
+    aliases: HashMap<String, String>,
+}
+
+#[derive(Debug, Default)]
+struct JustRecipe {
+    name: String,
+    dependencies: Vec<String>,
+    body: Vec<String>,
+    /// Shebang recipe run by a non-shell interpreter.
+    foreign: bool,
+}
+
+impl Justfile {
+    fn parse(content: &str) -> Self {
+        let mut justfile = Self::default();
+        let mut current: Option<usize> = None;
+        for line in logical_lines(content) {
+            if line.starts_with([' ', '\t']) {
+                let Some(index) = current else {
+                    continue;
+                };
+                let text = line.trim();
+                if text.is_empty() {
+                    continue;
+                }
+                let recipe = &mut justfile.recipes[index];
+                if recipe.body.is_empty() && text.starts_with("#!") {
+                    recipe.foreign = !["sh", "bash", "zsh", "dash"].iter().any(|shell| {
+                        text.split_whitespace()
+                            .any(|word| word.rsplit('/').next() == Some(*shell))
+                    });
+                    continue;
+                }
+                if !text.starts_with('#') {
+                    recipe
+                        .body
+                        .push(text.trim_start_matches(['@', '-']).to_string());
+                }
+                continue;
+            }
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+            current = None;
+            if trimmed.starts_with(['#', '[']) {
+                continue;
+            }
+            if let Some(alias) = trimmed.strip_prefix("alias ") {
+                if let Some((name, target)) = alias.split_once(":=") {
+                    justfile
+                        .aliases
+                        .insert(name.trim().to_string(), target.trim().to_string());
+                }
+                continue;
+            }
+            if let Some(recipe) = parse_just_header(trimmed) {
+                justfile.recipes.push(recipe);
+                current = Some(justfile.recipes.len() - 1);
+            }
+        }
+        justfile
+    }
+
+    fn resolve(&self, name: &str) -> Option<&JustRecipe> {
+        let name = self.aliases.get(name).map_or(name, String::as_str);
+        self.recipes.iter().find(|recipe| recipe.name == name)
+    }
+
+    /// Append the bodies for `name`, dependencies first.
+    fn collect(
+        &self,
+        name: &str,
+        dir: &Path,
+        depth: usize,
+        seen: &mut Vec<String>,
+        out: &mut Vec<ResolvedScript>,
+    ) {
+        let Some(recipe) = self.resolve(name) else {
+            return;
+        };
+        if depth > MAX_DEPENDENCY_DEPTH || seen.contains(&recipe.name) {
+            return;
+        }
+        seen.push(recipe.name.clone());
+        for dependency in &recipe.dependencies {
+            self.collect(dependency, dir, depth + 1, seen, out);
+        }
+        if !recipe.foreign && !recipe.body.is_empty() {
+            out.push(ResolvedScript {
+                name: recipe.name.clone(),
+                body: recipe.body.join("\n"),
+                dir: dir.to_path_buf(),
+            });
+        }
+    }
+}
+
+/// Parse `[@]name [params...]: [dependencies...]`, rejecting settings,
+/// assignments (`x := y`), and other top-level items.
+fn parse_just_header(line: &str) -> Option<JustRecipe> {
+    let line = line.strip_prefix('@').unwrap_or(line);
+    let colon = line.find(':')?;
+    if line[colon + 1..].starts_with('=') {
+        return None;
+    }
+    let mut head = line[..colon].split_whitespace();
+    let name = head.next()?;
+    if matches!(name, "set" | "export" | "import" | "mod")
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-'))
+    {
+        return None;
+    }
+    let dependencies = line[colon + 1..]
+        .split_whitespace()
+        .filter(|word| *word != "&&")
+        .map(|word| word.trim_start_matches('(').to_string())
+        .filter(|word| {
+            !word.is_empty()
+                && word
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-'))
+        })
+        .collect();
+    Some(JustRecipe {
+        name: name.to_string(),
+        dependencies,
+        body: Vec::new(),
+        foreign: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(scripts: &str) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("package.json"),
+            format!(r#"{{"name": "app", "scripts": {scripts}}}"#),
+        )
+        .unwrap();
+        dir
+    }
+
+    fn with_file(name: &str, content: &str) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(name), content).unwrap();
+        dir
+    }
+
+    fn names(command: &str, cwd: &Path) -> Vec<String> {
+        extract_script_invocations(command, cwd)
+            .into_iter()
+            .map(|invocation| invocation.name)
+            .collect()
+    }
+
+    fn bodies(command: &str, cwd: &Path) -> Vec<String> {
+        extract_script_invocations(command, cwd)
+            .into_iter()
+            .map(|invocation| invocation.body)
+            .collect()
+    }
+
+    #[test]
+    fn resolves_npm_run_with_lifecycle_scripts() {
+        let dir = project(
+            r#"{"prebuild": "rm -rf dist", "build": "tsc", "postbuild": "echo done", "test": "jest"}"#,
+        );
+        assert_eq!(
+            names("npm run build", dir.path()),
+            ["prebuild", "build", "postbuild"]
+        );
+        assert_eq!(names("npm test", dir.path()), ["test"]);
+        assert_eq!(
+            names("NODE_ENV=ci npm run-script test", dir.path()),
+            ["test"]
+        );
+        assert!(names("npm run missing", dir.path()).is_empty());
+        assert!(names("npm install", dir.path()).is_empty());
+
+        let invocation = &extract_script_invocations("ls && npm run build", dir.path())[0];
+        assert_eq!(invocation.runner, "npm");
+        assert_eq!(invocation.body, "rm -rf dist");
+        assert_eq!((invocation.start, invocation.end), (6, 19));
+    }
+
+    #[test]
+    fn resolves_bare_yarn_and_pnpm_scripts_but_not_builtins() {
+        let dir = project(r#"{"clean": "rm -rf node_modules", "install": "echo hi"}"#);
+        assert_eq!(names("yarn clean", dir.path()), ["clean"]);
+        assert_eq!(names("pnpm clean", dir.path()), ["clean"]);
+        assert_eq!(names("pnpm run clean", dir.path()), ["clean"]);
+        assert!(names("yarn install", dir.path()).is_empty());
+        assert!(names("npm clean", dir.path()).is_empty());
+    }
+
+    #[test]
+    fn follows_prefix_and_parent_directories() {
+        let dir = project(r#"{"nuke": "rm -rf ~"}"#);
+        let nested = dir.path().join("src").join("lib");
+        fs::create_dir_all(&nested).unwrap();
+        assert_eq!(names("npm run nuke", &nested), ["nuke"]);
+
+        let other = tempfile::tempdir().unwrap();
+        let command = format!("npm --prefix {} run nuke", dir.path().display());
+        assert_eq!(names(&command, other.path()), ["nuke"]);
+    }
+
+    #[test]
+    fn resolves_make_targets_prerequisites_and_variables() {
+        let dir = with_file(
+            "Makefile",
+            "BUILD_DIR := /\nRM = rm -rf\n\n.PHONY: all clean\n\nall: build\n\nbuild:\n\t@cargo build\n\nclean: \\\n  wipe\n\t-$(RM) $(BUILD_DIR)\n\nwipe:\n\techo wiping $@ $$HOME\n",
+        );
+        assert_eq!(names("make", dir.path()), ["build"]);
+        assert_eq!(bodies("make", dir.path()), ["cargo build"]);
+        assert_eq!(names("make clean", dir.path()), ["wipe", "clean"]);
+        assert_eq!(
+            bodies("make -j 4 clean V=1", dir.path()),
+            ["echo wiping wipe $HOME", "rm -rf /"]
+        );
+        assert!(names("make missing", dir.path()).is_empty());
+
+        let other = tempfile::tempdir().unwrap();
+        let command = format!("make -C {} build", dir.path().display());
+        assert_eq!(names(&command, other.path()), ["build"]);
+    }
+
+    #[test]
+    fn resolves_just_recipes_dependencies_and_aliases() {
+        let dir = with_file(
+            "justfile",
+            "set shell := [\"bash\", \"-c\"]\nversion := \"1.0\"\nalias c := clean\n\ndefault: test\n\ntest:\n    cargo test\n\n[confirm]\nclean: test\n    @rm -rf target\n    # comment\n\ndeploy env:\n    ./deploy.sh {{env}}\n\nreport:\n    #!/usr/bin/env python3\n    import shutil; shutil.rmtree('/')\n",
+        );
+        assert_eq!(names("just", dir.path()), ["test"]);
+        assert_eq!(names("just clean", dir.path()), ["test", "clean"]);
+        assert_eq!(
+            bodies("just c", dir.path()),
+            ["cargo test", "rm -rf target"]
+        );
+        assert_eq!(names("just deploy prod", dir.path()), ["deploy"]);
+        assert!(names("just report", dir.path()).is_empty());
+
+        let nested = dir.path().join("crates");
+        fs::create_dir_all(&nested).unwrap();
+        assert_eq!(names("just clean", &nested), ["test", "clean"]);
+    }
+
+    #[test]
+    fn quoted_separators_do_not_split_segments() {
+        let segments: Vec<&str> = command_segments(r#"echo "a; npm run x" && npm run y"#)
+            .into_iter()
+            .map(|(_, segment)| segment)
+            .collect();
+        assert_eq!(segments, [r#"echo "a; npm run x" "#, "npm run y"]);
+    }
+}
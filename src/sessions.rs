@@ -0,0 +1,455 @@
+//! Terminal multiplexer payload introspection.
+//!
+//! tmux and screen can run a command in a detached session, where it keeps
+//! going after the agent's shell has returned. This module finds the command
+//! text such invocations would run, so the evaluator can check it through the
+//! normal pipeline:
+//!
+//! ```text
+//! tmux send-keys -t work 'rm -rf /srv' Enter     -> rm -rf /srv
+//! tmux new-session -d 'git reset --hard'         -> git reset --hard
+//! tmux run-shell 'dropdb app'                    -> dropdb app
+//! screen -S work -X stuff 'rm -rf /srv\n'        -> rm -rf /srv
+//! screen -dmS job bash -c 'rm -rf /srv'          -> bash -c 'rm -rf /srv'
+//! ```
+//!
+//! `nohup` and `setsid` are plain prefix wrappers and are handled by
+//! [`crate::normalize::strip_wrapper_prefixes`].
+
+use crate::scripts::{Word, command_segments, is_env_assignment, shell_words};
+
+/// Most payloads returned for a single command.
+const MAX_PAYLOADS: usize = 8;
+
+/// Key names that submit the typed line in `tmux send-keys`.
+const ENTER_KEYS: &[&str] = &["Enter", "KPEnter", "C-m", "C-j", "^M", "^J"];
+
+/// Single-letter option syntax of a tmux command or of screen.
+struct OptionSpec {
+    /// Options without a value (may be clustered: `-dm`).
+    flags: &'static str,
+    /// Options with a value (`-t work` or `-twork`).
+    values: &'static str,
+    /// The option naming the target session or pane.
+    target: char,
+    /// Options that end option parsing (screen's `-X command`).
+    stop: &'static str,
+}
+
+/// A tmux command that runs a shell command or types keys.
+struct TmuxCommand {
+    names: &'static [&'static str],
+    options: OptionSpec,
+}
+
+/// Options before the tmux command (`tmux -L socket new ...`).
+const TMUX_GLOBAL: OptionSpec = OptionSpec {
+    flags: "2CDluNv",
+    values: "LSfT",
+    target: 'S',
+    stop: "",
+};
+
+const SEND_KEYS: TmuxCommand = TmuxCommand {
+    names: &["send-keys", "send"],
+    options: OptionSpec {
+        flags: "FHKlMRX",
+        values: "Nt",
+        target: 't',
+        stop: "",
+    },
+};
+
+/// tmux commands whose trailing arguments are a shell command.
+const TMUX_SHELL_COMMANDS: &[TmuxCommand] = &[
+    TmuxCommand {
+        names: &["new-session", "new"],
+        options: OptionSpec {
+            flags: "AdDEPX",
+            values: "cefFnstxy",
+            target: 's',
+            stop: "",
+        },
+    },
+    TmuxCommand {
+        names: &["new-window", "neww"],
+        options: OptionSpec {
+            flags: "abdkPS",
+            values: "ceFnt",
+            target: 't',
+            stop: "",
+        },
+    },
+    TmuxCommand {
+        names: &["split-window", "splitw"],
+        options: OptionSpec {
+            flags: "bdfhIvPZ",
+            values: "celpFt",
+            target: 't',
+            stop: "",
+        },
+    },
+    TmuxCommand {
+        names: &["respawn-pane", "respawnp", "respawn-window", "respawnw"],
+        options: OptionSpec {
+            flags: "k",
+            values: "cet",
+            target: 't',
+            stop: "",
+        },
+    },
+    TmuxCommand {
+        names: &["run-shell", "run"],
+        options: OptionSpec {
+            flags: "bC",
+            values: "dt",
+            target: 't',
+            stop: "",
+        },
+    },
+];
+
+const SCREEN: OptionSpec = OptionSpec {
+    flags: "aAdDfiklLmOqrRUwWx",
+    values: "cehpsStT",
+    target: 'S',
+    stop: "X",
+};
+
+/// A command that tmux or screen would run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionPayload {
+    /// `tmux` or `screen`.
+    pub tool: &'static str,
+    /// How the command is passed (`send-keys`, `new-session`, `stuff`, ...).
+    pub via: String,
+    /// Target session or pane (`-t` / `-S`), when given.
+    pub target: Option<String>,
+    /// The command text.
+    pub command: String,
+    /// Byte offset of the first payload word in the command.
+    pub start: usize,
+    /// Byte offset one past the last payload word.
+    pub end: usize,
+}
+
+impl SessionPayload {
+    /// Short preview of the payload for denial messages.
+    #[must_use]
+    pub fn preview(&self) -> String {
+        const MAX_PREVIEW: usize = 80;
+        let command = self.command.trim().replace('\n', "; ");
+        if command.chars().count() <= MAX_PREVIEW {
+            return command;
+        }
+        let truncated: String = command.chars().take(MAX_PREVIEW).collect();
+        format!("{truncated}...")
+    }
+}
+
+/// Cheap pre-check: does the command mention `tmux` or `screen` at all?
+#[must_use]
+pub fn mentions_session_tool(command: &str) -> bool {
+    command
+        .split(|c: char| c.is_whitespace() || matches!(c, ';' | '&' | '|' | '(' | ')'))
+        .any(|word| matches!(word.rsplit('/').next(), Some("tmux" | "screen")))
+}
+
+/// Find the commands tmux or screen invocations in `command` would run.
+#[must_use]
+pub fn extract_session_payloads(command: &str) -> Vec<SessionPayload> {
+    let mut payloads = Vec::new();
+    if !mentions_session_tool(command) {
+        return payloads;
+    }
+    for (start, segment) in command_segments(command) {
+        let words = shell_words(segment);
+        let mut words = words.iter().skip_while(|word| {
+            matches!(word.text.as_str(), "sudo" | "nohup" | "setsid")
+                || is_env_assignment(&word.text)
+        });
+        let Some(program) = words.next() else {
+            continue;
+        };
+        let rest: Vec<&Word> = words.collect();
+        let payload = match program.text.rsplit('/').next() {
+            Some("tmux") => tmux_payload(&rest),
+            Some("screen") => screen_payload(segment, &rest),
+            _ => None,
+        };
+        if let Some(mut payload) = payload {
+            payload.start += start;
+            payload.end += start;
+            payloads.push(payload);
+            if payloads.len() == MAX_PAYLOADS {
+                break;
+            }
+        }
+    }
+    payloads
+}
+
+fn tmux_payload(words: &[&Word]) -> Option<SessionPayload> {
+    let global = parse_options(words, &TMUX_GLOBAL)?;
+    let mut i = global.count;
+    let name = words.get(i)?.text.as_str();
+    i += 1;
+
+    if SEND_KEYS.names.contains(&name) {
+        let options = parse_options(&words[i..], &SEND_KEYS.options)?;
+        let keys = &words[i + options.count..];
+        let literal = options.flags.contains('l');
+        // Each argument is typed as is, with no space in between.
+        let mut text = String::new();
+        for key in keys {
+            let key = key.text.as_str();
+            if literal {
+                text.push_str(key);
+            } else if ENTER_KEYS.contains(&key) {
+                text.push('\n');
+            } else if key == "Space" {
+                text.push(' ');
+            } else if !is_key_name(key) {
+                text.push_str(key);
+            }
+        }
+        return payload("tmux", "send-keys", options.target, text, keys);
+    }
+
+    let spec = TMUX_SHELL_COMMANDS
+        .iter()
+        .find(|spec| spec.names.contains(&name))?;
+    let options = parse_options(&words[i..], &spec.options)?;
+    let args = &words[i + options.count..];
+    // tmux joins the arguments and hands them to the shell, as ssh does.
+    let text = args
+        .iter()
+        .map(|word| word.text.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+    payload("tmux", spec.names[0], options.target, text, args)
+}
+
+fn screen_payload(segment: &str, words: &[&Word]) -> Option<SessionPayload> {
+    let options = parse_options(words, &SCREEN)?;
+    let args = &words[options.count..];
+
+    if options.stopped {
+        // `-X stuff TEXT` types TEXT into the session; other -X commands
+        // are screen commands, not shell input.
+        let (command, rest) = args.split_first()?;
+        if command.text != "stuff" {
+            return None;
+        }
+        let text_word = rest.first()?;
+        let text = unescape_stuff(&text_word.text);
+        return payload(
+            "screen",
+            "stuff",
+            options.target,
+            text,
+            std::slice::from_ref(text_word),
+        );
+    }
+
+    // Attaching (`-r`, `-x`) or detaching (`-d` without `-m`) names a session
+    // instead of a command.
+    let creates = options.flags.contains('m');
+    if options.flags.contains(['r', 'R', 'x']) || (options.flags.contains(['d', 'D']) && !creates) {
+        return None;
+    }
+    // `screen [-dm] cmd args...` runs cmd directly, without a shell, so the
+    // words are kept as written.
+    let (first, last) = (args.first()?, args.last()?);
+    let via = if creates {
+        "detached session"
+    } else {
+        "session"
+    };
+    let text = segment[first.start..last.end].to_string();
+    payload("screen", via, options.target, text, args)
+}
+
+fn payload(
+    tool: &'static str,
+    via: &str,
+    target: Option<String>,
+    command: String,
+    words: &[&Word],
+) -> Option<SessionPayload> {
+    if command.trim().is_empty() {
+        return None;
+    }
+    Some(SessionPayload {
+        tool,
+        via: via.to_string(),
+        target,
+        command,
+        start: words.first()?.start,
+        end: words.last()?.end,
+    })
+}
+
+/// What [`parse_options`] found.
+#[derive(Default)]
+struct ParsedOptions {
+    /// Number of words that were options (and their values).
+    count: usize,
+    /// Value of the target option.
+    target: Option<String>,
+    /// Flags seen, including a stop option.
+    flags: String,
+    /// Parsing ended at a stop option.
+    stopped: bool,
+}
+
+/// Parse leading options; `None` for an option the spec does not know.
+fn parse_options(words: &[&Word], spec: &OptionSpec) -> Option<ParsedOptions> {
+    let mut parsed = ParsedOptions::default();
+    while let Some(word) = words.get(parsed.count) {
+        let text = word.text.as_str();
+        if text == "--" {
+            parsed.count += 1;
+            break;
+        }
+        let Some(cluster) = text.strip_prefix('-').filter(|c| !c.is_empty()) else {
+            break;
+        };
+        parsed.count += 1;
+        for (pos, flag) in cluster.char_indices() {
+            if spec.stop.contains(flag) {
+                parsed.flags.push(flag);
+                parsed.stopped = true;
+                return Some(parsed);
+            }
+            if spec.flags.contains(flag) {
+                parsed.flags.push(flag);
+                continue;
+            }
+            if !spec.values.contains(flag) {
+                return None;
+            }
+            let inline = &cluster[pos + flag.len_utf8()..];
+            let value = if inline.is_empty() {
+                parsed.count += 1;
+                words.get(parsed.count - 1)?.text.clone()
+            } else {
+                inline.to_string()
+            };
+            if flag == spec.target {
+                parsed.target = Some(value);
+            }
+            break;
+        }
+    }
+    Some(parsed)
+}
+
+/// tmux key names (`C-c`, `M-x`, `Escape`, `F1`, ...) that do not type text.
+fn is_key_name(word: &str) -> bool {
+    const NAMED_KEYS: &[&str] = &[
+        "Escape", "Tab", "BTab", "BSpace", "Up", "Down", "Left", "Right", "Home", "End", "PageUp",
+        "PageDown", "PgUp", "PgDn", "NPage", "PPage", "IC", "DC", "Insert", "Delete",
+    ];
+    if NAMED_KEYS.contains(&word) {
+        return true;
+    }
+    if let Some(rest) = word
+        .strip_prefix("C-")
+        .or_else(|| word.strip_prefix("M-"))
+        .or_else(|| word.strip_prefix("S-"))
+    {
+        return rest.chars().count() == 1 || is_key_name(rest);
+    }
+    word.strip_prefix('F')
+        .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Translate the escapes screen's `stuff` understands for line endings.
+fn unescape_stuff(text: &str) -> String {
+    text.replace("\\r\\n", "\n")
+        .replace("\\n", "\n")
+        .replace("\\r", "\n")
+        .replace("\\015", "\n")
+        .replace("\\012", "\n")
+        .replace("^M", "\n")
+        .replace("^J", "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commands_of(command: &str) -> Vec<String> {
+        extract_session_payloads(command)
+            .into_iter()
+            .map(|payload| payload.command)
+            .collect()
+    }
+
+    #[test]
+    fn extracts_tmux_payloads() {
+        let payloads = extract_session_payloads("tmux send-keys -t work 'rm -rf /srv' Enter");
+        assert_eq!(payloads.len(), 1);
+        assert_eq!(payloads[0].via, "send-keys");
+        assert_eq!(payloads[0].target.as_deref(), Some("work"));
+        assert_eq!(payloads[0].command, "rm -rf /srv\n");
+
+        assert_eq!(
+            commands_of("tmux send -t0 'git reset' Space --hard C-m"),
+            vec!["git reset --hard\n"]
+        );
+        assert_eq!(
+            commands_of("tmux send-keys -l -t0 'rm -rf /srv' Enter"),
+            vec!["rm -rf /srvEnter"]
+        );
+        assert_eq!(
+            commands_of("tmux -L agents new-session -d -s job 'git clean -fdx'"),
+            vec!["git clean -fdx"]
+        );
+        assert_eq!(
+            commands_of("tmux split-window -h -c /srv rm -rf data"),
+            vec!["rm -rf data"]
+        );
+        assert_eq!(
+            commands_of("tmux run-shell -b 'dropdb app'"),
+            vec!["dropdb app"]
+        );
+        assert_eq!(
+            commands_of("tmux send-keys -t work C-c"),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn extracts_screen_payloads() {
+        let payloads = extract_session_payloads(r"screen -S work -X stuff 'rm -rf /srv\n'");
+        assert_eq!(payloads[0].via, "stuff");
+        assert_eq!(payloads[0].target.as_deref(), Some("work"));
+        assert_eq!(payloads[0].command, "rm -rf /srv\n");
+        assert_eq!(
+            commands_of(r#"screen -X stuff "git clean -fdx\r""#),
+            vec!["git clean -fdx\n"]
+        );
+
+        let command = "nohup screen -dmS job bash -c 'rm -rf /srv' &";
+        let payloads = extract_session_payloads(command);
+        assert_eq!(payloads[0].via, "detached session");
+        assert_eq!(payloads[0].command, "bash -c 'rm -rf /srv'");
+        assert_eq!(
+            &command[payloads[0].start..payloads[0].end],
+            "bash -c 'rm -rf /srv'"
+        );
+    }
+
+    #[test]
+    fn ignores_commands_that_run_nothing() {
+        assert!(commands_of("tmux ls").is_empty());
+        assert!(commands_of("tmux attach -t work").is_empty());
+        assert!(commands_of("tmux kill-server").is_empty());
+        assert!(commands_of("screen -ls").is_empty());
+        assert!(commands_of("screen -r work").is_empty());
+        assert!(commands_of("screen -S work -X quit").is_empty());
+        assert!(commands_of("echo 'tmux send-keys rm Enter'").is_empty());
+    }
+}
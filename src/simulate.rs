@@ -10,6 +10,16 @@
 //! 2. **Hook JSON** - `{"tool_name":"Bash","tool_input":{"command":"..."}}`
 //! 3. **Structured decision log** - Schema-versioned log entries (future)
 //!
+//! With `--from-history`, the parser additionally understands shell history
+//! files and exported Claude transcripts:
+//!
+//! - **bash** - plain lines, `#<epoch>` timestamp comments (`HISTTIMEFORMAT`)
+//! - **zsh** - extended history (`: <epoch>:<elapsed>;<command>`)
+//! - **Transcript JSONL** - assistant messages with Bash `tool_use` blocks
+//!
+//! Backslash-continued lines are joined so multi-line commands are evaluated
+//! as a single command.
+//!
 //! # Design principles
 //!
 //! - **Streaming**: Process line-by-line, never load entire file into memory
@@ -18,6 +28,7 @@
 //! - **Panic-free**: Parser never panics on arbitrary input
 
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 use std::io::{BufRead, BufReader, Read};
 
 /// Schema version for simulate output (for future compatibility).
@@ -33,6 +44,10 @@ pub enum SimulateInputFormat {
     HookJson,
     /// Structured decision log entry (schema-versioned)
     DecisionLog,
+    /// Shell history entry (bash or zsh extended history)
+    ShellHistory,
+    /// Bash `tool_use` block from an exported Claude transcript
+    Transcript,
 }
 
 /// Result of parsing a single line.
@@ -109,6 +124,9 @@ pub struct SimulateParser<R: Read> {
     limits: SimulateLimits,
     stats: ParseStats,
     strict: bool,
+    history: bool,
+    /// Extra commands from a transcript line with several Bash tool uses.
+    pending: VecDeque<String>,
 }
 
 impl<R: Read> SimulateParser<R> {
@@ -119,6 +137,8 @@ impl<R: Read> SimulateParser<R> {
             limits,
             stats: ParseStats::default(),
             strict: false,
+            history: false,
+            pending: VecDeque::new(),
         }
     }
 
@@ -129,6 +149,13 @@ impl<R: Read> SimulateParser<R> {
         self
     }
 
+    /// Enable shell history mode (bash/zsh history files and transcripts).
+    #[must_use]
+    pub const fn from_history(mut self, history: bool) -> Self {
+        self.history = history;
+        self
+    }
+
     /// Get current parsing statistics.
     pub const fn stats(&self) -> &ParseStats {
         &self.stats
@@ -144,6 +171,15 @@ impl<R: Read> SimulateParser<R> {
     /// Returns `None` when input is exhausted or a limit is reached.
     /// Returns `Some(Err(...))` in strict mode when a malformed line is encountered.
     pub fn next_line(&mut self) -> Option<Result<ParsedLine, ParseError>> {
+        // Drain commands queued from a multi-command transcript line first
+        if let Some(command) = self.pending.pop_front() {
+            self.stats.commands_extracted += 1;
+            return Some(Ok(ParsedLine::Command {
+                command,
+                format: SimulateInputFormat::Transcript,
+            }));
+        }
+
         // Check limits before reading
         if let Some(max_lines) = self.limits.max_lines {
             if self.stats.lines_read >= max_lines {
@@ -175,7 +211,16 @@ impl<R: Read> SimulateParser<R> {
         }
 
         // Parse the line
-        let parsed = parse_line(&line, self.limits.max_command_bytes);
+        let parsed = if self.history {
+            if let Err(e) = self.read_continuation(&mut line) {
+                return Some(Err(e));
+            }
+            let (parsed, rest) = parse_history_line(&line, self.limits.max_command_bytes);
+            self.pending.extend(rest);
+            parsed
+        } else {
+            parse_line(&line, self.limits.max_command_bytes)
+        };
 
         // Update stats
         match &parsed {
@@ -196,6 +241,33 @@ impl<R: Read> SimulateParser<R> {
         Some(Ok(parsed))
     }
 
+    /// Join backslash-continued history lines into a single command.
+    ///
+    /// zsh and bash store multi-line commands as consecutive lines ending in
+    /// `\`; the continuation lines count toward the line and byte limits.
+    fn read_continuation(&mut self, line: &mut String) -> Result<(), ParseError> {
+        loop {
+            let body = line.trim_end_matches(['\n', '\r']);
+            if !body.ends_with('\\') || body.ends_with("\\\\") {
+                return Ok(());
+            }
+            let keep = body.len() - 1;
+            line.truncate(keep);
+            line.push('\n');
+
+            let mut next = String::new();
+            match self.reader.read_line(&mut next) {
+                Ok(0) => return Ok(()),
+                Ok(n) => {
+                    self.stats.lines_read += 1;
+                    self.stats.bytes_read += n;
+                    line.push_str(&next);
+                }
+                Err(e) => return Err(ParseError::Io(e.to_string())),
+            }
+        }
+    }
+
     /// Collect all parsed commands (for small inputs).
     ///
     /// Returns commands and final stats. In strict mode, stops on first error.
@@ -375,6 +447,122 @@ fn try_parse_hook_json(line: &str, max_command_bytes: Option<usize>) -> Option<P
     })
 }
 
+/// Parse a line from a shell history file or exported transcript.
+///
+/// Returns the parsed line plus any additional commands found on the same
+/// line (transcript messages may contain several Bash tool uses).
+fn parse_history_line(line: &str, max_command_bytes: Option<usize>) -> (ParsedLine, Vec<String>) {
+    let trimmed = line.trim();
+
+    if trimmed.is_empty() {
+        return (ParsedLine::Empty, Vec::new());
+    }
+
+    // bash HISTTIMEFORMAT timestamp comment: `#1700000000`
+    if let Some(rest) = trimmed.strip_prefix('#') {
+        if !rest.is_empty() && rest.bytes().all(|b| b.is_ascii_digit()) {
+            return (
+                ParsedLine::Ignore {
+                    reason: "history timestamp",
+                },
+                Vec::new(),
+            );
+        }
+    }
+
+    // zsh extended history: `: 1700000000:0;git status`
+    if let Some(command) = strip_zsh_extended_prefix(trimmed) {
+        return (
+            history_command(command.trim(), max_command_bytes),
+            Vec::new(),
+        );
+    }
+
+    if trimmed.starts_with('{') {
+        if let Some(parsed) = try_parse_hook_json(trimmed, max_command_bytes) {
+            return (parsed, Vec::new());
+        }
+        if let Some(mut commands) = try_parse_transcript_json(trimmed, max_command_bytes) {
+            if commands.is_empty() {
+                return (
+                    ParsedLine::Ignore {
+                        reason: "transcript entry without Bash tool use",
+                    },
+                    Vec::new(),
+                );
+            }
+            let first = commands.remove(0);
+            return (
+                ParsedLine::Command {
+                    command: first,
+                    format: SimulateInputFormat::Transcript,
+                },
+                commands,
+            );
+        }
+    }
+
+    (history_command(trimmed, max_command_bytes), Vec::new())
+}
+
+/// Strip the zsh `EXTENDED_HISTORY` prefix (`: <epoch>:<elapsed>;`).
+fn strip_zsh_extended_prefix(line: &str) -> Option<&str> {
+    let rest = line.strip_prefix(": ")?;
+    let (meta, command) = rest.split_once(';')?;
+    let (start, elapsed) = meta.split_once(':')?;
+    let is_number = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    if is_number(start.trim()) && is_number(elapsed.trim()) {
+        Some(command)
+    } else {
+        None
+    }
+}
+
+fn history_command(command: &str, max_command_bytes: Option<usize>) -> ParsedLine {
+    match parse_plain_command(command, max_command_bytes) {
+        ParsedLine::Command { command, .. } => ParsedLine::Command {
+            command,
+            format: SimulateInputFormat::ShellHistory,
+        },
+        other => other,
+    }
+}
+
+/// Try to extract Bash commands from a Claude transcript JSONL entry.
+///
+/// Returns `None` if the line is not a transcript message, otherwise the
+/// (possibly empty) list of Bash commands found in `tool_use` blocks.
+/// Commands over the length limit are skipped.
+fn try_parse_transcript_json(line: &str, max_command_bytes: Option<usize>) -> Option<Vec<String>> {
+    let value: serde_json::Value = serde_json::from_str(line).ok()?;
+    let content = value.get("message")?.get("content")?;
+
+    let Some(blocks) = content.as_array() else {
+        return Some(Vec::new());
+    };
+
+    let commands = blocks
+        .iter()
+        .filter(|block| block.get("type").and_then(serde_json::Value::as_str) == Some("tool_use"))
+        .filter(|block| {
+            matches!(
+                block.get("name").and_then(serde_json::Value::as_str),
+                Some("Bash" | "launch-process")
+            )
+        })
+        .filter_map(|block| {
+            block
+                .get("input")?
+                .get("command")?
+                .as_str()
+                .map(ToString::to_string)
+        })
+        .filter(|command| max_command_bytes.is_none_or(|max| command.len() <= max))
+        .collect();
+
+    Some(commands)
+}
+
 /// Parse a line as decision log format (future schema).
 fn parse_decision_log(line: &str, max_command_bytes: Option<usize>) -> ParsedLine {
     use base64::Engine;
@@ -817,7 +1005,7 @@ where
     let ordered_packs = REGISTRY.expand_enabled_ordered(&enabled_packs);
     let keywords = REGISTRY.collect_enabled_keywords(&enabled_packs);
     let keyword_index = REGISTRY.build_enabled_keyword_index(&ordered_packs);
-    let compiled_overrides = config.overrides.compile();
+    let compiled_overrides = config.compile_overrides();
     let allowlists = crate::allowlist::load_default_allowlists();
     let heredoc_settings = config.heredoc_settings();
 
@@ -856,6 +1044,25 @@ pub fn run_simulation_from_reader<R: std::io::Read>(
     Ok(run_simulation(commands, parse_stats, config, sim_config))
 }
 
+/// Run simulation over a bash/zsh history file or exported transcript.
+///
+/// # Errors
+///
+/// Returns `ParseError` if the input cannot be parsed.
+pub fn run_simulation_from_history<R: std::io::Read>(
+    reader: R,
+    limits: SimulateLimits,
+    config: &Config,
+    sim_config: SimulationConfig,
+    strict: bool,
+) -> Result<SimulationResult, ParseError> {
+    let parser = SimulateParser::new(reader, limits)
+        .strict(strict)
+        .from_history(true);
+    let (commands, parse_stats) = parser.collect_commands()?;
+    Ok(run_simulation(commands, parse_stats, config, sim_config))
+}
+
 // =============================================================================
 // Output Formatting (git_safety_guard-1gt.8.3)
 // =============================================================================
@@ -892,6 +1099,7 @@ pub struct SimulateJsonOutput {
     pub schema_version: u32,
     pub totals: SimulateJsonTotals,
     pub rules: Vec<SimulateJsonRule>,
+    pub packs: Vec<SimulateJsonPack>,
     pub errors: SimulateJsonErrors,
 }
 
@@ -911,6 +1119,13 @@ pub struct SimulateJsonRule {
     pub exemplars: Vec<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulateJsonPack {
+    pub pack_id: String,
+    pub count: usize,
+    pub by_decision: std::collections::BTreeMap<String, usize>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SimulateJsonErrors {
     pub malformed_count: usize,
@@ -1002,7 +1217,15 @@ pub fn format_pretty_output(result: &SimulationResult, config: &SimulateOutputCo
     if !result.packs.is_empty() {
         output.push_str("Packs Summary:\n");
         for pack in &result.packs {
-            output.push_str(&format!("  {:>5} x {}\n", pack.count, pack.pack_id));
+            let count_for = |key: &str| pack.by_decision.get(key).copied().unwrap_or(0);
+            output.push_str(&format!(
+                "  {:>5} x {} (deny: {}, warn: {}, allow: {})\n",
+                pack.count,
+                pack.pack_id,
+                count_for("deny"),
+                count_for("warn"),
+                count_for("allow")
+            ));
         }
         output.push('\n');
     }
@@ -1068,6 +1291,16 @@ pub fn format_json_output(
         })
         .collect();
 
+    let json_packs: Vec<SimulateJsonPack> = result
+        .packs
+        .into_iter()
+        .map(|p| SimulateJsonPack {
+            pack_id: p.pack_id,
+            count: p.count,
+            by_decision: p.by_decision.into_iter().collect(),
+        })
+        .collect();
+
     let output = SimulateJsonOutput {
         schema_version: result.schema_version,
         totals: SimulateJsonTotals {
@@ -1077,6 +1310,7 @@ pub fn format_json_output(
             denied: result.summary.deny_count,
         },
         rules: json_rules,
+        packs: json_packs,
         errors: SimulateJsonErrors {
             malformed_count: result.parse_stats.malformed_count,
             ignored_count: result.parse_stats.ignored_count,
@@ -1314,6 +1548,64 @@ echo hello
         assert_eq!(stats.malformed_count, 0);
     }
 
+    #[test]
+    fn history_parser_handles_bash_and_zsh_formats() {
+        let input = "#1700000000\ngit status\n: 1700000001:0;git reset --hard\n\n";
+
+        let parser =
+            SimulateParser::new(input.as_bytes(), SimulateLimits::default()).from_history(true);
+        let (commands, stats) = parser.collect_commands().unwrap();
+
+        assert_eq!(commands.len(), 2);
+        assert_eq!(commands[0].command, "git status");
+        assert_eq!(commands[0].format, SimulateInputFormat::ShellHistory);
+        assert_eq!(commands[1].command, "git reset --hard");
+        assert_eq!(stats.ignored_count, 1); // bash timestamp
+        assert_eq!(stats.empty_count, 1);
+    }
+
+    #[test]
+    fn history_parser_joins_continuation_lines() {
+        let input = "docker run \\\n  --rm alpine\nls\n";
+
+        let parser =
+            SimulateParser::new(input.as_bytes(), SimulateLimits::default()).from_history(true);
+        let (commands, stats) = parser.collect_commands().unwrap();
+
+        assert_eq!(commands.len(), 2);
+        assert_eq!(commands[0].command, "docker run \n  --rm alpine");
+        assert_eq!(commands[1].command, "ls");
+        assert_eq!(stats.lines_read, 3);
+    }
+
+    #[test]
+    fn history_parser_extracts_transcript_tool_uses() {
+        let input = r#"{"type":"user","message":{"role":"user","content":"clean up please"}}
+{"type":"assistant","message":{"content":[{"type":"text","text":"ok"},{"type":"tool_use","name":"Bash","input":{"command":"git stash"}},{"type":"tool_use","name":"Bash","input":{"command":"git clean -fd"}}]}}
+{"tool_name":"Bash","tool_input":{"command":"git log"}}
+"#;
+
+        let parser =
+            SimulateParser::new(input.as_bytes(), SimulateLimits::default()).from_history(true);
+        let (commands, stats) = parser.collect_commands().unwrap();
+
+        let extracted: Vec<&str> = commands.iter().map(|c| c.command.as_str()).collect();
+        assert_eq!(extracted, vec!["git stash", "git clean -fd", "git log"]);
+        assert_eq!(commands[0].format, SimulateInputFormat::Transcript);
+        assert_eq!(commands[1].format, SimulateInputFormat::Transcript);
+        assert_eq!(commands[2].format, SimulateInputFormat::HookJson);
+        assert_eq!(stats.commands_extracted, 3);
+        assert_eq!(stats.ignored_count, 1); // user message
+    }
+
+    #[test]
+    fn history_mode_off_keeps_timestamps_as_commands() {
+        let input = "#1700000000\n";
+        let parser = SimulateParser::new(input.as_bytes(), SimulateLimits::default());
+        let (commands, _) = parser.collect_commands().unwrap();
+        assert_eq!(commands.len(), 1);
+    }
+
     #[test]
     fn parser_respects_line_limit() {
         let input = "line1\nline2\nline3\nline4\nline5\n";
@@ -140,6 +140,175 @@ pub fn get_suggestion_by_kind(rule_id: &str, kind: SuggestionKind) -> Option<&'s
     get_suggestions(rule_id).and_then(|suggestions| suggestions.iter().find(|s| s.kind == kind))
 }
 
+// ============================================================================
+// Safe-Variant Rewriting
+// ============================================================================
+
+/// One step of rewriting a denied command into its safe variant.
+///
+/// Token steps operate on whitespace-separated tokens of the original command,
+/// so paths, remotes, and other arguments carry over unchanged.
+#[derive(Debug, Clone, Copy)]
+enum RewriteStep {
+    /// Replace the whole command.
+    Fixed(&'static str),
+    /// Replace every token equal to one of `from` with `to`.
+    Swap {
+        from: &'static [&'static str],
+        to: &'static str,
+    },
+    /// Remove every token equal to one of these.
+    Drop(&'static [&'static str]),
+    /// Append a token to the end of the command.
+    Append(&'static str),
+}
+
+/// Rewrite table keyed by `rule_id`.
+///
+/// Only rules with a mechanical, same-intent safe variant are listed. A `Swap`
+/// or `Drop` step that finds no matching token aborts the rewrite, so commands
+/// with an unexpected shape get no suggestion rather than a wrong one.
+static REWRITE_TABLE: &[(&str, &[RewriteStep])] = &[
+    ("core.git:reset-hard", &[RewriteStep::Fixed("git stash")]),
+    (
+        "core.git:checkout-discard",
+        &[RewriteStep::Swap {
+            from: &["checkout"],
+            to: "stash push",
+        }],
+    ),
+    ("core.git:clean-force", &[RewriteStep::Append("--dry-run")]),
+    (
+        "core.git:push-force-long",
+        &[RewriteStep::Swap {
+            from: &["--force"],
+            to: "--force-with-lease",
+        }],
+    ),
+    (
+        "core.git:push-force-short",
+        &[RewriteStep::Swap {
+            from: &["-f"],
+            to: "--force-with-lease",
+        }],
+    ),
+    (
+        "core.git:branch-force-delete",
+        &[RewriteStep::Swap {
+            from: &["-D"],
+            to: "-d",
+        }],
+    ),
+    (
+        "containers.docker:system-prune",
+        &[RewriteStep::Fixed("docker system df")],
+    ),
+    (
+        "containers.docker:rm-force",
+        &[RewriteStep::Drop(&["-f", "--force"])],
+    ),
+    (
+        "kubernetes.kubectl:delete-namespace",
+        &[RewriteStep::Append("--dry-run=client")],
+    ),
+    (
+        "kubernetes.kubectl:delete-workload",
+        &[RewriteStep::Append("--dry-run=client")],
+    ),
+    (
+        "infrastructure.terraform:destroy",
+        &[RewriteStep::Swap {
+            from: &["destroy"],
+            to: "plan -destroy",
+        }],
+    ),
+    (
+        "infrastructure.terraform:apply-auto-approve",
+        &[
+            RewriteStep::Swap {
+                from: &["apply"],
+                to: "plan",
+            },
+            RewriteStep::Drop(&["-auto-approve", "--auto-approve"]),
+        ],
+    ),
+];
+
+/// Build a machine-readable safe variant of a denied command.
+///
+/// Looks up `rule_id` in the rewrite table and applies its steps to the
+/// original command. Returns `None` when the rule has no registered rewrite,
+/// the command does not have the shape the rewrite expects, or the command
+/// chains several commands (rewriting the whole line would leave the other
+/// segments, or the destructive one, running unchanged).
+///
+/// # Examples
+///
+/// ```
+/// use destructive_command_guard::suggestions::suggested_alternative;
+///
+/// assert_eq!(
+///     suggested_alternative("core.git:push-force-long", "git push --force origin main"),
+///     Some("git push --force-with-lease origin main".to_string())
+/// );
+/// assert_eq!(suggested_alternative("core.git:stash-clear", "git stash clear"), None);
+/// ```
+#[must_use]
+pub fn suggested_alternative(rule_id: &str, command: &str) -> Option<String> {
+    let (_, steps) = REWRITE_TABLE.iter().find(|(id, _)| *id == rule_id)?;
+    if is_compound_command(command) {
+        return None;
+    }
+    let mut rewritten = command.trim().to_string();
+    for step in *steps {
+        rewritten = apply_rewrite_step(&rewritten, *step)?;
+    }
+    (rewritten != command.trim()).then_some(rewritten)
+}
+
+/// Whether `command` contains shell operators that join several commands
+/// (`;`, `&&`, `||`, `|`, `&`, newlines, or command substitution).
+fn is_compound_command(command: &str) -> bool {
+    command.contains(['\n', ';', '&', '|', '`']) || command.contains("$(")
+}
+
+/// Apply a single rewrite step, preserving the whitespace between kept tokens.
+fn apply_rewrite_step(command: &str, step: RewriteStep) -> Option<String> {
+    let (from, to) = match step {
+        RewriteStep::Fixed(replacement) => return Some(replacement.to_string()),
+        RewriteStep::Append(token) => return Some(format!("{command} {token}")),
+        RewriteStep::Swap { from, to } => (from, Some(to)),
+        RewriteStep::Drop(from) => (from, None),
+    };
+
+    let mut out = String::with_capacity(command.len() + 16);
+    let mut matched = false;
+    let mut rest = command;
+    while let Some(token_start) = rest.find(|c: char| !c.is_whitespace()) {
+        let gap = &rest[..token_start];
+        let token_end = rest[token_start..]
+            .find(char::is_whitespace)
+            .map_or(rest.len(), |len| token_start + len);
+        let token = &rest[token_start..token_end];
+        rest = &rest[token_end..];
+
+        let kept = if from.contains(&token) {
+            matched = true;
+            to
+        } else {
+            Some(token)
+        };
+        if let Some(kept) = kept {
+            if !out.is_empty() {
+                out.push_str(gap);
+            }
+            out.push_str(kept);
+        }
+    }
+
+    matched.then_some(out)
+}
+
 // ============================================================================
 // Explanation Fallback System
 // ============================================================================
@@ -235,6 +404,7 @@ fn build_suggestion_registry() -> HashMap<&'static str, Vec<Suggestion>> {
     let mut m = HashMap::new();
     register_core_git_suggestions(&mut m);
     register_core_filesystem_suggestions(&mut m);
+    register_core_pipe_shell_suggestions(&mut m);
     register_heredoc_suggestions(&mut m);
     register_docker_suggestions(&mut m);
     register_kubernetes_suggestions(&mut m);
@@ -450,6 +620,85 @@ fn register_core_filesystem_suggestions(m: &mut HashMap<&'static str, Vec<Sugges
     m.insert("core.filesystem:rm-rf-general", rm_rf_suggestions.clone());
     m.insert("core.filesystem:rm-r-f-separate", rm_rf_suggestions.clone());
     m.insert("core.filesystem:rm-recursive-force-long", rm_rf_suggestions);
+
+    // Shared suggestions for the cmd.exe recursive deletes (rmdir/rd/del/erase /s)
+    let windows_suggestions = vec![
+        Suggestion::new(
+            SuggestionKind::PreviewFirst,
+            "List the tree first with `dir /s` to verify the target",
+        )
+        .with_command("dir /s path"),
+        Suggestion::new(
+            SuggestionKind::SaferAlternative,
+            "Drop `/q` so cmd.exe asks for confirmation before deleting",
+        )
+        .with_command("rmdir /s path"),
+        Suggestion::new(
+            SuggestionKind::WorkflowFix,
+            "Move the folder aside instead: `move path path.old`",
+        ),
+    ];
+    m.insert(
+        "core.filesystem:windows-delete-root-home",
+        windows_suggestions.clone(),
+    );
+    m.insert(
+        "core.filesystem:windows-rmdir-recursive",
+        windows_suggestions.clone(),
+    );
+    m.insert("core.filesystem:windows-del-recursive", windows_suggestions);
+}
+
+/// Register suggestions for core.pipe_shell pack rules.
+fn register_core_pipe_shell_suggestions(m: &mut HashMap<&'static str, Vec<Suggestion>>) {
+    // Shared suggestions for POSIX shell download-and-run variants
+    let shell_suggestions = vec![
+        Suggestion::new(
+            SuggestionKind::PreviewFirst,
+            "Download the script to a file and read it before running",
+        )
+        .with_command("curl -fsSLo install.sh <url> && less install.sh"),
+        Suggestion::new(
+            SuggestionKind::SaferAlternative,
+            "Install through a package manager (brew, apt, cargo install) instead",
+        ),
+        Suggestion::new(
+            SuggestionKind::AllowSafely,
+            "Add the installer's domain to `[trusted_installers]` if it is official",
+        ),
+    ];
+    // Shared suggestions for PowerShell download cradles
+    let powershell_suggestions = vec![
+        Suggestion::new(
+            SuggestionKind::PreviewFirst,
+            "Save the script with `Invoke-WebRequest -OutFile` and read it first",
+        )
+        .with_command("Invoke-WebRequest <url> -OutFile install.ps1"),
+        Suggestion::new(
+            SuggestionKind::SaferAlternative,
+            "Check the signature with `Get-AuthenticodeSignature` before running",
+        ),
+        Suggestion::new(
+            SuggestionKind::AllowSafely,
+            "Add the installer's domain to `[trusted_installers]` if it is official",
+        ),
+    ];
+
+    m.insert("core.pipe_shell:curl-pipe-shell", shell_suggestions.clone());
+    m.insert("core.pipe_shell:wget-pipe-shell", shell_suggestions.clone());
+    m.insert(
+        "core.pipe_shell:shell-process-substitution",
+        shell_suggestions.clone(),
+    );
+    m.insert("core.pipe_shell:shell-eval-download", shell_suggestions);
+    m.insert(
+        "core.pipe_shell:powershell-iex-download",
+        powershell_suggestions.clone(),
+    );
+    m.insert(
+        "core.pipe_shell:powershell-pipe-iex",
+        powershell_suggestions,
+    );
 }
 
 /// Register suggestions for heredoc pattern rules.
@@ -950,6 +1199,22 @@ fn register_database_suggestions(m: &mut HashMap<&'static str, Vec<Suggestion>>)
         ],
     );
 
+    m.insert(
+        "database.mongodb:atlas-cluster-delete",
+        vec![
+            Suggestion::new(
+                SuggestionKind::PreviewFirst,
+                "Confirm the target with `atlas clusters describe <name>`",
+            )
+            .with_command("atlas clusters describe <name>"),
+            Suggestion::new(
+                SuggestionKind::WorkflowFix,
+                "Check `atlas backups snapshots list <name>` for a recent snapshot first",
+            )
+            .with_command("atlas backups snapshots list <name>"),
+        ],
+    );
+
     // Redis suggestions
     m.insert(
         "database.redis:flushall",
@@ -1021,6 +1286,58 @@ fn register_database_suggestions(m: &mut HashMap<&'static str, Vec<Suggestion>>)
         )],
     );
 
+    m.insert(
+        "database.redis:shutdown-nosave",
+        vec![
+            Suggestion::new(
+                SuggestionKind::PreviewFirst,
+                "Check unsaved writes with `INFO persistence`",
+            )
+            .with_command("redis-cli INFO persistence"),
+            Suggestion::new(
+                SuggestionKind::SaferAlternative,
+                "Use `SHUTDOWN SAVE` to write a snapshot before exiting",
+            )
+            .with_command("redis-cli SHUTDOWN SAVE"),
+        ],
+    );
+
+    m.insert(
+        "database.redis:config-disable-persistence",
+        vec![
+            Suggestion::new(
+                SuggestionKind::PreviewFirst,
+                "Check the current persistence settings with `CONFIG GET appendonly`",
+            )
+            .with_command("redis-cli CONFIG GET appendonly"),
+        ],
+    );
+
+    m.insert(
+        "database.redis:cluster-reset",
+        vec![
+            Suggestion::new(
+                SuggestionKind::PreviewFirst,
+                "Check the node's role and slots with `CLUSTER NODES`",
+            )
+            .with_command("redis-cli CLUSTER NODES"),
+        ],
+    );
+
+    m.insert(
+        "database.redis:cluster-del-node",
+        vec![
+            Suggestion::new(
+                SuggestionKind::PreviewFirst,
+                "Check slot ownership with `redis-cli --cluster check`",
+            ),
+            Suggestion::new(
+                SuggestionKind::WorkflowFix,
+                "Move the node's slots away with `redis-cli --cluster reshard` first",
+            ),
+        ],
+    );
+
     // SQLite suggestions
     m.insert(
         "database.sqlite:drop-table",
@@ -1080,6 +1397,21 @@ fn register_database_suggestions(m: &mut HashMap<&'static str, Vec<Suggestion>>)
         ],
     );
 
+    m.insert(
+        "database.sqlite:rm-database-file",
+        vec![
+            Suggestion::new(
+                SuggestionKind::SaferAlternative,
+                "Take a consistent copy with `.backup` before deleting",
+            )
+            .with_command("sqlite3 <file.db> \".backup <file.db>.bak\""),
+            Suggestion::new(
+                SuggestionKind::WorkflowFix,
+                "Stop the process writing to the database before removing -wal/-shm files",
+            ),
+        ],
+    );
+
     // MySQL suggestions
     m.insert(
         "database.mysql:drop-database",
@@ -1399,6 +1731,52 @@ fn register_system_permissions_suggestions(m: &mut HashMap<&'static str, Vec<Sug
             ),
         ],
     );
+
+    // setfacl -Rb (strip all ACLs recursively)
+    m.insert(
+        "system.permissions:setfacl-remove-all-recursive",
+        vec![
+            Suggestion::new(
+                SuggestionKind::PreviewFirst,
+                "Back up the current ACLs so they can be restored",
+            )
+            .with_command("getfacl -R <path> > acls.backup"),
+            Suggestion::new(
+                SuggestionKind::SaferAlternative,
+                "Remove only the entry you no longer need",
+            )
+            .with_command("setfacl -x u:<user> <path>"),
+        ],
+    );
+
+    // chattr -i / -a
+    m.insert(
+        "system.permissions:chattr-remove-immutable",
+        vec![
+            Suggestion::new(
+                SuggestionKind::PreviewFirst,
+                "Check which attributes are set and why",
+            )
+            .with_command("lsattr <path>"),
+            Suggestion::new(
+                SuggestionKind::WorkflowFix,
+                "Restore the attribute as soon as the edit is done",
+            )
+            .with_command("chattr +i <path>"),
+        ],
+    );
+
+    // umask 000
+    m.insert(
+        "system.permissions:umask-000",
+        vec![
+            Suggestion::new(
+                SuggestionKind::SaferAlternative,
+                "Use a umask that keeps new files from being world-writable",
+            )
+            .with_command("umask 022"),
+        ],
+    );
 }
 
 // ============================================================================
@@ -1519,6 +1897,9 @@ mod tests {
             "core.filesystem:rm-rf-general",
             "core.filesystem:rm-r-f-separate",
             "core.filesystem:rm-recursive-force-long",
+            "core.filesystem:windows-delete-root-home",
+            "core.filesystem:windows-rmdir-recursive",
+            "core.filesystem:windows-del-recursive",
         ];
 
         for rule in expected_rules {
@@ -1592,7 +1973,7 @@ mod tests {
 
         use crate::packs::REGISTRY;
 
-        let core_packs = ["core.git", "core.filesystem"];
+        let core_packs = ["core.git", "core.filesystem", "core.pipe_shell"];
         let mut missing_suggestions = Vec::new();
 
         for pack_id in core_packs {
@@ -1718,6 +2099,7 @@ mod tests {
             "database.mongodb:delete-all",
             "database.mongodb:mongorestore-drop",
             "database.mongodb:collection-drop",
+            "database.mongodb:atlas-cluster-delete",
             // Redis
             "database.redis:flushall",
             "database.redis:flushdb",
@@ -1725,11 +2107,16 @@ mod tests {
             "database.redis:debug-sleep",
             "database.redis:shutdown",
             "database.redis:config-dangerous",
+            "database.redis:shutdown-nosave",
+            "database.redis:config-disable-persistence",
+            "database.redis:cluster-reset",
+            "database.redis:cluster-del-node",
             // SQLite
             "database.sqlite:drop-table",
             "database.sqlite:delete-without-where",
             "database.sqlite:vacuum-into",
             "database.sqlite:sqlite3-stdin",
+            "database.sqlite:rm-database-file",
             // MySQL
             "database.mysql:drop-database",
             "database.mysql:drop-table",
@@ -1945,4 +2332,75 @@ mod tests {
             "Fallback should be concise: {word_count} words"
         );
     }
+
+    #[test]
+    fn suggested_alternative_templates_original_arguments() {
+        assert_eq!(
+            suggested_alternative("core.git:push-force-short", "git push -f origin feature/x"),
+            Some("git push --force-with-lease origin feature/x".to_string())
+        );
+        assert_eq!(
+            suggested_alternative("core.git:checkout-discard", "git checkout -- src/lib.rs"),
+            Some("git stash push -- src/lib.rs".to_string())
+        );
+        assert_eq!(
+            suggested_alternative("core.git:reset-hard", "git reset --hard HEAD~1"),
+            Some("git stash".to_string())
+        );
+        assert_eq!(
+            suggested_alternative(
+                "infrastructure.terraform:apply-auto-approve",
+                "terraform apply -auto-approve -var-file=prod.tfvars"
+            ),
+            Some("terraform plan -var-file=prod.tfvars".to_string())
+        );
+        assert_eq!(
+            suggested_alternative("containers.docker:rm-force", "docker rm -f web"),
+            Some("docker rm web".to_string())
+        );
+    }
+
+    #[test]
+    fn suggested_alternative_declines_unexpected_shapes() {
+        // `git branch -f` force-moves a branch; `-d` would not be the same intent.
+        assert_eq!(
+            suggested_alternative("core.git:branch-force-delete", "git branch -f main HEAD~1"),
+            None
+        );
+        assert_eq!(
+            suggested_alternative("core.git:stash-clear", "git stash clear"),
+            None
+        );
+        assert_eq!(suggested_alternative("unknown:rule", "rm -rf /"), None);
+    }
+
+    #[test]
+    fn suggested_alternative_declines_compound_commands() {
+        for (rule_id, command) in [
+            (
+                "kubernetes.kubectl:delete-namespace",
+                "kubectl delete ns prod && echo done",
+            ),
+            (
+                "kubernetes.kubectl:delete-workload",
+                "kubectl delete deploy api; kubectl get pods",
+            ),
+            (
+                "core.git:push-force-long",
+                "git push --force origin main || git push --force backup main",
+            ),
+            ("core.git:reset-hard", "cd repo && git reset --hard"),
+            ("core.git:clean-force", "git clean -fd | tee clean.log"),
+            (
+                "infrastructure.terraform:destroy",
+                "terraform destroy $(cat vars.txt)",
+            ),
+        ] {
+            assert_eq!(
+                suggested_alternative(rule_id, command),
+                None,
+                "{command} should not get a whole-line rewrite"
+            );
+        }
+    }
 }
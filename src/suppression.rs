@@ -0,0 +1,377 @@
+//! Inline suppression comments (`# dcg:allow rule=... reason="..."`).
+//!
+//! A command can carry a trailing shell comment naming the rule it expects
+//! to trip and why:
+//!
+//! ```text
+//! git reset --hard origin/main # dcg:allow rule=core.git:reset-hard reason="rebuilding scratch clone"
+//! ```
+//!
+//! The annotation is only honored when `[suppressions] inline = true`. It
+//! applies to that single command and only to the exact rule it names: if
+//! any other rule also matches the command, the annotation is ignored. With
+//! `require_confirmation = true` (the default) the command stays denied
+//! until a human confirms it with `dcg allow-once <code>`; otherwise the
+//! match is downgraded to a warning. Either way the suppression is written
+//! to the audit log (see [`crate::audit`]).
+
+use std::io;
+use std::path::Path;
+
+use crate::allowlist::{
+    AllowEntry, AllowSelector, AllowlistFile, AllowlistLayer, LayeredAllowlist,
+    LoadedAllowlistLayer, RuleId,
+};
+use crate::audit::AuditLog;
+use crate::config::SuppressionsConfig;
+use crate::logging::{RedactionConfig, redact_command};
+use crate::packs::Severity;
+
+/// Marker that starts an inline suppression comment.
+const MARKER: &str = "dcg:allow";
+
+/// Audit log action for inline suppressions.
+pub const AUDIT_ACTION: &str = "suppression.inline";
+
+/// A parsed `# dcg:allow` annotation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InlineSuppression {
+    /// Rule the annotation suppresses (`pack_id:pattern_name`).
+    pub rule: RuleId,
+    /// Why the command is safe to run.
+    pub reason: String,
+}
+
+/// What happens to a denied command that carries an annotation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SuppressionVerdict {
+    /// Downgrade the match to a warning.
+    Apply,
+    /// Keep the deny until a human runs `dcg allow-once`.
+    Confirm,
+    /// Keep the deny; the annotation does not apply.
+    Refuse(String),
+}
+
+impl InlineSuppression {
+    /// Parse the first `# dcg:allow` comment of `command`.
+    ///
+    /// The `#` must start a shell comment (unquoted, at the start of a word),
+    /// `rule=` must be a full rule id without wildcards, and `reason=` must be
+    /// present and non-empty.
+    #[must_use]
+    pub fn parse(command: &str) -> Option<Self> {
+        if !command.contains(MARKER) {
+            return None;
+        }
+        comments(command).find_map(Self::parse_comment)
+    }
+
+    fn parse_comment(comment: &str) -> Option<Self> {
+        let rest = comment.trim_start().strip_prefix(MARKER)?;
+        if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+            return None;
+        }
+        let mut rule = None;
+        let mut reason = None;
+        for word in crate::scripts::shell_words(rest) {
+            match word.text.split_once('=') {
+                Some(("rule", value)) => rule = RuleId::parse(value),
+                Some(("reason", value)) => reason = Some(value.trim().to_string()),
+                _ => {}
+            }
+        }
+        let rule = rule.filter(|rule| !rule.pattern_name.contains('*'))?;
+        let reason = reason.filter(|reason| !reason.is_empty())?;
+        Some(Self { rule, reason })
+    }
+
+    /// Decide whether the annotation downgrades a deny of `pack:pattern`.
+    ///
+    /// `covers_match` re-evaluates the command with only the named rule
+    /// allowed and reports whether that clears it; it is only called when
+    /// the annotation names the matched rule.
+    pub fn verdict(
+        &self,
+        config: &SuppressionsConfig,
+        pack: Option<&str>,
+        pattern: Option<&str>,
+        severity: Option<Severity>,
+        covers_match: impl FnOnce() -> bool,
+    ) -> SuppressionVerdict {
+        if !config.inline {
+            return SuppressionVerdict::Refuse(
+                "inline suppressions are disabled ([suppressions] inline = false)".to_string(),
+            );
+        }
+        let matched = pack.zip(pattern);
+        if matched != Some((self.rule.pack_id.as_str(), self.rule.pattern_name.as_str())) {
+            let matched = matched.map_or_else(
+                || "a rule without an id".to_string(),
+                |(pack, pattern)| format!("{pack}:{pattern}"),
+            );
+            return SuppressionVerdict::Refuse(format!(
+                "the annotation names {} but the command matched {matched}",
+                self.rule
+            ));
+        }
+        if !covers_match() {
+            return SuppressionVerdict::Refuse(format!(
+                "other rules besides {} also match this command",
+                self.rule
+            ));
+        }
+        // Critical rules always need a human, whatever the config says.
+        if config.require_confirmation || severity == Some(Severity::Critical) {
+            return SuppressionVerdict::Confirm;
+        }
+        SuppressionVerdict::Apply
+    }
+
+    /// `allowlists` plus an entry allowing only the annotated rule.
+    #[must_use]
+    pub fn scoped_allowlists(&self, allowlists: &LayeredAllowlist) -> LayeredAllowlist {
+        let mut scoped = allowlists.clone();
+        scoped.layers.insert(
+            0,
+            LoadedAllowlistLayer {
+                layer: AllowlistLayer::Project,
+                path: "<inline suppression>".into(),
+                file: AllowlistFile {
+                    entries: vec![AllowEntry {
+                        selector: AllowSelector::Rule(self.rule.clone()),
+                        reason: self.reason.clone(),
+                        added_by: None,
+                        added_at: None,
+                        expires_at: None,
+                        ttl: None,
+                        session: None,
+                        context: None,
+                        conditions: std::collections::HashMap::new(),
+                        environments: Vec::new(),
+                        paths: None,
+                        risk_acknowledged: false,
+                    }],
+                    errors: Vec::new(),
+                },
+            },
+        );
+        scoped
+    }
+
+    /// Note appended to the denial of an annotated command.
+    #[must_use]
+    pub fn notice(&self, verdict: &SuppressionVerdict, allow_once_code: Option<&str>) -> String {
+        match (verdict, allow_once_code) {
+            (SuppressionVerdict::Refuse(why), _) => {
+                format!("Inline suppression of {} ignored: {why}.", self.rule)
+            }
+            (_, Some(code)) => format!(
+                "Inline suppression of {} (\"{}\") needs human confirmation: \
+                 run `dcg allow-once {code}`.",
+                self.rule, self.reason
+            ),
+            (_, None) => format!(
+                "Inline suppression of {} (\"{}\") needs human confirmation, but no \
+                 allow-once code could be issued.",
+                self.rule, self.reason
+            ),
+        }
+    }
+
+    /// Record the suppression in the audit log.
+    ///
+    /// `confirmed` is true when a human approved it with `dcg allow-once`.
+    ///
+    /// # Errors
+    ///
+    /// Returns any I/O error from writing the audit log.
+    pub fn record(
+        &self,
+        command: &str,
+        cwd: Option<&Path>,
+        confirmed: bool,
+        redaction: &RedactionConfig,
+    ) -> io::Result<()> {
+        let log = AuditLog::new(AuditLog::default_path(cwd));
+        log.append(
+            AUDIT_ACTION,
+            cwd.unwrap_or_else(|| Path::new("<unknown>")),
+            serde_json::json!({
+                "rule": self.rule.to_string(),
+                "reason": self.reason,
+                "command": redact_command(command, redaction),
+                "confirmed": confirmed,
+            }),
+        )
+        .map(|_| ())
+    }
+}
+
+/// Bodies of the shell comments in `command` (text after `#`).
+fn comments(command: &str) -> impl Iterator<Item = &str> {
+    let mut found = Vec::new();
+    let mut quote: Option<char> = None;
+    let mut word_start = true;
+    let mut chars = command.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"'), '\\') | (None, '\\') => {
+                chars.next();
+            }
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '#') if word_start => {
+                let body = &command[i + 1..];
+                let end = body.find('\n').unwrap_or(body.len());
+                found.push(&body[..end]);
+                for (_, skipped) in chars.by_ref() {
+                    if skipped == '\n' {
+                        break;
+                    }
+                }
+                word_start = true;
+                continue;
+            }
+            _ => {}
+        }
+        word_start = quote.is_none() && (c.is_whitespace() || ";&|()".contains(c));
+    }
+    found.into_iter()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(id: &str) -> RuleId {
+        RuleId::parse(id).expect("valid rule id")
+    }
+
+    fn enabled(require_confirmation: bool) -> SuppressionsConfig {
+        SuppressionsConfig {
+            inline: true,
+            require_confirmation,
+        }
+    }
+
+    #[test]
+    fn parses_trailing_annotation() {
+        let parsed = InlineSuppression::parse(
+            "git reset --hard origin/main # dcg:allow rule=core.git:reset-hard reason=\"rebuilding scratch clone\"",
+        )
+        .expect("annotation parses");
+        assert_eq!(parsed.rule, rule("core.git:reset-hard"));
+        assert_eq!(parsed.reason, "rebuilding scratch clone");
+
+        let parsed = InlineSuppression::parse(
+            "rm -rf build #dcg:allow reason=stale rule=core.filesystem:rm-rf",
+        )
+        .expect("bare values and any order");
+        assert_eq!(parsed.rule, rule("core.filesystem:rm-rf"));
+        assert_eq!(parsed.reason, "stale");
+    }
+
+    #[test]
+    fn ignores_annotations_that_are_not_comments_or_incomplete() {
+        for command in [
+            "echo '# dcg:allow rule=core.git:reset-hard reason=x'; git reset --hard",
+            "echo \"# dcg:allow rule=core.git:reset-hard reason=x\"",
+            "git reset --hard foo#dcg:allow rule=core.git:reset-hard reason=x",
+            "git reset --hard # dcg:allow rule=core.git:reset-hard",
+            "git reset --hard # dcg:allow rule=core.git:reset-hard reason=\"\"",
+            "git reset --hard # dcg:allow rule=core.git:* reason=x",
+            "git reset --hard # dcg:allowed rule=core.git:reset-hard reason=x",
+            "git reset --hard # see dcg:allow rule=core.git:reset-hard reason=x",
+        ] {
+            assert_eq!(InlineSuppression::parse(command), None, "{command}");
+        }
+    }
+
+    #[test]
+    fn verdict_requires_exact_rule_and_sole_match() {
+        let parsed = InlineSuppression::parse(
+            "git reset --hard # dcg:allow rule=core.git:reset-hard reason=x",
+        )
+        .expect("annotation parses");
+        let high = Some(Severity::High);
+        let matched = (Some("core.git"), Some("reset-hard"));
+
+        assert!(matches!(
+            parsed.verdict(
+                &SuppressionsConfig::default(),
+                matched.0,
+                matched.1,
+                high,
+                || true
+            ),
+            SuppressionVerdict::Refuse(_)
+        ));
+        assert!(matches!(
+            parsed.verdict(
+                &enabled(false),
+                Some("core.git"),
+                Some("clean-force"),
+                high,
+                || true
+            ),
+            SuppressionVerdict::Refuse(_)
+        ));
+        assert!(matches!(
+            parsed.verdict(&enabled(false), matched.0, matched.1, high, || false),
+            SuppressionVerdict::Refuse(_)
+        ));
+        assert_eq!(
+            parsed.verdict(&enabled(true), matched.0, matched.1, high, || true),
+            SuppressionVerdict::Confirm
+        );
+        assert_eq!(
+            parsed.verdict(
+                &enabled(false),
+                matched.0,
+                matched.1,
+                Some(Severity::Critical),
+                || true
+            ),
+            SuppressionVerdict::Confirm
+        );
+        assert_eq!(
+            parsed.verdict(&enabled(false), matched.0, matched.1, high, || true),
+            SuppressionVerdict::Apply
+        );
+    }
+
+    #[test]
+    fn scoped_allowlist_only_clears_the_named_rule() {
+        use crate::config::Config;
+        use crate::evaluator::evaluate_command_with_pack_order;
+        use crate::packs::REGISTRY;
+
+        let config = Config::default();
+        let enabled = config.enabled_pack_ids();
+        let ordered = REGISTRY.expand_enabled_ordered(&enabled);
+        let keywords = REGISTRY.collect_enabled_keywords(&enabled);
+        let overrides = config.compile_overrides();
+        let heredoc = config.heredoc_settings();
+        let evaluate = |command: &str, allowlists: &LayeredAllowlist| {
+            evaluate_command_with_pack_order(
+                command, &keywords, &ordered, None, &overrides, allowlists, &heredoc,
+            )
+        };
+
+        let command = "git reset --hard # dcg:allow rule=core.git:reset-hard reason=scratch";
+        let base = LayeredAllowlist::default();
+        let denied = evaluate(command, &base);
+        let info = denied.pattern_info.as_ref().expect("denied");
+        let parsed = InlineSuppression::parse(command).expect("annotation parses");
+        assert_eq!(info.pack_id.as_deref(), Some("core.git"));
+        assert_eq!(info.pattern_name.as_deref(), Some("reset-hard"));
+        assert!(!evaluate(command, &parsed.scoped_allowlists(&base)).is_denied());
+
+        let chained =
+            "git reset --hard && git clean -fd # dcg:allow rule=core.git:reset-hard reason=x";
+        let parsed = InlineSuppression::parse(chained).expect("annotation parses");
+        assert!(evaluate(chained, &parsed.scoped_allowlists(&base)).is_denied());
+    }
+}
@@ -0,0 +1,410 @@
+//! Live activity view (`dcg top`).
+//!
+//! Re-reads the history database once per refresh interval and shows what
+//! dcg has been doing over a recent window: deny rate, evaluation latency
+//! percentiles, active agent sessions, and the latest evaluations. Hook
+//! processes write the database, so the view only needs read access and can
+//! run alongside agents.
+
+use std::io;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+
+use crate::history::{CommandEntry, HistoryDb, HistoryError, LiveSnapshot, Outcome, SessionStat};
+use crate::trace::format_duration;
+
+/// Key help shown in the footer.
+const KEY_HELP: &str = " q quit  p pause ";
+
+/// What `dcg top` shows and how often it refreshes.
+#[derive(Debug, Clone)]
+pub struct TopOptions {
+    /// Window the counts and percentiles cover.
+    pub window: chrono::Duration,
+    /// Label for the window in the header (e.g. "5m").
+    pub window_label: String,
+    /// Time between refreshes.
+    pub interval: Duration,
+    /// Maximum active sessions listed.
+    pub session_limit: usize,
+    /// Maximum recent evaluations listed.
+    pub recent_limit: usize,
+}
+
+/// Read the current snapshot from `db`.
+///
+/// # Errors
+///
+/// Returns an error if a history query fails.
+pub fn snapshot(db: &HistoryDb, options: &TopOptions) -> Result<LiveSnapshot, HistoryError> {
+    let now = Utc::now();
+    db.live_snapshot(
+        now - options.window,
+        now,
+        options.session_limit,
+        options.recent_limit,
+    )
+}
+
+/// State of the live view.
+#[derive(Debug)]
+pub struct TopApp {
+    window_label: String,
+    snapshot: Option<LiveSnapshot>,
+    error: Option<String>,
+    updated_at: Option<DateTime<Utc>>,
+    paused: bool,
+    quit: bool,
+}
+
+impl TopApp {
+    #[must_use]
+    pub fn new(window_label: impl Into<String>) -> Self {
+        Self {
+            window_label: window_label.into(),
+            snapshot: None,
+            error: None,
+            updated_at: None,
+            paused: false,
+            quit: false,
+        }
+    }
+
+    #[must_use]
+    pub const fn should_quit(&self) -> bool {
+        self.quit
+    }
+
+    #[must_use]
+    pub const fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Store the result of a refresh. A failed query keeps the last snapshot.
+    pub fn update(&mut self, result: Result<LiveSnapshot, HistoryError>, now: DateTime<Utc>) {
+        match result {
+            Ok(snapshot) => {
+                self.snapshot = Some(snapshot);
+                self.error = None;
+                self.updated_at = Some(now);
+            }
+            Err(e) => self.error = Some(e.to_string()),
+        }
+    }
+
+    /// Apply one key press.
+    pub fn handle_key(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Char('p' | ' ') => self.paused = !self.paused,
+            _ => {}
+        }
+    }
+
+    /// Draw the summary, active sessions, recent evaluations, and key help.
+    pub fn render(&self, frame: &mut Frame) {
+        let summary = self.summary_lines();
+        // Lines plus the top and bottom border.
+        let summary_height = u16::try_from(summary.len() + 2).unwrap_or(u16::MAX);
+        let [summary_area, sessions_area, recent_area, help_area] = Layout::vertical([
+            Constraint::Length(summary_height),
+            Constraint::Length(8),
+            Constraint::Min(5),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let title = match (self.paused, self.updated_at) {
+            (true, _) => format!(" dcg top - last {} (paused) ", self.window_label),
+            (false, Some(at)) => format!(
+                " dcg top - last {} (updated {}) ",
+                self.window_label,
+                at.with_timezone(&chrono::Local).format("%H:%M:%S")
+            ),
+            (false, None) => format!(" dcg top - last {} ", self.window_label),
+        };
+        frame.render_widget(
+            Paragraph::new(summary).block(Block::bordered().title(title)),
+            summary_area,
+        );
+
+        let now = self.updated_at.unwrap_or_else(Utc::now);
+        let sessions: Vec<ListItem> = self
+            .snapshot
+            .iter()
+            .flat_map(|s| &s.active_sessions)
+            .map(|session| session_line(session, now))
+            .collect();
+        frame.render_widget(
+            List::new(sessions).block(Block::bordered().title(format!(
+                " Active sessions ({}) ",
+                self.snapshot.as_ref().map_or(0, |s| s.active_sessions.len())
+            ))),
+            sessions_area,
+        );
+
+        let recent: Vec<ListItem> = self
+            .snapshot
+            .iter()
+            .flat_map(|s| &s.recent)
+            .map(recent_line)
+            .collect();
+        frame.render_widget(
+            List::new(recent).block(Block::bordered().title(" Recent evaluations ")),
+            recent_area,
+        );
+
+        frame.render_widget(
+            Paragraph::new(KEY_HELP).style(Style::new().fg(Color::DarkGray)),
+            help_area,
+        );
+    }
+
+    fn summary_lines(&self) -> Vec<Line<'_>> {
+        let mut lines = Vec::new();
+        match &self.snapshot {
+            Some(s) => {
+                let bold = Style::new().add_modifier(Modifier::BOLD);
+                let rate_color = if s.outcomes.denied == 0 {
+                    Color::Green
+                } else {
+                    Color::Red
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(format!("{} evaluations", s.total), bold),
+                    Span::raw("   deny rate "),
+                    Span::styled(format!("{:.1}%", s.deny_rate * 100.0), bold.fg(rate_color)),
+                    Span::raw(format!(
+                        "   allowed {}  denied {}  warned {}  bypassed {}",
+                        s.outcomes.allowed,
+                        s.outcomes.denied,
+                        s.outcomes.warned,
+                        s.outcomes.bypassed
+                    )),
+                ]));
+                lines.push(Line::from(format!(
+                    "latency p50 {}  p95 {}  p99 {}  max {}",
+                    format_duration(s.latency.p50_us),
+                    format_duration(s.latency.p95_us),
+                    format_duration(s.latency.p99_us),
+                    format_duration(s.latency.max_us)
+                )));
+            }
+            None => lines.push(Line::from("Reading history...")),
+        }
+        if let Some(error) = &self.error {
+            lines.push(Line::styled(
+                format!("history query failed: {error}"),
+                Style::new().fg(Color::Red),
+            ));
+        }
+        lines
+    }
+}
+
+/// Run the live view until the user quits, refreshing every `options.interval`.
+///
+/// # Errors
+///
+/// Returns an error if the terminal cannot be drawn or read.
+pub fn run(db: &HistoryDb, options: &TopOptions) -> io::Result<()> {
+    let mut app = TopApp::new(options.window_label.clone());
+    let mut terminal = ratatui::init();
+    let result = run_app(&mut terminal, &mut app, db, options);
+    ratatui::restore();
+    result
+}
+
+fn run_app(
+    terminal: &mut DefaultTerminal,
+    app: &mut TopApp,
+    db: &HistoryDb,
+    options: &TopOptions,
+) -> io::Result<()> {
+    let mut next_refresh = Instant::now();
+    while !app.should_quit() {
+        if Instant::now() >= next_refresh {
+            if !app.is_paused() {
+                app.update(snapshot(db, options), Utc::now());
+            }
+            next_refresh = Instant::now() + options.interval;
+        }
+        terminal.draw(|frame| app.render(frame))?;
+        if event::poll(next_refresh.saturating_duration_since(Instant::now()))? {
+            match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => app.handle_key(key.code),
+                _ => {}
+            }
+        }
+    }
+    Ok(())
+}
+
+const fn outcome_style(outcome: Outcome) -> (&'static str, Color) {
+    match outcome {
+        Outcome::Allow => ("ALLOW ", Color::Green),
+        Outcome::Deny => ("DENY  ", Color::Red),
+        Outcome::Warn => ("WARN  ", Color::Yellow),
+        Outcome::Bypass => ("BYPASS", Color::Magenta),
+    }
+}
+
+fn recent_line(entry: &CommandEntry) -> ListItem<'_> {
+    let (label, color) = outcome_style(entry.outcome);
+    let rule = entry
+        .rule_id
+        .as_deref()
+        .or(entry.pack_id.as_deref())
+        .unwrap_or("-");
+    ListItem::new(Line::from(vec![
+        Span::styled(
+            entry
+                .timestamp
+                .with_timezone(&chrono::Local)
+                .format("%H:%M:%S ")
+                .to_string(),
+            Style::new().fg(Color::DarkGray),
+        ),
+        Span::styled(format!("{label} "), Style::new().fg(color)),
+        Span::styled(
+            format!("{:>7} ", format_duration(entry.eval_duration_us)),
+            Style::new().fg(Color::DarkGray),
+        ),
+        Span::styled(format!("{rule:<32} "), Style::new().fg(Color::Cyan)),
+        Span::raw(entry.command.as_str()),
+    ]))
+}
+
+fn session_line(session: &SessionStat, now: DateTime<Utc>) -> ListItem<'_> {
+    let idle = (now - session.last_seen).num_seconds().max(0);
+    let denied_style = if session.denied > 0 {
+        Style::new().fg(Color::Red)
+    } else {
+        Style::new()
+    };
+    ListItem::new(Line::from(vec![
+        Span::styled(
+            format!("{:<38} ", session.session_id),
+            Style::new().fg(Color::Cyan),
+        ),
+        Span::raw(format!("{:<12} ", session.agent_type)),
+        Span::raw(format!("{:>5} cmds  ", session.total)),
+        Span::styled(format!("{:>3} denied  ", session.denied), denied_style),
+        Span::styled(
+            format!("last {idle}s ago"),
+            Style::new().fg(Color::DarkGray),
+        ),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::{OutcomeStats, PerformanceStats};
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    fn sample_snapshot(now: DateTime<Utc>) -> LiveSnapshot {
+        LiveSnapshot {
+            since: now - chrono::Duration::minutes(5),
+            total: 20,
+            outcomes: OutcomeStats {
+                allowed: 18,
+                denied: 2,
+                warned: 0,
+                bypassed: 0,
+            },
+            deny_rate: 0.1,
+            latency: PerformanceStats {
+                p50_us: 120,
+                p95_us: 850,
+                p99_us: 2_400,
+                max_us: 15_000,
+            },
+            active_sessions: vec![SessionStat {
+                session_id: "sess-1".to_string(),
+                agent_type: "claude_code".to_string(),
+                transcript_path: None,
+                first_seen: now - chrono::Duration::minutes(4),
+                last_seen: now - chrono::Duration::seconds(3),
+                total: 20,
+                denied: 2,
+                warned: 0,
+                failed: 0,
+            }],
+            recent: vec![CommandEntry {
+                timestamp: now,
+                agent_type: "claude_code".to_string(),
+                command: "git reset --hard".to_string(),
+                outcome: Outcome::Deny,
+                rule_id: Some("core.git:reset-hard".to_string()),
+                eval_duration_us: 310,
+                ..Default::default()
+            }],
+        }
+    }
+
+    fn screen(app: &TopApp) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(120, 24)).unwrap();
+        terminal.draw(|frame| app.render(frame)).unwrap();
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(ratatui::buffer::Cell::symbol)
+            .collect()
+    }
+
+    #[test]
+    fn renders_rates_sessions_and_recent_commands() {
+        let now = Utc::now();
+        let mut app = TopApp::new("5m");
+        app.update(Ok(sample_snapshot(now)), now);
+
+        let screen = screen(&app);
+        assert!(screen.contains("dcg top - last 5m"));
+        assert!(screen.contains("20 evaluations"));
+        assert!(screen.contains("deny rate 10.0%"));
+        assert!(screen.contains("p95 850us"));
+        assert!(screen.contains("p99 2.4ms"));
+        assert!(screen.contains("Active sessions (1)"));
+        assert!(screen.contains("last 3s ago"));
+        assert!(screen.contains("core.git:reset-hard"));
+        assert!(screen.contains("git reset --hard"));
+    }
+
+    #[test]
+    fn failed_refresh_keeps_last_snapshot() {
+        let now = Utc::now();
+        let mut app = TopApp::new("5m");
+        app.update(Ok(sample_snapshot(now)), now);
+        app.update(
+            Err(HistoryError::Io(io::Error::other("database is locked"))),
+            now,
+        );
+
+        let screen = screen(&app);
+        assert!(screen.contains("20 evaluations"));
+        assert!(screen.contains("history query failed"));
+    }
+
+    #[test]
+    fn keys_pause_and_quit() {
+        let mut app = TopApp::new("5m");
+        app.handle_key(KeyCode::Char('p'));
+        assert!(app.is_paused());
+        assert!(screen(&app).contains("(paused)"));
+        app.handle_key(KeyCode::Char(' '));
+        assert!(!app.is_paused());
+        app.handle_key(KeyCode::Char('q'));
+        assert!(app.should_quit());
+    }
+}
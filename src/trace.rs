@@ -41,7 +41,8 @@
 //! ```
 
 use crate::allowlist::AllowlistLayer;
-use crate::evaluator::{EvaluationDecision, MatchSource};
+use crate::evaluator::{DEFAULT_WINDOW_WIDTH, EvaluationDecision, MatchSource};
+use crate::highlight::{HighlightSpan, HighlightedCommand, format_highlighted_command};
 use crate::packs::Severity;
 use serde::Serialize;
 use std::time::Instant;
@@ -108,6 +109,8 @@ pub enum TraceDetails {
         keywords_checked: Vec<String>,
         /// First keyword that matched (if any).
         first_match: Option<String>,
+        /// Every enabled keyword found in the command.
+        keywords_hit: Vec<String>,
     },
 
     /// Command normalization.
@@ -373,6 +376,38 @@ impl ExplainTrace {
         self.steps.iter().find(|s| s.name == name)
     }
 
+    /// The matched span in context: the command windowed around the match
+    /// with a caret line underneath.
+    #[must_use]
+    pub fn match_highlight(&self, use_color: bool) -> Option<HighlightedCommand> {
+        let info = self.match_info.as_ref()?;
+        let span = HighlightSpan::new(info.match_start?, info.match_end?);
+        Some(format_highlighted_command(
+            &self.command,
+            &span,
+            use_color,
+            DEFAULT_WINDOW_WIDTH,
+        ))
+    }
+
+    /// A marker line to print under the command, with `~` under every byte that
+    /// sanitization masked. `None` if nothing was masked.
+    #[must_use]
+    pub fn sanitization_markers(&self) -> Option<String> {
+        let sanitized = self.sanitized_command.as_deref()?;
+        if sanitized == self.command || sanitized.len() != self.command.len() {
+            return None;
+        }
+        let original = self.command.as_bytes();
+        let masked = sanitized.as_bytes();
+        let markers: String = self
+            .command
+            .char_indices()
+            .map(|(i, _)| if original[i] == masked[i] { ' ' } else { '~' })
+            .collect();
+        Some(markers.trim_end().to_string())
+    }
+
     /// Format the trace as a compact single-line string.
     ///
     /// Format examples:
@@ -461,6 +496,11 @@ impl ExplainTrace {
             "{bold}─── Command ───────────────────────────────────────────────────────{reset}\n"
         ));
         out.push_str(&format!("{cyan}Input:{reset}      {}\n", &self.command));
+        if let Some(markers) = self.sanitization_markers() {
+            out.push_str(&format!(
+                "{dim}Masked:{reset}     {yellow}{markers}{reset}\n"
+            ));
+        }
 
         if let Some(ref normalized) = self.normalized_command {
             if normalized != &self.command {
@@ -518,6 +558,14 @@ impl ExplainTrace {
                     out.push_str(&format!("{cyan}Matched:{reset}    {red}{preview}{reset}\n"));
                 }
             }
+
+            if let Some(highlight) = self.match_highlight(use_color) {
+                out.push_str(&format!(
+                    "{cyan}Context:{reset}    {}\n",
+                    highlight.command_line
+                ));
+                out.push_str(&format!("            {}\n", highlight.caret_line));
+            }
             out.push('\n');
         }
 
@@ -759,6 +807,8 @@ pub enum JsonTraceDetails {
         quick_rejected: bool,
         keywords_checked: Vec<String>,
         first_match: Option<String>,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        keywords_hit: Vec<String>,
     },
     Normalization {
         was_modified: bool,
@@ -897,10 +947,12 @@ impl TraceDetails {
                 quick_rejected,
                 keywords_checked,
                 first_match,
+                keywords_hit,
             } => JsonTraceDetails::KeywordGating {
                 quick_rejected: *quick_rejected,
                 keywords_checked: keywords_checked.clone(),
                 first_match: first_match.clone(),
+                keywords_hit: keywords_hit.clone(),
             },
             Self::Normalization {
                 was_modified,
@@ -1065,10 +1117,13 @@ fn format_step_details_summary(details: &TraceDetails) -> String {
         TraceDetails::KeywordGating {
             quick_rejected,
             first_match,
+            keywords_hit,
             ..
         } => {
             if *quick_rejected {
                 "quick-rejected (no keywords)".to_string()
+            } else if keywords_hit.len() > 1 {
+                format!("matched keywords \"{}\"", keywords_hit.join("\", \""))
             } else if let Some(kw) = first_match {
                 format!("matched keyword \"{kw}\"")
             } else {
@@ -1244,6 +1299,7 @@ mod tests {
                 quick_rejected: false,
                 keywords_checked: vec!["git".to_string()],
                 first_match: Some("git".to_string()),
+                keywords_hit: vec!["git".to_string()],
             },
         );
 
@@ -1283,6 +1339,7 @@ mod tests {
                 quick_rejected: false,
                 keywords_checked: vec!["git".to_string()],
                 first_match: Some("git".to_string()),
+                keywords_hit: vec!["git".to_string()],
             },
         );
 
@@ -1346,6 +1403,7 @@ mod tests {
                 quick_rejected: true,
                 keywords_checked: vec![],
                 first_match: None,
+                keywords_hit: vec![],
             },
         );
         collector.record_step(
@@ -1379,6 +1437,7 @@ mod tests {
                 quick_rejected: false,
                 keywords_checked: vec!["git".to_string()],
                 first_match: Some("git".to_string()),
+                keywords_hit: vec!["git".to_string()],
             },
         );
 
@@ -1692,11 +1751,36 @@ mod tests {
         assert!(pretty.contains("bytes 0..16"));
         assert!(pretty.contains("Matched:"));
         assert!(pretty.contains("git reset --hard"));
+        assert!(pretty.contains("Context:    git reset --hard\n"));
+        assert!(pretty.contains("\n            ^^^^^^^^^^^^^^^^\n"));
 
         // Check suggestions section (core.git:reset-hard has suggestions)
         assert!(pretty.contains("─── Suggestions"));
     }
 
+    #[test]
+    fn format_pretty_marks_sanitized_bytes() {
+        let trace = ExplainTrace {
+            command: "git commit -m 'rm -rf' && ls".to_string(),
+            normalized_command: None,
+            sanitized_command: Some("git commit -m          && ls".to_string()),
+            decision: EvaluationDecision::Allow,
+            skipped_due_to_budget: false,
+            total_duration_us: 100,
+            steps: vec![],
+            match_info: None,
+            allowlist_info: None,
+            pack_summary: None,
+        };
+
+        assert_eq!(
+            trace.sanitization_markers().as_deref(),
+            Some("              ~~~ ~~~~")
+        );
+        let pretty = trace.format_pretty(false);
+        assert!(pretty.contains("Masked:                   ~~~ ~~~~\n"));
+    }
+
     #[test]
     fn format_pretty_with_normalized_command() {
         let trace = ExplainTrace {
@@ -1825,6 +1909,7 @@ mod tests {
                         quick_rejected: false,
                         keywords_checked: vec!["git".to_string()],
                         first_match: Some("git".to_string()),
+                        keywords_hit: vec!["git".to_string()],
                     },
                 },
                 TraceStep {
@@ -1918,6 +2003,7 @@ mod tests {
             quick_rejected: true,
             keywords_checked: vec![],
             first_match: None,
+            keywords_hit: vec![],
         };
         assert_eq!(
             format_step_details_summary(&quick_reject),
@@ -2105,6 +2191,7 @@ mod tests {
                         quick_rejected: false,
                         keywords_checked: vec!["git".to_string()],
                         first_match: Some("git".to_string()),
+                        keywords_hit: vec!["git".to_string()],
                     },
                 },
                 TraceStep {
@@ -2219,6 +2306,7 @@ mod tests {
                     quick_rejected: false,
                     keywords_checked: vec!["git".to_string()],
                     first_match: Some("git".to_string()),
+                    keywords_hit: vec!["git".to_string()],
                 },
             }],
             match_info: Some(MatchInfo {
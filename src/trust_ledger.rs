@@ -0,0 +1,381 @@
+//! Trust ledger: per-repository record of allow-once approvals (`dcg promote`).
+//!
+//! Every `dcg allow-once` approval appends a [`TrustRecord`] naming the
+//! repository, the rule that denied the command (recovered from the pending
+//! exception's reason), and the command. Approving the same thing over and
+//! over is a sign the rule does not fit this repository, so once a rule
+//! reaches `[trust] promote_after` approvals, `dcg promote` offers to turn it
+//! into a project allowlist entry:
+//!
+//! - If every approval was for the same command, the entry is that exact
+//!   command, which is the narrowest selector that covers what was approved.
+//! - If the approvals varied, the entry allowlists the rule
+//!   (`pack_id:pattern_name`) for the project.
+//! - Approvals without a rule (config blocklist entries, legacy patterns)
+//!   are only ever promoted as exact commands.
+//!
+//! Promoted records are removed from the ledger. The ledger lives next to the
+//! allow-once store (`trust_ledger.jsonl`) and is capped at
+//! [`MAX_LEDGER_RECORDS`].
+
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use fs2::FileExt;
+use serde::{Deserialize, Serialize};
+
+use crate::allowlist::RuleId;
+use crate::config::resolve_config_path_value;
+use crate::pending_exceptions::{AllowOnceEntry, AllowOnceStore};
+
+/// Environment variable overriding the ledger path.
+pub const ENV_TRUST_LEDGER_PATH: &str = "DCG_TRUST_LEDGER_PATH";
+
+/// Records kept; the oldest are dropped beyond this.
+pub const MAX_LEDGER_RECORDS: usize = 10_000;
+
+const LEDGER_FILE: &str = "trust_ledger.jsonl";
+
+/// One allow-once approval.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrustRecord {
+    /// RFC 3339 time of the approval.
+    pub approved_at: String,
+    /// Repository root (or directory, outside a repository) the entry was scoped to.
+    pub repo: String,
+    /// Rule that denied the command (`pack_id:pattern_name`), if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule_id: Option<String>,
+    /// Command as approved.
+    pub command_raw: String,
+    /// Command after redaction, for display.
+    pub command_redacted: String,
+}
+
+impl TrustRecord {
+    /// Record an allow-once entry. The rule comes from the entry's reason,
+    /// which the hook writes as `pack_id:pattern_name - reason`.
+    #[must_use]
+    pub fn from_allow_once(entry: &AllowOnceEntry) -> Self {
+        Self {
+            approved_at: entry.created_at.clone(),
+            repo: entry.scope_path.clone(),
+            rule_id: rule_from_reason(&entry.reason),
+            command_raw: entry.command_raw.clone(),
+            command_redacted: entry.command_redacted.clone(),
+        }
+    }
+}
+
+/// Extract the `pack_id:pattern_name` prefix of a pending exception reason.
+#[must_use]
+pub fn rule_from_reason(reason: &str) -> Option<String> {
+    let (rule, _) = reason.split_once(" - ")?;
+    RuleId::parse(rule.trim()).map(|_| rule.trim().to_string())
+}
+
+/// Allowlist selector a promotion would write.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+pub enum PromotionSelector {
+    /// `rule = "pack_id:pattern_name"`.
+    Rule(String),
+    /// `exact_command = "..."`.
+    ExactCommand(String),
+}
+
+/// A rule or command approved often enough to promote.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PromotionCandidate {
+    /// Repository the approvals were made in.
+    pub repo: String,
+    /// Rule the approvals were for, if known.
+    pub rule_id: Option<String>,
+    /// Entry to add to the project allowlist.
+    pub selector: PromotionSelector,
+    /// Number of approvals.
+    pub approvals: usize,
+    /// Distinct approved commands (redacted), most recent first.
+    pub commands: Vec<String>,
+    /// Most recent approval.
+    pub last_approved_at: String,
+}
+
+impl PromotionCandidate {
+    /// Whether `record` is one of the approvals this candidate covers.
+    #[must_use]
+    pub fn covers(&self, record: &TrustRecord) -> bool {
+        record.repo == self.repo
+            && match &self.selector {
+                PromotionSelector::Rule(rule) => record.rule_id.as_deref() == Some(rule.as_str()),
+                PromotionSelector::ExactCommand(command) => {
+                    record.rule_id == self.rule_id && &record.command_raw == command
+                }
+            }
+    }
+}
+
+/// Group the approvals made in `repo` and return those with at least
+/// `min_approvals`, most approved first.
+#[must_use]
+pub fn candidates(
+    records: &[TrustRecord],
+    repo: &str,
+    min_approvals: usize,
+) -> Vec<PromotionCandidate> {
+    // Records with a rule group by rule; the rest group by exact command.
+    let mut groups: BTreeMap<(Option<&str>, Option<&str>), Vec<&TrustRecord>> = BTreeMap::new();
+    for record in records.iter().filter(|record| record.repo == repo) {
+        let key = match record.rule_id.as_deref() {
+            Some(rule) => (Some(rule), None),
+            None => (None, Some(record.command_raw.as_str())),
+        };
+        groups.entry(key).or_default().push(record);
+    }
+
+    let mut candidates: Vec<PromotionCandidate> = groups
+        .into_values()
+        .filter(|group| group.len() >= min_approvals.max(1))
+        .map(|mut group| {
+            group.sort_by(|a, b| b.approved_at.cmp(&a.approved_at));
+            let mut raw: Vec<&str> = Vec::new();
+            let mut commands = Vec::new();
+            for record in &group {
+                if !raw.contains(&record.command_raw.as_str()) {
+                    raw.push(&record.command_raw);
+                    commands.push(record.command_redacted.clone());
+                }
+            }
+            let rule_id = group[0].rule_id.clone();
+            let selector = match &rule_id {
+                Some(rule) if raw.len() > 1 => PromotionSelector::Rule(rule.clone()),
+                _ => PromotionSelector::ExactCommand(raw[0].to_string()),
+            };
+            PromotionCandidate {
+                repo: repo.to_string(),
+                rule_id,
+                selector,
+                approvals: group.len(),
+                commands,
+                last_approved_at: group[0].approved_at.clone(),
+            }
+        })
+        .collect();
+    candidates.sort_by(|a, b| {
+        b.approvals
+            .cmp(&a.approvals)
+            .then_with(|| b.last_approved_at.cmp(&a.last_approved_at))
+    });
+    candidates
+}
+
+/// The ledger file.
+#[derive(Debug, Clone)]
+pub struct TrustLedger {
+    path: PathBuf,
+}
+
+impl TrustLedger {
+    #[must_use]
+    pub const fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Resolve the default path: `DCG_TRUST_LEDGER_PATH`, else next to the
+    /// allow-once store.
+    #[must_use]
+    pub fn default_path(cwd: Option<&Path>) -> PathBuf {
+        if let Ok(value) = std::env::var(ENV_TRUST_LEDGER_PATH) {
+            if let Some(path) = resolve_config_path_value(&value, cwd) {
+                return path;
+            }
+        }
+        AllowOnceStore::default_path(cwd)
+            .parent()
+            .map_or_else(|| PathBuf::from(LEDGER_FILE), |dir| dir.join(LEDGER_FILE))
+    }
+
+    /// Append an approval, dropping the oldest records beyond
+    /// [`MAX_LEDGER_RECORDS`].
+    ///
+    /// # Errors
+    ///
+    /// Returns any I/O error from opening, locking, or writing the ledger.
+    pub fn record(&self, record: &TrustRecord) -> io::Result<()> {
+        self.update(|records| {
+            records.push(record.clone());
+            let excess = records.len().saturating_sub(MAX_LEDGER_RECORDS);
+            records.drain(..excess);
+        })
+    }
+
+    /// All records; lines that fail to parse are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns any I/O error other than a missing ledger.
+    pub fn load(&self) -> io::Result<Vec<TrustRecord>> {
+        match fs::File::open(&self.path) {
+            Ok(file) => Ok(parse_records(&file)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Remove the records `candidate` covers; returns how many were removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns any I/O error from reading or rewriting the ledger.
+    pub fn remove_promoted(&self, candidate: &PromotionCandidate) -> io::Result<usize> {
+        let mut removed = 0;
+        self.update(|records| {
+            let before = records.len();
+            records.retain(|record| !candidate.covers(record));
+            removed = before - records.len();
+        })?;
+        Ok(removed)
+    }
+
+    /// Rewrite the ledger under an exclusive lock.
+    fn update(&self, change: impl FnOnce(&mut Vec<TrustRecord>)) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .read(true)
+            .write(true)
+            .open(&self.path)?;
+        file.lock_exclusive()?;
+        let mut records = parse_records(&file);
+        change(&mut records);
+        let mut content = String::new();
+        for record in &records {
+            content.push_str(&serde_json::to_string(record).map_err(io::Error::other)?);
+            content.push('\n');
+        }
+        let result = file
+            .set_len(0)
+            .and_then(|()| io::Seek::seek(&mut file, io::SeekFrom::Start(0)))
+            .and_then(|_| file.write_all(content.as_bytes()));
+        let _ = FileExt::unlock(&file);
+        result
+    }
+}
+
+fn parse_records(file: &fs::File) -> Vec<TrustRecord> {
+    BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str(&line).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const RULE: &str = "core.git:clean-force";
+
+    fn record(repo: &str, rule: Option<&str>, command: &str, at: &str) -> TrustRecord {
+        TrustRecord {
+            approved_at: at.to_string(),
+            repo: repo.to_string(),
+            rule_id: rule.map(str::to_string),
+            command_raw: command.to_string(),
+            command_redacted: command.to_string(),
+        }
+    }
+
+    #[test]
+    fn rule_is_read_from_pending_reason() {
+        assert_eq!(
+            rule_from_reason("core.git:reset-hard - destroys uncommitted changes").as_deref(),
+            Some("core.git:reset-hard")
+        );
+        assert_eq!(rule_from_reason("Blocked by config - custom"), None);
+        assert_eq!(rule_from_reason("no separator"), None);
+    }
+
+    #[test]
+    fn repeated_command_promotes_as_exact_command() {
+        let records = vec![
+            record("/repo", Some(RULE), "git clean -fd build", "1"),
+            record("/repo", Some(RULE), "git clean -fd build", "2"),
+            record("/repo", Some(RULE), "git clean -fd build", "3"),
+            record("/other", Some(RULE), "git clean -fd build", "4"),
+        ];
+        let found = candidates(&records, "/repo", 3);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].approvals, 3);
+        assert_eq!(
+            found[0].selector,
+            PromotionSelector::ExactCommand("git clean -fd build".to_string())
+        );
+        assert_eq!(found[0].last_approved_at, "3");
+        assert!(candidates(&records, "/repo", 4).is_empty());
+    }
+
+    #[test]
+    fn varied_commands_promote_the_rule() {
+        let records = vec![
+            record("/repo", Some(RULE), "git clean -fd build", "1"),
+            record("/repo", Some(RULE), "git clean -fd dist", "2"),
+            record("/repo", Some(RULE), "git clean -fd build", "3"),
+            record("/repo", None, "make nuke", "4"),
+            record("/repo", None, "make nuke", "5"),
+            record("/repo", None, "make purge", "6"),
+        ];
+        let found = candidates(&records, "/repo", 2);
+        assert_eq!(found.len(), 2);
+        assert_eq!(
+            found[0].selector,
+            PromotionSelector::Rule("core.git:clean-force".to_string())
+        );
+        assert_eq!(
+            found[0].commands,
+            vec![
+                "git clean -fd build".to_string(),
+                "git clean -fd dist".to_string()
+            ]
+        );
+        assert_eq!(
+            found[1].selector,
+            PromotionSelector::ExactCommand("make nuke".to_string())
+        );
+    }
+
+    #[test]
+    fn ledger_records_and_removes_promoted() {
+        let dir = TempDir::new().unwrap();
+        let ledger = TrustLedger::new(dir.path().join("nested").join(LEDGER_FILE));
+        assert!(ledger.load().unwrap().is_empty());
+
+        for at in ["1", "2"] {
+            ledger
+                .record(&record("/repo", Some(RULE), "git clean -fd", at))
+                .unwrap();
+        }
+        ledger
+            .record(&record("/repo", None, "make nuke", "3"))
+            .unwrap();
+        let records = ledger.load().unwrap();
+        assert_eq!(records.len(), 3);
+
+        let candidate = candidates(&records, "/repo", 2).remove(0);
+        assert_eq!(ledger.remove_promoted(&candidate).unwrap(), 2);
+        let remaining = ledger.load().unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].command_raw, "make nuke");
+    }
+}
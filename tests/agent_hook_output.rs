@@ -364,3 +364,154 @@ fn test_hook_output_remediation_safe_alternative() {
         }
     }
 }
+
+#[test]
+fn test_hook_output_suggested_alternative_keeps_arguments() {
+    let (stdout, _stderr, _) = run_hook_mode("git push --force origin main");
+
+    let json: serde_json::Value =
+        serde_json::from_str(&stdout).expect("hook output should be valid JSON");
+
+    assert_eq!(
+        json["hookSpecificOutput"]["suggestedAlternative"],
+        "git push --force-with-lease origin main",
+        "suggestedAlternative should rewrite the flag and keep the remote/branch"
+    );
+}
+
+#[test]
+fn test_hook_output_suggested_alternative_omitted_without_rewrite() {
+    let (stdout, _stderr, _) = run_hook_mode("git stash clear");
+
+    let json: serde_json::Value =
+        serde_json::from_str(&stdout).expect("hook output should be valid JSON");
+
+    assert!(
+        json["hookSpecificOutput"]
+            .get("suggestedAlternative")
+            .is_none(),
+        "suggestedAlternative should be omitted when the rule has no rewrite"
+    );
+}
+
+#[test]
+fn test_hook_output_structured_details_are_opt_in() {
+    let (stdout, _stderr, _) = run_hook_mode("git reset --hard HEAD");
+    let json: serde_json::Value =
+        serde_json::from_str(&stdout).expect("hook output should be valid JSON");
+    assert!(json["hookSpecificOutput"].get("schemaVersion").is_none());
+    assert!(json["hookSpecificOutput"].get("details").is_none());
+
+    let mut child = Command::new(dcg_binary())
+        .env("DCG_STRUCTURED_DENY", "1")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to spawn dcg process");
+    child
+        .stdin
+        .as_mut()
+        .expect("failed to get stdin")
+        .write_all(br#"{"tool_name":"Bash","tool_input":{"command":"git reset --hard HEAD"}}"#)
+        .expect("failed to write to stdin");
+    let output = child.wait_with_output().expect("failed to wait for dcg");
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("hook output should be valid JSON");
+
+    let hook = &json["hookSpecificOutput"];
+    assert_eq!(hook["schemaVersion"], 1);
+    assert_eq!(hook["details"]["packId"], "core.git");
+    assert_eq!(hook["details"]["patternName"], "reset-hard");
+    assert_eq!(hook["details"]["severity"], hook["severity"]);
+    assert!(hook["details"]["span"]["end"].as_u64().unwrap_or(0) > 0);
+}
+
+/// Run dcg in hook mode with a raw JSON payload and extra CLI args.
+fn run_hook_raw(input: &str, args: &[&str]) -> String {
+    let mut child = Command::new(dcg_binary())
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to spawn dcg process");
+
+    child
+        .stdin
+        .as_mut()
+        .expect("failed to get stdin")
+        .write_all(input.as_bytes())
+        .expect("failed to write to stdin");
+
+    let output = child.wait_with_output().expect("failed to wait for dcg");
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn test_cursor_payload_gets_cursor_response() {
+    let stdout = run_hook_raw(
+        r#"{"hook_event_name":"beforeShellExecution","command":"git reset --hard","cwd":"/tmp"}"#,
+        &[],
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_str(&stdout).expect("hook output should be valid JSON");
+    assert_eq!(json["permission"], "deny");
+    assert!(json["userMessage"].as_str().unwrap().contains("BLOCKED"));
+    assert!(json["agentMessage"].as_str().unwrap().contains("core.git"));
+    assert_eq!(json["ruleId"], "core.git:reset-hard");
+    assert!(json.get("hookSpecificOutput").is_none());
+}
+
+#[test]
+fn test_agent_flag_selects_cursor_response() {
+    let stdout = run_hook_raw(
+        r#"{"tool_name":"Bash","tool_input":{"command":"git reset --hard"}}"#,
+        &["--agent", "cursor"],
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_str(&stdout).expect("hook output should be valid JSON");
+    assert_eq!(json["permission"], "deny");
+    assert!(json.get("hookSpecificOutput").is_none());
+}
+
+#[test]
+fn test_cursor_payload_allowed_command_is_silent() {
+    let stdout = run_hook_raw(
+        r#"{"hook_event_name":"beforeShellExecution","command":"git status","cwd":"/tmp"}"#,
+        &[],
+    );
+    assert!(stdout.trim().is_empty());
+}
+
+#[test]
+fn test_gemini_before_tool_payload_gets_gemini_response() {
+    let stdout = run_hook_raw(
+        r#"{"session_id":"s1","hook_event_name":"BeforeTool","tool_name":"run_shell_command","tool_input":{"command":"git reset --hard"}}"#,
+        &[],
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_str(&stdout).expect("hook output should be valid JSON");
+    assert_eq!(json["decision"], "deny");
+    assert!(json["reason"].as_str().unwrap().contains("core.git"));
+    assert!(json["systemMessage"].as_str().unwrap().contains("BLOCKED"));
+    assert_eq!(json["ruleId"], "core.git:reset-hard");
+}
+
+#[test]
+fn test_codex_argv_payload_gets_codex_response() {
+    let stdout = run_hook_raw(
+        r#"{"command":["bash","-lc","git reset --hard"],"cwd":"/tmp"}"#,
+        &["--agent", "codex"],
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_str(&stdout).expect("hook output should be valid JSON");
+    assert_eq!(json["decision"], "deny");
+    assert_eq!(json["ruleId"], "core.git:reset-hard");
+    assert!(json.get("systemMessage").is_none());
+    assert!(json.get("hookSpecificOutput").is_none());
+}
@@ -27,6 +27,7 @@ fn mock_deny_result(
             matched_text_preview: Some("rm -rf".to_string()),
             explanation: None,
             suggestions: &[],
+            recovery_hint: None,
         }),
         allowlist_override: None,
         effective_mode: Some(DecisionMode::Deny),
@@ -20,6 +20,8 @@ mod tests {
             keyword_matcher: None,
             safe_regex_set: None,
             safe_regex_set_is_complete: false,
+            destructive_regex_set: None,
+            critical_patterns: Vec::new(),
         }
     }
 
@@ -107,6 +107,7 @@ fn generate_category_doc(category: &str, packs: &[&Pack]) -> String {
         "cicd" => "CI/CD Packs",
         "secrets" => "Secrets Management Packs",
         "platform" => "Platform Packs",
+        "paas" => "PaaS Packs",
         "dns" => "DNS Packs",
         "email" => "Email Packs",
         "featureflags" => "Feature Flags Packs",
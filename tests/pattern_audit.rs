@@ -39,6 +39,10 @@ fn test_audit_backtracking_requirements() {
                 "rm-force-recursive-tmpdir",
                 "rm-recursive-force-tmpdir-brace",
                 "rm-force-recursive-tmpdir-brace",
+                "windows-delete-temp",
+                "windows-delete-root-home",
+                "windows-rmdir-recursive",
+                "windows-del-recursive",
             ]),
         ),
         (
@@ -65,6 +69,7 @@ fn test_audit_backtracking_requirements() {
                 "gh-actions-variable-remove",
                 "gh-actions-workflow-disable",
                 "gh-actions-run-cancel",
+                "gh-actions-workflow-run-production",
                 "gh-actions-api-delete-secrets",
                 "gh-actions-api-delete-variables",
             ]),
@@ -73,27 +78,74 @@ fn test_audit_backtracking_requirements() {
             "containers.compose",
             HashSet::from(["compose-down-no-volumes"]),
         ),
+        (
+            "database.migrations",
+            HashSet::from([
+                "migrations-status",
+                "migrations-generate",
+                "migrations-up",
+                "flyway-clean",
+            ]),
+        ),
         ("dns.generic", HashSet::from(["dns-dig-safe"])),
         ("database.mongodb", HashSet::from(["mongodump-no-drop"])),
         ("database.postgresql", HashSet::from(["pg-dump-no-clean"])),
         ("database.redis", HashSet::from(["shutdown"])),
         (
             "infrastructure.ansible",
-            HashSet::from(["playbook-all-hosts"]),
+            HashSet::from(["playbook-all-hosts", "file-absent-system-path"]),
         ),
         (
             "infrastructure.terraform",
             HashSet::from(["terraform-plan"]),
         ),
+        (
+            "kubernetes.argocd",
+            HashSet::from(["app-delete-cascade", "app-sync-prune"]),
+        ),
+        ("kubernetes.flux", HashSet::from(["uninstall"])),
         ("kubernetes.helm", HashSet::from(["uninstall", "rollback"])),
         (
             "kubernetes.kubectl",
-            HashSet::from(["delete-workload", "delete-pvc", "delete-pv"]),
+            HashSet::from([
+                "delete-workload",
+                "delete-pvc",
+                "delete-pv",
+                "delete-namespace",
+                "delete-all-all-namespaces",
+                "drain-delete-data-force",
+                "delete-crd",
+            ]),
         ),
         (
             "kubernetes.kustomize",
             HashSet::from(["kustomize-build", "kubectl-kustomize", "kubectl-delete-k"]),
         ),
+        ("kv.etcd", HashSet::from(["del-all-keys"])),
+        (
+            "paas.fly",
+            HashSet::from(["fly-apps-destroy-yes", "fly-volumes-destroy-yes"]),
+        ),
+        (
+            "paas.heroku",
+            HashSet::from(["heroku-apps-destroy-confirm", "heroku-pg-reset-confirm"]),
+        ),
+        (
+            "paas.netlify",
+            HashSet::from(["netlify-sites-delete-force", "netlify-deploy-preview"]),
+        ),
+        (
+            "paas.railway",
+            HashSet::from(["railway-delete-yes", "railway-down-yes"]),
+        ),
+        (
+            "paas.render",
+            HashSet::from(["render-services-delete-confirm"]),
+        ),
+        (
+            "paas.vercel",
+            HashSet::from(["vercel-remove-yes", "vercel-deploy-preview"]),
+        ),
         (
             "package_managers",
             HashSet::from([
@@ -103,8 +155,13 @@ fn test_audit_backtracking_requirements() {
                 "pnpm-publish",
                 "cargo-publish",
                 "poetry-publish",
+                "npm-publish-force",
             ]),
         ),
+        (
+            "package_managers.cargo",
+            HashSet::from(["cargo-publish", "cargo-yank", "cargo-clean"]),
+        ),
         (
             "platform.github",
             HashSet::from([
@@ -119,6 +176,7 @@ fn test_audit_backtracking_requirements() {
                 "gh-auth-status",
                 "gh-status",
                 "gh-api-explicit-get",
+                "gh-pr-read",
                 // Destructive patterns
                 "gh-repo-delete",
                 "gh-repo-archive",
@@ -136,11 +194,30 @@ fn test_audit_backtracking_requirements() {
                 "gh-api-delete-hook",
                 "gh-api-delete-deploy-key",
                 "gh-api-delete-release",
+                "gh-pr-close-delete-branch",
+            ]),
+        ),
+        (
+            "storage.objectstore",
+            HashSet::from([
+                "aws-s3-rm-bucket",
+                "aws-s3-rb-force",
+                "aws-s3-rm-recursive",
+                "s3cmd-del-bucket",
+                "s3cmd-rb-recursive",
+                "s3cmd-del-recursive",
+                "gsutil-rm-bucket",
+                "gsutil-rm-recursive",
+                "mc-rm-bucket",
+                "mc-rm-recursive-force",
+                "rclone-purge-bucket",
+                "rclone-delete-bucket",
+                "rclone-sync-delete",
             ]),
         ),
         (
             "system.disk",
-            HashSet::from(["fdisk-edit", "parted-modify"]),
+            HashSet::from(["fdisk-edit", "parted-modify", "windows-format"]),
         ),
         ("system.permissions", HashSet::from(["chmod-non-recursive"])),
     ]);